@@ -1,19 +1,54 @@
 //! BACnet/SC (Secure Connect) transport over WebSocket.
 //!
 //! Implements [`DataLink`] by tunnelling BACnet frames through a WebSocket
-//! connection to a BACnet/SC hub.
+//! connection to a BACnet/SC hub. [`ScListener`] provides the accept side, so a
+//! service can also act as a hub or direct-connect responder.
+//!
+//! On `wasm32` the `web` feature provides the same [`BacnetScTransport`] on top of the
+//! browser's WebSocket API, so dashboards can talk to a hub directly. [`ScListener`] and
+//! the `proxy` feature are native-only.
 
 #![allow(async_fn_in_trait)]
 
+#[cfg(all(target_arch = "wasm32", not(feature = "web")))]
+compile_error!("rustbac-bacnet-sc needs the `web` feature on wasm32");
+#[cfg(all(target_arch = "wasm32", feature = "proxy"))]
+compile_error!(
+    "the `proxy` feature is not available on wasm32; browsers apply their own proxy settings"
+);
+
+#[cfg(not(target_arch = "wasm32"))]
 use futures_util::{SinkExt, StreamExt};
 use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError};
-use std::io;
+#[cfg(not(target_arch = "wasm32"))]
 use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
-use tokio::net::lookup_host;
-use tokio::sync::{broadcast, mpsc};
-use tokio_tungstenite::connect_async;
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::io::{AsyncRead, AsyncWrite};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::net::{lookup_host, TcpStream};
+use tokio::sync::{broadcast, mpsc, watch};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::{Instant, Interval};
+#[cfg(feature = "proxy")]
+use tokio_tungstenite::client_async_tls_with_config;
+#[cfg(not(target_arch = "wasm32"))]
 use tokio_tungstenite::tungstenite::Message;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+#[cfg(feature = "proxy")]
+mod proxy;
+#[cfg(not(target_arch = "wasm32"))]
+mod server;
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod web;
+
+#[cfg(feature = "proxy")]
+pub use proxy::{ProxyCredentials, ScProxy};
+#[cfg(not(target_arch = "wasm32"))]
+pub use server::{ScListener, ScServerConfig, ScTlsServerConfig};
 
 const CHANNEL_DEPTH: usize = 128;
 /// Broadcast channel capacity for inbound frames.
@@ -24,6 +59,71 @@ const CHANNEL_DEPTH: usize = 128;
 /// recover automatically on the next iteration.
 const BROADCAST_DEPTH: usize = 64;
 
+/// Connection options for [`BacnetScTransport::connect_with_options`].
+///
+/// By default the transport pings the hub every 30 s, treats the connection as dead when
+/// nothing (data or pong) has been received for 90 s, and reconnects 1 s after losing it.
+/// Browsers do not expose websocket pings, so the `web` backend ignores the keepalive and
+/// liveness settings and relies on the socket's close and error events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScConnectOptions {
+    /// Interval between websocket pings. `None` disables keepalive and dead-peer detection.
+    pub keepalive_interval: Option<Duration>,
+    /// How long the peer may stay silent before the connection is considered dead.
+    pub liveness_timeout: Duration,
+    /// Delay before reconnecting after the connection is lost. `None` disables reconnection.
+    pub reconnect_delay: Option<Duration>,
+    /// Proxy used to reach the endpoint. `None` connects directly.
+    #[cfg(feature = "proxy")]
+    pub proxy: Option<ScProxy>,
+}
+
+impl Default for ScConnectOptions {
+    fn default() -> Self {
+        Self {
+            keepalive_interval: Some(Duration::from_secs(30)),
+            liveness_timeout: Duration::from_secs(90),
+            reconnect_delay: Some(Duration::from_secs(1)),
+            #[cfg(feature = "proxy")]
+            proxy: None,
+        }
+    }
+}
+
+impl ScConnectOptions {
+    pub fn with_keepalive_interval(mut self, interval: Option<Duration>) -> Self {
+        self.keepalive_interval = interval;
+        self
+    }
+
+    pub fn with_liveness_timeout(mut self, timeout: Duration) -> Self {
+        self.liveness_timeout = timeout;
+        self
+    }
+
+    pub fn with_reconnect_delay(mut self, delay: Option<Duration>) -> Self {
+        self.reconnect_delay = delay;
+        self
+    }
+
+    #[cfg(feature = "proxy")]
+    pub fn with_proxy(mut self, proxy: Option<ScProxy>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+}
+
+/// Connection state of a [`BacnetScTransport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScConnectionState {
+    /// The websocket is open and the peer is responsive.
+    Connected,
+    /// The connection was lost and a reconnect is pending.
+    Reconnecting,
+    /// The connection was lost and will not be re-established.
+    Closed,
+}
+
 /// A [`DataLink`] implementation that transports BACnet frames over a
 /// WebSocket connection (BACnet/SC).
 ///
@@ -35,6 +135,14 @@ const BROADCAST_DEPTH: usize = 64;
 /// running in the same task via `tokio::select!`), inbound frames are
 /// published through a [`broadcast`] channel so that **every** concurrent
 /// caller sees every frame and discards what it doesn't need.
+///
+/// ## Connection loss
+///
+/// A background task owns the websocket, sends keepalive pings, and reconnects when the
+/// hub stops responding or closes the connection (see [`ScConnectOptions`]). While the
+/// connection is down `send()` fails with [`DataLinkError::Disconnected`]; a `recv()`
+/// that is waiting when the connection drops returns the same error, and later calls wait
+/// for the reconnect.
 #[derive(Clone)]
 pub struct BacnetScTransport {
     endpoint: String,
@@ -42,6 +150,7 @@ pub struct BacnetScTransport {
     outbound: mpsc::Sender<Vec<u8>>,
     /// Broadcast sender; each `recv()` call subscribes to get its own stream.
     inbound: Arc<broadcast::Sender<Vec<u8>>>,
+    state: watch::Receiver<ScConnectionState>,
 }
 
 impl std::fmt::Debug for BacnetScTransport {
@@ -49,61 +158,99 @@ impl std::fmt::Debug for BacnetScTransport {
         f.debug_struct("BacnetScTransport")
             .field("endpoint", &self.endpoint)
             .field("peer_address", &self.peer_address)
+            .field("state", &*self.state.borrow())
             .finish()
     }
 }
 
 impl BacnetScTransport {
+    /// Connects to `endpoint` (`ws://` or `wss://`) with the default [`ScConnectOptions`].
     pub async fn connect(endpoint: impl Into<String>) -> Result<Self, DataLinkError> {
+        Self::connect_with_options(endpoint, ScConnectOptions::default()).await
+    }
+
+    /// Connects to `endpoint` with explicit keepalive and reconnection options.
+    ///
+    /// The initial connection attempt is not retried; its failure is returned directly.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn connect_with_options(
+        endpoint: impl Into<String>,
+        options: ScConnectOptions,
+    ) -> Result<Self, DataLinkError> {
         let endpoint = endpoint.into();
-        let peer_address = resolve_peer_address(&endpoint).await?;
+        let peer_address = match resolve_peer_address(&endpoint).await {
+            Ok(address) => address,
+            // A hub behind a proxy is often not resolvable locally; identify it by the
+            // proxy address instead.
+            #[cfg(feature = "proxy")]
+            Err(_) if options.proxy.is_some() => {
+                parse_endpoint(&endpoint)?;
+                let proxy = options.proxy.as_ref().expect("checked above");
+                proxy::resolve_proxy_address(proxy).await?
+            }
+            Err(err) => return Err(err),
+        };
 
-        let (socket, _) = connect_async(endpoint.as_str())
-            .await
-            .map_err(|err| ws_io_error(io::ErrorKind::ConnectionRefused, err))?;
-        let (mut writer, mut reader) = socket.split();
+        let socket = open_websocket(&endpoint, &options).await?;
 
-        let (outbound_tx, mut outbound_rx) = mpsc::channel::<Vec<u8>>(CHANNEL_DEPTH);
+        let (outbound_tx, outbound_rx) = mpsc::channel::<Vec<u8>>(CHANNEL_DEPTH);
         let (inbound_tx, _) = broadcast::channel::<Vec<u8>>(BROADCAST_DEPTH);
         let inbound_tx = Arc::new(inbound_tx);
-        let inbound_tx_clone = inbound_tx.clone();
+        let (state_tx, state_rx) = watch::channel(ScConnectionState::Connected);
 
-        tokio::spawn(async move {
-            while let Some(frame) = outbound_rx.recv().await {
-                if writer.send(Message::Binary(frame)).await.is_err() {
-                    return;
-                }
-            }
-            let _ = writer.close().await;
-        });
+        tokio::spawn(supervise(
+            endpoint.clone(),
+            options,
+            socket,
+            outbound_rx,
+            inbound_tx.clone(),
+            state_tx,
+        ));
 
-        tokio::spawn(async move {
-            while let Some(next) = reader.next().await {
-                let message = match next {
-                    Ok(message) => message,
-                    Err(_) => break,
-                };
+        Ok(Self {
+            endpoint,
+            peer_address,
+            outbound: outbound_tx,
+            inbound: inbound_tx,
+            state: state_rx,
+        })
+    }
 
-                match message {
-                    Message::Binary(payload) => {
-                        // If no receivers are subscribed yet the send fails
-                        // silently — the caller will wait and retry.
-                        let _ = inbound_tx_clone.send(payload.to_vec());
-                    }
-                    Message::Text(text) => {
-                        log::debug!("ignoring non-binary BACnet/SC websocket frame: {text}");
-                    }
-                    _ => {}
-                }
+    /// Wraps a websocket accepted by [`ScListener`]. Inbound connections are never
+    /// re-established by this side, so the transport closes when the session ends.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn from_accepted<S>(
+        ws: WebSocketStream<S>,
+        endpoint: String,
+        peer_address: DataLinkAddress,
+        options: ScConnectOptions,
+    ) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let (outbound_tx, mut outbound_rx) = mpsc::channel::<Vec<u8>>(CHANNEL_DEPTH);
+        let (inbound_tx, _) = broadcast::channel::<Vec<u8>>(BROADCAST_DEPTH);
+        let inbound_tx = Arc::new(inbound_tx);
+        let (state_tx, state_rx) = watch::channel(ScConnectionState::Connected);
+
+        let inbound = inbound_tx.clone();
+        let log_endpoint = endpoint.clone();
+        tokio::spawn(async move {
+            if let SessionEnd::Lost(reason) =
+                run_session(ws, &options, &mut outbound_rx, &inbound).await
+            {
+                log::info!("BACnet/SC node {log_endpoint} disconnected: {reason}");
             }
+            state_tx.send_replace(ScConnectionState::Closed);
         });
 
-        Ok(Self {
+        Self {
             endpoint,
             peer_address,
             outbound: outbound_tx,
             inbound: inbound_tx,
-        })
+            state: state_rx,
+        }
     }
 
     pub fn endpoint(&self) -> &str {
@@ -113,16 +260,27 @@ impl BacnetScTransport {
     pub fn peer_address(&self) -> DataLinkAddress {
         self.peer_address
     }
+
+    /// Returns the current connection state.
+    pub fn state(&self) -> ScConnectionState {
+        *self.state.borrow()
+    }
+
+    /// Returns `true` while the websocket is open and the peer is responsive.
+    pub fn is_connected(&self) -> bool {
+        self.state() == ScConnectionState::Connected
+    }
 }
 
 impl DataLink for BacnetScTransport {
     async fn send(&self, _address: DataLinkAddress, payload: &[u8]) -> Result<(), DataLinkError> {
-        self.outbound.send(payload.to_vec()).await.map_err(|_| {
-            DataLinkError::Io(io::Error::new(
-                io::ErrorKind::BrokenPipe,
-                "BACnet/SC websocket sender task stopped",
-            ))
-        })
+        if !self.is_connected() {
+            return Err(DataLinkError::Disconnected);
+        }
+        self.outbound
+            .send(payload.to_vec())
+            .await
+            .map_err(|_| DataLinkError::Disconnected)
     }
 
     async fn recv(&self, buf: &mut [u8]) -> Result<(usize, DataLinkAddress), DataLinkError> {
@@ -130,92 +288,260 @@ impl DataLink for BacnetScTransport {
         // point can be missed.  Frames published before subscribe() are not
         // delivered, but the caller's retry loop handles that gracefully.
         let mut rx = self.inbound.subscribe();
+        let mut state = self.state.clone();
         loop {
-            match rx.recv().await {
-                Ok(payload) => {
-                    if payload.len() > buf.len() {
-                        return Err(DataLinkError::FrameTooLarge);
+            let current = *state.borrow_and_update();
+            match current {
+                ScConnectionState::Closed => return Err(DataLinkError::Disconnected),
+                ScConnectionState::Reconnecting => {
+                    if state.changed().await.is_err() {
+                        return Err(DataLinkError::Disconnected);
                     }
-                    buf[..payload.len()].copy_from_slice(&payload);
-                    return Ok((payload.len(), self.peer_address));
-                }
-                Err(broadcast::error::RecvError::Lagged(n)) => {
-                    // This subscriber fell behind; some frames were dropped.
-                    // Skip past them and try again — the request loop will
-                    // time out if the frame we need never arrives.
-                    log::debug!("BACnet/SC recv lagged by {n} frames; skipping");
                     continue;
                 }
-                Err(broadcast::error::RecvError::Closed) => {
-                    return Err(DataLinkError::Io(io::Error::new(
-                        io::ErrorKind::UnexpectedEof,
-                        "BACnet/SC websocket receiver task stopped",
-                    )));
+                ScConnectionState::Connected => {}
+            }
+
+            tokio::select! {
+                next = rx.recv() => match next {
+                    Ok(payload) => {
+                        if payload.len() > buf.len() {
+                            return Err(DataLinkError::FrameTooLarge);
+                        }
+                        buf[..payload.len()].copy_from_slice(&payload);
+                        return Ok((payload.len(), self.peer_address));
+                    }
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        // This subscriber fell behind; some frames were dropped.
+                        // Skip past them and try again — the request loop will
+                        // time out if the frame we need never arrives.
+                        log::debug!("BACnet/SC recv lagged by {n} frames; skipping");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err(DataLinkError::Disconnected);
+                    }
+                },
+                changed = state.changed() => {
+                    if changed.is_err() || *state.borrow() != ScConnectionState::Connected {
+                        return Err(DataLinkError::Disconnected);
+                    }
                 }
             }
         }
     }
 }
 
-fn ws_io_error(kind: io::ErrorKind, err: impl std::fmt::Display) -> DataLinkError {
-    DataLinkError::Io(io::Error::new(
-        kind,
-        format!("BACnet/SC websocket error: {err}"),
-    ))
+/// Why a websocket session ended.
+enum SessionEnd {
+    /// Every transport handle was dropped; shut down for good.
+    OwnerDropped,
+    /// The connection failed or the peer went silent.
+    Lost(String),
 }
 
-async fn resolve_peer_address(endpoint: &str) -> Result<DataLinkAddress, DataLinkError> {
-    let (scheme, remainder) = endpoint.split_once("://").ok_or_else(|| {
-        DataLinkError::Io(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!("invalid BACnet/SC endpoint '{endpoint}'"),
-        ))
-    })?;
+/// Owns the websocket for the lifetime of the transport, reconnecting as configured.
+#[cfg(not(target_arch = "wasm32"))]
+async fn supervise(
+    endpoint: String,
+    options: ScConnectOptions,
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    mut outbound: mpsc::Receiver<Vec<u8>>,
+    inbound: Arc<broadcast::Sender<Vec<u8>>>,
+    state: watch::Sender<ScConnectionState>,
+) {
+    let mut socket = Some(socket);
+    loop {
+        let ws = match socket.take() {
+            Some(ws) => ws,
+            None => {
+                let Some(delay) = options.reconnect_delay else {
+                    break;
+                };
+                // Frames queued before the link went down are stale; drop them.
+                while outbound.try_recv().is_ok() {}
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    frame = outbound.recv() => {
+                        if frame.is_none() {
+                            break;
+                        }
+                        continue;
+                    }
+                }
+                match open_websocket(&endpoint, &options).await {
+                    Ok(ws) => {
+                        log::info!("BACnet/SC reconnected to {endpoint}");
+                        ws
+                    }
+                    Err(err) => {
+                        log::debug!("BACnet/SC reconnect to {endpoint} failed: {err}");
+                        continue;
+                    }
+                }
+            }
+        };
+
+        state.send_replace(ScConnectionState::Connected);
+        match run_session(ws, &options, &mut outbound, &inbound).await {
+            SessionEnd::OwnerDropped => break,
+            SessionEnd::Lost(reason) => {
+                log::warn!("BACnet/SC connection to {endpoint} lost: {reason}");
+                state.send_replace(ScConnectionState::Reconnecting);
+            }
+        }
+    }
+    state.send_replace(ScConnectionState::Closed);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn run_session<S>(
+    ws: WebSocketStream<S>,
+    options: &ScConnectOptions,
+    outbound: &mut mpsc::Receiver<Vec<u8>>,
+    inbound: &broadcast::Sender<Vec<u8>>,
+) -> SessionEnd
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (mut writer, mut reader) = ws.split();
+    let mut last_seen = Instant::now();
+    let mut keepalive = options
+        .keepalive_interval
+        .map(|period| tokio::time::interval_at(Instant::now() + period, period));
+
+    loop {
+        tokio::select! {
+            frame = outbound.recv() => {
+                let Some(frame) = frame else {
+                    let _ = writer.close().await;
+                    return SessionEnd::OwnerDropped;
+                };
+                if let Err(err) = writer.send(Message::Binary(frame)).await {
+                    return SessionEnd::Lost(err.to_string());
+                }
+            }
+            next = reader.next() => {
+                let message = match next {
+                    Some(Ok(message)) => message,
+                    Some(Err(err)) => return SessionEnd::Lost(err.to_string()),
+                    None => return SessionEnd::Lost("connection closed".into()),
+                };
+                last_seen = Instant::now();
+                match message {
+                    Message::Binary(payload) => {
+                        // If no receivers are subscribed yet the send fails
+                        // silently — the caller will wait and retry.
+                        let _ = inbound.send(payload.to_vec());
+                    }
+                    Message::Text(text) => {
+                        log::debug!("ignoring non-binary BACnet/SC websocket frame: {text}");
+                    }
+                    Message::Ping(_) => {
+                        // tungstenite queues the pong; flushing sends it.
+                        let _ = writer.flush().await;
+                    }
+                    Message::Close(_) => return SessionEnd::Lost("closed by peer".into()),
+                    _ => {}
+                }
+            }
+            _ = tick(&mut keepalive) => {
+                if last_seen.elapsed() >= options.liveness_timeout {
+                    return SessionEnd::Lost(format!(
+                        "no response from peer for {:?}",
+                        last_seen.elapsed()
+                    ));
+                }
+                if let Err(err) = writer.send(Message::Ping(Vec::new())).await {
+                    return SessionEnd::Lost(err.to_string());
+                }
+            }
+        }
+    }
+}
+
+/// Waits for the next keepalive tick, or forever when keepalive is disabled.
+#[cfg(not(target_arch = "wasm32"))]
+async fn tick(keepalive: &mut Option<Interval>) {
+    match keepalive {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Opens the websocket to `endpoint`, through the configured proxy if any.
+#[cfg(not(target_arch = "wasm32"))]
+async fn open_websocket(
+    endpoint: &str,
+    #[cfg_attr(not(feature = "proxy"), allow(unused_variables))] options: &ScConnectOptions,
+) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>, DataLinkError> {
+    #[cfg(feature = "proxy")]
+    if let Some(proxy) = &options.proxy {
+        let (host, port) = parse_endpoint(endpoint)?;
+        let stream = proxy::tunnel(proxy, &host, port).await?;
+        let (socket, _) = client_async_tls_with_config(endpoint, stream, None, None)
+            .await
+            .map_err(|err| connect_error(endpoint, err))?;
+        return Ok(socket);
+    }
+
+    let (socket, _) = connect_async(endpoint)
+        .await
+        .map_err(|err| connect_error(endpoint, err))?;
+    Ok(socket)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn connect_error(endpoint: &str, err: tokio_tungstenite::tungstenite::Error) -> DataLinkError {
+    DataLinkError::Connect {
+        target: endpoint.to_string(),
+        source: Box::new(err),
+    }
+}
+
+/// Splits a `ws://` / `wss://` endpoint into host and port (default 80 / 443).
+fn parse_endpoint(endpoint: &str) -> Result<(String, u16), DataLinkError> {
+    let (scheme, remainder) = endpoint
+        .split_once("://")
+        .ok_or_else(|| DataLinkError::Config(format!("invalid BACnet/SC endpoint '{endpoint}'")))?;
     let default_port = match scheme {
         "ws" => 80,
         "wss" => 443,
         _ => {
-            return Err(DataLinkError::Io(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!("unsupported BACnet/SC endpoint scheme '{scheme}'"),
+            return Err(DataLinkError::Config(format!(
+                "unsupported BACnet/SC endpoint scheme '{scheme}'"
             )))
         }
     };
     let authority = remainder.split('/').next().unwrap_or_default();
     if authority.is_empty() {
-        return Err(DataLinkError::Io(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!("BACnet/SC endpoint '{endpoint}' is missing host"),
+        return Err(DataLinkError::Config(format!(
+            "BACnet/SC endpoint '{endpoint}' is missing host"
         )));
     }
     let authority = authority.rsplit('@').next().unwrap_or(authority);
     if authority.is_empty() {
-        return Err(DataLinkError::Io(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!("BACnet/SC endpoint '{endpoint}' is missing host"),
+        return Err(DataLinkError::Config(format!(
+            "BACnet/SC endpoint '{endpoint}' is missing host"
         )));
     }
 
     let (host, port) = if let Some(rest) = authority.strip_prefix('[') {
         let (ipv6_host, suffix) = rest.split_once(']').ok_or_else(|| {
-            DataLinkError::Io(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!("invalid IPv6 host in BACnet/SC endpoint '{endpoint}'"),
+            DataLinkError::Config(format!(
+                "invalid IPv6 host in BACnet/SC endpoint '{endpoint}'"
             ))
         })?;
         let port = if suffix.is_empty() {
             default_port
         } else if let Some(raw_port) = suffix.strip_prefix(':') {
             raw_port.parse::<u16>().map_err(|_| {
-                DataLinkError::Io(io::Error::new(
-                    io::ErrorKind::InvalidInput,
-                    format!("invalid BACnet/SC endpoint port in '{endpoint}'"),
-                ))
+                DataLinkError::Config(format!("invalid BACnet/SC endpoint port in '{endpoint}'"))
             })?
         } else {
-            return Err(DataLinkError::Io(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!("invalid BACnet/SC endpoint authority '{authority}'"),
+            return Err(DataLinkError::Config(format!(
+                "invalid BACnet/SC endpoint authority '{authority}'"
             )));
         };
         (ipv6_host.to_string(), port)
@@ -223,9 +549,8 @@ async fn resolve_peer_address(endpoint: &str) -> Result<DataLinkAddress, DataLin
         match authority.rsplit_once(':') {
             Some((host, raw_port)) if !host.is_empty() && !raw_port.is_empty() => {
                 let port = raw_port.parse::<u16>().map_err(|_| {
-                    DataLinkError::Io(io::Error::new(
-                        io::ErrorKind::InvalidInput,
-                        format!("invalid BACnet/SC endpoint port in '{endpoint}'"),
+                    DataLinkError::Config(format!(
+                        "invalid BACnet/SC endpoint port in '{endpoint}'"
                     ))
                 })?;
                 (host.to_string(), port)
@@ -233,7 +558,12 @@ async fn resolve_peer_address(endpoint: &str) -> Result<DataLinkAddress, DataLin
             _ => (authority.to_string(), default_port),
         }
     };
+    Ok((host, port))
+}
 
+#[cfg(not(target_arch = "wasm32"))]
+async fn resolve_peer_address(endpoint: &str) -> Result<DataLinkAddress, DataLinkError> {
+    let (host, port) = parse_endpoint(endpoint)?;
     if let Ok(ip) = host.parse::<IpAddr>() {
         return Ok(DataLinkAddress::Ip(SocketAddr::new(ip, port)));
     }
@@ -241,17 +571,18 @@ async fn resolve_peer_address(endpoint: &str) -> Result<DataLinkAddress, DataLin
     let mut addrs = lookup_host((host.as_str(), port))
         .await
         .map_err(DataLinkError::Io)?;
-    addrs.next().map(DataLinkAddress::Ip).ok_or_else(|| {
-        DataLinkError::Io(io::Error::new(
-            io::ErrorKind::NotFound,
-            format!("unable to resolve BACnet/SC host '{host}'"),
-        ))
-    })
+    addrs
+        .next()
+        .map(DataLinkAddress::Ip)
+        .ok_or_else(|| DataLinkError::Connect {
+            target: host.clone(),
+            source: "host name did not resolve to any address".into(),
+        })
 }
 
-#[cfg(test)]
+#[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
-    use super::BacnetScTransport;
+    use super::{BacnetScTransport, ScConnectOptions, ScConnectionState};
     use futures_util::{SinkExt, StreamExt};
     use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError};
     use std::net::SocketAddr;
@@ -260,7 +591,7 @@ mod tests {
     use tokio_tungstenite::accept_async;
     use tokio_tungstenite::tungstenite::Message;
 
-    async fn spawn_echo_server() -> (SocketAddr, tokio::task::JoinHandle<()>) {
+    pub(crate) async fn spawn_echo_server() -> (SocketAddr, tokio::task::JoinHandle<()>) {
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let addr = listener.local_addr().unwrap();
         let task = tokio::spawn(async move {
@@ -345,6 +676,139 @@ mod tests {
     #[tokio::test]
     async fn connect_rejects_invalid_endpoint() {
         let err = BacnetScTransport::connect("not a url").await.unwrap_err();
-        assert!(matches!(err, DataLinkError::Io(_)));
+        assert!(matches!(err, DataLinkError::Config(_)));
+    }
+
+    fn fast_options() -> ScConnectOptions {
+        ScConnectOptions::default()
+            .with_keepalive_interval(Some(Duration::from_millis(50)))
+            .with_liveness_timeout(Duration::from_millis(200))
+            .with_reconnect_delay(Some(Duration::from_millis(20)))
+    }
+
+    async fn wait_for_state(transport: &BacnetScTransport, state: ScConnectionState) {
+        timeout(Duration::from_secs(2), async {
+            while transport.state() != state {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap_or_else(|_| panic!("transport never reached {state:?}"));
+    }
+
+    #[tokio::test]
+    async fn silent_peer_is_detected_as_dead() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        // Completes the handshake but never reads again, so pings go unanswered.
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let _ws = accept_async(stream).await.unwrap();
+            std::future::pending::<()>().await;
+        });
+
+        let transport = BacnetScTransport::connect_with_options(
+            format!("ws://{addr}/hub"),
+            fast_options().with_reconnect_delay(None),
+        )
+        .await
+        .unwrap();
+        assert!(transport.is_connected());
+
+        let mut out = [0u8; 16];
+        let err = timeout(Duration::from_secs(2), transport.recv(&mut out))
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(err, DataLinkError::Disconnected));
+        wait_for_state(&transport, ScConnectionState::Closed).await;
+
+        let err = transport
+            .send(DataLinkAddress::Ip(addr), &[1])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DataLinkError::Disconnected));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn responsive_peer_stays_connected() {
+        let (addr, server) = spawn_echo_server().await;
+        let transport =
+            BacnetScTransport::connect_with_options(format!("ws://{addr}/hub"), fast_options())
+                .await
+                .unwrap();
+
+        // Several liveness timeouts pass; pongs keep the connection alive.
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        assert!(transport.is_connected());
+
+        transport
+            .send(DataLinkAddress::Ip(addr), &[5, 6])
+            .await
+            .unwrap();
+        let mut out = [0u8; 16];
+        let (n, _) = timeout(Duration::from_secs(1), transport.recv(&mut out))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&out[..n], &[5, 6]);
+
+        drop(transport);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn reconnects_after_peer_closes() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            // First connection: close immediately.
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(stream).await.unwrap();
+            ws.close(None).await.unwrap();
+            while ws.next().await.is_some() {}
+
+            // Second connection: echo binary frames.
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(stream).await.unwrap();
+            while let Some(Ok(msg)) = ws.next().await {
+                if let Message::Binary(payload) = msg {
+                    ws.send(Message::Binary(payload)).await.unwrap();
+                }
+            }
+        });
+
+        let transport =
+            BacnetScTransport::connect_with_options(format!("ws://{addr}/hub"), fast_options())
+                .await
+                .unwrap();
+
+        // The close is observed and the transport comes back on its own; frames sent
+        // while it is down are rejected.
+        let mut out = [0u8; 16];
+        let n = timeout(Duration::from_secs(2), async {
+            loop {
+                if transport
+                    .send(DataLinkAddress::Ip(addr), &[7, 7])
+                    .await
+                    .is_ok()
+                {
+                    if let Ok(Ok((n, _))) =
+                        timeout(Duration::from_millis(100), transport.recv(&mut out)).await
+                    {
+                        return n;
+                    }
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(&out[..n], &[7, 7]);
+
+        drop(transport);
+        server.abort();
     }
 }
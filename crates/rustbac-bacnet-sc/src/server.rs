@@ -0,0 +1,472 @@
+//! Accept side of BACnet/SC: listen for inbound websocket connections from nodes.
+
+use crate::{BacnetScTransport, ScConnectOptions};
+use rustbac_datalink::{DataLinkAddress, DataLinkError};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::RootCertStore;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+use tokio_rustls::TlsAcceptor;
+use tokio_tungstenite::accept_async;
+
+/// TLS settings for an [`ScListener`].
+#[derive(Clone)]
+pub struct ScTlsServerConfig {
+    config: Arc<rustls::ServerConfig>,
+}
+
+impl std::fmt::Debug for ScTlsServerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScTlsServerConfig").finish_non_exhaustive()
+    }
+}
+
+impl ScTlsServerConfig {
+    /// Builds a TLS configuration from PEM-encoded material.
+    ///
+    /// `cert_chain_pem` holds the server certificate followed by any intermediates, and
+    /// `private_key_pem` its private key. When `client_ca_pem` is given, connecting nodes
+    /// must present a certificate issued by one of those CAs (mutual TLS, as BACnet/SC
+    /// requires); otherwise client certificates are not requested.
+    pub fn from_pem(
+        cert_chain_pem: &[u8],
+        private_key_pem: &[u8],
+        client_ca_pem: Option<&[u8]>,
+    ) -> Result<Self, DataLinkError> {
+        let cert_chain = CertificateDer::pem_slice_iter(cert_chain_pem)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| tls_config_error(format!("invalid certificate chain: {err}")))?;
+        if cert_chain.is_empty() {
+            return Err(tls_config_error("certificate chain is empty"));
+        }
+        let private_key = PrivateKeyDer::from_pem_slice(private_key_pem)
+            .map_err(|err| tls_config_error(format!("invalid private key: {err}")))?;
+
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let builder = rustls::ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(tls_config_error)?;
+        let builder = match client_ca_pem {
+            Some(pem) => {
+                let mut roots = RootCertStore::empty();
+                for cert in CertificateDer::pem_slice_iter(pem) {
+                    let cert = cert.map_err(|err| {
+                        tls_config_error(format!("invalid client CA certificate: {err}"))
+                    })?;
+                    roots.add(cert).map_err(tls_config_error)?;
+                }
+                let verifier =
+                    WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                        .build()
+                        .map_err(tls_config_error)?;
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+        let config = builder
+            .with_single_cert(cert_chain, private_key)
+            .map_err(tls_config_error)?;
+        Ok(Self::from_rustls(Arc::new(config)))
+    }
+
+    /// Uses a caller-built rustls configuration as-is.
+    pub fn from_rustls(config: Arc<rustls::ServerConfig>) -> Self {
+        Self { config }
+    }
+}
+
+/// Configuration for an [`ScListener`].
+#[derive(Debug, Clone)]
+pub struct ScServerConfig {
+    /// TLS settings. `None` accepts plain `ws://` connections.
+    pub tls: Option<ScTlsServerConfig>,
+    /// Keepalive and liveness settings applied to every accepted connection.
+    /// `reconnect_delay` is ignored; accepted connections are never re-established.
+    pub options: ScConnectOptions,
+    /// Maximum time for the TLS and websocket handshakes of one connection.
+    pub handshake_timeout: Duration,
+    /// Most connections handshaking or waiting for [`ScListener::accept`] at once; further
+    /// nodes wait in the TCP backlog. At least 1.
+    pub max_pending_handshakes: usize,
+}
+
+impl Default for ScServerConfig {
+    fn default() -> Self {
+        Self {
+            tls: None,
+            options: ScConnectOptions::default(),
+            handshake_timeout: Duration::from_secs(10),
+            max_pending_handshakes: 64,
+        }
+    }
+}
+
+impl ScServerConfig {
+    pub fn with_tls(mut self, tls: ScTlsServerConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    pub fn with_options(mut self, options: ScConnectOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn with_handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.handshake_timeout = timeout;
+        self
+    }
+
+    pub fn with_max_pending_handshakes(mut self, handshakes: usize) -> Self {
+        self.max_pending_handshakes = handshakes;
+        self
+    }
+}
+
+/// Listens for inbound BACnet/SC websocket connections.
+///
+/// Each accepted node is returned as its own [`BacnetScTransport`], which can be wrapped
+/// in a `BacnetClient` or `BacnetServer` like an outbound connection. The transport's
+/// peer address is the node's socket address and it closes for good when the node
+/// disconnects.
+///
+/// A background task accepts TCP connections and runs each node's handshakes in a task
+/// of its own, so a slow or stalled node does not hold up the others. Dropping the
+/// listener closes the socket.
+pub struct ScListener {
+    local_addr: SocketAddr,
+    tls: bool,
+    accepted: Mutex<mpsc::Receiver<Result<BacnetScTransport, DataLinkError>>>,
+    accept_task: JoinHandle<()>,
+}
+
+impl std::fmt::Debug for ScListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScListener")
+            .field("local_addr", &self.local_addr)
+            .field("tls", &self.tls)
+            .finish()
+    }
+}
+
+impl ScListener {
+    /// Binds a listener on `addr` and starts accepting nodes.
+    pub async fn bind(
+        addr: impl ToSocketAddrs,
+        config: ScServerConfig,
+    ) -> Result<Self, DataLinkError> {
+        let listener = TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        let acceptor = config
+            .tls
+            .as_ref()
+            .map(|tls| TlsAcceptor::from(tls.config.clone()));
+        let (tx, rx) = mpsc::channel(config.max_pending_handshakes.max(1));
+        Ok(Self {
+            local_addr,
+            tls: acceptor.is_some(),
+            accepted: Mutex::new(rx),
+            accept_task: tokio::spawn(accept_loop(listener, acceptor, config, tx)),
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, DataLinkError> {
+        Ok(self.local_addr)
+    }
+
+    /// Accepts the next node whose handshakes have finished.
+    ///
+    /// Handshakes are bounded by [`ScServerConfig::handshake_timeout`]. A node that fails
+    /// the TLS handshake (e.g. an untrusted client certificate) or the websocket upgrade
+    /// yields an error; the listener stays usable, so callers typically log the error and
+    /// call `accept()` again.
+    pub async fn accept(&self) -> Result<BacnetScTransport, DataLinkError> {
+        self.accepted
+            .lock()
+            .await
+            .recv()
+            .await
+            .unwrap_or(Err(DataLinkError::Disconnected))
+    }
+}
+
+impl Drop for ScListener {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+    }
+}
+
+/// Accepts TCP connections while a slot is free for their result, handshaking each in
+/// its own task.
+async fn accept_loop(
+    listener: TcpListener,
+    acceptor: Option<TlsAcceptor>,
+    config: ScServerConfig,
+    tx: mpsc::Sender<Result<BacnetScTransport, DataLinkError>>,
+) {
+    loop {
+        let Ok(slot) = tx.clone().reserve_owned().await else {
+            return;
+        };
+        match listener.accept().await {
+            Ok((stream, peer)) => {
+                let acceptor = acceptor.clone();
+                let handshake_timeout = config.handshake_timeout;
+                let options = config.options.clone();
+                tokio::spawn(async move {
+                    slot.send(handshake(stream, peer, acceptor, handshake_timeout, options).await);
+                });
+            }
+            Err(err) => {
+                slot.send(Err(err.into()));
+            }
+        }
+    }
+}
+
+async fn handshake(
+    stream: TcpStream,
+    peer: SocketAddr,
+    acceptor: Option<TlsAcceptor>,
+    handshake_timeout: Duration,
+    options: ScConnectOptions,
+) -> Result<BacnetScTransport, DataLinkError> {
+    let _ = stream.set_nodelay(true);
+    let peer_address = DataLinkAddress::Ip(peer);
+
+    match acceptor {
+        Some(acceptor) => {
+            let tls = tokio::time::timeout(handshake_timeout, acceptor.accept(stream))
+                .await
+                .map_err(|_| handshake_timeout_error(peer, "TLS"))?
+                .map_err(|err| handshake_error(peer, err))?;
+            let ws = tokio::time::timeout(handshake_timeout, accept_async(tls))
+                .await
+                .map_err(|_| handshake_timeout_error(peer, "websocket"))?
+                .map_err(|err| handshake_error(peer, err))?;
+            Ok(BacnetScTransport::from_accepted(
+                ws,
+                format!("wss://{peer}"),
+                peer_address,
+                options,
+            ))
+        }
+        None => {
+            let ws = tokio::time::timeout(handshake_timeout, accept_async(stream))
+                .await
+                .map_err(|_| handshake_timeout_error(peer, "websocket"))?
+                .map_err(|err| handshake_error(peer, err))?;
+            Ok(BacnetScTransport::from_accepted(
+                ws,
+                format!("ws://{peer}"),
+                peer_address,
+                options,
+            ))
+        }
+    }
+}
+
+fn tls_config_error(err: impl std::fmt::Display) -> DataLinkError {
+    DataLinkError::Config(format!("BACnet/SC TLS: {err}"))
+}
+
+fn handshake_error(
+    peer: SocketAddr,
+    err: impl std::error::Error + Send + Sync + 'static,
+) -> DataLinkError {
+    DataLinkError::Connect {
+        target: peer.to_string(),
+        source: Box::new(err),
+    }
+}
+
+fn handshake_timeout_error(peer: SocketAddr, stage: &str) -> DataLinkError {
+    handshake_error(
+        peer,
+        io::Error::new(
+            io::ErrorKind::TimedOut,
+            format!("{stage} handshake timed out"),
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ScListener, ScServerConfig, ScTlsServerConfig};
+    use crate::{BacnetScTransport, ScConnectionState};
+    use futures_util::{SinkExt, StreamExt};
+    use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError};
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+    use std::sync::Arc;
+    use tokio::time::{timeout, Duration};
+    use tokio_tungstenite::tungstenite::Message;
+    use tokio_tungstenite::{connect_async_tls_with_config, Connector};
+
+    const CA: &[u8] = include_bytes!("../tests/certs/ca.pem");
+    const HUB_CERT: &[u8] = include_bytes!("../tests/certs/hub.pem");
+    const HUB_KEY: &[u8] = include_bytes!("../tests/certs/hub.key");
+    const NODE_CERT: &[u8] = include_bytes!("../tests/certs/node.pem");
+    const NODE_KEY: &[u8] = include_bytes!("../tests/certs/node.key");
+    const ROGUE_CERT: &[u8] = include_bytes!("../tests/certs/rogue.pem");
+    const ROGUE_KEY: &[u8] = include_bytes!("../tests/certs/rogue.key");
+
+    fn client_tls(cert: &[u8], key: &[u8]) -> Connector {
+        let mut roots = rustls::RootCertStore::empty();
+        roots
+            .add(CertificateDer::from_pem_slice(CA).unwrap())
+            .unwrap();
+        let chain = CertificateDer::pem_slice_iter(cert)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let config = rustls::ClientConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_root_certificates(roots)
+        .with_client_auth_cert(chain, PrivateKeyDer::from_pem_slice(key).unwrap())
+        .unwrap();
+        Connector::Rustls(Arc::new(config))
+    }
+
+    async fn recv_frame(transport: &BacnetScTransport) -> Vec<u8> {
+        let mut out = [0u8; 64];
+        let (n, _) = timeout(Duration::from_secs(2), transport.recv(&mut out))
+            .await
+            .unwrap()
+            .unwrap();
+        out[..n].to_vec()
+    }
+
+    #[tokio::test]
+    async fn accepts_plain_websocket_node() {
+        let listener = ScListener::bind("127.0.0.1:0", ScServerConfig::default())
+            .await
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let (node, client) = tokio::join!(
+            listener.accept(),
+            BacnetScTransport::connect(format!("ws://{addr}/hub"))
+        );
+        let (node, client) = (node.unwrap(), client.unwrap());
+        assert!(node.endpoint().starts_with("ws://127.0.0.1:"));
+        assert!(matches!(node.peer_address(), DataLinkAddress::Ip(_)));
+
+        client
+            .send(client.peer_address(), &[1, 2, 3])
+            .await
+            .unwrap();
+        assert_eq!(recv_frame(&node).await, vec![1, 2, 3]);
+        node.send(node.peer_address(), &[4, 5]).await.unwrap();
+        assert_eq!(recv_frame(&client).await, vec![4, 5]);
+
+        // The node side closes for good when the initiator goes away.
+        drop(client);
+        timeout(Duration::from_secs(2), async {
+            while node.state() != ScConnectionState::Closed {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        let err = node.send(node.peer_address(), &[6]).await.unwrap_err();
+        assert!(matches!(err, DataLinkError::Disconnected));
+    }
+
+    #[tokio::test]
+    async fn mutual_tls_accepts_trusted_node_and_rejects_untrusted() {
+        let tls = ScTlsServerConfig::from_pem(HUB_CERT, HUB_KEY, Some(CA)).unwrap();
+        let listener = ScListener::bind("127.0.0.1:0", ScServerConfig::default().with_tls(tls))
+            .await
+            .unwrap();
+        let url = format!(
+            "wss://localhost:{}/hub",
+            listener.local_addr().unwrap().port()
+        );
+
+        let (rejected, _) = tokio::join!(
+            listener.accept(),
+            connect_async_tls_with_config(
+                url.as_str(),
+                None,
+                false,
+                Some(client_tls(ROGUE_CERT, ROGUE_KEY)),
+            )
+        );
+        match rejected.unwrap_err() {
+            DataLinkError::Connect { source, .. } => {
+                // The rustls error is kept as the source.
+                assert!(source.downcast_ref::<std::io::Error>().is_some());
+            }
+            other => panic!("unexpected error {other:?}"),
+        }
+
+        let (node, client) = tokio::join!(
+            listener.accept(),
+            connect_async_tls_with_config(
+                url.as_str(),
+                None,
+                false,
+                Some(client_tls(NODE_CERT, NODE_KEY)),
+            )
+        );
+        let node = node.unwrap();
+        let (mut client, _) = client.unwrap();
+        assert!(node.endpoint().starts_with("wss://"));
+
+        client.send(Message::Binary(vec![9, 9])).await.unwrap();
+        assert_eq!(recv_frame(&node).await, vec![9, 9]);
+        node.send(node.peer_address(), &[8]).await.unwrap();
+        let reply = timeout(Duration::from_secs(2), client.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(reply, Message::Binary(vec![8]));
+    }
+
+    #[tokio::test]
+    async fn stalled_node_does_not_hold_up_others() {
+        let config = ScServerConfig::default().with_handshake_timeout(Duration::from_millis(500));
+        let listener = ScListener::bind("127.0.0.1:0", config).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // Connects but never sends its websocket upgrade.
+        let _stalled = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let client = BacnetScTransport::connect(format!("ws://{addr}/hub"));
+        let (node, client) = tokio::join!(
+            timeout(Duration::from_millis(250), listener.accept()),
+            client
+        );
+        let (node, client) = (node.unwrap().unwrap(), client.unwrap());
+        client.send(client.peer_address(), &[1, 2]).await.unwrap();
+        assert_eq!(recv_frame(&node).await, vec![1, 2]);
+
+        let err = timeout(Duration::from_secs(2), listener.accept())
+            .await
+            .unwrap()
+            .unwrap_err();
+        match err {
+            DataLinkError::Connect { source, .. } => {
+                let source = source.downcast_ref::<std::io::Error>().unwrap();
+                assert_eq!(source.kind(), std::io::ErrorKind::TimedOut);
+            }
+            other => panic!("unexpected error {other:?}"),
+        }
+    }
+
+    #[test]
+    fn tls_config_rejects_missing_certificate() {
+        let err = ScTlsServerConfig::from_pem(b"", HUB_KEY, None).unwrap_err();
+        assert!(matches!(err, DataLinkError::Config(_)));
+    }
+}
@@ -0,0 +1,249 @@
+//! Browser WebSocket backend for [`BacnetScTransport`] (`web` feature on wasm32).
+//!
+//! Browsers cannot resolve host names for a page, so a hub addressed by name reports
+//! `0.0.0.0:<port>` as its peer address. TLS for `wss://` endpoints is handled by the
+//! browser, using its trust store and any client certificate it is configured with.
+
+use crate::{
+    parse_endpoint, BacnetScTransport, ScConnectOptions, ScConnectionState, SessionEnd,
+    BROADCAST_DEPTH, CHANNEL_DEPTH,
+};
+use js_sys::{ArrayBuffer, Uint8Array};
+use rustbac_datalink::{DataLinkAddress, DataLinkError};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc, watch};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{BinaryType, CloseEvent, MessageEvent, WebSocket};
+
+/// What the browser reported through the websocket's event handlers.
+enum SocketEvent {
+    Open,
+    Frame(Vec<u8>),
+    Closed(String),
+}
+
+/// A browser websocket together with its event handlers; closed on drop.
+struct Socket {
+    ws: WebSocket,
+    events: mpsc::UnboundedReceiver<SocketEvent>,
+    _on_open: Closure<dyn FnMut()>,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_close: Closure<dyn FnMut(CloseEvent)>,
+    _on_error: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        // Detach the handlers first; the closures are freed with this struct.
+        self.ws.set_onopen(None);
+        self.ws.set_onmessage(None);
+        self.ws.set_onclose(None);
+        self.ws.set_onerror(None);
+        let _ = self.ws.close();
+    }
+}
+
+impl BacnetScTransport {
+    /// Connects to `endpoint` (`ws://` or `wss://`) through the browser's WebSocket API.
+    ///
+    /// The initial connection attempt is not retried; its failure is returned directly.
+    pub async fn connect_with_options(
+        endpoint: impl Into<String>,
+        options: ScConnectOptions,
+    ) -> Result<Self, DataLinkError> {
+        let endpoint = endpoint.into();
+        let peer_address = peer_address(&endpoint)?;
+        let socket = open_websocket(&endpoint).await?;
+
+        let (outbound_tx, outbound_rx) = mpsc::channel::<Vec<u8>>(CHANNEL_DEPTH);
+        let (inbound_tx, _) = broadcast::channel::<Vec<u8>>(BROADCAST_DEPTH);
+        let inbound_tx = Arc::new(inbound_tx);
+        let (state_tx, state_rx) = watch::channel(ScConnectionState::Connected);
+
+        wasm_bindgen_futures::spawn_local(supervise(
+            endpoint.clone(),
+            options,
+            socket,
+            outbound_rx,
+            inbound_tx.clone(),
+            state_tx,
+        ));
+
+        Ok(Self {
+            endpoint,
+            peer_address,
+            outbound: outbound_tx,
+            inbound: inbound_tx,
+            state: state_rx,
+        })
+    }
+}
+
+fn peer_address(endpoint: &str) -> Result<DataLinkAddress, DataLinkError> {
+    let (host, port) = parse_endpoint(endpoint)?;
+    let ip = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+        .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+    Ok(DataLinkAddress::Ip(SocketAddr::new(ip, port)))
+}
+
+/// Owns the websocket for the lifetime of the transport, reconnecting as configured.
+async fn supervise(
+    endpoint: String,
+    options: ScConnectOptions,
+    socket: Socket,
+    mut outbound: mpsc::Receiver<Vec<u8>>,
+    inbound: Arc<broadcast::Sender<Vec<u8>>>,
+    state: watch::Sender<ScConnectionState>,
+) {
+    let mut socket = Some(socket);
+    loop {
+        let ws = match socket.take() {
+            Some(ws) => ws,
+            None => {
+                let Some(delay) = options.reconnect_delay else {
+                    break;
+                };
+                // Frames queued before the link went down are stale; drop them.
+                while outbound.try_recv().is_ok() {}
+                tokio::select! {
+                    _ = gloo_timers::future::sleep(delay) => {}
+                    frame = outbound.recv() => {
+                        if frame.is_none() {
+                            break;
+                        }
+                        continue;
+                    }
+                }
+                match open_websocket(&endpoint).await {
+                    Ok(ws) => {
+                        log::info!("BACnet/SC reconnected to {endpoint}");
+                        ws
+                    }
+                    Err(err) => {
+                        log::debug!("BACnet/SC reconnect to {endpoint} failed: {err}");
+                        continue;
+                    }
+                }
+            }
+        };
+
+        state.send_replace(ScConnectionState::Connected);
+        match run_session(ws, &mut outbound, &inbound).await {
+            SessionEnd::OwnerDropped => break,
+            SessionEnd::Lost(reason) => {
+                log::warn!("BACnet/SC connection to {endpoint} lost: {reason}");
+                state.send_replace(ScConnectionState::Reconnecting);
+            }
+        }
+    }
+    state.send_replace(ScConnectionState::Closed);
+}
+
+async fn run_session(
+    mut socket: Socket,
+    outbound: &mut mpsc::Receiver<Vec<u8>>,
+    inbound: &broadcast::Sender<Vec<u8>>,
+) -> SessionEnd {
+    loop {
+        tokio::select! {
+            frame = outbound.recv() => {
+                let Some(frame) = frame else {
+                    return SessionEnd::OwnerDropped;
+                };
+                if let Err(err) = socket.ws.send_with_u8_array(&frame) {
+                    return SessionEnd::Lost(js_message(&err));
+                }
+            }
+            event = socket.events.recv() => match event {
+                Some(SocketEvent::Frame(payload)) => {
+                    // If no receivers are subscribed yet the send fails
+                    // silently — the caller will wait and retry.
+                    let _ = inbound.send(payload);
+                }
+                Some(SocketEvent::Open) => {}
+                Some(SocketEvent::Closed(reason)) => return SessionEnd::Lost(reason),
+                None => return SessionEnd::Lost("connection closed".into()),
+            }
+        }
+    }
+}
+
+/// Opens a browser websocket to `endpoint` and waits for it to connect.
+async fn open_websocket(endpoint: &str) -> Result<Socket, DataLinkError> {
+    let ws = WebSocket::new(endpoint).map_err(|err| connect_error(endpoint, js_message(&err)))?;
+    ws.set_binary_type(BinaryType::Arraybuffer);
+    let (events_tx, events) = mpsc::unbounded_channel();
+
+    let on_open = {
+        let events = events_tx.clone();
+        Closure::<dyn FnMut()>::new(move || {
+            let _ = events.send(SocketEvent::Open);
+        })
+    };
+    let on_message = {
+        let events = events_tx.clone();
+        Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            match event.data().dyn_into::<ArrayBuffer>() {
+                Ok(buffer) => {
+                    let _ = events.send(SocketEvent::Frame(Uint8Array::new(&buffer).to_vec()));
+                }
+                Err(data) => {
+                    log::debug!("ignoring non-binary BACnet/SC websocket frame: {data:?}");
+                }
+            }
+        })
+    };
+    let on_close = {
+        let events = events_tx.clone();
+        Closure::<dyn FnMut(CloseEvent)>::new(move |event: CloseEvent| {
+            let _ = events.send(SocketEvent::Closed(format!(
+                "closed by peer (code {})",
+                event.code()
+            )));
+        })
+    };
+    let on_error = Closure::<dyn FnMut(web_sys::Event)>::new(move |_| {
+        // Browsers deliberately withhold websocket error details from pages.
+        let _ = events_tx.send(SocketEvent::Closed("websocket error".into()));
+    });
+    ws.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+    ws.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    ws.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+    ws.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+    let mut socket = Socket {
+        ws,
+        events,
+        _on_open: on_open,
+        _on_message: on_message,
+        _on_close: on_close,
+        _on_error: on_error,
+    };
+    match socket.events.recv().await {
+        Some(SocketEvent::Open) => Ok(socket),
+        Some(SocketEvent::Closed(reason)) => Err(connect_error(endpoint, reason)),
+        Some(SocketEvent::Frame(_)) | None => {
+            Err(connect_error(endpoint, "connection closed".into()))
+        }
+    }
+}
+
+fn connect_error(endpoint: &str, reason: String) -> DataLinkError {
+    DataLinkError::Connect {
+        target: endpoint.to_string(),
+        source: reason.into(),
+    }
+}
+
+fn js_message(value: &JsValue) -> String {
+    value
+        .dyn_ref::<js_sys::Error>()
+        .map(|err| String::from(err.message()))
+        .or_else(|| value.as_string())
+        .unwrap_or_else(|| format!("{value:?}"))
+}
@@ -0,0 +1,56 @@
+//! Expose the registers of a Modbus TCP device as a BACnet device.
+//!
+//! Usage:
+//!   cargo run -p rustbac-client --features modbus --example modbus_gateway -- --modbus 192.168.1.50:502
+
+use rustbac_client::gateway::modbus::{ModbusPoint, ModbusTcpProvider, RegisterFormat};
+use rustbac_client::{BacnetServer, ClientDataValue, ObjectStore, PointGateway};
+use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+use rustbac_datalink::BacnetIpTransport;
+use std::sync::Arc;
+use std::time::Duration;
+
+const DEVICE_INSTANCE: u32 = 2001;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+
+    let modbus = std::env::args()
+        .skip_while(|a| a != "--modbus")
+        .nth(1)
+        .expect("usage: --modbus <host:port>");
+
+    // Input register 0-1: supply temperature (float, degrees Celsius = 62).
+    // Holding register 10: setpoint in tenths of a degree.
+    let provider = ModbusTcpProvider::connect(modbus, 1)
+        .await?
+        .with_point(
+            ModbusPoint::input("Supply Temp", 0)
+                .with_format(RegisterFormat::F32)
+                .with_units(62),
+        )
+        .with_point(
+            ModbusPoint::holding("Setpoint", 10)
+                .with_scale(0.1)
+                .with_units(62),
+        );
+
+    let store = Arc::new(ObjectStore::new());
+    let device = ObjectId::new(ObjectType::Device, DEVICE_INSTANCE);
+    store.set(
+        device,
+        PropertyId::ObjectName,
+        ClientDataValue::CharacterString("Modbus Gateway".into()),
+    );
+    let gateway = PointGateway::new(provider, store);
+
+    let transport = BacnetIpTransport::bind("0.0.0.0:47808".parse()?).await?;
+    let server = BacnetServer::new(transport, DEVICE_INSTANCE, gateway.handler());
+
+    tokio::select! {
+        _ = server.serve() => {}
+        _ = gateway.run(Duration::from_secs(5)) => {}
+    }
+    Ok(())
+}
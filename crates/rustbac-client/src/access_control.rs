@@ -0,0 +1,200 @@
+//! Convenience types for the BACnet access control objects.
+//!
+//! Access Doors lock and unlock on command and report their position and lock sensors;
+//! Access Zones count who is inside; Credential Data Inputs hold the last credential a
+//! reader saw. Access Points report each attempt as an access-event notification,
+//! decoded into [`EventNotification::access_event`](crate::EventNotification::access_event).
+//! See [`BacnetClient::read_access_door`](crate::BacnetClient::read_access_door),
+//! [`BacnetClient::command_access_door`](crate::BacnetClient::command_access_door),
+//! [`BacnetClient::read_access_zone`](crate::BacnetClient::read_access_zone) and
+//! [`BacnetClient::read_credential_data_input`](crate::BacnetClient::read_credential_data_input).
+
+use crate::{ClientBitString, ClientDataValue};
+use rustbac_core::services::access_control::{
+    AccessEvent, AccessEventParameters, AccessZoneOccupancyState, AuthenticationFactor,
+    DoorAlarmState, DoorStatus, DoorValue, LockStatus,
+};
+use rustbac_core::services::acknowledge_alarm::TimeStamp;
+use rustbac_core::types::{DeviceObjectReference, PropertyId};
+
+/// Properties read for an [`AccessDoorState`].
+pub(crate) const ACCESS_DOOR_PROPERTIES: &[PropertyId] = &[
+    PropertyId::PresentValue,
+    PropertyId::DoorStatus,
+    PropertyId::LockStatus,
+    PropertyId::DoorAlarmState,
+];
+
+/// Properties read for an [`AccessZoneState`].
+pub(crate) const ACCESS_ZONE_PROPERTIES: &[PropertyId] = &[
+    PropertyId::OccupancyState,
+    PropertyId::OccupancyCount,
+    PropertyId::OccupancyLowerLimit,
+    PropertyId::OccupancyUpperLimit,
+];
+
+/// State read from an Access Door object.
+///
+/// The sensors are optional; a door without one reports `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccessDoorState {
+    /// `Present_Value`: the command in effect.
+    pub present_value: Option<DoorValue>,
+    /// `Door_Status`, from the door position sensor.
+    pub door_status: Option<DoorStatus>,
+    /// `Lock_Status`, from the lock sensor.
+    pub lock_status: Option<LockStatus>,
+    /// `Door_Alarm_State`.
+    pub door_alarm_state: Option<DoorAlarmState>,
+}
+
+impl AccessDoorState {
+    pub(crate) fn from_properties(values: &[(PropertyId, ClientDataValue)]) -> Self {
+        let mut state = Self {
+            present_value: None,
+            door_status: None,
+            lock_status: None,
+            door_alarm_state: None,
+        };
+        for (property_id, value) in values {
+            let ClientDataValue::Enumerated(value) = *value else {
+                continue;
+            };
+            match property_id {
+                PropertyId::PresentValue => state.present_value = DoorValue::from_u32(value),
+                PropertyId::DoorStatus => state.door_status = DoorStatus::from_u32(value),
+                PropertyId::LockStatus => state.lock_status = LockStatus::from_u32(value),
+                PropertyId::DoorAlarmState => {
+                    state.door_alarm_state = DoorAlarmState::from_u32(value);
+                }
+                _ => {}
+            }
+        }
+        state
+    }
+}
+
+/// Occupancy read from an Access Zone object; properties the zone does not have are
+/// `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccessZoneState {
+    /// `Occupancy_State`, relative to the limits.
+    pub occupancy_state: Option<AccessZoneOccupancyState>,
+    /// `Occupancy_Count`: credentials currently in the zone.
+    pub occupancy_count: Option<u32>,
+    pub occupancy_lower_limit: Option<u32>,
+    pub occupancy_upper_limit: Option<u32>,
+}
+
+impl AccessZoneState {
+    pub(crate) fn from_properties(values: &[(PropertyId, ClientDataValue)]) -> Self {
+        let mut state = Self::default();
+        for (property_id, value) in values {
+            match (property_id, value) {
+                (PropertyId::OccupancyState, ClientDataValue::Enumerated(v)) => {
+                    state.occupancy_state = AccessZoneOccupancyState::from_u32(*v);
+                }
+                (PropertyId::OccupancyCount, ClientDataValue::Unsigned(v)) => {
+                    state.occupancy_count = Some(*v);
+                }
+                (PropertyId::OccupancyLowerLimit, ClientDataValue::Unsigned(v)) => {
+                    state.occupancy_lower_limit = Some(*v);
+                }
+                (PropertyId::OccupancyUpperLimit, ClientDataValue::Unsigned(v)) => {
+                    state.occupancy_upper_limit = Some(*v);
+                }
+                _ => {}
+            }
+        }
+        state
+    }
+}
+
+/// An owned BACnetAuthenticationFactor, such as a card number read at a door.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticationFactorValue {
+    /// BACnetAuthenticationFactorType (e.g. 11 = simple-number-32).
+    pub format_type: u32,
+    pub format_class: u32,
+    pub value: Vec<u8>,
+}
+
+impl From<AuthenticationFactor<'_>> for AuthenticationFactorValue {
+    fn from(factor: AuthenticationFactor<'_>) -> Self {
+        Self {
+            format_type: factor.format_type,
+            format_class: factor.format_class,
+            value: factor.value.to_vec(),
+        }
+    }
+}
+
+/// The event values of an access-event notification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessEventNotification {
+    pub access_event_raw: u32,
+    pub access_event: Option<AccessEvent>,
+    pub status_flags: ClientBitString,
+    /// Access_Event_Tag, which orders the events of one Access Point.
+    pub access_event_tag: u32,
+    pub access_event_time: TimeStamp,
+    /// The credential presented.
+    pub access_credential: DeviceObjectReference,
+    /// The authentication factor read, when the Access Point reports it.
+    pub authentication_factor: Option<AuthenticationFactorValue>,
+}
+
+impl From<AccessEventParameters<'_>> for AccessEventNotification {
+    fn from(parameters: AccessEventParameters<'_>) -> Self {
+        Self {
+            access_event_raw: parameters.access_event,
+            access_event: parameters.access_event(),
+            status_flags: ClientBitString {
+                unused_bits: parameters.status_flags.unused_bits,
+                data: parameters.status_flags.data.to_vec(),
+            },
+            access_event_tag: parameters.access_event_tag,
+            access_event_time: parameters.access_event_time,
+            access_credential: parameters.access_credential,
+            authentication_factor: parameters.authentication_factor.map(Into::into),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccessDoorState, AccessZoneState};
+    use crate::ClientDataValue;
+    use rustbac_core::services::access_control::{
+        AccessZoneOccupancyState, DoorStatus, DoorValue, LockStatus,
+    };
+    use rustbac_core::types::PropertyId;
+
+    #[test]
+    fn door_and_zone_state_are_built_from_their_properties() {
+        let door = AccessDoorState::from_properties(&[
+            (PropertyId::PresentValue, ClientDataValue::Enumerated(2)),
+            (PropertyId::DoorStatus, ClientDataValue::Enumerated(1)),
+            (PropertyId::LockStatus, ClientDataValue::Enumerated(1)),
+        ]);
+        assert_eq!(door.present_value, Some(DoorValue::PulseUnlock));
+        assert_eq!(door.door_status, Some(DoorStatus::Opened));
+        assert_eq!(door.lock_status, Some(LockStatus::Unlocked));
+        assert_eq!(door.door_alarm_state, None);
+
+        let zone = AccessZoneState::from_properties(&[
+            (PropertyId::OccupancyState, ClientDataValue::Enumerated(3)),
+            (PropertyId::OccupancyCount, ClientDataValue::Unsigned(40)),
+            (
+                PropertyId::OccupancyUpperLimit,
+                ClientDataValue::Unsigned(40),
+            ),
+        ]);
+        assert_eq!(
+            zone.occupancy_state,
+            Some(AccessZoneOccupancyState::AtUpperLimit)
+        );
+        assert_eq!(zone.occupancy_count, Some(40));
+        assert_eq!(zone.occupancy_lower_limit, None);
+    }
+}
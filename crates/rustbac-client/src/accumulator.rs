@@ -0,0 +1,60 @@
+//! Engineering-unit scaling for the BACnet Accumulator and Pulse Converter objects.
+//!
+//! An Accumulator's Present_Value is a pulse count; its Scale says what one pulse is
+//! worth in its Units. A Pulse Converter reports engineering units itself, but keeps the
+//! same pair as Count and Scale_Factor. See
+//! [`BacnetClient::read_pulse_scaling`](crate::BacnetClient::read_pulse_scaling) and
+//! [`BacnetClient::read_scaled_pulses`](crate::BacnetClient::read_scaled_pulses).
+
+use rustbac_core::services::accumulator::{Prescale, Scale};
+
+/// How an Accumulator or Pulse Converter converts pulses to engineering units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PulseScaling {
+    /// `Scale`, or a Pulse Converter's `Scale_Factor` as [`Scale::Float`].
+    pub scale: Scale,
+    /// `Units`, the BACnetEngineeringUnits of the scaled value.
+    pub units: u32,
+    /// `Prescale`, for an Accumulator that has one.
+    pub prescale: Option<Prescale>,
+}
+
+impl PulseScaling {
+    /// `pulses` in engineering units.
+    pub fn to_engineering(&self, pulses: u32) -> f64 {
+        self.scale.apply(pulses)
+    }
+}
+
+/// A pulse count read from an Accumulator or Pulse Converter, with its scaled value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScaledPulses {
+    /// The raw count: an Accumulator's Present_Value or a Pulse Converter's Count.
+    pub pulses: u32,
+    /// `pulses` in `units`.
+    pub value: f64,
+    /// BACnetEngineeringUnits of `value`.
+    pub units: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PulseScaling;
+    use rustbac_core::services::accumulator::Scale;
+
+    #[test]
+    fn pulse_scaling_applies_the_scale() {
+        let scaling = PulseScaling {
+            scale: Scale::Integer(-1),
+            units: 19,
+            prescale: None,
+        };
+        assert_eq!(scaling.to_engineering(12_345), 1234.5);
+        let scaling = PulseScaling {
+            scale: Scale::Float(2.5),
+            ..scaling
+        };
+        assert_eq!(scaling.to_engineering(4), 10.0);
+    }
+}
@@ -1,5 +1,6 @@
-use crate::ClientBitString;
+use crate::{AccessEventNotification, ClientBitString, LifeSafetyNotification};
 use rustbac_core::services::acknowledge_alarm::{EventState, TimeStamp};
+use rustbac_core::services::event_notification::EventNotificationRequest;
 use rustbac_core::types::ObjectId;
 use rustbac_datalink::DataLinkAddress;
 
@@ -39,6 +40,7 @@ pub struct EventInformationResult {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct EventNotification {
     pub source: DataLinkAddress,
     pub confirmed: bool,
@@ -56,4 +58,46 @@ pub struct EventNotification {
     pub from_state: Option<EventState>,
     pub to_state_raw: u32,
     pub to_state: Option<EventState>,
+    /// The decoded event values, for the event types the client understands.
+    pub event_values: Option<Box<EventValues>>,
+}
+
+impl EventNotification {
+    /// The event values of an access event (`event_type` 13).
+    pub fn access_event(&self) -> Option<&AccessEventNotification> {
+        match self.event_values.as_deref() {
+            Some(EventValues::AccessEvent(values)) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// The event values of a change-of-life-safety event (`event_type` 8).
+    pub fn life_safety(&self) -> Option<&LifeSafetyNotification> {
+        match self.event_values.as_deref() {
+            Some(EventValues::LifeSafety(values)) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+/// The event values of an event notification, by event type.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum EventValues {
+    /// An access event (`event_type` 13).
+    AccessEvent(AccessEventNotification),
+    /// A change-of-life-safety event (`event_type` 8).
+    LifeSafety(LifeSafetyNotification),
+}
+
+impl EventValues {
+    pub(crate) fn from_request(request: &EventNotificationRequest<'_>) -> Option<Box<Self>> {
+        if let Some(values) = request.access_event {
+            return Some(Box::new(Self::AccessEvent(values.into())));
+        }
+        if let Some(values) = request.life_safety {
+            return Some(Box::new(Self::LifeSafety(values.into())));
+        }
+        None
+    }
 }
@@ -0,0 +1,789 @@
+//! Routing of alarm and event notifications to operators.
+//!
+//! An [`AlarmRouter`] holds [`AlarmRoute`]s matched against each received
+//! [`EventNotification`] by notification class, priority and time of day, and named
+//! [`AlarmSink`]s the matching routes deliver to: e-mail through an [`EmailTransport`],
+//! MQTT through an [`MqttPublisher`], or any other implementation. A route can escalate
+//! an alarm to more sinks while it stays unacknowledged; an acknowledgement
+//! notification, a return to normal or [`AlarmRouter::acknowledge`] ends the escalation.
+//!
+//! Routing is sans-IO apart from the sinks: [`AlarmRouter::route_at`] and
+//! [`AlarmRouter::escalate_due`] take the current time, and [`AlarmRouter::run`] drives
+//! both from a [`NotificationListener`].
+
+use crate::event_message::render_event_message;
+use crate::listener::{Notification, NotificationListener};
+use crate::occupancy::Weekday;
+use crate::runtime::{BoxFuture, Runtime, TokioRuntime};
+use crate::EventNotification;
+use rustbac_core::services::acknowledge_alarm::EventState;
+use rustbac_core::types::{ObjectId, Time};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Days in BACnet order, Monday first.
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+    Weekday::Sunday,
+];
+
+/// Notify_Type of an acknowledgement notification.
+const NOTIFY_TYPE_ACK_NOTIFICATION: u32 = 2;
+
+/// An alarm handed to an [`AlarmSink`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutedAlarm {
+    pub notification: EventNotification,
+    /// Name of the route that matched.
+    pub route: String,
+    /// 0 when first routed, `n` for the `n`th escalation step.
+    pub escalation_level: usize,
+    /// Host time the notification was routed.
+    pub received_at: SystemTime,
+}
+
+impl RoutedAlarm {
+    /// One line describing the alarm, e.g. `analog-input,3: normal to high-limit`.
+    pub fn summary(&self) -> String {
+        let summary =
+            render_event_message("{object}: {from_state} to {to_state}", &self.notification);
+        match &self.notification.message_text {
+            Some(text) if !text.is_empty() => format!("{summary} ({text})"),
+            _ => summary,
+        }
+    }
+}
+
+/// A destination for routed alarms.
+pub trait AlarmSink: Send + Sync {
+    /// Delivers `alarm`; an error is reported by the router and not retried.
+    fn deliver(&self, alarm: RoutedAlarm) -> BoxFuture<Result<(), String>>;
+}
+
+/// An e-mail to send.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailMessage {
+    pub to: Vec<String>,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Sends e-mail for an [`EmailSink`], e.g. over SMTP.
+pub trait EmailTransport: Send + Sync {
+    fn send(&self, message: EmailMessage) -> BoxFuture<Result<(), String>>;
+}
+
+/// Delivers alarms as e-mail to fixed recipients.
+pub struct EmailSink<T> {
+    transport: T,
+    recipients: Vec<String>,
+    subject_template: String,
+}
+
+impl<T: EmailTransport> EmailSink<T> {
+    /// Mails alarms to `recipients` with subjects like
+    /// `[alarm 100] analog-input,3: high-limit`.
+    pub fn new(transport: T, recipients: Vec<String>) -> Self {
+        Self {
+            transport,
+            recipients,
+            subject_template: "[{notify_type} {priority}] {object}: {to_state}".to_string(),
+        }
+    }
+
+    /// Sets the subject, a [`render_event_message`] template.
+    pub fn with_subject_template(mut self, template: impl Into<String>) -> Self {
+        self.subject_template = template.into();
+        self
+    }
+}
+
+impl<T: EmailTransport> AlarmSink for EmailSink<T> {
+    fn deliver(&self, alarm: RoutedAlarm) -> BoxFuture<Result<(), String>> {
+        let n = &alarm.notification;
+        let mut body = format!(
+            "{}\n\nDevice: {}\nNotification class: {}\nPriority: {}\nRoute: {}\n",
+            alarm.summary(),
+            n.initiating_device_id.instance(),
+            n.notification_class,
+            n.priority,
+            alarm.route,
+        );
+        if alarm.escalation_level > 0 {
+            body.push_str(&format!(
+                "Escalation level: {} (unacknowledged)\n",
+                alarm.escalation_level
+            ));
+        }
+        self.transport.send(EmailMessage {
+            to: self.recipients.clone(),
+            subject: render_event_message(&self.subject_template, n),
+            body,
+        })
+    }
+}
+
+/// Publishes messages for an [`MqttSink`] on an MQTT client.
+pub trait MqttPublisher: Send + Sync {
+    fn publish(&self, topic: String, payload: Vec<u8>) -> BoxFuture<Result<(), String>>;
+}
+
+/// Delivers alarms as MQTT messages.
+pub struct MqttSink<P> {
+    publisher: P,
+    topic_template: String,
+    payload_template: String,
+}
+
+impl<P: MqttPublisher> MqttSink<P> {
+    /// Publishes each alarm's [`summary`](RoutedAlarm::summary) to
+    /// `bacnet/alarms/{device}/{object_type}/{instance}`.
+    pub fn new(publisher: P) -> Self {
+        Self {
+            publisher,
+            topic_template: "bacnet/alarms/{device}/{object_type}/{instance}".to_string(),
+            payload_template: String::new(),
+        }
+    }
+
+    /// Sets the topic, a [`render_event_message`] template.
+    pub fn with_topic_template(mut self, template: impl Into<String>) -> Self {
+        self.topic_template = template.into();
+        self
+    }
+
+    /// Publishes `template` rendered by [`render_event_message`] instead of the summary.
+    pub fn with_payload_template(mut self, template: impl Into<String>) -> Self {
+        self.payload_template = template.into();
+        self
+    }
+}
+
+impl<P: MqttPublisher> AlarmSink for MqttSink<P> {
+    fn deliver(&self, alarm: RoutedAlarm) -> BoxFuture<Result<(), String>> {
+        let payload = if self.payload_template.is_empty() {
+            alarm.summary()
+        } else {
+            render_event_message(&self.payload_template, &alarm.notification)
+        };
+        self.publisher.publish(
+            render_event_message(&self.topic_template, &alarm.notification),
+            payload.into_bytes(),
+        )
+    }
+}
+
+/// Days and local times of day during which a route applies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeWindow {
+    pub days: Vec<Weekday>,
+    pub start: Time,
+    /// Exclusive; at or before `start` the window runs past midnight into the next day.
+    pub end: Time,
+}
+
+impl TimeWindow {
+    pub fn new(days: &[Weekday], start: Time, end: Time) -> Self {
+        Self {
+            days: days.to_vec(),
+            start,
+            end,
+        }
+    }
+
+    /// Whether the window covers `day` at `seconds` past local midnight.
+    fn contains(&self, day: Weekday, seconds: u32) -> bool {
+        let (start, end) = (seconds_of_day(self.start), seconds_of_day(self.end));
+        if start < end {
+            return self.days.contains(&day) && (start..end).contains(&seconds);
+        }
+        // Past midnight: the tail belongs to the day the window started.
+        let previous = WEEKDAYS[(day.index() + 6) % 7];
+        (self.days.contains(&day) && seconds >= start)
+            || (self.days.contains(&previous) && seconds < end)
+    }
+}
+
+fn seconds_of_day(time: Time) -> u32 {
+    u32::from(time.hour.min(24)) * 3600
+        + u32::from(time.minute.min(59)) * 60
+        + u32::from(time.second.min(59))
+}
+
+/// Further sinks an unacknowledged alarm is delivered to once `after` has passed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscalationStep {
+    /// Time since the alarm was first routed.
+    pub after: Duration,
+    pub sinks: Vec<String>,
+}
+
+/// Which alarms go to which sinks.
+///
+/// A new route matches every notification class, every priority and every time of day,
+/// and skips returns to normal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlarmRoute {
+    pub name: String,
+    /// Notification classes routed; empty routes all.
+    pub notification_classes: Vec<u32>,
+    /// Priorities routed, 0 being the most urgent.
+    pub priorities: RangeInclusive<u32>,
+    /// Local times the route applies; empty applies at all times.
+    pub windows: Vec<TimeWindow>,
+    /// Also routes transitions to normal, which never escalate.
+    pub include_normal: bool,
+    /// Names of the sinks alarms are delivered to.
+    pub sinks: Vec<String>,
+    /// Steps in increasing `after` order.
+    pub escalation: Vec<EscalationStep>,
+    /// Routes after this one are not tried for alarms it matches.
+    pub exclusive: bool,
+}
+
+impl AlarmRoute {
+    pub fn new(name: impl Into<String>, sinks: &[&str]) -> Self {
+        Self {
+            name: name.into(),
+            notification_classes: Vec::new(),
+            priorities: 0..=u32::MAX,
+            windows: Vec::new(),
+            include_normal: false,
+            sinks: sinks.iter().map(|sink| sink.to_string()).collect(),
+            escalation: Vec::new(),
+            exclusive: false,
+        }
+    }
+
+    /// Routes only these notification classes.
+    pub fn with_notification_classes(mut self, classes: &[u32]) -> Self {
+        self.notification_classes = classes.to_vec();
+        self
+    }
+
+    /// Routes only priorities in `priorities`.
+    pub fn with_priorities(mut self, priorities: RangeInclusive<u32>) -> Self {
+        self.priorities = priorities;
+        self
+    }
+
+    /// Adds a time window; with any window the route applies only within one.
+    pub fn with_time_window(mut self, window: TimeWindow) -> Self {
+        self.windows.push(window);
+        self
+    }
+
+    /// Also routes transitions to normal.
+    pub fn with_returns_to_normal(mut self) -> Self {
+        self.include_normal = true;
+        self
+    }
+
+    /// Delivers alarms still unacknowledged `after` they were routed to `sinks` too.
+    pub fn with_escalation(mut self, after: Duration, sinks: &[&str]) -> Self {
+        self.escalation.push(EscalationStep {
+            after,
+            sinks: sinks.iter().map(|sink| sink.to_string()).collect(),
+        });
+        self.escalation.sort_by_key(|step| step.after);
+        self
+    }
+
+    /// Stops routing at this route for the alarms it matches.
+    pub fn exclusive(mut self) -> Self {
+        self.exclusive = true;
+        self
+    }
+
+    fn matches(&self, n: &EventNotification, day: Weekday, seconds: u32) -> bool {
+        (self.notification_classes.is_empty()
+            || self.notification_classes.contains(&n.notification_class))
+            && self.priorities.contains(&n.priority)
+            && (self.include_normal || n.to_state != Some(EventState::Normal))
+            && (self.windows.is_empty()
+                || self
+                    .windows
+                    .iter()
+                    .any(|window| window.contains(day, seconds)))
+    }
+}
+
+/// One delivery to a sink.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlarmDelivery {
+    pub route: String,
+    pub sink: String,
+    pub escalation_level: usize,
+    pub device: ObjectId,
+    pub object: ObjectId,
+}
+
+/// A delivery that failed, or named a sink the router does not have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlarmDeliveryFailure {
+    pub delivery: AlarmDelivery,
+    pub error: String,
+}
+
+/// Outcome of routing one notification or one round of escalations.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoutingReport {
+    pub delivered: Vec<AlarmDelivery>,
+    pub failed: Vec<AlarmDeliveryFailure>,
+}
+
+impl RoutingReport {
+    /// Returns `true` when nothing was delivered or attempted.
+    pub fn is_empty(&self) -> bool {
+        self.delivered.is_empty() && self.failed.is_empty()
+    }
+}
+
+/// An escalation waiting for its step to come due.
+#[derive(Debug, Clone)]
+struct PendingEscalation {
+    alarm: RoutedAlarm,
+    route: usize,
+    step: usize,
+    due: SystemTime,
+}
+
+/// Routes event notifications to sinks and escalates unacknowledged alarms.
+pub struct AlarmRouter {
+    routes: Vec<AlarmRoute>,
+    sinks: HashMap<String, Arc<dyn AlarmSink>>,
+    utc_offset_minutes: i32,
+    pending: Vec<PendingEscalation>,
+    runtime: Arc<dyn Runtime>,
+}
+
+impl Default for AlarmRouter {
+    fn default() -> Self {
+        Self {
+            routes: Vec::new(),
+            sinks: HashMap::new(),
+            utc_offset_minutes: 0,
+            pending: Vec::new(),
+            runtime: Arc::new(TokioRuntime),
+        }
+    }
+}
+
+impl std::fmt::Debug for AlarmRouter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut sinks: Vec<_> = self.sinks.keys().collect();
+        sinks.sort();
+        f.debug_struct("AlarmRouter")
+            .field("routes", &self.routes)
+            .field("sinks", &sinks)
+            .field("utc_offset_minutes", &self.utc_offset_minutes)
+            .field("pending_escalations", &self.pending.len())
+            .field("runtime", &self.runtime)
+            .finish()
+    }
+}
+
+impl AlarmRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a sink that routes refer to as `name`, replacing one of the same name.
+    pub fn with_sink(mut self, name: impl Into<String>, sink: impl AlarmSink + 'static) -> Self {
+        self.sinks.insert(name.into(), Arc::new(sink));
+        self
+    }
+
+    /// Adds a route, tried after those already added.
+    pub fn with_route(mut self, route: AlarmRoute) -> Self {
+        self.routes.push(route);
+        self
+    }
+
+    /// Evaluates time windows in local time `utc_offset_minutes` behind UTC, daylight
+    /// saving included (UTC by default).
+    pub fn with_utc_offset(mut self, utc_offset_minutes: i32) -> Self {
+        self.utc_offset_minutes = utc_offset_minutes;
+        self
+    }
+
+    /// Waits for escalations in [`run`](Self::run) on `runtime` instead of Tokio.
+    pub fn with_runtime(mut self, runtime: impl Runtime) -> Self {
+        self.runtime = Arc::new(runtime);
+        self
+    }
+
+    /// Escalations waiting to come due.
+    pub fn pending_escalations(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// When the next escalation comes due.
+    pub fn next_escalation(&self) -> Option<SystemTime> {
+        self.pending.iter().map(|pending| pending.due).min()
+    }
+
+    /// Cancels the escalations of the alarm of `object` in `device`. Returns how many
+    /// were cancelled.
+    pub fn acknowledge(&mut self, device: ObjectId, object: ObjectId) -> usize {
+        let before = self.pending.len();
+        self.pending.retain(|pending| {
+            pending.alarm.notification.initiating_device_id != device
+                || pending.alarm.notification.event_object_id != object
+        });
+        before - self.pending.len()
+    }
+
+    /// Routes `notification` at `now`.
+    ///
+    /// An acknowledgement notification and a transition to normal cancel the alarm's
+    /// escalations, and any other transition replaces them.
+    pub async fn route_at(
+        &mut self,
+        notification: EventNotification,
+        now: SystemTime,
+    ) -> RoutingReport {
+        self.acknowledge(
+            notification.initiating_device_id,
+            notification.event_object_id,
+        );
+        let mut report = RoutingReport::default();
+        if notification.notify_type == NOTIFY_TYPE_ACK_NOTIFICATION {
+            return report;
+        }
+        let (day, seconds) = local_day_and_seconds(now, self.utc_offset_minutes);
+        let matched: Vec<usize> = {
+            let mut matched = Vec::new();
+            for (index, route) in self.routes.iter().enumerate() {
+                if route.matches(&notification, day, seconds) {
+                    matched.push(index);
+                    if route.exclusive {
+                        break;
+                    }
+                }
+            }
+            matched
+        };
+        for index in matched {
+            let route = &self.routes[index];
+            let alarm = RoutedAlarm {
+                notification: notification.clone(),
+                route: route.name.clone(),
+                escalation_level: 0,
+                received_at: now,
+            };
+            if notification.to_state != Some(EventState::Normal) {
+                if let Some(step) = route.escalation.first() {
+                    self.pending.push(PendingEscalation {
+                        alarm: alarm.clone(),
+                        route: index,
+                        step: 0,
+                        due: now + step.after,
+                    });
+                }
+            }
+            let sinks = route.sinks.clone();
+            self.deliver(&alarm, &sinks, &mut report).await;
+        }
+        report
+    }
+
+    /// Routes `notification` now.
+    pub async fn route(&mut self, notification: EventNotification) -> RoutingReport {
+        self.route_at(notification, SystemTime::now()).await
+    }
+
+    /// Delivers the escalations due at `now`, scheduling each alarm's next step.
+    pub async fn escalate_due(&mut self, now: SystemTime) -> RoutingReport {
+        let mut report = RoutingReport::default();
+        let (due, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|pending| pending.due <= now);
+        self.pending = waiting;
+        for mut pending in due {
+            let steps = &self.routes[pending.route].escalation;
+            let sinks = steps[pending.step].sinks.clone();
+            let mut alarm = pending.alarm.clone();
+            alarm.escalation_level = pending.step + 1;
+            if let Some(next) = steps.get(pending.step + 1) {
+                pending.step += 1;
+                pending.due = pending.alarm.received_at + next.after;
+                self.pending.push(pending);
+            }
+            self.deliver(&alarm, &sinks, &mut report).await;
+        }
+        report
+    }
+
+    /// Routes the event notifications `listener` receives, and escalates, until the
+    /// listener closes.
+    pub async fn run(&mut self, listener: &mut NotificationListener) {
+        loop {
+            let wait = self.next_escalation().map(|due| {
+                due.duration_since(SystemTime::now())
+                    .unwrap_or(Duration::ZERO)
+            });
+            let received = match wait {
+                Some(wait) => crate::runtime::timeout(&*self.runtime, wait, listener.recv())
+                    .await
+                    .ok(),
+                None => Some(listener.recv().await),
+            };
+            let report = match received {
+                Some(None) => return,
+                Some(Some(Notification::Event(notification))) => self.route(notification).await,
+                Some(Some(_)) => continue,
+                None => self.escalate_due(SystemTime::now()).await,
+            };
+            for failure in &report.failed {
+                log::warn!(
+                    "alarm route {} could not deliver to {}: {}",
+                    failure.delivery.route,
+                    failure.delivery.sink,
+                    failure.error
+                );
+            }
+        }
+    }
+
+    async fn deliver(&self, alarm: &RoutedAlarm, sinks: &[String], report: &mut RoutingReport) {
+        for name in sinks {
+            let delivery = AlarmDelivery {
+                route: alarm.route.clone(),
+                sink: name.clone(),
+                escalation_level: alarm.escalation_level,
+                device: alarm.notification.initiating_device_id,
+                object: alarm.notification.event_object_id,
+            };
+            let result = match self.sinks.get(name) {
+                Some(sink) => sink.deliver(alarm.clone()).await,
+                None => Err(format!("no sink named {name}")),
+            };
+            match result {
+                Ok(()) => report.delivered.push(delivery),
+                Err(error) => report.failed.push(AlarmDeliveryFailure { delivery, error }),
+            }
+        }
+    }
+}
+
+/// The local weekday and seconds past midnight at `at`.
+fn local_day_and_seconds(at: SystemTime, utc_offset_minutes: i32) -> (Weekday, u32) {
+    let utc = at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+    let local = utc - i64::from(utc_offset_minutes) * 60;
+    let days = local.div_euclid(86_400);
+    // 1970-01-01 was a Thursday.
+    let day = WEEKDAYS[(days + 3).rem_euclid(7) as usize];
+    (day, local.rem_euclid(86_400) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AlarmRoute, AlarmRouter, AlarmSink, EmailMessage, EmailSink, EmailTransport, RoutedAlarm,
+        TimeWindow,
+    };
+    use crate::occupancy::Weekday;
+    use crate::runtime::BoxFuture;
+    use crate::EventNotification;
+    use rustbac_core::services::acknowledge_alarm::{EventState, TimeStamp};
+    use rustbac_core::types::{ObjectId, ObjectType, Time};
+    use rustbac_datalink::DataLinkAddress;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<(String, usize)>>>);
+
+    impl Recorder {
+        fn sink(&self, name: &'static str) -> RecordingSink {
+            RecordingSink(name, self.0.clone())
+        }
+
+        fn take(&self) -> Vec<(String, usize)> {
+            std::mem::take(&mut self.0.lock().unwrap())
+        }
+    }
+
+    struct RecordingSink(&'static str, Arc<Mutex<Vec<(String, usize)>>>);
+
+    impl AlarmSink for RecordingSink {
+        fn deliver(&self, alarm: RoutedAlarm) -> BoxFuture<Result<(), String>> {
+            self.1
+                .lock()
+                .unwrap()
+                .push((self.0.to_string(), alarm.escalation_level));
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    fn notification(
+        class: u32,
+        priority: u32,
+        notify_type: u32,
+        to: EventState,
+    ) -> EventNotification {
+        EventNotification {
+            source: DataLinkAddress::Ip(([192, 168, 1, 50], 47808).into()),
+            confirmed: false,
+            process_id: 1,
+            initiating_device_id: ObjectId::new(ObjectType::Device, 10),
+            event_object_id: ObjectId::new(ObjectType::AnalogInput, 3),
+            timestamp: TimeStamp::SequenceNumber(1),
+            notification_class: class,
+            priority,
+            event_type: 5,
+            message_text: Some("Supply air high".to_string()),
+            notify_type,
+            ack_required: Some(true),
+            from_state_raw: 0,
+            from_state: Some(EventState::Normal),
+            to_state_raw: to.to_u32(),
+            to_state: Some(to),
+            event_values: None,
+        }
+    }
+
+    fn time(hour: u8) -> Time {
+        Time {
+            hour,
+            minute: 0,
+            second: 0,
+            hundredths: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn routes_by_class_priority_and_local_time_of_day() {
+        let recorder = Recorder::default();
+        let mut router = AlarmRouter::new()
+            .with_sink("desk", recorder.sink("desk"))
+            .with_sink("on-call", recorder.sink("on-call"))
+            .with_sink("log", recorder.sink("log"))
+            .with_route(
+                AlarmRoute::new("office hours", &["desk"])
+                    .with_notification_classes(&[1, 2])
+                    .with_time_window(TimeWindow::new(&Weekday::WORKDAYS, time(8), time(18)))
+                    .exclusive(),
+            )
+            .with_route(AlarmRoute::new("urgent", &["on-call"]).with_priorities(0..=63))
+            .with_route(AlarmRoute::new("everything", &["log"]).with_returns_to_normal())
+            // Five hours behind UTC.
+            .with_utc_offset(300);
+
+        // Saturday 2026-02-07 15:00 UTC is 10:00 local: outside office hours.
+        let saturday = UNIX_EPOCH + Duration::from_secs(1_770_476_400);
+        let report = router
+            .route_at(notification(1, 50, 0, EventState::HighLimit), saturday)
+            .await;
+        assert!(report.failed.is_empty());
+        assert_eq!(
+            recorder.take(),
+            vec![("on-call".to_string(), 0), ("log".to_string(), 0)]
+        );
+
+        // Monday 2026-02-09 15:00 UTC is 10:00 local: the exclusive route stops routing.
+        let monday = saturday + Duration::from_secs(2 * 86_400);
+        router
+            .route_at(notification(2, 50, 0, EventState::HighLimit), monday)
+            .await;
+        assert_eq!(recorder.take(), vec![("desk".to_string(), 0)]);
+
+        // Low priority, other class: only the catch-all, which also takes returns to normal.
+        router
+            .route_at(notification(7, 200, 1, EventState::Normal), monday)
+            .await;
+        assert_eq!(recorder.take(), vec![("log".to_string(), 0)]);
+    }
+
+    #[tokio::test]
+    async fn unacknowledged_alarms_escalate_until_acknowledged() {
+        let recorder = Recorder::default();
+        let mut router = AlarmRouter::new()
+            .with_sink("operator", recorder.sink("operator"))
+            .with_sink("supervisor", recorder.sink("supervisor"))
+            .with_route(
+                AlarmRoute::new("critical", &["operator", "pager"])
+                    .with_escalation(Duration::from_secs(300), &["supervisor"])
+                    .with_escalation(Duration::from_secs(900), &["supervisor", "operator"]),
+            );
+        let start = UNIX_EPOCH + Duration::from_secs(1_770_476_400);
+
+        let report = router
+            .route_at(notification(1, 10, 0, EventState::Offnormal), start)
+            .await;
+        assert_eq!(report.delivered.len(), 1);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].delivery.sink, "pager");
+        assert_eq!(recorder.take(), vec![("operator".to_string(), 0)]);
+        assert_eq!(
+            router.next_escalation(),
+            Some(start + Duration::from_secs(300))
+        );
+
+        assert!(router
+            .escalate_due(start + Duration::from_secs(299))
+            .await
+            .is_empty());
+        router.escalate_due(start + Duration::from_secs(300)).await;
+        assert_eq!(recorder.take(), vec![("supervisor".to_string(), 1)]);
+        assert_eq!(
+            router.next_escalation(),
+            Some(start + Duration::from_secs(900))
+        );
+
+        // The acknowledgement notification ends the escalation without being routed.
+        let report = router
+            .route_at(
+                notification(1, 10, 2, EventState::Offnormal),
+                start + Duration::from_secs(400),
+            )
+            .await;
+        assert!(report.is_empty());
+        assert_eq!(router.pending_escalations(), 0);
+        assert!(router
+            .escalate_due(start + Duration::from_secs(900))
+            .await
+            .is_empty());
+        assert!(recorder.take().is_empty());
+    }
+
+    #[tokio::test]
+    async fn email_sink_renders_subject_and_body() {
+        #[derive(Clone, Default)]
+        struct Outbox(Arc<Mutex<Vec<EmailMessage>>>);
+
+        impl EmailTransport for Outbox {
+            fn send(&self, message: EmailMessage) -> BoxFuture<Result<(), String>> {
+                self.0.lock().unwrap().push(message);
+                Box::pin(async { Ok(()) })
+            }
+        }
+
+        let outbox = Outbox::default();
+        let mut router = AlarmRouter::new()
+            .with_sink(
+                "mail",
+                EmailSink::new(outbox.clone(), vec!["ops@example.com".to_string()]),
+            )
+            .with_route(AlarmRoute::new("all", &["mail"]));
+        router
+            .route_at(notification(1, 100, 0, EventState::HighLimit), UNIX_EPOCH)
+            .await;
+
+        let sent = outbox.0.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, vec!["ops@example.com".to_string()]);
+        assert_eq!(sent[0].subject, "[alarm 100] analog-input,3: high-limit");
+        assert!(sent[0]
+            .body
+            .starts_with("analog-input,3: normal to high-limit (Supply air high)\n"));
+    }
+}
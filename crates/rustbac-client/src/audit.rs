@@ -0,0 +1,73 @@
+use crate::{ClientDataValue, TransactionOutcome};
+use rustbac_core::services::device_management::{DeviceCommunicationState, ReinitializeState};
+use rustbac_core::services::object_management::CreateObjectSpecifier;
+use rustbac_core::types::{ObjectId, PropertyId};
+use rustbac_datalink::DataLinkAddress;
+use std::time::SystemTime;
+
+/// One property write of a [`WriteAction`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyWrite {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub value: ClientDataValue,
+    pub priority: Option<u8>,
+}
+
+/// A write-class request sent to a device. Passwords are never included.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WriteAction {
+    WriteProperty(PropertyWrite),
+    WritePropertyMultiple(Vec<PropertyWrite>),
+    CreateObject {
+        specifier: CreateObjectSpecifier,
+        /// The object the device created, if it did.
+        created: Option<ObjectId>,
+    },
+    DeleteObject {
+        object_id: ObjectId,
+    },
+    DeviceCommunicationControl {
+        state: DeviceCommunicationState,
+        time_duration_seconds: Option<u16>,
+    },
+    ReinitializeDevice {
+        state: ReinitializeState,
+    },
+}
+
+/// A write-class request and how it ended, passed to a [`WriteAuditHook`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WriteAuditEvent {
+    /// Wall-clock time the request was first sent.
+    pub timestamp: SystemTime,
+    pub address: DataLinkAddress,
+    pub action: WriteAction,
+    pub outcome: TransactionOutcome,
+}
+
+/// Receives every write-class request a [`BacnetClient`](crate::BacnetClient) sends once
+/// it has ended, for a local audit log of control actions.
+///
+/// WriteProperty (including [`write_burst`](crate::BacnetClient::write_burst) and the
+/// writes of [`write_verified`](crate::BacnetClient::write_verified) and
+/// [`write_with_rollback`](crate::BacnetClient::write_with_rollback)),
+/// WritePropertyMultiple, CreateObject, DeleteObject, DeviceCommunicationControl and
+/// ReinitializeDevice are reported. The typed helpers that encode their own values, such
+/// as `write_weekly_schedule` or `write_lighting_command`, are not.
+///
+/// The hook runs on the task that made the request, so it should return quickly. Any
+/// `Fn(&WriteAuditEvent) + Send + Sync` closure is a hook.
+pub trait WriteAuditHook: Send + Sync {
+    fn on_write(&self, event: &WriteAuditEvent);
+}
+
+impl<F> WriteAuditHook for F
+where
+    F: Fn(&WriteAuditEvent) + Send + Sync,
+{
+    fn on_write(&self, event: &WriteAuditEvent) {
+        self(event)
+    }
+}
@@ -0,0 +1,325 @@
+//! Synchronous wrapper around [`BacnetClient`](crate::BacnetClient) for scripts and GUI
+//! applications that do not run an async runtime.
+//!
+//! Each [`BacnetClient`] owns a small Tokio runtime and blocks the calling thread on it,
+//! in the style of `reqwest::blocking`. Background work started through the client (COV
+//! notifications, foreign-device renewal) keeps running on the runtime's worker thread
+//! between calls.
+//!
+//! The blocking client must not be created, used or dropped from within an async
+//! context; Tokio panics when a runtime is blocked on or shut down inside another one.
+//!
+//! ```no_run
+//! use rustbac_client::blocking::BacnetClient;
+//! use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+//! use std::time::Duration;
+//!
+//! # fn main() -> Result<(), rustbac_client::ClientError> {
+//! let client = BacnetClient::new()?;
+//! for device in client.who_is(None, Duration::from_secs(2))? {
+//!     let value = client.read_property(
+//!         device.address,
+//!         ObjectId::new(ObjectType::AnalogInput, 1),
+//!         PropertyId::PresentValue,
+//!     )?;
+//!     println!("{:?}: {value:?}", device.device_id);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::properties::ObjectProperties;
+use crate::walk::DeviceWalkResult;
+use crate::{ClientDataValue, ClientError, DiscoveredDevice, DiscoveredObject};
+use rustbac_bacnet_sc::BacnetScTransport;
+use rustbac_core::services::write_property::WritePropertyRequest;
+use rustbac_core::services::write_property_multiple::PropertyWriteSpec;
+use rustbac_core::types::{ObjectId, PropertyId};
+use rustbac_datalink::bip::transport::BacnetIpTransport;
+use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError};
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// Blocking BACnet client. See the [module documentation](self).
+///
+/// Clones share the runtime and the underlying [`crate::BacnetClient`] state; the runtime
+/// shuts down when the last clone is dropped.
+pub struct BacnetClient<D: DataLink = BacnetIpTransport> {
+    inner: crate::BacnetClient<D>,
+    runtime: Arc<Runtime>,
+}
+
+impl<D: DataLink> Clone for BacnetClient<D> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+impl<D: DataLink + std::fmt::Debug> std::fmt::Debug for BacnetClient<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BacnetClient")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+fn build_runtime() -> Result<Runtime, ClientError> {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("rustbac-blocking")
+        .enable_all()
+        .build()
+        .map_err(|err| ClientError::DataLink(DataLinkError::Io(err)))
+}
+
+impl BacnetClient<BacnetIpTransport> {
+    /// Blocking [`crate::BacnetClient::new`].
+    pub fn new() -> Result<Self, ClientError> {
+        Self::connect(crate::BacnetClient::new())
+    }
+
+    /// Blocking [`crate::BacnetClient::new_foreign`].
+    pub fn new_foreign(bbmd_addr: SocketAddr, ttl_seconds: u16) -> Result<Self, ClientError> {
+        Self::connect(crate::BacnetClient::new_foreign(bbmd_addr, ttl_seconds))
+    }
+}
+
+impl BacnetClient<BacnetScTransport> {
+    /// Blocking [`crate::BacnetClient::new_sc`].
+    pub fn new_sc(endpoint: impl Into<String>) -> Result<Self, ClientError> {
+        Self::connect(crate::BacnetClient::new_sc(endpoint))
+    }
+}
+
+impl<D: DataLink> BacnetClient<D> {
+    /// Builds the async client on a fresh runtime, e.g.
+    /// `BacnetClient::connect(async { Ok(rustbac_client::BacnetClient::with_datalink(link)) })`
+    /// for a custom transport. Transports must be created inside `make`, on the runtime
+    /// that will drive them.
+    pub fn connect<F>(make: F) -> Result<Self, ClientError>
+    where
+        F: Future<Output = Result<crate::BacnetClient<D>, ClientError>>,
+    {
+        let runtime = build_runtime()?;
+        let inner = runtime.block_on(make)?;
+        Ok(Self {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Override the per-request response timeout (default: 3 s).
+    pub fn with_response_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.with_response_timeout(timeout);
+        self
+    }
+
+    /// The wrapped async client, for operations without a blocking counterpart. Drive
+    /// its futures with [`block_on`](Self::block_on).
+    pub fn async_client(&self) -> &crate::BacnetClient<D> {
+        &self.inner
+    }
+
+    /// Runs `future` to completion on this client's runtime.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Blocking [`crate::BacnetClient::who_is`].
+    pub fn who_is(
+        &self,
+        range: Option<(u32, u32)>,
+        wait: Duration,
+    ) -> Result<Vec<DiscoveredDevice>, ClientError> {
+        self.block_on(self.inner.who_is(range, wait))
+    }
+
+    /// Blocking [`crate::BacnetClient::who_has_object_id`].
+    pub fn who_has_object_id(
+        &self,
+        range: Option<(u32, u32)>,
+        object_id: ObjectId,
+        wait: Duration,
+    ) -> Result<Vec<DiscoveredObject>, ClientError> {
+        self.block_on(self.inner.who_has_object_id(range, object_id, wait))
+    }
+
+    /// Blocking [`crate::BacnetClient::who_has_object_name`].
+    pub fn who_has_object_name(
+        &self,
+        range: Option<(u32, u32)>,
+        object_name: &str,
+        wait: Duration,
+    ) -> Result<Vec<DiscoveredObject>, ClientError> {
+        self.block_on(self.inner.who_has_object_name(range, object_name, wait))
+    }
+
+    /// Blocking [`crate::BacnetClient::read_property`].
+    pub fn read_property(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        property_id: PropertyId,
+    ) -> Result<ClientDataValue, ClientError> {
+        self.block_on(self.inner.read_property(address, object_id, property_id))
+    }
+
+    /// Blocking [`crate::BacnetClient::read_property_multiple`].
+    pub fn read_property_multiple(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        property_ids: &[PropertyId],
+    ) -> Result<Vec<(PropertyId, ClientDataValue)>, ClientError> {
+        self.block_on(
+            self.inner
+                .read_property_multiple(address, object_id, property_ids),
+        )
+    }
+
+    /// Blocking [`crate::BacnetClient::read_property_set`].
+    pub fn read_property_set(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        selector: PropertyId,
+    ) -> Result<ObjectProperties, ClientError> {
+        self.block_on(self.inner.read_property_set(address, object_id, selector))
+    }
+
+    /// Blocking [`crate::BacnetClient::read_properties_with_fallback`].
+    pub fn read_properties_with_fallback(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        property_ids: &[PropertyId],
+    ) -> Result<Vec<(PropertyId, ClientDataValue)>, ClientError> {
+        self.block_on(
+            self.inner
+                .read_properties_with_fallback(address, object_id, property_ids),
+        )
+    }
+
+    /// Blocking [`crate::BacnetClient::read_many`].
+    pub fn read_many(
+        &self,
+        address: DataLinkAddress,
+        requests: &[(ObjectId, PropertyId)],
+    ) -> Result<HashMap<(ObjectId, PropertyId), ClientDataValue>, ClientError> {
+        self.block_on(self.inner.read_many(address, requests))
+    }
+
+    /// Blocking [`crate::BacnetClient::read_object_list`].
+    pub fn read_object_list(
+        &self,
+        address: DataLinkAddress,
+        device_id: ObjectId,
+    ) -> Result<Vec<ObjectId>, ClientError> {
+        self.block_on(self.inner.read_object_list(address, device_id))
+    }
+
+    /// Blocking [`walk_device`](crate::walk::walk_device).
+    pub fn walk_device(
+        &self,
+        address: DataLinkAddress,
+        device_id: ObjectId,
+    ) -> Result<DeviceWalkResult, ClientError> {
+        self.block_on(crate::walk::walk_device(&self.inner, address, device_id))
+    }
+
+    /// Blocking [`crate::BacnetClient::write_property`].
+    pub fn write_property(
+        &self,
+        address: DataLinkAddress,
+        request: WritePropertyRequest<'_>,
+    ) -> Result<(), ClientError> {
+        self.block_on(self.inner.write_property(address, request))
+    }
+
+    /// Blocking [`crate::BacnetClient::write_property_multiple`].
+    pub fn write_property_multiple(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        properties: &[PropertyWriteSpec<'_>],
+    ) -> Result<(), ClientError> {
+        self.block_on(
+            self.inner
+                .write_property_multiple(address, object_id, properties),
+        )
+    }
+
+    /// Blocking [`crate::BacnetClient::write_many`].
+    pub fn write_many(
+        &self,
+        address: DataLinkAddress,
+        writes: &[(ObjectId, PropertyId, ClientDataValue, Option<u8>)],
+    ) -> Result<(), ClientError> {
+        self.block_on(self.inner.write_many(address, writes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BacnetClient;
+    use crate::{ClientDataValue, SimulatedDevice};
+    use rustbac_core::services::write_property::WritePropertyRequest;
+    use rustbac_core::types::{DataValue, ObjectId, ObjectType, PropertyId};
+    use rustbac_datalink::bip::transport::BacnetIpTransport;
+    use rustbac_datalink::DataLinkAddress;
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+    #[test]
+    fn reads_and_writes_without_an_async_runtime() {
+        let loopback = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let client = BacnetClient::connect(async move {
+            Ok(crate::BacnetClient::with_datalink(
+                BacnetIpTransport::bind(loopback).await?,
+            ))
+        })
+        .unwrap();
+
+        // Serve a simulated device on the client's runtime.
+        let device_id = ObjectId::new(ObjectType::Device, 42);
+        let av = ObjectId::new(ObjectType::AnalogValue, 1);
+        let device_addr = client.block_on(async move {
+            let transport = BacnetIpTransport::bind(loopback).await.unwrap();
+            let addr = transport.local_addr().unwrap();
+            let device = SimulatedDevice::new(42, transport);
+            device
+                .add_object(
+                    av,
+                    [(PropertyId::PresentValue, ClientDataValue::Real(20.0))].into(),
+                )
+                .await;
+            tokio::spawn(async move { device.run().await });
+            DataLinkAddress::Ip(addr)
+        });
+
+        client
+            .write_property(
+                device_addr,
+                WritePropertyRequest {
+                    object_id: av,
+                    property_id: PropertyId::PresentValue,
+                    value: DataValue::Real(23.5),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let value = client
+            .read_property(device_addr, av, PropertyId::PresentValue)
+            .unwrap();
+        assert_eq!(value, ClientDataValue::Real(23.5));
+        assert!(client
+            .read_property(device_addr, device_id, PropertyId::ObjectName)
+            .is_ok());
+    }
+}
@@ -0,0 +1,264 @@
+//! What a device says it supports, read from its Device object.
+//!
+//! [`BacnetClient::probe_capabilities`](crate::BacnetClient::probe_capabilities) reads the
+//! protocol, APDU and vendor properties into a [`DeviceCapabilities`] and tunes the
+//! client's requests to that device from it.
+
+use crate::{ClientBitString, ClientDataValue};
+use rustbac_core::types::{ObjectId, ObjectType, PropertyId, Segmentation};
+use std::time::Duration;
+
+/// Device properties read for a [`DeviceCapabilities`].
+pub(crate) const CAPABILITY_PROPERTIES: &[PropertyId] = &[
+    PropertyId::ObjectIdentifier,
+    PropertyId::ProtocolRevision,
+    PropertyId::ProtocolServicesSupported,
+    PropertyId::ProtocolObjectTypesSupported,
+    PropertyId::MaxApduLengthAccepted,
+    PropertyId::SegmentationSupported,
+    PropertyId::ApduTimeout,
+    PropertyId::NumberOfApduRetries,
+    PropertyId::VendorIdentifier,
+    PropertyId::VendorName,
+    PropertyId::ModelName,
+    PropertyId::FirmwareRevision,
+];
+
+/// A service, by its bit in Protocol_Services_Supported (BACnetServicesSupported).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum ProtocolService {
+    AcknowledgeAlarm = 0,
+    ConfirmedCovNotification = 1,
+    ConfirmedEventNotification = 2,
+    GetAlarmSummary = 3,
+    GetEnrollmentSummary = 4,
+    SubscribeCov = 5,
+    AtomicReadFile = 6,
+    AtomicWriteFile = 7,
+    AddListElement = 8,
+    RemoveListElement = 9,
+    CreateObject = 10,
+    DeleteObject = 11,
+    ReadProperty = 12,
+    ReadPropertyMultiple = 14,
+    WriteProperty = 15,
+    WritePropertyMultiple = 16,
+    DeviceCommunicationControl = 17,
+    ConfirmedPrivateTransfer = 18,
+    ConfirmedTextMessage = 19,
+    ReinitializeDevice = 20,
+    VtOpen = 21,
+    VtClose = 22,
+    VtData = 23,
+    IAm = 26,
+    IHave = 27,
+    UnconfirmedCovNotification = 28,
+    UnconfirmedEventNotification = 29,
+    UnconfirmedPrivateTransfer = 30,
+    UnconfirmedTextMessage = 31,
+    TimeSynchronization = 32,
+    WhoHas = 33,
+    WhoIs = 34,
+    ReadRange = 35,
+    UtcTimeSynchronization = 36,
+    LifeSafetyOperation = 37,
+    SubscribeCovProperty = 38,
+    GetEventInformation = 39,
+    WriteGroup = 40,
+    SubscribeCovPropertyMultiple = 41,
+    ConfirmedCovNotificationMultiple = 42,
+    UnconfirmedCovNotificationMultiple = 43,
+    ConfirmedAuditNotification = 44,
+    AuditLogQuery = 45,
+    UnconfirmedAuditNotification = 46,
+    WhoAmI = 47,
+    YouAre = 48,
+}
+
+impl ProtocolService {
+    /// Position of the service in Protocol_Services_Supported.
+    pub const fn bit(self) -> usize {
+        self as usize
+    }
+}
+
+/// The capabilities a device reports in its Device object; properties it does not have
+/// are `None`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceCapabilities {
+    /// The Device object, as the device names itself.
+    pub device_id: Option<ObjectId>,
+    pub protocol_revision: Option<u32>,
+    /// `Protocol_Services_Supported`; see [`supports_service`](Self::supports_service).
+    pub services_supported: Option<ClientBitString>,
+    /// `Protocol_Object_Types_Supported`; see
+    /// [`supports_object_type`](Self::supports_object_type).
+    pub object_types_supported: Option<ClientBitString>,
+    /// `Max_APDU_Length_Accepted`, in octets.
+    pub max_apdu: Option<u32>,
+    pub segmentation: Option<Segmentation>,
+    /// `APDU_Timeout`: how long the device waits for a response before retrying.
+    pub apdu_timeout: Option<Duration>,
+    /// `Number_Of_APDU_Retries`.
+    pub apdu_retries: Option<u32>,
+    pub vendor_id: Option<u32>,
+    pub vendor_name: Option<String>,
+    pub model_name: Option<String>,
+    pub firmware_revision: Option<String>,
+}
+
+impl DeviceCapabilities {
+    pub(crate) fn from_properties(values: &[(PropertyId, ClientDataValue)]) -> Self {
+        let mut caps = Self {
+            device_id: None,
+            protocol_revision: None,
+            services_supported: None,
+            object_types_supported: None,
+            max_apdu: None,
+            segmentation: None,
+            apdu_timeout: None,
+            apdu_retries: None,
+            vendor_id: None,
+            vendor_name: None,
+            model_name: None,
+            firmware_revision: None,
+        };
+        for (property_id, value) in values {
+            match (property_id, value) {
+                (PropertyId::ObjectIdentifier, ClientDataValue::ObjectId(id)) => {
+                    caps.device_id = Some(*id);
+                }
+                (PropertyId::ProtocolRevision, ClientDataValue::Unsigned(v)) => {
+                    caps.protocol_revision = Some(*v);
+                }
+                (
+                    PropertyId::ProtocolServicesSupported,
+                    ClientDataValue::BitString { unused_bits, data },
+                ) => {
+                    caps.services_supported = Some(ClientBitString {
+                        unused_bits: *unused_bits,
+                        data: data.clone(),
+                    });
+                }
+                (
+                    PropertyId::ProtocolObjectTypesSupported,
+                    ClientDataValue::BitString { unused_bits, data },
+                ) => {
+                    caps.object_types_supported = Some(ClientBitString {
+                        unused_bits: *unused_bits,
+                        data: data.clone(),
+                    });
+                }
+                (PropertyId::MaxApduLengthAccepted, ClientDataValue::Unsigned(v)) => {
+                    caps.max_apdu = Some(*v);
+                }
+                (PropertyId::SegmentationSupported, ClientDataValue::Enumerated(v)) => {
+                    caps.segmentation = Segmentation::from_u32(*v);
+                }
+                (PropertyId::ApduTimeout, ClientDataValue::Unsigned(v)) => {
+                    caps.apdu_timeout = Some(Duration::from_millis(u64::from(*v)));
+                }
+                (PropertyId::NumberOfApduRetries, ClientDataValue::Unsigned(v)) => {
+                    caps.apdu_retries = Some(*v);
+                }
+                (PropertyId::VendorIdentifier, ClientDataValue::Unsigned(v)) => {
+                    caps.vendor_id = Some(*v);
+                }
+                (PropertyId::VendorName, ClientDataValue::CharacterString(s)) => {
+                    caps.vendor_name = Some(s.to_string());
+                }
+                (PropertyId::ModelName, ClientDataValue::CharacterString(s)) => {
+                    caps.model_name = Some(s.to_string());
+                }
+                (PropertyId::FirmwareRevision, ClientDataValue::CharacterString(s)) => {
+                    caps.firmware_revision = Some(s.to_string());
+                }
+                _ => {}
+            }
+        }
+        caps
+    }
+
+    /// Whether the device executes `service`, or `None` if it did not report its
+    /// services or the list is too short to say.
+    pub fn supports_service(&self, service: ProtocolService) -> Option<bool> {
+        self.services_supported.as_ref()?.bit(service.bit())
+    }
+
+    /// Whether the device can hold objects of `object_type`, or `None` if unknown.
+    pub fn supports_object_type(&self, object_type: ObjectType) -> Option<bool> {
+        self.object_types_supported
+            .as_ref()?
+            .bit(usize::from(object_type.to_u16()))
+    }
+
+    /// Whether the device can send segmented responses, or `None` if unknown.
+    pub fn transmits_segments(&self) -> Option<bool> {
+        self.segmentation.map(|segmentation| {
+            matches!(
+                segmentation,
+                Segmentation::SegmentedBoth | Segmentation::SegmentedTransmit
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeviceCapabilities, ProtocolService};
+    use crate::ClientDataValue;
+    use rustbac_core::types::{ObjectType, PropertyId, Segmentation};
+    use std::time::Duration;
+
+    #[test]
+    fn capabilities_decode_service_and_object_type_bits() {
+        let caps = DeviceCapabilities::from_properties(&[
+            (
+                PropertyId::ProtocolServicesSupported,
+                // ReadProperty (12) and WriteProperty (15), 40 bits.
+                ClientDataValue::BitString {
+                    unused_bits: 0,
+                    data: vec![0x00, 0x09, 0x00, 0x00, 0x00],
+                },
+            ),
+            (
+                PropertyId::ProtocolObjectTypesSupported,
+                // Analog Input (0) and Device (8).
+                ClientDataValue::BitString {
+                    unused_bits: 6,
+                    data: vec![0x80, 0x80],
+                },
+            ),
+            (
+                PropertyId::SegmentationSupported,
+                ClientDataValue::Enumerated(2),
+            ),
+            (PropertyId::ApduTimeout, ClientDataValue::Unsigned(6000)),
+        ]);
+        assert_eq!(
+            caps.supports_service(ProtocolService::ReadProperty),
+            Some(true)
+        );
+        assert_eq!(
+            caps.supports_service(ProtocolService::WriteProperty),
+            Some(true)
+        );
+        assert_eq!(
+            caps.supports_service(ProtocolService::ReadPropertyMultiple),
+            Some(false)
+        );
+        assert_eq!(caps.supports_service(ProtocolService::WhoAmI), None);
+        assert_eq!(caps.supports_object_type(ObjectType::Device), Some(true));
+        assert_eq!(
+            caps.supports_object_type(ObjectType::AnalogOutput),
+            Some(false)
+        );
+        assert_eq!(caps.supports_object_type(ObjectType::Schedule), None);
+        assert_eq!(caps.segmentation, Some(Segmentation::SegmentedReceive));
+        assert_eq!(caps.transmits_segments(), Some(false));
+        assert_eq!(caps.apdu_timeout, Some(Duration::from_secs(6)));
+        assert_eq!(caps.max_apdu, None);
+    }
+}
@@ -1,21 +1,70 @@
+use crate::access_control::{
+    AccessDoorState, AccessZoneState, AuthenticationFactorValue, ACCESS_DOOR_PROPERTIES,
+    ACCESS_ZONE_PROPERTIES,
+};
+use crate::accumulator::{PulseScaling, ScaledPulses};
+use crate::audit::{PropertyWrite, WriteAction, WriteAuditEvent, WriteAuditHook};
+use crate::capabilities::{DeviceCapabilities, ProtocolService, CAPABILITY_PROPERTIES};
+use crate::control_loop::{LoopTuning, LoopTuningChange, LOOP_TUNING_PROPERTIES};
+use crate::cov_inventory::{ActiveCovMultipleSubscription, ActiveCovSubscription};
+use crate::event_enrollment::{event_transitions, EventEnrollmentConfig};
+use crate::event_message::{EventMessageTexts, EventTransition};
+use crate::export::bacnet_date_time;
+use crate::global_group::{GlobalGroupValue, GroupMemberResult};
+use crate::invoke_id::{InvokeIdTracker, INVOKE_ID_QUARANTINE};
+use crate::journal::TransactionJournal;
+use crate::life_safety::{LifeSafetyStatus, LIFE_SAFETY_PROPERTIES};
+use crate::lift::{
+    ElevatorGroupState, EscalatorState, LandingCallStatusValue, LiftCalls, LiftCarState,
+    ELEVATOR_GROUP_PROPERTIES, ESCALATOR_PROPERTIES, LIFT_CAR_PROPERTIES,
+};
+use crate::log_buffer::{
+    AuditLogDatum, AuditLogRecord, AuditNotification, LogBufferRecord, LogMultipleRecord,
+    TrendLogRecord,
+};
+use crate::memory_limits::{MemoryLimits, DEFAULT_MAX_REASSEMBLY_BYTES};
+use crate::network_port::{NetworkPortConfig, NetworkPortIpChange, NETWORK_PORT_PROPERTIES};
+use crate::occupancy::{OccupancySchedule, ScheduleApplyFailure, ScheduleApplyReport};
+use crate::properties::ObjectProperties;
+use crate::range::{local_date_time, record_utc_time, TimedReadRangeResult, TimedRecord};
+use crate::request_options::{RequestOptions, SegmentationOptions};
+use crate::router::{
+    InboundKind, InboundRouter, RoutedLink, TransactionKey, TransactionSubscription,
+};
+use crate::runtime::{Runtime, TaskHandle, TokioRuntime};
+use crate::schedule::{
+    validate_exception_schedule, validate_weekly_schedule, CalendarEntry, SpecialEvent,
+    SpecialEventPeriod, TimeValue,
+};
+use crate::time_master::{run_time_master, TimeMaster, TimeMasterConfig, TimeMasterStatus};
+use crate::time_sync::{
+    ClockAuditEntry, ClockAuditReport, ClockReadFailure, DeviceClock, TimeSyncFailure,
+    TimeSyncReport,
+};
+use crate::trace::{DeviceStats, TransactionLog, DEFAULT_TRANSACTION_LOG_CAPACITY};
+use crate::write_policy::{WriteDecision, WritePolicy};
 use crate::{
     AlarmSummaryItem, AtomicReadFileResult, AtomicWriteFileResult, ClientBitString,
     ClientDataValue, ClientError, CovNotification, CovPropertyValue, DiscoveredDevice,
-    DiscoveredObject, EnrollmentSummaryItem, EventInformationItem, EventInformationResult,
-    EventNotification, ReadRangeResult,
+    DiscoveredObject, DiscoveryDedup, EnrollmentSummaryItem, EventInformationItem,
+    EventInformationResult, EventNotification, EventValues, ReadRangeResult, RequestContext,
+    StringInterner, TransactionOutcome, TransactionRecord, WriteBatchFailure, WriteBatchItem,
+    WriteBatchReport, WriteBurstFailure, WriteBurstReport, WriteVerifyOptions,
 };
-use rustbac_bacnet_sc::BacnetScTransport;
+use rustbac_bacnet_sc::{BacnetScTransport, ScConnectOptions};
 use rustbac_core::apdu::{
-    AbortPdu, ApduType, BacnetError, ComplexAckHeader, ConfirmedRequestHeader, RejectPdu,
+    abort_reason, AbortPdu, ApduType, BacnetError, ComplexAckHeader, ConfirmedRequestHeader,
     SegmentAck, SimpleAck, UnconfirmedRequestHeader,
 };
 use rustbac_core::encoding::{
-    primitives::{decode_unsigned, encode_ctx_unsigned},
+    primitives::{decode_unsigned, encode_ctx_object_id, encode_ctx_unsigned},
     reader::Reader,
     tag::Tag,
     writer::Writer,
 };
-use rustbac_core::npdu::Npdu;
+use rustbac_core::npdu::{NetworkPriority, Npdu};
+use rustbac_core::services::access_control::{AuthenticationFactorAck, DoorValue};
+use rustbac_core::services::accumulator::{PrescaleAck, Scale, ScaleAck};
 use rustbac_core::services::acknowledge_alarm::{
     AcknowledgeAlarmRequest, SERVICE_ACKNOWLEDGE_ALARM,
 };
@@ -29,10 +78,22 @@ use rustbac_core::services::atomic_read_file::{
 use rustbac_core::services::atomic_write_file::{
     AtomicWriteFileAck, AtomicWriteFileRequest, SERVICE_ATOMIC_WRITE_FILE,
 };
+use rustbac_core::services::calendar::{
+    AddDateListEntriesRequest, CalendarEntry as CoreCalendarEntry, DateListAck,
+    RemoveDateListEntriesRequest, WriteDateListRequest,
+};
+use rustbac_core::services::channel::{
+    ChannelMembersAck, ChannelValue, DeviceObjectPropertyReference, WriteChannelMembersRequest,
+    WriteChannelRequest, WriteStatus,
+};
+use rustbac_core::services::control_loop::SetpointReferenceAck;
 use rustbac_core::services::cov_notification::{
     CovNotificationRequest, SERVICE_CONFIRMED_COV_NOTIFICATION,
     SERVICE_UNCONFIRMED_COV_NOTIFICATION,
 };
+use rustbac_core::services::cov_subscription::{
+    ActiveCovMultipleSubscriptionsAck, ActiveCovSubscriptionsAck,
+};
 use rustbac_core::services::device_management::{
     DeviceCommunicationControlRequest, DeviceCommunicationState, ReinitializeDeviceRequest,
     ReinitializeState, SERVICE_DEVICE_COMMUNICATION_CONTROL, SERVICE_REINITIALIZE_DEVICE,
@@ -41,6 +102,10 @@ use rustbac_core::services::enrollment_summary::{
     EnrollmentSummaryItem as CoreEnrollmentSummaryItem, GetEnrollmentSummaryAck,
     GetEnrollmentSummaryRequest, SERVICE_GET_ENROLLMENT_SUMMARY,
 };
+use rustbac_core::services::event_enrollment::{
+    EventParameter, EventParametersAck, EventReferenceAck, WriteEventParametersRequest,
+    WriteEventReferenceRequest,
+};
 use rustbac_core::services::event_information::{
     EventSummaryItem as CoreEventSummaryItem, GetEventInformationAck, GetEventInformationRequest,
     SERVICE_GET_EVENT_INFORMATION,
@@ -49,7 +114,16 @@ use rustbac_core::services::event_notification::{
     EventNotificationRequest, SERVICE_CONFIRMED_EVENT_NOTIFICATION,
     SERVICE_UNCONFIRMED_EVENT_NOTIFICATION,
 };
+use rustbac_core::services::global_group::{
+    AccessResult, GlobalGroupValuesAck, GroupMembersAck, WriteGroupMembersRequest,
+};
 use rustbac_core::services::i_am::{IAmRequest, SERVICE_I_AM};
+use rustbac_core::services::lift::{
+    AssignedLandingCallsAck, LandingCallsAck, RegisteredCarCallAck,
+};
+use rustbac_core::services::lighting::{
+    ColorCommand, LightingCommand, WriteColorCommandRequest, WriteLightingCommandRequest, XyColor,
+};
 use rustbac_core::services::list_element::{
     AddListElementRequest, RemoveListElementRequest, SERVICE_ADD_LIST_ELEMENT,
     SERVICE_REMOVE_LIST_ELEMENT,
@@ -69,7 +143,15 @@ use rustbac_core::services::read_property_multiple::{
     PropertyReference, ReadAccessSpecification, ReadPropertyMultipleAck,
     ReadPropertyMultipleRequest, SERVICE_READ_PROPERTY_MULTIPLE,
 };
-use rustbac_core::services::read_range::{ReadRangeAck, ReadRangeRequest, SERVICE_READ_RANGE};
+use rustbac_core::services::read_range::{
+    AuditLogDatum as CoreAuditLogDatum, LogBufferRecord as CoreLogBufferRecord, ReadRangeAck,
+    ReadRangeRequest, SERVICE_READ_RANGE,
+};
+use rustbac_core::services::schedule::{
+    ScheduleReferencesAck, SpecialEvent as CoreSpecialEvent,
+    SpecialEventPeriod as CoreSpecialEventPeriod, TimeValue as CoreTimeValue,
+    WriteExceptionScheduleRequest, WriteScheduleReferencesRequest, WriteWeeklyScheduleRequest,
+};
 use rustbac_core::services::subscribe_cov::{SubscribeCovRequest, SERVICE_SUBSCRIBE_COV};
 use rustbac_core::services::subscribe_cov_property::{
     SubscribeCovPropertyRequest, SERVICE_SUBSCRIBE_COV_PROPERTY,
@@ -83,6 +165,11 @@ use rustbac_core::services::write_property_multiple::{
     PropertyWriteSpec, WriteAccessSpecification, WritePropertyMultipleRequest,
     SERVICE_WRITE_PROPERTY_MULTIPLE,
 };
+use rustbac_core::services::{DecodeMode, SkippedTag};
+use rustbac_core::transaction::{
+    acknowledged_segments, match_response, ClientTransaction, ConfirmedResponse, SegmentWindow,
+    TimerStep, TransactionError, TransactionStep,
+};
 use rustbac_core::types::{
     DataValue, Date, ErrorClass, ErrorCode, ObjectId, ObjectType, PropertyId, Time,
 };
@@ -90,17 +177,35 @@ use rustbac_core::EncodeError;
 use rustbac_datalink::bip::transport::{
     BacnetIpTransport, BroadcastDistributionEntry, ForeignDeviceTableEntry,
 };
-use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError};
+use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError, SwappableDataLink};
 use std::collections::{HashMap, HashSet};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::sync::RwLock;
 use std::time::Duration;
-use tokio::sync::Mutex;
-use tokio::task::JoinHandle;
-use tokio::time::{timeout, Instant};
+use std::time::Instant;
+use std::time::SystemTime;
+use tokio::sync::{watch, Mutex};
 
 const MIN_SEGMENT_DATA_LEN: usize = 32;
-const MAX_COMPLEX_ACK_REASSEMBLY_BYTES: usize = 1024 * 1024;
+/// Device instance that a device treats as its own instance in ReadProperty requests.
+pub(crate) const WILDCARD_DEVICE_INSTANCE: u32 = 4_194_303;
+const MAX_COMPLEX_ACK_REASSEMBLY_BYTES: usize = DEFAULT_MAX_REASSEMBLY_BYTES;
+/// Response segments of the largest (1476-octet) APDU that fit the reassembly limit.
+const DEFAULT_MAX_SEGMENTS_ACCEPTED: usize = MAX_COMPLEX_ACK_REASSEMBLY_BYTES / 1476;
+const REJECT_UNRECOGNIZED_SERVICE: u8 = 9;
+const ABORT_BUFFER_OVERFLOW: u8 = 1;
+const ABORT_SEGMENTATION_NOT_SUPPORTED: u8 = 4;
+const ABORT_APDU_TOO_LONG: u8 = 11;
+/// WriteProperty requests `write_burst` keeps outstanding unless configured otherwise.
+const DEFAULT_WRITE_BURST_WINDOW: usize = 4;
+/// Max APDU assumed for peers that have not announced theirs in an I-Am.
+const DEFAULT_PEER_MAX_APDU: usize = 480;
+/// Fixed octets in a single-object ReadPropertyMultiple ack: header and object id tags.
+const RPM_ACK_OVERHEAD_OCTETS: usize = 10;
+/// Worst-case octets per indexed Object_List element in a ReadPropertyMultiple ack.
+const RPM_OBJECT_LIST_ELEMENT_OCTETS: usize = 14;
+/// Default time to wait for a reply the device postponed.
+const DEFAULT_REPLY_POSTPONED_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// High-level async BACnet client.
 ///
@@ -108,8 +213,22 @@ const MAX_COMPLEX_ACK_REASSEMBLY_BYTES: usize = 1024 * 1024;
 /// BACnet operations: reading and writing properties, device and object discovery, COV
 /// subscriptions, alarm/event services, file I/O, and device management.
 ///
-/// The invoke-id counter and the per-request I/O lock are stored behind `Mutex`es, so the
-/// client is intentionally not `Clone` — wrap it in an `Arc` to share it across tasks.
+/// # Sharing
+///
+/// `BacnetClient` is a cheap, cloneable handle. Clones share the transport, the invoke-id
+/// counter, the request I/O lock, the device caches (capabilities, object names, RPM
+/// fallback) and the transaction log, so a clone can be handed to each task instead of
+/// wrapping the client in `Arc<Mutex<_>>`. Configuration set with the `with_*` builders
+/// (timeouts, segmentation window, server handler) is copied at clone time and may then
+/// differ per handle.
+///
+/// Confirmed requests from all clones hold the shared I/O lock while they are sent, and
+/// then wait for their answers concurrently, so requests from clones or concurrent tasks
+/// are outstanding at once; invoke ids stay unique across clones. Unconfirmed broadcasts
+/// (e.g. Who-Is) are sent without taking the lock. Everything clones receive goes through
+/// one [`InboundRouter`], so a response, notification or I-Am read by one task is handed
+/// to the task waiting for it, by address and invoke id for responses, rather than
+/// dropped.
 ///
 /// # Construction
 ///
@@ -118,15 +237,41 @@ const MAX_COMPLEX_ACK_REASSEMBLY_BYTES: usize = 1024 * 1024;
 /// - BACnet/SC (WebSocket): [`BacnetClient::new_sc()`].
 /// - Custom transport: [`BacnetClient::with_datalink()`].
 pub struct BacnetClient<D: DataLink> {
-    datalink: D,
-    invoke_id: Mutex<u8>,
-    request_io_lock: Mutex<()>,
+    datalink: std::sync::Arc<D>,
+    /// Invoke-id allocation and the transactions using them.
+    invoke_ids: std::sync::Arc<std::sync::Mutex<InvokeIdTracker>>,
+    request_io_lock: std::sync::Arc<Mutex<()>>,
     response_timeout: Duration,
+    /// Time to wait for a reply the device postponed.
+    reply_postponed_timeout: Duration,
     segmented_request_window_size: u8,
     segmented_request_retries: u8,
     segment_ack_timeout: Duration,
+    /// Response segments announced in the max-segments-accepted field of requests.
+    max_segments_accepted: usize,
     /// Peer max-APDU sizes in bytes, populated from I-Am responses via `who_is`.
     capability_cache: std::sync::Arc<RwLock<HashMap<DataLinkAddress, usize>>>,
+    /// Object identifiers resolved by name, keyed by device address and Object_Name.
+    object_name_cache: std::sync::Arc<RwLock<HashMap<(DataLinkAddress, String), ObjectId>>>,
+    /// Devices that rejected or could not answer ReadPropertyMultiple, read property by
+    /// property instead by `read_properties_with_fallback`.
+    rpm_fallback_devices: std::sync::Arc<RwLock<HashSet<DataLinkAddress>>>,
+    /// Segmentation settings for devices with broken or limited segmentation.
+    device_segmentation: std::sync::Arc<RwLock<HashMap<DataLinkAddress, SegmentationOptions>>>,
+    /// Segmentation overrides of this handle, from [`with_options`](Self::with_options).
+    segmentation: SegmentationOptions,
+    /// Most recent confirmed transactions, for diagnostics.
+    transaction_log: std::sync::Arc<std::sync::Mutex<TransactionLog>>,
+    /// Confirmed-request counters per device, for diagnostics.
+    device_stats: std::sync::Arc<std::sync::Mutex<HashMap<DataLinkAddress, DeviceStats>>>,
+    /// Journal every confirmed transaction is appended to, if attached.
+    transaction_journal: Option<TransactionJournal>,
+    /// Hook told of every write-class request, if attached.
+    write_audit: Option<std::sync::Arc<dyn WriteAuditHook>>,
+    /// Policy write-class requests are checked against before sending, if attached.
+    write_policy: Option<std::sync::Arc<dyn WritePolicy>>,
+    /// Whether requests the write policy holds for confirmation are sent by this handle.
+    writes_confirmed: bool,
     /// Optional server handler for inline request dispatch.
     server_handler: Option<std::sync::Arc<dyn crate::server::ServiceHandler>>,
     /// Device instance number used for I-Am responses when serving inline.
@@ -134,41 +279,146 @@ pub struct BacnetClient<D: DataLink> {
     /// Vendor ID used for I-Am responses when serving inline.
     #[allow(unused)]
     server_vendor_id: u16,
+    /// Executor used for timers and background tasks.
+    runtime: std::sync::Arc<dyn Runtime>,
+    /// How ReadProperty and ReadPropertyMultiple acks are decoded.
+    decode_mode: DecodeMode,
+    /// How repeated I-Am replies are merged by `who_is`.
+    discovery_dedup: DiscoveryDedup,
+    /// Resends of a confirmed request after a timeout.
+    request_retries: u8,
+    /// WriteProperty requests `write_burst` keeps outstanding at once.
+    write_burst_window: usize,
+    /// Network priority of outgoing requests.
+    network_priority: NetworkPriority,
+    /// Shared copies of decoded character strings, when enabled.
+    string_interner: Option<StringInterner>,
+    /// Hands every received frame to the receiver waiting for its kind.
+    router: std::sync::Arc<InboundRouter>,
+    memory_limits: MemoryLimits,
+}
+
+impl<D: DataLink> Clone for BacnetClient<D> {
+    fn clone(&self) -> Self {
+        Self {
+            datalink: self.datalink.clone(),
+            invoke_ids: self.invoke_ids.clone(),
+            request_io_lock: self.request_io_lock.clone(),
+            response_timeout: self.response_timeout,
+            reply_postponed_timeout: self.reply_postponed_timeout,
+            segmented_request_window_size: self.segmented_request_window_size,
+            segmented_request_retries: self.segmented_request_retries,
+            segment_ack_timeout: self.segment_ack_timeout,
+            max_segments_accepted: self.max_segments_accepted,
+            capability_cache: self.capability_cache.clone(),
+            object_name_cache: self.object_name_cache.clone(),
+            rpm_fallback_devices: self.rpm_fallback_devices.clone(),
+            device_segmentation: self.device_segmentation.clone(),
+            segmentation: self.segmentation,
+            transaction_log: self.transaction_log.clone(),
+            transaction_journal: self.transaction_journal.clone(),
+            write_audit: self.write_audit.clone(),
+            write_policy: self.write_policy.clone(),
+            writes_confirmed: self.writes_confirmed,
+            device_stats: self.device_stats.clone(),
+            server_handler: self.server_handler.clone(),
+            server_device_id: self.server_device_id,
+            server_vendor_id: self.server_vendor_id,
+            runtime: self.runtime.clone(),
+            decode_mode: self.decode_mode,
+            discovery_dedup: self.discovery_dedup,
+            request_retries: self.request_retries,
+            write_burst_window: self.write_burst_window,
+            network_priority: self.network_priority,
+            string_interner: self.string_interner.clone(),
+            router: self.router.clone(),
+            memory_limits: self.memory_limits,
+        }
+    }
 }
 
 impl<D: DataLink + std::fmt::Debug> std::fmt::Debug for BacnetClient<D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("BacnetClient")
             .field("datalink", &self.datalink)
-            .field("invoke_id", &self.invoke_id)
+            .field("invoke_ids", &self.invoke_ids)
             .field("response_timeout", &self.response_timeout)
+            .field("reply_postponed_timeout", &self.reply_postponed_timeout)
             .field(
                 "segmented_request_window_size",
                 &self.segmented_request_window_size,
             )
             .field("segmented_request_retries", &self.segmented_request_retries)
             .field("segment_ack_timeout", &self.segment_ack_timeout)
+            .field("max_segments_accepted", &self.max_segments_accepted)
             .field(
                 "server_handler",
                 &self.server_handler.as_ref().map(|_| "..."),
             )
             .field("server_device_id", &self.server_device_id)
             .field("server_vendor_id", &self.server_vendor_id)
+            .field("runtime", &self.runtime)
+            .field("decode_mode", &self.decode_mode)
+            .field("request_retries", &self.request_retries)
+            .field("write_burst_window", &self.write_burst_window)
+            .field("network_priority", &self.network_priority)
+            .field("segmentation", &self.segmentation)
+            .field("string_interner", &self.string_interner)
+            .field("memory_limits", &self.memory_limits)
             .finish()
     }
 }
 
+/// Grace period a BBMD allows past the registration TTL before purging a foreign device
+/// (J.5.2.3).
+const FOREIGN_DEVICE_GRACE: Duration = Duration::from_secs(30);
+
+/// First retry delay after a failed foreign-device renewal; doubled per failure up to the
+/// renewal interval.
+const FOREIGN_DEVICE_RETRY_INITIAL: Duration = Duration::from_secs(1);
+
+/// Foreign-device registration state reported by a [`ForeignDeviceRenewal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ForeignDeviceStatus {
+    /// The BBMD acknowledged the most recent registration.
+    Registered,
+    /// The most recent renewal got no answer (or could not be sent), but the previous
+    /// registration may still be live. Renewal is retried with backoff.
+    Unreachable,
+    /// The BBMD rejected the registration with a BVLC-Result NAK, or no renewal has
+    /// succeeded within the TTL plus the BBMD grace period. Broadcasts are no longer being
+    /// forwarded; registration is retried with backoff.
+    Expired,
+}
+
 /// Handle for a background task that periodically re-registers this client as a BACnet
 /// foreign device with the BBMD.
 ///
+/// Each renewal waits for the BBMD's BVLC-Result. Failed renewals are retried with
+/// exponential backoff, and the outcome is published through [`status`](Self::status) and
+/// [`subscribe`](Self::subscribe), so the client re-registers on its own once the BBMD is
+/// reachable again.
+///
 /// Dropping this value aborts the renewal task automatically. Call [`ForeignDeviceRenewal::stop`]
 /// to abort it explicitly.
 #[derive(Debug)]
 pub struct ForeignDeviceRenewal {
-    task: JoinHandle<()>,
+    task: Box<dyn TaskHandle>,
+    status: watch::Receiver<ForeignDeviceStatus>,
 }
 
 impl ForeignDeviceRenewal {
+    /// Current registration status.
+    pub fn status(&self) -> ForeignDeviceStatus {
+        *self.status.borrow()
+    }
+
+    /// Returns a receiver that is notified whenever the registration status changes.
+    pub fn subscribe(&self) -> watch::Receiver<ForeignDeviceStatus> {
+        self.status.clone()
+    }
+
     /// Abort the background renewal task immediately.
     pub fn stop(self) {
         self.task.abort();
@@ -189,17 +439,42 @@ impl BacnetClient<BacnetIpTransport> {
         let bind_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0);
         let datalink = BacnetIpTransport::bind(bind_addr).await?;
         Ok(Self {
-            datalink,
-            invoke_id: Mutex::new(1),
-            request_io_lock: Mutex::new(()),
+            datalink: std::sync::Arc::new(datalink),
+            invoke_ids: std::sync::Arc::new(std::sync::Mutex::new(InvokeIdTracker::new(
+                INVOKE_ID_QUARANTINE,
+            ))),
+            request_io_lock: std::sync::Arc::new(Mutex::new(())),
             response_timeout: Duration::from_secs(3),
+            reply_postponed_timeout: DEFAULT_REPLY_POSTPONED_TIMEOUT,
             segmented_request_window_size: 16,
             segmented_request_retries: 2,
             segment_ack_timeout: Duration::from_millis(500),
+            max_segments_accepted: DEFAULT_MAX_SEGMENTS_ACCEPTED,
             capability_cache: std::sync::Arc::new(RwLock::new(HashMap::new())),
+            object_name_cache: std::sync::Arc::new(RwLock::new(HashMap::new())),
+            rpm_fallback_devices: std::sync::Arc::new(RwLock::new(HashSet::new())),
+            device_segmentation: std::sync::Arc::new(RwLock::new(HashMap::new())),
+            segmentation: SegmentationOptions::default(),
+            transaction_log: std::sync::Arc::new(std::sync::Mutex::new(TransactionLog::new(
+                DEFAULT_TRANSACTION_LOG_CAPACITY,
+            ))),
+            transaction_journal: None,
+            write_audit: None,
+            write_policy: None,
+            writes_confirmed: false,
+            device_stats: std::sync::Arc::new(std::sync::Mutex::new(HashMap::new())),
             server_handler: None,
             server_device_id: 0,
             server_vendor_id: 0,
+            runtime: std::sync::Arc::new(TokioRuntime),
+            decode_mode: DecodeMode::Strict,
+            discovery_dedup: DiscoveryDedup::default(),
+            request_retries: 0,
+            write_burst_window: DEFAULT_WRITE_BURST_WINDOW,
+            network_priority: NetworkPriority::Normal,
+            string_interner: None,
+            router: std::sync::Arc::new(InboundRouter::new()),
+            memory_limits: MemoryLimits::default(),
         })
     }
 
@@ -214,20 +489,68 @@ impl BacnetClient<BacnetIpTransport> {
         let datalink = BacnetIpTransport::bind_foreign(bind_addr, bbmd_addr).await?;
         datalink.register_foreign_device(ttl_seconds).await?;
         Ok(Self {
-            datalink,
-            invoke_id: Mutex::new(1),
-            request_io_lock: Mutex::new(()),
+            datalink: std::sync::Arc::new(datalink),
+            invoke_ids: std::sync::Arc::new(std::sync::Mutex::new(InvokeIdTracker::new(
+                INVOKE_ID_QUARANTINE,
+            ))),
+            request_io_lock: std::sync::Arc::new(Mutex::new(())),
             response_timeout: Duration::from_secs(3),
+            reply_postponed_timeout: DEFAULT_REPLY_POSTPONED_TIMEOUT,
             segmented_request_window_size: 16,
             segmented_request_retries: 2,
             segment_ack_timeout: Duration::from_millis(500),
+            max_segments_accepted: DEFAULT_MAX_SEGMENTS_ACCEPTED,
             capability_cache: std::sync::Arc::new(RwLock::new(HashMap::new())),
+            object_name_cache: std::sync::Arc::new(RwLock::new(HashMap::new())),
+            rpm_fallback_devices: std::sync::Arc::new(RwLock::new(HashSet::new())),
+            device_segmentation: std::sync::Arc::new(RwLock::new(HashMap::new())),
+            segmentation: SegmentationOptions::default(),
+            transaction_log: std::sync::Arc::new(std::sync::Mutex::new(TransactionLog::new(
+                DEFAULT_TRANSACTION_LOG_CAPACITY,
+            ))),
+            transaction_journal: None,
+            write_audit: None,
+            write_policy: None,
+            writes_confirmed: false,
+            device_stats: std::sync::Arc::new(std::sync::Mutex::new(HashMap::new())),
             server_handler: None,
             server_device_id: 0,
             server_vendor_id: 0,
+            runtime: std::sync::Arc::new(TokioRuntime),
+            decode_mode: DecodeMode::Strict,
+            discovery_dedup: DiscoveryDedup::default(),
+            request_retries: 0,
+            write_burst_window: DEFAULT_WRITE_BURST_WINDOW,
+            network_priority: NetworkPriority::Normal,
+            string_interner: None,
+            router: std::sync::Arc::new(InboundRouter::new()),
+            memory_limits: MemoryLimits::default(),
         })
     }
 
+    /// Create a UDP/IP BACnet client registered as a foreign device with the first of
+    /// `bbmd_addrs` (in priority order) that acknowledges.
+    ///
+    /// Later registrations, including those made by
+    /// [`start_foreign_device_renewal`](Self::start_foreign_device_renewal), fail over to
+    /// another BBMD when the active one stops answering, and broadcasts follow the active
+    /// registration. Returns [`ClientError::DataLink`] if the list is empty or no BBMD
+    /// accepts the registration.
+    pub async fn new_foreign_redundant(
+        bbmd_addrs: &[SocketAddr],
+        ttl_seconds: u16,
+    ) -> Result<Self, ClientError> {
+        let bind_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0);
+        let datalink = BacnetIpTransport::bind_foreign_redundant(bind_addr, bbmd_addrs).await?;
+        datalink.register_foreign_device(ttl_seconds).await?;
+        Ok(Self::with_datalink(datalink))
+    }
+
+    /// The BBMD currently holding this client's foreign-device registration.
+    pub fn active_bbmd(&self) -> Option<SocketAddr> {
+        self.datalink.bbmd_addr()
+    }
+
     /// Re-register this client as a foreign device with the BBMD, using `ttl_seconds` as the new
     /// time-to-live.
     pub async fn register_foreign_device(&self, ttl_seconds: u16) -> Result<(), ClientError> {
@@ -285,6 +608,10 @@ impl BacnetClient<BacnetIpTransport> {
     /// Spawn a background task that re-registers this client as a foreign device at roughly
     /// 75 % of `ttl_seconds` to keep the registration alive indefinitely.
     ///
+    /// The registration made by [`BacnetClient::new_foreign`] is assumed to be current, so
+    /// the status starts as [`ForeignDeviceStatus::Registered`]. A failed renewal is retried
+    /// after 1 s, doubling up to the renewal interval, until the BBMD acknowledges again.
+    ///
     /// Returns a [`ForeignDeviceRenewal`] handle; dropping it (or calling `.stop()`) cancels
     /// the task. Returns an error if `ttl_seconds` is 0.
     pub fn start_foreign_device_renewal(
@@ -296,17 +623,53 @@ impl BacnetClient<BacnetIpTransport> {
         }
 
         let datalink = self.datalink.clone();
+        let io_lock = self.request_io_lock.clone();
         let refresh_seconds = u64::from(ttl_seconds).saturating_mul(3) / 4;
         let interval = Duration::from_secs(refresh_seconds.max(1));
-        let task = tokio::spawn(async move {
+        let lifetime = Duration::from_secs(u64::from(ttl_seconds)) + FOREIGN_DEVICE_GRACE;
+        let (status_tx, status) = watch::channel(ForeignDeviceStatus::Registered);
+        let runtime = self.runtime.clone();
+        let task = self.runtime.spawn(Box::pin(async move {
+            let mut registered_at = runtime.now();
+            let mut delay = interval;
+            let mut retry_delay = FOREIGN_DEVICE_RETRY_INITIAL.min(interval);
             loop {
-                tokio::time::sleep(interval).await;
-                if let Err(err) = datalink.register_foreign_device_no_wait(ttl_seconds).await {
-                    log::warn!("foreign device renewal send failed: {err}");
-                }
+                runtime.sleep(delay).await;
+                let result = {
+                    let _io = io_lock.lock().await;
+                    datalink.register_foreign_device(ttl_seconds).await
+                };
+                let next = match result {
+                    Ok(()) => {
+                        registered_at = runtime.now();
+                        delay = interval;
+                        retry_delay = FOREIGN_DEVICE_RETRY_INITIAL.min(interval);
+                        ForeignDeviceStatus::Registered
+                    }
+                    Err(err) => {
+                        delay = retry_delay;
+                        retry_delay = (retry_delay * 2).min(interval);
+                        let next = if matches!(err, DataLinkError::BvlcResult(_))
+                            || runtime.now().saturating_duration_since(registered_at) > lifetime
+                        {
+                            ForeignDeviceStatus::Expired
+                        } else {
+                            ForeignDeviceStatus::Unreachable
+                        };
+                        log::warn!(
+                            "foreign device renewal failed: {err}; {next:?}, retrying in {delay:?}"
+                        );
+                        next
+                    }
+                };
+                status_tx.send_if_modified(|current| {
+                    let changed = *current != next;
+                    *current = next;
+                    changed
+                });
             }
-        });
-        Ok(ForeignDeviceRenewal { task })
+        }));
+        Ok(ForeignDeviceRenewal { task, status })
     }
 }
 
@@ -317,6 +680,34 @@ impl BacnetClient<BacnetScTransport> {
         let datalink = BacnetScTransport::connect(endpoint).await?;
         Ok(Self::with_datalink(datalink))
     }
+
+    /// Like [`new_sc`](Self::new_sc), with explicit keepalive and reconnection options.
+    pub async fn new_sc_with_options(
+        endpoint: impl Into<String>,
+        options: ScConnectOptions,
+    ) -> Result<Self, ClientError> {
+        let datalink = BacnetScTransport::connect_with_options(endpoint, options).await?;
+        Ok(Self::with_datalink(datalink))
+    }
+}
+
+impl<D: DataLink> BacnetClient<SwappableDataLink<D>> {
+    /// Create a client whose link can later be replaced with
+    /// [`replace_datalink`](Self::replace_datalink).
+    pub fn with_swappable_datalink(datalink: D) -> Self {
+        Self::with_datalink(SwappableDataLink::new(datalink))
+    }
+
+    /// Replace the underlying link, e.g. with a socket re-bound after an interface came
+    /// back or a freshly connected BACnet/SC session.
+    ///
+    /// Requests waiting on the old link fail with [`DataLinkError::Disconnected`] (their
+    /// error is [retryable](ClientError::is_retryable)); later requests use the new link.
+    /// Caches and anything holding this client, such as a
+    /// [`CovManager`](crate::CovManager), carry on unchanged.
+    pub fn replace_datalink(&self, datalink: D) {
+        self.datalink.replace(datalink);
+    }
 }
 
 impl<D: DataLink> BacnetClient<D> {
@@ -325,18 +716,170 @@ impl<D: DataLink> BacnetClient<D> {
     /// Use this when you need a custom or pre-configured [`DataLink`] implementation.
     pub fn with_datalink(datalink: D) -> Self {
         Self {
-            datalink,
-            invoke_id: Mutex::new(1),
-            request_io_lock: Mutex::new(()),
+            datalink: std::sync::Arc::new(datalink),
+            invoke_ids: std::sync::Arc::new(std::sync::Mutex::new(InvokeIdTracker::new(
+                INVOKE_ID_QUARANTINE,
+            ))),
+            request_io_lock: std::sync::Arc::new(Mutex::new(())),
             response_timeout: Duration::from_secs(3),
+            reply_postponed_timeout: DEFAULT_REPLY_POSTPONED_TIMEOUT,
             segmented_request_window_size: 16,
             segmented_request_retries: 2,
             segment_ack_timeout: Duration::from_millis(500),
+            max_segments_accepted: DEFAULT_MAX_SEGMENTS_ACCEPTED,
             capability_cache: std::sync::Arc::new(RwLock::new(HashMap::new())),
+            object_name_cache: std::sync::Arc::new(RwLock::new(HashMap::new())),
+            rpm_fallback_devices: std::sync::Arc::new(RwLock::new(HashSet::new())),
+            device_segmentation: std::sync::Arc::new(RwLock::new(HashMap::new())),
+            segmentation: SegmentationOptions::default(),
+            transaction_log: std::sync::Arc::new(std::sync::Mutex::new(TransactionLog::new(
+                DEFAULT_TRANSACTION_LOG_CAPACITY,
+            ))),
+            transaction_journal: None,
+            write_audit: None,
+            write_policy: None,
+            writes_confirmed: false,
+            device_stats: std::sync::Arc::new(std::sync::Mutex::new(HashMap::new())),
             server_handler: None,
             server_device_id: 0,
             server_vendor_id: 0,
+            runtime: std::sync::Arc::new(TokioRuntime),
+            decode_mode: DecodeMode::Strict,
+            discovery_dedup: DiscoveryDedup::default(),
+            request_retries: 0,
+            write_burst_window: DEFAULT_WRITE_BURST_WINDOW,
+            network_priority: NetworkPriority::Normal,
+            string_interner: None,
+            router: std::sync::Arc::new(InboundRouter::new()),
+            memory_limits: MemoryLimits::default(),
+        }
+    }
+
+    /// Run timers and background tasks (foreign-device renewal) on `runtime` instead of
+    /// Tokio. See the [`runtime`](crate::runtime) module for what still needs Tokio.
+    pub fn with_runtime(mut self, runtime: impl Runtime) -> Self {
+        self.runtime = std::sync::Arc::new(runtime);
+        self
+    }
+
+    /// The runtime the client's timers and background tasks run on.
+    pub(crate) fn runtime(&self) -> &dyn Runtime {
+        &*self.runtime
+    }
+
+    /// Decode ReadProperty and ReadPropertyMultiple acks in `mode` (default: strict).
+    ///
+    /// [`DecodeMode::Lenient`] accepts devices that send the optional array index out of
+    /// order or add vendor context tags; each skipped tag is logged as a warning instead of
+    /// failing the read.
+    pub fn with_decode_mode(mut self, mode: DecodeMode) -> Self {
+        self.decode_mode = mode;
+        self
+    }
+
+    /// Merge repeated I-Am replies in [`who_is`](Self::who_is) by `dedup` (default:
+    /// latest address wins).
+    pub fn with_discovery_dedup(mut self, dedup: DiscoveryDedup) -> Self {
+        self.discovery_dedup = dedup;
+        self
+    }
+
+    /// Share character strings decoded by this client through `interner`.
+    ///
+    /// Applies to ReadProperty, ReadPropertyMultiple (and so device walks), ReadRange,
+    /// GetGlobalGroupValues and COV notification values. Pass the same interner to several
+    /// clients to share strings between them.
+    pub fn with_string_interner(mut self, interner: StringInterner) -> Self {
+        self.string_interner = Some(interner);
+        self
+    }
+
+    /// The interner set by [`with_string_interner`](Self::with_string_interner).
+    pub fn string_interner(&self) -> Option<&StringInterner> {
+        self.string_interner.as_ref()
+    }
+
+    fn intern(&self, mut value: ClientDataValue) -> ClientDataValue {
+        if let Some(interner) = &self.string_interner {
+            interner.intern_value(&mut value);
+        }
+        value
+    }
+
+    fn intern_cov_notification(&self, mut notification: CovNotification) -> CovNotification {
+        if let Some(interner) = &self.string_interner {
+            for property in &mut notification.values {
+                interner.intern_value(&mut property.value);
+            }
+        }
+        notification
+    }
+
+    /// A handle to this client whose confirmed requests use `options`.
+    ///
+    /// The handle shares the link, invoke ids and caches with this client, so it is cheap
+    /// to make one per call:
+    ///
+    /// ```no_run
+    /// # async fn demo(
+    /// #     client: rustbac_client::BacnetClient<rustbac_datalink::bip::transport::BacnetIpTransport>,
+    /// #     address: rustbac_datalink::DataLinkAddress,
+    /// #     object: rustbac_core::types::ObjectId,
+    /// # ) -> Result<(), rustbac_client::ClientError> {
+    /// use rustbac_client::RequestOptions;
+    /// use rustbac_core::types::PropertyId;
+    /// use std::time::Duration;
+    ///
+    /// let slow = RequestOptions::default()
+    ///     .with_timeout(Duration::from_secs(10))
+    ///     .with_retries(2);
+    /// let value = client
+    ///     .with_options(slow)
+    ///     .read_property(address, object, PropertyId::PresentValue)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        let mut client = self.clone();
+        if let Some(timeout) = options.timeout {
+            client.response_timeout = timeout;
+        }
+        if let Some(timeout) = options.reply_postponed_timeout {
+            client.reply_postponed_timeout = timeout;
         }
+        client.request_retries = options.retries;
+        client.network_priority = options.priority;
+        client.segmentation = options.segmentation;
+        client.writes_confirmed = options.confirm_writes;
+        client
+    }
+
+    /// Sets the segmentation settings for requests to `address`, e.g.
+    /// [`SegmentationOptions::unsegmented`] for a device whose segmented responses are
+    /// broken. Per-call [`RequestOptions::segmentation`] fields take precedence.
+    pub fn set_device_segmentation(&self, address: DataLinkAddress, options: SegmentationOptions) {
+        if let Ok(mut devices) = self.device_segmentation.write() {
+            devices.insert(address, options);
+        }
+    }
+
+    /// Returns to the client-wide segmentation settings for `address`.
+    pub fn clear_device_segmentation(&self, address: DataLinkAddress) {
+        if let Ok(mut devices) = self.device_segmentation.write() {
+            devices.remove(&address);
+        }
+    }
+
+    /// The segmentation settings in effect for a request to `address`.
+    fn segmentation_for(&self, address: DataLinkAddress) -> SegmentationOptions {
+        let device = self
+            .device_segmentation
+            .read()
+            .ok()
+            .and_then(|devices| devices.get(&address).copied())
+            .unwrap_or_default();
+        self.segmentation.or(device)
     }
 
     /// Override the per-request response timeout (default: 3 s).
@@ -345,6 +888,32 @@ impl<D: DataLink> BacnetClient<D> {
         self
     }
 
+    /// Caps the memory held for segmented responses, outstanding requests and queued
+    /// frames; see [`MemoryLimits`]. Fields below their minimum are raised to it. The
+    /// frame queues are shared with every clone of the client, so their cap changes for
+    /// all of them; the other limits apply to this handle.
+    pub fn with_memory_limits(mut self, limits: MemoryLimits) -> Self {
+        let limits = limits.clamped();
+        self.memory_limits = limits;
+        self.max_segments_accepted = self
+            .max_segments_accepted
+            .min((limits.max_reassembly_bytes / 1476).max(1));
+        self.router.set_capacity(limits.max_queued_frames);
+        self
+    }
+
+    /// The limits set with [`with_memory_limits`](Self::with_memory_limits).
+    pub fn memory_limits(&self) -> MemoryLimits {
+        self.memory_limits
+    }
+
+    /// Override how long to wait for a reply once the device postponed it, with a
+    /// server Segment-ACK or an application-exceeded-reply-time Abort (default: 30 s).
+    pub fn with_reply_postponed_timeout(mut self, timeout: Duration) -> Self {
+        self.reply_postponed_timeout = timeout;
+        self
+    }
+
     /// Override the segmented-request window size (number of segments sent before waiting
     /// for an ACK). Clamped to a minimum of 1. Default: 16.
     pub fn with_segmented_request_window_size(mut self, window_size: u8) -> Self {
@@ -359,6 +928,14 @@ impl<D: DataLink> BacnetClient<D> {
         self
     }
 
+    /// Override how many WriteProperty requests [`write_burst`](Self::write_burst) keeps
+    /// outstanding to one device. `1` writes strictly one after another, for devices that
+    /// cannot hold several transactions from the same client. Default: 4.
+    pub fn with_write_burst_window(mut self, window: usize) -> Self {
+        self.write_burst_window = window.max(1);
+        self
+    }
+
     /// Override the timeout used when waiting for a segment ACK from the remote device
     /// during a segmented confirmed request. Clamped to a minimum of 1 ms. Default: 500 ms.
     pub fn with_segment_ack_timeout(mut self, timeout: Duration) -> Self {
@@ -366,6 +943,185 @@ impl<D: DataLink> BacnetClient<D> {
         self
     }
 
+    /// Override how many response segments requests say the client accepts. `0` sends
+    /// "unspecified", which some servers treat as no segmentation. Default: as many as fit
+    /// the 1 MiB reassembly limit at a 1476-octet APDU, announced as more than 64.
+    pub fn with_max_segments_accepted(mut self, segments: usize) -> Self {
+        self.max_segments_accepted = segments;
+        self
+    }
+
+    /// Override how many recent transactions [`recent_transactions`](Self::recent_transactions)
+    /// keeps. `0` disables recording. Default: 64.
+    pub fn with_transaction_log_capacity(mut self, capacity: usize) -> Self {
+        self.transaction_log =
+            std::sync::Arc::new(std::sync::Mutex::new(TransactionLog::new(capacity)));
+        self
+    }
+
+    /// Returns the most recent confirmed transactions, oldest first.
+    ///
+    /// Every confirmed request is recorded with its invoke ID, service choice, timing, and
+    /// outcome, so applications can show a diagnostics view without a packet capture.
+    pub fn recent_transactions(&self) -> Vec<TransactionRecord> {
+        self.transaction_log
+            .lock()
+            .map(|log| log.snapshot())
+            .unwrap_or_default()
+    }
+
+    /// Appends every confirmed transaction of this client, and of clones made after this
+    /// call, to `journal`.
+    ///
+    /// Unlike [`recent_transactions`](Self::recent_transactions) the journal is not limited
+    /// to the latest exchanges unless created with
+    /// [`TransactionJournal::with_limit`]; keep a clone of it to query or dump it.
+    pub fn with_transaction_journal(mut self, journal: TransactionJournal) -> Self {
+        self.transaction_journal = Some(journal);
+        self
+    }
+
+    /// The journal attached with [`with_transaction_journal`](Self::with_transaction_journal).
+    pub fn transaction_journal(&self) -> Option<&TransactionJournal> {
+        self.transaction_journal.as_ref()
+    }
+
+    /// Calls `hook` with every write-class request this client, and clones made after this
+    /// call, send: WriteProperty, WritePropertyMultiple, CreateObject, DeleteObject,
+    /// DeviceCommunicationControl and ReinitializeDevice. See [`WriteAuditHook`] for what
+    /// is reported.
+    pub fn with_write_audit(mut self, hook: impl WriteAuditHook + 'static) -> Self {
+        self.write_audit = Some(std::sync::Arc::new(hook));
+        self
+    }
+
+    /// Checks every write-class request this client, and clones made after this call, send
+    /// against `policy` first. A denied request fails with [`ClientError::WriteDenied`]; one
+    /// that needs confirmation fails with [`ClientError::WriteNeedsConfirmation`] unless it
+    /// is made on a handle from [`with_options`](Self::with_options) with
+    /// [`RequestOptions::with_confirmed_writes`]. Nothing is sent for a refused request.
+    pub fn with_write_policy(mut self, policy: impl WritePolicy + 'static) -> Self {
+        self.write_policy = Some(std::sync::Arc::new(policy));
+        self
+    }
+
+    fn check_write_policy(
+        &self,
+        address: DataLinkAddress,
+        action: impl FnOnce() -> WriteAction,
+    ) -> Result<(), ClientError> {
+        let Some(policy) = &self.write_policy else {
+            return Ok(());
+        };
+        match policy.check(address, &action()) {
+            WriteDecision::Allow => Ok(()),
+            WriteDecision::Confirm { .. } if self.writes_confirmed => Ok(()),
+            WriteDecision::Confirm { reason } => {
+                Err(ClientError::WriteNeedsConfirmation { reason })
+            }
+            WriteDecision::Deny { reason } => Err(ClientError::WriteDenied { reason }),
+        }
+    }
+
+    fn audit_write<T>(
+        &self,
+        address: DataLinkAddress,
+        timestamp: SystemTime,
+        result: &Result<T, ClientError>,
+        action: impl FnOnce(Option<&T>) -> WriteAction,
+    ) {
+        if let Some(hook) = &self.write_audit {
+            hook.on_write(&WriteAuditEvent {
+                timestamp,
+                address,
+                action: action(result.as_ref().ok()),
+                outcome: TransactionOutcome::from_result(result, |_| TransactionOutcome::SimpleAck),
+            });
+        }
+    }
+
+    /// Discards all recorded transactions.
+    pub fn clear_transactions(&self) {
+        if let Ok(mut log) = self.transaction_log.lock() {
+            log.clear();
+        }
+    }
+
+    /// Returns the confirmed-request counters for `address`, or `None` if no request to it
+    /// has ended since the client was created or the counters were reset.
+    ///
+    /// Applications can poll this to flag devices that time out, reject or answer slowly.
+    pub fn device_stats(&self, address: DataLinkAddress) -> Option<DeviceStats> {
+        self.device_stats
+            .lock()
+            .ok()
+            .and_then(|stats| stats.get(&address).cloned())
+    }
+
+    /// Returns the confirmed-request counters of every device the client has talked to.
+    pub fn all_device_stats(&self) -> Vec<(DataLinkAddress, DeviceStats)> {
+        self.device_stats
+            .lock()
+            .map(|stats| stats.iter().map(|(a, s)| (*a, s.clone())).collect())
+            .unwrap_or_default()
+    }
+
+    /// Resets the counters for `address`.
+    pub fn reset_device_stats(&self, address: DataLinkAddress) {
+        if let Ok(mut stats) = self.device_stats.lock() {
+            stats.remove(&address);
+        }
+    }
+
+    /// Resets the counters of every device.
+    pub fn reset_all_device_stats(&self) {
+        if let Ok(mut stats) = self.device_stats.lock() {
+            stats.clear();
+        }
+    }
+
+    fn update_device_stats(&self, address: DataLinkAddress, update: impl FnOnce(&mut DeviceStats)) {
+        if let Ok(mut stats) = self.device_stats.lock() {
+            update(stats.entry(address).or_default());
+        }
+    }
+
+    fn record_transaction(
+        &self,
+        address: DataLinkAddress,
+        tx: &[u8],
+        invoke_id: u8,
+        service_choice: u8,
+        started: (std::time::SystemTime, Instant),
+        outcome: TransactionOutcome,
+    ) {
+        let record = TransactionRecord {
+            address,
+            invoke_id,
+            service_choice,
+            request_len: tx.len(),
+            started_at: started.0,
+            elapsed: self.runtime.now().saturating_duration_since(started.1),
+            outcome,
+        };
+        self.update_device_stats(address, |stats| stats.record(&record));
+        if let Some(journal) = &self.transaction_journal {
+            journal.record(&record);
+        }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            invoke_id = invoke_id,
+            service = service_choice,
+            target = %address,
+            elapsed_ms = record.elapsed.as_millis() as u64,
+            outcome = ?record.outcome,
+            "confirmed request ended"
+        );
+        if let Ok(mut log) = self.transaction_log.lock() {
+            log.push(record);
+        }
+    }
+
     /// Attach a [`ServiceHandler`](crate::server::ServiceHandler) so that incoming service
     /// requests (e.g. ReadProperty, WriteProperty, Who-Is) are dispatched inline while the
     /// client waits for responses.  This avoids the need for a separate
@@ -395,10 +1151,21 @@ impl<D: DataLink> BacnetClient<D> {
         let handler = self.server_handler.as_ref().ok_or(ClientError::Timeout)?;
         let _io_lock = self.request_io_lock.lock().await;
         let mut buf = [0u8; 1500];
-        match tokio::time::timeout(Duration::from_millis(50), self.datalink.recv(&mut buf)).await {
-            Ok(Ok((n, src))) => {
+        let deadline = self.runtime.now() + Duration::from_millis(50);
+        match self
+            .router
+            .recv(
+                &*self.datalink,
+                &*self.runtime,
+                &[InboundKind::Request],
+                &mut buf,
+                Some(deadline),
+            )
+            .await
+        {
+            Ok((n, src)) => {
                 let _ = dispatch_incoming_request(
-                    &self.datalink,
+                    &*self.datalink,
                     handler.as_ref(),
                     self.server_device_id,
                     self.server_vendor_id,
@@ -412,56 +1179,52 @@ impl<D: DataLink> BacnetClient<D> {
         }
     }
 
-    async fn next_invoke_id(&self) -> u8 {
-        let mut lock = self.invoke_id.lock().await;
-        let id = *lock;
-        *lock = lock.wrapping_add(1);
-        if *lock == 0 {
-            *lock = 1;
+    /// The NPDU header for an outgoing request.
+    fn request_npdu(&self) -> Npdu {
+        let mut npdu = Npdu::new(0);
+        npdu.set_priority(self.network_priority);
+        npdu
+    }
+
+    /// The invoke id for a new request to `address`; see [`InvokeIdTracker::allocate`].
+    ///
+    /// Fails with [`ClientError::Busy`], without taking an id, when the outstanding
+    /// transaction limit is reached.
+    async fn next_invoke_id(&self, address: DataLinkAddress) -> Result<u8, ClientError> {
+        let limit = self.memory_limits.max_outstanding_transactions;
+        let mut invoke_ids = self.invoke_ids.lock().expect("invoke id lock poisoned");
+        if invoke_ids.in_flight() >= limit {
+            return Err(ClientError::Busy { limit });
         }
-        id
+        Ok(invoke_ids.allocate(address, self.runtime.now()))
     }
 
     async fn send_segment_ack(
         &self,
         address: DataLinkAddress,
-        invoke_id: u8,
-        sequence_number: u8,
-        window_size: u8,
+        apdu: &[u8],
     ) -> Result<(), ClientError> {
         let mut tx = [0u8; 64];
         let mut w = Writer::new(&mut tx);
         Npdu::new(0).encode(&mut w)?;
-        SegmentAck {
-            negative_ack: false,
-            sent_by_server: false,
-            invoke_id,
-            sequence_number,
-            actual_window_size: window_size,
-        }
-        .encode(&mut w)?;
+        w.write_all(apdu)?;
         self.datalink.send(address, w.as_written()).await?;
         Ok(())
     }
 
+    /// Receives the next answer to one of `transactions`, or a frame nobody waits for.
     async fn recv_ignoring_invalid_frame(
         &self,
+        transactions: &[TransactionKey],
         buf: &mut [u8],
         deadline: Instant,
     ) -> Result<(usize, DataLinkAddress), ClientError> {
-        loop {
-            let remaining = deadline.saturating_duration_since(Instant::now());
-            if remaining.is_zero() {
-                return Err(ClientError::Timeout);
-            }
-
-            match timeout(remaining, self.datalink.recv(buf)).await {
-                Err(_) => return Err(ClientError::Timeout),
-                Ok(Err(DataLinkError::InvalidFrame)) => continue,
-                Ok(Err(e)) => return Err(e.into()),
-                Ok(Ok(v)) => return Ok(v),
-            }
+        if deadline <= self.runtime.now() {
+            return Err(ClientError::Timeout);
         }
+        self.router
+            .recv_transactions(&*self.datalink, &*self.runtime, transactions, buf, deadline)
+            .await
     }
 
     async fn send_simple_ack(
@@ -519,23 +1282,15 @@ impl<D: DataLink> BacnetClient<D> {
         address: DataLinkAddress,
         invoke_id: u8,
         service_choice: u8,
-        expected_sequence: u8,
+        first_sequence: u8,
+        sent: u8,
         deadline: Instant,
-    ) -> Result<SegmentAck, ClientError> {
+    ) -> Result<(SegmentAck, u8), ClientError> {
         loop {
-            let remaining = deadline.saturating_duration_since(Instant::now());
-            if remaining.is_zero() {
-                return Err(ClientError::Timeout);
-            }
-
             let mut rx = [0u8; 1500];
-            let recv = timeout(remaining, self.datalink.recv(&mut rx)).await;
-            let (n, src) = match recv {
-                Err(_) => return Err(ClientError::Timeout),
-                Ok(Err(DataLinkError::InvalidFrame)) => continue,
-                Ok(Err(e)) => return Err(e.into()),
-                Ok(Ok(v)) => v,
-            };
+            let (n, src) = self
+                .recv_ignoring_invalid_frame(&[(address, invoke_id)], &mut rx, deadline)
+                .await?;
             if src != address {
                 continue;
             }
@@ -543,52 +1298,37 @@ impl<D: DataLink> BacnetClient<D> {
             let Ok(apdu) = extract_apdu(&rx[..n]) else {
                 continue;
             };
-            let first = *apdu.first().ok_or(ClientError::UnsupportedResponse)?;
-            match ApduType::from_u8(first >> 4) {
-                Some(ApduType::SegmentAck) => {
-                    let mut r = Reader::new(apdu);
-                    let ack = SegmentAck::decode(&mut r)?;
-                    if ack.invoke_id != invoke_id || !ack.sent_by_server {
-                        continue;
-                    }
-                    if ack.negative_ack {
-                        return Err(ClientError::SegmentNegativeAck {
-                            sequence_number: ack.sequence_number,
-                        });
-                    }
-                    if ack.sequence_number == expected_sequence {
-                        return Ok(ack);
-                    }
-                }
-                Some(ApduType::Error) => {
-                    let mut r = Reader::new(apdu);
-                    let err = BacnetError::decode(&mut r)?;
-                    if err.invoke_id == invoke_id && err.service_choice == service_choice {
-                        return Err(remote_service_error(err));
-                    }
-                }
-                Some(ApduType::Reject) => {
-                    let mut r = Reader::new(apdu);
-                    let rej = RejectPdu::decode(&mut r)?;
-                    if rej.invoke_id == invoke_id {
-                        return Err(ClientError::RemoteReject { reason: rej.reason });
+            match match_response(apdu, invoke_id, service_choice)? {
+                Some(ConfirmedResponse::SegmentAck(ack)) => {
+                    match acknowledged_segments(first_sequence, sent, ack.sequence_number) {
+                        // A positive ACK for nothing new is a stale repeat.
+                        Some(0) if !ack.negative_ack => {}
+                        Some(acknowledged) => return Ok((ack, acknowledged)),
+                        None => {}
                     }
                 }
-                Some(ApduType::Abort) => {
-                    let mut r = Reader::new(apdu);
-                    let abort = AbortPdu::decode(&mut r)?;
-                    if abort.invoke_id == invoke_id {
-                        return Err(ClientError::RemoteAbort {
-                            reason: abort.reason,
-                            server: abort.server,
-                        });
+                Some(response) => {
+                    if let Some(err) = confirmed_failure(response) {
+                        return Err(err);
                     }
                 }
-                _ => continue,
+                None => continue,
             }
         }
     }
 
+    /// Sends a confirmed request, every segment of it, holding the I/O lock.
+    async fn send_request(
+        &self,
+        address: DataLinkAddress,
+        frame: &[u8],
+        deadline: Instant,
+    ) -> Result<(), ClientError> {
+        let _io_lock = self.request_io_lock.lock().await;
+        self.router.send_queued_aborts(&*self.datalink).await;
+        self.send_confirmed_request(address, frame, deadline).await
+    }
+
     async fn send_confirmed_request(
         &self,
         address: DataLinkAddress,
@@ -602,8 +1342,13 @@ impl<D: DataLink> BacnetClient<D> {
         let apdu = &frame[npdu_len..];
 
         let mut ar = Reader::new(apdu);
-        let header = ConfirmedRequestHeader::decode(&mut ar)?;
+        let mut header = ConfirmedRequestHeader::decode(&mut ar)?;
         let service_payload = ar.read_exact(ar.remaining())?;
+        header.max_segments = ConfirmedRequestHeader::max_segments_code(self.max_segments_accepted);
+        let segmentation = self.segmentation_for(address);
+        if let Some(accepted) = segmentation.segmented_response_accepted {
+            header.segmented_response_accepted = accepted;
+        }
 
         // Use the peer's max-APDU if we learned it from a prior I-Am; fall back to
         // the code declared in the request header (our own capability advertisement).
@@ -617,7 +1362,15 @@ impl<D: DataLink> BacnetClient<D> {
         let segment_count = service_payload.len().div_ceil(segment_data_len);
 
         if segment_count <= 1 {
-            self.datalink.send(address, frame).await?;
+            let mut tx = vec![0u8; frame.len()];
+            let written_len = {
+                let mut w = Writer::new(&mut tx);
+                w.write_all(npdu_bytes)?;
+                header.encode(&mut w)?;
+                w.write_all(service_payload)?;
+                w.as_written().len()
+            };
+            self.datalink.send(address, &tx[..written_len]).await?;
             return Ok(());
         }
 
@@ -625,16 +1378,20 @@ impl<D: DataLink> BacnetClient<D> {
             return Err(ClientError::SegmentedRequestTooLarge);
         }
 
-        let configured_window_size = self.segmented_request_window_size.max(1);
-        let mut window_size = configured_window_size;
-        let mut peer_window_ceiling = configured_window_size;
-        let mut batch_start = 0usize;
-        while batch_start < segment_count {
-            let batch_end = (batch_start + usize::from(window_size)).min(segment_count);
-            let expected_sequence = (batch_end - 1) as u8;
-
-            let mut frames = Vec::with_capacity(batch_end - batch_start);
-            for segment_index in batch_start..batch_end {
+        let mut window = SegmentWindow::new(
+            segmentation
+                .window_size
+                .unwrap_or(self.segmented_request_window_size)
+                .max(1),
+        );
+        let mut retries_remaining = self.segmented_request_retries;
+        // The first segment the server has not acknowledged.
+        let mut next_segment = 0usize;
+        while next_segment < segment_count {
+            let window_size = window.size();
+            let batch_end = (next_segment + usize::from(window_size)).min(segment_count);
+
+            for segment_index in next_segment..batch_end {
                 let seq = segment_index as u8;
                 let more_follows = segment_index + 1 < segment_count;
                 let start = segment_index * segment_data_len;
@@ -662,199 +1419,63 @@ impl<D: DataLink> BacnetClient<D> {
                     w.as_written().len()
                 };
                 tx.truncate(written_len);
-                frames.push(tx);
+                self.datalink.send(address, &tx).await?;
+                self.update_device_stats(address, |stats| stats.segments_sent += 1);
             }
 
-            let mut retries_remaining = self.segmented_request_retries;
-            loop {
-                for frame in &frames {
-                    self.datalink.send(address, frame).await?;
-                }
+            if batch_end == segment_count {
+                break;
+            }
 
-                if batch_end == segment_count {
-                    break;
+            let remaining = deadline.saturating_duration_since(self.runtime.now());
+            if remaining.is_zero() {
+                return Err(ClientError::Timeout);
+            }
+            let ack_wait_deadline = self.runtime.now() + remaining.min(self.segment_ack_timeout);
+            match self
+                .await_segment_ack(
+                    address,
+                    header.invoke_id,
+                    header.service_choice,
+                    next_segment as u8,
+                    (batch_end - next_segment) as u8,
+                    ack_wait_deadline,
+                )
+                .await
+            {
+                Ok((ack, acknowledged)) => {
+                    // Slide past what the server has; after a negative ACK only the
+                    // segments from the first missing one are sent again.
+                    next_segment += usize::from(acknowledged);
+                    if ack.negative_ack {
+                        if retries_remaining == 0 {
+                            return Err(ClientError::SegmentNegativeAck {
+                                sequence_number: ack.sequence_number,
+                            });
+                        }
+                        retries_remaining -= 1;
+                        window.on_loss();
+                    } else {
+                        retries_remaining = self.segmented_request_retries;
+                        window.on_ack(ack.actual_window_size);
+                    }
                 }
-
-                let remaining = deadline.saturating_duration_since(Instant::now());
-                if remaining.is_zero() {
-                    return Err(ClientError::Timeout);
-                }
-                let ack_wait_deadline = Instant::now() + remaining.min(self.segment_ack_timeout);
-                match self
-                    .await_segment_ack(
-                        address,
-                        header.invoke_id,
-                        header.service_choice,
-                        expected_sequence,
-                        ack_wait_deadline,
-                    )
-                    .await
-                {
-                    Ok(ack) => {
-                        peer_window_ceiling =
-                            peer_window_ceiling.min(ack.actual_window_size.max(1));
-                        window_size = window_size
-                            .saturating_add(1)
-                            .min(configured_window_size)
-                            .min(peer_window_ceiling)
-                            .max(1);
-                        break;
-                    }
-                    Err(ClientError::Timeout | ClientError::SegmentNegativeAck { .. })
-                        if retries_remaining > 0 =>
-                    {
-                        retries_remaining -= 1;
-                        window_size = window_size.saturating_div(2).max(1);
-                        continue;
-                    }
-                    Err(e) => return Err(e),
+                Err(ClientError::Timeout) if retries_remaining > 0 => {
+                    retries_remaining -= 1;
+                    window.on_loss();
                 }
+                Err(e) => return Err(e),
             }
-
-            batch_start = batch_end;
         }
 
         Ok(())
     }
 
-    async fn collect_complex_ack_payload(
-        &self,
-        address: DataLinkAddress,
-        invoke_id: u8,
-        service_choice: u8,
-        first_header: ComplexAckHeader,
-        first_payload: &[u8],
-        deadline: Instant,
-    ) -> Result<Vec<u8>, ClientError> {
-        let mut payload = first_payload.to_vec();
-        if payload.len() > MAX_COMPLEX_ACK_REASSEMBLY_BYTES {
-            return Err(ClientError::ResponseTooLarge {
-                limit: MAX_COMPLEX_ACK_REASSEMBLY_BYTES,
-            });
-        }
-        if !first_header.segmented {
-            return Ok(payload);
-        }
-
-        let mut last_seq = first_header
-            .sequence_number
-            .ok_or(ClientError::UnsupportedResponse)?;
-        let mut window_size = first_header.proposed_window_size.unwrap_or(1);
-        self.send_segment_ack(address, invoke_id, last_seq, window_size)
-            .await?;
-        let mut more_follows = first_header.more_follows;
-
-        while more_follows {
-            let mut rx = [0u8; 1500];
-            let (n, src) = self.recv_ignoring_invalid_frame(&mut rx, deadline).await?;
-            if src != address {
-                // Try to dispatch as an incoming server request
-                if let Some(ref handler) = self.server_handler {
-                    let _ = dispatch_incoming_request(
-                        &self.datalink,
-                        handler.as_ref(),
-                        self.server_device_id,
-                        self.server_vendor_id,
-                        &rx[..n],
-                        src,
-                    )
-                    .await;
-                }
-                continue;
-            }
-
-            let Ok(apdu) = extract_apdu(&rx[..n]) else {
-                continue;
-            };
-            let first = *apdu.first().ok_or(ClientError::UnsupportedResponse)?;
-            match ApduType::from_u8(first >> 4) {
-                Some(ApduType::ComplexAck) => {
-                    let mut r = Reader::new(apdu);
-                    let seg = ComplexAckHeader::decode(&mut r)?;
-                    if seg.invoke_id != invoke_id || seg.service_choice != service_choice {
-                        continue;
-                    }
-                    if !seg.segmented {
-                        return Err(ClientError::UnsupportedResponse);
-                    }
-                    let seq = seg
-                        .sequence_number
-                        .ok_or(ClientError::UnsupportedResponse)?;
-                    if seq == last_seq {
-                        // Duplicate segment: acknowledge again and continue waiting.
-                        self.send_segment_ack(address, invoke_id, last_seq, window_size)
-                            .await?;
-                        continue;
-                    }
-                    if seq != last_seq.wrapping_add(1) {
-                        continue;
-                    }
-
-                    let seg_payload = r.read_exact(r.remaining())?;
-                    if payload.len().saturating_add(seg_payload.len())
-                        > MAX_COMPLEX_ACK_REASSEMBLY_BYTES
-                    {
-                        return Err(ClientError::ResponseTooLarge {
-                            limit: MAX_COMPLEX_ACK_REASSEMBLY_BYTES,
-                        });
-                    }
-                    payload.extend_from_slice(seg_payload);
-
-                    last_seq = seq;
-                    more_follows = seg.more_follows;
-                    window_size = seg.proposed_window_size.unwrap_or(window_size);
-                    self.send_segment_ack(address, invoke_id, last_seq, window_size)
-                        .await?;
-                }
-                Some(ApduType::Error) => {
-                    let mut r = Reader::new(apdu);
-                    let err = BacnetError::decode(&mut r)?;
-                    if err.invoke_id == invoke_id && err.service_choice == service_choice {
-                        return Err(remote_service_error(err));
-                    }
-                }
-                Some(ApduType::Reject) => {
-                    let mut r = Reader::new(apdu);
-                    let rej = RejectPdu::decode(&mut r)?;
-                    if rej.invoke_id == invoke_id {
-                        return Err(ClientError::RemoteReject { reason: rej.reason });
-                    }
-                }
-                Some(ApduType::Abort) => {
-                    let mut r = Reader::new(apdu);
-                    let abort = AbortPdu::decode(&mut r)?;
-                    if abort.invoke_id == invoke_id {
-                        return Err(ClientError::RemoteAbort {
-                            reason: abort.reason,
-                            server: abort.server,
-                        });
-                    }
-                }
-                _ => {
-                    // Try to dispatch as an incoming server request
-                    if let Some(ref handler) = self.server_handler {
-                        let _ = dispatch_incoming_request(
-                            &self.datalink,
-                            handler.as_ref(),
-                            self.server_device_id,
-                            self.server_vendor_id,
-                            &rx[..n],
-                            src,
-                        )
-                        .await;
-                    }
-                    continue;
-                }
-            }
-        }
-
-        Ok(payload)
-    }
-
     /// Broadcast a Who-Is request and collect I-Am replies for the duration of `wait`.
     ///
     /// `range` constrains the device-instance range as `(low, high)`; `None` performs a
-    /// global Who-Is. Duplicate devices (same object id) are deduplicated. Returns
+    /// global Who-Is. Replies from the same device instance are merged as set by
+    /// [`with_discovery_dedup`](Self::with_discovery_dedup). Returns
     /// [`ClientError::DataLink`] on send/receive failure.
     pub async fn who_is(
         &self,
@@ -883,16 +1504,24 @@ impl<D: DataLink> BacnetClient<D> {
             )
             .await?;
 
-        let mut devices = Vec::new();
-        let mut seen = HashSet::new();
-        let deadline = tokio::time::Instant::now() + wait;
+        let mut devices: Vec<DiscoveredDevice> = Vec::new();
+        let mut seen: HashMap<_, usize> = HashMap::new();
+        let deadline = self.runtime.now() + wait;
 
-        while tokio::time::Instant::now() < deadline {
-            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        while self.runtime.now() < deadline {
             let mut rx = [0u8; 1500];
-            let recv = timeout(remaining, self.datalink.recv(&mut rx)).await;
+            let recv = self
+                .router
+                .recv(
+                    &*self.datalink,
+                    &*self.runtime,
+                    &[InboundKind::Discovery],
+                    &mut rx,
+                    Some(deadline),
+                )
+                .await;
             match recv {
-                Ok(Ok((n, src))) => {
+                Ok((n, src)) => {
                     let Ok(apdu) = extract_apdu(&rx[..n]) else {
                         continue;
                     };
@@ -906,21 +1535,31 @@ impl<D: DataLink> BacnetClient<D> {
                     let Ok(i_am) = IAmRequest::decode_after_header(&mut r) else {
                         continue;
                     };
-                    if seen.insert(i_am.device_id) {
-                        devices.push(DiscoveredDevice {
-                            address: src,
-                            device_id: Some(i_am.device_id),
-                        });
-                        // Cache the peer's reported max-APDU so segmented
-                        // requests can be sized correctly without a separate read.
-                        if let Ok(mut cache) = self.capability_cache.write() {
-                            cache.insert(src, i_am.max_apdu as usize);
+                    let key = match self.discovery_dedup {
+                        DiscoveryDedup::Off => (i_am.device_id, Some(src)),
+                        _ => (i_am.device_id, None),
+                    };
+                    match seen.get(&key) {
+                        Some(&index) if self.discovery_dedup == DiscoveryDedup::LatestAddress => {
+                            devices[index].address = src;
+                        }
+                        Some(_) => continue,
+                        None => {
+                            seen.insert(key, devices.len());
+                            devices.push(DiscoveredDevice {
+                                address: src,
+                                device_id: Some(i_am.device_id),
+                            });
                         }
                     }
+                    // Cache the peer's reported max-APDU so segmented
+                    // requests can be sized correctly without a separate read.
+                    if let Ok(mut cache) = self.capability_cache.write() {
+                        cache.insert(src, i_am.max_apdu as usize);
+                    }
                 }
-                Ok(Err(DataLinkError::InvalidFrame)) => continue,
-                Ok(Err(e)) => return Err(e.into()),
-                Err(_) => break,
+                Err(ClientError::Timeout) => break,
+                Err(e) => return Err(e),
             }
         }
 
@@ -969,7 +1608,7 @@ impl<D: DataLink> BacnetClient<D> {
     ) -> Result<Vec<DiscoveredObject>, ClientError> {
         // Unconfirmed broadcast — same rationale as who_is.
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
+            self.request_npdu().encode(w)?;
             request.encode(w)
         })?;
         self.datalink
@@ -981,14 +1620,22 @@ impl<D: DataLink> BacnetClient<D> {
 
         let mut objects = Vec::new();
         let mut seen = HashSet::new();
-        let deadline = tokio::time::Instant::now() + wait;
+        let deadline = self.runtime.now() + wait;
 
-        while tokio::time::Instant::now() < deadline {
-            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        while self.runtime.now() < deadline {
             let mut rx = [0u8; 1500];
-            let recv = timeout(remaining, self.datalink.recv(&mut rx)).await;
+            let recv = self
+                .router
+                .recv(
+                    &*self.datalink,
+                    &*self.runtime,
+                    &[InboundKind::Discovery],
+                    &mut rx,
+                    Some(deadline),
+                )
+                .await;
             match recv {
-                Ok(Ok((n, src))) => {
+                Ok((n, src)) => {
                     let Ok(apdu) = extract_apdu(&rx[..n]) else {
                         continue;
                     };
@@ -1012,15 +1659,102 @@ impl<D: DataLink> BacnetClient<D> {
                         object_name: i_have.object_name.to_string(),
                     });
                 }
-                Ok(Err(DataLinkError::InvalidFrame)) => continue,
-                Ok(Err(e)) => return Err(e.into()),
-                Err(_) => break,
+                Err(ClientError::Timeout) => break,
+                Err(e) => return Err(e),
             }
         }
 
         Ok(objects)
     }
 
+    /// Resolve an object identifier on the device at `address` from its Object_Name.
+    ///
+    /// Tries a Who-Has by name first, accepting only I-Have replies from `address`. Devices
+    /// that do not answer Who-Has are resolved by reading the Object_List and then the
+    /// Object_Name of each object; every name seen along the way is cached, so later lookups
+    /// on the same device are answered without network traffic. Returns `Ok(None)` if no
+    /// object carries that name.
+    pub async fn resolve_object_by_name(
+        &self,
+        address: DataLinkAddress,
+        name: &str,
+    ) -> Result<Option<ObjectId>, ClientError> {
+        let key = (address, name.to_string());
+        if let Some(object_id) = self
+            .object_name_cache
+            .read()
+            .ok()
+            .and_then(|cache| cache.get(&key).copied())
+        {
+            return Ok(Some(object_id));
+        }
+
+        let replies = self
+            .who_has_object_name(None, name, self.response_timeout)
+            .await?;
+        if let Some(found) = replies
+            .into_iter()
+            .find(|obj| obj.address == address && obj.object_name == name)
+        {
+            self.cache_object_name(address, name.to_string(), found.object_id);
+            return Ok(Some(found.object_id));
+        }
+
+        // Walk Object_List one element at a time: it works on devices without segmentation
+        // and stops as soon as the name is found.
+        let device = ObjectId::new(ObjectType::Device, WILDCARD_DEVICE_INSTANCE);
+        let count = match self
+            .read_property_indexed(address, device, PropertyId::ObjectList, Some(0))
+            .await?
+        {
+            ClientDataValue::Unsigned(count) => count,
+            _ => return Err(ClientError::UnsupportedResponse),
+        };
+
+        let mut resolved = None;
+        for index in 1..=count {
+            let object_id = match self
+                .read_property_indexed(address, device, PropertyId::ObjectList, Some(index))
+                .await?
+            {
+                ClientDataValue::ObjectId(object_id) => object_id,
+                _ => return Err(ClientError::UnsupportedResponse),
+            };
+            let value = match self
+                .read_property(address, object_id, PropertyId::ObjectName)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) if matches!(err.root(), ClientError::RemoteServiceError { .. }) => {
+                    continue
+                }
+                Err(err) => return Err(err),
+            };
+            if let ClientDataValue::CharacterString(object_name) = value {
+                let matched = &*object_name == name;
+                self.cache_object_name(address, object_name.to_string(), object_id);
+                if matched {
+                    resolved = Some(object_id);
+                    break;
+                }
+            }
+        }
+        Ok(resolved)
+    }
+
+    /// Drop all cached name resolutions for `address`, e.g. after the device was reconfigured.
+    pub fn clear_object_name_cache(&self, address: DataLinkAddress) {
+        if let Ok(mut cache) = self.object_name_cache.write() {
+            cache.retain(|(cached_address, _), _| *cached_address != address);
+        }
+    }
+
+    fn cache_object_name(&self, address: DataLinkAddress, name: String, object_id: ObjectId) {
+        if let Ok(mut cache) = self.object_name_cache.write() {
+            cache.insert((address, name), object_id);
+        }
+    }
+
     /// Send a DeviceCommunicationControl request to a device.
     ///
     /// `time_duration_seconds` sets the duration for which the state applies; `None` means
@@ -1033,7 +1767,11 @@ impl<D: DataLink> BacnetClient<D> {
         enable_disable: DeviceCommunicationState,
         password: Option<&str>,
     ) -> Result<(), ClientError> {
-        let invoke_id = self.next_invoke_id().await;
+        self.check_write_policy(address, || WriteAction::DeviceCommunicationControl {
+            state: enable_disable,
+            time_duration_seconds,
+        })?;
+        let invoke_id = self.next_invoke_id(address).await?;
         let request = DeviceCommunicationControlRequest {
             time_duration_seconds,
             enable_disable,
@@ -1041,17 +1779,26 @@ impl<D: DataLink> BacnetClient<D> {
             invoke_id,
         };
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
+            self.request_npdu().encode(w)?;
             request.encode(w)
         })?;
-        self.await_simple_ack_or_error(
-            address,
-            &tx,
-            invoke_id,
-            SERVICE_DEVICE_COMMUNICATION_CONTROL,
-            self.response_timeout,
-        )
-        .await
+        let timestamp = SystemTime::now();
+        let result = self
+            .await_simple_ack_or_error(
+                address,
+                &tx,
+                invoke_id,
+                SERVICE_DEVICE_COMMUNICATION_CONTROL,
+                self.response_timeout,
+            )
+            .await;
+        self.audit_write(address, timestamp, &result, |_| {
+            WriteAction::DeviceCommunicationControl {
+                state: enable_disable,
+                time_duration_seconds,
+            }
+        });
+        result
     }
 
     /// Send a ReinitializeDevice request to a device (e.g. cold-start, warm-start, or backup).
@@ -1063,24 +1810,103 @@ impl<D: DataLink> BacnetClient<D> {
         state: ReinitializeState,
         password: Option<&str>,
     ) -> Result<(), ClientError> {
-        let invoke_id = self.next_invoke_id().await;
+        self.check_write_policy(address, || WriteAction::ReinitializeDevice { state })?;
+        let invoke_id = self.next_invoke_id(address).await?;
         let request = ReinitializeDeviceRequest {
             state,
             password,
             invoke_id,
         };
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
+            self.request_npdu().encode(w)?;
             request.encode(w)
         })?;
-        self.await_simple_ack_or_error(
-            address,
-            &tx,
-            invoke_id,
-            SERVICE_REINITIALIZE_DEVICE,
-            self.response_timeout,
-        )
-        .await
+        let timestamp = SystemTime::now();
+        let result = self
+            .await_simple_ack_or_error(
+                address,
+                &tx,
+                invoke_id,
+                SERVICE_REINITIALIZE_DEVICE,
+                self.response_timeout,
+            )
+            .await;
+        self.audit_write(address, timestamp, &result, |_| {
+            WriteAction::ReinitializeDevice { state }
+        });
+        result
+    }
+
+    /// Read the configuration of the Network Port object `port`.
+    ///
+    /// Each property is read with ReadProperty; ones the port does not have (the device
+    /// answers with an Error) are left `None` in the result.
+    pub async fn read_network_port(
+        &self,
+        address: DataLinkAddress,
+        port: ObjectId,
+    ) -> Result<NetworkPortConfig, ClientError> {
+        let values = self
+            .read_optional_properties(address, port, NETWORK_PORT_PROPERTIES)
+            .await?;
+        Ok(NetworkPortConfig::from_properties(&values))
+    }
+
+    /// Whether the Network Port object `port` has written changes waiting for activation.
+    pub async fn network_port_changes_pending(
+        &self,
+        address: DataLinkAddress,
+        port: ObjectId,
+    ) -> Result<bool, ClientError> {
+        match self
+            .read_property(address, port, PropertyId::ChangesPending)
+            .await?
+        {
+            ClientDataValue::Boolean(pending) => Ok(pending),
+            _ => Err(ClientError::UnsupportedResponse),
+        }
+    }
+
+    /// Write new IP settings to the Network Port object `port`.
+    ///
+    /// The device keeps its current settings and sets `Changes_Pending`; call
+    /// [`activate_network_port_changes`](Self::activate_network_port_changes) to apply them.
+    pub async fn stage_network_port_ip(
+        &self,
+        address: DataLinkAddress,
+        port: ObjectId,
+        change: &NetworkPortIpChange,
+    ) -> Result<(), ClientError> {
+        for (property_id, value) in change.writes() {
+            let request = WritePropertyRequest {
+                object_id: port,
+                property_id,
+                value: dispatch_client_value_to_borrowed(&value),
+                ..Default::default()
+            };
+            self.write_property(address, request).await?;
+        }
+        Ok(())
+    }
+
+    /// Apply the changes staged on the Network Port object `port` by sending
+    /// ReinitializeDevice `ACTIVATE_CHANGES`.
+    ///
+    /// Returns `false` without reinitializing when `Changes_Pending` is not set. The device
+    /// may drop off the network while it reconfigures, so address it by its new settings
+    /// afterwards.
+    pub async fn activate_network_port_changes(
+        &self,
+        address: DataLinkAddress,
+        port: ObjectId,
+        password: Option<&str>,
+    ) -> Result<bool, ClientError> {
+        if !self.network_port_changes_pending(address, port).await? {
+            return Ok(false);
+        }
+        self.reinitialize_device(address, ReinitializeState::ActivateChanges, password)
+            .await?;
+        Ok(true)
     }
 
     /// Send a TimeSynchronization (or UTCTimeSynchronization) request to a device.
@@ -1100,13 +1926,175 @@ impl<D: DataLink> BacnetClient<D> {
             TimeSynchronizationRequest::local(date, time)
         };
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
+            self.request_npdu().encode(w)?;
             request.encode(w)
         })?;
         self.datalink.send(address, &tx).await?;
         Ok(())
     }
 
+    /// Send a UTCTimeSynchronization request carrying `utc` to each of `targets`.
+    ///
+    /// Targets may be device addresses or broadcast addresses, one per network to reach
+    /// every device on it. Sends start at least `min_interval` apart so a long list does
+    /// not flood slow links, and each request carries `utc` advanced by the time spent
+    /// getting to it. A failed send is recorded and the run continues.
+    pub async fn time_synchronize_all(
+        &self,
+        targets: &[DataLinkAddress],
+        utc: SystemTime,
+        min_interval: Duration,
+    ) -> TimeSyncReport {
+        self.time_synchronize_each(targets, utc, None, min_interval)
+            .await
+    }
+
+    /// Sends `utc` to each of `targets` as UTC, or as local time for `local_utc_offset`.
+    pub(crate) async fn time_synchronize_each(
+        &self,
+        targets: &[DataLinkAddress],
+        utc: SystemTime,
+        local_utc_offset: Option<i32>,
+        min_interval: Duration,
+    ) -> TimeSyncReport {
+        let started = self.runtime.now();
+        let mut report = TimeSyncReport::default();
+        for (index, &address) in targets.iter().enumerate() {
+            if index > 0 && !min_interval.is_zero() {
+                self.runtime.sleep(min_interval).await;
+            }
+            let now = utc + (self.runtime.now() - started);
+            let (date, time) = match local_utc_offset {
+                Some(offset) => local_date_time(now, offset),
+                None => bacnet_date_time(now),
+            };
+            match self
+                .time_synchronize(address, date, time, local_utc_offset.is_none())
+                .await
+            {
+                Ok(()) => report.synchronized.push(address),
+                Err(error) => report.failed.push(TimeSyncFailure { address, error }),
+            }
+        }
+        report
+    }
+
+    /// Read a device's Local_Date, Local_Time, UTC_Offset and Daylight_Savings_Status to
+    /// compare its clock with the host's.
+    ///
+    /// UTC_Offset and Daylight_Savings_Status are optional and left `None` when the device
+    /// rejects them; Local_Date and Local_Time are required.
+    pub async fn read_device_clock(
+        &self,
+        address: DataLinkAddress,
+    ) -> Result<DeviceClock, ClientError> {
+        let device = ObjectId::new(ObjectType::Device, WILDCARD_DEVICE_INSTANCE);
+        let utc_offset = match self
+            .read_property(address, device, PropertyId::UtcOffset)
+            .await
+        {
+            Ok(ClientDataValue::Signed(minutes)) => Some(minutes),
+            Ok(ClientDataValue::Unsigned(minutes)) => {
+                Some(i32::try_from(minutes).map_err(|_| ClientError::UnsupportedResponse)?)
+            }
+            Ok(_) => return Err(ClientError::UnsupportedResponse),
+            Err(err) if matches!(err.root(), ClientError::RemoteServiceError { .. }) => None,
+            Err(err) => return Err(err),
+        };
+        let daylight_savings = match self
+            .read_property(address, device, PropertyId::DaylightSavingsStatus)
+            .await
+        {
+            Ok(ClientDataValue::Boolean(active)) => Some(active),
+            Ok(_) => return Err(ClientError::UnsupportedResponse),
+            Err(err) if matches!(err.root(), ClientError::RemoteServiceError { .. }) => None,
+            Err(err) => return Err(err),
+        };
+        let ClientDataValue::Date(date) = self
+            .read_property(address, device, PropertyId::LocalDate)
+            .await?
+        else {
+            return Err(ClientError::UnsupportedResponse);
+        };
+        let before = SystemTime::now();
+        let ClientDataValue::Time(time) = self
+            .read_property(address, device, PropertyId::LocalTime)
+            .await?
+        else {
+            return Err(ClientError::UnsupportedResponse);
+        };
+        let elapsed = SystemTime::now().duration_since(before).unwrap_or_default();
+        Ok(DeviceClock {
+            date,
+            time,
+            utc_offset_minutes: utc_offset.map(|offset| {
+                offset
+                    - if daylight_savings == Some(true) {
+                        60
+                    } else {
+                        0
+                    }
+            }),
+            daylight_savings,
+            read_at: before + elapsed / 2,
+        })
+    }
+
+    /// Read the clock of each of `targets` and compare it with the host's, for finding
+    /// devices with drifted clocks or a wrong UTC offset or daylight saving status.
+    ///
+    /// Devices are read one after another; a device whose clock cannot be read is recorded
+    /// and the sweep continues.
+    pub async fn audit_device_clocks(
+        &self,
+        targets: &[DataLinkAddress],
+        threshold: Duration,
+    ) -> ClockAuditReport {
+        let mut report = ClockAuditReport {
+            threshold,
+            clocks: Vec::new(),
+            failed: Vec::new(),
+        };
+        for &address in targets {
+            match self.read_device_clock(address).await {
+                Ok(clock) => report.clocks.push(ClockAuditEntry {
+                    address,
+                    drift_millis: clock.drift_millis(),
+                    clock,
+                }),
+                Err(error) => report.failed.push(ClockReadFailure { address, error }),
+            }
+        }
+        report
+    }
+
+    /// Start a [`TimeMaster`] that synchronizes `config.recipients` on its schedule and
+    /// checks the clocks of `config.drift_check` after each round.
+    ///
+    /// A checked device without UTC_Offset is taken to keep the time the master sends:
+    /// local time for [`local_utc_offset_minutes`](TimeMasterConfig::local_utc_offset_minutes),
+    /// UTC otherwise. The master runs on its own thread driven by the current Tokio
+    /// runtime; dropping the handle (or calling `.stop()`) ends it.
+    pub fn start_time_master(&self, config: TimeMasterConfig) -> Result<TimeMaster, ClientError>
+    where
+        D: 'static,
+    {
+        let runtime_handle =
+            tokio::runtime::Handle::try_current().map_err(|_| ClientError::NoTokioRuntime)?;
+        let (shutdown, mut shutdown_rx) = watch::channel(false);
+        let (status_tx, status) = watch::channel(TimeMasterStatus::default());
+        let client = self.clone();
+        std::thread::spawn(move || {
+            runtime_handle.block_on(async move {
+                tokio::select! {
+                    _ = shutdown_rx.wait_for(|stop| *stop) => {}
+                    () = run_time_master(&client, &config, &status_tx) => {}
+                }
+            });
+        });
+        Ok(TimeMaster { shutdown, status })
+    }
+
     /// Create a new object of the given type on the device, letting the device choose the
     /// instance number. Returns the [`ObjectId`] assigned by the device.
     pub async fn create_object_by_type(
@@ -1126,13 +2114,18 @@ impl<D: DataLink> BacnetClient<D> {
         address: DataLinkAddress,
         mut request: CreateObjectRequest,
     ) -> Result<ObjectId, ClientError> {
-        request.invoke_id = self.next_invoke_id().await;
+        self.check_write_policy(address, || WriteAction::CreateObject {
+            specifier: request.specifier,
+            created: None,
+        })?;
+        request.invoke_id = self.next_invoke_id(address).await?;
         let invoke_id = request.invoke_id;
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
+            self.request_npdu().encode(w)?;
             request.encode(w)
         })?;
-        let payload = self
+        let timestamp = SystemTime::now();
+        let result = self
             .await_complex_ack_payload_or_error(
                 address,
                 &tx,
@@ -1140,10 +2133,18 @@ impl<D: DataLink> BacnetClient<D> {
                 SERVICE_CREATE_OBJECT,
                 self.response_timeout,
             )
-            .await?;
-        let mut pr = Reader::new(&payload);
-        let parsed = CreateObjectAck::decode_after_header(&mut pr)?;
-        Ok(parsed.object_id)
+            .await
+            .and_then(|payload| {
+                let mut pr = Reader::new(&payload);
+                Ok(CreateObjectAck::decode_after_header(&mut pr)?.object_id)
+            });
+        self.audit_write(address, timestamp, &result, |created| {
+            WriteAction::CreateObject {
+                specifier: request.specifier,
+                created: created.copied(),
+            }
+        });
+        result
     }
 
     /// Send a DeleteObject request to remove `object_id` from the device.
@@ -1152,23 +2153,30 @@ impl<D: DataLink> BacnetClient<D> {
         address: DataLinkAddress,
         object_id: ObjectId,
     ) -> Result<(), ClientError> {
-        let invoke_id = self.next_invoke_id().await;
+        self.check_write_policy(address, || WriteAction::DeleteObject { object_id })?;
+        let invoke_id = self.next_invoke_id(address).await?;
         let request = DeleteObjectRequest {
             object_id,
             invoke_id,
         };
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
+            self.request_npdu().encode(w)?;
             request.encode(w)
         })?;
-        self.await_simple_ack_or_error(
-            address,
-            &tx,
-            invoke_id,
-            SERVICE_DELETE_OBJECT,
-            self.response_timeout,
-        )
-        .await
+        let timestamp = SystemTime::now();
+        let result = self
+            .await_simple_ack_or_error(
+                address,
+                &tx,
+                invoke_id,
+                SERVICE_DELETE_OBJECT,
+                self.response_timeout,
+            )
+            .await;
+        self.audit_write(address, timestamp, &result, |_| WriteAction::DeleteObject {
+            object_id,
+        });
+        result
     }
 
     /// Send an AddListElement request to append elements to a list property on the device.
@@ -1177,10 +2185,10 @@ impl<D: DataLink> BacnetClient<D> {
         address: DataLinkAddress,
         mut request: AddListElementRequest<'_>,
     ) -> Result<(), ClientError> {
-        request.invoke_id = self.next_invoke_id().await;
+        request.invoke_id = self.next_invoke_id(address).await?;
         let invoke_id = request.invoke_id;
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
+            self.request_npdu().encode(w)?;
             request.encode(w)
         })?;
         self.await_simple_ack_or_error(
@@ -1199,10 +2207,10 @@ impl<D: DataLink> BacnetClient<D> {
         address: DataLinkAddress,
         mut request: RemoveListElementRequest<'_>,
     ) -> Result<(), ClientError> {
-        request.invoke_id = self.next_invoke_id().await;
+        request.invoke_id = self.next_invoke_id(address).await?;
         let invoke_id = request.invoke_id;
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
+            self.request_npdu().encode(w)?;
             request.encode(w)
         })?;
         self.await_simple_ack_or_error(
@@ -1215,790 +2223,760 @@ impl<D: DataLink> BacnetClient<D> {
         .await
     }
 
-    async fn await_simple_ack_or_error(
+    /// Write `value` to the Present_Value of the Channel object `channel`.
+    ///
+    /// The channel writes the value through to every member of its
+    /// List_Of_Object_Property_References at `priority` (16 when `None`); it keeps no
+    /// priority array of its own. The write is acknowledged before the members are
+    /// updated, so check [`channel_write_status`](Self::channel_write_status) to learn
+    /// whether they all accepted it.
+    pub async fn write_channel(
         &self,
         address: DataLinkAddress,
-        tx: &[u8],
-        invoke_id: u8,
-        service_choice: u8,
-        timeout_window: Duration,
+        channel: ObjectId,
+        value: ChannelValue<'_>,
+        priority: Option<u8>,
     ) -> Result<(), ClientError> {
-        #[cfg(feature = "tracing")]
-        tracing::debug!(invoke_id = invoke_id, service = service_choice, target = %address, "sending confirmed request");
-        let _io_lock = self.request_io_lock.lock().await;
-        let deadline = tokio::time::Instant::now() + timeout_window;
-        self.send_confirmed_request(address, tx, deadline).await?;
-        while tokio::time::Instant::now() < deadline {
-            let mut rx = [0u8; 1500];
-            let (n, src) = self.recv_ignoring_invalid_frame(&mut rx, deadline).await?;
-            if src != address {
-                // Try to dispatch as an incoming server request
-                if let Some(ref handler) = self.server_handler {
-                    let _ = dispatch_incoming_request(
-                        &self.datalink,
-                        handler.as_ref(),
-                        self.server_device_id,
-                        self.server_vendor_id,
-                        &rx[..n],
-                        src,
-                    )
-                    .await;
-                }
-                continue;
-            }
-            let apdu = extract_apdu(&rx[..n])?;
-            let first = *apdu.first().ok_or(ClientError::UnsupportedResponse)?;
-            match ApduType::from_u8(first >> 4) {
-                Some(ApduType::SimpleAck) => {
-                    let mut r = Reader::new(apdu);
-                    let ack = SimpleAck::decode(&mut r)?;
-                    if ack.invoke_id == invoke_id && ack.service_choice == service_choice {
-                        return Ok(());
-                    }
-                }
-                Some(ApduType::Error) => {
-                    let mut r = Reader::new(apdu);
-                    let err = BacnetError::decode(&mut r)?;
-                    if err.invoke_id == invoke_id && err.service_choice == service_choice {
-                        return Err(remote_service_error(err));
-                    }
-                }
-                Some(ApduType::Reject) => {
-                    let mut r = Reader::new(apdu);
-                    let rej = RejectPdu::decode(&mut r)?;
-                    if rej.invoke_id == invoke_id {
-                        return Err(ClientError::RemoteReject { reason: rej.reason });
-                    }
-                }
-                Some(ApduType::Abort) => {
-                    let mut r = Reader::new(apdu);
-                    let abort = AbortPdu::decode(&mut r)?;
-                    if abort.invoke_id == invoke_id {
-                        return Err(ClientError::RemoteAbort {
-                            reason: abort.reason,
-                            server: abort.server,
-                        });
-                    }
-                }
-                _ => {
-                    // Try to dispatch as an incoming server request
-                    if let Some(ref handler) = self.server_handler {
-                        let _ = dispatch_incoming_request(
-                            &self.datalink,
-                            handler.as_ref(),
-                            self.server_device_id,
-                            self.server_vendor_id,
-                            &rx[..n],
-                            src,
-                        )
-                        .await;
-                    }
-                    continue;
-                }
-            }
-        }
-        #[cfg(feature = "tracing")]
-        tracing::warn!(invoke_id = invoke_id, "request timed out");
-        Err(ClientError::Timeout)
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = WriteChannelRequest {
+            channel,
+            value,
+            priority,
+            invoke_id,
+        };
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            request.encode(w)
+        })?;
+        self.await_simple_ack_or_error(
+            address,
+            &tx,
+            invoke_id,
+            SERVICE_WRITE_PROPERTY,
+            self.response_timeout,
+        )
+        .await
     }
 
-    async fn await_complex_ack_payload_or_error(
+    /// Read the Write_Status of the Channel object `channel`: whether the last write to its
+    /// Present_Value is still being passed on to the members, and whether that succeeded.
+    pub async fn channel_write_status(
         &self,
         address: DataLinkAddress,
-        tx: &[u8],
-        invoke_id: u8,
-        service_choice: u8,
-        timeout_window: Duration,
-    ) -> Result<Vec<u8>, ClientError> {
-        #[cfg(feature = "tracing")]
-        tracing::debug!(invoke_id = invoke_id, service = service_choice, target = %address, "sending confirmed request");
-        let _io_lock = self.request_io_lock.lock().await;
-        let deadline = tokio::time::Instant::now() + timeout_window;
-        self.send_confirmed_request(address, tx, deadline).await?;
-        while tokio::time::Instant::now() < deadline {
-            let mut rx = [0u8; 1500];
-            let (n, src) = self.recv_ignoring_invalid_frame(&mut rx, deadline).await?;
-            if src != address {
-                // Try to dispatch as an incoming server request
-                if let Some(ref handler) = self.server_handler {
-                    let _ = dispatch_incoming_request(
-                        &self.datalink,
-                        handler.as_ref(),
-                        self.server_device_id,
-                        self.server_vendor_id,
-                        &rx[..n],
-                        src,
-                    )
-                    .await;
-                }
-                continue;
-            }
-
-            let apdu = extract_apdu(&rx[..n])?;
-            let first = *apdu.first().ok_or(ClientError::UnsupportedResponse)?;
-            match ApduType::from_u8(first >> 4) {
-                Some(ApduType::ComplexAck) => {
-                    let mut r = Reader::new(apdu);
-                    let ack = ComplexAckHeader::decode(&mut r)?;
-                    if ack.invoke_id != invoke_id || ack.service_choice != service_choice {
-                        continue;
-                    }
-                    return self
-                        .collect_complex_ack_payload(
-                            address,
-                            invoke_id,
-                            service_choice,
-                            ack,
-                            r.read_exact(r.remaining())?,
-                            deadline,
-                        )
-                        .await;
-                }
-                Some(ApduType::Error) => {
-                    let mut r = Reader::new(apdu);
-                    let err = BacnetError::decode(&mut r)?;
-                    if err.invoke_id == invoke_id && err.service_choice == service_choice {
-                        return Err(remote_service_error(err));
-                    }
-                }
-                Some(ApduType::Reject) => {
-                    let mut r = Reader::new(apdu);
-                    let rej = RejectPdu::decode(&mut r)?;
-                    if rej.invoke_id == invoke_id {
-                        return Err(ClientError::RemoteReject { reason: rej.reason });
-                    }
-                }
-                Some(ApduType::Abort) => {
-                    let mut r = Reader::new(apdu);
-                    let abort = AbortPdu::decode(&mut r)?;
-                    if abort.invoke_id == invoke_id {
-                        return Err(ClientError::RemoteAbort {
-                            reason: abort.reason,
-                            server: abort.server,
-                        });
-                    }
-                }
-                _ => {
-                    // Try to dispatch as an incoming server request
-                    if let Some(ref handler) = self.server_handler {
-                        let _ = dispatch_incoming_request(
-                            &self.datalink,
-                            handler.as_ref(),
-                            self.server_device_id,
-                            self.server_vendor_id,
-                            &rx[..n],
-                            src,
-                        )
-                        .await;
-                    }
-                    continue;
-                }
+        channel: ObjectId,
+    ) -> Result<WriteStatus, ClientError> {
+        match self
+            .read_property(address, channel, PropertyId::WriteStatus)
+            .await?
+        {
+            ClientDataValue::Enumerated(status) => {
+                WriteStatus::from_u32(status).ok_or(ClientError::UnsupportedResponse)
             }
+            _ => Err(ClientError::UnsupportedResponse),
         }
-        #[cfg(feature = "tracing")]
-        tracing::warn!(invoke_id = invoke_id, "request timed out");
-        Err(ClientError::Timeout)
     }
 
-    /// Send a GetAlarmSummary request and return the list of active alarms on the device.
-    pub async fn get_alarm_summary(
+    /// Read the members (List_Of_Object_Property_References) of the Channel object `channel`.
+    pub async fn read_channel_members(
         &self,
         address: DataLinkAddress,
-    ) -> Result<Vec<AlarmSummaryItem>, ClientError> {
-        let invoke_id = self.next_invoke_id().await;
-        let request = GetAlarmSummaryRequest { invoke_id };
-        let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
-            request.encode(w)
-        })?;
+        channel: ObjectId,
+    ) -> Result<Vec<DeviceObjectPropertyReference>, ClientError> {
+        let mut buf = [0u8; 16];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, channel.raw())?;
+        encode_ctx_unsigned(
+            &mut w,
+            1,
+            PropertyId::ListOfObjectPropertyReferences.to_u32(),
+        )?;
         let payload = self
-            .await_complex_ack_payload_or_error(
-                address,
-                &tx,
-                invoke_id,
-                SERVICE_GET_ALARM_SUMMARY,
-                self.response_timeout,
-            )
+            .send_raw_confirmed(address, SERVICE_READ_PROPERTY, w.as_written())
             .await?;
-        let mut pr = Reader::new(&payload);
-        let parsed = GetAlarmSummaryAck::decode_after_header(&mut pr)?;
-        Ok(into_client_alarm_summary(parsed.summaries))
+        let ack = ChannelMembersAck::decode_after_header(&mut Reader::new(&payload))?;
+        Ok(ack.members)
     }
 
-    /// Send a GetEnrollmentSummary request and return the list of event enrollments on the device.
-    pub async fn get_enrollment_summary(
+    /// Replace the members (List_Of_Object_Property_References) of the Channel object
+    /// `channel`.
+    pub async fn write_channel_members(
         &self,
         address: DataLinkAddress,
-    ) -> Result<Vec<EnrollmentSummaryItem>, ClientError> {
-        let invoke_id = self.next_invoke_id().await;
-        let request = GetEnrollmentSummaryRequest { invoke_id };
+        channel: ObjectId,
+        members: &[DeviceObjectPropertyReference],
+    ) -> Result<(), ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = WriteChannelMembersRequest {
+            channel,
+            members,
+            invoke_id,
+        };
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
+            self.request_npdu().encode(w)?;
             request.encode(w)
         })?;
-        let payload = self
-            .await_complex_ack_payload_or_error(
-                address,
-                &tx,
-                invoke_id,
-                SERVICE_GET_ENROLLMENT_SUMMARY,
-                self.response_timeout,
-            )
+        self.await_simple_ack_or_error(
+            address,
+            &tx,
+            invoke_id,
+            SERVICE_WRITE_PROPERTY,
+            self.response_timeout,
+        )
+        .await
+    }
+
+    /// Add `member` to the Channel object `channel`, unless it is already there.
+    ///
+    /// The member array is read, extended and written back whole. Returns `false` when
+    /// the channel already had the member.
+    pub async fn add_channel_member(
+        &self,
+        address: DataLinkAddress,
+        channel: ObjectId,
+        member: DeviceObjectPropertyReference,
+    ) -> Result<bool, ClientError> {
+        let mut members = self.read_channel_members(address, channel).await?;
+        if members.contains(&member) {
+            return Ok(false);
+        }
+        members.push(member);
+        self.write_channel_members(address, channel, &members)
             .await?;
-        let mut pr = Reader::new(&payload);
-        let parsed = GetEnrollmentSummaryAck::decode_after_header(&mut pr)?;
-        Ok(into_client_enrollment_summary(parsed.summaries))
+        Ok(true)
     }
 
-    /// Send a GetEventInformation request and return active event summaries from the device.
+    /// Remove `member` from the Channel object `channel`.
     ///
-    /// Pass `last_received_object_id` to page through results; the returned
-    /// [`EventInformationResult::more_events`] indicates whether another call is needed.
-    pub async fn get_event_information(
+    /// The member array is read, filtered and written back whole. Returns `false` when
+    /// the channel did not have the member.
+    pub async fn remove_channel_member(
         &self,
         address: DataLinkAddress,
-        last_received_object_id: Option<ObjectId>,
-    ) -> Result<EventInformationResult, ClientError> {
-        let invoke_id = self.next_invoke_id().await;
-        let request = GetEventInformationRequest {
-            last_received_object_id,
-            invoke_id,
-        };
-        let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
-            request.encode(w)
-        })?;
+        channel: ObjectId,
+        member: DeviceObjectPropertyReference,
+    ) -> Result<bool, ClientError> {
+        let mut members = self.read_channel_members(address, channel).await?;
+        let before = members.len();
+        members.retain(|m| *m != member);
+        if members.len() == before {
+            return Ok(false);
+        }
+        self.write_channel_members(address, channel, &members)
+            .await?;
+        Ok(true)
+    }
+
+    /// Read the members (Group_Members) of the Global Group object `group`.
+    pub async fn read_global_group_members(
+        &self,
+        address: DataLinkAddress,
+        group: ObjectId,
+    ) -> Result<Vec<DeviceObjectPropertyReference>, ClientError> {
+        let mut buf = [0u8; 16];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, group.raw())?;
+        encode_ctx_unsigned(&mut w, 1, PropertyId::GroupMembers.to_u32())?;
         let payload = self
-            .await_complex_ack_payload_or_error(
-                address,
-                &tx,
-                invoke_id,
-                SERVICE_GET_EVENT_INFORMATION,
-                self.response_timeout,
-            )
+            .send_raw_confirmed(address, SERVICE_READ_PROPERTY, w.as_written())
             .await?;
-        let mut pr = Reader::new(&payload);
-        let parsed = GetEventInformationAck::decode_after_header(&mut pr)?;
-        Ok(EventInformationResult {
-            summaries: into_client_event_information(parsed.summaries),
-            more_events: parsed.more_events,
-        })
+        let ack = GroupMembersAck::decode_after_header(&mut Reader::new(&payload))?;
+        Ok(ack.members)
     }
 
-    /// Send an AcknowledgeAlarm request to the device.
-    pub async fn acknowledge_alarm(
+    /// Replace the members (Group_Members) of the Global Group object `group`.
+    ///
+    /// See [`group_members`](crate::group_members) for building the list.
+    pub async fn write_global_group_members(
         &self,
         address: DataLinkAddress,
-        mut request: AcknowledgeAlarmRequest<'_>,
+        group: ObjectId,
+        members: &[DeviceObjectPropertyReference],
     ) -> Result<(), ClientError> {
-        request.invoke_id = self.next_invoke_id().await;
-        let invoke_id = request.invoke_id;
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = WriteGroupMembersRequest {
+            group,
+            members,
+            invoke_id,
+        };
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
+            self.request_npdu().encode(w)?;
             request.encode(w)
         })?;
         self.await_simple_ack_or_error(
             address,
             &tx,
             invoke_id,
-            SERVICE_ACKNOWLEDGE_ALARM,
+            SERVICE_WRITE_PROPERTY,
             self.response_timeout,
         )
         .await
     }
 
-    /// Read a contiguous byte range from a BACnet File object using stream access.
+    /// Read the Present_Value of the Global Group object `group`: the value of every
+    /// member, gathered by the device holding the group, in one request.
     ///
-    /// `file_start_position` is the byte offset (may be negative for end-relative access).
-    /// `requested_octet_count` is the number of bytes to read.
-    pub async fn atomic_read_file_stream(
+    /// A member the group could not read is reported as
+    /// [`GroupMemberResult::Error`] rather than failing
+    /// the whole read.
+    pub async fn read_global_group_values(
         &self,
         address: DataLinkAddress,
-        file_object_id: ObjectId,
-        file_start_position: i32,
-        requested_octet_count: u32,
-    ) -> Result<AtomicReadFileResult, ClientError> {
-        let invoke_id = self.next_invoke_id().await;
-        let request = AtomicReadFileRequest::stream(
-            file_object_id,
-            file_start_position,
-            requested_octet_count,
-            invoke_id,
-        );
-        self.atomic_read_file(address, request).await
+        group: ObjectId,
+    ) -> Result<Vec<GlobalGroupValue>, ClientError> {
+        let mut buf = [0u8; 16];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, group.raw())?;
+        encode_ctx_unsigned(&mut w, 1, PropertyId::PresentValue.to_u32())?;
+        let payload = self
+            .send_raw_confirmed(address, SERVICE_READ_PROPERTY, w.as_written())
+            .await?;
+        let ack = GlobalGroupValuesAck::decode_after_header(&mut Reader::new(&payload))?;
+        ack.results
+            .into_iter()
+            .map(|entry| {
+                let result = match entry.result {
+                    AccessResult::Value(value) => {
+                        GroupMemberResult::Value(self.intern(into_client_value(value)?))
+                    }
+                    AccessResult::Error {
+                        error_class,
+                        error_code,
+                    } => GroupMemberResult::Error {
+                        error_class,
+                        error_code,
+                    },
+                };
+                Ok(GlobalGroupValue {
+                    member: entry.member,
+                    result,
+                })
+            })
+            .collect()
     }
 
-    /// Read a range of records from a BACnet File object using record access.
-    ///
-    /// `file_start_record` is the first record index (may be negative for end-relative access).
-    /// `requested_record_count` is the number of records to read.
-    pub async fn atomic_read_file_record(
+    /// Read the List_Of_Object_Property_References of the Schedule object `schedule`:
+    /// the properties it writes its Present_Value to.
+    pub async fn read_schedule_references(
         &self,
         address: DataLinkAddress,
-        file_object_id: ObjectId,
-        file_start_record: i32,
-        requested_record_count: u32,
-    ) -> Result<AtomicReadFileResult, ClientError> {
-        let invoke_id = self.next_invoke_id().await;
-        let request = AtomicReadFileRequest::record(
-            file_object_id,
-            file_start_record,
-            requested_record_count,
-            invoke_id,
-        );
-        self.atomic_read_file(address, request).await
+        schedule: ObjectId,
+    ) -> Result<Vec<DeviceObjectPropertyReference>, ClientError> {
+        let mut buf = [0u8; 16];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, schedule.raw())?;
+        encode_ctx_unsigned(
+            &mut w,
+            1,
+            PropertyId::ListOfObjectPropertyReferences.to_u32(),
+        )?;
+        let payload = self
+            .send_raw_confirmed(address, SERVICE_READ_PROPERTY, w.as_written())
+            .await?;
+        let ack = ScheduleReferencesAck::decode_after_header(&mut Reader::new(&payload))?;
+        Ok(ack.references)
     }
 
-    async fn atomic_read_file(
+    /// Replace the Weekly_Schedule of the Schedule object `schedule`; `week` is Monday
+    /// first.
+    ///
+    /// The days are checked with [`validate_weekly_schedule`] first; if any has a
+    /// problem nothing is sent and [`ClientError::InvalidSchedule`] lists them all.
+    pub async fn write_weekly_schedule(
         &self,
         address: DataLinkAddress,
-        request: AtomicReadFileRequest,
-    ) -> Result<AtomicReadFileResult, ClientError> {
-        let invoke_id = request.invoke_id;
+        schedule: ObjectId,
+        week: &[Vec<TimeValue>; 7],
+    ) -> Result<(), ClientError> {
+        let diagnostics = validate_weekly_schedule(week);
+        if !diagnostics.is_empty() {
+            return Err(ClientError::InvalidSchedule { diagnostics });
+        }
+        let time_values: Vec<Vec<CoreTimeValue<'_>>> = week
+            .iter()
+            .map(|day| day.iter().map(core_time_value).collect())
+            .collect();
+
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = WriteWeeklyScheduleRequest {
+            schedule,
+            days: std::array::from_fn(|day| time_values[day].as_slice()),
+            invoke_id,
+        };
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
+            self.request_npdu().encode(w)?;
             request.encode(w)
         })?;
-        let payload = self
-            .await_complex_ack_payload_or_error(
-                address,
-                &tx,
-                invoke_id,
-                SERVICE_ATOMIC_READ_FILE,
-                self.response_timeout,
-            )
-            .await?;
-        let mut pr = Reader::new(&payload);
-        let parsed = AtomicReadFileAck::decode_after_header(&mut pr)?;
-        Ok(into_client_atomic_read_result(parsed))
+        self.await_simple_ack_or_error(
+            address,
+            &tx,
+            invoke_id,
+            SERVICE_WRITE_PROPERTY,
+            self.response_timeout,
+        )
+        .await
     }
 
-    /// Write `file_data` to a BACnet File object starting at `file_start_position` using stream
-    /// access. The returned result contains the actual start position used by the device.
-    pub async fn atomic_write_file_stream(
+    /// Replace the Exception_Schedule of the Schedule object `schedule`.
+    ///
+    /// The events are checked with [`validate_exception_schedule`] first; if any has a
+    /// problem nothing is sent and [`ClientError::InvalidSchedule`] lists them all.
+    pub async fn write_exception_schedule(
         &self,
         address: DataLinkAddress,
-        file_object_id: ObjectId,
-        file_start_position: i32,
-        file_data: &[u8],
-    ) -> Result<AtomicWriteFileResult, ClientError> {
-        let invoke_id = self.next_invoke_id().await;
-        let request = AtomicWriteFileRequest::stream(
-            file_object_id,
-            file_start_position,
-            file_data,
-            invoke_id,
-        );
-        self.atomic_write_file(address, request).await
+        schedule: ObjectId,
+        events: &[SpecialEvent],
+    ) -> Result<(), ClientError> {
+        let diagnostics = validate_exception_schedule(events);
+        if !diagnostics.is_empty() {
+            return Err(ClientError::InvalidSchedule { diagnostics });
+        }
+        let time_values: Vec<Vec<CoreTimeValue<'_>>> = events
+            .iter()
+            .map(|event| event.time_values.iter().map(core_time_value).collect())
+            .collect();
+        let core_events: Vec<CoreSpecialEvent<'_>> = events
+            .iter()
+            .zip(&time_values)
+            .map(|(event, time_values)| CoreSpecialEvent {
+                period: match &event.period {
+                    SpecialEventPeriod::CalendarEntry(entry) => {
+                        CoreSpecialEventPeriod::CalendarEntry(entry.to_core())
+                    }
+                    SpecialEventPeriod::CalendarReference(calendar) => {
+                        CoreSpecialEventPeriod::CalendarReference(*calendar)
+                    }
+                },
+                time_values,
+                priority: event.priority,
+            })
+            .collect();
+
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = WriteExceptionScheduleRequest {
+            schedule,
+            events: &core_events,
+            invoke_id,
+        };
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            request.encode(w)
+        })?;
+        self.await_simple_ack_or_error(
+            address,
+            &tx,
+            invoke_id,
+            SERVICE_WRITE_PROPERTY,
+            self.response_timeout,
+        )
+        .await
     }
 
-    /// Write records to a BACnet File object starting at `file_start_record` using record access.
+    /// Write the Weekly_Schedule and Exception_Schedule generated from `occupancy` to
+    /// each of `targets`, given as the device address and its Schedule object.
     ///
-    /// Each element of `file_record_data` is one record's raw bytes.
-    pub async fn atomic_write_file_record(
+    /// Targets are written one after the other; a failed write is recorded and the run
+    /// continues with the next target.
+    pub async fn apply_occupancy_schedule(
         &self,
-        address: DataLinkAddress,
-        file_object_id: ObjectId,
-        file_start_record: i32,
-        file_record_data: &[&[u8]],
-    ) -> Result<AtomicWriteFileResult, ClientError> {
-        let invoke_id = self.next_invoke_id().await;
-        let request = AtomicWriteFileRequest::record(
-            file_object_id,
-            file_start_record,
-            file_record_data,
-            invoke_id,
-        );
-        self.atomic_write_file(address, request).await
+        targets: &[(DataLinkAddress, ObjectId)],
+        occupancy: &OccupancySchedule,
+    ) -> ScheduleApplyReport {
+        let week = occupancy.weekly_schedule();
+        let events = occupancy.exception_schedule();
+        let mut report = ScheduleApplyReport::default();
+        for &(address, schedule) in targets {
+            let result = async {
+                self.write_weekly_schedule(address, schedule, &week).await?;
+                self.write_exception_schedule(address, schedule, &events)
+                    .await
+            }
+            .await;
+            match result {
+                Ok(()) => report.applied.push((address, schedule)),
+                Err(error) => report.failed.push(ScheduleApplyFailure {
+                    address,
+                    schedule,
+                    error,
+                }),
+            }
+        }
+        report
     }
 
-    async fn atomic_write_file(
+    /// Replace the List_Of_Object_Property_References of the Schedule object `schedule`.
+    pub async fn write_schedule_references(
         &self,
         address: DataLinkAddress,
-        request: AtomicWriteFileRequest<'_>,
-    ) -> Result<AtomicWriteFileResult, ClientError> {
-        let invoke_id = request.invoke_id;
+        schedule: ObjectId,
+        references: &[DeviceObjectPropertyReference],
+    ) -> Result<(), ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = WriteScheduleReferencesRequest {
+            schedule,
+            references,
+            invoke_id,
+        };
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
+            self.request_npdu().encode(w)?;
             request.encode(w)
         })?;
+        self.await_simple_ack_or_error(
+            address,
+            &tx,
+            invoke_id,
+            SERVICE_WRITE_PROPERTY,
+            self.response_timeout,
+        )
+        .await
+    }
+
+    /// Read the Date_List of the Calendar object `calendar`.
+    pub async fn read_calendar_date_list(
+        &self,
+        address: DataLinkAddress,
+        calendar: ObjectId,
+    ) -> Result<Vec<CalendarEntry>, ClientError> {
+        let mut buf = [0u8; 16];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, calendar.raw())?;
+        encode_ctx_unsigned(&mut w, 1, PropertyId::DateList.to_u32())?;
         let payload = self
-            .await_complex_ack_payload_or_error(
-                address,
-                &tx,
-                invoke_id,
-                SERVICE_ATOMIC_WRITE_FILE,
-                self.response_timeout,
-            )
+            .send_raw_confirmed(address, SERVICE_READ_PROPERTY, w.as_written())
             .await?;
-        let mut pr = Reader::new(&payload);
-        let parsed = AtomicWriteFileAck::decode_after_header(&mut pr)?;
-        Ok(into_client_atomic_write_result(parsed))
+        let ack = DateListAck::decode_after_header(&mut Reader::new(&payload))?;
+        Ok(ack.entries.into_iter().map(CalendarEntry::from).collect())
     }
 
-    /// Send a SubscribeCOV request to start (or renew) a COV subscription on the device.
+    /// Replace the Date_List of the Calendar object `calendar`.
     ///
-    /// Use [`cancel_cov_subscription`](Self::cancel_cov_subscription) to unsubscribe.
-    pub async fn subscribe_cov(
+    /// Every entry is [validated](CalendarEntry::validate) before anything is sent.
+    pub async fn write_calendar_date_list(
         &self,
         address: DataLinkAddress,
-        mut request: SubscribeCovRequest,
+        calendar: ObjectId,
+        entries: &[CalendarEntry],
     ) -> Result<(), ClientError> {
-        request.invoke_id = self.next_invoke_id().await;
-        let invoke_id = request.invoke_id;
+        let entries = core_calendar_entries(entries)?;
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = WriteDateListRequest {
+            calendar,
+            entries: &entries,
+            invoke_id,
+        };
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
+            self.request_npdu().encode(w)?;
             request.encode(w)
         })?;
         self.await_simple_ack_or_error(
             address,
             &tx,
             invoke_id,
-            SERVICE_SUBSCRIBE_COV,
+            SERVICE_WRITE_PROPERTY,
             self.response_timeout,
         )
         .await
     }
 
-    /// Cancel an existing COV subscription identified by `subscriber_process_id` and
-    /// `monitored_object_id`.
-    pub async fn cancel_cov_subscription(
+    /// Add `entries` to the Date_List of the Calendar object `calendar` with
+    /// AddListElement, leaving the existing entries in place.
+    pub async fn add_calendar_entries(
         &self,
         address: DataLinkAddress,
-        subscriber_process_id: u32,
-        monitored_object_id: ObjectId,
+        calendar: ObjectId,
+        entries: &[CalendarEntry],
     ) -> Result<(), ClientError> {
-        self.subscribe_cov(
+        let entries = core_calendar_entries(entries)?;
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = AddDateListEntriesRequest {
+            calendar,
+            entries: &entries,
+            invoke_id,
+        };
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            request.encode(w)
+        })?;
+        self.await_simple_ack_or_error(
             address,
-            SubscribeCovRequest::cancel(subscriber_process_id, monitored_object_id, 0),
+            &tx,
+            invoke_id,
+            SERVICE_ADD_LIST_ELEMENT,
+            self.response_timeout,
         )
         .await
     }
 
-    /// Send a SubscribeCOVProperty request to subscribe to changes of a specific property.
-    ///
-    /// Use [`cancel_cov_property_subscription`](Self::cancel_cov_property_subscription) to
-    /// unsubscribe.
-    pub async fn subscribe_cov_property(
+    /// Remove `entries` from the Date_List of the Calendar object `calendar` with
+    /// RemoveListElement. The device rejects the request if any entry is not in the list.
+    pub async fn remove_calendar_entries(
         &self,
         address: DataLinkAddress,
-        mut request: SubscribeCovPropertyRequest,
+        calendar: ObjectId,
+        entries: &[CalendarEntry],
     ) -> Result<(), ClientError> {
-        request.invoke_id = self.next_invoke_id().await;
-        let invoke_id = request.invoke_id;
+        let entries = core_calendar_entries(entries)?;
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = RemoveDateListEntriesRequest {
+            calendar,
+            entries: &entries,
+            invoke_id,
+        };
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
+            self.request_npdu().encode(w)?;
             request.encode(w)
         })?;
         self.await_simple_ack_or_error(
             address,
             &tx,
             invoke_id,
-            SERVICE_SUBSCRIBE_COV_PROPERTY,
+            SERVICE_REMOVE_LIST_ELEMENT,
             self.response_timeout,
         )
         .await
     }
 
-    /// Cancel an existing COV property subscription.
+    /// Create an Event Enrollment object on the device and configure it with `config`.
     ///
-    /// The combination of `subscriber_process_id`, `monitored_object_id`,
-    /// `monitored_property_id`, and `monitored_property_array_index` must match the
-    /// subscription to cancel.
-    pub async fn cancel_cov_property_subscription(
+    /// The device picks the instance number. If the configuration is rejected the new
+    /// object is deleted again and the rejection returned.
+    pub async fn create_event_enrollment(
         &self,
         address: DataLinkAddress,
-        subscriber_process_id: u32,
-        monitored_object_id: ObjectId,
-        monitored_property_id: PropertyId,
-        monitored_property_array_index: Option<u32>,
-    ) -> Result<(), ClientError> {
-        self.subscribe_cov_property(
-            address,
-            SubscribeCovPropertyRequest::cancel(
-                subscriber_process_id,
-                monitored_object_id,
-                monitored_property_id,
-                monitored_property_array_index,
-                0,
-            ),
-        )
-        .await
+        config: &EventEnrollmentConfig,
+    ) -> Result<ObjectId, ClientError> {
+        let event_enrollment = self
+            .create_object_by_type(address, ObjectType::EventEnrollment)
+            .await?;
+        if let Err(err) = self
+            .configure_event_enrollment(address, event_enrollment, config)
+            .await
+        {
+            let _ = self.delete_object(address, event_enrollment).await;
+            return Err(err);
+        }
+        Ok(event_enrollment)
     }
 
-    /// Read a range of entries from a list/log property by absolute position.
+    /// Write `config` to the Event Enrollment object `event_enrollment`.
     ///
-    /// `reference_index` is the 1-based starting entry index. A positive `count` reads
-    /// forward; negative reads backward from `reference_index`.
-    pub async fn read_range_by_position(
+    /// Object_Property_Reference and Event_Parameters are written first, then
+    /// Notification_Class, Event_Enable and Notify_Type, one WriteProperty each.
+    pub async fn configure_event_enrollment(
         &self,
         address: DataLinkAddress,
-        object_id: ObjectId,
-        property_id: PropertyId,
-        array_index: Option<u32>,
-        reference_index: i32,
-        count: i16,
-    ) -> Result<ReadRangeResult, ClientError> {
-        let invoke_id = self.next_invoke_id().await;
-        let req = ReadRangeRequest::by_position(
-            object_id,
-            property_id,
-            array_index,
-            reference_index,
-            count,
-            invoke_id,
-        );
-        self.read_range_with_request(address, req).await
+        event_enrollment: ObjectId,
+        config: &EventEnrollmentConfig,
+    ) -> Result<(), ClientError> {
+        self.write_event_reference(address, event_enrollment, config.reference)
+            .await?;
+        self.write_event_parameters(address, event_enrollment, &config.parameters)
+            .await?;
+        for (property_id, value) in config.simple_writes() {
+            let request = WritePropertyRequest {
+                object_id: event_enrollment,
+                property_id,
+                value: dispatch_client_value_to_borrowed(&value),
+                ..Default::default()
+            };
+            self.write_property(address, request).await?;
+        }
+        Ok(())
     }
 
-    /// Read a range of entries from a list/log property anchored at a sequence number.
+    /// Read the configuration of the Event Enrollment object `event_enrollment`.
     ///
-    /// `reference_sequence` is the sequence number of the anchor entry. A positive `count`
-    /// reads forward; negative reads backward.
-    pub async fn read_range_by_sequence_number(
+    /// Event parameters of an event type [`EventParameter`] does not cover fail with
+    /// [`ClientError::UnsupportedResponse`].
+    pub async fn read_event_enrollment(
         &self,
         address: DataLinkAddress,
-        object_id: ObjectId,
-        property_id: PropertyId,
-        array_index: Option<u32>,
-        reference_sequence: u32,
-        count: i16,
-    ) -> Result<ReadRangeResult, ClientError> {
-        let invoke_id = self.next_invoke_id().await;
-        let req = ReadRangeRequest::by_sequence_number(
-            object_id,
-            property_id,
-            array_index,
-            reference_sequence,
-            count,
-            invoke_id,
-        );
-        self.read_range_with_request(address, req).await
+        event_enrollment: ObjectId,
+    ) -> Result<EventEnrollmentConfig, ClientError> {
+        let mut buf = [0u8; 16];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, event_enrollment.raw())?;
+        encode_ctx_unsigned(&mut w, 1, PropertyId::ObjectPropertyReference.to_u32())?;
+        let payload = self
+            .send_raw_confirmed(address, SERVICE_READ_PROPERTY, w.as_written())
+            .await?;
+        let reference = EventReferenceAck::decode_after_header(&mut Reader::new(&payload))?;
+
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, event_enrollment.raw())?;
+        encode_ctx_unsigned(&mut w, 1, PropertyId::EventParameters.to_u32())?;
+        let payload = self
+            .send_raw_confirmed(address, SERVICE_READ_PROPERTY, w.as_written())
+            .await?;
+        let parameters = EventParametersAck::decode_after_header(&mut Reader::new(&payload))?;
+
+        let notification_class = match self
+            .read_property(address, event_enrollment, PropertyId::NotificationClass)
+            .await?
+        {
+            ClientDataValue::Unsigned(class) => class,
+            _ => return Err(ClientError::UnsupportedResponse),
+        };
+        let event_enable = event_transitions(
+            &self
+                .read_property(address, event_enrollment, PropertyId::EventEnable)
+                .await?,
+        )
+        .ok_or(ClientError::UnsupportedResponse)?;
+        let notify_type = match self
+            .read_property(address, event_enrollment, PropertyId::NotifyType)
+            .await?
+        {
+            ClientDataValue::Enumerated(notify_type) => notify_type,
+            _ => return Err(ClientError::UnsupportedResponse),
+        };
+
+        Ok(EventEnrollmentConfig {
+            reference: reference.reference,
+            parameters: parameters.parameters,
+            notification_class,
+            event_enable,
+            notify_type,
+        })
     }
 
-    /// Read a range of entries from a list/log property anchored at a timestamp.
-    ///
-    /// `at` is the reference date and time. A positive `count` reads forward from that
-    /// timestamp; negative reads backward.
-    pub async fn read_range_by_time(
+    /// Write the Event_Parameters of the Event Enrollment object `event_enrollment`,
+    /// which also sets its Event_Type.
+    pub async fn write_event_parameters(
         &self,
         address: DataLinkAddress,
-        object_id: ObjectId,
-        property_id: PropertyId,
-        array_index: Option<u32>,
-        at: (Date, Time),
-        count: i16,
-    ) -> Result<ReadRangeResult, ClientError> {
-        let (date, time) = at;
-        let invoke_id = self.next_invoke_id().await;
-        let req = ReadRangeRequest::by_time(
-            object_id,
-            property_id,
-            array_index,
-            date,
-            time,
-            count,
+        event_enrollment: ObjectId,
+        parameters: &EventParameter,
+    ) -> Result<(), ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = WriteEventParametersRequest {
+            event_enrollment,
+            parameters,
             invoke_id,
-        );
-        self.read_range_with_request(address, req).await
+        };
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            request.encode(w)
+        })?;
+        self.await_simple_ack_or_error(
+            address,
+            &tx,
+            invoke_id,
+            SERVICE_WRITE_PROPERTY,
+            self.response_timeout,
+        )
+        .await
     }
 
-    async fn read_range_with_request(
+    /// Point the Event Enrollment object `event_enrollment` at the property `reference`.
+    pub async fn write_event_reference(
         &self,
         address: DataLinkAddress,
-        req: ReadRangeRequest,
-    ) -> Result<ReadRangeResult, ClientError> {
-        let invoke_id = req.invoke_id;
+        event_enrollment: ObjectId,
+        reference: DeviceObjectPropertyReference,
+    ) -> Result<(), ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = WriteEventReferenceRequest {
+            event_enrollment,
+            reference,
+            invoke_id,
+        };
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
-            req.encode(w)
+            self.request_npdu().encode(w)?;
+            request.encode(w)
         })?;
-        let payload = self
-            .await_complex_ack_payload_or_error(
-                address,
-                &tx,
-                invoke_id,
-                SERVICE_READ_RANGE,
-                self.response_timeout,
-            )
-            .await?;
-        let mut pr = Reader::new(&payload);
-        let parsed = ReadRangeAck::decode_after_header(&mut pr)?;
-        into_client_read_range(parsed)
-    }
-
-    /// Wait up to `wait` for a single incoming COV notification (confirmed or unconfirmed).
-    ///
-    /// Returns `Ok(Some(_))` when a notification arrives, `Ok(None)` on timeout, and
-    /// `Err` on transport failure. Confirmed notifications are automatically acknowledged.
-    /// Segmented confirmed notifications return [`ClientError::UnsupportedResponse`].
-    pub async fn recv_cov_notification(
-        &self,
-        wait: Duration,
-    ) -> Result<Option<CovNotification>, ClientError> {
-        let _io_lock = self.request_io_lock.lock().await;
-        let deadline = tokio::time::Instant::now() + wait;
-
-        while tokio::time::Instant::now() < deadline {
-            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
-            let mut rx = [0u8; 1500];
-            let recv = timeout(remaining, self.datalink.recv(&mut rx)).await;
-            let (n, source) = match recv {
-                Ok(Ok(v)) => v,
-                Ok(Err(e)) => return Err(e.into()),
-                Err(_) => break,
-            };
-
-            let apdu = extract_apdu(&rx[..n])?;
-            let first = *apdu.first().ok_or(ClientError::UnsupportedResponse)?;
-            match ApduType::from_u8(first >> 4) {
-                Some(ApduType::UnconfirmedRequest) => {
-                    let mut r = Reader::new(apdu);
-                    let header = UnconfirmedRequestHeader::decode(&mut r)?;
-                    if header.service_choice != SERVICE_UNCONFIRMED_COV_NOTIFICATION {
-                        continue;
-                    }
-                    let cov = CovNotificationRequest::decode_after_header(&mut r)?;
-                    return Ok(Some(into_client_cov_notification(source, false, cov)?));
-                }
-                Some(ApduType::ConfirmedRequest) => {
-                    let mut r = Reader::new(apdu);
-                    let header = ConfirmedRequestHeader::decode(&mut r)?;
-                    if header.service_choice != SERVICE_CONFIRMED_COV_NOTIFICATION {
-                        continue;
-                    }
-                    if header.segmented {
-                        return Err(ClientError::UnsupportedResponse);
-                    }
-
-                    let cov = CovNotificationRequest::decode_after_header(&mut r)?;
-                    self.send_simple_ack(
-                        source,
-                        header.invoke_id,
-                        SERVICE_CONFIRMED_COV_NOTIFICATION,
-                    )
-                    .await?;
-                    return Ok(Some(into_client_cov_notification(source, true, cov)?));
-                }
-                _ => continue,
-            }
-        }
-
-        Ok(None)
+        self.await_simple_ack_or_error(
+            address,
+            &tx,
+            invoke_id,
+            SERVICE_WRITE_PROPERTY,
+            self.response_timeout,
+        )
+        .await
     }
 
-    /// Wait up to `wait` for a single incoming event notification (confirmed or unconfirmed).
-    ///
-    /// Returns `Ok(Some(_))` when a notification arrives, `Ok(None)` on timeout, and
-    /// `Err` on transport failure. Confirmed notifications are automatically acknowledged.
-    /// Segmented confirmed notifications return [`ClientError::UnsupportedResponse`].
-    pub async fn recv_event_notification(
+    /// Read the Event_Message_Texts of `object`: the message texts of its last
+    /// to-offnormal, to-fault and to-normal notifications.
+    pub async fn read_event_message_texts(
         &self,
-        wait: Duration,
-    ) -> Result<Option<EventNotification>, ClientError> {
-        let _io_lock = self.request_io_lock.lock().await;
-        let deadline = tokio::time::Instant::now() + wait;
-
-        while tokio::time::Instant::now() < deadline {
-            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
-            let mut rx = [0u8; 1500];
-            let recv = timeout(remaining, self.datalink.recv(&mut rx)).await;
-            let (n, source) = match recv {
-                Ok(Ok(v)) => v,
-                Ok(Err(e)) => return Err(e.into()),
-                Err(_) => break,
-            };
-
-            let apdu = extract_apdu(&rx[..n])?;
-            let first = *apdu.first().ok_or(ClientError::UnsupportedResponse)?;
-            match ApduType::from_u8(first >> 4) {
-                Some(ApduType::UnconfirmedRequest) => {
-                    let mut r = Reader::new(apdu);
-                    let header = UnconfirmedRequestHeader::decode(&mut r)?;
-                    if header.service_choice != SERVICE_UNCONFIRMED_EVENT_NOTIFICATION {
-                        continue;
-                    }
-                    let notification = EventNotificationRequest::decode_after_header(&mut r)?;
-                    return Ok(Some(into_client_event_notification(
-                        source,
-                        false,
-                        notification,
-                    )));
-                }
-                Some(ApduType::ConfirmedRequest) => {
-                    let mut r = Reader::new(apdu);
-                    let header = ConfirmedRequestHeader::decode(&mut r)?;
-                    if header.service_choice != SERVICE_CONFIRMED_EVENT_NOTIFICATION {
-                        continue;
-                    }
-                    if header.segmented {
-                        return Err(ClientError::UnsupportedResponse);
-                    }
-                    let notification = EventNotificationRequest::decode_after_header(&mut r)?;
-                    self.send_simple_ack(
-                        source,
-                        header.invoke_id,
-                        SERVICE_CONFIRMED_EVENT_NOTIFICATION,
-                    )
-                    .await?;
-                    return Ok(Some(into_client_event_notification(
-                        source,
-                        true,
-                        notification,
-                    )));
-                }
-                _ => continue,
-            }
-        }
+        address: DataLinkAddress,
+        object: ObjectId,
+    ) -> Result<EventMessageTexts, ClientError> {
+        let value = self
+            .read_property(address, object, PropertyId::EventMessageTexts)
+            .await?;
+        EventMessageTexts::from_value(&value).ok_or(ClientError::UnsupportedResponse)
+    }
 
-        Ok(None)
+    /// Read the Event_Message_Texts_Config of `object`: the message texts it puts in
+    /// its notifications.
+    pub async fn read_event_message_texts_config(
+        &self,
+        address: DataLinkAddress,
+        object: ObjectId,
+    ) -> Result<EventMessageTexts, ClientError> {
+        let value = self
+            .read_property(address, object, PropertyId::EventMessageTextsConfig)
+            .await?;
+        EventMessageTexts::from_value(&value).ok_or(ClientError::UnsupportedResponse)
     }
 
-    /// Send a ReadProperty request and return the property value as a [`ClientDataValue`].
-    ///
-    /// Use [`read_property_multiple`](Self::read_property_multiple) to fetch several
-    /// properties in a single round-trip.
-    pub async fn read_property(
+    /// Write `texts` to the Event_Message_Texts_Config of `object`, one WriteProperty per
+    /// transition; the first rejected write stops the rest.
+    pub async fn write_event_message_texts_config(
         &self,
         address: DataLinkAddress,
-        object_id: ObjectId,
-        property_id: PropertyId,
-    ) -> Result<ClientDataValue, ClientError> {
-        let invoke_id = self.next_invoke_id().await;
-        let req = ReadPropertyRequest {
-            object_id,
-            property_id,
-            array_index: None,
-            invoke_id,
-        };
-        let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
-            req.encode(w)
-        })?;
-        let payload = self
-            .await_complex_ack_payload_or_error(
+        object: ObjectId,
+        texts: &EventMessageTexts,
+    ) -> Result<(), ClientError> {
+        for transition in EventTransition::ALL {
+            self.write_event_message_text_config(
                 address,
-                &tx,
-                invoke_id,
-                SERVICE_READ_PROPERTY,
-                self.response_timeout,
+                object,
+                transition,
+                texts.get(transition),
             )
             .await?;
-        let mut pr = Reader::new(&payload);
-        let parsed = ReadPropertyAck::decode_after_header(&mut pr)?;
-        into_client_value(parsed.value)
+        }
+        Ok(())
     }
 
-    /// Send a WriteProperty request to set a single property on the device.
-    pub async fn write_property(
+    /// Write the Event_Message_Texts_Config text of one `transition` of `object`.
+    pub async fn write_event_message_text_config(
         &self,
         address: DataLinkAddress,
-        mut request: WritePropertyRequest<'_>,
+        object: ObjectId,
+        transition: EventTransition,
+        text: &str,
     ) -> Result<(), ClientError> {
-        request.invoke_id = self.next_invoke_id().await;
-        let invoke_id = request.invoke_id;
+        let request = WritePropertyRequest {
+            object_id: object,
+            property_id: PropertyId::EventMessageTextsConfig,
+            array_index: Some(transition.array_index()),
+            value: DataValue::CharacterString(text),
+            ..Default::default()
+        };
+        self.write_property(address, request).await
+    }
+
+    /// Write `command` (a fade, ramp or step) to the Lighting_Command of the Lighting
+    /// Output object `lighting_output`.
+    ///
+    /// The command's own priority applies; the output uses its
+    /// Lighting_Command_Default_Priority when the command has none.
+    pub async fn write_lighting_command(
+        &self,
+        address: DataLinkAddress,
+        lighting_output: ObjectId,
+        command: LightingCommand,
+    ) -> Result<(), ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = WriteLightingCommandRequest {
+            lighting_output,
+            command,
+            invoke_id,
+        };
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
+            self.request_npdu().encode(w)?;
             request.encode(w)
         })?;
         self.await_simple_ack_or_error(
@@ -2011,1786 +2989,6842 @@ impl<D: DataLink> BacnetClient<D> {
         .await
     }
 
-    /// Send a ReadPropertyMultiple request to fetch several properties of one object in a
-    /// single round-trip.
-    ///
-    /// Returns pairs of `(PropertyId, ClientDataValue)` in the order returned by the device.
-    pub async fn read_property_multiple(
-        &self,
-        address: DataLinkAddress,
-        object_id: ObjectId,
-        property_ids: &[PropertyId],
-    ) -> Result<Vec<(PropertyId, ClientDataValue)>, ClientError> {
-        let refs: Vec<PropertyReference> = property_ids
-            .iter()
-            .copied()
-            .map(|property_id| PropertyReference {
-                property_id,
-                array_index: None,
-            })
-            .collect();
-        let specs = [ReadAccessSpecification {
-            object_id,
-            properties: &refs,
-        }];
-
-        let invoke_id = self.next_invoke_id().await;
-        let req = ReadPropertyMultipleRequest {
-            specs: &specs,
-            invoke_id,
-        };
-
-        let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
-            req.encode(w)
-        })?;
-        let payload = self
-            .await_complex_ack_payload_or_error(
-                address,
-                &tx,
-                invoke_id,
-                SERVICE_READ_PROPERTY_MULTIPLE,
-                self.response_timeout,
-            )
-            .await?;
-        let mut pr = Reader::new(&payload);
-        let parsed = ReadPropertyMultipleAck::decode_after_header(&mut pr)?;
-        let mut out = Vec::new();
-        for access in parsed.results {
-            if access.object_id != object_id {
-                continue;
-            }
-            for item in access.results {
-                out.push((item.property_id, into_client_value(item.value)?));
-            }
-        }
-        Ok(out)
-    }
-
-    /// Send a WritePropertyMultiple request to set several properties of one object in a
-    /// single round-trip.
-    pub async fn write_property_multiple(
+    /// Write `command` to the Color_Command of the Color or Color Temperature object
+    /// `object_id`.
+    pub async fn write_color_command(
         &self,
         address: DataLinkAddress,
         object_id: ObjectId,
-        properties: &[PropertyWriteSpec<'_>],
+        command: ColorCommand,
     ) -> Result<(), ClientError> {
-        let invoke_id = self.next_invoke_id().await;
-        let specs = [WriteAccessSpecification {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = WriteColorCommandRequest {
             object_id,
-            properties,
-        }];
-        let req = WritePropertyMultipleRequest {
-            specs: &specs,
+            command,
             invoke_id,
         };
-
         let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
-            req.encode(w)
+            self.request_npdu().encode(w)?;
+            request.encode(w)
         })?;
         self.await_simple_ack_or_error(
             address,
             &tx,
             invoke_id,
-            SERVICE_WRITE_PROPERTY_MULTIPLE,
+            SERVICE_WRITE_PROPERTY,
             self.response_timeout,
         )
         .await
     }
 
-    /// Send a ConfirmedPrivateTransfer request and return the ack.
-    pub async fn private_transfer(
+    /// Read the Present_Value of the Color object `color` as an [`XyColor`].
+    pub async fn read_xy_color(
         &self,
         address: DataLinkAddress,
-        vendor_id: u32,
-        service_number: u32,
-        service_parameters: Option<&[u8]>,
-    ) -> Result<PrivateTransferAck, ClientError> {
-        let invoke_id = self.next_invoke_id().await;
-        let req = ConfirmedPrivateTransferRequest {
-            vendor_id,
-            service_number,
-            service_parameters,
-            invoke_id,
-        };
-
-        let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
-            req.encode(w)
-        })?;
-        let payload = self
-            .await_complex_ack_payload_or_error(
-                address,
-                &tx,
-                invoke_id,
-                SERVICE_CONFIRMED_PRIVATE_TRANSFER,
-                self.response_timeout,
-            )
-            .await?;
-        let mut r = Reader::new(&payload);
-        PrivateTransferAck::decode(&mut r).map_err(ClientError::from)
+        color: ObjectId,
+    ) -> Result<XyColor, ClientError> {
+        match self
+            .read_property(address, color, PropertyId::PresentValue)
+            .await?
+        {
+            ClientDataValue::Constructed { values, .. } => match values.as_slice() {
+                [ClientDataValue::Real(x), ClientDataValue::Real(y)] => Ok(XyColor::new(*x, *y)),
+                _ => Err(ClientError::UnsupportedResponse),
+            },
+            _ => Err(ClientError::UnsupportedResponse),
+        }
     }
 
-    /// Read multiple `(object_id, property_id)` pairs in a single ReadPropertyMultiple round-trip.
+    /// Read how the Accumulator or Pulse Converter `object` scales its pulses: Scale (a
+    /// Pulse Converter's Scale_Factor), Units and, if the Accumulator has one, Prescale.
     ///
-    /// All pairs must target the same device at `address`. Returns a map from each requested
-    /// `(ObjectId, PropertyId)` to its value. Properties not returned by the device are absent
-    /// from the map (the device may skip unknown properties rather than erroring).
-    pub async fn read_many(
+    /// Fails with [`ClientError::UnsupportedResponse`] for other object types.
+    pub async fn read_pulse_scaling(
         &self,
         address: DataLinkAddress,
-        requests: &[(ObjectId, PropertyId)],
-    ) -> Result<HashMap<(ObjectId, PropertyId), ClientDataValue>, ClientError> {
-        // Group by object to build ReadAccessSpecifications
-        let mut grouped: Vec<(ObjectId, Vec<PropertyReference>)> = Vec::new();
-        for &(oid, pid) in requests {
-            if let Some(entry) = grouped.iter_mut().find(|(o, _)| *o == oid) {
-                entry.1.push(PropertyReference {
-                    property_id: pid,
-                    array_index: None,
-                });
-            } else {
-                grouped.push((
-                    oid,
-                    vec![PropertyReference {
-                        property_id: pid,
-                        array_index: None,
-                    }],
-                ));
+        object: ObjectId,
+    ) -> Result<PulseScaling, ClientError> {
+        let (scale, prescale) = match object.object_type() {
+            ObjectType::Accumulator => {
+                let payload = self
+                    .read_property_payload(address, object, PropertyId::Scale)
+                    .await?;
+                let scale = ScaleAck::decode_after_header(&mut Reader::new(&payload))?.scale;
+                let prescale = match self
+                    .read_property_payload(address, object, PropertyId::Prescale)
+                    .await
+                {
+                    Ok(payload) => {
+                        Some(PrescaleAck::decode_after_header(&mut Reader::new(&payload))?.prescale)
+                    }
+                    Err(err) if matches!(err.root(), ClientError::RemoteServiceError { .. }) => {
+                        None
+                    }
+                    Err(err) => return Err(err),
+                };
+                (scale, prescale)
             }
-        }
+            ObjectType::PulseConverter => match self
+                .read_property(address, object, PropertyId::ScaleFactor)
+                .await?
+            {
+                ClientDataValue::Real(factor) => (Scale::Float(factor), None),
+                _ => return Err(ClientError::UnsupportedResponse),
+            },
+            _ => return Err(ClientError::UnsupportedResponse),
+        };
+        let units = match self
+            .read_property(address, object, PropertyId::Units)
+            .await?
+        {
+            ClientDataValue::Enumerated(units) => units,
+            _ => return Err(ClientError::UnsupportedResponse),
+        };
+        Ok(PulseScaling {
+            scale,
+            units,
+            prescale,
+        })
+    }
 
-        let specs: Vec<ReadAccessSpecification<'_>> = grouped
-            .iter()
-            .map(|(oid, props)| ReadAccessSpecification {
-                object_id: *oid,
-                properties: props,
-            })
-            .collect();
+    /// Read the pulse count of the Accumulator or Pulse Converter `object` (Present_Value
+    /// or Count) and convert it with the object's [`PulseScaling`].
+    pub async fn read_scaled_pulses(
+        &self,
+        address: DataLinkAddress,
+        object: ObjectId,
+    ) -> Result<ScaledPulses, ClientError> {
+        let scaling = self.read_pulse_scaling(address, object).await?;
+        let property_id = match object.object_type() {
+            ObjectType::PulseConverter => PropertyId::Count,
+            _ => PropertyId::PresentValue,
+        };
+        let pulses = match self.read_property(address, object, property_id).await? {
+            ClientDataValue::Unsigned(pulses) => pulses,
+            _ => return Err(ClientError::UnsupportedResponse),
+        };
+        Ok(ScaledPulses {
+            pulses,
+            value: scaling.to_engineering(pulses),
+            units: scaling.units,
+        })
+    }
 
-        let invoke_id = self.next_invoke_id().await;
-        let req = ReadPropertyMultipleRequest {
-            specs: &specs,
-            invoke_id,
+    /// Read the command and sensor state of the Access Door object `door`.
+    ///
+    /// Each property is read with ReadProperty; ones the door does not have are left
+    /// `None` in the result.
+    pub async fn read_access_door(
+        &self,
+        address: DataLinkAddress,
+        door: ObjectId,
+    ) -> Result<AccessDoorState, ClientError> {
+        let values = self
+            .read_optional_properties(address, door, ACCESS_DOOR_PROPERTIES)
+            .await?;
+        Ok(AccessDoorState::from_properties(&values))
+    }
+
+    /// Command the Access Door object `door` by writing its Present_Value at `priority`.
+    pub async fn command_access_door(
+        &self,
+        address: DataLinkAddress,
+        door: ObjectId,
+        value: DoorValue,
+        priority: u8,
+    ) -> Result<(), ClientError> {
+        let request = WritePropertyRequest {
+            object_id: door,
+            property_id: PropertyId::PresentValue,
+            value: DataValue::Enumerated(value.to_u32()),
+            priority: Some(priority),
+            ..Default::default()
         };
-        let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
-            req.encode(w)
-        })?;
+        self.write_property(address, request).await
+    }
+
+    /// Read the occupancy of the Access Zone object `zone`.
+    ///
+    /// Each property is read with ReadProperty; ones the zone does not have are left
+    /// `None` in the result.
+    pub async fn read_access_zone(
+        &self,
+        address: DataLinkAddress,
+        zone: ObjectId,
+    ) -> Result<AccessZoneState, ClientError> {
+        let values = self
+            .read_optional_properties(address, zone, ACCESS_ZONE_PROPERTIES)
+            .await?;
+        Ok(AccessZoneState::from_properties(&values))
+    }
+
+    /// Read the last authentication factor (the Present_Value) of the Credential Data
+    /// Input object `input`.
+    pub async fn read_credential_data_input(
+        &self,
+        address: DataLinkAddress,
+        input: ObjectId,
+    ) -> Result<AuthenticationFactorValue, ClientError> {
         let payload = self
-            .await_complex_ack_payload_or_error(
-                address,
-                &tx,
-                invoke_id,
-                SERVICE_READ_PROPERTY_MULTIPLE,
-                self.response_timeout,
-            )
+            .read_property_payload(address, input, PropertyId::PresentValue)
             .await?;
+        let ack = AuthenticationFactorAck::decode_after_header(&mut Reader::new(&payload))?;
+        Ok(ack.factor.into())
+    }
 
-        let mut pr = Reader::new(&payload);
-        let parsed = ReadPropertyMultipleAck::decode_after_header(&mut pr)?;
-        let mut out = HashMap::new();
-        for access in parsed.results {
-            for item in access.results {
-                if let Ok(v) = into_client_value(item.value) {
-                    out.insert((access.object_id, item.property_id), v);
-                }
+    /// ReadProperty of each of `property_ids`, leaving out the ones the device answers
+    /// with an Error.
+    async fn read_optional_properties(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        property_ids: &[PropertyId],
+    ) -> Result<Vec<(PropertyId, ClientDataValue)>, ClientError> {
+        let mut values = Vec::with_capacity(property_ids.len());
+        for &property_id in property_ids {
+            match self.read_property(address, object_id, property_id).await {
+                Ok(value) => values.push((property_id, value)),
+                Err(err) if matches!(err.root(), ClientError::RemoteServiceError { .. }) => {}
+                Err(err) => return Err(err),
             }
         }
-        Ok(out)
+        Ok(values)
     }
 
-    /// Write multiple properties across one or more objects in a single WritePropertyMultiple
-    /// round-trip.
+    /// Read the state of the Life Safety Point or Life Safety Zone `object` with one
+    /// ReadPropertyMultiple.
+    pub async fn read_life_safety(
+        &self,
+        address: DataLinkAddress,
+        object: ObjectId,
+    ) -> Result<LifeSafetyStatus, ClientError> {
+        if !matches!(
+            object.object_type(),
+            ObjectType::LifeSafetyPoint | ObjectType::LifeSafetyZone
+        ) {
+            return Err(ClientError::UnsupportedResponse);
+        }
+        let values = self
+            .read_properties_with_fallback(address, object, LIFE_SAFETY_PROPERTIES)
+            .await?;
+        LifeSafetyStatus::from_properties(&values).ok_or(ClientError::UnsupportedResponse)
+    }
+
+    /// Read the car state of the Lift object `lift`.
     ///
-    /// `writes` is a slice of `(object_id, property_id, value, priority)` tuples.
-    /// `priority` may be `None` for the relinquish-default case. All writes target the same
-    /// device at `address`. Takes [`ClientDataValue`] (the owned form) for ergonomic use.
-    pub async fn write_many(
+    /// Each property is read with ReadProperty; ones the lift does not have are left
+    /// `None` in the result.
+    pub async fn read_lift_car_state(
         &self,
         address: DataLinkAddress,
-        writes: &[(ObjectId, PropertyId, ClientDataValue, Option<u8>)],
-    ) -> Result<(), ClientError> {
-        use rustbac_core::types::{BitString, DataValue as DV};
+        lift: ObjectId,
+    ) -> Result<LiftCarState, ClientError> {
+        let values = self
+            .read_optional_properties(address, lift, LIFT_CAR_PROPERTIES)
+            .await?;
+        Ok(LiftCarState::from_properties(&values))
+    }
 
-        fn cv_to_dv(v: &ClientDataValue) -> DV<'_> {
-            match v {
-                ClientDataValue::Null => DV::Null,
-                ClientDataValue::Boolean(b) => DV::Boolean(*b),
-                ClientDataValue::Unsigned(n) => DV::Unsigned(*n),
-                ClientDataValue::Signed(n) => DV::Signed(*n),
-                ClientDataValue::Real(f) => DV::Real(*f),
-                ClientDataValue::Double(f) => DV::Double(*f),
-                ClientDataValue::OctetString(b) => DV::OctetString(b),
-                ClientDataValue::CharacterString(s) => DV::CharacterString(s),
-                ClientDataValue::BitString { unused_bits, data } => DV::BitString(BitString {
-                    unused_bits: *unused_bits,
-                    data,
-                }),
-                ClientDataValue::Enumerated(n) => DV::Enumerated(*n),
-                ClientDataValue::Date(d) => DV::Date(*d),
-                ClientDataValue::Time(t) => DV::Time(*t),
-                ClientDataValue::ObjectId(o) => DV::ObjectId(*o),
-                ClientDataValue::Constructed { tag_num, values } => DV::Constructed {
-                    tag_num: *tag_num,
-                    values: values.iter().map(cv_to_dv).collect(),
-                },
-            }
-        }
+    /// Read the Registered_Car_Call and Assigned_Landing_Calls of the Lift object `lift`.
+    pub async fn read_lift_calls(
+        &self,
+        address: DataLinkAddress,
+        lift: ObjectId,
+    ) -> Result<LiftCalls, ClientError> {
+        let payload = self
+            .read_property_payload(address, lift, PropertyId::RegisteredCarCall)
+            .await?;
+        let registered = RegisteredCarCallAck::decode_after_header(&mut Reader::new(&payload))?;
+        let payload = self
+            .read_property_payload(address, lift, PropertyId::AssignedLandingCalls)
+            .await?;
+        let assigned = AssignedLandingCallsAck::decode_after_header(&mut Reader::new(&payload))?;
+        Ok(LiftCalls {
+            registered_car_calls: registered.doors,
+            assigned_landing_calls: assigned.doors,
+        })
+    }
 
-        // Pre-convert values so DataValue borrows from the input slice lifetime.
-        let converted: Vec<(ObjectId, PropertyId, DV<'_>, Option<u8>)> = writes
-            .iter()
-            .map(|(oid, pid, val, prio)| (*oid, *pid, cv_to_dv(val), *prio))
-            .collect();
+    /// Read the Landing_Calls of a Lift or Elevator Group.
+    pub async fn read_landing_calls(
+        &self,
+        address: DataLinkAddress,
+        object: ObjectId,
+    ) -> Result<Vec<LandingCallStatusValue>, ClientError> {
+        let payload = self
+            .read_property_payload(address, object, PropertyId::LandingCalls)
+            .await?;
+        let ack = LandingCallsAck::decode_after_header(&mut Reader::new(&payload))?;
+        Ok(ack.calls.into_iter().map(Into::into).collect())
+    }
 
-        // Group by object to build WriteAccessSpecifications.
-        let mut grouped: Vec<(ObjectId, Vec<PropertyWriteSpec<'_>>)> = Vec::new();
-        for (oid, pid, val, prio) in &converted {
-            let spec = PropertyWriteSpec {
-                property_id: *pid,
-                array_index: None,
-                value: val.clone(),
-                priority: *prio,
-            };
-            if let Some(entry) = grouped.iter_mut().find(|(o, _)| o == oid) {
-                entry.1.push(spec);
-            } else {
-                grouped.push((*oid, vec![spec]));
-            }
-        }
+    /// Read the mode, direction and passenger alarm of the Escalator object `escalator`.
+    ///
+    /// Each property is read with ReadProperty; ones the escalator does not have are
+    /// left `None` in the result.
+    pub async fn read_escalator(
+        &self,
+        address: DataLinkAddress,
+        escalator: ObjectId,
+    ) -> Result<EscalatorState, ClientError> {
+        let values = self
+            .read_optional_properties(address, escalator, ESCALATOR_PROPERTIES)
+            .await?;
+        Ok(EscalatorState::from_properties(&values))
+    }
 
-        let specs: Vec<WriteAccessSpecification<'_>> = grouped
+    /// Read the group id and traffic mode of the Elevator Group object `group`.
+    pub async fn read_elevator_group(
+        &self,
+        address: DataLinkAddress,
+        group: ObjectId,
+    ) -> Result<ElevatorGroupState, ClientError> {
+        let values = self
+            .read_optional_properties(address, group, ELEVATOR_GROUP_PROPERTIES)
+            .await?;
+        Ok(ElevatorGroupState::from_properties(&values))
+    }
+
+    /// Read the tuning of the Loop object `control_loop`.
+    ///
+    /// Setpoint_Reference is read with ReadProperty, the other properties with
+    /// [`read_properties_with_fallback`](Self::read_properties_with_fallback). A Loop
+    /// without one of them (the P/I/D constants are optional) fails with
+    /// [`ClientError::UnsupportedResponse`].
+    pub async fn read_loop_tuning(
+        &self,
+        address: DataLinkAddress,
+        control_loop: ObjectId,
+    ) -> Result<LoopTuning, ClientError> {
+        let payload = self
+            .read_property_payload(address, control_loop, PropertyId::SetpointReference)
+            .await?;
+        let reference = SetpointReferenceAck::decode_after_header(&mut Reader::new(&payload))?;
+        let values = self
+            .read_properties_with_fallback(address, control_loop, LOOP_TUNING_PROPERTIES)
+            .await?;
+        LoopTuning::from_properties(reference.reference.0, &values)
+            .ok_or(ClientError::UnsupportedResponse)
+    }
+
+    /// Write `change` to the Loop object `control_loop` in one WritePropertyMultiple.
+    ///
+    /// Nothing is sent unless the change [validates](LoopTuningChange::validate).
+    pub async fn write_loop_tuning(
+        &self,
+        address: DataLinkAddress,
+        control_loop: ObjectId,
+        change: &LoopTuningChange,
+    ) -> Result<(), ClientError> {
+        change.validate()?;
+        let writes = change.writes();
+        if writes.is_empty() {
+            return Ok(());
+        }
+        let specs: Vec<PropertyWriteSpec<'_>> = writes
             .iter()
-            .map(|(oid, props)| WriteAccessSpecification {
-                object_id: *oid,
-                properties: props,
+            .map(|(property_id, value)| PropertyWriteSpec {
+                property_id: *property_id,
+                array_index: None,
+                value: dispatch_client_value_to_borrowed(value),
+                priority: None,
             })
             .collect();
+        self.write_property_multiple(address, control_loop, &specs)
+            .await
+    }
 
-        let invoke_id = self.next_invoke_id().await;
-        let req = WritePropertyMultipleRequest {
-            specs: &specs,
-            invoke_id,
-        };
-        let tx = self.encode_with_growth(|w| {
-            Npdu::new(0).encode(w)?;
-            req.encode(w)
-        })?;
-        self.await_simple_ack_or_error(
-            address,
-            &tx,
-            invoke_id,
-            SERVICE_WRITE_PROPERTY_MULTIPLE,
-            self.response_timeout,
-        )
-        .await
+    /// ReadProperty of `property_id`, returning the ACK parameters for a typed decoder.
+    async fn read_property_payload(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        property_id: PropertyId,
+    ) -> Result<Vec<u8>, ClientError> {
+        let mut buf = [0u8; 16];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, object_id.raw())?;
+        encode_ctx_unsigned(&mut w, 1, property_id.to_u32())?;
+        self.send_raw_confirmed(address, SERVICE_READ_PROPERTY, w.as_written())
+            .await
     }
-}
 
-fn extract_apdu(payload: &[u8]) -> Result<&[u8], ClientError> {
-    let mut r = Reader::new(payload);
-    let _npdu = Npdu::decode(&mut r)?;
-    r.read_exact(r.remaining()).map_err(ClientError::from)
-}
+    async fn await_simple_ack_or_error(
+        &self,
+        address: DataLinkAddress,
+        tx: &[u8],
+        invoke_id: u8,
+        service_choice: u8,
+        timeout_window: Duration,
+    ) -> Result<(), ClientError> {
+        let started = (std::time::SystemTime::now(), self.runtime.now());
+        let result = self
+            .exchange_confirmed(address, tx, invoke_id, service_choice, timeout_window)
+            .await
+            .map(drop);
+        let outcome = TransactionOutcome::from_result(&result, |_| TransactionOutcome::SimpleAck);
+        self.record_transaction(address, tx, invoke_id, service_choice, started, outcome);
+        result.map_err(|err| {
+            err.with_context(RequestContext {
+                address,
+                service_choice,
+                invoke_id,
+            })
+        })
+    }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Inline server dispatch
-// ─────────────────────────────────────────────────────────────────────────────
+    async fn await_complex_ack_payload_or_error(
+        &self,
+        address: DataLinkAddress,
+        tx: &[u8],
+        invoke_id: u8,
+        service_choice: u8,
+        timeout_window: Duration,
+    ) -> Result<Vec<u8>, ClientError> {
+        let started = (std::time::SystemTime::now(), self.runtime.now());
+        let result = self
+            .exchange_confirmed(address, tx, invoke_id, service_choice, timeout_window)
+            .await;
+        let outcome =
+            TransactionOutcome::from_result(&result, |payload| TransactionOutcome::ComplexAck {
+                payload_len: payload.len(),
+            });
+        self.record_transaction(address, tx, invoke_id, service_choice, started, outcome);
+        result.map_err(|err| {
+            err.with_context(RequestContext {
+                address,
+                service_choice,
+                invoke_id,
+            })
+        })
+    }
 
-/// Handle an incoming BACnet service request on behalf of the client's inline server handler.
-///
-/// This is the core dispatch logic extracted from [`BacnetServer::handle_frame`] so that
-/// `BacnetClient` can service requests inline while waiting for its own responses.
-async fn dispatch_incoming_request<D: DataLink>(
-    datalink: &D,
-    handler: &dyn crate::server::ServiceHandler,
-    device_id: u32,
-    vendor_id: u16,
-    frame: &[u8],
-    source: DataLinkAddress,
-) -> Result<(), ClientError> {
-    let mut r = Reader::new(frame);
-    let _npdu = match Npdu::decode(&mut r) {
-        Ok(n) => n,
-        Err(_) => return Ok(()),
-    };
-
-    if r.is_empty() {
-        return Ok(());
+    /// Runs [`exchange_confirmed_inner`](Self::exchange_confirmed_inner) and aborts the
+    /// transaction at the server when the client gives up on it: on a timeout, an
+    /// oversized or malformed segmented response, or when the caller drops the future.
+    /// Otherwise a server mid-way through a segmented exchange keeps its transaction
+    /// state, and may keep sending segments, until its own timers expire.
+    async fn exchange_confirmed(
+        &self,
+        address: DataLinkAddress,
+        tx: &[u8],
+        invoke_id: u8,
+        service_choice: u8,
+        timeout_window: Duration,
+    ) -> Result<Vec<u8>, ClientError> {
+        let _in_flight = InFlight::begin(
+            &self.invoke_ids,
+            &*self.runtime,
+            address,
+            invoke_id,
+            self.memory_limits.max_outstanding_transactions,
+        )?;
+        let mut cancel = AbortOnDrop {
+            router: &self.router,
+            address,
+            invoke_id,
+            armed: false,
+        };
+        let result = self
+            .exchange_confirmed_inner(
+                address,
+                tx,
+                invoke_id,
+                service_choice,
+                timeout_window,
+                &mut cancel,
+            )
+            .await;
+        cancel.armed = false;
+        if let Some(reason) = result.as_ref().err().and_then(abort_reason_for) {
+            if let Err(err) = self
+                .datalink
+                .send(address, &abort_frame(invoke_id, reason))
+                .await
+            {
+                log::debug!("failed to abort request {invoke_id} to {address}: {err}");
+            }
+        }
+        result
     }
 
-    let first = match r.peek_u8() {
-        Ok(b) => b,
-        Err(_) => return Ok(()),
-    };
-    let apdu_type = ApduType::from_u8(first >> 4);
+    /// Drives a [`ClientTransaction`] for the request in `tx`: sends it, feeds the state
+    /// machine every answer to it, sends the replies it asks for and turns its deadlines
+    /// into timers. Other frames read meanwhile that nobody waits for go to the inline
+    /// server handler. `cancel` is armed once the request is sent.
+    async fn exchange_confirmed_inner(
+        &self,
+        address: DataLinkAddress,
+        tx: &[u8],
+        invoke_id: u8,
+        service_choice: u8,
+        timeout_window: Duration,
+        cancel: &mut AbortOnDrop<'_>,
+    ) -> Result<Vec<u8>, ClientError> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(invoke_id = invoke_id, service = service_choice, target = %address, "sending confirmed request");
+        let _answers = self.router.expect_transaction(address, invoke_id);
+        let mut txn = ClientTransaction::new(invoke_id, service_choice, Vec::new())
+            .with_timeout_ms(duration_millis(timeout_window))
+            .with_postponed_timeout_ms(duration_millis(self.reply_postponed_timeout))
+            .with_retries(self.request_retries)
+            .with_payload_limit(self.memory_limits.max_reassembly_bytes);
+        let epoch = {
+            let _io_lock = self.request_io_lock.lock().await;
+            self.router.send_queued_aborts(&*self.datalink).await;
+            let epoch = self.runtime.now();
+            txn.on_sent(0);
+            self.send_confirmed_request(address, tx, epoch + timeout_window)
+                .await?;
+            epoch
+        };
+        cancel.armed = true;
 
-    match apdu_type {
-        Some(ApduType::UnconfirmedRequest) => {
-            let header = match UnconfirmedRequestHeader::decode(&mut r) {
-                Ok(h) => h,
-                Err(_) => return Ok(()),
-            };
-            if header.service_choice == 0x08 {
-                // Who-Is — check optional limits then respond with I-Am.
-                let limits = dispatch_decode_who_is_limits(&mut r);
-                if dispatch_matches_who_is(device_id, limits) {
-                    dispatch_send_i_am(datalink, device_id, vendor_id, source).await;
+        let mut reply = [0u8; 16];
+        loop {
+            let deadline = match txn
+                .poll_timeout(duration_millis(
+                    self.runtime.now().saturating_duration_since(epoch),
+                ))
+                .map_err(transaction_error)?
+            {
+                TimerStep::Wait(at) => epoch + Duration::from_millis(at),
+                TimerStep::Resend => {
+                    let now = duration_millis(self.runtime.now().saturating_duration_since(epoch));
+                    txn.on_sent(now);
+                    let deadline = txn.deadline().unwrap_or(now);
+                    self.send_request(address, tx, epoch + Duration::from_millis(deadline))
+                        .await?;
+                    continue;
                 }
-            }
-            // All other unconfirmed services are ignored.
-        }
-        Some(ApduType::ConfirmedRequest) => {
-            let header = match ConfirmedRequestHeader::decode(&mut r) {
-                Ok(h) => h,
-                Err(_) => return Ok(()),
             };
-            let invoke_id = header.invoke_id;
-            match header.service_choice {
-                SERVICE_READ_PROPERTY => {
-                    dispatch_handle_read_property(datalink, handler, &mut r, invoke_id, source)
-                        .await;
-                }
-                SERVICE_WRITE_PROPERTY => {
-                    dispatch_handle_write_property(datalink, handler, &mut r, invoke_id, source)
-                        .await;
-                }
-                SERVICE_READ_PROPERTY_MULTIPLE => {
-                    dispatch_handle_read_property_multiple(
-                        datalink, handler, &mut r, invoke_id, source,
-                    )
-                    .await;
-                }
-                SERVICE_WRITE_PROPERTY_MULTIPLE => {
-                    dispatch_handle_write_property_multiple(
-                        datalink, handler, &mut r, invoke_id, source,
-                    )
-                    .await;
-                }
-                SERVICE_SUBSCRIBE_COV => {
-                    dispatch_handle_subscribe_cov(datalink, handler, &mut r, invoke_id, source)
-                        .await;
+
+            let mut rx = [0u8; 1500];
+            let (n, src) = match self
+                .recv_ignoring_invalid_frame(&[(address, invoke_id)], &mut rx, deadline)
+                .await
+            {
+                Err(ClientError::Timeout) => continue,
+                result => result?,
+            };
+            if src != address {
+                self.dispatch_unmatched(&rx[..n], src).await;
+                continue;
+            }
+
+            let apdu = extract_apdu(&rx[..n])?;
+            let segment = ComplexAckHeader::decode(&mut Reader::new(apdu))
+                .is_ok_and(|header| header.segmented);
+            let step = txn.handle_apdu(apdu, &mut reply).map_err(|err| match err {
+                TransactionError::ResponseTooLarge => ClientError::ResponseTooLarge {
+                    limit: self.memory_limits.max_reassembly_bytes,
+                },
+                err => transaction_error(err),
+            })?;
+            if segment && !matches!(step, TransactionStep::Ignored) {
+                self.update_device_stats(address, |stats| stats.segments_received += 1);
+            }
+            match step {
+                TransactionStep::Ignored => {
+                    self.dispatch_unmatched(&rx[..n], src).await;
                 }
-                SERVICE_CREATE_OBJECT => {
-                    dispatch_handle_create_object(datalink, handler, &mut r, invoke_id, source)
-                        .await;
+                TransactionStep::Reply(len) => {
+                    self.send_segment_ack(address, &reply[..len]).await?;
                 }
-                SERVICE_DELETE_OBJECT => {
-                    dispatch_handle_delete_object(datalink, handler, &mut r, invoke_id, source)
-                        .await;
+                TransactionStep::Postponed => {
+                    log::debug!("{address} postponed its reply to request {invoke_id}");
+                    self.update_device_stats(address, |stats| stats.postponed_replies += 1);
+                    txn.on_reply_postponed(duration_millis(
+                        self.runtime.now().saturating_duration_since(epoch),
+                    ));
                 }
-                _ => {
-                    // Unknown service — send Reject with UNRECOGNIZED_SERVICE (0x08).
-                    dispatch_send_reject(datalink, invoke_id, 0x08, source).await;
+                TransactionStep::Complete { reply: ack } => {
+                    if let Some(len) = ack {
+                        self.send_segment_ack(address, &reply[..len]).await?;
+                    }
+                    return Ok(txn.into_payload());
                 }
             }
         }
-        _ => {
-            // Not a request — ignore.
+    }
+
+    /// Handles a frame that is not a response to the current request: late responses to
+    /// finished transactions are dropped, anything else goes to the inline server handler.
+    async fn dispatch_unmatched(&self, frame: &[u8], src: DataLinkAddress) {
+        let stale = extract_apdu(frame).is_ok_and(|apdu| {
+            self.invoke_ids
+                .lock()
+                .expect("invoke id lock poisoned")
+                .is_stale_response(src, apdu, self.runtime.now())
+        });
+        if stale {
+            log::debug!("dropping late response from {src} to a finished transaction");
+            return;
+        }
+        self.dispatch_to_server_handler(frame, src).await;
+    }
+
+    /// Offers a frame that is not a response to our request to the inline server handler.
+    async fn dispatch_to_server_handler(&self, frame: &[u8], src: DataLinkAddress) {
+        if let Some(ref handler) = self.server_handler {
+            let _ = dispatch_incoming_request(
+                &*self.datalink,
+                handler.as_ref(),
+                self.server_device_id,
+                self.server_vendor_id,
+                frame,
+                src,
+            )
+            .await;
         }
     }
 
-    Ok(())
-}
+    /// Send a GetAlarmSummary request and return the list of active alarms on the device.
+    pub async fn get_alarm_summary(
+        &self,
+        address: DataLinkAddress,
+    ) -> Result<Vec<AlarmSummaryItem>, ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = GetAlarmSummaryRequest { invoke_id };
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            request.encode(w)
+        })?;
+        let payload = self
+            .await_complex_ack_payload_or_error(
+                address,
+                &tx,
+                invoke_id,
+                SERVICE_GET_ALARM_SUMMARY,
+                self.response_timeout,
+            )
+            .await?;
+        let mut pr = Reader::new(&payload);
+        let parsed = GetAlarmSummaryAck::decode_after_header(&mut pr)?;
+        Ok(into_client_alarm_summary(parsed.summaries))
+    }
 
-// ── Inline dispatch helpers ──────────────────────────────────────────────────
+    /// Send a GetEnrollmentSummary request and return the list of event enrollments on the device.
+    pub async fn get_enrollment_summary(
+        &self,
+        address: DataLinkAddress,
+    ) -> Result<Vec<EnrollmentSummaryItem>, ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = GetEnrollmentSummaryRequest { invoke_id };
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            request.encode(w)
+        })?;
+        let payload = self
+            .await_complex_ack_payload_or_error(
+                address,
+                &tx,
+                invoke_id,
+                SERVICE_GET_ENROLLMENT_SUMMARY,
+                self.response_timeout,
+            )
+            .await?;
+        let mut pr = Reader::new(&payload);
+        let parsed = GetEnrollmentSummaryAck::decode_after_header(&mut pr)?;
+        Ok(into_client_enrollment_summary(parsed.summaries))
+    }
 
-async fn dispatch_send_i_am<D: DataLink>(
-    datalink: &D,
-    device_id: u32,
-    vendor_id: u16,
-    target: DataLinkAddress,
-) {
-    let device_oid = ObjectId::new(ObjectType::Device, device_id);
-    let req = IAmRequest {
-        device_id: device_oid,
-        max_apdu: 1476,
-        segmentation: 3, // no-segmentation
-        vendor_id: vendor_id as u32,
-    };
-    let mut buf = [0u8; 128];
-    let mut w = Writer::new(&mut buf);
-    if Npdu::new(0).encode(&mut w).is_err() {
-        return;
+    /// Send a GetEventInformation request and return active event summaries from the device.
+    ///
+    /// Pass `last_received_object_id` to page through results; the returned
+    /// [`EventInformationResult::more_events`] indicates whether another call is needed.
+    pub async fn get_event_information(
+        &self,
+        address: DataLinkAddress,
+        last_received_object_id: Option<ObjectId>,
+    ) -> Result<EventInformationResult, ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = GetEventInformationRequest {
+            last_received_object_id,
+            invoke_id,
+        };
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            request.encode(w)
+        })?;
+        let payload = self
+            .await_complex_ack_payload_or_error(
+                address,
+                &tx,
+                invoke_id,
+                SERVICE_GET_EVENT_INFORMATION,
+                self.response_timeout,
+            )
+            .await?;
+        let mut pr = Reader::new(&payload);
+        let parsed = GetEventInformationAck::decode_after_header(&mut pr)?;
+        Ok(EventInformationResult {
+            summaries: into_client_event_information(parsed.summaries),
+            more_events: parsed.more_events,
+        })
     }
-    if req.encode(&mut w).is_err() {
-        return;
+
+    /// Send an AcknowledgeAlarm request to the device.
+    pub async fn acknowledge_alarm(
+        &self,
+        address: DataLinkAddress,
+        mut request: AcknowledgeAlarmRequest<'_>,
+    ) -> Result<(), ClientError> {
+        request.invoke_id = self.next_invoke_id(address).await?;
+        let invoke_id = request.invoke_id;
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            request.encode(w)
+        })?;
+        self.await_simple_ack_or_error(
+            address,
+            &tx,
+            invoke_id,
+            SERVICE_ACKNOWLEDGE_ALARM,
+            self.response_timeout,
+        )
+        .await
     }
-    let _ = datalink.send(target, w.as_written()).await;
-}
 
-async fn dispatch_handle_read_property<D: DataLink>(
-    datalink: &D,
-    handler: &dyn crate::server::ServiceHandler,
-    r: &mut Reader<'_>,
-    invoke_id: u8,
-    source: DataLinkAddress,
-) {
-    let object_id = match crate::decode_ctx_object_id(r) {
-        Ok(v) => v,
-        Err(_) => return,
-    };
-    let property_id_raw = match crate::decode_ctx_unsigned(r) {
-        Ok(v) => v,
-        Err(_) => return,
-    };
-    let property_id = PropertyId::from_u32(property_id_raw);
+    /// Read a contiguous byte range from a BACnet File object using stream access.
+    ///
+    /// `file_start_position` is the byte offset (may be negative for end-relative access).
+    /// `requested_octet_count` is the number of bytes to read.
+    pub async fn atomic_read_file_stream(
+        &self,
+        address: DataLinkAddress,
+        file_object_id: ObjectId,
+        file_start_position: i32,
+        requested_octet_count: u32,
+    ) -> Result<AtomicReadFileResult, ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = AtomicReadFileRequest::stream(
+            file_object_id,
+            file_start_position,
+            requested_octet_count,
+            invoke_id,
+        );
+        self.atomic_read_file(address, request).await
+    }
 
-    // Optional array index [2].
-    let array_index = dispatch_decode_optional_array_index(r);
+    /// Read a range of records from a BACnet File object using record access.
+    ///
+    /// `file_start_record` is the first record index (may be negative for end-relative access).
+    /// `requested_record_count` is the number of records to read.
+    pub async fn atomic_read_file_record(
+        &self,
+        address: DataLinkAddress,
+        file_object_id: ObjectId,
+        file_start_record: i32,
+        requested_record_count: u32,
+    ) -> Result<AtomicReadFileResult, ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = AtomicReadFileRequest::record(
+            file_object_id,
+            file_start_record,
+            requested_record_count,
+            invoke_id,
+        );
+        self.atomic_read_file(address, request).await
+    }
 
-    match handler.read_property(object_id, property_id, array_index) {
-        Ok(value) => {
-            let borrowed = dispatch_client_value_to_borrowed(&value);
-            let mut buf = [0u8; 1400];
-            let mut w = Writer::new(&mut buf);
-            if Npdu::new(0).encode(&mut w).is_err() {
-                return;
-            }
-            if (ComplexAckHeader {
-                segmented: false,
-                more_follows: false,
+    async fn atomic_read_file(
+        &self,
+        address: DataLinkAddress,
+        request: AtomicReadFileRequest,
+    ) -> Result<AtomicReadFileResult, ClientError> {
+        let invoke_id = request.invoke_id;
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            request.encode(w)
+        })?;
+        let payload = self
+            .await_complex_ack_payload_or_error(
+                address,
+                &tx,
                 invoke_id,
-                sequence_number: None,
-                proposed_window_size: None,
-                service_choice: SERVICE_READ_PROPERTY,
-            })
-            .encode(&mut w)
-            .is_err()
-            {
-                return;
-            }
-            if encode_ctx_unsigned(&mut w, 0, object_id.raw()).is_err() {
-                return;
-            }
-            if encode_ctx_unsigned(&mut w, 1, property_id.to_u32()).is_err() {
-                return;
-            }
-            if (Tag::Opening { tag_num: 3 }).encode(&mut w).is_err() {
-                return;
-            }
-            if encode_application_data_value(&mut w, &borrowed).is_err() {
-                return;
-            }
-            if (Tag::Closing { tag_num: 3 }).encode(&mut w).is_err() {
-                return;
-            }
-            let _ = datalink.send(source, w.as_written()).await;
-        }
-        Err(err) => {
-            dispatch_send_error(datalink, invoke_id, SERVICE_READ_PROPERTY, err, source).await;
-        }
+                SERVICE_ATOMIC_READ_FILE,
+                self.response_timeout,
+            )
+            .await?;
+        let mut pr = Reader::new(&payload);
+        let parsed = AtomicReadFileAck::decode_after_header(&mut pr)?;
+        Ok(into_client_atomic_read_result(parsed))
     }
-}
-
-async fn dispatch_handle_write_property<D: DataLink>(
-    datalink: &D,
-    handler: &dyn crate::server::ServiceHandler,
-    r: &mut Reader<'_>,
-    invoke_id: u8,
-    source: DataLinkAddress,
-) {
-    let object_id = match crate::decode_ctx_object_id(r) {
-        Ok(v) => v,
-        Err(_) => return,
-    };
-    let property_id_raw = match crate::decode_ctx_unsigned(r) {
-        Ok(v) => v,
-        Err(_) => return,
-    };
-    let property_id = PropertyId::from_u32(property_id_raw);
-
-    // Optional array index [2].
-    let next_tag = match Tag::decode(r) {
-        Ok(t) => t,
-        Err(_) => return,
-    };
-    let (array_index, value_start_tag) = match next_tag {
-        Tag::Context { tag_num: 2, len } => {
-            let idx = match decode_unsigned(r, len as usize) {
-                Ok(v) => v,
-                Err(_) => return,
-            };
-            let vt = match Tag::decode(r) {
-                Ok(t) => t,
-                Err(_) => return,
-            };
-            (Some(idx), vt)
-        }
-        other => (None, other),
-    };
 
-    if value_start_tag != (Tag::Opening { tag_num: 3 }) {
-        return;
+    /// Write `file_data` to a BACnet File object starting at `file_start_position` using stream
+    /// access. The returned result contains the actual start position used by the device.
+    pub async fn atomic_write_file_stream(
+        &self,
+        address: DataLinkAddress,
+        file_object_id: ObjectId,
+        file_start_position: i32,
+        file_data: &[u8],
+    ) -> Result<AtomicWriteFileResult, ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = AtomicWriteFileRequest::stream(
+            file_object_id,
+            file_start_position,
+            file_data,
+            invoke_id,
+        );
+        self.atomic_write_file(address, request).await
     }
 
-    let val = match rustbac_core::services::value_codec::decode_application_data_value(r) {
-        Ok(v) => v,
-        Err(_) => return,
-    };
-
-    match Tag::decode(r) {
-        Ok(Tag::Closing { tag_num: 3 }) => {}
-        _ => return,
+    /// Write records to a BACnet File object starting at `file_start_record` using record access.
+    ///
+    /// Each element of `file_record_data` is one record's raw bytes.
+    pub async fn atomic_write_file_record(
+        &self,
+        address: DataLinkAddress,
+        file_object_id: ObjectId,
+        file_start_record: i32,
+        file_record_data: &[&[u8]],
+    ) -> Result<AtomicWriteFileResult, ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let request = AtomicWriteFileRequest::record(
+            file_object_id,
+            file_start_record,
+            file_record_data,
+            invoke_id,
+        );
+        self.atomic_write_file(address, request).await
     }
 
-    // Optional priority [4].
-    let priority = if !r.is_empty() {
-        match Tag::decode(r) {
-            Ok(Tag::Context { tag_num: 4, len }) => match decode_unsigned(r, len as usize) {
-                Ok(p) => Some(p as u8),
-                Err(_) => return,
-            },
-            _ => None,
-        }
-    } else {
-        None
-    };
-
-    let client_val = crate::data_value_to_client(val);
-
-    match handler.write_property(object_id, property_id, array_index, client_val, priority) {
-        Ok(()) => {
-            let mut buf = [0u8; 32];
-            let mut w = Writer::new(&mut buf);
-            if Npdu::new(0).encode(&mut w).is_err() {
-                return;
-            }
-            if (SimpleAck {
+    async fn atomic_write_file(
+        &self,
+        address: DataLinkAddress,
+        request: AtomicWriteFileRequest<'_>,
+    ) -> Result<AtomicWriteFileResult, ClientError> {
+        let invoke_id = request.invoke_id;
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            request.encode(w)
+        })?;
+        let payload = self
+            .await_complex_ack_payload_or_error(
+                address,
+                &tx,
                 invoke_id,
-                service_choice: SERVICE_WRITE_PROPERTY,
-            })
-            .encode(&mut w)
-            .is_err()
-            {
-                return;
-            }
-            let _ = datalink.send(source, w.as_written()).await;
-        }
-        Err(err) => {
-            dispatch_send_error(datalink, invoke_id, SERVICE_WRITE_PROPERTY, err, source).await;
-        }
+                SERVICE_ATOMIC_WRITE_FILE,
+                self.response_timeout,
+            )
+            .await?;
+        let mut pr = Reader::new(&payload);
+        let parsed = AtomicWriteFileAck::decode_after_header(&mut pr)?;
+        Ok(into_client_atomic_write_result(parsed))
     }
-}
-
-async fn dispatch_handle_read_property_multiple<D: DataLink>(
-    datalink: &D,
-    handler: &dyn crate::server::ServiceHandler,
-    r: &mut Reader<'_>,
-    invoke_id: u8,
-    source: DataLinkAddress,
-) {
-    type PropRefs = Vec<(PropertyId, Option<u32>)>;
-    let mut specs: Vec<(ObjectId, PropRefs)> = Vec::new();
 
-    while !r.is_empty() {
-        let object_id = match crate::decode_ctx_object_id(r) {
-            Ok(v) => v,
-            Err(_) => return,
-        };
-        match Tag::decode(r) {
-            Ok(Tag::Opening { tag_num: 1 }) => {}
-            _ => return,
-        }
-        let mut props: Vec<(PropertyId, Option<u32>)> = Vec::new();
-        loop {
-            let tag = match Tag::decode(r) {
-                Ok(t) => t,
-                Err(_) => return,
-            };
-            if tag == (Tag::Closing { tag_num: 1 }) {
-                break;
-            }
-            let property_id = match tag {
-                Tag::Context { tag_num: 0, len } => match decode_unsigned(r, len as usize) {
-                    Ok(v) => PropertyId::from_u32(v),
-                    Err(_) => return,
-                },
-                _ => return,
-            };
-            let array_index = if !r.is_empty() {
-                match dispatch_peek_context_tag(r, 1) {
-                    Some(len) => {
-                        match Tag::decode(r) {
-                            Ok(_) => {}
-                            Err(_) => return,
-                        }
-                        match decode_unsigned(r, len as usize) {
-                            Ok(idx) => Some(idx),
-                            Err(_) => return,
-                        }
-                    }
-                    None => None,
-                }
-            } else {
-                None
-            };
-            props.push((property_id, array_index));
-        }
-        specs.push((object_id, props));
+    /// Send a SubscribeCOV request to start (or renew) a COV subscription on the device.
+    ///
+    /// Use [`cancel_cov_subscription`](Self::cancel_cov_subscription) to unsubscribe.
+    pub async fn subscribe_cov(
+        &self,
+        address: DataLinkAddress,
+        mut request: SubscribeCovRequest,
+    ) -> Result<(), ClientError> {
+        request.invoke_id = self.next_invoke_id(address).await?;
+        let invoke_id = request.invoke_id;
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            request.encode(w)
+        })?;
+        self.await_simple_ack_or_error(
+            address,
+            &tx,
+            invoke_id,
+            SERVICE_SUBSCRIBE_COV,
+            self.response_timeout,
+        )
+        .await
     }
 
-    let mut buf = [0u8; 1400];
-    let mut w = Writer::new(&mut buf);
-    if Npdu::new(0).encode(&mut w).is_err() {
-        return;
-    }
-    if (ComplexAckHeader {
-        segmented: false,
-        more_follows: false,
-        invoke_id,
-        sequence_number: None,
-        proposed_window_size: None,
-        service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
-    })
-    .encode(&mut w)
-    .is_err()
-    {
-        return;
+    /// Cancel an existing COV subscription identified by `subscriber_process_id` and
+    /// `monitored_object_id`.
+    pub async fn cancel_cov_subscription(
+        &self,
+        address: DataLinkAddress,
+        subscriber_process_id: u32,
+        monitored_object_id: ObjectId,
+    ) -> Result<(), ClientError> {
+        self.subscribe_cov(
+            address,
+            SubscribeCovRequest::cancel(subscriber_process_id, monitored_object_id, 0),
+        )
+        .await
     }
 
-    for (object_id, props) in &specs {
-        if encode_ctx_unsigned(&mut w, 0, object_id.raw()).is_err() {
-            return;
-        }
-        if (Tag::Opening { tag_num: 1 }).encode(&mut w).is_err() {
-            return;
-        }
+    /// Send a SubscribeCOVProperty request to subscribe to changes of a specific property.
+    ///
+    /// Use [`cancel_cov_property_subscription`](Self::cancel_cov_property_subscription) to
+    /// unsubscribe.
+    pub async fn subscribe_cov_property(
+        &self,
+        address: DataLinkAddress,
+        mut request: SubscribeCovPropertyRequest,
+    ) -> Result<(), ClientError> {
+        request.invoke_id = self.next_invoke_id(address).await?;
+        let invoke_id = request.invoke_id;
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            request.encode(w)
+        })?;
+        self.await_simple_ack_or_error(
+            address,
+            &tx,
+            invoke_id,
+            SERVICE_SUBSCRIBE_COV_PROPERTY,
+            self.response_timeout,
+        )
+        .await
+    }
 
-        for (property_id, array_index) in props {
-            if encode_ctx_unsigned(&mut w, 2, property_id.to_u32()).is_err() {
-                return;
-            }
-            if let Some(idx) = array_index {
-                if encode_ctx_unsigned(&mut w, 3, *idx).is_err() {
-                    return;
-                }
-            }
-            if (Tag::Opening { tag_num: 4 }).encode(&mut w).is_err() {
-                return;
-            }
+    /// Cancel an existing COV property subscription.
+    ///
+    /// The combination of `subscriber_process_id`, `monitored_object_id`,
+    /// `monitored_property_id`, and `monitored_property_array_index` must match the
+    /// subscription to cancel.
+    pub async fn cancel_cov_property_subscription(
+        &self,
+        address: DataLinkAddress,
+        subscriber_process_id: u32,
+        monitored_object_id: ObjectId,
+        monitored_property_id: PropertyId,
+        monitored_property_array_index: Option<u32>,
+    ) -> Result<(), ClientError> {
+        self.subscribe_cov_property(
+            address,
+            SubscribeCovPropertyRequest::cancel(
+                subscriber_process_id,
+                monitored_object_id,
+                monitored_property_id,
+                monitored_property_array_index,
+                0,
+            ),
+        )
+        .await
+    }
 
-            match handler.read_property(*object_id, *property_id, *array_index) {
-                Ok(value) => {
-                    let borrowed = dispatch_client_value_to_borrowed(&value);
-                    if encode_application_data_value(&mut w, &borrowed).is_err() {
-                        return;
-                    }
-                }
-                Err(err) => {
-                    let (class, code) = dispatch_error_class_code(err);
-                    if (Tag::Opening { tag_num: 5 }).encode(&mut w).is_err() {
-                        return;
-                    }
-                    if encode_ctx_unsigned(&mut w, 0, class as u32).is_err() {
-                        return;
-                    }
-                    if encode_ctx_unsigned(&mut w, 1, code as u32).is_err() {
-                        return;
-                    }
-                    if (Tag::Closing { tag_num: 5 }).encode(&mut w).is_err() {
-                        return;
-                    }
-                }
-            }
+    /// Read the Active_COV_Subscriptions of the Device object `device`: every
+    /// SubscribeCOV and SubscribeCOVProperty subscription it holds, from any client.
+    pub async fn read_active_cov_subscriptions(
+        &self,
+        address: DataLinkAddress,
+        device: ObjectId,
+    ) -> Result<Vec<ActiveCovSubscription>, ClientError> {
+        let payload = self
+            .read_property_payload(address, device, PropertyId::ActiveCovSubscriptions)
+            .await?;
+        let ack = ActiveCovSubscriptionsAck::decode_after_header(&mut Reader::new(&payload))?;
+        Ok(ack.subscriptions.into_iter().map(Into::into).collect())
+    }
 
-            if (Tag::Closing { tag_num: 4 }).encode(&mut w).is_err() {
-                return;
-            }
-        }
+    /// Read the Active_COV_Multiple_Subscriptions of the Device object `device`: every
+    /// SubscribeCOVPropertyMultiple subscription it holds, from any client.
+    pub async fn read_active_cov_multiple_subscriptions(
+        &self,
+        address: DataLinkAddress,
+        device: ObjectId,
+    ) -> Result<Vec<ActiveCovMultipleSubscription>, ClientError> {
+        let payload = self
+            .read_property_payload(address, device, PropertyId::ActiveCovMultipleSubscriptions)
+            .await?;
+        let ack =
+            ActiveCovMultipleSubscriptionsAck::decode_after_header(&mut Reader::new(&payload))?;
+        Ok(ack.subscriptions.into_iter().map(Into::into).collect())
+    }
 
-        if (Tag::Closing { tag_num: 1 }).encode(&mut w).is_err() {
-            return;
-        }
+    /// Read a range of entries from a list/log property by absolute position.
+    ///
+    /// `reference_index` is the 1-based starting entry index. A positive `count` reads
+    /// forward; negative reads backward from `reference_index`.
+    pub async fn read_range_by_position(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        property_id: PropertyId,
+        array_index: Option<u32>,
+        reference_index: i32,
+        count: i16,
+    ) -> Result<ReadRangeResult, ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let req = ReadRangeRequest::by_position(
+            object_id,
+            property_id,
+            array_index,
+            reference_index,
+            count,
+            invoke_id,
+        );
+        self.read_range_with_request(address, req).await
     }
 
-    let _ = datalink.send(source, w.as_written()).await;
-}
+    /// Read a range of entries from a list/log property anchored at a sequence number.
+    ///
+    /// `reference_sequence` is the sequence number of the anchor entry. A positive `count`
+    /// reads forward; negative reads backward.
+    pub async fn read_range_by_sequence_number(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        property_id: PropertyId,
+        array_index: Option<u32>,
+        reference_sequence: u32,
+        count: i16,
+    ) -> Result<ReadRangeResult, ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let req = ReadRangeRequest::by_sequence_number(
+            object_id,
+            property_id,
+            array_index,
+            reference_sequence,
+            count,
+            invoke_id,
+        );
+        self.read_range_with_request(address, req).await
+    }
 
-async fn dispatch_handle_write_property_multiple<D: DataLink>(
-    datalink: &D,
-    handler: &dyn crate::server::ServiceHandler,
-    r: &mut Reader<'_>,
-    invoke_id: u8,
-    source: DataLinkAddress,
-) {
-    while !r.is_empty() {
-        let object_id = match crate::decode_ctx_object_id(r) {
-            Ok(v) => v,
-            Err(_) => return,
-        };
-        match Tag::decode(r) {
-            Ok(Tag::Opening { tag_num: 1 }) => {}
-            _ => return,
-        }
-        loop {
-            let tag = match Tag::decode(r) {
-                Ok(t) => t,
-                Err(_) => return,
-            };
-            if tag == (Tag::Closing { tag_num: 1 }) {
-                break;
+    /// Read a range of entries from a list/log property anchored at a timestamp.
+    ///
+    /// `at` is the reference date and time. A positive `count` reads forward from that
+    /// timestamp; negative reads backward.
+    pub async fn read_range_by_time(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        property_id: PropertyId,
+        array_index: Option<u32>,
+        at: (Date, Time),
+        count: i16,
+    ) -> Result<ReadRangeResult, ClientError> {
+        let (date, time) = at;
+        let invoke_id = self.next_invoke_id(address).await?;
+        let req = ReadRangeRequest::by_time(
+            object_id,
+            property_id,
+            array_index,
+            date,
+            time,
+            count,
+            invoke_id,
+        );
+        self.read_range_with_request(address, req).await
+    }
+
+    /// Read trend-log records logged since the UTC instant `since`.
+    ///
+    /// The device's `UTC_Offset` and `Daylight_Savings_Status` turn `since` into the
+    /// device-local reference time for a by-time ReadRange of `Log_Buffer`, and each
+    /// returned record's timestamp back into UTC. A device without `UTC_Offset` fails with
+    /// its error rather than being assumed to run on UTC.
+    pub async fn read_range_since(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        since: SystemTime,
+        count: i16,
+    ) -> Result<TimedReadRangeResult, ClientError> {
+        let utc_offset_minutes = self.read_device_utc_offset(address).await?;
+        let range = self
+            .read_range_by_time(
+                address,
+                object_id,
+                PropertyId::LogBuffer,
+                None,
+                local_date_time(since, utc_offset_minutes),
+                count,
+            )
+            .await?;
+        let records = range
+            .items
+            .into_iter()
+            .map(|item| TimedRecord {
+                timestamp: record_utc_time(&item, utc_offset_minutes),
+                item,
+            })
+            .collect();
+        Ok(TimedReadRangeResult {
+            object_id: range.object_id,
+            property_id: range.property_id,
+            result_flags: range.result_flags,
+            item_count: range.item_count,
+            utc_offset_minutes,
+            records,
+        })
+    }
+
+    /// The minutes the device's local time is behind UTC, daylight saving included.
+    async fn read_device_utc_offset(&self, address: DataLinkAddress) -> Result<i32, ClientError> {
+        let device = ObjectId::new(ObjectType::Device, WILDCARD_DEVICE_INSTANCE);
+        let utc_offset = match self
+            .read_property(address, device, PropertyId::UtcOffset)
+            .await?
+        {
+            ClientDataValue::Signed(minutes) => minutes,
+            ClientDataValue::Unsigned(minutes) => {
+                i32::try_from(minutes).map_err(|_| ClientError::UnsupportedResponse)?
             }
-            let property_id = match tag {
-                Tag::Context { tag_num: 0, len } => match decode_unsigned(r, len as usize) {
-                    Ok(v) => PropertyId::from_u32(v),
-                    Err(_) => return,
-                },
-                _ => return,
+            _ => return Err(ClientError::UnsupportedResponse),
+        };
+        // Daylight_Savings_Status is optional; a device without it never shifts.
+        let dst = match self
+            .read_property(address, device, PropertyId::DaylightSavingsStatus)
+            .await
+        {
+            Ok(ClientDataValue::Boolean(active)) => active,
+            Ok(_) => return Err(ClientError::UnsupportedResponse),
+            Err(err) if matches!(err.root(), ClientError::RemoteServiceError { .. }) => false,
+            Err(err) => return Err(err),
+        };
+        Ok(if dst { utc_offset - 60 } else { utc_offset })
+    }
+
+    async fn read_range_with_request(
+        &self,
+        address: DataLinkAddress,
+        req: ReadRangeRequest,
+    ) -> Result<ReadRangeResult, ClientError> {
+        let invoke_id = req.invoke_id;
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            req.encode(w)
+        })?;
+        let payload = self
+            .await_complex_ack_payload_or_error(
+                address,
+                &tx,
+                invoke_id,
+                SERVICE_READ_RANGE,
+                self.response_timeout,
+            )
+            .await?;
+        let mut pr = Reader::new(&payload);
+        let parsed = ReadRangeAck::decode_after_header(&mut pr)?;
+        let mut result = into_client_read_range(parsed)?;
+        result.items = result.items.into_iter().map(|v| self.intern(v)).collect();
+        Ok(result)
+    }
+
+    /// The router handing the frames this client and its clones receive to their
+    /// receivers.
+    ///
+    /// [`subscribe`](InboundRouter::subscribe) to keep notifications that arrive between
+    /// calls to [`recv_cov_notification`](Self::recv_cov_notification) or
+    /// [`recv_event_notification`](Self::recv_event_notification).
+    pub fn inbound_router(&self) -> &std::sync::Arc<InboundRouter> {
+        &self.router
+    }
+
+    /// A [`DataLink`] over this client's link that receives only frames of `kinds`, for
+    /// code that reads a link itself.
+    ///
+    /// A notification listener on `routed_link(&[InboundKind::CovNotification,
+    /// InboundKind::EventNotification, InboundKind::Discovery])` runs beside this
+    /// client's requests without either stealing the other's frames.
+    pub fn routed_link(&self, kinds: &[InboundKind]) -> RoutedLink<D> {
+        RoutedLink::new(
+            self.datalink.clone(),
+            self.router.clone(),
+            self.runtime.clone(),
+            kinds,
+        )
+    }
+
+    /// Wait up to `wait` for a single incoming COV notification (confirmed or unconfirmed).
+    ///
+    /// Returns `Ok(Some(_))` when a notification arrives, `Ok(None)` on timeout, and
+    /// `Err` on transport failure. Confirmed notifications are automatically acknowledged.
+    /// Segmented confirmed notifications return [`ClientError::UnsupportedResponse`].
+    /// Requests of other tasks run while this waits; notifications they read are handed
+    /// over.
+    pub async fn recv_cov_notification(
+        &self,
+        wait: Duration,
+    ) -> Result<Option<CovNotification>, ClientError> {
+        let deadline = self.runtime.now() + wait;
+
+        while self.runtime.now() < deadline {
+            let mut rx = [0u8; 1500];
+            let recv = self
+                .router
+                .recv(
+                    &*self.datalink,
+                    &*self.runtime,
+                    &[InboundKind::CovNotification],
+                    &mut rx,
+                    Some(deadline),
+                )
+                .await;
+            let (n, source) = match recv {
+                Ok(v) => v,
+                Err(ClientError::Timeout) => break,
+                Err(e) => return Err(e),
             };
-            let array_index = if !r.is_empty() {
-                match dispatch_peek_context_tag(r, 1) {
-                    Some(len) => {
-                        let _ = Tag::decode(r);
-                        decode_unsigned(r, len as usize).ok()
+
+            let apdu = extract_apdu(&rx[..n])?;
+            let first = *apdu.first().ok_or(ClientError::UnsupportedResponse)?;
+            match ApduType::from_u8(first >> 4) {
+                Some(ApduType::UnconfirmedRequest) => {
+                    let mut r = Reader::new(apdu);
+                    let header = UnconfirmedRequestHeader::decode(&mut r)?;
+                    if header.service_choice != SERVICE_UNCONFIRMED_COV_NOTIFICATION {
+                        continue;
                     }
-                    None => None,
+                    let cov = CovNotificationRequest::decode_after_header(&mut r)?;
+                    let cov = into_client_cov_notification(source, false, cov)?;
+                    return Ok(Some(self.intern_cov_notification(cov)));
                 }
-            } else {
-                None
-            };
-            match Tag::decode(r) {
-                Ok(Tag::Opening { tag_num: 2 }) => {}
-                _ => return,
+                Some(ApduType::ConfirmedRequest) => {
+                    let mut r = Reader::new(apdu);
+                    let header = ConfirmedRequestHeader::decode(&mut r)?;
+                    if header.service_choice != SERVICE_CONFIRMED_COV_NOTIFICATION {
+                        continue;
+                    }
+                    if header.segmented {
+                        return Err(ClientError::UnsupportedResponse);
+                    }
+
+                    let cov = CovNotificationRequest::decode_after_header(&mut r)?;
+                    self.send_simple_ack(
+                        source,
+                        header.invoke_id,
+                        SERVICE_CONFIRMED_COV_NOTIFICATION,
+                    )
+                    .await?;
+                    let cov = into_client_cov_notification(source, true, cov)?;
+                    return Ok(Some(self.intern_cov_notification(cov)));
+                }
+                _ => continue,
             }
-            let val = match rustbac_core::services::value_codec::decode_application_data_value(r) {
+        }
+
+        Ok(None)
+    }
+
+    /// Wait up to `wait` for a single incoming event notification (confirmed or unconfirmed).
+    ///
+    /// Returns `Ok(Some(_))` when a notification arrives, `Ok(None)` on timeout, and
+    /// `Err` on transport failure. Confirmed notifications are automatically acknowledged.
+    /// Segmented confirmed notifications return [`ClientError::UnsupportedResponse`].
+    /// Requests of other tasks run while this waits; notifications they read are handed
+    /// over.
+    pub async fn recv_event_notification(
+        &self,
+        wait: Duration,
+    ) -> Result<Option<EventNotification>, ClientError> {
+        let deadline = self.runtime.now() + wait;
+
+        while self.runtime.now() < deadline {
+            let mut rx = [0u8; 1500];
+            let recv = self
+                .router
+                .recv(
+                    &*self.datalink,
+                    &*self.runtime,
+                    &[InboundKind::EventNotification],
+                    &mut rx,
+                    Some(deadline),
+                )
+                .await;
+            let (n, source) = match recv {
                 Ok(v) => v,
-                Err(_) => return,
+                Err(ClientError::Timeout) => break,
+                Err(e) => return Err(e),
             };
-            match Tag::decode(r) {
-                Ok(Tag::Closing { tag_num: 2 }) => {}
-                _ => return,
-            }
-            let priority = if !r.is_empty() {
-                match dispatch_peek_context_tag(r, 3) {
-                    Some(len) => {
-                        let _ = Tag::decode(r);
-                        decode_unsigned(r, len as usize).ok().map(|p| p as u8)
+
+            let apdu = extract_apdu(&rx[..n])?;
+            let first = *apdu.first().ok_or(ClientError::UnsupportedResponse)?;
+            match ApduType::from_u8(first >> 4) {
+                Some(ApduType::UnconfirmedRequest) => {
+                    let mut r = Reader::new(apdu);
+                    let header = UnconfirmedRequestHeader::decode(&mut r)?;
+                    if header.service_choice != SERVICE_UNCONFIRMED_EVENT_NOTIFICATION {
+                        continue;
                     }
-                    None => None,
+                    let notification = EventNotificationRequest::decode_after_header(&mut r)?;
+                    return Ok(Some(into_client_event_notification(
+                        source,
+                        false,
+                        notification,
+                    )));
                 }
-            } else {
-                None
-            };
-            let client_val = crate::data_value_to_client(val);
-            if let Err(err) =
-                handler.write_property(object_id, property_id, array_index, client_val, priority)
-            {
-                dispatch_send_error(
-                    datalink,
-                    invoke_id,
-                    SERVICE_WRITE_PROPERTY_MULTIPLE,
-                    err,
-                    source,
-                )
-                .await;
-                return;
+                Some(ApduType::ConfirmedRequest) => {
+                    let mut r = Reader::new(apdu);
+                    let header = ConfirmedRequestHeader::decode(&mut r)?;
+                    if header.service_choice != SERVICE_CONFIRMED_EVENT_NOTIFICATION {
+                        continue;
+                    }
+                    if header.segmented {
+                        return Err(ClientError::UnsupportedResponse);
+                    }
+                    let notification = EventNotificationRequest::decode_after_header(&mut r)?;
+                    self.send_simple_ack(
+                        source,
+                        header.invoke_id,
+                        SERVICE_CONFIRMED_EVENT_NOTIFICATION,
+                    )
+                    .await?;
+                    return Ok(Some(into_client_event_notification(
+                        source,
+                        true,
+                        notification,
+                    )));
+                }
+                _ => continue,
             }
         }
+
+        Ok(None)
     }
-    // All properties written successfully — send SimpleAck.
-    let mut buf = [0u8; 32];
-    let mut w = Writer::new(&mut buf);
-    if Npdu::new(0).encode(&mut w).is_err() {
-        return;
-    }
-    if (SimpleAck {
-        invoke_id,
-        service_choice: SERVICE_WRITE_PROPERTY_MULTIPLE,
-    })
-    .encode(&mut w)
-    .is_err()
-    {
-        return;
-    }
-    let _ = datalink.send(source, w.as_written()).await;
-}
-
-async fn dispatch_handle_subscribe_cov<D: DataLink>(
-    datalink: &D,
-    handler: &dyn crate::server::ServiceHandler,
-    r: &mut Reader<'_>,
-    invoke_id: u8,
-    source: DataLinkAddress,
-) {
-    // subscriberProcessIdentifier [0]
-    let subscriber_process_id = match Tag::decode(r) {
-        Ok(Tag::Context { tag_num: 0, len }) => match decode_unsigned(r, len as usize) {
-            Ok(v) => v,
-            Err(_) => return,
-        },
-        _ => return,
-    };
-    // monitoredObjectIdentifier [1]
-    let monitored_object_id = match crate::decode_ctx_object_id(r) {
-        Ok(v) => v,
-        Err(_) => return,
-    };
-    // issueConfirmedNotifications [2]
-    let issue_confirmed = match Tag::decode(r) {
-        Ok(Tag::Context { tag_num: 2, len }) => match decode_unsigned(r, len as usize) {
-            Ok(v) => v != 0,
-            Err(_) => return,
-        },
-        _ => return,
-    };
-    // optional lifetime [3]
-    let lifetime = if !r.is_empty() {
-        match dispatch_peek_context_tag(r, 3) {
-            Some(len) => {
-                let _ = Tag::decode(r);
-                decode_unsigned(r, len as usize).ok()
-            }
-            None => None,
-        }
-    } else {
-        None
-    };
 
-    match handler.subscribe_cov(
-        subscriber_process_id,
-        monitored_object_id,
-        issue_confirmed,
-        lifetime,
-    ) {
-        Ok(()) => {
-            let mut buf = [0u8; 32];
-            let mut w = Writer::new(&mut buf);
-            if Npdu::new(0).encode(&mut w).is_err() {
-                return;
-            }
-            if (SimpleAck {
-                invoke_id,
-                service_choice: SERVICE_SUBSCRIBE_COV,
-            })
-            .encode(&mut w)
-            .is_err()
-            {
-                return;
-            }
-            let _ = datalink.send(source, w.as_written()).await;
-        }
-        Err(err) => {
-            dispatch_send_error(datalink, invoke_id, SERVICE_SUBSCRIBE_COV, err, source).await;
-        }
+    /// Send a ReadProperty request and return the property value as a [`ClientDataValue`].
+    ///
+    /// Use [`read_property_multiple`](Self::read_property_multiple) to fetch several
+    /// properties in a single round-trip.
+    pub async fn read_property(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        property_id: PropertyId,
+    ) -> Result<ClientDataValue, ClientError> {
+        self.read_property_indexed(address, object_id, property_id, None)
+            .await
     }
-}
 
-async fn dispatch_handle_create_object<D: DataLink>(
-    datalink: &D,
-    handler: &dyn crate::server::ServiceHandler,
-    r: &mut Reader<'_>,
-    invoke_id: u8,
-    source: DataLinkAddress,
-) {
-    // objectSpecifier [0] opening
-    match Tag::decode(r) {
-        Ok(Tag::Opening { tag_num: 0 }) => {}
-        _ => return,
-    }
-    // objectType [0] — context-tagged enumerated
-    let object_type_raw = match Tag::decode(r) {
-        Ok(Tag::Context { tag_num: 0, len }) => match decode_unsigned(r, len as usize) {
-            Ok(v) => v,
-            Err(_) => return,
-        },
-        _ => return,
-    };
-    let object_type = ObjectType::from_u16(object_type_raw as u16);
-    // objectSpecifier [0] closing
-    match Tag::decode(r) {
-        Ok(Tag::Closing { tag_num: 0 }) => {}
-        _ => return,
+    async fn read_property_indexed(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        property_id: PropertyId,
+        array_index: Option<u32>,
+    ) -> Result<ClientDataValue, ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let req = ReadPropertyRequest {
+            object_id,
+            property_id,
+            array_index,
+            invoke_id,
+        };
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            req.encode(w)
+        })?;
+        let payload = self
+            .await_complex_ack_payload_or_error(
+                address,
+                &tx,
+                invoke_id,
+                SERVICE_READ_PROPERTY,
+                self.response_timeout,
+            )
+            .await?;
+        let mut pr = Reader::new(&payload);
+        let parsed = ReadPropertyAck::decode_after_header_with(
+            &mut pr,
+            self.decode_mode,
+            &mut report_skipped_tag(address, "ReadProperty"),
+        )?;
+        Ok(self.intern(into_client_value(parsed.value)?))
     }
 
-    match handler.create_object(object_type) {
-        Ok(created_id) => {
-            let mut buf = [0u8; 64];
-            let mut w = Writer::new(&mut buf);
-            if Npdu::new(0).encode(&mut w).is_err() {
-                return;
-            }
-            if (ComplexAckHeader {
-                segmented: false,
-                more_follows: false,
+    /// Send a WriteProperty request to set a single property on the device.
+    pub async fn write_property(
+        &self,
+        address: DataLinkAddress,
+        mut request: WritePropertyRequest<'_>,
+    ) -> Result<(), ClientError> {
+        self.check_write_policy(address, || {
+            WriteAction::WriteProperty(PropertyWrite {
+                object_id: request.object_id,
+                property_id: request.property_id,
+                array_index: request.array_index,
+                value: audit_value(&request.value),
+                priority: request.priority,
+            })
+        })?;
+        request.invoke_id = self.next_invoke_id(address).await?;
+        let invoke_id = request.invoke_id;
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            request.encode(w)
+        })?;
+        let timestamp = SystemTime::now();
+        let result = self
+            .await_simple_ack_or_error(
+                address,
+                &tx,
                 invoke_id,
-                sequence_number: None,
-                proposed_window_size: None,
-                service_choice: SERVICE_CREATE_OBJECT,
+                SERVICE_WRITE_PROPERTY,
+                self.response_timeout,
+            )
+            .await;
+        self.audit_write(address, timestamp, &result, |_| {
+            WriteAction::WriteProperty(PropertyWrite {
+                object_id: request.object_id,
+                property_id: request.property_id,
+                array_index: request.array_index,
+                value: audit_value(&request.value),
+                priority: request.priority,
             })
-            .encode(&mut w)
-            .is_err()
-            {
-                return;
+        });
+        result
+    }
+
+    /// Write a property and confirm the device applied it by reading it back.
+    ///
+    /// After each WriteProperty the property is re-read (after `options.settle_delay`) and
+    /// compared with [`values_match`](crate::verify::values_match). On mismatch the write is
+    /// repeated up to `options.retries` more times. Returns the verified value as read back,
+    /// or [`ClientError::VerificationFailed`] carrying the last observed value.
+    pub async fn write_verified(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        property_id: PropertyId,
+        value: ClientDataValue,
+        priority: Option<u8>,
+        options: WriteVerifyOptions,
+    ) -> Result<ClientDataValue, ClientError> {
+        let attempts = u32::from(options.retries) + 1;
+        let mut observed = ClientDataValue::Null;
+        for _ in 0..attempts {
+            let request = WritePropertyRequest {
+                object_id,
+                property_id,
+                value: dispatch_client_value_to_borrowed(&value),
+                priority,
+                ..Default::default()
+            };
+            self.write_property(address, request).await?;
+            if !options.settle_delay.is_zero() {
+                self.runtime.sleep(options.settle_delay).await;
             }
-            if encode_ctx_unsigned(&mut w, 0, created_id.raw()).is_err() {
-                return;
+            observed = self.read_property(address, object_id, property_id).await?;
+            if crate::verify::values_match(&value, &observed, options.tolerance) {
+                return Ok(observed);
             }
-            let _ = datalink.send(source, w.as_written()).await;
-        }
-        Err(err) => {
-            dispatch_send_error(datalink, invoke_id, SERVICE_CREATE_OBJECT, err, source).await;
         }
+        Err(ClientError::VerificationFailed {
+            expected: value,
+            observed,
+            attempts,
+        })
     }
-}
 
-async fn dispatch_handle_delete_object<D: DataLink>(
-    datalink: &D,
-    handler: &dyn crate::server::ServiceHandler,
-    r: &mut Reader<'_>,
-    invoke_id: u8,
-    source: DataLinkAddress,
-) {
-    let object_id = match crate::decode_ctx_object_id(r) {
-        Ok(v) => v,
-        Err(_) => return,
-    };
+    /// Apply a batch of writes as a unit, undoing already-applied writes if one fails.
+    ///
+    /// `writes` is a slice of `(object_id, property_id, value, priority)` tuples. The current
+    /// value of every targeted property is read first; if any of those reads fails nothing is
+    /// written and the error is returned. Writes are then issued one at a time in order. When a
+    /// write fails, the writes applied so far are undone in reverse order: writes made at a
+    /// command priority are relinquished (Null at the same priority), other writes are restored
+    /// to the recorded value. The returned [`WriteBatchReport`] lists what was applied, rolled
+    /// back, and failed.
+    pub async fn write_with_rollback(
+        &self,
+        address: DataLinkAddress,
+        writes: &[(ObjectId, PropertyId, ClientDataValue, Option<u8>)],
+    ) -> Result<WriteBatchReport, ClientError> {
+        let mut items = Vec::with_capacity(writes.len());
+        for (object_id, property_id, value, priority) in writes {
+            let previous = self
+                .read_property(address, *object_id, *property_id)
+                .await?;
+            items.push(WriteBatchItem {
+                object_id: *object_id,
+                property_id: *property_id,
+                value: value.clone(),
+                priority: *priority,
+                previous,
+            });
+        }
 
-    match handler.delete_object(object_id) {
-        Ok(()) => {
-            let mut buf = [0u8; 32];
-            let mut w = Writer::new(&mut buf);
-            if Npdu::new(0).encode(&mut w).is_err() {
-                return;
-            }
-            if (SimpleAck {
-                invoke_id,
-                service_choice: SERVICE_DELETE_OBJECT,
-            })
-            .encode(&mut w)
-            .is_err()
-            {
-                return;
+        let mut report = WriteBatchReport::default();
+        for item in items {
+            let request = WritePropertyRequest {
+                object_id: item.object_id,
+                property_id: item.property_id,
+                value: dispatch_client_value_to_borrowed(&item.value),
+                priority: item.priority,
+                ..Default::default()
+            };
+            match self.write_property(address, request).await {
+                Ok(()) => report.applied.push(item),
+                Err(error) => {
+                    report.failed = Some(WriteBatchFailure { item, error });
+                    break;
+                }
             }
-            let _ = datalink.send(source, w.as_written()).await;
         }
-        Err(err) => {
-            dispatch_send_error(datalink, invoke_id, SERVICE_DELETE_OBJECT, err, source).await;
+        if report.failed.is_none() {
+            return Ok(report);
         }
-    }
-}
 
-async fn dispatch_send_error<D: DataLink>(
-    datalink: &D,
-    invoke_id: u8,
-    service_choice: u8,
-    err: crate::server::BacnetServiceError,
-    target: DataLinkAddress,
-) {
-    let (class, code) = dispatch_error_class_code(err);
-    let mut buf = [0u8; 64];
-    let mut w = Writer::new(&mut buf);
-    if Npdu::new(0).encode(&mut w).is_err() {
-        return;
-    }
-    // Error PDU header: type=5 (Error)
-    if w.write_u8(0x50).is_err() {
-        return;
-    }
-    if w.write_u8(invoke_id).is_err() {
-        return;
-    }
-    if w.write_u8(service_choice).is_err() {
-        return;
+        while let Some(item) = report.applied.pop() {
+            let restore = match item.priority {
+                Some(_) => DataValue::Null,
+                None => dispatch_client_value_to_borrowed(&item.previous),
+            };
+            let request = WritePropertyRequest {
+                object_id: item.object_id,
+                property_id: item.property_id,
+                value: restore,
+                priority: item.priority,
+                ..Default::default()
+            };
+            match self.write_property(address, request).await {
+                Ok(()) => report.rolled_back.push(item),
+                Err(error) => report
+                    .rollback_failed
+                    .push(WriteBatchFailure { item, error }),
+            }
+        }
+        Ok(report)
     }
-    // error-class: application Enumerated
-    if (Tag::Application {
-        tag: rustbac_core::encoding::tag::AppTag::Enumerated,
-        len: 1,
-    })
-    .encode(&mut w)
-    .is_err()
-    {
-        return;
-    }
-    if w.write_u8(class).is_err() {
-        return;
-    }
-    // error-code: application Enumerated
-    if (Tag::Application {
-        tag: rustbac_core::encoding::tag::AppTag::Enumerated,
-        len: 1,
-    })
-    .encode(&mut w)
-    .is_err()
-    {
-        return;
-    }
-    if w.write_u8(code).is_err() {
-        return;
-    }
-    let _ = datalink.send(target, w.as_written()).await;
-}
 
-async fn dispatch_send_reject<D: DataLink>(
-    datalink: &D,
-    invoke_id: u8,
-    reason: u8,
-    target: DataLinkAddress,
-) {
-    let mut buf = [0u8; 16];
-    let mut w = Writer::new(&mut buf);
-    if Npdu::new(0).encode(&mut w).is_err() {
-        return;
-    }
-    // Reject PDU: type=6 (Reject)
-    if w.write_u8(0x60).is_err() {
-        return;
+    /// Write many properties of one device, keeping several WriteProperty requests
+    /// outstanding at once.
+    ///
+    /// `writes` is a slice of `(object_id, property_id, value, priority)` tuples. Up to the
+    /// [write burst window](Self::with_write_burst_window) of requests are sent with
+    /// distinct invoke ids before the first SimpleACK is awaited, and each answer lets the
+    /// next request go out, so a large setpoint download takes a fraction of the
+    /// round-trips of serial writes. Every write is attempted; the report lists the
+    /// indexes written and the failures. Only errors of the link itself end the burst
+    /// early.
+    pub async fn write_burst(
+        &self,
+        address: DataLinkAddress,
+        writes: &[(ObjectId, PropertyId, ClientDataValue, Option<u8>)],
+    ) -> Result<WriteBurstReport, ClientError> {
+        let mut report = WriteBurstReport::default();
+        let epoch = self.runtime.now();
+        let elapsed_ms = || duration_millis(self.runtime.now().saturating_duration_since(epoch));
+        let mut queued = writes.iter().enumerate();
+        let limit = self.memory_limits.max_outstanding_transactions;
+        let window = self.write_burst_window.min(limit);
+        let mut outstanding: Vec<BurstWrite<'_>> = Vec::with_capacity(window);
+        let mut reply = [0u8; 16];
+
+        loop {
+            while outstanding.len() < window {
+                // Requests of other tasks count against the limit; wait for our own
+                // answers to make room.
+                let full = self
+                    .invoke_ids
+                    .lock()
+                    .expect("invoke id lock poisoned")
+                    .in_flight()
+                    >= limit;
+                if full && !outstanding.is_empty() {
+                    break;
+                }
+                let Some((index, (object_id, property_id, value, priority))) = queued.next() else {
+                    break;
+                };
+                if let Err(error) = self.check_write_policy(address, || {
+                    WriteAction::WriteProperty(PropertyWrite {
+                        object_id: *object_id,
+                        property_id: *property_id,
+                        array_index: None,
+                        value: value.clone(),
+                        priority: *priority,
+                    })
+                }) {
+                    report.failed.push(WriteBurstFailure { index, error });
+                    continue;
+                }
+                let invoke_id = match self.next_invoke_id(address).await {
+                    Ok(invoke_id) => invoke_id,
+                    Err(error) => {
+                        report.failed.push(WriteBurstFailure { index, error });
+                        continue;
+                    }
+                };
+                let request = WritePropertyRequest {
+                    object_id: *object_id,
+                    property_id: *property_id,
+                    value: dispatch_client_value_to_borrowed(value),
+                    priority: *priority,
+                    invoke_id,
+                    ..Default::default()
+                };
+                let tx = match self.encode_with_growth(|w| {
+                    self.request_npdu().encode(w)?;
+                    request.encode(w)
+                }) {
+                    Ok(tx) => tx,
+                    Err(error) => {
+                        report.failed.push(WriteBurstFailure { index, error });
+                        continue;
+                    }
+                };
+                let in_flight = match InFlight::begin(
+                    &self.invoke_ids,
+                    &*self.runtime,
+                    address,
+                    invoke_id,
+                    limit,
+                ) {
+                    Ok(in_flight) => in_flight,
+                    Err(error) => {
+                        report.failed.push(WriteBurstFailure { index, error });
+                        continue;
+                    }
+                };
+                let answers = self.router.expect_transaction(address, invoke_id);
+                let started = (SystemTime::now(), self.runtime.now());
+                let mut txn = ClientTransaction::new(invoke_id, SERVICE_WRITE_PROPERTY, Vec::new())
+                    .with_timeout_ms(duration_millis(self.response_timeout))
+                    .with_postponed_timeout_ms(duration_millis(self.reply_postponed_timeout))
+                    .with_retries(self.request_retries);
+                txn.on_sent(elapsed_ms());
+                self.send_request(address, &tx, started.1 + self.response_timeout)
+                    .await?;
+                outstanding.push(BurstWrite {
+                    index,
+                    tx,
+                    txn,
+                    started,
+                    _in_flight: in_flight,
+                    _answers: answers,
+                });
+            }
+            if outstanding.is_empty() {
+                return Ok(report);
+            }
+
+            // Resend or expire requests whose timer ran out; wait for the earliest other.
+            let mut deadline = None;
+            let mut i = 0;
+            while i < outstanding.len() {
+                let slot = &mut outstanding[i];
+                match slot.txn.poll_timeout(elapsed_ms()) {
+                    Ok(TimerStep::Wait(at)) => {
+                        let at = epoch + Duration::from_millis(at);
+                        deadline = Some(deadline.map_or(at, |d: Instant| d.min(at)));
+                        i += 1;
+                    }
+                    Ok(TimerStep::Resend) => {
+                        let now = elapsed_ms();
+                        slot.txn.on_sent(now);
+                        let at = slot.txn.deadline().unwrap_or(now);
+                        self.send_request(address, &slot.tx, epoch + Duration::from_millis(at))
+                            .await?;
+                    }
+                    Err(err) => {
+                        let slot = outstanding.swap_remove(i);
+                        self.finish_burst_write(
+                            address,
+                            writes,
+                            slot,
+                            Err(transaction_error(err)),
+                            &mut report,
+                        )
+                        .await;
+                    }
+                }
+            }
+            let Some(deadline) = deadline else {
+                continue;
+            };
+
+            let awaited: Vec<TransactionKey> = outstanding
+                .iter()
+                .map(|slot| (address, slot.txn.invoke_id()))
+                .collect();
+            let mut rx = [0u8; 1500];
+            let (n, src) = match self
+                .recv_ignoring_invalid_frame(&awaited, &mut rx, deadline)
+                .await
+            {
+                Err(ClientError::Timeout) => continue,
+                result => result?,
+            };
+            let apdu = match extract_apdu(&rx[..n]) {
+                Ok(apdu) if src == address => apdu,
+                _ => {
+                    self.dispatch_unmatched(&rx[..n], src).await;
+                    continue;
+                }
+            };
+            let Some(position) = outstanding
+                .iter()
+                .position(|slot| apdu.get(1) == Some(&slot.txn.invoke_id()))
+            else {
+                self.dispatch_unmatched(&rx[..n], src).await;
+                continue;
+            };
+            let result = match outstanding[position].txn.handle_apdu(apdu, &mut reply) {
+                Ok(TransactionStep::Ignored) => {
+                    self.dispatch_unmatched(&rx[..n], src).await;
+                    continue;
+                }
+                Ok(TransactionStep::Reply(len)) => {
+                    self.send_segment_ack(address, &reply[..len]).await?;
+                    continue;
+                }
+                Ok(TransactionStep::Postponed) => {
+                    self.update_device_stats(address, |stats| stats.postponed_replies += 1);
+                    outstanding[position].txn.on_reply_postponed(elapsed_ms());
+                    continue;
+                }
+                // WriteProperty is answered with a SimpleACK; anything else is a
+                // misbehaving device.
+                Ok(TransactionStep::Complete { .. }) => {
+                    if outstanding[position].txn.payload().is_empty() {
+                        Ok(())
+                    } else {
+                        Err(ClientError::UnsupportedResponse)
+                    }
+                }
+                Err(err) => Err(transaction_error(err)),
+            };
+            let slot = outstanding.swap_remove(position);
+            self.finish_burst_write(address, writes, slot, result, &mut report)
+                .await;
+        }
     }
-    if w.write_u8(invoke_id).is_err() {
-        return;
+
+    /// Records the end of one [`write_burst`](Self::write_burst) write, aborting it at the
+    /// device if the client gave up on it.
+    async fn finish_burst_write(
+        &self,
+        address: DataLinkAddress,
+        writes: &[(ObjectId, PropertyId, ClientDataValue, Option<u8>)],
+        write: BurstWrite<'_>,
+        result: Result<(), ClientError>,
+        report: &mut WriteBurstReport,
+    ) {
+        let invoke_id = write.txn.invoke_id();
+        let outcome = TransactionOutcome::from_result(&result, |_| TransactionOutcome::SimpleAck);
+        self.record_transaction(
+            address,
+            &write.tx,
+            invoke_id,
+            SERVICE_WRITE_PROPERTY,
+            write.started,
+            outcome,
+        );
+        let index = write.index;
+        self.audit_write(address, write.started.0, &result, |_| {
+            let (object_id, property_id, value, priority) = &writes[index];
+            WriteAction::WriteProperty(PropertyWrite {
+                object_id: *object_id,
+                property_id: *property_id,
+                array_index: None,
+                value: value.clone(),
+                priority: *priority,
+            })
+        });
+        match result {
+            Ok(()) => report.written.push(index),
+            Err(error) => {
+                if let Some(reason) = abort_reason_for(&error) {
+                    if let Err(err) = self
+                        .datalink
+                        .send(address, &abort_frame(invoke_id, reason))
+                        .await
+                    {
+                        log::debug!("failed to abort request {invoke_id} to {address}: {err}");
+                    }
+                }
+                report.failed.push(WriteBurstFailure {
+                    index,
+                    error: error.with_context(RequestContext {
+                        address,
+                        service_choice: SERVICE_WRITE_PROPERTY,
+                        invoke_id,
+                    }),
+                });
+            }
+        }
     }
-    if w.write_u8(reason).is_err() {
-        return;
+
+    /// Send a ReadPropertyMultiple request to fetch several properties of one object in a
+    /// single round-trip.
+    ///
+    /// Returns pairs of `(PropertyId, ClientDataValue)` in the order returned by the device.
+    pub async fn read_property_multiple(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        property_ids: &[PropertyId],
+    ) -> Result<Vec<(PropertyId, ClientDataValue)>, ClientError> {
+        let refs: Vec<PropertyReference> = property_ids
+            .iter()
+            .copied()
+            .map(|property_id| PropertyReference {
+                property_id,
+                array_index: None,
+            })
+            .collect();
+        let specs = [ReadAccessSpecification {
+            object_id,
+            properties: &refs,
+        }];
+
+        let payload = self.send_read_property_multiple(address, &specs).await?;
+        let mut pr = Reader::new(&payload);
+        let parsed = ReadPropertyMultipleAck::decode_after_header_with(
+            &mut pr,
+            self.decode_mode,
+            &mut report_skipped_tag(address, "ReadPropertyMultiple"),
+        )?;
+        let mut out = Vec::new();
+        for access in parsed.results {
+            if access.object_id != object_id {
+                continue;
+            }
+            for item in access.results {
+                out.push((
+                    item.property_id,
+                    self.intern(into_client_value(item.value)?),
+                ));
+            }
+        }
+        Ok(out)
     }
-    let _ = datalink.send(target, w.as_written()).await;
-}
 
-/// Decode Who-Is optional [0] low-limit and [1] high-limit (dispatch-local copy).
-fn dispatch_decode_who_is_limits(r: &mut Reader<'_>) -> Option<(u32, u32)> {
-    if r.is_empty() {
-        return None;
+    /// Read a whole object in one ReadPropertyMultiple with one of the special property
+    /// identifiers: [`PropertyId::All`], [`PropertyId::Required`] or
+    /// [`PropertyId::Optional`].
+    ///
+    /// The device answers with the properties the identifier stands for; those it cannot
+    /// read are returned in [`ObjectProperties::errors`] rather than failing the call. If
+    /// the device does not handle ReadPropertyMultiple at all it is remembered, as for
+    /// [`read_properties_with_fallback`](Self::read_properties_with_fallback), and the
+    /// error is returned.
+    pub async fn read_property_set(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        selector: PropertyId,
+    ) -> Result<ObjectProperties, ClientError> {
+        let refs = [PropertyReference::new(selector)];
+        let specs = [ReadAccessSpecification {
+            object_id,
+            properties: &refs,
+        }];
+        let payload = match self.send_read_property_multiple(address, &specs).await {
+            Ok(payload) => payload,
+            Err(err) => {
+                if is_rpm_unavailable(&err) && !is_response_too_large(&err) {
+                    if let Ok(mut devices) = self.rpm_fallback_devices.write() {
+                        devices.insert(address);
+                    }
+                }
+                return Err(err);
+            }
+        };
+        let mut pr = Reader::new(&payload);
+        let parsed = ReadPropertyMultipleAck::decode_after_header_with_errors(
+            &mut pr,
+            self.decode_mode,
+            &mut report_skipped_tag(address, "ReadPropertyMultiple"),
+        )?;
+        let mut properties = ObjectProperties {
+            object_id,
+            values: Vec::new(),
+            errors: Vec::new(),
+        };
+        for access in parsed.results {
+            if access.object_id != object_id {
+                continue;
+            }
+            for item in access.results {
+                properties.values.push((
+                    item.property_id,
+                    self.intern(into_client_value(item.value)?),
+                ));
+            }
+            properties.errors.extend(access.errors);
+        }
+        Ok(properties)
     }
-    let tag0 = Tag::decode(r).ok()?;
-    let low = match tag0 {
-        Tag::Context { tag_num: 0, len } => decode_unsigned(r, len as usize).ok()?,
-        _ => return None,
-    };
-    let tag1 = Tag::decode(r).ok()?;
-    let high = match tag1 {
-        Tag::Context { tag_num: 1, len } => decode_unsigned(r, len as usize).ok()?,
-        _ => return None,
-    };
-    Some((low, high))
-}
 
-fn dispatch_error_class_code(err: crate::server::BacnetServiceError) -> (u8, u8) {
-    use crate::server::BacnetServiceError;
-    match err {
-        BacnetServiceError::UnknownObject => (1, 31),
-        BacnetServiceError::UnknownProperty => (2, 32),
-        BacnetServiceError::WriteAccessDenied => (2, 40),
-        BacnetServiceError::InvalidDataType => (2, 9),
-        BacnetServiceError::ServiceNotSupported => (5, 53),
+    async fn send_read_property_multiple(
+        &self,
+        address: DataLinkAddress,
+        specs: &[ReadAccessSpecification<'_>],
+    ) -> Result<Vec<u8>, ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let req = ReadPropertyMultipleRequest { specs, invoke_id };
+
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            req.encode(w)
+        })?;
+        self.await_complex_ack_payload_or_error(
+            address,
+            &tx,
+            invoke_id,
+            SERVICE_READ_PROPERTY_MULTIPLE,
+            self.response_timeout,
+        )
+        .await
     }
-}
 
-fn dispatch_matches_who_is(device_id: u32, limits: Option<(u32, u32)>) -> bool {
-    match limits {
-        None => true,
-        Some((low, high)) => device_id >= low && device_id <= high,
+    /// Read several properties of one object, using ReadPropertyMultiple when the device
+    /// supports it and sequential ReadProperty requests otherwise.
+    ///
+    /// ReadPropertyMultiple is tried first. If the device rejects it as an unrecognized
+    /// service, or aborts because the response would need segmentation it does not
+    /// support, the properties are read one at a time and the device is remembered so
+    /// later calls go straight to ReadProperty. Results are returned in request order.
+    pub async fn read_properties_with_fallback(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        property_ids: &[PropertyId],
+    ) -> Result<Vec<(PropertyId, ClientDataValue)>, ClientError> {
+        if !self.uses_rpm_fallback(address) {
+            match self
+                .read_property_multiple(address, object_id, property_ids)
+                .await
+            {
+                Ok(values) => return Ok(values),
+                Err(err) if is_rpm_unavailable(&err) => {
+                    log::debug!("ReadPropertyMultiple unavailable on {address} ({err}); falling back to ReadProperty");
+                    if let Ok(mut devices) = self.rpm_fallback_devices.write() {
+                        devices.insert(address);
+                    }
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        let mut out = Vec::with_capacity(property_ids.len());
+        for &property_id in property_ids {
+            let value = self.read_property(address, object_id, property_id).await?;
+            out.push((property_id, value));
+        }
+        Ok(out)
     }
-}
 
-fn dispatch_decode_optional_array_index(r: &mut Reader<'_>) -> Option<u32> {
-    if r.is_empty() {
-        return None;
+    /// Returns `true` if `address` is known not to handle ReadPropertyMultiple.
+    pub fn uses_rpm_fallback(&self, address: DataLinkAddress) -> bool {
+        self.rpm_fallback_devices
+            .read()
+            .map(|devices| devices.contains(&address))
+            .unwrap_or(false)
     }
-    let tag = Tag::decode(r).ok()?;
-    match tag {
-        Tag::Context { tag_num: 2, len } => decode_unsigned(r, len as usize).ok(),
-        _ => None,
+
+    /// Forget that `address` needed the ReadProperty fallback, e.g. after a firmware update.
+    pub fn clear_rpm_fallback(&self, address: DataLinkAddress) {
+        if let Ok(mut devices) = self.rpm_fallback_devices.write() {
+            devices.remove(&address);
+        }
     }
-}
 
-fn dispatch_peek_context_tag(r: &mut Reader<'_>, tag_num: u8) -> Option<u32> {
-    let first = r.peek_u8().ok()?;
-    let is_context = (first & 0x08) != 0 && (first & 0x07) < 6;
-    if !is_context {
-        return None;
-    }
-    let this_tag_num = first >> 4;
-    if this_tag_num != tag_num {
-        return None;
-    }
-    let short_len = first & 0x07;
-    if short_len < 5 {
-        Some(short_len as u32)
-    } else {
-        None
-    }
-}
+    /// Read what the device at `address` supports from its Device object and tune later
+    /// requests to it.
+    ///
+    /// Each property is read with ReadProperty, so this works on devices without
+    /// ReadPropertyMultiple; properties the device does not have are left `None`. From
+    /// the answer the client remembers the device's Max_APDU for segmenting requests,
+    /// sends it ReadProperty instead of ReadPropertyMultiple when it does not list that
+    /// service, and asks for unsegmented responses when it cannot send segments (unless
+    /// [`set_device_segmentation`](Self::set_device_segmentation) already configured it).
+    pub async fn probe_capabilities(
+        &self,
+        address: DataLinkAddress,
+    ) -> Result<DeviceCapabilities, ClientError> {
+        let device = ObjectId::new(ObjectType::Device, WILDCARD_DEVICE_INSTANCE);
+        let values = self
+            .read_optional_properties(address, device, CAPABILITY_PROPERTIES)
+            .await?;
+        let capabilities = DeviceCapabilities::from_properties(&values);
 
-fn dispatch_client_value_to_borrowed(val: &ClientDataValue) -> DataValue<'_> {
-    match val {
-        ClientDataValue::Null => DataValue::Null,
-        ClientDataValue::Boolean(v) => DataValue::Boolean(*v),
-        ClientDataValue::Unsigned(v) => DataValue::Unsigned(*v),
-        ClientDataValue::Signed(v) => DataValue::Signed(*v),
-        ClientDataValue::Real(v) => DataValue::Real(*v),
-        ClientDataValue::Double(v) => DataValue::Double(*v),
-        ClientDataValue::OctetString(v) => DataValue::OctetString(v),
-        ClientDataValue::CharacterString(v) => DataValue::CharacterString(v),
-        ClientDataValue::BitString { unused_bits, data } => {
-            DataValue::BitString(rustbac_core::types::BitString {
-                unused_bits: *unused_bits,
-                data,
-            })
+        if let Some(max_apdu) = capabilities.max_apdu {
+            if let Ok(mut cache) = self.capability_cache.write() {
+                cache.insert(address, max_apdu as usize);
+            }
         }
-        ClientDataValue::Enumerated(v) => DataValue::Enumerated(*v),
-        ClientDataValue::Date(v) => DataValue::Date(*v),
-        ClientDataValue::Time(v) => DataValue::Time(*v),
-        ClientDataValue::ObjectId(v) => DataValue::ObjectId(*v),
-        ClientDataValue::Constructed { tag_num, values } => DataValue::Constructed {
-            tag_num: *tag_num,
-            values: values
-                .iter()
-                .map(dispatch_client_value_to_borrowed)
-                .collect(),
-        },
+        if capabilities.supports_service(ProtocolService::ReadPropertyMultiple) == Some(false) {
+            if let Ok(mut devices) = self.rpm_fallback_devices.write() {
+                devices.insert(address);
+            }
+        }
+        if capabilities.transmits_segments() == Some(false) {
+            if let Ok(mut devices) = self.device_segmentation.write() {
+                devices
+                    .entry(address)
+                    .or_insert_with(SegmentationOptions::unsegmented);
+            }
+        }
+        Ok(capabilities)
     }
-}
 
-fn remote_service_error(err: BacnetError) -> ClientError {
-    ClientError::RemoteServiceError {
-        service_choice: err.service_choice,
-        error_class_raw: err.error_class,
-        error_code_raw: err.error_code,
-        error_class: err.error_class.and_then(ErrorClass::from_u32),
-        error_code: err.error_code.and_then(ErrorCode::from_u32),
+    /// Read the Object_List of `device_id`.
+    ///
+    /// The whole array is requested first. If the device aborts because the response is
+    /// too large for it (no segmentation, buffer overflow or APDU too long), the list is
+    /// read with [`read_object_list_chunked`](Self::read_object_list_chunked) instead.
+    pub async fn read_object_list(
+        &self,
+        address: DataLinkAddress,
+        device_id: ObjectId,
+    ) -> Result<Vec<ObjectId>, ClientError> {
+        match self
+            .read_property(address, device_id, PropertyId::ObjectList)
+            .await
+        {
+            Ok(value) => Ok(object_ids_from_value(&value)),
+            Err(err) if is_response_too_large(&err) => {
+                log::debug!("Object_List of {address} does not fit one response ({err}); reading it in chunks");
+                self.read_object_list_chunked(address, device_id).await
+            }
+            Err(err) => Err(err),
+        }
     }
-}
 
-fn into_client_value(value: DataValue<'_>) -> Result<ClientDataValue, ClientError> {
-    Ok(match value {
-        DataValue::Null => ClientDataValue::Null,
-        DataValue::Boolean(v) => ClientDataValue::Boolean(v),
-        DataValue::Unsigned(v) => ClientDataValue::Unsigned(v),
-        DataValue::Signed(v) => ClientDataValue::Signed(v),
-        DataValue::Real(v) => ClientDataValue::Real(v),
-        DataValue::Double(v) => ClientDataValue::Double(v),
-        DataValue::OctetString(v) => ClientDataValue::OctetString(v.to_vec()),
-        DataValue::CharacterString(v) => ClientDataValue::CharacterString(v.to_string()),
-        DataValue::BitString(v) => ClientDataValue::BitString {
-            unused_bits: v.unused_bits,
-            data: v.data.to_vec(),
-        },
-        DataValue::Enumerated(v) => ClientDataValue::Enumerated(v),
-        DataValue::Date(v) => ClientDataValue::Date(v),
-        DataValue::Time(v) => ClientDataValue::Time(v),
-        DataValue::ObjectId(v) => ClientDataValue::ObjectId(v),
-        DataValue::Constructed { tag_num, values } => {
-            let mut children = Vec::with_capacity(values.len());
-            for child in values {
-                children.push(into_client_value(child)?);
+    /// Read the Object_List of `device_id` element by element, for devices that cannot
+    /// return the full array in one response.
+    ///
+    /// The array length is read from index 0, then the elements in index batches sized to
+    /// the device's max APDU (learned from its I-Am, 480 octets if unknown). Batches use
+    /// ReadPropertyMultiple, or one ReadProperty per index on devices without it, and are
+    /// halved whenever the device still aborts.
+    pub async fn read_object_list_chunked(
+        &self,
+        address: DataLinkAddress,
+        device_id: ObjectId,
+    ) -> Result<Vec<ObjectId>, ClientError> {
+        let count = match self
+            .read_property_indexed(address, device_id, PropertyId::ObjectList, Some(0))
+            .await?
+        {
+            ClientDataValue::Unsigned(count) => count,
+            _ => return Err(ClientError::UnsupportedResponse),
+        };
+
+        let peer_max_apdu = self
+            .capability_cache
+            .read()
+            .ok()
+            .and_then(|c| c.get(&address).copied())
+            .unwrap_or(DEFAULT_PEER_MAX_APDU);
+        let mut batch = (peer_max_apdu.saturating_sub(RPM_ACK_OVERHEAD_OCTETS)
+            / RPM_OBJECT_LIST_ELEMENT_OCTETS)
+            .max(1) as u32;
+
+        let mut object_ids = Vec::with_capacity(count as usize);
+        let mut next = 1;
+        while next <= count {
+            let len = batch.min(count - next + 1);
+            match self
+                .read_object_list_range(address, device_id, next, len)
+                .await
+            {
+                Ok(chunk) => {
+                    object_ids.extend(chunk);
+                    next += len;
+                }
+                Err(err) if len > 1 && is_response_too_large(&err) => batch = len / 2,
+                Err(err) => return Err(err),
             }
-            ClientDataValue::Constructed {
-                tag_num,
-                values: children,
+        }
+        Ok(object_ids)
+    }
+
+    async fn read_object_list_range(
+        &self,
+        address: DataLinkAddress,
+        device_id: ObjectId,
+        first: u32,
+        len: u32,
+    ) -> Result<Vec<ObjectId>, ClientError> {
+        if len > 1 && !self.uses_rpm_fallback(address) {
+            let refs: Vec<PropertyReference> = (first..first + len)
+                .map(|index| PropertyReference {
+                    property_id: PropertyId::ObjectList,
+                    array_index: Some(index),
+                })
+                .collect();
+            let specs = [ReadAccessSpecification {
+                object_id: device_id,
+                properties: &refs,
+            }];
+            match self.send_read_property_multiple(address, &specs).await {
+                Ok(payload) => {
+                    let mut pr = Reader::new(&payload);
+                    let parsed = ReadPropertyMultipleAck::decode_after_header_with(
+                        &mut pr,
+                        self.decode_mode,
+                        &mut report_skipped_tag(address, "ReadPropertyMultiple"),
+                    )?;
+                    let mut out = Vec::with_capacity(len as usize);
+                    for item in parsed.results.into_iter().flat_map(|access| access.results) {
+                        match into_client_value(item.value)? {
+                            ClientDataValue::ObjectId(object_id) => out.push(object_id),
+                            _ => return Err(ClientError::UnsupportedResponse),
+                        }
+                    }
+                    return Ok(out);
+                }
+                Err(err) if is_rpm_unavailable(&err) && !is_response_too_large(&err) => {
+                    log::debug!("ReadPropertyMultiple unavailable on {address} ({err}); falling back to ReadProperty");
+                    if let Ok(mut devices) = self.rpm_fallback_devices.write() {
+                        devices.insert(address);
+                    }
+                }
+                Err(err) => return Err(err),
             }
         }
-    })
-}
 
-fn into_client_alarm_summary(value: Vec<CoreAlarmSummaryItem<'_>>) -> Vec<AlarmSummaryItem> {
-    value
-        .into_iter()
-        .map(|item| AlarmSummaryItem {
-            object_id: item.object_id,
-            alarm_state_raw: item.alarm_state,
-            alarm_state: rustbac_core::services::acknowledge_alarm::EventState::from_u32(
-                item.alarm_state,
-            ),
-            acknowledged_transitions: ClientBitString {
-                unused_bits: item.acknowledged_transitions.unused_bits,
-                data: item.acknowledged_transitions.data.to_vec(),
-            },
-        })
-        .collect()
-}
+        let mut out = Vec::with_capacity(len as usize);
+        for index in first..first + len {
+            match self
+                .read_property_indexed(address, device_id, PropertyId::ObjectList, Some(index))
+                .await?
+            {
+                ClientDataValue::ObjectId(object_id) => out.push(object_id),
+                _ => return Err(ClientError::UnsupportedResponse),
+            }
+        }
+        Ok(out)
+    }
 
-fn into_client_enrollment_summary(
-    value: Vec<CoreEnrollmentSummaryItem>,
-) -> Vec<EnrollmentSummaryItem> {
-    value
-        .into_iter()
-        .map(|item| EnrollmentSummaryItem {
-            object_id: item.object_id,
-            event_type: item.event_type,
-            event_state_raw: item.event_state,
-            event_state: rustbac_core::services::acknowledge_alarm::EventState::from_u32(
-                item.event_state,
-            ),
-            priority: item.priority,
-            notification_class: item.notification_class,
-        })
-        .collect()
-}
+    /// Read the Property_List of `object_id`: the properties the object supports.
+    ///
+    /// As the standard specifies, the list omits Object_Identifier, Object_Name,
+    /// Object_Type and Property_List itself. Devices older than protocol revision 14 do
+    /// not implement it and answer with an unknown-property error.
+    pub async fn read_property_list(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+    ) -> Result<Vec<PropertyId>, ClientError> {
+        let value = self
+            .read_property(address, object_id, PropertyId::PropertyList)
+            .await?;
+        let values = match value {
+            ClientDataValue::Constructed { values, .. } => values,
+            single => vec![single],
+        };
+        values
+            .into_iter()
+            .map(|value| match value {
+                ClientDataValue::Enumerated(id) => Ok(PropertyId::from_u32(id)),
+                _ => Err(ClientError::UnsupportedResponse),
+            })
+            .collect()
+    }
 
-fn into_client_event_information(
-    value: Vec<CoreEventSummaryItem<'_>>,
-) -> Vec<EventInformationItem> {
-    value
-        .into_iter()
-        .map(|item| EventInformationItem {
-            object_id: item.object_id,
-            event_state_raw: item.event_state,
-            event_state: rustbac_core::services::acknowledge_alarm::EventState::from_u32(
-                item.event_state,
-            ),
-            acknowledged_transitions: ClientBitString {
-                unused_bits: item.acknowledged_transitions.unused_bits,
-                data: item.acknowledged_transitions.data.to_vec(),
-            },
-            notify_type: item.notify_type,
-            event_enable: ClientBitString {
-                unused_bits: item.event_enable.unused_bits,
-                data: item.event_enable.data.to_vec(),
-            },
-            event_priorities: item.event_priorities,
-        })
-        .collect()
-}
+    /// Send a WritePropertyMultiple request to set several properties of one object in a
+    /// single round-trip.
+    pub async fn write_property_multiple(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        properties: &[PropertyWriteSpec<'_>],
+    ) -> Result<(), ClientError> {
+        let specs = [WriteAccessSpecification {
+            object_id,
+            properties,
+        }];
+        self.check_write_policy(address, || {
+            WriteAction::WritePropertyMultiple(audit_property_writes(&specs))
+        })?;
+        let invoke_id = self.next_invoke_id(address).await?;
+        let req = WritePropertyMultipleRequest {
+            specs: &specs,
+            invoke_id,
+        };
 
-fn into_client_cov_notification(
-    source: DataLinkAddress,
-    confirmed: bool,
-    value: CovNotificationRequest<'_>,
-) -> Result<CovNotification, ClientError> {
-    let mut values = Vec::with_capacity(value.values.len());
-    for property in value.values {
-        values.push(CovPropertyValue {
-            property_id: property.property_id,
-            array_index: property.array_index,
-            value: into_client_value(property.value)?,
-            priority: property.priority,
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            req.encode(w)
+        })?;
+        let timestamp = SystemTime::now();
+        let result = self
+            .await_simple_ack_or_error(
+                address,
+                &tx,
+                invoke_id,
+                SERVICE_WRITE_PROPERTY_MULTIPLE,
+                self.response_timeout,
+            )
+            .await;
+        self.audit_write(address, timestamp, &result, |_| {
+            WriteAction::WritePropertyMultiple(audit_property_writes(&specs))
         });
+        result
     }
 
-    Ok(CovNotification {
-        source,
-        confirmed,
-        subscriber_process_id: value.subscriber_process_id,
-        initiating_device_id: value.initiating_device_id,
-        monitored_object_id: value.monitored_object_id,
-        time_remaining_seconds: value.time_remaining_seconds,
-        values,
-    })
-}
+    /// Send a ConfirmedPrivateTransfer request and return the ack.
+    pub async fn private_transfer(
+        &self,
+        address: DataLinkAddress,
+        vendor_id: u32,
+        service_number: u32,
+        service_parameters: Option<&[u8]>,
+    ) -> Result<PrivateTransferAck, ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let req = ConfirmedPrivateTransferRequest {
+            vendor_id,
+            service_number,
+            service_parameters,
+            invoke_id,
+        };
 
-fn into_client_event_notification(
-    source: DataLinkAddress,
-    confirmed: bool,
-    value: EventNotificationRequest<'_>,
-) -> EventNotification {
-    EventNotification {
-        source,
-        confirmed,
-        process_id: value.process_id,
-        initiating_device_id: value.initiating_device_id,
-        event_object_id: value.event_object_id,
-        timestamp: value.timestamp,
-        notification_class: value.notification_class,
-        priority: value.priority,
-        event_type: value.event_type,
-        message_text: value.message_text.map(str::to_string),
-        notify_type: value.notify_type,
-        ack_required: value.ack_required,
-        from_state_raw: value.from_state,
-        from_state: rustbac_core::services::acknowledge_alarm::EventState::from_u32(
-            value.from_state,
-        ),
-        to_state_raw: value.to_state,
-        to_state: rustbac_core::services::acknowledge_alarm::EventState::from_u32(value.to_state),
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            req.encode(w)
+        })?;
+        let payload = self
+            .await_complex_ack_payload_or_error(
+                address,
+                &tx,
+                invoke_id,
+                SERVICE_CONFIRMED_PRIVATE_TRANSFER,
+                self.response_timeout,
+            )
+            .await?;
+        let mut r = Reader::new(&payload);
+        PrivateTransferAck::decode(&mut r).map_err(ClientError::from)
     }
-}
 
-fn into_client_read_range(value: ReadRangeAck<'_>) -> Result<ReadRangeResult, ClientError> {
-    let mut items = Vec::with_capacity(value.items.len());
-    for item in value.items {
-        items.push(into_client_value(item)?);
+    /// Send a confirmed request for `service_choice` whose service parameters the caller
+    /// has already encoded in `payload`.
+    ///
+    /// Use this for vendor-proprietary services, or standard ones the client does not
+    /// implement yet. The client assigns the invoke id, segments the request if the peer
+    /// needs it, matches the response and reassembles a segmented ComplexAck. Returns the
+    /// ComplexAck service data, or an empty buffer for a SimpleAck; Error, Reject and Abort
+    /// responses map to the usual [`ClientError`] variants.
+    pub async fn send_raw_confirmed(
+        &self,
+        address: DataLinkAddress,
+        service_choice: u8,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, ClientError> {
+        let invoke_id = self.next_invoke_id(address).await?;
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            ConfirmedRequestHeader {
+                segmented: false,
+                more_follows: false,
+                segmented_response_accepted: true,
+                max_segments: 0,
+                max_apdu: 5,
+                invoke_id,
+                sequence_number: None,
+                proposed_window_size: None,
+                service_choice,
+            }
+            .encode(w)?;
+            w.write_all(payload)
+        })?;
+        self.await_complex_ack_payload_or_error(
+            address,
+            &tx,
+            invoke_id,
+            service_choice,
+            self.response_timeout,
+        )
+        .await
     }
-    Ok(ReadRangeResult {
-        object_id: value.object_id,
-        property_id: value.property_id,
-        array_index: value.array_index,
-        result_flags: ClientBitString {
-            unused_bits: value.result_flags.unused_bits,
-            data: value.result_flags.data.to_vec(),
-        },
-        item_count: value.item_count,
-        items,
-    })
-}
 
-fn into_client_atomic_read_result(value: AtomicReadFileAck<'_>) -> AtomicReadFileResult {
-    match value.access_method {
-        AtomicReadFileAckAccess::Stream {
-            file_start_position,
-            file_data,
-        } => AtomicReadFileResult::Stream {
-            end_of_file: value.end_of_file,
-            file_start_position,
-            file_data: file_data.to_vec(),
-        },
-        AtomicReadFileAckAccess::Record {
-            file_start_record,
-            returned_record_count,
-            file_record_data,
-        } => AtomicReadFileResult::Record {
-            end_of_file: value.end_of_file,
-            file_start_record,
-            returned_record_count,
-            file_record_data: file_record_data
-                .into_iter()
-                .map(|record| record.to_vec())
-                .collect(),
-        },
+    /// Send an unconfirmed request for `service_choice` with caller-encoded service
+    /// parameters. Pass a broadcast address to reach every device.
+    pub async fn send_raw_unconfirmed(
+        &self,
+        address: DataLinkAddress,
+        service_choice: u8,
+        payload: &[u8],
+    ) -> Result<(), ClientError> {
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            UnconfirmedRequestHeader { service_choice }.encode(w)?;
+            w.write_all(payload)
+        })?;
+        self.datalink.send(address, &tx).await?;
+        Ok(())
     }
-}
 
-fn into_client_atomic_write_result(value: AtomicWriteFileAck) -> AtomicWriteFileResult {
-    match value {
-        AtomicWriteFileAck::Stream {
-            file_start_position,
-        } => AtomicWriteFileResult::Stream {
-            file_start_position,
-        },
-        AtomicWriteFileAck::Record { file_start_record } => {
-            AtomicWriteFileResult::Record { file_start_record }
+    /// Read multiple `(object_id, property_id)` pairs in a single ReadPropertyMultiple round-trip.
+    ///
+    /// All pairs must target the same device at `address`. Returns a map from each requested
+    /// `(ObjectId, PropertyId)` to its value. Properties not returned by the device are absent
+    /// from the map (the device may skip unknown properties rather than erroring).
+    pub async fn read_many(
+        &self,
+        address: DataLinkAddress,
+        requests: &[(ObjectId, PropertyId)],
+    ) -> Result<HashMap<(ObjectId, PropertyId), ClientDataValue>, ClientError> {
+        // Group by object to build ReadAccessSpecifications
+        let mut grouped: Vec<(ObjectId, Vec<PropertyReference>)> = Vec::new();
+        for &(oid, pid) in requests {
+            if let Some(entry) = grouped.iter_mut().find(|(o, _)| *o == oid) {
+                entry.1.push(PropertyReference {
+                    property_id: pid,
+                    array_index: None,
+                });
+            } else {
+                grouped.push((
+                    oid,
+                    vec![PropertyReference {
+                        property_id: pid,
+                        array_index: None,
+                    }],
+                ));
+            }
+        }
+
+        let specs: Vec<ReadAccessSpecification<'_>> = grouped
+            .iter()
+            .map(|(oid, props)| ReadAccessSpecification {
+                object_id: *oid,
+                properties: props,
+            })
+            .collect();
+
+        let invoke_id = self.next_invoke_id(address).await?;
+        let req = ReadPropertyMultipleRequest {
+            specs: &specs,
+            invoke_id,
+        };
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            req.encode(w)
+        })?;
+        let payload = self
+            .await_complex_ack_payload_or_error(
+                address,
+                &tx,
+                invoke_id,
+                SERVICE_READ_PROPERTY_MULTIPLE,
+                self.response_timeout,
+            )
+            .await?;
+
+        let mut pr = Reader::new(&payload);
+        let parsed = ReadPropertyMultipleAck::decode_after_header_with(
+            &mut pr,
+            self.decode_mode,
+            &mut report_skipped_tag(address, "ReadPropertyMultiple"),
+        )?;
+        let mut out = HashMap::new();
+        for access in parsed.results {
+            for item in access.results {
+                if let Ok(v) = into_client_value(item.value) {
+                    out.insert((access.object_id, item.property_id), self.intern(v));
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Write multiple properties across one or more objects in a single WritePropertyMultiple
+    /// round-trip.
+    ///
+    /// `writes` is a slice of `(object_id, property_id, value, priority)` tuples.
+    /// `priority` may be `None` for the relinquish-default case. All writes target the same
+    /// device at `address`. Takes [`ClientDataValue`] (the owned form) for ergonomic use.
+    pub async fn write_many(
+        &self,
+        address: DataLinkAddress,
+        writes: &[(ObjectId, PropertyId, ClientDataValue, Option<u8>)],
+    ) -> Result<(), ClientError> {
+        use rustbac_core::types::{BitString, DataValue as DV};
+
+        fn cv_to_dv(v: &ClientDataValue) -> DV<'_> {
+            match v {
+                ClientDataValue::Null => DV::Null,
+                ClientDataValue::Boolean(b) => DV::Boolean(*b),
+                ClientDataValue::Unsigned(n) => DV::Unsigned(*n),
+                ClientDataValue::Signed(n) => DV::Signed(*n),
+                ClientDataValue::Real(f) => DV::Real(*f),
+                ClientDataValue::Double(f) => DV::Double(*f),
+                ClientDataValue::OctetString(b) => DV::OctetString(b),
+                ClientDataValue::CharacterString(s) => DV::CharacterString(s),
+                ClientDataValue::BitString { unused_bits, data } => DV::BitString(BitString {
+                    unused_bits: *unused_bits,
+                    data,
+                }),
+                ClientDataValue::Enumerated(n) => DV::Enumerated(*n),
+                ClientDataValue::Date(d) => DV::Date(*d),
+                ClientDataValue::Time(t) => DV::Time(*t),
+                ClientDataValue::ObjectId(o) => DV::ObjectId(*o),
+                ClientDataValue::Constructed { tag_num, values } => DV::Constructed {
+                    tag_num: *tag_num,
+                    values: values.iter().map(cv_to_dv).collect(),
+                },
+            }
+        }
+
+        // Pre-convert values so DataValue borrows from the input slice lifetime.
+        let converted: Vec<(ObjectId, PropertyId, DV<'_>, Option<u8>)> = writes
+            .iter()
+            .map(|(oid, pid, val, prio)| (*oid, *pid, cv_to_dv(val), *prio))
+            .collect();
+
+        // Group by object to build WriteAccessSpecifications.
+        let mut grouped: Vec<(ObjectId, Vec<PropertyWriteSpec<'_>>)> = Vec::new();
+        for (oid, pid, val, prio) in &converted {
+            let spec = PropertyWriteSpec {
+                property_id: *pid,
+                array_index: None,
+                value: val.clone(),
+                priority: *prio,
+            };
+            if let Some(entry) = grouped.iter_mut().find(|(o, _)| o == oid) {
+                entry.1.push(spec);
+            } else {
+                grouped.push((*oid, vec![spec]));
+            }
         }
+
+        let specs: Vec<WriteAccessSpecification<'_>> = grouped
+            .iter()
+            .map(|(oid, props)| WriteAccessSpecification {
+                object_id: *oid,
+                properties: props,
+            })
+            .collect();
+        self.check_write_policy(address, || {
+            WriteAction::WritePropertyMultiple(audit_property_writes(&specs))
+        })?;
+
+        let invoke_id = self.next_invoke_id(address).await?;
+        let req = WritePropertyMultipleRequest {
+            specs: &specs,
+            invoke_id,
+        };
+        let tx = self.encode_with_growth(|w| {
+            self.request_npdu().encode(w)?;
+            req.encode(w)
+        })?;
+        let timestamp = SystemTime::now();
+        let result = self
+            .await_simple_ack_or_error(
+                address,
+                &tx,
+                invoke_id,
+                SERVICE_WRITE_PROPERTY_MULTIPLE,
+                self.response_timeout,
+            )
+            .await;
+        self.audit_write(address, timestamp, &result, |_| {
+            WriteAction::WritePropertyMultiple(audit_property_writes(&specs))
+        });
+        result
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::BacnetClient;
-    use crate::{
-        AlarmSummaryItem, AtomicReadFileResult, AtomicWriteFileResult, ClientDataValue,
-        EnrollmentSummaryItem, EventInformationItem, EventNotification,
-    };
-    use rustbac_core::apdu::{
-        ApduType, ComplexAckHeader, ConfirmedRequestHeader, SegmentAck, SimpleAck,
-        UnconfirmedRequestHeader,
-    };
-    use rustbac_core::encoding::{
-        primitives::{
-            decode_signed, decode_unsigned, encode_app_real, encode_ctx_character_string,
-            encode_ctx_object_id, encode_ctx_unsigned,
-        },
-        reader::Reader,
-        tag::{AppTag, Tag},
-        writer::Writer,
-    };
-    use rustbac_core::npdu::Npdu;
-    use rustbac_core::services::acknowledge_alarm::{
-        AcknowledgeAlarmRequest, EventState, TimeStamp, SERVICE_ACKNOWLEDGE_ALARM,
-    };
-    use rustbac_core::services::alarm_summary::SERVICE_GET_ALARM_SUMMARY;
-    use rustbac_core::services::atomic_read_file::SERVICE_ATOMIC_READ_FILE;
-    use rustbac_core::services::atomic_write_file::SERVICE_ATOMIC_WRITE_FILE;
-    use rustbac_core::services::cov_notification::{
-        SERVICE_CONFIRMED_COV_NOTIFICATION, SERVICE_UNCONFIRMED_COV_NOTIFICATION,
-    };
-    use rustbac_core::services::device_management::{
-        DeviceCommunicationState, ReinitializeState, SERVICE_DEVICE_COMMUNICATION_CONTROL,
-        SERVICE_REINITIALIZE_DEVICE,
+/// Marks an invoke id in flight for the life of a confirmed exchange, however it ends.
+struct InFlight<'a> {
+    invoke_ids: &'a std::sync::Mutex<InvokeIdTracker>,
+    runtime: &'a dyn Runtime,
+    address: DataLinkAddress,
+    invoke_id: u8,
+}
+
+impl<'a> InFlight<'a> {
+    /// Fails with [`ClientError::Busy`] when `limit` transactions are already in flight.
+    fn begin(
+        invoke_ids: &'a std::sync::Mutex<InvokeIdTracker>,
+        runtime: &'a dyn Runtime,
+        address: DataLinkAddress,
+        invoke_id: u8,
+        limit: usize,
+    ) -> Result<Self, ClientError> {
+        let mut ids = invoke_ids.lock().expect("invoke id lock poisoned");
+        if ids.in_flight() >= limit {
+            return Err(ClientError::Busy { limit });
+        }
+        ids.begin(address, invoke_id);
+        drop(ids);
+        Ok(Self {
+            invoke_ids,
+            runtime,
+            address,
+            invoke_id,
+        })
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut invoke_ids) = self.invoke_ids.lock() {
+            invoke_ids.finish(self.address, self.invoke_id, self.runtime.now());
+        }
+    }
+}
+
+/// A WriteProperty request of a [`BacnetClient::write_burst`] waiting for its answer.
+struct BurstWrite<'a> {
+    /// Position of the write in the burst.
+    index: usize,
+    tx: Vec<u8>,
+    txn: ClientTransaction<Vec<u8>>,
+    started: (SystemTime, Instant),
+    _in_flight: InFlight<'a>,
+    _answers: TransactionSubscription,
+}
+
+/// Aborts a confirmed request whose future is dropped after the request was sent and
+/// before the transaction ends.
+///
+/// `Drop` cannot await, so the Abort is queued on the router, which has the task reading
+/// the link send it; see [`InboundRouter::queue_abort`].
+struct AbortOnDrop<'a> {
+    router: &'a InboundRouter,
+    address: DataLinkAddress,
+    invoke_id: u8,
+    armed: bool,
+}
+
+impl Drop for AbortOnDrop<'_> {
+    fn drop(&mut self) {
+        if self.armed {
+            self.router.queue_abort(
+                self.address,
+                abort_frame(self.invoke_id, abort_reason::OTHER),
+            );
+        }
+    }
+}
+
+/// The Abort reason for a confirmed request the client gave up on with `err`; `None`
+/// when the server has already ended the transaction or never saw the request.
+fn abort_reason_for(err: &ClientError) -> Option<u8> {
+    match err {
+        ClientError::Timeout => Some(abort_reason::TSM_TIMEOUT),
+        ClientError::ResponseTooLarge { .. } => Some(abort_reason::BUFFER_OVERFLOW),
+        ClientError::UnsupportedResponse => Some(abort_reason::INVALID_APDU_IN_THIS_STATE),
+        _ => None,
+    }
+}
+
+/// A client Abort for `invoke_id`, NPDU included.
+fn abort_frame(invoke_id: u8, reason: u8) -> Vec<u8> {
+    let mut buf = [0u8; 8];
+    let mut w = Writer::new(&mut buf);
+    Npdu::new(0)
+        .encode(&mut w)
+        .and_then(|()| {
+            AbortPdu {
+                server: false,
+                invoke_id,
+                reason,
+            }
+            .encode(&mut w)
+        })
+        .expect("abort fits its buffer");
+    w.as_written().to_vec()
+}
+
+/// Logs each tag a lenient decode of a `service` ack from `address` passed over.
+fn report_skipped_tag(address: DataLinkAddress, service: &'static str) -> impl FnMut(SkippedTag) {
+    move |tag| {
+        log::warn!(
+            "{service} ack from {address}: skipped {} context tag [{}]",
+            if tag.constructed {
+                "constructed"
+            } else {
+                "primitive"
+            },
+            tag.tag_num
+        );
+    }
+}
+
+fn extract_apdu(payload: &[u8]) -> Result<&[u8], ClientError> {
+    let mut r = Reader::new(payload);
+    let _npdu = Npdu::decode(&mut r)?;
+    r.read_exact(r.remaining()).map_err(ClientError::from)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Inline server dispatch
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Handle an incoming BACnet service request on behalf of the client's inline server handler.
+///
+/// This is the core dispatch logic extracted from [`BacnetServer::handle_frame`] so that
+/// `BacnetClient` can service requests inline while waiting for its own responses.
+async fn dispatch_incoming_request<D: DataLink>(
+    datalink: &D,
+    handler: &dyn crate::server::ServiceHandler,
+    device_id: u32,
+    vendor_id: u16,
+    frame: &[u8],
+    source: DataLinkAddress,
+) -> Result<(), ClientError> {
+    let mut r = Reader::new(frame);
+    let _npdu = match Npdu::decode(&mut r) {
+        Ok(n) => n,
+        Err(_) => return Ok(()),
     };
-    use rustbac_core::services::enrollment_summary::SERVICE_GET_ENROLLMENT_SUMMARY;
-    use rustbac_core::services::event_information::SERVICE_GET_EVENT_INFORMATION;
-    use rustbac_core::services::event_notification::{
-        SERVICE_CONFIRMED_EVENT_NOTIFICATION, SERVICE_UNCONFIRMED_EVENT_NOTIFICATION,
+
+    if r.is_empty() {
+        return Ok(());
+    }
+
+    let first = match r.peek_u8() {
+        Ok(b) => b,
+        Err(_) => return Ok(()),
     };
-    use rustbac_core::services::list_element::{
-        AddListElementRequest, RemoveListElementRequest, SERVICE_ADD_LIST_ELEMENT,
-        SERVICE_REMOVE_LIST_ELEMENT,
+    let apdu_type = ApduType::from_u8(first >> 4);
+
+    match apdu_type {
+        Some(ApduType::UnconfirmedRequest) => {
+            let header = match UnconfirmedRequestHeader::decode(&mut r) {
+                Ok(h) => h,
+                Err(_) => return Ok(()),
+            };
+            if header.service_choice == 0x08 {
+                // Who-Is — check optional limits then respond with I-Am.
+                let limits = dispatch_decode_who_is_limits(&mut r);
+                if dispatch_matches_who_is(device_id, limits) {
+                    dispatch_send_i_am(datalink, device_id, vendor_id, source).await;
+                }
+            }
+            // All other unconfirmed services are ignored.
+        }
+        Some(ApduType::ConfirmedRequest) => {
+            let header = match ConfirmedRequestHeader::decode(&mut r) {
+                Ok(h) => h,
+                Err(_) => return Ok(()),
+            };
+            let invoke_id = header.invoke_id;
+            match header.service_choice {
+                SERVICE_READ_PROPERTY => {
+                    dispatch_handle_read_property(datalink, handler, &mut r, invoke_id, source)
+                        .await;
+                }
+                SERVICE_WRITE_PROPERTY => {
+                    dispatch_handle_write_property(datalink, handler, &mut r, invoke_id, source)
+                        .await;
+                }
+                SERVICE_READ_PROPERTY_MULTIPLE => {
+                    dispatch_handle_read_property_multiple(
+                        datalink, handler, &mut r, invoke_id, source,
+                    )
+                    .await;
+                }
+                SERVICE_WRITE_PROPERTY_MULTIPLE => {
+                    dispatch_handle_write_property_multiple(
+                        datalink, handler, &mut r, invoke_id, source,
+                    )
+                    .await;
+                }
+                SERVICE_SUBSCRIBE_COV => {
+                    dispatch_handle_subscribe_cov(datalink, handler, &mut r, invoke_id, source)
+                        .await;
+                }
+                SERVICE_CREATE_OBJECT => {
+                    dispatch_handle_create_object(datalink, handler, &mut r, invoke_id, source)
+                        .await;
+                }
+                SERVICE_DELETE_OBJECT => {
+                    dispatch_handle_delete_object(datalink, handler, &mut r, invoke_id, source)
+                        .await;
+                }
+                _ => {
+                    // Unknown service — send Reject with UNRECOGNIZED_SERVICE (0x08).
+                    dispatch_send_reject(datalink, invoke_id, 0x08, source).await;
+                }
+            }
+        }
+        _ => {
+            // Not a request — ignore.
+        }
+    }
+
+    Ok(())
+}
+
+// ── Inline dispatch helpers ──────────────────────────────────────────────────
+
+async fn dispatch_send_i_am<D: DataLink>(
+    datalink: &D,
+    device_id: u32,
+    vendor_id: u16,
+    target: DataLinkAddress,
+) {
+    let device_oid = ObjectId::new(ObjectType::Device, device_id);
+    let req = IAmRequest {
+        device_id: device_oid,
+        max_apdu: 1476,
+        segmentation: 3, // no-segmentation
+        vendor_id: vendor_id as u32,
     };
-    use rustbac_core::services::object_management::{SERVICE_CREATE_OBJECT, SERVICE_DELETE_OBJECT};
-    use rustbac_core::services::read_property::SERVICE_READ_PROPERTY;
-    use rustbac_core::services::read_property_multiple::SERVICE_READ_PROPERTY_MULTIPLE;
-    use rustbac_core::services::read_range::SERVICE_READ_RANGE;
-    use rustbac_core::services::subscribe_cov::{SubscribeCovRequest, SERVICE_SUBSCRIBE_COV};
-    use rustbac_core::services::subscribe_cov_property::{
-        SubscribeCovPropertyRequest, SERVICE_SUBSCRIBE_COV_PROPERTY,
+    let mut buf = [0u8; 128];
+    let mut w = Writer::new(&mut buf);
+    if Npdu::new(0).encode(&mut w).is_err() {
+        return;
+    }
+    if req.encode(&mut w).is_err() {
+        return;
+    }
+    let _ = datalink.send(target, w.as_written()).await;
+}
+
+async fn dispatch_handle_read_property<D: DataLink>(
+    datalink: &D,
+    handler: &dyn crate::server::ServiceHandler,
+    r: &mut Reader<'_>,
+    invoke_id: u8,
+    source: DataLinkAddress,
+) {
+    let object_id = match crate::decode_ctx_object_id(r) {
+        Ok(v) => v,
+        Err(_) => return,
     };
-    use rustbac_core::services::time_synchronization::SERVICE_TIME_SYNCHRONIZATION;
-    use rustbac_core::services::who_has::{SERVICE_I_HAVE, SERVICE_WHO_HAS};
-    use rustbac_core::services::write_property_multiple::{
-        PropertyWriteSpec, SERVICE_WRITE_PROPERTY_MULTIPLE,
+    let property_id_raw = match crate::decode_ctx_unsigned(r) {
+        Ok(v) => v,
+        Err(_) => return,
     };
-    use rustbac_core::types::{DataValue, Date, ObjectId, ObjectType, PropertyId, Time};
-    use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError};
-    use std::collections::VecDeque;
-    use std::sync::Arc;
-    use std::time::Duration;
-    use tokio::sync::Mutex;
+    let property_id = PropertyId::from_u32(property_id_raw);
+
+    // Optional array index [2].
+    let array_index = dispatch_decode_optional_array_index(r);
+
+    match handler.read_property(object_id, property_id, array_index) {
+        Ok(value) => {
+            let borrowed = dispatch_client_value_to_borrowed(&value);
+            let mut buf = [0u8; 1400];
+            let mut w = Writer::new(&mut buf);
+            if Npdu::new(0).encode(&mut w).is_err() {
+                return;
+            }
+            if (ComplexAckHeader {
+                segmented: false,
+                more_follows: false,
+                invoke_id,
+                sequence_number: None,
+                proposed_window_size: None,
+                service_choice: SERVICE_READ_PROPERTY,
+            })
+            .encode(&mut w)
+            .is_err()
+            {
+                return;
+            }
+            if encode_ctx_unsigned(&mut w, 0, object_id.raw()).is_err() {
+                return;
+            }
+            if encode_ctx_unsigned(&mut w, 1, property_id.to_u32()).is_err() {
+                return;
+            }
+            if (Tag::Opening { tag_num: 3 }).encode(&mut w).is_err() {
+                return;
+            }
+            if encode_application_data_value(&mut w, &borrowed).is_err() {
+                return;
+            }
+            if (Tag::Closing { tag_num: 3 }).encode(&mut w).is_err() {
+                return;
+            }
+            let _ = datalink.send(source, w.as_written()).await;
+        }
+        Err(err) => {
+            dispatch_send_error(datalink, invoke_id, SERVICE_READ_PROPERTY, err, source).await;
+        }
+    }
+}
+
+async fn dispatch_handle_write_property<D: DataLink>(
+    datalink: &D,
+    handler: &dyn crate::server::ServiceHandler,
+    r: &mut Reader<'_>,
+    invoke_id: u8,
+    source: DataLinkAddress,
+) {
+    let object_id = match crate::decode_ctx_object_id(r) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+    let property_id_raw = match crate::decode_ctx_unsigned(r) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+    let property_id = PropertyId::from_u32(property_id_raw);
+
+    // Optional array index [2].
+    let next_tag = match Tag::decode(r) {
+        Ok(t) => t,
+        Err(_) => return,
+    };
+    let (array_index, value_start_tag) = match next_tag {
+        Tag::Context { tag_num: 2, len } => {
+            let idx = match decode_unsigned(r, len as usize) {
+                Ok(v) => v,
+                Err(_) => return,
+            };
+            let vt = match Tag::decode(r) {
+                Ok(t) => t,
+                Err(_) => return,
+            };
+            (Some(idx), vt)
+        }
+        other => (None, other),
+    };
+
+    if value_start_tag != (Tag::Opening { tag_num: 3 }) {
+        return;
+    }
+
+    let val = match rustbac_core::services::value_codec::decode_application_data_value(r) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    match Tag::decode(r) {
+        Ok(Tag::Closing { tag_num: 3 }) => {}
+        _ => return,
+    }
+
+    // Optional priority [4].
+    let priority = if !r.is_empty() {
+        match Tag::decode(r) {
+            Ok(Tag::Context { tag_num: 4, len }) => match decode_unsigned(r, len as usize) {
+                Ok(p) => Some(p as u8),
+                Err(_) => return,
+            },
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    let client_val = crate::data_value_to_client(val);
+
+    match handler.write_property(object_id, property_id, array_index, client_val, priority) {
+        Ok(()) => {
+            let mut buf = [0u8; 32];
+            let mut w = Writer::new(&mut buf);
+            if Npdu::new(0).encode(&mut w).is_err() {
+                return;
+            }
+            if (SimpleAck {
+                invoke_id,
+                service_choice: SERVICE_WRITE_PROPERTY,
+            })
+            .encode(&mut w)
+            .is_err()
+            {
+                return;
+            }
+            let _ = datalink.send(source, w.as_written()).await;
+        }
+        Err(err) => {
+            dispatch_send_error(datalink, invoke_id, SERVICE_WRITE_PROPERTY, err, source).await;
+        }
+    }
+}
+
+async fn dispatch_handle_read_property_multiple<D: DataLink>(
+    datalink: &D,
+    handler: &dyn crate::server::ServiceHandler,
+    r: &mut Reader<'_>,
+    invoke_id: u8,
+    source: DataLinkAddress,
+) {
+    type PropRefs = Vec<(PropertyId, Option<u32>)>;
+    let mut specs: Vec<(ObjectId, PropRefs)> = Vec::new();
+
+    while !r.is_empty() {
+        let object_id = match crate::decode_ctx_object_id(r) {
+            Ok(v) => v,
+            Err(_) => return,
+        };
+        match Tag::decode(r) {
+            Ok(Tag::Opening { tag_num: 1 }) => {}
+            _ => return,
+        }
+        let mut props: Vec<(PropertyId, Option<u32>)> = Vec::new();
+        loop {
+            let tag = match Tag::decode(r) {
+                Ok(t) => t,
+                Err(_) => return,
+            };
+            if tag == (Tag::Closing { tag_num: 1 }) {
+                break;
+            }
+            let property_id = match tag {
+                Tag::Context { tag_num: 0, len } => match decode_unsigned(r, len as usize) {
+                    Ok(v) => PropertyId::from_u32(v),
+                    Err(_) => return,
+                },
+                _ => return,
+            };
+            let array_index = if !r.is_empty() {
+                match dispatch_peek_context_tag(r, 1) {
+                    Some(len) => {
+                        match Tag::decode(r) {
+                            Ok(_) => {}
+                            Err(_) => return,
+                        }
+                        match decode_unsigned(r, len as usize) {
+                            Ok(idx) => Some(idx),
+                            Err(_) => return,
+                        }
+                    }
+                    None => None,
+                }
+            } else {
+                None
+            };
+            props.push((property_id, array_index));
+        }
+        specs.push((object_id, props));
+    }
+
+    let mut buf = [0u8; 1400];
+    let mut w = Writer::new(&mut buf);
+    if Npdu::new(0).encode(&mut w).is_err() {
+        return;
+    }
+    if (ComplexAckHeader {
+        segmented: false,
+        more_follows: false,
+        invoke_id,
+        sequence_number: None,
+        proposed_window_size: None,
+        service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
+    })
+    .encode(&mut w)
+    .is_err()
+    {
+        return;
+    }
+
+    for (object_id, props) in &specs {
+        if encode_ctx_unsigned(&mut w, 0, object_id.raw()).is_err() {
+            return;
+        }
+        if (Tag::Opening { tag_num: 1 }).encode(&mut w).is_err() {
+            return;
+        }
+
+        for (property_id, array_index) in props {
+            if encode_ctx_unsigned(&mut w, 2, property_id.to_u32()).is_err() {
+                return;
+            }
+            if let Some(idx) = array_index {
+                if encode_ctx_unsigned(&mut w, 3, *idx).is_err() {
+                    return;
+                }
+            }
+            if (Tag::Opening { tag_num: 4 }).encode(&mut w).is_err() {
+                return;
+            }
+
+            match handler.read_property(*object_id, *property_id, *array_index) {
+                Ok(value) => {
+                    let borrowed = dispatch_client_value_to_borrowed(&value);
+                    if encode_application_data_value(&mut w, &borrowed).is_err() {
+                        return;
+                    }
+                }
+                Err(err) => {
+                    let (class, code) = dispatch_error_class_code(err);
+                    if (Tag::Opening { tag_num: 5 }).encode(&mut w).is_err() {
+                        return;
+                    }
+                    if encode_ctx_unsigned(&mut w, 0, class as u32).is_err() {
+                        return;
+                    }
+                    if encode_ctx_unsigned(&mut w, 1, code as u32).is_err() {
+                        return;
+                    }
+                    if (Tag::Closing { tag_num: 5 }).encode(&mut w).is_err() {
+                        return;
+                    }
+                }
+            }
+
+            if (Tag::Closing { tag_num: 4 }).encode(&mut w).is_err() {
+                return;
+            }
+        }
+
+        if (Tag::Closing { tag_num: 1 }).encode(&mut w).is_err() {
+            return;
+        }
+    }
+
+    let _ = datalink.send(source, w.as_written()).await;
+}
+
+async fn dispatch_handle_write_property_multiple<D: DataLink>(
+    datalink: &D,
+    handler: &dyn crate::server::ServiceHandler,
+    r: &mut Reader<'_>,
+    invoke_id: u8,
+    source: DataLinkAddress,
+) {
+    while !r.is_empty() {
+        let object_id = match crate::decode_ctx_object_id(r) {
+            Ok(v) => v,
+            Err(_) => return,
+        };
+        match Tag::decode(r) {
+            Ok(Tag::Opening { tag_num: 1 }) => {}
+            _ => return,
+        }
+        loop {
+            let tag = match Tag::decode(r) {
+                Ok(t) => t,
+                Err(_) => return,
+            };
+            if tag == (Tag::Closing { tag_num: 1 }) {
+                break;
+            }
+            let property_id = match tag {
+                Tag::Context { tag_num: 0, len } => match decode_unsigned(r, len as usize) {
+                    Ok(v) => PropertyId::from_u32(v),
+                    Err(_) => return,
+                },
+                _ => return,
+            };
+            let array_index = if !r.is_empty() {
+                match dispatch_peek_context_tag(r, 1) {
+                    Some(len) => {
+                        let _ = Tag::decode(r);
+                        decode_unsigned(r, len as usize).ok()
+                    }
+                    None => None,
+                }
+            } else {
+                None
+            };
+            match Tag::decode(r) {
+                Ok(Tag::Opening { tag_num: 2 }) => {}
+                _ => return,
+            }
+            let val = match rustbac_core::services::value_codec::decode_application_data_value(r) {
+                Ok(v) => v,
+                Err(_) => return,
+            };
+            match Tag::decode(r) {
+                Ok(Tag::Closing { tag_num: 2 }) => {}
+                _ => return,
+            }
+            let priority = if !r.is_empty() {
+                match dispatch_peek_context_tag(r, 3) {
+                    Some(len) => {
+                        let _ = Tag::decode(r);
+                        decode_unsigned(r, len as usize).ok().map(|p| p as u8)
+                    }
+                    None => None,
+                }
+            } else {
+                None
+            };
+            let client_val = crate::data_value_to_client(val);
+            if let Err(err) =
+                handler.write_property(object_id, property_id, array_index, client_val, priority)
+            {
+                dispatch_send_error(
+                    datalink,
+                    invoke_id,
+                    SERVICE_WRITE_PROPERTY_MULTIPLE,
+                    err,
+                    source,
+                )
+                .await;
+                return;
+            }
+        }
+    }
+    // All properties written successfully — send SimpleAck.
+    let mut buf = [0u8; 32];
+    let mut w = Writer::new(&mut buf);
+    if Npdu::new(0).encode(&mut w).is_err() {
+        return;
+    }
+    if (SimpleAck {
+        invoke_id,
+        service_choice: SERVICE_WRITE_PROPERTY_MULTIPLE,
+    })
+    .encode(&mut w)
+    .is_err()
+    {
+        return;
+    }
+    let _ = datalink.send(source, w.as_written()).await;
+}
+
+async fn dispatch_handle_subscribe_cov<D: DataLink>(
+    datalink: &D,
+    handler: &dyn crate::server::ServiceHandler,
+    r: &mut Reader<'_>,
+    invoke_id: u8,
+    source: DataLinkAddress,
+) {
+    // subscriberProcessIdentifier [0]
+    let subscriber_process_id = match Tag::decode(r) {
+        Ok(Tag::Context { tag_num: 0, len }) => match decode_unsigned(r, len as usize) {
+            Ok(v) => v,
+            Err(_) => return,
+        },
+        _ => return,
+    };
+    // monitoredObjectIdentifier [1]
+    let monitored_object_id = match crate::decode_ctx_object_id(r) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+    // issueConfirmedNotifications [2]
+    let issue_confirmed = match Tag::decode(r) {
+        Ok(Tag::Context { tag_num: 2, len }) => match decode_unsigned(r, len as usize) {
+            Ok(v) => v != 0,
+            Err(_) => return,
+        },
+        _ => return,
+    };
+    // optional lifetime [3]
+    let lifetime = if !r.is_empty() {
+        match dispatch_peek_context_tag(r, 3) {
+            Some(len) => {
+                let _ = Tag::decode(r);
+                decode_unsigned(r, len as usize).ok()
+            }
+            None => None,
+        }
+    } else {
+        None
+    };
+
+    match handler.subscribe_cov(
+        subscriber_process_id,
+        monitored_object_id,
+        issue_confirmed,
+        lifetime,
+    ) {
+        Ok(()) => {
+            let mut buf = [0u8; 32];
+            let mut w = Writer::new(&mut buf);
+            if Npdu::new(0).encode(&mut w).is_err() {
+                return;
+            }
+            if (SimpleAck {
+                invoke_id,
+                service_choice: SERVICE_SUBSCRIBE_COV,
+            })
+            .encode(&mut w)
+            .is_err()
+            {
+                return;
+            }
+            let _ = datalink.send(source, w.as_written()).await;
+        }
+        Err(err) => {
+            dispatch_send_error(datalink, invoke_id, SERVICE_SUBSCRIBE_COV, err, source).await;
+        }
+    }
+}
+
+async fn dispatch_handle_create_object<D: DataLink>(
+    datalink: &D,
+    handler: &dyn crate::server::ServiceHandler,
+    r: &mut Reader<'_>,
+    invoke_id: u8,
+    source: DataLinkAddress,
+) {
+    // objectSpecifier [0] opening
+    match Tag::decode(r) {
+        Ok(Tag::Opening { tag_num: 0 }) => {}
+        _ => return,
+    }
+    // objectType [0] — context-tagged enumerated
+    let object_type_raw = match Tag::decode(r) {
+        Ok(Tag::Context { tag_num: 0, len }) => match decode_unsigned(r, len as usize) {
+            Ok(v) => v,
+            Err(_) => return,
+        },
+        _ => return,
+    };
+    let object_type = ObjectType::from_u16(object_type_raw as u16);
+    // objectSpecifier [0] closing
+    match Tag::decode(r) {
+        Ok(Tag::Closing { tag_num: 0 }) => {}
+        _ => return,
+    }
+
+    match handler.create_object(object_type) {
+        Ok(created_id) => {
+            let mut buf = [0u8; 64];
+            let mut w = Writer::new(&mut buf);
+            if Npdu::new(0).encode(&mut w).is_err() {
+                return;
+            }
+            if (ComplexAckHeader {
+                segmented: false,
+                more_follows: false,
+                invoke_id,
+                sequence_number: None,
+                proposed_window_size: None,
+                service_choice: SERVICE_CREATE_OBJECT,
+            })
+            .encode(&mut w)
+            .is_err()
+            {
+                return;
+            }
+            if encode_ctx_unsigned(&mut w, 0, created_id.raw()).is_err() {
+                return;
+            }
+            let _ = datalink.send(source, w.as_written()).await;
+        }
+        Err(err) => {
+            dispatch_send_error(datalink, invoke_id, SERVICE_CREATE_OBJECT, err, source).await;
+        }
+    }
+}
+
+async fn dispatch_handle_delete_object<D: DataLink>(
+    datalink: &D,
+    handler: &dyn crate::server::ServiceHandler,
+    r: &mut Reader<'_>,
+    invoke_id: u8,
+    source: DataLinkAddress,
+) {
+    let object_id = match crate::decode_ctx_object_id(r) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    match handler.delete_object(object_id) {
+        Ok(()) => {
+            let mut buf = [0u8; 32];
+            let mut w = Writer::new(&mut buf);
+            if Npdu::new(0).encode(&mut w).is_err() {
+                return;
+            }
+            if (SimpleAck {
+                invoke_id,
+                service_choice: SERVICE_DELETE_OBJECT,
+            })
+            .encode(&mut w)
+            .is_err()
+            {
+                return;
+            }
+            let _ = datalink.send(source, w.as_written()).await;
+        }
+        Err(err) => {
+            dispatch_send_error(datalink, invoke_id, SERVICE_DELETE_OBJECT, err, source).await;
+        }
+    }
+}
+
+async fn dispatch_send_error<D: DataLink>(
+    datalink: &D,
+    invoke_id: u8,
+    service_choice: u8,
+    err: crate::server::BacnetServiceError,
+    target: DataLinkAddress,
+) {
+    let (class, code) = dispatch_error_class_code(err);
+    let mut buf = [0u8; 64];
+    let mut w = Writer::new(&mut buf);
+    if Npdu::new(0).encode(&mut w).is_err() {
+        return;
+    }
+    // Error PDU header: type=5 (Error)
+    if w.write_u8(0x50).is_err() {
+        return;
+    }
+    if w.write_u8(invoke_id).is_err() {
+        return;
+    }
+    if w.write_u8(service_choice).is_err() {
+        return;
+    }
+    // error-class: application Enumerated
+    if (Tag::Application {
+        tag: rustbac_core::encoding::tag::AppTag::Enumerated,
+        len: 1,
+    })
+    .encode(&mut w)
+    .is_err()
+    {
+        return;
+    }
+    if w.write_u8(class).is_err() {
+        return;
+    }
+    // error-code: application Enumerated
+    if (Tag::Application {
+        tag: rustbac_core::encoding::tag::AppTag::Enumerated,
+        len: 1,
+    })
+    .encode(&mut w)
+    .is_err()
+    {
+        return;
+    }
+    if w.write_u8(code).is_err() {
+        return;
+    }
+    let _ = datalink.send(target, w.as_written()).await;
+}
+
+async fn dispatch_send_reject<D: DataLink>(
+    datalink: &D,
+    invoke_id: u8,
+    reason: u8,
+    target: DataLinkAddress,
+) {
+    let mut buf = [0u8; 16];
+    let mut w = Writer::new(&mut buf);
+    if Npdu::new(0).encode(&mut w).is_err() {
+        return;
+    }
+    // Reject PDU: type=6 (Reject)
+    if w.write_u8(0x60).is_err() {
+        return;
+    }
+    if w.write_u8(invoke_id).is_err() {
+        return;
+    }
+    if w.write_u8(reason).is_err() {
+        return;
+    }
+    let _ = datalink.send(target, w.as_written()).await;
+}
+
+/// Decode Who-Is optional [0] low-limit and [1] high-limit (dispatch-local copy).
+fn dispatch_decode_who_is_limits(r: &mut Reader<'_>) -> Option<(u32, u32)> {
+    if r.is_empty() {
+        return None;
+    }
+    let tag0 = Tag::decode(r).ok()?;
+    let low = match tag0 {
+        Tag::Context { tag_num: 0, len } => decode_unsigned(r, len as usize).ok()?,
+        _ => return None,
+    };
+    let tag1 = Tag::decode(r).ok()?;
+    let high = match tag1 {
+        Tag::Context { tag_num: 1, len } => decode_unsigned(r, len as usize).ok()?,
+        _ => return None,
+    };
+    Some((low, high))
+}
+
+fn dispatch_error_class_code(err: crate::server::BacnetServiceError) -> (u8, u8) {
+    use crate::server::BacnetServiceError;
+    match err {
+        BacnetServiceError::UnknownObject => (1, 31),
+        BacnetServiceError::UnknownProperty => (2, 32),
+        BacnetServiceError::WriteAccessDenied => (2, 40),
+        BacnetServiceError::InvalidDataType => (2, 9),
+        BacnetServiceError::ServiceNotSupported => (5, 53),
+    }
+}
+
+fn dispatch_matches_who_is(device_id: u32, limits: Option<(u32, u32)>) -> bool {
+    match limits {
+        None => true,
+        Some((low, high)) => device_id >= low && device_id <= high,
+    }
+}
+
+fn dispatch_decode_optional_array_index(r: &mut Reader<'_>) -> Option<u32> {
+    if r.is_empty() {
+        return None;
+    }
+    let tag = Tag::decode(r).ok()?;
+    match tag {
+        Tag::Context { tag_num: 2, len } => decode_unsigned(r, len as usize).ok(),
+        _ => None,
+    }
+}
+
+fn dispatch_peek_context_tag(r: &mut Reader<'_>, tag_num: u8) -> Option<u32> {
+    let first = r.peek_u8().ok()?;
+    let is_context = (first & 0x08) != 0 && (first & 0x07) < 6;
+    if !is_context {
+        return None;
+    }
+    let this_tag_num = first >> 4;
+    if this_tag_num != tag_num {
+        return None;
+    }
+    let short_len = first & 0x07;
+    if short_len < 5 {
+        Some(short_len as u32)
+    } else {
+        None
+    }
+}
+
+/// Validates `entries` and converts them for encoding.
+fn core_calendar_entries(entries: &[CalendarEntry]) -> Result<Vec<CoreCalendarEntry>, ClientError> {
+    entries
+        .iter()
+        .map(|entry| {
+            let entry = entry.to_core();
+            entry.validate()?;
+            Ok(entry)
+        })
+        .collect()
+}
+
+fn core_time_value(time_value: &TimeValue) -> CoreTimeValue<'_> {
+    CoreTimeValue {
+        time: time_value.time,
+        value: dispatch_client_value_to_borrowed(&time_value.value),
+    }
+}
+
+fn dispatch_client_value_to_borrowed(val: &ClientDataValue) -> DataValue<'_> {
+    match val {
+        ClientDataValue::Null => DataValue::Null,
+        ClientDataValue::Boolean(v) => DataValue::Boolean(*v),
+        ClientDataValue::Unsigned(v) => DataValue::Unsigned(*v),
+        ClientDataValue::Signed(v) => DataValue::Signed(*v),
+        ClientDataValue::Real(v) => DataValue::Real(*v),
+        ClientDataValue::Double(v) => DataValue::Double(*v),
+        ClientDataValue::OctetString(v) => DataValue::OctetString(v),
+        ClientDataValue::CharacterString(v) => DataValue::CharacterString(v),
+        ClientDataValue::BitString { unused_bits, data } => {
+            DataValue::BitString(rustbac_core::types::BitString {
+                unused_bits: *unused_bits,
+                data,
+            })
+        }
+        ClientDataValue::Enumerated(v) => DataValue::Enumerated(*v),
+        ClientDataValue::Date(v) => DataValue::Date(*v),
+        ClientDataValue::Time(v) => DataValue::Time(*v),
+        ClientDataValue::ObjectId(v) => DataValue::ObjectId(*v),
+        ClientDataValue::Constructed { tag_num, values } => DataValue::Constructed {
+            tag_num: *tag_num,
+            values: values
+                .iter()
+                .map(dispatch_client_value_to_borrowed)
+                .collect(),
+        },
+    }
+}
+
+fn transaction_error(err: TransactionError) -> ClientError {
+    match err {
+        TransactionError::Decode(err) => ClientError::Decode(err),
+        TransactionError::Encode(err) => ClientError::Encode(err),
+        TransactionError::Remote(err) => remote_service_error(err),
+        TransactionError::Reject(rej) => ClientError::RemoteReject { reason: rej.reason },
+        TransactionError::Abort(abort) => ClientError::RemoteAbort {
+            reason: abort.reason,
+            server: abort.server,
+        },
+        TransactionError::UnexpectedResponse => ClientError::UnsupportedResponse,
+        TransactionError::ResponseTooLarge => ClientError::ResponseTooLarge {
+            limit: MAX_COMPLEX_ACK_REASSEMBLY_BYTES,
+        },
+        TransactionError::Timeout => ClientError::Timeout,
+    }
+}
+
+fn duration_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// The client error for an Error, Reject or Abort reply; `None` for any other response.
+fn confirmed_failure(response: ConfirmedResponse<'_>) -> Option<ClientError> {
+    match response {
+        ConfirmedResponse::Error(err) => Some(remote_service_error(err)),
+        ConfirmedResponse::Reject(rej) => Some(ClientError::RemoteReject { reason: rej.reason }),
+        ConfirmedResponse::Abort(abort) => Some(ClientError::RemoteAbort {
+            reason: abort.reason,
+            server: abort.server,
+        }),
+        _ => None,
+    }
+}
+
+fn remote_service_error(err: BacnetError) -> ClientError {
+    ClientError::RemoteServiceError {
+        service_choice: err.service_choice,
+        error_class_raw: err.error_class,
+        error_code_raw: err.error_code,
+        error_class: err.error_class.and_then(ErrorClass::from_u32),
+        error_code: err.error_code.and_then(ErrorCode::from_u32),
+    }
+}
+
+/// The owned form of a value written, for a [`WriteAuditEvent`].
+fn audit_value(value: &DataValue<'_>) -> ClientDataValue {
+    into_client_value(value.clone()).unwrap_or(ClientDataValue::Null)
+}
+
+fn audit_property_writes(specs: &[WriteAccessSpecification<'_>]) -> Vec<PropertyWrite> {
+    specs
+        .iter()
+        .flat_map(|spec| {
+            spec.properties.iter().map(|property| PropertyWrite {
+                object_id: spec.object_id,
+                property_id: property.property_id,
+                array_index: property.array_index,
+                value: audit_value(&property.value),
+                priority: property.priority,
+            })
+        })
+        .collect()
+}
+
+fn into_client_value(value: DataValue<'_>) -> Result<ClientDataValue, ClientError> {
+    Ok(match value {
+        DataValue::Null => ClientDataValue::Null,
+        DataValue::Boolean(v) => ClientDataValue::Boolean(v),
+        DataValue::Unsigned(v) => ClientDataValue::Unsigned(v),
+        DataValue::Signed(v) => ClientDataValue::Signed(v),
+        DataValue::Real(v) => ClientDataValue::Real(v),
+        DataValue::Double(v) => ClientDataValue::Double(v),
+        DataValue::OctetString(v) => ClientDataValue::OctetString(v.to_vec()),
+        DataValue::CharacterString(v) => ClientDataValue::CharacterString(v.into()),
+        DataValue::BitString(v) => ClientDataValue::BitString {
+            unused_bits: v.unused_bits,
+            data: v.data.to_vec(),
+        },
+        DataValue::Enumerated(v) => ClientDataValue::Enumerated(v),
+        DataValue::Date(v) => ClientDataValue::Date(v),
+        DataValue::Time(v) => ClientDataValue::Time(v),
+        DataValue::ObjectId(v) => ClientDataValue::ObjectId(v),
+        DataValue::Constructed { tag_num, values } => {
+            let mut children = Vec::with_capacity(values.len());
+            for child in values {
+                children.push(into_client_value(child)?);
+            }
+            ClientDataValue::Constructed {
+                tag_num,
+                values: children,
+            }
+        }
+    })
+}
+
+fn into_client_alarm_summary(value: Vec<CoreAlarmSummaryItem<'_>>) -> Vec<AlarmSummaryItem> {
+    value
+        .into_iter()
+        .map(|item| AlarmSummaryItem {
+            object_id: item.object_id,
+            alarm_state_raw: item.alarm_state,
+            alarm_state: rustbac_core::services::acknowledge_alarm::EventState::from_u32(
+                item.alarm_state,
+            ),
+            acknowledged_transitions: ClientBitString {
+                unused_bits: item.acknowledged_transitions.unused_bits,
+                data: item.acknowledged_transitions.data.to_vec(),
+            },
+        })
+        .collect()
+}
+
+fn into_client_enrollment_summary(
+    value: Vec<CoreEnrollmentSummaryItem>,
+) -> Vec<EnrollmentSummaryItem> {
+    value
+        .into_iter()
+        .map(|item| EnrollmentSummaryItem {
+            object_id: item.object_id,
+            event_type: item.event_type,
+            event_state_raw: item.event_state,
+            event_state: rustbac_core::services::acknowledge_alarm::EventState::from_u32(
+                item.event_state,
+            ),
+            priority: item.priority,
+            notification_class: item.notification_class,
+        })
+        .collect()
+}
+
+fn into_client_event_information(
+    value: Vec<CoreEventSummaryItem<'_>>,
+) -> Vec<EventInformationItem> {
+    value
+        .into_iter()
+        .map(|item| EventInformationItem {
+            object_id: item.object_id,
+            event_state_raw: item.event_state,
+            event_state: rustbac_core::services::acknowledge_alarm::EventState::from_u32(
+                item.event_state,
+            ),
+            acknowledged_transitions: ClientBitString {
+                unused_bits: item.acknowledged_transitions.unused_bits,
+                data: item.acknowledged_transitions.data.to_vec(),
+            },
+            notify_type: item.notify_type,
+            event_enable: ClientBitString {
+                unused_bits: item.event_enable.unused_bits,
+                data: item.event_enable.data.to_vec(),
+            },
+            event_priorities: item.event_priorities,
+        })
+        .collect()
+}
+
+fn into_client_cov_notification(
+    source: DataLinkAddress,
+    confirmed: bool,
+    value: CovNotificationRequest<'_>,
+) -> Result<CovNotification, ClientError> {
+    let mut values = Vec::with_capacity(value.values.len());
+    for property in value.values {
+        values.push(CovPropertyValue {
+            property_id: property.property_id,
+            array_index: property.array_index,
+            value: into_client_value(property.value)?,
+            priority: property.priority,
+        });
+    }
+
+    Ok(CovNotification {
+        source,
+        confirmed,
+        subscriber_process_id: value.subscriber_process_id,
+        initiating_device_id: value.initiating_device_id,
+        monitored_object_id: value.monitored_object_id,
+        time_remaining_seconds: value.time_remaining_seconds,
+        values,
+    })
+}
+
+fn into_client_event_notification(
+    source: DataLinkAddress,
+    confirmed: bool,
+    value: EventNotificationRequest<'_>,
+) -> EventNotification {
+    EventNotification {
+        source,
+        confirmed,
+        process_id: value.process_id,
+        initiating_device_id: value.initiating_device_id,
+        event_object_id: value.event_object_id,
+        timestamp: value.timestamp,
+        notification_class: value.notification_class,
+        priority: value.priority,
+        event_type: value.event_type,
+        message_text: value.message_text.map(str::to_string),
+        notify_type: value.notify_type,
+        ack_required: value.ack_required,
+        from_state_raw: value.from_state,
+        from_state: rustbac_core::services::acknowledge_alarm::EventState::from_u32(
+            value.from_state,
+        ),
+        to_state_raw: value.to_state,
+        to_state: rustbac_core::services::acknowledge_alarm::EventState::from_u32(value.to_state),
+        event_values: EventValues::from_request(&value),
+    }
+}
+
+fn into_client_read_range(value: ReadRangeAck<'_>) -> Result<ReadRangeResult, ClientError> {
+    let mut items = Vec::with_capacity(value.items.len());
+    for item in value.items {
+        items.push(into_client_value(item)?);
+    }
+    let mut records = Vec::with_capacity(value.records.len());
+    for record in value.records {
+        records.push(into_client_log_record(record)?);
+    }
+    Ok(ReadRangeResult {
+        object_id: value.object_id,
+        property_id: value.property_id,
+        array_index: value.array_index,
+        result_flags: ClientBitString {
+            unused_bits: value.result_flags.unused_bits,
+            data: value.result_flags.data.to_vec(),
+        },
+        item_count: value.item_count,
+        items,
+        records,
+    })
+}
+
+fn into_client_log_record(record: CoreLogBufferRecord<'_>) -> Result<LogBufferRecord, ClientError> {
+    Ok(match record {
+        CoreLogBufferRecord::Trend(record) => LogBufferRecord::Trend(TrendLogRecord {
+            date: record.date,
+            time: record.time,
+            datum: record.datum.into(),
+            status_flags: record.status_flags.map(|flags| ClientBitString {
+                unused_bits: flags.unused_bits,
+                data: flags.data.to_vec(),
+            }),
+        }),
+        CoreLogBufferRecord::Multiple(record) => LogBufferRecord::Multiple(LogMultipleRecord {
+            date: record.date,
+            time: record.time,
+            data: record.data.into(),
+        }),
+        CoreLogBufferRecord::Audit(record) => {
+            let datum = match record.datum {
+                CoreAuditLogDatum::LogStatus(bits) => AuditLogDatum::LogStatus(ClientBitString {
+                    unused_bits: bits.unused_bits,
+                    data: bits.data.to_vec(),
+                }),
+                CoreAuditLogDatum::Notification(notification) => {
+                    let notification = *notification;
+                    AuditLogDatum::Notification(Box::new(AuditNotification {
+                        source_timestamp: notification.source_timestamp,
+                        target_timestamp: notification.target_timestamp,
+                        source_device: notification.source_device.into(),
+                        source_object: notification.source_object,
+                        operation_raw: notification.operation_raw,
+                        operation: notification.operation(),
+                        source_comment: notification.source_comment.map(str::to_owned),
+                        target_comment: notification.target_comment.map(str::to_owned),
+                        invoke_id: notification.invoke_id,
+                        source_user_id: notification.source_user_id,
+                        source_user_role: notification.source_user_role,
+                        target_device: notification.target_device.into(),
+                        target_object: notification.target_object,
+                        target_property: notification.target_property,
+                        target_priority: notification.target_priority,
+                        target_value: notification
+                            .target_value
+                            .map(into_client_value)
+                            .transpose()?,
+                        current_value: notification
+                            .current_value
+                            .map(into_client_value)
+                            .transpose()?,
+                        result: notification.result,
+                    }))
+                }
+                CoreAuditLogDatum::TimeChange(v) => AuditLogDatum::TimeChange(v),
+            };
+            LogBufferRecord::Audit(AuditLogRecord {
+                date: record.date,
+                time: record.time,
+                datum,
+            })
+        }
+    })
+}
+
+fn into_client_atomic_read_result(value: AtomicReadFileAck<'_>) -> AtomicReadFileResult {
+    match value.access_method {
+        AtomicReadFileAckAccess::Stream {
+            file_start_position,
+            file_data,
+        } => AtomicReadFileResult::Stream {
+            end_of_file: value.end_of_file,
+            file_start_position,
+            file_data: file_data.to_vec(),
+        },
+        AtomicReadFileAckAccess::Record {
+            file_start_record,
+            returned_record_count,
+            file_record_data,
+        } => AtomicReadFileResult::Record {
+            end_of_file: value.end_of_file,
+            file_start_record,
+            returned_record_count,
+            file_record_data: file_record_data
+                .into_iter()
+                .map(|record| record.to_vec())
+                .collect(),
+        },
+    }
+}
+
+fn into_client_atomic_write_result(value: AtomicWriteFileAck) -> AtomicWriteFileResult {
+    match value {
+        AtomicWriteFileAck::Stream {
+            file_start_position,
+        } => AtomicWriteFileResult::Stream {
+            file_start_position,
+        },
+        AtomicWriteFileAck::Record { file_start_record } => {
+            AtomicWriteFileResult::Record { file_start_record }
+        }
+    }
+}
+
+/// Whether a ReadPropertyMultiple failure means the device cannot serve the request at
+/// all, as opposed to an error about the requested object or properties.
+fn is_rpm_unavailable(err: &ClientError) -> bool {
+    matches!(
+        err.root(),
+        ClientError::RemoteReject {
+            reason: REJECT_UNRECOGNIZED_SERVICE
+        } | ClientError::RemoteAbort {
+            reason: ABORT_SEGMENTATION_NOT_SUPPORTED,
+            ..
+        }
+    )
+}
+
+/// Whether a confirmed request failed because the response was too large for the peer to
+/// send in one APDU.
+fn is_response_too_large(err: &ClientError) -> bool {
+    matches!(
+        err.root(),
+        ClientError::RemoteAbort {
+            reason: ABORT_BUFFER_OVERFLOW | ABORT_SEGMENTATION_NOT_SUPPORTED | ABORT_APDU_TOO_LONG,
+            ..
+        }
+    )
+}
+
+fn object_ids_from_value(value: &ClientDataValue) -> Vec<ObjectId> {
+    match value {
+        ClientDataValue::ObjectId(oid) => vec![*oid],
+        ClientDataValue::Constructed { values, .. } => values
+            .iter()
+            .filter_map(|v| {
+                if let ClientDataValue::ObjectId(oid) = v {
+                    Some(*oid)
+                } else {
+                    None
+                }
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BacnetClient;
+    use crate::export::bacnet_date_time;
+    use crate::runtime::{Runtime, TaskHandle, TokioRuntime};
+    use crate::{
+        AlarmSummaryItem, AtomicReadFileResult, AtomicWriteFileResult, ClientDataValue,
+        ClientError, EnrollmentSummaryItem, EventInformationItem, EventNotification, InboundKind,
+        MemoryLimits, RequestOptions, ScaledPulses, SegmentationOptions, StringInterner,
+        TimeMasterConfig,
+    };
+    use rustbac_core::apdu::{
+        abort_reason, AbortPdu, ApduType, ComplexAckHeader, ConfirmedRequestHeader, SegmentAck,
+        SimpleAck, UnconfirmedRequestHeader,
+    };
+    use rustbac_core::encoding::{
+        primitives::{
+            decode_signed, decode_unsigned, encode_app_real, encode_ctx_character_string,
+            encode_ctx_object_id, encode_ctx_unsigned,
+        },
+        reader::Reader,
+        tag::{AppTag, Tag},
+        writer::Writer,
+    };
+    use rustbac_core::npdu::{NetworkPriority, Npdu};
+    use rustbac_core::services::acknowledge_alarm::{
+        AcknowledgeAlarmRequest, EventState, TimeStamp, SERVICE_ACKNOWLEDGE_ALARM,
+    };
+    use rustbac_core::services::alarm_summary::SERVICE_GET_ALARM_SUMMARY;
+    use rustbac_core::services::atomic_read_file::SERVICE_ATOMIC_READ_FILE;
+    use rustbac_core::services::atomic_write_file::SERVICE_ATOMIC_WRITE_FILE;
+    use rustbac_core::services::cov_notification::{
+        SERVICE_CONFIRMED_COV_NOTIFICATION, SERVICE_UNCONFIRMED_COV_NOTIFICATION,
+    };
+    use rustbac_core::services::device_management::{
+        DeviceCommunicationState, ReinitializeState, SERVICE_DEVICE_COMMUNICATION_CONTROL,
+        SERVICE_REINITIALIZE_DEVICE,
+    };
+    use rustbac_core::services::enrollment_summary::SERVICE_GET_ENROLLMENT_SUMMARY;
+    use rustbac_core::services::event_information::SERVICE_GET_EVENT_INFORMATION;
+    use rustbac_core::services::event_notification::{
+        SERVICE_CONFIRMED_EVENT_NOTIFICATION, SERVICE_UNCONFIRMED_EVENT_NOTIFICATION,
+    };
+    use rustbac_core::services::list_element::{
+        AddListElementRequest, RemoveListElementRequest, SERVICE_ADD_LIST_ELEMENT,
+        SERVICE_REMOVE_LIST_ELEMENT,
+    };
+    use rustbac_core::services::object_management::{SERVICE_CREATE_OBJECT, SERVICE_DELETE_OBJECT};
+    use rustbac_core::services::read_property::SERVICE_READ_PROPERTY;
+    use rustbac_core::services::read_property_multiple::SERVICE_READ_PROPERTY_MULTIPLE;
+    use rustbac_core::services::read_range::{LogDatum, LogRecord, SERVICE_READ_RANGE};
+    use rustbac_core::services::subscribe_cov::{SubscribeCovRequest, SERVICE_SUBSCRIBE_COV};
+    use rustbac_core::services::subscribe_cov_property::{
+        SubscribeCovPropertyRequest, SERVICE_SUBSCRIBE_COV_PROPERTY,
+    };
+    use rustbac_core::services::time_synchronization::{
+        SERVICE_TIME_SYNCHRONIZATION, SERVICE_UTC_TIME_SYNCHRONIZATION,
+    };
+    use rustbac_core::services::value_codec::encode_application_data_value;
+    use rustbac_core::services::who_has::{SERVICE_I_HAVE, SERVICE_WHO_HAS};
+    use rustbac_core::services::write_property::{WritePropertyRequest, SERVICE_WRITE_PROPERTY};
+    use rustbac_core::services::write_property_multiple::{
+        PropertyWriteSpec, SERVICE_WRITE_PROPERTY_MULTIPLE,
+    };
+    use rustbac_core::types::{DataValue, Date, ObjectId, ObjectType, PropertyId, Time};
+    use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError};
+    use std::collections::VecDeque;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+    use tokio::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct MockState {
+        sent: Mutex<Vec<(DataLinkAddress, Vec<u8>)>>,
+        recv: Mutex<VecDeque<(Vec<u8>, DataLinkAddress)>>,
+    }
+
+    #[derive(Debug, Clone)]
+    struct MockDataLink {
+        state: Arc<MockState>,
+    }
+
+    impl MockDataLink {
+        fn new() -> (Self, Arc<MockState>) {
+            let state = Arc::new(MockState::default());
+            (
+                Self {
+                    state: state.clone(),
+                },
+                state,
+            )
+        }
+    }
+
+    impl DataLink for MockDataLink {
+        async fn send(
+            &self,
+            address: DataLinkAddress,
+            payload: &[u8],
+        ) -> Result<(), DataLinkError> {
+            self.state
+                .sent
+                .lock()
+                .await
+                .push((address, payload.to_vec()));
+            Ok(())
+        }
+
+        async fn recv(&self, buf: &mut [u8]) -> Result<(usize, DataLinkAddress), DataLinkError> {
+            let Some((payload, addr)) = self.state.recv.lock().await.pop_front() else {
+                return Err(DataLinkError::InvalidFrame);
+            };
+            if payload.len() > buf.len() {
+                return Err(DataLinkError::FrameTooLarge);
+            }
+            buf[..payload.len()].copy_from_slice(&payload);
+            Ok((payload.len(), addr))
+        }
+    }
+
+    fn with_npdu(apdu: &[u8]) -> Vec<u8> {
+        let mut out = [0u8; 512];
+        let mut w = Writer::new(&mut out);
+        Npdu::new(0).encode(&mut w).unwrap();
+        w.write_all(apdu).unwrap();
+        w.as_written().to_vec()
+    }
+
+    fn read_range_ack_apdu(invoke_id: u8, object_id: ObjectId) -> Vec<u8> {
+        let mut apdu_buf = [0u8; 256];
+        let mut w = Writer::new(&mut apdu_buf);
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_READ_RANGE,
+        }
+        .encode(&mut w)
+        .unwrap();
+        encode_ctx_object_id(&mut w, 0, object_id.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::PresentValue.to_u32()).unwrap();
+        Tag::Context { tag_num: 3, len: 2 }.encode(&mut w).unwrap();
+        w.write_u8(5).unwrap();
+        w.write_u8(0b1110_0000).unwrap();
+        encode_ctx_unsigned(&mut w, 4, 2).unwrap();
+        Tag::Opening { tag_num: 5 }.encode(&mut w).unwrap();
+        encode_app_real(&mut w, 42.0).unwrap();
+        encode_app_real(&mut w, 43.0).unwrap();
+        Tag::Closing { tag_num: 5 }.encode(&mut w).unwrap();
+        w.as_written().to_vec()
+    }
+
+    fn atomic_read_file_stream_ack_apdu(invoke_id: u8, eof: bool, data: &[u8]) -> Vec<u8> {
+        let mut apdu_buf = [0u8; 256];
+        let mut w = Writer::new(&mut apdu_buf);
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_ATOMIC_READ_FILE,
+        }
+        .encode(&mut w)
+        .unwrap();
+        Tag::Application {
+            tag: AppTag::Boolean,
+            len: if eof { 1 } else { 0 },
+        }
+        .encode(&mut w)
+        .unwrap();
+        Tag::Opening { tag_num: 0 }.encode(&mut w).unwrap();
+        Tag::Application {
+            tag: AppTag::SignedInt,
+            len: 1,
+        }
+        .encode(&mut w)
+        .unwrap();
+        w.write_u8(0).unwrap();
+        Tag::Application {
+            tag: AppTag::OctetString,
+            len: data.len() as u32,
+        }
+        .encode(&mut w)
+        .unwrap();
+        w.write_all(data).unwrap();
+        Tag::Closing { tag_num: 0 }.encode(&mut w).unwrap();
+        w.as_written().to_vec()
+    }
+
+    fn atomic_read_file_record_ack_apdu(invoke_id: u8) -> Vec<u8> {
+        let mut apdu_buf = [0u8; 256];
+        let mut w = Writer::new(&mut apdu_buf);
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_ATOMIC_READ_FILE,
+        }
+        .encode(&mut w)
+        .unwrap();
+        Tag::Application {
+            tag: AppTag::Boolean,
+            len: 0,
+        }
+        .encode(&mut w)
+        .unwrap();
+        Tag::Opening { tag_num: 1 }.encode(&mut w).unwrap();
+        Tag::Application {
+            tag: AppTag::SignedInt,
+            len: 1,
+        }
+        .encode(&mut w)
+        .unwrap();
+        w.write_u8(7).unwrap();
+        Tag::Application {
+            tag: AppTag::UnsignedInt,
+            len: 1,
+        }
+        .encode(&mut w)
+        .unwrap();
+        w.write_u8(2).unwrap();
+        Tag::Application {
+            tag: AppTag::OctetString,
+            len: 2,
+        }
+        .encode(&mut w)
+        .unwrap();
+        w.write_all(&[0x01, 0x02]).unwrap();
+        Tag::Application {
+            tag: AppTag::OctetString,
+            len: 3,
+        }
+        .encode(&mut w)
+        .unwrap();
+        w.write_all(&[0x03, 0x04, 0x05]).unwrap();
+        Tag::Closing { tag_num: 1 }.encode(&mut w).unwrap();
+        w.as_written().to_vec()
+    }
+
+    fn atomic_write_file_stream_ack_apdu(invoke_id: u8, start_position: i32) -> Vec<u8> {
+        let mut apdu_buf = [0u8; 64];
+        let mut w = Writer::new(&mut apdu_buf);
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_ATOMIC_WRITE_FILE,
+        }
+        .encode(&mut w)
+        .unwrap();
+        Tag::Context { tag_num: 0, len: 2 }.encode(&mut w).unwrap();
+        w.write_all(&(start_position as i16).to_be_bytes()).unwrap();
+        w.as_written().to_vec()
+    }
+
+    fn atomic_write_file_record_ack_apdu(invoke_id: u8, start_record: i32) -> Vec<u8> {
+        let mut apdu_buf = [0u8; 64];
+        let mut w = Writer::new(&mut apdu_buf);
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_ATOMIC_WRITE_FILE,
+        }
+        .encode(&mut w)
+        .unwrap();
+        Tag::Context { tag_num: 1, len: 1 }.encode(&mut w).unwrap();
+        w.write_u8(start_record as u8).unwrap();
+        w.as_written().to_vec()
+    }
+
+    fn create_object_ack_apdu(invoke_id: u8, object_id: ObjectId) -> Vec<u8> {
+        let mut apdu_buf = [0u8; 64];
+        let mut w = Writer::new(&mut apdu_buf);
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_CREATE_OBJECT,
+        }
+        .encode(&mut w)
+        .unwrap();
+        encode_ctx_object_id(&mut w, 0, object_id.raw()).unwrap();
+        w.as_written().to_vec()
+    }
+
+    fn get_alarm_summary_ack_apdu(invoke_id: u8) -> Vec<u8> {
+        let mut apdu_buf = [0u8; 128];
+        let mut w = Writer::new(&mut apdu_buf);
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_GET_ALARM_SUMMARY,
+        }
+        .encode(&mut w)
+        .unwrap();
+        encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::AnalogInput, 1).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, 1).unwrap();
+        Tag::Context { tag_num: 2, len: 2 }.encode(&mut w).unwrap();
+        w.write_u8(5).unwrap();
+        w.write_u8(0b1110_0000).unwrap();
+
+        encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::BinaryInput, 2).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, 0).unwrap();
+        Tag::Context { tag_num: 2, len: 2 }.encode(&mut w).unwrap();
+        w.write_u8(5).unwrap();
+        w.write_u8(0b1100_0000).unwrap();
+        w.as_written().to_vec()
+    }
+
+    fn get_enrollment_summary_ack_apdu(invoke_id: u8) -> Vec<u8> {
+        let mut apdu_buf = [0u8; 160];
+        let mut w = Writer::new(&mut apdu_buf);
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_GET_ENROLLMENT_SUMMARY,
+        }
+        .encode(&mut w)
+        .unwrap();
+        encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::AnalogInput, 7).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, 1).unwrap();
+        encode_ctx_unsigned(&mut w, 2, 2).unwrap();
+        encode_ctx_unsigned(&mut w, 3, 200).unwrap();
+        encode_ctx_unsigned(&mut w, 4, 10).unwrap();
+
+        encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::BinaryInput, 8).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, 0).unwrap();
+        encode_ctx_unsigned(&mut w, 2, 0).unwrap();
+        encode_ctx_unsigned(&mut w, 3, 20).unwrap();
+        encode_ctx_unsigned(&mut w, 4, 11).unwrap();
+        w.as_written().to_vec()
+    }
+
+    fn get_event_information_ack_apdu(invoke_id: u8) -> Vec<u8> {
+        let mut apdu_buf = [0u8; 256];
+        let mut w = Writer::new(&mut apdu_buf);
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_GET_EVENT_INFORMATION,
+        }
+        .encode(&mut w)
+        .unwrap();
+        Tag::Opening { tag_num: 0 }.encode(&mut w).unwrap();
+        encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::AnalogInput, 1).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, 2).unwrap();
+        Tag::Context { tag_num: 2, len: 2 }.encode(&mut w).unwrap();
+        w.write_u8(5).unwrap();
+        w.write_u8(0b1110_0000).unwrap();
+        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+        Tag::Opening { tag_num: 0 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 1, 1).unwrap();
+        Tag::Closing { tag_num: 0 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 4, 0).unwrap();
+        Tag::Context { tag_num: 5, len: 2 }.encode(&mut w).unwrap();
+        w.write_u8(5).unwrap();
+        w.write_u8(0b1100_0000).unwrap();
+        Tag::Opening { tag_num: 6 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 0, 1).unwrap();
+        encode_ctx_unsigned(&mut w, 1, 2).unwrap();
+        encode_ctx_unsigned(&mut w, 2, 3).unwrap();
+        Tag::Closing { tag_num: 6 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 0 }.encode(&mut w).unwrap();
+        Tag::Context { tag_num: 1, len: 1 }.encode(&mut w).unwrap();
+        w.write_u8(0).unwrap();
+        w.as_written().to_vec()
+    }
+
+    #[tokio::test]
+    async fn who_has_object_name_collects_i_have() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 31], 47808).into());
+
+        let mut apdu = [0u8; 128];
+        let mut w = Writer::new(&mut apdu);
+        UnconfirmedRequestHeader {
+            service_choice: SERVICE_I_HAVE,
+        }
+        .encode(&mut w)
+        .unwrap();
+        encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::Device, 10).raw()).unwrap();
+        encode_ctx_object_id(&mut w, 1, ObjectId::new(ObjectType::AnalogInput, 7).raw()).unwrap();
+        encode_ctx_character_string(&mut w, 2, "Zone Temp").unwrap();
+
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(w.as_written()), addr));
+
+        let results = client
+            .who_has_object_name(None, "Zone Temp", Duration::from_millis(10))
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].address, addr);
+        assert_eq!(results[0].device_id, ObjectId::new(ObjectType::Device, 10));
+        assert_eq!(
+            results[0].object_id,
+            ObjectId::new(ObjectType::AnalogInput, 7)
+        );
+        assert_eq!(results[0].object_name, "Zone Temp");
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        let mut r = Reader::new(&sent[0].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = UnconfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_WHO_HAS);
+    }
+
+    #[tokio::test]
+    async fn device_communication_control_handles_simple_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 32], 47808).into());
+
+        let mut apdu = [0u8; 32];
+        let mut w = Writer::new(&mut apdu);
+        SimpleAck {
+            invoke_id: 1,
+            service_choice: SERVICE_DEVICE_COMMUNICATION_CONTROL,
+        }
+        .encode(&mut w)
+        .unwrap();
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(w.as_written()), addr));
+
+        client
+            .device_communication_control(addr, Some(30), DeviceCommunicationState::Disable, None)
+            .await
+            .unwrap();
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        let mut r = Reader::new(&sent[0].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_DEVICE_COMMUNICATION_CONTROL);
+    }
+
+    #[tokio::test]
+    async fn reinitialize_device_handles_simple_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 33], 47808).into());
+
+        let mut apdu = [0u8; 32];
+        let mut w = Writer::new(&mut apdu);
+        SimpleAck {
+            invoke_id: 1,
+            service_choice: SERVICE_REINITIALIZE_DEVICE,
+        }
+        .encode(&mut w)
+        .unwrap();
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(w.as_written()), addr));
+
+        client
+            .reinitialize_device(addr, ReinitializeState::ActivateChanges, Some("pw"))
+            .await
+            .unwrap();
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        let mut r = Reader::new(&sent[0].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_REINITIALIZE_DEVICE);
+    }
+
+    #[tokio::test]
+    async fn activate_network_port_changes_reinitializes_when_changes_are_pending() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 39], 47808).into());
+        let port = ObjectId::new(ObjectType::NetworkPort, 1);
+        {
+            let mut recv = state.recv.lock().await;
+            let pending = read_property_ack_apdu(
+                1,
+                port,
+                PropertyId::ChangesPending,
+                None,
+                &DataValue::Boolean(true),
+            );
+            recv.push_back((with_npdu(&pending), addr));
+            recv.push_back((
+                with_npdu(&simple_ack_apdu(2, SERVICE_REINITIALIZE_DEVICE)),
+                addr,
+            ));
+        }
+
+        let activated = client
+            .activate_network_port_changes(addr, port, Some("pw"))
+            .await
+            .unwrap();
+        assert!(activated);
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 2);
+        let mut r = Reader::new(&sent[1].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_REINITIALIZE_DEVICE);
+        // ReinitializeDevice: reinitialized-state [0] = activate-changes (7).
+        assert_eq!(&r.read_exact(2).unwrap(), &[0x09, 7]);
+    }
+
+    #[tokio::test]
+    async fn add_channel_member_rewrites_the_member_array() {
+        use rustbac_core::services::channel::{
+            ChannelMembersAck, DeviceObjectPropertyReference, WriteChannelMembersRequest,
+        };
+
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 40], 47808).into());
+        let channel = ObjectId::new(ObjectType::Channel, 1);
+        let existing = DeviceObjectPropertyReference::local(
+            ObjectId::new(ObjectType::LightingOutput, 1),
+            PropertyId::LightingCommand,
+        );
+        let added = DeviceObjectPropertyReference::local(
+            ObjectId::new(ObjectType::LightingOutput, 2),
+            PropertyId::LightingCommand,
+        );
+
+        // ReadProperty-ACK with the current member, laid out like WriteProperty parameters.
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        WriteChannelMembersRequest {
+            channel,
+            members: &[existing],
+            invoke_id: 0,
+        }
+        .encode(&mut w)
+        .unwrap();
+        let mut ack = vec![0x30, 1, SERVICE_READ_PROPERTY];
+        ack.extend_from_slice(&w.as_written()[4..]);
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((with_npdu(&ack), addr));
+            recv.push_back((with_npdu(&simple_ack_apdu(2, SERVICE_WRITE_PROPERTY)), addr));
+        }
+
+        assert!(client
+            .add_channel_member(addr, channel, added)
+            .await
+            .unwrap());
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 2);
+        let mut r = Reader::new(&sent[1].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_WRITE_PROPERTY);
+        let written = ChannelMembersAck::decode_after_header(&mut r).unwrap();
+        assert_eq!(written.members, vec![existing, added]);
+    }
+
+    #[tokio::test]
+    async fn read_global_group_values_keeps_member_errors() {
+        use crate::GroupMemberResult;
+        use rustbac_core::services::global_group::{AccessResult, PropertyAccessResult};
+        use rustbac_core::types::DeviceObjectPropertyReference;
+
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 42], 47808).into());
+        let group = ObjectId::new(ObjectType::GlobalGroup, 1);
+        let device = ObjectId::new(ObjectType::Device, 30);
+        let zone = DeviceObjectPropertyReference::remote(
+            device,
+            ObjectId::new(ObjectType::AnalogInput, 1),
+            PropertyId::PresentValue,
+        );
+        let offline = DeviceObjectPropertyReference::remote(
+            device,
+            ObjectId::new(ObjectType::AnalogInput, 2),
+            PropertyId::PresentValue,
+        );
+
+        let mut buf = [0u8; 128];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, group.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::PresentValue.to_u32()).unwrap();
+        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+        PropertyAccessResult {
+            member: zone,
+            result: AccessResult::Value(DataValue::Real(22.5)),
+        }
+        .encode(&mut w)
+        .unwrap();
+        PropertyAccessResult {
+            member: offline,
+            result: AccessResult::Error {
+                error_class: 7,
+                error_code: 0,
+            },
+        }
+        .encode(&mut w)
+        .unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        let mut ack = vec![0x30, 1, SERVICE_READ_PROPERTY];
+        ack.extend_from_slice(w.as_written());
+        state.recv.lock().await.push_back((with_npdu(&ack), addr));
+
+        let values = client.read_global_group_values(addr, group).await.unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0].member, zone);
+        assert_eq!(values[0].value(), Some(&ClientDataValue::Real(22.5)));
+        assert_eq!(values[1].member, offline);
+        assert_eq!(
+            values[1].result,
+            GroupMemberResult::Error {
+                error_class: 7,
+                error_code: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn lighting_and_color_helpers_round_trip_through_the_datalink() {
+        use rustbac_core::services::lighting::{ColorCommand, LightingCommand, XyColor};
+
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 41], 47808).into());
+        let output = ObjectId::new(ObjectType::LightingOutput, 1);
+        let color = ObjectId::new(ObjectType::Color, 1);
+
+        let mut color_ack = vec![0x30, 3, SERVICE_READ_PROPERTY];
+        let mut buf = [0u8; 16];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, color.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::PresentValue.to_u32()).unwrap();
+        color_ack.extend_from_slice(w.as_written());
+        color_ack.extend_from_slice(&[
+            0x3E, 0x44, 0x3E, 0xA0, 0x00, 0x00, 0x44, 0x3E, 0xC0, 0x00, 0x00, 0x3F,
+        ]);
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((with_npdu(&simple_ack_apdu(1, SERVICE_WRITE_PROPERTY)), addr));
+            recv.push_back((with_npdu(&simple_ack_apdu(2, SERVICE_WRITE_PROPERTY)), addr));
+            recv.push_back((with_npdu(&color_ack), addr));
+        }
+
+        client
+            .write_lighting_command(addr, output, LightingCommand::ramp_to(80.0, 5.0))
+            .await
+            .unwrap();
+        client
+            .write_color_command(addr, color, ColorCommand::fade_to_cct(4000, 1000))
+            .await
+            .unwrap();
+        assert_eq!(
+            client.read_xy_color(addr, color).await.unwrap(),
+            XyColor::new(0.3125, 0.375)
+        );
+
+        let sent = state.sent.lock().await;
+        let mut r = Reader::new(&sent[0].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_WRITE_PROPERTY);
+        // [0] object, [1] Lighting_Command, then the command inside [3].
+        assert_eq!(r.read_exact(8).unwrap()[5..], [0x1A, 0x01, 0x7C]);
+        assert_eq!(r.read_u8().unwrap(), 0x3E);
+        assert_eq!(
+            LightingCommand::decode(&mut r).unwrap(),
+            LightingCommand::ramp_to(80.0, 5.0)
+        );
+        assert_eq!(r.read_u8().unwrap(), 0x3F);
+    }
+
+    #[tokio::test]
+    async fn scaled_pulses_use_scale_or_scale_factor() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 43], 47808).into());
+        let meter = ObjectId::new(ObjectType::Accumulator, 1);
+        let converter = ObjectId::new(ObjectType::PulseConverter, 2);
+
+        let mut scale_ack = vec![0x30, 1, SERVICE_READ_PROPERTY];
+        let mut buf = [0u8; 16];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, meter.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::Scale.to_u32()).unwrap();
+        scale_ack.extend_from_slice(w.as_written());
+        // [3] { integer-scale -2 }
+        scale_ack.extend_from_slice(&[0x3E, 0x19, 0xFE, 0x3F]);
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((with_npdu(&scale_ack), addr));
+            // No Prescale: property / unknown-property.
+            recv.push_back((
+                with_npdu(&[0x50, 2, SERVICE_READ_PROPERTY, 0x91, 0x02, 0x91, 0x20]),
+                addr,
+            ));
+            for (invoke_id, object_id, property_id, value) in [
+                (3, meter, PropertyId::Units, DataValue::Enumerated(19)),
+                (
+                    4,
+                    meter,
+                    PropertyId::PresentValue,
+                    DataValue::Unsigned(12_345),
+                ),
+                (5, converter, PropertyId::ScaleFactor, DataValue::Real(0.5)),
+                (6, converter, PropertyId::Units, DataValue::Enumerated(80)),
+                (7, converter, PropertyId::Count, DataValue::Unsigned(30)),
+            ] {
+                recv.push_back((
+                    with_npdu(&read_property_ack_apdu(
+                        invoke_id,
+                        object_id,
+                        property_id,
+                        None,
+                        &value,
+                    )),
+                    addr,
+                ));
+            }
+        }
+
+        let reading = client.read_scaled_pulses(addr, meter).await.unwrap();
+        assert_eq!(
+            reading,
+            ScaledPulses {
+                pulses: 12_345,
+                value: 123.45,
+                units: 19,
+            }
+        );
+        let reading = client.read_scaled_pulses(addr, converter).await.unwrap();
+        assert_eq!(reading.value, 15.0);
+        assert_eq!(reading.units, 80);
+        assert!(matches!(
+            client
+                .read_pulse_scaling(addr, ObjectId::new(ObjectType::AnalogInput, 1))
+                .await,
+            Err(ClientError::UnsupportedResponse)
+        ));
+    }
+
+    #[tokio::test]
+    async fn loop_tuning_reads_and_writes_validated_constants() {
+        use crate::LoopTuningChange;
+        use rustbac_core::services::control_loop::{LoopAction, SetpointReference};
+        use rustbac_core::types::ObjectPropertyReference;
+
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 44], 47808).into());
+        let control_loop = ObjectId::new(ObjectType::Loop, 1);
+        let setpoint = ObjectPropertyReference::new(
+            ObjectId::new(ObjectType::AnalogValue, 3),
+            PropertyId::PresentValue,
+        );
+
+        let mut buf = [0u8; 128];
+        let mut w = Writer::new(&mut buf);
+        w.write_all(&[0x30, 1, SERVICE_READ_PROPERTY]).unwrap();
+        encode_ctx_object_id(&mut w, 0, control_loop.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::SetpointReference.to_u32()).unwrap();
+        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+        SetpointReference(Some(setpoint)).encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        let reference_ack = with_npdu(w.as_written());
+
+        let mut w = Writer::new(&mut buf);
+        w.write_all(&[0x30, 2, SERVICE_READ_PROPERTY_MULTIPLE])
+            .unwrap();
+        encode_ctx_object_id(&mut w, 0, control_loop.raw()).unwrap();
+        Tag::Opening { tag_num: 1 }.encode(&mut w).unwrap();
+        for (property_id, value) in [
+            (PropertyId::Setpoint, DataValue::Real(21.5)),
+            (PropertyId::ProportionalConstant, DataValue::Real(4.0)),
+            (PropertyId::IntegralConstant, DataValue::Real(0.25)),
+            (PropertyId::DerivativeConstant, DataValue::Real(0.0)),
+            (PropertyId::Action, DataValue::Enumerated(1)),
+            (PropertyId::OutputUnits, DataValue::Enumerated(98)),
+        ] {
+            encode_ctx_unsigned(&mut w, 2, property_id.to_u32()).unwrap();
+            Tag::Opening { tag_num: 4 }.encode(&mut w).unwrap();
+            encode_application_data_value(&mut w, &value).unwrap();
+            Tag::Closing { tag_num: 4 }.encode(&mut w).unwrap();
+        }
+        Tag::Closing { tag_num: 1 }.encode(&mut w).unwrap();
+        let rpm_ack = with_npdu(w.as_written());
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((reference_ack, addr));
+            recv.push_back((rpm_ack, addr));
+            recv.push_back((
+                with_npdu(&simple_ack_apdu(3, SERVICE_WRITE_PROPERTY_MULTIPLE)),
+                addr,
+            ));
+        }
+
+        let tuning = client.read_loop_tuning(addr, control_loop).await.unwrap();
+        assert_eq!(tuning.setpoint_reference, Some(setpoint));
+        assert_eq!(tuning.setpoint, 21.5);
+        assert_eq!(tuning.proportional_constant, 4.0);
+        assert_eq!(tuning.integral_constant, 0.25);
+        assert_eq!(tuning.action, LoopAction::Reverse);
+        assert_eq!(tuning.output_units, 98);
+
+        let change = LoopTuningChange::default().with_pid(3.0, 0.5, 0.0);
+        client
+            .write_loop_tuning(addr, control_loop, &change)
+            .await
+            .unwrap();
+        // Rejected before anything is sent.
+        assert!(matches!(
+            client
+                .write_loop_tuning(addr, control_loop, &change.with_pid(-3.0, 0.5, 0.0))
+                .await,
+            Err(ClientError::Encode(_))
+        ));
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 3);
+        let mut r = Reader::new(&sent[2].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_WRITE_PROPERTY_MULTIPLE);
+    }
+
+    #[tokio::test]
+    async fn access_door_is_read_and_commanded_and_credentials_decoded() {
+        use rustbac_core::services::access_control::{
+            AuthenticationFactor, DoorStatus, DoorValue, LockStatus,
+        };
+
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 45], 47808).into());
+        let door = ObjectId::new(ObjectType::AccessDoor, 1);
+        let input = ObjectId::new(ObjectType::CredentialDataInput, 2);
+
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        w.write_all(&[0x30, 6, SERVICE_READ_PROPERTY]).unwrap();
+        encode_ctx_object_id(&mut w, 0, input.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::PresentValue.to_u32()).unwrap();
+        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+        AuthenticationFactor {
+            format_type: 11,
+            format_class: 0,
+            value: &[0x00, 0x01, 0xE2, 0x40],
+        }
+        .encode(&mut w)
+        .unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        let factor_ack = with_npdu(w.as_written());
+        {
+            let mut recv = state.recv.lock().await;
+            for (invoke_id, property_id, value) in [
+                (1, PropertyId::PresentValue, 1),
+                (2, PropertyId::DoorStatus, 0),
+                (3, PropertyId::LockStatus, 0),
+            ] {
+                recv.push_back((
+                    with_npdu(&read_property_ack_apdu(
+                        invoke_id,
+                        door,
+                        property_id,
+                        None,
+                        &DataValue::Enumerated(value),
+                    )),
+                    addr,
+                ));
+            }
+            // No Door_Alarm_State: unknown-property.
+            recv.push_back((
+                with_npdu(&[0x50, 4, SERVICE_READ_PROPERTY, 0x91, 0x02, 0x91, 0x20]),
+                addr,
+            ));
+            recv.push_back((with_npdu(&simple_ack_apdu(5, SERVICE_WRITE_PROPERTY)), addr));
+            recv.push_back((factor_ack, addr));
+        }
+
+        let door_state = client.read_access_door(addr, door).await.unwrap();
+        assert_eq!(door_state.present_value, Some(DoorValue::Unlock));
+        assert_eq!(door_state.door_status, Some(DoorStatus::Closed));
+        assert_eq!(door_state.lock_status, Some(LockStatus::Locked));
+        assert_eq!(door_state.door_alarm_state, None);
+
+        client
+            .command_access_door(addr, door, DoorValue::PulseUnlock, 8)
+            .await
+            .unwrap();
+        let factor = client
+            .read_credential_data_input(addr, input)
+            .await
+            .unwrap();
+        assert_eq!(factor.format_type, 11);
+        assert_eq!(factor.value, vec![0x00, 0x01, 0xE2, 0x40]);
+
+        let sent = state.sent.lock().await;
+        let mut r = Reader::new(&sent[4].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_WRITE_PROPERTY);
+    }
+
+    #[tokio::test]
+    async fn life_safety_status_is_read_for_points_and_zones_only() {
+        use rustbac_core::types::{LifeSafetyMode, LifeSafetyOperation, LifeSafetyState};
+
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 46], 47808).into());
+        let detector = ObjectId::new(ObjectType::LifeSafetyPoint, 12);
+
+        let mut buf = [0u8; 128];
+        let mut w = Writer::new(&mut buf);
+        w.write_all(&[0x30, 1, SERVICE_READ_PROPERTY_MULTIPLE])
+            .unwrap();
+        encode_ctx_object_id(&mut w, 0, detector.raw()).unwrap();
+        Tag::Opening { tag_num: 1 }.encode(&mut w).unwrap();
+        for (property_id, value) in [
+            (PropertyId::PresentValue, 2),
+            (PropertyId::TrackingValue, 7),
+            (PropertyId::Mode, 1),
+            (PropertyId::OperationExpected, 1),
+            (PropertyId::Silenced, 0),
+        ] {
+            encode_ctx_unsigned(&mut w, 2, property_id.to_u32()).unwrap();
+            Tag::Opening { tag_num: 4 }.encode(&mut w).unwrap();
+            encode_application_data_value(&mut w, &DataValue::Enumerated(value)).unwrap();
+            Tag::Closing { tag_num: 4 }.encode(&mut w).unwrap();
+        }
+        Tag::Closing { tag_num: 1 }.encode(&mut w).unwrap();
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(w.as_written()), addr));
+
+        let status = client.read_life_safety(addr, detector).await.unwrap();
+        assert_eq!(status.present_value(), Some(LifeSafetyState::Alarm));
+        assert_eq!(status.tracking_value(), Some(LifeSafetyState::Active));
+        assert_eq!(status.mode(), Some(LifeSafetyMode::On));
+        assert_eq!(
+            status.operation_expected(),
+            Some(LifeSafetyOperation::Silence)
+        );
+        assert!(status.in_alarm());
+
+        assert!(matches!(
+            client
+                .read_life_safety(addr, ObjectId::new(ObjectType::AnalogInput, 1))
+                .await,
+            Err(ClientError::UnsupportedResponse)
+        ));
+        assert_eq!(state.sent.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn lift_calls_and_landing_calls_are_decoded() {
+        use rustbac_core::services::lift::{
+            LandingCall, LandingCallCommand, LandingCallStatus, LiftCarDirection,
+        };
+
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 47], 47808).into());
+        let lift = ObjectId::new(ObjectType::Lift, 1);
+
+        let ack = |invoke_id: u8, property_id: PropertyId, body: &dyn Fn(&mut Writer<'_>)| {
+            let mut buf = [0u8; 128];
+            let mut w = Writer::new(&mut buf);
+            w.write_all(&[0x30, invoke_id, SERVICE_READ_PROPERTY])
+                .unwrap();
+            encode_ctx_object_id(&mut w, 0, lift.raw()).unwrap();
+            encode_ctx_unsigned(&mut w, 1, property_id.to_u32()).unwrap();
+            Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+            body(&mut w);
+            Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+            with_npdu(w.as_written())
+        };
+        let registered = ack(1, PropertyId::RegisteredCarCall, &|w| {
+            Tag::Opening { tag_num: 0 }.encode(w).unwrap();
+            encode_application_data_value(w, &DataValue::Unsigned(6)).unwrap();
+            Tag::Closing { tag_num: 0 }.encode(w).unwrap();
+        });
+        let assigned = ack(2, PropertyId::AssignedLandingCalls, &|w| {
+            Tag::Opening { tag_num: 0 }.encode(w).unwrap();
+            LandingCall {
+                floor_number: 3,
+                direction: LiftCarDirection::Up,
+            }
+            .encode(w)
+            .unwrap();
+            Tag::Closing { tag_num: 0 }.encode(w).unwrap();
+        });
+        let landing = ack(3, PropertyId::LandingCalls, &|w| {
+            LandingCallStatus {
+                floor_number: 0,
+                command: LandingCallCommand::Destination(6),
+                floor_text: Some("Lobby"),
+            }
+            .encode(w)
+            .unwrap();
+        });
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((registered, addr));
+            recv.push_back((assigned, addr));
+            recv.push_back((landing, addr));
+        }
+
+        let calls = client.read_lift_calls(addr, lift).await.unwrap();
+        assert_eq!(calls.registered_car_calls, vec![vec![6]]);
+        assert_eq!(calls.stops(), vec![3, 6]);
+        let landing_calls = client.read_landing_calls(addr, lift).await.unwrap();
+        assert_eq!(landing_calls.len(), 1);
+        assert_eq!(landing_calls[0].command, LandingCallCommand::Destination(6));
+        assert_eq!(landing_calls[0].floor_text.as_deref(), Some("Lobby"));
+    }
+
+    #[tokio::test]
+    async fn exception_schedule_is_validated_before_it_is_written() {
+        use crate::{CalendarEntry, SpecialEvent, SpecialEventPeriod, TimeValue};
+
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 48], 47808).into());
+        let schedule = ObjectId::new(ObjectType::Schedule, 1);
+        let mut event = SpecialEvent {
+            period: SpecialEventPeriod::CalendarEntry(CalendarEntry::Date(Date {
+                year_since_1900: 124,
+                month: 12,
+                day: 25,
+                weekday: 0xFF,
+            })),
+            time_values: vec![TimeValue {
+                time: Time {
+                    hour: 0,
+                    minute: 0,
+                    second: 0,
+                    hundredths: 0,
+                },
+                value: ClientDataValue::Real(16.0),
+            }],
+            priority: 20,
+        };
+
+        let err = client
+            .write_exception_schedule(addr, schedule, std::slice::from_ref(&event))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            &err,
+            ClientError::InvalidSchedule { diagnostics } if diagnostics.len() == 1
+        ));
+        assert_eq!(err.code(), "client.invalid_schedule");
+        assert!(state.sent.lock().await.is_empty());
+
+        event.priority = 2;
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(&simple_ack_apdu(1, SERVICE_WRITE_PROPERTY)), addr));
+        client
+            .write_exception_schedule(addr, schedule, &[event])
+            .await
+            .unwrap();
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        let mut r = Reader::new(&sent[0].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_WRITE_PROPERTY);
+    }
+
+    #[tokio::test]
+    async fn occupancy_schedule_is_applied_to_each_target() {
+        use crate::{CalendarEntry, OccupancySchedule};
+
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 49], 47808).into());
+        let lobby = ObjectId::new(ObjectType::Schedule, 1);
+        let offices = ObjectId::new(ObjectType::Schedule, 2);
+        let hm = |hour| Time {
+            hour,
+            minute: 0,
+            second: 0,
+            hundredths: 0,
+        };
+        let occupancy = OccupancySchedule::binary()
+            .with_workday_hours(hm(7), hm(19))
+            .with_holiday(CalendarEntry::Date(Date {
+                year_since_1900: 0xFF,
+                month: 1,
+                day: 1,
+                weekday: 0xFF,
+            }));
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((with_npdu(&simple_ack_apdu(1, SERVICE_WRITE_PROPERTY)), addr));
+            recv.push_back((with_npdu(&simple_ack_apdu(2, SERVICE_WRITE_PROPERTY)), addr));
+            recv.push_back((
+                with_npdu(&[0x50, 3, SERVICE_WRITE_PROPERTY, 0x91, 0x02, 0x91, 0x28]),
+                addr,
+            ));
+        }
+
+        let report = client
+            .apply_occupancy_schedule(&[(addr, lobby), (addr, offices)], &occupancy)
+            .await;
+        assert!(!report.is_complete());
+        assert_eq!(report.applied, vec![(addr, lobby)]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].schedule, offices);
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 3);
+        let properties: Vec<u8> = sent
+            .iter()
+            .map(|(_, frame)| {
+                let mut r = Reader::new(frame);
+                let _npdu = Npdu::decode(&mut r).unwrap();
+                let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+                assert_eq!(hdr.service_choice, SERVICE_WRITE_PROPERTY);
+                let body = r.read_exact(7).unwrap();
+                body[6]
+            })
+            .collect();
+        // Weekly_Schedule (123), Exception_Schedule (38), then Weekly_Schedule again.
+        assert_eq!(properties, vec![123, 38, 123]);
+    }
+
+    #[tokio::test]
+    async fn who_is_merges_replies_from_one_device_by_policy() {
+        use crate::DiscoveryDedup;
+        use rustbac_core::services::i_am::IAmRequest;
+
+        let direct = DataLinkAddress::Ip(([192, 168, 1, 50], 47808).into());
+        let forwarded = DataLinkAddress::Ip(([10, 0, 0, 1], 47808).into());
+        let other = DataLinkAddress::Ip(([192, 168, 1, 51], 47808).into());
+        let i_am = |instance| {
+            let mut buf = [0u8; 64];
+            let mut w = Writer::new(&mut buf);
+            Npdu::new(0).encode(&mut w).unwrap();
+            IAmRequest {
+                device_id: ObjectId::new(ObjectType::Device, instance),
+                max_apdu: 1476,
+                segmentation: 3,
+                vendor_id: 1,
+            }
+            .encode(&mut w)
+            .unwrap();
+            w.as_written().to_vec()
+        };
+
+        for (dedup, expected) in [
+            (
+                DiscoveryDedup::LatestAddress,
+                vec![(forwarded, 10), (other, 11)],
+            ),
+            (
+                DiscoveryDedup::FirstAddress,
+                vec![(direct, 10), (other, 11)],
+            ),
+            (
+                DiscoveryDedup::Off,
+                vec![(direct, 10), (other, 11), (forwarded, 10)],
+            ),
+        ] {
+            let (dl, state) = MockDataLink::new();
+            let client = BacnetClient::with_datalink(dl).with_discovery_dedup(dedup);
+            {
+                let mut recv = state.recv.lock().await;
+                recv.push_back((i_am(10), direct));
+                recv.push_back((i_am(11), other));
+                recv.push_back((i_am(10), forwarded));
+                recv.push_back((i_am(10), forwarded));
+            }
+            let devices = client
+                .who_is(None, Duration::from_millis(20))
+                .await
+                .unwrap();
+            let found: Vec<_> = devices
+                .iter()
+                .map(|device| (device.address, device.device_id.unwrap().instance()))
+                .collect();
+            assert_eq!(found, expected, "{dedup:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn probe_capabilities_tunes_requests_to_the_device() {
+        use crate::client::WILDCARD_DEVICE_INSTANCE;
+        use crate::ProtocolService;
+        use rustbac_core::types::{BitString, Segmentation};
+
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Mstp(12);
+        let device = ObjectId::new(ObjectType::Device, WILDCARD_DEVICE_INSTANCE);
+        let services = [0x00, 0x09, 0x00, 0x00, 0x00]; // ReadProperty, WriteProperty
+        let answers = [
+            Some((
+                PropertyId::ObjectIdentifier,
+                DataValue::ObjectId(ObjectId::new(ObjectType::Device, 1200)),
+            )),
+            None,
+            Some((
+                PropertyId::ProtocolServicesSupported,
+                DataValue::BitString(BitString::new(0, &services)),
+            )),
+            None,
+            Some((PropertyId::MaxApduLengthAccepted, DataValue::Unsigned(206))),
+            Some((PropertyId::SegmentationSupported, DataValue::Enumerated(3))),
+        ];
+        {
+            let mut recv = state.recv.lock().await;
+            for invoke_id in 1..=12u8 {
+                let apdu = match answers.get(usize::from(invoke_id) - 1).cloned().flatten() {
+                    Some((property_id, value)) => {
+                        read_property_ack_apdu(invoke_id, device, property_id, None, &value)
+                    }
+                    None => vec![0x50, invoke_id, SERVICE_READ_PROPERTY, 0x91, 2, 0x91, 32],
+                };
+                recv.push_back((with_npdu(&apdu), addr));
+            }
+        }
+
+        let caps = client.probe_capabilities(addr).await.unwrap();
+        assert_eq!(
+            caps.device_id,
+            Some(ObjectId::new(ObjectType::Device, 1200))
+        );
+        assert_eq!(caps.max_apdu, Some(206));
+        assert_eq!(caps.segmentation, Some(Segmentation::NoSegmentation));
+        assert_eq!(
+            caps.supports_service(ProtocolService::ReadPropertyMultiple),
+            Some(false)
+        );
+        assert_eq!(caps.vendor_name, None);
+
+        assert!(client.uses_rpm_fallback(addr));
+        assert_eq!(
+            client.segmentation_for(addr),
+            SegmentationOptions::unsegmented()
+        );
+        assert_eq!(
+            client.capability_cache.read().unwrap().get(&addr),
+            Some(&206)
+        );
+    }
+
+    #[tokio::test]
+    async fn device_database_refresh_walks_only_new_devices() {
+        use crate::{DeviceDatabase, DeviceRecord, RefreshOptions};
+        use rustbac_core::services::i_am::IAmRequest;
+        use std::time::SystemTime;
+
+        let i_am = |instance| {
+            let mut buf = [0u8; 64];
+            let mut w = Writer::new(&mut buf);
+            Npdu::new(0).encode(&mut w).unwrap();
+            IAmRequest {
+                device_id: ObjectId::new(ObjectType::Device, instance),
+                max_apdu: 1476,
+                segmentation: 3,
+                vendor_id: 1,
+            }
+            .encode(&mut w)
+            .unwrap();
+            w.as_written().to_vec()
+        };
+        let old = DataLinkAddress::Ip(([192, 168, 1, 10], 47808).into());
+        let moved = DataLinkAddress::Ip(([192, 168, 1, 110], 47808).into());
+        let new = DataLinkAddress::Ip(([192, 168, 1, 11], 47808).into());
+        let now = SystemTime::now();
+        let mut db = DeviceDatabase::new();
+        for (instance, address) in [(10, old), (30, old)] {
+            let mut record =
+                DeviceRecord::new(ObjectId::new(ObjectType::Device, instance), address, now);
+            record.last_walked = Some(now);
+            db.insert(record);
+        }
+
+        let (dl, state) = MockDataLink::new();
+        let client =
+            BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_millis(50));
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((i_am(10), moved));
+            recv.push_back((i_am(11), new));
+        }
+        let report = db
+            .refresh(
+                &client,
+                RefreshOptions::default().with_wait(Duration::from_millis(20)),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.added, vec![11]);
+        assert_eq!(report.moved, vec![10]);
+        assert_eq!(report.missing, vec![30]);
+        // Device 10 was walked recently; device 11 does not answer the probe.
+        assert!(report.walked.is_empty());
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].device_instance, 11);
+        assert!(!report.is_complete());
+        assert_eq!(db.len(), 3);
+        assert_eq!(db.get(10).unwrap().address, moved);
+        assert_eq!(db.get(11).unwrap().last_walked, None);
+    }
+
+    #[tokio::test]
+    async fn create_event_enrollment_configures_and_rolls_back_on_rejection() {
+        use crate::EventEnrollmentConfig;
+        use rustbac_core::services::event_enrollment::{EventParameter, EventParametersAck};
+        use rustbac_core::services::object_management::SERVICE_DELETE_OBJECT;
+        use rustbac_core::types::DeviceObjectPropertyReference;
+
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 42], 47808).into());
+        let created = ObjectId::new(ObjectType::EventEnrollment, 3);
+        let config = EventEnrollmentConfig::new(
+            DeviceObjectPropertyReference::local(
+                ObjectId::new(ObjectType::AnalogInput, 1),
+                PropertyId::PresentValue,
+            ),
+            EventParameter::OutOfRange {
+                time_delay: 30,
+                low_limit: 15.0,
+                high_limit: 28.0,
+                deadband: 0.5,
+            },
+            2,
+        );
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((with_npdu(&create_object_ack_apdu(1, created)), addr));
+            for invoke_id in 2..=6 {
+                recv.push_back((
+                    with_npdu(&simple_ack_apdu(invoke_id, SERVICE_WRITE_PROPERTY)),
+                    addr,
+                ));
+            }
+            recv.push_back((with_npdu(&create_object_ack_apdu(7, created)), addr));
+            recv.push_back((with_npdu(&simple_ack_apdu(8, SERVICE_WRITE_PROPERTY)), addr));
+            // value-out-of-range for the Event_Parameters write.
+            recv.push_back((
+                with_npdu(&[0x50, 9, SERVICE_WRITE_PROPERTY, 0x91, 0x02, 0x91, 0x25]),
+                addr,
+            ));
+            recv.push_back((with_npdu(&simple_ack_apdu(10, SERVICE_DELETE_OBJECT)), addr));
+        }
+
+        assert_eq!(
+            client.create_event_enrollment(addr, &config).await.unwrap(),
+            created
+        );
+        assert!(client.create_event_enrollment(addr, &config).await.is_err());
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 10);
+        // The Event_Parameters write carries the parameters inside [3].
+        let mut r = Reader::new(&sent[2].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_WRITE_PROPERTY);
+        let ack = EventParametersAck::decode_after_header(&mut r).unwrap();
+        assert_eq!(ack.event_enrollment, created);
+        assert_eq!(ack.parameters, config.parameters);
+
+        let mut r = Reader::new(&sent[9].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_DELETE_OBJECT);
+    }
+
+    #[tokio::test]
+    async fn event_message_texts_config_reads_array_and_writes_per_transition() {
+        use crate::{EventMessageTexts, EventTransition};
+        use rustbac_core::services::write_property::WritePropertyRequest;
+
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 42], 47808).into());
+        let object = ObjectId::new(ObjectType::AnalogInput, 4);
+
+        let mut texts_ack = vec![0x30, 1, SERVICE_READ_PROPERTY];
+        let mut buf = [0u8; 128];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, object.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::EventMessageTextsConfig.to_u32()).unwrap();
+        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+        for text in ["too hot", "sensor fault", "back to normal"] {
+            encode_application_data_value(&mut w, &DataValue::CharacterString(text)).unwrap();
+        }
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        texts_ack.extend_from_slice(w.as_written());
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((with_npdu(&texts_ack), addr));
+            for invoke_id in 2..=4 {
+                recv.push_back((
+                    with_npdu(&simple_ack_apdu(invoke_id, SERVICE_WRITE_PROPERTY)),
+                    addr,
+                ));
+            }
+        }
+
+        let texts = client
+            .read_event_message_texts_config(addr, object)
+            .await
+            .unwrap();
+        assert_eq!(
+            texts,
+            EventMessageTexts::new("too hot", "sensor fault", "back to normal")
+        );
+        let mut changed = texts.clone();
+        changed.set(EventTransition::ToFault, "{object} sensor fault");
+        client
+            .write_event_message_texts_config(addr, object, &changed)
+            .await
+            .unwrap();
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 4);
+        for (invoke_id, transition) in (2..).zip(EventTransition::ALL) {
+            let request = WritePropertyRequest {
+                object_id: object,
+                property_id: PropertyId::EventMessageTextsConfig,
+                array_index: Some(transition.array_index()),
+                value: DataValue::CharacterString(changed.get(transition)),
+                invoke_id,
+                ..Default::default()
+            };
+            let mut w = Writer::new(&mut buf);
+            request.encode(&mut w).unwrap();
+            // Past the confirmed request header, which the client rewrites when sending.
+            assert!(sent[usize::from(invoke_id) - 1]
+                .1
+                .ends_with(&w.as_written()[4..]));
+        }
+    }
+
+    #[tokio::test]
+    async fn read_active_cov_subscriptions_decodes_recipients() {
+        use crate::Recipient;
+        use rustbac_core::services::cov_subscription::CovSubscription;
+        use rustbac_core::services::read_range::Recipient as CoreRecipient;
+        use rustbac_core::types::ObjectPropertyReference;
+
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 42], 47808).into());
+        let device = ObjectId::new(ObjectType::Device, 42);
+        let monitored = ObjectPropertyReference::new(
+            ObjectId::new(ObjectType::AnalogInput, 1),
+            PropertyId::PresentValue,
+        );
+
+        let mut ack = vec![0x30, 1, SERVICE_READ_PROPERTY];
+        let mut buf = [0u8; 128];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, device.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::ActiveCovSubscriptions.to_u32()).unwrap();
+        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+        CovSubscription {
+            recipient: CoreRecipient::Address {
+                network: 0,
+                mac: &[192, 168, 1, 10, 0xBA, 0xC0],
+            },
+            process_id: 3,
+            monitored,
+            issue_confirmed_notifications: false,
+            time_remaining: 90,
+            cov_increment: Some(0.5),
+        }
+        .encode(&mut w)
+        .unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        ack.extend_from_slice(w.as_written());
+        state.recv.lock().await.push_back((with_npdu(&ack), addr));
+
+        let subscriptions = client
+            .read_active_cov_subscriptions(addr, device)
+            .await
+            .unwrap();
+        assert_eq!(subscriptions.len(), 1);
+        assert_eq!(
+            subscriptions[0].recipient,
+            Recipient::Address {
+                network: 0,
+                mac: vec![192, 168, 1, 10, 0xBA, 0xC0],
+            }
+        );
+        assert_eq!(subscriptions[0].monitored, monitored);
+        assert_eq!(subscriptions[0].time_remaining, 90);
+        assert_eq!(subscriptions[0].cov_increment, Some(0.5));
+    }
+
+    #[tokio::test]
+    async fn calendar_date_list_helpers_validate_and_round_trip() {
+        use crate::{CalendarEntry, DateRange};
+        use rustbac_core::services::list_element::SERVICE_ADD_LIST_ELEMENT;
+
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 43], 47808).into());
+        let calendar = ObjectId::new(ObjectType::Calendar, 1);
+        let holidays = [
+            CalendarEntry::Date(Date {
+                year_since_1900: 0xFF,
+                month: 12,
+                day: 25,
+                weekday: 0xFF,
+            }),
+            CalendarEntry::WeekNDay {
+                month: 11,
+                week_of_month: 4,
+                day_of_week: 4,
+            },
+        ];
+
+        let mut date_list_ack = vec![0x30, 2, SERVICE_READ_PROPERTY];
+        let mut buf = [0u8; 16];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, calendar.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::DateList.to_u32()).unwrap();
+        date_list_ack.extend_from_slice(w.as_written());
+        date_list_ack.extend_from_slice(&[
+            0x3E, 0x0C, 0xFF, 12, 25, 0xFF, 0x2B, 11, 4, 4, 0x1E, 0xA4, 124, 7, 1, 0xFF, 0xA4, 124,
+            7, 14, 0xFF, 0x1F, 0x3F,
+        ]);
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((
+                with_npdu(&simple_ack_apdu(1, SERVICE_ADD_LIST_ELEMENT)),
+                addr,
+            ));
+            recv.push_back((with_npdu(&date_list_ack), addr));
+        }
+
+        client
+            .add_calendar_entries(addr, calendar, &holidays)
+            .await
+            .unwrap();
+        let entries = client
+            .read_calendar_date_list(addr, calendar)
+            .await
+            .unwrap();
+        assert_eq!(entries[..2], holidays);
+        assert_eq!(
+            entries[2],
+            CalendarEntry::Range(DateRange {
+                start: Date {
+                    year_since_1900: 124,
+                    month: 7,
+                    day: 1,
+                    weekday: 0xFF,
+                },
+                end: Date {
+                    year_since_1900: 124,
+                    month: 7,
+                    day: 14,
+                    weekday: 0xFF,
+                },
+            })
+        );
+
+        let invalid = [CalendarEntry::WeekNDay {
+            month: 1,
+            week_of_month: 0,
+            day_of_week: 1,
+        }];
+        let err = client
+            .write_calendar_date_list(addr, calendar, &invalid)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ClientError::Encode(rustbac_core::EncodeError::ValueOutOfRange)
+        ));
+        assert_eq!(state.sent.lock().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn time_synchronize_sends_unconfirmed_request() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 34], 47808).into());
+
+        client
+            .time_synchronize(
+                addr,
+                Date {
+                    year_since_1900: 126,
+                    month: 2,
+                    day: 7,
+                    weekday: 6,
+                },
+                Time {
+                    hour: 10,
+                    minute: 11,
+                    second: 12,
+                    hundredths: 13,
+                },
+                false,
+            )
+            .await
+            .unwrap();
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        let mut r = Reader::new(&sent[0].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = UnconfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_TIME_SYNCHRONIZATION);
+    }
+
+    #[tokio::test]
+    async fn time_synchronize_all_reports_each_target() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl);
+        let device = DataLinkAddress::Ip(([192, 168, 1, 34], 47808).into());
+        let broadcast = DataLinkAddress::Ip(([192, 168, 2, 255], 47808).into());
+
+        // 2026-02-07 15:00:00 UTC.
+        let utc = UNIX_EPOCH + Duration::from_secs(1_770_476_400);
+        let report = client
+            .time_synchronize_all(&[device, broadcast], utc, Duration::ZERO)
+            .await;
+
+        assert!(report.is_complete());
+        assert_eq!(report.synchronized, vec![device, broadcast]);
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 2);
+        for ((address, frame), expected) in sent.iter().zip([device, broadcast]) {
+            assert_eq!(*address, expected);
+            let mut r = Reader::new(frame);
+            let _npdu = Npdu::decode(&mut r).unwrap();
+            let hdr = UnconfirmedRequestHeader::decode(&mut r).unwrap();
+            assert_eq!(hdr.service_choice, SERVICE_UTC_TIME_SYNCHRONIZATION);
+            assert!(frame.windows(4).any(|b| b == [126, 2, 7, 6]));
+            assert!(frame.windows(2).any(|b| b == [15, 0]));
+        }
+    }
+
+    fn clock_acks(
+        addr: DataLinkAddress,
+        first_invoke_id: u8,
+        utc_offset: i32,
+        local: SystemTime,
+    ) -> Vec<(Vec<u8>, DataLinkAddress)> {
+        let device = ObjectId::new(ObjectType::Device, 1);
+        let (date, time) = bacnet_date_time(local);
+        [
+            (PropertyId::UtcOffset, DataValue::Signed(utc_offset)),
+            (PropertyId::DaylightSavingsStatus, DataValue::Boolean(false)),
+            (PropertyId::LocalDate, DataValue::Date(date)),
+            (PropertyId::LocalTime, DataValue::Time(time)),
+        ]
+        .into_iter()
+        .zip(first_invoke_id..)
+        .map(|((property, value), invoke_id)| {
+            (
+                with_npdu(&read_property_ack_apdu(
+                    invoke_id, device, property, None, &value,
+                )),
+                addr,
+            )
+        })
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn read_device_clock_reports_drift_against_the_host() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 36], 47808).into());
+
+        // Five hours behind UTC, running 90 s fast.
+        let local = SystemTime::now() - Duration::from_secs(5 * 3600 - 90);
+        state
+            .recv
+            .lock()
+            .await
+            .extend(clock_acks(addr, 1, 300, local));
+
+        let clock = client.read_device_clock(addr).await.unwrap();
+        assert_eq!(clock.utc_offset_minutes, Some(300));
+        assert_eq!(clock.daylight_savings, Some(false));
+        let drift = clock.drift_millis().unwrap();
+        assert!((88_000..=92_000).contains(&drift), "drift {drift}");
+    }
+
+    #[tokio::test]
+    async fn clock_audit_reports_drift_offsets_and_unreadable_devices() {
+        let (dl, state) = MockDataLink::new();
+        let client =
+            BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_millis(50));
+        let good = DataLinkAddress::Ip(([192, 168, 1, 38], 47808).into());
+        let fast = DataLinkAddress::Ip(([192, 168, 1, 39], 47808).into());
+        let silent = DataLinkAddress::Ip(([192, 168, 1, 40], 47808).into());
+
+        let now = SystemTime::now();
+        {
+            let mut recv = state.recv.lock().await;
+            recv.extend(clock_acks(good, 1, 0, now));
+            // An hour off: UTC_Offset set for a zone an hour west.
+            recv.extend(clock_acks(
+                fast,
+                5,
+                60,
+                now + Duration::from_secs(120) - Duration::from_secs(3600),
+            ));
+        }
+
+        let report = client
+            .audit_device_clocks(&[good, fast, silent], Duration::from_secs(5))
+            .await;
+
+        assert!(!report.is_clean());
+        assert_eq!(report.clocks.len(), 2);
+        let drifted: Vec<_> = report.drifted().map(|entry| entry.address).collect();
+        assert_eq!(drifted, vec![fast]);
+        let mismatched: Vec<_> = report
+            .offset_mismatches(0)
+            .map(|entry| entry.address)
+            .collect();
+        assert_eq!(mismatched, vec![fast]);
+        assert_eq!(report.unchecked().count(), 0);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].address, silent);
+    }
+
+    #[tokio::test]
+    async fn time_master_synchronizes_recipients_and_reports_drifted_clocks() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl);
+        let broadcast = DataLinkAddress::Ip(([192, 168, 2, 255], 47808).into());
+        let checked = DataLinkAddress::Ip(([192, 168, 1, 37], 47808).into());
+
+        // UTC device, 30 s slow.
+        let local = SystemTime::now() - Duration::from_secs(30);
+        state
+            .recv
+            .lock()
+            .await
+            .extend(clock_acks(checked, 1, 0, local));
+
+        let master = client
+            .start_time_master(
+                TimeMasterConfig::new(vec![broadcast])
+                    .without_alignment()
+                    .with_drift_check(vec![checked], Duration::from_secs(5)),
+            )
+            .unwrap();
+        let mut updates = master.subscribe();
+        let status = tokio::time::timeout(
+            Duration::from_secs(2),
+            updates.wait_for(|status| status.rounds == 1),
+        )
+        .await
+        .unwrap()
+        .unwrap()
+        .clone();
+
+        assert!(status.failed.is_empty());
+        assert!(status.unreadable.is_empty());
+        assert_eq!(status.drifted.len(), 1);
+        assert_eq!(status.drifted[0].address, checked);
+        assert!((-32_000..=-28_000).contains(&status.drifted[0].drift_millis));
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent[0].0, broadcast);
+        let mut r = Reader::new(&sent[0].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = UnconfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_UTC_TIME_SYNCHRONIZATION);
+        master.stop();
+    }
+
+    #[tokio::test]
+    async fn send_raw_confirmed_returns_the_ack_payload() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 35], 47808).into());
+
+        let mut apdu = [0u8; 32];
+        let mut w = Writer::new(&mut apdu);
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id: 1,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: 0x40,
+        }
+        .encode(&mut w)
+        .unwrap();
+        w.write_all(&[0xAA, 0xBB]).unwrap();
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(w.as_written()), addr));
+
+        let ack = client
+            .send_raw_confirmed(addr, 0x40, &[0x09, 0x01])
+            .await
+            .unwrap();
+        assert_eq!(ack, vec![0xAA, 0xBB]);
+
+        client
+            .send_raw_unconfirmed(addr, 0x20, &[0x01])
+            .await
+            .unwrap();
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 2);
+        let mut r = Reader::new(&sent[0].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!((hdr.invoke_id, hdr.service_choice), (1, 0x40));
+        assert_eq!(r.read_exact(r.remaining()).unwrap(), &[0x09, 0x01]);
+        let mut r = Reader::new(&sent[1].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = UnconfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, 0x20);
+        assert_eq!(r.read_exact(r.remaining()).unwrap(), &[0x01]);
+    }
+
+    #[tokio::test]
+    async fn request_options_set_priority_and_retries() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 36], 47808).into());
+        let options = RequestOptions::default()
+            .with_timeout(Duration::from_millis(30))
+            .with_retries(1)
+            .with_priority(NetworkPriority::Urgent);
+
+        let err = client
+            .with_options(options)
+            .read_property(
+                addr,
+                ObjectId::new(ObjectType::AnalogInput, 1),
+                PropertyId::PresentValue,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err.root(), ClientError::Timeout), "{err:?}");
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 3, "one send, one retry and the abort");
+        let npdu = Npdu::decode(&mut Reader::new(&sent[0].1)).unwrap();
+        assert_eq!(npdu.priority(), NetworkPriority::Urgent);
+        let mut r = Reader::new(&sent[2].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let abort = AbortPdu::decode(&mut r).unwrap();
+        assert_eq!(abort.reason, abort_reason::TSM_TIMEOUT);
+        drop(sent);
+        // The original client keeps its defaults.
+        assert_eq!(client.request_retries, 0);
+        assert_eq!(client.network_priority, NetworkPriority::Normal);
+    }
+
+    #[tokio::test]
+    async fn requests_announce_the_configured_max_segments() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl)
+            .with_response_timeout(Duration::from_millis(20))
+            .with_max_segments_accepted(16);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 38], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogInput, 1);
+
+        let _ = client
+            .read_property(addr, object_id, PropertyId::PresentValue)
+            .await;
+        let _ = client
+            .clone()
+            .with_max_segments_accepted(0)
+            .read_property(addr, object_id, PropertyId::PresentValue)
+            .await;
+
+        let sent = state.sent.lock().await;
+        let max_segments: Vec<u8> = sent
+            .iter()
+            .filter_map(|(_, frame)| {
+                let mut r = Reader::new(frame);
+                Npdu::decode(&mut r).ok()?;
+                ConfirmedRequestHeader::decode(&mut r)
+                    .ok()
+                    .map(|h| h.max_segments)
+            })
+            .collect();
+        assert_eq!(max_segments, vec![4, 0]);
+        drop(sent);
+        assert_eq!(
+            ConfirmedRequestHeader::max_segments_code(super::DEFAULT_MAX_SEGMENTS_ACCEPTED),
+            7
+        );
+    }
+
+    #[tokio::test]
+    async fn segmentation_settings_apply_per_device_and_per_request() {
+        let (dl, state) = MockDataLink::new();
+        let client =
+            BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_millis(20));
+        let broken = DataLinkAddress::Ip(([192, 168, 1, 39], 47808).into());
+        let healthy = DataLinkAddress::Ip(([192, 168, 1, 40], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogInput, 1);
+        client.set_device_segmentation(broken, SegmentationOptions::unsegmented());
+
+        let _ = client
+            .read_property(broken, object_id, PropertyId::PresentValue)
+            .await;
+        let _ = client
+            .read_property(healthy, object_id, PropertyId::PresentValue)
+            .await;
+        let _ = client
+            .with_options(RequestOptions::default().with_segmentation(
+                SegmentationOptions::default().with_segmented_response_accepted(true),
+            ))
+            .read_property(broken, object_id, PropertyId::PresentValue)
+            .await;
+        client.clear_device_segmentation(broken);
+        let _ = client
+            .read_property(broken, object_id, PropertyId::PresentValue)
+            .await;
+
+        let sent = state.sent.lock().await;
+        let accepted: Vec<bool> = sent
+            .iter()
+            .filter_map(|(_, frame)| {
+                let mut r = Reader::new(frame);
+                Npdu::decode(&mut r).ok()?;
+                ConfirmedRequestHeader::decode(&mut r)
+                    .ok()
+                    .map(|h| h.segmented_response_accepted)
+            })
+            .collect();
+        assert_eq!(accepted, vec![false, true, true, true]);
+    }
+
+    #[tokio::test]
+    async fn dropping_a_request_aborts_it() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(5));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 37], 47808).into());
+        let ai = |instance| ObjectId::new(ObjectType::AnalogInput, instance);
+
+        // The request still waiting for its answer sends the Abort of the cancelled one;
+        // nothing else is sent.
+        let waiting = client.read_property(addr, ai(1), PropertyId::PresentValue);
+        let cancel = async {
+            let cancelled = tokio::time::timeout(
+                Duration::from_millis(20),
+                client.read_property(addr, ai(2), PropertyId::PresentValue),
+            )
+            .await;
+            assert!(cancelled.is_err());
+            while state.sent.lock().await.len() < 3 {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+            state
+                .recv
+                .lock()
+                .await
+                .push_back((with_npdu(&read_property_real_ack_apdu(1, ai(1), 1.0)), addr));
+        };
+        let (waiting, ()) = tokio::time::timeout(Duration::from_secs(2), async {
+            tokio::join!(waiting, cancel)
+        })
+        .await
+        .expect("the Abort was not sent");
+        assert_eq!(waiting.unwrap(), ClientDataValue::Real(1.0));
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 3);
+        let mut r = Reader::new(&sent[2].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let abort = AbortPdu::decode(&mut r).unwrap();
+        assert_eq!(
+            abort,
+            AbortPdu {
+                server: false,
+                invoke_id: 2,
+                reason: abort_reason::OTHER,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn dropping_a_request_not_yet_sent_aborts_nothing() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(5));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 37], 47808).into());
+        let ai = |instance| ObjectId::new(ObjectType::AnalogInput, instance);
+
+        let io = client.request_io_lock.lock().await;
+        let cancelled = tokio::time::timeout(
+            Duration::from_millis(20),
+            client.read_property(addr, ai(1), PropertyId::PresentValue),
+        )
+        .await;
+        assert!(cancelled.is_err());
+        drop(io);
+
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(&read_property_real_ack_apdu(2, ai(2), 2.0)), addr));
+        let value = client
+            .read_property(addr, ai(2), PropertyId::PresentValue)
+            .await
+            .unwrap();
+        assert_eq!(value, ClientDataValue::Real(2.0));
+        assert_eq!(state.sent.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_are_outstanding_together() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(5));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 37], 47808).into());
+        let ai = |instance| ObjectId::new(ObjectType::AnalogInput, instance);
+
+        // The device answers only once both requests are out, the second one first.
+        let device = async {
+            while state.sent.lock().await.len() < 2 {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+            let mut recv = state.recv.lock().await;
+            recv.push_back((with_npdu(&read_property_real_ack_apdu(2, ai(2), 2.0)), addr));
+            recv.push_back((with_npdu(&read_property_real_ack_apdu(1, ai(1), 1.0)), addr));
+        };
+        let (first, second, ()) = tokio::join!(
+            client.read_property(addr, ai(1), PropertyId::PresentValue),
+            client.read_property(addr, ai(2), PropertyId::PresentValue),
+            device,
+        );
+        assert_eq!(first.unwrap(), ClientDataValue::Real(1.0));
+        assert_eq!(second.unwrap(), ClientDataValue::Real(2.0));
+    }
+
+    #[tokio::test]
+    async fn get_alarm_summary_decodes_complex_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 38], 47808).into());
+
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(&get_alarm_summary_ack_apdu(1)), addr));
+
+        let summaries = client.get_alarm_summary(addr).await.unwrap();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(
+            summaries[0],
+            AlarmSummaryItem {
+                object_id: ObjectId::new(ObjectType::AnalogInput, 1),
+                alarm_state_raw: 1,
+                alarm_state: Some(EventState::Fault),
+                acknowledged_transitions: crate::ClientBitString {
+                    unused_bits: 5,
+                    data: vec![0b1110_0000],
+                },
+            }
+        );
+        assert_eq!(
+            summaries[1],
+            AlarmSummaryItem {
+                object_id: ObjectId::new(ObjectType::BinaryInput, 2),
+                alarm_state_raw: 0,
+                alarm_state: Some(EventState::Normal),
+                acknowledged_transitions: crate::ClientBitString {
+                    unused_bits: 5,
+                    data: vec![0b1100_0000],
+                },
+            }
+        );
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        let mut r = Reader::new(&sent[0].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_GET_ALARM_SUMMARY);
+    }
+
+    #[tokio::test]
+    async fn get_enrollment_summary_decodes_complex_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 37], 47808).into());
+
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(&get_enrollment_summary_ack_apdu(1)), addr));
+
+        let summaries = client.get_enrollment_summary(addr).await.unwrap();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(
+            summaries[0],
+            EnrollmentSummaryItem {
+                object_id: ObjectId::new(ObjectType::AnalogInput, 7),
+                event_type: 1,
+                event_state_raw: 2,
+                event_state: Some(EventState::Offnormal),
+                priority: 200,
+                notification_class: 10,
+            }
+        );
+        assert_eq!(
+            summaries[1],
+            EnrollmentSummaryItem {
+                object_id: ObjectId::new(ObjectType::BinaryInput, 8),
+                event_type: 0,
+                event_state_raw: 0,
+                event_state: Some(EventState::Normal),
+                priority: 20,
+                notification_class: 11,
+            }
+        );
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        let mut r = Reader::new(&sent[0].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_GET_ENROLLMENT_SUMMARY);
+    }
+
+    #[tokio::test]
+    async fn get_event_information_decodes_complex_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 57], 47808).into());
+
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(&get_event_information_ack_apdu(1)), addr));
+
+        let result = client.get_event_information(addr, None).await.unwrap();
+        assert!(!result.more_events);
+        assert_eq!(result.summaries.len(), 1);
+        assert_eq!(
+            result.summaries[0],
+            EventInformationItem {
+                object_id: ObjectId::new(ObjectType::AnalogInput, 1),
+                event_state_raw: 2,
+                event_state: Some(EventState::Offnormal),
+                acknowledged_transitions: crate::ClientBitString {
+                    unused_bits: 5,
+                    data: vec![0b1110_0000],
+                },
+                notify_type: 0,
+                event_enable: crate::ClientBitString {
+                    unused_bits: 5,
+                    data: vec![0b1100_0000],
+                },
+                event_priorities: [1, 2, 3],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn acknowledge_alarm_handles_simple_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 39], 47808).into());
+
+        let mut apdu = [0u8; 32];
+        let mut w = Writer::new(&mut apdu);
+        SimpleAck {
+            invoke_id: 1,
+            service_choice: SERVICE_ACKNOWLEDGE_ALARM,
+        }
+        .encode(&mut w)
+        .unwrap();
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(w.as_written()), addr));
+
+        client
+            .acknowledge_alarm(
+                addr,
+                AcknowledgeAlarmRequest {
+                    acknowledging_process_id: 10,
+                    event_object_id: ObjectId::new(ObjectType::AnalogInput, 1),
+                    event_state_acknowledged: EventState::Offnormal,
+                    event_time_stamp: TimeStamp::SequenceNumber(42),
+                    acknowledgment_source: "operator",
+                    time_of_acknowledgment: TimeStamp::DateTime {
+                        date: Date {
+                            year_since_1900: 126,
+                            month: 2,
+                            day: 7,
+                            weekday: 6,
+                        },
+                        time: Time {
+                            hour: 10,
+                            minute: 11,
+                            second: 12,
+                            hundredths: 13,
+                        },
+                    },
+                    invoke_id: 0,
+                },
+            )
+            .await
+            .unwrap();
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        let mut r = Reader::new(&sent[0].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_ACKNOWLEDGE_ALARM);
+    }
+
+    #[tokio::test]
+    async fn create_object_by_type_decodes_complex_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 50], 47808).into());
+        let created = ObjectId::new(ObjectType::AnalogValue, 42);
+
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(&create_object_ack_apdu(1, created)), addr));
+
+        let result = client
+            .create_object_by_type(addr, ObjectType::AnalogValue)
+            .await
+            .unwrap();
+        assert_eq!(result, created);
+
+        let sent = state.sent.lock().await;
+        let mut r = Reader::new(&sent[0].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_CREATE_OBJECT);
+    }
+
+    #[tokio::test]
+    async fn delete_object_handles_simple_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 51], 47808).into());
+
+        let mut apdu = [0u8; 32];
+        let mut w = Writer::new(&mut apdu);
+        SimpleAck {
+            invoke_id: 1,
+            service_choice: SERVICE_DELETE_OBJECT,
+        }
+        .encode(&mut w)
+        .unwrap();
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(w.as_written()), addr));
 
-    #[derive(Debug, Default)]
-    struct MockState {
-        sent: Mutex<Vec<(DataLinkAddress, Vec<u8>)>>,
-        recv: Mutex<VecDeque<(Vec<u8>, DataLinkAddress)>>,
+        client
+            .delete_object(addr, ObjectId::new(ObjectType::AnalogValue, 42))
+            .await
+            .unwrap();
     }
 
-    #[derive(Debug, Clone)]
-    struct MockDataLink {
-        state: Arc<MockState>,
-    }
+    #[tokio::test]
+    async fn add_list_element_handles_simple_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 52], 47808).into());
 
-    impl MockDataLink {
-        fn new() -> (Self, Arc<MockState>) {
-            let state = Arc::new(MockState::default());
-            (
-                Self {
-                    state: state.clone(),
+        let mut apdu = [0u8; 32];
+        let mut w = Writer::new(&mut apdu);
+        SimpleAck {
+            invoke_id: 1,
+            service_choice: SERVICE_ADD_LIST_ELEMENT,
+        }
+        .encode(&mut w)
+        .unwrap();
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(w.as_written()), addr));
+
+        let values = [DataValue::Unsigned(1), DataValue::Unsigned(2)];
+        client
+            .add_list_element(
+                addr,
+                AddListElementRequest {
+                    object_id: ObjectId::new(ObjectType::AnalogValue, 1),
+                    property_id: PropertyId::Proprietary(512),
+                    array_index: None,
+                    elements: &values,
+                    invoke_id: 0,
                 },
-                state,
             )
-        }
+            .await
+            .unwrap();
     }
 
-    impl DataLink for MockDataLink {
-        async fn send(
-            &self,
-            address: DataLinkAddress,
-            payload: &[u8],
-        ) -> Result<(), DataLinkError> {
-            self.state
-                .sent
-                .lock()
-                .await
-                .push((address, payload.to_vec()));
-            Ok(())
+    #[tokio::test]
+    async fn remove_list_element_handles_simple_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 53], 47808).into());
+
+        let mut apdu = [0u8; 32];
+        let mut w = Writer::new(&mut apdu);
+        SimpleAck {
+            invoke_id: 1,
+            service_choice: SERVICE_REMOVE_LIST_ELEMENT,
         }
+        .encode(&mut w)
+        .unwrap();
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(w.as_written()), addr));
 
-        async fn recv(&self, buf: &mut [u8]) -> Result<(usize, DataLinkAddress), DataLinkError> {
-            let Some((payload, addr)) = self.state.recv.lock().await.pop_front() else {
-                return Err(DataLinkError::InvalidFrame);
-            };
-            if payload.len() > buf.len() {
-                return Err(DataLinkError::FrameTooLarge);
+        let values = [DataValue::Unsigned(1)];
+        client
+            .remove_list_element(
+                addr,
+                RemoveListElementRequest {
+                    object_id: ObjectId::new(ObjectType::AnalogValue, 1),
+                    property_id: PropertyId::Proprietary(513),
+                    array_index: None,
+                    elements: &values,
+                    invoke_id: 0,
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn atomic_read_file_stream_decodes_complex_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 40], 47808).into());
+        let file_object = ObjectId::new(ObjectType::File, 2);
+
+        state.recv.lock().await.push_back((
+            with_npdu(&atomic_read_file_stream_ack_apdu(
+                1,
+                true,
+                &[0xAA, 0xBB, 0xCC],
+            )),
+            addr,
+        ));
+
+        let result = client
+            .atomic_read_file_stream(addr, file_object, 0, 3)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            AtomicReadFileResult::Stream {
+                end_of_file: true,
+                file_start_position: 0,
+                file_data: vec![0xAA, 0xBB, 0xCC],
             }
-            buf[..payload.len()].copy_from_slice(&payload);
-            Ok((payload.len(), addr))
-        }
+        );
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        let mut r = Reader::new(&sent[0].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_ATOMIC_READ_FILE);
     }
 
-    fn with_npdu(apdu: &[u8]) -> Vec<u8> {
-        let mut out = [0u8; 512];
-        let mut w = Writer::new(&mut out);
-        Npdu::new(0).encode(&mut w).unwrap();
-        w.write_all(apdu).unwrap();
-        w.as_written().to_vec()
+    #[tokio::test]
+    async fn atomic_read_file_record_decodes_complex_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 41], 47808).into());
+        let file_object = ObjectId::new(ObjectType::File, 5);
+
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(&atomic_read_file_record_ack_apdu(1)), addr));
+
+        let result = client
+            .atomic_read_file_record(addr, file_object, 7, 2)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            AtomicReadFileResult::Record {
+                end_of_file: false,
+                file_start_record: 7,
+                returned_record_count: 2,
+                file_record_data: vec![vec![0x01, 0x02], vec![0x03, 0x04, 0x05]],
+            }
+        );
     }
 
-    fn read_range_ack_apdu(invoke_id: u8, object_id: ObjectId) -> Vec<u8> {
-        let mut apdu_buf = [0u8; 256];
-        let mut w = Writer::new(&mut apdu_buf);
-        ComplexAckHeader {
-            segmented: false,
-            more_follows: false,
-            invoke_id,
-            sequence_number: None,
-            proposed_window_size: None,
-            service_choice: SERVICE_READ_RANGE,
-        }
-        .encode(&mut w)
-        .unwrap();
-        encode_ctx_object_id(&mut w, 0, object_id.raw()).unwrap();
-        encode_ctx_unsigned(&mut w, 1, PropertyId::PresentValue.to_u32()).unwrap();
-        Tag::Context { tag_num: 3, len: 2 }.encode(&mut w).unwrap();
-        w.write_u8(5).unwrap();
-        w.write_u8(0b1110_0000).unwrap();
-        encode_ctx_unsigned(&mut w, 4, 2).unwrap();
-        Tag::Opening { tag_num: 5 }.encode(&mut w).unwrap();
-        encode_app_real(&mut w, 42.0).unwrap();
-        encode_app_real(&mut w, 43.0).unwrap();
-        Tag::Closing { tag_num: 5 }.encode(&mut w).unwrap();
-        w.as_written().to_vec()
+    #[tokio::test]
+    async fn atomic_write_file_stream_decodes_complex_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 42], 47808).into());
+        let file_object = ObjectId::new(ObjectType::File, 3);
+
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(&atomic_write_file_stream_ack_apdu(1, 128)), addr));
+
+        let result = client
+            .atomic_write_file_stream(addr, file_object, 128, &[1, 2, 3, 4])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            AtomicWriteFileResult::Stream {
+                file_start_position: 128
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn atomic_write_file_record_decodes_complex_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 43], 47808).into());
+        let file_object = ObjectId::new(ObjectType::File, 9);
+        let records: [&[u8]; 2] = [&[0x10, 0x11], &[0x12]];
+
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(&atomic_write_file_record_ack_apdu(1, 7)), addr));
+
+        let result = client
+            .atomic_write_file_record(addr, file_object, 7, &records)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            AtomicWriteFileResult::Record {
+                file_start_record: 7
+            }
+        );
     }
 
-    fn atomic_read_file_stream_ack_apdu(invoke_id: u8, eof: bool, data: &[u8]) -> Vec<u8> {
+    #[tokio::test]
+    async fn read_properties_decodes_complex_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 5], 47808).into());
+        let object_id = ObjectId::new(ObjectType::Device, 1);
+
         let mut apdu_buf = [0u8; 256];
         let mut w = Writer::new(&mut apdu_buf);
         ComplexAckHeader {
             segmented: false,
             more_follows: false,
-            invoke_id,
+            invoke_id: 1,
             sequence_number: None,
             proposed_window_size: None,
-            service_choice: SERVICE_ATOMIC_READ_FILE,
-        }
-        .encode(&mut w)
-        .unwrap();
-        Tag::Application {
-            tag: AppTag::Boolean,
-            len: if eof { 1 } else { 0 },
-        }
-        .encode(&mut w)
-        .unwrap();
-        Tag::Opening { tag_num: 0 }.encode(&mut w).unwrap();
-        Tag::Application {
-            tag: AppTag::SignedInt,
-            len: 1,
-        }
-        .encode(&mut w)
-        .unwrap();
-        w.write_u8(0).unwrap();
-        Tag::Application {
-            tag: AppTag::OctetString,
-            len: data.len() as u32,
+            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
         }
         .encode(&mut w)
         .unwrap();
-        w.write_all(data).unwrap();
-        Tag::Closing { tag_num: 0 }.encode(&mut w).unwrap();
-        w.as_written().to_vec()
+        encode_ctx_unsigned(&mut w, 0, object_id.raw()).unwrap();
+        rustbac_core::encoding::tag::Tag::Opening { tag_num: 1 }
+            .encode(&mut w)
+            .unwrap();
+        encode_ctx_unsigned(&mut w, 2, PropertyId::PresentValue.to_u32()).unwrap();
+        rustbac_core::encoding::tag::Tag::Opening { tag_num: 4 }
+            .encode(&mut w)
+            .unwrap();
+        encode_app_real(&mut w, 55.5).unwrap();
+        rustbac_core::encoding::tag::Tag::Closing { tag_num: 4 }
+            .encode(&mut w)
+            .unwrap();
+        rustbac_core::encoding::tag::Tag::Closing { tag_num: 1 }
+            .encode(&mut w)
+            .unwrap();
+
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(w.as_written()), addr));
+
+        let values = client
+            .read_property_multiple(addr, object_id, &[PropertyId::PresentValue])
+            .await
+            .unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].0, PropertyId::PresentValue);
+        assert!(matches!(values[0].1, ClientDataValue::Real(v) if (v - 55.5).abs() < f32::EPSILON));
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        let mut r = Reader::new(&sent[0].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_READ_PROPERTY_MULTIPLE);
     }
 
-    fn atomic_read_file_record_ack_apdu(invoke_id: u8) -> Vec<u8> {
-        let mut apdu_buf = [0u8; 256];
-        let mut w = Writer::new(&mut apdu_buf);
+    #[tokio::test]
+    async fn read_property_multiple_reassembles_segmented_complex_ack() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 12], 47808).into());
+        let object_id = ObjectId::new(ObjectType::Device, 1);
+
+        let mut payload_buf = [0u8; 256];
+        let mut pw = Writer::new(&mut payload_buf);
+        encode_ctx_unsigned(&mut pw, 0, object_id.raw()).unwrap();
+        rustbac_core::encoding::tag::Tag::Opening { tag_num: 1 }
+            .encode(&mut pw)
+            .unwrap();
+        encode_ctx_unsigned(&mut pw, 2, PropertyId::PresentValue.to_u32()).unwrap();
+        rustbac_core::encoding::tag::Tag::Opening { tag_num: 4 }
+            .encode(&mut pw)
+            .unwrap();
+        encode_app_real(&mut pw, 66.0).unwrap();
+        rustbac_core::encoding::tag::Tag::Closing { tag_num: 4 }
+            .encode(&mut pw)
+            .unwrap();
+        rustbac_core::encoding::tag::Tag::Closing { tag_num: 1 }
+            .encode(&mut pw)
+            .unwrap();
+        let payload = pw.as_written();
+        let split = payload.len() / 2;
+
+        let mut apdu1 = [0u8; 256];
+        let mut w1 = Writer::new(&mut apdu1);
         ComplexAckHeader {
-            segmented: false,
-            more_follows: false,
-            invoke_id,
-            sequence_number: None,
-            proposed_window_size: None,
-            service_choice: SERVICE_ATOMIC_READ_FILE,
-        }
-        .encode(&mut w)
-        .unwrap();
-        Tag::Application {
-            tag: AppTag::Boolean,
-            len: 0,
-        }
-        .encode(&mut w)
-        .unwrap();
-        Tag::Opening { tag_num: 1 }.encode(&mut w).unwrap();
-        Tag::Application {
-            tag: AppTag::SignedInt,
-            len: 1,
+            segmented: true,
+            more_follows: true,
+            invoke_id: 1,
+            sequence_number: Some(0),
+            proposed_window_size: Some(1),
+            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
         }
-        .encode(&mut w)
+        .encode(&mut w1)
         .unwrap();
-        w.write_u8(7).unwrap();
-        Tag::Application {
-            tag: AppTag::UnsignedInt,
-            len: 1,
+        w1.write_all(&payload[..split]).unwrap();
+
+        let mut apdu2 = [0u8; 256];
+        let mut w2 = Writer::new(&mut apdu2);
+        ComplexAckHeader {
+            segmented: true,
+            more_follows: false,
+            invoke_id: 1,
+            sequence_number: Some(1),
+            proposed_window_size: Some(1),
+            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
         }
-        .encode(&mut w)
+        .encode(&mut w2)
         .unwrap();
-        w.write_u8(2).unwrap();
-        Tag::Application {
-            tag: AppTag::OctetString,
-            len: 2,
+        w2.write_all(&payload[split..]).unwrap();
+
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(w1.as_written()), addr));
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(w2.as_written()), addr));
+
+        let values = client
+            .read_property_multiple(addr, object_id, &[PropertyId::PresentValue])
+            .await
+            .unwrap();
+        assert_eq!(values.len(), 1);
+        assert!(matches!(values[0].1, ClientDataValue::Real(v) if (v - 66.0).abs() < f32::EPSILON));
+
+        let sent = state.sent.lock().await;
+        assert!(sent.len() >= 3);
+
+        let mut saw_segment_ack = 0usize;
+        for (_, frame) in sent.iter().skip(1) {
+            let mut r = Reader::new(frame);
+            let _npdu = Npdu::decode(&mut r).unwrap();
+            let apdu = r.read_exact(r.remaining()).unwrap();
+            if (apdu[0] >> 4) == ApduType::SegmentAck as u8 {
+                let mut sr = Reader::new(apdu);
+                let sack = SegmentAck::decode(&mut sr).unwrap();
+                assert_eq!(sack.invoke_id, 1);
+                saw_segment_ack += 1;
+            }
         }
-        .encode(&mut w)
-        .unwrap();
-        w.write_all(&[0x01, 0x02]).unwrap();
-        Tag::Application {
-            tag: AppTag::OctetString,
-            len: 3,
+        assert!(saw_segment_ack >= 1);
+        assert_eq!(client.device_stats(addr).unwrap().segments_received, 2);
+    }
+
+    #[tokio::test]
+    async fn device_stats_count_outcomes_per_address() {
+        let (dl, state) = MockDataLink::new();
+        let client =
+            BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_millis(50));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 52], 47808).into());
+        let other = DataLinkAddress::Ip(([192, 168, 1, 53], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogValue, 42);
+
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(&simple_ack_apdu(1, SERVICE_DELETE_OBJECT)), addr));
+        client.delete_object(addr, object_id).await.unwrap();
+        assert!(client.delete_object(addr, object_id).await.is_err());
+
+        let stats = client.device_stats(addr).unwrap();
+        assert_eq!(stats.requests, 2);
+        assert_eq!(stats.acks, 1);
+        assert_eq!(stats.timeouts, 1);
+        assert!(stats.average_latency().is_some());
+        assert!(client.device_stats(other).is_none());
+        assert_eq!(client.all_device_stats().len(), 1);
+
+        client.reset_device_stats(addr);
+        assert!(client.device_stats(addr).is_none());
+    }
+
+    #[tokio::test]
+    async fn memory_limits_refuse_requests_beyond_the_outstanding_cap() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl)
+            .with_memory_limits(MemoryLimits::embedded().with_max_outstanding_transactions(1));
+        assert_eq!(client.max_segments_accepted, 44);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 55], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogValue, 42);
+
+        client.invoke_ids.lock().unwrap().begin(addr, 200);
+        let err = client.delete_object(addr, object_id).await.unwrap_err();
+        assert!(matches!(err.root(), ClientError::Busy { limit: 1 }));
+        assert!(err.is_retryable());
+        assert!(state.sent.lock().await.is_empty());
+
+        // The refused request took no invoke id.
+        client
+            .invoke_ids
+            .lock()
+            .unwrap()
+            .finish(addr, 200, Instant::now());
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(&simple_ack_apdu(1, SERVICE_DELETE_OBJECT)), addr));
+        client.delete_object(addr, object_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn memory_limits_abort_responses_beyond_the_reassembly_cap() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl)
+            .with_memory_limits(MemoryLimits::default().with_max_reassembly_bytes(0));
+        assert_eq!(client.memory_limits().max_reassembly_bytes, 1476);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 56], 47808).into());
+
+        // Four 480-octet segments, the fourth over the cap.
+        for sequence_number in 0..4 {
+            let mut apdu = [0u8; 512];
+            let mut w = Writer::new(&mut apdu);
+            ComplexAckHeader {
+                segmented: true,
+                more_follows: true,
+                invoke_id: 1,
+                sequence_number: Some(sequence_number),
+                proposed_window_size: Some(1),
+                service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
+            }
+            .encode(&mut w)
+            .unwrap();
+            w.write_all(&[0u8; 480]).unwrap();
+            state
+                .recv
+                .lock()
+                .await
+                .push_back((with_npdu(w.as_written()), addr));
         }
-        .encode(&mut w)
-        .unwrap();
-        w.write_all(&[0x03, 0x04, 0x05]).unwrap();
-        Tag::Closing { tag_num: 1 }.encode(&mut w).unwrap();
-        w.as_written().to_vec()
+
+        let err = client
+            .read_property_multiple(
+                addr,
+                ObjectId::new(ObjectType::Device, 1),
+                &[PropertyId::PresentValue],
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err.root(), ClientError::ResponseTooLarge { limit: 1476 }),
+            "{err:?}"
+        );
+
+        let sent = state.sent.lock().await;
+        let (_, last) = sent.last().unwrap();
+        let mut r = Reader::new(last);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        assert_eq!(
+            AbortPdu::decode(&mut r).unwrap().reason,
+            abort_reason::BUFFER_OVERFLOW
+        );
+    }
+
+    #[tokio::test]
+    async fn memory_limits_clamp_fields_set_directly() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_memory_limits(MemoryLimits {
+            max_reassembly_bytes: 0,
+            max_outstanding_transactions: 0,
+            max_queued_frames: 0,
+        });
+        assert_eq!(
+            client.memory_limits(),
+            MemoryLimits {
+                max_reassembly_bytes: 1476,
+                max_outstanding_transactions: 1,
+                max_queued_frames: 1,
+            }
+        );
+
+        // A write burst still sends its writes one at a time.
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 57], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogValue, 1);
+        {
+            let mut recv = state.recv.lock().await;
+            for invoke_id in 1..=2 {
+                recv.push_back((
+                    with_npdu(&simple_ack_apdu(invoke_id, SERVICE_WRITE_PROPERTY)),
+                    addr,
+                ));
+            }
+        }
+        let writes = [
+            (
+                object_id,
+                PropertyId::PresentValue,
+                ClientDataValue::Real(1.0),
+                None,
+            ),
+            (
+                object_id,
+                PropertyId::PresentValue,
+                ClientDataValue::Real(2.0),
+                None,
+            ),
+        ];
+        let report = client.write_burst(addr, &writes).await.unwrap();
+        assert!(report.failed.is_empty(), "{:?}", report.failed);
+        assert_eq!(state.sent.lock().await.len(), 2);
     }
 
-    fn atomic_write_file_stream_ack_apdu(invoke_id: u8, start_position: i32) -> Vec<u8> {
-        let mut apdu_buf = [0u8; 64];
-        let mut w = Writer::new(&mut apdu_buf);
-        ComplexAckHeader {
-            segmented: false,
-            more_follows: false,
-            invoke_id,
-            sequence_number: None,
-            proposed_window_size: None,
-            service_choice: SERVICE_ATOMIC_WRITE_FILE,
-        }
-        .encode(&mut w)
-        .unwrap();
-        Tag::Context { tag_num: 0, len: 2 }.encode(&mut w).unwrap();
-        w.write_all(&(start_position as i16).to_be_bytes()).unwrap();
-        w.as_written().to_vec()
+    #[test]
+    fn memory_limits_resize_the_router_shared_with_clones() {
+        let (dl, _state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl);
+        let earlier = client.clone();
+        let client = client.with_memory_limits(MemoryLimits::default().with_max_queued_frames(3));
+        assert!(std::sync::Arc::ptr_eq(
+            client.inbound_router(),
+            earlier.inbound_router()
+        ));
+        assert_eq!(earlier.inbound_router().capacity(), 3);
     }
 
-    fn atomic_write_file_record_ack_apdu(invoke_id: u8, start_record: i32) -> Vec<u8> {
-        let mut apdu_buf = [0u8; 64];
-        let mut w = Writer::new(&mut apdu_buf);
-        ComplexAckHeader {
-            segmented: false,
-            more_follows: false,
-            invoke_id,
-            sequence_number: None,
-            proposed_window_size: None,
-            service_choice: SERVICE_ATOMIC_WRITE_FILE,
+    #[tokio::test]
+    async fn postponed_reply_is_awaited_and_counted() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl)
+            .with_response_timeout(Duration::from_millis(50))
+            .with_options(RequestOptions::default().with_retries(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 54], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogValue, 42);
+
+        {
+            let mut recv = state.recv.lock().await;
+            // Server Segment-ACK: still working. Then application-exceeded-reply-time,
+            // which asks for the request again, and the answer to the resend.
+            recv.push_back((with_npdu(&[0x41, 0x01, 0x00, 0x01]), addr));
+            recv.push_back((with_npdu(&[0x71, 0x01, 0x08]), addr));
+            recv.push_back((with_npdu(&simple_ack_apdu(1, SERVICE_DELETE_OBJECT)), addr));
         }
-        .encode(&mut w)
-        .unwrap();
-        Tag::Context { tag_num: 1, len: 1 }.encode(&mut w).unwrap();
-        w.write_u8(start_record as u8).unwrap();
-        w.as_written().to_vec()
+        client.delete_object(addr, object_id).await.unwrap();
+
+        assert_eq!(state.sent.lock().await.len(), 2);
+        let stats = client.device_stats(addr).unwrap();
+        assert_eq!(stats.postponed_replies, 2);
+        assert_eq!(stats.acks, 1);
     }
 
-    fn create_object_ack_apdu(invoke_id: u8, object_id: ObjectId) -> Vec<u8> {
-        let mut apdu_buf = [0u8; 64];
-        let mut w = Writer::new(&mut apdu_buf);
+    #[tokio::test]
+    async fn segmented_response_timeout_aborts_the_transaction() {
+        let (dl, state) = MockDataLink::new();
+        let client =
+            BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_millis(50));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 13], 47808).into());
+
+        // The first of several segments arrives; the rest never do.
+        let mut apdu = [0u8; 32];
+        let mut w = Writer::new(&mut apdu);
         ComplexAckHeader {
-            segmented: false,
-            more_follows: false,
-            invoke_id,
-            sequence_number: None,
-            proposed_window_size: None,
-            service_choice: SERVICE_CREATE_OBJECT,
+            segmented: true,
+            more_follows: true,
+            invoke_id: 1,
+            sequence_number: Some(0),
+            proposed_window_size: Some(1),
+            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
         }
         .encode(&mut w)
         .unwrap();
-        encode_ctx_object_id(&mut w, 0, object_id.raw()).unwrap();
-        w.as_written().to_vec()
+        w.write_all(&[0x0C, 0x02, 0x00, 0x00, 0x01]).unwrap();
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(w.as_written()), addr));
+
+        let err = client
+            .read_property_multiple(
+                addr,
+                ObjectId::new(ObjectType::Device, 1),
+                &[PropertyId::PresentValue],
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err.root(), ClientError::Timeout), "{err:?}");
+
+        let sent = state.sent.lock().await;
+        let (_, last) = sent.last().unwrap();
+        let mut r = Reader::new(last);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        assert_eq!(
+            AbortPdu::decode(&mut r).unwrap(),
+            AbortPdu {
+                server: false,
+                invoke_id: 1,
+                reason: abort_reason::TSM_TIMEOUT,
+            }
+        );
     }
 
-    fn get_alarm_summary_ack_apdu(invoke_id: u8) -> Vec<u8> {
-        let mut apdu_buf = [0u8; 128];
-        let mut w = Writer::new(&mut apdu_buf);
+    #[tokio::test]
+    async fn read_property_multiple_tolerates_duplicate_segment() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 18], 47808).into());
+        let object_id = ObjectId::new(ObjectType::Device, 1);
+
+        let mut payload_buf = [0u8; 256];
+        let mut pw = Writer::new(&mut payload_buf);
+        encode_ctx_unsigned(&mut pw, 0, object_id.raw()).unwrap();
+        rustbac_core::encoding::tag::Tag::Opening { tag_num: 1 }
+            .encode(&mut pw)
+            .unwrap();
+        encode_ctx_unsigned(&mut pw, 2, PropertyId::PresentValue.to_u32()).unwrap();
+        rustbac_core::encoding::tag::Tag::Opening { tag_num: 4 }
+            .encode(&mut pw)
+            .unwrap();
+        encode_app_real(&mut pw, 66.0).unwrap();
+        rustbac_core::encoding::tag::Tag::Closing { tag_num: 4 }
+            .encode(&mut pw)
+            .unwrap();
+        rustbac_core::encoding::tag::Tag::Closing { tag_num: 1 }
+            .encode(&mut pw)
+            .unwrap();
+        let payload = pw.as_written();
+        let split = payload.len() / 2;
+
+        let mut apdu1 = [0u8; 256];
+        let mut w1 = Writer::new(&mut apdu1);
         ComplexAckHeader {
-            segmented: false,
-            more_follows: false,
-            invoke_id,
-            sequence_number: None,
-            proposed_window_size: None,
-            service_choice: SERVICE_GET_ALARM_SUMMARY,
+            segmented: true,
+            more_follows: true,
+            invoke_id: 1,
+            sequence_number: Some(0),
+            proposed_window_size: Some(1),
+            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
         }
-        .encode(&mut w)
+        .encode(&mut w1)
         .unwrap();
-        encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::AnalogInput, 1).raw()).unwrap();
-        encode_ctx_unsigned(&mut w, 1, 1).unwrap();
-        Tag::Context { tag_num: 2, len: 2 }.encode(&mut w).unwrap();
-        w.write_u8(5).unwrap();
-        w.write_u8(0b1110_0000).unwrap();
-
-        encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::BinaryInput, 2).raw()).unwrap();
-        encode_ctx_unsigned(&mut w, 1, 0).unwrap();
-        Tag::Context { tag_num: 2, len: 2 }.encode(&mut w).unwrap();
-        w.write_u8(5).unwrap();
-        w.write_u8(0b1100_0000).unwrap();
-        w.as_written().to_vec()
-    }
+        w1.write_all(&payload[..split]).unwrap();
 
-    fn get_enrollment_summary_ack_apdu(invoke_id: u8) -> Vec<u8> {
-        let mut apdu_buf = [0u8; 160];
-        let mut w = Writer::new(&mut apdu_buf);
+        let mut dup = [0u8; 256];
+        let mut wd = Writer::new(&mut dup);
         ComplexAckHeader {
-            segmented: false,
-            more_follows: false,
-            invoke_id,
-            sequence_number: None,
-            proposed_window_size: None,
-            service_choice: SERVICE_GET_ENROLLMENT_SUMMARY,
+            segmented: true,
+            more_follows: true,
+            invoke_id: 1,
+            sequence_number: Some(0),
+            proposed_window_size: Some(1),
+            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
         }
-        .encode(&mut w)
+        .encode(&mut wd)
         .unwrap();
-        encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::AnalogInput, 7).raw()).unwrap();
-        encode_ctx_unsigned(&mut w, 1, 1).unwrap();
-        encode_ctx_unsigned(&mut w, 2, 2).unwrap();
-        encode_ctx_unsigned(&mut w, 3, 200).unwrap();
-        encode_ctx_unsigned(&mut w, 4, 10).unwrap();
-
-        encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::BinaryInput, 8).raw()).unwrap();
-        encode_ctx_unsigned(&mut w, 1, 0).unwrap();
-        encode_ctx_unsigned(&mut w, 2, 0).unwrap();
-        encode_ctx_unsigned(&mut w, 3, 20).unwrap();
-        encode_ctx_unsigned(&mut w, 4, 11).unwrap();
-        w.as_written().to_vec()
-    }
+        wd.write_all(&payload[..split]).unwrap();
 
-    fn get_event_information_ack_apdu(invoke_id: u8) -> Vec<u8> {
-        let mut apdu_buf = [0u8; 256];
-        let mut w = Writer::new(&mut apdu_buf);
+        let mut apdu2 = [0u8; 256];
+        let mut w2 = Writer::new(&mut apdu2);
         ComplexAckHeader {
-            segmented: false,
+            segmented: true,
             more_follows: false,
-            invoke_id,
-            sequence_number: None,
-            proposed_window_size: None,
-            service_choice: SERVICE_GET_EVENT_INFORMATION,
+            invoke_id: 1,
+            sequence_number: Some(1),
+            proposed_window_size: Some(1),
+            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
         }
-        .encode(&mut w)
+        .encode(&mut w2)
         .unwrap();
-        Tag::Opening { tag_num: 0 }.encode(&mut w).unwrap();
-        encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::AnalogInput, 1).raw()).unwrap();
-        encode_ctx_unsigned(&mut w, 1, 2).unwrap();
-        Tag::Context { tag_num: 2, len: 2 }.encode(&mut w).unwrap();
-        w.write_u8(5).unwrap();
-        w.write_u8(0b1110_0000).unwrap();
-        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
-        Tag::Opening { tag_num: 0 }.encode(&mut w).unwrap();
-        encode_ctx_unsigned(&mut w, 1, 1).unwrap();
-        Tag::Closing { tag_num: 0 }.encode(&mut w).unwrap();
-        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
-        encode_ctx_unsigned(&mut w, 4, 0).unwrap();
-        Tag::Context { tag_num: 5, len: 2 }.encode(&mut w).unwrap();
-        w.write_u8(5).unwrap();
-        w.write_u8(0b1100_0000).unwrap();
-        Tag::Opening { tag_num: 6 }.encode(&mut w).unwrap();
-        encode_ctx_unsigned(&mut w, 0, 1).unwrap();
-        encode_ctx_unsigned(&mut w, 1, 2).unwrap();
-        encode_ctx_unsigned(&mut w, 2, 3).unwrap();
-        Tag::Closing { tag_num: 6 }.encode(&mut w).unwrap();
-        Tag::Closing { tag_num: 0 }.encode(&mut w).unwrap();
-        Tag::Context { tag_num: 1, len: 1 }.encode(&mut w).unwrap();
-        w.write_u8(0).unwrap();
-        w.as_written().to_vec()
+        w2.write_all(&payload[split..]).unwrap();
+
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((with_npdu(w1.as_written()), addr));
+            recv.push_back((with_npdu(wd.as_written()), addr));
+            recv.push_back((with_npdu(w2.as_written()), addr));
+        }
+
+        let values = client
+            .read_property_multiple(addr, object_id, &[PropertyId::PresentValue])
+            .await
+            .unwrap();
+        assert_eq!(values.len(), 1);
+        assert!(matches!(values[0].1, ClientDataValue::Real(v) if (v - 66.0).abs() < f32::EPSILON));
     }
 
     #[tokio::test]
-    async fn who_has_object_name_collects_i_have() {
+    async fn write_properties_handles_simple_ack() {
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 31], 47808).into());
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 6], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogOutput, 2);
 
-        let mut apdu = [0u8; 128];
-        let mut w = Writer::new(&mut apdu);
-        UnconfirmedRequestHeader {
-            service_choice: SERVICE_I_HAVE,
+        let mut apdu_buf = [0u8; 32];
+        let mut w = Writer::new(&mut apdu_buf);
+        SimpleAck {
+            invoke_id: 1,
+            service_choice: SERVICE_WRITE_PROPERTY_MULTIPLE,
         }
         .encode(&mut w)
         .unwrap();
-        encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::Device, 10).raw()).unwrap();
-        encode_ctx_object_id(&mut w, 1, ObjectId::new(ObjectType::AnalogInput, 7).raw()).unwrap();
-        encode_ctx_character_string(&mut w, 2, "Zone Temp").unwrap();
-
         state
             .recv
             .lock()
             .await
             .push_back((with_npdu(w.as_written()), addr));
 
-        let results = client
-            .who_has_object_name(None, "Zone Temp", Duration::from_millis(10))
+        let writes = [PropertyWriteSpec {
+            property_id: PropertyId::PresentValue,
+            array_index: None,
+            value: DataValue::Real(12.5),
+            priority: Some(8),
+        }];
+        client
+            .write_property_multiple(addr, object_id, &writes)
             .await
             .unwrap();
-        assert_eq!(results.len(), 1);
-        assert_eq!(results[0].address, addr);
-        assert_eq!(results[0].device_id, ObjectId::new(ObjectType::Device, 10));
-        assert_eq!(
-            results[0].object_id,
-            ObjectId::new(ObjectType::AnalogInput, 7)
-        );
-        assert_eq!(results[0].object_name, "Zone Temp");
 
         let sent = state.sent.lock().await;
         assert_eq!(sent.len(), 1);
         let mut r = Reader::new(&sent[0].1);
         let _npdu = Npdu::decode(&mut r).unwrap();
-        let hdr = UnconfirmedRequestHeader::decode(&mut r).unwrap();
-        assert_eq!(hdr.service_choice, SERVICE_WHO_HAS);
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_WRITE_PROPERTY_MULTIPLE);
     }
 
     #[tokio::test]
-    async fn device_communication_control_handles_simple_ack() {
+    async fn subscribe_cov_handles_simple_ack() {
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 32], 47808).into());
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 11], 47808).into());
 
-        let mut apdu = [0u8; 32];
-        let mut w = Writer::new(&mut apdu);
+        let mut apdu_buf = [0u8; 32];
+        let mut w = Writer::new(&mut apdu_buf);
         SimpleAck {
             invoke_id: 1,
-            service_choice: SERVICE_DEVICE_COMMUNICATION_CONTROL,
+            service_choice: SERVICE_SUBSCRIBE_COV,
         }
         .encode(&mut w)
         .unwrap();
@@ -3801,7 +9835,16 @@ mod tests {
             .push_back((with_npdu(w.as_written()), addr));
 
         client
-            .device_communication_control(addr, Some(30), DeviceCommunicationState::Disable, None)
+            .subscribe_cov(
+                addr,
+                SubscribeCovRequest {
+                    subscriber_process_id: 10,
+                    monitored_object_id: ObjectId::new(ObjectType::AnalogInput, 3),
+                    issue_confirmed_notifications: Some(false),
+                    lifetime_seconds: Some(300),
+                    invoke_id: 0,
+                },
+            )
             .await
             .unwrap();
 
@@ -3810,20 +9853,20 @@ mod tests {
         let mut r = Reader::new(&sent[0].1);
         let _npdu = Npdu::decode(&mut r).unwrap();
         let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-        assert_eq!(hdr.service_choice, SERVICE_DEVICE_COMMUNICATION_CONTROL);
+        assert_eq!(hdr.service_choice, SERVICE_SUBSCRIBE_COV);
     }
 
     #[tokio::test]
-    async fn reinitialize_device_handles_simple_ack() {
+    async fn subscribe_cov_property_handles_simple_ack() {
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 33], 47808).into());
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 21], 47808).into());
 
-        let mut apdu = [0u8; 32];
-        let mut w = Writer::new(&mut apdu);
+        let mut apdu_buf = [0u8; 32];
+        let mut w = Writer::new(&mut apdu_buf);
         SimpleAck {
             invoke_id: 1,
-            service_choice: SERVICE_REINITIALIZE_DEVICE,
+            service_choice: SERVICE_SUBSCRIBE_COV_PROPERTY,
         }
         .encode(&mut w)
         .unwrap();
@@ -3834,7 +9877,19 @@ mod tests {
             .push_back((with_npdu(w.as_written()), addr));
 
         client
-            .reinitialize_device(addr, ReinitializeState::ActivateChanges, Some("pw"))
+            .subscribe_cov_property(
+                addr,
+                SubscribeCovPropertyRequest {
+                    subscriber_process_id: 22,
+                    monitored_object_id: ObjectId::new(ObjectType::AnalogInput, 3),
+                    issue_confirmed_notifications: Some(true),
+                    lifetime_seconds: Some(120),
+                    monitored_property_id: PropertyId::PresentValue,
+                    monitored_property_array_index: None,
+                    cov_increment: Some(0.1),
+                    invoke_id: 0,
+                },
+            )
             .await
             .unwrap();
 
@@ -3843,1753 +9898,2633 @@ mod tests {
         let mut r = Reader::new(&sent[0].1);
         let _npdu = Npdu::decode(&mut r).unwrap();
         let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-        assert_eq!(hdr.service_choice, SERVICE_REINITIALIZE_DEVICE);
+        assert_eq!(hdr.service_choice, SERVICE_SUBSCRIBE_COV_PROPERTY);
     }
 
     #[tokio::test]
-    async fn time_synchronize_sends_unconfirmed_request() {
+    async fn read_range_by_position_decodes_complex_ack() {
         let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 34], 47808).into());
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 22], 47808).into());
+        let object_id = ObjectId::new(ObjectType::TrendLog, 1);
 
-        client
-            .time_synchronize(
-                addr,
-                Date {
-                    year_since_1900: 126,
-                    month: 2,
-                    day: 7,
-                    weekday: 6,
-                },
-                Time {
-                    hour: 10,
-                    minute: 11,
-                    second: 12,
-                    hundredths: 13,
-                },
-                false,
-            )
+        let mut apdu_buf = [0u8; 256];
+        let mut w = Writer::new(&mut apdu_buf);
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id: 1,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_READ_RANGE,
+        }
+        .encode(&mut w)
+        .unwrap();
+        encode_ctx_object_id(&mut w, 0, object_id.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::PresentValue.to_u32()).unwrap();
+        Tag::Context { tag_num: 3, len: 2 }.encode(&mut w).unwrap();
+        w.write_u8(5).unwrap();
+        w.write_u8(0b1110_0000).unwrap();
+        encode_ctx_unsigned(&mut w, 4, 2).unwrap();
+        Tag::Opening { tag_num: 5 }.encode(&mut w).unwrap();
+        encode_app_real(&mut w, 42.0).unwrap();
+        encode_app_real(&mut w, 43.0).unwrap();
+        Tag::Closing { tag_num: 5 }.encode(&mut w).unwrap();
+
+        state
+            .recv
+            .lock()
             .await
-            .unwrap();
+            .push_back((with_npdu(w.as_written()), addr));
 
-        let sent = state.sent.lock().await;
-        assert_eq!(sent.len(), 1);
-        let mut r = Reader::new(&sent[0].1);
-        let _npdu = Npdu::decode(&mut r).unwrap();
-        let hdr = UnconfirmedRequestHeader::decode(&mut r).unwrap();
-        assert_eq!(hdr.service_choice, SERVICE_TIME_SYNCHRONIZATION);
+        let result = client
+            .read_range_by_position(addr, object_id, PropertyId::PresentValue, None, 1, 2)
+            .await
+            .unwrap();
+        assert_eq!(result.object_id, object_id);
+        assert_eq!(result.item_count, 2);
+        assert_eq!(result.items.len(), 2);
+        assert!(matches!(
+            result.items[0],
+            ClientDataValue::Real(v) if (v - 42.0).abs() < f32::EPSILON
+        ));
     }
 
     #[tokio::test]
-    async fn get_alarm_summary_decodes_complex_ack() {
+    async fn read_range_types_trend_log_multiple_records() {
+        use crate::{LogBufferRecord, LogMultipleData, LogValue};
+        use rustbac_core::services::read_range::{
+            LogMultipleData as CoreLogMultipleData, LogMultipleRecord,
+        };
+
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 38], 47808).into());
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 23], 47808).into());
+        let object_id = ObjectId::new(ObjectType::TrendLogMultiple, 4);
+        let date = Date {
+            year_since_1900: 124,
+            month: 6,
+            day: 3,
+            weekday: 1,
+        };
+        let time = Time {
+            hour: 9,
+            minute: 15,
+            second: 0,
+            hundredths: 0,
+        };
 
+        let mut apdu_buf = [0u8; 256];
+        let mut w = Writer::new(&mut apdu_buf);
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id: 1,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_READ_RANGE,
+        }
+        .encode(&mut w)
+        .unwrap();
+        encode_ctx_object_id(&mut w, 0, object_id.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::LogBuffer.to_u32()).unwrap();
+        Tag::Context { tag_num: 3, len: 2 }.encode(&mut w).unwrap();
+        w.write_u8(5).unwrap();
+        w.write_u8(0b1110_0000).unwrap();
+        encode_ctx_unsigned(&mut w, 4, 1).unwrap();
+        Tag::Opening { tag_num: 5 }.encode(&mut w).unwrap();
+        LogMultipleRecord {
+            date,
+            time,
+            data: CoreLogMultipleData::Values(vec![LogDatum::Real(21.5), LogDatum::Null]),
+        }
+        .encode(&mut w)
+        .unwrap();
+        Tag::Closing { tag_num: 5 }.encode(&mut w).unwrap();
         state
             .recv
             .lock()
             .await
-            .push_back((with_npdu(&get_alarm_summary_ack_apdu(1)), addr));
+            .push_back((with_npdu(w.as_written()), addr));
 
-        let summaries = client.get_alarm_summary(addr).await.unwrap();
-        assert_eq!(summaries.len(), 2);
-        assert_eq!(
-            summaries[0],
-            AlarmSummaryItem {
-                object_id: ObjectId::new(ObjectType::AnalogInput, 1),
-                alarm_state_raw: 1,
-                alarm_state: Some(EventState::Fault),
-                acknowledged_transitions: crate::ClientBitString {
-                    unused_bits: 5,
-                    data: vec![0b1110_0000],
-                },
-            }
-        );
+        let result = client
+            .read_range_by_position(addr, object_id, PropertyId::LogBuffer, None, 1, 1)
+            .await
+            .unwrap();
+        assert_eq!(result.items.len(), 1);
+        let [LogBufferRecord::Multiple(record)] = result.records.as_slice() else {
+            panic!(
+                "expected one Trend Log Multiple record, got {:?}",
+                result.records
+            );
+        };
+        assert_eq!(result.records[0].timestamp(), (date, time));
         assert_eq!(
-            summaries[1],
-            AlarmSummaryItem {
-                object_id: ObjectId::new(ObjectType::BinaryInput, 2),
-                alarm_state_raw: 0,
-                alarm_state: Some(EventState::Normal),
-                acknowledged_transitions: crate::ClientBitString {
-                    unused_bits: 5,
-                    data: vec![0b1100_0000],
-                },
-            }
+            record.data,
+            LogMultipleData::Values(vec![LogValue::Real(21.5), LogValue::Null])
         );
+    }
+
+    #[tokio::test]
+    async fn read_range_by_sequence_number_encodes_range_selector() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 35], 47808).into());
+        let object_id = ObjectId::new(ObjectType::TrendLog, 1);
+
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(&read_range_ack_apdu(1, object_id)), addr));
+
+        let _ = client
+            .read_range_by_sequence_number(addr, object_id, PropertyId::PresentValue, None, 20, 2)
+            .await
+            .unwrap();
 
         let sent = state.sent.lock().await;
-        assert_eq!(sent.len(), 1);
         let mut r = Reader::new(&sent[0].1);
         let _npdu = Npdu::decode(&mut r).unwrap();
         let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-        assert_eq!(hdr.service_choice, SERVICE_GET_ALARM_SUMMARY);
+        assert_eq!(hdr.service_choice, SERVICE_READ_RANGE);
+        match Tag::decode(&mut r).unwrap() {
+            Tag::Context { tag_num: 0, len: 4 } => {
+                let _ = r.read_exact(4).unwrap();
+            }
+            other => panic!("unexpected object id tag: {other:?}"),
+        }
+        match Tag::decode(&mut r).unwrap() {
+            Tag::Context { tag_num: 1, len } => {
+                let _ = decode_unsigned(&mut r, len as usize).unwrap();
+            }
+            other => panic!("unexpected property tag: {other:?}"),
+        }
+        assert_eq!(Tag::decode(&mut r).unwrap(), Tag::Opening { tag_num: 6 });
+        match Tag::decode(&mut r).unwrap() {
+            Tag::Application {
+                tag: AppTag::UnsignedInt,
+                len,
+            } => {
+                assert_eq!(decode_unsigned(&mut r, len as usize).unwrap(), 20);
+            }
+            other => panic!("unexpected ref seq tag: {other:?}"),
+        }
+        match Tag::decode(&mut r).unwrap() {
+            Tag::Application {
+                tag: AppTag::SignedInt,
+                len,
+            } => {
+                assert_eq!(decode_signed(&mut r, len as usize).unwrap(), 2);
+            }
+            other => panic!("unexpected count tag: {other:?}"),
+        }
+        assert_eq!(Tag::decode(&mut r).unwrap(), Tag::Closing { tag_num: 6 });
     }
 
     #[tokio::test]
-    async fn get_enrollment_summary_decodes_complex_ack() {
+    async fn read_range_by_time_encodes_range_selector() {
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 37], 47808).into());
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 36], 47808).into());
+        let object_id = ObjectId::new(ObjectType::TrendLog, 1);
+        let date = Date {
+            year_since_1900: 126,
+            month: 2,
+            day: 7,
+            weekday: 6,
+        };
+        let time = Time {
+            hour: 10,
+            minute: 11,
+            second: 12,
+            hundredths: 13,
+        };
 
         state
             .recv
             .lock()
             .await
-            .push_back((with_npdu(&get_enrollment_summary_ack_apdu(1)), addr));
+            .push_back((with_npdu(&read_range_ack_apdu(1, object_id)), addr));
 
-        let summaries = client.get_enrollment_summary(addr).await.unwrap();
-        assert_eq!(summaries.len(), 2);
-        assert_eq!(
-            summaries[0],
-            EnrollmentSummaryItem {
-                object_id: ObjectId::new(ObjectType::AnalogInput, 7),
-                event_type: 1,
-                event_state_raw: 2,
-                event_state: Some(EventState::Offnormal),
-                priority: 200,
-                notification_class: 10,
-            }
-        );
-        assert_eq!(
-            summaries[1],
-            EnrollmentSummaryItem {
-                object_id: ObjectId::new(ObjectType::BinaryInput, 8),
-                event_type: 0,
-                event_state_raw: 0,
-                event_state: Some(EventState::Normal),
-                priority: 20,
-                notification_class: 11,
-            }
-        );
+        let _ = client
+            .read_range_by_time(
+                addr,
+                object_id,
+                PropertyId::PresentValue,
+                None,
+                (date, time),
+                2,
+            )
+            .await
+            .unwrap();
 
         let sent = state.sent.lock().await;
-        assert_eq!(sent.len(), 1);
         let mut r = Reader::new(&sent[0].1);
         let _npdu = Npdu::decode(&mut r).unwrap();
         let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-        assert_eq!(hdr.service_choice, SERVICE_GET_ENROLLMENT_SUMMARY);
+        assert_eq!(hdr.service_choice, SERVICE_READ_RANGE);
+        match Tag::decode(&mut r).unwrap() {
+            Tag::Context { tag_num: 0, len: 4 } => {
+                let _ = r.read_exact(4).unwrap();
+            }
+            other => panic!("unexpected object id tag: {other:?}"),
+        }
+        match Tag::decode(&mut r).unwrap() {
+            Tag::Context { tag_num: 1, len } => {
+                let _ = decode_unsigned(&mut r, len as usize).unwrap();
+            }
+            other => panic!("unexpected property tag: {other:?}"),
+        }
+        assert_eq!(Tag::decode(&mut r).unwrap(), Tag::Opening { tag_num: 7 });
+        match Tag::decode(&mut r).unwrap() {
+            Tag::Application {
+                tag: AppTag::Date,
+                len: 4,
+            } => {
+                let raw = r.read_exact(4).unwrap();
+                assert_eq!(
+                    raw,
+                    &[date.year_since_1900, date.month, date.day, date.weekday]
+                );
+            }
+            other => panic!("unexpected date tag: {other:?}"),
+        }
+        match Tag::decode(&mut r).unwrap() {
+            Tag::Application {
+                tag: AppTag::Time,
+                len: 4,
+            } => {
+                let raw = r.read_exact(4).unwrap();
+                assert_eq!(raw, &[time.hour, time.minute, time.second, time.hundredths]);
+            }
+            other => panic!("unexpected time tag: {other:?}"),
+        }
+        match Tag::decode(&mut r).unwrap() {
+            Tag::Application {
+                tag: AppTag::SignedInt,
+                len,
+            } => {
+                assert_eq!(decode_signed(&mut r, len as usize).unwrap(), 2);
+            }
+            other => panic!("unexpected count tag: {other:?}"),
+        }
+        assert_eq!(Tag::decode(&mut r).unwrap(), Tag::Closing { tag_num: 7 });
     }
 
     #[tokio::test]
-    async fn get_event_information_decodes_complex_ack() {
+    async fn read_range_since_converts_through_device_local_time() {
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 57], 47808).into());
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 37], 47808).into());
+        let device = ObjectId::new(ObjectType::Device, 4_194_303);
+        let trend = ObjectId::new(ObjectType::TrendLog, 1);
 
-        state
-            .recv
-            .lock()
+        // UTC-5 with daylight saving in effect: local time runs four hours behind UTC.
+        let mut ack = [0u8; 128];
+        let mut w = Writer::new(&mut ack);
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id: 3,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_READ_RANGE,
+        }
+        .encode(&mut w)
+        .unwrap();
+        encode_ctx_object_id(&mut w, 0, trend.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::LogBuffer.to_u32()).unwrap();
+        Tag::Context { tag_num: 3, len: 2 }.encode(&mut w).unwrap();
+        w.write_u8(5).unwrap();
+        w.write_u8(0b1100_0000).unwrap();
+        encode_ctx_unsigned(&mut w, 4, 1).unwrap();
+        Tag::Opening { tag_num: 5 }.encode(&mut w).unwrap();
+        LogRecord {
+            date: Date {
+                year_since_1900: 126,
+                month: 2,
+                day: 7,
+                weekday: 6,
+            },
+            time: Time {
+                hour: 11,
+                minute: 30,
+                second: 0,
+                hundredths: 0,
+            },
+            datum: LogDatum::Real(21.5),
+            status_flags: None,
+        }
+        .encode(&mut w)
+        .unwrap();
+        Tag::Closing { tag_num: 5 }.encode(&mut w).unwrap();
+        let read_range_ack = w.as_written().to_vec();
+
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((
+                with_npdu(&read_property_ack_apdu(
+                    1,
+                    device,
+                    PropertyId::UtcOffset,
+                    None,
+                    &DataValue::Signed(300),
+                )),
+                addr,
+            ));
+            recv.push_back((
+                with_npdu(&read_property_ack_apdu(
+                    2,
+                    device,
+                    PropertyId::DaylightSavingsStatus,
+                    None,
+                    &DataValue::Boolean(true),
+                )),
+                addr,
+            ));
+            recv.push_back((with_npdu(&read_range_ack), addr));
+        }
+
+        // 2026-02-07 15:00:00 UTC.
+        let since = UNIX_EPOCH + Duration::from_secs(1_770_476_400);
+        let result = client
+            .read_range_since(addr, trend, since, 10)
             .await
-            .push_back((with_npdu(&get_event_information_ack_apdu(1)), addr));
+            .unwrap();
 
-        let result = client.get_event_information(addr, None).await.unwrap();
-        assert!(!result.more_events);
-        assert_eq!(result.summaries.len(), 1);
+        assert_eq!(result.utc_offset_minutes, 240);
+        assert_eq!(result.records.len(), 1);
         assert_eq!(
-            result.summaries[0],
-            EventInformationItem {
-                object_id: ObjectId::new(ObjectType::AnalogInput, 1),
-                event_state_raw: 2,
-                event_state: Some(EventState::Offnormal),
-                acknowledged_transitions: crate::ClientBitString {
-                    unused_bits: 5,
-                    data: vec![0b1110_0000],
-                },
-                notify_type: 0,
-                event_enable: crate::ClientBitString {
-                    unused_bits: 5,
-                    data: vec![0b1100_0000],
-                },
-                event_priorities: [1, 2, 3],
-            }
+            result.records[0].timestamp,
+            Some(UNIX_EPOCH + Duration::from_secs(1_770_478_200))
         );
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 3);
+        let request = &sent[2].1;
+        assert!(request.windows(4).any(|b| b == [126, 2, 7, 6]));
+        assert!(request.windows(4).any(|b| b == [11, 0, 0, 0]));
     }
 
     #[tokio::test]
-    async fn acknowledge_alarm_handles_simple_ack() {
+    async fn recv_unconfirmed_cov_notification_returns_decoded_value() {
         let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 39], 47808).into());
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 12], 47808).into());
 
-        let mut apdu = [0u8; 32];
+        let mut apdu = [0u8; 256];
         let mut w = Writer::new(&mut apdu);
-        SimpleAck {
-            invoke_id: 1,
-            service_choice: SERVICE_ACKNOWLEDGE_ALARM,
+        UnconfirmedRequestHeader {
+            service_choice: SERVICE_UNCONFIRMED_COV_NOTIFICATION,
         }
         .encode(&mut w)
         .unwrap();
+        encode_ctx_unsigned(&mut w, 0, 17).unwrap();
+        encode_ctx_unsigned(&mut w, 1, ObjectId::new(ObjectType::Device, 1).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 2, ObjectId::new(ObjectType::AnalogInput, 1).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 3, 60).unwrap();
+        Tag::Opening { tag_num: 4 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 0, PropertyId::PresentValue.to_u32()).unwrap();
+        Tag::Opening { tag_num: 2 }.encode(&mut w).unwrap();
+        encode_app_real(&mut w, 73.25).unwrap();
+        Tag::Closing { tag_num: 2 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 4 }.encode(&mut w).unwrap();
+
         state
             .recv
             .lock()
             .await
             .push_back((with_npdu(w.as_written()), addr));
 
-        client
-            .acknowledge_alarm(
-                addr,
-                AcknowledgeAlarmRequest {
-                    acknowledging_process_id: 10,
-                    event_object_id: ObjectId::new(ObjectType::AnalogInput, 1),
-                    event_state_acknowledged: EventState::Offnormal,
-                    event_time_stamp: TimeStamp::SequenceNumber(42),
-                    acknowledgment_source: "operator",
-                    time_of_acknowledgment: TimeStamp::DateTime {
-                        date: Date {
-                            year_since_1900: 126,
-                            month: 2,
-                            day: 7,
-                            weekday: 6,
-                        },
-                        time: Time {
-                            hour: 10,
-                            minute: 11,
-                            second: 12,
-                            hundredths: 13,
-                        },
-                    },
-                    invoke_id: 0,
-                },
-            )
+        let notification = client
+            .recv_cov_notification(Duration::from_secs(1))
             .await
+            .unwrap()
             .unwrap();
+        assert!(!notification.confirmed);
+        assert_eq!(notification.subscriber_process_id, 17);
+        assert_eq!(notification.values.len(), 1);
+        assert_eq!(notification.values[0].property_id, PropertyId::PresentValue);
+        assert!(matches!(
+            notification.values[0].value,
+            ClientDataValue::Real(v) if (v - 73.25).abs() < f32::EPSILON
+        ));
 
         let sent = state.sent.lock().await;
-        assert_eq!(sent.len(), 1);
-        let mut r = Reader::new(&sent[0].1);
-        let _npdu = Npdu::decode(&mut r).unwrap();
-        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-        assert_eq!(hdr.service_choice, SERVICE_ACKNOWLEDGE_ALARM);
+        assert!(sent.is_empty());
     }
 
     #[tokio::test]
-    async fn create_object_by_type_decodes_complex_ack() {
+    async fn cov_notification_read_by_a_request_is_kept_for_recv() {
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 50], 47808).into());
-        let created = ObjectId::new(ObjectType::AnalogValue, 42);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 12], 47808).into());
+        let _notifications = client
+            .inbound_router()
+            .subscribe(&[InboundKind::CovNotification]);
+
+        let mut apdu = [0u8; 256];
+        let mut w = Writer::new(&mut apdu);
+        UnconfirmedRequestHeader {
+            service_choice: SERVICE_UNCONFIRMED_COV_NOTIFICATION,
+        }
+        .encode(&mut w)
+        .unwrap();
+        encode_ctx_unsigned(&mut w, 0, 17).unwrap();
+        encode_ctx_unsigned(&mut w, 1, ObjectId::new(ObjectType::Device, 1).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 2, ObjectId::new(ObjectType::AnalogInput, 1).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 3, 60).unwrap();
+        Tag::Opening { tag_num: 4 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 0, PropertyId::PresentValue.to_u32()).unwrap();
+        Tag::Opening { tag_num: 2 }.encode(&mut w).unwrap();
+        encode_app_real(&mut w, 73.25).unwrap();
+        Tag::Closing { tag_num: 2 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 4 }.encode(&mut w).unwrap();
+
+        let object_id = ObjectId::new(ObjectType::AnalogValue, 3);
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((with_npdu(w.as_written()), addr));
+            recv.push_back((
+                with_npdu(&read_property_ack_apdu(
+                    1,
+                    object_id,
+                    PropertyId::PresentValue,
+                    None,
+                    &DataValue::Real(5.0),
+                )),
+                addr,
+            ));
+        }
 
-        state
-            .recv
-            .lock()
+        let value = client
+            .read_property(addr, object_id, PropertyId::PresentValue)
             .await
-            .push_back((with_npdu(&create_object_ack_apdu(1, created)), addr));
+            .unwrap();
+        assert_eq!(value, ClientDataValue::Real(5.0));
+        assert_eq!(
+            client.inbound_router().queued(InboundKind::CovNotification),
+            1
+        );
 
-        let result = client
-            .create_object_by_type(addr, ObjectType::AnalogValue)
+        let notification = client
+            .recv_cov_notification(Duration::from_millis(10))
             .await
+            .unwrap()
             .unwrap();
-        assert_eq!(result, created);
-
-        let sent = state.sent.lock().await;
-        let mut r = Reader::new(&sent[0].1);
-        let _npdu = Npdu::decode(&mut r).unwrap();
-        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-        assert_eq!(hdr.service_choice, SERVICE_CREATE_OBJECT);
+        assert_eq!(notification.subscriber_process_id, 17);
     }
 
     #[tokio::test]
-    async fn delete_object_handles_simple_ack() {
+    async fn recv_confirmed_cov_notification_sends_simple_ack() {
         let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 51], 47808).into());
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 13], 47808).into());
 
-        let mut apdu = [0u8; 32];
+        let mut apdu = [0u8; 256];
         let mut w = Writer::new(&mut apdu);
-        SimpleAck {
-            invoke_id: 1,
-            service_choice: SERVICE_DELETE_OBJECT,
+        ConfirmedRequestHeader {
+            segmented: false,
+            more_follows: false,
+            segmented_response_accepted: false,
+            max_segments: 0,
+            max_apdu: 5,
+            invoke_id: 9,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_CONFIRMED_COV_NOTIFICATION,
         }
         .encode(&mut w)
         .unwrap();
+        encode_ctx_unsigned(&mut w, 0, 18).unwrap();
+        encode_ctx_unsigned(&mut w, 1, ObjectId::new(ObjectType::Device, 1).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 2, ObjectId::new(ObjectType::AnalogInput, 2).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 3, 120).unwrap();
+        Tag::Opening { tag_num: 4 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 0, PropertyId::PresentValue.to_u32()).unwrap();
+        Tag::Opening { tag_num: 2 }.encode(&mut w).unwrap();
+        encode_app_real(&mut w, 55.0).unwrap();
+        Tag::Closing { tag_num: 2 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 4 }.encode(&mut w).unwrap();
+
         state
             .recv
             .lock()
             .await
             .push_back((with_npdu(w.as_written()), addr));
 
-        client
-            .delete_object(addr, ObjectId::new(ObjectType::AnalogValue, 42))
+        let notification = client
+            .recv_cov_notification(Duration::from_secs(1))
             .await
+            .unwrap()
             .unwrap();
+        assert!(notification.confirmed);
+        assert_eq!(notification.values.len(), 1);
+
+        let sent = state.sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        let mut r = Reader::new(&sent[0].1);
+        let _npdu = Npdu::decode(&mut r).unwrap();
+        let ack = SimpleAck::decode(&mut r).unwrap();
+        assert_eq!(ack.invoke_id, 9);
+        assert_eq!(ack.service_choice, SERVICE_CONFIRMED_COV_NOTIFICATION);
     }
 
     #[tokio::test]
-    async fn add_list_element_handles_simple_ack() {
+    async fn recv_unconfirmed_event_notification_returns_decoded_value() {
         let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 52], 47808).into());
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 16], 47808).into());
 
-        let mut apdu = [0u8; 32];
+        let mut apdu = [0u8; 256];
         let mut w = Writer::new(&mut apdu);
-        SimpleAck {
-            invoke_id: 1,
-            service_choice: SERVICE_ADD_LIST_ELEMENT,
+        UnconfirmedRequestHeader {
+            service_choice: SERVICE_UNCONFIRMED_EVENT_NOTIFICATION,
         }
         .encode(&mut w)
         .unwrap();
+        encode_ctx_unsigned(&mut w, 0, 99).unwrap();
+        encode_ctx_unsigned(&mut w, 1, ObjectId::new(ObjectType::Device, 1).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 2, ObjectId::new(ObjectType::AnalogInput, 6).raw()).unwrap();
+        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 1, 55).unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 4, 7).unwrap();
+        encode_ctx_unsigned(&mut w, 5, 100).unwrap();
+        encode_ctx_unsigned(&mut w, 6, 2).unwrap();
+        encode_ctx_character_string(&mut w, 7, "fan alarm").unwrap();
+        encode_ctx_unsigned(&mut w, 8, 0).unwrap();
+        Tag::Context { tag_num: 9, len: 1 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 10, 2).unwrap();
+        encode_ctx_unsigned(&mut w, 11, 0).unwrap();
+        Tag::Opening { tag_num: 12 }.encode(&mut w).unwrap();
+        Tag::Opening { tag_num: 0 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 0, 1).unwrap();
+        Tag::Closing { tag_num: 0 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 12 }.encode(&mut w).unwrap();
+
         state
             .recv
             .lock()
             .await
             .push_back((with_npdu(w.as_written()), addr));
 
-        let values = [DataValue::Unsigned(1), DataValue::Unsigned(2)];
-        client
-            .add_list_element(
-                addr,
-                AddListElementRequest {
-                    object_id: ObjectId::new(ObjectType::AnalogValue, 1),
-                    property_id: PropertyId::Proprietary(512),
-                    array_index: None,
-                    elements: &values,
-                    invoke_id: 0,
-                },
-            )
+        let notification: EventNotification = client
+            .recv_event_notification(Duration::from_secs(1))
             .await
+            .unwrap()
             .unwrap();
+        assert!(!notification.confirmed);
+        assert_eq!(notification.process_id, 99);
+        assert_eq!(notification.message_text.as_deref(), Some("fan alarm"));
+        assert_eq!(notification.ack_required, Some(true));
+        assert_eq!(notification.from_state, Some(EventState::Offnormal));
+        assert_eq!(notification.to_state, Some(EventState::Normal));
+        assert_eq!(notification.notify_type, 0);
+
+        let sent = state.sent.lock().await;
+        assert!(sent.is_empty());
     }
 
     #[tokio::test]
-    async fn remove_list_element_handles_simple_ack() {
+    async fn recv_confirmed_event_notification_sends_simple_ack() {
         let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 53], 47808).into());
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 17], 47808).into());
 
-        let mut apdu = [0u8; 32];
+        let mut apdu = [0u8; 256];
         let mut w = Writer::new(&mut apdu);
-        SimpleAck {
-            invoke_id: 1,
-            service_choice: SERVICE_REMOVE_LIST_ELEMENT,
+        ConfirmedRequestHeader {
+            segmented: false,
+            more_follows: false,
+            segmented_response_accepted: false,
+            max_segments: 0,
+            max_apdu: 5,
+            invoke_id: 11,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_CONFIRMED_EVENT_NOTIFICATION,
         }
         .encode(&mut w)
         .unwrap();
+        encode_ctx_unsigned(&mut w, 0, 100).unwrap();
+        encode_ctx_unsigned(&mut w, 1, ObjectId::new(ObjectType::Device, 1).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 2, ObjectId::new(ObjectType::AnalogInput, 7).raw()).unwrap();
+        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 1, 56).unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 4, 7).unwrap();
+        encode_ctx_unsigned(&mut w, 5, 100).unwrap();
+        encode_ctx_unsigned(&mut w, 6, 2).unwrap();
+        encode_ctx_unsigned(&mut w, 8, 0).unwrap();
+        encode_ctx_unsigned(&mut w, 10, 2).unwrap();
+        encode_ctx_unsigned(&mut w, 11, 0).unwrap();
+        Tag::Opening { tag_num: 12 }.encode(&mut w).unwrap();
+        Tag::Opening { tag_num: 0 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 0, 1).unwrap();
+        Tag::Closing { tag_num: 0 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 12 }.encode(&mut w).unwrap();
+
         state
             .recv
             .lock()
             .await
             .push_back((with_npdu(w.as_written()), addr));
 
-        let values = [DataValue::Unsigned(1)];
-        client
-            .remove_list_element(
-                addr,
-                RemoveListElementRequest {
-                    object_id: ObjectId::new(ObjectType::AnalogValue, 1),
-                    property_id: PropertyId::Proprietary(513),
-                    array_index: None,
-                    elements: &values,
-                    invoke_id: 0,
-                },
-            )
-            .await
-            .unwrap();
-    }
-
-    #[tokio::test]
-    async fn atomic_read_file_stream_decodes_complex_ack() {
-        let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 40], 47808).into());
-        let file_object = ObjectId::new(ObjectType::File, 2);
-
-        state.recv.lock().await.push_back((
-            with_npdu(&atomic_read_file_stream_ack_apdu(
-                1,
-                true,
-                &[0xAA, 0xBB, 0xCC],
-            )),
-            addr,
-        ));
-
-        let result = client
-            .atomic_read_file_stream(addr, file_object, 0, 3)
+        let notification = client
+            .recv_event_notification(Duration::from_secs(1))
             .await
+            .unwrap()
             .unwrap();
-
-        assert_eq!(
-            result,
-            AtomicReadFileResult::Stream {
-                end_of_file: true,
-                file_start_position: 0,
-                file_data: vec![0xAA, 0xBB, 0xCC],
-            }
-        );
+        assert!(notification.confirmed);
 
         let sent = state.sent.lock().await;
         assert_eq!(sent.len(), 1);
         let mut r = Reader::new(&sent[0].1);
         let _npdu = Npdu::decode(&mut r).unwrap();
-        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-        assert_eq!(hdr.service_choice, SERVICE_ATOMIC_READ_FILE);
+        let ack = SimpleAck::decode(&mut r).unwrap();
+        assert_eq!(ack.invoke_id, 11);
+        assert_eq!(ack.service_choice, SERVICE_CONFIRMED_EVENT_NOTIFICATION);
     }
 
     #[tokio::test]
-    async fn atomic_read_file_record_decodes_complex_ack() {
+    async fn write_property_multiple_segments_large_request() {
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 41], 47808).into());
-        let file_object = ObjectId::new(ObjectType::File, 5);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 10], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogOutput, 5);
 
-        state
-            .recv
-            .lock()
-            .await
-            .push_back((with_npdu(&atomic_read_file_record_ack_apdu(1)), addr));
+        {
+            let mut recv = state.recv.lock().await;
+            for seq in 0u8..=254 {
+                let mut apdu = [0u8; 16];
+                let mut w = Writer::new(&mut apdu);
+                SegmentAck {
+                    negative_ack: false,
+                    sent_by_server: true,
+                    invoke_id: 1,
+                    sequence_number: seq,
+                    actual_window_size: 1,
+                }
+                .encode(&mut w)
+                .unwrap();
+                recv.push_back((with_npdu(w.as_written()), addr));
+            }
+
+            let mut apdu = [0u8; 16];
+            let mut w = Writer::new(&mut apdu);
+            SimpleAck {
+                invoke_id: 1,
+                service_choice: SERVICE_WRITE_PROPERTY_MULTIPLE,
+            }
+            .encode(&mut w)
+            .unwrap();
+            recv.push_back((with_npdu(w.as_written()), addr));
+        }
 
-        let result = client
-            .atomic_read_file_record(addr, file_object, 7, 2)
+        let writes: Vec<PropertyWriteSpec> = (0..180)
+            .map(|_| PropertyWriteSpec {
+                property_id: PropertyId::Description,
+                array_index: None,
+                value: DataValue::CharacterString(
+                    "rustbac segmented write test payload................................................................",
+                ),
+                priority: None,
+            })
+            .collect();
+
+        client
+            .write_property_multiple(addr, object_id, &writes)
             .await
             .unwrap();
 
-        assert_eq!(
-            result,
-            AtomicReadFileResult::Record {
-                end_of_file: false,
-                file_start_record: 7,
-                returned_record_count: 2,
-                file_record_data: vec![vec![0x01, 0x02], vec![0x03, 0x04, 0x05]],
+        let sent = state.sent.lock().await;
+        assert!(sent.len() > 1);
+
+        let mut seqs = Vec::new();
+        let mut saw_more_follows = false;
+        let mut saw_last = false;
+        for (_, frame) in sent.iter() {
+            let mut r = Reader::new(frame);
+            let _npdu = Npdu::decode(&mut r).unwrap();
+            let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+            assert!(hdr.segmented);
+            assert_eq!(hdr.service_choice, SERVICE_WRITE_PROPERTY_MULTIPLE);
+            if hdr.more_follows {
+                saw_more_follows = true;
+            } else {
+                saw_last = true;
             }
-        );
+            seqs.push(hdr.sequence_number.unwrap());
+        }
+
+        assert!(saw_more_follows);
+        assert!(saw_last);
+        for (idx, seq) in seqs.iter().enumerate() {
+            assert_eq!(*seq as usize, idx);
+        }
     }
 
     #[tokio::test]
-    async fn atomic_write_file_stream_decodes_complex_ack() {
+    async fn write_property_multiple_uses_configured_segment_window() {
         let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 42], 47808).into());
-        let file_object = ObjectId::new(ObjectType::File, 3);
+        let client = BacnetClient::with_datalink(dl)
+            .with_response_timeout(Duration::from_secs(1))
+            .with_segmented_request_window_size(4);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 14], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogOutput, 6);
 
-        state
-            .recv
-            .lock()
-            .await
-            .push_back((with_npdu(&atomic_write_file_stream_ack_apdu(1, 128)), addr));
+        {
+            let mut recv = state.recv.lock().await;
+            for seq in 0u8..=254 {
+                let mut apdu = [0u8; 16];
+                let mut w = Writer::new(&mut apdu);
+                SegmentAck {
+                    negative_ack: false,
+                    sent_by_server: true,
+                    invoke_id: 1,
+                    sequence_number: seq,
+                    actual_window_size: 4,
+                }
+                .encode(&mut w)
+                .unwrap();
+                recv.push_back((with_npdu(w.as_written()), addr));
+            }
 
-        let result = client
-            .atomic_write_file_stream(addr, file_object, 128, &[1, 2, 3, 4])
+            let mut apdu = [0u8; 16];
+            let mut w = Writer::new(&mut apdu);
+            SimpleAck {
+                invoke_id: 1,
+                service_choice: SERVICE_WRITE_PROPERTY_MULTIPLE,
+            }
+            .encode(&mut w)
+            .unwrap();
+            recv.push_back((with_npdu(w.as_written()), addr));
+        }
+
+        let writes: Vec<PropertyWriteSpec> = (0..180)
+            .map(|_| PropertyWriteSpec {
+                property_id: PropertyId::Description,
+                array_index: None,
+                value: DataValue::CharacterString(
+                    "rustbac segmented write test payload................................................................",
+                ),
+                priority: None,
+            })
+            .collect();
+
+        client
+            .write_property_multiple(addr, object_id, &writes)
             .await
             .unwrap();
 
-        assert_eq!(
-            result,
-            AtomicWriteFileResult::Stream {
-                file_start_position: 128
-            }
-        );
+        let sent = state.sent.lock().await;
+        assert!(sent.len() > 4);
+        for (idx, (_, frame)) in sent.iter().take(4).enumerate() {
+            let mut r = Reader::new(frame);
+            let _npdu = Npdu::decode(&mut r).unwrap();
+            let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+            assert!(hdr.segmented);
+            assert_eq!(hdr.service_choice, SERVICE_WRITE_PROPERTY_MULTIPLE);
+            assert_eq!(hdr.sequence_number, Some(idx as u8));
+            assert_eq!(hdr.proposed_window_size, Some(4));
+        }
     }
 
     #[tokio::test]
-    async fn atomic_write_file_record_decodes_complex_ack() {
+    async fn write_property_multiple_adapts_window_to_peer_ack_window() {
         let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 43], 47808).into());
-        let file_object = ObjectId::new(ObjectType::File, 9);
-        let records: [&[u8]; 2] = [&[0x10, 0x11], &[0x12]];
+        let client = BacnetClient::with_datalink(dl)
+            .with_response_timeout(Duration::from_secs(1))
+            .with_segmented_request_window_size(4);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 19], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogOutput, 9);
 
-        state
-            .recv
-            .lock()
-            .await
-            .push_back((with_npdu(&atomic_write_file_record_ack_apdu(1, 7)), addr));
+        {
+            let mut recv = state.recv.lock().await;
+            for seq in 0u8..=254 {
+                let mut apdu = [0u8; 16];
+                let mut w = Writer::new(&mut apdu);
+                SegmentAck {
+                    negative_ack: false,
+                    sent_by_server: true,
+                    invoke_id: 1,
+                    sequence_number: seq,
+                    actual_window_size: 2,
+                }
+                .encode(&mut w)
+                .unwrap();
+                recv.push_back((with_npdu(w.as_written()), addr));
+            }
 
-        let result = client
-            .atomic_write_file_record(addr, file_object, 7, &records)
+            let mut apdu = [0u8; 16];
+            let mut w = Writer::new(&mut apdu);
+            SimpleAck {
+                invoke_id: 1,
+                service_choice: SERVICE_WRITE_PROPERTY_MULTIPLE,
+            }
+            .encode(&mut w)
+            .unwrap();
+            recv.push_back((with_npdu(w.as_written()), addr));
+        }
+
+        let writes: Vec<PropertyWriteSpec> = (0..180)
+            .map(|_| PropertyWriteSpec {
+                property_id: PropertyId::Description,
+                array_index: None,
+                value: DataValue::CharacterString(
+                    "rustbac segmented write test payload................................................................",
+                ),
+                priority: None,
+            })
+            .collect();
+
+        client
+            .write_property_multiple(addr, object_id, &writes)
             .await
             .unwrap();
 
-        assert_eq!(
-            result,
-            AtomicWriteFileResult::Record {
-                file_start_record: 7
+        let sent = state.sent.lock().await;
+        let mut saw_adapted_window = false;
+        for (_, frame) in sent.iter() {
+            let mut r = Reader::new(frame);
+            let _npdu = Npdu::decode(&mut r).unwrap();
+            let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+            if hdr.sequence_number.unwrap_or(0) >= 4 && hdr.proposed_window_size == Some(2) {
+                saw_adapted_window = true;
+                break;
             }
-        );
+        }
+        assert!(saw_adapted_window);
     }
 
     #[tokio::test]
-    async fn read_properties_decodes_complex_ack() {
+    async fn write_property_multiple_resends_from_the_negatively_acknowledged_segment() {
         let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 5], 47808).into());
-        let object_id = ObjectId::new(ObjectType::Device, 1);
+        let client = BacnetClient::with_datalink(dl)
+            .with_response_timeout(Duration::from_secs(1))
+            .with_segmented_request_window_size(2)
+            .with_segmented_request_retries(1);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 15], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogOutput, 7);
 
-        let mut apdu_buf = [0u8; 256];
-        let mut w = Writer::new(&mut apdu_buf);
-        ComplexAckHeader {
-            segmented: false,
-            more_follows: false,
-            invoke_id: 1,
-            sequence_number: None,
-            proposed_window_size: None,
-            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
-        }
-        .encode(&mut w)
-        .unwrap();
-        encode_ctx_unsigned(&mut w, 0, object_id.raw()).unwrap();
-        rustbac_core::encoding::tag::Tag::Opening { tag_num: 1 }
-            .encode(&mut w)
-            .unwrap();
-        encode_ctx_unsigned(&mut w, 2, PropertyId::PresentValue.to_u32()).unwrap();
-        rustbac_core::encoding::tag::Tag::Opening { tag_num: 4 }
-            .encode(&mut w)
-            .unwrap();
-        encode_app_real(&mut w, 55.5).unwrap();
-        rustbac_core::encoding::tag::Tag::Closing { tag_num: 4 }
-            .encode(&mut w)
-            .unwrap();
-        rustbac_core::encoding::tag::Tag::Closing { tag_num: 1 }
-            .encode(&mut w)
-            .unwrap();
+        {
+            let mut recv = state.recv.lock().await;
+            // Segment 0 arrived, segment 1 did not: resend from 1.
+            for (negative_ack, sequence_number) in [(true, 0u8), (false, 1)] {
+                let mut apdu = [0u8; 16];
+                let mut w = Writer::new(&mut apdu);
+                SegmentAck {
+                    negative_ack,
+                    sent_by_server: true,
+                    invoke_id: 1,
+                    sequence_number,
+                    actual_window_size: 2,
+                }
+                .encode(&mut w)
+                .unwrap();
+                recv.push_back((with_npdu(w.as_written()), addr));
+            }
 
-        state
-            .recv
-            .lock()
-            .await
-            .push_back((with_npdu(w.as_written()), addr));
+            let mut apdu = [0u8; 16];
+            let mut w = Writer::new(&mut apdu);
+            SimpleAck {
+                invoke_id: 1,
+                service_choice: SERVICE_WRITE_PROPERTY_MULTIPLE,
+            }
+            .encode(&mut w)
+            .unwrap();
+            recv.push_back((with_npdu(w.as_written()), addr));
+        }
 
-        let values = client
-            .read_property_multiple(addr, object_id, &[PropertyId::PresentValue])
+        // Three segments at the default 1476-octet max APDU.
+        let writes: Vec<PropertyWriteSpec> = (0..35)
+            .map(|_| PropertyWriteSpec {
+                property_id: PropertyId::Description,
+                array_index: None,
+                value: DataValue::CharacterString(
+                    "rustbac segmented write test payload................................................................",
+                ),
+                priority: None,
+            })
+            .collect();
+
+        client
+            .write_property_multiple(addr, object_id, &writes)
             .await
             .unwrap();
-        assert_eq!(values.len(), 1);
-        assert_eq!(values[0].0, PropertyId::PresentValue);
-        assert!(matches!(values[0].1, ClientDataValue::Real(v) if (v - 55.5).abs() < f32::EPSILON));
 
         let sent = state.sent.lock().await;
-        assert_eq!(sent.len(), 1);
-        let mut r = Reader::new(&sent[0].1);
-        let _npdu = Npdu::decode(&mut r).unwrap();
-        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-        assert_eq!(hdr.service_choice, SERVICE_READ_PROPERTY_MULTIPLE);
+        let sequences: Vec<Option<u8>> = sent
+            .iter()
+            .map(|(_, frame)| {
+                let mut r = Reader::new(frame);
+                let _npdu = Npdu::decode(&mut r).unwrap();
+                ConfirmedRequestHeader::decode(&mut r)
+                    .unwrap()
+                    .sequence_number
+            })
+            .collect();
+        assert_eq!(sequences, vec![Some(0), Some(1), Some(1), Some(2)]);
     }
 
     #[tokio::test]
-    async fn read_property_multiple_reassembles_segmented_complex_ack() {
+    async fn read_property_ignores_invalid_frames_until_valid_response() {
         let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 12], 47808).into());
-        let object_id = ObjectId::new(ObjectType::Device, 1);
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 16], 47808).into());
+        let state_for_task = state.clone();
 
-        let mut payload_buf = [0u8; 256];
-        let mut pw = Writer::new(&mut payload_buf);
-        encode_ctx_unsigned(&mut pw, 0, object_id.raw()).unwrap();
-        rustbac_core::encoding::tag::Tag::Opening { tag_num: 1 }
-            .encode(&mut pw)
-            .unwrap();
-        encode_ctx_unsigned(&mut pw, 2, PropertyId::PresentValue.to_u32()).unwrap();
-        rustbac_core::encoding::tag::Tag::Opening { tag_num: 4 }
-            .encode(&mut pw)
-            .unwrap();
-        encode_app_real(&mut pw, 66.0).unwrap();
-        rustbac_core::encoding::tag::Tag::Closing { tag_num: 4 }
-            .encode(&mut pw)
-            .unwrap();
-        rustbac_core::encoding::tag::Tag::Closing { tag_num: 1 }
-            .encode(&mut pw)
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let mut apdu = [0u8; 128];
+            let mut w = Writer::new(&mut apdu);
+            ComplexAckHeader {
+                segmented: false,
+                more_follows: false,
+                invoke_id: 1,
+                sequence_number: None,
+                proposed_window_size: None,
+                service_choice: SERVICE_READ_PROPERTY,
+            }
+            .encode(&mut w)
             .unwrap();
-        let payload = pw.as_written();
-        let split = payload.len() / 2;
+            encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::Device, 1).raw()).unwrap();
+            encode_ctx_unsigned(&mut w, 1, PropertyId::PresentValue.to_u32()).unwrap();
+            Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+            encode_app_real(&mut w, 77.0).unwrap();
+            Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+            state_for_task
+                .recv
+                .lock()
+                .await
+                .push_back((with_npdu(w.as_written()), addr));
+        });
 
-        let mut apdu1 = [0u8; 256];
-        let mut w1 = Writer::new(&mut apdu1);
-        ComplexAckHeader {
-            segmented: true,
-            more_follows: true,
-            invoke_id: 1,
-            sequence_number: Some(0),
-            proposed_window_size: Some(1),
-            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
-        }
-        .encode(&mut w1)
-        .unwrap();
-        w1.write_all(&payload[..split]).unwrap();
+        let value = client
+            .read_property(
+                addr,
+                ObjectId::new(ObjectType::Device, 1),
+                PropertyId::PresentValue,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            value,
+            ClientDataValue::Real(v) if (v - 77.0).abs() < f32::EPSILON
+        ));
+    }
 
-        let mut apdu2 = [0u8; 256];
-        let mut w2 = Writer::new(&mut apdu2);
-        ComplexAckHeader {
-            segmented: true,
-            more_follows: false,
-            invoke_id: 1,
-            sequence_number: Some(1),
-            proposed_window_size: Some(1),
-            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
-        }
-        .encode(&mut w2)
-        .unwrap();
-        w2.write_all(&payload[split..]).unwrap();
+    #[tokio::test]
+    async fn read_property_maps_reject() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 7], 47808).into());
 
+        let mut apdu = [0u8; 8];
+        let mut w = Writer::new(&mut apdu);
+        w.write_u8((ApduType::Reject as u8) << 4).unwrap();
+        w.write_u8(1).unwrap(); // invoke id
+        w.write_u8(2).unwrap(); // reason
         state
             .recv
             .lock()
             .await
-            .push_back((with_npdu(w1.as_written()), addr));
-        state
-            .recv
-            .lock()
-            .await
-            .push_back((with_npdu(w2.as_written()), addr));
+            .push_back((with_npdu(w.as_written()), addr));
 
-        let values = client
-            .read_property_multiple(addr, object_id, &[PropertyId::PresentValue])
+        let err = client
+            .read_property(
+                addr,
+                ObjectId::new(ObjectType::Device, 1),
+                PropertyId::ObjectName,
+            )
             .await
-            .unwrap();
-        assert_eq!(values.len(), 1);
-        assert!(matches!(values[0].1, ClientDataValue::Real(v) if (v - 66.0).abs() < f32::EPSILON));
-
-        let sent = state.sent.lock().await;
-        assert!(sent.len() >= 3);
-
-        let mut saw_segment_ack = 0usize;
-        for (_, frame) in sent.iter().skip(1) {
-            let mut r = Reader::new(frame);
-            let _npdu = Npdu::decode(&mut r).unwrap();
-            let apdu = r.read_exact(r.remaining()).unwrap();
-            if (apdu[0] >> 4) == ApduType::SegmentAck as u8 {
-                let mut sr = Reader::new(apdu);
-                let sack = SegmentAck::decode(&mut sr).unwrap();
-                assert_eq!(sack.invoke_id, 1);
-                saw_segment_ack += 1;
-            }
-        }
-        assert!(saw_segment_ack >= 1);
+            .unwrap_err();
+        assert!(matches!(
+            err.root(),
+            crate::ClientError::RemoteReject { reason: 2 }
+        ));
+        let context = err.context().expect("request context");
+        assert_eq!(context.address, addr);
+        assert_eq!(context.invoke_id, 1);
+        assert_eq!(
+            context.service_choice,
+            rustbac_core::services::read_property::SERVICE_READ_PROPERTY
+        );
+        assert_eq!(err.code(), "client.remote_reject");
+        assert!(!err.is_retryable());
     }
 
     #[tokio::test]
-    async fn read_property_multiple_tolerates_duplicate_segment() {
+    async fn read_property_maps_remote_error_details() {
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 18], 47808).into());
-        let object_id = ObjectId::new(ObjectType::Device, 1);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 17], 47808).into());
 
-        let mut payload_buf = [0u8; 256];
-        let mut pw = Writer::new(&mut payload_buf);
-        encode_ctx_unsigned(&mut pw, 0, object_id.raw()).unwrap();
-        rustbac_core::encoding::tag::Tag::Opening { tag_num: 1 }
-            .encode(&mut pw)
-            .unwrap();
-        encode_ctx_unsigned(&mut pw, 2, PropertyId::PresentValue.to_u32()).unwrap();
-        rustbac_core::encoding::tag::Tag::Opening { tag_num: 4 }
-            .encode(&mut pw)
-            .unwrap();
-        encode_app_real(&mut pw, 66.0).unwrap();
-        rustbac_core::encoding::tag::Tag::Closing { tag_num: 4 }
-            .encode(&mut pw)
-            .unwrap();
-        rustbac_core::encoding::tag::Tag::Closing { tag_num: 1 }
-            .encode(&mut pw)
+        let mut apdu = [0u8; 16];
+        let mut w = Writer::new(&mut apdu);
+        w.write_u8((ApduType::Error as u8) << 4).unwrap();
+        w.write_u8(1).unwrap(); // invoke id
+        w.write_u8(rustbac_core::services::read_property::SERVICE_READ_PROPERTY)
             .unwrap();
-        let payload = pw.as_written();
-        let split = payload.len() / 2;
-
-        let mut apdu1 = [0u8; 256];
-        let mut w1 = Writer::new(&mut apdu1);
-        ComplexAckHeader {
-            segmented: true,
-            more_follows: true,
-            invoke_id: 1,
-            sequence_number: Some(0),
-            proposed_window_size: Some(1),
-            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
-        }
-        .encode(&mut w1)
-        .unwrap();
-        w1.write_all(&payload[..split]).unwrap();
-
-        let mut dup = [0u8; 256];
-        let mut wd = Writer::new(&mut dup);
-        ComplexAckHeader {
-            segmented: true,
-            more_follows: true,
-            invoke_id: 1,
-            sequence_number: Some(0),
-            proposed_window_size: Some(1),
-            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
-        }
-        .encode(&mut wd)
-        .unwrap();
-        wd.write_all(&payload[..split]).unwrap();
-
-        let mut apdu2 = [0u8; 256];
-        let mut w2 = Writer::new(&mut apdu2);
-        ComplexAckHeader {
-            segmented: true,
-            more_follows: false,
-            invoke_id: 1,
-            sequence_number: Some(1),
-            proposed_window_size: Some(1),
-            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
-        }
-        .encode(&mut w2)
-        .unwrap();
-        w2.write_all(&payload[split..]).unwrap();
-
-        {
-            let mut recv = state.recv.lock().await;
-            recv.push_back((with_npdu(w1.as_written()), addr));
-            recv.push_back((with_npdu(wd.as_written()), addr));
-            recv.push_back((with_npdu(w2.as_written()), addr));
-        }
+        Tag::Context { tag_num: 0, len: 1 }.encode(&mut w).unwrap();
+        w.write_u8(2).unwrap(); // property class
+        Tag::Context { tag_num: 1, len: 1 }.encode(&mut w).unwrap();
+        w.write_u8(32).unwrap(); // unknownProperty
 
-        let values = client
-            .read_property_multiple(addr, object_id, &[PropertyId::PresentValue])
+        state
+            .recv
+            .lock()
+            .await
+            .push_back((with_npdu(w.as_written()), addr));
+
+        let err = client
+            .read_property(
+                addr,
+                ObjectId::new(ObjectType::Device, 1),
+                PropertyId::ObjectName,
+            )
             .await
-            .unwrap();
-        assert_eq!(values.len(), 1);
-        assert!(matches!(values[0].1, ClientDataValue::Real(v) if (v - 66.0).abs() < f32::EPSILON));
+            .unwrap_err();
+        assert!(matches!(
+            err.root(),
+            crate::ClientError::RemoteServiceError {
+                service_choice: rustbac_core::services::read_property::SERVICE_READ_PROPERTY,
+                error_class_raw: Some(2),
+                error_code_raw: Some(32),
+                error_class: Some(rustbac_core::types::ErrorClass::Property),
+                error_code: Some(rustbac_core::types::ErrorCode::UnknownProperty),
+            }
+        ));
     }
 
     #[tokio::test]
-    async fn write_properties_handles_simple_ack() {
+    async fn write_property_maps_abort() {
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 6], 47808).into());
-        let object_id = ObjectId::new(ObjectType::AnalogOutput, 2);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 8], 47808).into());
 
-        let mut apdu_buf = [0u8; 32];
-        let mut w = Writer::new(&mut apdu_buf);
-        SimpleAck {
-            invoke_id: 1,
-            service_choice: SERVICE_WRITE_PROPERTY_MULTIPLE,
-        }
-        .encode(&mut w)
-        .unwrap();
+        let mut apdu = [0u8; 8];
+        let mut w = Writer::new(&mut apdu);
+        w.write_u8(((ApduType::Abort as u8) << 4) | 0x01).unwrap(); // server abort
+        w.write_u8(1).unwrap(); // invoke id
+        w.write_u8(9).unwrap(); // reason
         state
             .recv
             .lock()
             .await
             .push_back((with_npdu(w.as_written()), addr));
 
-        let writes = [PropertyWriteSpec {
+        let req = rustbac_core::services::write_property::WritePropertyRequest {
+            object_id: ObjectId::new(ObjectType::AnalogOutput, 1),
             property_id: PropertyId::PresentValue,
-            array_index: None,
-            value: DataValue::Real(12.5),
+            value: DataValue::Real(10.0),
             priority: Some(8),
-        }];
-        client
-            .write_property_multiple(addr, object_id, &writes)
-            .await
-            .unwrap();
-
-        let sent = state.sent.lock().await;
-        assert_eq!(sent.len(), 1);
-        let mut r = Reader::new(&sent[0].1);
-        let _npdu = Npdu::decode(&mut r).unwrap();
-        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-        assert_eq!(hdr.service_choice, SERVICE_WRITE_PROPERTY_MULTIPLE);
+            ..Default::default()
+        };
+        let err = client.write_property(addr, req).await.unwrap_err();
+        assert!(matches!(
+            err.root(),
+            crate::ClientError::RemoteAbort {
+                reason: 9,
+                server: true
+            }
+        ));
+        // Out-of-resources is a temporary condition.
+        assert!(err.is_retryable());
     }
 
     #[tokio::test]
-    async fn subscribe_cov_handles_simple_ack() {
+    async fn read_property_multiple_returns_owned_string() {
         let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 11], 47808).into());
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 9], 47808).into());
+        let object_id = ObjectId::new(ObjectType::Device, 1);
 
-        let mut apdu_buf = [0u8; 32];
+        let mut apdu_buf = [0u8; 256];
         let mut w = Writer::new(&mut apdu_buf);
-        SimpleAck {
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
             invoke_id: 1,
-            service_choice: SERVICE_SUBSCRIBE_COV,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
         }
         .encode(&mut w)
         .unwrap();
+        encode_ctx_unsigned(&mut w, 0, object_id.raw()).unwrap();
+        rustbac_core::encoding::tag::Tag::Opening { tag_num: 1 }
+            .encode(&mut w)
+            .unwrap();
+        encode_ctx_unsigned(&mut w, 2, PropertyId::ObjectName.to_u32()).unwrap();
+        rustbac_core::encoding::tag::Tag::Opening { tag_num: 4 }
+            .encode(&mut w)
+            .unwrap();
+        rustbac_core::services::value_codec::encode_application_data_value(
+            &mut w,
+            &DataValue::CharacterString("AHU-1"),
+        )
+        .unwrap();
+        rustbac_core::encoding::tag::Tag::Closing { tag_num: 4 }
+            .encode(&mut w)
+            .unwrap();
+        rustbac_core::encoding::tag::Tag::Closing { tag_num: 1 }
+            .encode(&mut w)
+            .unwrap();
+
         state
             .recv
             .lock()
             .await
             .push_back((with_npdu(w.as_written()), addr));
 
-        client
-            .subscribe_cov(
-                addr,
-                SubscribeCovRequest {
-                    subscriber_process_id: 10,
-                    monitored_object_id: ObjectId::new(ObjectType::AnalogInput, 3),
-                    issue_confirmed_notifications: Some(false),
-                    lifetime_seconds: Some(300),
-                    invoke_id: 0,
-                },
-            )
+        let values = client
+            .read_property_multiple(addr, object_id, &[PropertyId::ObjectName])
             .await
             .unwrap();
-
-        let sent = state.sent.lock().await;
-        assert_eq!(sent.len(), 1);
-        let mut r = Reader::new(&sent[0].1);
-        let _npdu = Npdu::decode(&mut r).unwrap();
-        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-        assert_eq!(hdr.service_choice, SERVICE_SUBSCRIBE_COV);
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].0, PropertyId::ObjectName);
+        assert!(matches!(
+            &values[0].1,
+            ClientDataValue::CharacterString(s) if &**s == "AHU-1"
+        ));
     }
 
     #[tokio::test]
-    async fn subscribe_cov_property_handles_simple_ack() {
+    async fn string_interner_shares_repeated_strings() {
         let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 21], 47808).into());
+        let interner = StringInterner::new();
+        let client = BacnetClient::with_datalink(dl).with_string_interner(interner.clone());
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 9], 47808).into());
+        let object_id = ObjectId::new(ObjectType::MultiStateValue, 1);
 
-        let mut apdu_buf = [0u8; 32];
+        let mut apdu_buf = [0u8; 256];
         let mut w = Writer::new(&mut apdu_buf);
-        SimpleAck {
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
             invoke_id: 1,
-            service_choice: SERVICE_SUBSCRIBE_COV_PROPERTY,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
         }
         .encode(&mut w)
         .unwrap();
+        encode_ctx_unsigned(&mut w, 0, object_id.raw()).unwrap();
+        rustbac_core::encoding::tag::Tag::Opening { tag_num: 1 }
+            .encode(&mut w)
+            .unwrap();
+        for property in [PropertyId::ObjectName, PropertyId::Description] {
+            encode_ctx_unsigned(&mut w, 2, property.to_u32()).unwrap();
+            rustbac_core::encoding::tag::Tag::Opening { tag_num: 4 }
+                .encode(&mut w)
+                .unwrap();
+            rustbac_core::services::value_codec::encode_application_data_value(
+                &mut w,
+                &DataValue::CharacterString("Fan Mode"),
+            )
+            .unwrap();
+            rustbac_core::encoding::tag::Tag::Closing { tag_num: 4 }
+                .encode(&mut w)
+                .unwrap();
+        }
+        rustbac_core::encoding::tag::Tag::Closing { tag_num: 1 }
+            .encode(&mut w)
+            .unwrap();
         state
             .recv
             .lock()
             .await
             .push_back((with_npdu(w.as_written()), addr));
 
-        client
-            .subscribe_cov_property(
+        let values = client
+            .read_property_multiple(
                 addr,
-                SubscribeCovPropertyRequest {
-                    subscriber_process_id: 22,
-                    monitored_object_id: ObjectId::new(ObjectType::AnalogInput, 3),
-                    issue_confirmed_notifications: Some(true),
-                    lifetime_seconds: Some(120),
-                    monitored_property_id: PropertyId::PresentValue,
-                    monitored_property_array_index: None,
-                    cov_increment: Some(0.1),
-                    invoke_id: 0,
-                },
+                object_id,
+                &[PropertyId::ObjectName, PropertyId::Description],
             )
             .await
             .unwrap();
-
-        let sent = state.sent.lock().await;
-        assert_eq!(sent.len(), 1);
-        let mut r = Reader::new(&sent[0].1);
-        let _npdu = Npdu::decode(&mut r).unwrap();
-        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-        assert_eq!(hdr.service_choice, SERVICE_SUBSCRIBE_COV_PROPERTY);
+        let [(_, ClientDataValue::CharacterString(name)), (_, ClientDataValue::CharacterString(description))] =
+            &values[..]
+        else {
+            panic!("unexpected values {values:?}");
+        };
+        assert!(std::sync::Arc::ptr_eq(name, description));
+        assert_eq!(interner.len(), 1);
+        assert!(client.string_interner().is_some());
     }
 
-    #[tokio::test]
-    async fn read_range_by_position_decodes_complex_ack() {
-        let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 22], 47808).into());
-        let object_id = ObjectId::new(ObjectType::TrendLog, 1);
+    fn simple_ack_apdu(invoke_id: u8, service_choice: u8) -> Vec<u8> {
+        let mut apdu = [0u8; 8];
+        let mut w = Writer::new(&mut apdu);
+        SimpleAck {
+            invoke_id,
+            service_choice,
+        }
+        .encode(&mut w)
+        .unwrap();
+        w.as_written().to_vec()
+    }
 
-        let mut apdu_buf = [0u8; 256];
-        let mut w = Writer::new(&mut apdu_buf);
+    fn read_property_real_ack_apdu(invoke_id: u8, object_id: ObjectId, value: f32) -> Vec<u8> {
+        let mut apdu = [0u8; 64];
+        let mut w = Writer::new(&mut apdu);
         ComplexAckHeader {
             segmented: false,
             more_follows: false,
-            invoke_id: 1,
+            invoke_id,
             sequence_number: None,
             proposed_window_size: None,
-            service_choice: SERVICE_READ_RANGE,
+            service_choice: SERVICE_READ_PROPERTY,
         }
         .encode(&mut w)
         .unwrap();
         encode_ctx_object_id(&mut w, 0, object_id.raw()).unwrap();
         encode_ctx_unsigned(&mut w, 1, PropertyId::PresentValue.to_u32()).unwrap();
-        Tag::Context { tag_num: 3, len: 2 }.encode(&mut w).unwrap();
-        w.write_u8(5).unwrap();
-        w.write_u8(0b1110_0000).unwrap();
-        encode_ctx_unsigned(&mut w, 4, 2).unwrap();
-        Tag::Opening { tag_num: 5 }.encode(&mut w).unwrap();
-        encode_app_real(&mut w, 42.0).unwrap();
-        encode_app_real(&mut w, 43.0).unwrap();
-        Tag::Closing { tag_num: 5 }.encode(&mut w).unwrap();
+        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+        encode_app_real(&mut w, value).unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        w.as_written().to_vec()
+    }
 
-        state
-            .recv
-            .lock()
-            .await
-            .push_back((with_npdu(w.as_written()), addr));
+    #[tokio::test]
+    async fn write_verified_retries_until_value_matches() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 40], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogValue, 3);
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((with_npdu(&simple_ack_apdu(1, SERVICE_WRITE_PROPERTY)), addr));
+            recv.push_back((
+                with_npdu(&read_property_real_ack_apdu(2, object_id, 20.0)),
+                addr,
+            ));
+            recv.push_back((with_npdu(&simple_ack_apdu(3, SERVICE_WRITE_PROPERTY)), addr));
+            recv.push_back((
+                with_npdu(&read_property_real_ack_apdu(4, object_id, 21.0004)),
+                addr,
+            ));
+        }
 
-        let result = client
-            .read_range_by_position(addr, object_id, PropertyId::PresentValue, None, 1, 2)
+        let value = client
+            .write_verified(
+                addr,
+                object_id,
+                PropertyId::PresentValue,
+                ClientDataValue::Real(21.0),
+                Some(8),
+                crate::WriteVerifyOptions::default(),
+            )
             .await
             .unwrap();
-        assert_eq!(result.object_id, object_id);
-        assert_eq!(result.item_count, 2);
-        assert_eq!(result.items.len(), 2);
+        assert!(matches!(value, ClientDataValue::Real(v) if (v - 21.0004).abs() < f32::EPSILON));
+        assert_eq!(state.sent.lock().await.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn write_verified_reports_observed_value_on_failure() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 41], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogValue, 4);
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((with_npdu(&simple_ack_apdu(1, SERVICE_WRITE_PROPERTY)), addr));
+            recv.push_back((
+                with_npdu(&read_property_real_ack_apdu(2, object_id, 18.0)),
+                addr,
+            ));
+        }
+
+        let err = client
+            .write_verified(
+                addr,
+                object_id,
+                PropertyId::PresentValue,
+                ClientDataValue::Real(21.0),
+                None,
+                crate::WriteVerifyOptions::default().with_retries(0),
+            )
+            .await
+            .unwrap_err();
         assert!(matches!(
-            result.items[0],
-            ClientDataValue::Real(v) if (v - 42.0).abs() < f32::EPSILON
+            err,
+            crate::ClientError::VerificationFailed {
+                observed: ClientDataValue::Real(v),
+                attempts: 1,
+                ..
+            } if (v - 18.0).abs() < f32::EPSILON
         ));
     }
 
     #[tokio::test]
-    async fn read_range_by_sequence_number_encodes_range_selector() {
+    async fn write_with_rollback_undoes_applied_writes_on_failure() {
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 35], 47808).into());
-        let object_id = ObjectId::new(ObjectType::TrendLog, 1);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 42], 47808).into());
+        let first = ObjectId::new(ObjectType::AnalogValue, 1);
+        let second = ObjectId::new(ObjectType::AnalogValue, 2);
 
-        state
-            .recv
-            .lock()
-            .await
-            .push_back((with_npdu(&read_range_ack_apdu(1, object_id)), addr));
+        let mut reject = [0u8; 8];
+        let mut w = Writer::new(&mut reject);
+        w.write_u8((ApduType::Reject as u8) << 4).unwrap();
+        w.write_u8(4).unwrap(); // invoke id
+        w.write_u8(2).unwrap(); // reason
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((
+                with_npdu(&read_property_real_ack_apdu(1, first, 10.0)),
+                addr,
+            ));
+            recv.push_back((
+                with_npdu(&read_property_real_ack_apdu(2, second, 20.0)),
+                addr,
+            ));
+            recv.push_back((with_npdu(&simple_ack_apdu(3, SERVICE_WRITE_PROPERTY)), addr));
+            recv.push_back((with_npdu(w.as_written()), addr));
+            recv.push_back((with_npdu(&simple_ack_apdu(5, SERVICE_WRITE_PROPERTY)), addr));
+        }
 
-        let _ = client
-            .read_range_by_sequence_number(addr, object_id, PropertyId::PresentValue, None, 20, 2)
+        let report = client
+            .write_with_rollback(
+                addr,
+                &[
+                    (
+                        first,
+                        PropertyId::PresentValue,
+                        ClientDataValue::Real(11.0),
+                        None,
+                    ),
+                    (
+                        second,
+                        PropertyId::PresentValue,
+                        ClientDataValue::Real(21.0),
+                        None,
+                    ),
+                ],
+            )
             .await
             .unwrap();
 
+        assert!(!report.is_committed());
+        assert!(report.is_fully_rolled_back());
+        assert_eq!(report.rolled_back.len(), 1);
+        assert_eq!(report.rolled_back[0].object_id, first);
+        assert_eq!(report.rolled_back[0].previous, ClientDataValue::Real(10.0));
+        let failed = report.failed.unwrap();
+        assert_eq!(failed.item.object_id, second);
+        assert!(matches!(
+            failed.error.root(),
+            crate::ClientError::RemoteReject { reason: 2 }
+        ));
+
         let sent = state.sent.lock().await;
-        let mut r = Reader::new(&sent[0].1);
+        assert_eq!(sent.len(), 5);
+        let mut r = Reader::new(&sent[4].1);
         let _npdu = Npdu::decode(&mut r).unwrap();
         let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-        assert_eq!(hdr.service_choice, SERVICE_READ_RANGE);
-        match Tag::decode(&mut r).unwrap() {
-            Tag::Context { tag_num: 0, len: 4 } => {
-                let _ = r.read_exact(4).unwrap();
-            }
-            other => panic!("unexpected object id tag: {other:?}"),
-        }
-        match Tag::decode(&mut r).unwrap() {
-            Tag::Context { tag_num: 1, len } => {
-                let _ = decode_unsigned(&mut r, len as usize).unwrap();
-            }
-            other => panic!("unexpected property tag: {other:?}"),
-        }
-        assert_eq!(Tag::decode(&mut r).unwrap(), Tag::Opening { tag_num: 6 });
-        match Tag::decode(&mut r).unwrap() {
-            Tag::Application {
-                tag: AppTag::UnsignedInt,
-                len,
-            } => {
-                assert_eq!(decode_unsigned(&mut r, len as usize).unwrap(), 20);
-            }
-            other => panic!("unexpected ref seq tag: {other:?}"),
-        }
-        match Tag::decode(&mut r).unwrap() {
-            Tag::Application {
-                tag: AppTag::SignedInt,
-                len,
-            } => {
-                assert_eq!(decode_signed(&mut r, len as usize).unwrap(), 2);
-            }
-            other => panic!("unexpected count tag: {other:?}"),
-        }
-        assert_eq!(Tag::decode(&mut r).unwrap(), Tag::Closing { tag_num: 6 });
+        assert_eq!(hdr.service_choice, SERVICE_WRITE_PROPERTY);
+        assert_eq!(hdr.invoke_id, 5);
+    }
+
+    /// The service parameters of a confirmed request frame after its NPDU and header.
+    fn confirmed_request_body(frame: &[u8]) -> (u8, Vec<u8>) {
+        let mut r = Reader::new(frame);
+        Npdu::decode(&mut r).unwrap();
+        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+        assert_eq!(hdr.service_choice, SERVICE_WRITE_PROPERTY);
+        (hdr.invoke_id, r.read_exact(r.remaining()).unwrap().to_vec())
+    }
+
+    fn write_property_body(request: WritePropertyRequest<'_>) -> Vec<u8> {
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        request.encode(&mut w).unwrap();
+        let mut r = Reader::new(w.as_written());
+        ConfirmedRequestHeader::decode(&mut r).unwrap();
+        r.read_exact(r.remaining()).unwrap().to_vec()
     }
 
     #[tokio::test]
-    async fn read_range_by_time_encodes_range_selector() {
+    async fn write_with_rollback_undoes_writes_before_a_mid_batch_failure_in_reverse() {
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 36], 47808).into());
-        let object_id = ObjectId::new(ObjectType::TrendLog, 1);
-        let date = Date {
-            year_since_1900: 126,
-            month: 2,
-            day: 7,
-            weekday: 6,
-        };
-        let time = Time {
-            hour: 10,
-            minute: 11,
-            second: 12,
-            hundredths: 13,
-        };
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 44], 47808).into());
+        let objects: Vec<_> = (1..=4)
+            .map(|instance| ObjectId::new(ObjectType::AnalogValue, instance))
+            .collect();
+        {
+            let mut recv = state.recv.lock().await;
+            for (invoke_id, object_id) in (1..).zip(&objects) {
+                recv.push_back((
+                    with_npdu(&read_property_real_ack_apdu(
+                        invoke_id,
+                        *object_id,
+                        10.0 * invoke_id as f32,
+                    )),
+                    addr,
+                ));
+            }
+            recv.push_back((with_npdu(&simple_ack_apdu(5, SERVICE_WRITE_PROPERTY)), addr));
+            recv.push_back((with_npdu(&simple_ack_apdu(6, SERVICE_WRITE_PROPERTY)), addr));
+            // The third write is refused (write-access-denied).
+            recv.push_back((
+                with_npdu(&[0x50, 7, SERVICE_WRITE_PROPERTY, 0x91, 2, 0x91, 40]),
+                addr,
+            ));
+            recv.push_back((with_npdu(&simple_ack_apdu(8, SERVICE_WRITE_PROPERTY)), addr));
+            recv.push_back((with_npdu(&simple_ack_apdu(9, SERVICE_WRITE_PROPERTY)), addr));
+        }
 
-        state
-            .recv
-            .lock()
-            .await
-            .push_back((with_npdu(&read_range_ack_apdu(1, object_id)), addr));
+        let priorities = [Some(8), None, Some(8), None];
+        let writes: Vec<_> = objects
+            .iter()
+            .zip(priorities)
+            .map(|(object_id, priority)| {
+                (
+                    *object_id,
+                    PropertyId::PresentValue,
+                    ClientDataValue::Real(99.0),
+                    priority,
+                )
+            })
+            .collect();
+        let report = client.write_with_rollback(addr, &writes).await.unwrap();
 
-        let _ = client
-            .read_range_by_time(
-                addr,
-                object_id,
-                PropertyId::PresentValue,
-                None,
-                (date, time),
-                2,
-            )
-            .await
-            .unwrap();
+        assert!(report.is_fully_rolled_back());
+        let rolled_back: Vec<_> = report.rolled_back.iter().map(|i| i.object_id).collect();
+        assert_eq!(rolled_back, vec![objects[1], objects[0]]);
+        assert_eq!(report.failed.as_ref().unwrap().item.object_id, objects[2]);
 
+        // Four reads, three writes, two undos; the fourth write is never sent.
         let sent = state.sent.lock().await;
-        let mut r = Reader::new(&sent[0].1);
-        let _npdu = Npdu::decode(&mut r).unwrap();
-        let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-        assert_eq!(hdr.service_choice, SERVICE_READ_RANGE);
-        match Tag::decode(&mut r).unwrap() {
-            Tag::Context { tag_num: 0, len: 4 } => {
-                let _ = r.read_exact(4).unwrap();
-            }
-            other => panic!("unexpected object id tag: {other:?}"),
-        }
-        match Tag::decode(&mut r).unwrap() {
-            Tag::Context { tag_num: 1, len } => {
-                let _ = decode_unsigned(&mut r, len as usize).unwrap();
-            }
-            other => panic!("unexpected property tag: {other:?}"),
-        }
-        assert_eq!(Tag::decode(&mut r).unwrap(), Tag::Opening { tag_num: 7 });
-        match Tag::decode(&mut r).unwrap() {
-            Tag::Application {
-                tag: AppTag::Date,
-                len: 4,
-            } => {
-                let raw = r.read_exact(4).unwrap();
-                assert_eq!(
-                    raw,
-                    &[date.year_since_1900, date.month, date.day, date.weekday]
-                );
-            }
-            other => panic!("unexpected date tag: {other:?}"),
-        }
-        match Tag::decode(&mut r).unwrap() {
-            Tag::Application {
-                tag: AppTag::Time,
-                len: 4,
-            } => {
-                let raw = r.read_exact(4).unwrap();
-                assert_eq!(raw, &[time.hour, time.minute, time.second, time.hundredths]);
+        assert_eq!(sent.len(), 9);
+        assert_eq!(
+            confirmed_request_body(&sent[7].1),
+            (
+                8,
+                write_property_body(WritePropertyRequest {
+                    object_id: objects[1],
+                    property_id: PropertyId::PresentValue,
+                    value: DataValue::Real(20.0),
+                    ..Default::default()
+                })
+            )
+        );
+        assert_eq!(
+            confirmed_request_body(&sent[8].1),
+            (
+                9,
+                write_property_body(WritePropertyRequest {
+                    object_id: objects[0],
+                    property_id: PropertyId::PresentValue,
+                    value: DataValue::Null,
+                    priority: Some(8),
+                    ..Default::default()
+                })
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn write_with_rollback_reports_undos_that_fail() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 45], 47808).into());
+        let first = ObjectId::new(ObjectType::AnalogValue, 1);
+        let second = ObjectId::new(ObjectType::AnalogValue, 2);
+        let third = ObjectId::new(ObjectType::AnalogValue, 3);
+        {
+            let mut recv = state.recv.lock().await;
+            for (invoke_id, object_id) in [(1, first), (2, second), (3, third)] {
+                recv.push_back((
+                    with_npdu(&read_property_real_ack_apdu(invoke_id, object_id, 10.0)),
+                    addr,
+                ));
             }
-            other => panic!("unexpected time tag: {other:?}"),
+            recv.push_back((with_npdu(&simple_ack_apdu(4, SERVICE_WRITE_PROPERTY)), addr));
+            recv.push_back((with_npdu(&simple_ack_apdu(5, SERVICE_WRITE_PROPERTY)), addr));
+            recv.push_back((
+                with_npdu(&[0x50, 6, SERVICE_WRITE_PROPERTY, 0x91, 2, 0x91, 37]),
+                addr,
+            ));
+            // Undoing the second write is refused too; undoing the first succeeds.
+            recv.push_back((
+                with_npdu(&[0x50, 7, SERVICE_WRITE_PROPERTY, 0x91, 2, 0x91, 40]),
+                addr,
+            ));
+            recv.push_back((with_npdu(&simple_ack_apdu(8, SERVICE_WRITE_PROPERTY)), addr));
         }
-        match Tag::decode(&mut r).unwrap() {
-            Tag::Application {
-                tag: AppTag::SignedInt,
-                len,
-            } => {
-                assert_eq!(decode_signed(&mut r, len as usize).unwrap(), 2);
+
+        let writes: Vec<_> = [first, second, third]
+            .into_iter()
+            .map(|object_id| {
+                (
+                    object_id,
+                    PropertyId::PresentValue,
+                    ClientDataValue::Real(11.0),
+                    None,
+                )
+            })
+            .collect();
+        let report = client.write_with_rollback(addr, &writes).await.unwrap();
+
+        assert!(!report.is_committed());
+        assert!(!report.is_fully_rolled_back());
+        assert!(report.applied.is_empty());
+        assert_eq!(report.failed.as_ref().unwrap().item.object_id, third);
+        assert_eq!(report.rollback_failed.len(), 1);
+        let undo = &report.rollback_failed[0];
+        assert_eq!(undo.item.object_id, second);
+        assert_eq!(undo.item.value, ClientDataValue::Real(11.0));
+        assert!(matches!(
+            undo.error.root(),
+            crate::ClientError::RemoteServiceError {
+                error_code_raw: Some(40),
+                ..
             }
-            other => panic!("unexpected count tag: {other:?}"),
-        }
-        assert_eq!(Tag::decode(&mut r).unwrap(), Tag::Closing { tag_num: 7 });
+        ));
+        assert_eq!(report.rolled_back.len(), 1);
+        assert_eq!(report.rolled_back[0].object_id, first);
+        assert_eq!(state.sent.lock().await.len(), 8);
     }
 
     #[tokio::test]
-    async fn recv_unconfirmed_cov_notification_returns_decoded_value() {
+    async fn write_burst_pipelines_writes_within_the_window() {
         let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 12], 47808).into());
+        let client = BacnetClient::with_datalink(dl)
+            .with_response_timeout(Duration::from_millis(50))
+            .with_write_burst_window(2);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 43], 47808).into());
+        {
+            let mut recv = state.recv.lock().await;
+            // Answers arrive out of order; the first write is refused.
+            recv.push_back((with_npdu(&simple_ack_apdu(2, SERVICE_WRITE_PROPERTY)), addr));
+            recv.push_back((
+                with_npdu(&[0x50, 1, SERVICE_WRITE_PROPERTY, 0x91, 2, 0x91, 40]),
+                addr,
+            ));
+            recv.push_back((with_npdu(&simple_ack_apdu(3, SERVICE_WRITE_PROPERTY)), addr));
+        }
 
-        let mut apdu = [0u8; 256];
+        let writes: Vec<_> = (0..4)
+            .map(|instance| {
+                (
+                    ObjectId::new(ObjectType::AnalogValue, instance),
+                    PropertyId::PresentValue,
+                    ClientDataValue::Real(20.0 + instance as f32),
+                    Some(8),
+                )
+            })
+            .collect();
+        let report = client.write_burst(addr, &writes).await.unwrap();
+
+        assert!(!report.is_complete());
+        assert_eq!(report.written, vec![1, 2]);
+        assert_eq!(report.failed.len(), 2);
+        assert_eq!(report.failed[0].index, 0);
+        assert!(matches!(
+            report.failed[0].error.root(),
+            crate::ClientError::RemoteServiceError {
+                error_code_raw: Some(40),
+                ..
+            }
+        ));
+        assert_eq!(report.failed[1].index, 3);
+        assert!(matches!(
+            report.failed[1].error.root(),
+            crate::ClientError::Timeout
+        ));
+
+        // Two requests went out before the first answer; the unanswered fourth one is
+        // aborted after its timeout.
+        let sent = state.sent.lock().await;
+        let invoke_ids: Vec<u8> = sent
+            .iter()
+            .filter_map(|(_, frame)| {
+                let mut r = Reader::new(frame);
+                Npdu::decode(&mut r).unwrap();
+                ConfirmedRequestHeader::decode(&mut r)
+                    .ok()
+                    .map(|hdr| hdr.invoke_id)
+            })
+            .collect();
+        assert_eq!(invoke_ids, vec![1, 2, 3, 4]);
+        assert_eq!(sent.len(), 5);
+        assert_eq!(client.recent_transactions().len(), 4);
+    }
+
+    fn read_property_ack_apdu(
+        invoke_id: u8,
+        object_id: ObjectId,
+        property_id: PropertyId,
+        array_index: Option<u32>,
+        value: &DataValue<'_>,
+    ) -> Vec<u8> {
+        let mut apdu = [0u8; 128];
         let mut w = Writer::new(&mut apdu);
-        UnconfirmedRequestHeader {
-            service_choice: SERVICE_UNCONFIRMED_COV_NOTIFICATION,
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_READ_PROPERTY,
         }
         .encode(&mut w)
         .unwrap();
-        encode_ctx_unsigned(&mut w, 0, 17).unwrap();
-        encode_ctx_unsigned(&mut w, 1, ObjectId::new(ObjectType::Device, 1).raw()).unwrap();
-        encode_ctx_unsigned(&mut w, 2, ObjectId::new(ObjectType::AnalogInput, 1).raw()).unwrap();
-        encode_ctx_unsigned(&mut w, 3, 60).unwrap();
-        Tag::Opening { tag_num: 4 }.encode(&mut w).unwrap();
-        encode_ctx_unsigned(&mut w, 0, PropertyId::PresentValue.to_u32()).unwrap();
-        Tag::Opening { tag_num: 2 }.encode(&mut w).unwrap();
-        encode_app_real(&mut w, 73.25).unwrap();
-        Tag::Closing { tag_num: 2 }.encode(&mut w).unwrap();
-        Tag::Closing { tag_num: 4 }.encode(&mut w).unwrap();
+        encode_ctx_object_id(&mut w, 0, object_id.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, property_id.to_u32()).unwrap();
+        if let Some(index) = array_index {
+            encode_ctx_unsigned(&mut w, 2, index).unwrap();
+        }
+        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+        encode_application_data_value(&mut w, value).unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        w.as_written().to_vec()
+    }
 
-        state
-            .recv
-            .lock()
-            .await
-            .push_back((with_npdu(w.as_written()), addr));
+    #[tokio::test]
+    async fn resolve_object_by_name_uses_i_have_and_caches() {
+        let (dl, state) = MockDataLink::new();
+        let client =
+            BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_millis(20));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 43], 47808).into());
+        let other = DataLinkAddress::Ip(([192, 168, 1, 44], 47808).into());
+
+        for (src, instance) in [(other, 9), (addr, 7)] {
+            let mut apdu = [0u8; 128];
+            let mut w = Writer::new(&mut apdu);
+            UnconfirmedRequestHeader {
+                service_choice: SERVICE_I_HAVE,
+            }
+            .encode(&mut w)
+            .unwrap();
+            encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::Device, 10).raw()).unwrap();
+            encode_ctx_object_id(
+                &mut w,
+                1,
+                ObjectId::new(ObjectType::AnalogInput, instance).raw(),
+            )
+            .unwrap();
+            encode_ctx_character_string(&mut w, 2, "Zone Temp").unwrap();
+            state
+                .recv
+                .lock()
+                .await
+                .push_back((with_npdu(w.as_written()), src));
+        }
 
-        let notification = client
-            .recv_cov_notification(Duration::from_secs(1))
+        let resolved = client
+            .resolve_object_by_name(addr, "Zone Temp")
             .await
-            .unwrap()
             .unwrap();
-        assert!(!notification.confirmed);
-        assert_eq!(notification.subscriber_process_id, 17);
-        assert_eq!(notification.values.len(), 1);
-        assert_eq!(notification.values[0].property_id, PropertyId::PresentValue);
-        assert!(matches!(
-            notification.values[0].value,
-            ClientDataValue::Real(v) if (v - 73.25).abs() < f32::EPSILON
-        ));
+        assert_eq!(resolved, Some(ObjectId::new(ObjectType::AnalogInput, 7)));
 
-        let sent = state.sent.lock().await;
-        assert!(sent.is_empty());
+        // Second lookup is served from the cache without touching the network.
+        let resolved = client
+            .resolve_object_by_name(addr, "Zone Temp")
+            .await
+            .unwrap();
+        assert_eq!(resolved, Some(ObjectId::new(ObjectType::AnalogInput, 7)));
+        assert_eq!(state.sent.lock().await.len(), 1);
     }
 
     #[tokio::test]
-    async fn recv_confirmed_cov_notification_sends_simple_ack() {
+    async fn resolve_object_by_name_falls_back_to_object_list() {
         let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 13], 47808).into());
+        let client =
+            BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_millis(300));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 45], 47808).into());
+        let device = ObjectId::new(ObjectType::Device, 4_194_303);
+        let first = ObjectId::new(ObjectType::AnalogInput, 1);
+        let second = ObjectId::new(ObjectType::AnalogValue, 5);
+        let state_for_task = state.clone();
+
+        // Responses arrive only after the Who-Has window has closed unanswered.
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(450)).await;
+            let list = PropertyId::ObjectList;
+            let name = PropertyId::ObjectName;
+            let acks = [
+                read_property_ack_apdu(1, device, list, Some(0), &DataValue::Unsigned(2)),
+                read_property_ack_apdu(2, device, list, Some(1), &DataValue::ObjectId(first)),
+                read_property_ack_apdu(3, first, name, None, &DataValue::CharacterString("OAT")),
+                read_property_ack_apdu(4, device, list, Some(2), &DataValue::ObjectId(second)),
+                read_property_ack_apdu(
+                    5,
+                    second,
+                    name,
+                    None,
+                    &DataValue::CharacterString("Setpoint"),
+                ),
+            ];
+            let mut recv = state_for_task.recv.lock().await;
+            for ack in acks {
+                recv.push_back((with_npdu(&ack), addr));
+            }
+        });
 
+        let resolved = client
+            .resolve_object_by_name(addr, "Setpoint")
+            .await
+            .unwrap();
+        assert_eq!(resolved, Some(second));
+
+        // Names seen during the walk are cached too.
+        let resolved = client.resolve_object_by_name(addr, "OAT").await.unwrap();
+        assert_eq!(resolved, Some(first));
+        assert_eq!(state.sent.lock().await.len(), 6);
+    }
+
+    fn object_list_rpm_ack_apdu(
+        invoke_id: u8,
+        device_id: ObjectId,
+        elements: &[(u32, ObjectId)],
+    ) -> Vec<u8> {
         let mut apdu = [0u8; 256];
         let mut w = Writer::new(&mut apdu);
-        ConfirmedRequestHeader {
+        ComplexAckHeader {
             segmented: false,
             more_follows: false,
-            segmented_response_accepted: false,
-            max_segments: 0,
-            max_apdu: 5,
-            invoke_id: 9,
+            invoke_id,
             sequence_number: None,
             proposed_window_size: None,
-            service_choice: SERVICE_CONFIRMED_COV_NOTIFICATION,
+            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
         }
         .encode(&mut w)
         .unwrap();
-        encode_ctx_unsigned(&mut w, 0, 18).unwrap();
-        encode_ctx_unsigned(&mut w, 1, ObjectId::new(ObjectType::Device, 1).raw()).unwrap();
-        encode_ctx_unsigned(&mut w, 2, ObjectId::new(ObjectType::AnalogInput, 2).raw()).unwrap();
-        encode_ctx_unsigned(&mut w, 3, 120).unwrap();
-        Tag::Opening { tag_num: 4 }.encode(&mut w).unwrap();
-        encode_ctx_unsigned(&mut w, 0, PropertyId::PresentValue.to_u32()).unwrap();
-        Tag::Opening { tag_num: 2 }.encode(&mut w).unwrap();
-        encode_app_real(&mut w, 55.0).unwrap();
-        Tag::Closing { tag_num: 2 }.encode(&mut w).unwrap();
-        Tag::Closing { tag_num: 4 }.encode(&mut w).unwrap();
+        encode_ctx_object_id(&mut w, 0, device_id.raw()).unwrap();
+        Tag::Opening { tag_num: 1 }.encode(&mut w).unwrap();
+        for (index, object_id) in elements {
+            encode_ctx_unsigned(&mut w, 2, PropertyId::ObjectList.to_u32()).unwrap();
+            encode_ctx_unsigned(&mut w, 3, *index).unwrap();
+            Tag::Opening { tag_num: 4 }.encode(&mut w).unwrap();
+            encode_application_data_value(&mut w, &DataValue::ObjectId(*object_id)).unwrap();
+            Tag::Closing { tag_num: 4 }.encode(&mut w).unwrap();
+        }
+        Tag::Closing { tag_num: 1 }.encode(&mut w).unwrap();
+        w.as_written().to_vec()
+    }
 
-        state
-            .recv
-            .lock()
-            .await
-            .push_back((with_npdu(w.as_written()), addr));
+    #[tokio::test]
+    async fn read_object_list_falls_back_to_chunks_after_abort() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 47], 47808).into());
+        let device_id = ObjectId::new(ObjectType::Device, 47);
+        let objects = [
+            device_id,
+            ObjectId::new(ObjectType::AnalogInput, 1),
+            ObjectId::new(ObjectType::BinaryValue, 2),
+        ];
+        // A 50-octet max APDU fits two Object_List elements per ReadPropertyMultiple ack.
+        client.capability_cache.write().unwrap().insert(addr, 50);
+        {
+            let mut recv = state.recv.lock().await;
+            // Full read: Abort from server, segmentation-not-supported.
+            recv.push_back((
+                with_npdu(&[((ApduType::Abort as u8) << 4) | 0x01, 1, 4]),
+                addr,
+            ));
+            for ack in [
+                read_property_ack_apdu(
+                    2,
+                    device_id,
+                    PropertyId::ObjectList,
+                    Some(0),
+                    &DataValue::Unsigned(3),
+                ),
+                object_list_rpm_ack_apdu(3, device_id, &[(1, objects[0]), (2, objects[1])]),
+                read_property_ack_apdu(
+                    4,
+                    device_id,
+                    PropertyId::ObjectList,
+                    Some(3),
+                    &DataValue::ObjectId(objects[2]),
+                ),
+            ] {
+                recv.push_back((with_npdu(&ack), addr));
+            }
+        }
 
-        let notification = client
-            .recv_cov_notification(Duration::from_secs(1))
-            .await
-            .unwrap()
-            .unwrap();
-        assert!(notification.confirmed);
-        assert_eq!(notification.values.len(), 1);
+        let object_ids = client.read_object_list(addr, device_id).await.unwrap();
+        assert_eq!(object_ids, objects);
 
         let sent = state.sent.lock().await;
-        assert_eq!(sent.len(), 1);
-        let mut r = Reader::new(&sent[0].1);
-        let _npdu = Npdu::decode(&mut r).unwrap();
-        let ack = SimpleAck::decode(&mut r).unwrap();
-        assert_eq!(ack.invoke_id, 9);
-        assert_eq!(ack.service_choice, SERVICE_CONFIRMED_COV_NOTIFICATION);
+        let services: Vec<u8> = sent.iter().map(|(_, frame)| frame[5]).collect();
+        assert_eq!(
+            services,
+            [
+                SERVICE_READ_PROPERTY,
+                SERVICE_READ_PROPERTY,
+                SERVICE_READ_PROPERTY_MULTIPLE,
+                SERVICE_READ_PROPERTY
+            ]
+        );
     }
 
     #[tokio::test]
-    async fn recv_unconfirmed_event_notification_returns_decoded_value() {
+    async fn read_property_set_keeps_values_and_property_errors() {
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 16], 47808).into());
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 49], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogInput, 9);
 
-        let mut apdu = [0u8; 256];
+        let mut apdu = [0u8; 128];
         let mut w = Writer::new(&mut apdu);
-        UnconfirmedRequestHeader {
-            service_choice: SERVICE_UNCONFIRMED_EVENT_NOTIFICATION,
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id: 1,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
         }
         .encode(&mut w)
         .unwrap();
-        encode_ctx_unsigned(&mut w, 0, 99).unwrap();
-        encode_ctx_unsigned(&mut w, 1, ObjectId::new(ObjectType::Device, 1).raw()).unwrap();
-        encode_ctx_unsigned(&mut w, 2, ObjectId::new(ObjectType::AnalogInput, 6).raw()).unwrap();
-        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
-        encode_ctx_unsigned(&mut w, 1, 55).unwrap();
-        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
-        encode_ctx_unsigned(&mut w, 4, 7).unwrap();
-        encode_ctx_unsigned(&mut w, 5, 100).unwrap();
-        encode_ctx_unsigned(&mut w, 6, 2).unwrap();
-        encode_ctx_character_string(&mut w, 7, "fan alarm").unwrap();
-        encode_ctx_unsigned(&mut w, 8, 0).unwrap();
-        Tag::Context { tag_num: 9, len: 1 }.encode(&mut w).unwrap();
-        encode_ctx_unsigned(&mut w, 10, 2).unwrap();
-        encode_ctx_unsigned(&mut w, 11, 0).unwrap();
-        Tag::Opening { tag_num: 12 }.encode(&mut w).unwrap();
-        Tag::Opening { tag_num: 0 }.encode(&mut w).unwrap();
-        encode_ctx_unsigned(&mut w, 0, 1).unwrap();
-        Tag::Closing { tag_num: 0 }.encode(&mut w).unwrap();
-        Tag::Closing { tag_num: 12 }.encode(&mut w).unwrap();
-
+        encode_ctx_object_id(&mut w, 0, object_id.raw()).unwrap();
+        Tag::Opening { tag_num: 1 }.encode(&mut w).unwrap();
+        for (property_id, value) in [
+            (PropertyId::ObjectIdentifier, DataValue::ObjectId(object_id)),
+            (PropertyId::ObjectName, DataValue::CharacterString("OAT")),
+            (PropertyId::PresentValue, DataValue::Real(12.5)),
+        ] {
+            encode_ctx_unsigned(&mut w, 2, property_id.to_u32()).unwrap();
+            Tag::Opening { tag_num: 4 }.encode(&mut w).unwrap();
+            encode_application_data_value(&mut w, &value).unwrap();
+            Tag::Closing { tag_num: 4 }.encode(&mut w).unwrap();
+        }
+        // Description: property / read-access-denied.
+        encode_ctx_unsigned(&mut w, 2, PropertyId::Description.to_u32()).unwrap();
+        Tag::Opening { tag_num: 5 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 0, 2).unwrap();
+        encode_ctx_unsigned(&mut w, 1, 27).unwrap();
+        Tag::Closing { tag_num: 5 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 1 }.encode(&mut w).unwrap();
         state
             .recv
             .lock()
             .await
             .push_back((with_npdu(w.as_written()), addr));
 
-        let notification: EventNotification = client
-            .recv_event_notification(Duration::from_secs(1))
+        let all = client
+            .read_property_set(addr, object_id, PropertyId::All)
             .await
-            .unwrap()
             .unwrap();
-        assert!(!notification.confirmed);
-        assert_eq!(notification.process_id, 99);
-        assert_eq!(notification.message_text.as_deref(), Some("fan alarm"));
-        assert_eq!(notification.ack_required, Some(true));
-        assert_eq!(notification.from_state, Some(EventState::Offnormal));
-        assert_eq!(notification.to_state, Some(EventState::Normal));
-        assert_eq!(notification.notify_type, 0);
+        assert_eq!(all.values.len(), 3);
+        assert_eq!(
+            all.get(PropertyId::PresentValue),
+            Some(&ClientDataValue::Real(12.5))
+        );
+        assert_eq!(all.errors.len(), 1);
+        assert_eq!(all.errors[0].property_id, PropertyId::Description);
+        assert_eq!(all.errors[0].error_code, 27);
+        assert_eq!(
+            all.property_ids(),
+            vec![
+                PropertyId::ObjectIdentifier,
+                PropertyId::ObjectName,
+                PropertyId::PresentValue,
+                PropertyId::Description
+            ]
+        );
 
+        // The request names the property ALL (8).
         let sent = state.sent.lock().await;
-        assert!(sent.is_empty());
+        let frame = &sent[0].1;
+        assert_eq!(&frame[frame.len() - 4..], &[0x1E, 0x09, 0x08, 0x1F]);
     }
 
     #[tokio::test]
-    async fn recv_confirmed_event_notification_sends_simple_ack() {
+    async fn read_property_list_decodes_whole_array() {
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 17], 47808).into());
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 48], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogInput, 4);
 
-        let mut apdu = [0u8; 256];
+        let mut apdu = [0u8; 64];
         let mut w = Writer::new(&mut apdu);
-        ConfirmedRequestHeader {
+        ComplexAckHeader {
             segmented: false,
             more_follows: false,
-            segmented_response_accepted: false,
-            max_segments: 0,
-            max_apdu: 5,
-            invoke_id: 11,
+            invoke_id: 1,
             sequence_number: None,
             proposed_window_size: None,
-            service_choice: SERVICE_CONFIRMED_EVENT_NOTIFICATION,
+            service_choice: SERVICE_READ_PROPERTY,
         }
         .encode(&mut w)
         .unwrap();
-        encode_ctx_unsigned(&mut w, 0, 100).unwrap();
-        encode_ctx_unsigned(&mut w, 1, ObjectId::new(ObjectType::Device, 1).raw()).unwrap();
-        encode_ctx_unsigned(&mut w, 2, ObjectId::new(ObjectType::AnalogInput, 7).raw()).unwrap();
+        encode_ctx_object_id(&mut w, 0, object_id.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::PropertyList.to_u32()).unwrap();
         Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
-        encode_ctx_unsigned(&mut w, 1, 56).unwrap();
+        for pid in [
+            PropertyId::PresentValue,
+            PropertyId::Units,
+            PropertyId::Proprietary(600),
+        ] {
+            encode_application_data_value(&mut w, &DataValue::Enumerated(pid.to_u32())).unwrap();
+        }
         Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
-        encode_ctx_unsigned(&mut w, 4, 7).unwrap();
-        encode_ctx_unsigned(&mut w, 5, 100).unwrap();
-        encode_ctx_unsigned(&mut w, 6, 2).unwrap();
-        encode_ctx_unsigned(&mut w, 8, 0).unwrap();
-        encode_ctx_unsigned(&mut w, 10, 2).unwrap();
-        encode_ctx_unsigned(&mut w, 11, 0).unwrap();
-        Tag::Opening { tag_num: 12 }.encode(&mut w).unwrap();
-        Tag::Opening { tag_num: 0 }.encode(&mut w).unwrap();
-        encode_ctx_unsigned(&mut w, 0, 1).unwrap();
-        Tag::Closing { tag_num: 0 }.encode(&mut w).unwrap();
-        Tag::Closing { tag_num: 12 }.encode(&mut w).unwrap();
-
         state
             .recv
             .lock()
             .await
             .push_back((with_npdu(w.as_written()), addr));
 
-        let notification = client
-            .recv_event_notification(Duration::from_secs(1))
-            .await
-            .unwrap()
-            .unwrap();
-        assert!(notification.confirmed);
-
-        let sent = state.sent.lock().await;
-        assert_eq!(sent.len(), 1);
-        let mut r = Reader::new(&sent[0].1);
-        let _npdu = Npdu::decode(&mut r).unwrap();
-        let ack = SimpleAck::decode(&mut r).unwrap();
-        assert_eq!(ack.invoke_id, 11);
-        assert_eq!(ack.service_choice, SERVICE_CONFIRMED_EVENT_NOTIFICATION);
+        let list = client.read_property_list(addr, object_id).await.unwrap();
+        assert_eq!(
+            list,
+            vec![
+                PropertyId::PresentValue,
+                PropertyId::Units,
+                PropertyId::Proprietary(600)
+            ]
+        );
     }
 
     #[tokio::test]
-    async fn write_property_multiple_segments_large_request() {
+    async fn read_properties_with_fallback_uses_read_property_after_reject() {
         let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 10], 47808).into());
-        let object_id = ObjectId::new(ObjectType::AnalogOutput, 5);
-
+        let client = BacnetClient::with_datalink(dl);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 46], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogInput, 3);
+        let name = PropertyId::ObjectName;
+        let pv = PropertyId::PresentValue;
         {
             let mut recv = state.recv.lock().await;
-            for seq in 0u8..=254 {
-                let mut apdu = [0u8; 16];
-                let mut w = Writer::new(&mut apdu);
-                SegmentAck {
-                    negative_ack: false,
-                    sent_by_server: true,
-                    invoke_id: 1,
-                    sequence_number: seq,
-                    actual_window_size: 1,
-                }
-                .encode(&mut w)
-                .unwrap();
-                recv.push_back((with_npdu(w.as_written()), addr));
-            }
-
-            let mut apdu = [0u8; 16];
-            let mut w = Writer::new(&mut apdu);
-            SimpleAck {
-                invoke_id: 1,
-                service_choice: SERVICE_WRITE_PROPERTY_MULTIPLE,
+            // ReadPropertyMultiple: Reject, unrecognized-service.
+            recv.push_back((with_npdu(&[(ApduType::Reject as u8) << 4, 1, 9]), addr));
+            for ack in [
+                read_property_ack_apdu(
+                    2,
+                    object_id,
+                    name,
+                    None,
+                    &DataValue::CharacterString("OAT"),
+                ),
+                read_property_ack_apdu(3, object_id, pv, None, &DataValue::Real(12.5)),
+                read_property_ack_apdu(4, object_id, pv, None, &DataValue::Real(13.0)),
+            ] {
+                recv.push_back((with_npdu(&ack), addr));
             }
-            .encode(&mut w)
-            .unwrap();
-            recv.push_back((with_npdu(w.as_written()), addr));
         }
 
-        let writes: Vec<PropertyWriteSpec> = (0..180)
-            .map(|_| PropertyWriteSpec {
-                property_id: PropertyId::Description,
-                array_index: None,
-                value: DataValue::CharacterString(
-                    "rustbac segmented write test payload................................................................",
-                ),
-                priority: None,
-            })
-            .collect();
-
-        client
-            .write_property_multiple(addr, object_id, &writes)
+        let values = client
+            .read_properties_with_fallback(addr, object_id, &[name, pv])
             .await
             .unwrap();
+        assert_eq!(
+            values,
+            vec![
+                (name, ClientDataValue::CharacterString("OAT".into())),
+                (pv, ClientDataValue::Real(12.5)),
+            ]
+        );
+        assert!(client.uses_rpm_fallback(addr));
 
+        // The device is remembered: the next read skips ReadPropertyMultiple.
+        let values = client
+            .read_properties_with_fallback(addr, object_id, &[pv])
+            .await
+            .unwrap();
+        assert_eq!(values, vec![(pv, ClientDataValue::Real(13.0))]);
         let sent = state.sent.lock().await;
-        assert!(sent.len() > 1);
-
-        let mut seqs = Vec::new();
-        let mut saw_more_follows = false;
-        let mut saw_last = false;
-        for (_, frame) in sent.iter() {
-            let mut r = Reader::new(frame);
-            let _npdu = Npdu::decode(&mut r).unwrap();
-            let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-            assert!(hdr.segmented);
-            assert_eq!(hdr.service_choice, SERVICE_WRITE_PROPERTY_MULTIPLE);
-            if hdr.more_follows {
-                saw_more_follows = true;
-            } else {
-                saw_last = true;
-            }
-            seqs.push(hdr.sequence_number.unwrap());
+        assert_eq!(sent.len(), 4);
+        assert!(sent[1..].iter().all(|(_, frame)| {
+            frame[2] >> 4 == ApduType::ConfirmedRequest as u8 && frame[5] == SERVICE_READ_PROPERTY
+        }));
+    }
+
+    /// Delegates to Tokio but counts the sleeps the client asks for.
+    #[derive(Debug)]
+    struct CountingRuntime(std::sync::Arc<std::sync::atomic::AtomicUsize>);
+
+    impl Runtime for CountingRuntime {
+        fn spawn(&self, future: crate::runtime::BoxFuture<()>) -> Box<dyn TaskHandle> {
+            TokioRuntime.spawn(future)
         }
 
-        assert!(saw_more_follows);
-        assert!(saw_last);
-        for (idx, seq) in seqs.iter().enumerate() {
-            assert_eq!(*seq as usize, idx);
+        fn sleep(&self, duration: Duration) -> crate::runtime::BoxFuture<()> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            TokioRuntime.sleep(duration)
+        }
+
+        fn now(&self) -> std::time::Instant {
+            TokioRuntime.now()
         }
     }
 
     #[tokio::test]
-    async fn write_property_multiple_uses_configured_segment_window() {
-        let (dl, state) = MockDataLink::new();
+    async fn timers_run_on_the_configured_runtime() {
+        let (dl, _state) = MockDataLink::new();
+        let sleeps = std::sync::Arc::default();
         let client = BacnetClient::with_datalink(dl)
-            .with_response_timeout(Duration::from_secs(1))
-            .with_segmented_request_window_size(4);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 14], 47808).into());
-        let object_id = ObjectId::new(ObjectType::AnalogOutput, 6);
+            .with_response_timeout(Duration::from_millis(20))
+            .with_runtime(CountingRuntime(std::sync::Arc::clone(&sleeps)));
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 61], 47808).into());
+        let err = client
+            .read_property(
+                addr,
+                ObjectId::new(ObjectType::AnalogValue, 1),
+                PropertyId::PresentValue,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err.root(), crate::ClientError::Timeout));
+        assert!(sleeps.load(std::sync::atomic::Ordering::SeqCst) > 0);
+    }
 
-        {
-            let mut recv = state.recv.lock().await;
-            for seq in 0u8..=254 {
-                let mut apdu = [0u8; 16];
-                let mut w = Writer::new(&mut apdu);
-                SegmentAck {
-                    negative_ack: false,
-                    sent_by_server: true,
-                    invoke_id: 1,
-                    sequence_number: seq,
-                    actual_window_size: 4,
-                }
-                .encode(&mut w)
-                .unwrap();
-                recv.push_back((with_npdu(w.as_written()), addr));
-            }
+    #[tokio::test]
+    async fn new_sc_rejects_invalid_endpoint() {
+        let err = BacnetClient::new_sc("not a url").await.unwrap_err();
+        assert!(matches!(err, crate::ClientError::DataLink(_)));
+    }
 
-            let mut apdu = [0u8; 16];
-            let mut w = Writer::new(&mut apdu);
-            SimpleAck {
-                invoke_id: 1,
-                service_choice: SERVICE_WRITE_PROPERTY_MULTIPLE,
-            }
-            .encode(&mut w)
+    #[tokio::test]
+    async fn recent_transactions_records_outcomes() {
+        let (dl, state) = MockDataLink::new();
+        let client = BacnetClient::with_datalink(dl)
+            .with_response_timeout(Duration::from_millis(50))
+            .with_transaction_log_capacity(2);
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 60], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogValue, 1);
+        state.recv.lock().await.push_back((
+            with_npdu(&read_property_real_ack_apdu(1, object_id, 4.5)),
+            addr,
+        ));
+
+        client
+            .read_property(addr, object_id, PropertyId::PresentValue)
+            .await
             .unwrap();
-            recv.push_back((with_npdu(w.as_written()), addr));
-        }
+        let err = client
+            .read_property(addr, object_id, PropertyId::PresentValue)
+            .await
+            .unwrap_err();
+        assert!(matches!(err.root(), crate::ClientError::Timeout));
+        assert!(err.is_retryable());
+
+        let records = client.recent_transactions();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].invoke_id, 1);
+        assert_eq!(records[0].service_choice, SERVICE_READ_PROPERTY);
+        assert_eq!(records[0].address, addr);
+        assert!(matches!(
+            records[0].outcome,
+            crate::TransactionOutcome::ComplexAck { payload_len } if payload_len > 0
+        ));
+        assert_eq!(records[1].invoke_id, 2);
+        assert_eq!(records[1].outcome, crate::TransactionOutcome::Timeout);
+        assert!(records[1].elapsed >= Duration::from_millis(50));
 
-        let writes: Vec<PropertyWriteSpec> = (0..180)
-            .map(|_| PropertyWriteSpec {
-                property_id: PropertyId::Description,
-                array_index: None,
-                value: DataValue::CharacterString(
-                    "rustbac segmented write test payload................................................................",
-                ),
-                priority: None,
-            })
-            .collect();
+        client.clear_transactions();
+        assert!(client.recent_transactions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn transaction_journal_is_shared_by_clones_and_outlives_the_log() {
+        let (dl, state) = MockDataLink::new();
+        let journal = crate::TransactionJournal::new();
+        let client = BacnetClient::with_datalink(dl)
+            .with_response_timeout(Duration::from_millis(20))
+            .with_transaction_log_capacity(1)
+            .with_transaction_journal(journal.clone());
+        let clone = client.clone();
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 62], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogValue, 1);
+        state.recv.lock().await.push_back((
+            with_npdu(&read_property_real_ack_apdu(1, object_id, 4.5)),
+            addr,
+        ));
 
         client
-            .write_property_multiple(addr, object_id, &writes)
+            .read_property(addr, object_id, PropertyId::PresentValue)
             .await
             .unwrap();
+        clone
+            .read_property(addr, object_id, PropertyId::PresentValue)
+            .await
+            .unwrap_err();
 
-        let sent = state.sent.lock().await;
-        assert!(sent.len() > 4);
-        for (idx, (_, frame)) in sent.iter().take(4).enumerate() {
-            let mut r = Reader::new(frame);
-            let _npdu = Npdu::decode(&mut r).unwrap();
-            let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-            assert!(hdr.segmented);
-            assert_eq!(hdr.service_choice, SERVICE_WRITE_PROPERTY_MULTIPLE);
-            assert_eq!(hdr.sequence_number, Some(idx as u8));
-            assert_eq!(hdr.proposed_window_size, Some(4));
-        }
+        assert_eq!(client.recent_transactions().len(), 1);
+        let entries = journal.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].sequence, 0);
+        assert_eq!(entries[1].record.invoke_id, 2);
+        assert_eq!(
+            entries[1].record.outcome,
+            crate::TransactionOutcome::Timeout
+        );
+
+        let timeouts = journal.query(
+            &crate::JournalFilter::default()
+                .with_address(addr)
+                .with_failures_only(),
+        );
+        assert_eq!(timeouts.len(), 1);
+        let found = journal
+            .find(addr, 2, entries[1].completed_at())
+            .expect("second transaction");
+        assert_eq!(found.sequence, 1);
+        assert!(journal.to_json().contains("\"kind\":\"timeout\""));
     }
 
     #[tokio::test]
-    async fn write_property_multiple_adapts_window_to_peer_ack_window() {
+    async fn write_policy_denies_or_holds_writes_before_sending() {
+        use crate::{RequestOptions, WriteDecision, WriteRule, WriteRules};
+        use rustbac_core::services::device_management::ReinitializeState;
+
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl)
-            .with_response_timeout(Duration::from_secs(1))
-            .with_segmented_request_window_size(4);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 19], 47808).into());
-        let object_id = ObjectId::new(ObjectType::AnalogOutput, 9);
+            .with_response_timeout(Duration::from_millis(20))
+            .with_write_policy(
+                WriteRules::new()
+                    .with_rule(WriteRule::deny("manual life safety").with_priorities(1, 2))
+                    .with_rule(
+                        WriteRule::confirm("plant setpoint")
+                            .with_object_type(ObjectType::AnalogValue),
+                    )
+                    .with_device_management(WriteDecision::deny("no restarts")),
+            );
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 64], 47808).into());
+        let setpoint = ObjectId::new(ObjectType::AnalogValue, 1);
+        let writes = |priority| {
+            vec![(
+                setpoint,
+                PropertyId::PresentValue,
+                ClientDataValue::Real(7.0),
+                Some(priority),
+            )]
+        };
 
-        {
-            let mut recv = state.recv.lock().await;
-            for seq in 0u8..=254 {
-                let mut apdu = [0u8; 16];
-                let mut w = Writer::new(&mut apdu);
-                SegmentAck {
-                    negative_ack: false,
-                    sent_by_server: true,
-                    invoke_id: 1,
-                    sequence_number: seq,
-                    actual_window_size: 2,
-                }
-                .encode(&mut w)
-                .unwrap();
-                recv.push_back((with_npdu(w.as_written()), addr));
-            }
+        let err = client.write_many(addr, &writes(1)).await.unwrap_err();
+        assert!(
+            matches!(err, ClientError::WriteDenied { ref reason } if reason == "manual life safety")
+        );
+        let err = client.write_many(addr, &writes(8)).await.unwrap_err();
+        assert!(matches!(err, ClientError::WriteNeedsConfirmation { .. }));
+        let err = client
+            .reinitialize_device(addr, ReinitializeState::Warmstart, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClientError::WriteDenied { .. }));
+        assert!(state.sent.lock().await.is_empty());
 
-            let mut apdu = [0u8; 16];
-            let mut w = Writer::new(&mut apdu);
-            SimpleAck {
-                invoke_id: 1,
-                service_choice: SERVICE_WRITE_PROPERTY_MULTIPLE,
-            }
-            .encode(&mut w)
-            .unwrap();
-            recv.push_back((with_npdu(w.as_written()), addr));
-        }
+        let confirmed = client.with_options(RequestOptions::default().with_confirmed_writes());
+        state.recv.lock().await.push_back((
+            with_npdu(&simple_ack_apdu(1, SERVICE_WRITE_PROPERTY_MULTIPLE)),
+            addr,
+        ));
+        confirmed.write_many(addr, &writes(8)).await.unwrap();
+        let err = confirmed.write_many(addr, &writes(2)).await.unwrap_err();
+        assert!(matches!(err, ClientError::WriteDenied { .. }));
+        assert_eq!(state.sent.lock().await.len(), 1);
+    }
 
-        let writes: Vec<PropertyWriteSpec> = (0..180)
-            .map(|_| PropertyWriteSpec {
-                property_id: PropertyId::Description,
-                array_index: None,
-                value: DataValue::CharacterString(
-                    "rustbac segmented write test payload................................................................",
-                ),
-                priority: None,
-            })
-            .collect();
+    #[tokio::test]
+    async fn write_audit_hook_sees_write_class_requests() {
+        use crate::{PropertyWrite, WriteAction, WriteAuditEvent};
+        use rustbac_core::services::object_management::SERVICE_DELETE_OBJECT;
+        use rustbac_core::services::write_property::WritePropertyRequest;
 
+        let (dl, state) = MockDataLink::new();
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let client = BacnetClient::with_datalink(dl)
+            .with_response_timeout(Duration::from_millis(20))
+            .with_write_audit(move |event: &WriteAuditEvent| {
+                sink.lock().unwrap().push(event.clone());
+            });
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 63], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogValue, 4);
+        {
+            let mut recv = state.recv.lock().await;
+            recv.push_back((with_npdu(&simple_ack_apdu(1, SERVICE_WRITE_PROPERTY)), addr));
+            // Error: object (1) / object-deletion-not-permitted (23).
+            recv.push_back((
+                with_npdu(&[0x50, 2, SERVICE_DELETE_OBJECT, 0x91, 1, 0x91, 23]),
+                addr,
+            ));
+        }
         client
-            .write_property_multiple(addr, object_id, &writes)
+            .write_property(
+                addr,
+                WritePropertyRequest {
+                    object_id,
+                    property_id: PropertyId::PresentValue,
+                    value: DataValue::Real(21.5),
+                    priority: Some(8),
+                    ..Default::default()
+                },
+            )
             .await
             .unwrap();
+        client.delete_object(addr, object_id).await.unwrap_err();
+        client
+            .read_property(addr, object_id, PropertyId::PresentValue)
+            .await
+            .unwrap_err();
 
-        let sent = state.sent.lock().await;
-        let mut saw_adapted_window = false;
-        for (_, frame) in sent.iter() {
-            let mut r = Reader::new(frame);
-            let _npdu = Npdu::decode(&mut r).unwrap();
-            let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-            if hdr.sequence_number.unwrap_or(0) >= 4 && hdr.proposed_window_size == Some(2) {
-                saw_adapted_window = true;
-                break;
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].address, addr);
+        assert_eq!(
+            events[0].action,
+            WriteAction::WriteProperty(PropertyWrite {
+                object_id,
+                property_id: PropertyId::PresentValue,
+                array_index: None,
+                value: ClientDataValue::Real(21.5),
+                priority: Some(8),
+            })
+        );
+        assert!(events[0].outcome.is_success());
+        assert_eq!(events[1].action, WriteAction::DeleteObject { object_id });
+        assert_eq!(
+            events[1].outcome,
+            crate::TransactionOutcome::Error {
+                error_class_raw: Some(1),
+                error_code_raw: Some(23),
             }
-        }
-        assert!(saw_adapted_window);
+        );
     }
 
     #[tokio::test]
-    async fn write_property_multiple_retries_segment_batch_on_negative_ack() {
+    async fn write_audit_hook_reports_every_write_class_service_and_outcome() {
+        use crate::{PropertyWrite, TransactionOutcome, WriteAction, WriteAuditEvent};
+        use rustbac_core::services::device_management::{
+            DeviceCommunicationState, ReinitializeState,
+        };
+        use rustbac_core::services::object_management::CreateObjectSpecifier;
+        use rustbac_core::services::write_property_multiple::PropertyWriteSpec;
+
         let (dl, state) = MockDataLink::new();
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = events.clone();
         let client = BacnetClient::with_datalink(dl)
-            .with_response_timeout(Duration::from_secs(1))
-            .with_segmented_request_window_size(1)
-            .with_segmented_request_retries(1);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 15], 47808).into());
-        let object_id = ObjectId::new(ObjectType::AnalogOutput, 7);
-
+            .with_response_timeout(Duration::from_millis(20))
+            .with_write_audit(move |event: &WriteAuditEvent| {
+                sink.lock().unwrap().push(event.clone());
+            });
+        let addr = DataLinkAddress::Ip(([192, 168, 1, 64], 47808).into());
+        let object_id = ObjectId::new(ObjectType::AnalogValue, 5);
+        let created = ObjectId::new(ObjectType::AnalogValue, 42);
         {
             let mut recv = state.recv.lock().await;
+            // WritePropertyMultiple is rejected (reason 2); CreateObject succeeds;
+            // DeviceCommunicationControl (3) is not answered.
+            recv.push_back((with_npdu(&[0x60, 1, 2]), addr));
+            recv.push_back((with_npdu(&create_object_ack_apdu(2, created)), addr));
+        }
 
-            let mut nack_apdu = [0u8; 16];
-            let mut nack_w = Writer::new(&mut nack_apdu);
-            SegmentAck {
-                negative_ack: true,
-                sent_by_server: true,
-                invoke_id: 1,
-                sequence_number: 0,
-                actual_window_size: 1,
-            }
-            .encode(&mut nack_w)
+        client
+            .write_property_multiple(
+                addr,
+                object_id,
+                &[PropertyWriteSpec {
+                    property_id: PropertyId::PresentValue,
+                    array_index: None,
+                    value: DataValue::Real(1.5),
+                    priority: Some(10),
+                }],
+            )
+            .await
+            .unwrap_err();
+        client
+            .create_object_by_type(addr, ObjectType::AnalogValue)
+            .await
             .unwrap();
-            recv.push_back((with_npdu(nack_w.as_written()), addr));
-
-            for seq in 0u8..=254 {
-                let mut apdu = [0u8; 16];
-                let mut w = Writer::new(&mut apdu);
-                SegmentAck {
-                    negative_ack: false,
-                    sent_by_server: true,
-                    invoke_id: 1,
-                    sequence_number: seq,
-                    actual_window_size: 1,
-                }
-                .encode(&mut w)
+        client
+            .device_communication_control(
+                addr,
+                Some(5),
+                DeviceCommunicationState::Disable,
+                Some("secret"),
+            )
+            .await
+            .unwrap_err();
+        // The unanswered request was followed by the client's TSM-timeout Abort.
+        let reinitialize_id = {
+            let sent = state.sent.lock().await;
+            let last_request = sent
+                .iter()
+                .filter_map(|(_, frame)| {
+                    let mut r = Reader::new(frame);
+                    Npdu::decode(&mut r).ok()?;
+                    ConfirmedRequestHeader::decode(&mut r).ok()
+                })
+                .last()
                 .unwrap();
-                recv.push_back((with_npdu(w.as_written()), addr));
-            }
-
-            let mut apdu = [0u8; 16];
-            let mut w = Writer::new(&mut apdu);
-            SimpleAck {
-                invoke_id: 1,
-                service_choice: SERVICE_WRITE_PROPERTY_MULTIPLE,
-            }
-            .encode(&mut w)
-            .unwrap();
-            recv.push_back((with_npdu(w.as_written()), addr));
+            last_request.invoke_id + 1
+        };
+        {
+            let mut recv = state.recv.lock().await;
+            // ReinitializeDevice is aborted by the server (reason 4, security error).
+            recv.push_back((with_npdu(&[0x71, reinitialize_id, 4]), addr));
+            // The second write of the burst is refused: property (2) / write-access-denied (40).
+            recv.push_back((
+                with_npdu(&simple_ack_apdu(
+                    reinitialize_id + 1,
+                    SERVICE_WRITE_PROPERTY,
+                )),
+                addr,
+            ));
+            recv.push_back((
+                with_npdu(&[
+                    0x50,
+                    reinitialize_id + 2,
+                    SERVICE_WRITE_PROPERTY,
+                    0x91,
+                    2,
+                    0x91,
+                    40,
+                ]),
+                addr,
+            ));
         }
+        client
+            .reinitialize_device(addr, ReinitializeState::Warmstart, Some("secret"))
+            .await
+            .unwrap_err();
+        let burst = [
+            (
+                object_id,
+                PropertyId::PresentValue,
+                ClientDataValue::Real(2.0),
+                None,
+            ),
+            (
+                object_id,
+                PropertyId::OutOfService,
+                ClientDataValue::Boolean(true),
+                None,
+            ),
+        ];
+        let report = client.write_burst(addr, &burst).await.unwrap();
+        assert_eq!(report.failed.len(), 1);
 
-        let writes: Vec<PropertyWriteSpec> = (0..180)
-            .map(|_| PropertyWriteSpec {
-                property_id: PropertyId::Description,
+        let events = events.lock().unwrap();
+        let seen: Vec<_> = events
+            .iter()
+            .map(|event| (event.action.clone(), event.outcome.clone()))
+            .collect();
+        let burst_write = |property_id, value| {
+            WriteAction::WriteProperty(PropertyWrite {
+                object_id,
+                property_id,
                 array_index: None,
-                value: DataValue::CharacterString(
-                    "rustbac segmented write test payload................................................................",
-                ),
+                value,
                 priority: None,
             })
-            .collect();
-
-        client
-            .write_property_multiple(addr, object_id, &writes)
-            .await
-            .unwrap();
-
-        let sent = state.sent.lock().await;
-        let mut seq0_frames = 0usize;
-        for (_, frame) in sent.iter() {
-            let mut r = Reader::new(frame);
-            let _npdu = Npdu::decode(&mut r).unwrap();
-            let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
-            if hdr.sequence_number == Some(0) {
-                seq0_frames += 1;
-            }
-        }
-        assert!(seq0_frames >= 2);
+        };
+        assert_eq!(
+            seen,
+            vec![
+                (
+                    WriteAction::WritePropertyMultiple(vec![PropertyWrite {
+                        object_id,
+                        property_id: PropertyId::PresentValue,
+                        array_index: None,
+                        value: ClientDataValue::Real(1.5),
+                        priority: Some(10),
+                    }]),
+                    TransactionOutcome::Reject { reason: 2 },
+                ),
+                (
+                    WriteAction::CreateObject {
+                        specifier: CreateObjectSpecifier::ObjectType(ObjectType::AnalogValue),
+                        created: Some(created),
+                    },
+                    TransactionOutcome::SimpleAck,
+                ),
+                (
+                    WriteAction::DeviceCommunicationControl {
+                        state: DeviceCommunicationState::Disable,
+                        time_duration_seconds: Some(5),
+                    },
+                    TransactionOutcome::Timeout,
+                ),
+                (
+                    WriteAction::ReinitializeDevice {
+                        state: ReinitializeState::Warmstart,
+                    },
+                    TransactionOutcome::Abort {
+                        reason: 4,
+                        server: true,
+                    },
+                ),
+                (
+                    burst_write(PropertyId::PresentValue, ClientDataValue::Real(2.0)),
+                    TransactionOutcome::SimpleAck,
+                ),
+                (
+                    burst_write(PropertyId::OutOfService, ClientDataValue::Boolean(true)),
+                    TransactionOutcome::Error {
+                        error_class_raw: Some(2),
+                        error_code_raw: Some(40),
+                    },
+                ),
+            ]
+        );
+        assert!(events.iter().all(|event| event.address == addr));
     }
 
     #[tokio::test]
-    async fn read_property_ignores_invalid_frames_until_valid_response() {
-        let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl).with_response_timeout(Duration::from_secs(1));
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 16], 47808).into());
-        let state_for_task = state.clone();
+    async fn foreign_device_renewal_reports_nak_and_recovers() {
+        use super::ForeignDeviceStatus;
+        use rustbac_datalink::bip::transport::BacnetIpTransport;
+        use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+        use tokio::net::UdpSocket;
 
-        tokio::spawn(async move {
-            tokio::time::sleep(Duration::from_millis(20)).await;
-            let mut apdu = [0u8; 128];
-            let mut w = Writer::new(&mut apdu);
-            ComplexAckHeader {
-                segmented: false,
-                more_follows: false,
-                invoke_id: 1,
-                sequence_number: None,
-                proposed_window_size: None,
-                service_choice: SERVICE_READ_PROPERTY,
-            }
-            .encode(&mut w)
+        let bbmd = UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0))
+            .await
             .unwrap();
-            encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::Device, 1).raw()).unwrap();
-            encode_ctx_unsigned(&mut w, 1, PropertyId::PresentValue.to_u32()).unwrap();
-            Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
-            encode_app_real(&mut w, 77.0).unwrap();
-            Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
-            state_for_task
-                .recv
-                .lock()
-                .await
-                .push_back((with_npdu(w.as_written()), addr));
+        let bbmd_addr = bbmd.local_addr().unwrap();
+        let datalink = BacnetIpTransport::bind_foreign(
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+            bbmd_addr,
+        )
+        .await
+        .unwrap();
+        let client = BacnetClient::with_datalink(datalink);
+
+        // First renewal is NAKed (Register-Foreign-Device NAK, 0x0030), the retry is accepted.
+        let responder = tokio::spawn(async move {
+            let mut recv = [0u8; 64];
+            for code in [0x0030u16, 0x0000] {
+                let (_, src) = bbmd.recv_from(&mut recv).await.unwrap();
+                assert_eq!(recv[1], 0x05);
+                let [hi, lo] = code.to_be_bytes();
+                let reply = [0x81, 0x00, 0x00, 0x06, hi, lo];
+                bbmd.send_to(&reply, src).await.unwrap();
+            }
         });
 
-        let value = client
-            .read_property(
-                addr,
-                ObjectId::new(ObjectType::Device, 1),
-                PropertyId::PresentValue,
-            )
+        let renewal = client.start_foreign_device_renewal(1).unwrap();
+        assert_eq!(renewal.status(), ForeignDeviceStatus::Registered);
+        let mut status = renewal.subscribe();
+
+        let wait = std::time::Duration::from_secs(5);
+        tokio::time::timeout(wait, status.changed())
             .await
+            .unwrap()
             .unwrap();
-        assert!(matches!(
-            value,
-            ClientDataValue::Real(v) if (v - 77.0).abs() < f32::EPSILON
-        ));
+        assert_eq!(*status.borrow(), ForeignDeviceStatus::Expired);
+        tokio::time::timeout(wait, status.changed())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(renewal.status(), ForeignDeviceStatus::Registered);
+        responder.await.unwrap();
     }
 
     #[tokio::test]
-    async fn read_property_maps_reject() {
-        let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 7], 47808).into());
+    async fn register_foreign_device_nak_maps_to_bbmd_nak() {
+        use rustbac_datalink::bip::transport::BacnetIpTransport;
+        use rustbac_datalink::BvlcResultCode;
+        use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+        use tokio::net::UdpSocket;
 
-        let mut apdu = [0u8; 8];
-        let mut w = Writer::new(&mut apdu);
-        w.write_u8((ApduType::Reject as u8) << 4).unwrap();
-        w.write_u8(1).unwrap(); // invoke id
-        w.write_u8(2).unwrap(); // reason
-        state
-            .recv
-            .lock()
+        let bbmd = UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0))
             .await
-            .push_back((with_npdu(w.as_written()), addr));
+            .unwrap();
+        let bbmd_addr = bbmd.local_addr().unwrap();
+        let datalink = BacnetIpTransport::bind_foreign(
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+            bbmd_addr,
+        )
+        .await
+        .unwrap();
+        let client = BacnetClient::with_datalink(datalink);
 
-        let err = client
-            .read_property(
-                addr,
-                ObjectId::new(ObjectType::Device, 1),
-                PropertyId::ObjectName,
-            )
-            .await
-            .unwrap_err();
+        let responder = tokio::spawn(async move {
+            let mut recv = [0u8; 64];
+            let (_, src) = bbmd.recv_from(&mut recv).await.unwrap();
+            bbmd.send_to(&[0x81, 0x00, 0x00, 0x06, 0x00, 0x30], src)
+                .await
+                .unwrap();
+        });
+
+        let err = client.register_foreign_device(60).await.unwrap_err();
+        responder.await.unwrap();
         assert!(matches!(
             err,
-            crate::ClientError::RemoteReject { reason: 2 }
+            crate::ClientError::BbmdNak(BvlcResultCode::RegisterForeignDeviceNak)
         ));
+        assert_eq!(err.code(), "client.bbmd_nak");
+        assert!(!err.is_retryable());
+        assert!(err
+            .to_string()
+            .contains("BBMD rejected the request: Register-Foreign-Device-NAK (0x0030)"));
     }
 
     #[tokio::test]
-    async fn read_property_maps_remote_error_details() {
-        let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 17], 47808).into());
-
-        let mut apdu = [0u8; 16];
-        let mut w = Writer::new(&mut apdu);
-        w.write_u8((ApduType::Error as u8) << 4).unwrap();
-        w.write_u8(1).unwrap(); // invoke id
-        w.write_u8(rustbac_core::services::read_property::SERVICE_READ_PROPERTY)
-            .unwrap();
-        Tag::Context { tag_num: 0, len: 1 }.encode(&mut w).unwrap();
-        w.write_u8(2).unwrap(); // property class
-        Tag::Context { tag_num: 1, len: 1 }.encode(&mut w).unwrap();
-        w.write_u8(32).unwrap(); // unknownProperty
+    async fn replace_datalink_fails_in_flight_request_and_uses_new_link() {
+        use rustbac_datalink::bip::transport::BacnetIpTransport;
+        use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+        use tokio::net::UdpSocket;
+
+        let loopback = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let device = UdpSocket::bind(loopback).await.unwrap();
+        let device_addr = DataLinkAddress::Ip(device.local_addr().unwrap());
+        let client = Arc::new(BacnetClient::with_swappable_datalink(
+            BacnetIpTransport::bind(loopback).await.unwrap(),
+        ));
+        let object_id = ObjectId::new(ObjectType::AnalogValue, 1);
 
-        state
-            .recv
-            .lock()
-            .await
-            .push_back((with_npdu(w.as_written()), addr));
+        let in_flight = tokio::spawn({
+            let client = client.clone();
+            async move {
+                client
+                    .read_property(device_addr, object_id, PropertyId::PresentValue)
+                    .await
+            }
+        });
+        let mut frame = [0u8; 256];
+        device.recv_from(&mut frame).await.unwrap();
+        client.replace_datalink(BacnetIpTransport::bind(loopback).await.unwrap());
 
-        let err = client
-            .read_property(
-                addr,
-                ObjectId::new(ObjectType::Device, 1),
-                PropertyId::ObjectName,
-            )
+        let err = tokio::time::timeout(Duration::from_secs(1), in_flight)
             .await
+            .unwrap()
+            .unwrap()
             .unwrap_err();
         assert!(matches!(
-            err,
-            crate::ClientError::RemoteServiceError {
-                service_choice: rustbac_core::services::read_property::SERVICE_READ_PROPERTY,
-                error_class_raw: Some(2),
-                error_code_raw: Some(32),
-                error_class: Some(rustbac_core::types::ErrorClass::Property),
-                error_code: Some(rustbac_core::types::ErrorCode::UnknownProperty),
-            }
+            err.root(),
+            crate::ClientError::DataLink(DataLinkError::Disconnected)
         ));
-    }
-
-    #[tokio::test]
-    async fn write_property_maps_abort() {
-        let (dl, state) = MockDataLink::new();
-        let client = BacnetClient::with_datalink(dl);
-        let addr = DataLinkAddress::Ip(([192, 168, 1, 8], 47808).into());
+        assert!(err.is_retryable());
 
-        let mut apdu = [0u8; 8];
-        let mut w = Writer::new(&mut apdu);
-        w.write_u8(((ApduType::Abort as u8) << 4) | 0x01).unwrap(); // server abort
-        w.write_u8(1).unwrap(); // invoke id
-        w.write_u8(9).unwrap(); // reason
-        state
-            .recv
-            .lock()
+        let responder = tokio::spawn(async move {
+            let (n, src) = device.recv_from(&mut frame).await.unwrap();
+            // BVLC (4) + NPDU (2) precede the confirmed-request header.
+            let invoke_id = frame[..n][8];
+            let npdu = with_npdu(&read_property_ack_apdu(
+                invoke_id,
+                object_id,
+                PropertyId::PresentValue,
+                None,
+                &DataValue::Real(7.5),
+            ));
+            let mut reply = vec![0x81, 0x0A];
+            reply.extend_from_slice(&((npdu.len() + 4) as u16).to_be_bytes());
+            reply.extend_from_slice(&npdu);
+            device.send_to(&reply, src).await.unwrap();
+        });
+        let value = client
+            .read_property(device_addr, object_id, PropertyId::PresentValue)
             .await
-            .push_back((with_npdu(w.as_written()), addr));
-
-        let req = rustbac_core::services::write_property::WritePropertyRequest {
-            object_id: ObjectId::new(ObjectType::AnalogOutput, 1),
-            property_id: PropertyId::PresentValue,
-            value: DataValue::Real(10.0),
-            priority: Some(8),
-            ..Default::default()
-        };
-        let err = client.write_property(addr, req).await.unwrap_err();
-        assert!(matches!(
-            err,
-            crate::ClientError::RemoteAbort {
-                reason: 9,
-                server: true
-            }
-        ));
+            .unwrap();
+        assert_eq!(value, ClientDataValue::Real(7.5));
+        responder.await.unwrap();
     }
 
     #[tokio::test]
-    async fn read_property_multiple_returns_owned_string() {
+    async fn clones_share_invoke_ids_and_transaction_log() {
         let (dl, state) = MockDataLink::new();
         let client = BacnetClient::with_datalink(dl);
+        let clone = client
+            .clone()
+            .with_response_timeout(Duration::from_millis(50));
         let addr = DataLinkAddress::Ip(([192, 168, 1, 9], 47808).into());
-        let object_id = ObjectId::new(ObjectType::Device, 1);
-
-        let mut apdu_buf = [0u8; 256];
-        let mut w = Writer::new(&mut apdu_buf);
-        ComplexAckHeader {
-            segmented: false,
-            more_follows: false,
-            invoke_id: 1,
-            sequence_number: None,
-            proposed_window_size: None,
-            service_choice: SERVICE_READ_PROPERTY_MULTIPLE,
+        let object_id = ObjectId::new(ObjectType::AnalogValue, 1);
+        for invoke_id in [1, 2] {
+            let apdu = read_property_ack_apdu(
+                invoke_id,
+                object_id,
+                PropertyId::PresentValue,
+                None,
+                &DataValue::Real(f32::from(invoke_id)),
+            );
+            state.recv.lock().await.push_back((with_npdu(&apdu), addr));
         }
-        .encode(&mut w)
-        .unwrap();
-        encode_ctx_unsigned(&mut w, 0, object_id.raw()).unwrap();
-        rustbac_core::encoding::tag::Tag::Opening { tag_num: 1 }
-            .encode(&mut w)
-            .unwrap();
-        encode_ctx_unsigned(&mut w, 2, PropertyId::ObjectName.to_u32()).unwrap();
-        rustbac_core::encoding::tag::Tag::Opening { tag_num: 4 }
-            .encode(&mut w)
-            .unwrap();
-        rustbac_core::services::value_codec::encode_application_data_value(
-            &mut w,
-            &DataValue::CharacterString("AHU-1"),
-        )
-        .unwrap();
-        rustbac_core::encoding::tag::Tag::Closing { tag_num: 4 }
-            .encode(&mut w)
-            .unwrap();
-        rustbac_core::encoding::tag::Tag::Closing { tag_num: 1 }
-            .encode(&mut w)
-            .unwrap();
 
-        state
-            .recv
-            .lock()
+        let first = client
+            .read_property(addr, object_id, PropertyId::PresentValue)
             .await
-            .push_back((with_npdu(w.as_written()), addr));
-
-        let values = client
-            .read_property_multiple(addr, object_id, &[PropertyId::ObjectName])
+            .unwrap();
+        let second = clone
+            .read_property(addr, object_id, PropertyId::PresentValue)
             .await
             .unwrap();
-        assert_eq!(values.len(), 1);
-        assert_eq!(values[0].0, PropertyId::ObjectName);
-        assert!(matches!(
-            &values[0].1,
-            ClientDataValue::CharacterString(s) if s == "AHU-1"
-        ));
-    }
+        assert_eq!(first, ClientDataValue::Real(1.0));
+        assert_eq!(second, ClientDataValue::Real(2.0));
 
-    #[tokio::test]
-    async fn new_sc_rejects_invalid_endpoint() {
-        let err = BacnetClient::new_sc("not a url").await.unwrap_err();
-        assert!(matches!(err, crate::ClientError::DataLink(_)));
+        let invoke_ids: Vec<u8> = state
+            .sent
+            .lock()
+            .await
+            .iter()
+            .map(|(_, frame)| frame[4])
+            .collect();
+        assert_eq!(invoke_ids, vec![1, 2]);
+        assert_eq!(client.recent_transactions().len(), 2);
+        assert_eq!(clone.response_timeout, Duration::from_millis(50));
+        assert_eq!(client.response_timeout, Duration::from_secs(3));
     }
 }
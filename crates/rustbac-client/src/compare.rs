@@ -0,0 +1,257 @@
+//! Tolerant, structural comparison of [`ClientDataValue`]s.
+//!
+//! Devices rarely echo a value back bit for bit: a written `Real` may be stored as a
+//! `Double`, an analog value may be reported as `Unsigned`, and fixed-length text fields
+//! come back padded with NULs or spaces. [`values_equivalent`] compares two values the
+//! way a person reading them would; write verification, COV polling and walk diffing all
+//! use it.
+
+use crate::ClientDataValue;
+use std::borrow::Cow;
+
+/// How [`values_equivalent`] compares two values.
+///
+/// The default compares numbers exactly (but across `Real`, `Double`, `Unsigned` and
+/// `Signed`) and normalizes text with [`normalize_text`], case-sensitively.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueComparison {
+    /// Largest absolute difference accepted between two numbers.
+    pub tolerance: f64,
+    /// Compares character strings after [`normalize_text`].
+    pub normalize_text: bool,
+    /// Compares character strings ignoring case.
+    pub ignore_case: bool,
+}
+
+impl Default for ValueComparison {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.0,
+            normalize_text: true,
+            ignore_case: false,
+        }
+    }
+}
+
+impl ValueComparison {
+    /// Sets the numeric tolerance; negative values count as their magnitude.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance.abs();
+        self
+    }
+
+    pub fn with_normalize_text(mut self, normalize: bool) -> Self {
+        self.normalize_text = normalize;
+        self
+    }
+
+    pub fn with_ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+}
+
+/// Returns `true` when `a` and `b` are the same value under `comparison`.
+///
+/// - `Real`, `Double`, `Unsigned` and `Signed` compare numerically with each other,
+///   within the tolerance. `Enumerated` is a code, not a quantity, and only equals an
+///   equal `Enumerated`.
+/// - Character strings compare after normalization, if enabled.
+/// - Bit strings compare their bits, ignoring the padding bits of the last octet.
+/// - Constructed values compare their tag and children pairwise.
+/// - Everything else compares with `==`.
+pub fn values_equivalent(
+    a: &ClientDataValue,
+    b: &ClientDataValue,
+    comparison: &ValueComparison,
+) -> bool {
+    if let (Some(x), Some(y)) = (as_number(a), as_number(b)) {
+        return x == y || (x - y).abs() <= comparison.tolerance || (x.is_nan() && y.is_nan());
+    }
+    match (a, b) {
+        (ClientDataValue::CharacterString(x), ClientDataValue::CharacterString(y)) => {
+            let (x, y) = if comparison.normalize_text {
+                (normalize_text(x), normalize_text(y))
+            } else {
+                (Cow::Borrowed(&**x), Cow::Borrowed(&**y))
+            };
+            if comparison.ignore_case {
+                x.to_lowercase() == y.to_lowercase()
+            } else {
+                x == y
+            }
+        }
+        (
+            ClientDataValue::BitString {
+                unused_bits: unused_a,
+                data: data_a,
+            },
+            ClientDataValue::BitString {
+                unused_bits: unused_b,
+                data: data_b,
+            },
+        ) => {
+            unused_a == unused_b && masked_bits(*unused_a, data_a) == masked_bits(*unused_b, data_b)
+        }
+        (
+            ClientDataValue::Constructed {
+                tag_num: tag_a,
+                values: values_a,
+            },
+            ClientDataValue::Constructed {
+                tag_num: tag_b,
+                values: values_b,
+            },
+        ) => {
+            tag_a == tag_b
+                && values_a.len() == values_b.len()
+                && values_a
+                    .iter()
+                    .zip(values_b)
+                    .all(|(x, y)| values_equivalent(x, y, comparison))
+        }
+        _ => a == b,
+    }
+}
+
+/// Text as a person would compare it: NUL padding and trailing whitespace removed and
+/// line endings written as `\n`.
+pub fn normalize_text(text: &str) -> Cow<'_, str> {
+    let trimmed = text.trim_end_matches(|c: char| c == '\0' || c.is_whitespace());
+    if trimmed.contains('\r') {
+        Cow::Owned(trimmed.replace("\r\n", "\n").replace('\r', "\n"))
+    } else {
+        Cow::Borrowed(trimmed)
+    }
+}
+
+/// `value` with every character string, including those in constructed values,
+/// [normalized](normalize_text) and the padding bits of bit strings cleared.
+pub fn normalize_value(value: &ClientDataValue) -> ClientDataValue {
+    match value {
+        ClientDataValue::CharacterString(text) => match normalize_text(text) {
+            Cow::Borrowed(normalized) if normalized.len() == text.len() => value.clone(),
+            normalized => ClientDataValue::CharacterString(normalized.into()),
+        },
+        ClientDataValue::BitString { unused_bits, data } => ClientDataValue::BitString {
+            unused_bits: *unused_bits,
+            data: masked_bits(*unused_bits, data),
+        },
+        ClientDataValue::Constructed { tag_num, values } => ClientDataValue::Constructed {
+            tag_num: *tag_num,
+            values: values.iter().map(normalize_value).collect(),
+        },
+        other => other.clone(),
+    }
+}
+
+fn as_number(value: &ClientDataValue) -> Option<f64> {
+    match value {
+        ClientDataValue::Real(v) => Some(f64::from(*v)),
+        ClientDataValue::Double(v) => Some(*v),
+        ClientDataValue::Unsigned(v) => Some(f64::from(*v)),
+        ClientDataValue::Signed(v) => Some(f64::from(*v)),
+        _ => None,
+    }
+}
+
+fn masked_bits(unused_bits: u8, data: &[u8]) -> Vec<u8> {
+    let mut data = data.to_vec();
+    if let Some(last) = data.last_mut() {
+        *last &= 0xFFu8.checked_shl(u32::from(unused_bits)).unwrap_or(0);
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_text, normalize_value, values_equivalent, ValueComparison};
+    use crate::ClientDataValue;
+
+    #[test]
+    fn numbers_compare_across_types_within_tolerance() {
+        let exact = ValueComparison::default();
+        let loose = exact.with_tolerance(0.01);
+        assert!(values_equivalent(
+            &ClientDataValue::Real(3.0),
+            &ClientDataValue::Unsigned(3),
+            &exact
+        ));
+        assert!(values_equivalent(
+            &ClientDataValue::Double(-2.0),
+            &ClientDataValue::Signed(-2),
+            &exact
+        ));
+        assert!(!values_equivalent(
+            &ClientDataValue::Real(21.5),
+            &ClientDataValue::Double(21.505),
+            &exact
+        ));
+        assert!(values_equivalent(
+            &ClientDataValue::Real(21.5),
+            &ClientDataValue::Double(21.505),
+            &loose
+        ));
+        assert!(!values_equivalent(
+            &ClientDataValue::Enumerated(1),
+            &ClientDataValue::Unsigned(1),
+            &loose
+        ));
+    }
+
+    #[test]
+    fn text_and_constructed_values_compare_normalized() {
+        let comparison = ValueComparison::default();
+        assert_eq!(normalize_text("Zone 1\r\nNorth\0\0  "), "Zone 1\nNorth");
+        assert!(values_equivalent(
+            &ClientDataValue::CharacterString("AHU-1\0\0".into()),
+            &ClientDataValue::CharacterString("AHU-1".into()),
+            &comparison
+        ));
+        assert!(!values_equivalent(
+            &ClientDataValue::CharacterString("ahu-1".into()),
+            &ClientDataValue::CharacterString("AHU-1".into()),
+            &comparison
+        ));
+        assert!(values_equivalent(
+            &ClientDataValue::CharacterString("ahu-1".into()),
+            &ClientDataValue::CharacterString("AHU-1".into()),
+            &comparison.with_ignore_case(true)
+        ));
+
+        let a = ClientDataValue::Constructed {
+            tag_num: 0,
+            values: vec![
+                ClientDataValue::Real(1.0),
+                ClientDataValue::BitString {
+                    unused_bits: 4,
+                    data: vec![0b1010_0101],
+                },
+            ],
+        };
+        let b = ClientDataValue::Constructed {
+            tag_num: 0,
+            values: vec![
+                ClientDataValue::Double(1.0),
+                ClientDataValue::BitString {
+                    unused_bits: 4,
+                    data: vec![0b1010_0000],
+                },
+            ],
+        };
+        assert!(values_equivalent(&a, &b, &comparison));
+        assert_eq!(
+            normalize_value(&a),
+            ClientDataValue::Constructed {
+                tag_num: 0,
+                values: vec![
+                    ClientDataValue::Real(1.0),
+                    ClientDataValue::BitString {
+                        unused_bits: 4,
+                        data: vec![0b1010_0000],
+                    },
+                ],
+            }
+        );
+    }
+}
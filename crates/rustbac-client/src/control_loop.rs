@@ -0,0 +1,191 @@
+//! Typed tuning access for the BACnet Loop object.
+//!
+//! A Loop runs a PID algorithm: it reads a controlled variable, compares it with its
+//! setpoint (its own Setpoint, or the property its Setpoint_Reference names) and drives
+//! a manipulated variable. See
+//! [`BacnetClient::read_loop_tuning`](crate::BacnetClient::read_loop_tuning) and
+//! [`BacnetClient::write_loop_tuning`](crate::BacnetClient::write_loop_tuning).
+
+use crate::ClientDataValue;
+use rustbac_core::services::control_loop::LoopAction;
+use rustbac_core::types::{ObjectPropertyReference, PropertyId};
+use rustbac_core::EncodeError;
+
+/// Properties read for a [`LoopTuning`], besides Setpoint_Reference.
+pub(crate) const LOOP_TUNING_PROPERTIES: &[PropertyId] = &[
+    PropertyId::Setpoint,
+    PropertyId::ProportionalConstant,
+    PropertyId::IntegralConstant,
+    PropertyId::DerivativeConstant,
+    PropertyId::Action,
+    PropertyId::OutputUnits,
+];
+
+/// The tuning of a Loop object.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoopTuning {
+    /// `Setpoint_Reference`: where the setpoint comes from, or `None` when it is
+    /// `setpoint`.
+    pub setpoint_reference: Option<ObjectPropertyReference>,
+    /// `Setpoint`.
+    pub setpoint: f32,
+    /// `Proportional_Constant`.
+    pub proportional_constant: f32,
+    /// `Integral_Constant`.
+    pub integral_constant: f32,
+    /// `Derivative_Constant`.
+    pub derivative_constant: f32,
+    /// `Action`.
+    pub action: LoopAction,
+    /// `Output_Units`, the BACnetEngineeringUnits of the Loop's Present_Value.
+    pub output_units: u32,
+}
+
+impl LoopTuning {
+    /// Builds the tuning from ReadPropertyMultiple results; `None` if a property is
+    /// missing or of the wrong type.
+    pub(crate) fn from_properties(
+        setpoint_reference: Option<ObjectPropertyReference>,
+        values: &[(PropertyId, ClientDataValue)],
+    ) -> Option<Self> {
+        let real = |property_id| {
+            values.iter().find_map(|(id, value)| match value {
+                ClientDataValue::Real(v) if *id == property_id => Some(*v),
+                _ => None,
+            })
+        };
+        let enumerated = |property_id| {
+            values.iter().find_map(|(id, value)| match value {
+                ClientDataValue::Enumerated(v) if *id == property_id => Some(*v),
+                _ => None,
+            })
+        };
+        Some(Self {
+            setpoint_reference,
+            setpoint: real(PropertyId::Setpoint)?,
+            proportional_constant: real(PropertyId::ProportionalConstant)?,
+            integral_constant: real(PropertyId::IntegralConstant)?,
+            derivative_constant: real(PropertyId::DerivativeConstant)?,
+            action: LoopAction::from_u32(enumerated(PropertyId::Action)?)?,
+            output_units: enumerated(PropertyId::OutputUnits)?,
+        })
+    }
+}
+
+/// Tuning to write to a Loop; `None` fields are left unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LoopTuningChange {
+    pub setpoint: Option<f32>,
+    pub proportional_constant: Option<f32>,
+    pub integral_constant: Option<f32>,
+    pub derivative_constant: Option<f32>,
+    pub action: Option<LoopAction>,
+}
+
+impl LoopTuningChange {
+    /// Sets the Setpoint.
+    pub fn with_setpoint(mut self, setpoint: f32) -> Self {
+        self.setpoint = Some(setpoint);
+        self
+    }
+
+    /// Sets the proportional, integral and derivative constants.
+    pub fn with_pid(mut self, proportional: f32, integral: f32, derivative: f32) -> Self {
+        self.proportional_constant = Some(proportional);
+        self.integral_constant = Some(integral);
+        self.derivative_constant = Some(derivative);
+        self
+    }
+
+    /// Sets the Action.
+    pub fn with_action(mut self, action: LoopAction) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    /// Checks the setpoint is finite and the constants finite and non-negative; the
+    /// direction of control belongs in the Action, not in negative gains.
+    pub fn validate(&self) -> Result<(), EncodeError> {
+        let constants = [
+            self.proportional_constant,
+            self.integral_constant,
+            self.derivative_constant,
+        ];
+        let setpoint_ok = self.setpoint.map_or(true, f32::is_finite);
+        let constants_ok = constants
+            .into_iter()
+            .flatten()
+            .all(|constant| constant.is_finite() && constant >= 0.0);
+        if setpoint_ok && constants_ok {
+            Ok(())
+        } else {
+            Err(EncodeError::ValueOutOfRange)
+        }
+    }
+
+    /// The property writes for this change.
+    pub(crate) fn writes(&self) -> Vec<(PropertyId, ClientDataValue)> {
+        let reals = [
+            (PropertyId::Setpoint, self.setpoint),
+            (PropertyId::ProportionalConstant, self.proportional_constant),
+            (PropertyId::IntegralConstant, self.integral_constant),
+            (PropertyId::DerivativeConstant, self.derivative_constant),
+        ];
+        let mut writes: Vec<_> = reals
+            .into_iter()
+            .filter_map(|(property_id, value)| Some((property_id, ClientDataValue::Real(value?))))
+            .collect();
+        if let Some(action) = self.action {
+            writes.push((
+                PropertyId::Action,
+                ClientDataValue::Enumerated(action.to_u32()),
+            ));
+        }
+        writes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LoopTuning, LoopTuningChange};
+    use crate::ClientDataValue;
+    use rustbac_core::services::control_loop::LoopAction;
+    use rustbac_core::types::PropertyId;
+
+    #[test]
+    fn tuning_is_built_from_loop_properties() {
+        let values = [
+            (PropertyId::Setpoint, ClientDataValue::Real(21.0)),
+            (PropertyId::ProportionalConstant, ClientDataValue::Real(2.0)),
+            (PropertyId::IntegralConstant, ClientDataValue::Real(0.5)),
+            (PropertyId::DerivativeConstant, ClientDataValue::Real(0.0)),
+            (PropertyId::Action, ClientDataValue::Enumerated(1)),
+            (PropertyId::OutputUnits, ClientDataValue::Enumerated(98)),
+        ];
+        let tuning = LoopTuning::from_properties(None, &values).unwrap();
+        assert_eq!(tuning.setpoint, 21.0);
+        assert_eq!(tuning.integral_constant, 0.5);
+        assert_eq!(tuning.action, LoopAction::Reverse);
+        assert_eq!(tuning.output_units, 98);
+        assert!(LoopTuning::from_properties(None, &values[1..]).is_none());
+    }
+
+    #[test]
+    fn change_validates_and_lists_its_writes() {
+        let change = LoopTuningChange::default()
+            .with_pid(1.5, 0.2, 0.0)
+            .with_action(LoopAction::Direct);
+        assert!(change.validate().is_ok());
+        assert_eq!(
+            change.writes(),
+            vec![
+                (PropertyId::ProportionalConstant, ClientDataValue::Real(1.5)),
+                (PropertyId::IntegralConstant, ClientDataValue::Real(0.2)),
+                (PropertyId::DerivativeConstant, ClientDataValue::Real(0.0)),
+                (PropertyId::Action, ClientDataValue::Enumerated(0)),
+            ]
+        );
+        assert!(change.with_pid(-1.0, 0.2, 0.0).validate().is_err());
+        assert!(change.with_setpoint(f32::NAN).validate().is_err());
+    }
+}
@@ -0,0 +1,198 @@
+//! The COV subscriptions a device holds, and how they compare with a client's own.
+//!
+//! [`BacnetClient::read_active_cov_subscriptions`](crate::BacnetClient::read_active_cov_subscriptions)
+//! and
+//! [`BacnetClient::read_active_cov_multiple_subscriptions`](crate::BacnetClient::read_active_cov_multiple_subscriptions)
+//! list every subscription a device holds, from any client. [`reconcile_cov_subscriptions`]
+//! matches them against the specs of a [`CovManager`](crate::CovManager).
+
+use crate::{CovSubscriptionSpec, Recipient};
+use rustbac_core::services::cov_subscription::{
+    CovMultipleSubscription as CoreCovMultipleSubscription, CovSubscription as CoreCovSubscription,
+    CovSubscriptionSpecification,
+};
+use rustbac_core::types::{ObjectPropertyReference, PropertyId};
+use rustbac_datalink::DataLinkAddress;
+
+/// A SubscribeCOV or SubscribeCOVProperty subscription, from a device's
+/// Active_COV_Subscriptions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveCovSubscription {
+    /// Where the device sends the notifications.
+    pub recipient: Recipient,
+    pub process_id: u32,
+    /// The monitored property; Present_Value for a SubscribeCOV subscription.
+    pub monitored: ObjectPropertyReference,
+    pub issue_confirmed_notifications: bool,
+    /// Seconds until the subscription lapses; 0 for one without a lifetime.
+    pub time_remaining: u32,
+    pub cov_increment: Option<f32>,
+}
+
+impl From<CoreCovSubscription<'_>> for ActiveCovSubscription {
+    fn from(subscription: CoreCovSubscription<'_>) -> Self {
+        Self {
+            recipient: subscription.recipient.into(),
+            process_id: subscription.process_id,
+            monitored: subscription.monitored,
+            issue_confirmed_notifications: subscription.issue_confirmed_notifications,
+            time_remaining: subscription.time_remaining,
+            cov_increment: subscription.cov_increment,
+        }
+    }
+}
+
+/// A SubscribeCOVPropertyMultiple subscription, from a device's
+/// Active_COV_Multiple_Subscriptions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveCovMultipleSubscription {
+    pub recipient: Recipient,
+    pub process_id: u32,
+    pub issue_confirmed_notifications: bool,
+    /// Seconds until the subscription lapses; 0 for one without a lifetime.
+    pub time_remaining: u32,
+    /// Seconds the device may hold notifications to combine them.
+    pub max_notification_delay: u32,
+    /// The monitored properties, by object.
+    pub specifications: Vec<CovSubscriptionSpecification>,
+}
+
+impl From<CoreCovMultipleSubscription<'_>> for ActiveCovMultipleSubscription {
+    fn from(subscription: CoreCovMultipleSubscription<'_>) -> Self {
+        Self {
+            recipient: subscription.recipient.into(),
+            process_id: subscription.process_id,
+            issue_confirmed_notifications: subscription.issue_confirmed_notifications,
+            time_remaining: subscription.time_remaining,
+            max_notification_delay: subscription.max_notification_delay,
+            specifications: subscription.specifications,
+        }
+    }
+}
+
+/// The result of [`reconcile_cov_subscriptions`].
+#[derive(Debug, Clone, Default)]
+pub struct CovReconciliation {
+    /// Specs the device holds a subscription for, with that subscription.
+    pub matched: Vec<(CovSubscriptionSpec, ActiveCovSubscription)>,
+    /// Specs the device holds no subscription for: lapsed, rejected, lost in a device
+    /// restart, or polled.
+    pub missing: Vec<CovSubscriptionSpec>,
+    /// Subscriptions of this client's recipients that no spec asks for, e.g. left over
+    /// from an earlier run.
+    pub unexpected: Vec<ActiveCovSubscription>,
+}
+
+impl CovReconciliation {
+    /// Whether the device holds exactly the subscriptions the specs ask for.
+    pub fn is_consistent(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty()
+    }
+}
+
+/// Matches the `specs` for the device at `address` against the `active` subscriptions
+/// read from it.
+///
+/// A subscription matches a spec with the same process id, object and property (a spec
+/// without a property is a SubscribeCOV, listed as Present_Value). Only subscriptions
+/// whose recipient `is_ours` are matched or reported as unexpected; those of other
+/// clients are ignored. Specs for other addresses are skipped.
+pub fn reconcile_cov_subscriptions(
+    address: DataLinkAddress,
+    specs: &[CovSubscriptionSpec],
+    active: &[ActiveCovSubscription],
+    is_ours: impl Fn(&Recipient) -> bool,
+) -> CovReconciliation {
+    let mut unmatched: Vec<&ActiveCovSubscription> = active
+        .iter()
+        .filter(|subscription| is_ours(&subscription.recipient))
+        .collect();
+    let mut reconciliation = CovReconciliation::default();
+    for spec in specs.iter().filter(|spec| spec.address == address) {
+        let property_id = spec.property_id.unwrap_or(PropertyId::PresentValue);
+        let position = unmatched.iter().position(|subscription| {
+            subscription.process_id == spec.subscriber_process_id
+                && subscription.monitored.object_id == spec.object_id
+                && subscription.monitored.property_id == property_id
+        });
+        match position {
+            Some(position) => {
+                let subscription = unmatched.remove(position);
+                reconciliation
+                    .matched
+                    .push((spec.clone(), subscription.clone()));
+            }
+            None => reconciliation.missing.push(spec.clone()),
+        }
+    }
+    reconciliation.unexpected = unmatched.into_iter().cloned().collect();
+    reconciliation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reconcile_cov_subscriptions, ActiveCovSubscription};
+    use crate::{CovMode, CovSubscriptionSpec, Recipient};
+    use rustbac_core::types::{ObjectId, ObjectPropertyReference, ObjectType, PropertyId};
+    use rustbac_datalink::DataLinkAddress;
+
+    fn spec(
+        address: DataLinkAddress,
+        instance: u32,
+        property_id: Option<PropertyId>,
+    ) -> CovSubscriptionSpec {
+        CovSubscriptionSpec {
+            address,
+            object_id: ObjectId::new(ObjectType::AnalogInput, instance),
+            property_id,
+            lifetime_seconds: 300,
+            cov_increment: None,
+            confirmed: false,
+            subscriber_process_id: 1,
+            mode: CovMode::Subscribe,
+        }
+    }
+
+    fn active(
+        recipient: Recipient,
+        instance: u32,
+        property_id: PropertyId,
+    ) -> ActiveCovSubscription {
+        ActiveCovSubscription {
+            recipient,
+            process_id: 1,
+            monitored: ObjectPropertyReference::new(
+                ObjectId::new(ObjectType::AnalogInput, instance),
+                property_id,
+            ),
+            issue_confirmed_notifications: false,
+            time_remaining: 120,
+            cov_increment: None,
+        }
+    }
+
+    #[test]
+    fn reconciliation_reports_missing_and_unexpected_subscriptions() {
+        let addr = DataLinkAddress::Mstp(5);
+        let us = Recipient::Device(ObjectId::new(ObjectType::Device, 9000));
+        let other = Recipient::Device(ObjectId::new(ObjectType::Device, 1));
+        let specs = [
+            spec(addr, 1, None),
+            spec(addr, 2, Some(PropertyId::StatusFlags)),
+            spec(DataLinkAddress::Mstp(6), 3, None),
+        ];
+        let device = [
+            active(us.clone(), 1, PropertyId::PresentValue),
+            active(us.clone(), 7, PropertyId::PresentValue),
+            active(other, 2, PropertyId::StatusFlags),
+        ];
+
+        let result = reconcile_cov_subscriptions(addr, &specs, &device, |r| *r == us);
+        assert_eq!(result.matched.len(), 1);
+        assert_eq!(result.matched[0].1, device[0]);
+        assert_eq!(result.missing.len(), 1);
+        assert_eq!(result.missing[0].object_id.instance(), 2);
+        assert_eq!(result.unexpected, vec![device[1].clone()]);
+        assert!(!result.is_consistent());
+    }
+}
@@ -1,11 +1,16 @@
-use crate::{BacnetClient, CovNotification, CovPropertyValue};
+use crate::compare::{values_equivalent, ValueComparison};
+use crate::process_id::subscription_key;
+use crate::router::InboundKind;
+use crate::{BacnetClient, ClientDataValue, CovNotification, CovPropertyValue};
 use rustbac_core::services::subscribe_cov::SubscribeCovRequest;
 use rustbac_core::services::subscribe_cov_property::SubscribeCovPropertyRequest;
 use rustbac_core::types::{ObjectId, PropertyId};
 use rustbac_datalink::{DataLink, DataLinkAddress};
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tokio::sync::{mpsc, watch};
+use tokio::sync::{watch, Notify};
 use tokio::time::Instant;
 
 /// Source of a [`CovUpdate`].
@@ -16,6 +21,19 @@ pub enum UpdateSource {
     Poll,
 }
 
+/// How a [`CovSubscriptionSpec`] obtains its updates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CovMode {
+    /// Subscribe, falling back to polling while subscriptions fail or go silent.
+    #[default]
+    Subscribe,
+    /// Never subscribe; poll and emit an update only when the value changes (by at
+    /// least `cov_increment` for numeric values, if set). For devices that reject
+    /// SubscribeCOV outright.
+    PollOnly,
+}
+
 /// A managed COV subscription spec.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -27,6 +45,8 @@ pub struct CovSubscriptionSpec {
     pub cov_increment: Option<f32>,
     pub confirmed: bool,
     pub subscriber_process_id: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mode: CovMode,
 }
 
 /// A single update emitted by [`CovManager`].
@@ -40,17 +60,33 @@ pub struct CovUpdate {
 }
 
 /// Background COV manager handle.
+///
+/// Updates not yet received are queued up to the client's
+/// [`max_queued_frames`](crate::MemoryLimits::max_queued_frames); beyond that the oldest
+/// is dropped and counted by [`dropped`](Self::dropped).
 #[derive(Debug)]
 pub struct CovManager {
     thread: Option<std::thread::JoinHandle<()>>,
     shutdown: watch::Sender<bool>,
-    rx: mpsc::UnboundedReceiver<CovUpdate>,
+    updates: Arc<UpdateQueue>,
+    subscriptions: Vec<CovSubscriptionSpec>,
 }
 
 impl CovManager {
     /// Receive the next update from the manager.
     pub async fn recv(&mut self) -> Option<CovUpdate> {
-        self.rx.recv().await
+        self.updates.recv().await
+    }
+
+    /// Updates dropped because they were not received before the queue filled.
+    pub fn dropped(&self) -> u64 {
+        self.updates.dropped.load(Ordering::Relaxed)
+    }
+
+    /// The subscriptions the manager was built with, e.g. to
+    /// [reconcile](crate::reconcile_cov_subscriptions) them with a device's.
+    pub fn subscriptions(&self) -> &[CovSubscriptionSpec] {
+        &self.subscriptions
     }
 
     /// Stop the manager task.
@@ -88,7 +124,25 @@ impl<D: DataLink + 'static> CovManagerBuilder<D> {
         }
     }
 
+    /// Adds `spec`. A spec the device cannot tell apart from one already added, with the
+    /// same device, object, property and process id, is logged as a warning; see
+    /// [`SubscriberProcessIdAllocator`](crate::SubscriberProcessIdAllocator).
     pub fn subscribe(mut self, spec: CovSubscriptionSpec) -> Self {
+        let key = subscription_key(&spec);
+        if self
+            .subscriptions
+            .iter()
+            .any(|existing| subscription_key(existing) == key)
+        {
+            log::warn!(
+                "duplicate COV subscription to {} of {:?} {:?} with process id {}; \
+                 notifications cannot be told apart",
+                spec.address,
+                spec.object_id,
+                spec.property_id,
+                spec.subscriber_process_id
+            );
+        }
         self.subscriptions.push(spec);
         self
     }
@@ -112,13 +166,17 @@ impl<D: DataLink + 'static> CovManagerBuilder<D> {
         let runtime_handle = tokio::runtime::Handle::try_current()
             .map_err(|_| crate::ClientError::NoTokioRuntime)?;
 
-        let (tx, rx) = mpsc::unbounded_channel();
+        let updates = Arc::new(UpdateQueue::new(
+            self.client.memory_limits().max_queued_frames,
+        ));
+        let tx = UpdateSender(updates.clone());
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
         let poll_interval = self.poll_interval.max(Duration::from_millis(1));
         let silence_threshold = self.silence_threshold.max(Duration::from_millis(1));
         let renewal_fraction = sanitize_fraction(self.renewal_fraction);
         let client = self.client;
         let subscriptions = self.subscriptions;
+        let specs = subscriptions.clone();
 
         let thread = std::thread::spawn(move || {
             runtime_handle.block_on(async move {
@@ -137,11 +195,80 @@ impl<D: DataLink + 'static> CovManagerBuilder<D> {
         Ok(CovManager {
             thread: Some(thread),
             shutdown: shutdown_tx,
-            rx,
+            updates,
+            subscriptions: specs,
         })
     }
 }
 
+/// Updates waiting for [`CovManager::recv`], the oldest dropped beyond `capacity`.
+#[derive(Debug)]
+struct UpdateQueue {
+    updates: Mutex<VecDeque<CovUpdate>>,
+    capacity: usize,
+    dropped: AtomicU64,
+    closed: AtomicBool,
+    ready: Notify,
+}
+
+impl UpdateQueue {
+    fn new(capacity: usize) -> Self {
+        Self {
+            updates: Mutex::new(VecDeque::new()),
+            capacity: capacity.max(1),
+            dropped: AtomicU64::new(0),
+            closed: AtomicBool::new(false),
+            ready: Notify::new(),
+        }
+    }
+
+    fn push(&self, update: CovUpdate) {
+        let mut updates = self.updates.lock().unwrap_or_else(|e| e.into_inner());
+        if updates.len() >= self.capacity {
+            updates.pop_front();
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            log::warn!("cov manager updates not received — dropping the oldest");
+        }
+        updates.push_back(update);
+        drop(updates);
+        self.ready.notify_one();
+    }
+
+    /// The next update, or `None` once the manager has stopped and the queue is empty.
+    async fn recv(&self) -> Option<CovUpdate> {
+        loop {
+            let update = self
+                .updates
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .pop_front();
+            if update.is_some() {
+                return update;
+            }
+            if self.closed.load(Ordering::Acquire) {
+                return None;
+            }
+            self.ready.notified().await;
+        }
+    }
+}
+
+/// The manager task's end of an [`UpdateQueue`], closing it when the task ends.
+struct UpdateSender(Arc<UpdateQueue>);
+
+impl UpdateSender {
+    fn send(&self, update: CovUpdate) {
+        self.0.push(update);
+    }
+}
+
+impl Drop for UpdateSender {
+    fn drop(&mut self) {
+        self.0.closed.store(true, Ordering::Release);
+        self.0.ready.notify_one();
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum SubscriptionMode {
     Cov,
@@ -161,6 +288,8 @@ struct SubscriptionState {
     cov_mode_since: Option<Instant>,
     next_renewal: Instant,
     next_poll: Instant,
+    /// Last value emitted by a [`CovMode::PollOnly`] spec, for change detection.
+    last_polled: Option<ClientDataValue>,
 }
 
 impl SubscriptionState {
@@ -171,16 +300,25 @@ impl SubscriptionState {
         now: Instant,
     ) -> Self {
         let lifetime_seconds = spec.lifetime_seconds;
+        let (mode, next_poll) = match spec.mode {
+            CovMode::Subscribe => (SubscriptionMode::Cov, now + poll_interval),
+            CovMode::PollOnly => (SubscriptionMode::Polling, now),
+        };
         Self {
             spec,
-            mode: SubscriptionMode::Cov,
+            mode,
             last_notification: None,
             cov_mode_since: None,
             next_renewal: now + renewal_delay_seconds(lifetime_seconds, renewal_fraction),
-            next_poll: now + poll_interval,
+            next_poll,
+            last_polled: None,
         }
     }
 
+    fn is_poll_only(&self) -> bool {
+        self.spec.mode == CovMode::PollOnly
+    }
+
     fn on_subscribe_success(
         &mut self,
         now: Instant,
@@ -233,7 +371,7 @@ impl SubscriptionState {
 async fn run_cov_manager<D: DataLink>(
     client: Arc<BacnetClient<D>>,
     subscriptions: Vec<CovSubscriptionSpec>,
-    tx: mpsc::UnboundedSender<CovUpdate>,
+    tx: UpdateSender,
     mut shutdown_rx: watch::Receiver<bool>,
     poll_interval: Duration,
     silence_threshold: Duration,
@@ -242,6 +380,11 @@ async fn run_cov_manager<D: DataLink>(
     if subscriptions.is_empty() {
         return;
     }
+    // Notifications read by subscribes, polls or other tasks' requests wait for the next
+    // receive instead of being dropped.
+    let _notifications = client
+        .inbound_router()
+        .subscribe(&[InboundKind::CovNotification]);
 
     let now = Instant::now();
     let mut states: Vec<SubscriptionState> = subscriptions
@@ -249,7 +392,7 @@ async fn run_cov_manager<D: DataLink>(
         .map(|spec| SubscriptionState::new(spec, poll_interval, renewal_fraction, now))
         .collect();
 
-    for state in &mut states {
+    for state in states.iter_mut().filter(|state| !state.is_poll_only()) {
         let attempt = subscribe_spec(&client, &state.spec).await;
         let now = Instant::now();
         if attempt {
@@ -282,7 +425,9 @@ async fn run_cov_manager<D: DataLink>(
             Ok(Some(notification)) => {
                 let now = Instant::now();
                 for state in &mut states {
-                    if !notification_matches_spec(&notification, &state.spec) {
+                    if state.is_poll_only()
+                        || !notification_matches_spec(&notification, &state.spec)
+                    {
                         continue;
                     }
 
@@ -300,9 +445,7 @@ async fn run_cov_manager<D: DataLink>(
                         values,
                         source: UpdateSource::Cov,
                     };
-                    if tx.send(update).is_err() {
-                        return;
-                    }
+                    tx.send(update);
                 }
             }
             Ok(None) => {}
@@ -313,6 +456,24 @@ async fn run_cov_manager<D: DataLink>(
 
         let now = Instant::now();
         for state in &mut states {
+            if state.is_poll_only() {
+                if now >= state.next_poll {
+                    if let Some(update) = poll_spec(&client, &state.spec).await {
+                        let value = &update.values[0].value;
+                        if poll_value_changed(
+                            state.last_polled.as_ref(),
+                            value,
+                            state.spec.cov_increment,
+                        ) {
+                            state.last_polled = Some(value.clone());
+                            tx.send(update);
+                        }
+                    }
+                    state.next_poll = Instant::now() + poll_interval;
+                }
+                continue;
+            }
+
             if now >= state.next_renewal {
                 if subscribe_spec(&client, &state.spec).await {
                     state.on_subscribe_success(Instant::now(), renewal_fraction, poll_interval);
@@ -337,9 +498,7 @@ async fn run_cov_manager<D: DataLink>(
                 }
 
                 if let Some(update) = poll_spec(&client, &state.spec).await {
-                    if tx.send(update).is_err() {
-                        return;
-                    }
+                    tx.send(update);
                 }
                 state.next_poll = Instant::now() + poll_interval;
             }
@@ -424,6 +583,33 @@ fn filter_cov_values(
     }
 }
 
+/// Whether a polled value differs enough from the last emitted one to be reported.
+fn poll_value_changed(
+    previous: Option<&ClientDataValue>,
+    current: &ClientDataValue,
+    increment: Option<f32>,
+) -> bool {
+    let Some(previous) = previous else {
+        return true;
+    };
+    match (increment, as_f64(previous), as_f64(current)) {
+        (Some(increment), Some(previous), Some(current)) => {
+            (current - previous).abs() >= f64::from(increment)
+        }
+        _ => !values_equivalent(previous, current, &ValueComparison::default()),
+    }
+}
+
+fn as_f64(value: &ClientDataValue) -> Option<f64> {
+    match value {
+        ClientDataValue::Real(v) => Some(f64::from(*v)),
+        ClientDataValue::Double(v) => Some(*v),
+        ClientDataValue::Unsigned(v) => Some(f64::from(*v)),
+        ClientDataValue::Signed(v) => Some(f64::from(*v)),
+        _ => None,
+    }
+}
+
 fn sanitize_fraction(fraction: f64) -> f64 {
     if !fraction.is_finite() {
         return 0.75;
@@ -441,11 +627,12 @@ fn renewal_delay_seconds(lifetime_seconds: u32, renewal_fraction: f64) -> Durati
 #[cfg(test)]
 mod tests {
     use super::{
-        notification_matches_spec, renewal_delay_seconds, CovManagerBuilder, CovSubscriptionSpec,
-        SubscriptionMode, SubscriptionState, UpdateSource,
+        notification_matches_spec, poll_value_changed, renewal_delay_seconds, CovManagerBuilder,
+        CovMode, CovSubscriptionSpec, SubscriptionMode, SubscriptionState, UpdateSource,
     };
-    use crate::{BacnetClient, ClientDataValue, CovNotification, SimulatedDevice};
-    use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+    use crate::{BacnetClient, ClientDataValue, CovNotification, MemoryLimits, SimulatedDevice};
+    use rustbac_core::services::write_property::WritePropertyRequest;
+    use rustbac_core::types::{DataValue, ObjectId, ObjectType, PropertyId};
     use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError};
     use std::collections::HashMap;
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -519,6 +706,7 @@ mod tests {
             cov_increment: None,
             confirmed: false,
             subscriber_process_id: 1,
+            mode: CovMode::Subscribe,
         };
         let mut state = SubscriptionState::new(spec, Duration::from_secs(1), 0.75, now);
         state.mode = SubscriptionMode::Cov;
@@ -549,6 +737,7 @@ mod tests {
             cov_increment: None,
             confirmed: false,
             subscriber_process_id: 1,
+            mode: CovMode::Subscribe,
         };
         let t0 = Instant::now();
         let mut state = SubscriptionState::new(spec, Duration::from_secs(30), 0.75, t0);
@@ -591,6 +780,7 @@ mod tests {
             cov_increment: None,
             confirmed: false,
             subscriber_process_id: 42,
+            mode: CovMode::Subscribe,
         };
         let mut notification = CovNotification {
             source: address,
@@ -617,7 +807,7 @@ mod tests {
         props.insert(PropertyId::PresentValue, ClientDataValue::Real(42.0));
         props.insert(
             PropertyId::ObjectName,
-            ClientDataValue::CharacterString("AI-1".to_string()),
+            ClientDataValue::CharacterString("AI-1".into()),
         );
         simulator.add_object(object_id, props).await;
 
@@ -637,6 +827,7 @@ mod tests {
             cov_increment: None,
             confirmed: false,
             subscriber_process_id: 99,
+            mode: CovMode::Subscribe,
         };
 
         let mut manager = CovManagerBuilder::new(client)
@@ -660,4 +851,143 @@ mod tests {
         manager.stop();
         simulator_task.abort();
     }
+
+    #[test]
+    fn poll_only_change_detection_honours_cov_increment() {
+        let real = ClientDataValue::Real;
+        assert!(poll_value_changed(None, &real(1.0), Some(0.5)));
+        assert!(!poll_value_changed(Some(&real(1.0)), &real(1.2), Some(0.5)));
+        assert!(poll_value_changed(Some(&real(1.0)), &real(1.5), Some(0.5)));
+        assert!(poll_value_changed(Some(&real(1.0)), &real(1.2), None));
+        assert!(!poll_value_changed(
+            Some(&ClientDataValue::Enumerated(1)),
+            &ClientDataValue::Enumerated(1),
+            Some(0.5)
+        ));
+    }
+
+    #[tokio::test]
+    async fn poll_only_mode_emits_changes_without_subscribing() {
+        let (client_dl, simulator_dl, simulator_addr) = datalink_pair();
+
+        let simulator = SimulatedDevice::new(2001, simulator_dl);
+        let object_id = ObjectId::new(ObjectType::AnalogValue, 1);
+        let mut props = HashMap::new();
+        props.insert(PropertyId::PresentValue, ClientDataValue::Real(42.0));
+        simulator.add_object(object_id, props).await;
+        let simulator_task = tokio::spawn(async move {
+            let _ = simulator.run().await;
+        });
+
+        let client = Arc::new(
+            BacnetClient::with_datalink(client_dl)
+                .with_response_timeout(Duration::from_millis(200)),
+        );
+        let spec = CovSubscriptionSpec {
+            address: simulator_addr,
+            object_id,
+            property_id: None,
+            lifetime_seconds: 30,
+            cov_increment: Some(1.0),
+            confirmed: false,
+            subscriber_process_id: 5,
+            mode: CovMode::PollOnly,
+        };
+        let mut manager = CovManagerBuilder::new(client.clone())
+            .subscribe(spec)
+            .poll_interval(Duration::from_millis(40))
+            .build()
+            .unwrap();
+
+        let update = timeout(Duration::from_secs(2), manager.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(update.source, UpdateSource::Poll);
+        assert_eq!(update.values[0].value, ClientDataValue::Real(42.0));
+
+        // Unchanged values, and changes below the increment, are not reported.
+        assert!(timeout(Duration::from_millis(200), manager.recv())
+            .await
+            .is_err());
+
+        client
+            .write_property(
+                simulator_addr,
+                WritePropertyRequest {
+                    object_id,
+                    property_id: PropertyId::PresentValue,
+                    value: DataValue::Real(50.0),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let update = timeout(Duration::from_secs(2), manager.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(update.values[0].value, ClientDataValue::Real(50.0));
+
+        manager.stop();
+        simulator_task.abort();
+    }
+
+    #[tokio::test]
+    async fn updates_not_received_drop_the_oldest() {
+        let (client_dl, simulator_dl, simulator_addr) = datalink_pair();
+
+        let simulator = SimulatedDevice::new(2002, simulator_dl);
+        let first = ObjectId::new(ObjectType::AnalogValue, 1);
+        let second = ObjectId::new(ObjectType::AnalogValue, 2);
+        for (object_id, value) in [(first, 1.0), (second, 2.0)] {
+            let mut props = HashMap::new();
+            props.insert(PropertyId::PresentValue, ClientDataValue::Real(value));
+            simulator.add_object(object_id, props).await;
+        }
+        let simulator_task = tokio::spawn(async move {
+            let _ = simulator.run().await;
+        });
+
+        let client = Arc::new(
+            BacnetClient::with_datalink(client_dl)
+                .with_response_timeout(Duration::from_millis(200))
+                .with_memory_limits(MemoryLimits::default().with_max_queued_frames(1)),
+        );
+        let spec = |object_id| CovSubscriptionSpec {
+            address: simulator_addr,
+            object_id,
+            property_id: None,
+            lifetime_seconds: 30,
+            cov_increment: None,
+            confirmed: false,
+            subscriber_process_id: 6,
+            mode: CovMode::PollOnly,
+        };
+        let mut manager = CovManagerBuilder::new(client)
+            .subscribe(spec(first))
+            .subscribe(spec(second))
+            .poll_interval(Duration::from_secs(60))
+            .build()
+            .unwrap();
+
+        // Both first polls report a value; only the newest fits the queue.
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while manager.dropped() == 0 {
+            assert!(Instant::now() < deadline, "no update was dropped");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(manager.dropped(), 1);
+        let update = timeout(Duration::from_secs(1), manager.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(update.object_id, second);
+        assert!(timeout(Duration::from_millis(100), manager.recv())
+            .await
+            .is_err());
+
+        manager.stop();
+        simulator_task.abort();
+    }
 }
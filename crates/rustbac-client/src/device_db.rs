@@ -0,0 +1,408 @@
+//! A persistent database of the devices on a site.
+//!
+//! A [`DeviceDatabase`] keeps, per device instance, the address the device answers on,
+//! its [capabilities](crate::DeviceCapabilities) and its point inventory from the last
+//! [walk](crate::walk::walk_device). [`DeviceDatabase::refresh`] runs a Who-Is and only
+//! probes and walks devices that are new or whose inventory is older than
+//! [`RefreshOptions::max_age`], so tooling does not rediscover a whole site on every
+//! start.
+//!
+//! The database is saved to and loaded from a [`DeviceStore`]: [`MemoryStore`] for tests
+//! and short-lived tools, [`JsonFileStore`] (with the `serde` feature) for a file on
+//! disk. Other backends, such as an embedded key-value store or SQLite, implement the
+//! trait.
+
+use crate::walk::{walk_device, DeviceInfo, ObjectSummary};
+use crate::{BacnetClient, ClientError, DeviceCapabilities};
+use rustbac_core::types::{ObjectId, ObjectType};
+use rustbac_datalink::{DataLink, DataLinkAddress};
+use std::collections::BTreeMap;
+use std::io;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// What the database knows about one device.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceRecord {
+    pub device_id: ObjectId,
+    /// Address of the device's latest I-Am.
+    pub address: DataLinkAddress,
+    /// Capabilities from the latest probe, if the device was probed.
+    pub capabilities: Option<DeviceCapabilities>,
+    pub device_info: DeviceInfo,
+    /// Point inventory from the latest walk.
+    pub objects: Vec<ObjectSummary>,
+    /// Wall-clock time of the latest I-Am.
+    pub last_seen: SystemTime,
+    /// Wall-clock time of the latest successful walk; `None` before the first.
+    pub last_walked: Option<SystemTime>,
+}
+
+impl DeviceRecord {
+    /// A device seen at `address`, not probed or walked yet.
+    pub fn new(device_id: ObjectId, address: DataLinkAddress, last_seen: SystemTime) -> Self {
+        Self {
+            device_id,
+            address,
+            capabilities: None,
+            device_info: DeviceInfo::default(),
+            objects: Vec::new(),
+            last_seen,
+            last_walked: None,
+        }
+    }
+
+    /// Returns `true` if the device was never walked, or last walked more than `max_age`
+    /// before `now`.
+    pub fn needs_walk(&self, max_age: Duration, now: SystemTime) -> bool {
+        match self.last_walked {
+            None => true,
+            Some(walked) => now.duration_since(walked).is_ok_and(|age| age > max_age),
+        }
+    }
+}
+
+/// Persistence for a [`DeviceDatabase`].
+pub trait DeviceStore {
+    /// Every stored record; an empty store yields no records.
+    fn load(&self) -> io::Result<Vec<DeviceRecord>>;
+
+    /// Replaces the stored records with `records`.
+    fn save(&self, records: &[DeviceRecord]) -> io::Result<()>;
+}
+
+/// A [`DeviceStore`] held in memory.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    records: Mutex<Vec<DeviceRecord>>,
+}
+
+impl DeviceStore for MemoryStore {
+    fn load(&self) -> io::Result<Vec<DeviceRecord>> {
+        Ok(self
+            .records
+            .lock()
+            .map(|records| records.clone())
+            .unwrap_or_default())
+    }
+
+    fn save(&self, records: &[DeviceRecord]) -> io::Result<()> {
+        if let Ok(mut stored) = self.records.lock() {
+            *stored = records.to_vec();
+        }
+        Ok(())
+    }
+}
+
+/// A [`DeviceStore`] in a JSON file.
+///
+/// A missing file loads as an empty database. Saving writes a temporary file next to
+/// `path` and renames it over `path`, so an interrupted save keeps the previous file.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone)]
+pub struct JsonFileStore {
+    path: std::path::PathBuf,
+}
+
+#[cfg(feature = "serde")]
+impl JsonFileStore {
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+}
+
+#[cfg(feature = "serde")]
+impl DeviceStore for JsonFileStore {
+    fn load(&self) -> io::Result<Vec<DeviceRecord>> {
+        let data = match std::fs::read(&self.path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        serde_json::from_slice(&data).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    fn save(&self, records: &[DeviceRecord]) -> io::Result<()> {
+        let data = serde_json::to_vec_pretty(records)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        std::fs::write(&tmp, data)?;
+        std::fs::rename(&tmp, &self.path)
+    }
+}
+
+/// Options for [`DeviceDatabase::refresh`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefreshOptions {
+    /// Device instance range of the Who-Is; `None` discovers every device.
+    pub range: Option<(u32, u32)>,
+    /// How long I-Am replies are collected.
+    pub wait: Duration,
+    /// Inventories older than this are walked again. Default: one day.
+    pub max_age: Duration,
+}
+
+impl Default for RefreshOptions {
+    fn default() -> Self {
+        Self {
+            range: None,
+            wait: Duration::from_secs(3),
+            max_age: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
+impl RefreshOptions {
+    pub fn with_range(mut self, low: u32, high: u32) -> Self {
+        self.range = Some((low, high));
+        self
+    }
+
+    pub fn with_wait(mut self, wait: Duration) -> Self {
+        self.wait = wait;
+        self
+    }
+
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+}
+
+/// Outcome of a [`DeviceDatabase::refresh`], by device instance.
+#[derive(Debug, Default)]
+pub struct RefreshReport {
+    /// Devices seen for the first time.
+    pub added: Vec<u32>,
+    /// Known devices that answered from a different address.
+    pub moved: Vec<u32>,
+    /// Devices probed and walked.
+    pub walked: Vec<u32>,
+    /// Known devices in the range that did not answer the Who-Is; they are kept.
+    pub missing: Vec<u32>,
+    /// Devices that could not be probed or walked; their previous inventory is kept.
+    pub failed: Vec<RefreshFailure>,
+}
+
+impl RefreshReport {
+    /// Returns `true` when every device due for a walk was walked.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// A device a [`DeviceDatabase::refresh`] could not probe or walk.
+#[derive(Debug)]
+pub struct RefreshFailure {
+    pub device_instance: u32,
+    pub error: ClientError,
+}
+
+/// The devices of a site, keyed by device instance.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviceDatabase {
+    devices: BTreeMap<u32, DeviceRecord>,
+}
+
+impl DeviceDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the records of `store`.
+    pub fn load(store: &dyn DeviceStore) -> io::Result<Self> {
+        let mut db = Self::new();
+        for record in store.load()? {
+            db.insert(record);
+        }
+        Ok(db)
+    }
+
+    /// Saves every record to `store`, in device instance order.
+    pub fn save(&self, store: &dyn DeviceStore) -> io::Result<()> {
+        let records: Vec<DeviceRecord> = self.devices.values().cloned().collect();
+        store.save(&records)
+    }
+
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+
+    pub fn get(&self, device_instance: u32) -> Option<&DeviceRecord> {
+        self.devices.get(&device_instance)
+    }
+
+    /// Every record, in device instance order.
+    pub fn devices(&self) -> impl Iterator<Item = &DeviceRecord> {
+        self.devices.values()
+    }
+
+    /// Adds or replaces the record of its device.
+    pub fn insert(&mut self, record: DeviceRecord) -> Option<DeviceRecord> {
+        self.devices.insert(record.device_id.instance(), record)
+    }
+
+    pub fn remove(&mut self, device_instance: u32) -> Option<DeviceRecord> {
+        self.devices.remove(&device_instance)
+    }
+
+    /// Discovers devices with a Who-Is and probes and walks those that are new or due
+    /// for a walk.
+    ///
+    /// Known devices are updated with the address and time of their I-Am; devices that
+    /// do not answer are reported as missing but kept. Only a failure of the Who-Is
+    /// itself is returned as an error.
+    pub async fn refresh<D: DataLink>(
+        &mut self,
+        client: &BacnetClient<D>,
+        options: RefreshOptions,
+    ) -> Result<RefreshReport, ClientError> {
+        let discovered = client.who_is(options.range, options.wait).await?;
+        let now = SystemTime::now();
+        let mut report = RefreshReport::default();
+        let mut due = Vec::new();
+        let mut answered = Vec::with_capacity(discovered.len());
+
+        for device in discovered {
+            let Some(device_id) = device.device_id else {
+                continue;
+            };
+            if device_id.object_type() != ObjectType::Device {
+                continue;
+            }
+            let instance = device_id.instance();
+            answered.push(instance);
+            let record = match self.devices.get_mut(&instance) {
+                Some(record) => {
+                    if record.address != device.address {
+                        record.address = device.address;
+                        report.moved.push(instance);
+                    }
+                    record.last_seen = now;
+                    record
+                }
+                None => {
+                    report.added.push(instance);
+                    self.devices.entry(instance).or_insert(DeviceRecord::new(
+                        device_id,
+                        device.address,
+                        now,
+                    ))
+                }
+            };
+            if record.needs_walk(options.max_age, now) {
+                due.push(instance);
+            }
+        }
+
+        report.missing = self
+            .devices
+            .keys()
+            .copied()
+            .filter(|instance| {
+                !answered.contains(instance)
+                    && options
+                        .range
+                        .map_or(true, |(low, high)| (low..=high).contains(instance))
+            })
+            .collect();
+
+        for instance in due {
+            let Some(record) = self.devices.get_mut(&instance) else {
+                continue;
+            };
+            let result = async {
+                let capabilities = client.probe_capabilities(record.address).await?;
+                let walk = walk_device(client, record.address, record.device_id).await?;
+                Ok::<_, ClientError>((capabilities, walk))
+            }
+            .await;
+            match result {
+                Ok((capabilities, walk)) => {
+                    record.capabilities = Some(capabilities);
+                    record.device_info = walk.device_info;
+                    record.objects = walk.objects;
+                    record.last_walked = Some(SystemTime::now());
+                    report.walked.push(instance);
+                }
+                Err(error) => report.failed.push(RefreshFailure {
+                    device_instance: instance,
+                    error,
+                }),
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeviceDatabase, DeviceRecord, DeviceStore, MemoryStore};
+    use rustbac_core::types::{ObjectId, ObjectType};
+    use rustbac_datalink::DataLinkAddress;
+    use std::time::{Duration, SystemTime};
+
+    fn record(instance: u32) -> DeviceRecord {
+        DeviceRecord::new(
+            ObjectId::new(ObjectType::Device, instance),
+            DataLinkAddress::Mstp(instance as u8),
+            SystemTime::UNIX_EPOCH,
+        )
+    }
+
+    #[test]
+    fn database_round_trips_through_a_store() {
+        let mut db = DeviceDatabase::new();
+        db.insert(record(20));
+        db.insert(record(3));
+        let store = MemoryStore::default();
+        db.save(&store).unwrap();
+        let instances: Vec<u32> = store
+            .load()
+            .unwrap()
+            .iter()
+            .map(|r| r.device_id.instance())
+            .collect();
+        assert_eq!(instances, vec![3, 20]);
+        assert_eq!(DeviceDatabase::load(&store).unwrap(), db);
+    }
+
+    #[test]
+    fn walks_are_due_when_missing_or_old() {
+        let day = Duration::from_secs(86_400);
+        let now = SystemTime::UNIX_EPOCH + 3 * day;
+        let mut device = record(1);
+        assert!(device.needs_walk(day, now));
+        device.last_walked = Some(now - day / 2);
+        assert!(!device.needs_walk(day, now));
+        device.last_walked = Some(now - 2 * day);
+        assert!(device.needs_walk(day, now));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_file_store_saves_and_loads() {
+        use super::JsonFileStore;
+
+        let path =
+            std::env::temp_dir().join(format!("rustbac-device-db-{}.json", std::process::id()));
+        let store = JsonFileStore::new(&path);
+        assert!(store.load().unwrap().is_empty());
+        let mut db = DeviceDatabase::new();
+        db.insert(record(7));
+        db.save(&store).unwrap();
+        let loaded = DeviceDatabase::load(&store).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, db);
+    }
+}
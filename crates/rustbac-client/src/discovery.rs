@@ -14,6 +14,22 @@ pub struct DiscoveredDevice {
     pub device_id: Option<ObjectId>,
 }
 
+/// How [`BacnetClient::who_is`](crate::BacnetClient::who_is) handles several I-Am
+/// replies from the same device instance.
+///
+/// A client registered with a BBMD can hear a device twice: directly on the local
+/// network and forwarded from another BBMD, with a different source address each time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiscoveryDedup {
+    /// One entry per device, with the address of the latest reply.
+    #[default]
+    LatestAddress,
+    /// One entry per device, with the address of the first reply.
+    FirstAddress,
+    /// One entry per device and source address.
+    Off,
+}
+
 /// A BACnet object discovered via a Who-Has / I-Have exchange.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
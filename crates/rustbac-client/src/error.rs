@@ -1,13 +1,45 @@
+use crate::{ClientDataValue, ScheduleDiagnostic};
 use rustbac_core::types::{ErrorClass, ErrorCode};
-use rustbac_datalink::DataLinkError;
+use rustbac_datalink::{BvlcResultCode, DataLinkAddress, DataLinkError};
+use std::fmt;
 use thiserror::Error;
 
+/// Abort reasons (Clause 21) that indicate a temporary condition on the peer.
+const ABORT_PREEMPTED_BY_HIGHER_PRIORITY_TASK: u8 = 3;
+const ABORT_APPLICATION_EXCEEDED_REPLY_TIME: u8 = 8;
+const ABORT_OUT_OF_RESOURCES: u8 = 9;
+const ABORT_TSM_TIMEOUT: u8 = 10;
+
+/// The confirmed request a [`ClientError::Request`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestContext {
+    /// Device the request was sent to.
+    pub address: DataLinkAddress,
+    /// Confirmed service choice of the request (e.g. `0x0C` for ReadProperty).
+    pub service_choice: u8,
+    pub invoke_id: u8,
+}
+
+impl fmt::Display for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "service choice {} to {} (invoke id {})",
+            self.service_choice, self.address, self.invoke_id
+        )
+    }
+}
+
 /// Errors returned by [`BacnetClient`](crate::BacnetClient) operations.
 #[derive(Debug, Error)]
 pub enum ClientError {
     /// The underlying transport layer returned an error (send, receive, or bind failure).
     #[error("datalink error: {0}")]
-    DataLink(#[from] DataLinkError),
+    DataLink(DataLinkError),
+    /// A BBMD answered a BVLC command (BDT/FDT access, foreign-device registration) with a
+    /// NAK result code.
+    #[error("BBMD rejected the request: {0}")]
+    BbmdNak(BvlcResultCode),
     /// An APDU or NPDU could not be encoded into the output buffer.
     #[error("encode error: {0}")]
     Encode(#[from] rustbac_core::EncodeError),
@@ -37,16 +69,23 @@ pub enum ClientError {
     #[error("remote abort reason {reason} (server={server})")]
     RemoteAbort { reason: u8, server: bool },
     /// A segment-ACK with the negative-ACK bit set was received for `sequence_number`
-    /// during a segmented confirmed request.
+    /// during a segmented confirmed request, after the retransmit retries ran out.
     #[error("segment ack negative for sequence {sequence_number}")]
     SegmentNegativeAck { sequence_number: u8 },
     /// The encoded request payload is too large to fit within 255 segments of the
     /// negotiated maximum APDU size.
     #[error("segmented request too large")]
     SegmentedRequestTooLarge,
-    /// The reassembled segmented response exceeded the internal 1 MiB safety limit.
+    /// The reassembled segmented response exceeded the internal 1 MiB safety limit, or
+    /// the lower one set with
+    /// [`BacnetClient::with_memory_limits`](crate::BacnetClient::with_memory_limits).
     #[error("response payload exceeded {limit} bytes")]
     ResponseTooLarge { limit: usize },
+    /// The client already had `limit` confirmed requests outstanding, the cap set with
+    /// [`BacnetClient::with_memory_limits`](crate::BacnetClient::with_memory_limits);
+    /// nothing was sent.
+    #[error("client busy: {limit} requests outstanding")]
+    Busy { limit: usize },
     /// The response received from the device was syntactically valid but not understood
     /// (e.g. unexpected APDU type, missing required fields, or unsupported segmentation).
     #[error("unsupported response")]
@@ -55,4 +94,139 @@ pub enum ClientError {
     /// Tokio runtime context.
     #[error("no active Tokio runtime — build() must be called from within a tokio::Runtime")]
     NoTokioRuntime,
+    /// A verified write completed, but the value read back from the device did not match
+    /// the written value after `attempts` write + read-back cycles.
+    #[error("write verification failed after {attempts} attempt(s): wrote {expected:?}, read back {observed:?}")]
+    VerificationFailed {
+        expected: ClientDataValue,
+        observed: ClientDataValue,
+        attempts: u32,
+    },
+    /// A schedule write was not sent because the device would reject it; see
+    /// [`validate_exception_schedule`](crate::schedule::validate_exception_schedule).
+    #[error("invalid schedule: {} problem(s)", .diagnostics.len())]
+    InvalidSchedule {
+        diagnostics: Vec<ScheduleDiagnostic>,
+    },
+    /// The client's [`WritePolicy`](crate::WritePolicy) refused the request; nothing was
+    /// sent.
+    #[error("write denied by policy: {reason}")]
+    WriteDenied { reason: String },
+    /// The client's [`WritePolicy`](crate::WritePolicy) holds the request until it is
+    /// confirmed with [`RequestOptions::with_confirmed_writes`](crate::RequestOptions::with_confirmed_writes);
+    /// nothing was sent.
+    #[error("write needs confirmation: {reason}")]
+    WriteNeedsConfirmation { reason: String },
+    /// A confirmed request failed; `source` is the underlying error and `context`
+    /// identifies the request.
+    ///
+    /// Use [`root`](Self::root) to match on the underlying error regardless of context.
+    #[error("{context}: {source}")]
+    Request {
+        context: RequestContext,
+        source: Box<ClientError>,
+    },
+}
+
+impl From<DataLinkError> for ClientError {
+    fn from(err: DataLinkError) -> Self {
+        match err {
+            DataLinkError::BvlcResult(code) => Self::BbmdNak(code),
+            other => Self::DataLink(other),
+        }
+    }
+}
+
+impl ClientError {
+    /// Wraps `self` with the request it belongs to. Already-wrapped errors are unchanged.
+    pub fn with_context(self, context: RequestContext) -> Self {
+        match self {
+            Self::Request { .. } => self,
+            other => Self::Request {
+                context,
+                source: Box::new(other),
+            },
+        }
+    }
+
+    /// Returns the underlying error, looking through any [`ClientError::Request`] wrapper.
+    pub fn root(&self) -> &ClientError {
+        match self {
+            Self::Request { source, .. } => source.root(),
+            other => other,
+        }
+    }
+
+    /// Owned variant of [`root`](Self::root).
+    pub fn into_root(self) -> ClientError {
+        match self {
+            Self::Request { source, .. } => source.into_root(),
+            other => other,
+        }
+    }
+
+    /// Returns the request this error belongs to, if known.
+    pub fn context(&self) -> Option<&RequestContext> {
+        match self {
+            Self::Request { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Stable, machine-readable identifier for the underlying error kind (e.g.
+    /// `"client.timeout"`, `"datalink.io"`, `"decode.invalid_tag"`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Request { source, .. } => source.code(),
+            Self::DataLink(err) => err.code(),
+            Self::BbmdNak(_) => "client.bbmd_nak",
+            Self::Encode(err) => err.code(),
+            Self::Decode(err) => err.code(),
+            Self::Timeout => "client.timeout",
+            Self::RemoteServiceError { .. } => "client.remote_error",
+            Self::RemoteReject { .. } => "client.remote_reject",
+            Self::RemoteAbort { .. } => "client.remote_abort",
+            Self::SegmentNegativeAck { .. } => "client.segment_negative_ack",
+            Self::SegmentedRequestTooLarge => "client.segmented_request_too_large",
+            Self::ResponseTooLarge { .. } => "client.response_too_large",
+            Self::Busy { .. } => "client.busy",
+            Self::UnsupportedResponse => "client.unsupported_response",
+            Self::NoTokioRuntime => "client.no_tokio_runtime",
+            Self::VerificationFailed { .. } => "client.verification_failed",
+            Self::InvalidSchedule { .. } => "client.invalid_schedule",
+            Self::WriteDenied { .. } => "client.write_denied",
+            Self::WriteNeedsConfirmation { .. } => "client.write_needs_confirmation",
+        }
+    }
+
+    /// Returns `true` when sending the same request again may succeed: timeouts, a busy
+    /// client, transient transport failures, aborts for temporary conditions (preempted,
+    /// out of resources, reply-time exceeded), and Error PDUs reporting a busy device or
+    /// exhausted resources.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Request { source, .. } => source.is_retryable(),
+            Self::Timeout | Self::SegmentNegativeAck { .. } | Self::Busy { .. } => true,
+            Self::DataLink(err) => err.is_transient(),
+            Self::RemoteAbort { reason, .. } => matches!(
+                *reason,
+                ABORT_PREEMPTED_BY_HIGHER_PRIORITY_TASK
+                    | ABORT_APPLICATION_EXCEEDED_REPLY_TIME
+                    | ABORT_OUT_OF_RESOURCES
+                    | ABORT_TSM_TIMEOUT
+            ),
+            Self::RemoteServiceError {
+                error_class,
+                error_code,
+                ..
+            } => {
+                matches!(error_class, Some(ErrorClass::Resources))
+                    || matches!(
+                        error_code,
+                        Some(ErrorCode::DeviceBusy | ErrorCode::ConfigurationInProgress)
+                    )
+            }
+            _ => false,
+        }
+    }
 }
@@ -0,0 +1,130 @@
+//! Convenience types for the BACnet Event Enrollment object.
+//!
+//! An Event Enrollment watches one property, anywhere on the network, and raises events
+//! through a Notification Class when its event algorithm says so. Supervisors that keep
+//! alarming centrally create one per monitored point. See
+//! [`BacnetClient::create_event_enrollment`](crate::BacnetClient::create_event_enrollment),
+//! [`BacnetClient::configure_event_enrollment`](crate::BacnetClient::configure_event_enrollment)
+//! and [`BacnetClient::read_event_enrollment`](crate::BacnetClient::read_event_enrollment).
+
+use crate::ClientDataValue;
+use rustbac_core::services::event_enrollment::{EventParameter, EventTransitions};
+use rustbac_core::types::{DeviceObjectPropertyReference, PropertyId};
+
+/// The settings that make an Event Enrollment monitor a property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventEnrollmentConfig {
+    /// `Object_Property_Reference`: the monitored property.
+    pub reference: DeviceObjectPropertyReference,
+    /// `Event_Parameters`, which also fix the object's `Event_Type`.
+    pub parameters: EventParameter,
+    /// `Notification_Class`: the Notification Class instance that routes the events.
+    pub notification_class: u32,
+    /// `Event_Enable`: the transitions that generate notifications.
+    pub event_enable: EventTransitions,
+    /// `Notify_Type` (0 = alarm, 1 = event).
+    pub notify_type: u32,
+}
+
+impl EventEnrollmentConfig {
+    /// Alarms on every transition of `reference`, routed through `notification_class`.
+    pub fn new(
+        reference: DeviceObjectPropertyReference,
+        parameters: EventParameter,
+        notification_class: u32,
+    ) -> Self {
+        Self {
+            reference,
+            parameters,
+            notification_class,
+            event_enable: EventTransitions::ALL,
+            notify_type: 0,
+        }
+    }
+
+    /// Sets the transitions that generate notifications.
+    pub fn with_event_enable(mut self, event_enable: EventTransitions) -> Self {
+        self.event_enable = event_enable;
+        self
+    }
+
+    /// Reports the transitions as events rather than alarms.
+    pub fn as_events(mut self) -> Self {
+        self.notify_type = 1;
+        self
+    }
+
+    /// The writes of the plain application-valued properties, after the reference and
+    /// parameters.
+    pub(crate) fn simple_writes(&self) -> [(PropertyId, ClientDataValue); 3] {
+        [
+            (
+                PropertyId::NotificationClass,
+                ClientDataValue::Unsigned(self.notification_class),
+            ),
+            (
+                PropertyId::EventEnable,
+                ClientDataValue::BitString {
+                    unused_bits: EventTransitions::UNUSED_BITS,
+                    data: vec![self.event_enable.to_byte()],
+                },
+            ),
+            (
+                PropertyId::NotifyType,
+                ClientDataValue::Enumerated(self.notify_type),
+            ),
+        ]
+    }
+}
+
+/// Decodes an `Event_Enable` (or `Acked_Transitions`) value.
+pub(crate) fn event_transitions(value: &ClientDataValue) -> Option<EventTransitions> {
+    match value {
+        ClientDataValue::BitString { data, .. } => Some(EventTransitions::from_byte(
+            data.first().copied().unwrap_or(0),
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{event_transitions, EventEnrollmentConfig};
+    use crate::ClientDataValue;
+    use rustbac_core::services::event_enrollment::{EventParameter, EventTransitions};
+    use rustbac_core::types::{DeviceObjectPropertyReference, ObjectId, ObjectType, PropertyId};
+
+    #[test]
+    fn config_writes_event_enable_as_a_three_bit_string() {
+        let config = EventEnrollmentConfig::new(
+            DeviceObjectPropertyReference::local(
+                ObjectId::new(ObjectType::AnalogInput, 1),
+                PropertyId::PresentValue,
+            ),
+            EventParameter::OutOfRange {
+                time_delay: 0,
+                low_limit: 0.0,
+                high_limit: 1.0,
+                deadband: 0.0,
+            },
+            5,
+        )
+        .with_event_enable(EventTransitions {
+            to_normal: false,
+            ..EventTransitions::ALL
+        })
+        .as_events();
+
+        let writes = config.simple_writes();
+        assert_eq!(writes[0].1, ClientDataValue::Unsigned(5));
+        assert_eq!(
+            writes[1].1,
+            ClientDataValue::BitString {
+                unused_bits: 5,
+                data: vec![0xC0],
+            }
+        );
+        assert_eq!(writes[2].1, ClientDataValue::Enumerated(1));
+        assert_eq!(event_transitions(&writes[1].1), Some(config.event_enable));
+    }
+}
@@ -0,0 +1,360 @@
+//! Event message texts and their rendering for display.
+//!
+//! Event-reporting objects carry two three-element arrays, indexed by transition:
+//! Event_Message_Texts, the texts of the last notification of each transition, and
+//! Event_Message_Texts_Config, the texts the device puts in its notifications. See
+//! [`BacnetClient::read_event_message_texts`](crate::BacnetClient::read_event_message_texts)
+//! and
+//! [`BacnetClient::write_event_message_texts_config`](crate::BacnetClient::write_event_message_texts_config).
+//!
+//! An [`EventMessageCatalog`] renders received notifications from per-locale templates
+//! with `{placeholder}` parameters; see [`render_event_message`].
+
+use crate::{ClientDataValue, EventNotification};
+use rustbac_core::services::acknowledge_alarm::EventState;
+use rustbac_core::types::ObjectId;
+use std::collections::HashMap;
+
+/// An event transition, by its position in the message text arrays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventTransition {
+    ToOffnormal,
+    ToFault,
+    ToNormal,
+}
+
+impl EventTransition {
+    pub const ALL: [Self; 3] = [Self::ToOffnormal, Self::ToFault, Self::ToNormal];
+
+    /// The transition into `state`; every state but Normal and Fault is off-normal.
+    pub const fn into_state(state: EventState) -> Self {
+        match state {
+            EventState::Normal => Self::ToNormal,
+            EventState::Fault => Self::ToFault,
+            _ => Self::ToOffnormal,
+        }
+    }
+
+    /// The 1-based index of the transition in Event_Message_Texts and similar arrays.
+    pub const fn array_index(self) -> u32 {
+        match self {
+            Self::ToOffnormal => 1,
+            Self::ToFault => 2,
+            Self::ToNormal => 3,
+        }
+    }
+}
+
+/// One text per transition, as in Event_Message_Texts and Event_Message_Texts_Config.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventMessageTexts {
+    pub to_offnormal: String,
+    pub to_fault: String,
+    pub to_normal: String,
+}
+
+impl EventMessageTexts {
+    pub fn new(
+        to_offnormal: impl Into<String>,
+        to_fault: impl Into<String>,
+        to_normal: impl Into<String>,
+    ) -> Self {
+        Self {
+            to_offnormal: to_offnormal.into(),
+            to_fault: to_fault.into(),
+            to_normal: to_normal.into(),
+        }
+    }
+
+    pub fn get(&self, transition: EventTransition) -> &str {
+        match transition {
+            EventTransition::ToOffnormal => &self.to_offnormal,
+            EventTransition::ToFault => &self.to_fault,
+            EventTransition::ToNormal => &self.to_normal,
+        }
+    }
+
+    pub fn set(&mut self, transition: EventTransition, text: impl Into<String>) {
+        let text = text.into();
+        match transition {
+            EventTransition::ToOffnormal => self.to_offnormal = text,
+            EventTransition::ToFault => self.to_fault = text,
+            EventTransition::ToNormal => self.to_normal = text,
+        }
+    }
+
+    /// Decodes a whole three-element array of character strings.
+    pub(crate) fn from_value(value: &ClientDataValue) -> Option<Self> {
+        let ClientDataValue::Constructed { values, .. } = value else {
+            return None;
+        };
+        let [offnormal, fault, normal] = values.as_slice() else {
+            return None;
+        };
+        let text = |value: &ClientDataValue| match value {
+            ClientDataValue::CharacterString(text) => Some(text.to_string()),
+            _ => None,
+        };
+        Some(Self::new(text(offnormal)?, text(fault)?, text(normal)?))
+    }
+}
+
+impl EventNotification {
+    /// The transition this notification reports, or `None` for an unknown to-state.
+    pub fn transition(&self) -> Option<EventTransition> {
+        self.to_state.map(EventTransition::into_state)
+    }
+}
+
+/// Substitutes the parameters of a notification into `template`.
+///
+/// | Placeholder | Value |
+/// |---|---|
+/// | `{message}` | the message text the device sent, or nothing |
+/// | `{object}` | the event object, as `analog-input,3` |
+/// | `{object_type}`, `{instance}` | the parts of the event object |
+/// | `{device}` | the instance of the initiating device |
+/// | `{from_state}`, `{to_state}` | event states, as `high-limit`, or the number if unknown |
+/// | `{priority}`, `{notification_class}`, `{event_type}` | the numbers sent |
+/// | `{notify_type}` | `alarm`, `event` or `ack-notification` |
+///
+/// `{{` and `}}` stand for braces; unknown placeholders are kept as written.
+pub fn render_event_message(template: &str, notification: &EventNotification) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        let parameter = tail
+            .strip_prefix('{')
+            .and_then(|inner| Some((inner, inner.find('}')?)))
+            .and_then(|(inner, end)| Some((end, message_parameter(&inner[..end], notification)?)));
+        match parameter {
+            Some((end, value)) => {
+                out.push_str(&value);
+                rest = &tail[end + 2..];
+            }
+            None => {
+                out.push_str(&tail[..1]);
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn message_parameter(name: &str, n: &EventNotification) -> Option<String> {
+    Some(match name {
+        "message" => n.message_text.clone().unwrap_or_default(),
+        "object" => format!(
+            "{},{}",
+            n.event_object_id.object_type(),
+            n.event_object_id.instance()
+        ),
+        "object_type" => n.event_object_id.object_type().to_string(),
+        "instance" => n.event_object_id.instance().to_string(),
+        "device" => n.initiating_device_id.instance().to_string(),
+        "from_state" => state_name(n.from_state, n.from_state_raw),
+        "to_state" => state_name(n.to_state, n.to_state_raw),
+        "priority" => n.priority.to_string(),
+        "notification_class" => n.notification_class.to_string(),
+        "event_type" => n.event_type.to_string(),
+        "notify_type" => match n.notify_type {
+            0 => "alarm".to_string(),
+            1 => "event".to_string(),
+            2 => "ack-notification".to_string(),
+            other => other.to_string(),
+        },
+        _ => return None,
+    })
+}
+
+pub(crate) fn state_name(state: Option<EventState>, raw: u32) -> String {
+    match state {
+        Some(EventState::Normal) => "normal".to_string(),
+        Some(EventState::Fault) => "fault".to_string(),
+        Some(EventState::Offnormal) => "offnormal".to_string(),
+        Some(EventState::HighLimit) => "high-limit".to_string(),
+        Some(EventState::LowLimit) => "low-limit".to_string(),
+        Some(EventState::LifeSafetyAlarm) => "life-safety-alarm".to_string(),
+        None => raw.to_string(),
+    }
+}
+
+/// Per-locale [`render_event_message`] templates for each transition, optionally per
+/// event object.
+///
+/// A locale such as `de-AT` without templates of its own uses those of `de`, then those
+/// of the fallback locale. Notifications no template covers show the device's own
+/// message text, or [`DEFAULT_EVENT_TEMPLATE`] without one.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventMessageCatalog {
+    fallback_locale: Option<String>,
+    templates: HashMap<String, EventMessageTexts>,
+    object_templates: HashMap<(String, ObjectId), EventMessageTexts>,
+}
+
+/// Rendered for notifications without templates or message text.
+pub const DEFAULT_EVENT_TEMPLATE: &str = "{object}: {from_state} to {to_state}";
+
+impl EventMessageCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the locale used when the requested one has no templates.
+    pub fn with_fallback_locale(mut self, locale: impl Into<String>) -> Self {
+        self.fallback_locale = Some(locale.into());
+        self
+    }
+
+    /// Sets the templates of `locale` for every event object.
+    ///
+    /// An object's Event_Message_Texts_Config can be used as is.
+    pub fn with_templates(mut self, locale: impl Into<String>, texts: EventMessageTexts) -> Self {
+        self.templates.insert(locale.into(), texts);
+        self
+    }
+
+    /// Sets the templates of `locale` for `object` only.
+    pub fn with_object_templates(
+        mut self,
+        locale: impl Into<String>,
+        object: ObjectId,
+        texts: EventMessageTexts,
+    ) -> Self {
+        self.object_templates.insert((locale.into(), object), texts);
+        self
+    }
+
+    /// The template for `notification` in `locale`, if any; empty templates count as
+    /// missing.
+    pub fn template(&self, notification: &EventNotification, locale: &str) -> Option<&str> {
+        let transition = notification.transition()?;
+        let language = locale.split(['-', '_']).next().unwrap_or(locale);
+        let locales = [
+            Some(locale),
+            Some(language),
+            self.fallback_locale.as_deref(),
+        ];
+        locales.into_iter().flatten().find_map(|locale| {
+            let object = self
+                .object_templates
+                .get(&(locale.to_string(), notification.event_object_id));
+            [object, self.templates.get(locale)]
+                .into_iter()
+                .flatten()
+                .map(|texts| texts.get(transition))
+                .find(|template| !template.is_empty())
+        })
+    }
+
+    /// `notification` as text for a reader in `locale`.
+    pub fn render(&self, notification: &EventNotification, locale: &str) -> String {
+        match (
+            self.template(notification, locale),
+            &notification.message_text,
+        ) {
+            (Some(template), _) => render_event_message(template, notification),
+            (None, Some(text)) => text.clone(),
+            (None, None) => render_event_message(DEFAULT_EVENT_TEMPLATE, notification),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_event_message, EventMessageCatalog, EventMessageTexts, EventTransition};
+    use crate::{ClientDataValue, EventNotification};
+    use rustbac_core::services::acknowledge_alarm::{EventState, TimeStamp};
+    use rustbac_core::types::{ObjectId, ObjectType};
+    use rustbac_datalink::DataLinkAddress;
+
+    fn notification(to_state: EventState) -> EventNotification {
+        EventNotification {
+            source: DataLinkAddress::Mstp(3),
+            confirmed: false,
+            process_id: 1,
+            initiating_device_id: ObjectId::new(ObjectType::Device, 1200),
+            event_object_id: ObjectId::new(ObjectType::AnalogInput, 7),
+            timestamp: TimeStamp::SequenceNumber(1),
+            notification_class: 10,
+            priority: 100,
+            event_type: 5,
+            message_text: Some("AI-7 high".into()),
+            notify_type: 0,
+            ack_required: Some(true),
+            from_state_raw: 0,
+            from_state: Some(EventState::Normal),
+            to_state_raw: to_state as u32,
+            to_state: Some(to_state),
+            event_values: None,
+        }
+    }
+
+    #[test]
+    fn templates_substitute_parameters_and_escape_braces() {
+        let rendered = render_event_message(
+            "{{{object}}} on {device}: {from_state} -> {to_state} ({message}, {unknown}",
+            &notification(EventState::HighLimit),
+        );
+        assert_eq!(
+            rendered,
+            "{analog-input,7} on 1200: normal -> high-limit (AI-7 high, {unknown}"
+        );
+    }
+
+    #[test]
+    fn catalog_falls_back_from_region_to_language_to_default_locale() {
+        let catalog = EventMessageCatalog::new()
+            .with_fallback_locale("en")
+            .with_templates(
+                "en",
+                EventMessageTexts::new("{object} alarm", "{object} fault", "{object} normal"),
+            )
+            .with_templates("de", EventMessageTexts::new("{object} Alarm", "", ""))
+            .with_object_templates(
+                "de",
+                ObjectId::new(ObjectType::AnalogInput, 7),
+                EventMessageTexts::new("Zuluft zu warm", "", ""),
+            );
+
+        let high = notification(EventState::HighLimit);
+        assert_eq!(high.transition(), Some(EventTransition::ToOffnormal));
+        assert_eq!(catalog.render(&high, "de-AT"), "Zuluft zu warm");
+        assert_eq!(catalog.render(&high, "fr"), "analog-input,7 alarm");
+        assert_eq!(
+            catalog.render(&notification(EventState::Fault), "de"),
+            "analog-input,7 fault"
+        );
+        assert_eq!(
+            EventMessageCatalog::new().render(&notification(EventState::Normal), "en"),
+            "AI-7 high"
+        );
+    }
+
+    #[test]
+    fn texts_decode_from_a_three_element_array() {
+        let value = ClientDataValue::Constructed {
+            tag_num: 3,
+            values: vec![
+                ClientDataValue::CharacterString("hot".into()),
+                ClientDataValue::CharacterString("broken".into()),
+                ClientDataValue::CharacterString("ok".into()),
+            ],
+        };
+        let texts = EventMessageTexts::from_value(&value).unwrap();
+        assert_eq!(texts.get(EventTransition::ToFault), "broken");
+        assert_eq!(EventTransition::ToNormal.array_index(), 3);
+        assert_eq!(
+            EventMessageTexts::from_value(&ClientDataValue::CharacterString("x".into())),
+            None
+        );
+    }
+}
@@ -0,0 +1,633 @@
+//! Tabular export of walk, trend and COV data to CSV and (with the `parquet` feature)
+//! Apache Parquet.
+//!
+//! Each source is flattened into an [`ExportTable`] with a fixed, typed schema. Values are
+//! split into a numeric `value` column (Real, Double, integers, Enumerated, Boolean) and a
+//! `value_text` column for everything else, so a column always holds a single type.
+
+use crate::cov_manager::{CovUpdate, UpdateSource};
+use crate::range::ReadRangeResult;
+use crate::walk::DeviceWalkResult;
+use crate::ClientDataValue;
+use rustbac_core::types::{Date, ObjectId, Time};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "parquet")]
+mod parquet;
+
+/// Type of an [`ExportTable`] column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Boolean,
+    Int64,
+    Double,
+    Utf8,
+    /// Milliseconds since the Unix epoch. BACnet dates and times carry no time zone, so
+    /// timestamps taken from device data are device-local wall-clock time.
+    TimestampMillis,
+}
+
+/// A named, typed column. Every column is nullable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+    pub name: &'static str,
+    pub column_type: ColumnType,
+}
+
+/// One cell of an [`ExportTable`]; non-null cells match their column's [`ColumnType`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Null,
+    Boolean(bool),
+    Int64(i64),
+    Double(f64),
+    Utf8(String),
+    TimestampMillis(i64),
+}
+
+const WALK_COLUMNS: &[Column] = &[
+    column("device_instance", ColumnType::Int64),
+    column("object_type", ColumnType::Utf8),
+    column("object_instance", ColumnType::Int64),
+    column("object_name", ColumnType::Utf8),
+    column("description", ColumnType::Utf8),
+    column("value", ColumnType::Double),
+    column("value_text", ColumnType::Utf8),
+    column("units", ColumnType::Int64),
+    column("in_alarm", ColumnType::Boolean),
+    column("fault", ColumnType::Boolean),
+    column("overridden", ColumnType::Boolean),
+    column("out_of_service", ColumnType::Boolean),
+];
+
+const READ_RANGE_COLUMNS: &[Column] = &[
+    column("object_type", ColumnType::Utf8),
+    column("object_instance", ColumnType::Int64),
+    column("property", ColumnType::Utf8),
+    column("item", ColumnType::Int64),
+    column("timestamp", ColumnType::TimestampMillis),
+    column("value", ColumnType::Double),
+    column("value_text", ColumnType::Utf8),
+];
+
+const COV_COLUMNS: &[Column] = &[
+    column("received_at", ColumnType::TimestampMillis),
+    column("address", ColumnType::Utf8),
+    column("object_type", ColumnType::Utf8),
+    column("object_instance", ColumnType::Int64),
+    column("property", ColumnType::Utf8),
+    column("array_index", ColumnType::Int64),
+    column("value", ColumnType::Double),
+    column("value_text", ColumnType::Utf8),
+    column("source", ColumnType::Utf8),
+];
+
+const fn column(name: &'static str, column_type: ColumnType) -> Column {
+    Column { name, column_type }
+}
+
+/// A typed table ready to be written as CSV or Parquet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportTable {
+    columns: &'static [Column],
+    rows: Vec<Vec<Cell>>,
+}
+
+impl ExportTable {
+    /// One row per object of a device walk.
+    pub fn from_walk(walk: &DeviceWalkResult) -> Self {
+        let device_instance = Cell::Int64(walk.device_id.instance() as i64);
+        let rows = walk
+            .objects
+            .iter()
+            .map(|obj| {
+                let (value, value_text) = value_cells(obj.present_value.as_ref());
+                let flags = status_flags(obj.status_flags.as_ref());
+                let mut row = vec![
+                    device_instance.clone(),
+                    Cell::Utf8(obj.object_type.to_string()),
+                    Cell::Int64(obj.object_id.instance() as i64),
+                    utf8(obj.object_name.as_deref()),
+                    utf8(obj.description.as_deref()),
+                    value,
+                    value_text,
+                    obj.units.map_or(Cell::Null, |u| Cell::Int64(u as i64)),
+                ];
+                row.extend(flags);
+                row
+            })
+            .collect();
+        Self {
+            columns: WALK_COLUMNS,
+            rows,
+        }
+    }
+
+    /// One row per ReadRange item, e.g. trend-log records.
+    ///
+    /// For constructed items the first Date and Time found become `timestamp` and the
+    /// first other primitive (status flags excluded) becomes the value.
+    pub fn from_read_range(range: &ReadRangeResult) -> Self {
+        let rows = range
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let mut leaves = Vec::new();
+                flatten(item, &mut leaves);
+                let date = leaves.iter().find_map(|v| match v {
+                    ClientDataValue::Date(d) => Some(*d),
+                    _ => None,
+                });
+                let time = leaves.iter().find_map(|v| match v {
+                    ClientDataValue::Time(t) => Some(*t),
+                    _ => None,
+                });
+                let datum = leaves.iter().copied().find(|v| {
+                    !matches!(
+                        v,
+                        ClientDataValue::Date(_)
+                            | ClientDataValue::Time(_)
+                            | ClientDataValue::BitString { .. }
+                    )
+                });
+                let (value, value_text) = value_cells(datum);
+                vec![
+                    Cell::Utf8(range.object_id.object_type().to_string()),
+                    Cell::Int64(range.object_id.instance() as i64),
+                    Cell::Utf8(range.property_id.to_string()),
+                    Cell::Int64(index as i64 + 1),
+                    date.zip(time)
+                        .and_then(|(d, t)| bacnet_timestamp_millis(d, t))
+                        .map_or(Cell::Null, Cell::TimestampMillis),
+                    value,
+                    value_text,
+                ]
+            })
+            .collect();
+        Self {
+            columns: READ_RANGE_COLUMNS,
+            rows,
+        }
+    }
+
+    /// An empty table for COV updates; fill it with
+    /// [`push_cov_update`](Self::push_cov_update).
+    pub fn cov_updates() -> Self {
+        Self {
+            columns: COV_COLUMNS,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Appends one row per property value of `update`, stamped with `received_at`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table was not created with [`cov_updates`](Self::cov_updates).
+    pub fn push_cov_update(&mut self, update: &CovUpdate, received_at: SystemTime) {
+        assert!(
+            self.columns == COV_COLUMNS,
+            "push_cov_update on a non-COV table"
+        );
+        let received_at = received_at
+            .duration_since(UNIX_EPOCH)
+            .map_or(Cell::Null, |d| Cell::TimestampMillis(d.as_millis() as i64));
+        let source = match update.source {
+            UpdateSource::Cov => "cov",
+            UpdateSource::Poll => "poll",
+        };
+        for property in &update.values {
+            let (value, value_text) = value_cells(Some(&property.value));
+            self.rows.push(vec![
+                received_at.clone(),
+                Cell::Utf8(update.address.to_string()),
+                Cell::Utf8(update.object_id.object_type().to_string()),
+                Cell::Int64(update.object_id.instance() as i64),
+                Cell::Utf8(property.property_id.to_string()),
+                property
+                    .array_index
+                    .map_or(Cell::Null, |i| Cell::Int64(i as i64)),
+                value,
+                value_text,
+                Cell::Utf8(source.to_string()),
+            ]);
+        }
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        self.columns
+    }
+
+    pub fn rows(&self) -> &[Vec<Cell>] {
+        &self.rows
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Removes all rows, keeping the schema. Together with
+    /// [`write_csv_rows`](Self::write_csv_rows) this lets a long-running COV stream be
+    /// appended to one CSV file in batches.
+    pub fn clear(&mut self) {
+        self.rows.clear();
+    }
+
+    /// Writes the header line and all rows as RFC 4180 CSV.
+    pub fn write_csv<W: Write>(&self, mut w: W) -> io::Result<()> {
+        let header: Vec<&str> = self.columns.iter().map(|c| c.name).collect();
+        write_csv_line(&mut w, header.iter().map(|name| csv_field(name)))?;
+        self.write_csv_rows(w)
+    }
+
+    /// Writes the rows without a header line.
+    pub fn write_csv_rows<W: Write>(&self, mut w: W) -> io::Result<()> {
+        for row in &self.rows {
+            write_csv_line(&mut w, row.iter().map(csv_cell))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the table as an uncompressed Parquet file with a single row group.
+    #[cfg(feature = "parquet")]
+    pub fn write_parquet<W: Write>(&self, w: W) -> io::Result<()> {
+        parquet::write_table(self, w)
+    }
+}
+
+fn utf8(value: Option<&str>) -> Cell {
+    value.map_or(Cell::Null, |s| Cell::Utf8(s.to_string()))
+}
+
+/// Splits a value into the numeric `value` and textual `value_text` cells.
+fn value_cells(value: Option<&ClientDataValue>) -> (Cell, Cell) {
+    let Some(value) = value else {
+        return (Cell::Null, Cell::Null);
+    };
+    let number = match value {
+        ClientDataValue::Boolean(v) => Some(if *v { 1.0 } else { 0.0 }),
+        ClientDataValue::Unsigned(v) | ClientDataValue::Enumerated(v) => Some(f64::from(*v)),
+        ClientDataValue::Signed(v) => Some(f64::from(*v)),
+        ClientDataValue::Real(v) => Some(f64::from(*v)),
+        ClientDataValue::Double(v) => Some(*v),
+        _ => None,
+    };
+    match number {
+        Some(number) => (Cell::Double(number), Cell::Null),
+        None => (Cell::Null, Cell::Utf8(value_text(value))),
+    }
+}
+
+fn value_text(value: &ClientDataValue) -> String {
+    match value {
+        ClientDataValue::Null => "null".to_string(),
+        ClientDataValue::CharacterString(s) => s.to_string(),
+        ClientDataValue::OctetString(bytes) => bytes.iter().fold(String::new(), |mut hex, b| {
+            let _ = write!(hex, "{b:02x}");
+            hex
+        }),
+        ClientDataValue::BitString { unused_bits, data } => bits(*unused_bits, data)
+            .map(|bit| if bit { '1' } else { '0' })
+            .collect(),
+        ClientDataValue::Date(d) => format_date(*d),
+        ClientDataValue::Time(t) => format_time(*t),
+        ClientDataValue::ObjectId(oid) => format_object_id(*oid),
+        other => format!("{other:?}"),
+    }
+}
+
+fn bits(unused_bits: u8, data: &[u8]) -> impl Iterator<Item = bool> + '_ {
+    let len = (data.len() * 8).saturating_sub(unused_bits as usize);
+    (0..len).map(move |i| data[i / 8] & (0x80 >> (i % 8)) != 0)
+}
+
+fn status_flags(value: Option<&ClientDataValue>) -> [Cell; 4] {
+    match value {
+        Some(ClientDataValue::BitString { unused_bits, data }) => {
+            let mut flags = [Cell::Null, Cell::Null, Cell::Null, Cell::Null];
+            for (flag, bit) in flags.iter_mut().zip(bits(*unused_bits, data)) {
+                *flag = Cell::Boolean(bit);
+            }
+            flags
+        }
+        _ => [Cell::Null, Cell::Null, Cell::Null, Cell::Null],
+    }
+}
+
+fn flatten<'a>(value: &'a ClientDataValue, out: &mut Vec<&'a ClientDataValue>) {
+    match value {
+        ClientDataValue::Constructed { values, .. } => {
+            for child in values {
+                flatten(child, out);
+            }
+        }
+        leaf => out.push(leaf),
+    }
+}
+
+fn format_object_id(oid: ObjectId) -> String {
+    format!("{},{}", oid.object_type(), oid.instance())
+}
+
+fn format_date(d: Date) -> String {
+    let field = |v: u8, width: usize| {
+        if v == 0xFF {
+            "*".repeat(width)
+        } else {
+            format!("{v:0width$}")
+        }
+    };
+    let year = if d.year_since_1900 == 0xFF {
+        "****".to_string()
+    } else {
+        (1900 + u32::from(d.year_since_1900)).to_string()
+    };
+    format!("{year}-{}-{}", field(d.month, 2), field(d.day, 2))
+}
+
+fn format_time(t: Time) -> String {
+    let field = |v: u8| {
+        if v == 0xFF {
+            "**".to_string()
+        } else {
+            format!("{v:02}")
+        }
+    };
+    format!(
+        "{}:{}:{}.{}",
+        field(t.hour),
+        field(t.minute),
+        field(t.second),
+        field(t.hundredths)
+    )
+}
+
+/// Converts a fully specified BACnet date and time to milliseconds since the Unix epoch,
+/// treating them as UTC.
+fn bacnet_timestamp_millis(d: Date, t: Time) -> Option<i64> {
+    if [
+        d.year_since_1900,
+        d.month,
+        d.day,
+        t.hour,
+        t.minute,
+        t.second,
+    ]
+    .contains(&0xFF)
+        || !(1..=12).contains(&d.month)
+        || !(1..=31).contains(&d.day)
+    {
+        return None;
+    }
+    let hundredths = if t.hundredths == 0xFF {
+        0
+    } else {
+        t.hundredths
+    };
+    let days = days_from_civil(1900 + i64::from(d.year_since_1900), d.month, d.day);
+    let seconds =
+        days * 86_400 + i64::from(t.hour) * 3_600 + i64::from(t.minute) * 60 + i64::from(t.second);
+    Some(seconds * 1_000 + i64::from(hundredths) * 10)
+}
+
+/// The instant of a fully specified BACnet date and time, treating them as UTC.
+pub(crate) fn bacnet_system_time(d: Date, t: Time) -> Option<SystemTime> {
+    let millis = bacnet_timestamp_millis(d, t)?;
+    let magnitude = Duration::from_millis(millis.unsigned_abs());
+    if millis >= 0 {
+        UNIX_EPOCH.checked_add(magnitude)
+    } else {
+        UNIX_EPOCH.checked_sub(magnitude)
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Inverse of [`days_from_civil`].
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// The UTC BACnet date and time of `at`, to the hundredth of a second.
+pub(crate) fn bacnet_date_time(at: SystemTime) -> (Date, Time) {
+    let millis = at
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX));
+    let (days, ms_of_day) = (millis.div_euclid(86_400_000), millis.rem_euclid(86_400_000));
+    let (year, month, day) = civil_from_days(days);
+    let date = Date {
+        year_since_1900: u8::try_from(year - 1900).unwrap_or(0xFF),
+        month: month as u8,
+        day: day as u8,
+        // 1970-01-01 was a Thursday; BACnet counts Monday as 1.
+        weekday: ((days + 3).rem_euclid(7) + 1) as u8,
+    };
+    let time = Time {
+        hour: (ms_of_day / 3_600_000) as u8,
+        minute: (ms_of_day / 60_000 % 60) as u8,
+        second: (ms_of_day / 1_000 % 60) as u8,
+        hundredths: (ms_of_day % 1_000 / 10) as u8,
+    };
+    (date, time)
+}
+
+fn format_timestamp_millis(millis: i64) -> String {
+    let (days, ms_of_day) = (millis.div_euclid(86_400_000), millis.rem_euclid(86_400_000));
+    let (year, month, day) = civil_from_days(days);
+    let seconds = ms_of_day / 1_000;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}",
+        seconds / 3_600,
+        seconds / 60 % 60,
+        seconds % 60,
+        ms_of_day % 1_000
+    )
+}
+
+fn csv_cell(cell: &Cell) -> String {
+    match cell {
+        Cell::Null => String::new(),
+        Cell::Boolean(v) => v.to_string(),
+        Cell::Int64(v) => v.to_string(),
+        Cell::Double(v) => v.to_string(),
+        Cell::Utf8(s) => csv_field(s),
+        Cell::TimestampMillis(ms) => format_timestamp_millis(*ms),
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn write_csv_line<W: Write>(w: &mut W, fields: impl Iterator<Item = String>) -> io::Result<()> {
+    let line: Vec<String> = fields.collect();
+    w.write_all(line.join(",").as_bytes())?;
+    w.write_all(b"\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cell, ExportTable};
+    use crate::cov_manager::{CovUpdate, UpdateSource};
+    use crate::range::{ClientBitString, ReadRangeResult};
+    use crate::walk::{DeviceInfo, DeviceWalkResult, ObjectSummary};
+    use crate::{ClientDataValue, CovPropertyValue};
+    use rustbac_core::types::{Date, ObjectId, ObjectType, PropertyId, Time};
+    use rustbac_datalink::DataLinkAddress;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn csv(table: &ExportTable) -> String {
+        let mut out = Vec::new();
+        table.write_csv(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn walk_csv_has_typed_columns_and_quoting() {
+        let ai = ObjectId::new(ObjectType::AnalogInput, 3);
+        let walk = DeviceWalkResult {
+            device_id: ObjectId::new(ObjectType::Device, 10),
+            device_info: DeviceInfo::default(),
+            objects: vec![ObjectSummary {
+                object_id: ai,
+                object_name: Some("Zone \"A\", north".into()),
+                object_type: ObjectType::AnalogInput,
+                present_value: Some(ClientDataValue::Real(21.5)),
+                description: None,
+                units: Some(62),
+                status_flags: Some(ClientDataValue::BitString {
+                    unused_bits: 4,
+                    data: vec![0b0100_0000],
+                }),
+                property_list: None,
+            }],
+        };
+
+        assert_eq!(
+            csv(&ExportTable::from_walk(&walk)),
+            "device_instance,object_type,object_instance,object_name,description,value,\
+             value_text,units,in_alarm,fault,overridden,out_of_service\r\n\
+             10,analog-input,3,\"Zone \"\"A\"\", north\",,21.5,,62,false,true,false,false\r\n"
+        );
+    }
+
+    #[test]
+    fn read_range_records_yield_timestamp_and_value() {
+        let record = ClientDataValue::Constructed {
+            tag_num: 0,
+            values: vec![
+                ClientDataValue::Constructed {
+                    tag_num: 0,
+                    values: vec![
+                        ClientDataValue::Date(Date {
+                            year_since_1900: 124,
+                            month: 3,
+                            day: 1,
+                            weekday: 5,
+                        }),
+                        ClientDataValue::Time(Time {
+                            hour: 13,
+                            minute: 45,
+                            second: 30,
+                            hundredths: 25,
+                        }),
+                    ],
+                },
+                ClientDataValue::Real(19.25),
+                ClientDataValue::BitString {
+                    unused_bits: 4,
+                    data: vec![0],
+                },
+            ],
+        };
+        let range = ReadRangeResult {
+            object_id: ObjectId::new(ObjectType::TrendLog, 1),
+            property_id: PropertyId::LogBuffer,
+            array_index: None,
+            result_flags: ClientBitString {
+                unused_bits: 5,
+                data: vec![0],
+            },
+            item_count: 2,
+            items: vec![record, ClientDataValue::CharacterString("gap".into())],
+            records: Vec::new(),
+        };
+
+        let table = ExportTable::from_read_range(&range);
+        assert_eq!(table.rows()[0][4], Cell::TimestampMillis(1_709_300_730_250));
+        assert_eq!(table.rows()[0][5], Cell::Double(19.25));
+        assert_eq!(
+            csv(&table),
+            "object_type,object_instance,property,item,timestamp,value,value_text\r\n\
+             trend-log,1,log-buffer,1,2024-03-01T13:45:30.250,19.25,\r\n\
+             trend-log,1,log-buffer,2,,,gap\r\n"
+        );
+    }
+
+    #[test]
+    fn cov_updates_append_one_row_per_value() {
+        let mut table = ExportTable::cov_updates();
+        let update = CovUpdate {
+            address: DataLinkAddress::Ip(([192, 168, 1, 9], 47808).into()),
+            object_id: ObjectId::new(ObjectType::BinaryValue, 2),
+            values: vec![
+                CovPropertyValue {
+                    property_id: PropertyId::PresentValue,
+                    array_index: None,
+                    value: ClientDataValue::Enumerated(1),
+                    priority: None,
+                },
+                CovPropertyValue {
+                    property_id: PropertyId::StatusFlags,
+                    array_index: None,
+                    value: ClientDataValue::BitString {
+                        unused_bits: 4,
+                        data: vec![0b0001_0000],
+                    },
+                    priority: None,
+                },
+            ],
+            source: UpdateSource::Poll,
+        };
+        table.push_cov_update(&update, UNIX_EPOCH + Duration::from_millis(1_500));
+        assert_eq!(table.len(), 2);
+
+        let mut rows = Vec::new();
+        table.write_csv_rows(&mut rows).unwrap();
+        assert_eq!(
+            String::from_utf8(rows).unwrap(),
+            "1970-01-01T00:00:01.500,192.168.1.9:47808,binary-value,2,present-value,,1,,poll\r\n\
+             1970-01-01T00:00:01.500,192.168.1.9:47808,binary-value,2,status-flags,,,0001,poll\r\n"
+        );
+    }
+}
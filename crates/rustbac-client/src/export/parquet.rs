@@ -0,0 +1,302 @@
+//! Minimal Parquet writer: one row group, one uncompressed PLAIN data page per column,
+//! every column OPTIONAL. File and page metadata use the Thrift compact protocol.
+
+use super::{Cell, ColumnType, ExportTable};
+use std::io::{self, Write};
+
+const MAGIC: &[u8] = b"PAR1";
+
+// parquet.thrift enum values.
+const TYPE_BOOLEAN: i32 = 0;
+const TYPE_INT64: i32 = 2;
+const TYPE_DOUBLE: i32 = 5;
+const TYPE_BYTE_ARRAY: i32 = 6;
+const CONVERTED_UTF8: i32 = 0;
+const CONVERTED_TIMESTAMP_MILLIS: i32 = 9;
+const REPETITION_OPTIONAL: i32 = 1;
+const ENCODING_PLAIN: i32 = 0;
+const ENCODING_RLE: i32 = 3;
+const CODEC_UNCOMPRESSED: i32 = 0;
+const PAGE_DATA: i32 = 0;
+
+// Thrift compact protocol type ids.
+const CT_I32: u8 = 5;
+const CT_I64: u8 = 6;
+const CT_BINARY: u8 = 8;
+const CT_LIST: u8 = 9;
+const CT_STRUCT: u8 = 12;
+
+pub(super) fn write_table<W: Write>(table: &ExportTable, mut w: W) -> io::Result<()> {
+    let num_rows = table.rows.len() as i64;
+    let mut file = MAGIC.to_vec();
+    let mut chunks = Vec::with_capacity(table.columns.len());
+
+    for (index, column) in table.columns.iter().enumerate() {
+        let cells = table.rows.iter().map(|row| &row[index]);
+        let page = encode_page(column.column_type, cells)?;
+
+        let mut header = Compact::default();
+        header.i32(1, PAGE_DATA);
+        header.i32(2, page.len() as i32);
+        header.i32(3, page.len() as i32);
+        header.begin_struct(5);
+        header.i32(1, num_rows as i32);
+        header.i32(2, ENCODING_PLAIN);
+        header.i32(3, ENCODING_RLE);
+        header.i32(4, ENCODING_RLE);
+        header.end_struct();
+        header.stop();
+
+        let offset = file.len() as i64;
+        file.extend_from_slice(&header.buf);
+        file.extend_from_slice(&page);
+        chunks.push((offset, (header.buf.len() + page.len()) as i64));
+    }
+
+    let mut meta = Compact::default();
+    meta.i32(1, 1);
+    meta.begin_list(2, CT_STRUCT, table.columns.len() + 1);
+    meta.begin_element();
+    meta.binary(4, b"schema");
+    meta.i32(5, table.columns.len() as i32);
+    meta.end_element();
+    for column in table.columns {
+        let (physical, converted) = physical_type(column.column_type);
+        meta.begin_element();
+        meta.i32(1, physical);
+        meta.i32(3, REPETITION_OPTIONAL);
+        meta.binary(4, column.name.as_bytes());
+        if let Some(converted) = converted {
+            meta.i32(6, converted);
+        }
+        meta.end_element();
+    }
+    meta.i64(3, num_rows);
+    meta.begin_list(4, CT_STRUCT, 1);
+    meta.begin_element();
+    meta.begin_list(1, CT_STRUCT, table.columns.len());
+    for (column, &(offset, size)) in table.columns.iter().zip(&chunks) {
+        meta.begin_element();
+        meta.i64(2, offset);
+        meta.begin_struct(3);
+        meta.i32(1, physical_type(column.column_type).0);
+        meta.begin_list(2, CT_I32, 2);
+        meta.list_i32(ENCODING_PLAIN);
+        meta.list_i32(ENCODING_RLE);
+        meta.begin_list(3, CT_BINARY, 1);
+        meta.list_binary(column.name.as_bytes());
+        meta.i32(4, CODEC_UNCOMPRESSED);
+        meta.i64(5, num_rows);
+        meta.i64(6, size);
+        meta.i64(7, size);
+        meta.i64(9, offset);
+        meta.end_struct();
+        meta.end_element();
+    }
+    meta.i64(2, chunks.iter().map(|&(_, size)| size).sum());
+    meta.i64(3, num_rows);
+    meta.end_element();
+    meta.binary(6, concat!("rustbac ", env!("CARGO_PKG_VERSION")).as_bytes());
+    meta.stop();
+
+    file.extend_from_slice(&meta.buf);
+    file.extend_from_slice(&(meta.buf.len() as u32).to_le_bytes());
+    file.extend_from_slice(MAGIC);
+    w.write_all(&file)
+}
+
+fn physical_type(column_type: ColumnType) -> (i32, Option<i32>) {
+    match column_type {
+        ColumnType::Boolean => (TYPE_BOOLEAN, None),
+        ColumnType::Int64 => (TYPE_INT64, None),
+        ColumnType::Double => (TYPE_DOUBLE, None),
+        ColumnType::Utf8 => (TYPE_BYTE_ARRAY, Some(CONVERTED_UTF8)),
+        ColumnType::TimestampMillis => (TYPE_INT64, Some(CONVERTED_TIMESTAMP_MILLIS)),
+    }
+}
+
+/// Encodes a v1 data page body: definition levels, then PLAIN values of non-null cells.
+fn encode_page<'a>(
+    column_type: ColumnType,
+    cells: impl Iterator<Item = &'a Cell>,
+) -> io::Result<Vec<u8>> {
+    let mut defined = Vec::new();
+    let mut values = Vec::new();
+    let mut booleans = Vec::new();
+    for cell in cells {
+        let present = !matches!(cell, Cell::Null);
+        defined.push(present);
+        match (column_type, cell) {
+            (_, Cell::Null) => {}
+            (ColumnType::Boolean, Cell::Boolean(v)) => booleans.push(*v),
+            (ColumnType::Int64, Cell::Int64(v))
+            | (ColumnType::TimestampMillis, Cell::TimestampMillis(v)) => {
+                values.extend_from_slice(&v.to_le_bytes())
+            }
+            (ColumnType::Double, Cell::Double(v)) => values.extend_from_slice(&v.to_le_bytes()),
+            (ColumnType::Utf8, Cell::Utf8(s)) => {
+                values.extend_from_slice(&(s.len() as u32).to_le_bytes());
+                values.extend_from_slice(s.as_bytes());
+            }
+            (column_type, cell) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("cell {cell:?} does not match column type {column_type:?}"),
+                ))
+            }
+        }
+    }
+    values.extend(pack_bits(&booleans));
+
+    // Definition levels (max level 1) as one bit-packed run of the RLE/bit-packing hybrid.
+    let mut levels = Vec::new();
+    if !defined.is_empty() {
+        write_varint(&mut levels, ((defined.len().div_ceil(8) as u64) << 1) | 1);
+        levels.extend(pack_bits(&defined));
+    }
+
+    let mut page = Vec::with_capacity(4 + levels.len() + values.len());
+    page.extend_from_slice(&(levels.len() as u32).to_le_bytes());
+    page.extend_from_slice(&levels);
+    page.extend_from_slice(&values);
+    Ok(page)
+}
+
+/// Packs bits least-significant first, as Parquet does for booleans and bit-packed runs.
+fn pack_bits(bits: &[bool]) -> Vec<u8> {
+    let mut out = vec![0u8; bits.len().div_ceil(8)];
+    for (i, _) in bits.iter().enumerate().filter(|(_, &bit)| bit) {
+        out[i / 8] |= 1 << (i % 8);
+    }
+    out
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Thrift compact protocol encoder covering the field types Parquet metadata needs.
+#[derive(Default)]
+struct Compact {
+    buf: Vec<u8>,
+    last_field: Vec<i16>,
+    current: i16,
+}
+
+impl Compact {
+    fn field(&mut self, id: i16, field_type: u8) {
+        let delta = id - self.current;
+        if (1..=15).contains(&delta) {
+            self.buf.push(((delta as u8) << 4) | field_type);
+        } else {
+            self.buf.push(field_type);
+            write_varint(&mut self.buf, zigzag(i64::from(id)));
+        }
+        self.current = id;
+    }
+
+    fn i32(&mut self, id: i16, value: i32) {
+        self.field(id, CT_I32);
+        write_varint(&mut self.buf, zigzag(i64::from(value)));
+    }
+
+    fn i64(&mut self, id: i16, value: i64) {
+        self.field(id, CT_I64);
+        write_varint(&mut self.buf, zigzag(value));
+    }
+
+    fn binary(&mut self, id: i16, value: &[u8]) {
+        self.field(id, CT_BINARY);
+        self.list_binary(value);
+    }
+
+    fn begin_struct(&mut self, id: i16) {
+        self.field(id, CT_STRUCT);
+        self.begin_element();
+    }
+
+    fn end_struct(&mut self) {
+        self.end_element();
+    }
+
+    fn begin_list(&mut self, id: i16, element_type: u8, len: usize) {
+        self.field(id, CT_LIST);
+        if len < 15 {
+            self.buf.push(((len as u8) << 4) | element_type);
+        } else {
+            self.buf.push(0xF0 | element_type);
+            write_varint(&mut self.buf, len as u64);
+        }
+    }
+
+    fn list_i32(&mut self, value: i32) {
+        write_varint(&mut self.buf, zigzag(i64::from(value)));
+    }
+
+    fn list_binary(&mut self, value: &[u8]) {
+        write_varint(&mut self.buf, value.len() as u64);
+        self.buf.extend_from_slice(value);
+    }
+
+    /// Starts a struct element of a list (or the body of a struct field).
+    fn begin_element(&mut self) {
+        self.last_field.push(self.current);
+        self.current = 0;
+    }
+
+    fn end_element(&mut self) {
+        self.stop();
+        self.current = self.last_field.pop().unwrap_or(0);
+    }
+
+    fn stop(&mut self) {
+        self.buf.push(0);
+    }
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::export::ExportTable;
+    use crate::walk::{DeviceInfo, DeviceWalkResult, ObjectSummary};
+    use crate::ClientDataValue;
+    use rustbac_core::types::{ObjectId, ObjectType};
+
+    #[test]
+    fn writes_framed_parquet_file() {
+        let walk = DeviceWalkResult {
+            device_id: ObjectId::new(ObjectType::Device, 10),
+            device_info: DeviceInfo::default(),
+            objects: vec![ObjectSummary {
+                object_id: ObjectId::new(ObjectType::AnalogInput, 3),
+                object_name: Some("OAT".into()),
+                object_type: ObjectType::AnalogInput,
+                present_value: Some(ClientDataValue::Real(21.5)),
+                description: None,
+                units: Some(62),
+                status_flags: None,
+                property_list: None,
+            }],
+        };
+        let mut out = Vec::new();
+        ExportTable::from_walk(&walk)
+            .write_parquet(&mut out)
+            .unwrap();
+
+        assert_eq!(&out[..4], b"PAR1");
+        assert_eq!(&out[out.len() - 4..], b"PAR1");
+        let footer_len =
+            u32::from_le_bytes(out[out.len() - 8..out.len() - 4].try_into().unwrap()) as usize;
+        assert!(footer_len < out.len() - 12);
+        let footer = &out[out.len() - 8 - footer_len..out.len() - 8];
+        assert!(out.windows(3).any(|w| w == b"OAT"));
+        assert!(footer.windows(14).any(|w| w == b"out_of_service"));
+    }
+}
@@ -0,0 +1,435 @@
+//! Mapping of external points into a server's object database.
+//!
+//! A protocol gateway exposes values it reads from another system as BACnet objects.
+//! Implement [`PointProvider`] for that system, and [`PointGateway`] creates one Analog
+//! Input (read-only point) or Analog Value (writable point) per provided point in an
+//! [`ObjectStore`], refreshes `Present_Value` on every [`poll`](PointGateway::poll),
+//! and forwards BACnet writes back to the provider. Serve the store with
+//! [`PointGateway::handler`] on a [`BacnetServer`](crate::BacnetServer), or one store
+//! per upstream asset on a [`VirtualNetwork`](crate::VirtualNetwork).
+//!
+//! With the `modbus` feature, [`ModbusTcpProvider`](modbus::ModbusTcpProvider) reads
+//! and writes holding and input registers of a Modbus TCP device.
+
+#[cfg(feature = "modbus")]
+pub mod modbus;
+
+use crate::server::{BacnetServiceError, ObjectStore, ObjectStoreHandler, ServiceHandler};
+use crate::ClientDataValue;
+use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// `Units` value for points without engineering units (no-units).
+pub const NO_UNITS: u32 = 95;
+
+/// A point offered by a [`PointProvider`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PointDefinition {
+    /// Unique point name, used as the object's `Object_Name`.
+    pub name: String,
+    /// BACnet engineering units of the value.
+    pub units: u32,
+    /// Writable points become Analog Values, read-only points Analog Inputs.
+    pub writable: bool,
+}
+
+impl PointDefinition {
+    /// A read-only point without units.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            units: NO_UNITS,
+            writable: false,
+        }
+    }
+
+    /// Sets the engineering units.
+    pub fn with_units(mut self, units: u32) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Makes the point writable.
+    pub fn writable(mut self) -> Self {
+        self.writable = true;
+        self
+    }
+}
+
+/// A source of point values for a [`PointGateway`], e.g. a Modbus device.
+#[allow(async_fn_in_trait)]
+pub trait PointProvider {
+    /// Error returned by reads and writes.
+    type Error: fmt::Display;
+
+    /// The points to expose; called once when the gateway is created.
+    fn points(&self) -> Vec<PointDefinition>;
+
+    /// Reads the current value of the point named `name`.
+    async fn read(&self, name: &str) -> Result<f32, Self::Error>;
+
+    /// Writes `value` to the point named `name`.
+    async fn write(&self, name: &str, value: f32) -> Result<(), Self::Error>;
+}
+
+/// Outcome of one [`PointGateway::poll`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PollReport {
+    /// Points whose value was read and stored.
+    pub updated: usize,
+    /// Names of points whose read failed; their objects are flagged in fault.
+    pub failed_reads: Vec<String>,
+    /// Names of points whose pending BACnet write could not be applied.
+    pub failed_writes: Vec<String>,
+}
+
+/// Keeps an [`ObjectStore`] in step with a [`PointProvider`].
+pub struct PointGateway<P: PointProvider> {
+    provider: P,
+    store: Arc<ObjectStore>,
+    points: Vec<(PointDefinition, ObjectId)>,
+    pending_writes: Arc<Mutex<Vec<(usize, f32)>>>,
+}
+
+impl<P: PointProvider> PointGateway<P> {
+    /// Create the objects for every point of `provider` in `store`.
+    ///
+    /// Analog Inputs and Analog Values are numbered separately from 1 in point order.
+    pub fn new(provider: P, store: Arc<ObjectStore>) -> Self {
+        let (mut next_input, mut next_value) = (1, 1);
+        let points = provider
+            .points()
+            .into_iter()
+            .map(|point| {
+                let (object_type, next_instance) = if point.writable {
+                    (ObjectType::AnalogValue, &mut next_value)
+                } else {
+                    (ObjectType::AnalogInput, &mut next_input)
+                };
+                let object_id = ObjectId::new(object_type, *next_instance);
+                *next_instance += 1;
+                create_object(&store, object_id, &point);
+                (point, object_id)
+            })
+            .collect();
+        Self {
+            provider,
+            store,
+            points,
+            pending_writes: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// The provider.
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// The object database the points are mapped into.
+    pub fn store(&self) -> &Arc<ObjectStore> {
+        &self.store
+    }
+
+    /// The object created for the point named `name`.
+    pub fn object_id(&self, name: &str) -> Option<ObjectId> {
+        self.points
+            .iter()
+            .find(|(point, _)| point.name == name)
+            .map(|(_, object_id)| *object_id)
+    }
+
+    /// All points with their objects, in provider order.
+    pub fn points(&self) -> impl Iterator<Item = (&PointDefinition, ObjectId)> {
+        self.points
+            .iter()
+            .map(|(point, object_id)| (point, *object_id))
+    }
+
+    /// A [`ServiceHandler`] serving the store, queueing `Present_Value` writes to
+    /// writable points for the next [`poll`](Self::poll) and refusing them on read-only
+    /// points.
+    pub fn handler(&self) -> GatewayHandler {
+        GatewayHandler {
+            store: ObjectStoreHandler::new(self.store.clone()),
+            points: self
+                .points
+                .iter()
+                .enumerate()
+                .map(|(index, (point, object_id))| (*object_id, (index, point.writable)))
+                .collect(),
+            pending_writes: self.pending_writes.clone(),
+        }
+    }
+
+    /// Apply the pending BACnet writes, then read every point into the store.
+    pub async fn poll(&self) -> PollReport {
+        let mut report = PollReport::default();
+        let writes = std::mem::take(
+            &mut *self
+                .pending_writes
+                .lock()
+                .expect("gateway write queue lock poisoned"),
+        );
+        for (index, value) in writes {
+            let name = &self.points[index].0.name;
+            if let Err(e) = self.provider.write(name, value).await {
+                log::debug!("gateway: writing point {name} failed: {e}");
+                report.failed_writes.push(name.clone());
+            }
+        }
+
+        for (point, object_id) in &self.points {
+            let fault = match self.provider.read(&point.name).await {
+                Ok(value) => {
+                    self.store.set(
+                        *object_id,
+                        PropertyId::PresentValue,
+                        ClientDataValue::Real(value),
+                    );
+                    report.updated += 1;
+                    false
+                }
+                Err(e) => {
+                    log::debug!("gateway: reading point {} failed: {e}", point.name);
+                    report.failed_reads.push(point.name.clone());
+                    true
+                }
+            };
+            self.store
+                .set(*object_id, PropertyId::StatusFlags, status_flags(fault));
+        }
+        report
+    }
+
+    /// Poll every `interval` until the future is dropped.
+    pub async fn run(&self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            self.poll().await;
+        }
+    }
+}
+
+/// The [`ServiceHandler`] of a [`PointGateway`].
+pub struct GatewayHandler {
+    store: ObjectStoreHandler,
+    /// Point index and writability of each mapped object.
+    points: HashMap<ObjectId, (usize, bool)>,
+    pending_writes: Arc<Mutex<Vec<(usize, f32)>>>,
+}
+
+impl ServiceHandler for GatewayHandler {
+    fn read_property(
+        &self,
+        object_id: ObjectId,
+        property_id: PropertyId,
+        array_index: Option<u32>,
+    ) -> Result<ClientDataValue, BacnetServiceError> {
+        self.store
+            .read_property(object_id, property_id, array_index)
+    }
+
+    fn write_property(
+        &self,
+        object_id: ObjectId,
+        property_id: PropertyId,
+        array_index: Option<u32>,
+        value: ClientDataValue,
+        priority: Option<u8>,
+    ) -> Result<(), BacnetServiceError> {
+        let Some(&(index, writable)) = self.points.get(&object_id) else {
+            return self
+                .store
+                .write_property(object_id, property_id, array_index, value, priority);
+        };
+        if property_id != PropertyId::PresentValue {
+            return Err(BacnetServiceError::WriteAccessDenied);
+        }
+        if !writable {
+            return Err(BacnetServiceError::WriteAccessDenied);
+        }
+        let number = match value {
+            ClientDataValue::Real(v) => v,
+            ClientDataValue::Double(v) => v as f32,
+            ClientDataValue::Unsigned(v) => v as f32,
+            ClientDataValue::Signed(v) => v as f32,
+            _ => return Err(BacnetServiceError::InvalidDataType),
+        };
+        self.store.write_property(
+            object_id,
+            property_id,
+            None,
+            ClientDataValue::Real(number),
+            None,
+        )?;
+        self.pending_writes
+            .lock()
+            .expect("gateway write queue lock poisoned")
+            .push((index, number));
+        Ok(())
+    }
+}
+
+/// Status_Flags with only the fault bit possibly set.
+fn status_flags(fault: bool) -> ClientDataValue {
+    ClientDataValue::BitString {
+        unused_bits: 4,
+        data: vec![if fault { 0x40 } else { 0x00 }],
+    }
+}
+
+fn create_object(store: &ObjectStore, object_id: ObjectId, point: &PointDefinition) {
+    store.set(
+        object_id,
+        PropertyId::ObjectIdentifier,
+        ClientDataValue::ObjectId(object_id),
+    );
+    store.set(
+        object_id,
+        PropertyId::ObjectName,
+        ClientDataValue::CharacterString(point.name.as_str().into()),
+    );
+    store.set(
+        object_id,
+        PropertyId::ObjectType,
+        ClientDataValue::Enumerated(u32::from(object_id.object_type().to_u16())),
+    );
+    store.set(
+        object_id,
+        PropertyId::PresentValue,
+        ClientDataValue::Real(0.0),
+    );
+    store.set(object_id, PropertyId::StatusFlags, status_flags(false));
+    store.set(
+        object_id,
+        PropertyId::EventState,
+        ClientDataValue::Enumerated(0),
+    );
+    store.set(
+        object_id,
+        PropertyId::OutOfService,
+        ClientDataValue::Boolean(false),
+    );
+    store.set(
+        object_id,
+        PropertyId::Units,
+        ClientDataValue::Enumerated(point.units),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PointDefinition, PointGateway, PointProvider, PollReport};
+    use crate::server::{BacnetServiceError, ObjectStore, ServiceHandler};
+    use crate::ClientDataValue;
+    use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct MemoryProvider {
+        values: Mutex<HashMap<String, f32>>,
+    }
+
+    impl PointProvider for MemoryProvider {
+        type Error = String;
+
+        fn points(&self) -> Vec<PointDefinition> {
+            vec![
+                PointDefinition::new("supply-temp").with_units(62),
+                PointDefinition::new("setpoint").writable(),
+                PointDefinition::new("offline"),
+            ]
+        }
+
+        async fn read(&self, name: &str) -> Result<f32, String> {
+            self.values
+                .lock()
+                .unwrap()
+                .get(name)
+                .copied()
+                .ok_or_else(|| format!("{name} unreachable"))
+        }
+
+        async fn write(&self, name: &str, value: f32) -> Result<(), String> {
+            self.values.lock().unwrap().insert(name.to_string(), value);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn points_map_to_analog_objects_and_writes_reach_the_provider() {
+        let provider = MemoryProvider::default();
+        provider.values.lock().unwrap().extend([
+            ("supply-temp".to_string(), 18.5),
+            ("setpoint".to_string(), 21.0),
+        ]);
+        let gateway = PointGateway::new(provider, Arc::new(ObjectStore::new()));
+        let supply = ObjectId::new(ObjectType::AnalogInput, 1);
+        let setpoint = ObjectId::new(ObjectType::AnalogValue, 1);
+        let offline = ObjectId::new(ObjectType::AnalogInput, 2);
+        assert_eq!(gateway.object_id("supply-temp"), Some(supply));
+        assert_eq!(gateway.object_id("setpoint"), Some(setpoint));
+        assert_eq!(gateway.object_id("offline"), Some(offline));
+
+        let report = gateway.poll().await;
+        assert_eq!(
+            report,
+            PollReport {
+                updated: 2,
+                failed_reads: vec!["offline".to_string()],
+                failed_writes: Vec::new(),
+            }
+        );
+        let handler = gateway.handler();
+        assert_eq!(
+            handler.read_property(supply, PropertyId::PresentValue, None),
+            Ok(ClientDataValue::Real(18.5))
+        );
+        assert_eq!(
+            handler.read_property(supply, PropertyId::Units, None),
+            Ok(ClientDataValue::Enumerated(62))
+        );
+        assert_eq!(
+            handler.read_property(offline, PropertyId::StatusFlags, None),
+            Ok(ClientDataValue::BitString {
+                unused_bits: 4,
+                data: vec![0x40],
+            })
+        );
+
+        assert_eq!(
+            handler.write_property(
+                supply,
+                PropertyId::PresentValue,
+                None,
+                ClientDataValue::Real(1.0),
+                None
+            ),
+            Err(BacnetServiceError::WriteAccessDenied)
+        );
+        handler
+            .write_property(
+                setpoint,
+                PropertyId::PresentValue,
+                None,
+                ClientDataValue::Real(23.5),
+                Some(8),
+            )
+            .unwrap();
+        gateway.poll().await;
+        assert_eq!(
+            gateway.provider().values.lock().unwrap().get("setpoint"),
+            Some(&23.5)
+        );
+        assert_eq!(
+            handler.read_property(setpoint, PropertyId::PresentValue, None),
+            Ok(ClientDataValue::Real(23.5))
+        );
+    }
+}
@@ -0,0 +1,417 @@
+//! Modbus TCP [`PointProvider`]: holding and input registers as gateway points.
+
+use super::{PointDefinition, PointProvider, NO_UNITS};
+use std::io;
+use std::sync::atomic::{AtomicU16, Ordering};
+use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, ToSocketAddrs};
+use tokio::sync::Mutex;
+
+const READ_HOLDING_REGISTERS: u8 = 0x03;
+const READ_INPUT_REGISTERS: u8 = 0x04;
+const WRITE_SINGLE_REGISTER: u8 = 0x06;
+const WRITE_MULTIPLE_REGISTERS: u8 = 0x10;
+
+/// Errors from a [`ModbusTcpProvider`].
+#[derive(Debug, Error)]
+pub enum ModbusError {
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    /// The device answered with a Modbus exception code.
+    #[error("modbus exception {0:#04x}")]
+    Exception(u8),
+    #[error("invalid modbus response")]
+    InvalidResponse,
+    #[error("unknown point {0}")]
+    UnknownPoint(String),
+    /// The point is an input register, or the value does not fit the register format.
+    #[error("point {0} cannot be written with this value")]
+    NotWritable(String),
+}
+
+/// The register table a [`ModbusPoint`] is read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisterTable {
+    /// Read/write holding registers (function codes 3, 6 and 16).
+    Holding,
+    /// Read-only input registers (function code 4).
+    Input,
+}
+
+/// How a point's value is stored in registers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisterFormat {
+    /// One register, unsigned.
+    U16,
+    /// One register, two's complement.
+    I16,
+    /// Two registers holding an IEEE 754 float, high word first.
+    F32,
+}
+
+impl RegisterFormat {
+    fn register_count(self) -> u16 {
+        match self {
+            Self::U16 | Self::I16 => 1,
+            Self::F32 => 2,
+        }
+    }
+}
+
+/// A register mapped to a gateway point. The BACnet value is `raw * scale`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModbusPoint {
+    pub name: String,
+    pub table: RegisterTable,
+    /// Zero-based register address.
+    pub address: u16,
+    pub format: RegisterFormat,
+    pub scale: f32,
+    /// BACnet engineering units.
+    pub units: u32,
+}
+
+impl ModbusPoint {
+    /// A writable unsigned holding register.
+    pub fn holding(name: impl Into<String>, address: u16) -> Self {
+        Self::new(name.into(), RegisterTable::Holding, address)
+    }
+
+    /// A read-only unsigned input register.
+    pub fn input(name: impl Into<String>, address: u16) -> Self {
+        Self::new(name.into(), RegisterTable::Input, address)
+    }
+
+    fn new(name: String, table: RegisterTable, address: u16) -> Self {
+        Self {
+            name,
+            table,
+            address,
+            format: RegisterFormat::U16,
+            scale: 1.0,
+            units: NO_UNITS,
+        }
+    }
+
+    /// Sets the register format.
+    pub fn with_format(mut self, format: RegisterFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the factor from register value to BACnet value, e.g. `0.1` for tenths.
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets the BACnet engineering units.
+    pub fn with_units(mut self, units: u32) -> Self {
+        self.units = units;
+        self
+    }
+
+    fn decode(&self, registers: &[u16]) -> f32 {
+        let raw = match self.format {
+            RegisterFormat::U16 => f32::from(registers[0]),
+            RegisterFormat::I16 => f32::from(registers[0] as i16),
+            RegisterFormat::F32 => {
+                f32::from_bits((u32::from(registers[0]) << 16) | u32::from(registers[1]))
+            }
+        };
+        raw * self.scale
+    }
+
+    fn encode(&self, value: f32) -> Option<Vec<u16>> {
+        let raw = value / self.scale;
+        match self.format {
+            RegisterFormat::U16 => {
+                let raw = raw.round();
+                (0.0..=f32::from(u16::MAX))
+                    .contains(&raw)
+                    .then(|| vec![raw as u16])
+            }
+            RegisterFormat::I16 => {
+                let raw = raw.round();
+                (f32::from(i16::MIN)..=f32::from(i16::MAX))
+                    .contains(&raw)
+                    .then(|| vec![raw as i16 as u16])
+            }
+            RegisterFormat::F32 => {
+                let bits = raw.to_bits();
+                Some(vec![(bits >> 16) as u16, bits as u16])
+            }
+        }
+    }
+}
+
+/// A Modbus TCP device whose registers are exposed as gateway points.
+///
+/// Requests are sent one at a time over a single connection.
+pub struct ModbusTcpProvider {
+    stream: Mutex<TcpStream>,
+    unit_id: u8,
+    next_transaction: AtomicU16,
+    points: Vec<ModbusPoint>,
+}
+
+impl ModbusTcpProvider {
+    /// Connect to the device at `address`, addressing unit `unit_id`.
+    pub async fn connect(address: impl ToSocketAddrs, unit_id: u8) -> io::Result<Self> {
+        let stream = TcpStream::connect(address).await?;
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            stream: Mutex::new(stream),
+            unit_id,
+            next_transaction: AtomicU16::new(1),
+            points: Vec::new(),
+        })
+    }
+
+    /// Adds a point.
+    pub fn with_point(mut self, point: ModbusPoint) -> Self {
+        self.points.push(point);
+        self
+    }
+
+    fn point(&self, name: &str) -> Result<&ModbusPoint, ModbusError> {
+        self.points
+            .iter()
+            .find(|point| point.name == name)
+            .ok_or_else(|| ModbusError::UnknownPoint(name.to_string()))
+    }
+
+    /// Reads `count` registers starting at `address`.
+    pub async fn read_registers(
+        &self,
+        table: RegisterTable,
+        address: u16,
+        count: u16,
+    ) -> Result<Vec<u16>, ModbusError> {
+        let function = match table {
+            RegisterTable::Holding => READ_HOLDING_REGISTERS,
+            RegisterTable::Input => READ_INPUT_REGISTERS,
+        };
+        let mut pdu = vec![function];
+        pdu.extend_from_slice(&address.to_be_bytes());
+        pdu.extend_from_slice(&count.to_be_bytes());
+        let response = self.transact(&pdu).await?;
+        let data = response.get(2..).ok_or(ModbusError::InvalidResponse)?;
+        if usize::from(response[1]) != data.len() || data.len() != usize::from(count) * 2 {
+            return Err(ModbusError::InvalidResponse);
+        }
+        Ok(data
+            .chunks_exact(2)
+            .map(|word| u16::from_be_bytes([word[0], word[1]]))
+            .collect())
+    }
+
+    /// Writes `values` to the holding registers starting at `address`.
+    pub async fn write_registers(&self, address: u16, values: &[u16]) -> Result<(), ModbusError> {
+        let pdu = match values {
+            [value] => {
+                let mut pdu = vec![WRITE_SINGLE_REGISTER];
+                pdu.extend_from_slice(&address.to_be_bytes());
+                pdu.extend_from_slice(&value.to_be_bytes());
+                pdu
+            }
+            _ => {
+                // At most 123 registers fit one request.
+                let byte_count = u8::try_from(values.len() * 2)
+                    .ok()
+                    .filter(|&count| count <= 246)
+                    .ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidInput, "too many registers")
+                    })?;
+                let mut pdu = vec![WRITE_MULTIPLE_REGISTERS];
+                pdu.extend_from_slice(&address.to_be_bytes());
+                pdu.extend_from_slice(&(values.len() as u16).to_be_bytes());
+                pdu.push(byte_count);
+                for value in values {
+                    pdu.extend_from_slice(&value.to_be_bytes());
+                }
+                pdu
+            }
+        };
+        self.transact(&pdu).await?;
+        Ok(())
+    }
+
+    /// Sends one request PDU and returns the response PDU, function code included.
+    async fn transact(&self, pdu: &[u8]) -> Result<Vec<u8>, ModbusError> {
+        let transaction = self.next_transaction.fetch_add(1, Ordering::Relaxed);
+        let mut frame = Vec::with_capacity(7 + pdu.len());
+        frame.extend_from_slice(&transaction.to_be_bytes());
+        frame.extend_from_slice(&0u16.to_be_bytes());
+        frame.extend_from_slice(&(pdu.len() as u16 + 1).to_be_bytes());
+        frame.push(self.unit_id);
+        frame.extend_from_slice(pdu);
+
+        let mut stream = self.stream.lock().await;
+        stream.write_all(&frame).await?;
+        let mut header = [0u8; 7];
+        stream.read_exact(&mut header).await?;
+        let len = usize::from(u16::from_be_bytes([header[4], header[5]]));
+        if len < 2 {
+            return Err(ModbusError::InvalidResponse);
+        }
+        let mut response = vec![0u8; len - 1];
+        stream.read_exact(&mut response).await?;
+        drop(stream);
+
+        if u16::from_be_bytes([header[0], header[1]]) != transaction || header[6] != self.unit_id {
+            return Err(ModbusError::InvalidResponse);
+        }
+        if response[0] == pdu[0] | 0x80 {
+            return Err(ModbusError::Exception(
+                response.get(1).copied().unwrap_or_default(),
+            ));
+        }
+        if response[0] != pdu[0] {
+            return Err(ModbusError::InvalidResponse);
+        }
+        Ok(response)
+    }
+}
+
+impl PointProvider for ModbusTcpProvider {
+    type Error = ModbusError;
+
+    fn points(&self) -> Vec<PointDefinition> {
+        self.points
+            .iter()
+            .map(|point| PointDefinition {
+                name: point.name.clone(),
+                units: point.units,
+                writable: point.table == RegisterTable::Holding,
+            })
+            .collect()
+    }
+
+    async fn read(&self, name: &str) -> Result<f32, ModbusError> {
+        let point = self.point(name)?;
+        let registers = self
+            .read_registers(point.table, point.address, point.format.register_count())
+            .await?;
+        Ok(point.decode(&registers))
+    }
+
+    async fn write(&self, name: &str, value: f32) -> Result<(), ModbusError> {
+        let point = self.point(name)?;
+        let registers = point
+            .encode(value)
+            .filter(|_| point.table == RegisterTable::Holding)
+            .ok_or_else(|| ModbusError::NotWritable(name.to_string()))?;
+        self.write_registers(point.address, &registers).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ModbusPoint, ModbusTcpProvider, RegisterFormat};
+    use crate::gateway::{PointGateway, PointProvider};
+    use crate::server::ObjectStore;
+    use crate::ClientDataValue;
+    use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serves holding registers 0..8 and input registers 0..8 for one connection.
+    async fn serve_registers(listener: TcpListener, mut holding: [u16; 8], input: [u16; 8]) {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        loop {
+            let mut header = [0u8; 7];
+            if stream.read_exact(&mut header).await.is_err() {
+                return;
+            }
+            let len = usize::from(u16::from_be_bytes([header[4], header[5]]));
+            let mut pdu = vec![0u8; len - 1];
+            stream.read_exact(&mut pdu).await.unwrap();
+            let address = usize::from(u16::from_be_bytes([pdu[1], pdu[2]]));
+            let value = u16::from_be_bytes([pdu[3], pdu[4]]);
+            let response = match pdu[0] {
+                0x03 | 0x04 => {
+                    let table = if pdu[0] == 0x03 { &holding } else { &input };
+                    let count = usize::from(value);
+                    let mut response = vec![pdu[0], (count * 2) as u8];
+                    for register in &table[address..address + count] {
+                        response.extend_from_slice(&register.to_be_bytes());
+                    }
+                    response
+                }
+                0x06 => {
+                    holding[address] = value;
+                    pdu.clone()
+                }
+                0x10 => {
+                    for (i, word) in pdu[6..].chunks_exact(2).enumerate() {
+                        holding[address + i] = u16::from_be_bytes([word[0], word[1]]);
+                    }
+                    pdu[..5].to_vec()
+                }
+                function => vec![function | 0x80, 0x01],
+            };
+            let mut frame = header[..4].to_vec();
+            frame.extend_from_slice(&(response.len() as u16 + 1).to_be_bytes());
+            frame.push(header[6]);
+            frame.extend_from_slice(&response);
+            stream.write_all(&frame).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn modbus_registers_become_gateway_points() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let temp = 21.5f32.to_bits();
+        tokio::spawn(serve_registers(
+            listener,
+            [0, 0, 0, 0, 500, 0, 0, 0],
+            [
+                (temp >> 16) as u16,
+                temp as u16,
+                (-40i16) as u16,
+                0,
+                0,
+                0,
+                0,
+                0,
+            ],
+        ));
+
+        let provider = ModbusTcpProvider::connect(address, 1)
+            .await
+            .unwrap()
+            .with_point(ModbusPoint::input("temp", 0).with_format(RegisterFormat::F32))
+            .with_point(
+                ModbusPoint::input("outdoor", 2)
+                    .with_format(RegisterFormat::I16)
+                    .with_scale(0.1),
+            )
+            .with_point(ModbusPoint::holding("setpoint", 4).with_scale(0.1));
+        let gateway = PointGateway::new(provider, Arc::new(ObjectStore::new()));
+
+        let report = gateway.poll().await;
+        assert_eq!(report.updated, 3, "{report:?}");
+        let store = gateway.store();
+        let value = |id| store.get(id, PropertyId::PresentValue);
+        assert_eq!(
+            value(ObjectId::new(ObjectType::AnalogInput, 1)),
+            Some(ClientDataValue::Real(21.5))
+        );
+        assert_eq!(
+            value(ObjectId::new(ObjectType::AnalogInput, 2)),
+            Some(ClientDataValue::Real(-4.0))
+        );
+        assert_eq!(
+            value(ObjectId::new(ObjectType::AnalogValue, 1)),
+            Some(ClientDataValue::Real(50.0))
+        );
+
+        gateway.provider().write("setpoint", 42.0).await.unwrap();
+        assert_eq!(gateway.provider().read("setpoint").await.unwrap(), 42.0);
+        assert!(gateway.provider().write("temp", 1.0).await.is_err());
+    }
+}
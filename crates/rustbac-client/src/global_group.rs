@@ -0,0 +1,102 @@
+//! Convenience types for the BACnet Global Group object.
+//!
+//! A Global Group collects properties from any number of devices. Its Present_Value holds
+//! the current value of every member, so one ReadProperty returns a whole set of points
+//! that would otherwise need a read per device. See
+//! [`BacnetClient::read_global_group_values`](crate::BacnetClient::read_global_group_values).
+
+use crate::ClientDataValue;
+use rustbac_core::types::DeviceObjectPropertyReference;
+use rustbac_core::types::{ObjectId, PropertyId};
+
+/// The outcome of reading one Global Group member.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GroupMemberResult {
+    Value(ClientDataValue),
+    /// The group could not read the member.
+    Error {
+        error_class: u32,
+        error_code: u32,
+    },
+}
+
+/// One entry of a Global Group's Present_Value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalGroupValue {
+    pub member: DeviceObjectPropertyReference,
+    pub result: GroupMemberResult,
+}
+
+impl GlobalGroupValue {
+    /// The member's value, or `None` when the group could not read it.
+    pub fn value(&self) -> Option<&ClientDataValue> {
+        match &self.result {
+            GroupMemberResult::Value(value) => Some(value),
+            GroupMemberResult::Error { .. } => None,
+        }
+    }
+}
+
+/// Group_Members referencing `property_id` of each of `objects`, in `device` or, when
+/// `device` is `None`, in the device holding the group.
+pub fn group_members(
+    device: Option<ObjectId>,
+    objects: &[ObjectId],
+    property_id: PropertyId,
+) -> Vec<DeviceObjectPropertyReference> {
+    objects
+        .iter()
+        .map(|&object_id| match device {
+            Some(device_id) => {
+                DeviceObjectPropertyReference::remote(device_id, object_id, property_id)
+            }
+            None => DeviceObjectPropertyReference::local(object_id, property_id),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{group_members, GlobalGroupValue, GroupMemberResult};
+    use crate::ClientDataValue;
+    use rustbac_core::types::DeviceObjectPropertyReference;
+    use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+
+    #[test]
+    fn group_members_reference_each_object_in_the_device() {
+        let device = ObjectId::new(ObjectType::Device, 12);
+        let objects = [
+            ObjectId::new(ObjectType::AnalogInput, 1),
+            ObjectId::new(ObjectType::AnalogInput, 2),
+        ];
+        let members = group_members(Some(device), &objects, PropertyId::PresentValue);
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[1].object_id, objects[1]);
+        assert_eq!(members[1].device_id, Some(device));
+        assert_eq!(
+            group_members(None, &objects[..1], PropertyId::StatusFlags)[0],
+            DeviceObjectPropertyReference::local(objects[0], PropertyId::StatusFlags)
+        );
+    }
+
+    #[test]
+    fn value_is_none_for_failed_members() {
+        let member = DeviceObjectPropertyReference::local(
+            ObjectId::new(ObjectType::AnalogInput, 1),
+            PropertyId::PresentValue,
+        );
+        let read = GlobalGroupValue {
+            member,
+            result: GroupMemberResult::Value(ClientDataValue::Real(1.0)),
+        };
+        assert_eq!(read.value(), Some(&ClientDataValue::Real(1.0)));
+        let failed = GlobalGroupValue {
+            member,
+            result: GroupMemberResult::Error {
+                error_class: 2,
+                error_code: 32,
+            },
+        };
+        assert_eq!(failed.value(), None);
+    }
+}
@@ -0,0 +1,157 @@
+use rustbac_core::apdu::ApduType;
+use rustbac_datalink::DataLinkAddress;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// How long an invoke id stays reserved for a peer after its transaction ends.
+///
+/// A late or duplicated response to the finished transaction is discarded during this
+/// window instead of being matched against a new request that reused the 8-bit id.
+pub(crate) const INVOKE_ID_QUARANTINE: Duration = Duration::from_secs(10);
+
+/// Invoke-id lifecycle: allocation, in-flight transactions and recently finished ones.
+#[derive(Debug)]
+pub(crate) struct InvokeIdTracker {
+    next: u8,
+    in_flight: HashSet<(DataLinkAddress, u8)>,
+    finished: HashMap<(DataLinkAddress, u8), Instant>,
+    quarantine: Duration,
+}
+
+impl InvokeIdTracker {
+    pub(crate) fn new(quarantine: Duration) -> Self {
+        Self {
+            next: 1,
+            in_flight: HashSet::new(),
+            finished: HashMap::new(),
+            quarantine,
+        }
+    }
+
+    /// Picks the next invoke id (1–255) for a request to `address`, skipping ids that are
+    /// in flight to it or still quarantined. When every id is taken, the one that finished
+    /// longest ago is reused.
+    pub(crate) fn allocate(&mut self, address: DataLinkAddress, now: Instant) -> u8 {
+        self.expire(now);
+        let start = self.next;
+        let mut candidate = start;
+        let mut oldest: Option<(Instant, u8)> = None;
+        loop {
+            let key = (address, candidate);
+            if !self.in_flight.contains(&key) {
+                match self.finished.get(&key) {
+                    None => break,
+                    Some(&at) if oldest.map_or(true, |(t, _)| at < t) => {
+                        oldest = Some((at, candidate));
+                    }
+                    Some(_) => {}
+                }
+            }
+            candidate = following(candidate);
+            if candidate == start {
+                candidate = oldest.map_or(start, |(_, id)| id);
+                break;
+            }
+        }
+        self.next = following(candidate);
+        candidate
+    }
+
+    /// Records that a transaction with `invoke_id` to `address` is under way.
+    pub(crate) fn begin(&mut self, address: DataLinkAddress, invoke_id: u8) {
+        self.finished.remove(&(address, invoke_id));
+        self.in_flight.insert((address, invoke_id));
+    }
+
+    /// Number of transactions under way, to every peer.
+    pub(crate) fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Records that the transaction ended and quarantines its id.
+    pub(crate) fn finish(&mut self, address: DataLinkAddress, invoke_id: u8, now: Instant) {
+        self.in_flight.remove(&(address, invoke_id));
+        self.finished.insert((address, invoke_id), now);
+    }
+
+    /// Whether `apdu` from `address` is a response to a transaction that already finished.
+    pub(crate) fn is_stale_response(
+        &mut self,
+        address: DataLinkAddress,
+        apdu: &[u8],
+        now: Instant,
+    ) -> bool {
+        let (Some(&first), Some(&invoke_id)) = (apdu.first(), apdu.get(1)) else {
+            return false;
+        };
+        let from_server = match ApduType::from_u8(first >> 4) {
+            Some(
+                ApduType::SimpleAck | ApduType::ComplexAck | ApduType::Error | ApduType::Reject,
+            ) => true,
+            Some(ApduType::SegmentAck | ApduType::Abort) => first & 0x01 != 0,
+            _ => false,
+        };
+        if !from_server || self.in_flight.contains(&(address, invoke_id)) {
+            return false;
+        }
+        self.expire(now);
+        self.finished.contains_key(&(address, invoke_id))
+    }
+
+    fn expire(&mut self, now: Instant) {
+        let quarantine = self.quarantine;
+        self.finished
+            .retain(|_, at| now.saturating_duration_since(*at) < quarantine);
+    }
+}
+
+/// The id after `id`, skipping 0.
+fn following(id: u8) -> u8 {
+    id.checked_add(1).unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InvokeIdTracker;
+    use rustbac_datalink::DataLinkAddress;
+    use std::time::{Duration, Instant};
+
+    fn addr(port: u16) -> DataLinkAddress {
+        DataLinkAddress::Ip(([192, 168, 1, 20], port).into())
+    }
+
+    #[test]
+    fn quarantined_ids_are_skipped_per_peer() {
+        let mut ids = InvokeIdTracker::new(Duration::from_secs(10));
+        let now = Instant::now();
+        for expected in 1..=255u8 {
+            let id = ids.allocate(addr(1), now);
+            assert_eq!(id, expected);
+            ids.begin(addr(1), id);
+            ids.finish(addr(1), id, now + Duration::from_millis(u64::from(id)));
+        }
+        // Every id is quarantined for peer 1; the one that finished first comes back.
+        assert_eq!(ids.allocate(addr(1), now + Duration::from_secs(1)), 1);
+        // Another peer is unaffected.
+        assert_eq!(ids.allocate(addr(2), now), 2);
+        // Once the quarantine passes, ids are free again.
+        assert_eq!(ids.allocate(addr(1), now + Duration::from_secs(11)), 3);
+    }
+
+    #[test]
+    fn late_responses_to_finished_transactions_are_stale() {
+        let mut ids = InvokeIdTracker::new(Duration::from_secs(10));
+        let now = Instant::now();
+        let complex_ack = [0x30, 7, 0x0C];
+        assert!(!ids.is_stale_response(addr(1), &complex_ack, now));
+
+        ids.begin(addr(1), 7);
+        assert!(!ids.is_stale_response(addr(1), &complex_ack, now));
+        ids.finish(addr(1), 7, now);
+        assert!(ids.is_stale_response(addr(1), &complex_ack, now));
+        assert!(!ids.is_stale_response(addr(2), &complex_ack, now));
+        // A confirmed request from the peer is never a stale response.
+        assert!(!ids.is_stale_response(addr(1), &[0x00, 0x05, 7, 0x0C], now));
+        assert!(!ids.is_stale_response(addr(1), &complex_ack, now + Duration::from_secs(10)));
+    }
+}
@@ -0,0 +1,383 @@
+//! An opt-in journal of every confirmed transaction, for evidence when a device
+//! misbehaves.
+//!
+//! [`BacnetClient::recent_transactions`](crate::BacnetClient::recent_transactions) keeps
+//! only the last few exchanges. A [`TransactionJournal`] attached with
+//! [`BacnetClient::with_transaction_journal`](crate::BacnetClient::with_transaction_journal)
+//! keeps all of them, numbered in completion order, so a lost response can be traced
+//! back to its request even after the 8-bit invoke ID has wrapped. The journal can be
+//! queried with a [`JournalFilter`] and dumped with [`TransactionJournal::to_json`] to
+//! attach to a support case.
+
+use crate::trace::{TransactionOutcome, TransactionRecord};
+use rustbac_datalink::DataLinkAddress;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A journaled transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JournalEntry {
+    /// Position of the transaction in the journal, from 0; never reused.
+    pub sequence: u64,
+    pub record: TransactionRecord,
+}
+
+impl JournalEntry {
+    /// Wall-clock time the transaction ended.
+    pub fn completed_at(&self) -> SystemTime {
+        self.record.started_at + self.record.elapsed
+    }
+}
+
+/// Which journal entries a [`TransactionJournal::query`] returns; an empty filter
+/// matches every entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JournalFilter {
+    pub address: Option<DataLinkAddress>,
+    pub service_choice: Option<u8>,
+    pub invoke_id: Option<u8>,
+    /// Entries started at or after this time.
+    pub since: Option<SystemTime>,
+    /// Entries started at or before this time.
+    pub until: Option<SystemTime>,
+    /// Only entries that did not end in an ACK.
+    pub failures_only: bool,
+}
+
+impl JournalFilter {
+    pub fn with_address(mut self, address: DataLinkAddress) -> Self {
+        self.address = Some(address);
+        self
+    }
+
+    pub fn with_service_choice(mut self, service_choice: u8) -> Self {
+        self.service_choice = Some(service_choice);
+        self
+    }
+
+    pub fn with_invoke_id(mut self, invoke_id: u8) -> Self {
+        self.invoke_id = Some(invoke_id);
+        self
+    }
+
+    pub fn with_since(mut self, since: SystemTime) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    pub fn with_until(mut self, until: SystemTime) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    pub fn with_failures_only(mut self) -> Self {
+        self.failures_only = true;
+        self
+    }
+
+    /// Returns `true` if `entry` passes every condition of the filter.
+    pub fn matches(&self, entry: &JournalEntry) -> bool {
+        let record = &entry.record;
+        self.address.map_or(true, |a| a == record.address)
+            && self
+                .service_choice
+                .map_or(true, |s| s == record.service_choice)
+            && self.invoke_id.map_or(true, |i| i == record.invoke_id)
+            && self.since.map_or(true, |t| record.started_at >= t)
+            && self.until.map_or(true, |t| record.started_at <= t)
+            && !(self.failures_only && record.outcome.is_success())
+    }
+}
+
+#[derive(Debug)]
+struct JournalState {
+    limit: Option<usize>,
+    next_sequence: u64,
+    entries: VecDeque<JournalEntry>,
+}
+
+/// A shared, append-only record of confirmed transactions.
+///
+/// Clones share the same entries, so the application can keep one handle for queries
+/// while the client (and its clones) append to it.
+#[derive(Debug, Clone)]
+pub struct TransactionJournal {
+    state: Arc<Mutex<JournalState>>,
+}
+
+impl Default for TransactionJournal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransactionJournal {
+    /// A journal that keeps every transaction.
+    pub fn new() -> Self {
+        Self::with_state(None)
+    }
+
+    /// A journal that keeps the latest `limit` transactions, dropping the oldest.
+    pub fn with_limit(limit: usize) -> Self {
+        Self::with_state(Some(limit))
+    }
+
+    fn with_state(limit: Option<usize>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(JournalState {
+                limit,
+                next_sequence: 0,
+                entries: VecDeque::new(),
+            })),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().map(|s| s.entries.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Every kept entry, oldest first.
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.query(&JournalFilter::default())
+    }
+
+    /// The kept entries passing `filter`, oldest first.
+    pub fn query(&self, filter: &JournalFilter) -> Vec<JournalEntry> {
+        self.state
+            .lock()
+            .map(|s| {
+                s.entries
+                    .iter()
+                    .filter(|entry| filter.matches(entry))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The transaction to `address` that used `invoke_id` at time `at`.
+    ///
+    /// Invoke IDs are reused, so an ID seen in a packet capture is resolved by time: a
+    /// device's ID is not reused while its transaction is in progress, so this returns the
+    /// latest transaction with that ID started at or before `at`.
+    pub fn find(
+        &self,
+        address: DataLinkAddress,
+        invoke_id: u8,
+        at: SystemTime,
+    ) -> Option<JournalEntry> {
+        let state = self.state.lock().ok()?;
+        state
+            .entries
+            .iter()
+            .rev()
+            .find(|entry| {
+                entry.record.address == address
+                    && entry.record.invoke_id == invoke_id
+                    && entry.record.started_at <= at
+            })
+            .cloned()
+    }
+
+    /// Discards every entry; sequence numbers keep counting.
+    pub fn clear(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.entries.clear();
+        }
+    }
+
+    /// The kept entries as a JSON array, oldest first.
+    ///
+    /// Times are milliseconds since the Unix epoch and durations are milliseconds, so the
+    /// dump can be lined up with a packet capture.
+    pub fn to_json(&self) -> String {
+        let entries = self.entries();
+        let mut out = String::from("[");
+        for (i, entry) in entries.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            write_entry_json(&mut out, entry);
+        }
+        out.push(']');
+        out
+    }
+
+    /// Appends `record` under the next sequence number.
+    pub(crate) fn record(&self, record: &TransactionRecord) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        let sequence = state.next_sequence;
+        state.next_sequence += 1;
+        if state.limit == Some(0) {
+            return;
+        }
+        if let Some(limit) = state.limit {
+            while state.entries.len() >= limit {
+                state.entries.pop_front();
+            }
+        }
+        state.entries.push_back(JournalEntry {
+            sequence,
+            record: record.clone(),
+        });
+    }
+}
+
+fn write_entry_json(out: &mut String, entry: &JournalEntry) {
+    let record = &entry.record;
+    let _ = write!(out, "{{\"sequence\":{},\"address\":", entry.sequence);
+    write_json_string(out, &record.address.to_string());
+    let _ = write!(
+        out,
+        ",\"invoke_id\":{},\"service_choice\":{},\"request_len\":{},\"started_at_ms\":{},\
+         \"completed_at_ms\":{},\"elapsed_ms\":{},\"outcome\":",
+        record.invoke_id,
+        record.service_choice,
+        record.request_len,
+        epoch_millis(record.started_at),
+        epoch_millis(entry.completed_at()),
+        record.elapsed.as_millis(),
+    );
+    write_outcome_json(out, &record.outcome);
+    out.push('}');
+}
+
+fn write_outcome_json(out: &mut String, outcome: &TransactionOutcome) {
+    let optional = |v: Option<u32>| v.map_or_else(|| "null".to_owned(), |v| v.to_string());
+    let _ = match outcome {
+        TransactionOutcome::SimpleAck => write!(out, "{{\"kind\":\"simple_ack\"}}"),
+        TransactionOutcome::ComplexAck { payload_len } => write!(
+            out,
+            "{{\"kind\":\"complex_ack\",\"payload_len\":{payload_len}}}"
+        ),
+        TransactionOutcome::Error {
+            error_class_raw,
+            error_code_raw,
+        } => write!(
+            out,
+            "{{\"kind\":\"error\",\"error_class\":{},\"error_code\":{}}}",
+            optional(*error_class_raw),
+            optional(*error_code_raw)
+        ),
+        TransactionOutcome::Reject { reason } => {
+            write!(out, "{{\"kind\":\"reject\",\"reason\":{reason}}}")
+        }
+        TransactionOutcome::Abort { reason, server } => write!(
+            out,
+            "{{\"kind\":\"abort\",\"reason\":{reason},\"server\":{server}}}"
+        ),
+        TransactionOutcome::Timeout => write!(out, "{{\"kind\":\"timeout\"}}"),
+        TransactionOutcome::Failed { message } => {
+            out.push_str("{\"kind\":\"failed\",\"message\":");
+            write_json_string(out, message);
+            out.push('}');
+            Ok(())
+        }
+    };
+}
+
+fn write_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn epoch_millis(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JournalFilter, TransactionJournal};
+    use crate::trace::{TransactionOutcome, TransactionRecord};
+    use rustbac_datalink::DataLinkAddress;
+    use std::time::{Duration, SystemTime};
+
+    fn record(invoke_id: u8, started_s: u64, outcome: TransactionOutcome) -> TransactionRecord {
+        TransactionRecord {
+            address: DataLinkAddress::Mstp(5),
+            invoke_id,
+            service_choice: 0x0C,
+            request_len: 17,
+            started_at: SystemTime::UNIX_EPOCH + Duration::from_secs(started_s),
+            elapsed: Duration::from_millis(250),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn find_resolves_reused_invoke_ids_by_time() {
+        let journal = TransactionJournal::new();
+        journal.record(&record(1, 10, TransactionOutcome::SimpleAck));
+        journal.record(&record(2, 11, TransactionOutcome::Timeout));
+        journal.record(&record(1, 20, TransactionOutcome::Timeout));
+
+        let at = |s: u64, ms: u64| {
+            SystemTime::UNIX_EPOCH + Duration::from_secs(s) + Duration::from_millis(ms)
+        };
+        let addr = DataLinkAddress::Mstp(5);
+        assert_eq!(journal.find(addr, 1, at(10, 100)).unwrap().sequence, 0);
+        assert_eq!(journal.find(addr, 1, at(15, 0)).unwrap().sequence, 0);
+        assert_eq!(journal.find(addr, 1, at(20, 200)).unwrap().sequence, 2);
+        assert!(journal.find(addr, 1, at(9, 0)).is_none());
+        assert!(journal
+            .find(DataLinkAddress::Mstp(6), 1, at(20, 0))
+            .is_none());
+
+        let failures = journal.query(
+            &JournalFilter::default()
+                .with_failures_only()
+                .with_since(at(11, 0)),
+        );
+        let sequences: Vec<u64> = failures.iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, vec![1, 2]);
+    }
+
+    #[test]
+    fn limited_journal_keeps_numbering_and_dumps_json() {
+        let journal = TransactionJournal::with_limit(1);
+        journal.record(&record(1, 10, TransactionOutcome::SimpleAck));
+        journal.record(&record(
+            2,
+            11,
+            TransactionOutcome::Failed {
+                message: "bad \"frame\"".into(),
+            },
+        ));
+        assert_eq!(journal.len(), 1);
+        assert_eq!(
+            journal.to_json(),
+            "[{\"sequence\":1,\"address\":\"mstp:5\",\"invoke_id\":2,\"service_choice\":12,\
+             \"request_len\":17,\"started_at_ms\":11000,\"completed_at_ms\":11250,\
+             \"elapsed_ms\":250,\"outcome\":{\"kind\":\"failed\",\
+             \"message\":\"bad \\\"frame\\\"\"}}]"
+        );
+
+        journal.clear();
+        assert!(journal.is_empty());
+        assert_eq!(TransactionJournal::new().to_json(), "[]");
+    }
+}
@@ -5,67 +5,269 @@
 //! such as reading properties, discovering devices, and subscribing to
 //! change-of-value (COV) notifications.
 
+/// Access Door, Access Zone and Credential Data Input helpers and access events.
+pub mod access_control;
+/// Engineering-unit scaling for Accumulator and Pulse Converter objects.
+pub mod accumulator;
 /// Alarm and event services (GetAlarmSummary, GetEventInformation, etc.).
 pub mod alarm;
+/// Routing of alarms to e-mail, MQTT and other sinks, with escalation.
+pub mod alarm_routing;
+/// Audit hook for write-class requests (writes, object create/delete, DCC, reinitialize).
+pub mod audit;
+/// Synchronous client facade over a dedicated runtime.
+#[cfg(feature = "blocking")]
+pub mod blocking;
+/// Device capabilities read from the Device object.
+pub mod capabilities;
 /// Core [`BacnetClient`] type and transport setup.
 pub mod client;
+/// Tolerant, structural comparison and normalization of client values.
+pub mod compare;
+/// Typed tuning access for Loop objects.
+pub mod control_loop;
 /// Change-of-value (COV) notification types.
 pub mod cov;
+/// The COV subscriptions a device holds, and reconciling them with a client's own.
+pub mod cov_inventory;
 /// COV subscriptions with renewal and polling failover.
 pub mod cov_manager;
+/// Persistent database of discovered devices, capabilities and point inventories.
+pub mod device_db;
 /// Device and object discovery (Who-Is / I-Am / Who-Has).
 pub mod discovery;
 /// Client-level error type.
 pub mod error;
+/// Event Enrollment configuration.
+pub mod event_enrollment;
+/// Event message texts and localized, parameterized rendering of notifications.
+pub mod event_message;
+/// CSV and Parquet export of walk, trend and COV data.
+pub mod export;
 /// Atomic file read/write operations.
 pub mod file;
+/// Mapping of external points (e.g. Modbus registers) into a server's object database.
+pub mod gateway;
+/// Global Group member values and member lists.
+pub mod global_group;
+/// Invoke-id allocation and late-response tracking.
+mod invoke_id;
+/// Opt-in journal of every confirmed transaction, queryable and dumpable as JSON.
+pub mod journal;
+/// Life Safety Point and Zone state and change-of-life-safety notifications.
+pub mod life_safety;
+/// Lift, Escalator and Elevator Group state and call lists.
+pub mod lift;
 /// Long-running async notification listener.
 pub mod listener;
-/// Point type inference for BACnet objects.
+/// Typed Log_Buffer records of Trend Log, Trend Log Multiple and Audit Log objects.
+pub mod log_buffer;
+/// Caps on reassembly buffers, outstanding requests and queued frames.
+pub mod memory_limits;
+/// Network Port configuration and change activation.
+pub mod network_port;
+/// Passive traffic observation and per-peer statistics.
+pub mod observer;
+/// Occupancy schedules built from opening hours and holidays.
+pub mod occupancy;
+/// Point type inference and state-text labelling for BACnet objects.
 pub mod point;
+/// Compact `address/object/property` point reference parsing.
+pub mod point_ref;
+/// Subscriber process id allocation and duplicate COV subscription detection.
+pub mod process_id;
+/// Whole-object reads with the ALL, REQUIRED and OPTIONAL property identifiers.
+pub mod properties;
 /// ReadRange results and related types.
 pub mod range;
+/// Per-call timeout, retry, priority and segmentation overrides.
+pub mod request_options;
+/// Hands received frames to the receivers waiting for them.
+pub mod router;
+/// Executor abstraction for timers and background tasks.
+pub mod runtime;
 /// Schedule and Calendar convenience helpers.
 pub mod schedule;
 /// BACnet server/responder implementation.
 pub mod server;
 /// Lightweight simulated BACnet device.
 pub mod simulator;
+/// Who-Is proxying for MS/TP slave devices.
+pub mod slave_proxy;
+/// Startup announcement, Who-Is burst handling and ready signal for servers.
+pub mod startup;
 /// Per-device request throttling utility.
 pub mod throttle;
+/// Scheduled time synchronization with device clock drift detection.
+pub mod time_master;
+/// Bulk time synchronization reports, device clock readings and clock audits.
+pub mod time_sync;
+/// Recent confirmed-transaction records and per-device statistics for diagnostics.
+pub mod trace;
 /// Owned application-data values for client-side use.
 pub mod value;
+/// Write-then-read-back verification options.
+pub mod verify;
 /// Device discovery walk — reads all objects and their properties.
 pub mod walk;
+/// Comparison of two device walk snapshots.
+pub mod walk_diff;
+/// Batched, retried posting of notifications as JSON to HTTP endpoints.
+#[cfg(feature = "webhook")]
+pub mod webhook;
+/// Who-Is rate limiting, duplicate suppression and I-Am jitter for responders.
+pub mod who_is_policy;
+/// Multi-property writes with automatic rollback.
+pub mod write_batch;
+/// Object, property and priority rules checked before write-class requests.
+pub mod write_policy;
 
+pub use access_control::{
+    AccessDoorState, AccessEventNotification, AccessZoneState, AuthenticationFactorValue,
+};
+pub use accumulator::{PulseScaling, ScaledPulses};
 pub use alarm::{
     AlarmSummaryItem, EnrollmentSummaryItem, EventInformationItem, EventInformationResult,
-    EventNotification,
+    EventNotification, EventValues,
+};
+pub use alarm_routing::{
+    AlarmDelivery, AlarmDeliveryFailure, AlarmRoute, AlarmRouter, AlarmSink, EmailMessage,
+    EmailSink, EmailTransport, EscalationStep, MqttPublisher, MqttSink, RoutedAlarm, RoutingReport,
+    TimeWindow,
 };
-pub use client::{BacnetClient, ForeignDeviceRenewal};
+pub use audit::{PropertyWrite, WriteAction, WriteAuditEvent, WriteAuditHook};
+pub use capabilities::{DeviceCapabilities, ProtocolService};
+pub use client::{BacnetClient, ForeignDeviceRenewal, ForeignDeviceStatus};
+pub use compare::{normalize_text, normalize_value, values_equivalent, ValueComparison};
+pub use control_loop::{LoopTuning, LoopTuningChange};
 pub use cov::{CovNotification, CovPropertyValue};
+pub use cov_inventory::{
+    reconcile_cov_subscriptions, ActiveCovMultipleSubscription, ActiveCovSubscription,
+    CovReconciliation,
+};
 pub use cov_manager::{
-    CovManager, CovManagerBuilder, CovSubscriptionSpec, CovUpdate, UpdateSource,
+    CovManager, CovManagerBuilder, CovMode, CovSubscriptionSpec, CovUpdate, UpdateSource,
 };
-pub use discovery::{DiscoveredDevice, DiscoveredObject};
-pub use error::ClientError;
+#[cfg(feature = "serde")]
+pub use device_db::JsonFileStore;
+pub use device_db::{
+    DeviceDatabase, DeviceRecord, DeviceStore, MemoryStore, RefreshFailure, RefreshOptions,
+    RefreshReport,
+};
+pub use discovery::{DiscoveredDevice, DiscoveredObject, DiscoveryDedup};
+pub use error::{ClientError, RequestContext};
+pub use event_enrollment::EventEnrollmentConfig;
+pub use event_message::{
+    render_event_message, EventMessageCatalog, EventMessageTexts, EventTransition,
+    DEFAULT_EVENT_TEMPLATE,
+};
+pub use export::{Cell, Column, ColumnType, ExportTable};
 pub use file::{AtomicReadFileResult, AtomicWriteFileResult};
-pub use listener::{create_notification_listener, Notification, NotificationListener};
-pub use point::{PointClassification, PointDirection, PointKind};
-pub use range::{ClientBitString, ReadRangeResult};
-pub use rustbac_bacnet_sc::BacnetScTransport;
+pub use gateway::{GatewayHandler, PointDefinition, PointGateway, PointProvider, PollReport};
+pub use global_group::{group_members, GlobalGroupValue, GroupMemberResult};
+pub use journal::{JournalEntry, JournalFilter, TransactionJournal};
+pub use life_safety::{LifeSafetyNotification, LifeSafetyStatus};
+pub use lift::{
+    ElevatorGroupState, EscalatorState, LandingCallStatusValue, LiftCalls, LiftCarState,
+};
+pub use listener::{
+    create_notification_listener, create_notification_listener_with_options, AckPolicy,
+    ListenerOptions, Notification, NotificationListener,
+};
+pub use log_buffer::{
+    AuditLogDatum, AuditLogRecord, AuditNotification, LogBufferRecord, LogMultipleData,
+    LogMultipleRecord, LogValue, Recipient, TrendLogRecord,
+};
+pub use memory_limits::{MemoryLimits, DEFAULT_MAX_REASSEMBLY_BYTES};
+pub use network_port::{NetworkPortConfig, NetworkPortIpChange};
+pub use observer::{
+    FrameKind, ObservingDataLink, PeerTraffic, TrafficDirection, TrafficEvent, TrafficObserver,
+};
+pub use occupancy::{
+    OccupancySchedule, OccupiedPeriod, ScheduleApplyFailure, ScheduleApplyReport, Weekday,
+};
+pub use point::{PointClassification, PointDirection, PointKind, StateTextCache, StateValue};
+pub use point_ref::{PointReference, PointReferenceParseError};
+pub use process_id::{
+    find_duplicate_subscriptions, DuplicateSubscription, SubscriberProcessIdAllocator,
+};
+pub use properties::ObjectProperties;
+pub use range::{ClientBitString, ReadRangeResult, TimedReadRangeResult, TimedRecord};
+pub use request_options::{RequestOptions, SegmentationOptions};
+pub use router::{
+    classify_frame, InboundKind, InboundRouter, InboundSubscription, RoutedLink,
+    DEFAULT_INBOUND_QUEUE_CAPACITY,
+};
+pub use rustbac_bacnet_sc::{
+    BacnetScTransport, ScConnectOptions, ScConnectionState, ScListener, ScServerConfig,
+    ScTlsServerConfig,
+};
+#[cfg(feature = "sc-proxy")]
+pub use rustbac_bacnet_sc::{ProxyCredentials, ScProxy};
+pub use rustbac_core::npdu::NetworkPriority;
+pub use rustbac_core::services::access_control::{
+    AccessEvent, AccessZoneOccupancyState, AuthenticationStatus, DoorAlarmState, DoorStatus,
+    DoorValue, LockStatus,
+};
+pub use rustbac_core::services::accumulator::{Prescale, Scale};
 pub use rustbac_core::services::acknowledge_alarm::{EventState, TimeStamp};
+pub use rustbac_core::services::channel::{ChannelValue, WriteStatus};
+pub use rustbac_core::services::control_loop::LoopAction;
 pub use rustbac_core::services::device_management::{DeviceCommunicationState, ReinitializeState};
+pub use rustbac_core::services::event_enrollment::{
+    EventParameter, EventTransitions, PropertyState,
+};
+pub use rustbac_core::services::lift::{
+    EscalatorMode, EscalatorOperationDirection, LandingCall, LandingCallCommand, LiftCarDirection,
+    LiftCarDriveStatus, LiftCarMode, LiftGroupMode,
+};
+pub use rustbac_core::services::lighting::{
+    ColorCommand, ColorOperation, LightingCommand, LightingOperation, XyColor,
+};
+pub use rustbac_core::services::read_property_multiple::PropertyAccessError;
+pub use rustbac_core::services::read_range::AuditOperation;
+pub use rustbac_core::services::{DecodeMode, SkippedTag};
+pub use rustbac_core::types::{
+    DeviceObjectPropertyReference, LifeSafetyMode, LifeSafetyOperation, LifeSafetyState,
+    ObjectPropertyReference, PropertyReference, SilencedState,
+};
 pub use rustbac_datalink::bip::transport::{BroadcastDistributionEntry, ForeignDeviceTableEntry};
-pub use schedule::{CalendarEntry, DateRange, TimeValue};
+pub use rustbac_datalink::{BvlcResultCode, SwappableDataLink};
+pub use schedule::{
+    validate_exception_schedule, validate_weekly_schedule, CalendarEntry, DateRange,
+    ScheduleDiagnostic, ScheduleEntry, ScheduleIssue, SpecialEvent, SpecialEventPeriod, TimeValue,
+};
 pub use server::{
     encode_unconfirmed_cov_notification, BacnetServer, BacnetServiceError, CovSubscriptionManager,
-    ObjectStore, ObjectStoreHandler, ServiceHandler,
+    ObjectStore, ObjectStoreHandler, ServiceHandler, VirtualNetwork,
 };
-pub use simulator::SimulatedDevice;
+pub use simulator::{ForcedResponse, SimulatedDevice, SimulatedNetwork, SimulatorFaults};
+pub use slave_proxy::{SlaveBinding, SlaveProxy};
+pub use startup::{ServerReady, StartupOptions};
 pub use throttle::DeviceThrottle;
-pub use value::ClientDataValue;
-pub use walk::{DeviceInfo, DeviceWalkResult, ObjectSummary};
+pub use time_master::{ClockDrift, TimeMaster, TimeMasterConfig, TimeMasterStatus};
+pub use time_sync::{
+    ClockAuditEntry, ClockAuditReport, ClockReadFailure, DeviceClock, TimeSyncFailure,
+    TimeSyncReport,
+};
+pub use trace::{DeviceStats, TransactionOutcome, TransactionRecord};
+pub use value::{ClientDataValue, StringInterner};
+pub use verify::WriteVerifyOptions;
+pub use walk::{DeviceInfo, DeviceWalkResult, ObjectSummary, WalkOptions};
+pub use walk_diff::{
+    diff_walks, diff_walks_with_options, DeviceWalkDiff, ObjectChange, PropertyChange,
+    WalkDiffOptions,
+};
+#[cfg(feature = "webhook")]
+pub use webhook::{
+    event_notification_json, WebhookConfig, WebhookEndpoint, WebhookSink, WebhookStats,
+    WebhookUrlError,
+};
+pub use who_is_policy::WhoIsPolicy;
+pub use write_batch::{
+    WriteBatchFailure, WriteBatchItem, WriteBatchReport, WriteBurstFailure, WriteBurstReport,
+};
+pub use write_policy::{WriteDecision, WritePolicy, WriteRule, WriteRules};
 
 // Internal helpers used by simulator module.
 use rustbac_core::encoding::{primitives::decode_unsigned, reader::Reader, tag::Tag};
@@ -92,7 +294,7 @@ fn data_value_to_client(value: rustbac_core::types::DataValue<'_>) -> ClientData
         rustbac_core::types::DataValue::Double(v) => ClientDataValue::Double(v),
         rustbac_core::types::DataValue::OctetString(v) => ClientDataValue::OctetString(v.to_vec()),
         rustbac_core::types::DataValue::CharacterString(v) => {
-            ClientDataValue::CharacterString(v.to_string())
+            ClientDataValue::CharacterString(v.into())
         }
         rustbac_core::types::DataValue::BitString(v) => ClientDataValue::BitString {
             unused_bits: v.unused_bits,
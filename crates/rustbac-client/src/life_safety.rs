@@ -0,0 +1,146 @@
+//! Typed state for the BACnet Life Safety Point and Life Safety Zone objects.
+//!
+//! Fire and security panels expose detectors and zones as Life Safety objects: the
+//! Present_Value is the latched state, Tracking_Value the live one, and Mode and
+//! Operation_Expected say how the object is armed and what an operator should do next.
+//! Their changes arrive as change-of-life-safety notifications, decoded into
+//! [`EventNotification::life_safety`](crate::EventNotification::life_safety). See
+//! [`BacnetClient::read_life_safety`](crate::BacnetClient::read_life_safety).
+
+use crate::{ClientBitString, ClientDataValue};
+use rustbac_core::services::event_notification::ChangeOfLifeSafetyParameters;
+use rustbac_core::types::{
+    LifeSafetyMode, LifeSafetyOperation, LifeSafetyState, PropertyId, SilencedState,
+};
+
+/// Properties read for a [`LifeSafetyStatus`].
+pub(crate) const LIFE_SAFETY_PROPERTIES: &[PropertyId] = &[
+    PropertyId::PresentValue,
+    PropertyId::TrackingValue,
+    PropertyId::Mode,
+    PropertyId::OperationExpected,
+    PropertyId::Silenced,
+];
+
+/// The state of a Life Safety Point or Zone.
+///
+/// Values are kept raw so proprietary states survive; the accessors give the standard
+/// enumerations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LifeSafetyStatus {
+    /// `Present_Value`, latched until reset.
+    pub present_value: u32,
+    /// `Tracking_Value`, the unlatched state.
+    pub tracking_value: u32,
+    /// `Mode`.
+    pub mode: u32,
+    /// `Operation_Expected`.
+    pub operation_expected: u32,
+    /// `Silenced`.
+    pub silenced: u32,
+}
+
+impl LifeSafetyStatus {
+    /// Builds the status from ReadPropertyMultiple results; `None` if a property is
+    /// missing or not enumerated.
+    pub(crate) fn from_properties(values: &[(PropertyId, ClientDataValue)]) -> Option<Self> {
+        let enumerated = |property_id| {
+            values.iter().find_map(|(id, value)| match value {
+                ClientDataValue::Enumerated(v) if *id == property_id => Some(*v),
+                _ => None,
+            })
+        };
+        Some(Self {
+            present_value: enumerated(PropertyId::PresentValue)?,
+            tracking_value: enumerated(PropertyId::TrackingValue)?,
+            mode: enumerated(PropertyId::Mode)?,
+            operation_expected: enumerated(PropertyId::OperationExpected)?,
+            silenced: enumerated(PropertyId::Silenced)?,
+        })
+    }
+
+    pub fn present_value(&self) -> Option<LifeSafetyState> {
+        LifeSafetyState::from_u32(self.present_value)
+    }
+
+    pub fn tracking_value(&self) -> Option<LifeSafetyState> {
+        LifeSafetyState::from_u32(self.tracking_value)
+    }
+
+    pub fn mode(&self) -> Option<LifeSafetyMode> {
+        LifeSafetyMode::from_u32(self.mode)
+    }
+
+    pub fn operation_expected(&self) -> Option<LifeSafetyOperation> {
+        LifeSafetyOperation::from_u32(self.operation_expected)
+    }
+
+    pub fn silenced(&self) -> Option<SilencedState> {
+        SilencedState::from_u32(self.silenced)
+    }
+
+    /// Whether the latched state is an alarm (see [`LifeSafetyState::is_alarm`]).
+    pub fn in_alarm(&self) -> bool {
+        self.present_value().is_some_and(LifeSafetyState::is_alarm)
+    }
+}
+
+/// The event values of a change-of-life-safety notification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LifeSafetyNotification {
+    pub new_state_raw: u32,
+    pub new_state: Option<LifeSafetyState>,
+    pub new_mode_raw: u32,
+    pub new_mode: Option<LifeSafetyMode>,
+    pub status_flags: ClientBitString,
+    pub operation_expected_raw: u32,
+    pub operation_expected: Option<LifeSafetyOperation>,
+}
+
+impl From<ChangeOfLifeSafetyParameters<'_>> for LifeSafetyNotification {
+    fn from(parameters: ChangeOfLifeSafetyParameters<'_>) -> Self {
+        Self {
+            new_state_raw: parameters.new_state,
+            new_state: parameters.new_state(),
+            new_mode_raw: parameters.new_mode,
+            new_mode: parameters.new_mode(),
+            status_flags: ClientBitString {
+                unused_bits: parameters.status_flags.unused_bits,
+                data: parameters.status_flags.data.to_vec(),
+            },
+            operation_expected_raw: parameters.operation_expected,
+            operation_expected: parameters.operation_expected(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LifeSafetyStatus;
+    use crate::ClientDataValue;
+    use rustbac_core::types::{LifeSafetyMode, LifeSafetyState, PropertyId};
+
+    #[test]
+    fn status_is_built_from_life_safety_properties() {
+        let values = [
+            (PropertyId::PresentValue, ClientDataValue::Enumerated(2)),
+            (PropertyId::TrackingValue, ClientDataValue::Enumerated(0)),
+            (PropertyId::Mode, ClientDataValue::Enumerated(1)),
+            (
+                PropertyId::OperationExpected,
+                ClientDataValue::Enumerated(4),
+            ),
+            (PropertyId::Silenced, ClientDataValue::Enumerated(300)),
+        ];
+        let status = LifeSafetyStatus::from_properties(&values).unwrap();
+        assert_eq!(status.present_value(), Some(LifeSafetyState::Alarm));
+        assert_eq!(status.tracking_value(), Some(LifeSafetyState::Quiet));
+        assert_eq!(status.mode(), Some(LifeSafetyMode::On));
+        assert!(status.in_alarm());
+        // Proprietary values are kept raw.
+        assert_eq!(status.silenced, 300);
+        assert_eq!(status.silenced(), None);
+        assert!(LifeSafetyStatus::from_properties(&values[1..]).is_none());
+    }
+}
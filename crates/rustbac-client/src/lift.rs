@@ -0,0 +1,247 @@
+//! Typed state for the BACnet vertical-transport objects: Lift, Escalator and Elevator
+//! Group.
+//!
+//! A Lift reports its car's position, direction, drive and mode as plain properties,
+//! and its calls as constructed lists: the floors registered inside the car, and the
+//! landing calls assigned to it, one list per car door. An Elevator Group holds the
+//! landing calls not yet assigned to a car. See
+//! [`BacnetClient::read_lift_car_state`](crate::BacnetClient::read_lift_car_state),
+//! [`BacnetClient::read_lift_calls`](crate::BacnetClient::read_lift_calls),
+//! [`BacnetClient::read_landing_calls`](crate::BacnetClient::read_landing_calls),
+//! [`BacnetClient::read_escalator`](crate::BacnetClient::read_escalator) and
+//! [`BacnetClient::read_elevator_group`](crate::BacnetClient::read_elevator_group).
+
+use crate::ClientDataValue;
+use rustbac_core::services::lift::{
+    EscalatorMode, EscalatorOperationDirection, LandingCall, LandingCallCommand, LandingCallStatus,
+    LiftCarDirection, LiftCarDriveStatus, LiftCarMode, LiftGroupMode,
+};
+use rustbac_core::types::PropertyId;
+
+/// Properties read for a [`LiftCarState`].
+pub(crate) const LIFT_CAR_PROPERTIES: &[PropertyId] = &[
+    PropertyId::CarPosition,
+    PropertyId::CarMovingDirection,
+    PropertyId::CarAssignedDirection,
+    PropertyId::CarDriveStatus,
+    PropertyId::CarMode,
+    PropertyId::CarLoad,
+];
+
+/// Properties read for an [`EscalatorState`].
+pub(crate) const ESCALATOR_PROPERTIES: &[PropertyId] = &[
+    PropertyId::EscalatorMode,
+    PropertyId::OperationDirection,
+    PropertyId::PassengerAlarm,
+];
+
+/// Properties read for an [`ElevatorGroupState`].
+pub(crate) const ELEVATOR_GROUP_PROPERTIES: &[PropertyId] =
+    &[PropertyId::GroupId, PropertyId::GroupMode];
+
+/// The car state of a Lift object; properties the lift does not have are `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LiftCarState {
+    /// `Car_Position`: the floor number the car is at or passing.
+    pub position: Option<u8>,
+    /// `Car_Moving_Direction`.
+    pub moving_direction: Option<LiftCarDirection>,
+    /// `Car_Assigned_Direction`: the direction the car will travel next.
+    pub assigned_direction: Option<LiftCarDirection>,
+    /// `Car_Drive_Status`.
+    pub drive_status: Option<LiftCarDriveStatus>,
+    /// `Car_Mode`.
+    pub mode: Option<LiftCarMode>,
+    /// `Car_Load`, in Car_Load_Units.
+    pub load: Option<f32>,
+}
+
+impl LiftCarState {
+    pub(crate) fn from_properties(values: &[(PropertyId, ClientDataValue)]) -> Self {
+        let mut state = Self::default();
+        for (property_id, value) in values {
+            match (property_id, value) {
+                (PropertyId::CarPosition, ClientDataValue::Unsigned(v)) => {
+                    state.position = u8::try_from(*v).ok();
+                }
+                (PropertyId::CarMovingDirection, ClientDataValue::Enumerated(v)) => {
+                    state.moving_direction = LiftCarDirection::from_u32(*v);
+                }
+                (PropertyId::CarAssignedDirection, ClientDataValue::Enumerated(v)) => {
+                    state.assigned_direction = LiftCarDirection::from_u32(*v);
+                }
+                (PropertyId::CarDriveStatus, ClientDataValue::Enumerated(v)) => {
+                    state.drive_status = LiftCarDriveStatus::from_u32(*v);
+                }
+                (PropertyId::CarMode, ClientDataValue::Enumerated(v)) => {
+                    state.mode = LiftCarMode::from_u32(*v);
+                }
+                (PropertyId::CarLoad, ClientDataValue::Real(v)) => state.load = Some(*v),
+                _ => {}
+            }
+        }
+        state
+    }
+}
+
+/// The state of an Escalator object; properties the escalator does not have are `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EscalatorState {
+    /// `Escalator_Mode`.
+    pub mode: Option<EscalatorMode>,
+    /// `Operation_Direction`.
+    pub operation_direction: Option<EscalatorOperationDirection>,
+    /// `Passenger_Alarm`.
+    pub passenger_alarm: Option<bool>,
+}
+
+impl EscalatorState {
+    pub(crate) fn from_properties(values: &[(PropertyId, ClientDataValue)]) -> Self {
+        let mut state = Self::default();
+        for (property_id, value) in values {
+            match (property_id, value) {
+                (PropertyId::EscalatorMode, ClientDataValue::Enumerated(v)) => {
+                    state.mode = EscalatorMode::from_u32(*v);
+                }
+                (PropertyId::OperationDirection, ClientDataValue::Enumerated(v)) => {
+                    state.operation_direction = EscalatorOperationDirection::from_u32(*v);
+                }
+                (PropertyId::PassengerAlarm, ClientDataValue::Boolean(v)) => {
+                    state.passenger_alarm = Some(*v);
+                }
+                _ => {}
+            }
+        }
+        state
+    }
+}
+
+/// The state of an Elevator Group object; properties the group does not have are
+/// `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ElevatorGroupState {
+    /// `Group_ID`.
+    pub group_id: Option<u8>,
+    /// `Group_Mode`.
+    pub group_mode: Option<LiftGroupMode>,
+}
+
+impl ElevatorGroupState {
+    pub(crate) fn from_properties(values: &[(PropertyId, ClientDataValue)]) -> Self {
+        let mut state = Self::default();
+        for (property_id, value) in values {
+            match (property_id, value) {
+                (PropertyId::GroupId, ClientDataValue::Unsigned(v)) => {
+                    state.group_id = u8::try_from(*v).ok();
+                }
+                (PropertyId::GroupMode, ClientDataValue::Enumerated(v)) => {
+                    state.group_mode = LiftGroupMode::from_u32(*v);
+                }
+                _ => {}
+            }
+        }
+        state
+    }
+}
+
+/// An owned BACnetLandingCallStatus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LandingCallStatusValue {
+    pub floor_number: u8,
+    pub command: LandingCallCommand,
+    pub floor_text: Option<String>,
+}
+
+impl From<LandingCallStatus<'_>> for LandingCallStatusValue {
+    fn from(call: LandingCallStatus<'_>) -> Self {
+        Self {
+            floor_number: call.floor_number,
+            command: call.command,
+            floor_text: call.floor_text.map(str::to_owned),
+        }
+    }
+}
+
+/// The calls a Lift car is serving, one list per car door.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LiftCalls {
+    /// `Registered_Car_Call`: floors requested from inside the car.
+    pub registered_car_calls: Vec<Vec<u8>>,
+    /// `Assigned_Landing_Calls`: landing calls the group has given this car.
+    pub assigned_landing_calls: Vec<Vec<LandingCall>>,
+}
+
+impl LiftCalls {
+    /// Every floor the car has to stop at, in ascending order without repeats.
+    pub fn stops(&self) -> Vec<u8> {
+        let mut stops: Vec<u8> = self
+            .registered_car_calls
+            .iter()
+            .flatten()
+            .copied()
+            .chain(
+                self.assigned_landing_calls
+                    .iter()
+                    .flatten()
+                    .map(|call| call.floor_number),
+            )
+            .collect();
+        stops.sort_unstable();
+        stops.dedup();
+        stops
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EscalatorState, LiftCalls, LiftCarState};
+    use crate::ClientDataValue;
+    use rustbac_core::services::lift::{
+        EscalatorOperationDirection, LandingCall, LiftCarDirection, LiftCarMode,
+    };
+    use rustbac_core::types::PropertyId;
+
+    #[test]
+    fn car_and_escalator_state_are_built_from_their_properties() {
+        let car = LiftCarState::from_properties(&[
+            (PropertyId::CarPosition, ClientDataValue::Unsigned(4)),
+            (
+                PropertyId::CarMovingDirection,
+                ClientDataValue::Enumerated(3),
+            ),
+            (PropertyId::CarMode, ClientDataValue::Enumerated(6)),
+            (PropertyId::CarLoad, ClientDataValue::Real(320.0)),
+        ]);
+        assert_eq!(car.position, Some(4));
+        assert_eq!(car.moving_direction, Some(LiftCarDirection::Up));
+        assert_eq!(car.mode, Some(LiftCarMode::FirefighterControl));
+        assert_eq!(car.load, Some(320.0));
+        assert_eq!(car.drive_status, None);
+
+        let escalator = EscalatorState::from_properties(&[
+            (
+                PropertyId::OperationDirection,
+                ClientDataValue::Enumerated(5),
+            ),
+            (PropertyId::PassengerAlarm, ClientDataValue::Boolean(false)),
+        ]);
+        assert_eq!(
+            escalator.operation_direction,
+            Some(EscalatorOperationDirection::DownReducedSpeed)
+        );
+        assert_eq!(escalator.passenger_alarm, Some(false));
+        assert_eq!(escalator.mode, None);
+    }
+
+    #[test]
+    fn stops_merge_car_and_landing_calls() {
+        let calls = LiftCalls {
+            registered_car_calls: vec![vec![7, 2], vec![9]],
+            assigned_landing_calls: vec![vec![LandingCall {
+                floor_number: 7,
+                direction: LiftCarDirection::Down,
+            }]],
+        };
+        assert_eq!(calls.stops(), vec![2, 7, 9]);
+    }
+}
@@ -1,13 +1,23 @@
 //! Long-running async notification listener.
 //!
 //! Provides a notification listener that receives COV and event notifications
-//! and dispatches them through a bounded channel.
+//! and dispatches them through a bounded channel. I-Am and I-Have frames seen on the
+//! way are cached, so the devices and objects on the network can be queried without
+//! sending any broadcast.
 
-use crate::{ClientDataValue, CovNotification, CovPropertyValue, EventNotification};
+use crate::runtime::{self, TokioRuntime};
+use crate::{
+    ClientDataValue, CovNotification, CovPropertyValue, DiscoveredDevice, DiscoveredObject,
+    EventNotification, EventValues,
+};
 use rustbac_core::apdu::{
     abort_reason, AbortPdu, ApduType, ConfirmedRequestHeader, SimpleAck, UnconfirmedRequestHeader,
 };
-use rustbac_core::encoding::{reader::Reader, writer::Writer};
+use rustbac_core::encoding::{
+    reader::Reader,
+    tag::{AppTag, Tag},
+    writer::Writer,
+};
 use rustbac_core::npdu::Npdu;
 use rustbac_core::services::acknowledge_alarm::EventState;
 use rustbac_core::services::cov_notification::{
@@ -18,8 +28,13 @@ use rustbac_core::services::event_notification::{
     EventNotificationRequest, SERVICE_CONFIRMED_EVENT_NOTIFICATION,
     SERVICE_UNCONFIRMED_EVENT_NOTIFICATION,
 };
+use rustbac_core::services::i_am::{IAmRequest, SERVICE_I_AM};
+use rustbac_core::services::who_has::{IHaveRequest, SERVICE_I_HAVE};
+use rustbac_core::types::{ErrorClass, ErrorCode, ObjectId};
 use rustbac_datalink::{DataLink, DataLinkAddress};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 /// Default capacity of the bounded channel used by [`create_notification_listener`].
@@ -28,6 +43,63 @@ use tokio::sync::mpsc;
 /// new arrivals rather than growing the queue without bound.
 pub const DEFAULT_NOTIFICATION_CHANNEL_CAPACITY: usize = 256;
 
+/// When a listener acknowledges a confirmed notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AckPolicy {
+    /// Acknowledge as soon as the notification decodes. A notification then dropped
+    /// because the channel is full has still been acknowledged, so the device never
+    /// resends it.
+    #[default]
+    Immediate,
+    /// Acknowledge once the notification is queued for the consumer, waiting up to
+    /// `timeout` for channel room. A notification still unqueued after `timeout` is
+    /// dropped unacknowledged, so the device sends it again. Keep `timeout` below the
+    /// devices' APDU timeout; the driver reads no further frames while it waits.
+    AfterDelivery { timeout: Duration },
+}
+
+/// Options for [`create_notification_listener_with_options`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListenerOptions {
+    /// Channel capacity, clamped to a minimum of 1.
+    pub capacity: usize,
+    /// When confirmed notifications are acknowledged.
+    pub ack_policy: AckPolicy,
+    /// Answer confirmed notifications that fail to decode with an Error PDU
+    /// (services, invalid-tag) instead of ignoring them.
+    pub reject_malformed: bool,
+}
+
+impl Default for ListenerOptions {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_NOTIFICATION_CHANNEL_CAPACITY,
+            ack_policy: AckPolicy::Immediate,
+            reject_malformed: false,
+        }
+    }
+}
+
+impl ListenerOptions {
+    /// Sets the channel capacity.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets when confirmed notifications are acknowledged.
+    pub fn with_ack_policy(mut self, ack_policy: AckPolicy) -> Self {
+        self.ack_policy = ack_policy;
+        self
+    }
+
+    /// Answers undecodable confirmed notifications with an Error PDU.
+    pub fn reject_malformed(mut self) -> Self {
+        self.reject_malformed = true;
+        self
+    }
+}
+
 /// A notification received from a BACnet device — either a COV or an event notification.
 #[derive(Debug, Clone)]
 pub enum Notification {
@@ -44,6 +116,7 @@ pub enum Notification {
 /// returns `None` once the driver future finishes (i.e. when this receiver is dropped).
 pub struct NotificationListener {
     rx: mpsc::Receiver<Notification>,
+    discovered: Arc<Mutex<PassiveDiscovery>>,
 }
 
 impl NotificationListener {
@@ -51,6 +124,47 @@ impl NotificationListener {
     pub async fn recv(&mut self) -> Option<Notification> {
         self.rx.recv().await
     }
+
+    /// Every device whose I-Am the driver has seen, by device instance, each with the
+    /// address of its latest I-Am.
+    pub fn known_devices(&self) -> Vec<DiscoveredDevice> {
+        let discovered = self
+            .discovered
+            .lock()
+            .expect("discovery cache lock poisoned");
+        let mut devices: Vec<_> = discovered.devices.values().cloned().collect();
+        devices.sort_by_key(|d| d.device_id.map(ObjectId::instance));
+        devices
+    }
+
+    /// The latest I-Am seen from device `instance`, if any.
+    pub fn known_device(&self, instance: u32) -> Option<DiscoveredDevice> {
+        self.discovered
+            .lock()
+            .expect("discovery cache lock poisoned")
+            .devices
+            .get(&instance)
+            .cloned()
+    }
+
+    /// Every object whose I-Have the driver has seen, by device and object identifier,
+    /// each with the name from its latest I-Have.
+    pub fn known_objects(&self) -> Vec<DiscoveredObject> {
+        let discovered = self
+            .discovered
+            .lock()
+            .expect("discovery cache lock poisoned");
+        let mut objects: Vec<_> = discovered.objects.values().cloned().collect();
+        objects.sort_by_key(|o| (o.device_id.instance(), o.object_id.raw()));
+        objects
+    }
+}
+
+/// I-Am and I-Have announcements seen by a listener's driver.
+#[derive(Debug, Default)]
+struct PassiveDiscovery {
+    devices: HashMap<u32, DiscoveredDevice>,
+    objects: HashMap<(ObjectId, ObjectId), DiscoveredObject>,
 }
 
 /// Create a notification listener backed by a channel with [`DEFAULT_NOTIFICATION_CHANNEL_CAPACITY`].
@@ -75,7 +189,21 @@ pub fn create_notification_listener_with_capacity<D: DataLink + 'static>(
     datalink: Arc<D>,
     capacity: usize,
 ) -> (NotificationListener, impl std::future::Future<Output = ()>) {
-    let (tx, rx) = mpsc::channel(capacity.max(1));
+    create_notification_listener_with_options(
+        datalink,
+        ListenerOptions::default().with_capacity(capacity),
+    )
+}
+
+/// Like [`create_notification_listener`] but with an explicit acknowledgement policy,
+/// channel capacity and handling of malformed confirmed notifications.
+pub fn create_notification_listener_with_options<D: DataLink + 'static>(
+    datalink: Arc<D>,
+    options: ListenerOptions,
+) -> (NotificationListener, impl std::future::Future<Output = ()>) {
+    let (tx, rx) = mpsc::channel(options.capacity.max(1));
+    let discovered = Arc::new(Mutex::new(PassiveDiscovery::default()));
+    let cache = discovered.clone();
     let driver = async move {
         let mut buf = [0u8; 1500];
         loop {
@@ -84,46 +212,92 @@ pub fn create_notification_listener_with_capacity<D: DataLink + 'static>(
                 Err(_) => continue,
             };
 
-            match parse_notification(&buf[..n], source) {
+            match parse_notification(&buf[..n], source, options.reject_malformed) {
                 ParseResult::None => {}
+                ParseResult::IAm(device) => {
+                    if let Some(device_id) = device.device_id {
+                        let mut cache = cache.lock().expect("discovery cache lock poisoned");
+                        cache.devices.insert(device_id.instance(), device);
+                    }
+                }
+                ParseResult::IHave(object) => {
+                    let mut cache = cache.lock().expect("discovery cache lock poisoned");
+                    cache
+                        .objects
+                        .insert((object.device_id, object.object_id), object);
+                }
                 ParseResult::Abort(ack_bytes) => {
                     #[cfg(feature = "tracing")]
                     tracing::warn!("segmented notification aborted — segmentation not supported");
                     let _ = datalink.send(source, &ack_bytes).await;
                 }
-                ParseResult::Notification(notification, ack) => {
-                    if let Some(ack_bytes) = ack {
-                        let _ = datalink.send(source, &ack_bytes).await;
+                ParseResult::Reject(error_bytes) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!("malformed confirmed notification rejected");
+                    let _ = datalink.send(source, &error_bytes).await;
+                }
+                ParseResult::Notification(notification, ack) => match options.ack_policy {
+                    AckPolicy::Immediate => {
+                        if let Some(ack_bytes) = ack {
+                            let _ = datalink.send(source, &ack_bytes).await;
+                        }
+                        // Drop notifications when the consumer is slow rather than
+                        // growing the queue without bound. Break only when the
+                        // receiver has been dropped; a full channel just discards
+                        // this notification.
+                        match tx.try_send(*notification) {
+                            Ok(()) => {}
+                            Err(_) if tx.is_closed() => break, // receiver dropped
+                            Err(_) => {
+                                #[cfg(feature = "tracing")]
+                                tracing::warn!("notification channel full — dropping notification");
+                            }
+                        }
                     }
-                    // Drop notifications when the consumer is slow rather than
-                    // growing the queue without bound. Break only when the
-                    // receiver has been dropped; a full channel just discards
-                    // this notification.
-                    match tx.try_send(notification) {
-                        Ok(()) => {}
-                        Err(_) if tx.is_closed() => break, // receiver dropped
-                        Err(_) => {
-                            #[cfg(feature = "tracing")]
-                            tracing::warn!("notification channel full — dropping notification");
+                    AckPolicy::AfterDelivery { timeout } => {
+                        match runtime::timeout(&TokioRuntime, timeout, tx.send(*notification)).await
+                        {
+                            Ok(Ok(())) => {
+                                if let Some(ack_bytes) = ack {
+                                    let _ = datalink.send(source, &ack_bytes).await;
+                                }
+                            }
+                            Ok(Err(_)) => break, // receiver dropped
+                            Err(_) => {
+                                #[cfg(feature = "tracing")]
+                                tracing::warn!(
+                                    "notification channel full — leaving notification unacknowledged"
+                                );
+                            }
                         }
                     }
-                }
+                },
             }
         }
     };
 
-    (NotificationListener { rx }, driver)
+    (NotificationListener { rx, discovered }, driver)
 }
 
 enum ParseResult {
     None,
+    /// A device announced itself; cache it, emit no notification.
+    IAm(DiscoveredDevice),
+    /// A device announced one of its objects; cache it, emit no notification.
+    IHave(DiscoveredObject),
     /// Segmented request we cannot handle — send an Abort, emit no notification.
     Abort(Vec<u8>),
+    /// Malformed confirmed request — send the Error PDU, emit no notification.
+    Reject(Vec<u8>),
     /// Parsed notification and optional ack to send back.
-    Notification(Notification, Option<Vec<u8>>),
+    Notification(Box<Notification>, Option<Vec<u8>>),
 }
 
-fn parse_notification(frame: &[u8], source: DataLinkAddress) -> ParseResult {
+fn parse_notification(
+    frame: &[u8],
+    source: DataLinkAddress,
+    reject_malformed: bool,
+) -> ParseResult {
     let apdu = match extract_apdu(frame) {
         Some(a) => a,
         None => return ParseResult::None,
@@ -151,7 +325,7 @@ fn parse_notification(frame: &[u8], source: DataLinkAddress) -> ParseResult {
                         Err(_) => return ParseResult::None,
                     };
                     match build_cov_notification(source, false, cov) {
-                        Some(n) => ParseResult::Notification(Notification::Cov(n), None),
+                        Some(n) => ParseResult::Notification(Box::new(Notification::Cov(n)), None),
                         None => ParseResult::None,
                     }
                 }
@@ -161,10 +335,28 @@ fn parse_notification(frame: &[u8], source: DataLinkAddress) -> ParseResult {
                         Err(_) => return ParseResult::None,
                     };
                     match build_event_notification(source, false, evt) {
-                        Some(n) => ParseResult::Notification(Notification::Event(n), None),
+                        Some(n) => {
+                            ParseResult::Notification(Box::new(Notification::Event(n)), None)
+                        }
                         None => ParseResult::None,
                     }
                 }
+                SERVICE_I_AM => match IAmRequest::decode_after_header(&mut r) {
+                    Ok(i_am) => ParseResult::IAm(DiscoveredDevice {
+                        address: source,
+                        device_id: Some(i_am.device_id),
+                    }),
+                    Err(_) => ParseResult::None,
+                },
+                SERVICE_I_HAVE => match IHaveRequest::decode_after_header(&mut r) {
+                    Ok(i_have) => ParseResult::IHave(DiscoveredObject {
+                        address: source,
+                        device_id: i_have.device_id,
+                        object_id: i_have.object_id,
+                        object_name: i_have.object_name.to_string(),
+                    }),
+                    Err(_) => ParseResult::None,
+                },
                 _ => ParseResult::None,
             }
         }
@@ -180,12 +372,19 @@ fn parse_notification(frame: &[u8], source: DataLinkAddress) -> ParseResult {
             if header.segmented {
                 return ParseResult::Abort(build_abort(header.invoke_id));
             }
+            let malformed = || {
+                if reject_malformed {
+                    ParseResult::Reject(build_error(header.invoke_id, header.service_choice))
+                } else {
+                    ParseResult::None
+                }
+            };
 
             match header.service_choice {
                 SERVICE_CONFIRMED_COV_NOTIFICATION => {
                     let cov = match CovNotificationRequest::decode_after_header(&mut r) {
                         Ok(c) => c,
-                        Err(_) => return ParseResult::None,
+                        Err(_) => return malformed(),
                     };
                     match build_cov_notification(source, true, cov) {
                         Some(n) => {
@@ -193,7 +392,7 @@ fn parse_notification(frame: &[u8], source: DataLinkAddress) -> ParseResult {
                                 header.invoke_id,
                                 SERVICE_CONFIRMED_COV_NOTIFICATION,
                             );
-                            ParseResult::Notification(Notification::Cov(n), Some(ack))
+                            ParseResult::Notification(Box::new(Notification::Cov(n)), Some(ack))
                         }
                         None => ParseResult::None,
                     }
@@ -201,7 +400,7 @@ fn parse_notification(frame: &[u8], source: DataLinkAddress) -> ParseResult {
                 SERVICE_CONFIRMED_EVENT_NOTIFICATION => {
                     let evt = match EventNotificationRequest::decode_after_header(&mut r) {
                         Ok(e) => e,
-                        Err(_) => return ParseResult::None,
+                        Err(_) => return malformed(),
                     };
                     match build_event_notification(source, true, evt) {
                         Some(n) => {
@@ -209,7 +408,7 @@ fn parse_notification(frame: &[u8], source: DataLinkAddress) -> ParseResult {
                                 header.invoke_id,
                                 SERVICE_CONFIRMED_EVENT_NOTIFICATION,
                             );
-                            ParseResult::Notification(Notification::Event(n), Some(ack))
+                            ParseResult::Notification(Box::new(Notification::Event(n)), Some(ack))
                         }
                         None => ParseResult::None,
                     }
@@ -258,6 +457,28 @@ fn build_abort(invoke_id: u8) -> Vec<u8> {
     w.as_written().to_vec()
 }
 
+fn build_error(invoke_id: u8, service_choice: u8) -> Vec<u8> {
+    let mut buf = [0u8; 32];
+    let mut w = Writer::new(&mut buf);
+    Npdu::new(0).encode(&mut w).unwrap();
+    w.write_u8((ApduType::Error as u8) << 4).unwrap();
+    w.write_u8(invoke_id).unwrap();
+    w.write_u8(service_choice).unwrap();
+    for value in [
+        ErrorClass::Services as u8,
+        ErrorCode::InvalidTag.to_u32() as u8,
+    ] {
+        Tag::Application {
+            tag: AppTag::Enumerated,
+            len: 1,
+        }
+        .encode(&mut w)
+        .unwrap();
+        w.write_u8(value).unwrap();
+    }
+    w.as_written().to_vec()
+}
+
 fn build_cov_notification(
     source: DataLinkAddress,
     confirmed: bool,
@@ -309,6 +530,7 @@ fn build_event_notification(
         from_state: EventState::from_u32(evt.from_state),
         to_state_raw: evt.to_state,
         to_state: EventState::from_u32(evt.to_state),
+        event_values: EventValues::from_request(&evt),
     })
 }
 
@@ -322,7 +544,7 @@ fn into_client_value(value: rustbac_core::types::DataValue<'_>) -> Option<Client
         DataValue::Real(v) => ClientDataValue::Real(v),
         DataValue::Double(v) => ClientDataValue::Double(v),
         DataValue::OctetString(v) => ClientDataValue::OctetString(v.to_vec()),
-        DataValue::CharacterString(v) => ClientDataValue::CharacterString(v.to_string()),
+        DataValue::CharacterString(v) => ClientDataValue::CharacterString(v.into()),
         DataValue::BitString(v) => ClientDataValue::BitString {
             unused_bits: v.unused_bits,
             data: v.data.to_vec(),
@@ -340,3 +562,200 @@ fn into_client_value(value: rustbac_core::types::DataValue<'_>) -> Option<Client
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{create_notification_listener_with_options, AckPolicy, ListenerOptions};
+    use rustbac_core::apdu::UnconfirmedRequestHeader;
+    use rustbac_core::apdu::{ApduType, BacnetError, ConfirmedRequestHeader, SimpleAck};
+    use rustbac_core::encoding::{
+        primitives::{encode_ctx_character_string, encode_ctx_object_id, encode_ctx_unsigned},
+        reader::Reader,
+        writer::Writer,
+    };
+    use rustbac_core::npdu::Npdu;
+    use rustbac_core::services::cov_notification::SERVICE_CONFIRMED_COV_NOTIFICATION;
+    use rustbac_core::services::i_am::IAmRequest;
+    use rustbac_core::services::who_has::SERVICE_I_HAVE;
+    use rustbac_core::types::{ObjectId, ObjectType};
+    use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::{mpsc, Mutex};
+
+    struct QueueLink {
+        incoming: Mutex<mpsc::UnboundedReceiver<(Vec<u8>, DataLinkAddress)>>,
+        sent: mpsc::UnboundedSender<Vec<u8>>,
+    }
+
+    impl DataLink for QueueLink {
+        async fn send(&self, _: DataLinkAddress, payload: &[u8]) -> Result<(), DataLinkError> {
+            let _ = self.sent.send(payload.to_vec());
+            Ok(())
+        }
+
+        async fn recv(&self, buf: &mut [u8]) -> Result<(usize, DataLinkAddress), DataLinkError> {
+            let Some((frame, source)) = self.incoming.lock().await.recv().await else {
+                return std::future::pending().await;
+            };
+            buf[..frame.len()].copy_from_slice(&frame);
+            Ok((frame.len(), source))
+        }
+    }
+
+    const SOURCE: DataLinkAddress = DataLinkAddress::Mstp(5);
+
+    type Frames = mpsc::UnboundedSender<(Vec<u8>, DataLinkAddress)>;
+
+    fn confirmed_cov_frame(invoke_id: u8, body: bool) -> Vec<u8> {
+        let mut buf = [0u8; 128];
+        let mut w = Writer::new(&mut buf);
+        Npdu::new(0).encode(&mut w).unwrap();
+        ConfirmedRequestHeader {
+            segmented: false,
+            more_follows: false,
+            segmented_response_accepted: false,
+            max_segments: 0,
+            max_apdu: 5,
+            invoke_id,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_CONFIRMED_COV_NOTIFICATION,
+        }
+        .encode(&mut w)
+        .unwrap();
+        if body {
+            encode_ctx_unsigned(&mut w, 0, 1).unwrap();
+            encode_ctx_object_id(&mut w, 1, ObjectId::new(ObjectType::Device, 1).raw()).unwrap();
+            encode_ctx_object_id(&mut w, 2, ObjectId::new(ObjectType::AnalogInput, 1).raw())
+                .unwrap();
+            encode_ctx_unsigned(&mut w, 3, 60).unwrap();
+            w.write_all(&[0x4E, 0x4F]).unwrap();
+        } else {
+            w.write_all(&[0x09]).unwrap();
+        }
+        w.as_written().to_vec()
+    }
+
+    fn listen(
+        options: ListenerOptions,
+    ) -> (
+        super::NotificationListener,
+        Frames,
+        mpsc::UnboundedReceiver<Vec<u8>>,
+    ) {
+        let (incoming_tx, incoming_rx) = mpsc::unbounded_channel();
+        let (sent_tx, sent_rx) = mpsc::unbounded_channel();
+        let link = Arc::new(QueueLink {
+            incoming: Mutex::new(incoming_rx),
+            sent: sent_tx,
+        });
+        let (listener, driver) = create_notification_listener_with_options(link, options);
+        tokio::spawn(driver);
+        (listener, incoming_tx, sent_rx)
+    }
+
+    fn apdu(frame: &[u8]) -> Reader<'_> {
+        let mut r = Reader::new(frame);
+        Npdu::decode(&mut r).unwrap();
+        r
+    }
+
+    #[tokio::test]
+    async fn malformed_confirmed_notification_is_rejected_when_enabled() {
+        let (_listener, incoming, mut sent) = listen(ListenerOptions::default().reject_malformed());
+        incoming
+            .send((confirmed_cov_frame(7, false), SOURCE))
+            .unwrap();
+
+        let reply = sent.recv().await.unwrap();
+        let error = BacnetError::decode(&mut apdu(&reply)).unwrap();
+        assert_eq!(error.invoke_id, 7);
+        assert_eq!(error.service_choice, SERVICE_CONFIRMED_COV_NOTIFICATION);
+        assert_eq!(error.error_class, Some(5));
+        assert_eq!(error.error_code, Some(57));
+    }
+
+    #[tokio::test]
+    async fn after_delivery_acks_only_queued_notifications() {
+        let (mut listener, incoming, mut sent) =
+            listen(ListenerOptions::default().with_capacity(1).with_ack_policy(
+                AckPolicy::AfterDelivery {
+                    timeout: Duration::from_millis(20),
+                },
+            ));
+        incoming
+            .send((confirmed_cov_frame(1, true), SOURCE))
+            .unwrap();
+        incoming
+            .send((confirmed_cov_frame(2, true), SOURCE))
+            .unwrap();
+
+        let ack = sent.recv().await.unwrap();
+        let mut r = apdu(&ack);
+        assert_eq!(r.peek_u8().unwrap() >> 4, ApduType::SimpleAck as u8);
+        assert_eq!(SimpleAck::decode(&mut r).unwrap().invoke_id, 1);
+
+        // The second notification found the queue full and was left unacknowledged.
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(sent.try_recv().is_err());
+
+        // Once the consumer drains the queue, a resend is delivered and acknowledged.
+        assert!(listener.recv().await.is_some());
+        incoming
+            .send((confirmed_cov_frame(3, true), SOURCE))
+            .unwrap();
+        let ack = sent.recv().await.unwrap();
+        assert_eq!(SimpleAck::decode(&mut apdu(&ack)).unwrap().invoke_id, 3);
+    }
+
+    #[tokio::test]
+    async fn i_am_and_i_have_frames_are_cached() {
+        let (listener, incoming, mut sent) = listen(ListenerOptions::default());
+        let device = ObjectId::new(ObjectType::Device, 12);
+        let i_am = |address| {
+            let mut buf = [0u8; 64];
+            let mut w = Writer::new(&mut buf);
+            Npdu::new(0).encode(&mut w).unwrap();
+            IAmRequest {
+                device_id: device,
+                max_apdu: 480,
+                segmentation: 3,
+                vendor_id: 260,
+            }
+            .encode(&mut w)
+            .unwrap();
+            (w.as_written().to_vec(), address)
+        };
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        Npdu::new(0).encode(&mut w).unwrap();
+        UnconfirmedRequestHeader {
+            service_choice: SERVICE_I_HAVE,
+        }
+        .encode(&mut w)
+        .unwrap();
+        encode_ctx_object_id(&mut w, 0, device.raw()).unwrap();
+        encode_ctx_object_id(&mut w, 1, ObjectId::new(ObjectType::AnalogInput, 3).raw()).unwrap();
+        encode_ctx_character_string(&mut w, 2, "OAT").unwrap();
+        let i_have = w.as_written().to_vec();
+
+        incoming.send(i_am(DataLinkAddress::Mstp(4))).unwrap();
+        incoming.send(i_am(SOURCE)).unwrap();
+        incoming.send((i_have, SOURCE)).unwrap();
+        // The driver handles frames in order, so the ack means the announcements are in.
+        incoming
+            .send((confirmed_cov_frame(1, true), SOURCE))
+            .unwrap();
+        sent.recv().await.unwrap();
+
+        let devices = listener.known_devices();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].address, SOURCE);
+        assert_eq!(listener.known_device(12).unwrap().device_id, Some(device));
+        assert!(listener.known_device(13).is_none());
+        let objects = listener.known_objects();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].object_name, "OAT");
+    }
+}
@@ -0,0 +1,211 @@
+//! Typed `Log_Buffer` records for the Trend Log, Trend Log Multiple and Audit Log
+//! objects.
+//!
+//! ReadRange on a log's `Log_Buffer` returns the records both as generic
+//! [`ReadRangeResult::items`](crate::ReadRangeResult::items) and, decoded by the kind of
+//! log read, as [`ReadRangeResult::records`](crate::ReadRangeResult::records).
+
+use crate::{ClientBitString, ClientDataValue};
+use rustbac_core::services::acknowledge_alarm::TimeStamp;
+use rustbac_core::services::read_range::{
+    AuditOperation, LogDatum, LogMultipleData as CoreLogMultipleData, Recipient as CoreRecipient,
+};
+use rustbac_core::types::{BitString, Date, ObjectId, PropertyReference, Time};
+
+/// An owned [`LogDatum`]: one logged value, or a change of the log itself.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogValue {
+    LogStatus(ClientBitString),
+    Boolean(bool),
+    Real(f32),
+    Enumerated(u32),
+    Unsigned(u32),
+    Signed(i32),
+    BitString(ClientBitString),
+    Null,
+    Failure { error_class: u32, error_code: u32 },
+    TimeChange(f32),
+}
+
+impl From<LogDatum<'_>> for LogValue {
+    fn from(datum: LogDatum<'_>) -> Self {
+        match datum {
+            LogDatum::LogStatus(bits) => Self::LogStatus(bit_string(bits)),
+            LogDatum::Boolean(v) => Self::Boolean(v),
+            LogDatum::Real(v) => Self::Real(v),
+            LogDatum::Enumerated(v) => Self::Enumerated(v),
+            LogDatum::Unsigned(v) => Self::Unsigned(v),
+            LogDatum::Signed(v) => Self::Signed(v),
+            LogDatum::BitString(bits) => Self::BitString(bit_string(bits)),
+            LogDatum::Null => Self::Null,
+            LogDatum::Failure {
+                error_class,
+                error_code,
+            } => Self::Failure {
+                error_class,
+                error_code,
+            },
+            LogDatum::TimeChange(v) => Self::TimeChange(v),
+        }
+    }
+}
+
+/// A Trend Log record (BACnetLogRecord).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrendLogRecord {
+    pub date: Date,
+    pub time: Time,
+    pub datum: LogValue,
+    pub status_flags: Option<ClientBitString>,
+}
+
+/// The data of a [`LogMultipleRecord`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogMultipleData {
+    LogStatus(ClientBitString),
+    /// One value per logged property, in Log_DeviceObjectProperty order.
+    Values(Vec<LogValue>),
+    TimeChange(f32),
+}
+
+impl From<CoreLogMultipleData<'_>> for LogMultipleData {
+    fn from(data: CoreLogMultipleData<'_>) -> Self {
+        match data {
+            CoreLogMultipleData::LogStatus(bits) => Self::LogStatus(bit_string(bits)),
+            CoreLogMultipleData::Values(values) => {
+                Self::Values(values.into_iter().map(Into::into).collect())
+            }
+            CoreLogMultipleData::TimeChange(v) => Self::TimeChange(v),
+        }
+    }
+}
+
+/// A Trend Log Multiple record (BACnetLogMultipleRecord).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogMultipleRecord {
+    pub date: Date,
+    pub time: Time,
+    pub data: LogMultipleData,
+}
+
+/// An owned BACnetRecipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recipient {
+    Device(ObjectId),
+    Address { network: u16, mac: Vec<u8> },
+}
+
+impl From<CoreRecipient<'_>> for Recipient {
+    fn from(recipient: CoreRecipient<'_>) -> Self {
+        match recipient {
+            CoreRecipient::Device(device) => Self::Device(device),
+            CoreRecipient::Address { network, mac } => Self::Address {
+                network,
+                mac: mac.to_vec(),
+            },
+        }
+    }
+}
+
+/// An audited action (BACnetAuditNotification).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditNotification {
+    pub source_timestamp: Option<TimeStamp>,
+    pub target_timestamp: Option<TimeStamp>,
+    /// The device that requested the action.
+    pub source_device: Recipient,
+    pub source_object: Option<ObjectId>,
+    pub operation_raw: u32,
+    pub operation: Option<AuditOperation>,
+    pub source_comment: Option<String>,
+    pub target_comment: Option<String>,
+    pub invoke_id: Option<u8>,
+    pub source_user_id: Option<u16>,
+    pub source_user_role: Option<u8>,
+    /// The device the action was performed on.
+    pub target_device: Recipient,
+    pub target_object: Option<ObjectId>,
+    pub target_property: Option<PropertyReference>,
+    pub target_priority: Option<u8>,
+    pub target_value: Option<ClientDataValue>,
+    pub current_value: Option<ClientDataValue>,
+    /// `(error_class, error_code)` if the action failed.
+    pub result: Option<(u32, u32)>,
+}
+
+/// The datum of an [`AuditLogRecord`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditLogDatum {
+    LogStatus(ClientBitString),
+    Notification(Box<AuditNotification>),
+    TimeChange(f32),
+}
+
+/// An Audit Log record (BACnetAuditLogRecord).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditLogRecord {
+    pub date: Date,
+    pub time: Time,
+    pub datum: AuditLogDatum,
+}
+
+/// A `Log_Buffer` record, by the kind of log it was read from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogBufferRecord {
+    Trend(TrendLogRecord),
+    Multiple(LogMultipleRecord),
+    Audit(AuditLogRecord),
+}
+
+impl LogBufferRecord {
+    /// The record's local date and time.
+    pub fn timestamp(&self) -> (Date, Time) {
+        match self {
+            Self::Trend(record) => (record.date, record.time),
+            Self::Multiple(record) => (record.date, record.time),
+            Self::Audit(record) => (record.date, record.time),
+        }
+    }
+}
+
+fn bit_string(bits: BitString<'_>) -> ClientBitString {
+    ClientBitString {
+        unused_bits: bits.unused_bits,
+        data: bits.data.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LogMultipleData, LogValue};
+    use crate::ClientBitString;
+    use rustbac_core::services::read_range::{LogDatum, LogMultipleData as CoreLogMultipleData};
+    use rustbac_core::types::BitString;
+
+    #[test]
+    fn log_multiple_data_converts_each_value() {
+        let flags = [0x40];
+        let data = LogMultipleData::from(CoreLogMultipleData::Values(vec![
+            LogDatum::Real(18.5),
+            LogDatum::BitString(BitString::new(4, &flags)),
+            LogDatum::Failure {
+                error_class: 2,
+                error_code: 31,
+            },
+        ]));
+        assert_eq!(
+            data,
+            LogMultipleData::Values(vec![
+                LogValue::Real(18.5),
+                LogValue::BitString(ClientBitString {
+                    unused_bits: 4,
+                    data: vec![0x40],
+                }),
+                LogValue::Failure {
+                    error_class: 2,
+                    error_code: 31,
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use crate::router::DEFAULT_INBOUND_QUEUE_CAPACITY;
+
+/// Largest Complex-ACK payload a client reassembles by default.
+pub const DEFAULT_MAX_REASSEMBLY_BYTES: usize = 1024 * 1024;
+
+/// Caps on the memory a [`BacnetClient`](crate::BacnetClient) holds for work in progress,
+/// set with [`BacnetClient::with_memory_limits`](crate::BacnetClient::with_memory_limits).
+///
+/// The default keeps the client's usual limits: a 1 MiB segmented response, no cap on
+/// outstanding requests and [`DEFAULT_INBOUND_QUEUE_CAPACITY`] queued frames per kind.
+/// [`embedded`](Self::embedded) suits a gateway on a small Linux board.
+///
+/// Going over a limit is an error rather than growth: a larger response fails with
+/// [`ClientError::ResponseTooLarge`](crate::ClientError::ResponseTooLarge) and aborts the
+/// transaction, a request beyond the outstanding cap fails with
+/// [`ClientError::Busy`](crate::ClientError::Busy) before anything is sent, and a full
+/// frame queue drops its oldest frame, counted by
+/// [`InboundRouter::dropped`](crate::InboundRouter::dropped).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryLimits {
+    /// Largest segmented response reassembled, at least one 1476-octet APDU, per
+    /// request: up to [`max_outstanding_transactions`](Self::max_outstanding_transactions)
+    /// responses are reassembled at once. The max-segments value announced in requests is
+    /// lowered to match.
+    pub max_reassembly_bytes: usize,
+    /// Most confirmed requests started and not yet answered, counting those waiting to be
+    /// sent and the writes of a [`write_burst`](crate::BacnetClient::write_burst); at
+    /// least 1.
+    pub max_outstanding_transactions: usize,
+    /// Frames queued per [`InboundKind`](crate::InboundKind), answers per confirmed
+    /// request and updates per [`CovManager`](crate::CovManager), for a receiver that is
+    /// not reading; at least 1.
+    pub max_queued_frames: usize,
+}
+
+impl Default for MemoryLimits {
+    fn default() -> Self {
+        Self {
+            max_reassembly_bytes: DEFAULT_MAX_REASSEMBLY_BYTES,
+            max_outstanding_transactions: usize::MAX,
+            max_queued_frames: DEFAULT_INBOUND_QUEUE_CAPACITY,
+        }
+    }
+}
+
+impl MemoryLimits {
+    /// 64 KiB responses, 4 outstanding requests and 8 queued frames per kind.
+    pub fn embedded() -> Self {
+        Self {
+            max_reassembly_bytes: 64 * 1024,
+            max_outstanding_transactions: 4,
+            max_queued_frames: 8,
+        }
+    }
+
+    /// Sets the largest segmented response reassembled, clamped to a minimum of one
+    /// 1476-octet APDU.
+    pub fn with_max_reassembly_bytes(mut self, bytes: usize) -> Self {
+        self.max_reassembly_bytes = bytes.max(1476);
+        self
+    }
+
+    /// Sets the most outstanding confirmed requests, clamped to a minimum of 1.
+    pub fn with_max_outstanding_transactions(mut self, transactions: usize) -> Self {
+        self.max_outstanding_transactions = transactions.max(1);
+        self
+    }
+
+    /// Sets the frames queued per kind, clamped to a minimum of 1.
+    pub fn with_max_queued_frames(mut self, frames: usize) -> Self {
+        self.max_queued_frames = frames.max(1);
+        self
+    }
+
+    /// These limits with every field raised to its minimum, for limits built with
+    /// struct syntax.
+    pub(crate) fn clamped(self) -> Self {
+        Self::default()
+            .with_max_reassembly_bytes(self.max_reassembly_bytes)
+            .with_max_outstanding_transactions(self.max_outstanding_transactions)
+            .with_max_queued_frames(self.max_queued_frames)
+    }
+}
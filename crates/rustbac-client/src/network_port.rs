@@ -0,0 +1,228 @@
+//! Convenience types for the BACnet Network Port object.
+//!
+//! A Network Port holds a device's datalink configuration. New settings are written to
+//! the port, which then reports `Changes_Pending`, and take effect when the device
+//! receives ReinitializeDevice with `ACTIVATE_CHANGES`. See
+//! [`BacnetClient::read_network_port`](crate::BacnetClient::read_network_port),
+//! [`BacnetClient::stage_network_port_ip`](crate::BacnetClient::stage_network_port_ip) and
+//! [`BacnetClient::activate_network_port_changes`](crate::BacnetClient::activate_network_port_changes).
+
+use crate::ClientDataValue;
+use rustbac_core::types::PropertyId;
+use std::net::Ipv4Addr;
+
+/// Properties read for a [`NetworkPortConfig`].
+pub(crate) const NETWORK_PORT_PROPERTIES: &[PropertyId] = &[
+    PropertyId::NetworkType,
+    PropertyId::NetworkNumber,
+    PropertyId::MacAddress,
+    PropertyId::IpAddress,
+    PropertyId::IpSubnetMask,
+    PropertyId::IpDefaultGateway,
+    PropertyId::IpDnsServer,
+    PropertyId::IpDhcpEnable,
+    PropertyId::BacnetIpMode,
+    PropertyId::BacnetIpUdpPort,
+    PropertyId::ChangesPending,
+];
+
+/// Configuration read from a Network Port object.
+///
+/// Properties the port does not have (an MS/TP port has no IP settings, DHCP support is
+/// optional) are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NetworkPortConfig {
+    /// `Network_Type` (0 = Ethernet, 2 = MS/TP, 5 = IPv4, 9 = IPv6, 11 = secure connect).
+    pub network_type: Option<u32>,
+    /// `Network_Number`; 0 when the port does not know its network number.
+    pub network_number: Option<u16>,
+    /// `MAC_Address`: the six-octet IP address and port on BACnet/IP, one octet on MS/TP.
+    pub mac_address: Option<Vec<u8>>,
+    pub ip_address: Option<Ipv4Addr>,
+    pub ip_subnet_mask: Option<Ipv4Addr>,
+    pub ip_default_gateway: Option<Ipv4Addr>,
+    /// `IP_DNS_Server` entries, in array order.
+    pub ip_dns_servers: Vec<Ipv4Addr>,
+    pub ip_dhcp_enable: Option<bool>,
+    /// `BACnet_IP_Mode` (0 = normal, 1 = foreign device, 2 = BBMD).
+    pub bacnet_ip_mode: Option<u32>,
+    pub bacnet_ip_udp_port: Option<u16>,
+    /// `Changes_Pending`: written values are waiting for ReinitializeDevice `ACTIVATE_CHANGES`.
+    pub changes_pending: Option<bool>,
+}
+
+impl NetworkPortConfig {
+    /// Builds a configuration from `(property, value)` pairs, ignoring unexpected types.
+    pub(crate) fn from_properties(props: &[(PropertyId, ClientDataValue)]) -> Self {
+        let mut config = Self::default();
+        for (property_id, value) in props {
+            match (property_id, value) {
+                (PropertyId::NetworkType, ClientDataValue::Enumerated(v)) => {
+                    config.network_type = Some(*v);
+                }
+                (PropertyId::NetworkNumber, ClientDataValue::Unsigned(v)) => {
+                    config.network_number = u16::try_from(*v).ok();
+                }
+                (PropertyId::MacAddress, ClientDataValue::OctetString(bytes)) => {
+                    config.mac_address = Some(bytes.clone());
+                }
+                (PropertyId::IpAddress, value) => config.ip_address = ipv4(value),
+                (PropertyId::IpSubnetMask, value) => config.ip_subnet_mask = ipv4(value),
+                (PropertyId::IpDefaultGateway, value) => config.ip_default_gateway = ipv4(value),
+                (PropertyId::IpDnsServer, ClientDataValue::Constructed { values, .. }) => {
+                    config.ip_dns_servers = values.iter().filter_map(ipv4).collect();
+                }
+                (PropertyId::IpDnsServer, value) => {
+                    config.ip_dns_servers = ipv4(value).into_iter().collect();
+                }
+                (PropertyId::IpDhcpEnable, ClientDataValue::Boolean(v)) => {
+                    config.ip_dhcp_enable = Some(*v);
+                }
+                (PropertyId::BacnetIpMode, ClientDataValue::Enumerated(v)) => {
+                    config.bacnet_ip_mode = Some(*v);
+                }
+                (PropertyId::BacnetIpUdpPort, ClientDataValue::Unsigned(v)) => {
+                    config.bacnet_ip_udp_port = u16::try_from(*v).ok();
+                }
+                (PropertyId::ChangesPending, ClientDataValue::Boolean(v)) => {
+                    config.changes_pending = Some(*v);
+                }
+                _ => {}
+            }
+        }
+        config
+    }
+}
+
+/// IP settings to stage on a Network Port; `None` fields are left unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NetworkPortIpChange {
+    pub ip_address: Option<Ipv4Addr>,
+    pub ip_subnet_mask: Option<Ipv4Addr>,
+    pub ip_default_gateway: Option<Ipv4Addr>,
+    pub ip_dhcp_enable: Option<bool>,
+    pub bacnet_ip_udp_port: Option<u16>,
+}
+
+impl NetworkPortIpChange {
+    /// Sets a static IP address and subnet mask.
+    pub fn with_address(mut self, address: Ipv4Addr, subnet_mask: Ipv4Addr) -> Self {
+        self.ip_address = Some(address);
+        self.ip_subnet_mask = Some(subnet_mask);
+        self
+    }
+
+    /// Sets the default gateway.
+    pub fn with_default_gateway(mut self, gateway: Ipv4Addr) -> Self {
+        self.ip_default_gateway = Some(gateway);
+        self
+    }
+
+    /// Turns DHCP on or off.
+    pub fn with_dhcp(mut self, enable: bool) -> Self {
+        self.ip_dhcp_enable = Some(enable);
+        self
+    }
+
+    /// Sets the BACnet/IP UDP port.
+    pub fn with_udp_port(mut self, port: u16) -> Self {
+        self.bacnet_ip_udp_port = Some(port);
+        self
+    }
+
+    /// The property writes for this change. DHCP goes first, since a device may refuse a
+    /// static address while DHCP is still on.
+    pub(crate) fn writes(&self) -> Vec<(PropertyId, ClientDataValue)> {
+        let address = |ip: Ipv4Addr| ClientDataValue::OctetString(ip.octets().to_vec());
+        let mut writes = Vec::new();
+        if let Some(enable) = self.ip_dhcp_enable {
+            writes.push((PropertyId::IpDhcpEnable, ClientDataValue::Boolean(enable)));
+        }
+        if let Some(ip) = self.ip_address {
+            writes.push((PropertyId::IpAddress, address(ip)));
+        }
+        if let Some(mask) = self.ip_subnet_mask {
+            writes.push((PropertyId::IpSubnetMask, address(mask)));
+        }
+        if let Some(gateway) = self.ip_default_gateway {
+            writes.push((PropertyId::IpDefaultGateway, address(gateway)));
+        }
+        if let Some(port) = self.bacnet_ip_udp_port {
+            writes.push((
+                PropertyId::BacnetIpUdpPort,
+                ClientDataValue::Unsigned(u32::from(port)),
+            ));
+        }
+        writes
+    }
+}
+
+fn ipv4(value: &ClientDataValue) -> Option<Ipv4Addr> {
+    match value {
+        ClientDataValue::OctetString(bytes) => <[u8; 4]>::try_from(bytes.as_slice())
+            .ok()
+            .map(Ipv4Addr::from),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NetworkPortConfig, NetworkPortIpChange};
+    use crate::ClientDataValue;
+    use rustbac_core::types::PropertyId;
+    use std::net::Ipv4Addr;
+
+    fn octets(ip: [u8; 4]) -> ClientDataValue {
+        ClientDataValue::OctetString(ip.to_vec())
+    }
+
+    #[test]
+    fn config_is_built_from_network_port_properties() {
+        let config = NetworkPortConfig::from_properties(&[
+            (PropertyId::NetworkType, ClientDataValue::Enumerated(5)),
+            (PropertyId::NetworkNumber, ClientDataValue::Unsigned(1)),
+            (PropertyId::IpAddress, octets([192, 168, 1, 20])),
+            (PropertyId::IpSubnetMask, octets([255, 255, 255, 0])),
+            (
+                PropertyId::IpDnsServer,
+                ClientDataValue::Constructed {
+                    tag_num: 3,
+                    values: vec![octets([1, 1, 1, 1]), octets([8, 8, 8, 8])],
+                },
+            ),
+            (
+                PropertyId::BacnetIpUdpPort,
+                ClientDataValue::Unsigned(47808),
+            ),
+            (PropertyId::ChangesPending, ClientDataValue::Boolean(false)),
+        ]);
+        assert_eq!(config.network_type, Some(5));
+        assert_eq!(config.network_number, Some(1));
+        assert_eq!(config.ip_address, Some(Ipv4Addr::new(192, 168, 1, 20)));
+        assert_eq!(config.ip_subnet_mask, Some(Ipv4Addr::new(255, 255, 255, 0)));
+        assert_eq!(config.ip_default_gateway, None);
+        assert_eq!(
+            config.ip_dns_servers,
+            vec![Ipv4Addr::new(1, 1, 1, 1), Ipv4Addr::new(8, 8, 8, 8)]
+        );
+        assert_eq!(config.bacnet_ip_udp_port, Some(47808));
+        assert_eq!(config.changes_pending, Some(false));
+    }
+
+    #[test]
+    fn ip_change_writes_dhcp_first_and_skips_unset_fields() {
+        let change = NetworkPortIpChange::default()
+            .with_address(Ipv4Addr::new(10, 0, 0, 5), Ipv4Addr::new(255, 0, 0, 0))
+            .with_dhcp(false);
+        assert_eq!(
+            change.writes(),
+            vec![
+                (PropertyId::IpDhcpEnable, ClientDataValue::Boolean(false)),
+                (PropertyId::IpAddress, octets([10, 0, 0, 5])),
+                (PropertyId::IpSubnetMask, octets([255, 0, 0, 0])),
+            ]
+        );
+    }
+}
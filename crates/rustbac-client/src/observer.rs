@@ -0,0 +1,511 @@
+//! Passive traffic observation.
+//!
+//! [`ObservingDataLink`] wraps any transport and hands every frame it sends or receives to
+//! a [`TrafficObserver`], which decodes the NPDU and APDU headers into a [`TrafficEvent`]
+//! and keeps per-peer [`PeerTraffic`] counters: who talked to whom, with which services,
+//! and how often it ended in an Error, Reject or Abort. [`TrafficObserver::run`] drives an
+//! observer from a transport nobody else reads, turning it into a lightweight network
+//! health analyzer.
+
+use rustbac_core::apdu::{
+    AbortPdu, ApduType, BacnetError, ComplexAckHeader, ConfirmedRequestHeader, RejectPdu,
+    SegmentAck, SimpleAck, UnconfirmedRequestHeader,
+};
+use rustbac_core::encoding::reader::Reader;
+use rustbac_core::npdu::Npdu;
+use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tokio::sync::broadcast;
+
+/// Number of events a lagging [`TrafficObserver::subscribe`] receiver may fall behind by.
+pub const DEFAULT_TRAFFIC_EVENT_CAPACITY: usize = 1024;
+
+/// Whether a frame was sent or received by the observed transport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TrafficDirection {
+    Sent,
+    Received,
+}
+
+/// What a frame carried, decoded from its NPDU and APDU headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrameKind {
+    ConfirmedRequest {
+        invoke_id: u8,
+        service_choice: u8,
+        segmented: bool,
+    },
+    UnconfirmedRequest {
+        service_choice: u8,
+    },
+    SimpleAck {
+        invoke_id: u8,
+        service_choice: u8,
+    },
+    ComplexAck {
+        invoke_id: u8,
+        service_choice: u8,
+        segmented: bool,
+    },
+    SegmentAck {
+        invoke_id: u8,
+        sequence_number: u8,
+    },
+    Error {
+        invoke_id: u8,
+        service_choice: u8,
+        error_class_raw: Option<u32>,
+        error_code_raw: Option<u32>,
+    },
+    Reject {
+        invoke_id: u8,
+        reason: u8,
+    },
+    Abort {
+        invoke_id: u8,
+        reason: u8,
+        server: bool,
+    },
+    /// A network-layer message (e.g. Who-Is-Router-To-Network).
+    NetworkMessage {
+        message_type: u8,
+    },
+    /// The frame did not decode as a BACnet NPDU and APDU.
+    Undecodable,
+}
+
+impl FrameKind {
+    /// Decodes the headers of an NPDU-framed frame.
+    pub fn decode(frame: &[u8]) -> Self {
+        decode_frame(frame).unwrap_or(Self::Undecodable)
+    }
+
+    /// Returns `true` for Error, Reject and Abort PDUs.
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::Error { .. } | Self::Reject { .. } | Self::Abort { .. }
+        )
+    }
+}
+
+/// One frame seen by a [`TrafficObserver`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrafficEvent {
+    pub direction: TrafficDirection,
+    /// The other end: the destination of a sent frame, the source of a received one.
+    pub peer: DataLinkAddress,
+    pub at: SystemTime,
+    /// Frame length in bytes, including the NPDU header.
+    pub len: usize,
+    pub kind: FrameKind,
+}
+
+/// Traffic counters for one peer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeerTraffic {
+    pub frames_sent: u64,
+    pub frames_received: u64,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub confirmed_requests: u64,
+    pub unconfirmed_requests: u64,
+    /// SimpleACKs and ComplexACKs.
+    pub acks: u64,
+    pub errors: u64,
+    pub rejects: u64,
+    pub aborts: u64,
+    pub undecodable: u64,
+    /// Confirmed and unconfirmed requests, in either direction, by service choice.
+    pub services: BTreeMap<u8, u64>,
+    pub last_seen: Option<SystemTime>,
+}
+
+impl PeerTraffic {
+    /// Errors, Rejects and Aborts per confirmed request, or `0.0` before any request.
+    pub fn failure_rate(&self) -> f64 {
+        if self.confirmed_requests == 0 {
+            return 0.0;
+        }
+        (self.errors + self.rejects + self.aborts) as f64 / self.confirmed_requests as f64
+    }
+
+    fn record(&mut self, event: &TrafficEvent) {
+        let len = event.len as u64;
+        match event.direction {
+            TrafficDirection::Sent => {
+                self.frames_sent += 1;
+                self.bytes_sent += len;
+            }
+            TrafficDirection::Received => {
+                self.frames_received += 1;
+                self.bytes_received += len;
+            }
+        }
+        match event.kind {
+            FrameKind::ConfirmedRequest { service_choice, .. } => {
+                self.confirmed_requests += 1;
+                *self.services.entry(service_choice).or_default() += 1;
+            }
+            FrameKind::UnconfirmedRequest { service_choice } => {
+                self.unconfirmed_requests += 1;
+                *self.services.entry(service_choice).or_default() += 1;
+            }
+            FrameKind::SimpleAck { .. } | FrameKind::ComplexAck { .. } => self.acks += 1,
+            FrameKind::Error { .. } => self.errors += 1,
+            FrameKind::Reject { .. } => self.rejects += 1,
+            FrameKind::Abort { .. } => self.aborts += 1,
+            FrameKind::Undecodable => self.undecodable += 1,
+            FrameKind::SegmentAck { .. } | FrameKind::NetworkMessage { .. } => {}
+        }
+        self.last_seen = Some(event.at);
+    }
+}
+
+/// Decodes frames into [`TrafficEvent`]s and aggregates them per peer.
+#[derive(Debug)]
+pub struct TrafficObserver {
+    peers: Mutex<HashMap<DataLinkAddress, PeerTraffic>>,
+    events: broadcast::Sender<TrafficEvent>,
+}
+
+impl Default for TrafficObserver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TrafficObserver {
+    /// Creates an observer with no recorded traffic.
+    pub fn new() -> Self {
+        Self {
+            peers: Mutex::new(HashMap::new()),
+            events: broadcast::channel(DEFAULT_TRAFFIC_EVENT_CAPACITY).0,
+        }
+    }
+
+    /// Decodes and records one frame, returning the resulting event.
+    pub fn observe(
+        &self,
+        direction: TrafficDirection,
+        peer: DataLinkAddress,
+        frame: &[u8],
+    ) -> TrafficEvent {
+        let event = TrafficEvent {
+            direction,
+            peer,
+            at: SystemTime::now(),
+            len: frame.len(),
+            kind: FrameKind::decode(frame),
+        };
+        self.peers
+            .lock()
+            .expect("traffic observer lock poisoned")
+            .entry(peer)
+            .or_default()
+            .record(&event);
+        // No subscribers is not an error.
+        let _ = self.events.send(event.clone());
+        event
+    }
+
+    /// A stream of every event recorded from now on. A receiver that falls more than
+    /// [`DEFAULT_TRAFFIC_EVENT_CAPACITY`] events behind skips the oldest.
+    pub fn subscribe(&self) -> broadcast::Receiver<TrafficEvent> {
+        self.events.subscribe()
+    }
+
+    /// Counters for one peer, if any frame to or from it has been seen.
+    pub fn peer(&self, address: DataLinkAddress) -> Option<PeerTraffic> {
+        self.peers
+            .lock()
+            .expect("traffic observer lock poisoned")
+            .get(&address)
+            .cloned()
+    }
+
+    /// Counters for every peer seen.
+    pub fn peers(&self) -> Vec<(DataLinkAddress, PeerTraffic)> {
+        self.peers
+            .lock()
+            .expect("traffic observer lock poisoned")
+            .iter()
+            .map(|(address, traffic)| (*address, traffic.clone()))
+            .collect()
+    }
+
+    /// Forgets all recorded counters.
+    pub fn reset(&self) {
+        self.peers
+            .lock()
+            .expect("traffic observer lock poisoned")
+            .clear();
+    }
+
+    /// Records every frame `datalink` receives until it fails with anything but an
+    /// invalid frame, returning that error.
+    pub async fn run<D: DataLink>(&self, datalink: &D) -> DataLinkError {
+        let mut buf = [0u8; 1500];
+        loop {
+            match datalink.recv(&mut buf).await {
+                Ok((n, source)) => {
+                    self.observe(TrafficDirection::Received, source, &buf[..n]);
+                }
+                Err(DataLinkError::InvalidFrame) => {}
+                Err(err) => return err,
+            }
+        }
+    }
+}
+
+/// A [`DataLink`] wrapper that records every frame in a [`TrafficObserver`].
+#[derive(Debug)]
+pub struct ObservingDataLink<D: DataLink> {
+    inner: D,
+    observer: Arc<TrafficObserver>,
+}
+
+impl<D: DataLink> ObservingDataLink<D> {
+    /// Wraps `inner` with a new observer.
+    pub fn new(inner: D) -> Self {
+        Self::with_observer(inner, Arc::new(TrafficObserver::new()))
+    }
+
+    /// Wraps `inner`, recording into an existing (possibly shared) observer.
+    pub fn with_observer(inner: D, observer: Arc<TrafficObserver>) -> Self {
+        Self { inner, observer }
+    }
+
+    /// The observer recording this transport's traffic.
+    pub fn observer(&self) -> Arc<TrafficObserver> {
+        self.observer.clone()
+    }
+}
+
+impl<D: DataLink> DataLink for ObservingDataLink<D> {
+    async fn send(&self, address: DataLinkAddress, payload: &[u8]) -> Result<(), DataLinkError> {
+        self.inner.send(address, payload).await?;
+        self.observer
+            .observe(TrafficDirection::Sent, address, payload);
+        Ok(())
+    }
+
+    async fn recv(&self, buf: &mut [u8]) -> Result<(usize, DataLinkAddress), DataLinkError> {
+        let (n, source) = self.inner.recv(buf).await?;
+        self.observer
+            .observe(TrafficDirection::Received, source, &buf[..n]);
+        Ok((n, source))
+    }
+}
+
+fn decode_frame(frame: &[u8]) -> Option<FrameKind> {
+    let mut r = Reader::new(frame);
+    let npdu = Npdu::decode(&mut r).ok()?;
+    if let Some(message_type) = npdu.message_type {
+        return Some(FrameKind::NetworkMessage { message_type });
+    }
+    let apdu = &frame[frame.len() - r.remaining()..];
+    let mut r = Reader::new(apdu);
+    Some(match ApduType::from_u8(apdu.first()? >> 4)? {
+        ApduType::ConfirmedRequest => {
+            let header = ConfirmedRequestHeader::decode(&mut r).ok()?;
+            FrameKind::ConfirmedRequest {
+                invoke_id: header.invoke_id,
+                service_choice: header.service_choice,
+                segmented: header.segmented,
+            }
+        }
+        ApduType::UnconfirmedRequest => FrameKind::UnconfirmedRequest {
+            service_choice: UnconfirmedRequestHeader::decode(&mut r)
+                .ok()?
+                .service_choice,
+        },
+        ApduType::SimpleAck => {
+            let ack = SimpleAck::decode(&mut r).ok()?;
+            FrameKind::SimpleAck {
+                invoke_id: ack.invoke_id,
+                service_choice: ack.service_choice,
+            }
+        }
+        ApduType::ComplexAck => {
+            let header = ComplexAckHeader::decode(&mut r).ok()?;
+            FrameKind::ComplexAck {
+                invoke_id: header.invoke_id,
+                service_choice: header.service_choice,
+                segmented: header.segmented,
+            }
+        }
+        ApduType::SegmentAck => {
+            let ack = SegmentAck::decode(&mut r).ok()?;
+            FrameKind::SegmentAck {
+                invoke_id: ack.invoke_id,
+                sequence_number: ack.sequence_number,
+            }
+        }
+        ApduType::Error => {
+            let error = BacnetError::decode(&mut r).ok()?;
+            FrameKind::Error {
+                invoke_id: error.invoke_id,
+                service_choice: error.service_choice,
+                error_class_raw: error.error_class,
+                error_code_raw: error.error_code,
+            }
+        }
+        ApduType::Reject => {
+            let reject = RejectPdu::decode(&mut r).ok()?;
+            FrameKind::Reject {
+                invoke_id: reject.invoke_id,
+                reason: reject.reason,
+            }
+        }
+        ApduType::Abort => {
+            let abort = AbortPdu::decode(&mut r).ok()?;
+            FrameKind::Abort {
+                invoke_id: abort.invoke_id,
+                reason: abort.reason,
+                server: abort.server,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FrameKind, ObservingDataLink, TrafficDirection};
+    use rustbac_core::apdu::{ConfirmedRequestHeader, SimpleAck};
+    use rustbac_core::encoding::writer::Writer;
+    use rustbac_core::npdu::Npdu;
+    use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError};
+    use std::sync::Mutex;
+
+    const PEER: DataLinkAddress = DataLinkAddress::Mstp(9);
+
+    struct ReplyLink {
+        replies: Mutex<Vec<Vec<u8>>>,
+    }
+
+    impl DataLink for ReplyLink {
+        async fn send(&self, _: DataLinkAddress, _: &[u8]) -> Result<(), DataLinkError> {
+            Ok(())
+        }
+
+        async fn recv(&self, buf: &mut [u8]) -> Result<(usize, DataLinkAddress), DataLinkError> {
+            let frame = self
+                .replies
+                .lock()
+                .unwrap()
+                .pop()
+                .ok_or(DataLinkError::InvalidFrame)?;
+            buf[..frame.len()].copy_from_slice(&frame);
+            Ok((frame.len(), PEER))
+        }
+    }
+
+    fn framed(apdu: &[u8]) -> Vec<u8> {
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        Npdu::new(0).encode(&mut w).unwrap();
+        w.write_all(apdu).unwrap();
+        w.as_written().to_vec()
+    }
+
+    fn read_property_request(invoke_id: u8) -> Vec<u8> {
+        let mut buf = [0u8; 16];
+        let mut w = Writer::new(&mut buf);
+        ConfirmedRequestHeader {
+            segmented: false,
+            more_follows: false,
+            segmented_response_accepted: true,
+            max_segments: 0,
+            max_apdu: 5,
+            invoke_id,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: 0x0C,
+        }
+        .encode(&mut w)
+        .unwrap();
+        framed(w.as_written())
+    }
+
+    #[test]
+    fn frame_headers_decode_into_kinds() {
+        assert_eq!(
+            FrameKind::decode(&read_property_request(4)),
+            FrameKind::ConfirmedRequest {
+                invoke_id: 4,
+                service_choice: 0x0C,
+                segmented: false,
+            }
+        );
+        assert_eq!(
+            FrameKind::decode(&framed(&[0x10, 0x08])),
+            FrameKind::UnconfirmedRequest {
+                service_choice: 0x08
+            }
+        );
+        assert_eq!(
+            FrameKind::decode(&framed(&[0x50, 4, 0x0C, 0x91, 0x02, 0x91, 0x20])),
+            FrameKind::Error {
+                invoke_id: 4,
+                service_choice: 0x0C,
+                error_class_raw: Some(2),
+                error_code_raw: Some(32),
+            }
+        );
+        assert_eq!(
+            FrameKind::decode(&framed(&[0x71, 4, 0x04])),
+            FrameKind::Abort {
+                invoke_id: 4,
+                reason: 4,
+                server: true,
+            }
+        );
+        assert_eq!(FrameKind::decode(&[0x02, 0x00]), FrameKind::Undecodable);
+    }
+
+    #[tokio::test]
+    async fn observing_link_counts_traffic_per_peer() {
+        let mut ack = [0u8; 8];
+        let mut w = Writer::new(&mut ack);
+        SimpleAck {
+            invoke_id: 2,
+            service_choice: 0x0F,
+        }
+        .encode(&mut w)
+        .unwrap();
+        let link = ObservingDataLink::new(ReplyLink {
+            replies: Mutex::new(vec![framed(w.as_written()), framed(&[0x60, 1, 0x09])]),
+        });
+        let observer = link.observer();
+        let mut events = observer.subscribe();
+
+        let mut buf = [0u8; 64];
+        link.send(PEER, &read_property_request(1)).await.unwrap();
+        link.recv(&mut buf).await.unwrap();
+        link.send(PEER, &read_property_request(2)).await.unwrap();
+        link.recv(&mut buf).await.unwrap();
+
+        let first = events.recv().await.unwrap();
+        assert_eq!(first.direction, TrafficDirection::Sent);
+        assert_eq!(first.peer, PEER);
+
+        let traffic = observer.peer(PEER).unwrap();
+        assert_eq!(traffic.frames_sent, 2);
+        assert_eq!(traffic.frames_received, 2);
+        assert_eq!(traffic.confirmed_requests, 2);
+        assert_eq!(traffic.rejects, 1);
+        assert_eq!(traffic.acks, 1);
+        assert_eq!(traffic.services.get(&0x0C), Some(&2));
+        assert_eq!(traffic.failure_rate(), 0.5);
+
+        observer.reset();
+        assert!(observer.peer(PEER).is_none());
+    }
+}
@@ -0,0 +1,278 @@
+//! Occupancy schedules built from opening hours and holidays.
+//!
+//! An [`OccupancySchedule`] describes when a building is occupied: periods per weekday
+//! and a list of holidays. It is turned into a Weekly_Schedule, with an occupied value
+//! at the start of each period and an unoccupied value at its end, and an
+//! Exception_Schedule that keeps every holiday unoccupied all day.
+//! [`BacnetClient::apply_occupancy_schedule`](crate::BacnetClient::apply_occupancy_schedule)
+//! writes both to many Schedule objects at once.
+
+use crate::schedule::{CalendarEntry, SpecialEvent, SpecialEventPeriod, TimeValue};
+use crate::{ClientDataValue, ClientError};
+use rustbac_core::types::{ObjectId, Time};
+use rustbac_datalink::DataLinkAddress;
+
+const MIDNIGHT: Time = Time {
+    hour: 0,
+    minute: 0,
+    second: 0,
+    hundredths: 0,
+};
+
+/// A day of the week, in BACnet order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    /// Monday to Friday.
+    pub const WORKDAYS: [Weekday; 5] = [
+        Self::Monday,
+        Self::Tuesday,
+        Self::Wednesday,
+        Self::Thursday,
+        Self::Friday,
+    ];
+
+    /// Index of the day in a Weekly_Schedule.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A period of one day during which the building is occupied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OccupiedPeriod {
+    pub start: Time,
+    /// Must be later than `start`; periods do not run past midnight.
+    pub end: Time,
+}
+
+/// Opening hours and holidays, to be written to Schedule objects.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OccupancySchedule {
+    /// Value the schedule takes while occupied.
+    pub occupied_value: ClientDataValue,
+    /// Value the schedule takes at all other times.
+    pub unoccupied_value: ClientDataValue,
+    /// Occupied periods of each day, Monday first.
+    pub hours: [Vec<OccupiedPeriod>; 7],
+    /// Days that are unoccupied whatever the weekday.
+    pub holidays: Vec<SpecialEventPeriod>,
+    /// Priority of the holiday special events; 16 unless set.
+    pub holiday_priority: u8,
+}
+
+impl OccupancySchedule {
+    /// An always-unoccupied schedule switching between the two values.
+    pub fn new(occupied_value: ClientDataValue, unoccupied_value: ClientDataValue) -> Self {
+        Self {
+            occupied_value,
+            unoccupied_value,
+            hours: Default::default(),
+            holidays: Vec::new(),
+            holiday_priority: 16,
+        }
+    }
+
+    /// A schedule for binary objects: active (1) when occupied, inactive (0) otherwise.
+    pub fn binary() -> Self {
+        Self::new(
+            ClientDataValue::Enumerated(1),
+            ClientDataValue::Enumerated(0),
+        )
+    }
+
+    /// Adds an occupied period on `day`; overlapping periods are merged.
+    pub fn with_hours(mut self, day: Weekday, start: Time, end: Time) -> Self {
+        self.hours[day.index()].push(OccupiedPeriod { start, end });
+        self
+    }
+
+    /// Adds the same occupied period on every workday.
+    pub fn with_workday_hours(self, start: Time, end: Time) -> Self {
+        Weekday::WORKDAYS
+            .into_iter()
+            .fold(self, |schedule, day| schedule.with_hours(day, start, end))
+    }
+
+    /// Adds a holiday given as a date, date range or week-and-day pattern.
+    pub fn with_holiday(mut self, entry: CalendarEntry) -> Self {
+        self.holidays.push(SpecialEventPeriod::CalendarEntry(entry));
+        self
+    }
+
+    /// Adds every day of a Calendar object in the scheduling device as a holiday.
+    pub fn with_holiday_calendar(mut self, calendar: ObjectId) -> Self {
+        self.holidays
+            .push(SpecialEventPeriod::CalendarReference(calendar));
+        self
+    }
+
+    pub fn with_holiday_priority(mut self, priority: u8) -> Self {
+        self.holiday_priority = priority;
+        self
+    }
+
+    /// The Weekly_Schedule, Monday first.
+    ///
+    /// Each day starts unoccupied at midnight unless a period starts then, so the
+    /// result does not depend on the Schedule_Default of the object written to.
+    pub fn weekly_schedule(&self) -> [Vec<TimeValue>; 7] {
+        std::array::from_fn(|day| self.day_schedule(&self.hours[day]))
+    }
+
+    /// The Exception_Schedule: one all-day unoccupied special event per holiday.
+    pub fn exception_schedule(&self) -> Vec<SpecialEvent> {
+        self.holidays
+            .iter()
+            .map(|period| SpecialEvent {
+                period: period.clone(),
+                time_values: vec![self.at(MIDNIGHT, &self.unoccupied_value)],
+                priority: self.holiday_priority,
+            })
+            .collect()
+    }
+
+    fn day_schedule(&self, periods: &[OccupiedPeriod]) -> Vec<TimeValue> {
+        let key = |t: Time| (t.hour, t.minute, t.second, t.hundredths);
+        let mut sorted = periods.to_vec();
+        sorted.sort_by_key(|period| key(period.start));
+        let mut merged: Vec<OccupiedPeriod> = Vec::with_capacity(sorted.len());
+        for period in sorted {
+            match merged.last_mut() {
+                Some(last) if key(period.start) <= key(last.end) => {
+                    if key(period.end) > key(last.end) {
+                        last.end = period.end;
+                    }
+                }
+                _ => merged.push(period),
+            }
+        }
+
+        let mut day = Vec::with_capacity(merged.len() * 2 + 1);
+        if !matches!(merged.first(), Some(first) if first.start == MIDNIGHT) {
+            day.push(self.at(MIDNIGHT, &self.unoccupied_value));
+        }
+        for period in merged {
+            day.push(self.at(period.start, &self.occupied_value));
+            day.push(self.at(period.end, &self.unoccupied_value));
+        }
+        day
+    }
+
+    fn at(&self, time: Time, value: &ClientDataValue) -> TimeValue {
+        TimeValue {
+            time,
+            value: value.clone(),
+        }
+    }
+}
+
+/// Outcome of a
+/// [`BacnetClient::apply_occupancy_schedule`](crate::BacnetClient::apply_occupancy_schedule)
+/// run.
+#[derive(Debug, Default)]
+pub struct ScheduleApplyReport {
+    /// Schedules both written to, in write order.
+    pub applied: Vec<(DataLinkAddress, ObjectId)>,
+    /// Schedules that could not be written.
+    pub failed: Vec<ScheduleApplyFailure>,
+}
+
+impl ScheduleApplyReport {
+    /// Returns `true` when every schedule was written.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// A Schedule object the occupancy schedule could not be written to.
+///
+/// The Weekly_Schedule is written before the Exception_Schedule, so a failure on the
+/// second write leaves the new weekly hours in place.
+#[derive(Debug)]
+pub struct ScheduleApplyFailure {
+    pub address: DataLinkAddress,
+    pub schedule: ObjectId,
+    pub error: ClientError,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OccupancySchedule, Weekday};
+    use crate::schedule::{validate_exception_schedule, validate_weekly_schedule, TimeValue};
+    use crate::{CalendarEntry, ClientDataValue};
+    use rustbac_core::types::{Date, Time};
+
+    fn hm(hour: u8, minute: u8) -> Time {
+        Time {
+            hour,
+            minute,
+            second: 0,
+            hundredths: 0,
+        }
+    }
+
+    fn times(day: &[TimeValue]) -> Vec<(u8, u8, u32)> {
+        day.iter()
+            .map(|tv| match tv.value {
+                ClientDataValue::Enumerated(v) => (tv.time.hour, tv.time.minute, v),
+                _ => panic!("unexpected value {:?}", tv.value),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn weekly_schedule_merges_periods_and_starts_each_day_unoccupied() {
+        let schedule = OccupancySchedule::binary()
+            .with_workday_hours(hm(8, 0), hm(12, 0))
+            .with_hours(Weekday::Monday, hm(11, 30), hm(18, 0))
+            .with_hours(Weekday::Monday, hm(6, 0), hm(7, 0))
+            .with_hours(Weekday::Saturday, hm(0, 0), hm(4, 0));
+        let week = schedule.weekly_schedule();
+        assert_eq!(
+            times(&week[0]),
+            vec![(0, 0, 0), (6, 0, 1), (7, 0, 0), (8, 0, 1), (18, 0, 0)]
+        );
+        assert_eq!(times(&week[4]), vec![(0, 0, 0), (8, 0, 1), (12, 0, 0)]);
+        assert_eq!(times(&week[5]), vec![(0, 0, 1), (4, 0, 0)]);
+        assert_eq!(times(&week[6]), vec![(0, 0, 0)]);
+        assert!(validate_weekly_schedule(&week).is_empty());
+
+        // An inverted period is kept so validation can report it.
+        let week = OccupancySchedule::binary()
+            .with_hours(Weekday::Sunday, hm(17, 0), hm(9, 0))
+            .weekly_schedule();
+        assert_eq!(validate_weekly_schedule(&week).len(), 1);
+    }
+
+    #[test]
+    fn holidays_become_all_day_unoccupied_special_events() {
+        let christmas = CalendarEntry::Date(Date {
+            year_since_1900: 0xFF,
+            month: 12,
+            day: 25,
+            weekday: 0xFF,
+        });
+        let events = OccupancySchedule::binary()
+            .with_holiday(christmas.clone())
+            .with_holiday_priority(3)
+            .exception_schedule();
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].period,
+            crate::SpecialEventPeriod::CalendarEntry(christmas)
+        );
+        assert_eq!(events[0].priority, 3);
+        assert_eq!(times(&events[0].time_values), vec![(0, 0, 0)]);
+        assert!(validate_exception_schedule(&events).is_empty());
+    }
+}
@@ -1,9 +1,14 @@
 //! Point type inference for BACnet objects.
 //!
 //! Maps BACnet [`ObjectType`](rustbac_core::types::ObjectType) to a simplified
-//! classification useful for building automation integrations.
+//! classification useful for building automation integrations, and labels binary and
+//! multistate values with their state texts ([`StateTextCache`]).
 
-use rustbac_core::types::ObjectType;
+use crate::{BacnetClient, ClientDataValue, ClientError};
+use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+use rustbac_datalink::{DataLink, DataLinkAddress};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
 /// The data kind of a BACnet point.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -99,6 +104,191 @@ pub fn classify_point(object_type: ObjectType) -> PointClassification {
     }
 }
 
+/// A binary or multistate present value with its state text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateValue {
+    /// The raw value: 0 (inactive) or 1 (active) for binary objects, the 1-based state
+    /// number for multistate objects.
+    pub value: u32,
+    /// Inactive_Text/Active_Text or the State_Text entry for `value`, when the device
+    /// has one.
+    pub label: Option<String>,
+}
+
+impl std::fmt::Display for StateValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.label {
+            Some(label) => f.write_str(label),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+/// State texts of one object: `[Inactive_Text, Active_Text]` for binary objects,
+/// State_Text for multistate objects.
+type StateTexts = Arc<[Option<String>]>;
+
+/// Per-object cache of state texts, for labelling binary and multistate values.
+///
+/// State texts rarely change, so they are read once per object (State_Text for
+/// multistate objects, Inactive_Text and Active_Text for binary objects) and reused for
+/// every later [`read_state`](Self::read_state) or [`label`](Self::label). A device
+/// that answers with an error (no such property) is cached as having no texts; a
+/// timeout is not cached. Clones share the cache.
+#[derive(Debug, Clone, Default)]
+pub struct StateTextCache {
+    texts: Arc<RwLock<HashMap<(DataLinkAddress, ObjectId), StateTexts>>>,
+}
+
+impl StateTextCache {
+    /// An empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the Present_Value of a binary or multistate object and label it, fetching
+    /// the object's state texts on first use.
+    ///
+    /// Fails with [`ClientError::UnsupportedResponse`] if the object is not binary or
+    /// multistate, or its Present_Value is not an enumeration or unsigned.
+    pub async fn read_state<D: DataLink>(
+        &self,
+        client: &BacnetClient<D>,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+    ) -> Result<StateValue, ClientError> {
+        let present_value = client
+            .read_property(address, object_id, PropertyId::PresentValue)
+            .await?;
+        let value = state_number(&present_value).ok_or(ClientError::UnsupportedResponse)?;
+        let texts = self.state_texts(client, address, object_id).await?;
+        Ok(StateValue {
+            value,
+            label: label_for(object_id.object_type(), &texts, value),
+        })
+    }
+
+    /// The state texts of `object_id`, read from the device unless cached.
+    pub async fn state_texts<D: DataLink>(
+        &self,
+        client: &BacnetClient<D>,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+    ) -> Result<Arc<[Option<String>]>, ClientError> {
+        if let Some(texts) = self.cached(address, object_id) {
+            return Ok(texts);
+        }
+        let properties: &[PropertyId] = match classify_point(object_id.object_type()).kind {
+            PointKind::Binary => &[PropertyId::InactiveText, PropertyId::ActiveText],
+            PointKind::MultiState => &[PropertyId::StateText],
+            _ => return Err(ClientError::UnsupportedResponse),
+        };
+        let mut texts = Vec::new();
+        for &property in properties {
+            let value = match client.read_property(address, object_id, property).await {
+                Ok(value) => Some(value),
+                Err(err) if answered_with_error(&err) => None,
+                Err(err) => return Err(err),
+            };
+            match (property, value) {
+                (PropertyId::StateText, Some(ClientDataValue::Constructed { values, .. })) => {
+                    texts.extend(values.iter().map(character_string));
+                }
+                (_, value) => texts.push(value.as_ref().and_then(character_string)),
+            }
+        }
+        Ok(self.insert(address, object_id, texts))
+    }
+
+    /// Cache `texts` for `object_id`, e.g. from a walk, replacing any cached ones.
+    pub fn insert(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        texts: Vec<Option<String>>,
+    ) -> Arc<[Option<String>]> {
+        let texts: StateTexts = texts.into();
+        self.texts
+            .write()
+            .expect("state text cache lock poisoned")
+            .insert((address, object_id), texts.clone());
+        texts
+    }
+
+    /// Label `value` (e.g. from a COV notification) with cached state texts, without
+    /// reading from the device. `None` if the texts of `object_id` are not cached or
+    /// `value` is not a state number.
+    pub fn label(
+        &self,
+        address: DataLinkAddress,
+        object_id: ObjectId,
+        value: &ClientDataValue,
+    ) -> Option<StateValue> {
+        let texts = self.cached(address, object_id)?;
+        let value = state_number(value)?;
+        Some(StateValue {
+            value,
+            label: label_for(object_id.object_type(), &texts, value),
+        })
+    }
+
+    /// Forget the texts of one object, e.g. after it was reconfigured.
+    pub fn invalidate(&self, address: DataLinkAddress, object_id: ObjectId) {
+        self.texts
+            .write()
+            .expect("state text cache lock poisoned")
+            .remove(&(address, object_id));
+    }
+
+    /// Forget every cached text.
+    pub fn clear(&self) {
+        self.texts
+            .write()
+            .expect("state text cache lock poisoned")
+            .clear();
+    }
+
+    fn cached(&self, address: DataLinkAddress, object_id: ObjectId) -> Option<StateTexts> {
+        self.texts
+            .read()
+            .expect("state text cache lock poisoned")
+            .get(&(address, object_id))
+            .cloned()
+    }
+}
+
+fn state_number(value: &ClientDataValue) -> Option<u32> {
+    match value {
+        ClientDataValue::Enumerated(v) | ClientDataValue::Unsigned(v) => Some(*v),
+        _ => None,
+    }
+}
+
+fn character_string(value: &ClientDataValue) -> Option<String> {
+    match value {
+        ClientDataValue::CharacterString(s) => Some(s.to_string()),
+        _ => None,
+    }
+}
+
+/// Whether the device answered, so that the missing text is worth caching.
+fn answered_with_error(err: &ClientError) -> bool {
+    matches!(
+        err.root(),
+        ClientError::RemoteServiceError { .. } | ClientError::RemoteReject { .. }
+    )
+}
+
+fn label_for(object_type: ObjectType, texts: &[Option<String>], value: u32) -> Option<String> {
+    let index = match classify_point(object_type).kind {
+        PointKind::Binary => value,
+        // State numbers start at 1.
+        _ => value.checked_sub(1)?,
+    };
+    texts.get(index as usize).cloned().flatten()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,4 +351,53 @@ mod tests {
         assert_eq!(c.direction, PointDirection::Unknown);
         assert!(!c.writable);
     }
+
+    #[test]
+    fn cached_state_texts_label_binary_and_multistate_values() {
+        let cache = StateTextCache::new();
+        let address = DataLinkAddress::Ip(([192, 168, 1, 20], 47808).into());
+        let msv = ObjectId::new(ObjectType::MultiStateValue, 1);
+        let bo = ObjectId::new(ObjectType::BinaryOutput, 1);
+        assert_eq!(
+            cache.label(address, msv, &ClientDataValue::Unsigned(2)),
+            None
+        );
+
+        cache.insert(
+            address,
+            msv,
+            vec![Some("Off".into()), Some("Low".into()), Some("High".into())],
+        );
+        cache.insert(address, bo, vec![None, Some("Running".into())]);
+
+        let state = cache
+            .label(address, msv, &ClientDataValue::Unsigned(2))
+            .unwrap();
+        assert_eq!(state.label.as_deref(), Some("Low"));
+        assert_eq!(state.to_string(), "Low");
+        // State 0 and states past the end have no text.
+        for value in [0, 4] {
+            let state = cache
+                .label(address, msv, &ClientDataValue::Unsigned(value))
+                .unwrap();
+            assert_eq!(state.label, None);
+            assert_eq!(state.to_string(), value.to_string());
+        }
+
+        let active = cache
+            .label(address, bo, &ClientDataValue::Enumerated(1))
+            .unwrap();
+        assert_eq!(active.to_string(), "Running");
+        let inactive = cache
+            .label(address, bo, &ClientDataValue::Enumerated(0))
+            .unwrap();
+        assert_eq!(inactive.to_string(), "0");
+        assert_eq!(cache.label(address, bo, &ClientDataValue::Real(1.0)), None);
+
+        cache.invalidate(address, msv);
+        assert_eq!(
+            cache.label(address, msv, &ClientDataValue::Unsigned(2)),
+            None
+        );
+    }
 }
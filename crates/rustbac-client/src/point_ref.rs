@@ -0,0 +1,286 @@
+use core::fmt;
+use core::str::FromStr;
+use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+use rustbac_datalink::DataLinkAddress;
+use std::net::{IpAddr, SocketAddr};
+use thiserror::Error;
+
+/// A compact, human-writable reference to one property on one device.
+///
+/// The textual form is `address/object-type,instance/property[index]@priority`, where the
+/// array index and priority are optional:
+///
+/// ```text
+/// 192.168.1.20:47808/analog-input,3/present-value
+/// 192.168.1.20/analog-output,1/present-value@8
+/// [fe80::1]:47808/device,1234/object-list[0]
+/// mstp:12/8,1234/77
+/// ```
+///
+/// The address is an IPv4/IPv6 socket address (the port defaults to 47808 when omitted) or
+/// `mstp:<mac>`. Object types and properties accept the hyphenated names used by
+/// [`ObjectType::from_name`] and [`PropertyId::from_name`], `proprietary-<n>`, or a raw
+/// number. [`Display`](fmt::Display) produces the canonical form, which parses back to
+/// the same value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PointReference {
+    pub address: DataLinkAddress,
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub priority: Option<u8>,
+}
+
+/// Errors returned when parsing a [`PointReference`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PointReferenceParseError {
+    /// The string is not of the form `address/object/property`.
+    #[error("expected address/object-type,instance/property")]
+    Format,
+    /// The address part is not a socket address, IP address, or `mstp:<mac>`.
+    #[error("invalid address {0:?}")]
+    Address(String),
+    /// The object type is neither a known name nor a number.
+    #[error("unknown object type {0:?}")]
+    ObjectType(String),
+    /// The object instance is missing, not a number, or above 4194303.
+    #[error("invalid object instance {0:?}")]
+    Instance(String),
+    /// The property is neither a known name nor a number.
+    #[error("unknown property {0:?}")]
+    Property(String),
+    /// The `[index]` suffix is malformed.
+    #[error("invalid array index {0:?}")]
+    ArrayIndex(String),
+    /// The `@priority` suffix is not in `1..=16`.
+    #[error("invalid priority {0:?}")]
+    Priority(String),
+}
+
+impl FromStr for PointReference {
+    type Err = PointReferenceParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().splitn(3, '/');
+        let (Some(address), Some(object), Some(property)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(PointReferenceParseError::Format);
+        };
+
+        let address = parse_address(address)?;
+        let object_id = parse_object_id(object)?;
+
+        let (property, priority) = match property.rsplit_once('@') {
+            Some((property, priority)) => (property, Some(parse_priority(priority)?)),
+            None => (property, None),
+        };
+        let (property, array_index) = match property.strip_suffix(']') {
+            Some(rest) => {
+                let (property, index) = rest
+                    .split_once('[')
+                    .ok_or_else(|| PointReferenceParseError::ArrayIndex(property.to_string()))?;
+                let index = index
+                    .parse::<u32>()
+                    .map_err(|_| PointReferenceParseError::ArrayIndex(index.to_string()))?;
+                (property, Some(index))
+            }
+            None => (property, None),
+        };
+        let property_id = parse_property_id(property)?;
+
+        Ok(Self {
+            address,
+            object_id,
+            property_id,
+            array_index,
+            priority,
+        })
+    }
+}
+
+impl fmt::Display for PointReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{},{}/{}",
+            self.address,
+            self.object_id.object_type(),
+            self.object_id.instance(),
+            self.property_id
+        )?;
+        if let Some(index) = self.array_index {
+            write!(f, "[{index}]")?;
+        }
+        if let Some(priority) = self.priority {
+            write!(f, "@{priority}")?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_address(s: &str) -> Result<DataLinkAddress, PointReferenceParseError> {
+    let err = || PointReferenceParseError::Address(s.to_string());
+    if let Some(mac) = s.strip_prefix("mstp:") {
+        return mac.parse().map(DataLinkAddress::Mstp).map_err(|_| err());
+    }
+    if let Ok(addr) = s.parse::<SocketAddr>() {
+        return Ok(DataLinkAddress::Ip(addr));
+    }
+    let ip = s
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .unwrap_or(s);
+    ip.parse::<IpAddr>()
+        .map(DataLinkAddress::bacnet_default)
+        .map_err(|_| err())
+}
+
+fn parse_object_id(s: &str) -> Result<ObjectId, PointReferenceParseError> {
+    let (object_type, instance) = s
+        .split_once(',')
+        .ok_or_else(|| PointReferenceParseError::Instance(s.to_string()))?;
+    let object_type = parse_object_type(object_type)?;
+    let instance = instance
+        .parse::<u32>()
+        .ok()
+        .filter(|v| *v <= 0x3F_FFFF)
+        .ok_or_else(|| PointReferenceParseError::Instance(instance.to_string()))?;
+    Ok(ObjectId::new(object_type, instance))
+}
+
+/// Parses the object type part of a point reference: a hyphenated name such as
+/// `analog-input` (underscores accepted), `proprietary-<n>` or a number.
+pub fn parse_object_type(s: &str) -> Result<ObjectType, PointReferenceParseError> {
+    parse_named(s, ObjectType::from_name, |n| {
+        u16::try_from(n).ok().map(ObjectType::from_u16)
+    })
+    .ok_or_else(|| PointReferenceParseError::ObjectType(s.to_string()))
+}
+
+/// Parses the property part of a point reference: a hyphenated name such as
+/// `present-value` (underscores accepted), `proprietary-<n>` or a number.
+pub fn parse_property_id(s: &str) -> Result<PropertyId, PointReferenceParseError> {
+    parse_named(s, PropertyId::from_name, |n| Some(PropertyId::from_u32(n)))
+        .ok_or_else(|| PointReferenceParseError::Property(s.to_string()))
+}
+
+fn parse_priority(s: &str) -> Result<u8, PointReferenceParseError> {
+    s.parse::<u8>()
+        .ok()
+        .filter(|p| (1..=16).contains(p))
+        .ok_or_else(|| PointReferenceParseError::Priority(s.to_string()))
+}
+
+/// Resolves a hyphenated name, `proprietary-<n>`, or bare number.
+fn parse_named<T>(
+    s: &str,
+    from_name: impl Fn(&str) -> Option<T>,
+    from_number: impl Fn(u32) -> Option<T>,
+) -> Option<T> {
+    let s = s.trim();
+    if let Some(value) = from_name(&s.to_ascii_lowercase().replace('_', "-")) {
+        return Some(value);
+    }
+    let number = s.strip_prefix("proprietary-").unwrap_or(s);
+    number.parse::<u32>().ok().and_then(from_number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_object_type, parse_property_id, PointReference, PointReferenceParseError};
+    use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+    use rustbac_datalink::DataLinkAddress;
+
+    #[test]
+    fn parses_full_reference() {
+        let point: PointReference = "192.168.1.20:47808/analog-input,3/present-value[2]@8"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            point.address,
+            DataLinkAddress::Ip(([192, 168, 1, 20], 47808).into())
+        );
+        assert_eq!(point.object_id, ObjectId::new(ObjectType::AnalogInput, 3));
+        assert_eq!(point.property_id, PropertyId::PresentValue);
+        assert_eq!(point.array_index, Some(2));
+        assert_eq!(point.priority, Some(8));
+    }
+
+    #[test]
+    fn parses_defaults_and_numeric_forms() {
+        let point: PointReference = "10.0.0.5/8,1234/77".parse().unwrap();
+        assert_eq!(
+            point.address,
+            DataLinkAddress::Ip(([10, 0, 0, 5], 47808).into())
+        );
+        assert_eq!(point.object_id, ObjectId::new(ObjectType::Device, 1234));
+        assert_eq!(point.property_id, PropertyId::ObjectName);
+        assert_eq!(point.array_index, None);
+        assert_eq!(point.priority, None);
+
+        let point: PointReference = "mstp:12/proprietary-600,1/proprietary-5000"
+            .parse()
+            .unwrap();
+        assert_eq!(point.address, DataLinkAddress::Mstp(12));
+        assert_eq!(point.object_id.object_type(), ObjectType::Proprietary(600));
+        assert_eq!(point.property_id, PropertyId::Proprietary(5000));
+    }
+
+    #[test]
+    fn parts_parse_on_their_own() {
+        assert_eq!(
+            parse_object_type("Analog_Output"),
+            Ok(ObjectType::AnalogOutput)
+        );
+        assert_eq!(parse_object_type("5"), Ok(ObjectType::BinaryValue));
+        assert_eq!(
+            parse_property_id("out_of_service"),
+            Ok(PropertyId::OutOfService)
+        );
+        assert!(matches!(
+            parse_property_id("no-such-property"),
+            Err(PointReferenceParseError::Property(_))
+        ));
+    }
+
+    #[test]
+    fn display_roundtrips() {
+        for text in [
+            "192.168.1.20:47808/analog-output,1/present-value@16",
+            "[fe80::1]:47808/device,99/object-list[0]",
+            "mstp:3/binary-value,7/status-flags",
+        ] {
+            let point: PointReference = text.parse().unwrap();
+            assert_eq!(point.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_references() {
+        assert_eq!(
+            "192.168.1.20/analog-input,3".parse::<PointReference>(),
+            Err(PointReferenceParseError::Format)
+        );
+        assert!(matches!(
+            "nowhere/analog-input,3/present-value".parse::<PointReference>(),
+            Err(PointReferenceParseError::Address(_))
+        ));
+        assert!(matches!(
+            "10.0.0.1/analog-thing,3/present-value".parse::<PointReference>(),
+            Err(PointReferenceParseError::ObjectType(_))
+        ));
+        assert!(matches!(
+            "10.0.0.1/analog-input,4194304/present-value".parse::<PointReference>(),
+            Err(PointReferenceParseError::Instance(_))
+        ));
+        assert!(matches!(
+            "10.0.0.1/analog-input,3/present-value[x]".parse::<PointReference>(),
+            Err(PointReferenceParseError::ArrayIndex(_))
+        ));
+        assert!(matches!(
+            "10.0.0.1/analog-input,3/present-value@17".parse::<PointReference>(),
+            Err(PointReferenceParseError::Priority(_))
+        ));
+    }
+}
@@ -0,0 +1,185 @@
+//! Subscriber process ids for COV subscriptions.
+//!
+//! A device tells a client's subscriptions apart only by the subscriber process id and
+//! the monitored object and property. Two [`CovSubscriptionSpec`]s sharing all of them to
+//! the same device are one subscription on the device, and the notifications for it
+//! cannot be attributed to either spec. [`SubscriberProcessIdAllocator`] hands out ids no
+//! other subscription of the client uses, and [`find_duplicate_subscriptions`] reports
+//! specs that collide.
+
+use crate::CovSubscriptionSpec;
+use rustbac_core::types::{ObjectId, PropertyId};
+use rustbac_datalink::DataLinkAddress;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Mutex, MutexGuard};
+
+/// Hands out subscriber process ids that are not in use, from one counter shared by
+/// everything that subscribes through a client.
+///
+/// Ids count up from the first id, wrap back to 1 after `u32::MAX`, and skip ids still
+/// in use. Methods take `&self`, so one allocator can be shared behind an `Arc`.
+#[derive(Debug)]
+pub struct SubscriberProcessIdAllocator {
+    state: Mutex<AllocatorState>,
+}
+
+#[derive(Debug)]
+struct AllocatorState {
+    next: u32,
+    in_use: BTreeSet<u32>,
+}
+
+impl Default for SubscriberProcessIdAllocator {
+    fn default() -> Self {
+        Self::starting_at(1)
+    }
+}
+
+impl SubscriberProcessIdAllocator {
+    /// An allocator handing out ids from 1.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An allocator handing out ids from `first`, e.g. to keep clear of ids configured
+    /// by hand.
+    pub fn starting_at(first: u32) -> Self {
+        Self {
+            state: Mutex::new(AllocatorState {
+                next: first,
+                in_use: BTreeSet::new(),
+            }),
+        }
+    }
+
+    /// The next id not in use, now marked in use, or `None` if every id is taken.
+    pub fn allocate(&self) -> Option<u32> {
+        let mut state = self.state();
+        if state.in_use.len() as u64 >= u64::from(u32::MAX) {
+            return None;
+        }
+        let mut id = state.next;
+        while id == 0 || state.in_use.contains(&id) {
+            id = id.wrapping_add(1);
+        }
+        state.in_use.insert(id);
+        state.next = id.wrapping_add(1);
+        Some(id)
+    }
+
+    /// Marks `id`, e.g. one configured by hand, in use. Returns `false` if it already
+    /// was.
+    pub fn reserve(&self, id: u32) -> bool {
+        self.state().in_use.insert(id)
+    }
+
+    /// Marks `id` free again, once its subscription is cancelled.
+    pub fn release(&self, id: u32) {
+        self.state().in_use.remove(&id);
+    }
+
+    pub fn is_in_use(&self, id: u32) -> bool {
+        self.state().in_use.contains(&id)
+    }
+
+    fn state(&self) -> MutexGuard<'_, AllocatorState> {
+        self.state.lock().expect("allocator lock poisoned")
+    }
+}
+
+/// Two [`CovSubscriptionSpec`]s the device cannot tell apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DuplicateSubscription {
+    /// Index of the earlier spec.
+    pub first: usize,
+    /// Index of the later spec.
+    pub second: usize,
+    pub address: DataLinkAddress,
+    pub object_id: ObjectId,
+    pub property_id: Option<PropertyId>,
+    pub subscriber_process_id: u32,
+}
+
+/// The specs in `specs` sharing device, object, property and process id with an earlier
+/// one, in order.
+pub fn find_duplicate_subscriptions(specs: &[CovSubscriptionSpec]) -> Vec<DuplicateSubscription> {
+    let mut seen = HashMap::new();
+    let mut duplicates = Vec::new();
+    for (index, spec) in specs.iter().enumerate() {
+        match seen.get(&subscription_key(spec)) {
+            Some(&first) => duplicates.push(DuplicateSubscription {
+                first,
+                second: index,
+                address: spec.address,
+                object_id: spec.object_id,
+                property_id: spec.property_id,
+                subscriber_process_id: spec.subscriber_process_id,
+            }),
+            None => {
+                seen.insert(subscription_key(spec), index);
+            }
+        }
+    }
+    duplicates
+}
+
+/// What a device tells a client's subscriptions apart by.
+pub(crate) fn subscription_key(
+    spec: &CovSubscriptionSpec,
+) -> (DataLinkAddress, ObjectId, Option<PropertyId>, u32) {
+    (
+        spec.address,
+        spec.object_id,
+        spec.property_id,
+        spec.subscriber_process_id,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_duplicate_subscriptions, SubscriberProcessIdAllocator};
+    use crate::{CovMode, CovSubscriptionSpec};
+    use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+    use rustbac_datalink::DataLinkAddress;
+
+    #[test]
+    fn allocator_skips_reserved_ids_and_wraps_past_zero() {
+        let allocator = SubscriberProcessIdAllocator::new();
+        assert!(allocator.reserve(2));
+        assert!(!allocator.reserve(2));
+        assert_eq!(allocator.allocate(), Some(1));
+        assert_eq!(allocator.allocate(), Some(3));
+        allocator.release(2);
+        assert!(!allocator.is_in_use(2));
+
+        let allocator = SubscriberProcessIdAllocator::starting_at(u32::MAX);
+        allocator.reserve(1);
+        assert_eq!(allocator.allocate(), Some(u32::MAX));
+        assert_eq!(allocator.allocate(), Some(2));
+    }
+
+    #[test]
+    fn duplicates_share_device_object_property_and_process_id() {
+        let spec = |address, property_id, subscriber_process_id| CovSubscriptionSpec {
+            address,
+            object_id: ObjectId::new(ObjectType::AnalogInput, 1),
+            property_id,
+            lifetime_seconds: 300,
+            cov_increment: None,
+            confirmed: false,
+            subscriber_process_id,
+            mode: CovMode::Subscribe,
+        };
+        let a = DataLinkAddress::Mstp(1);
+        let specs = [
+            spec(a, None, 1),
+            spec(a, Some(PropertyId::StatusFlags), 1),
+            spec(a, None, 2),
+            spec(DataLinkAddress::Mstp(2), None, 1),
+            spec(a, None, 1),
+        ];
+        let duplicates = find_duplicate_subscriptions(&specs);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!((duplicates[0].first, duplicates[0].second), (0, 4));
+    }
+}
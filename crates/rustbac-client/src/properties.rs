@@ -0,0 +1,35 @@
+use crate::ClientDataValue;
+use rustbac_core::services::read_property_multiple::PropertyAccessError;
+use rustbac_core::types::{ObjectId, PropertyId};
+
+/// The properties of one object, as returned for a ReadPropertyMultiple of ALL, REQUIRED
+/// or OPTIONAL by [`BacnetClient::read_property_set`](crate::BacnetClient::read_property_set).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectProperties {
+    pub object_id: ObjectId,
+    /// Values in the order the device returned them.
+    pub values: Vec<(PropertyId, ClientDataValue)>,
+    /// Properties the device listed but could not read.
+    pub errors: Vec<PropertyAccessError>,
+}
+
+impl ObjectProperties {
+    /// The value of `property_id`, if the device returned one.
+    pub fn get(&self, property_id: PropertyId) -> Option<&ClientDataValue> {
+        self.values
+            .iter()
+            .find(|(id, _)| *id == property_id)
+            .map(|(_, value)| value)
+    }
+
+    /// Every property the device returned, read or not, in response order.
+    pub fn property_ids(&self) -> Vec<PropertyId> {
+        let mut ids: Vec<PropertyId> = self.values.iter().map(|(id, _)| *id).collect();
+        for error in &self.errors {
+            if !ids.contains(&error.property_id) {
+                ids.push(error.property_id);
+            }
+        }
+        ids
+    }
+}
@@ -1,12 +1,28 @@
+use crate::export::{bacnet_date_time, bacnet_system_time};
+use crate::log_buffer::LogBufferRecord;
 use crate::ClientDataValue;
-use rustbac_core::types::{ObjectId, PropertyId};
+use rustbac_core::types::{Date, ObjectId, PropertyId, Time};
+use std::time::{Duration, SystemTime};
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClientBitString {
     pub unused_bits: u8,
     pub data: Vec<u8>,
 }
 
+impl ClientBitString {
+    /// Bit `index`, counted from the most significant bit of the first octet as BACnet
+    /// numbers them, or `None` past the end of the string.
+    pub fn bit(&self, index: usize) -> Option<bool> {
+        let len = (self.data.len() * 8).saturating_sub(usize::from(self.unused_bits));
+        if index >= len {
+            return None;
+        }
+        Some(self.data[index / 8] & (0x80 >> (index % 8)) != 0)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ReadRangeResult {
     pub object_id: ObjectId,
@@ -15,4 +31,72 @@ pub struct ReadRangeResult {
     pub result_flags: ClientBitString,
     pub item_count: u32,
     pub items: Vec<ClientDataValue>,
+    /// The items that are `Log_Buffer` records, typed by the kind of log read.
+    pub records: Vec<LogBufferRecord>,
+}
+
+/// A ReadRange item with its timestamp converted to UTC.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedRecord {
+    /// When the record was logged, or `None` if it carries no fully specified date and
+    /// time.
+    pub timestamp: Option<SystemTime>,
+    /// The record as returned by the device.
+    pub item: ClientDataValue,
+}
+
+/// The result of [`BacnetClient::read_range_since`](crate::BacnetClient::read_range_since).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedReadRangeResult {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub result_flags: ClientBitString,
+    pub item_count: u32,
+    /// Minutes the device's local time was behind UTC, daylight saving included; the
+    /// BACnet `UTC_Offset` sign convention.
+    pub utc_offset_minutes: i32,
+    pub records: Vec<TimedRecord>,
+}
+
+/// The device-local date and time of the UTC instant `at`.
+pub(crate) fn local_date_time(at: SystemTime, utc_offset_minutes: i32) -> (Date, Time) {
+    bacnet_date_time(shift_minutes(at, -i64::from(utc_offset_minutes)))
+}
+
+/// The UTC instant of the first date and time found in a device-local record.
+pub(crate) fn record_utc_time(
+    item: &ClientDataValue,
+    utc_offset_minutes: i32,
+) -> Option<SystemTime> {
+    let date = find_leaf(item, &|v| match v {
+        ClientDataValue::Date(d) => Some(*d),
+        _ => None,
+    })?;
+    let time = find_leaf(item, &|v| match v {
+        ClientDataValue::Time(t) => Some(*t),
+        _ => None,
+    })?;
+    let local = bacnet_system_time(date, time)?;
+    Some(shift_minutes(local, i64::from(utc_offset_minutes)))
+}
+
+fn find_leaf<T>(
+    value: &ClientDataValue,
+    pick: &impl Fn(&ClientDataValue) -> Option<T>,
+) -> Option<T> {
+    match value {
+        ClientDataValue::Constructed { values, .. } => {
+            values.iter().find_map(|child| find_leaf(child, pick))
+        }
+        leaf => pick(leaf),
+    }
+}
+
+pub(crate) fn shift_minutes(at: SystemTime, minutes: i64) -> SystemTime {
+    let delta = Duration::from_secs(minutes.unsigned_abs() * 60);
+    if minutes >= 0 {
+        at + delta
+    } else {
+        at - delta
+    }
 }
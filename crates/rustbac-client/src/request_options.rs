@@ -0,0 +1,111 @@
+use rustbac_core::npdu::NetworkPriority;
+use std::time::Duration;
+
+/// Segmentation overrides for confirmed requests, set per call in [`RequestOptions`] or
+/// per device with
+/// [`BacnetClient::set_device_segmentation`](crate::BacnetClient::set_device_segmentation).
+///
+/// The default keeps each service's segmented-response-accepted flag and the client's
+/// segmented-request window size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SegmentationOptions {
+    /// Overrides the segmented-response-accepted flag of requests. `Some(false)` makes a
+    /// device with broken segmentation answer unsegmented, or abort if it cannot.
+    pub segmented_response_accepted: Option<bool>,
+    /// Window size proposed in segmented requests; `None` keeps the client's.
+    pub window_size: Option<u8>,
+}
+
+impl SegmentationOptions {
+    /// Asks for unsegmented responses.
+    pub fn unsegmented() -> Self {
+        Self {
+            segmented_response_accepted: Some(false),
+            window_size: None,
+        }
+    }
+
+    /// Sets whether requests accept segmented responses.
+    pub fn with_segmented_response_accepted(mut self, accepted: bool) -> Self {
+        self.segmented_response_accepted = Some(accepted);
+        self
+    }
+
+    /// Sets the window size proposed in segmented requests, clamped to a minimum of 1.
+    pub fn with_window_size(mut self, window_size: u8) -> Self {
+        self.window_size = Some(window_size.max(1));
+        self
+    }
+
+    /// `self`, with unset fields taken from `fallback`.
+    pub(crate) fn or(self, fallback: Self) -> Self {
+        Self {
+            segmented_response_accepted: self
+                .segmented_response_accepted
+                .or(fallback.segmented_response_accepted),
+            window_size: self.window_size.or(fallback.window_size),
+        }
+    }
+}
+
+/// Per-call overrides for confirmed requests, applied with
+/// [`BacnetClient::with_options`](crate::BacnetClient::with_options).
+///
+/// The default keeps the client's response timeout, sends each request once, uses
+/// normal network priority, keeps the per-device segmentation settings and does not
+/// confirm writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RequestOptions {
+    /// Time to wait for each attempt's response; `None` keeps the client's timeout.
+    pub timeout: Option<Duration>,
+    /// Time to wait once the device postponed its reply; `None` keeps the client's.
+    pub reply_postponed_timeout: Option<Duration>,
+    /// Number of times a request is resent after a timeout before giving up.
+    pub retries: u8,
+    /// Network priority set in the NPDU of each request.
+    pub priority: NetworkPriority,
+    /// Segmentation overrides; set fields win over the per-device settings.
+    pub segmentation: SegmentationOptions,
+    /// Sends writes the client's [`WritePolicy`](crate::WritePolicy) holds for
+    /// confirmation.
+    pub confirm_writes: bool,
+}
+
+impl RequestOptions {
+    /// Sets the per-attempt response timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the wait for a reply the device postponed.
+    pub fn with_reply_postponed_timeout(mut self, timeout: Duration) -> Self {
+        self.reply_postponed_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the number of resends after a timeout.
+    pub fn with_retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the NPDU network priority.
+    pub fn with_priority(mut self, priority: NetworkPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the segmentation overrides.
+    pub fn with_segmentation(mut self, segmentation: SegmentationOptions) -> Self {
+        self.segmentation = segmentation;
+        self
+    }
+
+    /// Confirms the writes the client's [`WritePolicy`](crate::WritePolicy) holds for
+    /// confirmation; denied writes stay denied.
+    pub fn with_confirmed_writes(mut self) -> Self {
+        self.confirm_writes = true;
+        self
+    }
+}
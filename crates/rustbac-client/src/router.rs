@@ -0,0 +1,812 @@
+//! One reader for a client's link, handing every frame to whoever waits for its kind.
+//!
+//! A [`BacnetClient`](crate::BacnetClient) and everything built on it (its clones, a
+//! [`CovManager`](crate::CovManager), a notification listener on a
+//! [`RoutedLink`], a Who-Is running beside a request) receive through one
+//! [`InboundRouter`]. Only one of them reads the link at a time. A frame it reads that
+//! another is waiting for, by [`InboundKind`], is queued for that one instead of being
+//! dropped, so concurrent receivers no longer steal each other's responses and
+//! notifications. Answers to confirmed requests are matched further, by source address
+//! and invoke id, to the request they answer, so several requests can be outstanding at
+//! once. Frames nobody waits for go to the reader, which ignores them as before. The
+//! reader also sends the Aborts of confirmed requests whose callers gave up on them.
+
+use crate::runtime::{self, Runtime};
+use crate::ClientError;
+use rustbac_core::apdu::{ApduType, ConfirmedRequestHeader, UnconfirmedRequestHeader};
+use rustbac_core::encoding::reader::Reader;
+use rustbac_core::npdu::Npdu;
+use rustbac_core::services::cov_notification::{
+    SERVICE_CONFIRMED_COV_NOTIFICATION, SERVICE_UNCONFIRMED_COV_NOTIFICATION,
+};
+use rustbac_core::services::event_notification::{
+    SERVICE_CONFIRMED_EVENT_NOTIFICATION, SERVICE_UNCONFIRMED_EVENT_NOTIFICATION,
+};
+use rustbac_core::services::i_am::SERVICE_I_AM;
+use rustbac_core::services::who_has::SERVICE_I_HAVE;
+use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError};
+use std::collections::{HashMap, VecDeque};
+use std::future::{poll_fn, Future};
+use std::pin::pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::Poll;
+use std::time::Instant;
+
+/// Frames of each kind kept for a waiting receiver before the oldest is dropped.
+pub const DEFAULT_INBOUND_QUEUE_CAPACITY: usize = 64;
+
+/// The confirmed request an answer is for: the address it was sent to and its invoke id.
+pub(crate) type TransactionKey = (DataLinkAddress, u8);
+
+/// Who a received frame is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InboundKind {
+    /// SimpleACK, ComplexACK, SegmentACK, Error, Reject or Abort: the answer to a
+    /// confirmed request.
+    Transaction,
+    /// A confirmed or unconfirmed COV notification.
+    CovNotification,
+    /// A confirmed or unconfirmed event notification.
+    EventNotification,
+    /// An I-Am or I-Have.
+    Discovery,
+    /// Any other request, for the inline server handler.
+    Request,
+    /// A network-layer message or a frame that does not decode.
+    Other,
+}
+
+impl InboundKind {
+    pub const ALL: [InboundKind; 6] = [
+        InboundKind::Transaction,
+        InboundKind::CovNotification,
+        InboundKind::EventNotification,
+        InboundKind::Discovery,
+        InboundKind::Request,
+        InboundKind::Other,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// The [`InboundKind`] of the NPDU in `frame`.
+pub fn classify_frame(frame: &[u8]) -> InboundKind {
+    let mut r = Reader::new(frame);
+    let Ok(npdu) = Npdu::decode(&mut r) else {
+        return InboundKind::Other;
+    };
+    if npdu.is_network_message() {
+        return InboundKind::Other;
+    }
+    let Ok(apdu) = r.read_exact(r.remaining()) else {
+        return InboundKind::Other;
+    };
+    let Some(&first) = apdu.first() else {
+        return InboundKind::Other;
+    };
+    match ApduType::from_u8(first >> 4) {
+        Some(ApduType::ConfirmedRequest) => {
+            match ConfirmedRequestHeader::decode(&mut Reader::new(apdu)) {
+                Ok(header) => match header.service_choice {
+                    SERVICE_CONFIRMED_COV_NOTIFICATION => InboundKind::CovNotification,
+                    SERVICE_CONFIRMED_EVENT_NOTIFICATION => InboundKind::EventNotification,
+                    _ => InboundKind::Request,
+                },
+                Err(_) => InboundKind::Other,
+            }
+        }
+        Some(ApduType::UnconfirmedRequest) => {
+            match UnconfirmedRequestHeader::decode(&mut Reader::new(apdu)) {
+                Ok(header) => match header.service_choice {
+                    SERVICE_UNCONFIRMED_COV_NOTIFICATION => InboundKind::CovNotification,
+                    SERVICE_UNCONFIRMED_EVENT_NOTIFICATION => InboundKind::EventNotification,
+                    SERVICE_I_AM | SERVICE_I_HAVE => InboundKind::Discovery,
+                    _ => InboundKind::Request,
+                },
+                Err(_) => InboundKind::Other,
+            }
+        }
+        Some(_) => InboundKind::Transaction,
+        None => InboundKind::Other,
+    }
+}
+
+/// Hands the frames of a client's link to the receivers waiting for them.
+///
+/// A kind is *wanted* while a receive for it is running, or while a [`RoutedLink`] or
+/// [`subscribe`](Self::subscribe) keeps it wanted. Frames of a wanted kind read by a
+/// receiver of another kind are queued, up to the capacity per kind; frames of a kind
+/// nobody wants are returned to the receiver that read them. Each kind should have one
+/// consumer: two receivers of the same kind get its frames first come, first served.
+///
+/// Answers to the client's confirmed requests are queued per request instead, up to the
+/// same capacity, for as long as the request is in flight, whoever reads them.
+#[derive(Debug)]
+pub struct InboundRouter {
+    /// Held by the receiver reading the link.
+    reader: tokio::sync::Mutex<()>,
+    state: Mutex<RouterState>,
+    capacity: AtomicUsize,
+    /// Wakes the receiver reading the link to send a newly queued Abort.
+    abort_queued: tokio::sync::Notify,
+}
+
+#[derive(Debug, Default)]
+struct RouterState {
+    interest: [usize; InboundKind::ALL.len()],
+    queues: [VecDeque<(Vec<u8>, DataLinkAddress)>; InboundKind::ALL.len()],
+    /// Answers read by other receivers, per confirmed request in flight.
+    transactions: HashMap<TransactionKey, VecDeque<(Vec<u8>, DataLinkAddress)>>,
+    /// Aborts of cancelled requests, with the address each goes to.
+    aborts: Vec<(DataLinkAddress, Vec<u8>)>,
+    dropped: u64,
+}
+
+impl Default for InboundRouter {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_INBOUND_QUEUE_CAPACITY)
+    }
+}
+
+impl InboundRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A router queueing up to `capacity` frames per kind, at least 1.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            reader: tokio::sync::Mutex::new(()),
+            state: Mutex::new(RouterState::default()),
+            capacity: AtomicUsize::new(capacity.max(1)),
+            abort_queued: tokio::sync::Notify::new(),
+        }
+    }
+
+    /// Frames queued per kind before the oldest is dropped.
+    pub fn capacity(&self) -> usize {
+        self.capacity.load(Ordering::Relaxed)
+    }
+
+    /// Changes the frames queued per kind, at least 1, for every handle sharing this
+    /// router. Queues over the new capacity drop their oldest frames.
+    pub fn set_capacity(&self, capacity: usize) {
+        let capacity = capacity.max(1);
+        let mut state = self.state();
+        self.capacity.store(capacity, Ordering::Relaxed);
+        let mut dropped = 0;
+        let RouterState {
+            queues,
+            transactions,
+            ..
+        } = &mut *state;
+        for queue in queues.iter_mut().chain(transactions.values_mut()) {
+            while queue.len() > capacity {
+                queue.pop_front();
+                dropped += 1;
+            }
+        }
+        state.dropped += dropped;
+    }
+
+    /// Keeps `kinds` wanted until the returned guard is dropped, so their frames are
+    /// queued between receives.
+    pub fn subscribe(self: &Arc<Self>, kinds: &[InboundKind]) -> InboundSubscription {
+        let mut state = self.state();
+        for kind in kinds {
+            state.interest[kind.index()] += 1;
+        }
+        InboundSubscription {
+            router: self.clone(),
+            kinds: kinds.to_vec(),
+        }
+    }
+
+    /// Keeps the answers to the confirmed request `invoke_id` sent to `address` for
+    /// [`recv_transactions`](Self::recv_transactions) until the returned guard is dropped.
+    pub(crate) fn expect_transaction(
+        self: &Arc<Self>,
+        address: DataLinkAddress,
+        invoke_id: u8,
+    ) -> TransactionSubscription {
+        let key = (address, invoke_id);
+        self.state().transactions.entry(key).or_default();
+        TransactionSubscription {
+            router: self.clone(),
+            key,
+        }
+    }
+
+    /// Queues the Abort `frame` of a request cancelled where it cannot be sent, and wakes
+    /// the receiver reading the link, if any, to send it. Otherwise it goes out with the
+    /// next read or request.
+    pub(crate) fn queue_abort(&self, address: DataLinkAddress, frame: Vec<u8>) {
+        self.state().aborts.push((address, frame));
+        self.abort_queued.notify_one();
+    }
+
+    /// Sends the Aborts queued by [`queue_abort`](Self::queue_abort).
+    pub(crate) async fn send_queued_aborts<D: DataLink>(&self, datalink: &D) {
+        let aborts = std::mem::take(&mut self.state().aborts);
+        for (address, frame) in aborts {
+            if let Err(err) = datalink.send(address, &frame).await {
+                log::debug!("failed to abort a cancelled request to {address}: {err}");
+            }
+        }
+    }
+
+    /// Whether frames of `kind` are queued when another receiver reads them.
+    pub fn is_wanted(&self, kind: InboundKind) -> bool {
+        self.state().interest[kind.index()] > 0
+    }
+
+    /// Frames of `kind` waiting for their receiver.
+    pub fn queued(&self, kind: InboundKind) -> usize {
+        self.state().queues[kind.index()].len()
+    }
+
+    /// Queued frames dropped because their queue was full.
+    pub fn dropped(&self) -> u64 {
+        self.state().dropped
+    }
+
+    /// Receives the next frame of one of `kinds`, or one nobody wants, into `buf`.
+    ///
+    /// Queued frames are returned first. Gives up with [`ClientError::Timeout`] at
+    /// `deadline`, if any. Invalid frames are skipped.
+    pub(crate) async fn recv<D: DataLink>(
+        self: &Arc<Self>,
+        datalink: &D,
+        runtime: &dyn Runtime,
+        kinds: &[InboundKind],
+        buf: &mut [u8],
+        deadline: Option<Instant>,
+    ) -> Result<(usize, DataLinkAddress), ClientError> {
+        self.recv_matching(datalink, runtime, kinds, &[], buf, deadline)
+            .await
+    }
+
+    /// Receives the next answer to one of the confirmed requests `transactions`, each
+    /// expected with [`expect_transaction`](Self::expect_transaction), or a frame nobody
+    /// wants, such as a late answer, into `buf`.
+    ///
+    /// Queued answers are returned first. Gives up with [`ClientError::Timeout`] at
+    /// `deadline`. Invalid frames are skipped.
+    pub(crate) async fn recv_transactions<D: DataLink>(
+        self: &Arc<Self>,
+        datalink: &D,
+        runtime: &dyn Runtime,
+        transactions: &[TransactionKey],
+        buf: &mut [u8],
+        deadline: Instant,
+    ) -> Result<(usize, DataLinkAddress), ClientError> {
+        self.recv_matching(datalink, runtime, &[], transactions, buf, Some(deadline))
+            .await
+    }
+
+    async fn recv_matching<D: DataLink>(
+        self: &Arc<Self>,
+        datalink: &D,
+        runtime: &dyn Runtime,
+        kinds: &[InboundKind],
+        transactions: &[TransactionKey],
+        buf: &mut [u8],
+        deadline: Option<Instant>,
+    ) -> Result<(usize, DataLinkAddress), ClientError> {
+        let _interest = self.subscribe(kinds);
+        loop {
+            if let Some(frame) = self.take_queued(kinds, transactions, buf) {
+                return Ok(frame);
+            }
+            if deadline.is_some_and(|deadline| deadline <= runtime.now()) {
+                return Err(ClientError::Timeout);
+            }
+            let reader = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(runtime.now());
+                    match runtime::timeout(runtime, remaining, self.reader.lock()).await {
+                        Ok(reader) => reader,
+                        Err(_) => {
+                            return self
+                                .take_queued(kinds, transactions, buf)
+                                .ok_or(ClientError::Timeout)
+                        }
+                    }
+                }
+                None => self.reader.lock().await,
+            };
+            // Another receiver may have queued a frame for us while we waited.
+            if let Some(frame) = self.take_queued(kinds, transactions, buf) {
+                return Ok(frame);
+            }
+            self.send_queued_aborts(datalink).await;
+
+            let received = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(runtime.now());
+                    match runtime::timeout(runtime, remaining, self.recv_link(datalink, buf)).await
+                    {
+                        Ok(received) => received,
+                        Err(_) => return Err(ClientError::Timeout),
+                    }
+                }
+                None => self.recv_link(datalink, buf).await,
+            };
+            let (n, source) = match received {
+                // An Abort was queued; send it before reading on.
+                None => continue,
+                Some(Ok(frame)) => frame,
+                Some(Err(DataLinkError::InvalidFrame)) => continue,
+                Some(Err(e)) => return Err(e.into()),
+            };
+            let kind = classify_frame(&buf[..n]);
+            if let Some(key) = transaction_key(kind, &buf[..n], source) {
+                if transactions.contains(&key) {
+                    return Ok((n, source));
+                }
+                if self.queue_answer(key, &buf[..n], source) {
+                    drop(reader);
+                    continue;
+                }
+            }
+            if kinds.contains(&kind) || !self.queue(kind, &buf[..n], source) {
+                return Ok((n, source));
+            }
+            drop(reader);
+        }
+    }
+
+    /// Receives from `datalink`, or `None` as soon as an Abort is queued.
+    async fn recv_link<D: DataLink>(
+        &self,
+        datalink: &D,
+        buf: &mut [u8],
+    ) -> Option<Result<(usize, DataLinkAddress), DataLinkError>> {
+        let mut received = pin!(datalink.recv(buf));
+        let mut abort_queued = pin!(self.abort_queued.notified());
+        poll_fn(|cx| {
+            if abort_queued.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(None);
+            }
+            received.as_mut().poll(cx).map(Some)
+        })
+        .await
+    }
+
+    /// Queues a frame of `kind` if some other receiver wants it.
+    fn queue(&self, kind: InboundKind, frame: &[u8], source: DataLinkAddress) -> bool {
+        let mut state = self.state();
+        if state.interest[kind.index()] == 0 {
+            return false;
+        }
+        let queue = &mut state.queues[kind.index()];
+        if push_capped(queue, frame, source, self.capacity()) {
+            state.dropped += 1;
+            log::warn!("inbound {kind:?} queue full — dropping the oldest frame");
+        }
+        true
+    }
+
+    /// Queues an answer for the confirmed request `key` if it is still in flight.
+    fn queue_answer(&self, key: TransactionKey, frame: &[u8], source: DataLinkAddress) -> bool {
+        let mut state = self.state();
+        let Some(queue) = state.transactions.get_mut(&key) else {
+            return false;
+        };
+        if push_capped(queue, frame, source, self.capacity()) {
+            state.dropped += 1;
+            log::warn!(
+                "answers to request {} to {} not read — dropping the oldest",
+                key.1,
+                key.0
+            );
+        }
+        true
+    }
+
+    fn take_queued(
+        &self,
+        kinds: &[InboundKind],
+        transactions: &[TransactionKey],
+        buf: &mut [u8],
+    ) -> Option<(usize, DataLinkAddress)> {
+        let mut state = self.state();
+        let state = &mut *state;
+        for key in transactions {
+            if let Some(frame) = state
+                .transactions
+                .get_mut(key)
+                .and_then(|queue| pop_into(queue, buf))
+            {
+                return Some(frame);
+            }
+        }
+        kinds
+            .iter()
+            .find_map(|kind| pop_into(&mut state.queues[kind.index()], buf))
+    }
+
+    fn state(&self) -> MutexGuard<'_, RouterState> {
+        self.state.lock().expect("inbound router lock poisoned")
+    }
+}
+
+/// Appends a frame to `queue`, dropping the oldest when it holds `capacity`; returns
+/// whether one was dropped.
+fn push_capped(
+    queue: &mut VecDeque<(Vec<u8>, DataLinkAddress)>,
+    frame: &[u8],
+    source: DataLinkAddress,
+    capacity: usize,
+) -> bool {
+    let full = queue.len() >= capacity;
+    if full {
+        queue.pop_front();
+    }
+    queue.push_back((frame.to_vec(), source));
+    full
+}
+
+/// Copies the oldest frame of `queue` that fits into `buf`, dropping those that do not.
+fn pop_into(
+    queue: &mut VecDeque<(Vec<u8>, DataLinkAddress)>,
+    buf: &mut [u8],
+) -> Option<(usize, DataLinkAddress)> {
+    while let Some((frame, source)) = queue.pop_front() {
+        if frame.len() <= buf.len() {
+            buf[..frame.len()].copy_from_slice(&frame);
+            return Some((frame.len(), source));
+        }
+    }
+    None
+}
+
+/// The confirmed request a frame of `kind` from `source` answers, if it is an answer.
+fn transaction_key(
+    kind: InboundKind,
+    frame: &[u8],
+    source: DataLinkAddress,
+) -> Option<TransactionKey> {
+    if kind != InboundKind::Transaction {
+        return None;
+    }
+    let mut r = Reader::new(frame);
+    Npdu::decode(&mut r).ok()?;
+    // Every answer PDU carries the invoke id in its second octet.
+    let apdu = r.read_exact(r.remaining()).ok()?;
+    apdu.get(1).map(|&invoke_id| (source, invoke_id))
+}
+
+/// Keeps kinds of an [`InboundRouter`] wanted; see [`InboundRouter::subscribe`].
+///
+/// Queued frames of a kind nobody wants any more are dropped with the last guard.
+#[derive(Debug)]
+pub struct InboundSubscription {
+    router: Arc<InboundRouter>,
+    kinds: Vec<InboundKind>,
+}
+
+impl Drop for InboundSubscription {
+    fn drop(&mut self) {
+        let Ok(mut state) = self.router.state.lock() else {
+            return;
+        };
+        for kind in &self.kinds {
+            state.interest[kind.index()] -= 1;
+            if state.interest[kind.index()] == 0 {
+                state.queues[kind.index()].clear();
+            }
+        }
+    }
+}
+
+/// Keeps the answers to a confirmed request queued; see
+/// [`InboundRouter::expect_transaction`].
+///
+/// Answers still queued are dropped with the guard.
+#[derive(Debug)]
+pub(crate) struct TransactionSubscription {
+    router: Arc<InboundRouter>,
+    key: TransactionKey,
+}
+
+impl Drop for TransactionSubscription {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.router.state.lock() {
+            state.transactions.remove(&self.key);
+        }
+    }
+}
+
+/// A [`DataLink`] receiving only some [`InboundKind`]s of a client's link through its
+/// router, from [`BacnetClient::routed_link`](crate::BacnetClient::routed_link).
+///
+/// Hand it to code that reads a link itself, such as
+/// [`create_notification_listener`](crate::create_notification_listener), to run that
+/// code beside the client's own requests. Sends go straight to the client's link.
+#[derive(Debug)]
+pub struct RoutedLink<D: DataLink> {
+    datalink: Arc<D>,
+    router: Arc<InboundRouter>,
+    runtime: Arc<dyn Runtime>,
+    subscription: InboundSubscription,
+}
+
+impl<D: DataLink> RoutedLink<D> {
+    pub(crate) fn new(
+        datalink: Arc<D>,
+        router: Arc<InboundRouter>,
+        runtime: Arc<dyn Runtime>,
+        kinds: &[InboundKind],
+    ) -> Self {
+        let subscription = router.subscribe(kinds);
+        Self {
+            datalink,
+            router,
+            runtime,
+            subscription,
+        }
+    }
+
+    /// The kinds this link receives.
+    pub fn kinds(&self) -> &[InboundKind] {
+        &self.subscription.kinds
+    }
+}
+
+impl<D: DataLink> DataLink for RoutedLink<D> {
+    async fn send(&self, address: DataLinkAddress, payload: &[u8]) -> Result<(), DataLinkError> {
+        self.datalink.send(address, payload).await
+    }
+
+    async fn recv(&self, buf: &mut [u8]) -> Result<(usize, DataLinkAddress), DataLinkError> {
+        match self
+            .router
+            .recv(
+                &*self.datalink,
+                &*self.runtime,
+                &self.subscription.kinds,
+                buf,
+                None,
+            )
+            .await
+        {
+            Ok(frame) => Ok(frame),
+            Err(ClientError::DataLink(e)) => Err(e),
+            Err(_) => Err(DataLinkError::InvalidFrame),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{classify_frame, InboundKind, InboundRouter};
+    use crate::ClientError;
+    use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError};
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    struct QueueLink(Mutex<VecDeque<(Vec<u8>, DataLinkAddress)>>);
+
+    impl DataLink for QueueLink {
+        async fn send(&self, _: DataLinkAddress, _: &[u8]) -> Result<(), DataLinkError> {
+            Ok(())
+        }
+
+        async fn recv(&self, buf: &mut [u8]) -> Result<(usize, DataLinkAddress), DataLinkError> {
+            let next = self.0.lock().unwrap().pop_front();
+            match next {
+                Some((frame, source)) => {
+                    buf[..frame.len()].copy_from_slice(&frame);
+                    Ok((frame.len(), source))
+                }
+                None => std::future::pending().await,
+            }
+        }
+    }
+
+    /// Receives nothing; records what is sent.
+    #[derive(Default)]
+    struct IdleLink(Mutex<Vec<(DataLinkAddress, Vec<u8>)>>);
+
+    impl DataLink for IdleLink {
+        async fn send(
+            &self,
+            address: DataLinkAddress,
+            payload: &[u8],
+        ) -> Result<(), DataLinkError> {
+            self.0.lock().unwrap().push((address, payload.to_vec()));
+            Ok(())
+        }
+
+        async fn recv(&self, _: &mut [u8]) -> Result<(usize, DataLinkAddress), DataLinkError> {
+            std::future::pending().await
+        }
+    }
+
+    const SIMPLE_ACK: [u8; 5] = [0x01, 0x00, 0x20, 0x01, 0x0F];
+    const UNCONFIRMED_COV: [u8; 4] = [0x01, 0x00, 0x10, 0x02];
+    const I_AM: [u8; 4] = [0x01, 0x00, 0x10, 0x00];
+    const WHO_IS: [u8; 4] = [0x01, 0x00, 0x10, 0x08];
+
+    #[test]
+    fn frames_are_classified_by_apdu_type_and_service() {
+        assert_eq!(classify_frame(&SIMPLE_ACK), InboundKind::Transaction);
+        assert_eq!(
+            classify_frame(&UNCONFIRMED_COV),
+            InboundKind::CovNotification
+        );
+        assert_eq!(classify_frame(&I_AM), InboundKind::Discovery);
+        assert_eq!(classify_frame(&WHO_IS), InboundKind::Request);
+        assert_eq!(
+            classify_frame(&[0x01, 0x04, 0x00, 0x05, 0x01, 0x0C]),
+            InboundKind::Request
+        );
+        assert_eq!(classify_frame(&[0x01, 0x80, 0x01]), InboundKind::Other);
+        assert_eq!(classify_frame(&[]), InboundKind::Other);
+    }
+
+    #[tokio::test]
+    async fn frames_for_another_waiting_kind_are_queued_not_stolen() {
+        let source = DataLinkAddress::Mstp(3);
+        let link = QueueLink(Mutex::new(VecDeque::from([
+            (UNCONFIRMED_COV.to_vec(), source),
+            (WHO_IS.to_vec(), source),
+            (SIMPLE_ACK.to_vec(), source),
+        ])));
+        let runtime = crate::runtime::TokioRuntime;
+        let router = Arc::new(InboundRouter::new());
+        let cov = router.subscribe(&[InboundKind::CovNotification]);
+
+        // The transaction reads the COV notification and queues it, then is handed the
+        // Who-Is nobody wants, then its own ack.
+        let mut buf = [0u8; 64];
+        let deadline = Some(std::time::Instant::now() + Duration::from_secs(1));
+        let kinds = [InboundKind::Transaction];
+        let (n, _) = router
+            .recv(&link, &runtime, &kinds, &mut buf, deadline)
+            .await
+            .unwrap();
+        assert_eq!(&buf[..n], WHO_IS);
+        let (n, _) = router
+            .recv(&link, &runtime, &kinds, &mut buf, deadline)
+            .await
+            .unwrap();
+        assert_eq!(&buf[..n], SIMPLE_ACK);
+        assert_eq!(router.queued(InboundKind::CovNotification), 1);
+
+        let (n, from) = router
+            .recv(
+                &link,
+                &runtime,
+                &[InboundKind::CovNotification],
+                &mut buf,
+                deadline,
+            )
+            .await
+            .unwrap();
+        assert_eq!((&buf[..n], from), (&UNCONFIRMED_COV[..], source));
+
+        drop(cov);
+        assert!(!router.is_wanted(InboundKind::CovNotification));
+    }
+
+    #[tokio::test]
+    async fn answers_are_queued_for_the_request_they_answer() {
+        let device = DataLinkAddress::Mstp(3);
+        let ack = |invoke_id| [0x01, 0x00, 0x20, invoke_id, 0x0F].to_vec();
+        let link = QueueLink(Mutex::new(VecDeque::from([
+            (ack(1), device),
+            (ack(9), device),
+            (ack(2), device),
+        ])));
+        let runtime = crate::runtime::TokioRuntime;
+        let router = Arc::new(InboundRouter::new());
+        let first = router.expect_transaction(device, 1);
+        let _second = router.expect_transaction(device, 2);
+
+        // The second request reads the answer to the first and queues it, is handed the
+        // late answer nobody expects, then its own.
+        let mut buf = [0u8; 64];
+        let deadline = std::time::Instant::now() + Duration::from_secs(1);
+        let (n, _) = router
+            .recv_transactions(&link, &runtime, &[(device, 2)], &mut buf, deadline)
+            .await
+            .unwrap();
+        assert_eq!(&buf[..n], ack(9));
+        let (n, _) = router
+            .recv_transactions(&link, &runtime, &[(device, 2)], &mut buf, deadline)
+            .await
+            .unwrap();
+        assert_eq!(&buf[..n], ack(2));
+
+        let (n, _) = router
+            .recv_transactions(&link, &runtime, &[(device, 1)], &mut buf, deadline)
+            .await
+            .unwrap();
+        assert_eq!(&buf[..n], ack(1));
+
+        // Answers to a request that has ended are no longer queued.
+        drop(first);
+        assert!(!router.queue_answer((device, 1), &ack(1), device));
+    }
+
+    #[tokio::test]
+    async fn a_queued_abort_wakes_the_reader_to_send_it() {
+        let device = DataLinkAddress::Mstp(3);
+        let abort = vec![0x01, 0x00, 0x70, 0x05, 0x00];
+        let link = IdleLink::default();
+        let runtime = crate::runtime::TokioRuntime;
+        let router = Arc::new(InboundRouter::new());
+
+        let mut buf = [0u8; 64];
+        let deadline = Some(std::time::Instant::now() + Duration::from_millis(200));
+        let reading = router.recv(
+            &link,
+            &runtime,
+            &[InboundKind::CovNotification],
+            &mut buf,
+            deadline,
+        );
+        let cancel = async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            router.queue_abort(device, abort.clone());
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            link.0.lock().unwrap().clone()
+        };
+        let (received, sent) = tokio::join!(reading, cancel);
+        assert_eq!(sent, [(device, abort)]);
+        assert!(matches!(received, Err(ClientError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn full_queues_drop_their_oldest_frames() {
+        let source = DataLinkAddress::Mstp(3);
+        let link = QueueLink(Mutex::new(
+            [0x01, 0x02, 0x03]
+                .map(|n| ([&I_AM[..], &[n]].concat(), source))
+                .into_iter()
+                .chain([(SIMPLE_ACK.to_vec(), source)])
+                .collect(),
+        ));
+        let runtime = crate::runtime::TokioRuntime;
+        let router = Arc::new(InboundRouter::with_capacity(2));
+        let _discovery = router.subscribe(&[InboundKind::Discovery]);
+
+        let mut buf = [0u8; 64];
+        let deadline = Some(std::time::Instant::now() + Duration::from_secs(1));
+        router
+            .recv(
+                &link,
+                &runtime,
+                &[InboundKind::Transaction],
+                &mut buf,
+                deadline,
+            )
+            .await
+            .unwrap();
+        assert_eq!(router.queued(InboundKind::Discovery), 2);
+        assert_eq!(router.dropped(), 1);
+
+        // Shrinking the shared router trims queues already over the new capacity.
+        router.set_capacity(0);
+        assert_eq!(router.capacity(), 1);
+        assert_eq!(router.queued(InboundKind::Discovery), 1);
+        assert_eq!(router.dropped(), 2);
+        let (n, _) = router
+            .recv(
+                &link,
+                &runtime,
+                &[InboundKind::Discovery],
+                &mut buf,
+                deadline,
+            )
+            .await
+            .unwrap();
+        assert_eq!(buf[n - 1], 0x03);
+    }
+}
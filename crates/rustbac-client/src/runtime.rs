@@ -0,0 +1,183 @@
+//! Executor abstraction for the client's timers and background tasks.
+//!
+//! The requests, timers and background tasks of [`BacnetClient`](crate::BacnetClient),
+//! [`DeviceThrottle`](crate::throttle::DeviceThrottle) and
+//! [`AlarmRouter`](crate::AlarmRouter) do not call `tokio::spawn` or Tokio timers
+//! directly; they go through a [`Runtime`], [`TokioRuntime`] by default. To drive them
+//! from async-std, smol or an embedded executor, implement [`Runtime`] for it, pass it to
+//! [`BacnetClient::with_runtime`](crate::BacnetClient::with_runtime) or
+//! [`AlarmRouter::with_runtime`](crate::AlarmRouter::with_runtime) and use a
+//! [`DataLink`](rustbac_datalink::DataLink) built for that executor. The client's locks and
+//! channels are `tokio::sync` types, which work on any executor.
+//!
+//! The rest still requires a Tokio runtime:
+//!
+//! - the bundled BACnet/IP and BACnet/SC transports;
+//! - [`CovManager`](crate::CovManager),
+//!   [`BacnetClient::start_time_master`](crate::BacnetClient::start_time_master) and the
+//!   `blocking` facade, whose background threads are driven by the current Tokio runtime
+//!   (the time master's rounds still sleep on the client's [`Runtime`]);
+//! - the webhook sink, whose sockets are Tokio's;
+//! - the server side: [`BacnetServer`](crate::BacnetServer), its virtual networks, the
+//!   [`PointGateway`](crate::PointGateway) and the [`SimulatedNetwork`](crate::SimulatedNetwork).
+//!
+//! Executors without `std` can use the sans-IO state machine in
+//! `rustbac_core::transaction` instead of the client.
+//!
+//! ```
+//! use rustbac_client::runtime::{BoxFuture, Runtime, TaskHandle};
+//! use std::time::{Duration, Instant};
+//!
+//! # mod my_executor {
+//! #     pub fn spawn(_: rustbac_client::runtime::BoxFuture<()>) {}
+//! # }
+//! /// Spawns on some other executor and sleeps on a helper thread.
+//! #[derive(Debug)]
+//! struct MyRuntime;
+//!
+//! #[derive(Debug)]
+//! struct Detached;
+//!
+//! impl TaskHandle for Detached {
+//!     fn abort(&self) {}
+//! }
+//!
+//! impl Runtime for MyRuntime {
+//!     fn spawn(&self, future: BoxFuture<()>) -> Box<dyn TaskHandle> {
+//!         my_executor::spawn(future);
+//!         Box::new(Detached)
+//!     }
+//!
+//!     fn sleep(&self, duration: Duration) -> BoxFuture<()> {
+//!         let (tx, rx) = tokio::sync::oneshot::channel();
+//!         std::thread::spawn(move || {
+//!             std::thread::sleep(duration);
+//!             let _ = tx.send(());
+//!         });
+//!         Box::pin(async move {
+//!             let _ = rx.await;
+//!         })
+//!     }
+//!
+//!     fn now(&self) -> Instant {
+//!         Instant::now()
+//!     }
+//! }
+//! ```
+
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::pin::{pin, Pin};
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+/// A boxed, `Send` future, as taken and returned by [`Runtime`].
+pub type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send + 'static>>;
+
+/// The executor services the client needs: spawning, sleeping and reading the clock.
+pub trait Runtime: fmt::Debug + Send + Sync + 'static {
+    /// Runs `future` in the background until it completes or the handle aborts it.
+    fn spawn(&self, future: BoxFuture<()>) -> Box<dyn TaskHandle>;
+
+    /// A future that completes once `duration` has passed.
+    fn sleep(&self, duration: Duration) -> BoxFuture<()>;
+
+    /// The current time on the clock that [`sleep`](Self::sleep) measures.
+    fn now(&self) -> Instant;
+}
+
+/// Handle to a task started with [`Runtime::spawn`].
+pub trait TaskHandle: fmt::Debug + Send + Sync {
+    /// Cancels the task; it may already have finished.
+    fn abort(&self);
+}
+
+/// The default [`Runtime`]: `tokio::spawn` and Tokio timers on the current runtime.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioRuntime;
+
+impl Runtime for TokioRuntime {
+    fn spawn(&self, future: BoxFuture<()>) -> Box<dyn TaskHandle> {
+        Box::new(tokio::spawn(future))
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<()> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+
+    fn now(&self) -> Instant {
+        // Follows Tokio's clock, so paused test time applies.
+        tokio::time::Instant::now().into_std()
+    }
+}
+
+impl TaskHandle for tokio::task::JoinHandle<()> {
+    fn abort(&self) {
+        tokio::task::JoinHandle::abort(self);
+    }
+}
+
+/// Returned by [`timeout`] when the duration passes before the future completes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed;
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("deadline has elapsed")
+    }
+}
+
+impl std::error::Error for Elapsed {}
+
+/// Runs `future`, giving up once `duration` has passed on `runtime`'s clock.
+///
+/// The future is polled before the timer, so one that is already ready wins even with a
+/// zero duration.
+pub async fn timeout<F: Future>(
+    runtime: &dyn Runtime,
+    duration: Duration,
+    future: F,
+) -> Result<F::Output, Elapsed> {
+    let mut future = pin!(future);
+    let mut sleep = runtime.sleep(duration);
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Ok(output));
+        }
+        sleep.as_mut().poll(cx).map(|()| Err(Elapsed))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{timeout, Elapsed, Runtime, TokioRuntime};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn timeout_prefers_a_ready_future_and_expires_a_pending_one() {
+        let runtime = TokioRuntime;
+        assert_eq!(timeout(&runtime, Duration::ZERO, async { 7 }).await, Ok(7));
+        assert_eq!(
+            timeout(
+                &runtime,
+                Duration::from_millis(10),
+                std::future::pending::<()>()
+            )
+            .await,
+            Err(Elapsed)
+        );
+    }
+
+    #[tokio::test]
+    async fn spawned_tasks_can_be_aborted() {
+        let runtime = TokioRuntime;
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<()>(1);
+        let task = runtime.spawn(Box::pin(async move {
+            std::future::pending::<()>().await;
+            drop(tx);
+        }));
+        task.abort();
+        assert_eq!(rx.recv().await, None);
+    }
+}
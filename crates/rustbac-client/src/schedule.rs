@@ -2,9 +2,16 @@
 //!
 //! Provides typed representations of weekly schedules, exception schedules,
 //! and calendar entries that wrap the lower-level [`ClientDataValue`] encoding.
+//! [`validate_weekly_schedule`] and [`validate_exception_schedule`] check a schedule
+//! before [`BacnetClient::write_weekly_schedule`](crate::BacnetClient::write_weekly_schedule)
+//! or [`BacnetClient::write_exception_schedule`](crate::BacnetClient::write_exception_schedule)
+//! sends it.
 
 use crate::ClientDataValue;
-use rustbac_core::types::{Date, Time};
+use rustbac_core::services::calendar::{CalendarEntry as CoreCalendarEntry, WeekNDay};
+use rustbac_core::types::{Date, ObjectId, ObjectType, Time};
+use rustbac_core::EncodeError;
+use std::fmt;
 
 /// A single time-value pair in a daily schedule.
 #[derive(Debug, Clone, PartialEq)]
@@ -32,9 +39,203 @@ pub enum CalendarEntry {
     },
 }
 
+impl CalendarEntry {
+    /// Checks the entry's wildcard fields are ones the standard allows; see
+    /// [`rustbac_core::services::calendar::CalendarEntry::validate`].
+    pub fn validate(&self) -> Result<(), EncodeError> {
+        self.to_core().validate()
+    }
+
+    pub(crate) fn to_core(&self) -> CoreCalendarEntry {
+        match *self {
+            Self::Date(date) => CoreCalendarEntry::Date(date),
+            Self::Range(range) => CoreCalendarEntry::DateRange {
+                start: range.start,
+                end: range.end,
+            },
+            Self::WeekNDay {
+                month,
+                week_of_month,
+                day_of_week,
+            } => CoreCalendarEntry::WeekNDay(WeekNDay::new(month, week_of_month, day_of_week)),
+        }
+    }
+}
+
+impl From<CoreCalendarEntry> for CalendarEntry {
+    fn from(entry: CoreCalendarEntry) -> Self {
+        match entry {
+            CoreCalendarEntry::Date(date) => Self::Date(date),
+            CoreCalendarEntry::DateRange { start, end } => Self::Range(DateRange { start, end }),
+            CoreCalendarEntry::WeekNDay(week_n_day) => Self::WeekNDay {
+                month: week_n_day.month,
+                week_of_month: week_n_day.week_of_month,
+                day_of_week: week_n_day.day_of_week,
+            },
+        }
+    }
+}
+
+/// The days a [`SpecialEvent`] applies to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpecialEventPeriod {
+    CalendarEntry(CalendarEntry),
+    /// A Calendar object in the same device.
+    CalendarReference(ObjectId),
+}
+
+/// An entry of a Schedule's Exception_Schedule: on the days of `period`, the schedule
+/// follows `time_values` instead of its Weekly_Schedule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpecialEvent {
+    pub period: SpecialEventPeriod,
+    pub time_values: Vec<TimeValue>,
+    /// 1 (highest) to 16; overlapping events are resolved by priority.
+    pub priority: u8,
+}
+
+impl SpecialEvent {
+    /// Problems a device would reject this event for; empty when it is valid.
+    pub fn issues(&self) -> Vec<ScheduleIssue> {
+        let mut issues = Vec::new();
+        match &self.period {
+            SpecialEventPeriod::CalendarEntry(entry) => {
+                if entry.validate().is_err() {
+                    issues.push(ScheduleIssue::InvalidCalendarEntry);
+                }
+            }
+            SpecialEventPeriod::CalendarReference(object) => {
+                if object.object_type() != ObjectType::Calendar {
+                    issues.push(ScheduleIssue::NotACalendar(*object));
+                }
+            }
+        }
+        if !(1..=16).contains(&self.priority) {
+            issues.push(ScheduleIssue::PriorityOutOfRange(self.priority));
+        }
+        issues.extend(time_value_issues(&self.time_values));
+        issues
+    }
+}
+
+/// A problem found in an exception schedule before it is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleIssue {
+    /// The calendar entry has out-of-range fields or wildcards the standard does not
+    /// allow; see [`CalendarEntry::validate`].
+    InvalidCalendarEntry,
+    /// The calendar reference names an object that is not a Calendar.
+    NotACalendar(ObjectId),
+    /// The event priority is outside 1–16.
+    PriorityOutOfRange(u8),
+    /// Time value `index` has an out-of-range or unspecified field.
+    InvalidTime { index: usize },
+    /// Time value `index` is not later than the one before it.
+    TimeNotAscending { index: usize },
+}
+
+impl fmt::Display for ScheduleIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCalendarEntry => f.write_str("invalid calendar entry"),
+            Self::NotACalendar(object) => write!(f, "{object:?} is not a calendar"),
+            Self::PriorityOutOfRange(priority) => {
+                write!(f, "priority {priority} is outside 1-16")
+            }
+            Self::InvalidTime { index } => write!(f, "time value {index} has an invalid time"),
+            Self::TimeNotAscending { index } => {
+                write!(f, "time value {index} is not later than the one before it")
+            }
+        }
+    }
+}
+
+/// The part of a schedule a [`ScheduleDiagnostic`] points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleEntry {
+    /// Entry `n` of the Exception_Schedule.
+    SpecialEvent(usize),
+    /// Day `n` of the Weekly_Schedule, 0 being Monday.
+    Weekday(usize),
+}
+
+impl fmt::Display for ScheduleEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SpecialEvent(index) => write!(f, "special event {index}"),
+            Self::Weekday(index) => write!(f, "weekday {index}"),
+        }
+    }
+}
+
+/// A [`ScheduleIssue`] and the schedule entry it was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduleDiagnostic {
+    pub entry: ScheduleEntry,
+    pub issue: ScheduleIssue,
+}
+
+impl fmt::Display for ScheduleDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.entry, self.issue)
+    }
+}
+
+/// Checks every event of an exception schedule; the result is empty when the whole
+/// schedule is valid.
+pub fn validate_exception_schedule(events: &[SpecialEvent]) -> Vec<ScheduleDiagnostic> {
+    events
+        .iter()
+        .enumerate()
+        .flat_map(|(event, special_event)| {
+            special_event
+                .issues()
+                .into_iter()
+                .map(move |issue| ScheduleDiagnostic {
+                    entry: ScheduleEntry::SpecialEvent(event),
+                    issue,
+                })
+        })
+        .collect()
+}
+
+/// Checks the time values of every day of a weekly schedule (Monday first); the result
+/// is empty when the whole schedule is valid.
+pub fn validate_weekly_schedule(week: &[Vec<TimeValue>; 7]) -> Vec<ScheduleDiagnostic> {
+    week.iter()
+        .enumerate()
+        .flat_map(|(day, time_values)| {
+            time_value_issues(time_values)
+                .into_iter()
+                .map(move |issue| ScheduleDiagnostic {
+                    entry: ScheduleEntry::Weekday(day),
+                    issue,
+                })
+        })
+        .collect()
+}
+
+fn time_value_issues(time_values: &[TimeValue]) -> Vec<ScheduleIssue> {
+    let mut issues = Vec::new();
+    let mut previous: Option<Time> = None;
+    for (index, time_value) in time_values.iter().enumerate() {
+        let time = time_value.time;
+        if !(time.hour < 24 && time.minute < 60 && time.second < 60 && time.hundredths < 100) {
+            issues.push(ScheduleIssue::InvalidTime { index });
+            continue;
+        }
+        let key = |t: Time| (t.hour, t.minute, t.second, t.hundredths);
+        if previous.is_some_and(|previous| key(time) <= key(previous)) {
+            issues.push(ScheduleIssue::TimeNotAscending { index });
+        }
+        previous = Some(time);
+    }
+    issues
+}
+
 /// Decode a weekly schedule from a [`ClientDataValue::Constructed`].
 ///
-/// A BACnet weekly schedule is a sequence of 7 daily schedules (Sun–Sat),
+/// A BACnet weekly schedule is a sequence of 7 daily schedules (Mon–Sun),
 /// each containing a list of [`TimeValue`] pairs.
 pub fn decode_weekly_schedule(value: &ClientDataValue) -> Option<Vec<Vec<TimeValue>>> {
     let days = match value {
@@ -191,4 +392,122 @@ mod tests {
         assert_eq!(entries.len(), 1);
         assert_eq!(entries[0], CalendarEntry::Date(date));
     }
+
+    #[test]
+    fn calendar_entries_convert_to_and_from_core_entries() {
+        let entries = [
+            CalendarEntry::Range(DateRange {
+                start: Date {
+                    year_since_1900: 124,
+                    month: 1,
+                    day: 1,
+                    weekday: 0xFF,
+                },
+                end: Date {
+                    year_since_1900: 124,
+                    month: 1,
+                    day: 7,
+                    weekday: 0xFF,
+                },
+            }),
+            CalendarEntry::WeekNDay {
+                month: 0xFF,
+                week_of_month: 0xFF,
+                day_of_week: 6,
+            },
+        ];
+        for entry in entries {
+            assert!(entry.validate().is_ok());
+            assert_eq!(CalendarEntry::from(entry.to_core()), entry);
+        }
+        let bad = CalendarEntry::WeekNDay {
+            month: 0,
+            week_of_month: 1,
+            day_of_week: 1,
+        };
+        assert_eq!(bad.validate(), Err(EncodeError::ValueOutOfRange));
+    }
+
+    fn at(hour: u8, minute: u8, value: f32) -> TimeValue {
+        TimeValue {
+            time: Time {
+                hour,
+                minute,
+                second: 0,
+                hundredths: 0,
+            },
+            value: ClientDataValue::Real(value),
+        }
+    }
+
+    #[test]
+    fn exception_schedule_diagnostics_name_the_event_and_problem() {
+        let christmas = CalendarEntry::Date(Date {
+            year_since_1900: 124,
+            month: 12,
+            day: 25,
+            weekday: 0xFF,
+        });
+        let events = [
+            SpecialEvent {
+                period: SpecialEventPeriod::CalendarEntry(christmas),
+                time_values: vec![at(0, 0, 16.0), at(8, 0, 19.0)],
+                priority: 1,
+            },
+            SpecialEvent {
+                period: SpecialEventPeriod::CalendarReference(ObjectId::new(
+                    ObjectType::AnalogValue,
+                    1,
+                )),
+                time_values: vec![at(8, 0, 19.0), at(8, 0, 20.0), at(25, 0, 16.0)],
+                priority: 0,
+            },
+        ];
+        assert!(events[0].issues().is_empty());
+        assert_eq!(
+            validate_exception_schedule(&events),
+            vec![
+                ScheduleDiagnostic {
+                    entry: ScheduleEntry::SpecialEvent(1),
+                    issue: ScheduleIssue::NotACalendar(ObjectId::new(ObjectType::AnalogValue, 1)),
+                },
+                ScheduleDiagnostic {
+                    entry: ScheduleEntry::SpecialEvent(1),
+                    issue: ScheduleIssue::PriorityOutOfRange(0),
+                },
+                ScheduleDiagnostic {
+                    entry: ScheduleEntry::SpecialEvent(1),
+                    issue: ScheduleIssue::TimeNotAscending { index: 1 },
+                },
+                ScheduleDiagnostic {
+                    entry: ScheduleEntry::SpecialEvent(1),
+                    issue: ScheduleIssue::InvalidTime { index: 2 },
+                },
+            ]
+        );
+        assert_eq!(
+            validate_exception_schedule(&events)[1].to_string(),
+            "special event 1: priority 0 is outside 1-16"
+        );
+    }
+
+    #[test]
+    fn weekly_schedule_diagnostics_name_the_day() {
+        let mut week: [Vec<TimeValue>; 7] = Default::default();
+        week[0] = vec![at(8, 0, 21.0), at(17, 0, 16.0)];
+        assert!(validate_weekly_schedule(&week).is_empty());
+        week[4] = vec![at(17, 0, 16.0), at(8, 0, 21.0)];
+        let diagnostics = validate_weekly_schedule(&week);
+        assert_eq!(
+            diagnostics,
+            vec![ScheduleDiagnostic {
+                entry: ScheduleEntry::Weekday(4),
+                issue: ScheduleIssue::TimeNotAscending { index: 1 },
+            }]
+        );
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "weekday 4: time value 1 is not later than the one before it"
+        );
+    }
 }
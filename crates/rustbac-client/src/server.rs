@@ -3,8 +3,11 @@
 //! [`BacnetServer`] binds a [`DataLink`] transport and dispatches incoming
 //! service requests to a user-supplied [`ServiceHandler`].  [`ObjectStore`]
 //! is a convenient thread-safe property store that implements
-//! [`ServiceHandler`] out of the box.
+//! [`ServiceHandler`] out of the box. [`VirtualNetwork`] serves many devices from one
+//! data link behind a virtual network number, for protocol gateways.
 
+use crate::startup::{ForeignRegistration, ServerReady, StartupOptions, StartupState};
+use crate::who_is_policy::{IAmDecision, WhoIsGuard, WhoIsPolicy};
 use crate::ClientDataValue;
 use rustbac_core::apdu::{
     ApduType, ComplexAckHeader, ConfirmedRequestHeader, SimpleAck, UnconfirmedRequestHeader,
@@ -15,7 +18,7 @@ use rustbac_core::encoding::{
     tag::Tag,
     writer::Writer,
 };
-use rustbac_core::npdu::Npdu;
+use rustbac_core::npdu::{Npdu, NpduAddress};
 use rustbac_core::services::i_am::IAmRequest;
 use rustbac_core::services::read_property::SERVICE_READ_PROPERTY;
 use rustbac_core::services::read_property_multiple::SERVICE_READ_PROPERTY_MULTIPLE;
@@ -31,9 +34,10 @@ const SERVICE_SUBSCRIBE_COV: u8 = 0x05;
 const SERVICE_CREATE_OBJECT: u8 = 0x0A;
 /// DeleteObject service choice (0x0B).
 const SERVICE_DELETE_OBJECT: u8 = 0x0B;
-use rustbac_datalink::{DataLink, DataLinkAddress};
+use rustbac_datalink::{BacnetIpTransport, DataLink, DataLinkAddress};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // BacnetServiceError
@@ -258,6 +262,12 @@ pub struct BacnetServer<D: DataLink> {
     /// Stored for future use in I-Am responses and segmentation negotiation.
     #[allow(dead_code)]
     max_apdu: u8,
+    who_is: Mutex<WhoIsGuard>,
+    /// SNET/SADR carried by every reply when the device sits on a virtual network.
+    pub(crate) network_address: Option<NpduAddress>,
+    startup: Option<StartupOptions>,
+    foreign_registration: Option<ForeignRegistration<D>>,
+    ready: watch::Sender<bool>,
 }
 
 impl<D: DataLink> BacnetServer<D> {
@@ -269,15 +279,40 @@ impl<D: DataLink> BacnetServer<D> {
             device_id,
             vendor_id: 0,
             max_apdu: 5, // standard max APDU size index 5 → 1476 bytes
+            who_is: Mutex::new(WhoIsGuard::new(WhoIsPolicy::default())),
+            network_address: None,
+            startup: None,
+            foreign_registration: None,
+            ready: watch::channel(false).0,
         }
     }
 
+    /// Limit how the server answers Who-Is (duplicate suppression, rate limit, jitter).
+    pub fn with_who_is_policy(self, policy: WhoIsPolicy) -> Self {
+        *self.who_is.lock().expect("Who-Is guard lock poisoned") = WhoIsGuard::new(policy);
+        self
+    }
+
     /// Override the vendor ID sent in I-Am responses (default: 0).
     pub fn with_vendor_id(mut self, vendor_id: u16) -> Self {
         self.vendor_id = vendor_id;
         self
     }
 
+    /// Run the startup sequence in `options` when serving starts: announce the device
+    /// with I-Am and answer the Who-Is that follow under a stricter policy; see
+    /// [`crate::startup`].
+    pub fn with_startup(mut self, options: StartupOptions) -> Self {
+        self.startup = Some(options);
+        self
+    }
+
+    /// Reports when the server has joined the network. Take it before calling
+    /// [`serve`](Self::serve).
+    pub fn ready(&self) -> ServerReady {
+        ServerReady::new(self.ready.subscribe())
+    }
+
     /// Run the serve loop.
     ///
     /// Receives frames, parses them, and dispatches:
@@ -287,37 +322,125 @@ impl<D: DataLink> BacnetServer<D> {
     /// - ConfirmedRequest ReadPropertyMultiple (0x0E) → ComplexAck or Error.
     /// - Any other confirmed service → Reject (UNRECOGNIZED_SERVICE = 0x08).
     pub async fn serve(self) {
+        let mut startup = self.startup.map(|options| {
+            let mut who_is = self.who_is.lock().expect("Who-Is guard lock poisoned");
+            let server_policy = who_is.policy();
+            if let Some(policy) = options.startup_who_is_policy {
+                who_is.set_policy(policy);
+            }
+            StartupState::new(
+                options,
+                self.foreign_registration.is_some(),
+                server_policy,
+                tokio::time::Instant::now().into_std(),
+            )
+        });
+        if startup.is_none() {
+            self.ready.send_replace(true);
+        }
         let mut buf = [0u8; 1500];
         loop {
-            let result = self.datalink.recv(&mut buf).await;
+            let next_i_am = self
+                .who_is
+                .lock()
+                .expect("Who-Is guard lock poisoned")
+                .next_due();
+            let next_step = startup
+                .as_ref()
+                .and_then(StartupState::next_due)
+                .map(tokio::time::Instant::from_std);
+            let wake = [next_i_am, next_step].into_iter().flatten().min();
+            let result = match wake {
+                Some(due) => tokio::select! {
+                    result = self.datalink.recv(&mut buf) => Some(result),
+                    _ = tokio::time::sleep_until(due) => None,
+                },
+                None => Some(self.datalink.recv(&mut buf).await),
+            };
             match result {
-                Ok((n, source)) => {
+                Some(Ok((n, source))) => {
                     if let Err(e) = self.handle_frame(&buf[..n], source).await {
                         log::debug!("server: error handling frame: {e:?}");
                     }
                 }
-                Err(e) => {
+                Some(Err(e)) => {
                     log::debug!("server: datalink recv error: {e:?}");
                     // On persistent transport errors avoid a tight busy loop.
                     tokio::task::yield_now().await;
                 }
+                None => {}
+            }
+            self.send_due_i_ams().await;
+            if let Some(state) = &mut startup {
+                self.advance_startup(state).await;
+            }
+        }
+    }
+
+    /// Runs the steps of the startup sequence that are due.
+    async fn advance_startup(&self, state: &mut StartupState) {
+        let now = tokio::time::Instant::now().into_std();
+        if let (Some(at), Some(registration)) = (state.register_at, &self.foreign_registration) {
+            if at <= now {
+                let ttl_seconds = registration.ttl_seconds;
+                let result = (registration.register)(self.datalink.clone(), ttl_seconds).await;
+                if let Err(e) = &result {
+                    log::warn!("server: foreign device registration failed: {e}");
+                }
+                let now = tokio::time::Instant::now().into_std();
+                state.on_registration(result.is_ok(), ttl_seconds, now);
             }
         }
+        if state.registered && state.announcements_left > 0 && state.announce_at <= now {
+            self.send_i_am(state.options().announce_to).await;
+            state.on_announced(now);
+        }
+        if state.policy_until.is_some_and(|until| until <= now) {
+            state.policy_until = None;
+            self.who_is
+                .lock()
+                .expect("Who-Is guard lock poisoned")
+                .set_policy(state.server_policy);
+        }
+        if state.is_ready() {
+            self.ready
+                .send_if_modified(|ready| !std::mem::replace(ready, true));
+        }
+    }
+
+    /// Sends the jittered I-Am responses whose delay has passed.
+    async fn send_due_i_ams(&self) {
+        let due = self
+            .who_is
+            .lock()
+            .expect("Who-Is guard lock poisoned")
+            .take_due(tokio::time::Instant::now());
+        for target in due {
+            self.send_i_am(target).await;
+        }
     }
 
     // ── private helpers ──────────────────────────────────────────────────────
 
+    /// NPDU header for a reply to `request`, routed back through its SNET/SADR if any
+    /// and carrying this device's virtual address as the source.
+    fn reply_npdu(&self, request: &Npdu) -> Npdu {
+        let mut reply = request.reply();
+        reply.source = self.network_address;
+        reply
+    }
+
     async fn handle_frame(
         &self,
         frame: &[u8],
         source: DataLinkAddress,
     ) -> Result<(), rustbac_core::DecodeError> {
         let mut r = Reader::new(frame);
-        let _npdu = Npdu::decode(&mut r)?;
-
-        if r.is_empty() {
+        let npdu = Npdu::decode(&mut r)?;
+        if npdu.is_network_message() || r.is_empty() {
             return Ok(());
         }
+        let reply = self.reply_npdu(&npdu);
 
         let first = r.peek_u8()?;
         let apdu_type = ApduType::from_u8(first >> 4);
@@ -329,7 +452,14 @@ impl<D: DataLink> BacnetServer<D> {
                     // Who-Is — parse optional limits then respond.
                     let limits = decode_who_is_limits(&mut r);
                     if matches_who_is(self.device_id, limits) {
-                        self.send_i_am(source).await;
+                        let decision = self
+                            .who_is
+                            .lock()
+                            .expect("Who-Is guard lock poisoned")
+                            .on_who_is(source, limits, tokio::time::Instant::now());
+                        if decision == IAmDecision::Send {
+                            self.send_i_am(source).await;
+                        }
                     }
                 }
                 // All other unconfirmed services are ignored.
@@ -339,31 +469,36 @@ impl<D: DataLink> BacnetServer<D> {
                 let invoke_id = header.invoke_id;
                 match header.service_choice {
                     SERVICE_READ_PROPERTY => {
-                        self.handle_read_property(&mut r, invoke_id, source).await;
+                        self.handle_read_property(&mut r, invoke_id, source, reply)
+                            .await;
                     }
                     SERVICE_WRITE_PROPERTY => {
-                        self.handle_write_property(&mut r, invoke_id, source).await;
+                        self.handle_write_property(&mut r, invoke_id, source, reply)
+                            .await;
                     }
                     SERVICE_READ_PROPERTY_MULTIPLE => {
-                        self.handle_read_property_multiple(&mut r, invoke_id, source)
+                        self.handle_read_property_multiple(&mut r, invoke_id, source, reply)
                             .await;
                     }
                     SERVICE_WRITE_PROPERTY_MULTIPLE => {
-                        self.handle_write_property_multiple(&mut r, invoke_id, source)
+                        self.handle_write_property_multiple(&mut r, invoke_id, source, reply)
                             .await;
                     }
                     SERVICE_SUBSCRIBE_COV => {
-                        self.handle_subscribe_cov(&mut r, invoke_id, source).await;
+                        self.handle_subscribe_cov(&mut r, invoke_id, source, reply)
+                            .await;
                     }
                     SERVICE_CREATE_OBJECT => {
-                        self.handle_create_object(&mut r, invoke_id, source).await;
+                        self.handle_create_object(&mut r, invoke_id, source, reply)
+                            .await;
                     }
                     SERVICE_DELETE_OBJECT => {
-                        self.handle_delete_object(&mut r, invoke_id, source).await;
+                        self.handle_delete_object(&mut r, invoke_id, source, reply)
+                            .await;
                     }
                     _ => {
                         // Unknown service — send Reject with UNRECOGNIZED_SERVICE.
-                        self.send_reject(invoke_id, 0x08, source).await;
+                        self.send_reject(invoke_id, 0x08, source, reply).await;
                     }
                 }
             }
@@ -388,7 +523,9 @@ impl<D: DataLink> BacnetServer<D> {
         };
         let mut buf = [0u8; 128];
         let mut w = Writer::new(&mut buf);
-        if Npdu::new(0).encode(&mut w).is_err() {
+        let mut npdu = Npdu::new(0);
+        npdu.source = self.network_address;
+        if npdu.encode(&mut w).is_err() {
             return;
         }
         if req.encode(&mut w).is_err() {
@@ -402,6 +539,7 @@ impl<D: DataLink> BacnetServer<D> {
         r: &mut Reader<'_>,
         invoke_id: u8,
         source: DataLinkAddress,
+        reply: Npdu,
     ) {
         // Decode: object_id [0], property_id [1], optional array_index [2].
         let object_id = match crate::decode_ctx_object_id(r) {
@@ -425,7 +563,7 @@ impl<D: DataLink> BacnetServer<D> {
                 let borrowed = client_value_to_borrowed(&value);
                 let mut buf = [0u8; 1400];
                 let mut w = Writer::new(&mut buf);
-                if Npdu::new(0).encode(&mut w).is_err() {
+                if reply.encode(&mut w).is_err() {
                     return;
                 }
                 if (ComplexAckHeader {
@@ -459,7 +597,7 @@ impl<D: DataLink> BacnetServer<D> {
                 let _ = self.datalink.send(source, w.as_written()).await;
             }
             Err(err) => {
-                self.send_error(invoke_id, SERVICE_READ_PROPERTY, err, source)
+                self.send_error(invoke_id, SERVICE_READ_PROPERTY, err, source, reply)
                     .await;
             }
         }
@@ -470,6 +608,7 @@ impl<D: DataLink> BacnetServer<D> {
         r: &mut Reader<'_>,
         invoke_id: u8,
         source: DataLinkAddress,
+        reply: Npdu,
     ) {
         // Decode: object_id [0], property_id [1], optional array_index [2], value [3], optional priority [4].
         let object_id = match crate::decode_ctx_object_id(r) {
@@ -538,7 +677,7 @@ impl<D: DataLink> BacnetServer<D> {
             Ok(()) => {
                 let mut buf = [0u8; 32];
                 let mut w = Writer::new(&mut buf);
-                if Npdu::new(0).encode(&mut w).is_err() {
+                if reply.encode(&mut w).is_err() {
                     return;
                 }
                 if (SimpleAck {
@@ -553,7 +692,7 @@ impl<D: DataLink> BacnetServer<D> {
                 let _ = self.datalink.send(source, w.as_written()).await;
             }
             Err(err) => {
-                self.send_error(invoke_id, SERVICE_WRITE_PROPERTY, err, source)
+                self.send_error(invoke_id, SERVICE_WRITE_PROPERTY, err, source, reply)
                     .await;
             }
         }
@@ -564,6 +703,7 @@ impl<D: DataLink> BacnetServer<D> {
         r: &mut Reader<'_>,
         invoke_id: u8,
         source: DataLinkAddress,
+        reply: Npdu,
     ) {
         type PropRefs = Vec<(PropertyId, Option<u32>)>;
         // Collect all (object_id, [(property_id, array_index)]) specs from the request.
@@ -630,7 +770,7 @@ impl<D: DataLink> BacnetServer<D> {
         // Build response buffer.
         let mut buf = [0u8; 1400];
         let mut w = Writer::new(&mut buf);
-        if Npdu::new(0).encode(&mut w).is_err() {
+        if reply.encode(&mut w).is_err() {
             return;
         }
         if (ComplexAckHeader {
@@ -722,6 +862,7 @@ impl<D: DataLink> BacnetServer<D> {
         r: &mut Reader<'_>,
         invoke_id: u8,
         source: DataLinkAddress,
+        reply: Npdu,
     ) {
         // Parse write-access-specifications and call write_property for each property.
         while !r.is_empty() {
@@ -797,8 +938,14 @@ impl<D: DataLink> BacnetServer<D> {
                     client_val,
                     priority,
                 ) {
-                    self.send_error(invoke_id, SERVICE_WRITE_PROPERTY_MULTIPLE, err, source)
-                        .await;
+                    self.send_error(
+                        invoke_id,
+                        SERVICE_WRITE_PROPERTY_MULTIPLE,
+                        err,
+                        source,
+                        reply,
+                    )
+                    .await;
                     return;
                 }
             }
@@ -806,7 +953,7 @@ impl<D: DataLink> BacnetServer<D> {
         // All properties written successfully — send SimpleAck.
         let mut buf = [0u8; 32];
         let mut w = Writer::new(&mut buf);
-        if Npdu::new(0).encode(&mut w).is_err() {
+        if reply.encode(&mut w).is_err() {
             return;
         }
         if (SimpleAck {
@@ -826,6 +973,7 @@ impl<D: DataLink> BacnetServer<D> {
         r: &mut Reader<'_>,
         invoke_id: u8,
         source: DataLinkAddress,
+        reply: Npdu,
     ) {
         // subscriberProcessIdentifier [0]
         let subscriber_process_id = match Tag::decode(r) {
@@ -870,7 +1018,7 @@ impl<D: DataLink> BacnetServer<D> {
             Ok(()) => {
                 let mut buf = [0u8; 32];
                 let mut w = Writer::new(&mut buf);
-                if Npdu::new(0).encode(&mut w).is_err() {
+                if reply.encode(&mut w).is_err() {
                     return;
                 }
                 if (SimpleAck {
@@ -885,7 +1033,7 @@ impl<D: DataLink> BacnetServer<D> {
                 let _ = self.datalink.send(source, w.as_written()).await;
             }
             Err(err) => {
-                self.send_error(invoke_id, SERVICE_SUBSCRIBE_COV, err, source)
+                self.send_error(invoke_id, SERVICE_SUBSCRIBE_COV, err, source, reply)
                     .await;
             }
         }
@@ -896,6 +1044,7 @@ impl<D: DataLink> BacnetServer<D> {
         r: &mut Reader<'_>,
         invoke_id: u8,
         source: DataLinkAddress,
+        reply: Npdu,
     ) {
         // objectSpecifier [0] opening
         match Tag::decode(r) {
@@ -921,7 +1070,7 @@ impl<D: DataLink> BacnetServer<D> {
             Ok(created_id) => {
                 let mut buf = [0u8; 64];
                 let mut w = Writer::new(&mut buf);
-                if Npdu::new(0).encode(&mut w).is_err() {
+                if reply.encode(&mut w).is_err() {
                     return;
                 }
                 if (ComplexAckHeader {
@@ -943,7 +1092,7 @@ impl<D: DataLink> BacnetServer<D> {
                 let _ = self.datalink.send(source, w.as_written()).await;
             }
             Err(err) => {
-                self.send_error(invoke_id, SERVICE_CREATE_OBJECT, err, source)
+                self.send_error(invoke_id, SERVICE_CREATE_OBJECT, err, source, reply)
                     .await;
             }
         }
@@ -954,6 +1103,7 @@ impl<D: DataLink> BacnetServer<D> {
         r: &mut Reader<'_>,
         invoke_id: u8,
         source: DataLinkAddress,
+        reply: Npdu,
     ) {
         // objectIdentifier — application-tagged
         let object_id = match crate::decode_ctx_object_id(r) {
@@ -965,7 +1115,7 @@ impl<D: DataLink> BacnetServer<D> {
             Ok(()) => {
                 let mut buf = [0u8; 32];
                 let mut w = Writer::new(&mut buf);
-                if Npdu::new(0).encode(&mut w).is_err() {
+                if reply.encode(&mut w).is_err() {
                     return;
                 }
                 if (SimpleAck {
@@ -980,7 +1130,7 @@ impl<D: DataLink> BacnetServer<D> {
                 let _ = self.datalink.send(source, w.as_written()).await;
             }
             Err(err) => {
-                self.send_error(invoke_id, SERVICE_DELETE_OBJECT, err, source)
+                self.send_error(invoke_id, SERVICE_DELETE_OBJECT, err, source, reply)
                     .await;
             }
         }
@@ -992,11 +1142,12 @@ impl<D: DataLink> BacnetServer<D> {
         service_choice: u8,
         err: BacnetServiceError,
         target: DataLinkAddress,
+        reply: Npdu,
     ) {
         let (class, code) = err.to_error_class_code();
         let mut buf = [0u8; 64];
         let mut w = Writer::new(&mut buf);
-        if Npdu::new(0).encode(&mut w).is_err() {
+        if reply.encode(&mut w).is_err() {
             return;
         }
         // Error PDU header: type=5 (Error)
@@ -1038,10 +1189,10 @@ impl<D: DataLink> BacnetServer<D> {
         let _ = self.datalink.send(target, w.as_written()).await;
     }
 
-    async fn send_reject(&self, invoke_id: u8, reason: u8, target: DataLinkAddress) {
+    async fn send_reject(&self, invoke_id: u8, reason: u8, target: DataLinkAddress, reply: Npdu) {
         let mut buf = [0u8; 16];
         let mut w = Writer::new(&mut buf);
-        if Npdu::new(0).encode(&mut w).is_err() {
+        if reply.encode(&mut w).is_err() {
             return;
         }
         // Reject PDU: type=6 (Reject)
@@ -1058,6 +1209,200 @@ impl<D: DataLink> BacnetServer<D> {
     }
 }
 
+impl BacnetServer<BacnetIpTransport> {
+    /// Register with the transport's BBMD as a foreign device with `ttl_seconds` before
+    /// the startup announcements, and renew at 75 % of the TTL while serving. Needs a
+    /// transport bound with [`BacnetIpTransport::bind_foreign`] and
+    /// [`with_startup`](Self::with_startup).
+    pub fn with_foreign_device_registration(mut self, ttl_seconds: u16) -> Self {
+        self.foreign_registration = Some(ForeignRegistration {
+            ttl_seconds: ttl_seconds.max(1),
+            register: Box::new(|datalink, ttl_seconds| {
+                Box::pin(async move { datalink.register_foreign_device(ttl_seconds).await })
+            }),
+        });
+        self
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// VirtualNetwork
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Many virtual BACnet devices served from one data link, behind a virtual network
+/// number, as a protocol gateway presents each upstream asset (e.g. a Modbus device)
+/// as its own BACnet device.
+///
+/// Each device has its own instance number and [`ServiceHandler`] and is reachable at
+/// [`virtual_address`](Self::virtual_address): its replies and I-Am carry that address
+/// as SNET/SADR, and requests routed to it with DNET/DADR are delivered to it alone.
+/// Who-Is reaches every device. A confirmed request without a DNET goes to the device
+/// whose handler knows the addressed object.
+pub struct VirtualNetwork<D: DataLink> {
+    network: u16,
+    datalink: Arc<D>,
+    devices: Vec<BacnetServer<Arc<D>>>,
+    who_is_policy: WhoIsPolicy,
+    vendor_id: u16,
+}
+
+impl<D: DataLink> VirtualNetwork<D> {
+    /// Create an empty virtual network with the given network number.
+    pub fn new(network: u16, datalink: D) -> Self {
+        Self {
+            network,
+            datalink: Arc::new(datalink),
+            devices: Vec::new(),
+            who_is_policy: WhoIsPolicy::default(),
+            vendor_id: 0,
+        }
+    }
+
+    /// Apply `policy` to every device added afterwards; jitter keeps a large gateway
+    /// from answering a global Who-Is in one burst.
+    pub fn with_who_is_policy(mut self, policy: WhoIsPolicy) -> Self {
+        self.who_is_policy = policy;
+        self
+    }
+
+    /// Vendor ID sent in the I-Am of every device added afterwards (default: 0).
+    pub fn with_vendor_id(mut self, vendor_id: u16) -> Self {
+        self.vendor_id = vendor_id;
+        self
+    }
+
+    /// The virtual network number.
+    pub fn network(&self) -> u16 {
+        self.network
+    }
+
+    /// Add a device with the given instance number, served by `handler`. A device
+    /// already present with that instance is replaced.
+    pub fn add_device(&mut self, instance: u32, handler: impl ServiceHandler) {
+        self.remove_device(instance);
+        let mut device = BacnetServer::new(self.datalink.clone(), instance, handler)
+            .with_who_is_policy(self.who_is_policy)
+            .with_vendor_id(self.vendor_id);
+        device.network_address = Some(self.virtual_address(instance));
+        self.devices.push(device);
+    }
+
+    /// Remove the device with the given instance number; returns `false` if absent.
+    pub fn remove_device(&mut self, instance: u32) -> bool {
+        let before = self.devices.len();
+        self.devices.retain(|device| device.device_id != instance);
+        self.devices.len() != before
+    }
+
+    /// Instance numbers of all devices, in the order they were added.
+    pub fn device_instances(&self) -> Vec<u32> {
+        self.devices.iter().map(|device| device.device_id).collect()
+    }
+
+    /// Network address of a device: this network and the instance number as a
+    /// three-byte big-endian MAC.
+    pub fn virtual_address(&self, instance: u32) -> NpduAddress {
+        let [_, high, mid, low] = instance.to_be_bytes();
+        NpduAddress {
+            network: self.network,
+            mac: [high, mid, low, 0, 0, 0],
+            mac_len: 3,
+        }
+    }
+
+    /// Run the serve loop, dispatching each request to the devices it addresses.
+    pub async fn serve(self) {
+        let mut buf = [0u8; 1500];
+        loop {
+            let next_i_am = self
+                .devices
+                .iter()
+                .filter_map(|device| {
+                    device
+                        .who_is
+                        .lock()
+                        .expect("Who-Is guard lock poisoned")
+                        .next_due()
+                })
+                .min();
+            let result = match next_i_am {
+                Some(due) => tokio::select! {
+                    result = self.datalink.recv(&mut buf) => Some(result),
+                    _ = tokio::time::sleep_until(due) => None,
+                },
+                None => Some(self.datalink.recv(&mut buf).await),
+            };
+            match result {
+                Some(Ok((n, source))) => {
+                    if let Err(e) = self.handle_frame(&buf[..n], source).await {
+                        log::debug!("gateway: error handling frame: {e:?}");
+                    }
+                }
+                Some(Err(e)) => {
+                    log::debug!("gateway: datalink recv error: {e:?}");
+                    tokio::task::yield_now().await;
+                }
+                None => {}
+            }
+            for device in &self.devices {
+                device.send_due_i_ams().await;
+            }
+        }
+    }
+
+    async fn handle_frame(
+        &self,
+        frame: &[u8],
+        source: DataLinkAddress,
+    ) -> Result<(), rustbac_core::DecodeError> {
+        for device in self.recipients(frame)? {
+            device.handle_frame(frame, source).await?;
+        }
+        Ok(())
+    }
+
+    /// The devices a frame is addressed to.
+    fn recipients(
+        &self,
+        frame: &[u8],
+    ) -> Result<Vec<&BacnetServer<Arc<D>>>, rustbac_core::DecodeError> {
+        let mut r = Reader::new(frame);
+        let npdu = Npdu::decode(&mut r)?;
+        if npdu.is_network_message() || !npdu.is_for_network(self.network) || r.is_empty() {
+            return Ok(Vec::new());
+        }
+        if let Some(dest) = npdu.destination.filter(|dest| !dest.is_broadcast()) {
+            return Ok(self
+                .devices
+                .iter()
+                .filter(|device| {
+                    device
+                        .network_address
+                        .is_some_and(|address| address.mac() == dest.mac())
+                })
+                .collect());
+        }
+        if ApduType::from_u8(r.peek_u8()? >> 4) != Some(ApduType::ConfirmedRequest) {
+            return Ok(self.devices.iter().collect());
+        }
+
+        // Not routed: pick the device whose handler knows the addressed object.
+        ConfirmedRequestHeader::decode(&mut r)?;
+        if let Ok(object_id) = crate::decode_ctx_object_id(&mut r) {
+            if let Some(device) = self.devices.iter().find(|device| {
+                device
+                    .handler
+                    .read_property(object_id, PropertyId::ObjectIdentifier, None)
+                    != Err(BacnetServiceError::UnknownObject)
+            }) {
+                return Ok(vec![device]);
+            }
+        }
+        // Nobody owns it; let the first device answer with an error rather than time out.
+        Ok(self.devices.first().into_iter().collect())
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Free-standing helpers
 // ─────────────────────────────────────────────────────────────────────────────
@@ -1401,7 +1746,7 @@ mod tests {
         store.set(
             device_id,
             PropertyId::ObjectName,
-            ClientDataValue::CharacterString("TestDevice".to_string()),
+            ClientDataValue::CharacterString("TestDevice".into()),
         );
         let handler = ObjectStoreHandler::new(store.clone());
         let dl = MockDataLink::default();
@@ -1414,6 +1759,92 @@ mod tests {
         DataLinkAddress::Ip("127.0.0.1:47808".parse().unwrap())
     }
 
+    #[tokio::test]
+    async fn virtual_network_routes_requests_to_each_device() {
+        use rustbac_core::encoding::primitives::encode_ctx_unsigned;
+        use rustbac_core::services::i_am::IAmRequest;
+        use rustbac_core::services::who_is::WhoIsRequest;
+
+        let dl = MockDataLink::default();
+        let sent = dl.sent.clone();
+        let mut network = VirtualNetwork::new(7, dl).with_vendor_id(99);
+        for instance in [1001, 1002] {
+            let store = Arc::new(ObjectStore::new());
+            store.set(
+                ObjectId::new(ObjectType::AnalogInput, 1),
+                PropertyId::PresentValue,
+                ClientDataValue::Real(instance as f32),
+            );
+            network.add_device(instance, ObjectStoreHandler::new(store));
+        }
+        assert_eq!(network.device_instances(), vec![1001, 1002]);
+
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        Npdu::new(0).encode(&mut w).unwrap();
+        WhoIsRequest::global().encode(&mut w).unwrap();
+        network
+            .handle_frame(w.as_written(), source())
+            .await
+            .unwrap();
+
+        // ReadProperty routed to the second device, from a client on network 3.
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        let mut npdu = Npdu::new(0);
+        npdu.destination = Some(network.virtual_address(1002));
+        npdu.source = Some(NpduAddress {
+            network: 3,
+            mac: [9, 0, 0, 0, 0, 0],
+            mac_len: 1,
+        });
+        npdu.hop_count = Some(255);
+        npdu.encode(&mut w).unwrap();
+        rustbac_core::apdu::ConfirmedRequestHeader {
+            segmented: false,
+            more_follows: false,
+            segmented_response_accepted: true,
+            max_segments: 0,
+            max_apdu: 5,
+            invoke_id: 4,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_READ_PROPERTY,
+        }
+        .encode(&mut w)
+        .unwrap();
+        encode_ctx_unsigned(&mut w, 0, ObjectId::new(ObjectType::AnalogInput, 1).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::PresentValue.to_u32()).unwrap();
+        network
+            .handle_frame(w.as_written(), source())
+            .await
+            .unwrap();
+
+        let sent = sent.lock().expect("poisoned");
+        assert_eq!(sent.len(), 3);
+        for (frame, instance) in sent[..2].iter().zip([1001, 1002]) {
+            let mut r = Reader::new(&frame.1);
+            let npdu = Npdu::decode(&mut r).unwrap();
+            assert_eq!(npdu.source, Some(network.virtual_address(instance)));
+            UnconfirmedRequestHeader::decode(&mut r).unwrap();
+            let i_am = IAmRequest::decode_after_header(&mut r).unwrap();
+            assert_eq!(i_am.device_id.instance(), instance);
+            assert_eq!(i_am.vendor_id, 99);
+        }
+        let mut r = Reader::new(&sent[2].1);
+        let npdu = Npdu::decode(&mut r).unwrap();
+        assert_eq!(npdu.source, Some(network.virtual_address(1002)));
+        assert_eq!(npdu.destination.map(|dest| dest.network), Some(3));
+        assert_eq!(ComplexAckHeader::decode(&mut r).unwrap().invoke_id, 4);
+        crate::decode_ctx_object_id(&mut r).unwrap();
+        crate::decode_ctx_unsigned(&mut r).unwrap();
+        assert_eq!(Tag::decode(&mut r).unwrap(), Tag::Opening { tag_num: 3 });
+        assert_eq!(
+            rustbac_core::encoding::primitives::decode_app_real(&mut r).unwrap(),
+            1002.0
+        );
+    }
+
     #[tokio::test]
     async fn object_store_set_get_remove() {
         let store = ObjectStore::new();
@@ -1559,7 +1990,7 @@ mod tests {
         // Verify store updated.
         assert_eq!(
             store.get(device_id, PropertyId::ObjectName),
-            Some(ClientDataValue::CharacterString("NewName".to_string()))
+            Some(ClientDataValue::CharacterString("NewName".into()))
         );
     }
 
@@ -1588,6 +2019,199 @@ mod tests {
         assert_eq!(iam.device_id.instance(), 42);
     }
 
+    #[tokio::test]
+    async fn who_is_rate_limit_suppresses_i_am_flood() {
+        let (server, sent, _store) = make_server();
+        let server = server
+            .with_who_is_policy(crate::WhoIsPolicy::default().with_max_responses_per_second(3));
+
+        let mut req_buf = [0u8; 32];
+        let mut w = Writer::new(&mut req_buf);
+        Npdu::new(0).encode(&mut w).unwrap();
+        rustbac_core::apdu::UnconfirmedRequestHeader {
+            service_choice: 0x08,
+        }
+        .encode(&mut w)
+        .unwrap();
+        for port in 0..50u16 {
+            let requester = DataLinkAddress::Ip(([10, 0, 0, 1], 47808 + port).into());
+            server
+                .handle_frame(w.as_written(), requester)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(sent.lock().expect("poisoned").len(), 3);
+    }
+
+    #[tokio::test]
+    async fn startup_registers_then_announces_and_signals_ready() {
+        use crate::startup::{ForeignRegistration, StartupOptions, StartupState};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::Duration;
+
+        let (mut server, sent, _store) = make_server();
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = attempts.clone();
+        server.foreign_registration = Some(ForeignRegistration {
+            ttl_seconds: 60,
+            register: Box::new(move |_, _| {
+                // The BBMD does not answer the first registration.
+                let failed = counter.fetch_add(1, Ordering::SeqCst) == 0;
+                Box::pin(async move {
+                    if failed {
+                        Err(rustbac_datalink::DataLinkError::Disconnected)
+                    } else {
+                        Ok(())
+                    }
+                })
+            }),
+        });
+        let broadcast = DataLinkAddress::Ip(([10, 0, 0, 255], 47808).into());
+        let options = StartupOptions::default()
+            .with_announce_to(broadcast)
+            .with_announcements(2, Duration::ZERO)
+            .with_startup_who_is_policy(
+                crate::WhoIsPolicy::default().with_max_responses_per_second(1),
+                Duration::ZERO,
+            );
+        let server = server.with_startup(options);
+        let ready = server.ready();
+        let now = std::time::Instant::now();
+        let mut state = StartupState::new(options, true, crate::WhoIsPolicy::default(), now);
+
+        server.advance_startup(&mut state).await;
+        assert!(!ready.is_ready());
+        assert!(sent.lock().expect("poisoned").is_empty());
+        assert!(state.register_at.unwrap() >= now + Duration::from_secs(1));
+
+        state.register_at = Some(now);
+        server.advance_startup(&mut state).await;
+        assert!(ready.is_ready());
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        // Renewal at 75 % of the TTL; the startup Who-Is policy has ended.
+        assert!(state.register_at.unwrap() >= now + Duration::from_secs(45));
+        assert_eq!(state.policy_until, None);
+        assert_eq!(
+            server.who_is.lock().expect("poisoned").policy(),
+            crate::WhoIsPolicy::default()
+        );
+
+        server.advance_startup(&mut state).await;
+        let sent = sent.lock().expect("poisoned");
+        assert_eq!(sent.len(), 2);
+        for (target, frame) in sent.iter() {
+            assert_eq!(*target, broadcast);
+            let mut r = Reader::new(frame);
+            Npdu::decode(&mut r).unwrap();
+            rustbac_core::apdu::UnconfirmedRequestHeader::decode(&mut r).unwrap();
+            let iam = IAmRequest::decode_after_header(&mut r).unwrap();
+            assert_eq!(iam.device_id.instance(), 42);
+        }
+        assert_eq!(state.next_due(), state.register_at);
+    }
+
+    /// A link that sends like [`MockDataLink`] but never receives, so `serve` only wakes
+    /// for its timers.
+    #[derive(Clone, Default)]
+    struct QuietDataLink(MockDataLink);
+
+    impl rustbac_datalink::DataLink for QuietDataLink {
+        async fn send(
+            &self,
+            address: DataLinkAddress,
+            payload: &[u8],
+        ) -> Result<(), rustbac_datalink::DataLinkError> {
+            self.0.send(address, payload).await
+        }
+
+        async fn recv(
+            &self,
+            _buf: &mut [u8],
+        ) -> Result<(usize, DataLinkAddress), rustbac_datalink::DataLinkError> {
+            std::future::pending().await
+        }
+    }
+
+    /// A quiet server that registers, failing the first `failures` attempts, and the
+    /// counter of attempts.
+    fn registering_server(
+        dl: QuietDataLink,
+        failures: usize,
+    ) -> (
+        BacnetServer<QuietDataLink>,
+        Arc<std::sync::atomic::AtomicUsize>,
+    ) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::Duration;
+
+        let handler = ObjectStoreHandler::new(Arc::new(ObjectStore::new()));
+        let mut server = BacnetServer::new(dl, 42, handler);
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = attempts.clone();
+        server.foreign_registration = Some(ForeignRegistration {
+            ttl_seconds: 60,
+            register: Box::new(move |_, _| {
+                let failed = counter.fetch_add(1, Ordering::SeqCst) < failures;
+                Box::pin(async move {
+                    if failed {
+                        Err(rustbac_datalink::DataLinkError::Disconnected)
+                    } else {
+                        Ok(())
+                    }
+                })
+            }),
+        });
+        let options = StartupOptions::default().with_announcements(1, Duration::ZERO);
+        (server.with_startup(options), attempts)
+    }
+
+    #[tokio::test]
+    async fn failed_registrations_are_retried_until_the_bbmd_answers() {
+        use std::sync::atomic::Ordering;
+        use std::time::Duration;
+
+        let dl = QuietDataLink::default();
+        let sent = dl.0.sent.clone();
+        let (server, attempts) = registering_server(dl, 2);
+        let mut ready = server.ready();
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            tokio::select! {
+                () = server.serve() => unreachable!("serve returned"),
+                () = ready.wait() => {}
+            }
+        })
+        .await
+        .expect("ready after the third registration");
+        assert!(ready.is_ready());
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        // Announced only once registered.
+        assert_eq!(sent.lock().expect("poisoned").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn ready_is_not_reported_while_registration_fails() {
+        use std::sync::atomic::Ordering;
+        use std::time::Duration;
+
+        let dl = QuietDataLink::default();
+        let sent = dl.0.sent.clone();
+        let (server, attempts) = registering_server(dl, usize::MAX);
+        let mut ready = server.ready();
+
+        let _ = tokio::time::timeout(Duration::from_millis(1500), server.serve()).await;
+        // Tried at startup and again after the retry delay, announcing nothing.
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        assert!(sent.lock().expect("poisoned").is_empty());
+        assert!(!ready.is_ready());
+        // With the server gone, waiting returns at once and still reports not ready.
+        tokio::time::timeout(Duration::from_millis(100), ready.wait())
+            .await
+            .expect("wait returns once the server is dropped");
+        assert!(!ready.is_ready());
+    }
+
     #[tokio::test]
     async fn unknown_service_sends_reject() {
         let (server, sent, _store) = make_server();
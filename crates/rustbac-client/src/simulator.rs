@@ -2,33 +2,224 @@
 //!
 //! [`SimulatedDevice`] responds to Who-Is, ReadProperty, and WriteProperty
 //! requests. Useful for testing and development without physical hardware.
+//! [`SimulatedNetwork`] hosts many such devices behind a single data link.
+//! [`SimulatorFaults`] adds latency, lost replies and forced error responses.
+//!
+//! [`SimulatedDevice::add_trend_log`] adds a Trend Log that samples another property into
+//! a ring buffer served through ReadRange.
+//!
+//! Output objects, and value objects added with a `Priority_Array` or
+//! `Relinquish_Default` property, are commandable: writes to `Present_Value` go into a
+//! 16-level priority array (priority 16 when the request has none, Null relinquishes) and
+//! `Present_Value` follows the highest-priority command, or `Relinquish_Default` when
+//! every level is Null.
 
+use crate::who_is_policy::{IAmDecision, WhoIsGuard, WhoIsPolicy};
 use crate::{ClientDataValue, ClientError};
 use rustbac_core::apdu::{
-    ApduType, ComplexAckHeader, ConfirmedRequestHeader, SimpleAck, UnconfirmedRequestHeader,
+    AbortPdu, ApduType, ComplexAckHeader, ConfirmedRequestHeader, SimpleAck,
+    UnconfirmedRequestHeader,
 };
 use rustbac_core::encoding::{
-    primitives::{decode_unsigned, encode_ctx_unsigned},
+    primitives::{
+        decode_app_signed, decode_app_unsigned, decode_unsigned, encode_app_enumerated,
+        encode_ctx_object_id, encode_ctx_unsigned,
+    },
     reader::Reader,
     tag::Tag,
     writer::Writer,
 };
-use rustbac_core::npdu::Npdu;
+use rustbac_core::npdu::{Npdu, NpduAddress};
 use rustbac_core::services::i_am::IAmRequest;
 use rustbac_core::services::read_property::SERVICE_READ_PROPERTY;
-use rustbac_core::services::value_codec::encode_application_data_value;
+use rustbac_core::services::read_range::{LogDatum, LogRecord, SERVICE_READ_RANGE};
+use rustbac_core::services::value_codec::{
+    decode_application_data_value, encode_application_data_value,
+};
 use rustbac_core::services::write_property::SERVICE_WRITE_PROPERTY;
-use rustbac_core::types::{DataValue, ObjectId, ObjectType, PropertyId};
+use rustbac_core::types::{
+    BitString, DataValue, Date, ErrorClass, ErrorCode, ObjectId, ObjectType, PropertyId, Time,
+};
+use rustbac_core::DecodeError;
 use rustbac_datalink::{DataLink, DataLinkAddress};
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 use tokio::sync::RwLock;
+use tokio::time::Instant;
+
+/// Faults a [`SimulatedDevice`] injects into its replies, to exercise client timeout and
+/// retry handling.
+///
+/// Random choices come from a generator seeded with [`seed`](Self::seed), so a given
+/// configuration drops and delays the same replies on every run. The device answers one
+/// request at a time, so latency also holds back the requests queued behind it.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SimulatorFaults {
+    /// Fixed delay before every reply (including I-Am).
+    pub latency: Duration,
+    /// Extra random delay, up to this bound, added to `latency`.
+    pub jitter: Duration,
+    /// Probability in `0.0..=1.0` that a reply is silently dropped.
+    pub drop_probability: f64,
+    /// Answer every confirmed request with this instead of serving it.
+    pub forced_response: Option<ForcedResponse>,
+    /// Seed for the jitter and drop decisions.
+    pub seed: u64,
+}
+
+impl SimulatorFaults {
+    /// Delays every reply by `latency`.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Adds a random delay of up to `jitter` to every reply.
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Drops each reply with the given probability (clamped to `0.0..=1.0`).
+    pub fn with_drop_probability(mut self, probability: f64) -> Self {
+        self.drop_probability = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Answers every confirmed request with `response`.
+    pub fn with_forced_response(mut self, response: ForcedResponse) -> Self {
+        self.forced_response = Some(response);
+        self
+    }
+
+    /// Seeds the jitter and drop decisions.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/// A failure reply a [`SimulatedDevice`] sends in place of the real response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForcedResponse {
+    /// Error PDU with the given class and code.
+    Error { error_class: u32, error_code: u32 },
+    /// Reject PDU with the given reason.
+    Reject { reason: u8 },
+    /// Abort PDU, sent by the server, with the given reason.
+    Abort { reason: u8 },
+}
+
+/// [`SimulatorFaults`] plus the state of its random generator.
+#[derive(Debug)]
+struct FaultState {
+    faults: SimulatorFaults,
+    rng: u64,
+}
+
+impl FaultState {
+    fn new(faults: SimulatorFaults) -> Self {
+        Self {
+            faults,
+            rng: faults.seed,
+        }
+    }
+
+    /// Decides the fate of the next reply: `None` to drop it, otherwise its delay.
+    fn next_reply(&mut self) -> Option<Duration> {
+        if self.faults.drop_probability > 0.0 && self.next_unit() < self.faults.drop_probability {
+            return None;
+        }
+        let jitter = u64::try_from(self.faults.jitter.as_micros()).unwrap_or(u64::MAX);
+        let extra = if jitter == 0 {
+            0
+        } else {
+            self.next_u64() % (jitter + 1)
+        };
+        Some(self.faults.latency + Duration::from_micros(extra))
+    }
+
+    /// Uniform in `0.0..1.0`.
+    fn next_unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        // splitmix64: reproducible from any seed, including zero.
+        self.rng = self.rng.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Number of command priority levels.
+const PRIORITY_LEVELS: usize = 16;
+/// Priority reserved for minimum on/off time; clients may not write it.
+const MINIMUM_ON_OFF_PRIORITY: usize = 6;
+/// Reject reason: missing-required-parameter.
+const REJECT_MISSING_REQUIRED_PARAMETER: u8 = 5;
+/// Reject reason: parameter-out-of-range.
+const REJECT_PARAMETER_OUT_OF_RANGE: u8 = 6;
+/// Error code: invalid-array-index.
+const ERROR_INVALID_ARRAY_INDEX: u32 = 42;
+
+/// Error code: property-is-not-a-list.
+const ERROR_PROPERTY_IS_NOT_A_LIST: u32 = 22;
+/// Error code: datatype-not-supported.
+const ERROR_DATATYPE_NOT_SUPPORTED: u32 = 47;
+/// Most log records returned by one ReadRange; keeps the response in one APDU.
+const MAX_READ_RANGE_ITEMS: usize = 40;
+
+/// Commands held for a commandable `Present_Value`; `None` is a relinquished level.
+type PriorityArray = [Option<ClientDataValue>; PRIORITY_LEVELS];
+
+/// A Trend Log object sampling one property of the device.
+#[derive(Debug)]
+struct TrendLog {
+    id: ObjectId,
+    source: (ObjectId, PropertyId),
+    interval: Duration,
+    next_sample: Instant,
+    capacity: usize,
+    records: VecDeque<TrendRecord>,
+    total_record_count: u32,
+}
+
+/// One sample in a [`TrendLog`] buffer.
+#[derive(Debug, Clone)]
+struct TrendRecord {
+    sequence: u32,
+    date: Date,
+    time: Time,
+    /// The sampled value, or the error class and code when it could not be read.
+    value: Result<ClientDataValue, (u32, u32)>,
+    status_flags: Option<ClientDataValue>,
+}
+
+/// The range selector of a ReadRange request.
+#[derive(Debug, Clone, Copy)]
+enum RangeRequest {
+    All,
+    Position(u32, i32),
+    Sequence(u32, i32),
+    Time(Date, Time, i32),
+}
 
 /// A simulated BACnet device.
 pub struct SimulatedDevice<D: DataLink> {
     pub device_id: ObjectId,
     objects: Arc<RwLock<HashMap<ObjectId, HashMap<PropertyId, ClientDataValue>>>>,
     datalink: D,
+    who_is: Mutex<WhoIsGuard>,
+    /// SNET/SADR carried by every reply when the device sits on a virtual network.
+    network_address: Option<NpduAddress>,
+    faults: Mutex<FaultState>,
+    priority_arrays: RwLock<HashMap<ObjectId, PriorityArray>>,
+    priority_required: bool,
+    trend_logs: Mutex<Vec<TrendLog>>,
 }
 
 impl<D: DataLink> SimulatedDevice<D> {
@@ -42,7 +233,7 @@ impl<D: DataLink> SimulatedDevice<D> {
         );
         device_props.insert(
             PropertyId::ObjectName,
-            ClientDataValue::CharacterString(format!("SimDevice-{instance}")),
+            ClientDataValue::CharacterString(format!("SimDevice-{instance}").into()),
         );
         device_props.insert(
             PropertyId::ObjectType,
@@ -56,28 +247,258 @@ impl<D: DataLink> SimulatedDevice<D> {
             device_id,
             objects: Arc::new(RwLock::new(objects)),
             datalink,
+            who_is: Mutex::new(WhoIsGuard::new(WhoIsPolicy::default())),
+            network_address: None,
+            faults: Mutex::new(FaultState::new(SimulatorFaults::default())),
+            priority_arrays: RwLock::new(HashMap::new()),
+            priority_required: false,
+            trend_logs: Mutex::new(Vec::new()),
         }
     }
 
+    /// Limit how the device answers Who-Is (duplicate suppression, rate limit, jitter).
+    pub fn with_who_is_policy(self, policy: WhoIsPolicy) -> Self {
+        *self.who_is.lock().expect("Who-Is guard lock poisoned") = WhoIsGuard::new(policy);
+        self
+    }
+
+    /// Inject latency, dropped replies or forced failures into the device's replies.
+    pub fn with_faults(self, faults: SimulatorFaults) -> Self {
+        self.set_faults(faults);
+        self
+    }
+
+    /// Replace the injected faults, for example to let a client recover mid-test.
+    /// The random generator restarts from the new seed.
+    pub fn set_faults(&self, faults: SimulatorFaults) {
+        *self.faults.lock().expect("fault state lock poisoned") = FaultState::new(faults);
+    }
+
+    /// Reject `Present_Value` writes to commandable objects that carry no priority,
+    /// instead of applying them at priority 16.
+    pub fn with_priority_required(mut self, required: bool) -> Self {
+        self.priority_required = required;
+        self
+    }
+
     /// Add an object with its properties to the simulated device.
-    pub async fn add_object(&self, id: ObjectId, properties: HashMap<PropertyId, ClientDataValue>) {
+    ///
+    /// A commandable object starts with every priority level relinquished, so its
+    /// `Present_Value` is its `Relinquish_Default`; when that is not given, the
+    /// supplied `Present_Value` becomes the relinquish default.
+    pub async fn add_object(
+        &self,
+        id: ObjectId,
+        mut properties: HashMap<PropertyId, ClientDataValue>,
+    ) {
+        let mut priority_arrays = self.priority_arrays.write().await;
+        if is_commandable(id.object_type(), &properties) {
+            properties.remove(&PropertyId::PriorityArray);
+            let relinquish_default = properties
+                .get(&PropertyId::RelinquishDefault)
+                .or_else(|| properties.get(&PropertyId::PresentValue))
+                .cloned()
+                .unwrap_or(ClientDataValue::Null);
+            properties.insert(PropertyId::RelinquishDefault, relinquish_default.clone());
+            properties.insert(PropertyId::PresentValue, relinquish_default);
+            priority_arrays.insert(id, std::array::from_fn(|_| None));
+        } else {
+            priority_arrays.remove(&id);
+        }
         self.objects.write().await.insert(id, properties);
     }
 
+    /// Add a Trend Log object that samples `property` of `source` every `interval`,
+    /// keeping the newest `buffer_size` records.
+    ///
+    /// The first sample is taken one interval after the call, by [`run`](Self::run).
+    /// Records carry the UTC sample time and, when the source has one, its
+    /// `Status_Flags`. The `Log_Buffer` is read with ReadRange by position, sequence
+    /// number or time; `Record_Count` and `Total_Record_Count` are kept current.
+    pub async fn add_trend_log(
+        &self,
+        id: ObjectId,
+        source: ObjectId,
+        property: PropertyId,
+        interval: Duration,
+        buffer_size: usize,
+    ) {
+        let mut props = HashMap::new();
+        props.insert(PropertyId::ObjectIdentifier, ClientDataValue::ObjectId(id));
+        props.insert(
+            PropertyId::ObjectName,
+            ClientDataValue::CharacterString(format!("TL-{}", id.instance()).into()),
+        );
+        props.insert(
+            PropertyId::ObjectType,
+            ClientDataValue::Enumerated(u32::from(ObjectType::TrendLog.to_u16())),
+        );
+        props.insert(
+            PropertyId::LogInterval,
+            ClientDataValue::Unsigned(u32::try_from(interval.as_millis() / 10).unwrap_or(u32::MAX)),
+        );
+        props.insert(
+            PropertyId::BufferSize,
+            ClientDataValue::Unsigned(u32::try_from(buffer_size).unwrap_or(u32::MAX)),
+        );
+        props.insert(PropertyId::RecordCount, ClientDataValue::Unsigned(0));
+        props.insert(PropertyId::TotalRecordCount, ClientDataValue::Unsigned(0));
+        self.add_object(id, props).await;
+
+        let mut trend_logs = self.trend_logs.lock().expect("trend log lock poisoned");
+        trend_logs.retain(|log| log.id != id);
+        trend_logs.push(TrendLog {
+            id,
+            source: (source, property),
+            interval,
+            next_sample: Instant::now() + interval,
+            capacity: buffer_size.max(1),
+            records: VecDeque::new(),
+            total_record_count: 0,
+        });
+    }
+
     /// Run the device loop, responding to incoming requests until stopped.
     pub async fn run(&self) -> Result<(), ClientError> {
         let mut buf = [0u8; 1500];
         loop {
-            let (n, source) = self.datalink.recv(&mut buf).await?;
-            if let Err(e) = self.handle_frame(&buf[..n], source).await {
-                log::debug!("simulator: error handling frame: {e}");
+            let next_i_am = self.next_timer();
+            let received = match next_i_am {
+                Some(due) => tokio::select! {
+                    received = self.datalink.recv(&mut buf) => Some(received),
+                    _ = tokio::time::sleep_until(due) => None,
+                },
+                None => Some(self.datalink.recv(&mut buf).await),
+            };
+            if let Some(received) = received {
+                let (n, source) = received?;
+                if let Err(e) = self.handle_frame(&buf[..n], source).await {
+                    log::debug!("simulator: error handling frame: {e}");
+                }
             }
+            self.run_timers().await?;
         }
     }
 
+    /// When the next deferred I-Am or trend-log sample is due.
+    fn next_timer(&self) -> Option<Instant> {
+        let next_i_am = self
+            .who_is
+            .lock()
+            .expect("Who-Is guard lock poisoned")
+            .next_due();
+        let next_sample = self
+            .trend_logs
+            .lock()
+            .expect("trend log lock poisoned")
+            .iter()
+            .map(|log| log.next_sample)
+            .min();
+        next_i_am.into_iter().chain(next_sample).min()
+    }
+
+    /// Sends due I-Am responses and takes due trend-log samples.
+    async fn run_timers(&self) -> Result<(), ClientError> {
+        self.send_due_i_ams().await?;
+        self.sample_trend_logs(Instant::now()).await;
+        Ok(())
+    }
+
+    /// Records a sample in every trend log that is due at `now`.
+    async fn sample_trend_logs(&self, now: Instant) {
+        let due: Vec<(ObjectId, ObjectId, PropertyId)> = self
+            .trend_logs
+            .lock()
+            .expect("trend log lock poisoned")
+            .iter_mut()
+            .filter(|log| log.next_sample <= now)
+            .map(|log| {
+                // Skip missed intervals rather than catching up in a burst.
+                while log.next_sample <= now {
+                    log.next_sample += log.interval.max(Duration::from_millis(1));
+                }
+                (log.id, log.source.0, log.source.1)
+            })
+            .collect();
+        if due.is_empty() {
+            return;
+        }
+
+        let (date, time) = crate::export::bacnet_date_time(SystemTime::now());
+        let mut objects = self.objects.write().await;
+        for (log_id, source, property) in due {
+            let source_props = objects.get(&source);
+            let value = match source_props.map(|props| props.get(&property)) {
+                Some(Some(value)) => Ok(value.clone()),
+                Some(None) => Err((
+                    ErrorClass::Property as u32,
+                    ErrorCode::UnknownProperty as u32,
+                )),
+                None => Err((ErrorClass::Object as u32, ErrorCode::UnknownObject as u32)),
+            };
+            let status_flags =
+                source_props.and_then(|props| props.get(&PropertyId::StatusFlags).cloned());
+
+            let mut trend_logs = self.trend_logs.lock().expect("trend log lock poisoned");
+            let Some(log) = trend_logs.iter_mut().find(|log| log.id == log_id) else {
+                continue;
+            };
+            log.total_record_count = log.total_record_count.wrapping_add(1).max(1);
+            if log.records.len() == log.capacity {
+                log.records.pop_front();
+            }
+            log.records.push_back(TrendRecord {
+                sequence: log.total_record_count,
+                date,
+                time,
+                value,
+                status_flags,
+            });
+            let counts = (log.records.len() as u32, log.total_record_count);
+            drop(trend_logs);
+
+            if let Some(props) = objects.get_mut(&log_id) {
+                props.insert(PropertyId::RecordCount, ClientDataValue::Unsigned(counts.0));
+                props.insert(
+                    PropertyId::TotalRecordCount,
+                    ClientDataValue::Unsigned(counts.1),
+                );
+            }
+        }
+    }
+
+    /// Sends the jittered I-Am responses whose delay has passed.
+    async fn send_due_i_ams(&self) -> Result<(), ClientError> {
+        let due = self
+            .who_is
+            .lock()
+            .expect("Who-Is guard lock poisoned")
+            .take_due(Instant::now());
+        for target in due {
+            self.send_i_am(target).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` when the object database contains `id`.
+    async fn has_object(&self, id: ObjectId) -> bool {
+        self.objects.read().await.contains_key(&id)
+    }
+
+    /// NPDU header for a reply to `request`, routed back through its SNET/SADR if any.
+    fn reply_npdu(&self, request: &Npdu) -> Npdu {
+        let mut reply = request.reply();
+        reply.source = self.network_address;
+        reply
+    }
+
     async fn handle_frame(&self, frame: &[u8], source: DataLinkAddress) -> Result<(), ClientError> {
         let mut r = Reader::new(frame);
-        let _npdu = Npdu::decode(&mut r)?;
+        let npdu = Npdu::decode(&mut r)?;
+        if npdu.is_network_message() {
+            return Ok(());
+        }
+        let reply = self.reply_npdu(&npdu);
 
         if r.is_empty() {
             return Ok(());
@@ -93,19 +514,47 @@ impl<D: DataLink> SimulatedDevice<D> {
                     // Who-Is — decode optional limits from remaining payload.
                     let who_is_limits = self.decode_who_is_limits(&mut r);
                     if self.matches_who_is(who_is_limits) {
-                        self.send_i_am(source).await?;
+                        let decision = self
+                            .who_is
+                            .lock()
+                            .expect("Who-Is guard lock poisoned")
+                            .on_who_is(source, who_is_limits, Instant::now());
+                        if decision == IAmDecision::Send {
+                            self.send_i_am(source).await?;
+                        }
                     }
                 }
             }
             Some(ApduType::ConfirmedRequest) => {
                 let header = ConfirmedRequestHeader::decode(&mut r)?;
+                let forced = self
+                    .faults
+                    .lock()
+                    .expect("fault state lock poisoned")
+                    .faults
+                    .forced_response;
+                if let Some(forced) = forced {
+                    return self
+                        .send_failure(
+                            forced,
+                            header.invoke_id,
+                            header.service_choice,
+                            source,
+                            reply,
+                        )
+                        .await;
+                }
                 match header.service_choice {
                     SERVICE_READ_PROPERTY => {
-                        self.handle_read_property(&mut r, header.invoke_id, source)
+                        self.handle_read_property(&mut r, header.invoke_id, source, reply)
                             .await?;
                     }
                     SERVICE_WRITE_PROPERTY => {
-                        self.handle_write_property(&mut r, header.invoke_id, source)
+                        self.handle_write_property(&mut r, header.invoke_id, source, reply)
+                            .await?;
+                    }
+                    SERVICE_READ_RANGE => {
+                        self.handle_read_range(&mut r, header.invoke_id, source, reply)
                             .await?;
                     }
                     _ => {
@@ -119,6 +568,225 @@ impl<D: DataLink> SimulatedDevice<D> {
         Ok(())
     }
 
+    /// Sends a reply, subject to the injected latency and drop probability.
+    async fn send_reply(&self, target: DataLinkAddress, data: &[u8]) -> Result<(), ClientError> {
+        let delay = self
+            .faults
+            .lock()
+            .expect("fault state lock poisoned")
+            .next_reply();
+        let Some(delay) = delay else {
+            return Ok(());
+        };
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        self.datalink.send(target, data).await?;
+        Ok(())
+    }
+
+    /// Answers a confirmed request with an Error, Reject or Abort PDU.
+    async fn send_failure(
+        &self,
+        failure: ForcedResponse,
+        invoke_id: u8,
+        service_choice: u8,
+        source: DataLinkAddress,
+        reply: Npdu,
+    ) -> Result<(), ClientError> {
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        reply.encode(&mut w)?;
+        match failure {
+            ForcedResponse::Error {
+                error_class,
+                error_code,
+            } => {
+                w.write_u8((ApduType::Error as u8) << 4)?;
+                w.write_u8(invoke_id)?;
+                w.write_u8(service_choice)?;
+                encode_app_enumerated(&mut w, error_class)?;
+                encode_app_enumerated(&mut w, error_code)?;
+            }
+            ForcedResponse::Reject { reason } => {
+                w.write_u8((ApduType::Reject as u8) << 4)?;
+                w.write_u8(invoke_id)?;
+                w.write_u8(reason)?;
+            }
+            ForcedResponse::Abort { reason } => AbortPdu {
+                server: true,
+                invoke_id,
+                reason,
+            }
+            .encode(&mut w)?,
+        }
+        self.send_reply(source, w.as_written()).await
+    }
+
+    async fn handle_read_range(
+        &self,
+        r: &mut Reader<'_>,
+        invoke_id: u8,
+        source: DataLinkAddress,
+        reply: Npdu,
+    ) -> Result<(), ClientError> {
+        let object_id = crate::decode_ctx_object_id(r)?;
+        let property_id = PropertyId::from_u32(crate::decode_ctx_unsigned(r)?);
+        let mut next = if r.is_empty() {
+            None
+        } else {
+            Some(Tag::decode(r)?)
+        };
+        if let Some(Tag::Context { tag_num: 2, len }) = next {
+            decode_unsigned(r, len as usize)?;
+            next = if r.is_empty() {
+                None
+            } else {
+                Some(Tag::decode(r)?)
+            };
+        }
+        let range = match next {
+            None => RangeRequest::All,
+            Some(Tag::Opening { tag_num }) => {
+                let range = match tag_num {
+                    3 => RangeRequest::Position(decode_app_unsigned(r)?, decode_app_signed(r)?),
+                    6 => RangeRequest::Sequence(decode_app_unsigned(r)?, decode_app_signed(r)?),
+                    7 => {
+                        let (date, time) = match (
+                            decode_application_data_value(r)?,
+                            decode_application_data_value(r)?,
+                        ) {
+                            (DataValue::Date(date), DataValue::Time(time)) => (date, time),
+                            _ => return Err(DecodeError::InvalidTag.into()),
+                        };
+                        RangeRequest::Time(date, time, decode_app_signed(r)?)
+                    }
+                    _ => return Err(DecodeError::InvalidTag.into()),
+                };
+                if Tag::decode(r)? != (Tag::Closing { tag_num }) {
+                    return Err(DecodeError::InvalidTag.into());
+                }
+                range
+            }
+            Some(_) => return Err(DecodeError::InvalidTag.into()),
+        };
+
+        let selection = {
+            let trend_logs = self.trend_logs.lock().expect("trend log lock poisoned");
+            match trend_logs.iter().find(|log| log.id == object_id) {
+                None => Err((ErrorClass::Object as u32, ErrorCode::UnknownObject as u32)),
+                Some(_) if property_id != PropertyId::LogBuffer => {
+                    Err((ErrorClass::Services as u32, ERROR_PROPERTY_IS_NOT_A_LIST))
+                }
+                Some(log) => Ok(select_records(&log.records, range)),
+            }
+        };
+        let (records, more_items, first_item, last_item) = match selection {
+            Ok(selection) => selection,
+            Err((error_class, error_code)) => {
+                let failure = ForcedResponse::Error {
+                    error_class,
+                    error_code,
+                };
+                return self
+                    .send_failure(failure, invoke_id, SERVICE_READ_RANGE, source, reply)
+                    .await;
+            }
+        };
+
+        let mut buf = [0u8; 1476];
+        let mut w = Writer::new(&mut buf);
+        reply.encode(&mut w)?;
+        ComplexAckHeader {
+            segmented: false,
+            more_follows: false,
+            invoke_id,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_READ_RANGE,
+        }
+        .encode(&mut w)?;
+        encode_ctx_object_id(&mut w, 0, object_id.raw())?;
+        encode_ctx_unsigned(&mut w, 1, property_id.to_u32())?;
+        // Result flags: first-item, last-item, more-items.
+        let flags =
+            (u8::from(first_item) << 7) | (u8::from(last_item) << 6) | (u8::from(more_items) << 5);
+        Tag::Context { tag_num: 3, len: 2 }.encode(&mut w)?;
+        w.write_u8(5)?;
+        w.write_u8(flags)?;
+        encode_ctx_unsigned(&mut w, 4, records.len() as u32)?;
+        Tag::Opening { tag_num: 5 }.encode(&mut w)?;
+        for record in &records {
+            encode_trend_record(&mut w, record)?;
+        }
+        Tag::Closing { tag_num: 5 }.encode(&mut w)?;
+        if let (RangeRequest::Sequence(..) | RangeRequest::Time(..), Some(first)) =
+            (range, records.first())
+        {
+            encode_ctx_unsigned(&mut w, 6, first.sequence)?;
+        }
+        self.send_reply(source, w.as_written()).await
+    }
+
+    /// Applies a write, commanding `Present_Value` of commandable objects.
+    async fn write_value(
+        &self,
+        object_id: ObjectId,
+        property_id: PropertyId,
+        value: ClientDataValue,
+        priority: Option<u32>,
+    ) -> Result<(), ForcedResponse> {
+        let mut priority_arrays = self.priority_arrays.write().await;
+        let mut objects = self.objects.write().await;
+        let Some(props) = objects.get_mut(&object_id) else {
+            return Ok(());
+        };
+        let Some(slots) = priority_arrays.get_mut(&object_id) else {
+            props.insert(property_id, value);
+            return Ok(());
+        };
+
+        match property_id {
+            PropertyId::PresentValue => {
+                let level = match priority {
+                    None if self.priority_required => {
+                        return Err(ForcedResponse::Reject {
+                            reason: REJECT_MISSING_REQUIRED_PARAMETER,
+                        })
+                    }
+                    None => PRIORITY_LEVELS,
+                    Some(level @ 1..=16) => level as usize,
+                    Some(_) => {
+                        return Err(ForcedResponse::Reject {
+                            reason: REJECT_PARAMETER_OUT_OF_RANGE,
+                        })
+                    }
+                };
+                if level == MINIMUM_ON_OFF_PRIORITY {
+                    return Err(write_access_denied());
+                }
+                slots[level - 1] = match value {
+                    ClientDataValue::Null => None,
+                    value => Some(value),
+                };
+            }
+            PropertyId::PriorityArray => return Err(write_access_denied()),
+            _ => {
+                props.insert(property_id, value);
+            }
+        }
+
+        let present_value = slots
+            .iter()
+            .flatten()
+            .next()
+            .or_else(|| props.get(&PropertyId::RelinquishDefault))
+            .cloned()
+            .unwrap_or(ClientDataValue::Null);
+        props.insert(PropertyId::PresentValue, present_value);
+        Ok(())
+    }
+
     fn decode_who_is_limits(&self, r: &mut Reader<'_>) -> Option<(u32, u32)> {
         // Who-Is has optional [0] low-limit, [1] high-limit.
         if r.is_empty() {
@@ -155,10 +823,12 @@ impl<D: DataLink> SimulatedDevice<D> {
 
         let mut buf = [0u8; 128];
         let mut w = Writer::new(&mut buf);
-        Npdu::new(0).encode(&mut w)?;
+        let mut npdu = Npdu::new(0);
+        npdu.source = self.network_address;
+        npdu.encode(&mut w)?;
         req.encode(&mut w)?;
         let data = w.as_written();
-        self.datalink.send(target, data).await?;
+        self.send_reply(target, data).await?;
         Ok(())
     }
 
@@ -167,12 +837,74 @@ impl<D: DataLink> SimulatedDevice<D> {
         r: &mut Reader<'_>,
         invoke_id: u8,
         source: DataLinkAddress,
+        reply: Npdu,
     ) -> Result<(), ClientError> {
         // ReadPropertyRequest has no decode method — decode manually.
         let object_id = crate::decode_ctx_object_id(r)?;
         let property_id = PropertyId::from_u32(crate::decode_ctx_unsigned(r)?);
-        let objects = self.objects.read().await;
+        let array_index = if r.is_empty() {
+            None
+        } else {
+            match Tag::decode(r)? {
+                Tag::Context { tag_num: 2, len } => Some(decode_unsigned(r, len as usize)?),
+                _ => return Err(DecodeError::InvalidTag.into()),
+            }
+        };
+
+        if property_id == PropertyId::PriorityArray {
+            let values = self
+                .priority_arrays
+                .read()
+                .await
+                .get(&object_id)
+                .map(|slots| match array_index {
+                    None => slots
+                        .iter()
+                        .map(|slot| slot.clone().unwrap_or(ClientDataValue::Null))
+                        .collect::<Vec<_>>(),
+                    Some(0) => vec![ClientDataValue::Unsigned(PRIORITY_LEVELS as u32)],
+                    Some(index) => slots
+                        .get(index as usize - 1)
+                        .map(|slot| vec![slot.clone().unwrap_or(ClientDataValue::Null)])
+                        .unwrap_or_default(),
+                });
+            if let Some(values) = values {
+                if values.is_empty() {
+                    let failure = ForcedResponse::Error {
+                        error_class: ErrorClass::Property as u32,
+                        error_code: ERROR_INVALID_ARRAY_INDEX,
+                    };
+                    return self
+                        .send_failure(failure, invoke_id, SERVICE_READ_PROPERTY, source, reply)
+                        .await;
+                }
+                let mut buf = [0u8; 1400];
+                let mut w = Writer::new(&mut buf);
+                reply.encode(&mut w)?;
+                ComplexAckHeader {
+                    segmented: false,
+                    more_follows: false,
+                    invoke_id,
+                    sequence_number: None,
+                    proposed_window_size: None,
+                    service_choice: SERVICE_READ_PROPERTY,
+                }
+                .encode(&mut w)?;
+                encode_ctx_unsigned(&mut w, 0, object_id.raw())?;
+                encode_ctx_unsigned(&mut w, 1, property_id.to_u32())?;
+                if let Some(index) = array_index {
+                    encode_ctx_unsigned(&mut w, 2, index)?;
+                }
+                Tag::Opening { tag_num: 3 }.encode(&mut w)?;
+                for value in &values {
+                    encode_application_data_value(&mut w, &client_value_to_borrowed(value))?;
+                }
+                Tag::Closing { tag_num: 3 }.encode(&mut w)?;
+                return self.send_reply(source, w.as_written()).await;
+            }
+        }
 
+        let objects = self.objects.read().await;
         let value = objects
             .get(&object_id)
             .and_then(|props| props.get(&property_id));
@@ -182,7 +914,7 @@ impl<D: DataLink> SimulatedDevice<D> {
                 let borrowed = client_value_to_borrowed(val);
                 let mut buf = [0u8; 1400];
                 let mut w = Writer::new(&mut buf);
-                Npdu::new(0).encode(&mut w)?;
+                reply.encode(&mut w)?;
                 ComplexAckHeader {
                     segmented: false,
                     more_follows: false,
@@ -199,13 +931,13 @@ impl<D: DataLink> SimulatedDevice<D> {
                 encode_application_data_value(&mut w, &borrowed)?;
                 Tag::Closing { tag_num: 3 }.encode(&mut w)?;
                 let data = w.as_written();
-                self.datalink.send(source, data).await?;
+                self.send_reply(source, data).await?;
             }
             None => {
                 // Send error: unknown-property.
                 let mut buf = [0u8; 64];
                 let mut w = Writer::new(&mut buf);
-                Npdu::new(0).encode(&mut w)?;
+                reply.encode(&mut w)?;
                 // BACnet Error PDU: type=5, invoke_id, service_choice, error_class, error_code
                 w.write_u8(0x50)?; // Error PDU type (5 << 4)
                 w.write_u8(invoke_id)?;
@@ -224,7 +956,7 @@ impl<D: DataLink> SimulatedDevice<D> {
                 .encode(&mut w)?;
                 w.write_u8(32)?; // unknown-property
                 let data = w.as_written();
-                self.datalink.send(source, data).await?;
+                self.send_reply(source, data).await?;
             }
         }
 
@@ -236,6 +968,7 @@ impl<D: DataLink> SimulatedDevice<D> {
         r: &mut Reader<'_>,
         invoke_id: u8,
         source: DataLinkAddress,
+        reply: Npdu,
     ) -> Result<(), ClientError> {
         // Decode object_id [0], property_id [1], optional array_index [2], value [3]
         let object_id = crate::decode_ctx_object_id(r)?;
@@ -251,36 +984,332 @@ impl<D: DataLink> SimulatedDevice<D> {
             other => other,
         };
         if value_start_tag != (Tag::Opening { tag_num: 3 }) {
-            return Err(rustbac_core::DecodeError::InvalidTag.into());
+            return Err(DecodeError::InvalidTag.into());
         }
-        let val = rustbac_core::services::value_codec::decode_application_data_value(r)?;
+        let val = decode_application_data_value(r)?;
         match Tag::decode(r)? {
             Tag::Closing { tag_num: 3 } => {}
-            _ => return Err(rustbac_core::DecodeError::InvalidTag.into()),
+            _ => return Err(DecodeError::InvalidTag.into()),
         }
+        let priority = if r.is_empty() {
+            None
+        } else {
+            match Tag::decode(r)? {
+                Tag::Context { tag_num: 4, len } => Some(decode_unsigned(r, len as usize)?),
+                _ => return Err(DecodeError::InvalidTag.into()),
+            }
+        };
 
         let client_val = crate::data_value_to_client(val);
-        let mut objects = self.objects.write().await;
-        if let Some(props) = objects.get_mut(&object_id) {
-            props.insert(property_id, client_val);
+        if let Err(failure) = self
+            .write_value(object_id, property_id, client_val, priority)
+            .await
+        {
+            return self
+                .send_failure(failure, invoke_id, SERVICE_WRITE_PROPERTY, source, reply)
+                .await;
         }
 
         // Send SimpleAck
         let mut buf = [0u8; 32];
         let mut w = Writer::new(&mut buf);
-        Npdu::new(0).encode(&mut w)?;
+        reply.encode(&mut w)?;
         SimpleAck {
             invoke_id,
             service_choice: SERVICE_WRITE_PROPERTY,
         }
         .encode(&mut w)?;
         let data = w.as_written();
-        self.datalink.send(source, data).await?;
+        self.send_reply(source, data).await?;
 
         Ok(())
     }
 }
 
+/// Many simulated devices sharing one data link, as behind a BACnet gateway.
+///
+/// Each device has its own instance number and object database and is reachable at
+/// [`virtual_address`](Self::virtual_address) on the virtual network: its replies and
+/// I-Am carry that address as SNET/SADR, and requests routed to it with DNET/DADR are
+/// delivered to it alone. Who-Is reaches every device. A confirmed request without a
+/// DNET goes to the device whose database holds the addressed object, so give devices
+/// distinct object identifiers when clients address them directly.
+pub struct SimulatedNetwork<D: DataLink> {
+    network: u16,
+    datalink: Arc<D>,
+    devices: Vec<SimulatedDevice<Arc<D>>>,
+    who_is_policy: WhoIsPolicy,
+}
+
+impl<D: DataLink> SimulatedNetwork<D> {
+    /// Create an empty virtual network with the given network number.
+    pub fn new(network: u16, datalink: D) -> Self {
+        Self {
+            network,
+            datalink: Arc::new(datalink),
+            devices: Vec::new(),
+            who_is_policy: WhoIsPolicy::default(),
+        }
+    }
+
+    /// Apply `policy` to every device added afterwards; jitter keeps a large network
+    /// from answering a global Who-Is in one burst.
+    pub fn with_who_is_policy(mut self, policy: WhoIsPolicy) -> Self {
+        self.who_is_policy = policy;
+        self
+    }
+
+    /// The virtual network number.
+    pub fn network(&self) -> u16 {
+        self.network
+    }
+
+    /// Add a device with the given instance number and return it so objects can be
+    /// added. An instance that is already present is returned unchanged.
+    pub fn add_device(&mut self, instance: u32) -> &SimulatedDevice<Arc<D>> {
+        if let Some(index) = self
+            .devices
+            .iter()
+            .position(|device| device.device_id.instance() == instance)
+        {
+            return &self.devices[index];
+        }
+        let mut device = SimulatedDevice::new(instance, self.datalink.clone())
+            .with_who_is_policy(self.who_is_policy);
+        device.network_address = Some(self.virtual_address(instance));
+        self.devices.push(device);
+        &self.devices[self.devices.len() - 1]
+    }
+
+    /// The device with the given instance number.
+    pub fn device(&self, instance: u32) -> Option<&SimulatedDevice<Arc<D>>> {
+        self.devices
+            .iter()
+            .find(|device| device.device_id.instance() == instance)
+    }
+
+    /// All devices, in the order they were added.
+    pub fn devices(&self) -> &[SimulatedDevice<Arc<D>>] {
+        &self.devices
+    }
+
+    /// Network address of a device: this network and the instance number as a
+    /// three-byte big-endian MAC.
+    pub fn virtual_address(&self, instance: u32) -> NpduAddress {
+        let [_, high, mid, low] = instance.to_be_bytes();
+        NpduAddress {
+            network: self.network,
+            mac: [high, mid, low, 0, 0, 0],
+            mac_len: 3,
+        }
+    }
+
+    /// Run the network loop, dispatching incoming requests until stopped.
+    pub async fn run(&self) -> Result<(), ClientError> {
+        let mut buf = [0u8; 1500];
+        loop {
+            let next_i_am = self
+                .devices
+                .iter()
+                .filter_map(SimulatedDevice::next_timer)
+                .min();
+            let received = match next_i_am {
+                Some(due) => tokio::select! {
+                    received = self.datalink.recv(&mut buf) => Some(received),
+                    _ = tokio::time::sleep_until(due) => None,
+                },
+                None => Some(self.datalink.recv(&mut buf).await),
+            };
+            if let Some(received) = received {
+                let (n, source) = received?;
+                if let Err(e) = self.handle_frame(&buf[..n], source).await {
+                    log::debug!("simulator: error handling frame: {e}");
+                }
+            }
+            for device in &self.devices {
+                device.run_timers().await?;
+            }
+        }
+    }
+
+    async fn handle_frame(&self, frame: &[u8], source: DataLinkAddress) -> Result<(), ClientError> {
+        for device in self.recipients(frame).await? {
+            device.handle_frame(frame, source).await?;
+        }
+        Ok(())
+    }
+
+    /// The devices a frame is addressed to.
+    async fn recipients(&self, frame: &[u8]) -> Result<Vec<&SimulatedDevice<Arc<D>>>, ClientError> {
+        let mut r = Reader::new(frame);
+        let npdu = Npdu::decode(&mut r)?;
+        if npdu.is_network_message() || !npdu.is_for_network(self.network) || r.is_empty() {
+            return Ok(Vec::new());
+        }
+        if let Some(dest) = npdu.destination.filter(|dest| !dest.is_broadcast()) {
+            return Ok(self
+                .devices
+                .iter()
+                .filter(|device| {
+                    device
+                        .network_address
+                        .is_some_and(|address| address.mac() == dest.mac())
+                })
+                .collect());
+        }
+        if ApduType::from_u8(r.peek_u8()? >> 4) != Some(ApduType::ConfirmedRequest) {
+            return Ok(self.devices.iter().collect());
+        }
+
+        // Not routed: pick the device that owns the addressed object.
+        ConfirmedRequestHeader::decode(&mut r)?;
+        if let Ok(object_id) = crate::decode_ctx_object_id(&mut r) {
+            for device in &self.devices {
+                if device.has_object(object_id).await {
+                    return Ok(vec![device]);
+                }
+            }
+        }
+        // Nobody owns it; let the first device answer with an error rather than time out.
+        Ok(self.devices.first().into_iter().collect())
+    }
+}
+
+/// Picks the records a ReadRange asks for.
+///
+/// Returns the records (oldest first) and the more-items, first-item and last-item flags.
+fn select_records(
+    records: &VecDeque<TrendRecord>,
+    range: RangeRequest,
+) -> (Vec<TrendRecord>, bool, bool, bool) {
+    let len = records.len();
+    // `reference` is the 1-based position the count runs from, inclusive.
+    let (reference, count) = match range {
+        RangeRequest::All => (1, i32::try_from(len).unwrap_or(i32::MAX)),
+        RangeRequest::Position(index, count) => (index as usize, count),
+        RangeRequest::Sequence(sequence, count) => (
+            records
+                .iter()
+                .position(|record| record.sequence == sequence)
+                .map_or(0, |p| p + 1),
+            count,
+        ),
+        RangeRequest::Time(date, time, count) => {
+            let at = timestamp_key(date, time);
+            // Strictly after the time going forward, strictly before going back.
+            let reference = if count >= 0 {
+                records
+                    .iter()
+                    .position(|r| timestamp_key(r.date, r.time) > at)
+                    .map_or(0, |p| p + 1)
+            } else {
+                records
+                    .iter()
+                    .rposition(|r| timestamp_key(r.date, r.time) < at)
+                    .map_or(0, |p| p + 1)
+            };
+            (reference, count)
+        }
+    };
+    if reference == 0 || reference > len || count == 0 {
+        return (Vec::new(), false, false, false);
+    }
+
+    let wanted = count.unsigned_abs() as usize;
+    let (start, end) = if count > 0 {
+        (reference - 1, (reference - 1 + wanted).min(len))
+    } else {
+        (reference.saturating_sub(wanted), reference)
+    };
+    let mut more_items = if count > 0 { end < len } else { start > 0 };
+    let (start, end) = if end - start > MAX_READ_RANGE_ITEMS {
+        more_items = true;
+        if count > 0 {
+            (start, start + MAX_READ_RANGE_ITEMS)
+        } else {
+            (end - MAX_READ_RANGE_ITEMS, end)
+        }
+    } else {
+        (start, end)
+    };
+    let selected = records.range(start..end).cloned().collect();
+    (selected, more_items, start == 0, end == len)
+}
+
+/// Orders BACnet timestamps; wildcards sort as their raw value.
+fn timestamp_key(date: Date, time: Time) -> [u8; 7] {
+    [
+        date.year_since_1900,
+        date.month,
+        date.day,
+        time.hour,
+        time.minute,
+        time.second,
+        time.hundredths,
+    ]
+}
+
+fn encode_trend_record(w: &mut Writer<'_>, record: &TrendRecord) -> Result<(), ClientError> {
+    let datum = match &record.value {
+        Ok(ClientDataValue::Boolean(v)) => LogDatum::Boolean(*v),
+        Ok(ClientDataValue::Real(v)) => LogDatum::Real(*v),
+        Ok(ClientDataValue::Double(v)) => LogDatum::Real(*v as f32),
+        Ok(ClientDataValue::Enumerated(v)) => LogDatum::Enumerated(*v),
+        Ok(ClientDataValue::Unsigned(v)) => LogDatum::Unsigned(*v),
+        Ok(ClientDataValue::Signed(v)) => LogDatum::Signed(*v),
+        Ok(ClientDataValue::BitString { unused_bits, data }) => LogDatum::BitString(BitString {
+            unused_bits: *unused_bits,
+            data,
+        }),
+        Ok(ClientDataValue::Null) => LogDatum::Null,
+        Ok(_) => LogDatum::Failure {
+            error_class: ErrorClass::Property as u32,
+            error_code: ERROR_DATATYPE_NOT_SUPPORTED,
+        },
+        Err((error_class, error_code)) => LogDatum::Failure {
+            error_class: *error_class,
+            error_code: *error_code,
+        },
+    };
+    let status_flags = match &record.status_flags {
+        Some(ClientDataValue::BitString { unused_bits, data }) => Some(BitString {
+            unused_bits: *unused_bits,
+            data,
+        }),
+        _ => None,
+    };
+    LogRecord {
+        date: record.date,
+        time: record.time,
+        datum,
+        status_flags,
+    }
+    .encode(w)?;
+    Ok(())
+}
+
+/// Output objects are always commandable; value objects when given a priority array or
+/// relinquish default.
+fn is_commandable(
+    object_type: ObjectType,
+    properties: &HashMap<PropertyId, ClientDataValue>,
+) -> bool {
+    match object_type {
+        ObjectType::AnalogOutput | ObjectType::BinaryOutput | ObjectType::MultiStateOutput => true,
+        _ => {
+            properties.contains_key(&PropertyId::PriorityArray)
+                || properties.contains_key(&PropertyId::RelinquishDefault)
+        }
+    }
+}
+
+fn write_access_denied() -> ForcedResponse {
+    ForcedResponse::Error {
+        error_class: ErrorClass::Property as u32,
+        error_code: ErrorCode::WriteAccessDenied as u32,
+    }
+}
+
 /// Convert an owned ClientDataValue to a borrowed DataValue.
 ///
 /// This is a shallow conversion — strings and byte arrays reference the owned data.
@@ -315,7 +1344,7 @@ fn client_value_to_borrowed(val: &ClientDataValue) -> DataValue<'_> {
 mod tests {
     use super::*;
     use rustbac_core::encoding::{primitives::encode_ctx_unsigned, reader::Reader, writer::Writer};
-    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
     #[derive(Clone, Default)]
     struct MockDataLink {
@@ -364,15 +1393,15 @@ mod tests {
 
         let source = DataLinkAddress::Ip("127.0.0.1:47808".parse().unwrap());
         let mut r = Reader::new(w.as_written());
-        sim.handle_write_property(&mut r, 9, source).await.unwrap();
+        sim.handle_write_property(&mut r, 9, source, Npdu::new(0))
+            .await
+            .unwrap();
 
         let objects = sim.objects.read().await;
         let props = objects.get(&sim.device_id).unwrap();
         assert_eq!(
             props.get(&PropertyId::ObjectName),
-            Some(&ClientDataValue::CharacterString(
-                "updated-name".to_string()
-            ))
+            Some(&ClientDataValue::CharacterString("updated-name".into()))
         );
 
         let sent = sent.lock().expect("poisoned lock");
@@ -383,4 +1412,369 @@ mod tests {
         assert_eq!(ack.invoke_id, 9);
         assert_eq!(ack.service_choice, SERVICE_WRITE_PROPERTY);
     }
+
+    fn who_is_frame() -> Vec<u8> {
+        let mut buf = [0u8; 16];
+        let mut w = Writer::new(&mut buf);
+        Npdu::new(0).encode(&mut w).unwrap();
+        UnconfirmedRequestHeader {
+            service_choice: 0x08,
+        }
+        .encode(&mut w)
+        .unwrap();
+        w.as_written().to_vec()
+    }
+
+    #[tokio::test]
+    async fn duplicate_who_is_within_window_gets_one_i_am() {
+        let dl = MockDataLink::default();
+        let sent = dl.sent.clone();
+        let sim = SimulatedDevice::new(7, dl).with_who_is_policy(
+            WhoIsPolicy::default().with_duplicate_window(Duration::from_secs(5)),
+        );
+        let requester = DataLinkAddress::Ip("127.0.0.1:47808".parse().unwrap());
+        let other = DataLinkAddress::Ip("127.0.0.2:47808".parse().unwrap());
+
+        for _ in 0..10 {
+            sim.handle_frame(&who_is_frame(), requester).await.unwrap();
+        }
+        sim.handle_frame(&who_is_frame(), other).await.unwrap();
+
+        let sent = sent.lock().expect("poisoned lock");
+        let targets: Vec<_> = sent.iter().map(|(target, _)| *target).collect();
+        assert_eq!(targets, vec![requester, other]);
+    }
+
+    #[tokio::test]
+    async fn jittered_i_am_is_sent_once_due() {
+        let dl = MockDataLink::default();
+        let sent = dl.sent.clone();
+        let sim = SimulatedDevice::new(7, dl)
+            .with_who_is_policy(WhoIsPolicy::default().with_max_jitter(Duration::from_millis(20)));
+        let requester = DataLinkAddress::Ip("127.0.0.1:47808".parse().unwrap());
+
+        sim.handle_frame(&who_is_frame(), requester).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(25)).await;
+        sim.send_due_i_ams().await.unwrap();
+
+        let sent = sent.lock().expect("poisoned lock");
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, requester);
+    }
+
+    fn read_property_frame(object_id: ObjectId, destination: Option<NpduAddress>) -> Vec<u8> {
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        let mut npdu = Npdu::new(0);
+        npdu.destination = destination;
+        npdu.encode(&mut w).unwrap();
+        ConfirmedRequestHeader {
+            segmented: false,
+            more_follows: false,
+            segmented_response_accepted: false,
+            max_segments: 0,
+            max_apdu: 5,
+            invoke_id: 1,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_READ_PROPERTY,
+        }
+        .encode(&mut w)
+        .unwrap();
+        encode_ctx_object_id(&mut w, 0, object_id.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::ObjectName.to_u32()).unwrap();
+        w.as_written().to_vec()
+    }
+
+    fn reply_source(frame: &[u8]) -> Option<NpduAddress> {
+        Npdu::decode(&mut Reader::new(frame)).unwrap().source
+    }
+
+    #[tokio::test]
+    async fn network_dispatches_to_the_addressed_device() {
+        let dl = MockDataLink::default();
+        let sent = dl.sent.clone();
+        let mut network = SimulatedNetwork::new(9, dl);
+        for instance in [10, 20] {
+            network.add_device(instance);
+        }
+        let ai = ObjectId::new(ObjectType::AnalogInput, 1);
+        network
+            .device(20)
+            .unwrap()
+            .add_object(ai, HashMap::new())
+            .await;
+        let requester = DataLinkAddress::Ip("127.0.0.1:47808".parse().unwrap());
+        let device_10 = network.virtual_address(10);
+        let device_20 = network.virtual_address(20);
+
+        // Who-Is: every device answers with its own SNET/SADR.
+        network
+            .handle_frame(&who_is_frame(), requester)
+            .await
+            .unwrap();
+        // Routed: DNET/DADR picks the device.
+        let device_id_10 = ObjectId::new(ObjectType::Device, 10);
+        network
+            .handle_frame(
+                &read_property_frame(device_id_10, Some(device_10)),
+                requester,
+            )
+            .await
+            .unwrap();
+        // Not routed: the device owning the object answers.
+        network
+            .handle_frame(&read_property_frame(ai, None), requester)
+            .await
+            .unwrap();
+        // Routed to another network: ignored.
+        let mut elsewhere = device_10;
+        elsewhere.network = 10;
+        network
+            .handle_frame(
+                &read_property_frame(device_id_10, Some(elsewhere)),
+                requester,
+            )
+            .await
+            .unwrap();
+
+        let sent = sent.lock().expect("poisoned lock");
+        let sources: Vec<_> = sent.iter().map(|(_, frame)| reply_source(frame)).collect();
+        assert_eq!(
+            sources,
+            vec![
+                Some(device_10),
+                Some(device_20),
+                Some(device_10),
+                Some(device_20)
+            ]
+        );
+        assert!(sent.iter().all(|(target, _)| *target == requester));
+    }
+
+    fn read_device_name(sim: &SimulatedDevice<MockDataLink>) -> Vec<u8> {
+        read_property_frame(sim.device_id, None)
+    }
+
+    #[tokio::test]
+    async fn forced_responses_replace_the_real_reply() {
+        let dl = MockDataLink::default();
+        let sent = dl.sent.clone();
+        let sim = SimulatedDevice::new(3, dl);
+        let requester = DataLinkAddress::Ip("127.0.0.1:47808".parse().unwrap());
+        let forced = [
+            ForcedResponse::Error {
+                error_class: 0,
+                error_code: 3,
+            },
+            ForcedResponse::Reject { reason: 9 },
+            ForcedResponse::Abort { reason: 4 },
+        ];
+        for response in forced {
+            sim.set_faults(SimulatorFaults::default().with_forced_response(response));
+            sim.handle_frame(&read_device_name(&sim), requester)
+                .await
+                .unwrap();
+        }
+
+        let sent = sent.lock().expect("poisoned lock");
+        let apdus: Vec<_> = sent
+            .iter()
+            .map(|(_, frame)| {
+                let mut r = Reader::new(frame);
+                Npdu::decode(&mut r).unwrap();
+                r.read_exact(r.remaining()).unwrap().to_vec()
+            })
+            .collect();
+        assert_eq!(
+            apdus,
+            vec![
+                vec![0x50, 1, SERVICE_READ_PROPERTY, 0x91, 0x00, 0x91, 0x03],
+                vec![0x60, 1, 9],
+                vec![0x71, 1, 4],
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn dropped_replies_follow_the_seed() {
+        async fn delivered(seed: u64) -> Vec<bool> {
+            let dl = MockDataLink::default();
+            let sent = dl.sent.clone();
+            let sim = SimulatedDevice::new(3, dl).with_faults(
+                SimulatorFaults::default()
+                    .with_drop_probability(0.5)
+                    .with_seed(seed),
+            );
+            let requester = DataLinkAddress::Ip("127.0.0.1:47808".parse().unwrap());
+            let mut pattern = Vec::new();
+            for _ in 0..32 {
+                let before = sent.lock().expect("poisoned lock").len();
+                sim.handle_frame(&read_device_name(&sim), requester)
+                    .await
+                    .unwrap();
+                pattern.push(sent.lock().expect("poisoned lock").len() > before);
+            }
+            pattern
+        }
+
+        let first = delivered(42).await;
+        assert_eq!(first, delivered(42).await);
+        assert!(first.contains(&true) && first.contains(&false));
+        assert_ne!(first, delivered(1).await);
+    }
+
+    #[tokio::test]
+    async fn latency_delays_replies() {
+        let dl = MockDataLink::default();
+        let sent = dl.sent.clone();
+        let sim = SimulatedDevice::new(3, dl).with_faults(
+            SimulatorFaults::default()
+                .with_latency(Duration::from_millis(30))
+                .with_jitter(Duration::from_millis(10)),
+        );
+        let requester = DataLinkAddress::Ip("127.0.0.1:47808".parse().unwrap());
+
+        let started = std::time::Instant::now();
+        sim.handle_frame(&read_device_name(&sim), requester)
+            .await
+            .unwrap();
+        let elapsed = started.elapsed();
+
+        assert!(elapsed >= Duration::from_millis(30), "{elapsed:?}");
+        assert_eq!(sent.lock().expect("poisoned lock").len(), 1);
+    }
+
+    fn write_present_value_frame(object_id: ObjectId, priority: Option<u32>) -> Vec<u8> {
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        Npdu::new(0).encode(&mut w).unwrap();
+        ConfirmedRequestHeader {
+            segmented: false,
+            more_follows: false,
+            segmented_response_accepted: false,
+            max_segments: 0,
+            max_apdu: 5,
+            invoke_id: 2,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice: SERVICE_WRITE_PROPERTY,
+        }
+        .encode(&mut w)
+        .unwrap();
+        encode_ctx_object_id(&mut w, 0, object_id.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::PresentValue.to_u32()).unwrap();
+        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+        encode_application_data_value(&mut w, &DataValue::Enumerated(1)).unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        if let Some(priority) = priority {
+            encode_ctx_unsigned(&mut w, 4, priority).unwrap();
+        }
+        w.as_written().to_vec()
+    }
+
+    #[tokio::test]
+    async fn commandable_writes_check_the_priority() {
+        let dl = MockDataLink::default();
+        let sent = dl.sent.clone();
+        let sim = SimulatedDevice::new(3, dl).with_priority_required(true);
+        let bv = ObjectId::new(ObjectType::BinaryValue, 1);
+        let mut props = HashMap::new();
+        props.insert(
+            PropertyId::RelinquishDefault,
+            ClientDataValue::Enumerated(0),
+        );
+        sim.add_object(bv, props).await;
+        let requester = DataLinkAddress::Ip("127.0.0.1:47808".parse().unwrap());
+
+        for priority in [None, Some(0), Some(17), Some(6), Some(10)] {
+            sim.handle_frame(&write_present_value_frame(bv, priority), requester)
+                .await
+                .unwrap();
+        }
+
+        let apdus: Vec<_> = sent
+            .lock()
+            .expect("poisoned lock")
+            .iter()
+            .map(|(_, frame)| {
+                let mut r = Reader::new(frame);
+                Npdu::decode(&mut r).unwrap();
+                r.read_exact(r.remaining()).unwrap().to_vec()
+            })
+            .collect();
+        assert_eq!(
+            apdus,
+            vec![
+                vec![0x60, 2, REJECT_MISSING_REQUIRED_PARAMETER],
+                vec![0x60, 2, REJECT_PARAMETER_OUT_OF_RANGE],
+                vec![0x60, 2, REJECT_PARAMETER_OUT_OF_RANGE],
+                vec![0x50, 2, SERVICE_WRITE_PROPERTY, 0x91, 0x02, 0x91, 0x28],
+                vec![0x20, 2, SERVICE_WRITE_PROPERTY],
+            ]
+        );
+        let objects = sim.objects.read().await;
+        assert_eq!(
+            objects[&bv].get(&PropertyId::PresentValue),
+            Some(&ClientDataValue::Enumerated(1))
+        );
+        assert!(sim.priority_arrays.read().await[&bv][9].is_some());
+    }
+
+    #[tokio::test]
+    async fn trend_log_samples_into_a_ring_buffer() {
+        let sim = SimulatedDevice::new(4, MockDataLink::default());
+        let ai = ObjectId::new(ObjectType::AnalogInput, 1);
+        let mut props = HashMap::new();
+        props.insert(PropertyId::PresentValue, ClientDataValue::Real(1.0));
+        sim.add_object(ai, props).await;
+        let tl = ObjectId::new(ObjectType::TrendLog, 1);
+        sim.add_trend_log(tl, ai, PropertyId::PresentValue, Duration::from_secs(60), 3)
+            .await;
+
+        let start = Instant::now();
+        for step in 1..=5u32 {
+            sim.write_value(
+                ai,
+                PropertyId::PresentValue,
+                ClientDataValue::Real(step as f32),
+                None,
+            )
+            .await
+            .unwrap();
+            sim.sample_trend_logs(start + Duration::from_secs(60 * u64::from(step)))
+                .await;
+        }
+
+        {
+            let trend_logs = sim.trend_logs.lock().expect("poisoned lock");
+            let records = &trend_logs[0].records;
+            let sequences: Vec<u32> = records.iter().map(|r| r.sequence).collect();
+            assert_eq!(sequences, vec![3, 4, 5]);
+            assert_eq!(records[0].value, Ok(ClientDataValue::Real(3.0)));
+
+            let (selected, more, first, last) =
+                select_records(records, RangeRequest::Position(3, -2));
+            let sequences: Vec<u32> = selected.iter().map(|r| r.sequence).collect();
+            assert_eq!(sequences, vec![4, 5]);
+            assert!(more && !first && last);
+            let (selected, more, first, last) =
+                select_records(records, RangeRequest::Sequence(3, 5));
+            assert_eq!(selected.len(), 3);
+            assert!(!more && first && last);
+            let (selected, ..) = select_records(records, RangeRequest::Sequence(1, 5));
+            assert!(selected.is_empty(), "evicted records are gone");
+        }
+
+        let objects = sim.objects.read().await;
+        let counts = objects.get(&tl).unwrap();
+        assert_eq!(
+            counts.get(&PropertyId::RecordCount),
+            Some(&ClientDataValue::Unsigned(3))
+        );
+        assert_eq!(
+            counts.get(&PropertyId::TotalRecordCount),
+            Some(&ClientDataValue::Unsigned(5))
+        );
+    }
 }
@@ -0,0 +1,355 @@
+//! Who-Is proxying for MS/TP slave devices.
+//!
+//! MS/TP slaves never hold the token, so they cannot answer a Who-Is themselves. A
+//! router with slave-proxy support (Clause 16.10.2) answers on their behalf from a
+//! binding table. [`SlaveProxy`] is that responder: it listens on the router's
+//! BACnet/IP side and answers Who-Is for each bound slave with an I-Am whose SNET/SADR
+//! is the slave's MS/TP network and MAC address, as if the router had forwarded the
+//! slave's own I-Am.
+//!
+//! The table is configured with [`SlaveProxy::bind`] or built by polling the MS/TP
+//! segment with [`SlaveProxy::discover_slaves`].
+
+use crate::{BacnetClient, ClientDataValue, ClientError};
+use rustbac_core::apdu::{ApduType, UnconfirmedRequestHeader};
+use rustbac_core::encoding::{reader::Reader, writer::Writer};
+use rustbac_core::npdu::{Npdu, NpduAddress};
+use rustbac_core::services::i_am::IAmRequest;
+use rustbac_core::services::who_is::{WhoIsRequest, SERVICE_WHO_IS};
+use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+use rustbac_datalink::{DataLink, DataLinkAddress};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// Largest APDU an MS/TP device can accept.
+const MSTP_MAX_APDU: u32 = 480;
+
+/// `Segmentation_Supported` value for no segmentation.
+const NO_SEGMENTATION: u32 = 3;
+
+/// A slave device answered for by a [`SlaveProxy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlaveBinding {
+    /// The slave's Device object identifier.
+    pub device_id: ObjectId,
+    /// The slave's MS/TP MAC address.
+    pub mac: u8,
+    /// `Max_APDU_Length_Accepted` announced in the I-Am.
+    pub max_apdu: u32,
+    /// `Segmentation_Supported` announced in the I-Am.
+    pub segmentation: u32,
+    /// `Vendor_Identifier` announced in the I-Am.
+    pub vendor_id: u32,
+}
+
+impl SlaveBinding {
+    /// Binds device `instance` to MS/TP MAC `mac`, announcing a 480-octet APDU, no
+    /// segmentation and vendor 0.
+    pub fn new(instance: u32, mac: u8) -> Self {
+        Self {
+            device_id: ObjectId::new(ObjectType::Device, instance),
+            mac,
+            max_apdu: MSTP_MAX_APDU,
+            segmentation: NO_SEGMENTATION,
+            vendor_id: 0,
+        }
+    }
+
+    /// Sets the announced vendor identifier.
+    pub fn with_vendor_id(mut self, vendor_id: u32) -> Self {
+        self.vendor_id = vendor_id;
+        self
+    }
+}
+
+/// Answers Who-Is on behalf of MS/TP slave devices.
+pub struct SlaveProxy<D: DataLink> {
+    network: u16,
+    datalink: D,
+    bindings: Mutex<BTreeMap<u32, SlaveBinding>>,
+}
+
+impl<D: DataLink> SlaveProxy<D> {
+    /// Create a proxy for the slaves on MS/TP network `network`, answering Who-Is
+    /// received on `datalink`.
+    pub fn new(network: u16, datalink: D) -> Self {
+        Self {
+            network,
+            datalink,
+            bindings: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// The MS/TP network number the slaves sit on.
+    pub fn network(&self) -> u16 {
+        self.network
+    }
+
+    /// Add `binding` to the table, replacing any binding for the same instance.
+    pub fn bind(&self, binding: SlaveBinding) {
+        self.bindings
+            .lock()
+            .expect("slave binding lock poisoned")
+            .insert(binding.device_id.instance(), binding);
+    }
+
+    /// Remove the binding for device `instance`, returning it if present.
+    pub fn unbind(&self, instance: u32) -> Option<SlaveBinding> {
+        self.bindings
+            .lock()
+            .expect("slave binding lock poisoned")
+            .remove(&instance)
+    }
+
+    /// The binding table, ordered by device instance.
+    pub fn bindings(&self) -> Vec<SlaveBinding> {
+        self.bindings
+            .lock()
+            .expect("slave binding lock poisoned")
+            .values()
+            .copied()
+            .collect()
+    }
+
+    /// Network address of a slave: this network and its one-byte MS/TP MAC.
+    pub fn slave_address(&self, mac: u8) -> NpduAddress {
+        NpduAddress {
+            network: self.network,
+            mac: [mac, 0, 0, 0, 0, 0],
+            mac_len: 1,
+        }
+    }
+
+    /// Build the table by polling `macs` on the MS/TP segment through `client`.
+    ///
+    /// Each MAC is sent a ReadProperty of the wildcard Device object's
+    /// `Object_Identifier` (Clause 16.10.2); devices that answer are bound, with their
+    /// `Max_APDU_Length_Accepted`, `Segmentation_Supported` and `Vendor_Identifier`
+    /// when readable. MACs that time out or reject the request are skipped, so give
+    /// `client` a short response timeout. Returns the bindings added.
+    pub async fn discover_slaves<M: DataLink>(
+        &self,
+        client: &BacnetClient<M>,
+        macs: impl IntoIterator<Item = u8>,
+    ) -> Result<Vec<SlaveBinding>, ClientError> {
+        let device = ObjectId::new(ObjectType::Device, crate::client::WILDCARD_DEVICE_INSTANCE);
+        let mut found = Vec::new();
+        for mac in macs {
+            let address = DataLinkAddress::Mstp(mac);
+            let device_id = match client
+                .read_property(address, device, PropertyId::ObjectIdentifier)
+                .await
+            {
+                Ok(ClientDataValue::ObjectId(id)) if id.object_type() == ObjectType::Device => id,
+                Ok(_) => continue,
+                Err(err) if is_absent(&err) => continue,
+                Err(err) => return Err(err),
+            };
+            let mut binding = SlaveBinding::new(device_id.instance(), mac);
+            if let Some(max_apdu) =
+                read_optional_unsigned(client, address, PropertyId::MaxApduLengthAccepted).await?
+            {
+                binding.max_apdu = max_apdu;
+            }
+            if let Some(segmentation) =
+                read_optional_unsigned(client, address, PropertyId::SegmentationSupported).await?
+            {
+                binding.segmentation = segmentation;
+            }
+            if let Some(vendor_id) =
+                read_optional_unsigned(client, address, PropertyId::VendorIdentifier).await?
+            {
+                binding.vendor_id = vendor_id;
+            }
+            self.bind(binding);
+            found.push(binding);
+        }
+        Ok(found)
+    }
+
+    /// Run the proxy loop, answering Who-Is until the data link fails.
+    pub async fn run(&self) -> Result<(), ClientError> {
+        let mut buf = [0u8; 1500];
+        loop {
+            let (n, source) = self.datalink.recv(&mut buf).await?;
+            if let Err(e) = self.handle_frame(&buf[..n], source).await {
+                log::debug!("slave proxy: error handling frame: {e}");
+            }
+        }
+    }
+
+    async fn handle_frame(&self, frame: &[u8], source: DataLinkAddress) -> Result<(), ClientError> {
+        let mut r = Reader::new(frame);
+        let npdu = Npdu::decode(&mut r)?;
+        if npdu.is_network_message() || !npdu.is_for_network(self.network) || r.is_empty() {
+            return Ok(());
+        }
+        if ApduType::from_u8(r.peek_u8()? >> 4) != Some(ApduType::UnconfirmedRequest) {
+            return Ok(());
+        }
+        if UnconfirmedRequestHeader::decode(&mut r)?.service_choice != SERVICE_WHO_IS {
+            return Ok(());
+        }
+        let who_is = WhoIsRequest::decode_after_header(&mut r)?;
+
+        let matching: Vec<SlaveBinding> = self
+            .bindings()
+            .into_iter()
+            .filter(|binding| {
+                let instance = binding.device_id.instance();
+                who_is.low_limit.map_or(true, |low| instance >= low)
+                    && who_is.high_limit.map_or(true, |high| instance <= high)
+            })
+            .collect();
+        for binding in matching {
+            let mut buf = [0u8; 128];
+            let mut w = Writer::new(&mut buf);
+            let mut reply = npdu.reply();
+            reply.source = Some(self.slave_address(binding.mac));
+            reply.encode(&mut w)?;
+            IAmRequest {
+                device_id: binding.device_id,
+                max_apdu: binding.max_apdu,
+                segmentation: binding.segmentation,
+                vendor_id: binding.vendor_id,
+            }
+            .encode(&mut w)?;
+            self.datalink.send(source, w.as_written()).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads an unsigned or enumerated property of the device at `address`; `None` when the
+/// device does not answer with one.
+async fn read_optional_unsigned<M: DataLink>(
+    client: &BacnetClient<M>,
+    address: DataLinkAddress,
+    property: PropertyId,
+) -> Result<Option<u32>, ClientError> {
+    let device = ObjectId::new(ObjectType::Device, crate::client::WILDCARD_DEVICE_INSTANCE);
+    match client.read_property(address, device, property).await {
+        Ok(ClientDataValue::Unsigned(value) | ClientDataValue::Enumerated(value)) => {
+            Ok(Some(value))
+        }
+        Ok(_) => Ok(None),
+        Err(err) if is_absent(&err) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Returns `true` when `err` means no usable device answered at the polled MAC.
+fn is_absent(err: &ClientError) -> bool {
+    matches!(
+        err.root(),
+        ClientError::Timeout
+            | ClientError::RemoteServiceError { .. }
+            | ClientError::RemoteReject { .. }
+            | ClientError::RemoteAbort { .. }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SlaveBinding, SlaveProxy};
+    use rustbac_core::apdu::UnconfirmedRequestHeader;
+    use rustbac_core::encoding::{reader::Reader, writer::Writer};
+    use rustbac_core::npdu::{Npdu, NpduAddress};
+    use rustbac_core::services::i_am::IAmRequest;
+    use rustbac_core::services::who_is::WhoIsRequest;
+    use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError};
+    use std::sync::{Arc, Mutex};
+
+    type SentFrames = Arc<Mutex<Vec<(DataLinkAddress, Vec<u8>)>>>;
+
+    #[derive(Clone, Default)]
+    struct MockDataLink {
+        sent: SentFrames,
+    }
+
+    impl DataLink for MockDataLink {
+        async fn send(
+            &self,
+            address: DataLinkAddress,
+            payload: &[u8],
+        ) -> Result<(), DataLinkError> {
+            self.sent
+                .lock()
+                .expect("poisoned lock")
+                .push((address, payload.to_vec()));
+            Ok(())
+        }
+
+        async fn recv(&self, _buf: &mut [u8]) -> Result<(usize, DataLinkAddress), DataLinkError> {
+            Err(DataLinkError::InvalidFrame)
+        }
+    }
+
+    fn who_is(npdu: Npdu, request: WhoIsRequest) -> Vec<u8> {
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        npdu.encode(&mut w).unwrap();
+        request.encode(&mut w).unwrap();
+        w.as_written().to_vec()
+    }
+
+    fn decode_i_am(frame: &[u8]) -> (Npdu, IAmRequest) {
+        let mut r = Reader::new(frame);
+        let npdu = Npdu::decode(&mut r).unwrap();
+        UnconfirmedRequestHeader::decode(&mut r).unwrap();
+        (npdu, IAmRequest::decode_after_header(&mut r).unwrap())
+    }
+
+    #[tokio::test]
+    async fn answers_who_is_for_bound_slaves_with_their_mstp_address() {
+        let dl = MockDataLink::default();
+        let sent = dl.sent.clone();
+        let proxy = SlaveProxy::new(5, dl);
+        proxy.bind(SlaveBinding::new(100, 7).with_vendor_id(42));
+        proxy.bind(SlaveBinding::new(200, 9));
+        let requester = DataLinkAddress::Ip(([192, 168, 1, 10], 47808).into());
+
+        let ranged = WhoIsRequest {
+            low_limit: Some(150),
+            high_limit: Some(250),
+        };
+        proxy
+            .handle_frame(&who_is(Npdu::new(0), ranged), requester)
+            .await
+            .unwrap();
+        proxy
+            .handle_frame(&who_is(Npdu::new(0), WhoIsRequest::global()), requester)
+            .await
+            .unwrap();
+        // Routed to another network: not ours to answer.
+        let mut elsewhere = Npdu::new(0);
+        elsewhere.destination = Some(NpduAddress {
+            network: 6,
+            mac: [0; 6],
+            mac_len: 0,
+        });
+        elsewhere.hop_count = Some(255);
+        proxy
+            .handle_frame(&who_is(elsewhere, WhoIsRequest::global()), requester)
+            .await
+            .unwrap();
+
+        let sent = sent.lock().unwrap();
+        let answers: Vec<(u32, u8, u32)> = sent
+            .iter()
+            .map(|(address, frame)| {
+                assert_eq!(*address, requester);
+                let (npdu, i_am) = decode_i_am(frame);
+                let source = npdu.source.expect("SNET/SADR");
+                assert_eq!(source.network, 5);
+                assert_eq!(i_am.max_apdu, 480);
+                (i_am.device_id.instance(), source.mac()[0], i_am.vendor_id)
+            })
+            .collect();
+        assert_eq!(answers, vec![(200, 9, 0), (100, 7, 42), (200, 9, 0)]);
+        drop(sent);
+
+        assert_eq!(proxy.unbind(100).map(|b| b.mac), Some(7));
+        assert_eq!(proxy.bindings().len(), 1);
+    }
+}
@@ -0,0 +1,194 @@
+//! The "device joins the network" sequence of a [`BacnetServer`](crate::BacnetServer).
+//!
+//! With [`BacnetServer::with_startup`](crate::BacnetServer::with_startup), serving starts
+//! by registering as a foreign device when
+//! [`with_foreign_device_registration`](crate::BacnetServer::with_foreign_device_registration)
+//! asked for it, then broadcasts I-Am a few times. Workstations that hear the
+//! announcement often answer with a burst of Who-Is; for a while after startup those
+//! are answered under a stricter [`WhoIsPolicy`]. A [`ServerReady`] taken before
+//! serving reports when the device is registered and announced.
+
+use crate::runtime::BoxFuture;
+use crate::who_is_policy::WhoIsPolicy;
+use rustbac_datalink::{DataLinkAddress, DataLinkError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// Delay before a failed foreign-device registration is tried again.
+const REGISTRATION_RETRY: Duration = Duration::from_secs(1);
+
+/// What a [`BacnetServer`](crate::BacnetServer) does when it starts serving.
+///
+/// The default broadcasts I-Am three times, 1 s apart, on the local BACnet/IP network
+/// and answers Who-Is for 10 s after startup with a 1 s duplicate window and up to
+/// 500 ms of jitter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StartupOptions {
+    /// Where the I-Am announcements are sent.
+    pub announce_to: DataLinkAddress,
+    /// Number of I-Am announcements; 0 announces nothing.
+    pub announcements: u8,
+    /// Delay between announcements.
+    pub announce_interval: Duration,
+    /// Who-Is policy in force for [`startup_period`](Self::startup_period); `None` keeps
+    /// the server's.
+    pub startup_who_is_policy: Option<WhoIsPolicy>,
+    /// How long after startup [`startup_who_is_policy`](Self::startup_who_is_policy)
+    /// applies.
+    pub startup_period: Duration,
+}
+
+impl Default for StartupOptions {
+    fn default() -> Self {
+        Self {
+            announce_to: DataLinkAddress::local_broadcast(DataLinkAddress::BACNET_IP_DEFAULT_PORT),
+            announcements: 3,
+            announce_interval: Duration::from_secs(1),
+            startup_who_is_policy: Some(
+                WhoIsPolicy::default()
+                    .with_duplicate_window(Duration::from_secs(1))
+                    .with_max_jitter(Duration::from_millis(500)),
+            ),
+            startup_period: Duration::from_secs(10),
+        }
+    }
+}
+
+impl StartupOptions {
+    /// Sends the announcements to `address`, e.g. a directed broadcast.
+    pub fn with_announce_to(mut self, address: DataLinkAddress) -> Self {
+        self.announce_to = address;
+        self
+    }
+
+    /// Sets the number of announcements and the delay between them.
+    pub fn with_announcements(mut self, announcements: u8, interval: Duration) -> Self {
+        self.announcements = announcements;
+        self.announce_interval = interval;
+        self
+    }
+
+    /// Answers Who-Is under `policy` for `period` after startup.
+    pub fn with_startup_who_is_policy(mut self, policy: WhoIsPolicy, period: Duration) -> Self {
+        self.startup_who_is_policy = Some(policy);
+        self.startup_period = period;
+        self
+    }
+
+    /// Answers Who-Is under the server's own policy from the start.
+    pub fn without_startup_who_is_policy(mut self) -> Self {
+        self.startup_who_is_policy = None;
+        self
+    }
+}
+
+/// Reports when a [`BacnetServer`](crate::BacnetServer) has joined the network, from
+/// [`BacnetServer::ready`](crate::BacnetServer::ready).
+///
+/// A server without [`with_startup`](crate::BacnetServer::with_startup) is ready as soon
+/// as it serves.
+#[derive(Debug, Clone)]
+pub struct ServerReady {
+    rx: watch::Receiver<bool>,
+}
+
+impl ServerReady {
+    pub(crate) fn new(rx: watch::Receiver<bool>) -> Self {
+        Self { rx }
+    }
+
+    /// `true` once the device is registered (if it registers) and announced.
+    pub fn is_ready(&self) -> bool {
+        *self.rx.borrow()
+    }
+
+    /// Waits until the device is ready. Returns at once if it already is, and also
+    /// when the server is dropped before getting there.
+    pub async fn wait(&mut self) {
+        let _ = self.rx.wait_for(|ready| *ready).await;
+    }
+}
+
+/// Sends one foreign-device registration with a TTL over a server's link.
+pub(crate) type RegisterFn<D> =
+    Box<dyn Fn(Arc<D>, u16) -> BoxFuture<Result<(), DataLinkError>> + Send + Sync>;
+
+/// Registers a server's link with a BBMD; set for BACnet/IP links only.
+pub(crate) struct ForeignRegistration<D> {
+    pub(crate) ttl_seconds: u16,
+    pub(crate) register: RegisterFn<D>,
+}
+
+/// Where a server is in its startup sequence. Sans-IO: the server passes in the time.
+#[derive(Debug)]
+pub(crate) struct StartupState {
+    options: StartupOptions,
+    /// Next foreign-device registration or renewal, if the server registers.
+    pub(crate) register_at: Option<Instant>,
+    /// `false` until the first registration is acknowledged.
+    pub(crate) registered: bool,
+    pub(crate) announcements_left: u8,
+    pub(crate) announce_at: Instant,
+    /// End of the startup Who-Is policy, while it is in force.
+    pub(crate) policy_until: Option<Instant>,
+    /// The server's own Who-Is policy, restored at `policy_until`.
+    pub(crate) server_policy: WhoIsPolicy,
+}
+
+impl StartupState {
+    pub(crate) fn new(
+        options: StartupOptions,
+        registers: bool,
+        server_policy: WhoIsPolicy,
+        now: Instant,
+    ) -> Self {
+        Self {
+            options,
+            register_at: registers.then_some(now),
+            registered: !registers,
+            announcements_left: options.announcements,
+            announce_at: now,
+            policy_until: options
+                .startup_who_is_policy
+                .map(|_| now + options.startup_period),
+            server_policy,
+        }
+    }
+
+    pub(crate) fn options(&self) -> &StartupOptions {
+        &self.options
+    }
+
+    /// Registered, if the server registers, and announced at least once, if it announces.
+    pub(crate) fn is_ready(&self) -> bool {
+        self.registered && self.announcements_left < self.options.announcements.max(1)
+    }
+
+    /// When the sequence next has something to do.
+    pub(crate) fn next_due(&self) -> Option<Instant> {
+        let announce = (self.registered && self.announcements_left > 0).then_some(self.announce_at);
+        [self.register_at, announce, self.policy_until]
+            .into_iter()
+            .flatten()
+            .min()
+    }
+
+    /// Records a registration attempt at `now`: renewal at 75 % of the TTL once
+    /// acknowledged, another try shortly otherwise.
+    pub(crate) fn on_registration(&mut self, ok: bool, ttl_seconds: u16, now: Instant) {
+        if ok {
+            self.registered = true;
+            let refresh = u64::from(ttl_seconds).saturating_mul(3) / 4;
+            self.register_at = Some(now + Duration::from_secs(refresh.max(1)));
+        } else {
+            self.register_at = Some(now + REGISTRATION_RETRY);
+        }
+    }
+
+    /// Records an announcement sent at `now`.
+    pub(crate) fn on_announced(&mut self, now: Instant) {
+        self.announcements_left = self.announcements_left.saturating_sub(1);
+        self.announce_at = now + self.options.announce_interval;
+    }
+}
@@ -1,9 +1,9 @@
+use crate::runtime::{Runtime, TokioRuntime};
 use rustbac_datalink::DataLinkAddress;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
-use tokio::time::Instant;
 
 #[derive(Clone, Copy, Debug)]
 struct DeviceThrottleConfig {
@@ -22,6 +22,7 @@ pub struct DeviceThrottle {
     overrides: Mutex<HashMap<DataLinkAddress, DeviceThrottleConfig>>,
     default_max_concurrent: usize,
     default_min_interval: Duration,
+    runtime: Arc<dyn Runtime>,
 }
 
 impl DeviceThrottle {
@@ -33,9 +34,16 @@ impl DeviceThrottle {
             overrides: Mutex::new(HashMap::new()),
             default_max_concurrent: max_concurrent.max(1),
             default_min_interval: min_interval,
+            runtime: Arc::new(TokioRuntime),
         }
     }
 
+    /// Waits out minimum intervals on `runtime` instead of Tokio.
+    pub fn with_runtime(mut self, runtime: impl Runtime) -> Self {
+        self.runtime = Arc::new(runtime);
+        self
+    }
+
     /// Sets (or replaces) a per-device override.
     pub async fn set_device_limit(
         &self,
@@ -89,12 +97,12 @@ impl DeviceThrottle {
         if !config.min_interval.is_zero() {
             let mut last_request = self.last_request.lock().await;
             if let Some(last) = last_request.get(&address) {
-                let elapsed = last.elapsed();
+                let elapsed = self.runtime.now().saturating_duration_since(*last);
                 if elapsed < config.min_interval {
-                    tokio::time::sleep(config.min_interval - elapsed).await;
+                    self.runtime.sleep(config.min_interval - elapsed).await;
                 }
             }
-            last_request.insert(address, Instant::now());
+            last_request.insert(address, self.runtime.now());
         }
 
         permit
@@ -0,0 +1,264 @@
+//! Periodic time synchronization of a site from one client.
+//!
+//! [`BacnetClient::start_time_master`](crate::BacnetClient::start_time_master) runs a
+//! background task that sends UTCTimeSynchronization (or local TimeSynchronization) to a
+//! recipient list on a schedule modelled on the Device object's
+//! `Time_Synchronization_Interval`, `Align_Intervals` and `Interval_Offset`. After each
+//! round it can read the clocks of chosen devices and report the ones that drifted.
+
+use crate::BacnetClient;
+use rustbac_datalink::{DataLink, DataLinkAddress};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::watch;
+
+/// Schedule and recipients of a time master.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeMasterConfig {
+    /// Devices or broadcast addresses sent the time, one broadcast per network to reach
+    /// every device on it.
+    pub recipients: Vec<DataLinkAddress>,
+    /// Time between rounds.
+    pub interval: Duration,
+    /// Starts rounds on multiples of `interval` since midnight UTC (for intervals that
+    /// divide a day), instead of `interval` after the previous one.
+    pub align_intervals: bool,
+    /// Delay of each aligned round past the interval boundary.
+    pub interval_offset: Duration,
+    /// Sends local-time TimeSynchronization for this UTC offset (minutes local time is
+    /// behind UTC, daylight saving included) instead of UTCTimeSynchronization.
+    pub local_utc_offset_minutes: Option<i32>,
+    /// Least time between two sends of one round, so long lists do not flood slow links.
+    pub min_send_interval: Duration,
+    /// Devices whose clocks are read after each round.
+    pub drift_check: Vec<DataLinkAddress>,
+    /// Clock differences beyond this are reported as drift.
+    pub drift_threshold: Duration,
+}
+
+impl TimeMasterConfig {
+    /// Hourly UTC synchronization of `recipients`, aligned to the hour, without drift
+    /// checks; drift beyond 5 s is reported once devices are added.
+    pub fn new(recipients: Vec<DataLinkAddress>) -> Self {
+        Self {
+            recipients,
+            interval: Duration::from_secs(3600),
+            align_intervals: true,
+            interval_offset: Duration::ZERO,
+            local_utc_offset_minutes: None,
+            min_send_interval: Duration::ZERO,
+            drift_check: Vec::new(),
+            drift_threshold: Duration::from_secs(5),
+        }
+    }
+
+    /// Sets the time between rounds, clamped to a minimum of 1 s.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval.max(Duration::from_secs(1));
+        self
+    }
+
+    /// Aligns rounds to interval boundaries, `offset` past each.
+    pub fn with_alignment(mut self, offset: Duration) -> Self {
+        self.align_intervals = true;
+        self.interval_offset = offset;
+        self
+    }
+
+    /// Starts each round `interval` after the previous one, the first immediately.
+    pub fn without_alignment(mut self) -> Self {
+        self.align_intervals = false;
+        self
+    }
+
+    /// Sends local time for `utc_offset_minutes` instead of UTC.
+    pub fn with_local_time(mut self, utc_offset_minutes: i32) -> Self {
+        self.local_utc_offset_minutes = Some(utc_offset_minutes);
+        self
+    }
+
+    /// Spaces the sends of a round at least `interval` apart.
+    pub fn with_min_send_interval(mut self, interval: Duration) -> Self {
+        self.min_send_interval = interval;
+        self
+    }
+
+    /// Reads the clocks of `devices` after each round and reports drift beyond
+    /// `threshold`.
+    pub fn with_drift_check(mut self, devices: Vec<DataLinkAddress>, threshold: Duration) -> Self {
+        self.drift_check = devices;
+        self.drift_threshold = threshold;
+        self
+    }
+
+    /// Time from `now` to the next round, the previous one having started at `last`.
+    pub(crate) fn delay_until_next(&self, now: SystemTime, last: Option<SystemTime>) -> Duration {
+        if !self.align_intervals {
+            return match last {
+                Some(last) => (last + self.interval)
+                    .duration_since(now)
+                    .unwrap_or(Duration::ZERO),
+                None => Duration::ZERO,
+            };
+        }
+        // A timer that fires a little early must not run the same boundary twice.
+        let earliest = last.map_or(now, |last| (last + self.interval / 2).max(now));
+        let interval = self.interval.as_millis().max(1);
+        let offset = self.interval_offset.as_millis() % interval;
+        let since_epoch = earliest
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let into_interval = (since_epoch + interval - offset) % interval;
+        let wait = earliest.duration_since(now).unwrap_or_default().as_millis();
+        let delay = wait + (interval - into_interval) % interval;
+        Duration::from_millis(u64::try_from(delay).unwrap_or(u64::MAX))
+    }
+}
+
+/// A device clock that differed from the host's by more than the drift threshold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClockDrift {
+    pub address: DataLinkAddress,
+    /// Milliseconds the device clock was ahead of the host's; negative when behind.
+    pub drift_millis: i64,
+}
+
+/// What a time master has done so far.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimeMasterStatus {
+    /// Rounds completed.
+    pub rounds: u64,
+    /// Host time the latest round started.
+    pub last_round_at: Option<SystemTime>,
+    /// Recipients the latest round could not send to.
+    pub failed: Vec<DataLinkAddress>,
+    /// Checked devices whose clocks drifted beyond the threshold in the latest round.
+    pub drifted: Vec<ClockDrift>,
+    /// Checked devices whose clocks could not be read in the latest round.
+    pub unreadable: Vec<DataLinkAddress>,
+}
+
+/// Handle to the time master started by
+/// [`BacnetClient::start_time_master`](crate::BacnetClient::start_time_master).
+///
+/// Dropping this value stops the master; [`TimeMaster::stop`] stops it explicitly.
+#[derive(Debug)]
+pub struct TimeMaster {
+    pub(crate) shutdown: watch::Sender<bool>,
+    pub(crate) status: watch::Receiver<TimeMasterStatus>,
+}
+
+impl TimeMaster {
+    /// Status after the latest round.
+    pub fn status(&self) -> TimeMasterStatus {
+        self.status.borrow().clone()
+    }
+
+    /// Returns a receiver that is notified after every round.
+    pub fn subscribe(&self) -> watch::Receiver<TimeMasterStatus> {
+        self.status.clone()
+    }
+
+    /// Stop the master, abandoning a round in progress.
+    pub fn stop(self) {
+        let _ = self.shutdown.send(true);
+    }
+}
+
+impl Drop for TimeMaster {
+    fn drop(&mut self) {
+        let _ = self.shutdown.send(true);
+    }
+}
+
+/// Runs rounds on `config`'s schedule until the future is dropped.
+pub(crate) async fn run_time_master<D: DataLink>(
+    client: &BacnetClient<D>,
+    config: &TimeMasterConfig,
+    status_tx: &watch::Sender<TimeMasterStatus>,
+) {
+    let threshold = config.drift_threshold.as_millis();
+    let mut last = None;
+    loop {
+        let delay = config.delay_until_next(SystemTime::now(), last);
+        if !delay.is_zero() {
+            client.runtime().sleep(delay).await;
+        }
+        let started = SystemTime::now();
+        last = Some(started);
+        let report = client
+            .time_synchronize_each(
+                &config.recipients,
+                started,
+                config.local_utc_offset_minutes,
+                config.min_send_interval,
+            )
+            .await;
+        let mut drifted = Vec::new();
+        let mut unreadable = Vec::new();
+        for &address in &config.drift_check {
+            let drift = match client.read_device_clock(address).await {
+                Ok(mut clock) => {
+                    clock.utc_offset_minutes = clock
+                        .utc_offset_minutes
+                        .or(Some(config.local_utc_offset_minutes.unwrap_or(0)));
+                    clock.drift_millis()
+                }
+                Err(err) => {
+                    log::warn!("time master could not read the clock of {address:?}: {err}");
+                    None
+                }
+            };
+            match drift {
+                Some(drift_millis) if u128::from(drift_millis.unsigned_abs()) > threshold => {
+                    drifted.push(ClockDrift {
+                        address,
+                        drift_millis,
+                    });
+                }
+                Some(_) => {}
+                None => unreadable.push(address),
+            }
+        }
+        status_tx.send_modify(|status| {
+            status.rounds += 1;
+            status.last_round_at = Some(started);
+            status.failed = report.failed.iter().map(|f| f.address).collect();
+            status.drifted = drifted;
+            status.unreadable = unreadable;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimeMasterConfig;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn aligned_rounds_start_on_interval_boundaries_plus_offset() {
+        let config = TimeMasterConfig::new(Vec::new()).with_alignment(Duration::from_secs(300));
+        // 2026-02-07 15:20:00 UTC: next round at 16:05.
+        let now = UNIX_EPOCH + Duration::from_secs(1_770_477_600);
+        assert_eq!(
+            config.delay_until_next(now, None),
+            Duration::from_secs(45 * 60)
+        );
+        // A round that started just before the boundary does not run it again.
+        let boundary = UNIX_EPOCH + Duration::from_secs(1_770_476_700);
+        assert_eq!(config.delay_until_next(boundary, None), Duration::ZERO);
+        assert_eq!(
+            config.delay_until_next(boundary, Some(boundary - Duration::from_millis(5))),
+            Duration::from_secs(3600)
+        );
+
+        let free = TimeMasterConfig::new(Vec::new())
+            .with_interval(Duration::from_secs(60))
+            .without_alignment();
+        assert_eq!(free.delay_until_next(now, None), Duration::ZERO);
+        assert_eq!(
+            free.delay_until_next(now, Some(now - Duration::from_secs(20))),
+            Duration::from_secs(40)
+        );
+    }
+}
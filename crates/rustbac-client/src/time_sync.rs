@@ -0,0 +1,134 @@
+use crate::export::bacnet_system_time;
+use crate::range::shift_minutes;
+use crate::ClientError;
+use rustbac_core::types::{Date, Time};
+use rustbac_datalink::DataLinkAddress;
+use std::time::{Duration, SystemTime};
+
+/// Outcome of a
+/// [`BacnetClient::time_synchronize_all`](crate::BacnetClient::time_synchronize_all) run.
+///
+/// TimeSynchronization is unconfirmed, so "synchronized" means the request left the
+/// transport; a device that ignores it is not detected here.
+#[derive(Debug, Default)]
+pub struct TimeSyncReport {
+    /// Targets the request was sent to, in send order.
+    pub synchronized: Vec<DataLinkAddress>,
+    /// Targets the request could not be sent to.
+    pub failed: Vec<TimeSyncFailure>,
+}
+
+impl TimeSyncReport {
+    /// Returns `true` when every target was sent the request.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// A target that could not be sent the TimeSynchronization request.
+#[derive(Debug)]
+pub struct TimeSyncFailure {
+    pub address: DataLinkAddress,
+    pub error: ClientError,
+}
+
+/// A device's clock as read by
+/// [`BacnetClient::read_device_clock`](crate::BacnetClient::read_device_clock).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceClock {
+    /// The device's Local_Date.
+    pub date: Date,
+    /// The device's Local_Time.
+    pub time: Time,
+    /// The minutes local time is behind UTC, daylight saving included; `None` when the
+    /// device has no UTC_Offset.
+    pub utc_offset_minutes: Option<i32>,
+    /// The device's Daylight_Savings_Status, when it has one.
+    pub daylight_savings: Option<bool>,
+    /// Host time Local_Time was read at, halfway through the request.
+    pub read_at: SystemTime,
+}
+
+impl DeviceClock {
+    /// The UTC instant the device's clock showed, or `None` without a UTC offset or with
+    /// wildcards in the date or time.
+    pub fn utc(&self) -> Option<SystemTime> {
+        let local = bacnet_system_time(self.date, self.time)?;
+        Some(shift_minutes(local, i64::from(self.utc_offset_minutes?)))
+    }
+
+    /// Milliseconds the device's clock was ahead of the host's; negative when behind.
+    pub fn drift_millis(&self) -> Option<i64> {
+        let utc = self.utc()?;
+        Some(match utc.duration_since(self.read_at) {
+            Ok(ahead) => i64::try_from(ahead.as_millis()).unwrap_or(i64::MAX),
+            Err(behind) => -i64::try_from(behind.duration().as_millis()).unwrap_or(i64::MAX),
+        })
+    }
+}
+
+/// Outcome of a
+/// [`BacnetClient::audit_device_clocks`](crate::BacnetClient::audit_device_clocks) sweep.
+#[derive(Debug)]
+pub struct ClockAuditReport {
+    /// Drift beyond this is reported by [`drifted`](Self::drifted).
+    pub threshold: Duration,
+    /// Clocks read, in sweep order.
+    pub clocks: Vec<ClockAuditEntry>,
+    /// Devices whose clocks could not be read.
+    pub failed: Vec<ClockReadFailure>,
+}
+
+impl ClockAuditReport {
+    /// Clocks whose drift from the host exceeds the threshold.
+    pub fn drifted(&self) -> impl Iterator<Item = &ClockAuditEntry> {
+        let threshold = self.threshold.as_millis();
+        self.clocks.iter().filter(move |entry| {
+            entry
+                .drift_millis
+                .is_some_and(|drift| u128::from(drift.unsigned_abs()) > threshold)
+        })
+    }
+
+    /// Clocks whose drift is unknown: no UTC_Offset, or wildcards in the date or time.
+    pub fn unchecked(&self) -> impl Iterator<Item = &ClockAuditEntry> {
+        self.clocks
+            .iter()
+            .filter(|entry| entry.drift_millis.is_none())
+    }
+
+    /// Clocks whose UTC offset, daylight saving included, is not `expected_minutes`,
+    /// typically devices with the wrong time zone or a missed DST change.
+    pub fn offset_mismatches(
+        &self,
+        expected_minutes: i32,
+    ) -> impl Iterator<Item = &ClockAuditEntry> {
+        self.clocks.iter().filter(move |entry| {
+            entry
+                .clock
+                .utc_offset_minutes
+                .is_some_and(|offset| offset != expected_minutes)
+        })
+    }
+
+    /// Returns `true` when every clock was read and none drifted beyond the threshold.
+    pub fn is_clean(&self) -> bool {
+        self.failed.is_empty() && self.drifted().next().is_none()
+    }
+}
+
+/// One device's clock in a [`ClockAuditReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClockAuditEntry {
+    pub address: DataLinkAddress,
+    pub clock: DeviceClock,
+    /// [`DeviceClock::drift_millis`] of `clock`.
+    pub drift_millis: Option<i64>,
+}
+
+/// A device whose clock could not be read.
+#[derive(Debug)]
+pub struct ClockReadFailure {
+    pub address: DataLinkAddress,
+    pub error: ClientError,
+}
@@ -0,0 +1,258 @@
+use crate::ClientError;
+use rustbac_datalink::DataLinkAddress;
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+/// Number of transactions a [`BacnetClient`](crate::BacnetClient) remembers by default.
+pub const DEFAULT_TRANSACTION_LOG_CAPACITY: usize = 64;
+
+/// How a confirmed request ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransactionOutcome {
+    /// The device answered with a SimpleACK.
+    SimpleAck,
+    /// The device answered with a ComplexACK; `payload_len` is the reassembled service data
+    /// length in bytes.
+    ComplexAck { payload_len: usize },
+    /// The device answered with an Error PDU.
+    Error {
+        error_class_raw: Option<u32>,
+        error_code_raw: Option<u32>,
+    },
+    /// The device answered with a Reject PDU.
+    Reject { reason: u8 },
+    /// The transaction was aborted.
+    Abort { reason: u8, server: bool },
+    /// No response arrived within the response timeout.
+    Timeout,
+    /// The request failed locally (transport, encode, or decode error).
+    Failed { message: String },
+}
+
+impl TransactionOutcome {
+    /// Returns `true` for SimpleACK and ComplexACK outcomes.
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::SimpleAck | Self::ComplexAck { .. })
+    }
+
+    pub(crate) fn from_result<T>(
+        result: &Result<T, ClientError>,
+        ok: impl FnOnce(&T) -> Self,
+    ) -> Self {
+        match result.as_ref().map_err(ClientError::root) {
+            Ok(value) => ok(value),
+            Err(ClientError::RemoteServiceError {
+                error_class_raw,
+                error_code_raw,
+                ..
+            }) => Self::Error {
+                error_class_raw: *error_class_raw,
+                error_code_raw: *error_code_raw,
+            },
+            Err(ClientError::RemoteReject { reason }) => Self::Reject { reason: *reason },
+            Err(ClientError::RemoteAbort { reason, server }) => Self::Abort {
+                reason: *reason,
+                server: *server,
+            },
+            Err(ClientError::Timeout) => Self::Timeout,
+            Err(e) => Self::Failed {
+                message: e.to_string(),
+            },
+        }
+    }
+}
+
+/// One confirmed request/response exchange recorded by the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransactionRecord {
+    /// Device the request was sent to.
+    pub address: DataLinkAddress,
+    pub invoke_id: u8,
+    /// Confirmed service choice of the request (e.g. `0x0C` for ReadProperty).
+    pub service_choice: u8,
+    /// Encoded request length in bytes, including the NPDU header.
+    pub request_len: usize,
+    /// Wall-clock time the request was sent.
+    pub started_at: SystemTime,
+    /// Time from sending the request to the outcome.
+    pub elapsed: Duration,
+    pub outcome: TransactionOutcome,
+}
+
+/// Confirmed-request counters for one device, kept by a
+/// [`BacnetClient`](crate::BacnetClient) since it was created or the counters were reset.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceStats {
+    /// Confirmed requests that ended, whatever the outcome.
+    pub requests: u64,
+    /// Requests answered with a SimpleACK or ComplexACK.
+    pub acks: u64,
+    pub errors: u64,
+    pub rejects: u64,
+    pub aborts: u64,
+    pub timeouts: u64,
+    /// Requests that failed locally (transport, encode, or decode error).
+    pub failures: u64,
+    /// Segments of segmented requests sent to the device.
+    pub segments_sent: u64,
+    /// Segments of segmented ComplexACKs received from the device.
+    pub segments_received: u64,
+    /// Times the device postponed a reply, asking the client to wait longer.
+    pub postponed_replies: u64,
+    /// Summed time to the response, over requests the device answered.
+    pub total_latency: Duration,
+    /// Longest time to a response.
+    pub max_latency: Duration,
+    /// Wall-clock time the latest request was sent.
+    pub last_request_at: Option<SystemTime>,
+}
+
+impl DeviceStats {
+    /// Requests the device answered, with an ACK, Error, Reject or Abort.
+    pub fn answered(&self) -> u64 {
+        self.acks + self.errors + self.rejects + self.aborts
+    }
+
+    /// Mean time to a response, or `None` before the device answered anything.
+    pub fn average_latency(&self) -> Option<Duration> {
+        let answered = u32::try_from(self.answered()).ok().filter(|&n| n > 0)?;
+        Some(self.total_latency / answered)
+    }
+
+    /// Share of requests that did not end in an ACK, or `0.0` before any request.
+    pub fn failure_rate(&self) -> f64 {
+        if self.requests == 0 {
+            return 0.0;
+        }
+        (self.requests - self.acks) as f64 / self.requests as f64
+    }
+
+    pub(crate) fn record(&mut self, record: &TransactionRecord) {
+        self.requests += 1;
+        let answered = match record.outcome {
+            TransactionOutcome::SimpleAck | TransactionOutcome::ComplexAck { .. } => {
+                self.acks += 1;
+                true
+            }
+            TransactionOutcome::Error { .. } => {
+                self.errors += 1;
+                true
+            }
+            TransactionOutcome::Reject { .. } => {
+                self.rejects += 1;
+                true
+            }
+            TransactionOutcome::Abort { .. } => {
+                self.aborts += 1;
+                true
+            }
+            TransactionOutcome::Timeout => {
+                self.timeouts += 1;
+                false
+            }
+            TransactionOutcome::Failed { .. } => {
+                self.failures += 1;
+                false
+            }
+        };
+        if answered {
+            self.total_latency += record.elapsed;
+            self.max_latency = self.max_latency.max(record.elapsed);
+        }
+        self.last_request_at = Some(record.started_at);
+    }
+}
+
+/// Fixed-capacity ring buffer of the most recent transactions.
+#[derive(Debug)]
+pub(crate) struct TransactionLog {
+    capacity: usize,
+    records: VecDeque<TransactionRecord>,
+}
+
+impl TransactionLog {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            records: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub(crate) fn push(&mut self, record: TransactionRecord) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self.records.len() >= self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    pub(crate) fn snapshot(&self) -> Vec<TransactionRecord> {
+        self.records.iter().cloned().collect()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.records.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeviceStats, TransactionLog, TransactionOutcome, TransactionRecord};
+    use rustbac_datalink::DataLinkAddress;
+    use std::time::{Duration, SystemTime};
+
+    fn record(invoke_id: u8) -> TransactionRecord {
+        TransactionRecord {
+            address: DataLinkAddress::Mstp(1),
+            invoke_id,
+            service_choice: 0x0C,
+            request_len: 17,
+            started_at: SystemTime::UNIX_EPOCH,
+            elapsed: Duration::from_millis(5),
+            outcome: TransactionOutcome::Timeout,
+        }
+    }
+
+    #[test]
+    fn ring_buffer_keeps_most_recent() {
+        let mut log = TransactionLog::new(3);
+        for invoke_id in 0..5 {
+            log.push(record(invoke_id));
+        }
+        let ids: Vec<u8> = log.snapshot().iter().map(|r| r.invoke_id).collect();
+        assert_eq!(ids, vec![2, 3, 4]);
+
+        let mut disabled = TransactionLog::new(0);
+        disabled.push(record(0));
+        assert!(disabled.snapshot().is_empty());
+    }
+
+    #[test]
+    fn device_stats_average_latency_over_answered_requests() {
+        let mut stats = DeviceStats::default();
+        assert_eq!(stats.average_latency(), None);
+        stats.record(&record(0));
+        stats.record(&TransactionRecord {
+            elapsed: Duration::from_millis(30),
+            outcome: TransactionOutcome::SimpleAck,
+            ..record(1)
+        });
+        stats.record(&TransactionRecord {
+            elapsed: Duration::from_millis(10),
+            outcome: TransactionOutcome::Reject { reason: 9 },
+            ..record(2)
+        });
+
+        assert_eq!(stats.requests, 3);
+        assert_eq!(stats.timeouts, 1);
+        assert_eq!(stats.rejects, 1);
+        assert_eq!(stats.average_latency(), Some(Duration::from_millis(20)));
+        assert_eq!(stats.max_latency, Duration::from_millis(30));
+        assert!((stats.failure_rate() - 2.0 / 3.0).abs() < 1e-9);
+    }
+}
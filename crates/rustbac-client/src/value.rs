@@ -1,10 +1,12 @@
 use rustbac_core::types::{Date, Time};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
 
 /// An owned BACnet application-data value returned by client read operations.
 ///
 /// This is the client-side counterpart to the zero-copy `DataValue<'_>` used internally.
-/// All byte-slice and string fields are allocated as owned `Vec`/`String` so the value
-/// can outlive the receive buffer.
+/// Byte-slice fields are copied into owned `Vec`s and strings into `Arc<str>` so the value
+/// can outlive the receive buffer; see [`StringInterner`] for sharing repeated strings.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ClientDataValue {
@@ -23,7 +25,10 @@ pub enum ClientDataValue {
     /// BACnet Octet String — arbitrary raw bytes.
     OctetString(Vec<u8>),
     /// BACnet Character String — UTF-8 text.
-    CharacterString(String),
+    ///
+    /// An `Arc<str>` since 0.4.0 (a `String` before), so equal strings can share one
+    /// allocation. Build one with `"text".into()` and read it with `&*text`.
+    CharacterString(Arc<str>),
     /// BACnet Bit String.
     ///
     /// `unused_bits` is the number of padding bits in the last byte of `data` that are not
@@ -45,3 +50,133 @@ pub enum ClientDataValue {
         values: Vec<ClientDataValue>,
     },
 }
+
+/// Shares one allocation between equal [`ClientDataValue::CharacterString`]s.
+///
+/// Thousands of points on a site often carry the same state texts, descriptions or
+/// unit labels. Give a client an interner with
+/// [`BacnetClient::with_string_interner`](crate::BacnetClient::with_string_interner) and
+/// every string it decodes is looked up here first, so large walks, trend histories and
+/// COV streams hold one `Arc<str>` per distinct text. Clones share the table.
+///
+/// The table stops growing at [`max_strings`](Self::with_max_strings) entries; further
+/// new texts are returned unshared.
+#[derive(Debug, Clone)]
+pub struct StringInterner {
+    strings: Arc<Mutex<HashSet<Arc<str>>>>,
+    max_strings: usize,
+}
+
+impl Default for StringInterner {
+    fn default() -> Self {
+        Self {
+            strings: Arc::default(),
+            max_strings: 65_536,
+        }
+    }
+}
+
+impl StringInterner {
+    /// An empty interner holding up to 65 536 distinct strings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the most distinct strings kept.
+    pub fn with_max_strings(mut self, max_strings: usize) -> Self {
+        self.max_strings = max_strings;
+        self
+    }
+
+    /// The shared copy of `text`, adding it if there is room.
+    pub fn intern(&self, text: &str) -> Arc<str> {
+        let mut strings = self.strings.lock().expect("interner lock poisoned");
+        Self::lookup(&mut strings, self.max_strings, text).unwrap_or_else(|| Arc::from(text))
+    }
+
+    /// Replaces every character string in `value`, at any depth, with its shared copy.
+    pub fn intern_value(&self, value: &mut ClientDataValue) {
+        let mut strings = self.strings.lock().expect("interner lock poisoned");
+        Self::intern_in(&mut strings, self.max_strings, value);
+    }
+
+    /// Number of distinct strings held.
+    pub fn len(&self) -> usize {
+        self.strings.lock().expect("interner lock poisoned").len()
+    }
+
+    /// Whether no strings are held.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forgets every string; values already returned keep theirs.
+    pub fn clear(&self) {
+        self.strings.lock().expect("interner lock poisoned").clear();
+    }
+
+    fn lookup(strings: &mut HashSet<Arc<str>>, max_strings: usize, text: &str) -> Option<Arc<str>> {
+        if let Some(shared) = strings.get(text) {
+            return Some(shared.clone());
+        }
+        if strings.len() >= max_strings {
+            return None;
+        }
+        let shared: Arc<str> = Arc::from(text);
+        strings.insert(shared.clone());
+        Some(shared)
+    }
+
+    fn intern_in(strings: &mut HashSet<Arc<str>>, max_strings: usize, value: &mut ClientDataValue) {
+        match value {
+            ClientDataValue::CharacterString(text) => {
+                if let Some(shared) = Self::lookup(strings, max_strings, text) {
+                    *text = shared;
+                }
+            }
+            ClientDataValue::Constructed { values, .. } => {
+                for child in values {
+                    Self::intern_in(strings, max_strings, child);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClientDataValue, StringInterner};
+    use std::sync::Arc;
+
+    #[test]
+    fn interner_shares_equal_strings_up_to_its_limit() {
+        let interner = StringInterner::new().with_max_strings(2);
+        let mut value = ClientDataValue::Constructed {
+            tag_num: 3,
+            values: vec![
+                ClientDataValue::CharacterString("Off".into()),
+                ClientDataValue::CharacterString("On".into()),
+                ClientDataValue::CharacterString("Off".into()),
+                ClientDataValue::CharacterString("Auto".into()),
+            ],
+        };
+        interner.intern_value(&mut value);
+        let ClientDataValue::Constructed { values, .. } = &value else {
+            unreachable!()
+        };
+        let text = |i: usize| match &values[i] {
+            ClientDataValue::CharacterString(s) => s.clone(),
+            other => panic!("unexpected {other:?}"),
+        };
+        assert!(Arc::ptr_eq(&text(0), &text(2)));
+        assert!(Arc::ptr_eq(&text(0), &interner.intern("Off")));
+        // Full: "Auto" stays unshared.
+        assert_eq!(interner.len(), 2);
+        assert!(!Arc::ptr_eq(&text(3), &interner.intern("Auto")));
+        assert_eq!(&*text(3), "Auto");
+
+        interner.clear();
+        assert!(interner.is_empty());
+    }
+}
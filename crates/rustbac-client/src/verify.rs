@@ -0,0 +1,105 @@
+use crate::compare::{values_equivalent, ValueComparison};
+use crate::ClientDataValue;
+use std::time::Duration;
+
+/// Options controlling [`BacnetClient::write_verified`](crate::BacnetClient::write_verified).
+///
+/// The default performs one write plus up to two retries, compares numeric values with an
+/// absolute tolerance of `0.001`, and re-reads immediately after each write.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WriteVerifyOptions {
+    /// Maximum absolute difference accepted between the written and read-back value for
+    /// numeric properties; see [`values_match`].
+    pub tolerance: f64,
+    /// Number of additional write + read-back attempts after the first mismatch.
+    pub retries: u8,
+    /// Delay between a write and the following read-back, giving the device time to apply
+    /// the new value (e.g. when the write goes through a command priority array).
+    pub settle_delay: Duration,
+}
+
+impl Default for WriteVerifyOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.001,
+            retries: 2,
+            settle_delay: Duration::ZERO,
+        }
+    }
+}
+
+impl WriteVerifyOptions {
+    /// Sets the tolerance used when comparing floating-point values.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance.abs();
+        self
+    }
+
+    /// Sets the number of retries after a verification mismatch.
+    pub fn with_retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the delay between writing and reading back the property.
+    pub fn with_settle_delay(mut self, delay: Duration) -> Self {
+        self.settle_delay = delay;
+        self
+    }
+}
+
+/// Returns `true` when `observed` matches `expected` under the given numeric `tolerance`.
+///
+/// Values are compared with [`values_equivalent`]: numbers numerically across `Real`,
+/// `Double`, `Unsigned` and `Signed`, so a device that stores a written `Real` as `Double`
+/// still verifies, and text after normalization, so NUL padding does not fail a write.
+pub fn values_match(
+    expected: &ClientDataValue,
+    observed: &ClientDataValue,
+    tolerance: f64,
+) -> bool {
+    values_equivalent(
+        expected,
+        observed,
+        &ValueComparison::default().with_tolerance(tolerance),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::values_match;
+    use crate::ClientDataValue;
+
+    #[test]
+    fn real_values_match_within_tolerance() {
+        assert!(values_match(
+            &ClientDataValue::Real(21.5),
+            &ClientDataValue::Real(21.5004),
+            0.001
+        ));
+        assert!(!values_match(
+            &ClientDataValue::Real(21.5),
+            &ClientDataValue::Real(21.6),
+            0.001
+        ));
+        assert!(values_match(
+            &ClientDataValue::Real(1.0),
+            &ClientDataValue::Double(1.0),
+            0.0
+        ));
+    }
+
+    #[test]
+    fn non_float_values_require_equality() {
+        assert!(values_match(
+            &ClientDataValue::Enumerated(1),
+            &ClientDataValue::Enumerated(1),
+            10.0
+        ));
+        assert!(!values_match(
+            &ClientDataValue::Enumerated(1),
+            &ClientDataValue::Unsigned(1),
+            10.0
+        ));
+    }
+}
@@ -2,11 +2,12 @@
 //! every object on a BACnet device.
 
 use crate::{BacnetClient, ClientDataValue, ClientError};
+use futures_util::stream::{self, Stream, StreamExt, TryStreamExt};
 use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
 use rustbac_datalink::{DataLink, DataLinkAddress};
 
 /// Summary of a single object on a device.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ObjectSummary {
     pub object_id: ObjectId,
@@ -16,10 +17,13 @@ pub struct ObjectSummary {
     pub description: Option<String>,
     pub units: Option<u32>,
     pub status_flags: Option<ClientDataValue>,
+    /// The object's Property_List, if the device supports that property; for a walk
+    /// [reading ALL](WalkOptions::read_all), the properties the device returned.
+    pub property_list: Option<Vec<PropertyId>>,
 }
 
 /// Metadata read from the Device object during a walk.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeviceInfo {
     pub vendor_name: Option<String>,
@@ -28,7 +32,7 @@ pub struct DeviceInfo {
 }
 
 /// Result of a full device walk.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeviceWalkResult {
     pub device_id: ObjectId,
@@ -36,50 +40,74 @@ pub struct DeviceWalkResult {
     pub objects: Vec<ObjectSummary>,
 }
 
+/// Properties read for each object's [`ObjectSummary`].
+const SUMMARY_PROPERTIES: &[PropertyId] = &[
+    PropertyId::ObjectName,
+    PropertyId::ObjectType,
+    PropertyId::PresentValue,
+    PropertyId::Description,
+    PropertyId::Units,
+    PropertyId::StatusFlags,
+];
+
+/// Options for [`walk_device_stream`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalkOptions {
+    /// Objects read at once, clamped to a minimum of 1.
+    pub concurrency: usize,
+    /// Reads each object with one ReadPropertyMultiple of ALL instead of its
+    /// Property_List and then the summary properties. Objects the device will not read
+    /// that way are read the usual way.
+    pub read_all: bool,
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            read_all: false,
+        }
+    }
+}
+
+impl WalkOptions {
+    /// Sets how many objects are read at once.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Sets whether objects are read with a single ReadPropertyMultiple of ALL.
+    pub fn with_read_all(mut self, read_all: bool) -> Self {
+        self.read_all = read_all;
+        self
+    }
+}
+
 /// Walk a BACnet device: read its object list, then batch-read common
 /// properties for each object.
+///
+/// If the Device object has a Property_List, each object's Property_List is read
+/// first and only the summary properties it lists are requested, so objects are not
+/// probed for properties they lack.
+///
+/// Objects are read one at a time and collected; use [`walk_device_stream`] to process
+/// them as they arrive.
 pub async fn walk_device<D: DataLink>(
     client: &BacnetClient<D>,
     addr: DataLinkAddress,
     device_id: ObjectId,
 ) -> Result<DeviceWalkResult, ClientError> {
-    // 1. Read the object list.
-    let object_list_value = client
-        .read_property(addr, device_id, PropertyId::ObjectList)
-        .await?;
-
-    let object_ids = extract_object_ids(&object_list_value);
-
-    // 2. For each object, read common properties via ReadPropertyMultiple.
-    let properties = &[
-        PropertyId::ObjectName,
-        PropertyId::ObjectType,
-        PropertyId::PresentValue,
-        PropertyId::Description,
-        PropertyId::Units,
-        PropertyId::StatusFlags,
-    ];
-
-    let mut objects = Vec::with_capacity(object_ids.len());
-    for &oid in &object_ids {
-        let props = client.read_property_multiple(addr, oid, properties).await;
-
-        let summary = match props {
-            Ok(prop_values) => build_summary(oid, &prop_values),
-            Err(_) => ObjectSummary {
-                object_id: oid,
-                object_name: None,
-                object_type: oid.object_type(),
-                present_value: None,
-                description: None,
-                units: None,
-                status_flags: None,
-            },
-        };
-        objects.push(summary);
-    }
+    let objects = walk_device_stream(
+        client,
+        addr,
+        device_id,
+        WalkOptions::default().with_concurrency(1),
+    )
+    .try_collect()
+    .await?;
 
-    // 3. Read device metadata (vendor, model, firmware) from the Device object.
+    // Device metadata (vendor, model, firmware) from the Device object.
     let device_info = read_device_info(client, addr, device_id).await;
 
     Ok(DeviceWalkResult {
@@ -89,7 +117,103 @@ pub async fn walk_device<D: DataLink>(
     })
 }
 
-async fn read_device_info<D: DataLink>(
+/// Walk a BACnet device as a stream of [`ObjectSummary`]s, in Object_List order.
+///
+/// The object list is read when the stream is first polled; after that up to
+/// `options.concurrency` objects are read at once, and only as fast as the stream is
+/// consumed, so a UI can render a 10 000-object device progressively without holding
+/// the whole walk. An object whose properties cannot be read yields a summary with only
+/// its identifier; the stream fails only if the object list itself cannot be read.
+/// Device metadata is not included; see [`read_device_info`].
+pub fn walk_device_stream<D: DataLink>(
+    client: &BacnetClient<D>,
+    addr: DataLinkAddress,
+    device_id: ObjectId,
+    options: WalkOptions,
+) -> impl Stream<Item = Result<ObjectSummary, ClientError>> + '_ {
+    let concurrency = options.concurrency.max(1);
+    let read_all = options.read_all;
+    stream::once(async move {
+        let object_ids = client.read_object_list(addr, device_id).await?;
+        let has_property_list = client.read_property_list(addr, device_id).await.is_ok();
+        Ok::<_, ClientError>(
+            stream::iter(object_ids)
+                .map(move |oid| read_object_summary(client, addr, oid, has_property_list, read_all))
+                .buffered(concurrency)
+                .map(Ok),
+        )
+    })
+    .try_flatten()
+}
+
+/// Reads the summary properties of one object, with a read of ALL when `read_all` and
+/// the device handles ReadPropertyMultiple, otherwise limited to its Property_List when
+/// `has_property_list`.
+async fn read_object_summary<D: DataLink>(
+    client: &BacnetClient<D>,
+    addr: DataLinkAddress,
+    oid: ObjectId,
+    has_property_list: bool,
+    read_all: bool,
+) -> ObjectSummary {
+    if read_all && !client.uses_rpm_fallback(addr) {
+        if let Ok(all) = client.read_property_set(addr, oid, PropertyId::All).await {
+            let mut summary = build_summary(oid, &all.values);
+            summary.property_list = Some(
+                all.property_ids()
+                    .into_iter()
+                    .filter(|pid| {
+                        !matches!(
+                            pid,
+                            PropertyId::ObjectIdentifier
+                                | PropertyId::ObjectName
+                                | PropertyId::ObjectType
+                                | PropertyId::PropertyList
+                        )
+                    })
+                    .collect(),
+            );
+            return summary;
+        }
+    }
+    let property_list = if has_property_list {
+        client.read_property_list(addr, oid).await.ok()
+    } else {
+        None
+    };
+    let properties: Vec<PropertyId> = match &property_list {
+        // Property_List never includes Object_Name and Object_Type.
+        Some(list) => SUMMARY_PROPERTIES
+            .iter()
+            .copied()
+            .filter(|pid| {
+                matches!(pid, PropertyId::ObjectName | PropertyId::ObjectType) || list.contains(pid)
+            })
+            .collect(),
+        None => SUMMARY_PROPERTIES.to_vec(),
+    };
+    let props = client.read_property_multiple(addr, oid, &properties).await;
+
+    let mut summary = match props {
+        Ok(prop_values) => build_summary(oid, &prop_values),
+        Err(_) => ObjectSummary {
+            object_id: oid,
+            object_name: None,
+            object_type: oid.object_type(),
+            present_value: None,
+            description: None,
+            units: None,
+            status_flags: None,
+            property_list: None,
+        },
+    };
+    summary.property_list = property_list;
+    summary
+}
+
+/// Read vendor, model and firmware revision from the Device object; properties that
+/// cannot be read are left `None`.
+pub async fn read_device_info<D: DataLink>(
     client: &BacnetClient<D>,
     addr: DataLinkAddress,
     device_id: ObjectId,
@@ -112,9 +236,9 @@ async fn read_device_info<D: DataLink>(
     for (pid, val) in &prop_values {
         if let ClientDataValue::CharacterString(s) = val {
             match pid {
-                PropertyId::VendorName => info.vendor_name = Some(s.clone()),
-                PropertyId::ModelName => info.model_name = Some(s.clone()),
-                PropertyId::FirmwareRevision => info.firmware_revision = Some(s.clone()),
+                PropertyId::VendorName => info.vendor_name = Some(s.to_string()),
+                PropertyId::ModelName => info.model_name = Some(s.to_string()),
+                PropertyId::FirmwareRevision => info.firmware_revision = Some(s.to_string()),
                 _ => {}
             }
         }
@@ -122,23 +246,6 @@ async fn read_device_info<D: DataLink>(
     info
 }
 
-fn extract_object_ids(value: &ClientDataValue) -> Vec<ObjectId> {
-    match value {
-        ClientDataValue::ObjectId(oid) => vec![*oid],
-        ClientDataValue::Constructed { values, .. } => values
-            .iter()
-            .filter_map(|v| {
-                if let ClientDataValue::ObjectId(oid) = v {
-                    Some(*oid)
-                } else {
-                    None
-                }
-            })
-            .collect(),
-        _ => vec![],
-    }
-}
-
 fn build_summary(oid: ObjectId, props: &[(PropertyId, ClientDataValue)]) -> ObjectSummary {
     let mut summary = ObjectSummary {
         object_id: oid,
@@ -148,13 +255,14 @@ fn build_summary(oid: ObjectId, props: &[(PropertyId, ClientDataValue)]) -> Obje
         description: None,
         units: None,
         status_flags: None,
+        property_list: None,
     };
 
     for (pid, val) in props {
         match pid {
             PropertyId::ObjectName => {
                 if let ClientDataValue::CharacterString(s) = val {
-                    summary.object_name = Some(s.clone());
+                    summary.object_name = Some(s.to_string());
                 }
             }
             PropertyId::ObjectType => {
@@ -167,7 +275,7 @@ fn build_summary(oid: ObjectId, props: &[(PropertyId, ClientDataValue)]) -> Obje
             }
             PropertyId::Description => {
                 if let ClientDataValue::CharacterString(s) = val {
-                    summary.description = Some(s.clone());
+                    summary.description = Some(s.to_string());
                 }
             }
             PropertyId::Units => {
@@ -0,0 +1,312 @@
+//! Comparison of two device walk snapshots, e.g. before and after maintenance, for
+//! configuration-drift audits.
+
+use crate::compare::{values_equivalent, ValueComparison};
+use crate::walk::{DeviceInfo, DeviceWalkResult, ObjectSummary};
+use crate::ClientDataValue;
+use rustbac_core::types::{ObjectId, PropertyId};
+use std::collections::BTreeMap;
+
+/// One property whose value differs between two snapshots.
+///
+/// `None` means the property was absent (not read, or unreadable) in that snapshot.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PropertyChange {
+    pub property_id: PropertyId,
+    pub before: Option<ClientDataValue>,
+    pub after: Option<ClientDataValue>,
+}
+
+/// An object present in both snapshots with at least one changed property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectChange {
+    pub object_id: ObjectId,
+    pub changes: Vec<PropertyChange>,
+}
+
+/// Structured difference between two [`DeviceWalkResult`]s.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceWalkDiff {
+    /// Changes to the Device object's vendor, model and firmware strings.
+    pub device_info: Vec<PropertyChange>,
+    /// Objects only in the second snapshot.
+    pub added: Vec<ObjectSummary>,
+    /// Objects only in the first snapshot.
+    pub removed: Vec<ObjectSummary>,
+    /// Objects in both snapshots whose summary properties differ.
+    pub changed: Vec<ObjectChange>,
+}
+
+impl DeviceWalkDiff {
+    /// Returns `true` if the two snapshots were equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.device_info.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+/// Options controlling [`diff_walks_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct WalkDiffOptions {
+    /// Skip Present_Value and Status_Flags, which change in normal operation, so only
+    /// configuration changes are reported.
+    pub ignore_live_values: bool,
+    /// Tolerance for comparing numeric values; see [`values_equivalent`].
+    pub float_tolerance: f64,
+}
+
+impl WalkDiffOptions {
+    pub fn with_ignore_live_values(mut self, ignore: bool) -> Self {
+        self.ignore_live_values = ignore;
+        self
+    }
+
+    pub fn with_float_tolerance(mut self, tolerance: f64) -> Self {
+        self.float_tolerance = tolerance;
+        self
+    }
+}
+
+/// Compares two walks of the same device with default options.
+pub fn diff_walks(before: &DeviceWalkResult, after: &DeviceWalkResult) -> DeviceWalkDiff {
+    diff_walks_with_options(before, after, &WalkDiffOptions::default())
+}
+
+/// Compares two walks of the same device.
+///
+/// Objects are matched by object identifier. Results are ordered by object identifier
+/// and, within an object, by property in summary order.
+pub fn diff_walks_with_options(
+    before: &DeviceWalkResult,
+    after: &DeviceWalkResult,
+    options: &WalkDiffOptions,
+) -> DeviceWalkDiff {
+    let mut diff = DeviceWalkDiff {
+        device_info: diff_properties(
+            &device_info_properties(&before.device_info),
+            &device_info_properties(&after.device_info),
+            options,
+        ),
+        ..DeviceWalkDiff::default()
+    };
+
+    let before_objects: BTreeMap<u32, &ObjectSummary> = before
+        .objects
+        .iter()
+        .map(|obj| (obj.object_id.raw(), obj))
+        .collect();
+    let after_objects: BTreeMap<u32, &ObjectSummary> = after
+        .objects
+        .iter()
+        .map(|obj| (obj.object_id.raw(), obj))
+        .collect();
+
+    for (raw, obj) in &before_objects {
+        match after_objects.get(raw) {
+            None => diff.removed.push((*obj).clone()),
+            Some(new) => {
+                let changes =
+                    diff_properties(&summary_properties(obj), &summary_properties(new), options);
+                if !changes.is_empty() {
+                    diff.changed.push(ObjectChange {
+                        object_id: obj.object_id,
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+    diff.added = after_objects
+        .iter()
+        .filter(|(raw, _)| !before_objects.contains_key(raw))
+        .map(|(_, obj)| (*obj).clone())
+        .collect();
+    diff
+}
+
+type PropertySet = Vec<(PropertyId, Option<ClientDataValue>)>;
+
+fn device_info_properties(info: &DeviceInfo) -> PropertySet {
+    let string = |s: &Option<String>| {
+        s.as_deref()
+            .map(|s| ClientDataValue::CharacterString(s.into()))
+    };
+    vec![
+        (PropertyId::VendorName, string(&info.vendor_name)),
+        (PropertyId::ModelName, string(&info.model_name)),
+        (
+            PropertyId::FirmwareRevision,
+            string(&info.firmware_revision),
+        ),
+    ]
+}
+
+fn summary_properties(obj: &ObjectSummary) -> PropertySet {
+    vec![
+        (
+            PropertyId::ObjectName,
+            obj.object_name
+                .as_deref()
+                .map(|s| ClientDataValue::CharacterString(s.into())),
+        ),
+        (
+            PropertyId::ObjectType,
+            Some(ClientDataValue::Enumerated(obj.object_type.to_u16() as u32)),
+        ),
+        (PropertyId::PresentValue, obj.present_value.clone()),
+        (
+            PropertyId::Description,
+            obj.description
+                .as_deref()
+                .map(|s| ClientDataValue::CharacterString(s.into())),
+        ),
+        (
+            PropertyId::Units,
+            obj.units.map(ClientDataValue::Enumerated),
+        ),
+        (PropertyId::StatusFlags, obj.status_flags.clone()),
+        (
+            PropertyId::PropertyList,
+            obj.property_list
+                .as_ref()
+                .map(|list| ClientDataValue::Constructed {
+                    tag_num: 3,
+                    values: list
+                        .iter()
+                        .map(|pid| ClientDataValue::Enumerated(pid.to_u32()))
+                        .collect(),
+                }),
+        ),
+    ]
+}
+
+fn diff_properties(
+    before: &PropertySet,
+    after: &PropertySet,
+    options: &WalkDiffOptions,
+) -> Vec<PropertyChange> {
+    before
+        .iter()
+        .zip(after)
+        .filter(|((property_id, _), _)| {
+            !(options.ignore_live_values
+                && matches!(
+                    property_id,
+                    PropertyId::PresentValue | PropertyId::StatusFlags
+                ))
+        })
+        .filter(|((_, old), (_, new))| !values_equal(old, new, options.float_tolerance))
+        .map(|((property_id, old), (_, new))| PropertyChange {
+            property_id: *property_id,
+            before: old.clone(),
+            after: new.clone(),
+        })
+        .collect()
+}
+
+fn values_equal(
+    before: &Option<ClientDataValue>,
+    after: &Option<ClientDataValue>,
+    tolerance: f64,
+) -> bool {
+    match (before, after) {
+        (Some(a), Some(b)) => {
+            values_equivalent(a, b, &ValueComparison::default().with_tolerance(tolerance))
+        }
+        _ => before == after,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff_walks, diff_walks_with_options, PropertyChange, WalkDiffOptions};
+    use crate::walk::{DeviceInfo, DeviceWalkResult, ObjectSummary};
+    use crate::ClientDataValue;
+    use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+
+    fn summary(object_id: ObjectId, name: &str, present_value: f32) -> ObjectSummary {
+        ObjectSummary {
+            object_id,
+            object_name: Some(name.to_string()),
+            object_type: object_id.object_type(),
+            present_value: Some(ClientDataValue::Real(present_value)),
+            description: None,
+            units: Some(62),
+            status_flags: None,
+            property_list: None,
+        }
+    }
+
+    fn walk(firmware: &str, objects: Vec<ObjectSummary>) -> DeviceWalkResult {
+        DeviceWalkResult {
+            device_id: ObjectId::new(ObjectType::Device, 1),
+            device_info: DeviceInfo {
+                vendor_name: Some("Acme".into()),
+                model_name: None,
+                firmware_revision: Some(firmware.into()),
+            },
+            objects,
+        }
+    }
+
+    #[test]
+    fn reports_added_removed_and_changed_objects() {
+        let ai1 = ObjectId::new(ObjectType::AnalogInput, 1);
+        let ai2 = ObjectId::new(ObjectType::AnalogInput, 2);
+        let av3 = ObjectId::new(ObjectType::AnalogValue, 3);
+        let before = walk(
+            "1.0",
+            vec![summary(ai1, "OAT", 12.0), summary(ai2, "RAT", 21.0)],
+        );
+        let after = walk(
+            "1.1",
+            vec![summary(ai1, "OAT-1", 12.0), summary(av3, "SP", 22.0)],
+        );
+
+        let diff = diff_walks(&before, &after);
+        assert_eq!(
+            diff.device_info,
+            vec![PropertyChange {
+                property_id: PropertyId::FirmwareRevision,
+                before: Some(ClientDataValue::CharacterString("1.0".into())),
+                after: Some(ClientDataValue::CharacterString("1.1".into())),
+            }]
+        );
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].object_id, ai2);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].object_id, av3);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].object_id, ai1);
+        assert_eq!(
+            diff.changed[0].changes,
+            vec![PropertyChange {
+                property_id: PropertyId::ObjectName,
+                before: Some(ClientDataValue::CharacterString("OAT".into())),
+                after: Some(ClientDataValue::CharacterString("OAT-1".into())),
+            }]
+        );
+        assert!(!diff.is_empty());
+        assert!(diff_walks(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn options_ignore_live_values_and_float_noise() {
+        let ai1 = ObjectId::new(ObjectType::AnalogInput, 1);
+        let before = walk("1.0", vec![summary(ai1, "OAT", 12.0)]);
+        let after = walk("1.0", vec![summary(ai1, "OAT", 12.05)]);
+
+        assert_eq!(diff_walks(&before, &after).changed.len(), 1);
+        let tolerant = WalkDiffOptions::default().with_float_tolerance(0.1);
+        assert!(diff_walks_with_options(&before, &after, &tolerant).is_empty());
+
+        let after = walk("1.0", vec![summary(ai1, "OAT", 40.0)]);
+        let config_only = WalkDiffOptions::default().with_ignore_live_values(true);
+        assert!(diff_walks_with_options(&before, &after, &config_only).is_empty());
+    }
+}
@@ -0,0 +1,599 @@
+//! Posting of notifications to HTTP endpoints, behind the `webhook` feature.
+//!
+//! A [`WebhookSink`] queues [`EventNotification`]s, [`CovUpdate`]s and
+//! [`RoutedAlarm`]s as JSON objects and posts them in batches, as a JSON array, to every
+//! configured URL. A batch is posted once it holds
+//! [`max_batch`](WebhookConfig::max_batch) items or its first item has waited
+//! [`max_delay`](WebhookConfig::max_delay); a post that fails or is answered with a
+//! non-2xx status is retried with backoff. `http` and `https` URLs are supported, the
+//! latter verified against the system's root certificates.
+//!
+//! The sink's sockets are Tokio's, so it starts on the current Tokio runtime; its
+//! background task and timers go through the [`Runtime`](crate::runtime::Runtime) trait.
+//!
+//! Each item carries a `type` of `event`, `cov` or `alarm`:
+//!
+//! ```json
+//! [{"type": "cov", "address": ..., "object_id": ..., "values": [...], "source": "Cov"}]
+//! ```
+
+use crate::alarm_routing::{AlarmSink, RoutedAlarm};
+use crate::event_message::state_name;
+use crate::runtime::{BoxFuture, Runtime, TaskHandle, TokioRuntime};
+use crate::{ClientError, CovUpdate, EventNotification, TimeStamp};
+use rustls::pki_types::ServerName;
+use serde_json::{json, Value};
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot};
+use tokio_rustls::TlsConnector;
+
+/// A URL a [`WebhookSink`] posts to that could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid webhook URL {url}: {reason}")]
+pub struct WebhookUrlError {
+    pub url: String,
+    pub reason: &'static str,
+}
+
+/// A parsed `http` or `https` URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookEndpoint {
+    url: String,
+    tls: bool,
+    host: String,
+    port: u16,
+    /// Path and query, starting with `/`.
+    target: String,
+}
+
+impl WebhookEndpoint {
+    pub fn parse(url: &str) -> Result<Self, WebhookUrlError> {
+        let error = |reason| WebhookUrlError {
+            url: url.to_string(),
+            reason,
+        };
+        let (tls, rest) = if let Some(rest) = url.strip_prefix("https://") {
+            (true, rest)
+        } else if let Some(rest) = url.strip_prefix("http://") {
+            (false, rest)
+        } else {
+            return Err(error("scheme must be http or https"));
+        };
+        let (authority, target) = match rest.find(['/', '?']) {
+            Some(at) if rest[at..].starts_with('/') => (&rest[..at], rest[at..].to_string()),
+            Some(at) => (&rest[..at], format!("/{}", &rest[at..])),
+            None => (rest, "/".to_string()),
+        };
+        if authority.contains('@') {
+            return Err(error("credentials in the URL are not supported"));
+        }
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !port.contains(']') => (
+                host,
+                port.parse().map_err(|_| error("port is not a number"))?,
+            ),
+            _ => (authority, if tls { 443 } else { 80 }),
+        };
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        if host.is_empty() {
+            return Err(error("host is missing"));
+        }
+        Ok(Self {
+            url: url.to_string(),
+            tls,
+            host: host.to_string(),
+            port,
+            target,
+        })
+    }
+
+    /// The URL as given.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+/// Where and how a [`WebhookSink`] posts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookConfig {
+    pub endpoints: Vec<WebhookEndpoint>,
+    /// Extra request headers, e.g. `Authorization`.
+    pub headers: Vec<(String, String)>,
+    /// Most items posted in one request.
+    pub max_batch: usize,
+    /// Longest an item waits for its batch to fill.
+    pub max_delay: Duration,
+    /// Further attempts after a failed post.
+    pub retries: u32,
+    /// Delay before the first retry, doubled for each further one.
+    pub retry_delay: Duration,
+    /// Limit on one request, from connecting to the response status.
+    pub request_timeout: Duration,
+    /// Items queued for posting; items beyond it are dropped.
+    pub queue_capacity: usize,
+}
+
+impl WebhookConfig {
+    /// Posts to `urls` in batches of up to 50 items at most 1 s old, retrying a failed
+    /// post 3 times starting 1 s later.
+    pub fn new<S: AsRef<str>>(urls: &[S]) -> Result<Self, WebhookUrlError> {
+        Ok(Self {
+            endpoints: urls
+                .iter()
+                .map(|url| WebhookEndpoint::parse(url.as_ref()))
+                .collect::<Result<_, _>>()?,
+            headers: Vec::new(),
+            max_batch: 50,
+            max_delay: Duration::from_secs(1),
+            retries: 3,
+            retry_delay: Duration::from_secs(1),
+            request_timeout: Duration::from_secs(10),
+            queue_capacity: 1024,
+        })
+    }
+
+    /// Adds a request header.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the batch size, clamped to a minimum of 1, and the longest an item waits.
+    pub fn with_batching(mut self, max_batch: usize, max_delay: Duration) -> Self {
+        self.max_batch = max_batch.max(1);
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets the retries of a failed post and the delay before the first.
+    pub fn with_retries(mut self, retries: u32, retry_delay: Duration) -> Self {
+        self.retries = retries;
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Sets the limit on one request.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Sets the items queued for posting, clamped to a minimum of 1.
+    pub fn with_queue_capacity(mut self, capacity: usize) -> Self {
+        self.queue_capacity = capacity.max(1);
+        self
+    }
+}
+
+/// Counters of a [`WebhookSink`]; a batch posted to two URLs counts twice.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WebhookStats {
+    /// Batches an endpoint answered with a 2xx status.
+    pub batches_posted: u64,
+    /// Batches given up on after the last retry.
+    pub batches_failed: u64,
+    /// Posts retried.
+    pub retries: u64,
+    /// Items dropped because the queue was full.
+    pub dropped: u64,
+}
+
+/// Posts notifications as JSON to HTTP endpoints in the background.
+///
+/// Dropping the sink posts what is queued and then stops;
+/// [`close`](Self::close) waits for that.
+#[derive(Debug)]
+pub struct WebhookSink {
+    tx: mpsc::Sender<Value>,
+    stats: Arc<Mutex<WebhookStats>>,
+    /// Completes once the background task has posted everything and stopped.
+    done: oneshot::Receiver<()>,
+    _task: Box<dyn TaskHandle>,
+}
+
+impl WebhookSink {
+    /// Starts posting on the current Tokio runtime.
+    pub fn start(config: WebhookConfig) -> Result<Self, ClientError> {
+        tokio::runtime::Handle::try_current().map_err(|_| ClientError::NoTokioRuntime)?;
+        let runtime: Arc<dyn Runtime> = Arc::new(TokioRuntime);
+        let (tx, rx) = mpsc::channel(config.queue_capacity.max(1));
+        let (done_tx, done) = oneshot::channel();
+        let stats = Arc::new(Mutex::new(WebhookStats::default()));
+        let poster = Poster {
+            tls: config
+                .endpoints
+                .iter()
+                .any(|endpoint| endpoint.tls)
+                .then(tls_connector),
+            stats: stats.clone(),
+            config,
+            runtime: runtime.clone(),
+        };
+        let task = runtime.spawn(Box::pin(async move {
+            poster.run(rx).await;
+            let _ = done_tx.send(());
+        }));
+        Ok(Self {
+            tx,
+            stats,
+            done,
+            _task: task,
+        })
+    }
+
+    /// Queues an event notification. Returns `false` when the queue is full and the
+    /// notification was dropped.
+    pub fn send_event(&self, notification: &EventNotification) -> bool {
+        self.queue(event_notification_json(notification))
+    }
+
+    /// Queues a COV update. Returns `false` when the queue is full and the update was
+    /// dropped.
+    pub fn send_cov(&self, update: &CovUpdate) -> bool {
+        let mut value = serde_json::to_value(update).unwrap_or(Value::Null);
+        if let Value::Object(fields) = &mut value {
+            fields.insert("type".to_string(), json!("cov"));
+        }
+        self.queue(value)
+    }
+
+    /// Counters so far.
+    pub fn stats(&self) -> WebhookStats {
+        *self.stats.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Posts what is queued, retries included, and returns the final counters.
+    pub async fn close(self) -> WebhookStats {
+        let Self {
+            tx, stats, done, ..
+        } = self;
+        drop(tx);
+        let _ = done.await;
+        let stats = *stats.lock().unwrap_or_else(|e| e.into_inner());
+        stats
+    }
+
+    fn queue(&self, item: Value) -> bool {
+        let queued = self.tx.try_send(item).is_ok();
+        if !queued {
+            self.stats.lock().unwrap_or_else(|e| e.into_inner()).dropped += 1;
+        }
+        queued
+    }
+}
+
+impl AlarmSink for WebhookSink {
+    /// Queues the alarm as an `alarm` item: its notification plus `route` and
+    /// `escalation_level`.
+    fn deliver(&self, alarm: RoutedAlarm) -> BoxFuture<Result<(), String>> {
+        let mut value = event_notification_json(&alarm.notification);
+        value["type"] = json!("alarm");
+        value["route"] = json!(alarm.route);
+        value["escalation_level"] = json!(alarm.escalation_level);
+        let queued = self.queue(value);
+        Box::pin(async move {
+            if queued {
+                Ok(())
+            } else {
+                Err("webhook queue is full".to_string())
+            }
+        })
+    }
+}
+
+/// The JSON object a [`WebhookSink`] posts for an event notification.
+pub fn event_notification_json(n: &EventNotification) -> Value {
+    let timestamp = match n.timestamp {
+        TimeStamp::Time(time) => json!({ "time": time }),
+        TimeStamp::SequenceNumber(sequence) => json!({ "sequence_number": sequence }),
+        TimeStamp::DateTime { date, time } => json!({ "date": date, "time": time }),
+    };
+    json!({
+        "type": "event",
+        "source": n.source,
+        "confirmed": n.confirmed,
+        "process_id": n.process_id,
+        "initiating_device_id": n.initiating_device_id,
+        "event_object_id": n.event_object_id,
+        "timestamp": timestamp,
+        "notification_class": n.notification_class,
+        "priority": n.priority,
+        "event_type": n.event_type,
+        "message_text": n.message_text,
+        "notify_type": n.notify_type,
+        "ack_required": n.ack_required,
+        "from_state": state_name(n.from_state, n.from_state_raw),
+        "to_state": state_name(n.to_state, n.to_state_raw),
+    })
+}
+
+fn tls_connector() -> TlsConnector {
+    let mut roots = rustls::RootCertStore::empty();
+    let native = rustls_native_certs::load_native_certs();
+    for error in &native.errors {
+        log::warn!("webhook: could not load a root certificate: {error}");
+    }
+    let (added, ignored) = roots.add_parsable_certificates(native.certs);
+    log::debug!("webhook: {added} root certificates loaded, {ignored} ignored");
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .expect("the ring provider supports the default protocol versions")
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+    TlsConnector::from(Arc::new(config))
+}
+
+/// The background half of a [`WebhookSink`].
+struct Poster {
+    config: WebhookConfig,
+    tls: Option<TlsConnector>,
+    stats: Arc<Mutex<WebhookStats>>,
+    runtime: Arc<dyn Runtime>,
+}
+
+impl Poster {
+    async fn run(self, mut rx: mpsc::Receiver<Value>) {
+        let mut batch = Vec::new();
+        let mut deadline = self.runtime.now();
+        loop {
+            let received = if batch.is_empty() {
+                rx.recv().await
+            } else {
+                let wait = deadline.saturating_duration_since(self.runtime.now());
+                match crate::runtime::timeout(&*self.runtime, wait, rx.recv()).await {
+                    Ok(received) => received,
+                    Err(_) => {
+                        self.post_all(std::mem::take(&mut batch)).await;
+                        continue;
+                    }
+                }
+            };
+            let Some(item) = received else {
+                if !batch.is_empty() {
+                    self.post_all(batch).await;
+                }
+                return;
+            };
+            if batch.is_empty() {
+                deadline = self.runtime.now() + self.config.max_delay;
+            }
+            batch.push(item);
+            if batch.len() >= self.config.max_batch {
+                self.post_all(std::mem::take(&mut batch)).await;
+            }
+        }
+    }
+
+    async fn post_all(&self, batch: Vec<Value>) {
+        let body = Value::Array(batch).to_string();
+        for endpoint in &self.config.endpoints {
+            let mut delay = self.config.retry_delay;
+            let mut attempt = 0;
+            let posted = loop {
+                let result = crate::runtime::timeout(
+                    &*self.runtime,
+                    self.config.request_timeout,
+                    self.post(endpoint, &body),
+                )
+                .await
+                .unwrap_or_else(|_| {
+                    Err(io::Error::new(io::ErrorKind::TimedOut, "request timed out"))
+                });
+                let error = match result {
+                    Ok(status) if (200..300).contains(&status) => break true,
+                    Ok(status) => format!("status {status}"),
+                    Err(err) => err.to_string(),
+                };
+                if attempt >= self.config.retries {
+                    log::warn!("webhook: giving up on {}: {error}", endpoint.url);
+                    break false;
+                }
+                log::debug!(
+                    "webhook: post to {} failed: {error}; retrying",
+                    endpoint.url
+                );
+                attempt += 1;
+                self.update(|stats| stats.retries += 1);
+                self.runtime.sleep(delay).await;
+                delay = delay.saturating_mul(2);
+            };
+            self.update(|stats| {
+                if posted {
+                    stats.batches_posted += 1;
+                } else {
+                    stats.batches_failed += 1;
+                }
+            });
+        }
+    }
+
+    fn update(&self, change: impl FnOnce(&mut WebhookStats)) {
+        change(&mut self.stats.lock().unwrap_or_else(|e| e.into_inner()));
+    }
+
+    /// Posts `body` and returns the response status.
+    async fn post(&self, endpoint: &WebhookEndpoint, body: &str) -> io::Result<u16> {
+        let stream = TcpStream::connect((endpoint.host.as_str(), endpoint.port)).await?;
+        let mut request = format!(
+            "POST {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\nUser-Agent: rustbac/{}\r\n",
+            endpoint.target,
+            endpoint.host,
+            endpoint.port,
+            body.len(),
+            env!("CARGO_PKG_VERSION"),
+        );
+        for (name, value) in &self.config.headers {
+            request.push_str(&format!("{name}: {value}\r\n"));
+        }
+        request.push_str("\r\n");
+        request.push_str(body);
+        match &self.tls {
+            Some(tls) if endpoint.tls => {
+                let name = ServerName::try_from(endpoint.host.clone())
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+                exchange(tls.connect(name, stream).await?, request.as_bytes()).await
+            }
+            _ => exchange(stream, request.as_bytes()).await,
+        }
+    }
+}
+
+/// Writes `request` and reads the status code from the response's status line.
+async fn exchange<S: AsyncRead + AsyncWrite + Unpin>(
+    mut stream: S,
+    request: &[u8],
+) -> io::Result<u16> {
+    stream.write_all(request).await?;
+    stream.flush().await?;
+    let mut head = Vec::new();
+    let mut chunk = [0u8; 512];
+    while !head.windows(2).any(|w| w == b"\r\n") {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 || head.len() > 8192 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "no HTTP status line",
+            ));
+        }
+        head.extend_from_slice(&chunk[..n]);
+    }
+    let line = String::from_utf8_lossy(&head);
+    line.split_whitespace()
+        .nth(1)
+        .filter(|_| line.starts_with("HTTP/"))
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "bad HTTP status line"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WebhookConfig, WebhookEndpoint, WebhookSink};
+    use crate::{CovPropertyValue, CovUpdate, UpdateSource};
+    use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+    use rustbac_datalink::DataLinkAddress;
+    use serde_json::Value;
+    use std::future::Future;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    /// A server that answers each request with the next of `statuses` and forwards its
+    /// body, to be polled alongside the test.
+    async fn serve(
+        statuses: Vec<u16>,
+    ) -> (
+        String,
+        impl Future<Output = ()>,
+        mpsc::UnboundedReceiver<Value>,
+    ) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hooks/bacnet", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::unbounded_channel();
+        let server = async move {
+            for status in statuses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut chunk = [0u8; 1024];
+                let body = loop {
+                    let n = socket.read(&mut chunk).await.unwrap();
+                    request.extend_from_slice(&chunk[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        assert!(head.starts_with("POST /hooks/bacnet HTTP/1.1\r\n"));
+                        assert!(head.contains("\r\nX-Site: plant-3"));
+                        let length: usize = head
+                            .lines()
+                            .find_map(|line| line.strip_prefix("Content-Length: "))
+                            .unwrap()
+                            .parse()
+                            .unwrap();
+                        if body.len() >= length {
+                            break body.to_string();
+                        }
+                    }
+                };
+                socket
+                    .write_all(
+                        format!("HTTP/1.1 {status} X\r\nContent-Length: 0\r\n\r\n").as_bytes(),
+                    )
+                    .await
+                    .unwrap();
+                if status == 200 {
+                    tx.send(serde_json::from_str(&body).unwrap()).unwrap();
+                }
+            }
+        };
+        (url, server, rx)
+    }
+
+    fn update(instance: u32) -> CovUpdate {
+        CovUpdate {
+            address: DataLinkAddress::Ip(([192, 168, 1, 60], 47808).into()),
+            object_id: ObjectId::new(ObjectType::AnalogInput, instance),
+            values: vec![CovPropertyValue {
+                property_id: PropertyId::PresentValue,
+                array_index: None,
+                value: crate::ClientDataValue::Real(21.5),
+                priority: None,
+            }],
+            source: UpdateSource::Cov,
+        }
+    }
+
+    #[test]
+    fn endpoints_parse_scheme_host_port_and_target() {
+        let endpoint = WebhookEndpoint::parse("https://hooks.example.com/in?key=1").unwrap();
+        assert!(endpoint.tls);
+        assert_eq!(endpoint.host, "hooks.example.com");
+        assert_eq!(endpoint.port, 443);
+        assert_eq!(endpoint.target, "/in?key=1");
+        let endpoint = WebhookEndpoint::parse("http://[::1]:8080").unwrap();
+        assert_eq!((endpoint.host.as_str(), endpoint.port), ("::1", 8080));
+        assert_eq!(endpoint.target, "/");
+        assert!(WebhookEndpoint::parse("ftp://example.com/").is_err());
+        assert!(WebhookEndpoint::parse("http://user:pw@example.com/").is_err());
+    }
+
+    #[tokio::test]
+    async fn batches_are_posted_as_json_and_retried() {
+        // The first post is refused once; the second batch is posted on its delay.
+        let (url, server, mut bodies) = serve(vec![503, 200, 200]).await;
+        let sink = WebhookSink::start(
+            WebhookConfig::new(&[url])
+                .unwrap()
+                .with_header("X-Site", "plant-3")
+                .with_batching(2, Duration::from_millis(50))
+                .with_retries(2, Duration::from_millis(10)),
+        )
+        .unwrap();
+        for instance in 1..=3 {
+            assert!(sink.send_cov(&update(instance)));
+        }
+
+        let client = async {
+            let first = bodies.recv().await.unwrap();
+            let items = first.as_array().unwrap();
+            assert_eq!(items.len(), 2);
+            assert_eq!(items[0]["type"], "cov");
+            assert_eq!(
+                items[1]["object_id"],
+                serde_json::to_value(update(2).object_id).unwrap()
+            );
+            let second = bodies.recv().await.unwrap();
+            assert_eq!(second.as_array().unwrap().len(), 1);
+            sink.close().await
+        };
+        let ((), stats) = tokio::join!(server, client);
+        assert_eq!(stats.batches_posted, 2);
+        assert_eq!(stats.retries, 1);
+        assert_eq!(stats.batches_failed, 0);
+    }
+}
@@ -0,0 +1,236 @@
+use rustbac_datalink::DataLinkAddress;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// How a responder ([`SimulatedDevice`](crate::simulator::SimulatedDevice),
+/// [`BacnetServer`](crate::server::BacnetServer)) answers Who-Is.
+///
+/// The default answers every matching Who-Is immediately. During a broadcast storm,
+/// set a duplicate window and a rate limit so the device does not flood the network
+/// with I-Am; on busy segments, add jitter so devices answering the same global
+/// Who-Is do not all transmit at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WhoIsPolicy {
+    /// A repeat of a Who-Is (same requester, same range) within this window is ignored.
+    pub duplicate_window: Duration,
+    /// Most I-Am responses sent in any one second; further Who-Is are ignored.
+    pub max_responses_per_second: Option<u32>,
+    /// Each I-Am is delayed by a random duration up to this bound.
+    pub max_jitter: Duration,
+}
+
+impl WhoIsPolicy {
+    /// Sets the window in which repeated Who-Is from the same requester are ignored.
+    pub fn with_duplicate_window(mut self, window: Duration) -> Self {
+        self.duplicate_window = window;
+        self
+    }
+
+    /// Caps the number of I-Am responses per second.
+    pub fn with_max_responses_per_second(mut self, max: u32) -> Self {
+        self.max_responses_per_second = Some(max);
+        self
+    }
+
+    /// Delays each I-Am by a random duration up to `max_jitter`.
+    pub fn with_max_jitter(mut self, max_jitter: Duration) -> Self {
+        self.max_jitter = max_jitter;
+        self
+    }
+}
+
+/// What to do with a Who-Is that matched the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum IAmDecision {
+    /// Answer immediately.
+    Send,
+    /// Answer when [`WhoIsGuard::take_due`] returns the requester.
+    Deferred,
+    /// Do not answer.
+    Suppressed,
+}
+
+/// Per-responder state enforcing a [`WhoIsPolicy`].
+#[derive(Debug)]
+pub(crate) struct WhoIsGuard {
+    policy: WhoIsPolicy,
+    recent: HashMap<(DataLinkAddress, Option<(u32, u32)>), Instant>,
+    window_start: Option<Instant>,
+    window_count: u32,
+    pending: Vec<(Instant, DataLinkAddress)>,
+    rng: u64,
+}
+
+impl WhoIsGuard {
+    pub(crate) fn new(policy: WhoIsPolicy) -> Self {
+        let seed = RandomState::new().build_hasher().finish();
+        Self {
+            policy,
+            recent: HashMap::new(),
+            window_start: None,
+            window_count: 0,
+            pending: Vec::new(),
+            rng: seed | 1,
+        }
+    }
+
+    pub(crate) fn policy(&self) -> WhoIsPolicy {
+        self.policy
+    }
+
+    /// Switches to `policy`, keeping the I-Am responses already deferred.
+    pub(crate) fn set_policy(&mut self, policy: WhoIsPolicy) {
+        self.policy = policy;
+    }
+
+    pub(crate) fn on_who_is(
+        &mut self,
+        source: DataLinkAddress,
+        limits: Option<(u32, u32)>,
+        now: Instant,
+    ) -> IAmDecision {
+        if !self.policy.duplicate_window.is_zero() {
+            let window = self.policy.duplicate_window;
+            self.recent
+                .retain(|_, seen| now.saturating_duration_since(*seen) < window);
+            if self.recent.contains_key(&(source, limits)) {
+                return IAmDecision::Suppressed;
+            }
+            self.recent.insert((source, limits), now);
+        }
+        if self.pending.iter().any(|(_, target)| *target == source) {
+            return IAmDecision::Suppressed;
+        }
+
+        if let Some(max) = self.policy.max_responses_per_second {
+            match self.window_start {
+                Some(start) if now.saturating_duration_since(start) < Duration::from_secs(1) => {}
+                _ => {
+                    self.window_start = Some(now);
+                    self.window_count = 0;
+                }
+            }
+            if self.window_count >= max {
+                return IAmDecision::Suppressed;
+            }
+            self.window_count += 1;
+        }
+
+        let jitter = self.jitter();
+        if jitter.is_zero() {
+            IAmDecision::Send
+        } else {
+            self.pending.push((now + jitter, source));
+            IAmDecision::Deferred
+        }
+    }
+
+    /// When the earliest deferred I-Am is due.
+    pub(crate) fn next_due(&self) -> Option<Instant> {
+        self.pending.iter().map(|(due, _)| *due).min()
+    }
+
+    /// Removes and returns the requesters whose I-Am is due at `now`.
+    pub(crate) fn take_due(&mut self, now: Instant) -> Vec<DataLinkAddress> {
+        let mut due = Vec::new();
+        self.pending.retain(|(at, target)| {
+            if *at <= now {
+                due.push(*target);
+                false
+            } else {
+                true
+            }
+        });
+        due
+    }
+
+    fn jitter(&mut self) -> Duration {
+        let max = u64::try_from(self.policy.max_jitter.as_micros()).unwrap_or(u64::MAX);
+        if max == 0 {
+            return Duration::ZERO;
+        }
+        // xorshift64: plenty for spreading responses, no extra dependency.
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        Duration::from_micros(self.rng % (max + 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IAmDecision, WhoIsGuard, WhoIsPolicy};
+    use rustbac_datalink::DataLinkAddress;
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    fn addr(port: u16) -> DataLinkAddress {
+        DataLinkAddress::Ip(([192, 168, 1, 10], port).into())
+    }
+
+    #[test]
+    fn default_policy_answers_everything_immediately() {
+        let mut guard = WhoIsGuard::new(WhoIsPolicy::default());
+        let now = Instant::now();
+        for _ in 0..3 {
+            assert_eq!(guard.on_who_is(addr(1), None, now), IAmDecision::Send);
+        }
+    }
+
+    #[test]
+    fn repeats_within_the_duplicate_window_are_suppressed() {
+        let policy = WhoIsPolicy::default().with_duplicate_window(Duration::from_secs(2));
+        let mut guard = WhoIsGuard::new(policy);
+        let now = Instant::now();
+        assert_eq!(guard.on_who_is(addr(1), None, now), IAmDecision::Send);
+        assert_eq!(guard.on_who_is(addr(1), None, now), IAmDecision::Suppressed);
+        // A different range or requester is a different question.
+        assert_eq!(
+            guard.on_who_is(addr(1), Some((1, 10)), now),
+            IAmDecision::Send
+        );
+        assert_eq!(guard.on_who_is(addr(2), None, now), IAmDecision::Send);
+        let later = now + Duration::from_secs(2);
+        assert_eq!(guard.on_who_is(addr(1), None, later), IAmDecision::Send);
+    }
+
+    #[test]
+    fn rate_limit_caps_responses_per_second() {
+        let policy = WhoIsPolicy::default().with_max_responses_per_second(2);
+        let mut guard = WhoIsGuard::new(policy);
+        let now = Instant::now();
+        assert_eq!(guard.on_who_is(addr(1), None, now), IAmDecision::Send);
+        assert_eq!(guard.on_who_is(addr(2), None, now), IAmDecision::Send);
+        assert_eq!(guard.on_who_is(addr(3), None, now), IAmDecision::Suppressed);
+        let later = now + Duration::from_secs(1);
+        assert_eq!(guard.on_who_is(addr(3), None, later), IAmDecision::Send);
+    }
+
+    #[test]
+    fn jitter_defers_within_the_bound_and_coalesces_per_requester() {
+        let policy = WhoIsPolicy::default().with_max_jitter(Duration::from_millis(50));
+        let mut guard = WhoIsGuard::new(policy);
+        let now = Instant::now();
+        let mut deferred = 0;
+        for port in 0..20 {
+            match guard.on_who_is(addr(port), None, now) {
+                IAmDecision::Deferred => deferred += 1,
+                IAmDecision::Send => {}
+                IAmDecision::Suppressed => panic!("nothing should be suppressed"),
+            }
+        }
+        assert!(deferred > 0);
+        assert!(guard.next_due().unwrap() <= now + Duration::from_millis(50));
+        // A repeat from a requester that is still waiting is not queued twice.
+        let (_, waiting) = guard.pending[0];
+        assert_eq!(guard.on_who_is(waiting, None, now), IAmDecision::Suppressed);
+        assert_eq!(
+            guard.take_due(now + Duration::from_millis(50)).len(),
+            deferred
+        );
+        assert_eq!(guard.next_due(), None);
+    }
+}
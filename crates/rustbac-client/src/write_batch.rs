@@ -0,0 +1,72 @@
+use crate::{ClientDataValue, ClientError};
+use rustbac_core::types::{ObjectId, PropertyId};
+
+/// One write in a [`BacnetClient::write_with_rollback`](crate::BacnetClient::write_with_rollback)
+/// batch, together with the value recorded before the batch started.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WriteBatchItem {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    /// The value the batch wrote (or attempted to write).
+    pub value: ClientDataValue,
+    pub priority: Option<u8>,
+    /// The property value read back before any write was issued.
+    pub previous: ClientDataValue,
+}
+
+/// A write (or rollback write) that the device refused.
+#[derive(Debug)]
+pub struct WriteBatchFailure {
+    pub item: WriteBatchItem,
+    pub error: ClientError,
+}
+
+/// Outcome of a [`BacnetClient::write_with_rollback`](crate::BacnetClient::write_with_rollback)
+/// batch.
+#[derive(Debug, Default)]
+pub struct WriteBatchReport {
+    /// Writes that were applied and remain in effect.
+    pub applied: Vec<WriteBatchItem>,
+    /// Writes that were applied and then successfully undone, in rollback order.
+    pub rolled_back: Vec<WriteBatchItem>,
+    /// The write that failed and triggered the rollback, if any.
+    pub failed: Option<WriteBatchFailure>,
+    /// Rollback writes that themselves failed; these properties are left at the batch value.
+    pub rollback_failed: Vec<WriteBatchFailure>,
+}
+
+impl WriteBatchReport {
+    /// Returns `true` when every write in the batch was applied.
+    pub fn is_committed(&self) -> bool {
+        self.failed.is_none()
+    }
+
+    /// Returns `true` when the batch failed and every applied write was undone.
+    pub fn is_fully_rolled_back(&self) -> bool {
+        self.failed.is_some() && self.applied.is_empty() && self.rollback_failed.is_empty()
+    }
+}
+
+/// A write of a [`BacnetClient::write_burst`](crate::BacnetClient::write_burst) that failed.
+#[derive(Debug)]
+pub struct WriteBurstFailure {
+    /// Position of the write in the burst.
+    pub index: usize,
+    pub error: ClientError,
+}
+
+/// Outcome of a [`BacnetClient::write_burst`](crate::BacnetClient::write_burst).
+#[derive(Debug, Default)]
+pub struct WriteBurstReport {
+    /// Positions of the writes the device acknowledged, in acknowledgement order.
+    pub written: Vec<usize>,
+    /// Writes that were refused, timed out or could not be encoded.
+    pub failed: Vec<WriteBurstFailure>,
+}
+
+impl WriteBurstReport {
+    /// Returns `true` when every write was acknowledged.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
@@ -0,0 +1,317 @@
+//! Safety rules checked before write-class requests are sent.
+//!
+//! A [`WritePolicy`] attached with
+//! [`BacnetClient::with_write_policy`](crate::BacnetClient::with_write_policy) sees every
+//! request the [write audit](crate::audit) reports, before it is sent, and can let it
+//! through, refuse it ([`ClientError::WriteDenied`](crate::ClientError::WriteDenied)), or
+//! hold it until the caller confirms it
+//! ([`ClientError::WriteNeedsConfirmation`](crate::ClientError::WriteNeedsConfirmation)).
+//! A confirmed request is repeated on a handle made with
+//! [`RequestOptions::with_confirmed_writes`](crate::RequestOptions::with_confirmed_writes).
+//!
+//! [`WriteRules`] is a policy built from object, property and priority patterns.
+
+use crate::audit::{PropertyWrite, WriteAction};
+use rustbac_core::services::object_management::CreateObjectSpecifier;
+use rustbac_core::types::{ObjectType, PropertyId};
+use rustbac_datalink::DataLinkAddress;
+
+/// What a [`WritePolicy`] decided about a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteDecision {
+    Allow,
+    /// The request is never sent.
+    Deny {
+        reason: String,
+    },
+    /// The request is sent only from a handle with confirmed writes.
+    Confirm {
+        reason: String,
+    },
+}
+
+impl WriteDecision {
+    pub fn deny(reason: impl Into<String>) -> Self {
+        Self::Deny {
+            reason: reason.into(),
+        }
+    }
+
+    pub fn confirm(reason: impl Into<String>) -> Self {
+        Self::Confirm {
+            reason: reason.into(),
+        }
+    }
+
+    /// The stricter of two decisions: Deny over Confirm over Allow; on a tie, `self`.
+    pub fn stricter(self, other: Self) -> Self {
+        if other.rank() > self.rank() {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Allow => 0,
+            Self::Confirm { .. } => 1,
+            Self::Deny { .. } => 2,
+        }
+    }
+}
+
+/// Decides whether a write-class request may be sent.
+///
+/// For a request that [creates an object](WriteAction::CreateObject), `created` is
+/// always `None`. Any `Fn(DataLinkAddress, &WriteAction) -> WriteDecision + Send + Sync`
+/// closure is a policy.
+pub trait WritePolicy: Send + Sync {
+    fn check(&self, address: DataLinkAddress, action: &WriteAction) -> WriteDecision;
+}
+
+impl<F> WritePolicy for F
+where
+    F: Fn(DataLinkAddress, &WriteAction) -> WriteDecision + Send + Sync,
+{
+    fn check(&self, address: DataLinkAddress, action: &WriteAction) -> WriteDecision {
+        self(address, action)
+    }
+}
+
+/// A pattern of [`WriteRules`]; unset fields match anything.
+///
+/// Property writes match on every field. CreateObject and DeleteObject match on the
+/// object fields only, and only rules without a property or priority pattern apply to
+/// them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteRule {
+    pub object_type: Option<ObjectType>,
+    /// Inclusive range of object instances.
+    pub instances: Option<(u32, u32)>,
+    pub property_id: Option<PropertyId>,
+    /// Inclusive range of command priorities; a write without a priority counts as 16.
+    pub priorities: Option<(u8, u8)>,
+    pub decision: WriteDecision,
+}
+
+impl WriteRule {
+    /// A rule matching every write, with `decision`.
+    pub fn new(decision: WriteDecision) -> Self {
+        Self {
+            object_type: None,
+            instances: None,
+            property_id: None,
+            priorities: None,
+            decision,
+        }
+    }
+
+    pub fn deny(reason: impl Into<String>) -> Self {
+        Self::new(WriteDecision::deny(reason))
+    }
+
+    pub fn confirm(reason: impl Into<String>) -> Self {
+        Self::new(WriteDecision::confirm(reason))
+    }
+
+    pub fn with_object_type(mut self, object_type: ObjectType) -> Self {
+        self.object_type = Some(object_type);
+        self
+    }
+
+    pub fn with_instances(mut self, low: u32, high: u32) -> Self {
+        self.instances = Some((low, high));
+        self
+    }
+
+    pub fn with_property(mut self, property_id: PropertyId) -> Self {
+        self.property_id = Some(property_id);
+        self
+    }
+
+    pub fn with_priorities(mut self, low: u8, high: u8) -> Self {
+        self.priorities = Some((low, high));
+        self
+    }
+
+    fn matches_object(&self, object_type: ObjectType, instance: Option<u32>) -> bool {
+        self.object_type.map_or(true, |t| t == object_type)
+            && self.instances.map_or(true, |(low, high)| {
+                instance.is_some_and(|i| (low..=high).contains(&i))
+            })
+    }
+
+    fn matches_write(&self, write: &PropertyWrite) -> bool {
+        let priority = write.priority.unwrap_or(16);
+        self.matches_object(
+            write.object_id.object_type(),
+            Some(write.object_id.instance()),
+        ) && self.property_id.map_or(true, |p| p == write.property_id)
+            && self
+                .priorities
+                .map_or(true, |(low, high)| (low..=high).contains(&priority))
+    }
+
+    fn matches_object_only(&self, object_type: ObjectType, instance: Option<u32>) -> bool {
+        self.property_id.is_none()
+            && self.priorities.is_none()
+            && self.matches_object(object_type, instance)
+    }
+}
+
+/// A [`WritePolicy`] from a list of [`WriteRule`]s; the first matching rule decides and
+/// writes no rule matches are allowed.
+///
+/// A WritePropertyMultiple gets the strictest decision of its writes.
+/// DeviceCommunicationControl and ReinitializeDevice get the
+/// [device management](Self::with_device_management) decision, Allow by default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteRules {
+    rules: Vec<WriteRule>,
+    device_management: WriteDecision,
+}
+
+impl Default for WriteRules {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            device_management: WriteDecision::Allow,
+        }
+    }
+}
+
+impl WriteRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `rule` after the existing rules.
+    pub fn with_rule(mut self, rule: WriteRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Sets the decision for DeviceCommunicationControl and ReinitializeDevice.
+    pub fn with_device_management(mut self, decision: WriteDecision) -> Self {
+        self.device_management = decision;
+        self
+    }
+
+    pub fn rules(&self) -> &[WriteRule] {
+        &self.rules
+    }
+
+    fn first_match(&self, matches: impl Fn(&WriteRule) -> bool) -> WriteDecision {
+        self.rules
+            .iter()
+            .find(|rule| matches(rule))
+            .map_or(WriteDecision::Allow, |rule| rule.decision.clone())
+    }
+
+    fn check_write(&self, write: &PropertyWrite) -> WriteDecision {
+        self.first_match(|rule| rule.matches_write(write))
+    }
+}
+
+impl WritePolicy for WriteRules {
+    fn check(&self, _address: DataLinkAddress, action: &WriteAction) -> WriteDecision {
+        match action {
+            WriteAction::WriteProperty(write) => self.check_write(write),
+            WriteAction::WritePropertyMultiple(writes) => writes
+                .iter()
+                .map(|write| self.check_write(write))
+                .fold(WriteDecision::Allow, WriteDecision::stricter),
+            WriteAction::CreateObject { specifier, .. } => {
+                let (object_type, instance) = match specifier {
+                    CreateObjectSpecifier::ObjectType(object_type) => (*object_type, None),
+                    CreateObjectSpecifier::ObjectId(id) => (id.object_type(), Some(id.instance())),
+                };
+                self.first_match(|rule| rule.matches_object_only(object_type, instance))
+            }
+            WriteAction::DeleteObject { object_id } => self.first_match(|rule| {
+                rule.matches_object_only(object_id.object_type(), Some(object_id.instance()))
+            }),
+            WriteAction::DeviceCommunicationControl { .. }
+            | WriteAction::ReinitializeDevice { .. } => self.device_management.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WriteDecision, WritePolicy, WriteRule, WriteRules};
+    use crate::audit::{PropertyWrite, WriteAction};
+    use crate::ClientDataValue;
+    use rustbac_core::services::device_management::ReinitializeState;
+    use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+    use rustbac_datalink::DataLinkAddress;
+
+    fn write(object_type: ObjectType, instance: u32, priority: Option<u8>) -> PropertyWrite {
+        PropertyWrite {
+            object_id: ObjectId::new(object_type, instance),
+            property_id: PropertyId::PresentValue,
+            array_index: None,
+            value: ClientDataValue::Real(1.0),
+            priority,
+        }
+    }
+
+    #[test]
+    fn first_matching_rule_decides() {
+        let rules = WriteRules::new()
+            .with_rule(WriteRule::deny("life safety priorities").with_priorities(1, 2))
+            .with_rule(
+                WriteRule::confirm("chiller plant")
+                    .with_object_type(ObjectType::BinaryOutput)
+                    .with_instances(100, 199),
+            )
+            .with_device_management(WriteDecision::deny("no restarts"));
+        let addr = DataLinkAddress::Mstp(4);
+        let check = |action: WriteAction| rules.check(addr, &action);
+
+        assert_eq!(
+            check(WriteAction::WriteProperty(write(
+                ObjectType::BinaryOutput,
+                150,
+                Some(1)
+            ))),
+            WriteDecision::deny("life safety priorities")
+        );
+        assert_eq!(
+            check(WriteAction::WriteProperty(write(
+                ObjectType::BinaryOutput,
+                150,
+                None
+            ))),
+            WriteDecision::confirm("chiller plant")
+        );
+        assert_eq!(
+            check(WriteAction::WriteProperty(write(
+                ObjectType::BinaryOutput,
+                200,
+                Some(8)
+            ))),
+            WriteDecision::Allow
+        );
+        assert_eq!(
+            check(WriteAction::WritePropertyMultiple(vec![
+                write(ObjectType::AnalogValue, 1, Some(8)),
+                write(ObjectType::BinaryOutput, 101, Some(8)),
+            ])),
+            WriteDecision::confirm("chiller plant")
+        );
+        assert_eq!(
+            check(WriteAction::DeleteObject {
+                object_id: ObjectId::new(ObjectType::BinaryOutput, 120),
+            }),
+            WriteDecision::confirm("chiller plant")
+        );
+        assert_eq!(
+            check(WriteAction::ReinitializeDevice {
+                state: ReinitializeState::Warmstart,
+            }),
+            WriteDecision::deny("no restarts")
+        );
+    }
+}
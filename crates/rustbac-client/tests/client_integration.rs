@@ -1,4 +1,4 @@
-//! Integration tests for [`BacnetClient`] + [`SimulatedDevice`].
+//! Integration tests for [`BacnetClient`] + [`SimulatedDevice`] and [`SimulatedNetwork`].
 //!
 //! Each test spins up an in-memory [`ChannelLink`] pair so that no real UDP
 //! socket is needed. The client end is wrapped in a [`BacnetClient`] and the
@@ -7,7 +7,10 @@
 //! responses, COV notifications) spin a small ad-hoc responder task on the
 //! server link.
 
-use rustbac_client::{BacnetClient, ClientDataValue, ClientError, Notification, SimulatedDevice};
+use rustbac_client::{
+    BacnetClient, ClientDataValue, ClientError, NetworkPortIpChange, Notification, SimulatedDevice,
+    SimulatedNetwork,
+};
 use rustbac_core::{
     apdu::{ApduType, ComplexAckHeader, ConfirmedRequestHeader, SimpleAck},
     encoding::{
@@ -25,13 +28,16 @@ use rustbac_core::{
         subscribe_cov::SERVICE_SUBSCRIBE_COV,
         value_codec::encode_application_data_value,
     },
-    types::{DataValue, ObjectId, ObjectType, PropertyId},
+    types::{DataValue, Date, ObjectId, ObjectType, PropertyId, Time},
 };
 use rustbac_datalink::{DataLink, DataLinkAddress, DataLinkError};
 use std::{
     collections::HashMap,
     net::{IpAddr, Ipv4Addr, SocketAddr},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     time::Duration,
 };
 use tokio::{
@@ -106,6 +112,51 @@ impl DataLink for ChannelLink {
     }
 }
 
+/// A [`ChannelLink`] recording the most confirmed requests it had awaiting an answer.
+#[derive(Clone)]
+struct OutstandingLink {
+    inner: ChannelLink,
+    outstanding: Arc<AtomicUsize>,
+    peak: Arc<AtomicUsize>,
+}
+
+impl OutstandingLink {
+    fn new(inner: ChannelLink) -> Self {
+        Self {
+            inner,
+            outstanding: Arc::new(AtomicUsize::new(0)),
+            peak: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+fn apdu_type(frame: &[u8]) -> Option<ApduType> {
+    let mut r = Reader::new(frame);
+    Npdu::decode(&mut r).ok()?;
+    ApduType::from_u8(r.peek_u8().ok()? >> 4)
+}
+
+impl DataLink for OutstandingLink {
+    async fn send(&self, address: DataLinkAddress, payload: &[u8]) -> Result<(), DataLinkError> {
+        if apdu_type(payload) == Some(ApduType::ConfirmedRequest) {
+            let outstanding = self.outstanding.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(outstanding, Ordering::SeqCst);
+        }
+        self.inner.send(address, payload).await
+    }
+
+    async fn recv(&self, buf: &mut [u8]) -> Result<(usize, DataLinkAddress), DataLinkError> {
+        let (n, source) = self.inner.recv(buf).await?;
+        if matches!(
+            apdu_type(&buf[..n]),
+            Some(ApduType::SimpleAck | ApduType::ComplexAck | ApduType::Error)
+        ) {
+            self.outstanding.fetch_sub(1, Ordering::SeqCst);
+        }
+        Ok((n, source))
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Helper: wrap bare APDU bytes in an NPDU header
 // ---------------------------------------------------------------------------
@@ -130,7 +181,7 @@ async fn make_simulator(server_link: ChannelLink) -> SimulatedDevice<ChannelLink
     ai_props.insert(PropertyId::PresentValue, ClientDataValue::Real(42.0));
     ai_props.insert(
         PropertyId::ObjectName,
-        ClientDataValue::CharacterString("AI-1".to_string()),
+        ClientDataValue::CharacterString("AI-1".into()),
     );
     let ai_id = ObjectId::new(ObjectType::AnalogInput, 1);
     sim.add_object(ai_id, ai_props).await;
@@ -140,7 +191,7 @@ async fn make_simulator(server_link: ChannelLink) -> SimulatedDevice<ChannelLink
     ao_props.insert(PropertyId::PresentValue, ClientDataValue::Real(0.0));
     ao_props.insert(
         PropertyId::ObjectName,
-        ClientDataValue::CharacterString("AO-1".to_string()),
+        ClientDataValue::CharacterString("AO-1".into()),
     );
     let ao_id = ObjectId::new(ObjectType::AnalogOutput, 1);
     sim.add_object(ao_id, ao_props).await;
@@ -334,7 +385,7 @@ async fn test_read_property_multiple() {
 
     assert_eq!(
         name_val,
-        Some(ClientDataValue::CharacterString("AI-1".to_string())),
+        Some(ClientDataValue::CharacterString("AI-1".into())),
         "ObjectName mismatch"
     );
     assert_eq!(
@@ -783,7 +834,7 @@ async fn test_segmented_read() {
 
     assert_eq!(
         result,
-        ClientDataValue::CharacterString(large_string),
+        ClientDataValue::CharacterString(large_string.into()),
         "segmented response value mismatch"
     );
 }
@@ -830,7 +881,519 @@ async fn test_timeout_on_no_response() {
     .expect("outer timeout — client did not respect its own deadline");
 
     assert!(
-        matches!(result, Err(ClientError::Timeout)),
+        matches!(result, Err(ref e) if matches!(e.root(), ClientError::Timeout)),
         "expected ClientError::Timeout, got {result:?}"
     );
 }
+
+// ---------------------------------------------------------------------------
+// Test 9: simulated_network — many devices behind one link
+//
+// Who-Is finds every device of a SimulatedNetwork, and reads of each device's
+// own AnalogInput reach the device that owns it.
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_simulated_network_discovery_and_reads() {
+    let (client_link, server_link) = make_link_pair();
+    let mut network = SimulatedNetwork::new(5, server_link);
+    for instance in 1..=25 {
+        let device = network.add_device(instance);
+        let mut props = HashMap::new();
+        props.insert(
+            PropertyId::PresentValue,
+            ClientDataValue::Real(instance as f32),
+        );
+        device
+            .add_object(ObjectId::new(ObjectType::AnalogInput, instance), props)
+            .await;
+    }
+    tokio::spawn(async move { network.run().await });
+
+    let client =
+        BacnetClient::with_datalink(client_link).with_response_timeout(Duration::from_secs(5));
+
+    let devices = timeout(
+        Duration::from_secs(5),
+        client.who_is(None, Duration::from_millis(500)),
+    )
+    .await
+    .expect("test timed out")
+    .expect("who_is failed");
+    let mut instances: Vec<u32> = devices
+        .iter()
+        .filter_map(|d| d.device_id.map(|id| id.instance()))
+        .collect();
+    instances.sort_unstable();
+    instances.dedup();
+    assert_eq!(instances, (1..=25).collect::<Vec<_>>());
+
+    for instance in [1, 13, 25] {
+        let value = timeout(
+            Duration::from_secs(5),
+            client.read_property(
+                SERVER_ADDR,
+                ObjectId::new(ObjectType::AnalogInput, instance),
+                PropertyId::PresentValue,
+            ),
+        )
+        .await
+        .expect("test timed out")
+        .expect("read_property failed");
+        assert_eq!(value, ClientDataValue::Real(instance as f32));
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Test 10: commandable_present_value — priority array and relinquish
+//
+// The simulator's AnalogOutput is commandable: Present_Value follows the
+// highest-priority command and falls back to Relinquish_Default once every
+// level is relinquished.
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_commandable_present_value() {
+    let (client_link, server_link) = make_link_pair();
+    let sim = make_simulator(server_link).await;
+    tokio::spawn(async move { sim.run().await });
+
+    let client =
+        BacnetClient::with_datalink(client_link).with_response_timeout(Duration::from_secs(5));
+    let ao_id = ObjectId::new(ObjectType::AnalogOutput, 1);
+
+    let write = |value: DataValue<'static>, priority: Option<u8>| {
+        let client = client.clone();
+        async move {
+            timeout(
+                Duration::from_secs(5),
+                client.write_property(
+                    SERVER_ADDR,
+                    rustbac_core::services::write_property::WritePropertyRequest {
+                        object_id: ao_id,
+                        property_id: PropertyId::PresentValue,
+                        value,
+                        array_index: None,
+                        priority,
+                        invoke_id: 0,
+                    },
+                ),
+            )
+            .await
+            .expect("test timed out")
+        }
+    };
+    let present_value = || {
+        let client = client.clone();
+        async move {
+            timeout(
+                Duration::from_secs(5),
+                client.read_property(SERVER_ADDR, ao_id, PropertyId::PresentValue),
+            )
+            .await
+            .expect("test timed out")
+            .expect("read_property failed")
+        }
+    };
+
+    write(DataValue::Real(50.0), Some(8)).await.unwrap();
+    write(DataValue::Real(70.0), None).await.unwrap(); // priority 16
+    assert_eq!(present_value().await, ClientDataValue::Real(50.0));
+
+    let priority_array = timeout(
+        Duration::from_secs(5),
+        client.read_property(SERVER_ADDR, ao_id, PropertyId::PriorityArray),
+    )
+    .await
+    .expect("test timed out")
+    .expect("read_property failed");
+    let ClientDataValue::Constructed { values, .. } = priority_array else {
+        panic!("expected an array, got {priority_array:?}");
+    };
+    assert_eq!(values.len(), 16);
+    assert_eq!(values[7], ClientDataValue::Real(50.0));
+    assert_eq!(values[15], ClientDataValue::Real(70.0));
+
+    write(DataValue::Null, Some(8)).await.unwrap();
+    assert_eq!(present_value().await, ClientDataValue::Real(70.0));
+    write(DataValue::Null, None).await.unwrap();
+    assert_eq!(present_value().await, ClientDataValue::Real(0.0));
+
+    // Priority 6 is reserved for minimum on/off time.
+    let err = write(DataValue::Real(1.0), Some(6)).await.unwrap_err();
+    assert!(
+        matches!(err.root(), ClientError::RemoteServiceError { .. }),
+        "expected a remote error, got {err:?}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Test 11: trend_log_read_range — live trend log served via ReadRange
+//
+// A simulated Trend Log samples AI-1 into a four-record ring buffer; the
+// client reads the buffer by position, sequence number and time.
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_trend_log_read_range() {
+    let (client_link, server_link) = make_link_pair();
+    let sim = make_simulator(server_link).await;
+    let ai_id = ObjectId::new(ObjectType::AnalogInput, 1);
+    let tl_id = ObjectId::new(ObjectType::TrendLog, 1);
+    sim.add_trend_log(
+        tl_id,
+        ai_id,
+        PropertyId::PresentValue,
+        Duration::from_millis(40),
+        4,
+    )
+    .await;
+    tokio::spawn(async move { sim.run().await });
+    // Long enough for the buffer to wrap.
+    tokio::time::sleep(Duration::from_millis(260)).await;
+
+    let client =
+        BacnetClient::with_datalink(client_link).with_response_timeout(Duration::from_secs(5));
+
+    let by_position = timeout(
+        Duration::from_secs(5),
+        client.read_range_by_position(SERVER_ADDR, tl_id, PropertyId::LogBuffer, None, 1, 4),
+    )
+    .await
+    .expect("test timed out")
+    .expect("read_range_by_position failed");
+    assert_eq!(by_position.item_count, 4);
+    let ClientDataValue::Constructed { values, .. } = &by_position.items[0] else {
+        panic!("expected a log record, got {:?}", by_position.items[0]);
+    };
+    assert_eq!(values[1], ClientDataValue::Real(42.0));
+
+    let total = match timeout(
+        Duration::from_secs(5),
+        client.read_property(SERVER_ADDR, tl_id, PropertyId::TotalRecordCount),
+    )
+    .await
+    .expect("test timed out")
+    .expect("read_property failed")
+    {
+        ClientDataValue::Unsigned(total) => total,
+        other => panic!("expected Unsigned, got {other:?}"),
+    };
+    assert!(total > 4, "the buffer should have wrapped, total = {total}");
+
+    let by_sequence = timeout(
+        Duration::from_secs(5),
+        client.read_range_by_sequence_number(
+            SERVER_ADDR,
+            tl_id,
+            PropertyId::LogBuffer,
+            None,
+            total,
+            1,
+        ),
+    )
+    .await
+    .expect("test timed out")
+    .expect("read_range_by_sequence_number failed");
+    assert_eq!(by_sequence.item_count, 1);
+
+    // Everything was logged after 1990; the oldest two come back with first-item set.
+    let long_ago = (
+        Date {
+            year_since_1900: 90,
+            month: 1,
+            day: 1,
+            weekday: 1,
+        },
+        Time {
+            hour: 0,
+            minute: 0,
+            second: 0,
+            hundredths: 0,
+        },
+    );
+    let by_time = timeout(
+        Duration::from_secs(5),
+        client.read_range_by_time(SERVER_ADDR, tl_id, PropertyId::LogBuffer, None, long_ago, 2),
+    )
+    .await
+    .expect("test timed out")
+    .expect("read_range_by_time failed");
+    assert_eq!(by_time.item_count, 2);
+    assert_eq!(by_time.result_flags.data[0] & 0x80, 0x80);
+}
+
+// ---------------------------------------------------------------------------
+// Test 12: network_port — read IP configuration and stage a new address
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_network_port_configuration() {
+    let (client_link, server_link) = make_link_pair();
+    let sim = make_simulator(server_link).await;
+    let port_id = ObjectId::new(ObjectType::NetworkPort, 1);
+    let octets = |ip: [u8; 4]| ClientDataValue::OctetString(ip.to_vec());
+    let mut port_props = HashMap::new();
+    port_props.insert(PropertyId::NetworkType, ClientDataValue::Enumerated(5));
+    port_props.insert(PropertyId::NetworkNumber, ClientDataValue::Unsigned(1));
+    port_props.insert(PropertyId::IpAddress, octets([192, 168, 1, 20]));
+    port_props.insert(PropertyId::IpSubnetMask, octets([255, 255, 255, 0]));
+    port_props.insert(PropertyId::IpDefaultGateway, octets([192, 168, 1, 1]));
+    port_props.insert(
+        PropertyId::BacnetIpUdpPort,
+        ClientDataValue::Unsigned(47808),
+    );
+    port_props.insert(PropertyId::ChangesPending, ClientDataValue::Boolean(false));
+    sim.add_object(port_id, port_props).await;
+    tokio::spawn(async move { sim.run().await });
+
+    let client =
+        BacnetClient::with_datalink(client_link).with_response_timeout(Duration::from_secs(5));
+
+    let config = timeout(
+        Duration::from_secs(5),
+        client.read_network_port(SERVER_ADDR, port_id),
+    )
+    .await
+    .expect("test timed out")
+    .expect("read_network_port failed");
+    assert_eq!(config.network_type, Some(5));
+    assert_eq!(config.ip_address, Some(Ipv4Addr::new(192, 168, 1, 20)));
+    assert_eq!(config.bacnet_ip_udp_port, Some(47808));
+    // The simulated port has no DHCP or DNS properties.
+    assert_eq!(config.ip_dhcp_enable, None);
+    assert!(config.ip_dns_servers.is_empty());
+
+    let change = NetworkPortIpChange::default()
+        .with_address(Ipv4Addr::new(10, 0, 0, 5), Ipv4Addr::new(255, 0, 0, 0))
+        .with_default_gateway(Ipv4Addr::new(10, 0, 0, 1));
+    timeout(
+        Duration::from_secs(5),
+        client.stage_network_port_ip(SERVER_ADDR, port_id, &change),
+    )
+    .await
+    .expect("test timed out")
+    .expect("stage_network_port_ip failed");
+
+    let config = timeout(
+        Duration::from_secs(5),
+        client.read_network_port(SERVER_ADDR, port_id),
+    )
+    .await
+    .expect("test timed out")
+    .expect("read_network_port failed");
+    assert_eq!(config.ip_address, Some(Ipv4Addr::new(10, 0, 0, 5)));
+    assert_eq!(config.ip_subnet_mask, Some(Ipv4Addr::new(255, 0, 0, 0)));
+    assert_eq!(config.ip_default_gateway, Some(Ipv4Addr::new(10, 0, 0, 1)));
+
+    // The simulator does not raise Changes_Pending, so nothing is activated.
+    let activated = timeout(
+        Duration::from_secs(5),
+        client.activate_network_port_changes(SERVER_ADDR, port_id, None),
+    )
+    .await
+    .expect("test timed out")
+    .expect("activate_network_port_changes failed");
+    assert!(!activated);
+}
+
+// ---------------------------------------------------------------------------
+// Test 13: walk_device_stream — objects stream in Object_List order from a
+// BacnetServer while several are read at once
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_walk_device_stream() {
+    use futures_util::StreamExt;
+    use rustbac_client::walk::{walk_device, walk_device_stream, WalkOptions};
+    use rustbac_client::{BacnetServer, ObjectStore, ObjectStoreHandler};
+
+    let (client_link, server_link) = make_link_pair();
+
+    // The server answers a whole ReadPropertyMultiple with an error if any property is
+    // missing, so every object carries all the summary properties.
+    let store = Arc::new(ObjectStore::new());
+    let add = |id: ObjectId, name: String, present_value: ClientDataValue| {
+        for (property, value) in [
+            (
+                PropertyId::ObjectName,
+                ClientDataValue::CharacterString(name.into()),
+            ),
+            (
+                PropertyId::ObjectType,
+                ClientDataValue::Enumerated(u32::from(id.object_type().to_u16())),
+            ),
+            (PropertyId::PresentValue, present_value),
+            (
+                PropertyId::Description,
+                ClientDataValue::CharacterString("".into()),
+            ),
+            (PropertyId::Units, ClientDataValue::Enumerated(95)),
+            (
+                PropertyId::StatusFlags,
+                ClientDataValue::BitString {
+                    unused_bits: 4,
+                    data: vec![0],
+                },
+            ),
+        ] {
+            store.set(id, property, value);
+        }
+    };
+    let device_id = ObjectId::new(ObjectType::Device, 100);
+    add(device_id, "Walked".to_string(), ClientDataValue::Null);
+    let mut object_list = vec![ClientDataValue::ObjectId(device_id)];
+    for instance in 1..=12 {
+        let id = ObjectId::new(ObjectType::AnalogInput, instance);
+        add(
+            id,
+            format!("AI-{instance}"),
+            ClientDataValue::Real(instance as f32),
+        );
+        object_list.push(ClientDataValue::ObjectId(id));
+    }
+    store.set(
+        device_id,
+        PropertyId::ObjectList,
+        ClientDataValue::Constructed {
+            tag_num: 3,
+            values: object_list,
+        },
+    );
+    tokio::spawn(BacnetServer::new(server_link, 100, ObjectStoreHandler::new(store)).serve());
+
+    let client_link = OutstandingLink::new(client_link);
+    let peak = client_link.peak.clone();
+    let client =
+        BacnetClient::with_datalink(client_link).with_response_timeout(Duration::from_secs(5));
+    let objects: Vec<_> = timeout(
+        Duration::from_secs(10),
+        walk_device_stream(
+            &client,
+            SERVER_ADDR,
+            device_id,
+            WalkOptions::default().with_concurrency(4),
+        )
+        .collect::<Vec<_>>(),
+    )
+    .await
+    .expect("test timed out")
+    .into_iter()
+    .collect::<Result<_, _>>()
+    .expect("walk_device_stream failed");
+
+    assert_eq!(objects.len(), 13);
+    assert!(
+        peak.load(Ordering::SeqCst) > 1,
+        "objects were read one at a time"
+    );
+    assert_eq!(objects[0].object_id, device_id);
+    for (instance, object) in (1..=12).zip(&objects[1..]) {
+        assert_eq!(
+            object.object_id,
+            ObjectId::new(ObjectType::AnalogInput, instance)
+        );
+        assert_eq!(
+            object.object_name.as_deref(),
+            Some(format!("AI-{instance}").as_str())
+        );
+        assert_eq!(
+            object.present_value,
+            Some(ClientDataValue::Real(instance as f32))
+        );
+    }
+
+    let walk = timeout(
+        Duration::from_secs(10),
+        walk_device(&client, SERVER_ADDR, device_id),
+    )
+    .await
+    .expect("test timed out")
+    .expect("walk_device failed");
+    assert_eq!(walk.objects, objects);
+
+    // The server does not expand ALL, so every object falls back to the usual reads.
+    let read_all: Vec<_> = timeout(
+        Duration::from_secs(10),
+        walk_device_stream(
+            &client,
+            SERVER_ADDR,
+            device_id,
+            WalkOptions::default().with_read_all(true),
+        )
+        .collect::<Vec<_>>(),
+    )
+    .await
+    .expect("test timed out")
+    .into_iter()
+    .collect::<Result<_, _>>()
+    .expect("walk_device_stream failed");
+    assert_eq!(read_all, objects);
+}
+
+// ---------------------------------------------------------------------------
+// Test 14: state_text — multistate and binary values labelled from the device
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_state_text_labels() {
+    use rustbac_client::StateTextCache;
+
+    let (client_link, server_link) = make_link_pair();
+    let sim = SimulatedDevice::new(100, server_link);
+
+    let msv = ObjectId::new(ObjectType::MultiStateValue, 1);
+    let mut props = HashMap::new();
+    props.insert(PropertyId::PresentValue, ClientDataValue::Unsigned(3));
+    props.insert(
+        PropertyId::StateText,
+        ClientDataValue::Constructed {
+            tag_num: 3,
+            values: ["Off", "Heat", "Cool"]
+                .into_iter()
+                .map(|s| ClientDataValue::CharacterString(s.into()))
+                .collect(),
+        },
+    );
+    sim.add_object(msv, props).await;
+
+    // No Inactive_Text: the device answers with an error and only Active_Text is used.
+    let bi = ObjectId::new(ObjectType::BinaryInput, 1);
+    let mut props = HashMap::new();
+    props.insert(PropertyId::PresentValue, ClientDataValue::Enumerated(1));
+    props.insert(
+        PropertyId::ActiveText,
+        ClientDataValue::CharacterString("Alarm".into()),
+    );
+    sim.add_object(bi, props).await;
+    tokio::spawn(async move { sim.run().await });
+
+    let client =
+        BacnetClient::with_datalink(client_link).with_response_timeout(Duration::from_secs(5));
+    let cache = StateTextCache::new();
+
+    let state = timeout(
+        Duration::from_secs(5),
+        cache.read_state(&client, SERVER_ADDR, msv),
+    )
+    .await
+    .expect("test timed out")
+    .expect("read_state failed");
+    assert_eq!(state.value, 3);
+    assert_eq!(state.label.as_deref(), Some("Cool"));
+
+    let state = timeout(
+        Duration::from_secs(5),
+        cache.read_state(&client, SERVER_ADDR, bi),
+    )
+    .await
+    .expect("test timed out")
+    .expect("read_state failed");
+    assert_eq!(state.to_string(), "Alarm");
+    assert_eq!(
+        cache
+            .label(SERVER_ADDR, bi, &ClientDataValue::Enumerated(0))
+            .map(|state| state.to_string()),
+        Some("0".to_string())
+    );
+}
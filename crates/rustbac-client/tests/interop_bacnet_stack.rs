@@ -0,0 +1,247 @@
+//! Interoperability tests against the open-source bacnet-stack demo server (`bacserv`).
+//!
+//! These talk to a real BACnet/IP device over UDP, so they are `#[ignore]`d and only run
+//! on request:
+//!
+//! ```text
+//! RUSTBAC_INTEROP_COMPOSE=1 cargo test -p rustbac-client --test interop_bacnet_stack -- --ignored
+//! ```
+//!
+//! Environment variables:
+//!
+//! - `RUSTBAC_INTEROP_TARGET` — `ip:port` of the server (default `127.0.0.1:47808`).
+//! - `RUSTBAC_INTEROP_DEVICE` — device instance of the server (default `260001`).
+//! - `RUSTBAC_INTEROP_COMPOSE` — when `1`, build and start
+//!   `interop/bacnet-stack/docker-compose.yml` before the first test. The container is
+//!   left running; stop it with `docker compose -f interop/bacnet-stack/docker-compose.yml down`.
+//!
+//! The object instances used below (Analog Input 0, Analog Output 0) exist in the
+//! default `bacserv` object database.
+
+use rustbac_client::{BacnetClient, ClientDataValue, ClientError, WriteVerifyOptions};
+use rustbac_core::services::subscribe_cov::SubscribeCovRequest;
+use rustbac_core::services::write_property::WritePropertyRequest;
+use rustbac_core::types::{DataValue, ObjectId, ObjectType, PropertyId};
+use rustbac_datalink::{BacnetIpTransport, DataLinkAddress};
+use std::net::SocketAddr;
+use std::path::Path;
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+const DEFAULT_TARGET: &str = "127.0.0.1:47808";
+const DEFAULT_DEVICE_INSTANCE: u32 = 260_001;
+
+/// Segmentation_Supported values that allow the server to send segmented responses.
+const SEGMENTED_BOTH: u32 = 0;
+const SEGMENTED_TRANSMIT: u32 = 1;
+
+/// Starts the docker-compose stack once per test binary when `RUSTBAC_INTEROP_COMPOSE=1`.
+fn ensure_compose_stack() {
+    static STARTED: OnceLock<()> = OnceLock::new();
+    STARTED.get_or_init(|| {
+        if std::env::var("RUSTBAC_INTEROP_COMPOSE").as_deref() != Ok("1") {
+            return;
+        }
+        let compose_file = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../interop/bacnet-stack/docker-compose.yml");
+        let status = Command::new("docker")
+            .arg("compose")
+            .arg("-f")
+            .arg(&compose_file)
+            .args(["up", "-d", "--build", "--wait"])
+            .status()
+            .expect("failed to run `docker compose`");
+        assert!(status.success(), "docker compose up failed: {status}");
+        // bacserv does not expose a health check; give it a moment to bind its socket.
+        std::thread::sleep(Duration::from_secs(2));
+    });
+}
+
+fn target() -> DataLinkAddress {
+    let target = std::env::var("RUSTBAC_INTEROP_TARGET").unwrap_or_else(|_| DEFAULT_TARGET.into());
+    let addr: SocketAddr = target
+        .parse()
+        .unwrap_or_else(|_| panic!("RUSTBAC_INTEROP_TARGET {target:?} is not ip:port"));
+    DataLinkAddress::Ip(addr)
+}
+
+fn device_id() -> ObjectId {
+    let instance = std::env::var("RUSTBAC_INTEROP_DEVICE")
+        .ok()
+        .map(|v| v.parse().expect("RUSTBAC_INTEROP_DEVICE must be a number"))
+        .unwrap_or(DEFAULT_DEVICE_INSTANCE);
+    ObjectId::new(ObjectType::Device, instance)
+}
+
+async fn connect() -> BacnetClient<BacnetIpTransport> {
+    ensure_compose_stack();
+    BacnetClient::new()
+        .await
+        .expect("bind client socket")
+        .with_response_timeout(Duration::from_secs(5))
+}
+
+#[tokio::test]
+#[ignore = "requires a running bacnet-stack server"]
+async fn reads_device_properties() {
+    let client = connect().await;
+    let address = target();
+    let device = device_id();
+
+    let identifier = client
+        .read_property(address, device, PropertyId::ObjectIdentifier)
+        .await
+        .unwrap();
+    assert_eq!(identifier, ClientDataValue::ObjectId(device));
+
+    let name = client
+        .read_property(address, device, PropertyId::ObjectName)
+        .await
+        .unwrap();
+    assert!(matches!(name, ClientDataValue::CharacterString(ref s) if !s.is_empty()));
+
+    let revision = client
+        .read_property(address, device, PropertyId::ProtocolRevision)
+        .await
+        .unwrap();
+    assert!(matches!(revision, ClientDataValue::Unsigned(_)));
+}
+
+#[tokio::test]
+#[ignore = "requires a running bacnet-stack server"]
+async fn unknown_object_returns_typed_error() {
+    let client = connect().await;
+    let err = client
+        .read_property(
+            target(),
+            ObjectId::new(ObjectType::AnalogInput, 4_000_000),
+            PropertyId::PresentValue,
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err.root(), ClientError::RemoteServiceError { .. }),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires a running bacnet-stack server"]
+async fn writes_and_relinquishes_commandable_output() {
+    let client = connect().await;
+    let address = target();
+    let output = ObjectId::new(ObjectType::AnalogOutput, 0);
+
+    let observed = client
+        .write_verified(
+            address,
+            output,
+            PropertyId::PresentValue,
+            ClientDataValue::Real(42.5),
+            Some(8),
+            WriteVerifyOptions::default().with_settle_delay(Duration::from_millis(100)),
+        )
+        .await
+        .unwrap();
+    assert_eq!(observed, ClientDataValue::Real(42.5));
+
+    client
+        .write_property(
+            address,
+            WritePropertyRequest {
+                object_id: output,
+                property_id: PropertyId::PresentValue,
+                value: DataValue::Null,
+                array_index: None,
+                priority: Some(8),
+                invoke_id: 0,
+            },
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "requires a running bacnet-stack server"]
+async fn cov_subscription_delivers_initial_notification() {
+    let client = connect().await;
+    let address = target();
+    let input = ObjectId::new(ObjectType::AnalogInput, 0);
+
+    client
+        .subscribe_cov(
+            address,
+            SubscribeCovRequest {
+                subscriber_process_id: 17,
+                monitored_object_id: input,
+                issue_confirmed_notifications: Some(false),
+                lifetime_seconds: Some(60),
+                invoke_id: 0,
+            },
+        )
+        .await
+        .unwrap();
+
+    let notification = client
+        .recv_cov_notification(Duration::from_secs(5))
+        .await
+        .unwrap()
+        .expect("no COV notification after subscribing");
+    assert_eq!(notification.subscriber_process_id, 17);
+    assert_eq!(notification.monitored_object_id, input);
+    assert!(notification
+        .values
+        .iter()
+        .any(|v| v.property_id == PropertyId::PresentValue));
+
+    client
+        .cancel_cov_subscription(address, 17, input)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "requires a running bacnet-stack server"]
+async fn segmented_read_property_multiple_reassembles() {
+    let client = connect().await;
+    let address = target();
+    let device = device_id();
+
+    let segmentation = client
+        .read_property(address, device, PropertyId::SegmentationSupported)
+        .await
+        .unwrap();
+    if !matches!(
+        segmentation,
+        ClientDataValue::Enumerated(SEGMENTED_BOTH | SEGMENTED_TRANSMIT)
+    ) {
+        eprintln!("server does not transmit segmented responses ({segmentation:?}); skipping");
+        return;
+    }
+
+    // Enough string-heavy properties to exceed a single 1476-byte APDU.
+    let properties: Vec<PropertyId> = [
+        PropertyId::ObjectName,
+        PropertyId::VendorName,
+        PropertyId::ModelName,
+        PropertyId::FirmwareRevision,
+        PropertyId::ApplicationSoftwareVersion,
+        PropertyId::Description,
+        PropertyId::ProtocolVersion,
+        PropertyId::ProtocolRevision,
+    ]
+    .into_iter()
+    .cycle()
+    .take(160)
+    .collect();
+
+    let values = client
+        .read_property_multiple(address, device, &properties)
+        .await
+        .unwrap();
+    assert_eq!(values.len(), properties.len());
+    for ((property_id, _), expected) in values.iter().zip(&properties) {
+        assert_eq!(property_id, expected);
+    }
+}
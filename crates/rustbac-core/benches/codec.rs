@@ -0,0 +1,246 @@
+//! Throughput of the encode/decode hot paths: tags, ReadPropertyMultiple acks, large
+//! constructed values and NPDU headers.
+//!
+//! Run with `cargo bench -p rustbac-core --bench codec` (add `--features arena` to include
+//! arena decoding). CI compares a pull request
+//! against its base with `scripts/check_bench_regression.sh`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use rustbac_core::encoding::primitives::{
+    encode_closing_tag, encode_ctx_unsigned, encode_opening_tag,
+};
+use rustbac_core::encoding::{reader::Reader, tag::AppTag, tag::Tag, writer::Writer};
+use rustbac_core::npdu::{Npdu, NpduAddress};
+use rustbac_core::services::read_property_multiple::ReadPropertyMultipleAck;
+use rustbac_core::services::value_codec::{
+    decode_application_data_value, encode_application_data_value, ValueEvent, ValueEvents,
+};
+use rustbac_core::types::{BitString, DataValue, Date, ObjectId, ObjectType, PropertyId, Time};
+
+/// A mix of short and long application, context and delimiter tags.
+const TAGS: [Tag; 8] = [
+    Tag::Application {
+        tag: AppTag::UnsignedInt,
+        len: 1,
+    },
+    Tag::Application {
+        tag: AppTag::Real,
+        len: 4,
+    },
+    Tag::Application {
+        tag: AppTag::CharacterString,
+        len: 200,
+    },
+    Tag::Context { tag_num: 0, len: 4 },
+    Tag::Context {
+        tag_num: 14,
+        len: 2,
+    },
+    Tag::Context {
+        tag_num: 3,
+        len: 70_000,
+    },
+    Tag::Opening { tag_num: 1 },
+    Tag::Closing { tag_num: 1 },
+];
+
+fn encode_tags(buf: &mut [u8]) -> usize {
+    let mut w = Writer::new(buf);
+    for tag in TAGS {
+        tag.encode(&mut w).unwrap();
+    }
+    w.as_written().len()
+}
+
+/// An RPM ack body for 20 analog inputs with five properties each, as read by a
+/// supervisor polling a point list.
+fn rpm_ack() -> Vec<u8> {
+    let mut buf = vec![0u8; 8192];
+    let mut w = Writer::new(&mut buf);
+    for instance in 0..20 {
+        let object_id = ObjectId::new(ObjectType::AnalogInput, instance);
+        encode_ctx_unsigned(&mut w, 0, object_id.raw()).unwrap();
+        encode_opening_tag(&mut w, 1).unwrap();
+        let values = [
+            (
+                PropertyId::PresentValue,
+                DataValue::Real(20.5 + instance as f32),
+            ),
+            (
+                PropertyId::StatusFlags,
+                DataValue::BitString(BitString {
+                    unused_bits: 4,
+                    data: &[0x00],
+                }),
+            ),
+            (
+                PropertyId::ObjectName,
+                DataValue::CharacterString("AHU-1 Supply Air Temp"),
+            ),
+            (PropertyId::Units, DataValue::Enumerated(62)),
+            (PropertyId::OutOfService, DataValue::Boolean(false)),
+        ];
+        for (property, value) in &values {
+            encode_ctx_unsigned(&mut w, 2, property.to_u32()).unwrap();
+            encode_opening_tag(&mut w, 4).unwrap();
+            encode_application_data_value(&mut w, value).unwrap();
+            encode_closing_tag(&mut w, 4).unwrap();
+        }
+        encode_closing_tag(&mut w, 1).unwrap();
+    }
+    let len = w.as_written().len();
+    buf.truncate(len);
+    buf
+}
+
+/// A ReadRange-sized constructed value: 1000 log records of timestamp, datum and flags.
+fn large_constructed() -> Vec<u8> {
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut w = Writer::new(&mut buf);
+    encode_opening_tag(&mut w, 5).unwrap();
+    for n in 0..1000u32 {
+        encode_opening_tag(&mut w, 0).unwrap();
+        encode_opening_tag(&mut w, 0).unwrap();
+        let date = Date {
+            year_since_1900: 126,
+            month: 10,
+            day: 17,
+            weekday: 6,
+        };
+        let time = Time {
+            hour: (n / 60 % 24) as u8,
+            minute: (n % 60) as u8,
+            second: 0,
+            hundredths: 0,
+        };
+        encode_application_data_value(&mut w, &DataValue::Date(date)).unwrap();
+        encode_application_data_value(&mut w, &DataValue::Time(time)).unwrap();
+        encode_closing_tag(&mut w, 0).unwrap();
+        encode_application_data_value(&mut w, &DataValue::Real(n as f32 * 0.5)).unwrap();
+        encode_application_data_value(
+            &mut w,
+            &DataValue::BitString(BitString {
+                unused_bits: 4,
+                data: &[0x00],
+            }),
+        )
+        .unwrap();
+        encode_closing_tag(&mut w, 0).unwrap();
+    }
+    encode_closing_tag(&mut w, 5).unwrap();
+    let len = w.as_written().len();
+    buf.truncate(len);
+    buf
+}
+
+fn npdu_frames() -> (Vec<u8>, Vec<u8>) {
+    let mut local = [0u8; 8];
+    let mut w = Writer::new(&mut local);
+    let mut npdu = Npdu::new(0);
+    npdu.set_expecting_reply(true);
+    npdu.encode(&mut w).unwrap();
+    let local = w.as_written().to_vec();
+
+    let mut routed = [0u8; 32];
+    let mut w = Writer::new(&mut routed);
+    let mut npdu = Npdu::new(0);
+    npdu.destination = Some(NpduAddress {
+        network: 2001,
+        mac: [0x0A, 0, 0, 0, 0, 0],
+        mac_len: 1,
+    });
+    npdu.source = Some(NpduAddress {
+        network: 1,
+        mac: [192, 168, 1, 10, 0xBA, 0xC0],
+        mac_len: 6,
+    });
+    npdu.hop_count = Some(255);
+    npdu.encode(&mut w).unwrap();
+    (local, w.as_written().to_vec())
+}
+
+fn tags(c: &mut Criterion) {
+    let mut buf = [0u8; 64];
+    let encoded_len = encode_tags(&mut buf);
+    let encoded = buf[..encoded_len].to_vec();
+
+    let mut group = c.benchmark_group("tag");
+    group.throughput(Throughput::Bytes(encoded_len as u64));
+    group.bench_function("encode", |b| {
+        let mut buf = [0u8; 64];
+        b.iter(|| encode_tags(black_box(&mut buf)))
+    });
+    group.bench_function("decode", |b| {
+        b.iter(|| {
+            let mut r = Reader::new(black_box(&encoded));
+            while !r.is_empty() {
+                black_box(Tag::decode(&mut r).unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+fn rpm(c: &mut Criterion) {
+    let ack = rpm_ack();
+    let mut group = c.benchmark_group("rpm_ack");
+    group.throughput(Throughput::Bytes(ack.len() as u64));
+    group.bench_function("decode", |b| {
+        b.iter(|| {
+            let mut r = Reader::new(black_box(&ack));
+            black_box(ReadPropertyMultipleAck::decode_after_header(&mut r).unwrap())
+        })
+    });
+    group.finish();
+}
+
+fn constructed(c: &mut Criterion) {
+    let value = large_constructed();
+    let mut group = c.benchmark_group("constructed");
+    group.throughput(Throughput::Bytes(value.len() as u64));
+    group.bench_function("decode_tree", |b| {
+        b.iter(|| decode_application_data_value(&mut Reader::new(black_box(&value))).unwrap())
+    });
+    #[cfg(feature = "arena")]
+    group.bench_function("decode_arena", |b| {
+        use rustbac_core::services::value_arena::{
+            bumpalo::Bump, decode_application_data_value_in,
+        };
+        let mut bump = Bump::new();
+        b.iter(|| {
+            bump.reset();
+            decode_application_data_value_in(&bump, &mut Reader::new(black_box(&value)))
+                .unwrap()
+                .children()
+                .len()
+        })
+    });
+    group.bench_function("decode_events", |b| {
+        b.iter(|| {
+            let mut r = Reader::new(black_box(&value));
+            let mut reals = 0.0f32;
+            for event in ValueEvents::new(&mut r) {
+                if let ValueEvent::Primitive(DataValue::Real(v)) = event.unwrap() {
+                    reals += v;
+                }
+            }
+            reals
+        })
+    });
+    group.finish();
+}
+
+fn npdu(c: &mut Criterion) {
+    let (local, routed) = npdu_frames();
+    let mut group = c.benchmark_group("npdu");
+    for (name, frame) in [("decode_local", &local), ("decode_routed", &routed)] {
+        group.throughput(Throughput::Bytes(frame.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| Npdu::decode(&mut Reader::new(black_box(frame))).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tags, rpm, constructed, npdu);
+criterion_main!(benches);
@@ -23,6 +23,22 @@ pub struct ConfirmedRequestHeader {
 }
 
 impl ConfirmedRequestHeader {
+    /// The max-segments-accepted field value announcing room for `segments` response
+    /// segments, rounded down to an encodable count: `0` (unspecified) below two segments,
+    /// `7` for more than 64.
+    pub const fn max_segments_code(segments: usize) -> u8 {
+        match segments {
+            0..=1 => 0,
+            2..=3 => 1,
+            4..=7 => 2,
+            8..=15 => 3,
+            16..=31 => 4,
+            32..=63 => 5,
+            64 => 6,
+            _ => 7,
+        }
+    }
+
     pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
         let mut b0 = (ApduType::ConfirmedRequest as u8) << 4;
         if self.segmented {
@@ -252,8 +268,18 @@ pub struct AbortPdu {
 
 /// BACnet Abort reason codes.
 pub mod abort_reason {
+    /// No more specific reason applies, e.g. the requester cancelled the transaction.
+    pub const OTHER: u8 = 0x00;
+    /// The reassembled message would not fit the receiver's buffer.
+    pub const BUFFER_OVERFLOW: u8 = 0x01;
+    /// The PDU is not valid in the transaction's current state.
+    pub const INVALID_APDU_IN_THIS_STATE: u8 = 0x02;
     /// The peer does not support segmented messages.
     pub const SEGMENTATION_NOT_SUPPORTED: u8 = 0x04;
+    /// The server application did not produce the reply in time.
+    pub const APPLICATION_EXCEEDED_REPLY_TIME: u8 = 0x08;
+    /// The transaction state machine timed out waiting for the peer.
+    pub const TSM_TIMEOUT: u8 = 0x0A;
 }
 
 impl AbortPdu {
@@ -323,9 +349,27 @@ impl SegmentAck {
 
 #[cfg(test)]
 mod tests {
-    use super::BacnetError;
+    use super::{BacnetError, ConfirmedRequestHeader};
     use crate::encoding::reader::Reader;
 
+    #[test]
+    fn max_segments_code_rounds_down_to_an_encodable_count() {
+        let codes: [(usize, u8); 9] = [
+            (0, 0),
+            (1, 0),
+            (2, 1),
+            (7, 2),
+            (8, 3),
+            (31, 4),
+            (63, 5),
+            (64, 6),
+            (65, 7),
+        ];
+        for (segments, code) in codes {
+            assert_eq!(ConfirmedRequestHeader::max_segments_code(segments), code);
+        }
+    }
+
     #[test]
     fn bacnet_error_decodes_without_details() {
         let mut r = Reader::new(&[0x50, 1, 15]);
@@ -0,0 +1,682 @@
+//! [`proptest`] `Arbitrary` implementations for core types and request structs.
+//!
+//! Enabled by the `test-util` feature so downstream crates can property-test code that
+//! builds or consumes BACnet frames. Generated values are always encodable: object
+//! instances and property identifiers stay within their 22-bit wire range, priorities are
+//! `1..=16`, Read-Range reference indexes are non-negative, floats are never NaN (so `PartialEq` round-trips hold), and paired optional
+//! fields (e.g. Who-Is limits) are either both present or both absent.
+//!
+//! Types that borrow (`DataValue<'a>`, `&str` fields) are generated with a `'static`
+//! lifetime by leaking their backing storage. That is fine for tests but these impls
+//! should not be used outside them.
+
+use crate::services::atomic_read_file::{AtomicReadFileAccessMethod, AtomicReadFileRequest};
+use crate::services::device_management::{
+    DeviceCommunicationControlRequest, DeviceCommunicationState, ReinitializeDeviceRequest,
+    ReinitializeState,
+};
+use crate::services::i_am::IAmRequest;
+use crate::services::object_management::{
+    CreateObjectRequest, CreateObjectSpecifier, DeleteObjectRequest,
+};
+use crate::services::read_property::ReadPropertyRequest;
+use crate::services::read_range::{ReadRangeRequest, ReadRangeSpecifier};
+use crate::services::subscribe_cov::SubscribeCovRequest;
+use crate::services::subscribe_cov_property::SubscribeCovPropertyRequest;
+use crate::services::time_synchronization::TimeSynchronizationRequest;
+use crate::services::who_has::{WhoHasObject, WhoHasRequest};
+use crate::services::who_is::WhoIsRequest;
+use crate::services::write_property::WritePropertyRequest;
+use crate::types::{BitString, DataValue, Date, ObjectId, ObjectType, PropertyId, Time};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use proptest::prelude::*;
+
+fn leak_bytes(bytes: Vec<u8>) -> &'static [u8] {
+    Box::leak(bytes.into_boxed_slice())
+}
+
+fn leak_str(s: String) -> &'static str {
+    Box::leak(s.into_boxed_str())
+}
+
+fn instance() -> impl Strategy<Value = u32> {
+    0u32..=0x3F_FFFF
+}
+
+fn priority() -> impl Strategy<Value = Option<u8>> {
+    proptest::option::of(1u8..=16)
+}
+
+fn finite_f32() -> impl Strategy<Value = f32> {
+    any::<f32>().prop_filter("NaN never equals itself", |v| !v.is_nan())
+}
+
+fn finite_f64() -> impl Strategy<Value = f64> {
+    any::<f64>().prop_filter("NaN never equals itself", |v| !v.is_nan())
+}
+
+fn limits() -> impl Strategy<Value = (Option<u32>, Option<u32>)> {
+    proptest::option::of((instance(), instance()))
+        .prop_map(|range| (range.map(|(low, _)| low), range.map(|(_, high)| high)))
+}
+
+fn password() -> impl Strategy<Value = Option<&'static str>> {
+    proptest::option::of("[ -~]{1,20}".prop_map(leak_str))
+}
+
+impl Arbitrary for ObjectType {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (0u16..1024).prop_map(ObjectType::from_u16).boxed()
+    }
+}
+
+impl Arbitrary for ObjectId {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (any::<ObjectType>(), instance())
+            .prop_map(|(object_type, instance)| ObjectId::new(object_type, instance))
+            .boxed()
+    }
+}
+
+impl Arbitrary for PropertyId {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (0u32..=0x3F_FFFF).prop_map(PropertyId::from_u32).boxed()
+    }
+}
+
+impl Arbitrary for Date {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<[u8; 4]>()
+            .prop_map(|[year_since_1900, month, day, weekday]| Date {
+                year_since_1900,
+                month,
+                day,
+                weekday,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Time {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<[u8; 4]>()
+            .prop_map(|[hour, minute, second, hundredths]| Time {
+                hour,
+                minute,
+                second,
+                hundredths,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for BitString<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (0u8..=7, proptest::collection::vec(any::<u8>(), 0..8))
+            .prop_map(|(unused_bits, data)| BitString::new(unused_bits, leak_bytes(data)))
+            .boxed()
+    }
+}
+
+impl Arbitrary for DataValue<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let leaf = prop_oneof![
+            Just(DataValue::Null),
+            any::<bool>().prop_map(DataValue::Boolean),
+            any::<u32>().prop_map(DataValue::Unsigned),
+            any::<i32>().prop_map(DataValue::Signed),
+            finite_f32().prop_map(DataValue::Real),
+            finite_f64().prop_map(DataValue::Double),
+            proptest::collection::vec(any::<u8>(), 0..32)
+                .prop_map(|v| DataValue::OctetString(leak_bytes(v))),
+            ".{0,16}".prop_map(|s| DataValue::CharacterString(leak_str(s))),
+            any::<BitString<'static>>().prop_map(DataValue::BitString),
+            any::<u32>().prop_map(DataValue::Enumerated),
+            any::<Date>().prop_map(DataValue::Date),
+            any::<Time>().prop_map(DataValue::Time),
+            any::<ObjectId>().prop_map(DataValue::ObjectId),
+        ];
+        leaf.prop_recursive(3, 16, 4, |inner| {
+            (0u8..=14, proptest::collection::vec(inner, 0..4))
+                .prop_map(|(tag_num, values)| DataValue::Constructed { tag_num, values })
+        })
+        .boxed()
+    }
+}
+
+impl Arbitrary for ReadPropertyRequest {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            any::<ObjectId>(),
+            any::<PropertyId>(),
+            any::<Option<u32>>(),
+            any::<u8>(),
+        )
+            .prop_map(
+                |(object_id, property_id, array_index, invoke_id)| ReadPropertyRequest {
+                    object_id,
+                    property_id,
+                    array_index,
+                    invoke_id,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for WritePropertyRequest<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            any::<ObjectId>(),
+            any::<PropertyId>(),
+            any::<DataValue<'static>>(),
+            any::<Option<u32>>(),
+            priority(),
+            any::<u8>(),
+        )
+            .prop_map(
+                |(object_id, property_id, value, array_index, priority, invoke_id)| {
+                    WritePropertyRequest {
+                        object_id,
+                        property_id,
+                        value,
+                        array_index,
+                        priority,
+                        invoke_id,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for SubscribeCovRequest {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            any::<u32>(),
+            any::<ObjectId>(),
+            any::<Option<bool>>(),
+            any::<Option<u32>>(),
+            any::<u8>(),
+        )
+            .prop_map(
+                |(subscriber_process_id, monitored_object_id, confirmed, lifetime, invoke_id)| {
+                    SubscribeCovRequest {
+                        subscriber_process_id,
+                        monitored_object_id,
+                        issue_confirmed_notifications: confirmed,
+                        lifetime_seconds: lifetime,
+                        invoke_id,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for SubscribeCovPropertyRequest {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            any::<SubscribeCovRequest>(),
+            any::<PropertyId>(),
+            any::<Option<u32>>(),
+            proptest::option::of(finite_f32()),
+        )
+            .prop_map(|(base, property_id, array_index, cov_increment)| {
+                SubscribeCovPropertyRequest {
+                    subscriber_process_id: base.subscriber_process_id,
+                    monitored_object_id: base.monitored_object_id,
+                    issue_confirmed_notifications: base.issue_confirmed_notifications,
+                    lifetime_seconds: base.lifetime_seconds,
+                    monitored_property_id: property_id,
+                    monitored_property_array_index: array_index,
+                    cov_increment,
+                    invoke_id: base.invoke_id,
+                }
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for WhoIsRequest {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        limits()
+            .prop_map(|(low_limit, high_limit)| WhoIsRequest {
+                low_limit,
+                high_limit,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for WhoHasRequest<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let object = prop_oneof![
+            any::<ObjectId>().prop_map(WhoHasObject::ObjectId),
+            ".{0,32}".prop_map(|s| WhoHasObject::ObjectName(leak_str(s))),
+        ];
+        (limits(), object)
+            .prop_map(|((low_limit, high_limit), object)| WhoHasRequest {
+                low_limit,
+                high_limit,
+                object,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for IAmRequest {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            instance(),
+            any::<u32>(),
+            0u32..=3,
+            any::<u16>().prop_map(u32::from),
+        )
+            .prop_map(|(instance, max_apdu, segmentation, vendor_id)| IAmRequest {
+                device_id: ObjectId::new(ObjectType::Device, instance),
+                max_apdu,
+                segmentation,
+                vendor_id,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for TimeSynchronizationRequest {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (any::<Date>(), any::<Time>(), any::<bool>())
+            .prop_map(|(date, time, utc)| TimeSynchronizationRequest { date, time, utc })
+            .boxed()
+    }
+}
+
+impl Arbitrary for CreateObjectRequest {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let specifier = prop_oneof![
+            any::<ObjectType>().prop_map(CreateObjectSpecifier::ObjectType),
+            any::<ObjectId>().prop_map(CreateObjectSpecifier::ObjectId),
+        ];
+        (specifier, any::<u8>())
+            .prop_map(|(specifier, invoke_id)| CreateObjectRequest {
+                specifier,
+                invoke_id,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for DeleteObjectRequest {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (any::<ObjectId>(), any::<u8>())
+            .prop_map(|(object_id, invoke_id)| DeleteObjectRequest {
+                object_id,
+                invoke_id,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for DeviceCommunicationControlRequest<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let state = prop_oneof![
+            Just(DeviceCommunicationState::Enable),
+            Just(DeviceCommunicationState::Disable),
+            Just(DeviceCommunicationState::DisableInitiation),
+        ];
+        (any::<Option<u16>>(), state, password(), any::<u8>())
+            .prop_map(
+                |(time_duration_seconds, enable_disable, password, invoke_id)| {
+                    DeviceCommunicationControlRequest {
+                        time_duration_seconds,
+                        enable_disable,
+                        password,
+                        invoke_id,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for ReinitializeDeviceRequest<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let state = prop_oneof![
+            Just(ReinitializeState::Coldstart),
+            Just(ReinitializeState::Warmstart),
+            Just(ReinitializeState::StartBackup),
+            Just(ReinitializeState::EndBackup),
+            Just(ReinitializeState::StartRestore),
+            Just(ReinitializeState::EndRestore),
+            Just(ReinitializeState::AbortRestore),
+            Just(ReinitializeState::ActivateChanges),
+        ];
+        (state, password(), any::<u8>())
+            .prop_map(|(state, password, invoke_id)| ReinitializeDeviceRequest {
+                state,
+                password,
+                invoke_id,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ReadRangeRequest {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let range = prop_oneof![
+            (0..=i32::MAX, any::<i16>()).prop_map(|(reference_index, count)| {
+                ReadRangeSpecifier::ByPosition {
+                    reference_index,
+                    count,
+                }
+            }),
+            (any::<u32>(), any::<i16>()).prop_map(|(reference_sequence, count)| {
+                ReadRangeSpecifier::BySequenceNumber {
+                    reference_sequence,
+                    count,
+                }
+            }),
+            (any::<Date>(), any::<Time>(), any::<i16>())
+                .prop_map(|(date, time, count)| ReadRangeSpecifier::ByTime { date, time, count }),
+            Just(ReadRangeSpecifier::ReadAll),
+        ];
+        (
+            any::<ObjectId>(),
+            any::<PropertyId>(),
+            any::<Option<u32>>(),
+            range,
+            any::<u8>(),
+        )
+            .prop_map(
+                |(object_id, property_id, array_index, range, invoke_id)| ReadRangeRequest {
+                    object_id,
+                    property_id,
+                    array_index,
+                    range,
+                    invoke_id,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for AtomicReadFileRequest {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let access_method = prop_oneof![
+            (any::<i32>(), any::<u32>()).prop_map(
+                |(file_start_position, requested_octet_count)| {
+                    AtomicReadFileAccessMethod::Stream {
+                        file_start_position,
+                        requested_octet_count,
+                    }
+                }
+            ),
+            (any::<i32>(), any::<u32>()).prop_map(|(file_start_record, requested_record_count)| {
+                AtomicReadFileAccessMethod::Record {
+                    file_start_record,
+                    requested_record_count,
+                }
+            }),
+        ];
+        (instance(), access_method, any::<u8>())
+            .prop_map(
+                |(instance, access_method, invoke_id)| AtomicReadFileRequest {
+                    file_object_id: ObjectId::new(ObjectType::File, instance),
+                    access_method,
+                    invoke_id,
+                },
+            )
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::apdu::{ConfirmedRequestHeader, UnconfirmedRequestHeader};
+    use crate::encoding::{
+        reader::Reader,
+        tag::{AppTag, Tag},
+        writer::Writer,
+    };
+    use crate::services::atomic_read_file::{AtomicReadFileRequest, SERVICE_ATOMIC_READ_FILE};
+    use crate::services::device_management::{
+        DeviceCommunicationControlRequest, ReinitializeDeviceRequest,
+        SERVICE_DEVICE_COMMUNICATION_CONTROL, SERVICE_REINITIALIZE_DEVICE,
+    };
+    use crate::services::i_am::{IAmRequest, SERVICE_I_AM};
+    use crate::services::object_management::{
+        CreateObjectRequest, DeleteObjectRequest, SERVICE_CREATE_OBJECT, SERVICE_DELETE_OBJECT,
+    };
+    use crate::services::read_property::{ReadPropertyRequest, SERVICE_READ_PROPERTY};
+    use crate::services::read_range::{ReadRangeRequest, SERVICE_READ_RANGE};
+    use crate::services::subscribe_cov::{SubscribeCovRequest, SERVICE_SUBSCRIBE_COV};
+    use crate::services::subscribe_cov_property::{
+        SubscribeCovPropertyRequest, SERVICE_SUBSCRIBE_COV_PROPERTY,
+    };
+    use crate::services::time_synchronization::TimeSynchronizationRequest;
+    use crate::services::value_codec::{
+        decode_application_data_value, encode_application_data_value,
+    };
+    use crate::services::who_has::{WhoHasRequest, SERVICE_WHO_HAS};
+    use crate::services::who_is::{WhoIsRequest, SERVICE_WHO_IS};
+    use crate::services::write_property::{WritePropertyRequest, SERVICE_WRITE_PROPERTY};
+    use crate::types::{DataValue, ObjectId, ObjectType, PropertyId};
+    use proptest::prelude::*;
+
+    /// Walks the tag stream after a header and checks it ends exactly at the buffer end
+    /// with every opening tag closed.
+    fn assert_well_formed_body(r: &mut Reader<'_>) {
+        let mut open = alloc::vec::Vec::new();
+        while r.remaining() > 0 {
+            match Tag::decode(r).unwrap() {
+                Tag::Application {
+                    tag: AppTag::Boolean,
+                    ..
+                } => {}
+                Tag::Application { len, .. } | Tag::Context { len, .. } => {
+                    r.read_exact(len as usize).unwrap();
+                }
+                Tag::Opening { tag_num } => open.push(tag_num),
+                Tag::Closing { tag_num } => assert_eq!(open.pop(), Some(tag_num)),
+            }
+        }
+        assert!(open.is_empty(), "unclosed tags {open:?}");
+    }
+
+    macro_rules! confirmed_request_roundtrip {
+        ($name:ident, $ty:ty, $service:expr) => {
+            proptest! {
+                #[test]
+                fn $name(req in any::<$ty>()) {
+                    let mut buf = [0u8; 512];
+                    let mut w = Writer::new(&mut buf);
+                    req.encode(&mut w).unwrap();
+                    let mut r = Reader::new(w.as_written());
+                    let hdr = ConfirmedRequestHeader::decode(&mut r).unwrap();
+                    prop_assert_eq!(hdr.invoke_id, req.invoke_id);
+                    prop_assert_eq!(hdr.service_choice, $service);
+                    assert_well_formed_body(&mut r);
+                }
+            }
+        };
+    }
+
+    confirmed_request_roundtrip!(
+        read_property_encodes,
+        ReadPropertyRequest,
+        SERVICE_READ_PROPERTY
+    );
+    confirmed_request_roundtrip!(
+        write_property_encodes,
+        WritePropertyRequest<'static>,
+        SERVICE_WRITE_PROPERTY
+    );
+    confirmed_request_roundtrip!(
+        subscribe_cov_encodes,
+        SubscribeCovRequest,
+        SERVICE_SUBSCRIBE_COV
+    );
+    confirmed_request_roundtrip!(
+        subscribe_cov_property_encodes,
+        SubscribeCovPropertyRequest,
+        SERVICE_SUBSCRIBE_COV_PROPERTY
+    );
+    confirmed_request_roundtrip!(
+        create_object_encodes,
+        CreateObjectRequest,
+        SERVICE_CREATE_OBJECT
+    );
+    confirmed_request_roundtrip!(
+        delete_object_encodes,
+        DeleteObjectRequest,
+        SERVICE_DELETE_OBJECT
+    );
+    confirmed_request_roundtrip!(
+        device_communication_control_encodes,
+        DeviceCommunicationControlRequest<'static>,
+        SERVICE_DEVICE_COMMUNICATION_CONTROL
+    );
+    confirmed_request_roundtrip!(
+        reinitialize_device_encodes,
+        ReinitializeDeviceRequest<'static>,
+        SERVICE_REINITIALIZE_DEVICE
+    );
+    confirmed_request_roundtrip!(read_range_encodes, ReadRangeRequest, SERVICE_READ_RANGE);
+    confirmed_request_roundtrip!(
+        atomic_read_file_encodes,
+        AtomicReadFileRequest,
+        SERVICE_ATOMIC_READ_FILE
+    );
+
+    proptest! {
+        #[test]
+        fn data_value_roundtrip(value in any::<DataValue<'static>>()) {
+            let mut buf = [0u8; 1024];
+            let mut w = Writer::new(&mut buf);
+            encode_application_data_value(&mut w, &value).unwrap();
+            let mut r = Reader::new(w.as_written());
+            prop_assert_eq!(decode_application_data_value(&mut r).unwrap(), value);
+            prop_assert_eq!(r.remaining(), 0);
+        }
+
+        #[test]
+        fn object_id_roundtrip(object_id in any::<ObjectId>()) {
+            let again = ObjectId::new(object_id.object_type(), object_id.instance());
+            prop_assert_eq!(again, object_id);
+            prop_assert_eq!(ObjectType::from_u16(object_id.object_type().to_u16()), object_id.object_type());
+        }
+
+        #[test]
+        fn property_id_roundtrip(property_id in any::<PropertyId>()) {
+            prop_assert_eq!(PropertyId::from_u32(property_id.to_u32()), property_id);
+        }
+
+        #[test]
+        fn i_am_roundtrip(req in any::<IAmRequest>()) {
+            let mut buf = [0u8; 64];
+            let mut w = Writer::new(&mut buf);
+            req.encode(&mut w).unwrap();
+            let mut r = Reader::new(w.as_written());
+            let hdr = UnconfirmedRequestHeader::decode(&mut r).unwrap();
+            prop_assert_eq!(hdr.service_choice, SERVICE_I_AM);
+            prop_assert_eq!(IAmRequest::decode_after_header(&mut r).unwrap(), req);
+        }
+
+        #[test]
+        fn time_synchronization_roundtrip(req in any::<TimeSynchronizationRequest>()) {
+            let mut buf = [0u8; 32];
+            let mut w = Writer::new(&mut buf);
+            req.encode(&mut w).unwrap();
+            let mut r = Reader::new(w.as_written());
+            let _hdr = UnconfirmedRequestHeader::decode(&mut r).unwrap();
+            let decoded = TimeSynchronizationRequest::decode_after_header(&mut r).unwrap();
+            prop_assert_eq!(decoded.date, req.date);
+            prop_assert_eq!(decoded.time, req.time);
+        }
+
+        #[test]
+        fn who_is_encodes(req in any::<WhoIsRequest>()) {
+            let mut buf = [0u8; 32];
+            let mut w = Writer::new(&mut buf);
+            req.encode(&mut w).unwrap();
+            let mut r = Reader::new(w.as_written());
+            prop_assert_eq!(UnconfirmedRequestHeader::decode(&mut r).unwrap().service_choice, SERVICE_WHO_IS);
+            assert_well_formed_body(&mut r);
+        }
+
+        #[test]
+        fn who_has_encodes(req in any::<WhoHasRequest<'static>>()) {
+            let mut buf = [0u8; 256];
+            let mut w = Writer::new(&mut buf);
+            req.encode(&mut w).unwrap();
+            let mut r = Reader::new(w.as_written());
+            prop_assert_eq!(UnconfirmedRequestHeader::decode(&mut r).unwrap().service_choice, SERVICE_WHO_HAS);
+            assert_well_formed_body(&mut r);
+        }
+    }
+}
@@ -167,6 +167,11 @@ pub fn encode_ctx_signed(w: &mut Writer<'_>, tag_num: u8, value: i32) -> Result<
     w.write_all(&scratch[..len as usize])
 }
 
+pub fn encode_ctx_real(w: &mut Writer<'_>, tag_num: u8, value: f32) -> Result<(), EncodeError> {
+    Tag::Context { tag_num, len: 4 }.encode(w)?;
+    w.write_all(&value.to_bits().to_be_bytes())
+}
+
 pub fn encode_ctx_character_string(
     w: &mut Writer<'_>,
     tag_num: u8,
@@ -22,6 +22,19 @@ impl fmt::Display for EncodeError {
     }
 }
 
+impl EncodeError {
+    /// Stable, machine-readable identifier for the error kind (e.g. `"encode.buffer_too_small"`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::BufferTooSmall => "encode.buffer_too_small",
+            Self::ValueOutOfRange => "encode.value_out_of_range",
+            Self::InvalidLength => "encode.invalid_length",
+            Self::Unsupported => "encode.unsupported",
+            Self::Message(_) => "encode.message",
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 impl std::error::Error for EncodeError {}
 
@@ -49,5 +62,19 @@ impl fmt::Display for DecodeError {
     }
 }
 
+impl DecodeError {
+    /// Stable, machine-readable identifier for the error kind (e.g. `"decode.unexpected_eof"`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnexpectedEof => "decode.unexpected_eof",
+            Self::InvalidTag => "decode.invalid_tag",
+            Self::InvalidLength => "decode.invalid_length",
+            Self::InvalidValue => "decode.invalid_value",
+            Self::Unsupported => "decode.unsupported",
+            Self::Message(_) => "decode.message",
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 impl std::error::Error for DecodeError {}
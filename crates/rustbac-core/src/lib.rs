@@ -11,6 +11,10 @@
 //! - **`alloc`** (default) — enables service decoders that allocate (e.g. RPM, COV).
 //! - **`serde`** — derives `Serialize`/`Deserialize` on core types.
 //! - **`defmt`** — derives `defmt::Format` for embedded logging.
+//! - **`test-util`** — `proptest` `Arbitrary` impls for core types and request structs
+//!   (see [`arbitrary`]), for use in downstream property tests.
+//! - **`arena`** — decodes constructed values into a `bumpalo` arena (see
+//!   [`services::value_arena`]) instead of one `Vec` per nesting level.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -21,6 +25,9 @@ extern crate std;
 
 /// APDU (Application Protocol Data Unit) types for confirmed/unconfirmed requests and responses.
 pub mod apdu;
+/// `proptest` strategies for generating encodable values in tests.
+#[cfg(any(feature = "test-util", all(test, feature = "std", feature = "alloc")))]
+pub mod arbitrary;
 /// Binary encoding primitives, tag system, and zero-copy reader/writer.
 pub mod encoding;
 /// Error types for encoding and decoding operations.
@@ -29,6 +36,8 @@ pub mod error;
 pub mod npdu;
 /// BACnet service request and response codecs.
 pub mod services;
+/// Sans-IO confirmed-request state machine for initiators without an async runtime.
+pub mod transaction;
 /// Core BACnet data types: object identifiers, property identifiers, and data values.
 pub mod types;
 
@@ -4,6 +4,48 @@ use crate::{DecodeError, EncodeError};
 /// BACnet network layer protocol version (always `0x01`).
 pub const NPDU_VERSION: u8 = 0x01;
 
+/// Control bit 7: the NPDU carries a network-layer message instead of an APDU.
+pub const CONTROL_NETWORK_MESSAGE: u8 = 0x80;
+/// Control bit 5: DNET, DLEN, DADR and the hop count are present.
+pub const CONTROL_DESTINATION_PRESENT: u8 = 0x20;
+/// Control bit 3: SNET, SLEN and SADR are present.
+pub const CONTROL_SOURCE_PRESENT: u8 = 0x08;
+/// Control bit 2: the sender expects a reply (confirmed request or similar).
+pub const CONTROL_EXPECTING_REPLY: u8 = 0x04;
+/// Control bits 1-0: network priority.
+pub const CONTROL_PRIORITY_MASK: u8 = 0x03;
+
+/// Control bits 6 and 4 are reserved and always sent as zero.
+const CONTROL_RESERVED: u8 = 0x50;
+
+/// DNET value addressing every network (global broadcast).
+pub const GLOBAL_BROADCAST_NETWORK: u16 = 0xFFFF;
+/// Hop count used for newly originated routed messages.
+pub const DEFAULT_HOP_COUNT: u8 = 255;
+
+/// Network priority carried in the low two bits of the NPDU control octet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum NetworkPriority {
+    #[default]
+    Normal = 0,
+    Urgent = 1,
+    CriticalEquipment = 2,
+    LifeSafety = 3,
+}
+
+impl NetworkPriority {
+    /// Extracts the priority from a control octet.
+    pub const fn from_control(control: u8) -> Self {
+        match control & CONTROL_PRIORITY_MASK {
+            0 => Self::Normal,
+            1 => Self::Urgent,
+            2 => Self::CriticalEquipment,
+            _ => Self::LifeSafety,
+        }
+    }
+}
+
 /// A network-layer address consisting of a network number and a MAC address.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct NpduAddress {
@@ -44,21 +86,21 @@ impl Npdu {
     pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
         // Derive control bits from optional fields so the header is always
         // consistent, regardless of what the caller set in `self.control`.
-        let mut control = self.control;
+        let mut control = self.control & !CONTROL_RESERVED;
         if self.destination.is_some() {
-            control |= 0x20;
+            control |= CONTROL_DESTINATION_PRESENT;
         } else {
-            control &= !0x20;
+            control &= !CONTROL_DESTINATION_PRESENT;
         }
         if self.source.is_some() {
-            control |= 0x08;
+            control |= CONTROL_SOURCE_PRESENT;
         } else {
-            control &= !0x08;
+            control &= !CONTROL_SOURCE_PRESENT;
         }
         if self.message_type.is_some() {
-            control |= 0x80;
+            control |= CONTROL_NETWORK_MESSAGE;
         } else {
-            control &= !0x80;
+            control &= !CONTROL_NETWORK_MESSAGE;
         }
 
         w.write_u8(NPDU_VERSION)?;
@@ -68,10 +110,11 @@ impl Npdu {
             encode_addr(w, dest)?;
         }
         if let Some(src) = self.source {
+            validate_source(src).map_err(|_| EncodeError::ValueOutOfRange)?;
             encode_addr(w, src)?;
         }
         if self.destination.is_some() {
-            w.write_u8(self.hop_count.unwrap_or(255))?;
+            w.write_u8(self.hop_count.unwrap_or(DEFAULT_HOP_COUNT))?;
         }
         if let Some(mt) = self.message_type {
             w.write_u8(mt)?;
@@ -89,16 +132,22 @@ impl Npdu {
         }
 
         let control = r.read_u8()?;
-        let has_dest = (control & 0x20) != 0;
-        let has_src = (control & 0x08) != 0;
-        let is_network_msg = (control & 0x80) != 0;
+        let has_dest = (control & CONTROL_DESTINATION_PRESENT) != 0;
+        let has_src = (control & CONTROL_SOURCE_PRESENT) != 0;
+        let is_network_msg = (control & CONTROL_NETWORK_MESSAGE) != 0;
 
         let destination = if has_dest {
             Some(decode_addr(r)?)
         } else {
             None
         };
-        let source = if has_src { Some(decode_addr(r)?) } else { None };
+        let source = if has_src {
+            let source = decode_addr(r)?;
+            validate_source(source)?;
+            Some(source)
+        } else {
+            None
+        };
         let hop_count = if has_dest { Some(r.read_u8()?) } else { None };
 
         let (message_type, vendor_id) = if is_network_msg {
@@ -122,6 +171,98 @@ impl Npdu {
             vendor_id,
         })
     }
+
+    /// Returns `true` when the NPDU carries a network-layer message rather than an APDU.
+    pub const fn is_network_message(&self) -> bool {
+        self.message_type.is_some()
+    }
+
+    /// Returns the expecting-reply bit of the control octet.
+    pub const fn expecting_reply(&self) -> bool {
+        self.control & CONTROL_EXPECTING_REPLY != 0
+    }
+
+    /// Sets or clears the expecting-reply bit.
+    pub fn set_expecting_reply(&mut self, expecting_reply: bool) {
+        if expecting_reply {
+            self.control |= CONTROL_EXPECTING_REPLY;
+        } else {
+            self.control &= !CONTROL_EXPECTING_REPLY;
+        }
+    }
+
+    /// Returns the network priority from the control octet.
+    pub const fn priority(&self) -> NetworkPriority {
+        NetworkPriority::from_control(self.control)
+    }
+
+    /// Replaces the network priority bits of the control octet.
+    pub fn set_priority(&mut self, priority: NetworkPriority) {
+        self.control = (self.control & !CONTROL_PRIORITY_MASK) | priority as u8;
+    }
+
+    /// Decrements the hop count before a router forwards the message.
+    ///
+    /// Returns `false` when the hop count is exhausted and the message must be discarded.
+    /// NPDUs without a destination are never forwarded, so they also return `false`.
+    pub fn decrement_hop_count(&mut self) -> bool {
+        if self.destination.is_none() {
+            return false;
+        }
+        match self.hop_count.unwrap_or(DEFAULT_HOP_COUNT) {
+            0 | 1 => {
+                self.hop_count = Some(0);
+                false
+            }
+            n => {
+                self.hop_count = Some(n - 1);
+                true
+            }
+        }
+    }
+
+    /// Returns `true` when the NPDU should be delivered on network `network`.
+    ///
+    /// An NPDU without DNET is addressed to the network it was received on, so `network`
+    /// should be the number of the receiving port's network. A DNET of
+    /// [`GLOBAL_BROADCAST_NETWORK`] matches every network.
+    pub const fn is_for_network(&self, network: u16) -> bool {
+        match self.destination {
+            None => true,
+            Some(dest) => dest.network == network || dest.network == GLOBAL_BROADCAST_NETWORK,
+        }
+    }
+
+    /// Returns the network-layer address a reply must be sent to.
+    ///
+    /// `Some` when the NPDU was routed (SNET/SADR present): the reply must carry this as
+    /// its destination. `None` means reply directly to the data-link sender.
+    pub const fn reply_address(&self) -> Option<NpduAddress> {
+        self.source
+    }
+
+    /// Builds the NPDU header for a reply to this NPDU: destination set to
+    /// [`reply_address`](Self::reply_address), same priority, expecting-reply cleared.
+    pub fn reply(&self) -> Self {
+        let mut reply = Self::new(self.control & CONTROL_PRIORITY_MASK);
+        reply.destination = self.reply_address();
+        if reply.destination.is_some() {
+            reply.hop_count = Some(DEFAULT_HOP_COUNT);
+        }
+        reply
+    }
+}
+
+impl NpduAddress {
+    /// Returns the valid bytes of the MAC address.
+    pub fn mac(&self) -> &[u8] {
+        &self.mac[..usize::from(self.mac_len).min(self.mac.len())]
+    }
+
+    /// Returns `true` for a DLEN of zero, which broadcasts on the destination network.
+    pub const fn is_broadcast(&self) -> bool {
+        self.mac_len == 0
+    }
 }
 
 fn encode_addr(w: &mut Writer<'_>, addr: NpduAddress) -> Result<(), EncodeError> {
@@ -133,6 +274,14 @@ fn encode_addr(w: &mut Writer<'_>, addr: NpduAddress) -> Result<(), EncodeError>
     w.write_all(&addr.mac[..addr.mac_len as usize])
 }
 
+/// SNET may not be the global broadcast network and SLEN may not be zero.
+fn validate_source(addr: NpduAddress) -> Result<(), DecodeError> {
+    if addr.network == GLOBAL_BROADCAST_NETWORK || addr.mac_len == 0 {
+        return Err(DecodeError::InvalidValue);
+    }
+    Ok(())
+}
+
 fn decode_addr(r: &mut Reader<'_>) -> Result<NpduAddress, DecodeError> {
     let network = r.read_be_u16()?;
     let mac_len = r.read_u8()?;
@@ -151,8 +300,19 @@ fn decode_addr(r: &mut Reader<'_>) -> Result<NpduAddress, DecodeError> {
 
 #[cfg(test)]
 mod tests {
-    use super::{Npdu, NpduAddress};
+    use super::{NetworkPriority, Npdu, NpduAddress, GLOBAL_BROADCAST_NETWORK};
     use crate::encoding::{reader::Reader, writer::Writer};
+    use crate::DecodeError;
+
+    fn addr(network: u16, mac: &[u8]) -> NpduAddress {
+        let mut out = NpduAddress {
+            network,
+            mac: [0; 6],
+            mac_len: mac.len() as u8,
+        };
+        out.mac[..mac.len()].copy_from_slice(mac);
+        out
+    }
 
     #[test]
     fn npdu_roundtrip() {
@@ -189,4 +349,82 @@ mod tests {
         assert_eq!(dec.message_type, Some(0x80));
         assert_eq!(dec.vendor_id, Some(260));
     }
+
+    #[test]
+    fn control_octet_flags_roundtrip() {
+        let mut p = Npdu::new(0x50);
+        p.set_expecting_reply(true);
+        p.set_priority(NetworkPriority::LifeSafety);
+        p.source = Some(addr(7, &[0x0A]));
+
+        let mut buf = [0u8; 16];
+        let mut w = Writer::new(&mut buf);
+        p.encode(&mut w).unwrap();
+        assert_eq!(w.as_written()[1], 0x0F);
+
+        let dec = Npdu::decode(&mut Reader::new(w.as_written())).unwrap();
+        assert!(dec.expecting_reply());
+        assert_eq!(dec.priority(), NetworkPriority::LifeSafety);
+        assert!(!dec.is_network_message());
+        assert_eq!(dec.reply_address(), Some(addr(7, &[0x0A])));
+
+        let reply = dec.reply();
+        assert!(!reply.expecting_reply());
+        assert_eq!(reply.priority(), NetworkPriority::LifeSafety);
+        assert_eq!(reply.destination, Some(addr(7, &[0x0A])));
+        assert_eq!(reply.hop_count, Some(255));
+    }
+
+    #[test]
+    fn hop_count_decrements_until_exhausted() {
+        let mut local = Npdu::new(0);
+        assert!(!local.decrement_hop_count());
+
+        let mut p = Npdu::new(0);
+        p.destination = Some(addr(5, &[]));
+        p.hop_count = Some(2);
+        assert!(p.decrement_hop_count());
+        assert_eq!(p.hop_count, Some(1));
+        assert!(!p.decrement_hop_count());
+        assert_eq!(p.hop_count, Some(0));
+        assert!(!p.decrement_hop_count());
+    }
+
+    #[test]
+    fn network_matching() {
+        let local = Npdu::new(0);
+        assert!(local.is_for_network(3));
+        assert_eq!(local.reply_address(), None);
+        assert_eq!(local.reply().destination, None);
+
+        let mut p = Npdu::new(0);
+        p.destination = Some(addr(5, &[]));
+        assert!(p.is_for_network(5));
+        assert!(!p.is_for_network(6));
+        assert!(p.destination.unwrap().is_broadcast());
+
+        p.destination = Some(addr(GLOBAL_BROADCAST_NETWORK, &[]));
+        assert!(p.is_for_network(6));
+    }
+
+    #[test]
+    fn invalid_source_address_is_rejected() {
+        // SNET = 0xFFFF
+        let frame = [0x01, 0x08, 0xFF, 0xFF, 0x01, 0x0A];
+        assert_eq!(
+            Npdu::decode(&mut Reader::new(&frame)),
+            Err(DecodeError::InvalidValue)
+        );
+        // SLEN = 0
+        let frame = [0x01, 0x08, 0x00, 0x07, 0x00];
+        assert_eq!(
+            Npdu::decode(&mut Reader::new(&frame)),
+            Err(DecodeError::InvalidValue)
+        );
+
+        let mut p = Npdu::new(0);
+        p.source = Some(addr(7, &[]));
+        let mut buf = [0u8; 16];
+        assert!(p.encode(&mut Writer::new(&mut buf)).is_err());
+    }
 }
@@ -0,0 +1,523 @@
+use crate::encoding::{primitives::encode_ctx_unsigned, reader::Reader, tag::Tag, writer::Writer};
+use crate::services::acknowledge_alarm::{encode_timestamp, TimeStamp};
+use crate::services::{decode_ack_object, decode_required_ctx_unsigned, expect_tag};
+use crate::types::{BitString, DeviceObjectReference, ObjectId};
+use crate::{DecodeError, EncodeError};
+
+#[cfg(feature = "alloc")]
+use crate::services::event_notification::decode_required_ctx_timestamp;
+
+/// Event_Type (and notification-parameters choice) of an access event.
+pub const EVENT_TYPE_ACCESS_EVENT: u32 = 13;
+
+/// BACnetDoorValue: an Access Door's Present_Value, the command it carries out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum DoorValue {
+    Lock = 0,
+    Unlock = 1,
+    /// Unlock for Door_Pulse_Time, then lock.
+    PulseUnlock = 2,
+    /// Unlock for Door_Extended_Pulse_Time, then lock.
+    ExtendedPulseUnlock = 3,
+}
+
+impl DoorValue {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Lock),
+            1 => Some(Self::Unlock),
+            2 => Some(Self::PulseUnlock),
+            3 => Some(Self::ExtendedPulseUnlock),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetDoorStatus: an Access Door's Door_Status, from its position sensor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum DoorStatus {
+    Closed = 0,
+    Opened = 1,
+    Unknown = 2,
+    DoorFault = 3,
+    Unused = 4,
+    None = 5,
+    Closing = 6,
+    Opening = 7,
+    SafetyLocked = 8,
+    LimitedOpened = 9,
+}
+
+impl DoorStatus {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Closed),
+            1 => Some(Self::Opened),
+            2 => Some(Self::Unknown),
+            3 => Some(Self::DoorFault),
+            4 => Some(Self::Unused),
+            5 => Some(Self::None),
+            6 => Some(Self::Closing),
+            7 => Some(Self::Opening),
+            8 => Some(Self::SafetyLocked),
+            9 => Some(Self::LimitedOpened),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetLockStatus: an Access Door's Lock_Status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum LockStatus {
+    Locked = 0,
+    Unlocked = 1,
+    LockFault = 2,
+    Unused = 3,
+    Unknown = 4,
+}
+
+impl LockStatus {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Locked),
+            1 => Some(Self::Unlocked),
+            2 => Some(Self::LockFault),
+            3 => Some(Self::Unused),
+            4 => Some(Self::Unknown),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetDoorAlarmState: an Access Door's Door_Alarm_State.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum DoorAlarmState {
+    Normal = 0,
+    Alarm = 1,
+    DoorOpenTooLong = 2,
+    ForcedOpen = 3,
+    Tamper = 4,
+    DoorFault = 5,
+    LockDown = 6,
+    FreeAccess = 7,
+    EgressOpen = 8,
+}
+
+impl DoorAlarmState {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Normal),
+            1 => Some(Self::Alarm),
+            2 => Some(Self::DoorOpenTooLong),
+            3 => Some(Self::ForcedOpen),
+            4 => Some(Self::Tamper),
+            5 => Some(Self::DoorFault),
+            6 => Some(Self::LockDown),
+            7 => Some(Self::FreeAccess),
+            8 => Some(Self::EgressOpen),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetAccessZoneOccupancyState: an Access Zone's Occupancy_State.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum AccessZoneOccupancyState {
+    Normal = 0,
+    BelowLowerLimit = 1,
+    AtLowerLimit = 2,
+    AtUpperLimit = 3,
+    AboveUpperLimit = 4,
+    Disabled = 5,
+    NotSupported = 6,
+}
+
+impl AccessZoneOccupancyState {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Normal),
+            1 => Some(Self::BelowLowerLimit),
+            2 => Some(Self::AtLowerLimit),
+            3 => Some(Self::AtUpperLimit),
+            4 => Some(Self::AboveUpperLimit),
+            5 => Some(Self::Disabled),
+            6 => Some(Self::NotSupported),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetAuthenticationStatus: an Access Point's Authentication_Status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum AuthenticationStatus {
+    NotReady = 0,
+    Ready = 1,
+    Disabled = 2,
+    WaitingForAuthenticationFactor = 3,
+    WaitingForAccompaniment = 4,
+    WaitingForVerification = 5,
+    InProgress = 6,
+}
+
+impl AuthenticationStatus {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::NotReady),
+            1 => Some(Self::Ready),
+            2 => Some(Self::Disabled),
+            3 => Some(Self::WaitingForAuthenticationFactor),
+            4 => Some(Self::WaitingForAccompaniment),
+            5 => Some(Self::WaitingForVerification),
+            6 => Some(Self::InProgress),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetAccessEvent: what happened at an Access Point. Values from 128 deny access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum AccessEvent {
+    None = 0,
+    Granted = 1,
+    Muster = 2,
+    PassbackDetected = 3,
+    Duress = 4,
+    Trace = 5,
+    LockoutMaxAttempts = 6,
+    LockoutOther = 7,
+    LockoutRelinquished = 8,
+    LockedByHigherPriority = 9,
+    OutOfService = 10,
+    OutOfServiceRelinquished = 11,
+    AccompaniedBy = 12,
+    AuthenticationFactorRead = 13,
+    AuthorizationDelayed = 14,
+    VerificationRequired = 15,
+    NoEntryAfterGrant = 16,
+    DeniedDenyAll = 128,
+    DeniedUnknownCredential = 129,
+    DeniedAuthenticationUnavailable = 130,
+    DeniedAuthenticationFactorTimeout = 131,
+    DeniedIncorrectAuthenticationFactor = 132,
+    DeniedZoneNoAccessRights = 133,
+    DeniedPointNoAccessRights = 134,
+    DeniedNoAccessRights = 135,
+    DeniedOutOfTimeRange = 136,
+    DeniedThreatLevel = 137,
+    DeniedPassback = 138,
+    DeniedUnexpectedLocationUsage = 139,
+    DeniedMaxAttempts = 140,
+    DeniedLowerOccupancyLimit = 141,
+    DeniedUpperOccupancyLimit = 142,
+    DeniedAuthenticationFactorLost = 143,
+    DeniedAuthenticationFactorStolen = 144,
+    DeniedAuthenticationFactorDamaged = 145,
+    DeniedAuthenticationFactorDestroyed = 146,
+    DeniedAuthenticationFactorDisabled = 147,
+    DeniedAuthenticationFactorError = 148,
+    DeniedCredentialUnassigned = 149,
+    DeniedCredentialNotProvisioned = 150,
+    DeniedCredentialNotYetActive = 151,
+    DeniedCredentialExpired = 152,
+    DeniedCredentialManualDisable = 153,
+    DeniedCredentialLockout = 154,
+    DeniedCredentialMaxDays = 155,
+    DeniedCredentialMaxUses = 156,
+    DeniedCredentialInactivity = 157,
+    DeniedCredentialDisabled = 158,
+    DeniedNoAccompaniment = 159,
+    DeniedIncorrectAccompaniment = 160,
+    DeniedLockout = 161,
+    DeniedVerificationFailed = 162,
+    DeniedVerificationTimeout = 163,
+    DeniedOther = 164,
+}
+
+impl AccessEvent {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::None),
+            1 => Some(Self::Granted),
+            2 => Some(Self::Muster),
+            3 => Some(Self::PassbackDetected),
+            4 => Some(Self::Duress),
+            5 => Some(Self::Trace),
+            6 => Some(Self::LockoutMaxAttempts),
+            7 => Some(Self::LockoutOther),
+            8 => Some(Self::LockoutRelinquished),
+            9 => Some(Self::LockedByHigherPriority),
+            10 => Some(Self::OutOfService),
+            11 => Some(Self::OutOfServiceRelinquished),
+            12 => Some(Self::AccompaniedBy),
+            13 => Some(Self::AuthenticationFactorRead),
+            14 => Some(Self::AuthorizationDelayed),
+            15 => Some(Self::VerificationRequired),
+            16 => Some(Self::NoEntryAfterGrant),
+            128 => Some(Self::DeniedDenyAll),
+            129 => Some(Self::DeniedUnknownCredential),
+            130 => Some(Self::DeniedAuthenticationUnavailable),
+            131 => Some(Self::DeniedAuthenticationFactorTimeout),
+            132 => Some(Self::DeniedIncorrectAuthenticationFactor),
+            133 => Some(Self::DeniedZoneNoAccessRights),
+            134 => Some(Self::DeniedPointNoAccessRights),
+            135 => Some(Self::DeniedNoAccessRights),
+            136 => Some(Self::DeniedOutOfTimeRange),
+            137 => Some(Self::DeniedThreatLevel),
+            138 => Some(Self::DeniedPassback),
+            139 => Some(Self::DeniedUnexpectedLocationUsage),
+            140 => Some(Self::DeniedMaxAttempts),
+            141 => Some(Self::DeniedLowerOccupancyLimit),
+            142 => Some(Self::DeniedUpperOccupancyLimit),
+            143 => Some(Self::DeniedAuthenticationFactorLost),
+            144 => Some(Self::DeniedAuthenticationFactorStolen),
+            145 => Some(Self::DeniedAuthenticationFactorDamaged),
+            146 => Some(Self::DeniedAuthenticationFactorDestroyed),
+            147 => Some(Self::DeniedAuthenticationFactorDisabled),
+            148 => Some(Self::DeniedAuthenticationFactorError),
+            149 => Some(Self::DeniedCredentialUnassigned),
+            150 => Some(Self::DeniedCredentialNotProvisioned),
+            151 => Some(Self::DeniedCredentialNotYetActive),
+            152 => Some(Self::DeniedCredentialExpired),
+            153 => Some(Self::DeniedCredentialManualDisable),
+            154 => Some(Self::DeniedCredentialLockout),
+            155 => Some(Self::DeniedCredentialMaxDays),
+            156 => Some(Self::DeniedCredentialMaxUses),
+            157 => Some(Self::DeniedCredentialInactivity),
+            158 => Some(Self::DeniedCredentialDisabled),
+            159 => Some(Self::DeniedNoAccompaniment),
+            160 => Some(Self::DeniedIncorrectAccompaniment),
+            161 => Some(Self::DeniedLockout),
+            162 => Some(Self::DeniedVerificationFailed),
+            163 => Some(Self::DeniedVerificationTimeout),
+            164 => Some(Self::DeniedOther),
+            _ => None,
+        }
+    }
+}
+
+impl AccessEvent {
+    /// Whether the event refused access.
+    pub const fn is_denied(self) -> bool {
+        self.to_u32() >= 128
+    }
+}
+
+/// BACnetAuthenticationFactor: a credential as read at an Access Point, such as a card
+/// number or PIN. A Credential Data Input's Present_Value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthenticationFactor<'a> {
+    /// BACnetAuthenticationFactorType (e.g. 11 = simple-number-32, 20 = ABA track 2).
+    pub format_type: u32,
+    /// Vendor or site-specific class within the format type.
+    pub format_class: u32,
+    pub value: &'a [u8],
+}
+
+impl<'a> AuthenticationFactor<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_ctx_unsigned(w, 0, self.format_type)?;
+        encode_ctx_unsigned(w, 1, self.format_class)?;
+        Tag::Context {
+            tag_num: 2,
+            len: self.value.len() as u32,
+        }
+        .encode(w)?;
+        w.write_all(self.value)
+    }
+
+    pub fn decode(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let format_type = decode_required_ctx_unsigned(r, 0)?;
+        let format_class = decode_required_ctx_unsigned(r, 1)?;
+        let value = match Tag::decode(r)? {
+            Tag::Context { tag_num: 2, len } => r.read_exact(len as usize)?,
+            _ => return Err(DecodeError::InvalidTag),
+        };
+        Ok(Self {
+            format_type,
+            format_class,
+            value,
+        })
+    }
+}
+
+/// ReadProperty-ACK carrying a Credential Data Input's Present_Value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthenticationFactorAck<'a> {
+    pub object_id: ObjectId,
+    pub factor: AuthenticationFactor<'a>,
+}
+
+impl<'a> AuthenticationFactorAck<'a> {
+    pub fn decode_after_header(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let object_id = decode_ack_object(r)?;
+        expect_tag(r, Tag::Opening { tag_num: 3 })?;
+        let factor = AuthenticationFactor::decode(r)?;
+        expect_tag(r, Tag::Closing { tag_num: 3 })?;
+        Ok(Self { object_id, factor })
+    }
+}
+
+/// The access-event choice of BACnetNotificationParameters: who presented what, where,
+/// and what the Access Point made of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccessEventParameters<'a> {
+    /// BACnetAccessEvent; see [`access_event`](Self::access_event).
+    pub access_event: u32,
+    pub status_flags: BitString<'a>,
+    /// Access_Event_Tag, which orders the events of one Access Point.
+    pub access_event_tag: u32,
+    pub access_event_time: TimeStamp,
+    pub access_credential: DeviceObjectReference,
+    pub authentication_factor: Option<AuthenticationFactor<'a>>,
+}
+
+impl<'a> AccessEventParameters<'a> {
+    /// The access event, if it is a standard one.
+    pub const fn access_event(&self) -> Option<AccessEvent> {
+        AccessEvent::from_u32(self.access_event)
+    }
+
+    /// Encodes the parameters, without the enclosing access-event choice tags.
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_ctx_unsigned(w, 0, self.access_event)?;
+        Tag::Context {
+            tag_num: 1,
+            len: self.status_flags.data.len() as u32 + 1,
+        }
+        .encode(w)?;
+        w.write_u8(self.status_flags.unused_bits)?;
+        w.write_all(self.status_flags.data)?;
+        encode_ctx_unsigned(w, 2, self.access_event_tag)?;
+        Tag::Opening { tag_num: 3 }.encode(w)?;
+        encode_timestamp(w, self.access_event_time)?;
+        Tag::Closing { tag_num: 3 }.encode(w)?;
+        Tag::Opening { tag_num: 4 }.encode(w)?;
+        self.access_credential.encode(w)?;
+        Tag::Closing { tag_num: 4 }.encode(w)?;
+        if let Some(factor) = &self.authentication_factor {
+            Tag::Opening { tag_num: 5 }.encode(w)?;
+            factor.encode(w)?;
+            Tag::Closing { tag_num: 5 }.encode(w)?;
+        }
+        Ok(())
+    }
+
+    /// Decodes the parameters inside the access-event choice tags, stopping before the
+    /// closing one.
+    #[cfg(feature = "alloc")]
+    pub fn decode(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let access_event = decode_required_ctx_unsigned(r, 0)?;
+        let status_flags = match Tag::decode(r)? {
+            Tag::Context { tag_num: 1, len } if len > 0 => {
+                let bytes = r.read_exact(len as usize)?;
+                BitString::new(bytes[0], &bytes[1..])
+            }
+            _ => return Err(DecodeError::InvalidTag),
+        };
+        let access_event_tag = decode_required_ctx_unsigned(r, 2)?;
+        let access_event_time = decode_required_ctx_timestamp(r, 3)?;
+        expect_tag(r, Tag::Opening { tag_num: 4 })?;
+        let access_credential = DeviceObjectReference::decode(r)?;
+        expect_tag(r, Tag::Closing { tag_num: 4 })?;
+
+        let mut peek = *r;
+        let authentication_factor =
+            if !r.is_empty() && Tag::decode(&mut peek)? == (Tag::Opening { tag_num: 5 }) {
+                *r = peek;
+                let factor = AuthenticationFactor::decode(r)?;
+                expect_tag(r, Tag::Closing { tag_num: 5 })?;
+                Some(factor)
+            } else {
+                None
+            };
+        Ok(Self {
+            access_event,
+            status_flags,
+            access_event_tag,
+            access_event_time,
+            access_credential,
+            authentication_factor,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccessEvent, AuthenticationFactor, AuthenticationFactorAck, DoorValue};
+    use crate::encoding::{reader::Reader, writer::Writer};
+    use crate::types::{ObjectId, ObjectType};
+
+    #[test]
+    fn enums_round_trip_and_classify_denials() {
+        assert_eq!(DoorValue::from_u32(2), Some(DoorValue::PulseUnlock));
+        assert_eq!(DoorValue::from_u32(4), None);
+        assert_eq!(
+            AccessEvent::from_u32(152),
+            Some(AccessEvent::DeniedCredentialExpired)
+        );
+        assert_eq!(AccessEvent::from_u32(17), None);
+        assert!(AccessEvent::DeniedOther.is_denied());
+        assert!(!AccessEvent::Granted.is_denied());
+    }
+
+    #[test]
+    fn authentication_factor_ack_decodes() {
+        let cdi = ObjectId::new(ObjectType::CredentialDataInput, 2);
+        let factor = AuthenticationFactor {
+            format_type: 11,
+            format_class: 0,
+            value: &[0x00, 0x01, 0xE2, 0x40],
+        };
+        let mut buf = [0u8; 32];
+        let mut w = Writer::new(&mut buf);
+        w.write_all(&[0x0C, 0x09, 0x40, 0x00, 0x02, 0x19, 0x55, 0x3E])
+            .unwrap();
+        factor.encode(&mut w).unwrap();
+        w.write_u8(0x3F).unwrap();
+
+        let mut r = Reader::new(w.as_written());
+        assert_eq!(
+            AuthenticationFactorAck::decode_after_header(&mut r).unwrap(),
+            AuthenticationFactorAck {
+                object_id: cdi,
+                factor,
+            }
+        );
+        assert!(r.is_empty());
+    }
+}
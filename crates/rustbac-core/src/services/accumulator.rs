@@ -0,0 +1,212 @@
+use crate::encoding::{
+    primitives::{decode_signed, encode_ctx_real, encode_ctx_signed, encode_ctx_unsigned},
+    reader::Reader,
+    tag::Tag,
+    writer::Writer,
+};
+use crate::services::{decode_ack_object, decode_real, decode_required_ctx_unsigned, expect_tag};
+use crate::types::ObjectId;
+use crate::{DecodeError, EncodeError};
+
+/// BACnetScale: the conversion from an Accumulator's Present_Value (pulses) to its Units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scale {
+    /// Each pulse is worth this many units.
+    Float(f32),
+    /// Each pulse is worth 10 to this power units.
+    Integer(i32),
+}
+
+impl Scale {
+    /// Units per pulse.
+    pub fn factor(self) -> f64 {
+        match self {
+            Self::Float(factor) => f64::from(factor),
+            Self::Integer(exponent) => pow10(exponent),
+        }
+    }
+
+    /// `pulses` in engineering units.
+    pub fn apply(self, pulses: u32) -> f64 {
+        match self {
+            // Dividing keeps e.g. 1234 x 10^-2 at exactly 12.34.
+            Self::Integer(exponent) if exponent < 0 => {
+                f64::from(pulses) / pow10(exponent.saturating_neg())
+            }
+            _ => f64::from(pulses) * self.factor(),
+        }
+    }
+
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        match *self {
+            Self::Float(factor) => encode_ctx_real(w, 0, factor),
+            Self::Integer(exponent) => encode_ctx_signed(w, 1, exponent),
+        }
+    }
+
+    pub fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        match Tag::decode(r)? {
+            Tag::Context { tag_num: 0, len } => decode_real(r, len).map(Self::Float),
+            Tag::Context { tag_num: 1, len } => decode_signed(r, len as usize).map(Self::Integer),
+            _ => Err(DecodeError::InvalidTag),
+        }
+    }
+}
+
+/// BACnetPrescale: how an Accumulator divides raw input pulses before counting them.
+///
+/// Present_Value already counts prescaled pulses; this is what the device applies on its
+/// input, for reporting or for converting a raw meter reading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Prescale {
+    /// Counts added per `modulo_divide` raw pulses.
+    pub multiplier: u32,
+    /// Raw pulses per `multiplier` counts.
+    pub modulo_divide: u32,
+}
+
+impl Prescale {
+    pub const fn new(multiplier: u32, modulo_divide: u32) -> Self {
+        Self {
+            multiplier,
+            modulo_divide,
+        }
+    }
+
+    /// Counts for `raw_pulses` input pulses, or `None` if `modulo_divide` is zero.
+    pub fn counts(self, raw_pulses: u64) -> Option<u64> {
+        (raw_pulses * u64::from(self.multiplier)).checked_div(u64::from(self.modulo_divide))
+    }
+
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_ctx_unsigned(w, 0, self.multiplier)?;
+        encode_ctx_unsigned(w, 1, self.modulo_divide)
+    }
+
+    pub fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            multiplier: decode_required_ctx_unsigned(r, 0)?,
+            modulo_divide: decode_required_ctx_unsigned(r, 1)?,
+        })
+    }
+}
+
+/// ReadProperty-ACK carrying an Accumulator's Scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleAck {
+    pub accumulator: ObjectId,
+    pub scale: Scale,
+}
+
+impl ScaleAck {
+    pub fn decode_after_header(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let accumulator = decode_ack_object(r)?;
+        expect_tag(r, Tag::Opening { tag_num: 3 })?;
+        let scale = Scale::decode(r)?;
+        expect_tag(r, Tag::Closing { tag_num: 3 })?;
+        Ok(Self { accumulator, scale })
+    }
+}
+
+/// ReadProperty-ACK carrying an Accumulator's Prescale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrescaleAck {
+    pub accumulator: ObjectId,
+    pub prescale: Prescale,
+}
+
+impl PrescaleAck {
+    pub fn decode_after_header(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let accumulator = decode_ack_object(r)?;
+        expect_tag(r, Tag::Opening { tag_num: 3 })?;
+        let prescale = Prescale::decode(r)?;
+        expect_tag(r, Tag::Closing { tag_num: 3 })?;
+        Ok(Self {
+            accumulator,
+            prescale,
+        })
+    }
+}
+
+/// 10 to the power `exponent`, exact up to 10^22; `core` has no `powi`.
+fn pow10(exponent: i32) -> f64 {
+    // Past 10^400 the result is infinite (or zero) anyway.
+    let power = (0..exponent.unsigned_abs().min(400)).fold(1.0f64, |power, _| power * 10.0);
+    if exponent < 0 {
+        1.0 / power
+    } else {
+        power
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Prescale, PrescaleAck, Scale, ScaleAck};
+    use crate::encoding::{reader::Reader, writer::Writer};
+    use crate::types::{ObjectId, ObjectType};
+
+    #[test]
+    fn scale_round_trips_and_converts_pulses() {
+        for (scale, bytes) in [
+            (Scale::Float(0.25), &[0x0C, 0x3E, 0x80, 0x00, 0x00][..]),
+            (Scale::Integer(-2), &[0x19, 0xFE][..]),
+        ] {
+            let mut buf = [0u8; 8];
+            let mut w = Writer::new(&mut buf);
+            scale.encode(&mut w).unwrap();
+            assert_eq!(w.as_written(), bytes);
+            let mut r = Reader::new(bytes);
+            assert_eq!(Scale::decode(&mut r).unwrap(), scale);
+            assert!(r.is_empty());
+        }
+        assert_eq!(Scale::Float(0.25).apply(1000), 250.0);
+        assert_eq!(Scale::Integer(-2).apply(1234), 12.34);
+        assert_eq!(Scale::Integer(3).apply(7), 7000.0);
+    }
+
+    #[test]
+    fn prescale_counts_raw_pulses() {
+        let prescale = Prescale::new(1, 10);
+        assert_eq!(prescale.counts(1234), Some(123));
+        assert_eq!(Prescale::new(5, 2).counts(4), Some(10));
+        assert_eq!(Prescale::new(1, 0).counts(4), None);
+    }
+
+    #[test]
+    fn scale_and_prescale_acks_decode() {
+        let accumulator = ObjectId::new(ObjectType::Accumulator, 3);
+        let scale_ack = [
+            0x0C, 0x05, 0xC0, 0x00, 0x03, // [0] accumulator,3
+            0x19, 0xBB, // [1] scale
+            0x3E, 0x19, 0x01, 0x3F, // [3] { integer-scale 1 }
+        ];
+        assert_eq!(
+            ScaleAck::decode_after_header(&mut Reader::new(&scale_ack)).unwrap(),
+            ScaleAck {
+                accumulator,
+                scale: Scale::Integer(1),
+            }
+        );
+
+        let prescale_ack = [
+            0x0C, 0x05, 0xC0, 0x00, 0x03, // [0] accumulator,3
+            0x19, 0xB9, // [1] prescale
+            0x3E, 0x09, 0x01, 0x19, 0x64, 0x3F, // [3] { 1 / 100 }
+        ];
+        let mut r = Reader::new(&prescale_ack);
+        assert_eq!(
+            PrescaleAck::decode_after_header(&mut r).unwrap(),
+            PrescaleAck {
+                accumulator,
+                prescale: Prescale::new(1, 100),
+            }
+        );
+        assert!(r.is_empty());
+
+        // A float-scale that is not a four-octet REAL is rejected.
+        let bad = [
+            0x0C, 0x05, 0xC0, 0x00, 0x03, 0x19, 0xBB, 0x3E, 0x0A, 0x00, 0x01, 0x3F,
+        ];
+        assert!(ScaleAck::decode_after_header(&mut Reader::new(&bad)).is_err());
+    }
+}
@@ -87,7 +87,7 @@ impl<'a> AcknowledgeAlarmRequest<'a> {
     }
 }
 
-fn encode_timestamp(w: &mut Writer<'_>, value: TimeStamp) -> Result<(), EncodeError> {
+pub(crate) fn encode_timestamp(w: &mut Writer<'_>, value: TimeStamp) -> Result<(), EncodeError> {
     match value {
         TimeStamp::Time(time) => {
             Tag::Context { tag_num: 0, len: 4 }.encode(w)?;
@@ -0,0 +1,446 @@
+use crate::encoding::{tag::Tag, writer::Writer};
+use crate::services::list_element::{
+    encode_list_element_header, SERVICE_ADD_LIST_ELEMENT, SERVICE_REMOVE_LIST_ELEMENT,
+};
+use crate::services::value_codec::encode_application_data_value;
+use crate::services::write_property::encode_write_property_header;
+use crate::types::{DataValue, Date, ObjectId, PropertyId};
+use crate::EncodeError;
+
+#[cfg(feature = "alloc")]
+use crate::encoding::{primitives::decode_unsigned, reader::Reader, tag::AppTag};
+#[cfg(feature = "alloc")]
+use crate::DecodeError;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The value of an unspecified (wildcard) date field.
+const UNSPECIFIED: u8 = 0xFF;
+
+/// BACnetWeekNDay: a month, week of the month and day of the week, each of which may be
+/// [`WeekNDay::ANY`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeekNDay {
+    /// 1–12, 13 for odd months, 14 for even months.
+    pub month: u8,
+    /// 1–5 for days 1–7, 8–14, ... 29–31; 6 for the last 7 days of the month; 7–9 for
+    /// the 7 days before those, and so on.
+    pub week_of_month: u8,
+    /// 1 = Monday … 7 = Sunday.
+    pub day_of_week: u8,
+}
+
+impl WeekNDay {
+    /// The wildcard value of each field.
+    pub const ANY: u8 = UNSPECIFIED;
+
+    pub const fn new(month: u8, week_of_month: u8, day_of_week: u8) -> Self {
+        Self {
+            month,
+            week_of_month,
+            day_of_week,
+        }
+    }
+
+    /// Checks every field is in range or a wildcard.
+    pub fn validate(&self) -> Result<(), EncodeError> {
+        if in_range_or_any(self.month, 1, 14)
+            && in_range_or_any(self.week_of_month, 1, 9)
+            && in_range_or_any(self.day_of_week, 1, 7)
+        {
+            Ok(())
+        } else {
+            Err(EncodeError::ValueOutOfRange)
+        }
+    }
+
+    pub const fn to_bytes(self) -> [u8; 3] {
+        [self.month, self.week_of_month, self.day_of_week]
+    }
+
+    pub const fn from_bytes(bytes: [u8; 3]) -> Self {
+        Self::new(bytes[0], bytes[1], bytes[2])
+    }
+}
+
+/// BACnetCalendarEntry, the element type of a Calendar's Date_List and of special events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarEntry {
+    /// A date; any field may be unspecified, so `2024-*-25` matches the 25th of every
+    /// month of 2024.
+    Date(Date),
+    /// Every day from `start` to `end`, inclusive. Either end may be fully unspecified to
+    /// leave the range open on that side.
+    DateRange {
+        start: Date,
+        end: Date,
+    },
+    WeekNDay(WeekNDay),
+}
+
+impl CalendarEntry {
+    /// Checks the entry's wildcards are ones the standard allows.
+    ///
+    /// A date may leave any field unspecified, and its month and day may use the odd,
+    /// even and last-day values. A date range end is either a specific date or entirely
+    /// unspecified, and a specific start may not come after a specific end.
+    pub fn validate(&self) -> Result<(), EncodeError> {
+        match self {
+            Self::Date(date) => {
+                if in_range_or_any(date.month, 1, 14)
+                    && in_range_or_any(date.day, 1, 34)
+                    && in_range_or_any(date.weekday, 1, 7)
+                {
+                    Ok(())
+                } else {
+                    Err(EncodeError::ValueOutOfRange)
+                }
+            }
+            Self::DateRange { start, end } => {
+                let start_open = range_end_is_open(start)?;
+                let end_open = range_end_is_open(end)?;
+                let key = |d: &Date| (d.year_since_1900, d.month, d.day);
+                if !start_open && !end_open && key(start) > key(end) {
+                    return Err(EncodeError::ValueOutOfRange);
+                }
+                Ok(())
+            }
+            Self::WeekNDay(week_n_day) => week_n_day.validate(),
+        }
+    }
+
+    /// Validates and encodes the entry.
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        self.validate()?;
+        match self {
+            Self::Date(date) => {
+                Tag::Context { tag_num: 0, len: 4 }.encode(w)?;
+                w.write_all(&date_bytes(date))
+            }
+            Self::DateRange { start, end } => {
+                Tag::Opening { tag_num: 1 }.encode(w)?;
+                encode_application_data_value(w, &DataValue::Date(*start))?;
+                encode_application_data_value(w, &DataValue::Date(*end))?;
+                Tag::Closing { tag_num: 1 }.encode(w)
+            }
+            Self::WeekNDay(week_n_day) => {
+                Tag::Context { tag_num: 2, len: 3 }.encode(w)?;
+                w.write_all(&week_n_day.to_bytes())
+            }
+        }
+    }
+
+    /// Decodes one entry as sent, without validating its fields.
+    #[cfg(feature = "alloc")]
+    pub fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        match Tag::decode(r)? {
+            Tag::Context { tag_num: 0, len: 4 } => Ok(Self::Date(read_date(r)?)),
+            Tag::Opening { tag_num: 1 } => {
+                let start = decode_app_date(r)?;
+                let end = decode_app_date(r)?;
+                match Tag::decode(r)? {
+                    Tag::Closing { tag_num: 1 } => Ok(Self::DateRange { start, end }),
+                    _ => Err(DecodeError::InvalidTag),
+                }
+            }
+            Tag::Context { tag_num: 2, len: 3 } => {
+                let b = r.read_exact(3)?;
+                Ok(Self::WeekNDay(WeekNDay::from_bytes([b[0], b[1], b[2]])))
+            }
+            Tag::Context { tag_num: 0 | 2, .. } => Err(DecodeError::InvalidLength),
+            _ => Err(DecodeError::InvalidTag),
+        }
+    }
+}
+
+/// WriteProperty replacing a Calendar's whole Date_List.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteDateListRequest<'a> {
+    pub calendar: ObjectId,
+    pub entries: &'a [CalendarEntry],
+    pub invoke_id: u8,
+}
+
+impl<'a> WriteDateListRequest<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_write_property_header(w, self.invoke_id, self.calendar, PropertyId::DateList)?;
+        encode_entries(w, self.entries)
+    }
+}
+
+/// AddListElement of entries to a Calendar's Date_List.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddDateListEntriesRequest<'a> {
+    pub calendar: ObjectId,
+    pub entries: &'a [CalendarEntry],
+    pub invoke_id: u8,
+}
+
+impl<'a> AddDateListEntriesRequest<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_list_element_header(
+            w,
+            self.invoke_id,
+            SERVICE_ADD_LIST_ELEMENT,
+            self.calendar,
+            PropertyId::DateList,
+            None,
+        )?;
+        encode_entries(w, self.entries)
+    }
+}
+
+/// RemoveListElement of entries from a Calendar's Date_List.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemoveDateListEntriesRequest<'a> {
+    pub calendar: ObjectId,
+    pub entries: &'a [CalendarEntry],
+    pub invoke_id: u8,
+}
+
+impl<'a> RemoveDateListEntriesRequest<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_list_element_header(
+            w,
+            self.invoke_id,
+            SERVICE_REMOVE_LIST_ELEMENT,
+            self.calendar,
+            PropertyId::DateList,
+            None,
+        )?;
+        encode_entries(w, self.entries)
+    }
+}
+
+/// ReadProperty-ACK carrying a Calendar's Date_List.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateListAck {
+    pub calendar: ObjectId,
+    pub entries: Vec<CalendarEntry>,
+}
+
+#[cfg(feature = "alloc")]
+impl DateListAck {
+    pub fn decode_after_header(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let calendar = match Tag::decode(r)? {
+            Tag::Context { tag_num: 0, len } => {
+                ObjectId::from_raw(decode_unsigned(r, len as usize)?)
+            }
+            _ => return Err(DecodeError::InvalidTag),
+        };
+        match Tag::decode(r)? {
+            Tag::Context { tag_num: 1, len } => {
+                r.read_exact(len as usize)?;
+            }
+            _ => return Err(DecodeError::InvalidTag),
+        }
+        match Tag::decode(r)? {
+            Tag::Opening { tag_num: 3 } => {}
+            _ => return Err(DecodeError::InvalidTag),
+        }
+        let mut entries = Vec::new();
+        loop {
+            let mut peek = *r;
+            if Tag::decode(&mut peek)? == (Tag::Closing { tag_num: 3 }) {
+                *r = peek;
+                break;
+            }
+            entries.push(CalendarEntry::decode(r)?);
+        }
+        Ok(Self { calendar, entries })
+    }
+}
+
+fn encode_entries(w: &mut Writer<'_>, entries: &[CalendarEntry]) -> Result<(), EncodeError> {
+    Tag::Opening { tag_num: 3 }.encode(w)?;
+    for entry in entries {
+        entry.encode(w)?;
+    }
+    Tag::Closing { tag_num: 3 }.encode(w)
+}
+
+fn in_range_or_any(value: u8, min: u8, max: u8) -> bool {
+    value == UNSPECIFIED || (min..=max).contains(&value)
+}
+
+/// Whether a date range end is fully unspecified; errors when it is only partly so.
+fn range_end_is_open(date: &Date) -> Result<bool, EncodeError> {
+    if date_bytes(date) == [UNSPECIFIED; 4] {
+        return Ok(true);
+    }
+    if date.year_since_1900 != UNSPECIFIED
+        && (1..=12).contains(&date.month)
+        && (1..=31).contains(&date.day)
+        && in_range_or_any(date.weekday, 1, 7)
+    {
+        Ok(false)
+    } else {
+        Err(EncodeError::ValueOutOfRange)
+    }
+}
+
+const fn date_bytes(date: &Date) -> [u8; 4] {
+    [date.year_since_1900, date.month, date.day, date.weekday]
+}
+
+#[cfg(feature = "alloc")]
+fn read_date(r: &mut Reader<'_>) -> Result<Date, DecodeError> {
+    let b = r.read_exact(4)?;
+    Ok(Date {
+        year_since_1900: b[0],
+        month: b[1],
+        day: b[2],
+        weekday: b[3],
+    })
+}
+
+#[cfg(feature = "alloc")]
+fn decode_app_date(r: &mut Reader<'_>) -> Result<Date, DecodeError> {
+    match Tag::decode(r)? {
+        Tag::Application {
+            tag: AppTag::Date,
+            len: 4,
+        } => read_date(r),
+        _ => Err(DecodeError::InvalidTag),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CalendarEntry, WeekNDay, WriteDateListRequest};
+    use crate::encoding::writer::Writer;
+    use crate::types::{Date, ObjectId, ObjectType};
+    use crate::EncodeError;
+
+    const fn date(year_since_1900: u8, month: u8, day: u8) -> Date {
+        Date {
+            year_since_1900,
+            month,
+            day,
+            weekday: 0xFF,
+        }
+    }
+
+    #[test]
+    fn calendar_entries_encode_their_choice_tags() {
+        let mut buf = [0u8; 32];
+        let mut w = Writer::new(&mut buf);
+        CalendarEntry::Date(date(124, 12, 25))
+            .encode(&mut w)
+            .unwrap();
+        CalendarEntry::DateRange {
+            start: date(124, 7, 1),
+            end: date(124, 7, 14),
+        }
+        .encode(&mut w)
+        .unwrap();
+        // Last Monday of May.
+        CalendarEntry::WeekNDay(WeekNDay::new(5, 6, 1))
+            .encode(&mut w)
+            .unwrap();
+        assert_eq!(
+            w.as_written(),
+            &[
+                0x0C, 124, 12, 25, 0xFF, // [0] date
+                0x1E, 0xA4, 124, 7, 1, 0xFF, 0xA4, 124, 7, 14, 0xFF, 0x1F, // [1] range
+                0x2B, 5, 6, 1, // [2] week-n-day
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_wildcards_are_rejected_before_encoding() {
+        let any = WeekNDay::ANY;
+        assert!(CalendarEntry::WeekNDay(WeekNDay::new(any, any, 7))
+            .validate()
+            .is_ok());
+        assert_eq!(
+            CalendarEntry::WeekNDay(WeekNDay::new(15, any, any)).validate(),
+            Err(EncodeError::ValueOutOfRange)
+        );
+        assert_eq!(
+            CalendarEntry::WeekNDay(WeekNDay::new(any, 0, any)).validate(),
+            Err(EncodeError::ValueOutOfRange)
+        );
+        // Odd months, even days.
+        assert!(CalendarEntry::Date(date(0xFF, 13, 34)).validate().is_ok());
+        assert_eq!(
+            CalendarEntry::Date(date(124, 0, 1)).validate(),
+            Err(EncodeError::ValueOutOfRange)
+        );
+
+        let open = Date {
+            weekday: 0xFF,
+            ..date(0xFF, 0xFF, 0xFF)
+        };
+        assert!(CalendarEntry::DateRange {
+            start: open,
+            end: date(124, 1, 1),
+        }
+        .validate()
+        .is_ok());
+        assert_eq!(
+            CalendarEntry::DateRange {
+                start: date(124, 0xFF, 1),
+                end: date(124, 1, 1),
+            }
+            .validate(),
+            Err(EncodeError::ValueOutOfRange)
+        );
+        assert_eq!(
+            CalendarEntry::DateRange {
+                start: date(124, 2, 1),
+                end: date(124, 1, 1),
+            }
+            .validate(),
+            Err(EncodeError::ValueOutOfRange)
+        );
+
+        let entries = [CalendarEntry::WeekNDay(WeekNDay::new(1, 10, 1))];
+        let mut buf = [0u8; 32];
+        let mut w = Writer::new(&mut buf);
+        assert_eq!(
+            WriteDateListRequest {
+                calendar: ObjectId::new(ObjectType::Calendar, 1),
+                entries: &entries,
+                invoke_id: 1,
+            }
+            .encode(&mut w),
+            Err(EncodeError::ValueOutOfRange)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn date_list_round_trips_through_a_write() {
+        use super::DateListAck;
+        use crate::encoding::reader::Reader;
+
+        let calendar = ObjectId::new(ObjectType::Calendar, 4);
+        let entries = [
+            CalendarEntry::Date(date(0xFF, 1, 1)),
+            CalendarEntry::DateRange {
+                start: date(124, 12, 24),
+                end: date(125, 1, 2),
+            },
+            CalendarEntry::WeekNDay(WeekNDay::new(11, 4, 4)),
+        ];
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        WriteDateListRequest {
+            calendar,
+            entries: &entries,
+            invoke_id: 2,
+        }
+        .encode(&mut w)
+        .unwrap();
+
+        // The WriteProperty parameters after the header read back as a ReadProperty-ACK.
+        let mut r = Reader::new(&w.as_written()[4..]);
+        let ack = DateListAck::decode_after_header(&mut r).unwrap();
+        assert_eq!(ack.calendar, calendar);
+        assert_eq!(ack.entries, entries);
+        assert!(r.is_empty());
+    }
+}
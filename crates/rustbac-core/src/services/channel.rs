@@ -0,0 +1,289 @@
+use crate::encoding::{primitives::encode_ctx_unsigned, tag::Tag, writer::Writer};
+use crate::services::lighting::{ColorCommand, LightingCommand, XyColor};
+use crate::services::value_codec::encode_application_data_value;
+use crate::services::write_property::encode_write_property_header;
+use crate::types::{DataValue, ObjectId, PropertyId};
+use crate::EncodeError;
+
+pub use crate::types::DeviceObjectPropertyReference;
+
+#[cfg(feature = "alloc")]
+use crate::encoding::reader::Reader;
+#[cfg(feature = "alloc")]
+use crate::services::{
+    decode_required_ctx_object_id, decode_required_ctx_unsigned, take_optional_ctx_tag,
+};
+#[cfg(feature = "alloc")]
+use crate::DecodeError;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// BACnetChannelValue: what a Channel's Present_Value accepts and passes on to its members.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChannelValue<'a> {
+    /// A primitive application value (Null, Real, Unsigned, Enumerated, ...).
+    Value(DataValue<'a>),
+    LightingCommand(LightingCommand),
+    ColorCommand(ColorCommand),
+    XyColor(XyColor),
+}
+
+impl<'a> ChannelValue<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        match self {
+            Self::Value(value) => encode_application_data_value(w, value),
+            Self::LightingCommand(command) => {
+                Tag::Opening { tag_num: 0 }.encode(w)?;
+                command.encode(w)?;
+                Tag::Closing { tag_num: 0 }.encode(w)
+            }
+            Self::ColorCommand(command) => {
+                Tag::Opening { tag_num: 1 }.encode(w)?;
+                command.encode(w)?;
+                Tag::Closing { tag_num: 1 }.encode(w)
+            }
+            Self::XyColor(color) => {
+                Tag::Opening { tag_num: 2 }.encode(w)?;
+                color.encode(w)?;
+                Tag::Closing { tag_num: 2 }.encode(w)
+            }
+        }
+    }
+}
+
+/// BACnetWriteStatus: progress of the last write to a Channel's Present_Value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum WriteStatus {
+    Idle = 0,
+    InProgress = 1,
+    Successful = 2,
+    Failed = 3,
+}
+
+impl WriteStatus {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Idle),
+            1 => Some(Self::InProgress),
+            2 => Some(Self::Successful),
+            3 => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// WriteProperty of a Channel's Present_Value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WriteChannelRequest<'a> {
+    pub channel: ObjectId,
+    pub value: ChannelValue<'a>,
+    /// Priority used for the writes to the members; the device uses 16 when absent.
+    pub priority: Option<u8>,
+    pub invoke_id: u8,
+}
+
+impl<'a> WriteChannelRequest<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_write_property_header(w, self.invoke_id, self.channel, PropertyId::PresentValue)?;
+        Tag::Opening { tag_num: 3 }.encode(w)?;
+        self.value.encode(w)?;
+        Tag::Closing { tag_num: 3 }.encode(w)?;
+        if let Some(priority) = self.priority {
+            encode_ctx_unsigned(w, 4, priority as u32)?;
+        }
+        Ok(())
+    }
+}
+
+/// WriteProperty replacing a Channel's whole List_Of_Object_Property_References array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteChannelMembersRequest<'a> {
+    pub channel: ObjectId,
+    pub members: &'a [DeviceObjectPropertyReference],
+    pub invoke_id: u8,
+}
+
+impl<'a> WriteChannelMembersRequest<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_reference_list_write(
+            w,
+            self.invoke_id,
+            self.channel,
+            PropertyId::ListOfObjectPropertyReferences,
+            self.members,
+        )
+    }
+}
+
+/// ReadProperty-ACK carrying a List_Of_Object_Property_References.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelMembersAck {
+    pub channel: ObjectId,
+    pub members: Vec<DeviceObjectPropertyReference>,
+}
+
+#[cfg(feature = "alloc")]
+impl ChannelMembersAck {
+    pub fn decode_after_header(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let (channel, members) = decode_reference_list_ack(r)?;
+        Ok(Self { channel, members })
+    }
+}
+
+/// Encodes a WriteProperty replacing the reference list `property_id` of `object_id`.
+pub(crate) fn encode_reference_list_write(
+    w: &mut Writer<'_>,
+    invoke_id: u8,
+    object_id: ObjectId,
+    property_id: PropertyId,
+    members: &[DeviceObjectPropertyReference],
+) -> Result<(), EncodeError> {
+    encode_write_property_header(w, invoke_id, object_id, property_id)?;
+    Tag::Opening { tag_num: 3 }.encode(w)?;
+    for member in members {
+        member.encode(w)?;
+    }
+    Tag::Closing { tag_num: 3 }.encode(w)
+}
+
+/// Decodes a ReadProperty-ACK whose value is a list of references, returning the object
+/// read and the references.
+#[cfg(feature = "alloc")]
+pub(crate) fn decode_reference_list_ack(
+    r: &mut Reader<'_>,
+) -> Result<(ObjectId, Vec<DeviceObjectPropertyReference>), DecodeError> {
+    let object_id = decode_required_ctx_object_id(r, 0)?;
+    let _property_id = decode_required_ctx_unsigned(r, 1)?;
+    if let Some(len) = take_optional_ctx_tag(r, 2)? {
+        r.read_exact(len as usize)?;
+    }
+    match Tag::decode(r)? {
+        Tag::Opening { tag_num: 3 } => {}
+        _ => return Err(DecodeError::InvalidTag),
+    }
+    let mut members = Vec::new();
+    while r.peek_u8()? != CLOSING_TAG_3 {
+        members.push(DeviceObjectPropertyReference::decode(r)?);
+    }
+    match Tag::decode(r)? {
+        Tag::Closing { tag_num: 3 } => Ok((object_id, members)),
+        _ => Err(DecodeError::InvalidTag),
+    }
+}
+
+/// The encoded closing tag [3].
+#[cfg(feature = "alloc")]
+const CLOSING_TAG_3: u8 = 0x3F;
+
+#[cfg(test)]
+mod tests {
+    use super::{ChannelValue, WriteChannelRequest};
+    use crate::encoding::writer::Writer;
+    use crate::services::lighting::{ColorCommand, LightingCommand, XyColor};
+    use crate::types::{DataValue, ObjectId, ObjectType};
+
+    #[test]
+    fn write_channel_encodes_a_lighting_command() {
+        let req = WriteChannelRequest {
+            channel: ObjectId::new(ObjectType::Channel, 1),
+            value: ChannelValue::LightingCommand(LightingCommand::fade_to(50.0, 2000)),
+            priority: Some(8),
+            invoke_id: 4,
+        };
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        req.encode(&mut w).unwrap();
+        assert_eq!(
+            w.as_written(),
+            &[
+                0x00, 0x05, 4, 0x0F, // WriteProperty
+                0x0C, 0x0D, 0x40, 0x00, 0x01, // [0] channel,1
+                0x19, 0x55, // [1] present-value
+                0x3E, 0x0E, // [3] { [0] {
+                0x09, 0x01, // operation fade-to
+                0x1C, 0x42, 0x48, 0x00, 0x00, // target-level 50.0
+                0x4A, 0x07, 0xD0, // fade-time 2000
+                0x0F, 0x3F, // } }
+                0x49, 0x08, // [4] priority 8
+            ]
+        );
+
+        let req = WriteChannelRequest {
+            value: ChannelValue::Value(DataValue::Real(1.5)),
+            priority: None,
+            ..req
+        };
+        let mut w = Writer::new(&mut buf);
+        req.encode(&mut w).unwrap();
+        assert_eq!(
+            &w.as_written()[11..],
+            &[0x3E, 0x44, 0x3F, 0xC0, 0x00, 0x00, 0x3F]
+        );
+    }
+
+    #[test]
+    fn channel_values_wrap_color_commands_and_colors() {
+        let mut buf = [0u8; 32];
+        let mut w = Writer::new(&mut buf);
+        ChannelValue::ColorCommand(ColorCommand::fade_to_cct(3000, 500))
+            .encode(&mut w)
+            .unwrap();
+        assert_eq!(
+            w.as_written(),
+            &[0x1E, 0x09, 0x02, 0x2A, 0x0B, 0xB8, 0x3A, 0x01, 0xF4, 0x1F]
+        );
+
+        let mut w = Writer::new(&mut buf);
+        ChannelValue::XyColor(XyColor::new(0.5, 0.25))
+            .encode(&mut w)
+            .unwrap();
+        assert_eq!(
+            w.as_written(),
+            &[0x2E, 0x44, 0x3F, 0x00, 0x00, 0x00, 0x44, 0x3E, 0x80, 0x00, 0x00, 0x2F]
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn channel_members_round_trip() {
+        use super::{ChannelMembersAck, DeviceObjectPropertyReference, WriteChannelMembersRequest};
+        use crate::encoding::reader::Reader;
+        use crate::types::PropertyId;
+
+        let members = [
+            DeviceObjectPropertyReference::local(
+                ObjectId::new(ObjectType::LightingOutput, 1),
+                PropertyId::LightingCommand,
+            ),
+            DeviceObjectPropertyReference {
+                object_id: ObjectId::new(ObjectType::BinaryOutput, 2),
+                property_id: PropertyId::PresentValue,
+                array_index: Some(3),
+                device_id: Some(ObjectId::new(ObjectType::Device, 77)),
+            },
+        ];
+        let channel = ObjectId::new(ObjectType::Channel, 1);
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        WriteChannelMembersRequest {
+            channel,
+            members: &members,
+            invoke_id: 1,
+        }
+        .encode(&mut w)
+        .unwrap();
+
+        // The WriteProperty parameters after the header read back as a ReadProperty-ACK.
+        let mut r = Reader::new(&w.as_written()[4..]);
+        let ack = ChannelMembersAck::decode_after_header(&mut r).unwrap();
+        assert_eq!(ack.channel, channel);
+        assert_eq!(ack.members, members);
+        assert!(r.is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+use crate::encoding::{reader::Reader, tag::Tag, writer::Writer};
+use crate::services::{decode_ack_object, expect_tag};
+use crate::types::{ObjectId, ObjectPropertyReference};
+use crate::{DecodeError, EncodeError};
+
+/// BACnetAction: which way a Loop drives its output as the controlled variable rises
+/// above the setpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum LoopAction {
+    /// Output increases with the error (e.g. cooling).
+    Direct = 0,
+    /// Output decreases with the error (e.g. heating).
+    Reverse = 1,
+}
+
+impl LoopAction {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Direct),
+            1 => Some(Self::Reverse),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetSetpointReference: the property a Loop takes its setpoint from, or `None` when
+/// the setpoint is the Loop's own Setpoint property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SetpointReference(pub Option<ObjectPropertyReference>);
+
+impl SetpointReference {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        if let Some(reference) = &self.0 {
+            Tag::Opening { tag_num: 0 }.encode(w)?;
+            reference.encode(w)?;
+            Tag::Closing { tag_num: 0 }.encode(w)?;
+        }
+        Ok(())
+    }
+
+    /// Decodes the sequence, stopping before the tag that follows it.
+    pub fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let mut peek = *r;
+        if r.is_empty() || Tag::decode(&mut peek)? != (Tag::Opening { tag_num: 0 }) {
+            return Ok(Self(None));
+        }
+        *r = peek;
+        let reference = ObjectPropertyReference::decode(r)?;
+        expect_tag(r, Tag::Closing { tag_num: 0 })?;
+        Ok(Self(Some(reference)))
+    }
+}
+
+/// ReadProperty-ACK carrying a Loop's Setpoint_Reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetpointReferenceAck {
+    pub control_loop: ObjectId,
+    pub reference: SetpointReference,
+}
+
+impl SetpointReferenceAck {
+    pub fn decode_after_header(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let control_loop = decode_ack_object(r)?;
+        expect_tag(r, Tag::Opening { tag_num: 3 })?;
+        let reference = SetpointReference::decode(r)?;
+        expect_tag(r, Tag::Closing { tag_num: 3 })?;
+        Ok(Self {
+            control_loop,
+            reference,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LoopAction, SetpointReference, SetpointReferenceAck};
+    use crate::encoding::{reader::Reader, tag::Tag, writer::Writer};
+    use crate::types::{ObjectId, ObjectPropertyReference, ObjectType, PropertyId};
+
+    #[test]
+    fn setpoint_reference_acks_decode_with_and_without_a_reference() {
+        let control_loop = ObjectId::new(ObjectType::Loop, 1);
+        let setpoint = ObjectPropertyReference::new(
+            ObjectId::new(ObjectType::AnalogValue, 7),
+            PropertyId::PresentValue,
+        );
+        for reference in [SetpointReference(Some(setpoint)), SetpointReference(None)] {
+            let mut buf = [0u8; 32];
+            let mut w = Writer::new(&mut buf);
+            w.write_all(&[0x0C, 0x03, 0x00, 0x00, 0x01, 0x19, 0x6D])
+                .unwrap();
+            Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+            reference.encode(&mut w).unwrap();
+            Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+
+            let mut r = Reader::new(w.as_written());
+            assert_eq!(
+                SetpointReferenceAck::decode_after_header(&mut r).unwrap(),
+                SetpointReferenceAck {
+                    control_loop,
+                    reference,
+                }
+            );
+            assert!(r.is_empty());
+        }
+    }
+
+    #[test]
+    fn loop_action_round_trips() {
+        for action in [LoopAction::Direct, LoopAction::Reverse] {
+            assert_eq!(LoopAction::from_u32(action.to_u32()), Some(action));
+        }
+        assert_eq!(LoopAction::from_u32(2), None);
+    }
+}
@@ -0,0 +1,374 @@
+use crate::encoding::{
+    primitives::{encode_ctx_real, encode_ctx_unsigned},
+    tag::Tag,
+    writer::Writer,
+};
+use crate::services::read_range::Recipient;
+use crate::types::{ObjectPropertyReference, PropertyReference};
+use crate::EncodeError;
+
+#[cfg(feature = "alloc")]
+use crate::encoding::{primitives::encode_ctx_object_id, reader::Reader};
+#[cfg(feature = "alloc")]
+use crate::services::{
+    decode_ack_object, decode_real, decode_required_ctx_object_id, decode_required_ctx_unsigned,
+    expect_tag, take_optional_ctx_tag,
+};
+#[cfg(feature = "alloc")]
+use crate::types::ObjectId;
+#[cfg(feature = "alloc")]
+use crate::DecodeError;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// BACnetCOVSubscription: a SubscribeCOV or SubscribeCOVProperty subscription, as listed
+/// in a Device's Active_COV_Subscriptions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CovSubscription<'a> {
+    pub recipient: Recipient<'a>,
+    pub process_id: u32,
+    /// The monitored property; Present_Value for a SubscribeCOV subscription.
+    pub monitored: ObjectPropertyReference,
+    pub issue_confirmed_notifications: bool,
+    /// Seconds until the subscription lapses; 0 for one without a lifetime.
+    pub time_remaining: u32,
+    pub cov_increment: Option<f32>,
+}
+
+impl<'a> CovSubscription<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_recipient_process(w, 0, &self.recipient, self.process_id)?;
+        Tag::Opening { tag_num: 1 }.encode(w)?;
+        self.monitored.encode(w)?;
+        Tag::Closing { tag_num: 1 }.encode(w)?;
+        encode_ctx_boolean(w, 2, self.issue_confirmed_notifications)?;
+        encode_ctx_unsigned(w, 3, self.time_remaining)?;
+        if let Some(increment) = self.cov_increment {
+            encode_ctx_real(w, 4, increment)?;
+        }
+        Ok(())
+    }
+
+    /// Decodes one subscription, stopping before the tag that follows it.
+    #[cfg(feature = "alloc")]
+    pub fn decode(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let (recipient, process_id) = decode_recipient_process(r, 0)?;
+        expect_tag(r, Tag::Opening { tag_num: 1 })?;
+        let monitored = ObjectPropertyReference::decode(r)?;
+        expect_tag(r, Tag::Closing { tag_num: 1 })?;
+        Ok(Self {
+            recipient,
+            process_id,
+            monitored,
+            issue_confirmed_notifications: decode_ctx_boolean(r, 2)?,
+            time_remaining: decode_required_ctx_unsigned(r, 3)?,
+            cov_increment: decode_optional_ctx_real(r, 4)?,
+        })
+    }
+}
+
+/// One monitored property of a [`CovSubscriptionSpecification`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CovReference {
+    pub property: PropertyReference,
+    pub cov_increment: Option<f32>,
+    /// Whether notifications carry the time the value changed.
+    pub timestamped: bool,
+}
+
+/// The properties of one object a [`CovMultipleSubscription`] monitors.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CovSubscriptionSpecification {
+    pub object_id: ObjectId,
+    pub references: Vec<CovReference>,
+}
+
+/// BACnetCOVMultipleSubscription: a SubscribeCOVPropertyMultiple subscription, as listed
+/// in a Device's Active_COV_Multiple_Subscriptions.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CovMultipleSubscription<'a> {
+    pub recipient: Recipient<'a>,
+    pub process_id: u32,
+    pub issue_confirmed_notifications: bool,
+    /// Seconds until the subscription lapses; 0 for one without a lifetime.
+    pub time_remaining: u32,
+    /// Seconds the device may hold notifications to combine them.
+    pub max_notification_delay: u32,
+    pub specifications: Vec<CovSubscriptionSpecification>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> CovMultipleSubscription<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_recipient_process(w, 0, &self.recipient, self.process_id)?;
+        encode_ctx_boolean(w, 1, self.issue_confirmed_notifications)?;
+        encode_ctx_unsigned(w, 2, self.time_remaining)?;
+        encode_ctx_unsigned(w, 3, self.max_notification_delay)?;
+        Tag::Opening { tag_num: 4 }.encode(w)?;
+        for specification in &self.specifications {
+            encode_ctx_object_id(w, 0, specification.object_id.raw())?;
+            Tag::Opening { tag_num: 1 }.encode(w)?;
+            for reference in &specification.references {
+                Tag::Opening { tag_num: 0 }.encode(w)?;
+                reference.property.encode(w)?;
+                Tag::Closing { tag_num: 0 }.encode(w)?;
+                if let Some(increment) = reference.cov_increment {
+                    encode_ctx_real(w, 1, increment)?;
+                }
+                encode_ctx_boolean(w, 2, reference.timestamped)?;
+            }
+            Tag::Closing { tag_num: 1 }.encode(w)?;
+        }
+        Tag::Closing { tag_num: 4 }.encode(w)
+    }
+
+    /// Decodes one subscription, stopping before the tag that follows it.
+    pub fn decode(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let (recipient, process_id) = decode_recipient_process(r, 0)?;
+        let issue_confirmed_notifications = decode_ctx_boolean(r, 1)?;
+        let time_remaining = decode_required_ctx_unsigned(r, 2)?;
+        let max_notification_delay = decode_required_ctx_unsigned(r, 3)?;
+        expect_tag(r, Tag::Opening { tag_num: 4 })?;
+        let mut specifications = Vec::new();
+        while !take_closing(r, 4)? {
+            let object_id = decode_required_ctx_object_id(r, 0)?;
+            expect_tag(r, Tag::Opening { tag_num: 1 })?;
+            let mut references = Vec::new();
+            while !take_closing(r, 1)? {
+                expect_tag(r, Tag::Opening { tag_num: 0 })?;
+                let property = PropertyReference::decode(r)?;
+                expect_tag(r, Tag::Closing { tag_num: 0 })?;
+                references.push(CovReference {
+                    property,
+                    cov_increment: decode_optional_ctx_real(r, 1)?,
+                    timestamped: decode_ctx_boolean(r, 2)?,
+                });
+            }
+            specifications.push(CovSubscriptionSpecification {
+                object_id,
+                references,
+            });
+        }
+        Ok(Self {
+            recipient,
+            process_id,
+            issue_confirmed_notifications,
+            time_remaining,
+            max_notification_delay,
+            specifications,
+        })
+    }
+}
+
+/// ReadProperty-ACK carrying a Device's Active_COV_Subscriptions.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveCovSubscriptionsAck<'a> {
+    pub device: ObjectId,
+    pub subscriptions: Vec<CovSubscription<'a>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> ActiveCovSubscriptionsAck<'a> {
+    pub fn decode_after_header(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let device = decode_ack_object(r)?;
+        expect_tag(r, Tag::Opening { tag_num: 3 })?;
+        let mut subscriptions = Vec::new();
+        while !take_closing(r, 3)? {
+            subscriptions.push(CovSubscription::decode(r)?);
+        }
+        Ok(Self {
+            device,
+            subscriptions,
+        })
+    }
+}
+
+/// ReadProperty-ACK carrying a Device's Active_COV_Multiple_Subscriptions.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveCovMultipleSubscriptionsAck<'a> {
+    pub device: ObjectId,
+    pub subscriptions: Vec<CovMultipleSubscription<'a>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> ActiveCovMultipleSubscriptionsAck<'a> {
+    pub fn decode_after_header(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let device = decode_ack_object(r)?;
+        expect_tag(r, Tag::Opening { tag_num: 3 })?;
+        let mut subscriptions = Vec::new();
+        while !take_closing(r, 3)? {
+            subscriptions.push(CovMultipleSubscription::decode(r)?);
+        }
+        Ok(Self {
+            device,
+            subscriptions,
+        })
+    }
+}
+
+/// BACnetRecipientProcess enclosed in context tag `tag_num`.
+fn encode_recipient_process(
+    w: &mut Writer<'_>,
+    tag_num: u8,
+    recipient: &Recipient<'_>,
+    process_id: u32,
+) -> Result<(), EncodeError> {
+    Tag::Opening { tag_num }.encode(w)?;
+    recipient.encode_enclosed(w, 0)?;
+    encode_ctx_unsigned(w, 1, process_id)?;
+    Tag::Closing { tag_num }.encode(w)
+}
+
+#[cfg(feature = "alloc")]
+fn decode_recipient_process<'a>(
+    r: &mut Reader<'a>,
+    tag_num: u8,
+) -> Result<(Recipient<'a>, u32), DecodeError> {
+    expect_tag(r, Tag::Opening { tag_num })?;
+    let recipient = Recipient::decode_enclosed(r, 0)?;
+    let process_id = decode_required_ctx_unsigned(r, 1)?;
+    expect_tag(r, Tag::Closing { tag_num })?;
+    Ok((recipient, process_id))
+}
+
+fn encode_ctx_boolean(w: &mut Writer<'_>, tag_num: u8, value: bool) -> Result<(), EncodeError> {
+    Tag::Context { tag_num, len: 1 }.encode(w)?;
+    w.write_u8(u8::from(value))
+}
+
+#[cfg(feature = "alloc")]
+fn decode_ctx_boolean(r: &mut Reader<'_>, expected: u8) -> Result<bool, DecodeError> {
+    match take_optional_ctx_tag(r, expected)? {
+        Some(1) => Ok(r.read_u8()? != 0),
+        Some(_) => Err(DecodeError::InvalidLength),
+        None => Err(DecodeError::InvalidTag),
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn decode_optional_ctx_real(r: &mut Reader<'_>, expected: u8) -> Result<Option<f32>, DecodeError> {
+    take_optional_ctx_tag(r, expected)?
+        .map(|len| decode_real(r, len))
+        .transpose()
+}
+
+/// Consumes closing tag `tag_num` and returns `true` if it is next.
+#[cfg(feature = "alloc")]
+fn take_closing(r: &mut Reader<'_>, tag_num: u8) -> Result<bool, DecodeError> {
+    let mut peek = *r;
+    if Tag::decode(&mut peek)? == (Tag::Closing { tag_num }) {
+        *r = peek;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::{
+        ActiveCovMultipleSubscriptionsAck, ActiveCovSubscriptionsAck, CovMultipleSubscription,
+        CovReference, CovSubscription, CovSubscriptionSpecification,
+    };
+    use crate::encoding::{
+        primitives::{encode_ctx_object_id, encode_ctx_unsigned},
+        reader::Reader,
+        tag::Tag,
+        writer::Writer,
+    };
+    use crate::services::read_range::Recipient;
+    use crate::types::{
+        ObjectId, ObjectPropertyReference, ObjectType, PropertyId, PropertyReference,
+    };
+    use alloc::vec;
+
+    fn ack_header(w: &mut Writer<'_>, property_id: PropertyId) {
+        encode_ctx_object_id(w, 0, ObjectId::new(ObjectType::Device, 10).raw()).unwrap();
+        encode_ctx_unsigned(w, 1, property_id.to_u32()).unwrap();
+        Tag::Opening { tag_num: 3 }.encode(w).unwrap();
+    }
+
+    #[test]
+    fn active_cov_subscriptions_decode_device_and_address_recipients() {
+        let subscriptions = [
+            CovSubscription {
+                recipient: Recipient::Device(ObjectId::new(ObjectType::Device, 99)),
+                process_id: 7,
+                monitored: ObjectPropertyReference::new(
+                    ObjectId::new(ObjectType::AnalogInput, 1),
+                    PropertyId::PresentValue,
+                ),
+                issue_confirmed_notifications: false,
+                time_remaining: 240,
+                cov_increment: Some(0.5),
+            },
+            CovSubscription {
+                recipient: Recipient::Address {
+                    network: 0,
+                    mac: &[192, 168, 1, 20, 0xBA, 0xC0],
+                },
+                process_id: 1,
+                monitored: ObjectPropertyReference::new(
+                    ObjectId::new(ObjectType::BinaryValue, 3),
+                    PropertyId::StatusFlags,
+                ),
+                issue_confirmed_notifications: true,
+                time_remaining: 0,
+                cov_increment: None,
+            },
+        ];
+        let mut buf = [0u8; 128];
+        let mut w = Writer::new(&mut buf);
+        ack_header(&mut w, PropertyId::ActiveCovSubscriptions);
+        for subscription in &subscriptions {
+            subscription.encode(&mut w).unwrap();
+        }
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+
+        let mut r = Reader::new(w.as_written());
+        let ack = ActiveCovSubscriptionsAck::decode_after_header(&mut r).unwrap();
+        assert_eq!(ack.device, ObjectId::new(ObjectType::Device, 10));
+        assert_eq!(ack.subscriptions, subscriptions);
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn active_cov_multiple_subscriptions_decode_nested_references() {
+        let subscription = CovMultipleSubscription {
+            recipient: Recipient::Device(ObjectId::new(ObjectType::Device, 99)),
+            process_id: 12,
+            issue_confirmed_notifications: true,
+            time_remaining: 3600,
+            max_notification_delay: 5,
+            specifications: vec![CovSubscriptionSpecification {
+                object_id: ObjectId::new(ObjectType::AnalogValue, 4),
+                references: vec![
+                    CovReference {
+                        property: PropertyReference::new(PropertyId::PresentValue),
+                        cov_increment: Some(0.1),
+                        timestamped: true,
+                    },
+                    CovReference {
+                        property: PropertyReference::new(PropertyId::StatusFlags),
+                        cov_increment: None,
+                        timestamped: false,
+                    },
+                ],
+            }],
+        };
+        let mut buf = [0u8; 128];
+        let mut w = Writer::new(&mut buf);
+        ack_header(&mut w, PropertyId::ActiveCovMultipleSubscriptions);
+        subscription.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+
+        let mut r = Reader::new(w.as_written());
+        let ack = ActiveCovMultipleSubscriptionsAck::decode_after_header(&mut r).unwrap();
+        assert_eq!(ack.subscriptions, vec![subscription]);
+        assert!(r.is_empty());
+    }
+}
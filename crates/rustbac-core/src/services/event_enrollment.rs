@@ -0,0 +1,581 @@
+use crate::encoding::{
+    primitives::{decode_unsigned, encode_ctx_real, encode_ctx_unsigned},
+    reader::Reader,
+    tag::Tag,
+    writer::Writer,
+};
+use crate::services::write_property::encode_write_property_header;
+use crate::services::{
+    decode_real, decode_required_ctx_real, decode_required_ctx_unsigned, expect_tag,
+};
+use crate::types::{DeviceObjectPropertyReference, ObjectId, PropertyId};
+use crate::{DecodeError, EncodeError};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// One choice of BACnetPropertyStates, such as a binary or multistate value that counts
+/// as off-normal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropertyState {
+    /// Context tag of the choice (1 = binary-value, 11 = unsigned-value, ...).
+    pub choice: u8,
+    pub value: u32,
+}
+
+impl PropertyState {
+    pub const fn new(choice: u8, value: u32) -> Self {
+        Self { choice, value }
+    }
+
+    /// A BACnetBinaryPV state (0 = inactive, 1 = active).
+    pub const fn binary(value: u32) -> Self {
+        Self::new(1, value)
+    }
+
+    /// An unsigned state, such as a multistate object's Present_Value.
+    pub const fn unsigned(value: u32) -> Self {
+        Self::new(11, value)
+    }
+
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_ctx_unsigned(w, self.choice, self.value)
+    }
+
+    pub fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        match Tag::decode(r)? {
+            Tag::Context { tag_num, len } => {
+                Ok(Self::new(tag_num, decode_unsigned(r, len as usize)?))
+            }
+            _ => Err(DecodeError::InvalidTag),
+        }
+    }
+}
+
+/// BACnetEventParameter: the algorithm an Event Enrollment applies to its monitored
+/// property, with that algorithm's settings. Time delays are in seconds.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventParameter {
+    /// Off-normal while the property holds one of `values`.
+    #[cfg(feature = "alloc")]
+    ChangeOfState {
+        time_delay: u32,
+        values: Vec<PropertyState>,
+    },
+    /// Notify whenever the property changes by at least `increment`.
+    ChangeOfValue { time_delay: u32, increment: f32 },
+    /// Fault while the property disagrees with the `feedback` property.
+    CommandFailure {
+        time_delay: u32,
+        feedback: DeviceObjectPropertyReference,
+    },
+    /// High or low limit while the property strays from the `setpoint` property by more
+    /// than the differential limits.
+    FloatingLimit {
+        time_delay: u32,
+        setpoint: DeviceObjectPropertyReference,
+        low_diff_limit: f32,
+        high_diff_limit: f32,
+        deadband: f32,
+    },
+    /// High or low limit while the property is outside `low_limit..=high_limit`.
+    OutOfRange {
+        time_delay: u32,
+        low_limit: f32,
+        high_limit: f32,
+        deadband: f32,
+    },
+    /// Notify when a log buffer has gained `notification_threshold` records since the
+    /// last notification.
+    BufferReady {
+        notification_threshold: u32,
+        previous_notification_count: u32,
+    },
+    /// High or low limit while an unsigned property is outside `low_limit..=high_limit`.
+    UnsignedRange {
+        time_delay: u32,
+        low_limit: u32,
+        high_limit: u32,
+    },
+}
+
+impl EventParameter {
+    /// The BACnetEventType selected by the parameters, which an Event Enrollment reports
+    /// as its Event_Type.
+    pub const fn event_type(&self) -> u32 {
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::ChangeOfState { .. } => 1,
+            Self::ChangeOfValue { .. } => 2,
+            Self::CommandFailure { .. } => 3,
+            Self::FloatingLimit { .. } => 4,
+            Self::OutOfRange { .. } => 5,
+            Self::BufferReady { .. } => 10,
+            Self::UnsignedRange { .. } => 11,
+        }
+    }
+
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        let tag_num = self.event_type() as u8;
+        Tag::Opening { tag_num }.encode(w)?;
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::ChangeOfState { time_delay, values } => {
+                encode_ctx_unsigned(w, 0, *time_delay)?;
+                Tag::Opening { tag_num: 1 }.encode(w)?;
+                for value in values {
+                    value.encode(w)?;
+                }
+                Tag::Closing { tag_num: 1 }.encode(w)?;
+            }
+            Self::ChangeOfValue {
+                time_delay,
+                increment,
+            } => {
+                encode_ctx_unsigned(w, 0, *time_delay)?;
+                Tag::Opening { tag_num: 1 }.encode(w)?;
+                encode_ctx_real(w, 1, *increment)?;
+                Tag::Closing { tag_num: 1 }.encode(w)?;
+            }
+            Self::CommandFailure {
+                time_delay,
+                feedback,
+            } => {
+                encode_ctx_unsigned(w, 0, *time_delay)?;
+                encode_reference(w, 1, feedback)?;
+            }
+            Self::FloatingLimit {
+                time_delay,
+                setpoint,
+                low_diff_limit,
+                high_diff_limit,
+                deadband,
+            } => {
+                encode_ctx_unsigned(w, 0, *time_delay)?;
+                encode_reference(w, 1, setpoint)?;
+                encode_ctx_real(w, 2, *low_diff_limit)?;
+                encode_ctx_real(w, 3, *high_diff_limit)?;
+                encode_ctx_real(w, 4, *deadband)?;
+            }
+            Self::OutOfRange {
+                time_delay,
+                low_limit,
+                high_limit,
+                deadband,
+            } => {
+                encode_ctx_unsigned(w, 0, *time_delay)?;
+                encode_ctx_real(w, 1, *low_limit)?;
+                encode_ctx_real(w, 2, *high_limit)?;
+                encode_ctx_real(w, 3, *deadband)?;
+            }
+            Self::BufferReady {
+                notification_threshold,
+                previous_notification_count,
+            } => {
+                encode_ctx_unsigned(w, 0, *notification_threshold)?;
+                encode_ctx_unsigned(w, 1, *previous_notification_count)?;
+            }
+            Self::UnsignedRange {
+                time_delay,
+                low_limit,
+                high_limit,
+            } => {
+                encode_ctx_unsigned(w, 0, *time_delay)?;
+                encode_ctx_unsigned(w, 1, *low_limit)?;
+                encode_ctx_unsigned(w, 2, *high_limit)?;
+            }
+        }
+        Tag::Closing { tag_num }.encode(w)
+    }
+
+    /// Decodes the parameters, including their enclosing choice tag. Event types without
+    /// a variant here fail with [`DecodeError::Unsupported`].
+    pub fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let tag_num = match Tag::decode(r)? {
+            Tag::Opening { tag_num } => tag_num,
+            _ => return Err(DecodeError::InvalidTag),
+        };
+        let parameter = match tag_num {
+            #[cfg(feature = "alloc")]
+            1 => {
+                let time_delay = decode_required_ctx_unsigned(r, 0)?;
+                expect_tag(r, Tag::Opening { tag_num: 1 })?;
+                let mut values = Vec::new();
+                while !take_closing_tag(r, 1)? {
+                    values.push(PropertyState::decode(r)?);
+                }
+                Self::ChangeOfState { time_delay, values }
+            }
+            2 => {
+                let time_delay = decode_required_ctx_unsigned(r, 0)?;
+                expect_tag(r, Tag::Opening { tag_num: 1 })?;
+                // The bitmask criterion, for bit string properties, is not supported.
+                let increment = match Tag::decode(r)? {
+                    Tag::Context { tag_num: 1, len } => decode_real(r, len)?,
+                    _ => return Err(DecodeError::Unsupported),
+                };
+                expect_tag(r, Tag::Closing { tag_num: 1 })?;
+                Self::ChangeOfValue {
+                    time_delay,
+                    increment,
+                }
+            }
+            3 => Self::CommandFailure {
+                time_delay: decode_required_ctx_unsigned(r, 0)?,
+                feedback: decode_reference(r, 1)?,
+            },
+            4 => Self::FloatingLimit {
+                time_delay: decode_required_ctx_unsigned(r, 0)?,
+                setpoint: decode_reference(r, 1)?,
+                low_diff_limit: decode_required_ctx_real(r, 2)?,
+                high_diff_limit: decode_required_ctx_real(r, 3)?,
+                deadband: decode_required_ctx_real(r, 4)?,
+            },
+            5 => Self::OutOfRange {
+                time_delay: decode_required_ctx_unsigned(r, 0)?,
+                low_limit: decode_required_ctx_real(r, 1)?,
+                high_limit: decode_required_ctx_real(r, 2)?,
+                deadband: decode_required_ctx_real(r, 3)?,
+            },
+            10 => Self::BufferReady {
+                notification_threshold: decode_required_ctx_unsigned(r, 0)?,
+                previous_notification_count: decode_required_ctx_unsigned(r, 1)?,
+            },
+            11 => Self::UnsignedRange {
+                time_delay: decode_required_ctx_unsigned(r, 0)?,
+                low_limit: decode_required_ctx_unsigned(r, 1)?,
+                high_limit: decode_required_ctx_unsigned(r, 2)?,
+            },
+            _ => return Err(DecodeError::Unsupported),
+        };
+        expect_tag(r, Tag::Closing { tag_num })?;
+        Ok(parameter)
+    }
+}
+
+/// The to-offnormal, to-fault and to-normal transitions, as in an Event_Enable or
+/// Acked_Transitions bit string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventTransitions {
+    pub to_offnormal: bool,
+    pub to_fault: bool,
+    pub to_normal: bool,
+}
+
+impl EventTransitions {
+    pub const ALL: Self = Self {
+        to_offnormal: true,
+        to_fault: true,
+        to_normal: true,
+    };
+
+    pub const NONE: Self = Self {
+        to_offnormal: false,
+        to_fault: false,
+        to_normal: false,
+    };
+
+    /// The unused bit count of the encoded three-bit string.
+    pub const UNUSED_BITS: u8 = 5;
+
+    /// The single byte of the encoded bit string.
+    pub const fn to_byte(self) -> u8 {
+        (self.to_offnormal as u8) << 7 | (self.to_fault as u8) << 6 | (self.to_normal as u8) << 5
+    }
+
+    pub const fn from_byte(byte: u8) -> Self {
+        Self {
+            to_offnormal: byte & 0x80 != 0,
+            to_fault: byte & 0x40 != 0,
+            to_normal: byte & 0x20 != 0,
+        }
+    }
+}
+
+/// WriteProperty of an Event Enrollment's Event_Parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WriteEventParametersRequest<'a> {
+    pub event_enrollment: ObjectId,
+    pub parameters: &'a EventParameter,
+    pub invoke_id: u8,
+}
+
+impl<'a> WriteEventParametersRequest<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_write_property_header(
+            w,
+            self.invoke_id,
+            self.event_enrollment,
+            PropertyId::EventParameters,
+        )?;
+        Tag::Opening { tag_num: 3 }.encode(w)?;
+        self.parameters.encode(w)?;
+        Tag::Closing { tag_num: 3 }.encode(w)
+    }
+}
+
+/// WriteProperty of an Event Enrollment's Object_Property_Reference, the property it
+/// monitors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteEventReferenceRequest {
+    pub event_enrollment: ObjectId,
+    pub reference: DeviceObjectPropertyReference,
+    pub invoke_id: u8,
+}
+
+impl WriteEventReferenceRequest {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_write_property_header(
+            w,
+            self.invoke_id,
+            self.event_enrollment,
+            PropertyId::ObjectPropertyReference,
+        )?;
+        encode_reference(w, 3, &self.reference)
+    }
+}
+
+/// ReadProperty-ACK carrying an Event Enrollment's Event_Parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventParametersAck {
+    pub event_enrollment: ObjectId,
+    pub parameters: EventParameter,
+}
+
+impl EventParametersAck {
+    pub fn decode_after_header(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let event_enrollment = decode_ack_object(r)?;
+        expect_tag(r, Tag::Opening { tag_num: 3 })?;
+        let parameters = EventParameter::decode(r)?;
+        expect_tag(r, Tag::Closing { tag_num: 3 })?;
+        Ok(Self {
+            event_enrollment,
+            parameters,
+        })
+    }
+}
+
+/// ReadProperty-ACK carrying an Event Enrollment's Object_Property_Reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventReferenceAck {
+    pub event_enrollment: ObjectId,
+    pub reference: DeviceObjectPropertyReference,
+}
+
+impl EventReferenceAck {
+    pub fn decode_after_header(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let event_enrollment = decode_ack_object(r)?;
+        let reference = decode_reference(r, 3)?;
+        Ok(Self {
+            event_enrollment,
+            reference,
+        })
+    }
+}
+
+/// Decodes the object and property identifiers opening a ReadProperty-ACK, returning the
+/// object.
+fn decode_ack_object(r: &mut Reader<'_>) -> Result<ObjectId, DecodeError> {
+    let object_id = ObjectId::from_raw(decode_required_ctx_unsigned(r, 0)?);
+    let _property_id = decode_required_ctx_unsigned(r, 1)?;
+    Ok(object_id)
+}
+
+fn encode_reference(
+    w: &mut Writer<'_>,
+    tag_num: u8,
+    reference: &DeviceObjectPropertyReference,
+) -> Result<(), EncodeError> {
+    Tag::Opening { tag_num }.encode(w)?;
+    reference.encode(w)?;
+    Tag::Closing { tag_num }.encode(w)
+}
+
+fn decode_reference(
+    r: &mut Reader<'_>,
+    tag_num: u8,
+) -> Result<DeviceObjectPropertyReference, DecodeError> {
+    expect_tag(r, Tag::Opening { tag_num })?;
+    let reference = DeviceObjectPropertyReference::decode(r)?;
+    expect_tag(r, Tag::Closing { tag_num })?;
+    Ok(reference)
+}
+
+/// Consumes closing tag `tag_num` if it is next, leaving the reader untouched otherwise.
+#[cfg(feature = "alloc")]
+fn take_closing_tag(r: &mut Reader<'_>, tag_num: u8) -> Result<bool, DecodeError> {
+    let mut peek = *r;
+    if Tag::decode(&mut peek)? == (Tag::Closing { tag_num }) {
+        *r = peek;
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        EventParameter, EventParametersAck, EventReferenceAck, EventTransitions,
+        WriteEventParametersRequest, WriteEventReferenceRequest,
+    };
+    use crate::encoding::{reader::Reader, writer::Writer};
+    use crate::types::{DeviceObjectPropertyReference, ObjectId, ObjectType, PropertyId};
+
+    #[test]
+    fn out_of_range_parameters_encode_inside_their_choice_tag() {
+        let parameters = EventParameter::OutOfRange {
+            time_delay: 30,
+            low_limit: 10.0,
+            high_limit: 30.0,
+            deadband: 1.0,
+        };
+        let mut buf = [0u8; 32];
+        let mut w = Writer::new(&mut buf);
+        parameters.encode(&mut w).unwrap();
+        assert_eq!(
+            w.as_written(),
+            &[
+                0x5E, // [5] {
+                0x09, 0x1E, // time-delay 30
+                0x1C, 0x41, 0x20, 0x00, 0x00, // low-limit 10.0
+                0x2C, 0x41, 0xF0, 0x00, 0x00, // high-limit 30.0
+                0x3C, 0x3F, 0x80, 0x00, 0x00, // deadband 1.0
+                0x5F, // }
+            ]
+        );
+        assert_eq!(parameters.event_type(), 5);
+    }
+
+    #[test]
+    fn event_parameters_round_trip() {
+        let setpoint = DeviceObjectPropertyReference::local(
+            ObjectId::new(ObjectType::AnalogValue, 1),
+            PropertyId::PresentValue,
+        );
+        let all = [
+            EventParameter::ChangeOfValue {
+                time_delay: 0,
+                increment: 0.5,
+            },
+            EventParameter::CommandFailure {
+                time_delay: 10,
+                feedback: setpoint,
+            },
+            EventParameter::FloatingLimit {
+                time_delay: 60,
+                setpoint,
+                low_diff_limit: 2.0,
+                high_diff_limit: 3.0,
+                deadband: 0.5,
+            },
+            EventParameter::BufferReady {
+                notification_threshold: 100,
+                previous_notification_count: 4200,
+            },
+            EventParameter::UnsignedRange {
+                time_delay: 5,
+                low_limit: 1,
+                high_limit: 300,
+            },
+        ];
+        for parameters in all {
+            let mut buf = [0u8; 64];
+            let mut w = Writer::new(&mut buf);
+            parameters.encode(&mut w).unwrap();
+            let mut r = Reader::new(w.as_written());
+            assert_eq!(EventParameter::decode(&mut r).unwrap(), parameters);
+            assert!(r.is_empty());
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn change_of_state_parameters_round_trip() {
+        use super::PropertyState;
+
+        let parameters = EventParameter::ChangeOfState {
+            time_delay: 15,
+            values: alloc::vec![PropertyState::unsigned(3), PropertyState::unsigned(4)],
+        };
+        let mut buf = [0u8; 32];
+        let mut w = Writer::new(&mut buf);
+        parameters.encode(&mut w).unwrap();
+        assert_eq!(
+            w.as_written(),
+            &[0x1E, 0x09, 0x0F, 0x1E, 0xB9, 0x03, 0xB9, 0x04, 0x1F, 0x1F]
+        );
+        let mut r = Reader::new(w.as_written());
+        assert_eq!(EventParameter::decode(&mut r).unwrap(), parameters);
+    }
+
+    #[test]
+    fn unsupported_event_types_are_reported() {
+        // [6] change-of-status-flags { [0] time-delay 0 }
+        let mut r = Reader::new(&[0x6E, 0x09, 0x00, 0x6F]);
+        assert_eq!(
+            EventParameter::decode(&mut r),
+            Err(crate::DecodeError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn event_enrollment_writes_read_back_as_acks() {
+        let event_enrollment = ObjectId::new(ObjectType::EventEnrollment, 7);
+        let parameters = EventParameter::UnsignedRange {
+            time_delay: 0,
+            low_limit: 2,
+            high_limit: 8,
+        };
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        WriteEventParametersRequest {
+            event_enrollment,
+            parameters: &parameters,
+            invoke_id: 1,
+        }
+        .encode(&mut w)
+        .unwrap();
+        // The WriteProperty parameters after the header read back as a ReadProperty-ACK.
+        let mut r = Reader::new(&w.as_written()[4..]);
+        let ack = EventParametersAck::decode_after_header(&mut r).unwrap();
+        assert_eq!(ack.event_enrollment, event_enrollment);
+        assert_eq!(ack.parameters, parameters);
+        assert!(r.is_empty());
+
+        let reference = DeviceObjectPropertyReference::remote(
+            ObjectId::new(ObjectType::Device, 20),
+            ObjectId::new(ObjectType::AnalogInput, 1),
+            PropertyId::PresentValue,
+        );
+        let mut w = Writer::new(&mut buf);
+        WriteEventReferenceRequest {
+            event_enrollment,
+            reference,
+            invoke_id: 2,
+        }
+        .encode(&mut w)
+        .unwrap();
+        let mut r = Reader::new(&w.as_written()[4..]);
+        let ack = EventReferenceAck::decode_after_header(&mut r).unwrap();
+        assert_eq!(ack.reference, reference);
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn event_transitions_map_to_bit_string_bits() {
+        assert_eq!(EventTransitions::ALL.to_byte(), 0xE0);
+        let offnormal_only = EventTransitions {
+            to_offnormal: true,
+            ..EventTransitions::NONE
+        };
+        assert_eq!(offnormal_only.to_byte(), 0x80);
+        assert_eq!(
+            EventTransitions::from_byte(0x60),
+            EventTransitions {
+                to_offnormal: false,
+                to_fault: true,
+                to_normal: true,
+            }
+        );
+    }
+}
@@ -1,21 +1,88 @@
 #[cfg(feature = "alloc")]
-use crate::encoding::{
-    primitives::{decode_ctx_character_string, decode_unsigned},
-    reader::Reader,
-    tag::{AppTag, Tag},
-};
+use crate::encoding::primitives::{decode_ctx_character_string, decode_unsigned};
+use crate::encoding::{primitives::encode_ctx_unsigned, tag::Tag, writer::Writer};
+#[cfg(feature = "alloc")]
+use crate::encoding::{reader::Reader, tag::AppTag};
+#[cfg(feature = "alloc")]
+use crate::services::access_control::{AccessEventParameters, EVENT_TYPE_ACCESS_EVENT};
 #[cfg(feature = "alloc")]
 use crate::services::acknowledge_alarm::TimeStamp;
 #[cfg(feature = "alloc")]
 use crate::services::{decode_required_ctx_object_id, decode_required_ctx_unsigned};
+use crate::types::{BitString, LifeSafetyMode, LifeSafetyOperation, LifeSafetyState};
 #[cfg(feature = "alloc")]
 use crate::types::{Date, ObjectId, Time};
 #[cfg(feature = "alloc")]
 use crate::DecodeError;
+use crate::EncodeError;
 
 pub const SERVICE_CONFIRMED_EVENT_NOTIFICATION: u8 = 0x02;
 pub const SERVICE_UNCONFIRMED_EVENT_NOTIFICATION: u8 = 0x03;
 
+/// BACnetEventType of a change-of-life-safety event, and its BACnetNotificationParameters
+/// choice tag.
+pub const EVENT_TYPE_CHANGE_OF_LIFE_SAFETY: u32 = 8;
+
+/// The change-of-life-safety notification parameters. The enumerations are kept raw, as
+/// devices may report proprietary values; the accessors give the standard ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeOfLifeSafetyParameters<'a> {
+    pub new_state: u32,
+    pub new_mode: u32,
+    pub status_flags: BitString<'a>,
+    pub operation_expected: u32,
+}
+
+impl<'a> ChangeOfLifeSafetyParameters<'a> {
+    pub const fn new_state(&self) -> Option<LifeSafetyState> {
+        LifeSafetyState::from_u32(self.new_state)
+    }
+
+    pub const fn new_mode(&self) -> Option<LifeSafetyMode> {
+        LifeSafetyMode::from_u32(self.new_mode)
+    }
+
+    pub const fn operation_expected(&self) -> Option<LifeSafetyOperation> {
+        LifeSafetyOperation::from_u32(self.operation_expected)
+    }
+
+    /// Encodes the parameters, without the enclosing change-of-life-safety choice tags.
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_ctx_unsigned(w, 0, self.new_state)?;
+        encode_ctx_unsigned(w, 1, self.new_mode)?;
+        Tag::Context {
+            tag_num: 2,
+            len: self.status_flags.data.len() as u32 + 1,
+        }
+        .encode(w)?;
+        w.write_u8(self.status_flags.unused_bits)?;
+        w.write_all(self.status_flags.data)?;
+        encode_ctx_unsigned(w, 3, self.operation_expected)
+    }
+
+    /// Decodes the parameters inside the change-of-life-safety choice tags, stopping
+    /// before the closing one.
+    #[cfg(feature = "alloc")]
+    pub fn decode(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let new_state = decode_required_ctx_unsigned(r, 0)?;
+        let new_mode = decode_required_ctx_unsigned(r, 1)?;
+        let status_flags = match Tag::decode(r)? {
+            Tag::Context { tag_num: 2, len } if len > 0 => {
+                let bytes = r.read_exact(len as usize)?;
+                BitString::new(bytes[0], &bytes[1..])
+            }
+            _ => return Err(DecodeError::InvalidTag),
+        };
+        let operation_expected = decode_required_ctx_unsigned(r, 3)?;
+        Ok(Self {
+            new_state,
+            new_mode,
+            status_flags,
+            operation_expected,
+        })
+    }
+}
+
 #[cfg(feature = "alloc")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct EventNotificationRequest<'a> {
@@ -31,6 +98,11 @@ pub struct EventNotificationRequest<'a> {
     pub ack_required: Option<bool>,
     pub from_state: u32,
     pub to_state: u32,
+    /// The event values of an access event.
+    pub access_event: Option<AccessEventParameters<'a>>,
+    /// The event values of a change-of-life-safety event. Other event types' values are
+    /// skipped.
+    pub life_safety: Option<ChangeOfLifeSafetyParameters<'a>>,
 }
 
 #[cfg(feature = "alloc")]
@@ -68,8 +140,25 @@ impl<'a> EventNotificationRequest<'a> {
         let to_state = decode_required_ctx_unsigned(r, 11)?;
 
         let checkpoint = *r;
+        let mut access_event = None;
+        let mut life_safety = None;
         if Tag::decode(r)? == (Tag::Opening { tag_num: 12 }) {
-            skip_constructed(r, 12)?;
+            let mut choice = *r;
+            match Tag::decode(&mut choice)? {
+                Tag::Opening { tag_num } if u32::from(tag_num) == EVENT_TYPE_ACCESS_EVENT => {
+                    *r = choice;
+                    access_event = Some(AccessEventParameters::decode(r)?);
+                    expect_parameters_end(r, tag_num)?;
+                }
+                Tag::Opening { tag_num }
+                    if u32::from(tag_num) == EVENT_TYPE_CHANGE_OF_LIFE_SAFETY =>
+                {
+                    *r = choice;
+                    life_safety = Some(ChangeOfLifeSafetyParameters::decode(r)?);
+                    expect_parameters_end(r, tag_num)?;
+                }
+                _ => skip_constructed(r, 12)?,
+            }
         } else {
             *r = checkpoint;
         }
@@ -87,12 +176,28 @@ impl<'a> EventNotificationRequest<'a> {
             ack_required,
             from_state,
             to_state,
+            access_event,
+            life_safety,
+        })
+    }
+}
+
+/// Checks the closing tags of the event-values choice `choice_tag` and of [12].
+#[cfg(feature = "alloc")]
+fn expect_parameters_end(r: &mut Reader<'_>, choice_tag: u8) -> Result<(), DecodeError> {
+    if Tag::decode(r)?
+        != (Tag::Closing {
+            tag_num: choice_tag,
         })
+        || Tag::decode(r)? != (Tag::Closing { tag_num: 12 })
+    {
+        return Err(DecodeError::InvalidTag);
     }
+    Ok(())
 }
 
 #[cfg(feature = "alloc")]
-fn decode_required_ctx_timestamp(
+pub(crate) fn decode_required_ctx_timestamp(
     r: &mut Reader<'_>,
     expected_tag_num: u8,
 ) -> Result<TimeStamp, DecodeError> {
@@ -249,5 +354,123 @@ mod tests {
         assert_eq!(notification.ack_required, Some(true));
         assert_eq!(notification.from_state, 2);
         assert_eq!(notification.to_state, 0);
+        assert_eq!(notification.access_event, None);
+        assert_eq!(notification.life_safety, None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn decode_access_event_notification_parameters() {
+        use crate::services::access_control::{
+            AccessEvent, AccessEventParameters, AuthenticationFactor,
+        };
+        use crate::types::{BitString, DeviceObjectReference};
+
+        let parameters = AccessEventParameters {
+            access_event: AccessEvent::DeniedCredentialExpired.to_u32(),
+            status_flags: BitString::new(4, &[0x80]),
+            access_event_tag: 77,
+            access_event_time: TimeStamp::SequenceNumber(9),
+            access_credential: DeviceObjectReference {
+                device_id: Some(ObjectId::new(ObjectType::Device, 1)),
+                object_id: ObjectId::new(ObjectType::AccessCredential, 12),
+            },
+            authentication_factor: Some(AuthenticationFactor {
+                format_type: 11,
+                format_class: 0,
+                value: &[0x00, 0x00, 0x30, 0x39],
+            }),
+        };
+        let mut buf = [0u8; 256];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_unsigned(&mut w, 0, 1).unwrap();
+        encode_ctx_object_id(&mut w, 1, ObjectId::new(ObjectType::Device, 1).raw()).unwrap();
+        encode_ctx_object_id(&mut w, 2, ObjectId::new(ObjectType::AccessPoint, 2).raw()).unwrap();
+        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 1, 5).unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 4, 7).unwrap();
+        encode_ctx_unsigned(&mut w, 5, 100).unwrap();
+        encode_ctx_unsigned(&mut w, 6, 13).unwrap();
+        encode_ctx_unsigned(&mut w, 8, 1).unwrap();
+        encode_ctx_unsigned(&mut w, 10, 0).unwrap();
+        encode_ctx_unsigned(&mut w, 11, 0).unwrap();
+        Tag::Opening { tag_num: 12 }.encode(&mut w).unwrap();
+        Tag::Opening { tag_num: 13 }.encode(&mut w).unwrap();
+        parameters.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 13 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 12 }.encode(&mut w).unwrap();
+
+        let mut r = Reader::new(w.as_written());
+        let notification = EventNotificationRequest::decode_after_header(&mut r).unwrap();
+        assert!(r.is_empty());
+        let access_event = notification.access_event.unwrap();
+        assert_eq!(access_event, parameters);
+        assert_eq!(
+            access_event.access_event(),
+            Some(AccessEvent::DeniedCredentialExpired)
+        );
+
+        // Without the optional authentication factor.
+        let parameters = AccessEventParameters {
+            authentication_factor: None,
+            ..parameters
+        };
+        let mut w = Writer::new(&mut buf);
+        parameters.encode(&mut w).unwrap();
+        let mut r = Reader::new(w.as_written());
+        assert_eq!(AccessEventParameters::decode(&mut r).unwrap(), parameters);
+        assert!(r.is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn decode_change_of_life_safety_notification_parameters() {
+        use super::ChangeOfLifeSafetyParameters;
+        use crate::types::{BitString, LifeSafetyMode, LifeSafetyOperation, LifeSafetyState};
+
+        let parameters = ChangeOfLifeSafetyParameters {
+            new_state: LifeSafetyState::Alarm.to_u32(),
+            new_mode: LifeSafetyMode::On.to_u32(),
+            status_flags: BitString::new(4, &[0x80]),
+            operation_expected: LifeSafetyOperation::Reset.to_u32(),
+        };
+        let mut buf = [0u8; 128];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_unsigned(&mut w, 0, 1).unwrap();
+        encode_ctx_object_id(&mut w, 1, ObjectId::new(ObjectType::Device, 1).raw()).unwrap();
+        encode_ctx_object_id(
+            &mut w,
+            2,
+            ObjectId::new(ObjectType::LifeSafetyPoint, 4).raw(),
+        )
+        .unwrap();
+        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 1, 6).unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 4, 1).unwrap();
+        encode_ctx_unsigned(&mut w, 5, 1).unwrap();
+        encode_ctx_unsigned(&mut w, 6, 8).unwrap();
+        encode_ctx_unsigned(&mut w, 8, 0).unwrap();
+        encode_ctx_unsigned(&mut w, 10, 0).unwrap();
+        encode_ctx_unsigned(&mut w, 11, 5).unwrap();
+        Tag::Opening { tag_num: 12 }.encode(&mut w).unwrap();
+        Tag::Opening { tag_num: 8 }.encode(&mut w).unwrap();
+        parameters.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 8 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 12 }.encode(&mut w).unwrap();
+
+        let mut r = Reader::new(w.as_written());
+        let notification = EventNotificationRequest::decode_after_header(&mut r).unwrap();
+        assert!(r.is_empty());
+        assert_eq!(notification.access_event, None);
+        let life_safety = notification.life_safety.unwrap();
+        assert_eq!(life_safety, parameters);
+        assert_eq!(life_safety.new_state(), Some(LifeSafetyState::Alarm));
+        assert_eq!(life_safety.new_mode(), Some(LifeSafetyMode::On));
+        assert_eq!(
+            life_safety.operation_expected(),
+            Some(LifeSafetyOperation::Reset)
+        );
     }
 }
@@ -0,0 +1,257 @@
+use crate::encoding::{primitives::encode_app_enumerated, tag::Tag, writer::Writer};
+use crate::services::channel::encode_reference_list_write;
+use crate::services::value_codec::encode_application_data_value;
+use crate::types::{DataValue, DeviceObjectPropertyReference, ObjectId, PropertyId};
+use crate::EncodeError;
+
+#[cfg(feature = "alloc")]
+use crate::encoding::{primitives::decode_app_enumerated, reader::Reader};
+#[cfg(feature = "alloc")]
+use crate::services::channel::decode_reference_list_ack;
+#[cfg(feature = "alloc")]
+use crate::services::value_codec::decode_application_data_value_from_tag;
+#[cfg(feature = "alloc")]
+use crate::services::{
+    decode_required_ctx_object_id, decode_required_ctx_unsigned, take_optional_ctx_tag,
+};
+#[cfg(feature = "alloc")]
+use crate::DecodeError;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The value read for one Global Group member, or why it could not be read.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessResult<'a> {
+    /// The member's value. A value made of several application values is returned as
+    /// [`DataValue::Constructed`] with `tag_num` 4.
+    Value(DataValue<'a>),
+    Error {
+        error_class: u32,
+        error_code: u32,
+    },
+}
+
+/// BACnetPropertyAccessResult, the element type of a Global Group's Present_Value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyAccessResult<'a> {
+    pub member: DeviceObjectPropertyReference,
+    pub result: AccessResult<'a>,
+}
+
+impl<'a> PropertyAccessResult<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        self.member.encode(w)?;
+        match &self.result {
+            AccessResult::Value(value) => {
+                Tag::Opening { tag_num: 4 }.encode(w)?;
+                encode_application_data_value(w, value)?;
+                Tag::Closing { tag_num: 4 }.encode(w)
+            }
+            AccessResult::Error {
+                error_class,
+                error_code,
+            } => {
+                Tag::Opening { tag_num: 5 }.encode(w)?;
+                encode_app_enumerated(w, *error_class)?;
+                encode_app_enumerated(w, *error_code)?;
+                Tag::Closing { tag_num: 5 }.encode(w)
+            }
+        }
+    }
+
+    /// Decodes one result, stopping before the tag that follows it.
+    #[cfg(feature = "alloc")]
+    pub fn decode(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let member = DeviceObjectPropertyReference::decode(r)?;
+        let result = match Tag::decode(r)? {
+            Tag::Opening { tag_num: 4 } => {
+                let mut values = Vec::new();
+                loop {
+                    let tag = Tag::decode(r)?;
+                    if tag == (Tag::Closing { tag_num: 4 }) {
+                        break;
+                    }
+                    values.push(decode_application_data_value_from_tag(r, tag)?);
+                }
+                if values.len() == 1 {
+                    AccessResult::Value(values.remove(0))
+                } else {
+                    AccessResult::Value(DataValue::Constructed { tag_num: 4, values })
+                }
+            }
+            Tag::Opening { tag_num: 5 } => {
+                let error_class = decode_app_enumerated(r)?;
+                let error_code = decode_app_enumerated(r)?;
+                match Tag::decode(r)? {
+                    Tag::Closing { tag_num: 5 } => AccessResult::Error {
+                        error_class,
+                        error_code,
+                    },
+                    _ => return Err(DecodeError::InvalidTag),
+                }
+            }
+            _ => return Err(DecodeError::InvalidTag),
+        };
+        Ok(Self { member, result })
+    }
+}
+
+/// ReadProperty-ACK carrying a Global Group's Present_Value: one result per member, in
+/// Group_Members order.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalGroupValuesAck<'a> {
+    pub group: ObjectId,
+    pub results: Vec<PropertyAccessResult<'a>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> GlobalGroupValuesAck<'a> {
+    pub fn decode_after_header(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let group = decode_required_ctx_object_id(r, 0)?;
+        let _property_id = decode_required_ctx_unsigned(r, 1)?;
+        if let Some(len) = take_optional_ctx_tag(r, 2)? {
+            r.read_exact(len as usize)?;
+        }
+        match Tag::decode(r)? {
+            Tag::Opening { tag_num: 3 } => {}
+            _ => return Err(DecodeError::InvalidTag),
+        }
+        let mut results = Vec::new();
+        loop {
+            let mut peek = *r;
+            if Tag::decode(&mut peek)? == (Tag::Closing { tag_num: 3 }) {
+                *r = peek;
+                break;
+            }
+            results.push(PropertyAccessResult::decode(r)?);
+        }
+        Ok(Self { group, results })
+    }
+}
+
+/// WriteProperty replacing a Global Group's whole Group_Members array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteGroupMembersRequest<'a> {
+    pub group: ObjectId,
+    pub members: &'a [DeviceObjectPropertyReference],
+    pub invoke_id: u8,
+}
+
+impl<'a> WriteGroupMembersRequest<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_reference_list_write(
+            w,
+            self.invoke_id,
+            self.group,
+            PropertyId::GroupMembers,
+            self.members,
+        )
+    }
+}
+
+/// ReadProperty-ACK carrying a Global Group's Group_Members.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupMembersAck {
+    pub group: ObjectId,
+    pub members: Vec<DeviceObjectPropertyReference>,
+}
+
+#[cfg(feature = "alloc")]
+impl GroupMembersAck {
+    pub fn decode_after_header(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let (group, members) = decode_reference_list_ack(r)?;
+        Ok(Self { group, members })
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::{
+        AccessResult, GlobalGroupValuesAck, GroupMembersAck, PropertyAccessResult,
+        WriteGroupMembersRequest,
+    };
+    use crate::encoding::{
+        primitives::{encode_ctx_object_id, encode_ctx_unsigned},
+        reader::Reader,
+        tag::Tag,
+        writer::Writer,
+    };
+    use crate::types::{
+        DataValue, DeviceObjectPropertyReference, ObjectId, ObjectType, PropertyId,
+    };
+
+    #[test]
+    fn present_value_results_carry_values_and_errors() {
+        let group = ObjectId::new(ObjectType::GlobalGroup, 1);
+        let temp = DeviceObjectPropertyReference::remote(
+            ObjectId::new(ObjectType::Device, 10),
+            ObjectId::new(ObjectType::AnalogInput, 1),
+            PropertyId::PresentValue,
+        );
+        let missing = DeviceObjectPropertyReference::local(
+            ObjectId::new(ObjectType::AnalogInput, 99),
+            PropertyId::PresentValue,
+        );
+        let results = [
+            PropertyAccessResult {
+                member: temp,
+                result: AccessResult::Value(DataValue::Real(21.5)),
+            },
+            PropertyAccessResult {
+                member: missing,
+                result: AccessResult::Error {
+                    error_class: 1,
+                    error_code: 31,
+                },
+            },
+        ];
+
+        let mut buf = [0u8; 128];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, group.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::PresentValue.to_u32()).unwrap();
+        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+        for result in &results {
+            result.encode(&mut w).unwrap();
+        }
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+
+        let mut r = Reader::new(w.as_written());
+        let ack = GlobalGroupValuesAck::decode_after_header(&mut r).unwrap();
+        assert_eq!(ack.group, group);
+        assert_eq!(ack.results, results);
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn group_members_round_trip() {
+        let group = ObjectId::new(ObjectType::GlobalGroup, 2);
+        let members = [
+            DeviceObjectPropertyReference::local(
+                ObjectId::new(ObjectType::BinaryInput, 1),
+                PropertyId::PresentValue,
+            ),
+            DeviceObjectPropertyReference::remote(
+                ObjectId::new(ObjectType::Device, 20),
+                ObjectId::new(ObjectType::AnalogValue, 4),
+                PropertyId::StatusFlags,
+            ),
+        ];
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        WriteGroupMembersRequest {
+            group,
+            members: &members,
+            invoke_id: 1,
+        }
+        .encode(&mut w)
+        .unwrap();
+
+        let mut r = Reader::new(&w.as_written()[4..]);
+        let ack = GroupMembersAck::decode_after_header(&mut r).unwrap();
+        assert_eq!(ack.group, group);
+        assert_eq!(ack.members, members);
+    }
+}
@@ -0,0 +1,550 @@
+use crate::encoding::{
+    primitives::{encode_ctx_character_string, encode_ctx_unsigned},
+    writer::Writer,
+};
+use crate::EncodeError;
+
+#[cfg(feature = "alloc")]
+use crate::encoding::{
+    primitives::{decode_ctx_character_string, decode_unsigned},
+    reader::Reader,
+    tag::{AppTag, Tag},
+};
+#[cfg(feature = "alloc")]
+use crate::services::{decode_ack_object, take_optional_ctx_tag};
+#[cfg(feature = "alloc")]
+use crate::types::ObjectId;
+#[cfg(feature = "alloc")]
+use crate::DecodeError;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// BACnetLiftCarDirection: the direction a Lift car is moving, or has been assigned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum LiftCarDirection {
+    Unknown = 0,
+    None = 1,
+    Stopped = 2,
+    Up = 3,
+    Down = 4,
+    UpAndDown = 5,
+}
+
+impl LiftCarDirection {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Unknown),
+            1 => Some(Self::None),
+            2 => Some(Self::Stopped),
+            3 => Some(Self::Up),
+            4 => Some(Self::Down),
+            5 => Some(Self::UpAndDown),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetLiftCarDriveStatus: what a Lift car's drive is doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum LiftCarDriveStatus {
+    Unknown = 0,
+    Stationary = 1,
+    Braking = 2,
+    Accelerate = 3,
+    Decelerate = 4,
+    RatedSpeed = 5,
+    SingleFloorJump = 6,
+    TwoFloorJump = 7,
+    ThreeFloorJump = 8,
+    MultiFloorJump = 9,
+}
+
+impl LiftCarDriveStatus {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Unknown),
+            1 => Some(Self::Stationary),
+            2 => Some(Self::Braking),
+            3 => Some(Self::Accelerate),
+            4 => Some(Self::Decelerate),
+            5 => Some(Self::RatedSpeed),
+            6 => Some(Self::SingleFloorJump),
+            7 => Some(Self::TwoFloorJump),
+            8 => Some(Self::ThreeFloorJump),
+            9 => Some(Self::MultiFloorJump),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetLiftCarMode: the operating mode of a Lift car.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum LiftCarMode {
+    Unknown = 0,
+    Normal = 1,
+    Vip = 2,
+    Homing = 3,
+    Parking = 4,
+    AttendantControl = 5,
+    FirefighterControl = 6,
+    EmergencyPower = 7,
+    Inspection = 8,
+    CabinetRecall = 9,
+    EarthquakeOperation = 10,
+    FireOperation = 11,
+    OutOfService = 12,
+    OccupantEvacuation = 13,
+}
+
+impl LiftCarMode {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Unknown),
+            1 => Some(Self::Normal),
+            2 => Some(Self::Vip),
+            3 => Some(Self::Homing),
+            4 => Some(Self::Parking),
+            5 => Some(Self::AttendantControl),
+            6 => Some(Self::FirefighterControl),
+            7 => Some(Self::EmergencyPower),
+            8 => Some(Self::Inspection),
+            9 => Some(Self::CabinetRecall),
+            10 => Some(Self::EarthquakeOperation),
+            11 => Some(Self::FireOperation),
+            12 => Some(Self::OutOfService),
+            13 => Some(Self::OccupantEvacuation),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetLiftGroupMode: the traffic mode of an Elevator Group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum LiftGroupMode {
+    Unknown = 0,
+    Normal = 1,
+    DownPeak = 2,
+    TwoWay = 3,
+    FourWay = 4,
+    EmergencyPower = 5,
+    UpPeak = 6,
+}
+
+impl LiftGroupMode {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Unknown),
+            1 => Some(Self::Normal),
+            2 => Some(Self::DownPeak),
+            3 => Some(Self::TwoWay),
+            4 => Some(Self::FourWay),
+            5 => Some(Self::EmergencyPower),
+            6 => Some(Self::UpPeak),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetEscalatorMode: the operating mode of an Escalator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum EscalatorMode {
+    Unknown = 0,
+    Stop = 1,
+    Up = 2,
+    Down = 3,
+    Inspection = 4,
+    OutOfService = 5,
+}
+
+impl EscalatorMode {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Unknown),
+            1 => Some(Self::Stop),
+            2 => Some(Self::Up),
+            3 => Some(Self::Down),
+            4 => Some(Self::Inspection),
+            5 => Some(Self::OutOfService),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetEscalatorOperationDirection: the direction and speed an Escalator is running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum EscalatorOperationDirection {
+    Unknown = 0,
+    Stopped = 1,
+    UpRatedSpeed = 2,
+    UpReducedSpeed = 3,
+    DownRatedSpeed = 4,
+    DownReducedSpeed = 5,
+}
+
+impl EscalatorOperationDirection {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Unknown),
+            1 => Some(Self::Stopped),
+            2 => Some(Self::UpRatedSpeed),
+            3 => Some(Self::UpReducedSpeed),
+            4 => Some(Self::DownRatedSpeed),
+            5 => Some(Self::DownReducedSpeed),
+            _ => None,
+        }
+    }
+}
+
+/// What a landing call asks for: a direction of travel from the landing, or (with
+/// destination dispatch) the floor to travel to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LandingCallCommand {
+    Direction(LiftCarDirection),
+    Destination(u8),
+}
+
+/// BACnetLandingCallStatus: a call registered at a landing, as listed in Landing_Calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LandingCallStatus<'a> {
+    pub floor_number: u8,
+    pub command: LandingCallCommand,
+    /// The floor as shown to passengers, when it differs from the number.
+    pub floor_text: Option<&'a str>,
+}
+
+impl<'a> LandingCallStatus<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_ctx_unsigned(w, 0, self.floor_number.into())?;
+        match self.command {
+            LandingCallCommand::Direction(direction) => {
+                encode_ctx_unsigned(w, 1, direction.to_u32())?
+            }
+            LandingCallCommand::Destination(floor) => encode_ctx_unsigned(w, 2, floor.into())?,
+        }
+        if let Some(text) = self.floor_text {
+            encode_ctx_character_string(w, 3, text)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn decode(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let floor_number = decode_ctx_u8(r, 0)?;
+        let command = match Tag::decode(r)? {
+            Tag::Context { tag_num: 1, len } => LandingCallCommand::Direction(
+                LiftCarDirection::from_u32(decode_unsigned(r, len as usize)?)
+                    .ok_or(DecodeError::InvalidValue)?,
+            ),
+            Tag::Context { tag_num: 2, len } => {
+                LandingCallCommand::Destination(to_u8(decode_unsigned(r, len as usize)?)?)
+            }
+            _ => return Err(DecodeError::InvalidTag),
+        };
+        let floor_text = match take_optional_ctx_tag(r, 3)? {
+            Some(len) => Some(decode_ctx_character_string(r, len as usize)?),
+            None => None,
+        };
+        Ok(Self {
+            floor_number,
+            command,
+            floor_text,
+        })
+    }
+}
+
+/// A landing call assigned to a Lift car: the floor and the direction of travel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LandingCall {
+    pub floor_number: u8,
+    pub direction: LiftCarDirection,
+}
+
+impl LandingCall {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_ctx_unsigned(w, 0, self.floor_number.into())?;
+        encode_ctx_unsigned(w, 1, self.direction.to_u32())
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let floor_number = decode_ctx_u8(r, 0)?;
+        let direction = match Tag::decode(r)? {
+            Tag::Context { tag_num: 1, len } => {
+                LiftCarDirection::from_u32(decode_unsigned(r, len as usize)?)
+                    .ok_or(DecodeError::InvalidValue)?
+            }
+            _ => return Err(DecodeError::InvalidTag),
+        };
+        Ok(Self {
+            floor_number,
+            direction,
+        })
+    }
+}
+
+/// ReadProperty-ACK carrying the Landing_Calls of a Lift or Elevator Group (or an
+/// Elevator Group's Landing_Call_Control, as a single call).
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LandingCallsAck<'a> {
+    pub object_id: ObjectId,
+    pub calls: Vec<LandingCallStatus<'a>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> LandingCallsAck<'a> {
+    pub fn decode_after_header(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let object_id = decode_ack_object(r)?;
+        let calls = decode_ack_values(r, LandingCallStatus::decode)?;
+        Ok(Self { object_id, calls })
+    }
+}
+
+/// ReadProperty-ACK carrying a Lift's Assigned_Landing_Calls: the calls assigned to the
+/// car, one list per car door.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssignedLandingCallsAck {
+    pub lift: ObjectId,
+    pub doors: Vec<Vec<LandingCall>>,
+}
+
+#[cfg(feature = "alloc")]
+impl AssignedLandingCallsAck {
+    pub fn decode_after_header(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let lift = decode_ack_object(r)?;
+        let doors = decode_ack_values(r, |r| decode_sequence_of(r, LandingCall::decode))?;
+        Ok(Self { lift, doors })
+    }
+}
+
+/// ReadProperty-ACK carrying a Lift's Registered_Car_Call: the floors requested from
+/// inside the car, one list per car door.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisteredCarCallAck {
+    pub lift: ObjectId,
+    pub doors: Vec<Vec<u8>>,
+}
+
+#[cfg(feature = "alloc")]
+impl RegisteredCarCallAck {
+    pub fn decode_after_header(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let lift = decode_ack_object(r)?;
+        let doors = decode_ack_values(r, |r| decode_sequence_of(r, decode_app_u8))?;
+        Ok(Self { lift, doors })
+    }
+}
+
+/// Decodes the values between the ACK's [3] tags.
+#[cfg(feature = "alloc")]
+fn decode_ack_values<'a, T>(
+    r: &mut Reader<'a>,
+    decode: impl FnMut(&mut Reader<'a>) -> Result<T, DecodeError>,
+) -> Result<Vec<T>, DecodeError> {
+    decode_enclosed(r, 3, decode)
+}
+
+/// Decodes a `[0] SEQUENCE OF` field.
+#[cfg(feature = "alloc")]
+fn decode_sequence_of<'a, T>(
+    r: &mut Reader<'a>,
+    decode: impl FnMut(&mut Reader<'a>) -> Result<T, DecodeError>,
+) -> Result<Vec<T>, DecodeError> {
+    decode_enclosed(r, 0, decode)
+}
+
+#[cfg(feature = "alloc")]
+fn decode_enclosed<'a, T>(
+    r: &mut Reader<'a>,
+    tag_num: u8,
+    mut decode: impl FnMut(&mut Reader<'a>) -> Result<T, DecodeError>,
+) -> Result<Vec<T>, DecodeError> {
+    if Tag::decode(r)? != (Tag::Opening { tag_num }) {
+        return Err(DecodeError::InvalidTag);
+    }
+    let mut values = Vec::new();
+    loop {
+        let mut peek = *r;
+        if Tag::decode(&mut peek)? == (Tag::Closing { tag_num }) {
+            *r = peek;
+            return Ok(values);
+        }
+        values.push(decode(r)?);
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn decode_ctx_u8(r: &mut Reader<'_>, expected: u8) -> Result<u8, DecodeError> {
+    match Tag::decode(r)? {
+        Tag::Context { tag_num, len } if tag_num == expected => {
+            to_u8(decode_unsigned(r, len as usize)?)
+        }
+        _ => Err(DecodeError::InvalidTag),
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn decode_app_u8(r: &mut Reader<'_>) -> Result<u8, DecodeError> {
+    match Tag::decode(r)? {
+        Tag::Application {
+            tag: AppTag::UnsignedInt,
+            len,
+        } => to_u8(decode_unsigned(r, len as usize)?),
+        _ => Err(DecodeError::InvalidTag),
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn to_u8(value: u32) -> Result<u8, DecodeError> {
+    u8::try_from(value).map_err(|_| DecodeError::InvalidValue)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use super::{
+        AssignedLandingCallsAck, LandingCall, LandingCallCommand, LandingCallStatus,
+        LandingCallsAck, LiftCarDirection, RegisteredCarCallAck,
+    };
+    use super::{LiftCarMode, LiftGroupMode};
+    #[cfg(feature = "alloc")]
+    use crate::encoding::{
+        primitives::encode_app_unsigned, reader::Reader, tag::Tag, writer::Writer,
+    };
+    #[cfg(feature = "alloc")]
+    use crate::types::{ObjectId, ObjectType};
+    #[cfg(feature = "alloc")]
+    use alloc::vec;
+
+    /// Writes the ReadProperty-ACK parameters for Lift 1 and `property`, up to the [3] tag.
+    #[cfg(feature = "alloc")]
+    fn ack_header(w: &mut Writer<'_>, property: u16) {
+        w.write_all(&[0x0C, 0x0E, 0xC0, 0x00, 0x01, 0x1A]).unwrap();
+        w.write_all(&property.to_be_bytes()).unwrap();
+        Tag::Opening { tag_num: 3 }.encode(w).unwrap();
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn landing_calls_decode_directions_destinations_and_floor_text() {
+        let calls = [
+            LandingCallStatus {
+                floor_number: 3,
+                command: LandingCallCommand::Direction(LiftCarDirection::Up),
+                floor_text: None,
+            },
+            LandingCallStatus {
+                floor_number: 0,
+                command: LandingCallCommand::Destination(12),
+                floor_text: Some("G"),
+            },
+        ];
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        ack_header(&mut w, 470);
+        for call in &calls {
+            call.encode(&mut w).unwrap();
+        }
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+
+        let mut r = Reader::new(w.as_written());
+        let ack = LandingCallsAck::decode_after_header(&mut r).unwrap();
+        assert_eq!(ack.object_id, ObjectId::new(ObjectType::Lift, 1));
+        assert_eq!(ack.calls, calls);
+        assert!(r.is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn per_door_call_lists_decode() {
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        ack_header(&mut w, 447);
+        Tag::Opening { tag_num: 0 }.encode(&mut w).unwrap();
+        LandingCall {
+            floor_number: 5,
+            direction: LiftCarDirection::Down,
+        }
+        .encode(&mut w)
+        .unwrap();
+        Tag::Closing { tag_num: 0 }.encode(&mut w).unwrap();
+        Tag::Opening { tag_num: 0 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 0 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        let ack =
+            AssignedLandingCallsAck::decode_after_header(&mut Reader::new(w.as_written())).unwrap();
+        assert_eq!(
+            ack.doors,
+            vec![
+                vec![LandingCall {
+                    floor_number: 5,
+                    direction: LiftCarDirection::Down,
+                }],
+                vec![],
+            ]
+        );
+
+        let mut w = Writer::new(&mut buf);
+        ack_header(&mut w, 480);
+        Tag::Opening { tag_num: 0 }.encode(&mut w).unwrap();
+        for floor in [2, 7] {
+            encode_app_unsigned(&mut w, floor).unwrap();
+        }
+        Tag::Closing { tag_num: 0 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        let ack =
+            RegisteredCarCallAck::decode_after_header(&mut Reader::new(w.as_written())).unwrap();
+        assert_eq!(ack.doors, vec![vec![2, 7]]);
+
+        // Floor numbers are Unsigned8.
+        let mut w = Writer::new(&mut buf);
+        ack_header(&mut w, 480);
+        Tag::Opening { tag_num: 0 }.encode(&mut w).unwrap();
+        encode_app_unsigned(&mut w, 256).unwrap();
+        Tag::Closing { tag_num: 0 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        assert!(
+            RegisteredCarCallAck::decode_after_header(&mut Reader::new(w.as_written())).is_err()
+        );
+    }
+
+    #[test]
+    fn lift_enums_round_trip() {
+        for value in 0..14 {
+            assert_eq!(LiftCarMode::from_u32(value).unwrap().to_u32(), value);
+        }
+        assert_eq!(LiftCarMode::from_u32(14), None);
+        assert_eq!(LiftGroupMode::from_u32(6), Some(LiftGroupMode::UpPeak));
+    }
+}
@@ -0,0 +1,493 @@
+use crate::encoding::{
+    primitives::{
+        decode_app_real, decode_unsigned, encode_app_real, encode_ctx_real, encode_ctx_unsigned,
+    },
+    reader::Reader,
+    tag::Tag,
+    writer::Writer,
+};
+use crate::services::write_property::encode_write_property_header;
+use crate::services::{decode_real, decode_required_ctx_unsigned, take_optional_ctx_tag};
+use crate::types::{ObjectId, PropertyId};
+use crate::{DecodeError, EncodeError};
+
+/// BACnetLightingOperation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum LightingOperation {
+    None = 0,
+    FadeTo = 1,
+    RampTo = 2,
+    StepUp = 3,
+    StepDown = 4,
+    StepOn = 5,
+    StepOff = 6,
+    Warn = 7,
+    WarnOff = 8,
+    WarnRelinquish = 9,
+    Stop = 10,
+}
+
+impl LightingOperation {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::None),
+            1 => Some(Self::FadeTo),
+            2 => Some(Self::RampTo),
+            3 => Some(Self::StepUp),
+            4 => Some(Self::StepDown),
+            5 => Some(Self::StepOn),
+            6 => Some(Self::StepOff),
+            7 => Some(Self::Warn),
+            8 => Some(Self::WarnOff),
+            9 => Some(Self::WarnRelinquish),
+            10 => Some(Self::Stop),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetLightingCommand: a fade, ramp or step for Lighting Output objects, written
+/// directly or through a Channel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LightingCommand {
+    pub operation: LightingOperation,
+    /// Target level in percent.
+    pub target_level: Option<f32>,
+    /// Ramp rate in percent per second.
+    pub ramp_rate: Option<f32>,
+    /// Step size in percent.
+    pub step_increment: Option<f32>,
+    /// Fade time in milliseconds.
+    pub fade_time: Option<u32>,
+    pub priority: Option<u8>,
+}
+
+impl LightingCommand {
+    /// A command performing `operation` with every optional field left out.
+    pub const fn new(operation: LightingOperation) -> Self {
+        Self {
+            operation,
+            target_level: None,
+            ramp_rate: None,
+            step_increment: None,
+            fade_time: None,
+            priority: None,
+        }
+    }
+
+    /// Fade to `level` percent over `fade_time_ms`.
+    pub const fn fade_to(level: f32, fade_time_ms: u32) -> Self {
+        let mut command = Self::new(LightingOperation::FadeTo);
+        command.target_level = Some(level);
+        command.fade_time = Some(fade_time_ms);
+        command
+    }
+
+    /// Ramp to `level` percent at `rate` percent per second.
+    pub const fn ramp_to(level: f32, rate: f32) -> Self {
+        let mut command = Self::new(LightingOperation::RampTo);
+        command.target_level = Some(level);
+        command.ramp_rate = Some(rate);
+        command
+    }
+
+    /// Raise the level by `step` percent.
+    pub const fn step_up(step: f32) -> Self {
+        let mut command = Self::new(LightingOperation::StepUp);
+        command.step_increment = Some(step);
+        command
+    }
+
+    /// Lower the level by `step` percent.
+    pub const fn step_down(step: f32) -> Self {
+        let mut command = Self::new(LightingOperation::StepDown);
+        command.step_increment = Some(step);
+        command
+    }
+
+    /// Stop a fade or ramp in progress at the current level.
+    pub const fn stop() -> Self {
+        Self::new(LightingOperation::Stop)
+    }
+
+    /// Sets the priority the output applies the command at.
+    pub const fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Encodes the command's fields, without an enclosing tag.
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_ctx_unsigned(w, 0, self.operation.to_u32())?;
+        if let Some(level) = self.target_level {
+            encode_ctx_real(w, 1, level)?;
+        }
+        if let Some(rate) = self.ramp_rate {
+            encode_ctx_real(w, 2, rate)?;
+        }
+        if let Some(step) = self.step_increment {
+            encode_ctx_real(w, 3, step)?;
+        }
+        if let Some(fade_time) = self.fade_time {
+            encode_ctx_unsigned(w, 4, fade_time)?;
+        }
+        if let Some(priority) = self.priority {
+            encode_ctx_unsigned(w, 5, priority as u32)?;
+        }
+        Ok(())
+    }
+
+    /// Decodes the command's fields, stopping before the tag that follows them.
+    pub fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let operation = LightingOperation::from_u32(decode_required_ctx_unsigned(r, 0)?)
+            .ok_or(DecodeError::InvalidValue)?;
+        Ok(Self {
+            operation,
+            target_level: decode_optional_ctx_real(r, 1)?,
+            ramp_rate: decode_optional_ctx_real(r, 2)?,
+            step_increment: decode_optional_ctx_real(r, 3)?,
+            fade_time: decode_optional_ctx_unsigned(r, 4)?,
+            priority: decode_optional_ctx_unsigned(r, 5)?
+                .map(|priority| u8::try_from(priority).map_err(|_| DecodeError::InvalidValue))
+                .transpose()?,
+        })
+    }
+}
+
+/// BACnetxyColor: a CIE 1931 chromaticity, the Present_Value of a Color object.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XyColor {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl XyColor {
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// Encodes the two coordinates as application-tagged REALs.
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_app_real(w, self.x)?;
+        encode_app_real(w, self.y)
+    }
+
+    pub fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let x = decode_app_real(r)?;
+        let y = decode_app_real(r)?;
+        Ok(Self { x, y })
+    }
+}
+
+/// BACnetColorOperation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ColorOperation {
+    None = 0,
+    FadeToColor = 1,
+    FadeToCct = 2,
+    RampToCct = 3,
+    StepUpCct = 4,
+    StepDownCct = 5,
+    Stop = 6,
+}
+
+impl ColorOperation {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::None),
+            1 => Some(Self::FadeToColor),
+            2 => Some(Self::FadeToCct),
+            3 => Some(Self::RampToCct),
+            4 => Some(Self::StepUpCct),
+            5 => Some(Self::StepDownCct),
+            6 => Some(Self::Stop),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetColorCommand: a color fade or a color-temperature fade, ramp or step, written to
+/// the Color_Command of Color and Color Temperature objects or through a Channel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorCommand {
+    pub operation: ColorOperation,
+    pub target_color: Option<XyColor>,
+    /// Target color temperature in Kelvin.
+    pub target_color_temperature: Option<u32>,
+    /// Fade time in milliseconds.
+    pub fade_time: Option<u32>,
+    /// Ramp rate in Kelvin per second.
+    pub ramp_rate: Option<u32>,
+    /// Step size in Kelvin.
+    pub step_increment: Option<u32>,
+}
+
+impl ColorCommand {
+    /// A command performing `operation` with every optional field left out.
+    pub const fn new(operation: ColorOperation) -> Self {
+        Self {
+            operation,
+            target_color: None,
+            target_color_temperature: None,
+            fade_time: None,
+            ramp_rate: None,
+            step_increment: None,
+        }
+    }
+
+    /// Fade to `color` over `fade_time_ms`.
+    pub const fn fade_to_color(color: XyColor, fade_time_ms: u32) -> Self {
+        let mut command = Self::new(ColorOperation::FadeToColor);
+        command.target_color = Some(color);
+        command.fade_time = Some(fade_time_ms);
+        command
+    }
+
+    /// Fade to `kelvin` over `fade_time_ms`.
+    pub const fn fade_to_cct(kelvin: u32, fade_time_ms: u32) -> Self {
+        let mut command = Self::new(ColorOperation::FadeToCct);
+        command.target_color_temperature = Some(kelvin);
+        command.fade_time = Some(fade_time_ms);
+        command
+    }
+
+    /// Ramp to `kelvin` at `rate` Kelvin per second.
+    pub const fn ramp_to_cct(kelvin: u32, rate: u32) -> Self {
+        let mut command = Self::new(ColorOperation::RampToCct);
+        command.target_color_temperature = Some(kelvin);
+        command.ramp_rate = Some(rate);
+        command
+    }
+
+    /// Raise the color temperature by `step` Kelvin.
+    pub const fn step_up_cct(step: u32) -> Self {
+        let mut command = Self::new(ColorOperation::StepUpCct);
+        command.step_increment = Some(step);
+        command
+    }
+
+    /// Lower the color temperature by `step` Kelvin.
+    pub const fn step_down_cct(step: u32) -> Self {
+        let mut command = Self::new(ColorOperation::StepDownCct);
+        command.step_increment = Some(step);
+        command
+    }
+
+    /// Stop a fade or ramp in progress.
+    pub const fn stop() -> Self {
+        Self::new(ColorOperation::Stop)
+    }
+
+    /// Encodes the command's fields, without an enclosing tag.
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_ctx_unsigned(w, 0, self.operation.to_u32())?;
+        if let Some(color) = self.target_color {
+            Tag::Opening { tag_num: 1 }.encode(w)?;
+            color.encode(w)?;
+            Tag::Closing { tag_num: 1 }.encode(w)?;
+        }
+        if let Some(kelvin) = self.target_color_temperature {
+            encode_ctx_unsigned(w, 2, kelvin)?;
+        }
+        if let Some(fade_time) = self.fade_time {
+            encode_ctx_unsigned(w, 3, fade_time)?;
+        }
+        if let Some(rate) = self.ramp_rate {
+            encode_ctx_unsigned(w, 4, rate)?;
+        }
+        if let Some(step) = self.step_increment {
+            encode_ctx_unsigned(w, 5, step)?;
+        }
+        Ok(())
+    }
+
+    /// Decodes the command's fields, stopping before the tag that follows them.
+    pub fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let operation = ColorOperation::from_u32(decode_required_ctx_unsigned(r, 0)?)
+            .ok_or(DecodeError::InvalidValue)?;
+        let target_color = if !r.is_empty() && r.peek_u8()? == OPENING_TAG_1 {
+            Tag::decode(r)?;
+            let color = XyColor::decode(r)?;
+            match Tag::decode(r)? {
+                Tag::Closing { tag_num: 1 } => Some(color),
+                _ => return Err(DecodeError::InvalidTag),
+            }
+        } else {
+            None
+        };
+        Ok(Self {
+            operation,
+            target_color,
+            target_color_temperature: decode_optional_ctx_unsigned(r, 2)?,
+            fade_time: decode_optional_ctx_unsigned(r, 3)?,
+            ramp_rate: decode_optional_ctx_unsigned(r, 4)?,
+            step_increment: decode_optional_ctx_unsigned(r, 5)?,
+        })
+    }
+}
+
+/// WriteProperty of a Lighting Output's Lighting_Command.
+///
+/// The command carries its own priority, so the request has none.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WriteLightingCommandRequest {
+    pub lighting_output: ObjectId,
+    pub command: LightingCommand,
+    pub invoke_id: u8,
+}
+
+impl WriteLightingCommandRequest {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_write_property_header(
+            w,
+            self.invoke_id,
+            self.lighting_output,
+            PropertyId::LightingCommand,
+        )?;
+        Tag::Opening { tag_num: 3 }.encode(w)?;
+        self.command.encode(w)?;
+        Tag::Closing { tag_num: 3 }.encode(w)
+    }
+}
+
+/// WriteProperty of the Color_Command of a Color or Color Temperature object.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WriteColorCommandRequest {
+    pub object_id: ObjectId,
+    pub command: ColorCommand,
+    pub invoke_id: u8,
+}
+
+impl WriteColorCommandRequest {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_write_property_header(w, self.invoke_id, self.object_id, PropertyId::ColorCommand)?;
+        Tag::Opening { tag_num: 3 }.encode(w)?;
+        self.command.encode(w)?;
+        Tag::Closing { tag_num: 3 }.encode(w)
+    }
+}
+
+/// The encoded opening tag [1].
+const OPENING_TAG_1: u8 = 0x1E;
+
+fn decode_optional_ctx_unsigned(
+    r: &mut Reader<'_>,
+    expected: u8,
+) -> Result<Option<u32>, DecodeError> {
+    take_optional_ctx_tag(r, expected)?
+        .map(|len| decode_unsigned(r, len as usize))
+        .transpose()
+}
+
+fn decode_optional_ctx_real(r: &mut Reader<'_>, expected: u8) -> Result<Option<f32>, DecodeError> {
+    take_optional_ctx_tag(r, expected)?
+        .map(|len| decode_real(r, len))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ColorCommand, LightingCommand, WriteColorCommandRequest, WriteLightingCommandRequest,
+        XyColor,
+    };
+    use crate::encoding::{reader::Reader, writer::Writer};
+    use crate::types::{ObjectId, ObjectType};
+
+    #[test]
+    fn lighting_command_round_trips() {
+        let commands = [
+            LightingCommand::fade_to(75.0, 1500).with_priority(6),
+            LightingCommand::ramp_to(10.0, 2.5),
+            LightingCommand::step_down(5.0),
+            LightingCommand::stop(),
+        ];
+        for command in commands {
+            let mut buf = [0u8; 32];
+            let mut w = Writer::new(&mut buf);
+            command.encode(&mut w).unwrap();
+            let mut r = Reader::new(w.as_written());
+            assert_eq!(LightingCommand::decode(&mut r).unwrap(), command);
+            assert!(r.is_empty());
+        }
+    }
+
+    #[test]
+    fn write_lighting_command_encodes_a_step() {
+        let req = WriteLightingCommandRequest {
+            lighting_output: ObjectId::new(ObjectType::LightingOutput, 2),
+            command: LightingCommand::step_up(10.0),
+            invoke_id: 3,
+        };
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        req.encode(&mut w).unwrap();
+        assert_eq!(
+            w.as_written(),
+            &[
+                0x00, 0x05, 3, 0x0F, // WriteProperty
+                0x0C, 0x0D, 0x80, 0x00, 0x02, // [0] lighting-output,2
+                0x1A, 0x01, 0x7C, // [1] lighting-command (380)
+                0x3E, // [3] {
+                0x09, 0x03, // operation step-up
+                0x3C, 0x41, 0x20, 0x00, 0x00, // step-increment 10.0
+                0x3F, // }
+            ]
+        );
+    }
+
+    #[test]
+    fn color_command_encodes_the_target_color_as_a_constructed_value() {
+        let req = WriteColorCommandRequest {
+            object_id: ObjectId::new(ObjectType::Color, 1),
+            command: ColorCommand::fade_to_color(XyColor::new(0.5, 0.25), 1000),
+            invoke_id: 9,
+        };
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        req.encode(&mut w).unwrap();
+        assert_eq!(
+            &w.as_written()[9..],
+            &[
+                0x1B, 0x40, 0x00, 0x1E, // [1] color-command (4194334)
+                0x3E, // [3] {
+                0x09, 0x01, // operation fade-to-color
+                0x1E, // [1] {
+                0x44, 0x3F, 0x00, 0x00, 0x00, // x 0.5
+                0x44, 0x3E, 0x80, 0x00, 0x00, // y 0.25
+                0x1F, // }
+                0x3A, 0x03, 0xE8, // [3] fade-time 1000
+                0x3F, // }
+            ]
+        );
+    }
+
+    #[test]
+    fn color_command_round_trips() {
+        let commands = [
+            ColorCommand::fade_to_color(XyColor::new(0.3127, 0.329), 2000),
+            ColorCommand::fade_to_cct(2700, 500),
+            ColorCommand::ramp_to_cct(6500, 100),
+            ColorCommand::step_up_cct(250),
+            ColorCommand::stop(),
+        ];
+        for command in commands {
+            let mut buf = [0u8; 32];
+            let mut w = Writer::new(&mut buf);
+            command.encode(&mut w).unwrap();
+            let mut r = Reader::new(w.as_written());
+            assert_eq!(ColorCommand::decode(&mut r).unwrap(), command);
+            assert!(r.is_empty());
+        }
+    }
+}
@@ -55,6 +55,32 @@ fn encode_list_element_request(
     w: &mut Writer<'_>,
     req: &AddListElementRequest<'_>,
     service_choice: u8,
+) -> Result<(), EncodeError> {
+    encode_list_element_header(
+        w,
+        req.invoke_id,
+        service_choice,
+        req.object_id,
+        req.property_id,
+        req.array_index,
+    )?;
+    Tag::Opening { tag_num: 3 }.encode(w)?;
+    for value in req.elements {
+        encode_application_data_value(w, value)?;
+    }
+    Tag::Closing { tag_num: 3 }.encode(w)?;
+    Ok(())
+}
+
+/// Encodes the request header and the list's object, property and array index, for list
+/// element requests whose elements are not plain application values.
+pub(crate) fn encode_list_element_header(
+    w: &mut Writer<'_>,
+    invoke_id: u8,
+    service_choice: u8,
+    object_id: ObjectId,
+    property_id: PropertyId,
+    array_index: Option<u32>,
 ) -> Result<(), EncodeError> {
     ConfirmedRequestHeader {
         segmented: false,
@@ -62,22 +88,17 @@ fn encode_list_element_request(
         segmented_response_accepted: false,
         max_segments: 0,
         max_apdu: 5,
-        invoke_id: req.invoke_id,
+        invoke_id,
         sequence_number: None,
         proposed_window_size: None,
         service_choice,
     }
     .encode(w)?;
-    encode_ctx_object_id(w, 0, req.object_id.raw())?;
-    encode_ctx_unsigned(w, 1, req.property_id.to_u32())?;
-    if let Some(array_index) = req.array_index {
+    encode_ctx_object_id(w, 0, object_id.raw())?;
+    encode_ctx_unsigned(w, 1, property_id.to_u32())?;
+    if let Some(array_index) = array_index {
         encode_ctx_unsigned(w, 2, array_index)?;
     }
-    Tag::Opening { tag_num: 3 }.encode(w)?;
-    for value in req.elements {
-        encode_application_data_value(w, value)?;
-    }
-    Tag::Closing { tag_num: 3 }.encode(w)?;
     Ok(())
 }
 
@@ -1,37 +1,146 @@
+pub mod access_control;
+pub mod accumulator;
 pub mod acknowledge_alarm;
 pub mod alarm_summary;
 pub mod atomic_read_file;
 pub mod atomic_write_file;
+pub mod calendar;
+pub mod channel;
+pub mod control_loop;
 pub mod cov_notification;
+pub mod cov_subscription;
 pub mod device_management;
 pub mod enrollment_summary;
+pub mod event_enrollment;
 pub mod event_information;
 pub mod event_notification;
+pub mod global_group;
 pub mod i_am;
+pub mod lift;
+pub mod lighting;
 pub mod list_element;
 pub mod object_management;
 pub mod private_transfer;
 pub mod read_property;
 pub mod read_property_multiple;
 pub mod read_range;
+pub mod schedule;
 pub mod subscribe_cov;
 pub mod subscribe_cov_property;
 pub mod time_synchronization;
+#[cfg(feature = "arena")]
+pub mod value_arena;
 pub mod value_codec;
 pub mod who_has;
 pub mod who_is;
 pub mod write_property;
 pub mod write_property_multiple;
 
-#[cfg(feature = "alloc")]
-use crate::encoding::{primitives::decode_unsigned, reader::Reader, tag::Tag};
-#[cfg(feature = "alloc")]
+use crate::encoding::primitives::decode_unsigned;
+use crate::encoding::{reader::Reader, tag::Tag};
 use crate::types::ObjectId;
-#[cfg(feature = "alloc")]
 use crate::DecodeError;
 
+/// How acknowledgement decoders treat tags outside the standard layout.
+///
+/// Some devices emit the optional array index ahead of the property identifier, or add
+/// vendor context tags between the standard ones. [`Strict`](Self::Strict) rejects such
+/// responses; [`Lenient`](Self::Lenient) accepts the optional tags in any order and skips
+/// unknown context tags, reporting each as a [`SkippedTag`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DecodeMode {
+    /// Reject anything out of place.
+    #[default]
+    Strict,
+    /// Accept optional tags in any order and skip unknown context tags.
+    Lenient,
+}
+
+/// A tag passed over by a [`DecodeMode::Lenient`] decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkippedTag {
+    /// Context tag number.
+    pub tag_num: u8,
+    /// Whether it opened a constructed value, skipped up to its closing tag.
+    pub constructed: bool,
+}
+
+/// Skips the context tag `tag` (its data, or the whole constructed value it opens).
+pub(crate) fn skip_context_tag(r: &mut Reader<'_>, tag: Tag) -> Result<SkippedTag, DecodeError> {
+    match tag {
+        Tag::Context { tag_num, len } => {
+            r.read_exact(len as usize)?;
+            Ok(SkippedTag {
+                tag_num,
+                constructed: false,
+            })
+        }
+        Tag::Opening { tag_num } => {
+            let mut depth = 1usize;
+            while depth > 0 {
+                match Tag::decode(r)? {
+                    Tag::Opening { .. } => depth += 1,
+                    Tag::Closing { .. } => depth -= 1,
+                    Tag::Application { len, .. } | Tag::Context { len, .. } => {
+                        r.read_exact(len as usize)?;
+                    }
+                }
+            }
+            Ok(SkippedTag {
+                tag_num,
+                constructed: true,
+            })
+        }
+        _ => Err(DecodeError::InvalidTag),
+    }
+}
+
+/// Consumes context tag `expected` and returns its length if it is next, leaving the
+/// reader untouched otherwise.
+pub(crate) fn take_optional_ctx_tag(
+    r: &mut Reader<'_>,
+    expected: u8,
+) -> Result<Option<u32>, DecodeError> {
+    if r.is_empty() {
+        return Ok(None);
+    }
+    let mut peek = *r;
+    match Tag::decode(&mut peek)? {
+        Tag::Context { tag_num, len } if tag_num == expected => {
+            *r = peek;
+            Ok(Some(len))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Decodes the object and property identifiers (and any array index) opening a
+/// ReadProperty-ACK, returning the object.
+pub(crate) fn decode_ack_object(r: &mut Reader<'_>) -> Result<ObjectId, DecodeError> {
+    let object_id = match Tag::decode(r)? {
+        Tag::Context { tag_num: 0, len } => ObjectId::from_raw(decode_unsigned(r, len as usize)?),
+        _ => return Err(DecodeError::InvalidTag),
+    };
+    match Tag::decode(r)? {
+        Tag::Context { tag_num: 1, len } => r.read_exact(len as usize)?,
+        _ => return Err(DecodeError::InvalidTag),
+    };
+    if let Some(len) = take_optional_ctx_tag(r, 2)? {
+        r.read_exact(len as usize)?;
+    }
+    Ok(object_id)
+}
+
+/// Consumes the next tag, failing unless it is `expected`.
+pub(crate) fn expect_tag(r: &mut Reader<'_>, expected: Tag) -> Result<(), DecodeError> {
+    if Tag::decode(r)? == expected {
+        Ok(())
+    } else {
+        Err(DecodeError::InvalidTag)
+    }
+}
+
 /// Decode a required context-tagged unsigned integer at the expected tag number.
-#[cfg(feature = "alloc")]
 pub(crate) fn decode_required_ctx_unsigned(
     r: &mut Reader<'_>,
     expected_tag_num: u8,
@@ -44,6 +153,26 @@ pub(crate) fn decode_required_ctx_unsigned(
     }
 }
 
+/// Decode a required context-tagged REAL at the expected tag number.
+pub(crate) fn decode_required_ctx_real(
+    r: &mut Reader<'_>,
+    expected_tag_num: u8,
+) -> Result<f32, DecodeError> {
+    match Tag::decode(r)? {
+        Tag::Context { tag_num, len } if tag_num == expected_tag_num => decode_real(r, len),
+        _ => Err(DecodeError::InvalidTag),
+    }
+}
+
+/// Decodes the `len`-octet data of a REAL whose tag has been read.
+pub(crate) fn decode_real(r: &mut Reader<'_>, len: u32) -> Result<f32, DecodeError> {
+    if len != 4 {
+        return Err(DecodeError::InvalidLength);
+    }
+    let bytes = r.read_exact(4)?;
+    Ok(f32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
 /// Decode a required context-tagged BACnet object identifier at the expected tag number.
 #[cfg(feature = "alloc")]
 pub(crate) fn decode_required_ctx_object_id(
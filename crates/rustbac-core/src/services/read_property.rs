@@ -5,7 +5,7 @@ use crate::encoding::{
     tag::Tag,
     writer::Writer,
 };
-use crate::services::value_codec::decode_application_data_value;
+use crate::services::{skip_context_tag, value_codec, DecodeMode, SkippedTag};
 use crate::types::{DataValue, ObjectId, PropertyId};
 use crate::{DecodeError, EncodeError};
 
@@ -53,45 +53,133 @@ pub struct ReadPropertyAck<'a> {
 
 impl<'a> ReadPropertyAck<'a> {
     pub fn decode_after_header(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
-        let object_id = match Tag::decode(r)? {
-            Tag::Context { tag_num: 0, len } => {
-                ObjectId::from_raw(decode_unsigned(r, len as usize)?)
-            }
-            _ => return Err(DecodeError::InvalidTag),
-        };
-
-        let property_id = match Tag::decode(r)? {
-            Tag::Context { tag_num: 1, len } => {
-                PropertyId::from_u32(decode_unsigned(r, len as usize)?)
-            }
-            _ => return Err(DecodeError::InvalidTag),
-        };
+        Self::decode_after_header_with(r, DecodeMode::Strict, &mut |_| {})
+    }
 
-        let next = Tag::decode(r)?;
-        let (array_index, value_start_tag) = match next {
-            Tag::Context { tag_num: 2, len } => {
-                let idx = decode_unsigned(r, len as usize)?;
-                (Some(idx), Tag::decode(r)?)
+    /// Decodes the ack in `mode`, calling `on_skip` for each tag a lenient decode passes
+    /// over.
+    pub fn decode_after_header_with(
+        r: &mut Reader<'a>,
+        mode: DecodeMode,
+        on_skip: &mut dyn FnMut(SkippedTag),
+    ) -> Result<Self, DecodeError> {
+        let lenient = mode == DecodeMode::Lenient;
+        let mut object_id = None;
+        let mut property_id = None;
+        let mut array_index = None;
+        loop {
+            let tag = Tag::decode(r)?;
+            match tag {
+                Tag::Opening { tag_num: 3 } if object_id.is_some() && property_id.is_some() => {
+                    break
+                }
+                Tag::Context { tag_num: 0, len } if object_id.is_none() => {
+                    object_id = Some(ObjectId::from_raw(decode_unsigned(r, len as usize)?));
+                }
+                Tag::Context { tag_num: 1, len }
+                    if property_id.is_none() && (lenient || object_id.is_some()) =>
+                {
+                    property_id = Some(PropertyId::from_u32(decode_unsigned(r, len as usize)?));
+                }
+                Tag::Context { tag_num: 2, len }
+                    if array_index.is_none() && (lenient || property_id.is_some()) =>
+                {
+                    array_index = Some(decode_unsigned(r, len as usize)?);
+                }
+                Tag::Opening { tag_num: 3 } => return Err(DecodeError::InvalidTag),
+                _ if lenient => on_skip(skip_context_tag(r, tag)?),
+                _ => return Err(DecodeError::InvalidTag),
             }
-            other => (None, other),
-        };
-
-        if value_start_tag != (Tag::Opening { tag_num: 3 }) {
-            return Err(DecodeError::InvalidTag);
         }
 
-        let value = decode_application_data_value(r)?;
-
-        match Tag::decode(r)? {
-            Tag::Closing { tag_num: 3 } => {}
-            _ => return Err(DecodeError::InvalidTag),
-        }
+        let value = decode_property_value(r)?;
 
         Ok(Self {
-            object_id,
-            property_id,
+            object_id: object_id.ok_or(DecodeError::InvalidTag)?,
+            property_id: property_id.ok_or(DecodeError::InvalidTag)?,
             array_index,
             value,
         })
     }
 }
+
+/// Decodes the contents of the property-value tags `[3]` up to and including the closing
+/// tag. A whole array or list read carries zero or more values there; those are returned
+/// as [`DataValue::Constructed`] with `tag_num` 3.
+#[cfg(feature = "alloc")]
+fn decode_property_value<'a>(r: &mut Reader<'a>) -> Result<DataValue<'a>, DecodeError> {
+    let mut values = alloc::vec::Vec::new();
+    loop {
+        let tag = Tag::decode(r)?;
+        if tag == (Tag::Closing { tag_num: 3 }) {
+            break;
+        }
+        values.push(value_codec::decode_application_data_value_from_tag(r, tag)?);
+    }
+    if values.len() == 1 {
+        return Ok(values.remove(0));
+    }
+    Ok(DataValue::Constructed { tag_num: 3, values })
+}
+
+#[cfg(not(feature = "alloc"))]
+fn decode_property_value<'a>(r: &mut Reader<'a>) -> Result<DataValue<'a>, DecodeError> {
+    let value = value_codec::decode_application_data_value(r)?;
+    match Tag::decode(r)? {
+        Tag::Closing { tag_num: 3 } => Ok(value),
+        _ => Err(DecodeError::InvalidTag),
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::ReadPropertyAck;
+    use crate::encoding::{
+        primitives::{encode_app_real, encode_ctx_object_id, encode_ctx_unsigned},
+        reader::Reader,
+        tag::Tag,
+        writer::Writer,
+    };
+    use crate::services::{DecodeMode, SkippedTag};
+    use crate::types::{DataValue, ObjectId, ObjectType, PropertyId};
+    use alloc::vec::Vec;
+
+    /// An ack with the array index ahead of the property and a vendor tag [7].
+    fn out_of_order_ack(buf: &mut [u8]) -> &[u8] {
+        let mut w = Writer::new(buf);
+        encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::AnalogValue, 2).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 2, 4).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::PriorityArray.to_u32()).unwrap();
+        encode_ctx_unsigned(&mut w, 7, 99).unwrap();
+        Tag::Opening { tag_num: 3 }.encode(&mut w).unwrap();
+        encode_app_real(&mut w, 21.5).unwrap();
+        Tag::Closing { tag_num: 3 }.encode(&mut w).unwrap();
+        let len = w.as_written().len();
+        &buf[..len]
+    }
+
+    #[test]
+    fn lenient_decode_reorders_and_skips_vendor_tags() {
+        let mut buf = [0u8; 64];
+        let data = out_of_order_ack(&mut buf);
+        assert!(ReadPropertyAck::decode_after_header(&mut Reader::new(data)).is_err());
+
+        let mut skipped = Vec::new();
+        let ack = ReadPropertyAck::decode_after_header_with(
+            &mut Reader::new(data),
+            DecodeMode::Lenient,
+            &mut |tag| skipped.push(tag),
+        )
+        .unwrap();
+        assert_eq!(ack.property_id, PropertyId::PriorityArray);
+        assert_eq!(ack.array_index, Some(4));
+        assert_eq!(ack.value, DataValue::Real(21.5));
+        assert_eq!(
+            skipped,
+            alloc::vec![SkippedTag {
+                tag_num: 7,
+                constructed: false
+            }]
+        );
+    }
+}
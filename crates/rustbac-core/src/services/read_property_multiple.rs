@@ -1,18 +1,18 @@
 use crate::apdu::ConfirmedRequestHeader;
-use crate::encoding::{
-    primitives::{encode_ctx_object_id, encode_ctx_unsigned},
-    tag::Tag,
-    writer::Writer,
-};
-use crate::types::{ObjectId, PropertyId};
+use crate::encoding::{primitives::encode_ctx_object_id, tag::Tag, writer::Writer};
+use crate::types::ObjectId;
 use crate::EncodeError;
 
+pub use crate::types::PropertyReference;
+
 #[cfg(feature = "alloc")]
 use crate::encoding::{primitives::decode_unsigned, reader::Reader};
 #[cfg(feature = "alloc")]
 use crate::services::value_codec::decode_application_data_value_from_tag;
 #[cfg(feature = "alloc")]
-use crate::types::DataValue;
+use crate::services::{skip_context_tag, DecodeMode, SkippedTag};
+#[cfg(feature = "alloc")]
+use crate::types::{DataValue, PropertyId};
 #[cfg(feature = "alloc")]
 use crate::DecodeError;
 #[cfg(feature = "alloc")]
@@ -20,12 +20,6 @@ use alloc::vec::Vec;
 
 pub const SERVICE_READ_PROPERTY_MULTIPLE: u8 = 0x0E;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct PropertyReference {
-    pub property_id: PropertyId,
-    pub array_index: Option<u32>,
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ReadAccessSpecification<'a> {
     pub object_id: ObjectId,
@@ -57,10 +51,7 @@ impl<'a> ReadPropertyMultipleRequest<'a> {
             encode_ctx_object_id(w, 0, spec.object_id.raw())?;
             Tag::Opening { tag_num: 1 }.encode(w)?;
             for prop in spec.properties {
-                encode_ctx_unsigned(w, 0, prop.property_id.to_u32())?;
-                if let Some(idx) = prop.array_index {
-                    encode_ctx_unsigned(w, 1, idx)?;
-                }
+                prop.encode(w)?;
             }
             Tag::Closing { tag_num: 1 }.encode(w)?;
         }
@@ -77,11 +68,24 @@ pub struct ReadResultElement<'a> {
     pub value: DataValue<'a>,
 }
 
+/// A property the device could not read, from the error block of a read result.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropertyAccessError {
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub error_class: u32,
+    pub error_code: u32,
+}
+
 #[cfg(feature = "alloc")]
 #[derive(Debug, Clone, PartialEq)]
 pub struct ReadAccessResult<'a> {
     pub object_id: ObjectId,
     pub results: Vec<ReadResultElement<'a>>,
+    /// Properties that could not be read; only filled by
+    /// [`ReadPropertyMultipleAck::decode_after_header_with_errors`].
+    pub errors: Vec<PropertyAccessError>,
 }
 
 #[cfg(feature = "alloc")]
@@ -93,72 +97,111 @@ pub struct ReadPropertyMultipleAck<'a> {
 #[cfg(feature = "alloc")]
 impl<'a> ReadPropertyMultipleAck<'a> {
     pub fn decode_after_header(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        Self::decode_after_header_with(r, DecodeMode::Strict, &mut |_| {})
+    }
+
+    /// Decodes the ack in `mode`, calling `on_skip` for each tag a lenient decode passes
+    /// over. A property the device could not read fails the decode with
+    /// [`DecodeError::Unsupported`].
+    pub fn decode_after_header_with(
+        r: &mut Reader<'a>,
+        mode: DecodeMode,
+        on_skip: &mut dyn FnMut(SkippedTag),
+    ) -> Result<Self, DecodeError> {
+        Self::decode(r, mode, on_skip, false)
+    }
+
+    /// Like [`decode_after_header_with`](Self::decode_after_header_with), but a property
+    /// the device could not read is collected in [`ReadAccessResult::errors`].
+    ///
+    /// Use this for ALL, REQUIRED and OPTIONAL reads, whose results commonly mix values
+    /// with errors for properties that cannot be read.
+    pub fn decode_after_header_with_errors(
+        r: &mut Reader<'a>,
+        mode: DecodeMode,
+        on_skip: &mut dyn FnMut(SkippedTag),
+    ) -> Result<Self, DecodeError> {
+        Self::decode(r, mode, on_skip, true)
+    }
+
+    fn decode(
+        r: &mut Reader<'a>,
+        mode: DecodeMode,
+        on_skip: &mut dyn FnMut(SkippedTag),
+        keep_errors: bool,
+    ) -> Result<Self, DecodeError> {
+        let lenient = mode == DecodeMode::Lenient;
         let mut all_results = Vec::new();
 
         while !r.is_empty() {
-            let object_id = match Tag::decode(r)? {
-                Tag::Context { tag_num: 0, len } => {
-                    ObjectId::from_raw(decode_unsigned(r, len as usize)?)
+            let mut object_id = None;
+            loop {
+                let tag = Tag::decode(r)?;
+                match tag {
+                    Tag::Opening { tag_num: 1 } if object_id.is_some() => break,
+                    Tag::Context { tag_num: 0, len } if object_id.is_none() => {
+                        object_id = Some(ObjectId::from_raw(decode_unsigned(r, len as usize)?));
+                    }
+                    Tag::Opening { tag_num: 1 } => return Err(DecodeError::InvalidTag),
+                    _ if lenient => on_skip(skip_context_tag(r, tag)?),
+                    _ => return Err(DecodeError::InvalidTag),
                 }
-                _ => return Err(DecodeError::InvalidTag),
-            };
-
-            match Tag::decode(r)? {
-                Tag::Opening { tag_num: 1 } => {}
-                _ => return Err(DecodeError::InvalidTag),
             }
+            let object_id = object_id.ok_or(DecodeError::InvalidTag)?;
 
             let mut elements = Vec::new();
+            let mut errors = Vec::new();
             loop {
-                let tag = Tag::decode(r)?;
+                let mut tag = Tag::decode(r)?;
                 if tag == (Tag::Closing { tag_num: 1 }) {
                     break;
                 }
 
-                let property_id = match tag {
-                    Tag::Context { tag_num: 2, len } => {
-                        PropertyId::from_u32(decode_unsigned(r, len as usize)?)
-                    }
-                    _ => return Err(DecodeError::InvalidTag),
-                };
-
-                let next = Tag::decode(r)?;
-                let (array_index, read_result_open) = match next {
-                    Tag::Context { tag_num: 3, len } => {
-                        let idx = decode_unsigned(r, len as usize)?;
-                        (Some(idx), Tag::decode(r)?)
+                let mut property_id = None;
+                let mut array_index = None;
+                loop {
+                    match tag {
+                        Tag::Opening { tag_num: 4 | 5 } if property_id.is_some() => break,
+                        Tag::Context { tag_num: 2, len } if property_id.is_none() => {
+                            property_id =
+                                Some(PropertyId::from_u32(decode_unsigned(r, len as usize)?));
+                        }
+                        Tag::Context { tag_num: 3, len }
+                            if array_index.is_none() && (lenient || property_id.is_some()) =>
+                        {
+                            array_index = Some(decode_unsigned(r, len as usize)?);
+                        }
+                        Tag::Opening { tag_num: 4 | 5 } | Tag::Closing { .. } => {
+                            return Err(DecodeError::InvalidTag)
+                        }
+                        _ if lenient => on_skip(skip_context_tag(r, tag)?),
+                        _ => return Err(DecodeError::InvalidTag),
                     }
-                    other => (None, other),
-                };
-
-                if read_result_open != (Tag::Opening { tag_num: 4 }) {
-                    return Err(DecodeError::InvalidTag);
+                    tag = Tag::decode(r)?;
                 }
+                let property_id = property_id.ok_or(DecodeError::InvalidTag)?;
 
-                let value_or_error = Tag::decode(r)?;
-                let value = if value_or_error == (Tag::Opening { tag_num: 5 }) {
-                    // Property access error block [5] with errorClass [0], errorCode [1].
-                    // Phase 1: decode and surface as unsupported response.
-                    let class_tag = Tag::decode(r)?;
-                    let code_tag = Tag::decode(r)?;
-                    let close_tag = Tag::decode(r)?;
-                    match (class_tag, code_tag, close_tag) {
-                        (
-                            Tag::Context { tag_num: 0, .. },
-                            Tag::Context { tag_num: 1, .. },
-                            Tag::Closing { tag_num: 5 },
-                        ) => return Err(DecodeError::Unsupported),
-                        _ => return Err(DecodeError::InvalidTag),
+                if tag == (Tag::Opening { tag_num: 5 }) {
+                    // Property access error [5] with errorClass [0], errorCode [1].
+                    let (error_class, error_code) = decode_error_block(r)?;
+                    if !keep_errors {
+                        return Err(DecodeError::Unsupported);
                     }
-                } else {
-                    decode_application_data_value_from_tag(r, value_or_error)?
-                };
+                    errors.push(PropertyAccessError {
+                        property_id,
+                        array_index,
+                        error_class,
+                        error_code,
+                    });
+                    continue;
+                }
 
+                let first = Tag::decode(r)?;
+                let value = decode_application_data_value_from_tag(r, first)?;
                 match Tag::decode(r)? {
                     Tag::Closing { tag_num: 4 } => {}
                     _ => return Err(DecodeError::InvalidTag),
                 }
-
                 elements.push(ReadResultElement {
                     property_id,
                     array_index,
@@ -169,6 +212,7 @@ impl<'a> ReadPropertyMultipleAck<'a> {
             all_results.push(ReadAccessResult {
                 object_id,
                 results: elements,
+                errors,
             });
         }
 
@@ -178,6 +222,24 @@ impl<'a> ReadPropertyMultipleAck<'a> {
     }
 }
 
+/// Decodes the error class and code of an error block after its opening tag [5],
+/// through its closing tag.
+#[cfg(feature = "alloc")]
+fn decode_error_block(r: &mut Reader<'_>) -> Result<(u32, u32), DecodeError> {
+    let error_class = match Tag::decode(r)? {
+        Tag::Context { tag_num: 0, len } => decode_unsigned(r, len as usize)?,
+        _ => return Err(DecodeError::InvalidTag),
+    };
+    let error_code = match Tag::decode(r)? {
+        Tag::Context { tag_num: 1, len } => decode_unsigned(r, len as usize)?,
+        _ => return Err(DecodeError::InvalidTag),
+    };
+    match Tag::decode(r)? {
+        Tag::Closing { tag_num: 5 } => Ok((error_class, error_code)),
+        _ => Err(DecodeError::InvalidTag),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
@@ -257,4 +319,93 @@ mod tests {
         assert_eq!(parsed.results.len(), 1);
         assert_eq!(parsed.results[0].results.len(), 1);
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn property_access_errors_are_kept_on_request() {
+        use super::{PropertyAccessError, ReadPropertyMultipleAck};
+        use crate::encoding::primitives::{encode_app_real, encode_ctx_unsigned};
+        use crate::encoding::tag::Tag;
+        use crate::services::DecodeMode;
+
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_unsigned(&mut w, 0, ObjectId::new(ObjectType::AnalogInput, 1).raw()).unwrap();
+        Tag::Opening { tag_num: 1 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 2, PropertyId::PresentValue.to_u32()).unwrap();
+        Tag::Opening { tag_num: 4 }.encode(&mut w).unwrap();
+        encode_app_real(&mut w, 42.0).unwrap();
+        Tag::Closing { tag_num: 4 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 2, PropertyId::ProfileName.to_u32()).unwrap();
+        Tag::Opening { tag_num: 5 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 0, 2).unwrap();
+        encode_ctx_unsigned(&mut w, 1, 27).unwrap();
+        Tag::Closing { tag_num: 5 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 1 }.encode(&mut w).unwrap();
+        let data = w.as_written();
+
+        assert!(ReadPropertyMultipleAck::decode_after_header(&mut Reader::new(data)).is_err());
+        let ack = ReadPropertyMultipleAck::decode_after_header_with_errors(
+            &mut Reader::new(data),
+            DecodeMode::Strict,
+            &mut |_| {},
+        )
+        .unwrap();
+        assert_eq!(ack.results[0].results.len(), 1);
+        assert_eq!(
+            ack.results[0].errors,
+            alloc::vec![PropertyAccessError {
+                property_id: PropertyId::ProfileName,
+                array_index: None,
+                error_class: 2,
+                error_code: 27,
+            }]
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn lenient_decode_skips_vendor_tags_between_results() {
+        use super::ReadPropertyMultipleAck;
+        use crate::encoding::primitives::{encode_app_real, encode_ctx_unsigned};
+        use crate::encoding::tag::Tag;
+        use crate::services::{DecodeMode, SkippedTag};
+        use crate::types::DataValue;
+
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_unsigned(&mut w, 0, ObjectId::new(ObjectType::Device, 1).raw()).unwrap();
+        Tag::Opening { tag_num: 1 }.encode(&mut w).unwrap();
+        encode_ctx_unsigned(&mut w, 3, 1).unwrap();
+        encode_ctx_unsigned(&mut w, 2, PropertyId::PresentValue.to_u32()).unwrap();
+        Tag::Opening { tag_num: 9 }.encode(&mut w).unwrap();
+        encode_app_real(&mut w, 1.0).unwrap();
+        Tag::Closing { tag_num: 9 }.encode(&mut w).unwrap();
+        Tag::Opening { tag_num: 4 }.encode(&mut w).unwrap();
+        encode_app_real(&mut w, 42.0).unwrap();
+        Tag::Closing { tag_num: 4 }.encode(&mut w).unwrap();
+        Tag::Closing { tag_num: 1 }.encode(&mut w).unwrap();
+        let data = w.as_written();
+
+        assert!(ReadPropertyMultipleAck::decode_after_header(&mut Reader::new(data)).is_err());
+
+        let mut skipped = alloc::vec::Vec::new();
+        let ack = ReadPropertyMultipleAck::decode_after_header_with(
+            &mut Reader::new(data),
+            DecodeMode::Lenient,
+            &mut |tag| skipped.push(tag),
+        )
+        .unwrap();
+        let element = &ack.results[0].results[0];
+        assert_eq!(element.property_id, PropertyId::PresentValue);
+        assert_eq!(element.array_index, Some(1));
+        assert_eq!(element.value, DataValue::Real(42.0));
+        assert_eq!(
+            skipped,
+            alloc::vec![SkippedTag {
+                tag_num: 9,
+                constructed: true
+            }]
+        );
+    }
 }
@@ -1,24 +1,32 @@
 use crate::apdu::ConfirmedRequestHeader;
 use crate::encoding::{
     primitives::{
-        encode_app_signed, encode_app_unsigned, encode_ctx_object_id, encode_ctx_unsigned,
+        decode_signed, decode_unsigned, encode_app_enumerated, encode_app_signed,
+        encode_app_unsigned, encode_ctx_object_id, encode_ctx_real, encode_ctx_signed,
+        encode_ctx_unsigned,
     },
+    reader::Reader,
     tag::{AppTag, Tag},
     writer::Writer,
 };
-use crate::types::{Date, ObjectId, PropertyId, Time};
-use crate::EncodeError;
+use crate::services::{decode_real, expect_tag};
+use crate::types::{BitString, Date, ObjectId, PropertyId, Time};
+use crate::{DecodeError, EncodeError};
 
 #[cfg(feature = "alloc")]
-use crate::encoding::{primitives::decode_unsigned, reader::Reader};
+use crate::encoding::primitives::{decode_app_unsigned, decode_ctx_character_string};
+#[cfg(feature = "alloc")]
+use crate::services::acknowledge_alarm::TimeStamp;
+#[cfg(feature = "alloc")]
+use crate::services::event_notification::decode_required_ctx_timestamp;
 #[cfg(feature = "alloc")]
 use crate::services::value_codec::decode_application_data_value_from_tag;
 #[cfg(feature = "alloc")]
-use crate::types::{BitString, DataValue};
+use crate::services::{decode_required_ctx_unsigned, take_optional_ctx_tag};
 #[cfg(feature = "alloc")]
-use crate::DecodeError;
+use crate::types::{DataValue, ObjectType, PropertyReference};
 #[cfg(feature = "alloc")]
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 
 pub const SERVICE_READ_RANGE: u8 = 0x1A;
 
@@ -178,6 +186,783 @@ impl ReadRangeRequest {
     }
 }
 
+/// The value recorded in a [`LogRecord`] (the `log-datum` choice).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogDatum<'a> {
+    /// A change of the log's own status (log-disabled, buffer-purged, log-interrupted).
+    LogStatus(BitString<'a>),
+    Boolean(bool),
+    Real(f32),
+    Enumerated(u32),
+    Unsigned(u32),
+    Signed(i32),
+    BitString(BitString<'a>),
+    Null,
+    /// Reading the monitored property failed.
+    Failure {
+        error_class: u32,
+        error_code: u32,
+    },
+    /// The device clock was changed by this many seconds.
+    TimeChange(f32),
+}
+
+impl<'a> LogDatum<'a> {
+    /// The datum as a generic value; a failure is `Constructed { tag_num: 8 }` of its
+    /// error class and code.
+    #[cfg(feature = "alloc")]
+    pub fn to_data_value(&self) -> DataValue<'a> {
+        match *self {
+            LogDatum::LogStatus(bits) | LogDatum::BitString(bits) => DataValue::BitString(bits),
+            LogDatum::Boolean(v) => DataValue::Boolean(v),
+            LogDatum::Real(v) | LogDatum::TimeChange(v) => DataValue::Real(v),
+            LogDatum::Enumerated(v) => DataValue::Enumerated(v),
+            LogDatum::Unsigned(v) => DataValue::Unsigned(v),
+            LogDatum::Signed(v) => DataValue::Signed(v),
+            LogDatum::Null => DataValue::Null,
+            LogDatum::Failure {
+                error_class,
+                error_code,
+            } => DataValue::Constructed {
+                tag_num: 8,
+                values: alloc::vec![
+                    DataValue::Enumerated(error_class),
+                    DataValue::Enumerated(error_code),
+                ],
+            },
+        }
+    }
+}
+
+/// One entry of a Trend Log's `Log_Buffer` (BACnetLogRecord).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogRecord<'a> {
+    pub date: Date,
+    pub time: Time,
+    pub datum: LogDatum<'a>,
+    pub status_flags: Option<BitString<'a>>,
+}
+
+impl<'a> LogRecord<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_log_timestamp(w, self.date, self.time)?;
+
+        Tag::Opening { tag_num: 1 }.encode(w)?;
+        match self.datum {
+            LogDatum::LogStatus(bits) => encode_ctx_bit_string(w, 0, bits)?,
+            LogDatum::Boolean(v) => {
+                Tag::Context { tag_num: 1, len: 1 }.encode(w)?;
+                w.write_u8(u8::from(v))?;
+            }
+            LogDatum::Real(v) => encode_ctx_real(w, 2, v)?,
+            LogDatum::Enumerated(v) => encode_ctx_unsigned(w, 3, v)?,
+            LogDatum::Unsigned(v) => encode_ctx_unsigned(w, 4, v)?,
+            LogDatum::Signed(v) => encode_ctx_signed(w, 5, v)?,
+            LogDatum::BitString(bits) => encode_ctx_bit_string(w, 6, bits)?,
+            LogDatum::Null => Tag::Context { tag_num: 7, len: 0 }.encode(w)?,
+            LogDatum::Failure {
+                error_class,
+                error_code,
+            } => {
+                Tag::Opening { tag_num: 8 }.encode(w)?;
+                encode_app_enumerated(w, error_class)?;
+                encode_app_enumerated(w, error_code)?;
+                Tag::Closing { tag_num: 8 }.encode(w)?;
+            }
+            LogDatum::TimeChange(v) => encode_ctx_real(w, 9, v)?,
+        }
+        Tag::Closing { tag_num: 1 }.encode(w)?;
+
+        if let Some(flags) = self.status_flags {
+            encode_ctx_bit_string(w, 2, flags)?;
+        }
+        Ok(())
+    }
+
+    /// Decodes a record whose opening `[0]` tag has already been read.
+    pub fn decode_after_opening(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let (date, time) = decode_log_timestamp(r)?;
+        expect_tag(r, Tag::Opening { tag_num: 1 })?;
+
+        let datum = match Tag::decode(r)? {
+            Tag::Context { tag_num: 0, len } => LogDatum::LogStatus(decode_bit_string(r, len)?),
+            Tag::Context { tag_num: 1, len: 1 } => LogDatum::Boolean(r.read_u8()? != 0),
+            Tag::Context { tag_num: 2, len: 4 } => LogDatum::Real(decode_real(r, 4)?),
+            Tag::Context { tag_num: 3, len } => {
+                LogDatum::Enumerated(decode_unsigned(r, len as usize)?)
+            }
+            Tag::Context { tag_num: 4, len } => {
+                LogDatum::Unsigned(decode_unsigned(r, len as usize)?)
+            }
+            Tag::Context { tag_num: 5, len } => LogDatum::Signed(decode_signed(r, len as usize)?),
+            Tag::Context { tag_num: 6, len } => LogDatum::BitString(decode_bit_string(r, len)?),
+            Tag::Context { tag_num: 7, len: 0 } => LogDatum::Null,
+            Tag::Opening { tag_num: 8 } => {
+                let error_class = decode_app_enumerated_value(r)?;
+                let error_code = decode_app_enumerated_value(r)?;
+                expect_tag(r, Tag::Closing { tag_num: 8 })?;
+                LogDatum::Failure {
+                    error_class,
+                    error_code,
+                }
+            }
+            Tag::Context { tag_num: 9, len: 4 } => LogDatum::TimeChange(decode_real(r, 4)?),
+            // [10] any-value and unknown choices.
+            _ => return Err(DecodeError::Unsupported),
+        };
+        expect_tag(r, Tag::Closing { tag_num: 1 })?;
+
+        let mut status_flags = None;
+        if !r.is_empty() {
+            let checkpoint = *r;
+            match Tag::decode(r)? {
+                Tag::Context { tag_num: 2, len } => status_flags = Some(decode_bit_string(r, len)?),
+                _ => *r = checkpoint,
+            }
+        }
+
+        Ok(Self {
+            date,
+            time,
+            datum,
+            status_flags,
+        })
+    }
+
+    /// The record as a generic value: `Constructed { tag_num: 0 }` holding the timestamp
+    /// (`Constructed { tag_num: 0 }` of Date and Time), the datum and the status flags.
+    #[cfg(feature = "alloc")]
+    pub fn to_data_value(&self) -> DataValue<'a> {
+        let datum = self.datum.to_data_value();
+        let mut values = alloc::vec![timestamp_value(self.date, self.time), datum];
+        if let Some(flags) = self.status_flags {
+            values.push(DataValue::BitString(flags));
+        }
+        DataValue::Constructed { tag_num: 0, values }
+    }
+}
+
+/// BACnetLogMultipleRecord's `log-data` choice.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogMultipleData<'a> {
+    /// A change of the log's own status.
+    LogStatus(BitString<'a>),
+    /// One datum per logged property, in Log_DeviceObjectProperty order. Only the value
+    /// variants of [`LogDatum`] (and `Failure`) appear here.
+    Values(Vec<LogDatum<'a>>),
+    /// The device clock was changed by this many seconds.
+    TimeChange(f32),
+}
+
+/// One entry of a Trend Log Multiple's `Log_Buffer` (BACnetLogMultipleRecord).
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogMultipleRecord<'a> {
+    pub date: Date,
+    pub time: Time,
+    pub data: LogMultipleData<'a>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> LogMultipleRecord<'a> {
+    /// Encodes the record; errors if a value is `LogStatus` or `TimeChange`, which
+    /// `log-data` cannot hold per property.
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_log_timestamp(w, self.date, self.time)?;
+        Tag::Opening { tag_num: 1 }.encode(w)?;
+        match &self.data {
+            LogMultipleData::LogStatus(bits) => encode_ctx_bit_string(w, 0, *bits)?,
+            LogMultipleData::Values(values) => {
+                Tag::Opening { tag_num: 1 }.encode(w)?;
+                for value in values {
+                    match *value {
+                        LogDatum::Boolean(v) => {
+                            Tag::Context { tag_num: 0, len: 1 }.encode(w)?;
+                            w.write_u8(u8::from(v))?;
+                        }
+                        LogDatum::Real(v) => encode_ctx_real(w, 1, v)?,
+                        LogDatum::Enumerated(v) => encode_ctx_unsigned(w, 2, v)?,
+                        LogDatum::Unsigned(v) => encode_ctx_unsigned(w, 3, v)?,
+                        LogDatum::Signed(v) => encode_ctx_signed(w, 4, v)?,
+                        LogDatum::BitString(bits) => encode_ctx_bit_string(w, 5, bits)?,
+                        LogDatum::Null => Tag::Context { tag_num: 6, len: 0 }.encode(w)?,
+                        LogDatum::Failure {
+                            error_class,
+                            error_code,
+                        } => {
+                            Tag::Opening { tag_num: 7 }.encode(w)?;
+                            encode_app_enumerated(w, error_class)?;
+                            encode_app_enumerated(w, error_code)?;
+                            Tag::Closing { tag_num: 7 }.encode(w)?;
+                        }
+                        LogDatum::LogStatus(_) | LogDatum::TimeChange(_) => {
+                            return Err(EncodeError::ValueOutOfRange);
+                        }
+                    }
+                }
+                Tag::Closing { tag_num: 1 }.encode(w)?;
+            }
+            LogMultipleData::TimeChange(v) => encode_ctx_real(w, 2, *v)?,
+        }
+        Tag::Closing { tag_num: 1 }.encode(w)
+    }
+
+    /// Decodes a record whose opening `[0]` tag has already been read.
+    pub fn decode_after_opening(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let (date, time) = decode_log_timestamp(r)?;
+        expect_tag(r, Tag::Opening { tag_num: 1 })?;
+        let data = match Tag::decode(r)? {
+            Tag::Context { tag_num: 0, len } => {
+                LogMultipleData::LogStatus(decode_bit_string(r, len)?)
+            }
+            Tag::Opening { tag_num: 1 } => {
+                let mut values = Vec::new();
+                loop {
+                    let value = match Tag::decode(r)? {
+                        Tag::Closing { tag_num: 1 } => break,
+                        Tag::Context { tag_num: 0, len: 1 } => LogDatum::Boolean(r.read_u8()? != 0),
+                        Tag::Context { tag_num: 1, len: 4 } => LogDatum::Real(decode_real(r, 4)?),
+                        Tag::Context { tag_num: 2, len } => {
+                            LogDatum::Enumerated(decode_unsigned(r, len as usize)?)
+                        }
+                        Tag::Context { tag_num: 3, len } => {
+                            LogDatum::Unsigned(decode_unsigned(r, len as usize)?)
+                        }
+                        Tag::Context { tag_num: 4, len } => {
+                            LogDatum::Signed(decode_signed(r, len as usize)?)
+                        }
+                        Tag::Context { tag_num: 5, len } => {
+                            LogDatum::BitString(decode_bit_string(r, len)?)
+                        }
+                        Tag::Context { tag_num: 6, len: 0 } => LogDatum::Null,
+                        Tag::Opening { tag_num: 7 } => {
+                            let error_class = decode_app_enumerated_value(r)?;
+                            let error_code = decode_app_enumerated_value(r)?;
+                            expect_tag(r, Tag::Closing { tag_num: 7 })?;
+                            LogDatum::Failure {
+                                error_class,
+                                error_code,
+                            }
+                        }
+                        // [8] any-value and unknown choices.
+                        _ => return Err(DecodeError::Unsupported),
+                    };
+                    values.push(value);
+                }
+                LogMultipleData::Values(values)
+            }
+            Tag::Context { tag_num: 2, len: 4 } => LogMultipleData::TimeChange(decode_real(r, 4)?),
+            _ => return Err(DecodeError::Unsupported),
+        };
+        expect_tag(r, Tag::Closing { tag_num: 1 })?;
+        Ok(Self { date, time, data })
+    }
+
+    /// The record as a generic value: `Constructed { tag_num: 0 }` holding the timestamp
+    /// and the data, the per-property values as `Constructed { tag_num: 1 }`.
+    pub fn to_data_value(&self) -> DataValue<'a> {
+        let data = match &self.data {
+            LogMultipleData::LogStatus(bits) => DataValue::BitString(*bits),
+            LogMultipleData::Values(values) => DataValue::Constructed {
+                tag_num: 1,
+                values: values.iter().map(LogDatum::to_data_value).collect(),
+            },
+            LogMultipleData::TimeChange(v) => DataValue::Real(*v),
+        };
+        DataValue::Constructed {
+            tag_num: 0,
+            values: alloc::vec![timestamp_value(self.date, self.time), data],
+        }
+    }
+}
+
+/// BACnetRecipient: a device, or a network address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recipient<'a> {
+    Device(ObjectId),
+    Address { network: u16, mac: &'a [u8] },
+}
+
+impl<'a> Recipient<'a> {
+    /// Encodes the recipient enclosed in context tag `tag_num`.
+    pub(crate) fn encode_enclosed(
+        &self,
+        w: &mut Writer<'_>,
+        tag_num: u8,
+    ) -> Result<(), EncodeError> {
+        Tag::Opening { tag_num }.encode(w)?;
+        match self {
+            Self::Device(device) => encode_ctx_object_id(w, 0, device.raw())?,
+            Self::Address { network, mac } => {
+                Tag::Opening { tag_num: 1 }.encode(w)?;
+                encode_app_unsigned(w, u32::from(*network))?;
+                Tag::Application {
+                    tag: AppTag::OctetString,
+                    len: mac.len() as u32,
+                }
+                .encode(w)?;
+                w.write_all(mac)?;
+                Tag::Closing { tag_num: 1 }.encode(w)?;
+            }
+        }
+        Tag::Closing { tag_num }.encode(w)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Recipient<'a> {
+    /// Decodes the recipient enclosed in context tag `tag_num`.
+    pub(crate) fn decode_enclosed(r: &mut Reader<'a>, tag_num: u8) -> Result<Self, DecodeError> {
+        expect_tag(r, Tag::Opening { tag_num })?;
+        let recipient = match Tag::decode(r)? {
+            Tag::Context { tag_num: 0, len: 4 } => {
+                Self::Device(ObjectId::from_raw(r.read_be_u32()?))
+            }
+            Tag::Opening { tag_num: 1 } => {
+                let network = u16::try_from(decode_app_unsigned(r)?)
+                    .map_err(|_| DecodeError::InvalidValue)?;
+                let mac = match Tag::decode(r)? {
+                    Tag::Application {
+                        tag: AppTag::OctetString,
+                        len,
+                    } => r.read_exact(len as usize)?,
+                    _ => return Err(DecodeError::InvalidTag),
+                };
+                expect_tag(r, Tag::Closing { tag_num: 1 })?;
+                Self::Address { network, mac }
+            }
+            _ => return Err(DecodeError::InvalidTag),
+        };
+        expect_tag(r, Tag::Closing { tag_num })?;
+        Ok(recipient)
+    }
+}
+
+/// BACnetAuditOperation: what an audited action did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum AuditOperation {
+    Read = 0,
+    Write = 1,
+    Create = 2,
+    Delete = 3,
+    LifeSafety = 4,
+    AcknowledgeAlarm = 5,
+    DeviceDisableComm = 6,
+    DeviceEnableComm = 7,
+    DeviceReset = 8,
+    DeviceBackup = 9,
+    DeviceRestore = 10,
+    Subscription = 11,
+    Notification = 12,
+    AuditingFailure = 13,
+    NetworkChanges = 14,
+    General = 15,
+}
+
+impl AuditOperation {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Read),
+            1 => Some(Self::Write),
+            2 => Some(Self::Create),
+            3 => Some(Self::Delete),
+            4 => Some(Self::LifeSafety),
+            5 => Some(Self::AcknowledgeAlarm),
+            6 => Some(Self::DeviceDisableComm),
+            7 => Some(Self::DeviceEnableComm),
+            8 => Some(Self::DeviceReset),
+            9 => Some(Self::DeviceBackup),
+            10 => Some(Self::DeviceRestore),
+            11 => Some(Self::Subscription),
+            12 => Some(Self::Notification),
+            13 => Some(Self::AuditingFailure),
+            14 => Some(Self::NetworkChanges),
+            15 => Some(Self::General),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetAuditNotification: one audited action, as kept in an Audit Log.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditNotification<'a> {
+    pub source_timestamp: Option<TimeStamp>,
+    pub target_timestamp: Option<TimeStamp>,
+    /// The device that requested the action.
+    pub source_device: Recipient<'a>,
+    pub source_object: Option<ObjectId>,
+    /// BACnetAuditOperation; see [`AuditNotification::operation`].
+    pub operation_raw: u32,
+    pub source_comment: Option<&'a str>,
+    pub target_comment: Option<&'a str>,
+    pub invoke_id: Option<u8>,
+    pub source_user_id: Option<u16>,
+    pub source_user_role: Option<u8>,
+    /// The device the action was performed on.
+    pub target_device: Recipient<'a>,
+    pub target_object: Option<ObjectId>,
+    pub target_property: Option<PropertyReference>,
+    pub target_priority: Option<u8>,
+    /// The value written, for write operations.
+    pub target_value: Option<DataValue<'a>>,
+    /// The value before or after the action, as the auditing device chose to report.
+    pub current_value: Option<DataValue<'a>>,
+    /// `(error_class, error_code)` if the action failed.
+    pub result: Option<(u32, u32)>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> AuditNotification<'a> {
+    pub fn operation(&self) -> Option<AuditOperation> {
+        AuditOperation::from_u32(self.operation_raw)
+    }
+
+    /// Decodes a notification whose opening `[1]` tag has already been read, up to and
+    /// including its closing tag.
+    pub fn decode_after_opening(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let source_timestamp = decode_optional_timestamp(r, 0)?;
+        let target_timestamp = decode_optional_timestamp(r, 1)?;
+        let source_device = Recipient::decode_enclosed(r, 2)?;
+        let source_object = decode_optional_ctx_unsigned(r, 3)?.map(ObjectId::from_raw);
+        let operation_raw = decode_required_ctx_unsigned(r, 4)?;
+        let source_comment = decode_optional_ctx_string(r, 5)?;
+        let target_comment = decode_optional_ctx_string(r, 6)?;
+        let invoke_id = decode_optional_ctx_unsigned(r, 7)?
+            .map(u8::try_from)
+            .transpose()
+            .map_err(|_| DecodeError::InvalidValue)?;
+        let source_user_id = decode_optional_ctx_unsigned(r, 8)?
+            .map(u16::try_from)
+            .transpose()
+            .map_err(|_| DecodeError::InvalidValue)?;
+        let source_user_role = decode_optional_ctx_unsigned(r, 9)?
+            .map(u8::try_from)
+            .transpose()
+            .map_err(|_| DecodeError::InvalidValue)?;
+        let target_device = Recipient::decode_enclosed(r, 10)?;
+        let target_object = decode_optional_ctx_unsigned(r, 11)?.map(ObjectId::from_raw);
+        let target_property = if take_opening_tag(r, 12)? {
+            let property_id = PropertyId::from_u32(decode_required_ctx_unsigned(r, 0)?);
+            let array_index = decode_optional_ctx_unsigned(r, 1)?;
+            expect_tag(r, Tag::Closing { tag_num: 12 })?;
+            Some(PropertyReference {
+                property_id,
+                array_index,
+            })
+        } else {
+            None
+        };
+        let target_priority = decode_optional_ctx_unsigned(r, 13)?
+            .map(u8::try_from)
+            .transpose()
+            .map_err(|_| DecodeError::InvalidValue)?;
+        let target_value = decode_optional_abstract_value(r, 14)?;
+        let current_value = decode_optional_abstract_value(r, 15)?;
+        let result = if take_opening_tag(r, 16)? {
+            let error_class = decode_app_enumerated_value(r)?;
+            let error_code = decode_app_enumerated_value(r)?;
+            expect_tag(r, Tag::Closing { tag_num: 16 })?;
+            Some((error_class, error_code))
+        } else {
+            None
+        };
+        expect_tag(r, Tag::Closing { tag_num: 1 })?;
+        Ok(Self {
+            source_timestamp,
+            target_timestamp,
+            source_device,
+            source_object,
+            operation_raw,
+            source_comment,
+            target_comment,
+            invoke_id,
+            source_user_id,
+            source_user_role,
+            target_device,
+            target_object,
+            target_property,
+            target_priority,
+            target_value,
+            current_value,
+            result,
+        })
+    }
+}
+
+/// BACnetAuditLogRecord's `log-datum` choice.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditLogDatum<'a> {
+    /// A change of the log's own status.
+    LogStatus(BitString<'a>),
+    Notification(Box<AuditNotification<'a>>),
+    /// The device clock was changed by this many seconds.
+    TimeChange(f32),
+}
+
+/// One entry of an Audit Log's `Log_Buffer` (BACnetAuditLogRecord).
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditLogRecord<'a> {
+    pub date: Date,
+    pub time: Time,
+    pub datum: AuditLogDatum<'a>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> AuditLogRecord<'a> {
+    /// Decodes a record whose opening `[0]` tag has already been read.
+    pub fn decode_after_opening(r: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        let (date, time) = decode_log_timestamp(r)?;
+        expect_tag(r, Tag::Opening { tag_num: 1 })?;
+        let datum = match Tag::decode(r)? {
+            Tag::Context { tag_num: 0, len } => {
+                AuditLogDatum::LogStatus(decode_bit_string(r, len)?)
+            }
+            Tag::Opening { tag_num: 1 } => {
+                AuditLogDatum::Notification(Box::new(AuditNotification::decode_after_opening(r)?))
+            }
+            Tag::Context { tag_num: 2, len: 4 } => AuditLogDatum::TimeChange(decode_real(r, 4)?),
+            _ => return Err(DecodeError::Unsupported),
+        };
+        expect_tag(r, Tag::Closing { tag_num: 1 })?;
+        Ok(Self { date, time, datum })
+    }
+
+    /// The record as a generic value: `Constructed { tag_num: 0 }` holding the timestamp
+    /// and the datum. A notification is summarised as `Constructed { tag_num: 1 }` of its
+    /// operation, the source and target objects and the target and current values
+    /// present; [`AuditLogRecord::datum`] has every field.
+    pub fn to_data_value(&self) -> DataValue<'a> {
+        let datum = match &self.datum {
+            AuditLogDatum::LogStatus(bits) => DataValue::BitString(*bits),
+            AuditLogDatum::Notification(notification) => {
+                let mut values = alloc::vec![DataValue::Enumerated(notification.operation_raw)];
+                values.extend(notification.source_object.map(DataValue::ObjectId));
+                values.extend(notification.target_object.map(DataValue::ObjectId));
+                values.extend(notification.target_value.clone());
+                values.extend(notification.current_value.clone());
+                DataValue::Constructed { tag_num: 1, values }
+            }
+            AuditLogDatum::TimeChange(v) => DataValue::Real(*v),
+        };
+        DataValue::Constructed {
+            tag_num: 0,
+            values: alloc::vec![timestamp_value(self.date, self.time), datum],
+        }
+    }
+}
+
+/// A decoded `Log_Buffer` entry, by the kind of log it was read from.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogBufferRecord<'a> {
+    /// From a Trend Log.
+    Trend(LogRecord<'a>),
+    /// From a Trend Log Multiple.
+    Multiple(LogMultipleRecord<'a>),
+    /// From an Audit Log.
+    Audit(AuditLogRecord<'a>),
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> LogBufferRecord<'a> {
+    /// Decodes a record of a `log` object's buffer whose opening `[0]` tag has already
+    /// been read; objects other than Trend Log Multiple and Audit Log are read as Trend
+    /// Log records.
+    pub fn decode_after_opening(r: &mut Reader<'a>, log: ObjectType) -> Result<Self, DecodeError> {
+        Ok(match log {
+            ObjectType::TrendLogMultiple => {
+                Self::Multiple(LogMultipleRecord::decode_after_opening(r)?)
+            }
+            ObjectType::AuditLog => Self::Audit(AuditLogRecord::decode_after_opening(r)?),
+            _ => Self::Trend(LogRecord::decode_after_opening(r)?),
+        })
+    }
+
+    pub fn to_data_value(&self) -> DataValue<'a> {
+        match self {
+            Self::Trend(record) => record.to_data_value(),
+            Self::Multiple(record) => record.to_data_value(),
+            Self::Audit(record) => record.to_data_value(),
+        }
+    }
+}
+
+fn encode_log_timestamp(w: &mut Writer<'_>, date: Date, time: Time) -> Result<(), EncodeError> {
+    Tag::Opening { tag_num: 0 }.encode(w)?;
+    Tag::Application {
+        tag: AppTag::Date,
+        len: 4,
+    }
+    .encode(w)?;
+    w.write_all(&[date.year_since_1900, date.month, date.day, date.weekday])?;
+    Tag::Application {
+        tag: AppTag::Time,
+        len: 4,
+    }
+    .encode(w)?;
+    w.write_all(&[time.hour, time.minute, time.second, time.hundredths])?;
+    Tag::Closing { tag_num: 0 }.encode(w)
+}
+
+/// Decodes a record's `[0]` BACnetDateTime, its opening tag already read.
+fn decode_log_timestamp(r: &mut Reader<'_>) -> Result<(Date, Time), DecodeError> {
+    let date = match Tag::decode(r)? {
+        Tag::Application {
+            tag: AppTag::Date,
+            len: 4,
+        } => {
+            let b = r.read_exact(4)?;
+            Date {
+                year_since_1900: b[0],
+                month: b[1],
+                day: b[2],
+                weekday: b[3],
+            }
+        }
+        _ => return Err(DecodeError::InvalidTag),
+    };
+    let time = match Tag::decode(r)? {
+        Tag::Application {
+            tag: AppTag::Time,
+            len: 4,
+        } => {
+            let b = r.read_exact(4)?;
+            Time {
+                hour: b[0],
+                minute: b[1],
+                second: b[2],
+                hundredths: b[3],
+            }
+        }
+        _ => return Err(DecodeError::InvalidTag),
+    };
+    expect_tag(r, Tag::Closing { tag_num: 0 })?;
+    Ok((date, time))
+}
+
+/// A record timestamp as a generic value: `Constructed { tag_num: 0 }` of Date and Time.
+#[cfg(feature = "alloc")]
+fn timestamp_value<'a>(date: Date, time: Time) -> DataValue<'a> {
+    DataValue::Constructed {
+        tag_num: 0,
+        values: alloc::vec![DataValue::Date(date), DataValue::Time(time)],
+    }
+}
+
+/// Consumes opening tag `tag_num` if it is next.
+#[cfg(feature = "alloc")]
+fn take_opening_tag(r: &mut Reader<'_>, tag_num: u8) -> Result<bool, DecodeError> {
+    if r.is_empty() {
+        return Ok(false);
+    }
+    let mut peek = *r;
+    if Tag::decode(&mut peek)? == (Tag::Opening { tag_num }) {
+        *r = peek;
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+#[cfg(feature = "alloc")]
+fn decode_optional_timestamp(
+    r: &mut Reader<'_>,
+    tag_num: u8,
+) -> Result<Option<TimeStamp>, DecodeError> {
+    let mut peek = *r;
+    if Tag::decode(&mut peek)? != (Tag::Opening { tag_num }) {
+        return Ok(None);
+    }
+    decode_required_ctx_timestamp(r, tag_num).map(Some)
+}
+
+#[cfg(feature = "alloc")]
+fn decode_optional_ctx_unsigned(
+    r: &mut Reader<'_>,
+    tag_num: u8,
+) -> Result<Option<u32>, DecodeError> {
+    take_optional_ctx_tag(r, tag_num)?
+        .map(|len| decode_unsigned(r, len as usize))
+        .transpose()
+}
+
+#[cfg(feature = "alloc")]
+fn decode_optional_ctx_string<'a>(
+    r: &mut Reader<'a>,
+    tag_num: u8,
+) -> Result<Option<&'a str>, DecodeError> {
+    take_optional_ctx_tag(r, tag_num)?
+        .map(|len| decode_ctx_character_string(r, len as usize))
+        .transpose()
+}
+
+/// Decodes an ABSTRACT-SYNTAX value enclosed in tag `tag_num`, if present; several
+/// values are returned as `Constructed { tag_num }`.
+#[cfg(feature = "alloc")]
+fn decode_optional_abstract_value<'a>(
+    r: &mut Reader<'a>,
+    tag_num: u8,
+) -> Result<Option<DataValue<'a>>, DecodeError> {
+    if !take_opening_tag(r, tag_num)? {
+        return Ok(None);
+    }
+    let mut values = Vec::new();
+    loop {
+        let tag = Tag::decode(r)?;
+        if tag == (Tag::Closing { tag_num }) {
+            break;
+        }
+        values.push(decode_application_data_value_from_tag(r, tag)?);
+    }
+    if values.len() == 1 {
+        return Ok(values.pop());
+    }
+    Ok(Some(DataValue::Constructed { tag_num, values }))
+}
+
+fn encode_ctx_bit_string(
+    w: &mut Writer<'_>,
+    tag_num: u8,
+    bits: BitString<'_>,
+) -> Result<(), EncodeError> {
+    let len = u32::try_from(bits.data.len() + 1).map_err(|_| EncodeError::ValueOutOfRange)?;
+    Tag::Context { tag_num, len }.encode(w)?;
+    w.write_u8(bits.unused_bits)?;
+    w.write_all(bits.data)
+}
+
+fn decode_bit_string<'a>(r: &mut Reader<'a>, len: u32) -> Result<BitString<'a>, DecodeError> {
+    if len == 0 {
+        return Err(DecodeError::InvalidLength);
+    }
+    let raw = r.read_exact(len as usize)?;
+    if raw[0] > 7 {
+        return Err(DecodeError::InvalidValue);
+    }
+    Ok(BitString {
+        unused_bits: raw[0],
+        data: &raw[1..],
+    })
+}
+
+fn decode_app_enumerated_value(r: &mut Reader<'_>) -> Result<u32, DecodeError> {
+    match Tag::decode(r)? {
+        Tag::Application {
+            tag: AppTag::Enumerated,
+            len,
+        } => decode_unsigned(r, len as usize),
+        _ => Err(DecodeError::InvalidTag),
+    }
+}
+
 #[cfg(feature = "alloc")]
 #[derive(Debug, Clone, PartialEq)]
 pub struct ReadRangeAck<'a> {
@@ -186,7 +971,11 @@ pub struct ReadRangeAck<'a> {
     pub array_index: Option<u32>,
     pub result_flags: BitString<'a>,
     pub item_count: u32,
+    /// Every item; log records as their generic
+    /// [`LogBufferRecord::to_data_value`] form.
     pub items: Vec<DataValue<'a>>,
+    /// The items that are `Log_Buffer` records, decoded by the kind of log read.
+    pub records: Vec<LogBufferRecord<'a>>,
 }
 
 #[cfg(feature = "alloc")]
@@ -246,6 +1035,7 @@ impl<'a> ReadRangeAck<'a> {
         }
 
         let mut items = Vec::new();
+        let mut records = Vec::new();
         loop {
             let tag = Tag::decode(r)?;
             if tag == (Tag::Closing { tag_num: 5 }) {
@@ -254,6 +1044,13 @@ impl<'a> ReadRangeAck<'a> {
 
             let value = match tag {
                 Tag::Application { .. } => decode_application_data_value_from_tag(r, tag)?,
+                // A Log_Buffer record.
+                Tag::Opening { tag_num: 0 } => {
+                    let record = LogBufferRecord::decode_after_opening(r, object_id.object_type())?;
+                    let value = record.to_data_value();
+                    records.push(record);
+                    value
+                }
                 Tag::Context { .. } | Tag::Opening { .. } | Tag::Closing { .. } => {
                     return Err(DecodeError::Unsupported);
                 }
@@ -268,6 +1065,7 @@ impl<'a> ReadRangeAck<'a> {
             result_flags,
             item_count,
             items,
+            records,
         })
     }
 }
@@ -342,4 +1140,215 @@ mod tests {
         assert_eq!(parsed.item_count, 2);
         assert_eq!(parsed.items.len(), 2);
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn log_records_roundtrip_through_read_range_ack() {
+        use super::{LogDatum, LogRecord};
+        use crate::types::{BitString, DataValue, Date, Time};
+
+        let flags = [0u8];
+        let records = [
+            LogRecord {
+                date: Date {
+                    year_since_1900: 124,
+                    month: 3,
+                    day: 1,
+                    weekday: 5,
+                },
+                time: Time {
+                    hour: 13,
+                    minute: 45,
+                    second: 30,
+                    hundredths: 25,
+                },
+                datum: LogDatum::Real(19.25),
+                status_flags: Some(BitString {
+                    unused_bits: 4,
+                    data: &flags,
+                }),
+            },
+            LogRecord {
+                date: Date {
+                    year_since_1900: 124,
+                    month: 3,
+                    day: 1,
+                    weekday: 5,
+                },
+                time: Time {
+                    hour: 13,
+                    minute: 46,
+                    second: 0,
+                    hundredths: 0,
+                },
+                datum: LogDatum::Failure {
+                    error_class: 2,
+                    error_code: 32,
+                },
+                status_flags: None,
+            },
+        ];
+
+        let mut buf = [0u8; 256];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, ObjectId::new(ObjectType::TrendLog, 1).raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::LogBuffer.to_u32()).unwrap();
+        Tag::Context { tag_num: 3, len: 2 }.encode(&mut w).unwrap();
+        w.write_u8(5).unwrap();
+        w.write_u8(0b1100_0000).unwrap();
+        encode_ctx_unsigned(&mut w, 4, 2).unwrap();
+        Tag::Opening { tag_num: 5 }.encode(&mut w).unwrap();
+        for record in &records {
+            record.encode(&mut w).unwrap();
+        }
+        Tag::Closing { tag_num: 5 }.encode(&mut w).unwrap();
+
+        let mut r = Reader::new(w.as_written());
+        let parsed = ReadRangeAck::decode_after_header(&mut r).unwrap();
+        assert!(r.is_empty());
+        let expected: alloc::vec::Vec<DataValue<'_>> =
+            records.iter().map(LogRecord::to_data_value).collect();
+        assert_eq!(parsed.items, expected);
+        let DataValue::Constructed { values, .. } = &parsed.items[0] else {
+            panic!("expected a constructed record");
+        };
+        assert_eq!(values[1], DataValue::Real(19.25));
+    }
+
+    #[cfg(feature = "alloc")]
+    fn read_range_ack_around(
+        log: ObjectId,
+        encode_items: impl Fn(&mut Writer<'_>),
+    ) -> alloc::vec::Vec<u8> {
+        let mut buf = [0u8; 256];
+        let mut w = Writer::new(&mut buf);
+        encode_ctx_object_id(&mut w, 0, log.raw()).unwrap();
+        encode_ctx_unsigned(&mut w, 1, PropertyId::LogBuffer.to_u32()).unwrap();
+        Tag::Context { tag_num: 3, len: 2 }.encode(&mut w).unwrap();
+        w.write_u8(5).unwrap();
+        w.write_u8(0b1100_0000).unwrap();
+        encode_ctx_unsigned(&mut w, 4, 1).unwrap();
+        Tag::Opening { tag_num: 5 }.encode(&mut w).unwrap();
+        encode_items(&mut w);
+        Tag::Closing { tag_num: 5 }.encode(&mut w).unwrap();
+        w.as_written().to_vec()
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn log_multiple_records_decode_by_object_type() {
+        use super::{LogBufferRecord, LogDatum, LogMultipleData, LogMultipleRecord};
+        use crate::types::{DataValue, Date, Time};
+
+        let record = LogMultipleRecord {
+            date: Date {
+                year_since_1900: 124,
+                month: 6,
+                day: 3,
+                weekday: 1,
+            },
+            time: Time {
+                hour: 9,
+                minute: 15,
+                second: 0,
+                hundredths: 0,
+            },
+            data: LogMultipleData::Values(alloc::vec![
+                LogDatum::Real(21.5),
+                LogDatum::Enumerated(1),
+                LogDatum::Failure {
+                    error_class: 2,
+                    error_code: 31,
+                },
+            ]),
+        };
+        let bytes = read_range_ack_around(ObjectId::new(ObjectType::TrendLogMultiple, 1), |w| {
+            record.encode(w).unwrap();
+        });
+        let mut r = Reader::new(&bytes);
+        let parsed = ReadRangeAck::decode_after_header(&mut r).unwrap();
+        assert!(r.is_empty());
+        assert_eq!(parsed.records, [LogBufferRecord::Multiple(record.clone())]);
+        let DataValue::Constructed { values, .. } = &parsed.items[0] else {
+            panic!("expected a constructed record");
+        };
+        let DataValue::Constructed { values, .. } = &values[1] else {
+            panic!("expected per-property values");
+        };
+        assert_eq!(
+            values[..2],
+            [DataValue::Real(21.5), DataValue::Enumerated(1)]
+        );
+
+        // A Trend Log Multiple record is not a valid Trend Log record.
+        let bytes = read_range_ack_around(ObjectId::new(ObjectType::TrendLog, 1), |w| {
+            record.encode(w).unwrap();
+        });
+        assert!(ReadRangeAck::decode_after_header(&mut Reader::new(&bytes)).is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn audit_log_records_decode_notifications() {
+        use super::{AuditLogDatum, AuditOperation, LogBufferRecord, Recipient};
+        use crate::encoding::primitives::{encode_app_enumerated, encode_ctx_character_string};
+        use crate::types::{DataValue, PropertyReference};
+
+        let source = ObjectId::new(ObjectType::Device, 99);
+        let target = ObjectId::new(ObjectType::Device, 10);
+        let point = ObjectId::new(ObjectType::AnalogValue, 3);
+        let bytes = read_range_ack_around(ObjectId::new(ObjectType::AuditLog, 1), |w| {
+            Tag::Opening { tag_num: 0 }.encode(w).unwrap();
+            w.write_all(&[0xA4, 124, 6, 3, 1, 0xB4, 9, 15, 0, 0])
+                .unwrap();
+            Tag::Closing { tag_num: 0 }.encode(w).unwrap();
+            Tag::Opening { tag_num: 1 }.encode(w).unwrap();
+            Tag::Opening { tag_num: 1 }.encode(w).unwrap();
+            Tag::Opening { tag_num: 2 }.encode(w).unwrap();
+            encode_ctx_object_id(w, 0, source.raw()).unwrap();
+            Tag::Closing { tag_num: 2 }.encode(w).unwrap();
+            encode_ctx_unsigned(w, 4, AuditOperation::Write.to_u32()).unwrap();
+            encode_ctx_character_string(w, 5, "setpoint change").unwrap();
+            Tag::Opening { tag_num: 10 }.encode(w).unwrap();
+            encode_ctx_object_id(w, 0, target.raw()).unwrap();
+            Tag::Closing { tag_num: 10 }.encode(w).unwrap();
+            encode_ctx_object_id(w, 11, point.raw()).unwrap();
+            Tag::Opening { tag_num: 12 }.encode(w).unwrap();
+            encode_ctx_unsigned(w, 0, PropertyId::PresentValue.to_u32()).unwrap();
+            Tag::Closing { tag_num: 12 }.encode(w).unwrap();
+            encode_ctx_unsigned(w, 13, 8).unwrap();
+            Tag::Opening { tag_num: 14 }.encode(w).unwrap();
+            encode_app_real(w, 22.0).unwrap();
+            Tag::Closing { tag_num: 14 }.encode(w).unwrap();
+            Tag::Opening { tag_num: 16 }.encode(w).unwrap();
+            encode_app_enumerated(w, 2).unwrap();
+            encode_app_enumerated(w, 40).unwrap();
+            Tag::Closing { tag_num: 16 }.encode(w).unwrap();
+            Tag::Closing { tag_num: 1 }.encode(w).unwrap();
+            Tag::Closing { tag_num: 1 }.encode(w).unwrap();
+        });
+        let mut r = Reader::new(&bytes);
+        let parsed = ReadRangeAck::decode_after_header(&mut r).unwrap();
+        assert!(r.is_empty());
+        let [LogBufferRecord::Audit(record)] = parsed.records.as_slice() else {
+            panic!("expected one audit record, got {:?}", parsed.records);
+        };
+        let AuditLogDatum::Notification(notification) = &record.datum else {
+            panic!("expected a notification");
+        };
+        assert_eq!(notification.operation(), Some(AuditOperation::Write));
+        assert_eq!(notification.source_device, Recipient::Device(source));
+        assert_eq!(notification.target_device, Recipient::Device(target));
+        assert_eq!(notification.source_comment, Some("setpoint change"));
+        assert_eq!(notification.target_object, Some(point));
+        assert_eq!(
+            notification.target_property,
+            Some(PropertyReference::new(PropertyId::PresentValue))
+        );
+        assert_eq!(notification.target_priority, Some(8));
+        assert_eq!(notification.target_value, Some(DataValue::Real(22.0)));
+        assert_eq!(notification.current_value, None);
+        assert_eq!(notification.result, Some((2, 40)));
+        assert_eq!(parsed.items[0], parsed.records[0].to_data_value());
+    }
 }
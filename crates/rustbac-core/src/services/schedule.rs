@@ -0,0 +1,319 @@
+use crate::encoding::{
+    primitives::{encode_ctx_object_id, encode_ctx_unsigned},
+    tag::Tag,
+    writer::Writer,
+};
+use crate::services::calendar::CalendarEntry;
+use crate::services::channel::encode_reference_list_write;
+use crate::services::value_codec::encode_application_data_value;
+use crate::services::write_property::encode_write_property_header;
+use crate::types::{DataValue, DeviceObjectPropertyReference, ObjectId, PropertyId, Time};
+use crate::EncodeError;
+
+#[cfg(feature = "alloc")]
+use crate::encoding::reader::Reader;
+#[cfg(feature = "alloc")]
+use crate::services::channel::decode_reference_list_ack;
+#[cfg(feature = "alloc")]
+use crate::DecodeError;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// WriteProperty replacing a Schedule's List_Of_Object_Property_References, the
+/// properties the schedule writes its Present_Value to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteScheduleReferencesRequest<'a> {
+    pub schedule: ObjectId,
+    pub references: &'a [DeviceObjectPropertyReference],
+    pub invoke_id: u8,
+}
+
+impl<'a> WriteScheduleReferencesRequest<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_reference_list_write(
+            w,
+            self.invoke_id,
+            self.schedule,
+            PropertyId::ListOfObjectPropertyReferences,
+            self.references,
+        )
+    }
+}
+
+/// ReadProperty-ACK carrying a Schedule's List_Of_Object_Property_References.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleReferencesAck {
+    pub schedule: ObjectId,
+    pub references: Vec<DeviceObjectPropertyReference>,
+}
+
+#[cfg(feature = "alloc")]
+impl ScheduleReferencesAck {
+    pub fn decode_after_header(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let (schedule, references) = decode_reference_list_ack(r)?;
+        Ok(Self {
+            schedule,
+            references,
+        })
+    }
+}
+
+/// BACnetTimeValue: the value a schedule takes from `time` on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeValue<'a> {
+    pub time: Time,
+    pub value: DataValue<'a>,
+}
+
+impl<'a> TimeValue<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_application_data_value(w, &DataValue::Time(self.time))?;
+        encode_application_data_value(w, &self.value)
+    }
+}
+
+/// The days a special event applies to: an inline calendar entry, or a Calendar object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecialEventPeriod {
+    CalendarEntry(CalendarEntry),
+    CalendarReference(ObjectId),
+}
+
+/// BACnetSpecialEvent: an entry of a Schedule's Exception_Schedule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpecialEvent<'a> {
+    pub period: SpecialEventPeriod,
+    pub time_values: &'a [TimeValue<'a>],
+    /// 1 (highest) to 16.
+    pub priority: u8,
+}
+
+impl<'a> SpecialEvent<'a> {
+    /// Encodes the event; errors if the priority is outside 1–16 or the calendar entry
+    /// does not [validate](CalendarEntry::validate).
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        if !(1..=16).contains(&self.priority) {
+            return Err(EncodeError::ValueOutOfRange);
+        }
+        match &self.period {
+            SpecialEventPeriod::CalendarEntry(entry) => {
+                Tag::Opening { tag_num: 0 }.encode(w)?;
+                entry.encode(w)?;
+                Tag::Closing { tag_num: 0 }.encode(w)?;
+            }
+            SpecialEventPeriod::CalendarReference(calendar) => {
+                encode_ctx_object_id(w, 1, calendar.raw())?;
+            }
+        }
+        Tag::Opening { tag_num: 2 }.encode(w)?;
+        for time_value in self.time_values {
+            time_value.encode(w)?;
+        }
+        Tag::Closing { tag_num: 2 }.encode(w)?;
+        encode_ctx_unsigned(w, 3, self.priority.into())
+    }
+}
+
+/// WriteProperty replacing a Schedule's whole Exception_Schedule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WriteExceptionScheduleRequest<'a> {
+    pub schedule: ObjectId,
+    pub events: &'a [SpecialEvent<'a>],
+    pub invoke_id: u8,
+}
+
+impl<'a> WriteExceptionScheduleRequest<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_write_property_header(
+            w,
+            self.invoke_id,
+            self.schedule,
+            PropertyId::ExceptionSchedule,
+        )?;
+        Tag::Opening { tag_num: 3 }.encode(w)?;
+        for event in self.events {
+            event.encode(w)?;
+        }
+        Tag::Closing { tag_num: 3 }.encode(w)
+    }
+}
+
+/// WriteProperty replacing a Schedule's whole Weekly_Schedule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WriteWeeklyScheduleRequest<'a> {
+    pub schedule: ObjectId,
+    /// The daily schedules, Monday first.
+    pub days: [&'a [TimeValue<'a>]; 7],
+    pub invoke_id: u8,
+}
+
+impl<'a> WriteWeeklyScheduleRequest<'a> {
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_write_property_header(w, self.invoke_id, self.schedule, PropertyId::WeeklySchedule)?;
+        Tag::Opening { tag_num: 3 }.encode(w)?;
+        for day in self.days {
+            Tag::Opening { tag_num: 0 }.encode(w)?;
+            for time_value in day {
+                time_value.encode(w)?;
+            }
+            Tag::Closing { tag_num: 0 }.encode(w)?;
+        }
+        Tag::Closing { tag_num: 3 }.encode(w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use super::{ScheduleReferencesAck, WriteScheduleReferencesRequest};
+    use super::{
+        SpecialEvent, SpecialEventPeriod, TimeValue, WriteExceptionScheduleRequest,
+        WriteWeeklyScheduleRequest,
+    };
+    #[cfg(feature = "alloc")]
+    use crate::encoding::reader::Reader;
+    use crate::encoding::writer::Writer;
+    use crate::services::calendar::CalendarEntry;
+    use crate::types::{DataValue, Date, ObjectId, ObjectType, Time};
+    #[cfg(feature = "alloc")]
+    use crate::types::{DeviceObjectPropertyReference, PropertyId};
+    use crate::EncodeError;
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn schedule_references_round_trip() {
+        let schedule = ObjectId::new(ObjectType::Schedule, 2);
+        let references = [
+            DeviceObjectPropertyReference::local(
+                ObjectId::new(ObjectType::AnalogValue, 4),
+                PropertyId::PresentValue,
+            ),
+            DeviceObjectPropertyReference::remote(
+                ObjectId::new(ObjectType::Device, 10),
+                ObjectId::new(ObjectType::BinaryOutput, 1),
+                PropertyId::PresentValue,
+            ),
+        ];
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        WriteScheduleReferencesRequest {
+            schedule,
+            references: &references,
+            invoke_id: 3,
+        }
+        .encode(&mut w)
+        .unwrap();
+        assert_eq!(&w.as_written()[9..11], &[0x19, 0x36]);
+
+        let mut r = Reader::new(&w.as_written()[4..]);
+        let ack = ScheduleReferencesAck::decode_after_header(&mut r).unwrap();
+        assert_eq!(ack.schedule, schedule);
+        assert_eq!(ack.references, references);
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn exception_schedule_write_encodes_and_checks_priority() {
+        let time_values = [TimeValue {
+            time: Time {
+                hour: 8,
+                minute: 0,
+                second: 0,
+                hundredths: 0,
+            },
+            value: DataValue::Real(19.0),
+        }];
+        let holiday = SpecialEvent {
+            period: SpecialEventPeriod::CalendarEntry(CalendarEntry::Date(Date {
+                year_since_1900: 124,
+                month: 12,
+                day: 25,
+                weekday: 0xFF,
+            })),
+            time_values: &time_values,
+            priority: 1,
+        };
+        let shutdown = SpecialEvent {
+            period: SpecialEventPeriod::CalendarReference(ObjectId::new(ObjectType::Calendar, 1)),
+            time_values: &[],
+            priority: 16,
+        };
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        WriteExceptionScheduleRequest {
+            schedule: ObjectId::new(ObjectType::Schedule, 1),
+            events: &[holiday, shutdown],
+            invoke_id: 1,
+        }
+        .encode(&mut w)
+        .unwrap();
+        assert_eq!(
+            &w.as_written()[9..],
+            &[
+                0x19, 0x26, 0x3E, // Exception_Schedule, [3]
+                0x0E, 0x0C, 124, 12, 25, 0xFF, 0x0F, // [0]{ date }
+                0x2E, 0xB4, 8, 0, 0, 0, 0x44, 0x41, 0x98, 0x00, 0x00,
+                0x2F, // [2]{ 08:00 19.0 }
+                0x39, 0x01, // priority 1
+                0x1C, 0x01, 0x80, 0x00, 0x01, // [1] Calendar 1
+                0x2E, 0x2F, 0x39, 0x10, // [2]{ }, priority 16
+                0x3F,
+            ]
+        );
+
+        let mut w = Writer::new(&mut buf);
+        assert_eq!(
+            SpecialEvent {
+                priority: 17,
+                ..shutdown
+            }
+            .encode(&mut w),
+            Err(EncodeError::ValueOutOfRange)
+        );
+    }
+
+    #[test]
+    fn weekly_schedule_write_encodes_seven_days_monday_first() {
+        let workday = [
+            TimeValue {
+                time: Time {
+                    hour: 7,
+                    minute: 30,
+                    second: 0,
+                    hundredths: 0,
+                },
+                value: DataValue::Enumerated(1),
+            },
+            TimeValue {
+                time: Time {
+                    hour: 18,
+                    minute: 0,
+                    second: 0,
+                    hundredths: 0,
+                },
+                value: DataValue::Enumerated(0),
+            },
+        ];
+        let mut days: [&[TimeValue<'_>]; 7] = [&[]; 7];
+        days[0] = &workday;
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        WriteWeeklyScheduleRequest {
+            schedule: ObjectId::new(ObjectType::Schedule, 1),
+            days,
+            invoke_id: 1,
+        }
+        .encode(&mut w)
+        .unwrap();
+        assert_eq!(
+            &w.as_written()[9..],
+            &[
+                0x19, 0x7B, 0x3E, // Weekly_Schedule, [3]
+                0x0E, 0xB4, 7, 30, 0, 0, 0x91, 0x01, 0xB4, 18, 0, 0, 0, 0x91, 0x00,
+                0x0F, // Monday
+                0x0E, 0x0F, 0x0E, 0x0F, 0x0E, 0x0F, 0x0E, 0x0F, 0x0E, 0x0F, 0x0E, 0x0F, 0x3F,
+            ]
+        );
+    }
+}
@@ -0,0 +1,193 @@
+//! Arena-backed decoding of application data values.
+//!
+//! [`decode_application_data_value`](super::value_codec::decode_application_data_value)
+//! allocates a `Vec` for every constructed value it meets. At high notification rates
+//! those small, short-lived allocations dominate. [`decode_application_data_value_in`]
+//! places the children in a caller-owned [`Bump`] instead, so a whole value tree costs a
+//! few pointer bumps and is freed at once by [`Bump::reset`]:
+//!
+//! ```
+//! use bumpalo::Bump;
+//! use rustbac_core::encoding::reader::Reader;
+//! use rustbac_core::services::value_arena::decode_application_data_value_in;
+//!
+//! let mut bump = Bump::new();
+//! for frame in [[0x0E, 0x21, 0x05, 0x0F]] {
+//!     let value = decode_application_data_value_in(&bump, &mut Reader::new(&frame)).unwrap();
+//!     assert_eq!(value.children().len(), 1);
+//!     bump.reset();
+//! }
+//! ```
+
+use super::value_codec::{ValueEvent, ValueEvents};
+use crate::encoding::{reader::Reader, tag::Tag};
+use crate::types::DataValue;
+use crate::DecodeError;
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+pub use bumpalo;
+
+/// A decoded value whose constructed children live in a [`Bump`] arena.
+///
+/// `'b` is the arena's lifetime and `'a` the frame's, as for [`DataValue`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArenaValue<'b, 'a> {
+    /// A primitive value; never [`DataValue::Constructed`].
+    Primitive(DataValue<'a>),
+    /// A constructed value and its children, in order.
+    Constructed {
+        tag_num: u8,
+        values: &'b [ArenaValue<'b, 'a>],
+    },
+}
+
+impl<'b, 'a> ArenaValue<'b, 'a> {
+    /// The primitive value, or `None` for a constructed one.
+    pub fn as_primitive(&self) -> Option<&DataValue<'a>> {
+        match self {
+            Self::Primitive(value) => Some(value),
+            Self::Constructed { .. } => None,
+        }
+    }
+
+    /// The children of a constructed value; empty for a primitive one.
+    pub fn children(&self) -> &'b [ArenaValue<'b, 'a>] {
+        match self {
+            Self::Primitive(_) => &[],
+            Self::Constructed { values, .. } => values,
+        }
+    }
+
+    /// Copies the value out of the arena into an ordinary [`DataValue`] tree.
+    pub fn to_data_value(&self) -> DataValue<'a> {
+        match self {
+            Self::Primitive(value) => value.clone(),
+            Self::Constructed { tag_num, values } => DataValue::Constructed {
+                tag_num: *tag_num,
+                values: values.iter().map(ArenaValue::to_data_value).collect(),
+            },
+        }
+    }
+}
+
+/// Decodes one application data value, allocating its tree in `bump`.
+///
+/// Accepts the same encodings and nesting depth as
+/// [`decode_application_data_value`](super::value_codec::decode_application_data_value).
+pub fn decode_application_data_value_in<'b, 'a>(
+    bump: &'b Bump,
+    r: &mut Reader<'a>,
+) -> Result<ArenaValue<'b, 'a>, DecodeError> {
+    build(bump, ValueEvents::new(r))
+}
+
+/// Like [`decode_application_data_value_in`], for a value whose first tag has already
+/// been read.
+pub fn decode_application_data_value_from_tag_in<'b, 'a>(
+    bump: &'b Bump,
+    r: &mut Reader<'a>,
+    tag: Tag,
+) -> Result<ArenaValue<'b, 'a>, DecodeError> {
+    build(bump, ValueEvents::from_tag(r, tag))
+}
+
+fn build<'b, 'a>(
+    bump: &'b Bump,
+    events: ValueEvents<'a, '_>,
+) -> Result<ArenaValue<'b, 'a>, DecodeError> {
+    // Children of each open constructed value; the stack itself is at most
+    // MAX_CONSTRUCTED_DEPTH entries and allocated once.
+    let mut open: Vec<(u8, BumpVec<'b, ArenaValue<'b, 'a>>)> = Vec::new();
+    for event in events {
+        let value = match event? {
+            ValueEvent::Open { tag_num } => {
+                open.push((tag_num, BumpVec::new_in(bump)));
+                continue;
+            }
+            ValueEvent::Primitive(value) => ArenaValue::Primitive(value),
+            ValueEvent::Close { .. } => {
+                let (tag_num, values) = open.pop().ok_or(DecodeError::InvalidValue)?;
+                ArenaValue::Constructed {
+                    tag_num,
+                    values: values.into_bump_slice(),
+                }
+            }
+        };
+        match open.last_mut() {
+            Some((_, parent)) => parent.push(value),
+            None => return Ok(value),
+        }
+    }
+    // The events ended inside a constructed value.
+    Err(DecodeError::UnexpectedEof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_application_data_value_in, ArenaValue};
+    use crate::encoding::{reader::Reader, writer::Writer};
+    use crate::services::value_codec::{
+        decode_application_data_value, encode_application_data_value,
+    };
+    use crate::types::DataValue;
+    use crate::DecodeError;
+    use alloc::vec;
+    use bumpalo::Bump;
+
+    #[test]
+    fn arena_decode_matches_tree_decode() {
+        let value = DataValue::Constructed {
+            tag_num: 3,
+            values: vec![
+                DataValue::Real(21.5),
+                DataValue::Constructed {
+                    tag_num: 0,
+                    values: vec![DataValue::Unsigned(1), DataValue::CharacterString("x")],
+                },
+                DataValue::Constructed {
+                    tag_num: 1,
+                    values: vec![],
+                },
+            ],
+        };
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        encode_application_data_value(&mut w, &value).unwrap();
+        let frame = w.as_written();
+
+        let bump = Bump::new();
+        let decoded = decode_application_data_value_in(&bump, &mut Reader::new(frame)).unwrap();
+        assert_eq!(decoded.to_data_value(), value);
+        assert_eq!(
+            decoded.to_data_value(),
+            decode_application_data_value(&mut Reader::new(frame)).unwrap()
+        );
+        assert_eq!(decoded.children().len(), 3);
+        assert_eq!(
+            decoded.children()[0].as_primitive(),
+            Some(&DataValue::Real(21.5))
+        );
+        assert!(matches!(
+            decoded.children()[2],
+            ArenaValue::Constructed {
+                tag_num: 1,
+                values: []
+            }
+        ));
+    }
+
+    #[test]
+    fn arena_decode_rejects_truncated_and_over_deep_values() {
+        let bump = Bump::new();
+        assert_eq!(
+            decode_application_data_value_in(&bump, &mut Reader::new(&[0x0E, 0x21, 0x05])),
+            Err(DecodeError::UnexpectedEof)
+        );
+        let deep = vec![0x0Eu8; 1000];
+        assert!(decode_application_data_value_in(&bump, &mut Reader::new(&deep)).is_err());
+    }
+}
@@ -121,10 +121,48 @@ pub fn decode_application_data_value<'a>(r: &mut Reader<'a>) -> Result<DataValue
     decode_application_data_value_from_tag(r, tag)
 }
 
+/// Maximum nesting of opening/closing tag pairs accepted inside one value.
+///
+/// Bounds recursion so that a frame made of opening tags cannot exhaust the stack.
+pub const MAX_CONSTRUCTED_DEPTH: usize = 16;
+
 pub fn decode_application_data_value_from_tag<'a>(
     r: &mut Reader<'a>,
     tag: Tag,
 ) -> Result<DataValue<'a>, DecodeError> {
+    decode_value_at_depth(r, tag, 0)
+}
+
+fn decode_value_at_depth<'a>(
+    r: &mut Reader<'a>,
+    tag: Tag,
+    #[cfg_attr(not(feature = "alloc"), allow(unused_variables))] depth: usize,
+) -> Result<DataValue<'a>, DecodeError> {
+    match tag {
+        #[cfg(feature = "alloc")]
+        Tag::Opening { tag_num } => {
+            if depth >= MAX_CONSTRUCTED_DEPTH {
+                return Err(DecodeError::InvalidValue);
+            }
+            let mut children = Vec::new();
+            loop {
+                let child_tag = Tag::decode(r)?;
+                if child_tag == (Tag::Closing { tag_num }) {
+                    break;
+                }
+                children.push(decode_value_at_depth(r, child_tag, depth + 1)?);
+            }
+            Ok(DataValue::Constructed {
+                tag_num,
+                values: children,
+            })
+        }
+        _ => decode_primitive(r, tag),
+    }
+}
+
+/// Decodes the body of a primitive application-tagged value.
+fn decode_primitive<'a>(r: &mut Reader<'a>, tag: Tag) -> Result<DataValue<'a>, DecodeError> {
     match tag {
         Tag::Application {
             tag: AppTag::Null, ..
@@ -231,23 +269,147 @@ pub fn decode_application_data_value_from_tag<'a>(
                 [b[0], b[1], b[2], b[3]],
             ))))
         }
-        #[cfg(feature = "alloc")]
-        Tag::Opening { tag_num } => {
-            let mut children = Vec::new();
-            loop {
-                let child_tag = Tag::decode(r)?;
-                if child_tag == (Tag::Closing { tag_num }) {
-                    break;
+        _ => Err(DecodeError::Unsupported),
+    }
+}
+
+/// One step of a value decoded by [`ValueEvents`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueEvent<'a> {
+    /// A primitive application-tagged value.
+    Primitive(DataValue<'a>),
+    /// An opening tag; the values up to the matching [`Close`](Self::Close) belong to it.
+    Open { tag_num: u8 },
+    /// The closing tag matching the most recent unclosed [`Open`](Self::Open).
+    Close { tag_num: u8 },
+}
+
+/// Streaming parser for one application data value.
+///
+/// Yields the value as a flat sequence of [`ValueEvent`]s instead of building a
+/// [`DataValue::Constructed`] tree, so large constructed values (RPM and ReadRange
+/// results, lists of structures) can be walked without allocating. Works without the
+/// `alloc` feature. Nesting is limited to [`MAX_CONSTRUCTED_DEPTH`] like the tree
+/// decoder, and iteration ends after the first error or once the value is complete;
+/// the reader is then positioned just past it.
+#[derive(Debug)]
+pub struct ValueEvents<'a, 'r> {
+    reader: &'r mut Reader<'a>,
+    pending: Option<Tag>,
+    open: [u8; MAX_CONSTRUCTED_DEPTH],
+    depth: usize,
+    done: bool,
+}
+
+impl<'a, 'r> ValueEvents<'a, 'r> {
+    /// Parses the value starting at the reader's position.
+    pub fn new(reader: &'r mut Reader<'a>) -> Self {
+        Self {
+            reader,
+            pending: None,
+            open: [0; MAX_CONSTRUCTED_DEPTH],
+            depth: 0,
+            done: false,
+        }
+    }
+
+    /// Parses a value whose first tag has already been read.
+    pub fn from_tag(reader: &'r mut Reader<'a>, tag: Tag) -> Self {
+        let mut events = Self::new(reader);
+        events.pending = Some(tag);
+        events
+    }
+
+    /// Number of constructed values currently open.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    fn step(&mut self) -> Result<ValueEvent<'a>, DecodeError> {
+        let tag = match self.pending.take() {
+            Some(tag) => tag,
+            None => Tag::decode(self.reader)?,
+        };
+        match tag {
+            Tag::Opening { tag_num } => {
+                if self.depth >= MAX_CONSTRUCTED_DEPTH {
+                    return Err(DecodeError::InvalidValue);
                 }
-                children.push(decode_application_data_value_from_tag(r, child_tag)?);
+                self.open[self.depth] = tag_num;
+                self.depth += 1;
+                Ok(ValueEvent::Open { tag_num })
             }
-            Ok(DataValue::Constructed {
-                tag_num,
-                values: children,
-            })
+            Tag::Closing { tag_num } if self.depth > 0 && self.open[self.depth - 1] == tag_num => {
+                self.depth -= 1;
+                Ok(ValueEvent::Close { tag_num })
+            }
+            _ => decode_primitive(self.reader, tag).map(ValueEvent::Primitive),
+        }
+    }
+}
+
+impl<'a> Iterator for ValueEvents<'a, '_> {
+    type Item = Result<ValueEvent<'a>, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let event = self.step();
+        self.done = event.is_err() || self.depth == 0;
+        Some(event)
+    }
+}
+
+/// Receives the parts of a value walked by [`visit_application_data_value`].
+///
+/// Returning an error from any method stops the walk and is passed back to the caller.
+pub trait ValueVisitor<'a> {
+    /// Called for each primitive value, at any depth.
+    fn primitive(&mut self, value: DataValue<'a>) -> Result<(), DecodeError>;
+
+    /// Called on entering a constructed value.
+    fn open(&mut self, tag_num: u8) -> Result<(), DecodeError> {
+        let _ = tag_num;
+        Ok(())
+    }
+
+    /// Called on leaving a constructed value.
+    fn close(&mut self, tag_num: u8) -> Result<(), DecodeError> {
+        let _ = tag_num;
+        Ok(())
+    }
+}
+
+/// Walks one application data value, calling `visitor` for each part in order.
+pub fn visit_application_data_value<'a, V: ValueVisitor<'a>>(
+    r: &mut Reader<'a>,
+    visitor: &mut V,
+) -> Result<(), DecodeError> {
+    drive_visitor(ValueEvents::new(r), visitor)
+}
+
+/// Like [`visit_application_data_value`], for a value whose first tag has already been read.
+pub fn visit_application_data_value_from_tag<'a, V: ValueVisitor<'a>>(
+    r: &mut Reader<'a>,
+    tag: Tag,
+    visitor: &mut V,
+) -> Result<(), DecodeError> {
+    drive_visitor(ValueEvents::from_tag(r, tag), visitor)
+}
+
+fn drive_visitor<'a, V: ValueVisitor<'a>>(
+    events: ValueEvents<'a, '_>,
+    visitor: &mut V,
+) -> Result<(), DecodeError> {
+    for event in events {
+        match event? {
+            ValueEvent::Primitive(value) => visitor.primitive(value)?,
+            ValueEvent::Open { tag_num } => visitor.open(tag_num)?,
+            ValueEvent::Close { tag_num } => visitor.close(tag_num)?,
         }
-        _ => Err(DecodeError::Unsupported),
     }
+    Ok(())
 }
 
 fn encode_app_unsigned_like(
@@ -273,9 +435,13 @@ fn encode_app_signed_like(w: &mut Writer<'_>, tag: AppTag, value: i32) -> Result
 #[cfg(test)]
 #[cfg(feature = "alloc")]
 mod tests {
-    use super::{decode_application_data_value, encode_application_data_value};
-    use crate::encoding::{reader::Reader, writer::Writer};
+    use super::{
+        decode_application_data_value, encode_application_data_value, visit_application_data_value,
+        ValueEvent, ValueEvents, ValueVisitor, MAX_CONSTRUCTED_DEPTH,
+    };
+    use crate::encoding::{reader::Reader, tag::Tag, writer::Writer};
     use crate::types::{BitString, DataValue, Date, ObjectId, ObjectType, Time};
+    use crate::DecodeError;
 
     #[test]
     fn value_codec_roundtrip_supported_types() {
@@ -338,4 +504,108 @@ mod tests {
         let got = decode_application_data_value(&mut r).unwrap();
         assert_eq!(got, value);
     }
+
+    #[test]
+    fn value_events_stream_constructed_value_without_a_tree() {
+        use alloc::vec;
+        use alloc::vec::Vec;
+
+        let value = DataValue::Constructed {
+            tag_num: 2,
+            values: vec![
+                DataValue::Unsigned(42),
+                DataValue::Constructed {
+                    tag_num: 0,
+                    values: vec![DataValue::Boolean(true), DataValue::Real(2.5)],
+                },
+                DataValue::CharacterString("test"),
+            ],
+        };
+        let mut buf = [0u8; 128];
+        let mut w = Writer::new(&mut buf);
+        encode_application_data_value(&mut w, &value).unwrap();
+        encode_application_data_value(&mut w, &DataValue::Unsigned(7)).unwrap();
+
+        let mut r = Reader::new(w.as_written());
+        let events: Vec<_> = ValueEvents::new(&mut r).map(Result::unwrap).collect();
+        assert_eq!(
+            events,
+            vec![
+                ValueEvent::Open { tag_num: 2 },
+                ValueEvent::Primitive(DataValue::Unsigned(42)),
+                ValueEvent::Open { tag_num: 0 },
+                ValueEvent::Primitive(DataValue::Boolean(true)),
+                ValueEvent::Primitive(DataValue::Real(2.5)),
+                ValueEvent::Close { tag_num: 0 },
+                ValueEvent::Primitive(DataValue::CharacterString("test")),
+                ValueEvent::Close { tag_num: 2 },
+            ]
+        );
+        // The reader stops right after the value.
+        assert_eq!(
+            decode_application_data_value(&mut r).unwrap(),
+            DataValue::Unsigned(7)
+        );
+    }
+
+    #[test]
+    fn visitor_sees_every_primitive_and_respects_nesting_limit() {
+        struct Sum {
+            total: f32,
+            deepest: usize,
+            depth: usize,
+        }
+        impl<'a> ValueVisitor<'a> for Sum {
+            fn primitive(&mut self, value: DataValue<'a>) -> Result<(), DecodeError> {
+                if let DataValue::Real(v) = value {
+                    self.total += v;
+                }
+                Ok(())
+            }
+            fn open(&mut self, _tag_num: u8) -> Result<(), DecodeError> {
+                self.depth += 1;
+                self.deepest = self.deepest.max(self.depth);
+                Ok(())
+            }
+            fn close(&mut self, _tag_num: u8) -> Result<(), DecodeError> {
+                self.depth -= 1;
+                Ok(())
+            }
+        }
+
+        let mut buf = [0u8; 256];
+        let mut w = Writer::new(&mut buf);
+        Tag::Opening { tag_num: 1 }.encode(&mut w).unwrap();
+        for v in [1.0, 2.0, 3.5] {
+            Tag::Opening { tag_num: 0 }.encode(&mut w).unwrap();
+            encode_application_data_value(&mut w, &DataValue::Real(v)).unwrap();
+            Tag::Closing { tag_num: 0 }.encode(&mut w).unwrap();
+        }
+        Tag::Closing { tag_num: 1 }.encode(&mut w).unwrap();
+        let mut sum = Sum {
+            total: 0.0,
+            deepest: 0,
+            depth: 0,
+        };
+        visit_application_data_value(&mut Reader::new(w.as_written()), &mut sum).unwrap();
+        assert_eq!(sum.total, 6.5);
+        assert_eq!(sum.deepest, 2);
+        assert_eq!(sum.depth, 0);
+
+        // Mismatched closing tag.
+        let frame = [0x1E, 0x21, 0x05, 0x2F];
+        assert!(ValueEvents::new(&mut Reader::new(&frame))
+            .any(|event| event == Err(DecodeError::Unsupported)));
+
+        // One level deeper than the tree decoder accepts.
+        let frame = [0x0E; MAX_CONSTRUCTED_DEPTH + 1];
+        let mut r = Reader::new(&frame);
+        let mut events = ValueEvents::new(&mut r);
+        assert!(events
+            .by_ref()
+            .take(MAX_CONSTRUCTED_DEPTH)
+            .all(|e| e.is_ok()));
+        assert_eq!(events.next(), Some(Err(DecodeError::InvalidValue)));
+        assert_eq!(events.next(), None);
+    }
 }
@@ -1,7 +1,8 @@
 use crate::apdu::UnconfirmedRequestHeader;
 use crate::encoding::primitives::encode_ctx_unsigned;
-use crate::encoding::writer::Writer;
-use crate::EncodeError;
+use crate::encoding::{reader::Reader, writer::Writer};
+use crate::services::decode_required_ctx_unsigned;
+use crate::{DecodeError, EncodeError};
 
 pub const SERVICE_WHO_IS: u8 = 0x08;
 
@@ -33,4 +34,59 @@ impl WhoIsRequest {
         }
         Ok(())
     }
+
+    /// Decodes the `[0]` low and `[1]` high limits that follow the header. The range is
+    /// either absent or complete; a lone limit is rejected.
+    pub fn decode_after_header(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        if r.is_empty() {
+            return Ok(Self::global());
+        }
+        let low_limit = decode_required_ctx_unsigned(r, 0)?;
+        let high_limit = decode_required_ctx_unsigned(r, 1)?;
+        Ok(Self {
+            low_limit: Some(low_limit),
+            high_limit: Some(high_limit),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WhoIsRequest;
+    use crate::apdu::UnconfirmedRequestHeader;
+    use crate::encoding::{reader::Reader, writer::Writer};
+    use crate::DecodeError;
+
+    fn round_trip(req: WhoIsRequest) -> Result<WhoIsRequest, DecodeError> {
+        let mut buf = [0u8; 16];
+        let mut w = Writer::new(&mut buf);
+        req.encode(&mut w).unwrap();
+        let mut r = Reader::new(w.as_written());
+        UnconfirmedRequestHeader::decode(&mut r).unwrap();
+        let decoded = WhoIsRequest::decode_after_header(&mut r);
+        assert!(r.is_empty());
+        decoded
+    }
+
+    #[test]
+    fn who_is_limits_round_trip() {
+        let ranged = WhoIsRequest {
+            low_limit: Some(10),
+            high_limit: Some(70_000),
+        };
+        assert_eq!(round_trip(ranged), Ok(ranged));
+        assert_eq!(
+            round_trip(WhoIsRequest::global()),
+            Ok(WhoIsRequest::global())
+        );
+    }
+
+    #[test]
+    fn who_is_with_a_lone_limit_is_rejected() {
+        let low_only = WhoIsRequest {
+            low_limit: Some(10),
+            high_limit: None,
+        };
+        assert_eq!(round_trip(low_only), Err(DecodeError::UnexpectedEof));
+    }
 }
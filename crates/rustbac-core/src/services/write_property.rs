@@ -36,21 +36,7 @@ impl<'a> Default for WritePropertyRequest<'a> {
 
 impl<'a> WritePropertyRequest<'a> {
     pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
-        ConfirmedRequestHeader {
-            segmented: false,
-            more_follows: false,
-            segmented_response_accepted: false,
-            max_segments: 0,
-            max_apdu: 5,
-            invoke_id: self.invoke_id,
-            sequence_number: None,
-            proposed_window_size: None,
-            service_choice: SERVICE_WRITE_PROPERTY,
-        }
-        .encode(w)?;
-
-        encode_ctx_object_id(w, 0, self.object_id.raw())?;
-        encode_ctx_unsigned(w, 1, self.property_id.to_u32())?;
+        encode_write_property_header(w, self.invoke_id, self.object_id, self.property_id)?;
         if let Some(idx) = self.array_index {
             encode_ctx_unsigned(w, 2, idx)?;
         }
@@ -66,6 +52,30 @@ impl<'a> WritePropertyRequest<'a> {
     }
 }
 
+/// Encodes the WriteProperty request header followed by the object and property
+/// identifiers, for requests whose value is not a plain application value.
+pub(crate) fn encode_write_property_header(
+    w: &mut Writer<'_>,
+    invoke_id: u8,
+    object_id: ObjectId,
+    property_id: PropertyId,
+) -> Result<(), EncodeError> {
+    ConfirmedRequestHeader {
+        segmented: false,
+        more_follows: false,
+        segmented_response_accepted: false,
+        max_segments: 0,
+        max_apdu: 5,
+        invoke_id,
+        sequence_number: None,
+        proposed_window_size: None,
+        service_choice: SERVICE_WRITE_PROPERTY,
+    }
+    .encode(w)?;
+    encode_ctx_object_id(w, 0, object_id.raw())?;
+    encode_ctx_unsigned(w, 1, property_id.to_u32())
+}
+
 #[cfg(test)]
 mod tests {
     use super::{WritePropertyRequest, SERVICE_WRITE_PROPERTY};
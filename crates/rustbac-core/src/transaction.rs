@@ -0,0 +1,921 @@
+//! Sans-IO state machine for the initiating side of a confirmed-service transaction.
+//!
+//! Nothing here sends, receives or sleeps: the caller encodes the request, hands every
+//! received APDU to [`ClientTransaction::handle_apdu`], sends whatever reply it asks for
+//! and passes the current time to [`ClientTransaction::poll_timeout`] when the
+//! transaction's [`deadline`](ClientTransaction::deadline) arrives. This lets
+//! `no_std` devices (Embassy, RTIC, bare-metal loops) initiate ReadProperty and
+//! WriteProperty without an async runtime, using a caller-provided buffer for the
+//! response. `BacnetClient` in `rustbac-client` is a Tokio driver around the same
+//! state machine.
+//!
+//! ```
+//! use rustbac_core::encoding::{reader::Reader, writer::Writer};
+//! use rustbac_core::npdu::Npdu;
+//! use rustbac_core::services::read_property::{
+//!     ReadPropertyAck, ReadPropertyRequest, SERVICE_READ_PROPERTY,
+//! };
+//! use rustbac_core::transaction::{split_npdu, ClientTransaction, SliceBuffer, TransactionStep};
+//! use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
+//!
+//! # fn send(_: &[u8]) {}
+//! # fn recv() -> &'static [u8] {
+//! #     &[0x01, 0x00, 0x30, 0x07, 0x0C, 0x0C, 0x00, 0x00, 0x00, 0x01, 0x19, 0x55,
+//! #       0x3E, 0x44, 0x41, 0xA4, 0x00, 0x00, 0x3F]
+//! # }
+//! let object_id = ObjectId::new(ObjectType::AnalogInput, 1);
+//! let mut tx = [0u8; 64];
+//! let mut w = Writer::new(&mut tx);
+//! Npdu::new(0).encode(&mut w).unwrap();
+//! ReadPropertyRequest {
+//!     object_id,
+//!     property_id: PropertyId::PresentValue,
+//!     array_index: None,
+//!     invoke_id: 7,
+//! }
+//! .encode(&mut w)
+//! .unwrap();
+//! # let now = 0;
+//! send(w.as_written());
+//!
+//! let mut response = [0u8; 256];
+//! let mut txn = ClientTransaction::new(7, SERVICE_READ_PROPERTY, SliceBuffer::new(&mut response));
+//! txn.on_sent(now);
+//! let mut reply = [0u8; 8];
+//! loop {
+//!     let (_npdu, apdu) = split_npdu(recv()).unwrap();
+//!     match txn.handle_apdu(apdu, &mut reply).unwrap() {
+//!         TransactionStep::Ignored => continue,
+//!         // Prefix an NPDU and send the Segment-ACK in `reply[..len]`.
+//!         TransactionStep::Reply(_len) => continue,
+//!         TransactionStep::Postponed => txn.on_reply_postponed(now),
+//!         TransactionStep::Complete { .. } => break,
+//!     }
+//! }
+//! let ack = ReadPropertyAck::decode_after_header(&mut Reader::new(txn.payload())).unwrap();
+//! assert_eq!(ack.object_id, object_id);
+//! ```
+
+use crate::apdu::{
+    abort_reason, AbortPdu, ApduType, BacnetError, ComplexAckHeader, RejectPdu, SegmentAck,
+    SimpleAck,
+};
+use crate::encoding::{reader::Reader, writer::Writer};
+use crate::npdu::Npdu;
+use crate::{DecodeError, EncodeError};
+use core::fmt;
+
+/// Number of times [`ClientTransaction::poll_timeout`] asks for the request to be resent.
+pub const DEFAULT_RETRIES: u8 = 3;
+
+/// Default time to wait for a response after each send, in milliseconds.
+pub const DEFAULT_APDU_TIMEOUT_MS: u64 = 3_000;
+
+/// Splits a network-layer frame into its NPDU header and the APDU that follows.
+pub fn split_npdu(frame: &[u8]) -> Result<(Npdu, &[u8]), DecodeError> {
+    let mut r = Reader::new(frame);
+    let npdu = Npdu::decode(&mut r)?;
+    Ok((npdu, r.read_exact(r.remaining())?))
+}
+
+/// A reply that belongs to an outstanding confirmed request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmedResponse<'a> {
+    SimpleAck,
+    /// A Complex-ACK (or one segment of it); `payload` is the service data after the header.
+    ComplexAck {
+        header: ComplexAckHeader,
+        payload: &'a [u8],
+    },
+    /// A Segment-ACK from the server for a segmented request.
+    SegmentAck(SegmentAck),
+    Error(BacnetError),
+    Reject(RejectPdu),
+    Abort(AbortPdu),
+}
+
+/// Decodes `apdu` and returns it if it answers the request `invoke_id` / `service_choice`.
+///
+/// ACKs and Errors must match both the invoke id and the service; Reject and Abort carry
+/// only an invoke id. Anything else — other transactions, incoming requests — is `None`.
+pub fn match_response(
+    apdu: &[u8],
+    invoke_id: u8,
+    service_choice: u8,
+) -> Result<Option<ConfirmedResponse<'_>>, DecodeError> {
+    let first = *apdu.first().ok_or(DecodeError::UnexpectedEof)?;
+    let mut r = Reader::new(apdu);
+    let response = match ApduType::from_u8(first >> 4) {
+        Some(ApduType::SimpleAck) => {
+            let ack = SimpleAck::decode(&mut r)?;
+            (ack.invoke_id == invoke_id && ack.service_choice == service_choice)
+                .then_some(ConfirmedResponse::SimpleAck)
+        }
+        Some(ApduType::ComplexAck) => {
+            let header = ComplexAckHeader::decode(&mut r)?;
+            if header.invoke_id == invoke_id && header.service_choice == service_choice {
+                Some(ConfirmedResponse::ComplexAck {
+                    header,
+                    payload: r.read_exact(r.remaining())?,
+                })
+            } else {
+                None
+            }
+        }
+        Some(ApduType::SegmentAck) => {
+            let ack = SegmentAck::decode(&mut r)?;
+            (ack.invoke_id == invoke_id && ack.sent_by_server)
+                .then_some(ConfirmedResponse::SegmentAck(ack))
+        }
+        Some(ApduType::Error) => {
+            let err = BacnetError::decode(&mut r)?;
+            (err.invoke_id == invoke_id && err.service_choice == service_choice)
+                .then_some(ConfirmedResponse::Error(err))
+        }
+        Some(ApduType::Reject) => {
+            let reject = RejectPdu::decode(&mut r)?;
+            (reject.invoke_id == invoke_id).then_some(ConfirmedResponse::Reject(reject))
+        }
+        Some(ApduType::Abort) => {
+            let abort = AbortPdu::decode(&mut r)?;
+            (abort.invoke_id == invoke_id).then_some(ConfirmedResponse::Abort(abort))
+        }
+        _ => None,
+    };
+    Ok(response)
+}
+
+/// What to do with a segment of a segmented Complex-ACK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentDisposition {
+    /// The next segment in sequence: append its payload and acknowledge it.
+    Accepted,
+    /// A repeat of the last accepted segment: acknowledge it again, keep nothing.
+    Duplicate,
+    /// Neither the next nor the last segment: ignore it.
+    OutOfOrder,
+}
+
+/// Receive-side sequencing for a segmented Complex-ACK (Clause 5.4.4).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentReceiver {
+    invoke_id: u8,
+    last_sequence: u8,
+    window_size: u8,
+    more_follows: bool,
+}
+
+impl SegmentReceiver {
+    /// Starts tracking from the first Complex-ACK of a response; `None` if it is not
+    /// segmented. Fails if a segmented header has no sequence number.
+    pub fn start(first: &ComplexAckHeader) -> Result<Option<Self>, DecodeError> {
+        if !first.segmented {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            invoke_id: first.invoke_id,
+            last_sequence: first.sequence_number.ok_or(DecodeError::InvalidValue)?,
+            window_size: first.proposed_window_size.unwrap_or(1),
+            more_follows: first.more_follows,
+        }))
+    }
+
+    /// Classifies a later segment and, when accepted, advances the sequence. Fails if the
+    /// server switches to an unsegmented ACK mid-response.
+    pub fn accept(&mut self, header: &ComplexAckHeader) -> Result<SegmentDisposition, DecodeError> {
+        if !header.segmented {
+            return Err(DecodeError::InvalidValue);
+        }
+        let sequence = header.sequence_number.ok_or(DecodeError::InvalidValue)?;
+        if sequence == self.last_sequence {
+            return Ok(SegmentDisposition::Duplicate);
+        }
+        if sequence != self.last_sequence.wrapping_add(1) {
+            return Ok(SegmentDisposition::OutOfOrder);
+        }
+        self.last_sequence = sequence;
+        self.more_follows = header.more_follows;
+        self.window_size = header.proposed_window_size.unwrap_or(self.window_size);
+        Ok(SegmentDisposition::Accepted)
+    }
+
+    /// The Segment-ACK acknowledging the last accepted segment.
+    pub fn segment_ack(&self) -> SegmentAck {
+        SegmentAck {
+            negative_ack: false,
+            sent_by_server: false,
+            invoke_id: self.invoke_id,
+            sequence_number: self.last_sequence,
+            actual_window_size: self.window_size,
+        }
+    }
+
+    /// `true` once the segment with `more_follows` cleared has been accepted.
+    pub fn is_complete(&self) -> bool {
+        !self.more_follows
+    }
+}
+
+/// Window sizing for transmitting a segmented confirmed request (Clause 5.4.2).
+///
+/// The window starts at the configured size, grows by one after each acknowledged batch
+/// up to the smaller of the configured size and the peer's advertised window, and halves
+/// after a lost or negatively acknowledged batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentWindow {
+    configured: u8,
+    peer_ceiling: u8,
+    current: u8,
+}
+
+impl SegmentWindow {
+    pub fn new(configured: u8) -> Self {
+        let configured = configured.max(1);
+        Self {
+            configured,
+            peer_ceiling: configured,
+            current: configured,
+        }
+    }
+
+    /// Number of segments to send in the next batch.
+    pub fn size(&self) -> u8 {
+        self.current
+    }
+
+    /// Records a positive Segment-ACK carrying the peer's `actual_window_size`.
+    pub fn on_ack(&mut self, actual_window_size: u8) {
+        self.peer_ceiling = self.peer_ceiling.min(actual_window_size.max(1));
+        self.current = self
+            .current
+            .saturating_add(1)
+            .min(self.configured)
+            .min(self.peer_ceiling)
+            .max(1);
+    }
+
+    /// Records a batch that timed out or was negatively acknowledged.
+    pub fn on_loss(&mut self) {
+        self.current = (self.current / 2).max(1);
+    }
+}
+
+/// How many of the `sent` segments of a window starting at `first_sequence` a Segment-ACK
+/// for `acked_sequence` acknowledges, or `None` if it lies outside the window.
+///
+/// Per Clause 5.3 an ACK, positive or negative, covers every segment up to and including
+/// its sequence number; a negative ACK for `first_sequence - 1` means none of the window
+/// arrived in order. The sender resumes with the segment after the acknowledged ones.
+pub fn acknowledged_segments(first_sequence: u8, sent: u8, acked_sequence: u8) -> Option<u8> {
+    let offset = acked_sequence.wrapping_sub(first_sequence.wrapping_sub(1));
+    (offset <= sent).then_some(offset)
+}
+
+/// Why a [`ClientTransaction`] failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionError {
+    Decode(DecodeError),
+    Encode(EncodeError),
+    /// The server answered with a BACnet Error.
+    Remote(BacnetError),
+    Reject(RejectPdu),
+    Abort(AbortPdu),
+    /// The server broke the segmentation protocol, e.g. an unnumbered segment.
+    UnexpectedResponse,
+    /// The Complex-ACK does not fit the response buffer or payload limit.
+    ResponseTooLarge,
+    /// No (complete) response arrived within the allowed retries.
+    Timeout,
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decode(err) => write!(f, "decode error: {err}"),
+            Self::Encode(err) => write!(f, "encode error: {err}"),
+            Self::Remote(err) => write!(
+                f,
+                "remote error for service {}: class {:?} code {:?}",
+                err.service_choice, err.error_class, err.error_code
+            ),
+            Self::Reject(reject) => write!(f, "request rejected (reason {})", reject.reason),
+            Self::Abort(abort) => write!(f, "request aborted (reason {})", abort.reason),
+            Self::UnexpectedResponse => f.write_str("unexpected segmented response"),
+            Self::ResponseTooLarge => f.write_str("response does not fit the buffer"),
+            Self::Timeout => f.write_str("request timed out"),
+        }
+    }
+}
+
+impl TransactionError {
+    /// Stable, machine-readable identifier for the error kind (e.g. `"transaction.timeout"`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Decode(err) => err.code(),
+            Self::Encode(err) => err.code(),
+            Self::Remote(_) => "transaction.remote_error",
+            Self::Reject(_) => "transaction.reject",
+            Self::Abort(_) => "transaction.abort",
+            Self::UnexpectedResponse => "transaction.unexpected_response",
+            Self::ResponseTooLarge => "transaction.response_too_large",
+            Self::Timeout => "transaction.timeout",
+        }
+    }
+}
+
+impl From<DecodeError> for TransactionError {
+    fn from(err: DecodeError) -> Self {
+        Self::Decode(err)
+    }
+}
+
+impl From<EncodeError> for TransactionError {
+    fn from(err: EncodeError) -> Self {
+        Self::Encode(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TransactionError {}
+
+/// Storage for a reassembled Complex-ACK payload.
+pub trait PayloadBuffer {
+    /// Appends `bytes`, returning `false` (and appending nothing) if they do not fit.
+    fn append(&mut self, bytes: &[u8]) -> bool;
+
+    /// The bytes appended so far.
+    fn as_bytes(&self) -> &[u8];
+}
+
+/// A [`PayloadBuffer`] over a fixed, caller-provided slice.
+#[derive(Debug)]
+pub struct SliceBuffer<'b> {
+    buf: &'b mut [u8],
+    len: usize,
+}
+
+impl<'b> SliceBuffer<'b> {
+    pub fn new(buf: &'b mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+}
+
+impl PayloadBuffer for SliceBuffer<'_> {
+    fn append(&mut self, bytes: &[u8]) -> bool {
+        let Some(end) = self
+            .len
+            .checked_add(bytes.len())
+            .filter(|end| *end <= self.buf.len())
+        else {
+            return false;
+        };
+        self.buf[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+        true
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl PayloadBuffer for alloc::vec::Vec<u8> {
+    fn append(&mut self, bytes: &[u8]) -> bool {
+        self.extend_from_slice(bytes);
+        true
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+/// What the caller should do after [`ClientTransaction::handle_apdu`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionStep {
+    /// The APDU belongs to something else; keep waiting.
+    Ignored,
+    /// Send the Segment-ACK APDU in `reply[..len]` (behind an NPDU) and keep waiting.
+    Reply(usize),
+    /// The response is complete; send `reply[..len]` first if `reply` is set.
+    Complete { reply: Option<usize> },
+    /// The device has the request but needs more time to answer; call
+    /// [`on_reply_postponed`](ClientTransaction::on_reply_postponed) and keep waiting.
+    Postponed,
+}
+
+/// What the caller should do after [`ClientTransaction::poll_timeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerStep {
+    /// Keep waiting for a response until this time.
+    Wait(u64),
+    /// The deadline passed: resend the request and call
+    /// [`on_sent`](ClientTransaction::on_sent) again.
+    Resend,
+}
+
+/// One outstanding confirmed request, from the initiator's side.
+///
+/// Requests are sent unsegmented; Complex-ACKs may be segmented and are reassembled
+/// into the [`PayloadBuffer`] passed to [`new`](Self::new).
+///
+/// A slow device can postpone its reply: a Segment-ACK from the server for the
+/// unsegmented request means it is still working on it, and an Abort with reason
+/// application-exceeded-reply-time means it gave up in time to be asked again. Both
+/// switch the transaction to the longer postponed-reply timeout (see
+/// [`with_postponed_timeout_ms`](Self::with_postponed_timeout_ms)); the Abort also
+/// resends the request if retries remain.
+///
+/// Time is an input: every method that depends on it takes `now`, a millisecond count
+/// on any monotonic clock the caller chooses. The transaction never reads a clock
+/// itself, so the same sequence of calls always produces the same result.
+#[derive(Debug)]
+pub struct ClientTransaction<B> {
+    invoke_id: u8,
+    service_choice: u8,
+    buffer: B,
+    payload_limit: usize,
+    segments: Option<SegmentReceiver>,
+    complete: bool,
+    timeout_ms: u64,
+    postponed_timeout_ms: Option<u64>,
+    retries_remaining: u8,
+    deadline: Option<u64>,
+    postponed: bool,
+    resend_postponed: bool,
+}
+
+impl<B: PayloadBuffer> ClientTransaction<B> {
+    /// Tracks the request `invoke_id` / `service_choice`, reassembling any Complex-ACK
+    /// payload into `buffer`.
+    pub fn new(invoke_id: u8, service_choice: u8, buffer: B) -> Self {
+        Self {
+            invoke_id,
+            service_choice,
+            buffer,
+            payload_limit: usize::MAX,
+            segments: None,
+            complete: false,
+            timeout_ms: DEFAULT_APDU_TIMEOUT_MS,
+            postponed_timeout_ms: None,
+            retries_remaining: DEFAULT_RETRIES,
+            deadline: None,
+            postponed: false,
+            resend_postponed: false,
+        }
+    }
+
+    /// Sets how many times [`poll_timeout`](Self::poll_timeout) asks for a resend.
+    pub fn with_retries(mut self, retries: u8) -> Self {
+        self.retries_remaining = retries;
+        self
+    }
+
+    /// Sets how long to wait for a (complete) response after each send.
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Sets how long to wait after the device postponed its reply; the response timeout
+    /// when unset.
+    pub fn with_postponed_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.postponed_timeout_ms = Some(timeout_ms);
+        self
+    }
+
+    /// Caps the reassembled payload, for buffers that grow without bound.
+    pub fn with_payload_limit(mut self, limit: usize) -> Self {
+        self.payload_limit = limit;
+        self
+    }
+
+    pub fn invoke_id(&self) -> u8 {
+        self.invoke_id
+    }
+
+    pub fn service_choice(&self) -> u8 {
+        self.service_choice
+    }
+
+    /// `true` once a Simple-ACK or the last Complex-ACK segment has arrived.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// The Complex-ACK service data received so far; empty for a Simple-ACK.
+    pub fn payload(&self) -> &[u8] {
+        self.buffer.as_bytes()
+    }
+
+    /// Consumes the transaction, returning the payload buffer.
+    pub fn into_payload(self) -> B {
+        self.buffer
+    }
+
+    /// `true` once the device postponed its reply.
+    pub fn is_reply_postponed(&self) -> bool {
+        self.postponed
+    }
+
+    /// Records that the request was (re)sent at `now`, starting the response timer.
+    pub fn on_sent(&mut self, now: u64) {
+        self.deadline = Some(now.saturating_add(self.current_timeout_ms()));
+    }
+
+    /// Restarts the response timer at `now` after [`TransactionStep::Postponed`], with
+    /// the postponed-reply timeout. After a postponing Abort the deadline is `now`, so
+    /// the next [`poll_timeout`](Self::poll_timeout) asks for the resend.
+    pub fn on_reply_postponed(&mut self, now: u64) {
+        if core::mem::take(&mut self.resend_postponed) {
+            self.deadline = Some(now);
+        } else {
+            self.deadline = Some(now.saturating_add(self.current_timeout_ms()));
+        }
+    }
+
+    fn current_timeout_ms(&self) -> u64 {
+        match self.postponed_timeout_ms {
+            Some(timeout_ms) if self.postponed => timeout_ms,
+            _ => self.timeout_ms,
+        }
+    }
+
+    /// When the caller should next call [`poll_timeout`](Self::poll_timeout); `None`
+    /// before the first [`on_sent`](Self::on_sent) and after completion.
+    pub fn deadline(&self) -> Option<u64> {
+        self.deadline.filter(|_| !self.complete)
+    }
+
+    /// Checks the response timer at `now`. Once the deadline passes this asks for a
+    /// resend (same invoke id) until the retries are used up; a segmented response that
+    /// stalls is never resent. Then it returns [`TransactionError::Timeout`].
+    pub fn poll_timeout(&mut self, now: u64) -> Result<TimerStep, TransactionError> {
+        if let Some(deadline) = self.deadline.filter(|deadline| now < *deadline) {
+            return Ok(TimerStep::Wait(deadline));
+        }
+        if self.complete || self.segments.is_some() || self.retries_remaining == 0 {
+            return Err(TransactionError::Timeout);
+        }
+        self.retries_remaining -= 1;
+        self.deadline = None;
+        Ok(TimerStep::Resend)
+    }
+
+    /// Processes one received APDU. Segment-ACKs to send are encoded into `reply`, which
+    /// needs at least 4 bytes.
+    pub fn handle_apdu(
+        &mut self,
+        apdu: &[u8],
+        reply: &mut [u8],
+    ) -> Result<TransactionStep, TransactionError> {
+        if self.complete {
+            return Ok(TransactionStep::Ignored);
+        }
+        let Some(response) = match_response(apdu, self.invoke_id, self.service_choice)? else {
+            return Ok(TransactionStep::Ignored);
+        };
+        match response {
+            ConfirmedResponse::SimpleAck => {
+                self.complete = true;
+                Ok(TransactionStep::Complete { reply: None })
+            }
+            ConfirmedResponse::ComplexAck { header, payload } => {
+                self.handle_complex_ack(&header, payload, reply)
+            }
+            // Requests are never segmented, so there is nothing for a Segment-ACK to pace:
+            // one from the server before any segment of the answer postpones the reply.
+            ConfirmedResponse::SegmentAck(ack) if ack.sent_by_server && self.segments.is_none() => {
+                self.postponed = true;
+                Ok(TransactionStep::Postponed)
+            }
+            ConfirmedResponse::SegmentAck(_) => Ok(TransactionStep::Ignored),
+            ConfirmedResponse::Error(err) => Err(TransactionError::Remote(err)),
+            ConfirmedResponse::Reject(reject) => Err(TransactionError::Reject(reject)),
+            ConfirmedResponse::Abort(abort)
+                if abort.server
+                    && abort.reason == abort_reason::APPLICATION_EXCEEDED_REPLY_TIME
+                    && self.segments.is_none()
+                    && self.retries_remaining > 0 =>
+            {
+                self.postponed = true;
+                self.resend_postponed = true;
+                Ok(TransactionStep::Postponed)
+            }
+            ConfirmedResponse::Abort(abort) => Err(TransactionError::Abort(abort)),
+        }
+    }
+
+    fn handle_complex_ack(
+        &mut self,
+        header: &ComplexAckHeader,
+        payload: &[u8],
+        reply: &mut [u8],
+    ) -> Result<TransactionStep, TransactionError> {
+        let receiver = match &mut self.segments {
+            None => {
+                self.append(payload)?;
+                match SegmentReceiver::start(header)
+                    .map_err(|_| TransactionError::UnexpectedResponse)?
+                {
+                    Some(receiver) => self.segments.insert(receiver),
+                    None => {
+                        self.complete = true;
+                        return Ok(TransactionStep::Complete { reply: None });
+                    }
+                }
+            }
+            Some(receiver) => match receiver
+                .accept(header)
+                .map_err(|_| TransactionError::UnexpectedResponse)?
+            {
+                SegmentDisposition::OutOfOrder => return Ok(TransactionStep::Ignored),
+                SegmentDisposition::Duplicate => {
+                    let len = encode_segment_ack(receiver.segment_ack(), reply)?;
+                    return Ok(TransactionStep::Reply(len));
+                }
+                SegmentDisposition::Accepted => {
+                    let receiver = *receiver;
+                    self.append(payload)?;
+                    self.segments.insert(receiver)
+                }
+            },
+        };
+        let len = encode_segment_ack(receiver.segment_ack(), reply)?;
+        if receiver.is_complete() {
+            self.complete = true;
+            Ok(TransactionStep::Complete { reply: Some(len) })
+        } else {
+            Ok(TransactionStep::Reply(len))
+        }
+    }
+
+    fn append(&mut self, payload: &[u8]) -> Result<(), TransactionError> {
+        let within_limit = self
+            .buffer
+            .as_bytes()
+            .len()
+            .checked_add(payload.len())
+            .is_some_and(|len| len <= self.payload_limit);
+        if within_limit && self.buffer.append(payload) {
+            Ok(())
+        } else {
+            Err(TransactionError::ResponseTooLarge)
+        }
+    }
+}
+
+fn encode_segment_ack(ack: SegmentAck, reply: &mut [u8]) -> Result<usize, TransactionError> {
+    let mut w = Writer::new(reply);
+    ack.encode(&mut w)?;
+    Ok(w.as_written().len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn complex_ack(seq: Option<u8>, more_follows: bool, payload: &[u8], out: &mut [u8]) -> usize {
+        let mut w = Writer::new(out);
+        ComplexAckHeader {
+            segmented: seq.is_some(),
+            more_follows,
+            invoke_id: 9,
+            sequence_number: seq,
+            proposed_window_size: seq.map(|_| 2),
+            service_choice: 0x0E,
+        }
+        .encode(&mut w)
+        .unwrap();
+        w.write_all(payload).unwrap();
+        w.as_written().len()
+    }
+
+    #[test]
+    fn simple_ack_completes_and_unrelated_apdus_are_ignored() {
+        let mut buf = [0u8; 16];
+        let mut reply = [0u8; 4];
+        let mut txn = ClientTransaction::new(5, 0x0F, SliceBuffer::new(&mut buf));
+        // Other invoke id, then an unconfirmed request.
+        assert_eq!(
+            txn.handle_apdu(&[0x20, 0x06, 0x0F], &mut reply),
+            Ok(TransactionStep::Ignored)
+        );
+        assert_eq!(
+            txn.handle_apdu(&[0x10, 0x08], &mut reply),
+            Ok(TransactionStep::Ignored)
+        );
+        assert_eq!(
+            txn.handle_apdu(&[0x20, 0x05, 0x0F], &mut reply),
+            Ok(TransactionStep::Complete { reply: None })
+        );
+        assert!(txn.is_complete());
+        assert!(txn.payload().is_empty());
+    }
+
+    #[test]
+    fn reassembles_segmented_complex_ack() {
+        let mut buf = [0u8; 16];
+        let mut reply = [0u8; 4];
+        let mut frame = [0u8; 32];
+        let mut txn = ClientTransaction::new(9, 0x0E, SliceBuffer::new(&mut buf));
+
+        let n = complex_ack(Some(0), true, &[1, 2], &mut frame);
+        assert_eq!(
+            txn.handle_apdu(&frame[..n], &mut reply),
+            Ok(TransactionStep::Reply(4))
+        );
+        assert_eq!(reply, [0x40, 9, 0, 2]);
+
+        // A repeated segment is acknowledged again but not appended.
+        assert_eq!(
+            txn.handle_apdu(&frame[..n], &mut reply),
+            Ok(TransactionStep::Reply(4))
+        );
+        // A segment from the future is ignored.
+        let n = complex_ack(Some(5), true, &[9], &mut frame);
+        assert_eq!(
+            txn.handle_apdu(&frame[..n], &mut reply),
+            Ok(TransactionStep::Ignored)
+        );
+
+        let n = complex_ack(Some(1), false, &[3], &mut frame);
+        assert_eq!(
+            txn.handle_apdu(&frame[..n], &mut reply),
+            Ok(TransactionStep::Complete { reply: Some(4) })
+        );
+        assert_eq!(reply, [0x40, 9, 1, 2]);
+        assert_eq!(txn.payload(), &[1, 2, 3]);
+        assert_eq!(txn.deadline(), None);
+    }
+
+    #[test]
+    fn unsegmented_complex_ack_must_fit_the_buffer() {
+        let mut buf = [0u8; 2];
+        let mut reply = [0u8; 4];
+        let mut frame = [0u8; 32];
+        let mut txn = ClientTransaction::new(9, 0x0E, SliceBuffer::new(&mut buf));
+        let n = complex_ack(None, false, &[1, 2, 3], &mut frame);
+        assert_eq!(
+            txn.handle_apdu(&frame[..n], &mut reply),
+            Err(TransactionError::ResponseTooLarge)
+        );
+    }
+
+    #[test]
+    fn error_reject_and_abort_fail_the_transaction() {
+        let mut buf = [0u8; 8];
+        let mut reply = [0u8; 4];
+        let mut txn = ClientTransaction::new(3, 0x0F, SliceBuffer::new(&mut buf));
+        let error = [0x50, 0x03, 0x0F, 0x91, 0x02, 0x91, 0x20];
+        assert!(matches!(
+            txn.handle_apdu(&error, &mut reply),
+            Err(TransactionError::Remote(BacnetError {
+                error_class: Some(2),
+                error_code: Some(32),
+                ..
+            }))
+        ));
+        assert!(matches!(
+            txn.handle_apdu(&[0x60, 0x03, 0x04], &mut reply),
+            Err(TransactionError::Reject(RejectPdu { reason: 4, .. }))
+        ));
+        assert!(matches!(
+            txn.handle_apdu(&[0x71, 0x03, 0x04], &mut reply),
+            Err(TransactionError::Abort(AbortPdu { server: true, .. }))
+        ));
+    }
+
+    #[test]
+    fn timer_resends_until_retries_run_out() {
+        let mut buf = [0u8; 8];
+        let mut txn = ClientTransaction::new(1, 0x0C, SliceBuffer::new(&mut buf))
+            .with_timeout_ms(100)
+            .with_retries(1);
+        assert_eq!(txn.deadline(), None);
+        txn.on_sent(1_000);
+        assert_eq!(txn.poll_timeout(1_050), Ok(TimerStep::Wait(1_100)));
+        assert_eq!(txn.poll_timeout(1_100), Ok(TimerStep::Resend));
+        txn.on_sent(1_100);
+        assert_eq!(txn.deadline(), Some(1_200));
+        assert_eq!(txn.poll_timeout(1_250), Err(TransactionError::Timeout));
+    }
+
+    #[test]
+    fn postponed_reply_extends_the_timer_and_abort_resends() {
+        let mut buf = [0u8; 8];
+        let mut reply = [0u8; 4];
+        let mut txn = ClientTransaction::new(1, 0x0C, SliceBuffer::new(&mut buf))
+            .with_timeout_ms(100)
+            .with_postponed_timeout_ms(1_000)
+            .with_retries(1);
+        txn.on_sent(0);
+        // A client Segment-ACK is not the server's; the server's postpones the reply.
+        assert_eq!(
+            txn.handle_apdu(&[0x40, 0x01, 0x00, 0x01], &mut reply),
+            Ok(TransactionStep::Ignored)
+        );
+        assert_eq!(
+            txn.handle_apdu(&[0x41, 0x01, 0x00, 0x01], &mut reply),
+            Ok(TransactionStep::Postponed)
+        );
+        assert!(txn.is_reply_postponed());
+        txn.on_reply_postponed(50);
+        assert_eq!(txn.poll_timeout(500), Ok(TimerStep::Wait(1_050)));
+
+        assert_eq!(
+            txn.handle_apdu(&[0x71, 0x01, 0x08], &mut reply),
+            Ok(TransactionStep::Postponed)
+        );
+        txn.on_reply_postponed(600);
+        assert_eq!(txn.poll_timeout(600), Ok(TimerStep::Resend));
+        txn.on_sent(600);
+        assert_eq!(txn.deadline(), Some(1_600));
+        // Without retries left the Abort fails the transaction.
+        assert!(matches!(
+            txn.handle_apdu(&[0x71, 0x01, 0x08], &mut reply),
+            Err(TransactionError::Abort(AbortPdu { reason: 8, .. }))
+        ));
+    }
+
+    #[test]
+    fn stalled_segmented_response_is_not_resent() {
+        let mut buf = [0u8; 16];
+        let mut reply = [0u8; 4];
+        let mut frame = [0u8; 32];
+        let mut txn =
+            ClientTransaction::new(9, 0x0E, SliceBuffer::new(&mut buf)).with_timeout_ms(100);
+        txn.on_sent(0);
+        let n = complex_ack(Some(0), true, &[1], &mut frame);
+        assert_eq!(
+            txn.handle_apdu(&frame[..n], &mut reply),
+            Ok(TransactionStep::Reply(4))
+        );
+        assert_eq!(txn.poll_timeout(100), Err(TransactionError::Timeout));
+    }
+
+    #[test]
+    fn unnumbered_segment_is_an_unexpected_response() {
+        let mut buf = [0u8; 16];
+        let mut reply = [0u8; 4];
+        let mut frame = [0u8; 32];
+        let mut txn = ClientTransaction::new(9, 0x0E, SliceBuffer::new(&mut buf));
+        let n = complex_ack(Some(0), true, &[1], &mut frame);
+        txn.handle_apdu(&frame[..n], &mut reply).unwrap();
+        let n = complex_ack(None, false, &[2], &mut frame);
+        assert_eq!(
+            txn.handle_apdu(&frame[..n], &mut reply),
+            Err(TransactionError::UnexpectedResponse)
+        );
+    }
+
+    #[test]
+    fn payload_limit_applies_to_growable_buffers() {
+        let mut reply = [0u8; 4];
+        let mut frame = [0u8; 32];
+        let n = complex_ack(None, false, &[1, 2, 3], &mut frame);
+        let mut buf = [0u8; 8];
+        let mut txn =
+            ClientTransaction::new(9, 0x0E, SliceBuffer::new(&mut buf)).with_payload_limit(2);
+        assert_eq!(
+            txn.handle_apdu(&frame[..n], &mut reply),
+            Err(TransactionError::ResponseTooLarge)
+        );
+    }
+
+    #[test]
+    fn segment_window_grows_to_the_peer_ceiling_and_halves_on_loss() {
+        let mut window = SegmentWindow::new(4);
+        window.on_loss();
+        assert_eq!(window.size(), 2);
+        window.on_ack(3);
+        assert_eq!(window.size(), 3);
+        window.on_ack(3);
+        assert_eq!(window.size(), 3);
+        window.on_loss();
+        window.on_loss();
+        window.on_loss();
+        assert_eq!(window.size(), 1);
+        assert_eq!(SegmentWindow::new(0).size(), 1);
+    }
+
+    #[test]
+    fn acknowledged_segments_counts_from_the_window_start() {
+        assert_eq!(acknowledged_segments(4, 4, 7), Some(4));
+        assert_eq!(acknowledged_segments(4, 4, 5), Some(2));
+        assert_eq!(acknowledged_segments(4, 4, 3), Some(0));
+        assert_eq!(acknowledged_segments(4, 4, 8), None);
+        assert_eq!(acknowledged_segments(4, 4, 1), None);
+        // Sequence numbers wrap modulo 256.
+        assert_eq!(acknowledged_segments(254, 4, 1), Some(4));
+        assert_eq!(acknowledged_segments(0, 2, 255), Some(0));
+    }
+
+    #[test]
+    fn split_npdu_returns_the_apdu() {
+        let (npdu, apdu) = split_npdu(&[0x01, 0x00, 0x20, 0x01, 0x0F]).unwrap();
+        assert_eq!(npdu.control, 0);
+        assert_eq!(apdu, &[0x20, 0x01, 0x0F]);
+    }
+}
@@ -0,0 +1,235 @@
+/// BACnetLifeSafetyState: the condition a Life Safety Point or Zone reports in its
+/// Present_Value and Tracking_Value. Values 256 and up are proprietary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum LifeSafetyState {
+    Quiet = 0,
+    PreAlarm = 1,
+    Alarm = 2,
+    Fault = 3,
+    FaultPreAlarm = 4,
+    FaultAlarm = 5,
+    NotReady = 6,
+    Active = 7,
+    Tamper = 8,
+    TestAlarm = 9,
+    TestActive = 10,
+    TestFault = 11,
+    TestFaultAlarm = 12,
+    Holdup = 13,
+    Duress = 14,
+    TamperAlarm = 15,
+    Abnormal = 16,
+    EmergencyPower = 17,
+    Delayed = 18,
+    Blocked = 19,
+    LocalAlarm = 20,
+    GeneralAlarm = 21,
+    Supervisory = 22,
+    TestSupervisory = 23,
+}
+
+impl LifeSafetyState {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Quiet),
+            1 => Some(Self::PreAlarm),
+            2 => Some(Self::Alarm),
+            3 => Some(Self::Fault),
+            4 => Some(Self::FaultPreAlarm),
+            5 => Some(Self::FaultAlarm),
+            6 => Some(Self::NotReady),
+            7 => Some(Self::Active),
+            8 => Some(Self::Tamper),
+            9 => Some(Self::TestAlarm),
+            10 => Some(Self::TestActive),
+            11 => Some(Self::TestFault),
+            12 => Some(Self::TestFaultAlarm),
+            13 => Some(Self::Holdup),
+            14 => Some(Self::Duress),
+            15 => Some(Self::TamperAlarm),
+            16 => Some(Self::Abnormal),
+            17 => Some(Self::EmergencyPower),
+            18 => Some(Self::Delayed),
+            19 => Some(Self::Blocked),
+            20 => Some(Self::LocalAlarm),
+            21 => Some(Self::GeneralAlarm),
+            22 => Some(Self::Supervisory),
+            23 => Some(Self::TestSupervisory),
+            _ => None,
+        }
+    }
+
+    /// Whether the state is one of the alarm states an operator must respond to
+    /// (alarm, fault-alarm, holdup, duress, tamper-alarm, local-alarm, general-alarm).
+    pub const fn is_alarm(self) -> bool {
+        matches!(
+            self,
+            Self::Alarm
+                | Self::FaultAlarm
+                | Self::Holdup
+                | Self::Duress
+                | Self::TamperAlarm
+                | Self::LocalAlarm
+                | Self::GeneralAlarm
+        )
+    }
+}
+
+/// BACnetLifeSafetyMode: the operating mode of a Life Safety Point or Zone. Values 256
+/// and up are proprietary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum LifeSafetyMode {
+    Off = 0,
+    On = 1,
+    Test = 2,
+    Manned = 3,
+    Unmanned = 4,
+    Armed = 5,
+    Disarmed = 6,
+    Prearmed = 7,
+    Slow = 8,
+    Fast = 9,
+    Disconnected = 10,
+    Enabled = 11,
+    Disabled = 12,
+    AutomaticReleaseDisabled = 13,
+    Default = 14,
+    ActivatedOeoAlarm = 15,
+    ActivatedOeoEvacuate = 16,
+    ActivatedOeoPhase1Recall = 17,
+    ActivatedOeoUnavailable = 18,
+    Deactivated = 19,
+}
+
+impl LifeSafetyMode {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Off),
+            1 => Some(Self::On),
+            2 => Some(Self::Test),
+            3 => Some(Self::Manned),
+            4 => Some(Self::Unmanned),
+            5 => Some(Self::Armed),
+            6 => Some(Self::Disarmed),
+            7 => Some(Self::Prearmed),
+            8 => Some(Self::Slow),
+            9 => Some(Self::Fast),
+            10 => Some(Self::Disconnected),
+            11 => Some(Self::Enabled),
+            12 => Some(Self::Disabled),
+            13 => Some(Self::AutomaticReleaseDisabled),
+            14 => Some(Self::Default),
+            15 => Some(Self::ActivatedOeoAlarm),
+            16 => Some(Self::ActivatedOeoEvacuate),
+            17 => Some(Self::ActivatedOeoPhase1Recall),
+            18 => Some(Self::ActivatedOeoUnavailable),
+            19 => Some(Self::Deactivated),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetLifeSafetyOperation: a silence or reset request, as sent with
+/// LifeSafetyOperation and reported in Operation_Expected. Values 64 and up are
+/// proprietary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum LifeSafetyOperation {
+    None = 0,
+    /// Silence audible and visual notification appliances.
+    Silence = 1,
+    SilenceAudible = 2,
+    SilenceVisual = 3,
+    /// Reset latched alarms and faults.
+    Reset = 4,
+    ResetAlarm = 5,
+    ResetFault = 6,
+    Unsilence = 7,
+    UnsilenceAudible = 8,
+    UnsilenceVisual = 9,
+}
+
+impl LifeSafetyOperation {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::None),
+            1 => Some(Self::Silence),
+            2 => Some(Self::SilenceAudible),
+            3 => Some(Self::SilenceVisual),
+            4 => Some(Self::Reset),
+            5 => Some(Self::ResetAlarm),
+            6 => Some(Self::ResetFault),
+            7 => Some(Self::Unsilence),
+            8 => Some(Self::UnsilenceAudible),
+            9 => Some(Self::UnsilenceVisual),
+            _ => None,
+        }
+    }
+}
+
+/// BACnetSilencedState: which notification appliances of a Life Safety Point or Zone
+/// are silenced. Values 64 and up are proprietary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum SilencedState {
+    Unsilenced = 0,
+    AudibleSilenced = 1,
+    VisibleSilenced = 2,
+    AllSilenced = 3,
+}
+
+impl SilencedState {
+    pub const fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Unsilenced),
+            1 => Some(Self::AudibleSilenced),
+            2 => Some(Self::VisibleSilenced),
+            3 => Some(Self::AllSilenced),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LifeSafetyMode, LifeSafetyOperation, LifeSafetyState, SilencedState};
+
+    #[test]
+    fn life_safety_enums_round_trip() {
+        for value in 0..24 {
+            assert_eq!(LifeSafetyState::from_u32(value).unwrap().to_u32(), value);
+        }
+        for value in 0..20 {
+            assert_eq!(LifeSafetyMode::from_u32(value).unwrap().to_u32(), value);
+        }
+        for value in 0..10 {
+            assert_eq!(
+                LifeSafetyOperation::from_u32(value).unwrap().to_u32(),
+                value
+            );
+        }
+        assert_eq!(SilencedState::from_u32(3), Some(SilencedState::AllSilenced));
+        assert_eq!(LifeSafetyState::from_u32(24), None);
+        assert_eq!(LifeSafetyMode::from_u32(256), None);
+        assert!(LifeSafetyState::Duress.is_alarm());
+        assert!(!LifeSafetyState::PreAlarm.is_alarm());
+    }
+}
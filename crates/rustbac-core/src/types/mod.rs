@@ -4,19 +4,28 @@ pub mod bit_string;
 pub mod data_value;
 /// BACnet date and time types.
 pub mod date_time;
+/// Life safety state, mode, operation and silenced-state enumerations.
+pub mod life_safety;
 /// Packed object identifier (type + instance).
 pub mod object_id;
 /// BACnet object type enumeration.
 pub mod object_type;
 /// BACnet property identifier enumeration.
 pub mod property_id;
+/// Property, object-property and device-object-property references.
+pub mod property_reference;
 /// Protocol-level enumerations (segmentation, max APDU, errors).
 pub mod spec;
 
 pub use bit_string::BitString;
 pub use data_value::DataValue;
 pub use date_time::{Date, Time};
+pub use life_safety::{LifeSafetyMode, LifeSafetyOperation, LifeSafetyState, SilencedState};
 pub use object_id::ObjectId;
 pub use object_type::ObjectType;
 pub use property_id::PropertyId;
+pub use property_reference::{
+    DeviceObjectPropertyReference, DeviceObjectReference, ObjectPropertyReference,
+    PropertyReference,
+};
 pub use spec::{ErrorClass, ErrorCode, MaxApdu, Segmentation};
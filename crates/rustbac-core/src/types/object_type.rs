@@ -1,7 +1,8 @@
 /// BACnet object type identifiers as defined in the BACnet specification.
 ///
-/// Known standard types are represented as named variants; proprietary
-/// vendor-specific types use the [`Proprietary`](Self::Proprietary) variant.
+/// Every standard type of ASHRAE 135-2020 and its addenda (network port, lighting,
+/// elevator, staging, audit, color) is a named variant; proprietary vendor-specific types
+/// use the [`Proprietary`](Self::Proprietary) variant.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ObjectType {
@@ -33,8 +34,43 @@ pub enum ObjectType {
     EventLog,
     GlobalGroup,
     TrendLogMultiple,
+    LoadControl,
     StructuredView,
     AccessDoor,
+    Timer,
+    AccessCredential,
+    AccessPoint,
+    AccessRights,
+    AccessUser,
+    AccessZone,
+    CredentialDataInput,
+    NetworkSecurity,
+    BitstringValue,
+    CharacterstringValue,
+    DatePatternValue,
+    DateValue,
+    DatetimePatternValue,
+    DatetimeValue,
+    IntegerValue,
+    LargeAnalogValue,
+    OctetstringValue,
+    PositiveIntegerValue,
+    TimePatternValue,
+    TimeValue,
+    NotificationForwarder,
+    AlertEnrollment,
+    Channel,
+    LightingOutput,
+    BinaryLightingOutput,
+    NetworkPort,
+    ElevatorGroup,
+    Escalator,
+    Lift,
+    Staging,
+    AuditLog,
+    AuditReporter,
+    Color,
+    ColorTemperature,
     Proprietary(u16),
 }
 
@@ -70,8 +106,43 @@ impl ObjectType {
             Self::EventLog => 25,
             Self::GlobalGroup => 26,
             Self::TrendLogMultiple => 27,
+            Self::LoadControl => 28,
             Self::StructuredView => 29,
             Self::AccessDoor => 30,
+            Self::Timer => 31,
+            Self::AccessCredential => 32,
+            Self::AccessPoint => 33,
+            Self::AccessRights => 34,
+            Self::AccessUser => 35,
+            Self::AccessZone => 36,
+            Self::CredentialDataInput => 37,
+            Self::NetworkSecurity => 38,
+            Self::BitstringValue => 39,
+            Self::CharacterstringValue => 40,
+            Self::DatePatternValue => 41,
+            Self::DateValue => 42,
+            Self::DatetimePatternValue => 43,
+            Self::DatetimeValue => 44,
+            Self::IntegerValue => 45,
+            Self::LargeAnalogValue => 46,
+            Self::OctetstringValue => 47,
+            Self::PositiveIntegerValue => 48,
+            Self::TimePatternValue => 49,
+            Self::TimeValue => 50,
+            Self::NotificationForwarder => 51,
+            Self::AlertEnrollment => 52,
+            Self::Channel => 53,
+            Self::LightingOutput => 54,
+            Self::BinaryLightingOutput => 55,
+            Self::NetworkPort => 56,
+            Self::ElevatorGroup => 57,
+            Self::Escalator => 58,
+            Self::Lift => 59,
+            Self::Staging => 60,
+            Self::AuditLog => 61,
+            Self::AuditReporter => 62,
+            Self::Color => 63,
+            Self::ColorTemperature => 64,
             Self::Proprietary(v) => v,
         }
     }
@@ -109,8 +180,43 @@ impl ObjectType {
             25 => Self::EventLog,
             26 => Self::GlobalGroup,
             27 => Self::TrendLogMultiple,
+            28 => Self::LoadControl,
             29 => Self::StructuredView,
             30 => Self::AccessDoor,
+            31 => Self::Timer,
+            32 => Self::AccessCredential,
+            33 => Self::AccessPoint,
+            34 => Self::AccessRights,
+            35 => Self::AccessUser,
+            36 => Self::AccessZone,
+            37 => Self::CredentialDataInput,
+            38 => Self::NetworkSecurity,
+            39 => Self::BitstringValue,
+            40 => Self::CharacterstringValue,
+            41 => Self::DatePatternValue,
+            42 => Self::DateValue,
+            43 => Self::DatetimePatternValue,
+            44 => Self::DatetimeValue,
+            45 => Self::IntegerValue,
+            46 => Self::LargeAnalogValue,
+            47 => Self::OctetstringValue,
+            48 => Self::PositiveIntegerValue,
+            49 => Self::TimePatternValue,
+            50 => Self::TimeValue,
+            51 => Self::NotificationForwarder,
+            52 => Self::AlertEnrollment,
+            53 => Self::Channel,
+            54 => Self::LightingOutput,
+            55 => Self::BinaryLightingOutput,
+            56 => Self::NetworkPort,
+            57 => Self::ElevatorGroup,
+            58 => Self::Escalator,
+            59 => Self::Lift,
+            60 => Self::Staging,
+            61 => Self::AuditLog,
+            62 => Self::AuditReporter,
+            63 => Self::Color,
+            64 => Self::ColorTemperature,
             v => Self::Proprietary(v),
         }
     }
@@ -148,8 +254,43 @@ impl ObjectType {
             "event-log" => Some(Self::EventLog),
             "global-group" => Some(Self::GlobalGroup),
             "trend-log-multiple" => Some(Self::TrendLogMultiple),
+            "load-control" => Some(Self::LoadControl),
             "structured-view" => Some(Self::StructuredView),
             "access-door" => Some(Self::AccessDoor),
+            "timer" => Some(Self::Timer),
+            "access-credential" => Some(Self::AccessCredential),
+            "access-point" => Some(Self::AccessPoint),
+            "access-rights" => Some(Self::AccessRights),
+            "access-user" => Some(Self::AccessUser),
+            "access-zone" => Some(Self::AccessZone),
+            "credential-data-input" => Some(Self::CredentialDataInput),
+            "network-security" => Some(Self::NetworkSecurity),
+            "bitstring-value" => Some(Self::BitstringValue),
+            "characterstring-value" => Some(Self::CharacterstringValue),
+            "date-pattern-value" => Some(Self::DatePatternValue),
+            "date-value" => Some(Self::DateValue),
+            "datetime-pattern-value" => Some(Self::DatetimePatternValue),
+            "datetime-value" => Some(Self::DatetimeValue),
+            "integer-value" => Some(Self::IntegerValue),
+            "large-analog-value" => Some(Self::LargeAnalogValue),
+            "octetstring-value" => Some(Self::OctetstringValue),
+            "positive-integer-value" => Some(Self::PositiveIntegerValue),
+            "time-pattern-value" => Some(Self::TimePatternValue),
+            "time-value" => Some(Self::TimeValue),
+            "notification-forwarder" => Some(Self::NotificationForwarder),
+            "alert-enrollment" => Some(Self::AlertEnrollment),
+            "channel" => Some(Self::Channel),
+            "lighting-output" => Some(Self::LightingOutput),
+            "binary-lighting-output" => Some(Self::BinaryLightingOutput),
+            "network-port" => Some(Self::NetworkPort),
+            "elevator-group" => Some(Self::ElevatorGroup),
+            "escalator" => Some(Self::Escalator),
+            "lift" => Some(Self::Lift),
+            "staging" => Some(Self::Staging),
+            "audit-log" => Some(Self::AuditLog),
+            "audit-reporter" => Some(Self::AuditReporter),
+            "color" => Some(Self::Color),
+            "color-temperature" => Some(Self::ColorTemperature),
             _ => None,
         }
     }
@@ -186,9 +327,66 @@ impl core::fmt::Display for ObjectType {
             Self::EventLog => f.write_str("event-log"),
             Self::GlobalGroup => f.write_str("global-group"),
             Self::TrendLogMultiple => f.write_str("trend-log-multiple"),
+            Self::LoadControl => f.write_str("load-control"),
             Self::StructuredView => f.write_str("structured-view"),
             Self::AccessDoor => f.write_str("access-door"),
+            Self::Timer => f.write_str("timer"),
+            Self::AccessCredential => f.write_str("access-credential"),
+            Self::AccessPoint => f.write_str("access-point"),
+            Self::AccessRights => f.write_str("access-rights"),
+            Self::AccessUser => f.write_str("access-user"),
+            Self::AccessZone => f.write_str("access-zone"),
+            Self::CredentialDataInput => f.write_str("credential-data-input"),
+            Self::NetworkSecurity => f.write_str("network-security"),
+            Self::BitstringValue => f.write_str("bitstring-value"),
+            Self::CharacterstringValue => f.write_str("characterstring-value"),
+            Self::DatePatternValue => f.write_str("date-pattern-value"),
+            Self::DateValue => f.write_str("date-value"),
+            Self::DatetimePatternValue => f.write_str("datetime-pattern-value"),
+            Self::DatetimeValue => f.write_str("datetime-value"),
+            Self::IntegerValue => f.write_str("integer-value"),
+            Self::LargeAnalogValue => f.write_str("large-analog-value"),
+            Self::OctetstringValue => f.write_str("octetstring-value"),
+            Self::PositiveIntegerValue => f.write_str("positive-integer-value"),
+            Self::TimePatternValue => f.write_str("time-pattern-value"),
+            Self::TimeValue => f.write_str("time-value"),
+            Self::NotificationForwarder => f.write_str("notification-forwarder"),
+            Self::AlertEnrollment => f.write_str("alert-enrollment"),
+            Self::Channel => f.write_str("channel"),
+            Self::LightingOutput => f.write_str("lighting-output"),
+            Self::BinaryLightingOutput => f.write_str("binary-lighting-output"),
+            Self::NetworkPort => f.write_str("network-port"),
+            Self::ElevatorGroup => f.write_str("elevator-group"),
+            Self::Escalator => f.write_str("escalator"),
+            Self::Lift => f.write_str("lift"),
+            Self::Staging => f.write_str("staging"),
+            Self::AuditLog => f.write_str("audit-log"),
+            Self::AuditReporter => f.write_str("audit-reporter"),
+            Self::Color => f.write_str("color"),
+            Self::ColorTemperature => f.write_str("color-temperature"),
             Self::Proprietary(v) => write!(f, "proprietary-{v}"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ObjectType;
+
+    #[test]
+    fn standard_types_round_trip_through_numbers_and_names() {
+        for value in 0..=64u16 {
+            let object_type = ObjectType::from_u16(value);
+            assert_ne!(object_type, ObjectType::Proprietary(value));
+            assert_eq!(object_type.to_u16(), value);
+            #[cfg(feature = "alloc")]
+            {
+                use alloc::string::ToString;
+                let name = object_type.to_string();
+                assert_eq!(ObjectType::from_name(&name), Some(object_type));
+            }
+        }
+        assert_eq!(ObjectType::from_u16(56), ObjectType::NetworkPort);
+        assert_eq!(ObjectType::from_u16(65), ObjectType::Proprietary(65));
+    }
+}
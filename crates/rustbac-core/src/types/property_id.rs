@@ -1,196 +1,1551 @@
 /// BACnet property identifiers.
 ///
-/// Common standard properties are named variants; vendor-specific or
-/// unrecognised identifiers use [`Proprietary`](Self::Proprietary).
+/// Every standard property of ASHRAE 135-2020 and its published addenda is a named
+/// variant, including the BACnet/SC and color properties numbered from 4194304 up.
+/// Vendor-specific (512–4194303) or unrecognised identifiers use
+/// [`Proprietary`](Self::Proprietary).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PropertyId {
+    AbsenteeLimit,
+    AcceptedModes,
+    AccessAlarmEvents,
+    AccessDoors,
+    AccessEvent,
+    AccessEventAuthenticationFactor,
+    AccessEventCredential,
+    AccessEventTag,
+    AccessEventTime,
+    AccessTransactionEvents,
+    Accompaniment,
+    AccompanimentTime,
+    AckRequired,
     AckedTransitions,
+    Action,
+    ActionText,
+    ActivationTime,
+    ActiveAuthenticationPolicy,
+    ActiveCovMultipleSubscriptions,
+    ActiveCovSubscriptions,
     ActiveText,
+    ActiveVtSessions,
+    ActualShedLevel,
+    AdditionalReferencePorts,
+    AdjustValue,
+    AlarmValue,
+    AlarmValues,
+    AlignIntervals,
+    All,
+    AllWritesSuccessful,
+    AllowGroupDelayInhibit,
+    ApduLength,
+    ApduSegmentTimeout,
     ApduTimeout,
     ApplicationSoftwareVersion,
+    Archive,
+    AssignedAccessRights,
+    AssignedLandingCalls,
+    AttemptedSamples,
+    AuditLevel,
+    AuditNotificationRecipient,
+    AuditPriorityFilter,
+    AuditSourceLevel,
+    AuditableOperations,
+    AuthenticationFactors,
+    AuthenticationPolicyList,
+    AuthenticationPolicyNames,
+    AuthenticationStatus,
+    AuthorizationExemptions,
+    AuthorizationMode,
+    AutoSlaveDiscovery,
+    AverageValue,
+    BackupAndRestoreState,
+    BackupFailureTimeout,
+    BackupPreparationTime,
+    BacnetIpGlobalAddress,
+    BacnetIpMode,
+    BacnetIpMulticastAddress,
+    BacnetIpNatTraversal,
+    BacnetIpUdpPort,
+    BacnetIpv6Mode,
+    BacnetIpv6MulticastAddress,
+    BacnetIpv6UdpPort,
+    BaseDeviceSecurityPolicy,
+    BbmdAcceptFdRegistrations,
+    BbmdBroadcastDistributionTable,
+    BbmdForeignDeviceTable,
+    BelongsTo,
+    Bias,
+    BitMask,
+    BitText,
+    BlinkWarnEnable,
     BufferSize,
+    CarAssignedDirection,
+    CarDoorCommand,
+    CarDoorStatus,
+    CarDoorText,
+    CarDoorZone,
+    CarDriveStatus,
+    CarLoad,
+    CarLoadUnits,
+    CarMode,
+    CarMovingDirection,
+    CarPosition,
+    CertificateSigningRequestFile,
+    ChangeOfStateCount,
+    ChangeOfStateTime,
+    ChangesPending,
+    ChannelNumber,
+    ClientCovIncrement,
+    ColorCommand,
+    ColorOverride,
+    ColorReference,
+    Command,
+    CommandTimeArray,
+    CommandValidationResult,
+    ConfigurationFiles,
+    ControlGroups,
+    ControlledVariableReference,
+    ControlledVariableUnits,
+    ControlledVariableValue,
+    Count,
+    CountBeforeChange,
+    CountChangeTime,
     CovIncrement,
+    CovPeriod,
+    CovResubscriptionInterval,
+    CovuPeriod,
+    CovuRecipients,
+    CredentialDisable,
+    CredentialStatus,
+    Credentials,
+    CredentialsInZone,
+    CurrentCommandPriority,
+    CurrentHealth,
     DatabaseRevision,
     DateList,
+    DaylightSavingsStatus,
+    DaysRemaining,
     Deadband,
+    DefaultColor,
+    DefaultColorTemperature,
+    DefaultFadeTime,
+    DefaultPresentValue,
+    DefaultRampRate,
+    DefaultStepIncrement,
+    DefaultSubordinateRelationship,
+    DefaultTimeout,
+    DeleteOnForward,
+    DeployedProfileLocation,
+    DerivativeConstant,
+    DerivativeConstantUnits,
     Description,
+    DescriptionOfHalt,
+    DeviceAddressBinding,
+    DeviceType,
+    DeviceUuid,
+    DirectReading,
+    DistributionKeyRevision,
+    DoNotHide,
+    DoorAlarmState,
+    DoorExtendedPulseTime,
+    DoorMembers,
+    DoorOpenTooLongTime,
+    DoorPulseTime,
+    DoorStatus,
+    DoorUnlockDelayTime,
+    DutyWindow,
     EffectivePeriod,
+    EgressActive,
+    EgressTime,
+    ElapsedActiveTime,
+    ElevatorGroup,
     Enable,
+    EnergyMeter,
+    EnergyMeterRef,
+    EntryPoints,
+    ErrorLimit,
+    EscalatorMode,
+    EventAlgorithmInhibit,
+    EventAlgorithmInhibitRef,
+    EventDetectionEnable,
     EventEnable,
+    EventMessageTexts,
+    EventMessageTextsConfig,
+    EventParameters,
     EventState,
     EventTimeStamps,
+    EventType,
     ExceptionSchedule,
+    ExecutionDelay,
+    ExitPoints,
+    ExpectedShedLevel,
+    ExpirationTime,
+    ExtendedTimeEnable,
+    FailedAttemptEvents,
+    FailedAttempts,
+    FailedAttemptsTime,
+    FaultHighLimit,
+    FaultLowLimit,
+    FaultParameters,
+    FaultSignals,
+    FaultType,
+    FaultValues,
+    FdBbmdAddress,
+    FdSubscriptionLifetime,
+    FeedbackValue,
+    FileAccessMethod,
+    FileSize,
+    FileType,
     FirmwareRevision,
+    FloorNumber,
+    FloorText,
+    FullDutyBaseline,
+    GlobalIdentifier,
+    GroupId,
+    GroupMemberNames,
+    GroupMembers,
+    GroupMode,
+    HighEndTrim,
     HighLimit,
+    HigherDeck,
+    InProcess,
+    InProgress,
     InactiveText,
+    InitialTimeout,
+    InputReference,
+    InstallationId,
+    InstanceOf,
+    InstantaneousPower,
+    IntegralConstant,
+    IntegralConstantUnits,
+    InterfaceValue,
+    IntervalOffset,
+    IpAddress,
+    IpDefaultGateway,
+    IpDhcpEnable,
+    IpDhcpLeaseTime,
+    IpDhcpLeaseTimeRemaining,
+    IpDhcpServer,
+    IpDnsServer,
+    IpSubnetMask,
+    Ipv6Address,
+    Ipv6AutoAddressingEnable,
+    Ipv6DefaultGateway,
+    Ipv6DhcpLeaseTime,
+    Ipv6DhcpLeaseTimeRemaining,
+    Ipv6DhcpServer,
+    Ipv6DnsServer,
+    Ipv6PrefixLength,
+    Ipv6ZoneIndex,
+    IsUtc,
+    IssuerCertificateFiles,
+    KeySets,
+    LandingCallControl,
+    LandingCalls,
+    LandingDoorStatus,
+    LastAccessEvent,
+    LastAccessPoint,
+    LastCommandTime,
+    LastCredentialAdded,
+    LastCredentialAddedTime,
+    LastCredentialRemoved,
+    LastCredentialRemovedTime,
+    LastKeyServer,
+    LastNotifyRecord,
+    LastPriority,
+    LastRestartReason,
+    LastRestoreTime,
+    LastStateChange,
+    LastUseTime,
+    LifeSafetyAlarmValues,
+    LightingCommand,
+    LightingCommandDefaultPriority,
     LimitEnable,
+    LimitMonitoringInterval,
+    LinkSpeed,
+    LinkSpeedAutonegotiate,
+    LinkSpeeds,
+    ListOfGroupMembers,
     ListOfObjectPropertyReferences,
+    LocalDate,
+    LocalForwardingOnly,
+    LocalTime,
+    Location,
+    LockStatus,
+    Lockout,
+    LockoutRelinquishTime,
     LogBuffer,
     LogDeviceObjectProperty,
     LogInterval,
+    LoggingObject,
+    LoggingRecord,
+    LoggingType,
+    LowDiffLimit,
+    LowEndTrim,
     LowLimit,
+    LowerDeck,
+    MacAddress,
+    MachineRoomId,
+    MaintenanceRequired,
+    MakingCarCall,
+    ManipulatedVariableReference,
+    ManualSlaveAddressBinding,
+    MaskedAlarmValues,
+    MaxActualValue,
     MaxApduLengthAccepted,
+    MaxBvlcLengthAccepted,
+    MaxFailedAttempts,
+    MaxInfoFrames,
+    MaxMaster,
+    MaxNpduLengthAccepted,
     MaxPresValue,
+    MaxSegmentsAccepted,
+    MaximumOutput,
+    MaximumSendDelay,
+    MaximumValue,
+    MaximumValueTimestamp,
+    MemberOf,
+    MemberStatusFlags,
+    Members,
+    MinActualValue,
     MinPresValue,
+    MinimumOffTime,
+    MinimumOnTime,
+    MinimumOutput,
+    MinimumValue,
+    MinimumValueTimestamp,
+    Mode,
     ModelName,
+    ModificationDate,
+    MonitoredObjects,
+    MusterPoint,
+    NegativeAccessRules,
+    NetworkAccessSecurityPolicies,
+    NetworkInterfaceName,
+    NetworkNumber,
+    NetworkNumberQuality,
+    NetworkType,
+    NextStoppingFloor,
+    NodeSubtype,
+    NodeType,
     NotificationClass,
+    NotificationThreshold,
     NotifyType,
     NumberOfApduRetries,
+    NumberOfAuthenticationPolicies,
+    NumberOfStates,
     ObjectIdentifier,
     ObjectList,
     ObjectName,
+    ObjectPropertyReference,
     ObjectType,
+    OccupancyCount,
+    OccupancyCountAdjust,
+    OccupancyCountEnable,
+    OccupancyLowerLimit,
+    OccupancyLowerLimitEnforced,
+    OccupancyState,
+    OccupancyUpperLimit,
+    OccupancyUpperLimitEnforced,
+    OperationDirection,
+    OperationExpected,
+    OperationalCertificateFile,
+    Optional,
     OutOfService,
+    OutputUnits,
+    OverrideColorReference,
+    PacketReorderTime,
+    PassbackMode,
+    PassbackTimeout,
+    PassengerAlarm,
+    Polarity,
+    PortFilter,
+    PositiveAccessRules,
+    Power,
+    PowerMode,
+    Prescale,
+    PresentStage,
     PresentValue,
+    Priority,
     PriorityArray,
+    PriorityForWriting,
+    ProcessIdentifier,
+    ProcessIdentifierFilter,
+    ProfileLocation,
+    ProfileName,
+    ProgramChange,
+    ProgramLocation,
+    ProgramState,
+    PropertyList,
+    ProportionalConstant,
+    ProportionalConstantUnits,
+    ProtocolLevel,
+    ProtocolObjectTypesSupported,
     ProtocolRevision,
+    ProtocolServicesSupported,
     ProtocolVersion,
+    PulseRate,
+    ReadOnly,
+    ReasonForDisable,
+    ReasonForHalt,
     RecipientList,
     RecordCount,
+    RecordsSinceNotification,
+    ReferencePort,
+    RegisteredCarCall,
     Reliability,
+    ReliabilityEvaluationInhibit,
     RelinquishDefault,
+    Represents,
+    RequestedShedLevel,
+    RequestedUpdateInterval,
+    Required,
     Resolution,
+    RestartNotificationRecipients,
+    RestoreCompletionTime,
+    RestorePreparationTime,
+    RoutingTable,
+    ScConnectWaitTimeout,
+    ScDirectConnectAcceptEnable,
+    ScDirectConnectAcceptUris,
+    ScDirectConnectBinding,
+    ScDirectConnectConnectionStatus,
+    ScDirectConnectInitiateEnable,
+    ScDisconnectWaitTimeout,
+    ScFailedConnectionRequests,
+    ScFailoverHubConnectionStatus,
+    ScFailoverHubUri,
+    ScHeartbeatTimeout,
+    ScHubConnectorState,
+    ScHubFunctionAcceptUris,
+    ScHubFunctionBinding,
+    ScHubFunctionConnectionStatus,
+    ScHubFunctionEnable,
+    ScMaximumReconnectTime,
+    ScMinimumReconnectTime,
+    ScPrimaryHubConnectionStatus,
+    ScPrimaryHubUri,
+    Scale,
+    ScaleFactor,
     ScheduleDefault,
+    SecuredStatus,
+    SecurityPduTimeout,
+    SecurityTimeWindow,
     SegmentationSupported,
+    SendNow,
+    SerialNumber,
+    Setpoint,
+    SetpointReference,
+    Setting,
+    ShedDuration,
+    ShedLevelDescriptions,
+    ShedLevels,
+    Silenced,
+    SlaveAddressBinding,
+    SlaveProxyEnable,
+    StageNames,
+    Stages,
     StartTime,
+    StateChangeValues,
+    StateDescription,
+    StateText,
     StatusFlags,
     StopTime,
+    StopWhenFull,
+    StrikeCount,
+    StructuredObjectList,
+    SubordinateAnnotations,
+    SubordinateList,
+    SubordinateNodeTypes,
+    SubordinateRelationships,
+    SubordinateTags,
+    SubscribedRecipients,
+    SupportedFormatClasses,
+    SupportedFormats,
+    SupportedSecurityAlgorithms,
     SystemStatus,
+    Tags,
+    TargetReferences,
+    ThreatAuthority,
+    ThreatLevel,
     TimeDelay,
+    TimeDelayNormal,
+    TimeOfActiveTimeReset,
+    TimeOfDeviceRestart,
+    TimeOfStateCountReset,
+    TimeOfStrikeCountReset,
+    TimeSynchronizationInterval,
+    TimeSynchronizationRecipients,
+    TimerRunning,
+    TimerState,
     TotalRecordCount,
+    TraceFlag,
+    TrackingValue,
+    TransactionNotificationClass,
+    Transition,
+    Trigger,
+    TrimFadeTime,
     Units,
     UpdateInterval,
+    UpdateKeySetTimeout,
+    UpdateTime,
+    UserExternalIdentifier,
+    UserInformationReference,
+    UserName,
+    UserType,
+    UsesRemaining,
+    UtcOffset,
+    UtcTimeSynchronizationRecipients,
+    ValidSamples,
+    ValueBeforeChange,
+    ValueChangeTime,
+    ValueSet,
+    ValueSource,
+    ValueSourceArray,
+    VarianceValue,
+    VendorIdentifier,
     VendorName,
+    VerificationTime,
+    VirtualMacAddressTable,
+    VtClassesSupported,
     WeeklySchedule,
+    WindowInterval,
+    WindowSamples,
+    WriteStatus,
+    ZoneFrom,
+    ZoneMembers,
+    ZoneTo,
     Proprietary(u32),
 }
 
 impl PropertyId {
     pub const fn to_u32(self) -> u32 {
         match self {
+            Self::AbsenteeLimit => 244,
+            Self::AcceptedModes => 175,
+            Self::AccessAlarmEvents => 245,
+            Self::AccessDoors => 246,
+            Self::AccessEvent => 247,
+            Self::AccessEventAuthenticationFactor => 248,
+            Self::AccessEventCredential => 249,
+            Self::AccessEventTag => 322,
+            Self::AccessEventTime => 250,
+            Self::AccessTransactionEvents => 251,
+            Self::Accompaniment => 252,
+            Self::AccompanimentTime => 253,
+            Self::AckRequired => 1,
             Self::AckedTransitions => 0,
+            Self::Action => 2,
+            Self::ActionText => 3,
+            Self::ActivationTime => 254,
+            Self::ActiveAuthenticationPolicy => 255,
+            Self::ActiveCovMultipleSubscriptions => 481,
+            Self::ActiveCovSubscriptions => 152,
             Self::ActiveText => 4,
+            Self::ActiveVtSessions => 5,
+            Self::ActualShedLevel => 212,
+            Self::AdditionalReferencePorts => 508,
+            Self::AdjustValue => 176,
+            Self::AlarmValue => 6,
+            Self::AlarmValues => 7,
+            Self::AlignIntervals => 193,
+            Self::All => 8,
+            Self::AllWritesSuccessful => 9,
+            Self::AllowGroupDelayInhibit => 365,
+            Self::ApduLength => 399,
+            Self::ApduSegmentTimeout => 10,
             Self::ApduTimeout => 11,
             Self::ApplicationSoftwareVersion => 12,
+            Self::Archive => 13,
+            Self::AssignedAccessRights => 256,
+            Self::AssignedLandingCalls => 447,
+            Self::AttemptedSamples => 124,
+            Self::AuditLevel => 498,
+            Self::AuditNotificationRecipient => 499,
+            Self::AuditPriorityFilter => 500,
+            Self::AuditSourceLevel => 497,
+            Self::AuditableOperations => 501,
+            Self::AuthenticationFactors => 257,
+            Self::AuthenticationPolicyList => 258,
+            Self::AuthenticationPolicyNames => 259,
+            Self::AuthenticationStatus => 260,
+            Self::AuthorizationExemptions => 364,
+            Self::AuthorizationMode => 261,
+            Self::AutoSlaveDiscovery => 169,
+            Self::AverageValue => 125,
+            Self::BackupAndRestoreState => 338,
+            Self::BackupFailureTimeout => 153,
+            Self::BackupPreparationTime => 339,
+            Self::BacnetIpGlobalAddress => 407,
+            Self::BacnetIpMode => 408,
+            Self::BacnetIpMulticastAddress => 409,
+            Self::BacnetIpNatTraversal => 410,
+            Self::BacnetIpUdpPort => 412,
+            Self::BacnetIpv6Mode => 435,
+            Self::BacnetIpv6MulticastAddress => 440,
+            Self::BacnetIpv6UdpPort => 438,
+            Self::BaseDeviceSecurityPolicy => 327,
+            Self::BbmdAcceptFdRegistrations => 413,
+            Self::BbmdBroadcastDistributionTable => 414,
+            Self::BbmdForeignDeviceTable => 415,
+            Self::BelongsTo => 262,
+            Self::Bias => 14,
+            Self::BitMask => 342,
+            Self::BitText => 343,
+            Self::BlinkWarnEnable => 373,
             Self::BufferSize => 126,
+            Self::CarAssignedDirection => 448,
+            Self::CarDoorCommand => 449,
+            Self::CarDoorStatus => 450,
+            Self::CarDoorText => 451,
+            Self::CarDoorZone => 452,
+            Self::CarDriveStatus => 453,
+            Self::CarLoad => 454,
+            Self::CarLoadUnits => 455,
+            Self::CarMode => 456,
+            Self::CarMovingDirection => 457,
+            Self::CarPosition => 458,
+            Self::CertificateSigningRequestFile => 509,
+            Self::ChangeOfStateCount => 15,
+            Self::ChangeOfStateTime => 16,
+            Self::ChangesPending => 416,
+            Self::ChannelNumber => 366,
+            Self::ClientCovIncrement => 127,
+            Self::ColorCommand => 4194334,
+            Self::ColorOverride => 4194328,
+            Self::ColorReference => 4194329,
+            Self::Command => 417,
+            Self::CommandTimeArray => 430,
+            Self::CommandValidationResult => 510,
+            Self::ConfigurationFiles => 154,
+            Self::ControlGroups => 367,
+            Self::ControlledVariableReference => 19,
+            Self::ControlledVariableUnits => 20,
+            Self::ControlledVariableValue => 21,
+            Self::Count => 177,
+            Self::CountBeforeChange => 178,
+            Self::CountChangeTime => 179,
             Self::CovIncrement => 22,
+            Self::CovPeriod => 180,
+            Self::CovResubscriptionInterval => 128,
+            Self::CovuPeriod => 349,
+            Self::CovuRecipients => 350,
+            Self::CredentialDisable => 263,
+            Self::CredentialStatus => 264,
+            Self::Credentials => 265,
+            Self::CredentialsInZone => 266,
+            Self::CurrentCommandPriority => 431,
+            Self::CurrentHealth => 4194307,
             Self::DatabaseRevision => 155,
             Self::DateList => 23,
+            Self::DaylightSavingsStatus => 24,
+            Self::DaysRemaining => 267,
             Self::Deadband => 25,
+            Self::DefaultColor => 4194330,
+            Self::DefaultColorTemperature => 4194331,
+            Self::DefaultFadeTime => 374,
+            Self::DefaultPresentValue => 492,
+            Self::DefaultRampRate => 375,
+            Self::DefaultStepIncrement => 376,
+            Self::DefaultSubordinateRelationship => 490,
+            Self::DefaultTimeout => 393,
+            Self::DeleteOnForward => 502,
+            Self::DeployedProfileLocation => 484,
+            Self::DerivativeConstant => 26,
+            Self::DerivativeConstantUnits => 27,
             Self::Description => 28,
+            Self::DescriptionOfHalt => 29,
+            Self::DeviceAddressBinding => 30,
+            Self::DeviceType => 31,
+            Self::DeviceUuid => 507,
+            Self::DirectReading => 156,
+            Self::DistributionKeyRevision => 328,
+            Self::DoNotHide => 329,
+            Self::DoorAlarmState => 226,
+            Self::DoorExtendedPulseTime => 227,
+            Self::DoorMembers => 228,
+            Self::DoorOpenTooLongTime => 229,
+            Self::DoorPulseTime => 230,
+            Self::DoorStatus => 231,
+            Self::DoorUnlockDelayTime => 232,
+            Self::DutyWindow => 213,
             Self::EffectivePeriod => 32,
+            Self::EgressActive => 386,
+            Self::EgressTime => 377,
+            Self::ElapsedActiveTime => 33,
+            Self::ElevatorGroup => 459,
             Self::Enable => 133,
+            Self::EnergyMeter => 460,
+            Self::EnergyMeterRef => 461,
+            Self::EntryPoints => 268,
+            Self::ErrorLimit => 34,
+            Self::EscalatorMode => 462,
+            Self::EventAlgorithmInhibit => 354,
+            Self::EventAlgorithmInhibitRef => 355,
+            Self::EventDetectionEnable => 353,
             Self::EventEnable => 35,
+            Self::EventMessageTexts => 351,
+            Self::EventMessageTextsConfig => 352,
+            Self::EventParameters => 83,
             Self::EventState => 36,
             Self::EventTimeStamps => 130,
+            Self::EventType => 37,
             Self::ExceptionSchedule => 38,
+            Self::ExecutionDelay => 368,
+            Self::ExitPoints => 269,
+            Self::ExpectedShedLevel => 214,
+            Self::ExpirationTime => 270,
+            Self::ExtendedTimeEnable => 271,
+            Self::FailedAttemptEvents => 272,
+            Self::FailedAttempts => 273,
+            Self::FailedAttemptsTime => 274,
+            Self::FaultHighLimit => 388,
+            Self::FaultLowLimit => 389,
+            Self::FaultParameters => 358,
+            Self::FaultSignals => 463,
+            Self::FaultType => 359,
+            Self::FaultValues => 39,
+            Self::FdBbmdAddress => 418,
+            Self::FdSubscriptionLifetime => 419,
+            Self::FeedbackValue => 40,
+            Self::FileAccessMethod => 41,
+            Self::FileSize => 42,
+            Self::FileType => 43,
             Self::FirmwareRevision => 44,
+            Self::FloorNumber => 506,
+            Self::FloorText => 464,
+            Self::FullDutyBaseline => 215,
+            Self::GlobalIdentifier => 323,
+            Self::GroupId => 465,
+            Self::GroupMemberNames => 346,
+            Self::GroupMembers => 345,
+            Self::GroupMode => 467,
+            Self::HighEndTrim => 4194335,
             Self::HighLimit => 45,
+            Self::HigherDeck => 468,
+            Self::InProcess => 47,
+            Self::InProgress => 378,
             Self::InactiveText => 46,
+            Self::InitialTimeout => 394,
+            Self::InputReference => 181,
+            Self::InstallationId => 469,
+            Self::InstanceOf => 48,
+            Self::InstantaneousPower => 379,
+            Self::IntegralConstant => 49,
+            Self::IntegralConstantUnits => 50,
+            Self::InterfaceValue => 387,
+            Self::IntervalOffset => 195,
+            Self::IpAddress => 400,
+            Self::IpDefaultGateway => 401,
+            Self::IpDhcpEnable => 402,
+            Self::IpDhcpLeaseTime => 403,
+            Self::IpDhcpLeaseTimeRemaining => 404,
+            Self::IpDhcpServer => 405,
+            Self::IpDnsServer => 406,
+            Self::IpSubnetMask => 411,
+            Self::Ipv6Address => 436,
+            Self::Ipv6AutoAddressingEnable => 442,
+            Self::Ipv6DefaultGateway => 439,
+            Self::Ipv6DhcpLeaseTime => 443,
+            Self::Ipv6DhcpLeaseTimeRemaining => 444,
+            Self::Ipv6DhcpServer => 445,
+            Self::Ipv6DnsServer => 441,
+            Self::Ipv6PrefixLength => 437,
+            Self::Ipv6ZoneIndex => 446,
+            Self::IsUtc => 344,
+            Self::IssuerCertificateFiles => 511,
+            Self::KeySets => 330,
+            Self::LandingCallControl => 471,
+            Self::LandingCalls => 470,
+            Self::LandingDoorStatus => 472,
+            Self::LastAccessEvent => 275,
+            Self::LastAccessPoint => 276,
+            Self::LastCommandTime => 432,
+            Self::LastCredentialAdded => 277,
+            Self::LastCredentialAddedTime => 278,
+            Self::LastCredentialRemoved => 279,
+            Self::LastCredentialRemovedTime => 280,
+            Self::LastKeyServer => 331,
+            Self::LastNotifyRecord => 173,
+            Self::LastPriority => 369,
+            Self::LastRestartReason => 196,
+            Self::LastRestoreTime => 157,
+            Self::LastStateChange => 395,
+            Self::LastUseTime => 281,
+            Self::LifeSafetyAlarmValues => 166,
+            Self::LightingCommand => 380,
+            Self::LightingCommandDefaultPriority => 381,
             Self::LimitEnable => 52,
-            Self::ListOfObjectPropertyReferences => 78,
+            Self::LimitMonitoringInterval => 182,
+            Self::LinkSpeed => 420,
+            Self::LinkSpeedAutonegotiate => 422,
+            Self::LinkSpeeds => 421,
+            Self::ListOfGroupMembers => 53,
+            Self::ListOfObjectPropertyReferences => 54,
+            Self::LocalDate => 56,
+            Self::LocalForwardingOnly => 360,
+            Self::LocalTime => 57,
+            Self::Location => 58,
+            Self::LockStatus => 233,
+            Self::Lockout => 282,
+            Self::LockoutRelinquishTime => 283,
             Self::LogBuffer => 131,
             Self::LogDeviceObjectProperty => 132,
             Self::LogInterval => 134,
+            Self::LoggingObject => 183,
+            Self::LoggingRecord => 184,
+            Self::LoggingType => 197,
+            Self::LowDiffLimit => 390,
+            Self::LowEndTrim => 4194336,
             Self::LowLimit => 59,
+            Self::LowerDeck => 473,
+            Self::MacAddress => 423,
+            Self::MachineRoomId => 474,
+            Self::MaintenanceRequired => 158,
+            Self::MakingCarCall => 475,
+            Self::ManipulatedVariableReference => 60,
+            Self::ManualSlaveAddressBinding => 170,
+            Self::MaskedAlarmValues => 234,
+            Self::MaxActualValue => 382,
             Self::MaxApduLengthAccepted => 62,
+            Self::MaxBvlcLengthAccepted => 4194304,
+            Self::MaxFailedAttempts => 285,
+            Self::MaxInfoFrames => 63,
+            Self::MaxMaster => 64,
+            Self::MaxNpduLengthAccepted => 4194305,
             Self::MaxPresValue => 65,
+            Self::MaxSegmentsAccepted => 167,
+            Self::MaximumOutput => 61,
+            Self::MaximumSendDelay => 503,
+            Self::MaximumValue => 135,
+            Self::MaximumValueTimestamp => 149,
+            Self::MemberOf => 159,
+            Self::MemberStatusFlags => 347,
+            Self::Members => 286,
+            Self::MinActualValue => 383,
             Self::MinPresValue => 69,
+            Self::MinimumOffTime => 66,
+            Self::MinimumOnTime => 67,
+            Self::MinimumOutput => 68,
+            Self::MinimumValue => 136,
+            Self::MinimumValueTimestamp => 150,
+            Self::Mode => 160,
             Self::ModelName => 70,
+            Self::ModificationDate => 71,
+            Self::MonitoredObjects => 504,
+            Self::MusterPoint => 287,
+            Self::NegativeAccessRules => 288,
+            Self::NetworkAccessSecurityPolicies => 332,
+            Self::NetworkInterfaceName => 424,
+            Self::NetworkNumber => 425,
+            Self::NetworkNumberQuality => 426,
+            Self::NetworkType => 427,
+            Self::NextStoppingFloor => 476,
+            Self::NodeSubtype => 207,
+            Self::NodeType => 208,
             Self::NotificationClass => 17,
+            Self::NotificationThreshold => 137,
             Self::NotifyType => 72,
             Self::NumberOfApduRetries => 73,
+            Self::NumberOfAuthenticationPolicies => 289,
+            Self::NumberOfStates => 74,
             Self::ObjectIdentifier => 75,
             Self::ObjectList => 76,
             Self::ObjectName => 77,
+            Self::ObjectPropertyReference => 78,
             Self::ObjectType => 79,
+            Self::OccupancyCount => 290,
+            Self::OccupancyCountAdjust => 291,
+            Self::OccupancyCountEnable => 292,
+            Self::OccupancyLowerLimit => 294,
+            Self::OccupancyLowerLimitEnforced => 295,
+            Self::OccupancyState => 296,
+            Self::OccupancyUpperLimit => 297,
+            Self::OccupancyUpperLimitEnforced => 298,
+            Self::OperationDirection => 477,
+            Self::OperationExpected => 161,
+            Self::OperationalCertificateFile => 4194306,
+            Self::Optional => 80,
             Self::OutOfService => 81,
+            Self::OutputUnits => 82,
+            Self::OverrideColorReference => 4194332,
+            Self::PacketReorderTime => 333,
+            Self::PassbackMode => 300,
+            Self::PassbackTimeout => 301,
+            Self::PassengerAlarm => 478,
+            Self::Polarity => 84,
+            Self::PortFilter => 363,
+            Self::PositiveAccessRules => 302,
+            Self::Power => 384,
+            Self::PowerMode => 479,
+            Self::Prescale => 185,
+            Self::PresentStage => 493,
             Self::PresentValue => 85,
+            Self::Priority => 86,
             Self::PriorityArray => 87,
+            Self::PriorityForWriting => 88,
+            Self::ProcessIdentifier => 89,
+            Self::ProcessIdentifierFilter => 361,
+            Self::ProfileLocation => 485,
+            Self::ProfileName => 168,
+            Self::ProgramChange => 90,
+            Self::ProgramLocation => 91,
+            Self::ProgramState => 92,
+            Self::PropertyList => 371,
+            Self::ProportionalConstant => 93,
+            Self::ProportionalConstantUnits => 94,
+            Self::ProtocolLevel => 482,
+            Self::ProtocolObjectTypesSupported => 96,
             Self::ProtocolRevision => 139,
+            Self::ProtocolServicesSupported => 97,
             Self::ProtocolVersion => 98,
+            Self::PulseRate => 186,
+            Self::ReadOnly => 99,
+            Self::ReasonForDisable => 303,
+            Self::ReasonForHalt => 100,
             Self::RecipientList => 102,
             Self::RecordCount => 141,
+            Self::RecordsSinceNotification => 140,
+            Self::ReferencePort => 483,
+            Self::RegisteredCarCall => 480,
             Self::Reliability => 103,
+            Self::ReliabilityEvaluationInhibit => 357,
             Self::RelinquishDefault => 104,
+            Self::Represents => 491,
+            Self::RequestedShedLevel => 218,
+            Self::RequestedUpdateInterval => 348,
+            Self::Required => 105,
             Self::Resolution => 106,
+            Self::RestartNotificationRecipients => 202,
+            Self::RestoreCompletionTime => 340,
+            Self::RestorePreparationTime => 341,
+            Self::RoutingTable => 428,
+            Self::ScConnectWaitTimeout => 4194308,
+            Self::ScDirectConnectAcceptEnable => 4194309,
+            Self::ScDirectConnectAcceptUris => 4194310,
+            Self::ScDirectConnectBinding => 4194311,
+            Self::ScDirectConnectConnectionStatus => 4194312,
+            Self::ScDirectConnectInitiateEnable => 4194313,
+            Self::ScDisconnectWaitTimeout => 4194314,
+            Self::ScFailedConnectionRequests => 4194315,
+            Self::ScFailoverHubConnectionStatus => 4194316,
+            Self::ScFailoverHubUri => 4194317,
+            Self::ScHeartbeatTimeout => 4194323,
+            Self::ScHubConnectorState => 4194318,
+            Self::ScHubFunctionAcceptUris => 4194319,
+            Self::ScHubFunctionBinding => 4194320,
+            Self::ScHubFunctionConnectionStatus => 4194321,
+            Self::ScHubFunctionEnable => 4194322,
+            Self::ScMaximumReconnectTime => 4194326,
+            Self::ScMinimumReconnectTime => 4194327,
+            Self::ScPrimaryHubConnectionStatus => 4194324,
+            Self::ScPrimaryHubUri => 4194325,
+            Self::Scale => 187,
+            Self::ScaleFactor => 188,
             Self::ScheduleDefault => 174,
+            Self::SecuredStatus => 235,
+            Self::SecurityPduTimeout => 334,
+            Self::SecurityTimeWindow => 335,
             Self::SegmentationSupported => 107,
+            Self::SendNow => 505,
+            Self::SerialNumber => 372,
+            Self::Setpoint => 108,
+            Self::SetpointReference => 109,
+            Self::Setting => 162,
+            Self::ShedDuration => 219,
+            Self::ShedLevelDescriptions => 220,
+            Self::ShedLevels => 221,
+            Self::Silenced => 163,
+            Self::SlaveAddressBinding => 171,
+            Self::SlaveProxyEnable => 172,
+            Self::StageNames => 495,
+            Self::Stages => 494,
             Self::StartTime => 142,
+            Self::StateChangeValues => 396,
+            Self::StateDescription => 222,
+            Self::StateText => 110,
             Self::StatusFlags => 111,
             Self::StopTime => 143,
+            Self::StopWhenFull => 144,
+            Self::StrikeCount => 391,
+            Self::StructuredObjectList => 209,
+            Self::SubordinateAnnotations => 210,
+            Self::SubordinateList => 211,
+            Self::SubordinateNodeTypes => 487,
+            Self::SubordinateRelationships => 489,
+            Self::SubordinateTags => 488,
+            Self::SubscribedRecipients => 362,
+            Self::SupportedFormatClasses => 305,
+            Self::SupportedFormats => 304,
+            Self::SupportedSecurityAlgorithms => 336,
             Self::SystemStatus => 112,
+            Self::Tags => 486,
+            Self::TargetReferences => 496,
+            Self::ThreatAuthority => 306,
+            Self::ThreatLevel => 307,
             Self::TimeDelay => 113,
+            Self::TimeDelayNormal => 356,
+            Self::TimeOfActiveTimeReset => 114,
+            Self::TimeOfDeviceRestart => 203,
+            Self::TimeOfStateCountReset => 115,
+            Self::TimeOfStrikeCountReset => 392,
+            Self::TimeSynchronizationInterval => 204,
+            Self::TimeSynchronizationRecipients => 116,
+            Self::TimerRunning => 397,
+            Self::TimerState => 398,
             Self::TotalRecordCount => 145,
+            Self::TraceFlag => 308,
+            Self::TrackingValue => 164,
+            Self::TransactionNotificationClass => 309,
+            Self::Transition => 385,
+            Self::Trigger => 205,
+            Self::TrimFadeTime => 4194337,
             Self::Units => 117,
             Self::UpdateInterval => 118,
+            Self::UpdateKeySetTimeout => 337,
+            Self::UpdateTime => 189,
+            Self::UserExternalIdentifier => 310,
+            Self::UserInformationReference => 311,
+            Self::UserName => 317,
+            Self::UserType => 318,
+            Self::UsesRemaining => 319,
+            Self::UtcOffset => 119,
+            Self::UtcTimeSynchronizationRecipients => 206,
+            Self::ValidSamples => 146,
+            Self::ValueBeforeChange => 190,
+            Self::ValueChangeTime => 192,
+            Self::ValueSet => 191,
+            Self::ValueSource => 433,
+            Self::ValueSourceArray => 434,
+            Self::VarianceValue => 151,
+            Self::VendorIdentifier => 120,
             Self::VendorName => 121,
+            Self::VerificationTime => 326,
+            Self::VirtualMacAddressTable => 429,
+            Self::VtClassesSupported => 122,
             Self::WeeklySchedule => 123,
+            Self::WindowInterval => 147,
+            Self::WindowSamples => 148,
+            Self::WriteStatus => 370,
+            Self::ZoneFrom => 320,
+            Self::ZoneMembers => 165,
+            Self::ZoneTo => 321,
             Self::Proprietary(v) => v,
         }
     }
 
     pub const fn from_u32(value: u32) -> Self {
         match value {
+            244 => Self::AbsenteeLimit,
+            175 => Self::AcceptedModes,
+            245 => Self::AccessAlarmEvents,
+            246 => Self::AccessDoors,
+            247 => Self::AccessEvent,
+            248 => Self::AccessEventAuthenticationFactor,
+            249 => Self::AccessEventCredential,
+            322 => Self::AccessEventTag,
+            250 => Self::AccessEventTime,
+            251 => Self::AccessTransactionEvents,
+            252 => Self::Accompaniment,
+            253 => Self::AccompanimentTime,
+            1 => Self::AckRequired,
             0 => Self::AckedTransitions,
+            2 => Self::Action,
+            3 => Self::ActionText,
+            254 => Self::ActivationTime,
+            255 => Self::ActiveAuthenticationPolicy,
+            481 => Self::ActiveCovMultipleSubscriptions,
+            152 => Self::ActiveCovSubscriptions,
             4 => Self::ActiveText,
+            5 => Self::ActiveVtSessions,
+            212 => Self::ActualShedLevel,
+            508 => Self::AdditionalReferencePorts,
+            176 => Self::AdjustValue,
+            6 => Self::AlarmValue,
+            7 => Self::AlarmValues,
+            193 => Self::AlignIntervals,
+            8 => Self::All,
+            9 => Self::AllWritesSuccessful,
+            365 => Self::AllowGroupDelayInhibit,
+            399 => Self::ApduLength,
+            10 => Self::ApduSegmentTimeout,
             11 => Self::ApduTimeout,
             12 => Self::ApplicationSoftwareVersion,
+            13 => Self::Archive,
+            256 => Self::AssignedAccessRights,
+            447 => Self::AssignedLandingCalls,
+            124 => Self::AttemptedSamples,
+            498 => Self::AuditLevel,
+            499 => Self::AuditNotificationRecipient,
+            500 => Self::AuditPriorityFilter,
+            497 => Self::AuditSourceLevel,
+            501 => Self::AuditableOperations,
+            257 => Self::AuthenticationFactors,
+            258 => Self::AuthenticationPolicyList,
+            259 => Self::AuthenticationPolicyNames,
+            260 => Self::AuthenticationStatus,
+            364 => Self::AuthorizationExemptions,
+            261 => Self::AuthorizationMode,
+            169 => Self::AutoSlaveDiscovery,
+            125 => Self::AverageValue,
+            338 => Self::BackupAndRestoreState,
+            153 => Self::BackupFailureTimeout,
+            339 => Self::BackupPreparationTime,
+            407 => Self::BacnetIpGlobalAddress,
+            408 => Self::BacnetIpMode,
+            409 => Self::BacnetIpMulticastAddress,
+            410 => Self::BacnetIpNatTraversal,
+            412 => Self::BacnetIpUdpPort,
+            435 => Self::BacnetIpv6Mode,
+            440 => Self::BacnetIpv6MulticastAddress,
+            438 => Self::BacnetIpv6UdpPort,
+            327 => Self::BaseDeviceSecurityPolicy,
+            413 => Self::BbmdAcceptFdRegistrations,
+            414 => Self::BbmdBroadcastDistributionTable,
+            415 => Self::BbmdForeignDeviceTable,
+            262 => Self::BelongsTo,
+            14 => Self::Bias,
+            342 => Self::BitMask,
+            343 => Self::BitText,
+            373 => Self::BlinkWarnEnable,
             126 => Self::BufferSize,
+            448 => Self::CarAssignedDirection,
+            449 => Self::CarDoorCommand,
+            450 => Self::CarDoorStatus,
+            451 => Self::CarDoorText,
+            452 => Self::CarDoorZone,
+            453 => Self::CarDriveStatus,
+            454 => Self::CarLoad,
+            455 => Self::CarLoadUnits,
+            456 => Self::CarMode,
+            457 => Self::CarMovingDirection,
+            458 => Self::CarPosition,
+            509 => Self::CertificateSigningRequestFile,
+            15 => Self::ChangeOfStateCount,
+            16 => Self::ChangeOfStateTime,
+            416 => Self::ChangesPending,
+            366 => Self::ChannelNumber,
+            127 => Self::ClientCovIncrement,
+            4194334 => Self::ColorCommand,
+            4194328 => Self::ColorOverride,
+            4194329 => Self::ColorReference,
+            417 => Self::Command,
+            430 => Self::CommandTimeArray,
+            510 => Self::CommandValidationResult,
+            154 => Self::ConfigurationFiles,
+            367 => Self::ControlGroups,
+            19 => Self::ControlledVariableReference,
+            20 => Self::ControlledVariableUnits,
+            21 => Self::ControlledVariableValue,
+            177 => Self::Count,
+            178 => Self::CountBeforeChange,
+            179 => Self::CountChangeTime,
             22 => Self::CovIncrement,
+            180 => Self::CovPeriod,
+            128 => Self::CovResubscriptionInterval,
+            349 => Self::CovuPeriod,
+            350 => Self::CovuRecipients,
+            263 => Self::CredentialDisable,
+            264 => Self::CredentialStatus,
+            265 => Self::Credentials,
+            266 => Self::CredentialsInZone,
+            431 => Self::CurrentCommandPriority,
+            4194307 => Self::CurrentHealth,
             155 => Self::DatabaseRevision,
             23 => Self::DateList,
+            24 => Self::DaylightSavingsStatus,
+            267 => Self::DaysRemaining,
             25 => Self::Deadband,
+            4194330 => Self::DefaultColor,
+            4194331 => Self::DefaultColorTemperature,
+            374 => Self::DefaultFadeTime,
+            492 => Self::DefaultPresentValue,
+            375 => Self::DefaultRampRate,
+            376 => Self::DefaultStepIncrement,
+            490 => Self::DefaultSubordinateRelationship,
+            393 => Self::DefaultTimeout,
+            502 => Self::DeleteOnForward,
+            484 => Self::DeployedProfileLocation,
+            26 => Self::DerivativeConstant,
+            27 => Self::DerivativeConstantUnits,
             28 => Self::Description,
+            29 => Self::DescriptionOfHalt,
+            30 => Self::DeviceAddressBinding,
+            31 => Self::DeviceType,
+            507 => Self::DeviceUuid,
+            156 => Self::DirectReading,
+            328 => Self::DistributionKeyRevision,
+            329 => Self::DoNotHide,
+            226 => Self::DoorAlarmState,
+            227 => Self::DoorExtendedPulseTime,
+            228 => Self::DoorMembers,
+            229 => Self::DoorOpenTooLongTime,
+            230 => Self::DoorPulseTime,
+            231 => Self::DoorStatus,
+            232 => Self::DoorUnlockDelayTime,
+            213 => Self::DutyWindow,
             32 => Self::EffectivePeriod,
+            386 => Self::EgressActive,
+            377 => Self::EgressTime,
+            33 => Self::ElapsedActiveTime,
+            459 => Self::ElevatorGroup,
             133 => Self::Enable,
+            460 => Self::EnergyMeter,
+            461 => Self::EnergyMeterRef,
+            268 => Self::EntryPoints,
+            34 => Self::ErrorLimit,
+            462 => Self::EscalatorMode,
+            354 => Self::EventAlgorithmInhibit,
+            355 => Self::EventAlgorithmInhibitRef,
+            353 => Self::EventDetectionEnable,
             35 => Self::EventEnable,
+            351 => Self::EventMessageTexts,
+            352 => Self::EventMessageTextsConfig,
+            83 => Self::EventParameters,
             36 => Self::EventState,
             130 => Self::EventTimeStamps,
+            37 => Self::EventType,
             38 => Self::ExceptionSchedule,
+            368 => Self::ExecutionDelay,
+            269 => Self::ExitPoints,
+            214 => Self::ExpectedShedLevel,
+            270 => Self::ExpirationTime,
+            271 => Self::ExtendedTimeEnable,
+            272 => Self::FailedAttemptEvents,
+            273 => Self::FailedAttempts,
+            274 => Self::FailedAttemptsTime,
+            388 => Self::FaultHighLimit,
+            389 => Self::FaultLowLimit,
+            358 => Self::FaultParameters,
+            463 => Self::FaultSignals,
+            359 => Self::FaultType,
+            39 => Self::FaultValues,
+            418 => Self::FdBbmdAddress,
+            419 => Self::FdSubscriptionLifetime,
+            40 => Self::FeedbackValue,
+            41 => Self::FileAccessMethod,
+            42 => Self::FileSize,
+            43 => Self::FileType,
             44 => Self::FirmwareRevision,
+            506 => Self::FloorNumber,
+            464 => Self::FloorText,
+            215 => Self::FullDutyBaseline,
+            323 => Self::GlobalIdentifier,
+            465 => Self::GroupId,
+            346 => Self::GroupMemberNames,
+            345 => Self::GroupMembers,
+            467 => Self::GroupMode,
+            4194335 => Self::HighEndTrim,
             45 => Self::HighLimit,
+            468 => Self::HigherDeck,
+            47 => Self::InProcess,
+            378 => Self::InProgress,
             46 => Self::InactiveText,
+            394 => Self::InitialTimeout,
+            181 => Self::InputReference,
+            469 => Self::InstallationId,
+            48 => Self::InstanceOf,
+            379 => Self::InstantaneousPower,
+            49 => Self::IntegralConstant,
+            50 => Self::IntegralConstantUnits,
+            387 => Self::InterfaceValue,
+            195 => Self::IntervalOffset,
+            400 => Self::IpAddress,
+            401 => Self::IpDefaultGateway,
+            402 => Self::IpDhcpEnable,
+            403 => Self::IpDhcpLeaseTime,
+            404 => Self::IpDhcpLeaseTimeRemaining,
+            405 => Self::IpDhcpServer,
+            406 => Self::IpDnsServer,
+            411 => Self::IpSubnetMask,
+            436 => Self::Ipv6Address,
+            442 => Self::Ipv6AutoAddressingEnable,
+            439 => Self::Ipv6DefaultGateway,
+            443 => Self::Ipv6DhcpLeaseTime,
+            444 => Self::Ipv6DhcpLeaseTimeRemaining,
+            445 => Self::Ipv6DhcpServer,
+            441 => Self::Ipv6DnsServer,
+            437 => Self::Ipv6PrefixLength,
+            446 => Self::Ipv6ZoneIndex,
+            344 => Self::IsUtc,
+            511 => Self::IssuerCertificateFiles,
+            330 => Self::KeySets,
+            471 => Self::LandingCallControl,
+            470 => Self::LandingCalls,
+            472 => Self::LandingDoorStatus,
+            275 => Self::LastAccessEvent,
+            276 => Self::LastAccessPoint,
+            432 => Self::LastCommandTime,
+            277 => Self::LastCredentialAdded,
+            278 => Self::LastCredentialAddedTime,
+            279 => Self::LastCredentialRemoved,
+            280 => Self::LastCredentialRemovedTime,
+            331 => Self::LastKeyServer,
+            173 => Self::LastNotifyRecord,
+            369 => Self::LastPriority,
+            196 => Self::LastRestartReason,
+            157 => Self::LastRestoreTime,
+            395 => Self::LastStateChange,
+            281 => Self::LastUseTime,
+            166 => Self::LifeSafetyAlarmValues,
+            380 => Self::LightingCommand,
+            381 => Self::LightingCommandDefaultPriority,
             52 => Self::LimitEnable,
-            78 => Self::ListOfObjectPropertyReferences,
+            182 => Self::LimitMonitoringInterval,
+            420 => Self::LinkSpeed,
+            422 => Self::LinkSpeedAutonegotiate,
+            421 => Self::LinkSpeeds,
+            53 => Self::ListOfGroupMembers,
+            54 => Self::ListOfObjectPropertyReferences,
+            56 => Self::LocalDate,
+            360 => Self::LocalForwardingOnly,
+            57 => Self::LocalTime,
+            58 => Self::Location,
+            233 => Self::LockStatus,
+            282 => Self::Lockout,
+            283 => Self::LockoutRelinquishTime,
             131 => Self::LogBuffer,
             132 => Self::LogDeviceObjectProperty,
             134 => Self::LogInterval,
+            183 => Self::LoggingObject,
+            184 => Self::LoggingRecord,
+            197 => Self::LoggingType,
+            390 => Self::LowDiffLimit,
+            4194336 => Self::LowEndTrim,
             59 => Self::LowLimit,
+            473 => Self::LowerDeck,
+            423 => Self::MacAddress,
+            474 => Self::MachineRoomId,
+            158 => Self::MaintenanceRequired,
+            475 => Self::MakingCarCall,
+            60 => Self::ManipulatedVariableReference,
+            170 => Self::ManualSlaveAddressBinding,
+            234 => Self::MaskedAlarmValues,
+            382 => Self::MaxActualValue,
             62 => Self::MaxApduLengthAccepted,
+            4194304 => Self::MaxBvlcLengthAccepted,
+            285 => Self::MaxFailedAttempts,
+            63 => Self::MaxInfoFrames,
+            64 => Self::MaxMaster,
+            4194305 => Self::MaxNpduLengthAccepted,
             65 => Self::MaxPresValue,
+            167 => Self::MaxSegmentsAccepted,
+            61 => Self::MaximumOutput,
+            503 => Self::MaximumSendDelay,
+            135 => Self::MaximumValue,
+            149 => Self::MaximumValueTimestamp,
+            159 => Self::MemberOf,
+            347 => Self::MemberStatusFlags,
+            286 => Self::Members,
+            383 => Self::MinActualValue,
             69 => Self::MinPresValue,
+            66 => Self::MinimumOffTime,
+            67 => Self::MinimumOnTime,
+            68 => Self::MinimumOutput,
+            136 => Self::MinimumValue,
+            150 => Self::MinimumValueTimestamp,
+            160 => Self::Mode,
             70 => Self::ModelName,
+            71 => Self::ModificationDate,
+            504 => Self::MonitoredObjects,
+            287 => Self::MusterPoint,
+            288 => Self::NegativeAccessRules,
+            332 => Self::NetworkAccessSecurityPolicies,
+            424 => Self::NetworkInterfaceName,
+            425 => Self::NetworkNumber,
+            426 => Self::NetworkNumberQuality,
+            427 => Self::NetworkType,
+            476 => Self::NextStoppingFloor,
+            207 => Self::NodeSubtype,
+            208 => Self::NodeType,
             17 => Self::NotificationClass,
+            137 => Self::NotificationThreshold,
             72 => Self::NotifyType,
             73 => Self::NumberOfApduRetries,
+            289 => Self::NumberOfAuthenticationPolicies,
+            74 => Self::NumberOfStates,
             75 => Self::ObjectIdentifier,
             76 => Self::ObjectList,
             77 => Self::ObjectName,
+            78 => Self::ObjectPropertyReference,
             79 => Self::ObjectType,
+            290 => Self::OccupancyCount,
+            291 => Self::OccupancyCountAdjust,
+            292 => Self::OccupancyCountEnable,
+            294 => Self::OccupancyLowerLimit,
+            295 => Self::OccupancyLowerLimitEnforced,
+            296 => Self::OccupancyState,
+            297 => Self::OccupancyUpperLimit,
+            298 => Self::OccupancyUpperLimitEnforced,
+            477 => Self::OperationDirection,
+            161 => Self::OperationExpected,
+            4194306 => Self::OperationalCertificateFile,
+            80 => Self::Optional,
             81 => Self::OutOfService,
+            82 => Self::OutputUnits,
+            4194332 => Self::OverrideColorReference,
+            333 => Self::PacketReorderTime,
+            300 => Self::PassbackMode,
+            301 => Self::PassbackTimeout,
+            478 => Self::PassengerAlarm,
+            84 => Self::Polarity,
+            363 => Self::PortFilter,
+            302 => Self::PositiveAccessRules,
+            384 => Self::Power,
+            479 => Self::PowerMode,
+            185 => Self::Prescale,
+            493 => Self::PresentStage,
             85 => Self::PresentValue,
+            86 => Self::Priority,
             87 => Self::PriorityArray,
+            88 => Self::PriorityForWriting,
+            89 => Self::ProcessIdentifier,
+            361 => Self::ProcessIdentifierFilter,
+            485 => Self::ProfileLocation,
+            168 => Self::ProfileName,
+            90 => Self::ProgramChange,
+            91 => Self::ProgramLocation,
+            92 => Self::ProgramState,
+            371 => Self::PropertyList,
+            93 => Self::ProportionalConstant,
+            94 => Self::ProportionalConstantUnits,
+            482 => Self::ProtocolLevel,
+            96 => Self::ProtocolObjectTypesSupported,
             139 => Self::ProtocolRevision,
+            97 => Self::ProtocolServicesSupported,
             98 => Self::ProtocolVersion,
+            186 => Self::PulseRate,
+            99 => Self::ReadOnly,
+            303 => Self::ReasonForDisable,
+            100 => Self::ReasonForHalt,
             102 => Self::RecipientList,
             141 => Self::RecordCount,
+            140 => Self::RecordsSinceNotification,
+            483 => Self::ReferencePort,
+            480 => Self::RegisteredCarCall,
             103 => Self::Reliability,
+            357 => Self::ReliabilityEvaluationInhibit,
             104 => Self::RelinquishDefault,
+            491 => Self::Represents,
+            218 => Self::RequestedShedLevel,
+            348 => Self::RequestedUpdateInterval,
+            105 => Self::Required,
             106 => Self::Resolution,
+            202 => Self::RestartNotificationRecipients,
+            340 => Self::RestoreCompletionTime,
+            341 => Self::RestorePreparationTime,
+            428 => Self::RoutingTable,
+            4194308 => Self::ScConnectWaitTimeout,
+            4194309 => Self::ScDirectConnectAcceptEnable,
+            4194310 => Self::ScDirectConnectAcceptUris,
+            4194311 => Self::ScDirectConnectBinding,
+            4194312 => Self::ScDirectConnectConnectionStatus,
+            4194313 => Self::ScDirectConnectInitiateEnable,
+            4194314 => Self::ScDisconnectWaitTimeout,
+            4194315 => Self::ScFailedConnectionRequests,
+            4194316 => Self::ScFailoverHubConnectionStatus,
+            4194317 => Self::ScFailoverHubUri,
+            4194323 => Self::ScHeartbeatTimeout,
+            4194318 => Self::ScHubConnectorState,
+            4194319 => Self::ScHubFunctionAcceptUris,
+            4194320 => Self::ScHubFunctionBinding,
+            4194321 => Self::ScHubFunctionConnectionStatus,
+            4194322 => Self::ScHubFunctionEnable,
+            4194326 => Self::ScMaximumReconnectTime,
+            4194327 => Self::ScMinimumReconnectTime,
+            4194324 => Self::ScPrimaryHubConnectionStatus,
+            4194325 => Self::ScPrimaryHubUri,
+            187 => Self::Scale,
+            188 => Self::ScaleFactor,
             174 => Self::ScheduleDefault,
+            235 => Self::SecuredStatus,
+            334 => Self::SecurityPduTimeout,
+            335 => Self::SecurityTimeWindow,
             107 => Self::SegmentationSupported,
+            505 => Self::SendNow,
+            372 => Self::SerialNumber,
+            108 => Self::Setpoint,
+            109 => Self::SetpointReference,
+            162 => Self::Setting,
+            219 => Self::ShedDuration,
+            220 => Self::ShedLevelDescriptions,
+            221 => Self::ShedLevels,
+            163 => Self::Silenced,
+            171 => Self::SlaveAddressBinding,
+            172 => Self::SlaveProxyEnable,
+            495 => Self::StageNames,
+            494 => Self::Stages,
             142 => Self::StartTime,
+            396 => Self::StateChangeValues,
+            222 => Self::StateDescription,
+            110 => Self::StateText,
             111 => Self::StatusFlags,
             143 => Self::StopTime,
+            144 => Self::StopWhenFull,
+            391 => Self::StrikeCount,
+            209 => Self::StructuredObjectList,
+            210 => Self::SubordinateAnnotations,
+            211 => Self::SubordinateList,
+            487 => Self::SubordinateNodeTypes,
+            489 => Self::SubordinateRelationships,
+            488 => Self::SubordinateTags,
+            362 => Self::SubscribedRecipients,
+            305 => Self::SupportedFormatClasses,
+            304 => Self::SupportedFormats,
+            336 => Self::SupportedSecurityAlgorithms,
             112 => Self::SystemStatus,
+            486 => Self::Tags,
+            496 => Self::TargetReferences,
+            306 => Self::ThreatAuthority,
+            307 => Self::ThreatLevel,
             113 => Self::TimeDelay,
+            356 => Self::TimeDelayNormal,
+            114 => Self::TimeOfActiveTimeReset,
+            203 => Self::TimeOfDeviceRestart,
+            115 => Self::TimeOfStateCountReset,
+            392 => Self::TimeOfStrikeCountReset,
+            204 => Self::TimeSynchronizationInterval,
+            116 => Self::TimeSynchronizationRecipients,
+            397 => Self::TimerRunning,
+            398 => Self::TimerState,
             145 => Self::TotalRecordCount,
+            308 => Self::TraceFlag,
+            164 => Self::TrackingValue,
+            309 => Self::TransactionNotificationClass,
+            385 => Self::Transition,
+            205 => Self::Trigger,
+            4194337 => Self::TrimFadeTime,
             117 => Self::Units,
             118 => Self::UpdateInterval,
+            337 => Self::UpdateKeySetTimeout,
+            189 => Self::UpdateTime,
+            310 => Self::UserExternalIdentifier,
+            311 => Self::UserInformationReference,
+            317 => Self::UserName,
+            318 => Self::UserType,
+            319 => Self::UsesRemaining,
+            119 => Self::UtcOffset,
+            206 => Self::UtcTimeSynchronizationRecipients,
+            146 => Self::ValidSamples,
+            190 => Self::ValueBeforeChange,
+            192 => Self::ValueChangeTime,
+            191 => Self::ValueSet,
+            433 => Self::ValueSource,
+            434 => Self::ValueSourceArray,
+            151 => Self::VarianceValue,
+            120 => Self::VendorIdentifier,
             121 => Self::VendorName,
+            326 => Self::VerificationTime,
+            429 => Self::VirtualMacAddressTable,
+            122 => Self::VtClassesSupported,
             123 => Self::WeeklySchedule,
+            147 => Self::WindowInterval,
+            148 => Self::WindowSamples,
+            370 => Self::WriteStatus,
+            320 => Self::ZoneFrom,
+            165 => Self::ZoneMembers,
+            321 => Self::ZoneTo,
             v => Self::Proprietary(v),
         }
     }
@@ -201,133 +1556,1088 @@ impl PropertyId {
     /// round-tripped through this method; use [`from_u32`](Self::from_u32) instead.
     pub fn from_name(name: &str) -> Option<Self> {
         match name {
+            "absentee-limit" => Some(Self::AbsenteeLimit),
+            "accepted-modes" => Some(Self::AcceptedModes),
+            "access-alarm-events" => Some(Self::AccessAlarmEvents),
+            "access-doors" => Some(Self::AccessDoors),
+            "access-event" => Some(Self::AccessEvent),
+            "access-event-authentication-factor" => Some(Self::AccessEventAuthenticationFactor),
+            "access-event-credential" => Some(Self::AccessEventCredential),
+            "access-event-tag" => Some(Self::AccessEventTag),
+            "access-event-time" => Some(Self::AccessEventTime),
+            "access-transaction-events" => Some(Self::AccessTransactionEvents),
+            "accompaniment" => Some(Self::Accompaniment),
+            "accompaniment-time" => Some(Self::AccompanimentTime),
+            "ack-required" => Some(Self::AckRequired),
             "acked-transitions" => Some(Self::AckedTransitions),
+            "action" => Some(Self::Action),
+            "action-text" => Some(Self::ActionText),
+            "activation-time" => Some(Self::ActivationTime),
+            "active-authentication-policy" => Some(Self::ActiveAuthenticationPolicy),
+            "active-cov-multiple-subscriptions" => Some(Self::ActiveCovMultipleSubscriptions),
+            "active-cov-subscriptions" => Some(Self::ActiveCovSubscriptions),
             "active-text" => Some(Self::ActiveText),
+            "active-vt-sessions" => Some(Self::ActiveVtSessions),
+            "actual-shed-level" => Some(Self::ActualShedLevel),
+            "additional-reference-ports" => Some(Self::AdditionalReferencePorts),
+            "adjust-value" => Some(Self::AdjustValue),
+            "alarm-value" => Some(Self::AlarmValue),
+            "alarm-values" => Some(Self::AlarmValues),
+            "align-intervals" => Some(Self::AlignIntervals),
+            "all" => Some(Self::All),
+            "all-writes-successful" => Some(Self::AllWritesSuccessful),
+            "allow-group-delay-inhibit" => Some(Self::AllowGroupDelayInhibit),
+            "apdu-length" => Some(Self::ApduLength),
+            "apdu-segment-timeout" => Some(Self::ApduSegmentTimeout),
             "apdu-timeout" => Some(Self::ApduTimeout),
             "application-software-version" => Some(Self::ApplicationSoftwareVersion),
+            "archive" => Some(Self::Archive),
+            "assigned-access-rights" => Some(Self::AssignedAccessRights),
+            "assigned-landing-calls" => Some(Self::AssignedLandingCalls),
+            "attempted-samples" => Some(Self::AttemptedSamples),
+            "audit-level" => Some(Self::AuditLevel),
+            "audit-notification-recipient" => Some(Self::AuditNotificationRecipient),
+            "audit-priority-filter" => Some(Self::AuditPriorityFilter),
+            "audit-source-level" => Some(Self::AuditSourceLevel),
+            "auditable-operations" => Some(Self::AuditableOperations),
+            "authentication-factors" => Some(Self::AuthenticationFactors),
+            "authentication-policy-list" => Some(Self::AuthenticationPolicyList),
+            "authentication-policy-names" => Some(Self::AuthenticationPolicyNames),
+            "authentication-status" => Some(Self::AuthenticationStatus),
+            "authorization-exemptions" => Some(Self::AuthorizationExemptions),
+            "authorization-mode" => Some(Self::AuthorizationMode),
+            "auto-slave-discovery" => Some(Self::AutoSlaveDiscovery),
+            "average-value" => Some(Self::AverageValue),
+            "backup-and-restore-state" => Some(Self::BackupAndRestoreState),
+            "backup-failure-timeout" => Some(Self::BackupFailureTimeout),
+            "backup-preparation-time" => Some(Self::BackupPreparationTime),
+            "bacnet-ip-global-address" => Some(Self::BacnetIpGlobalAddress),
+            "bacnet-ip-mode" => Some(Self::BacnetIpMode),
+            "bacnet-ip-multicast-address" => Some(Self::BacnetIpMulticastAddress),
+            "bacnet-ip-nat-traversal" => Some(Self::BacnetIpNatTraversal),
+            "bacnet-ip-udp-port" => Some(Self::BacnetIpUdpPort),
+            "bacnet-ipv6-mode" => Some(Self::BacnetIpv6Mode),
+            "bacnet-ipv6-multicast-address" => Some(Self::BacnetIpv6MulticastAddress),
+            "bacnet-ipv6-udp-port" => Some(Self::BacnetIpv6UdpPort),
+            "base-device-security-policy" => Some(Self::BaseDeviceSecurityPolicy),
+            "bbmd-accept-fd-registrations" => Some(Self::BbmdAcceptFdRegistrations),
+            "bbmd-broadcast-distribution-table" => Some(Self::BbmdBroadcastDistributionTable),
+            "bbmd-foreign-device-table" => Some(Self::BbmdForeignDeviceTable),
+            "belongs-to" => Some(Self::BelongsTo),
+            "bias" => Some(Self::Bias),
+            "bit-mask" => Some(Self::BitMask),
+            "bit-text" => Some(Self::BitText),
+            "blink-warn-enable" => Some(Self::BlinkWarnEnable),
             "buffer-size" => Some(Self::BufferSize),
+            "car-assigned-direction" => Some(Self::CarAssignedDirection),
+            "car-door-command" => Some(Self::CarDoorCommand),
+            "car-door-status" => Some(Self::CarDoorStatus),
+            "car-door-text" => Some(Self::CarDoorText),
+            "car-door-zone" => Some(Self::CarDoorZone),
+            "car-drive-status" => Some(Self::CarDriveStatus),
+            "car-load" => Some(Self::CarLoad),
+            "car-load-units" => Some(Self::CarLoadUnits),
+            "car-mode" => Some(Self::CarMode),
+            "car-moving-direction" => Some(Self::CarMovingDirection),
+            "car-position" => Some(Self::CarPosition),
+            "certificate-signing-request-file" => Some(Self::CertificateSigningRequestFile),
+            "change-of-state-count" => Some(Self::ChangeOfStateCount),
+            "change-of-state-time" => Some(Self::ChangeOfStateTime),
+            "changes-pending" => Some(Self::ChangesPending),
+            "channel-number" => Some(Self::ChannelNumber),
+            "client-cov-increment" => Some(Self::ClientCovIncrement),
+            "color-command" => Some(Self::ColorCommand),
+            "color-override" => Some(Self::ColorOverride),
+            "color-reference" => Some(Self::ColorReference),
+            "command" => Some(Self::Command),
+            "command-time-array" => Some(Self::CommandTimeArray),
+            "command-validation-result" => Some(Self::CommandValidationResult),
+            "configuration-files" => Some(Self::ConfigurationFiles),
+            "control-groups" => Some(Self::ControlGroups),
+            "controlled-variable-reference" => Some(Self::ControlledVariableReference),
+            "controlled-variable-units" => Some(Self::ControlledVariableUnits),
+            "controlled-variable-value" => Some(Self::ControlledVariableValue),
+            "count" => Some(Self::Count),
+            "count-before-change" => Some(Self::CountBeforeChange),
+            "count-change-time" => Some(Self::CountChangeTime),
             "cov-increment" => Some(Self::CovIncrement),
+            "cov-period" => Some(Self::CovPeriod),
+            "cov-resubscription-interval" => Some(Self::CovResubscriptionInterval),
+            "covu-period" => Some(Self::CovuPeriod),
+            "covu-recipients" => Some(Self::CovuRecipients),
+            "credential-disable" => Some(Self::CredentialDisable),
+            "credential-status" => Some(Self::CredentialStatus),
+            "credentials" => Some(Self::Credentials),
+            "credentials-in-zone" => Some(Self::CredentialsInZone),
+            "current-command-priority" => Some(Self::CurrentCommandPriority),
+            "current-health" => Some(Self::CurrentHealth),
             "database-revision" => Some(Self::DatabaseRevision),
             "date-list" => Some(Self::DateList),
+            "daylight-savings-status" => Some(Self::DaylightSavingsStatus),
+            "days-remaining" => Some(Self::DaysRemaining),
             "deadband" => Some(Self::Deadband),
+            "default-color" => Some(Self::DefaultColor),
+            "default-color-temperature" => Some(Self::DefaultColorTemperature),
+            "default-fade-time" => Some(Self::DefaultFadeTime),
+            "default-present-value" => Some(Self::DefaultPresentValue),
+            "default-ramp-rate" => Some(Self::DefaultRampRate),
+            "default-step-increment" => Some(Self::DefaultStepIncrement),
+            "default-subordinate-relationship" => Some(Self::DefaultSubordinateRelationship),
+            "default-timeout" => Some(Self::DefaultTimeout),
+            "delete-on-forward" => Some(Self::DeleteOnForward),
+            "deployed-profile-location" => Some(Self::DeployedProfileLocation),
+            "derivative-constant" => Some(Self::DerivativeConstant),
+            "derivative-constant-units" => Some(Self::DerivativeConstantUnits),
             "description" => Some(Self::Description),
+            "description-of-halt" => Some(Self::DescriptionOfHalt),
+            "device-address-binding" => Some(Self::DeviceAddressBinding),
+            "device-type" => Some(Self::DeviceType),
+            "device-uuid" => Some(Self::DeviceUuid),
+            "direct-reading" => Some(Self::DirectReading),
+            "distribution-key-revision" => Some(Self::DistributionKeyRevision),
+            "do-not-hide" => Some(Self::DoNotHide),
+            "door-alarm-state" => Some(Self::DoorAlarmState),
+            "door-extended-pulse-time" => Some(Self::DoorExtendedPulseTime),
+            "door-members" => Some(Self::DoorMembers),
+            "door-open-too-long-time" => Some(Self::DoorOpenTooLongTime),
+            "door-pulse-time" => Some(Self::DoorPulseTime),
+            "door-status" => Some(Self::DoorStatus),
+            "door-unlock-delay-time" => Some(Self::DoorUnlockDelayTime),
+            "duty-window" => Some(Self::DutyWindow),
             "effective-period" => Some(Self::EffectivePeriod),
+            "egress-active" => Some(Self::EgressActive),
+            "egress-time" => Some(Self::EgressTime),
+            "elapsed-active-time" => Some(Self::ElapsedActiveTime),
+            "elevator-group" => Some(Self::ElevatorGroup),
             "enable" => Some(Self::Enable),
+            "energy-meter" => Some(Self::EnergyMeter),
+            "energy-meter-ref" => Some(Self::EnergyMeterRef),
+            "entry-points" => Some(Self::EntryPoints),
+            "error-limit" => Some(Self::ErrorLimit),
+            "escalator-mode" => Some(Self::EscalatorMode),
+            "event-algorithm-inhibit" => Some(Self::EventAlgorithmInhibit),
+            "event-algorithm-inhibit-ref" => Some(Self::EventAlgorithmInhibitRef),
+            "event-detection-enable" => Some(Self::EventDetectionEnable),
             "event-enable" => Some(Self::EventEnable),
+            "event-message-texts" => Some(Self::EventMessageTexts),
+            "event-message-texts-config" => Some(Self::EventMessageTextsConfig),
+            "event-parameters" => Some(Self::EventParameters),
             "event-state" => Some(Self::EventState),
             "event-time-stamps" => Some(Self::EventTimeStamps),
+            "event-type" => Some(Self::EventType),
             "exception-schedule" => Some(Self::ExceptionSchedule),
+            "execution-delay" => Some(Self::ExecutionDelay),
+            "exit-points" => Some(Self::ExitPoints),
+            "expected-shed-level" => Some(Self::ExpectedShedLevel),
+            "expiration-time" => Some(Self::ExpirationTime),
+            "extended-time-enable" => Some(Self::ExtendedTimeEnable),
+            "failed-attempt-events" => Some(Self::FailedAttemptEvents),
+            "failed-attempts" => Some(Self::FailedAttempts),
+            "failed-attempts-time" => Some(Self::FailedAttemptsTime),
+            "fault-high-limit" => Some(Self::FaultHighLimit),
+            "fault-low-limit" => Some(Self::FaultLowLimit),
+            "fault-parameters" => Some(Self::FaultParameters),
+            "fault-signals" => Some(Self::FaultSignals),
+            "fault-type" => Some(Self::FaultType),
+            "fault-values" => Some(Self::FaultValues),
+            "fd-bbmd-address" => Some(Self::FdBbmdAddress),
+            "fd-subscription-lifetime" => Some(Self::FdSubscriptionLifetime),
+            "feedback-value" => Some(Self::FeedbackValue),
+            "file-access-method" => Some(Self::FileAccessMethod),
+            "file-size" => Some(Self::FileSize),
+            "file-type" => Some(Self::FileType),
             "firmware-revision" => Some(Self::FirmwareRevision),
+            "floor-number" => Some(Self::FloorNumber),
+            "floor-text" => Some(Self::FloorText),
+            "full-duty-baseline" => Some(Self::FullDutyBaseline),
+            "global-identifier" => Some(Self::GlobalIdentifier),
+            "group-id" => Some(Self::GroupId),
+            "group-member-names" => Some(Self::GroupMemberNames),
+            "group-members" => Some(Self::GroupMembers),
+            "group-mode" => Some(Self::GroupMode),
+            "high-end-trim" => Some(Self::HighEndTrim),
             "high-limit" => Some(Self::HighLimit),
+            "higher-deck" => Some(Self::HigherDeck),
+            "in-process" => Some(Self::InProcess),
+            "in-progress" => Some(Self::InProgress),
             "inactive-text" => Some(Self::InactiveText),
+            "initial-timeout" => Some(Self::InitialTimeout),
+            "input-reference" => Some(Self::InputReference),
+            "installation-id" => Some(Self::InstallationId),
+            "instance-of" => Some(Self::InstanceOf),
+            "instantaneous-power" => Some(Self::InstantaneousPower),
+            "integral-constant" => Some(Self::IntegralConstant),
+            "integral-constant-units" => Some(Self::IntegralConstantUnits),
+            "interface-value" => Some(Self::InterfaceValue),
+            "interval-offset" => Some(Self::IntervalOffset),
+            "ip-address" => Some(Self::IpAddress),
+            "ip-default-gateway" => Some(Self::IpDefaultGateway),
+            "ip-dhcp-enable" => Some(Self::IpDhcpEnable),
+            "ip-dhcp-lease-time" => Some(Self::IpDhcpLeaseTime),
+            "ip-dhcp-lease-time-remaining" => Some(Self::IpDhcpLeaseTimeRemaining),
+            "ip-dhcp-server" => Some(Self::IpDhcpServer),
+            "ip-dns-server" => Some(Self::IpDnsServer),
+            "ip-subnet-mask" => Some(Self::IpSubnetMask),
+            "ipv6-address" => Some(Self::Ipv6Address),
+            "ipv6-auto-addressing-enable" => Some(Self::Ipv6AutoAddressingEnable),
+            "ipv6-default-gateway" => Some(Self::Ipv6DefaultGateway),
+            "ipv6-dhcp-lease-time" => Some(Self::Ipv6DhcpLeaseTime),
+            "ipv6-dhcp-lease-time-remaining" => Some(Self::Ipv6DhcpLeaseTimeRemaining),
+            "ipv6-dhcp-server" => Some(Self::Ipv6DhcpServer),
+            "ipv6-dns-server" => Some(Self::Ipv6DnsServer),
+            "ipv6-prefix-length" => Some(Self::Ipv6PrefixLength),
+            "ipv6-zone-index" => Some(Self::Ipv6ZoneIndex),
+            "is-utc" => Some(Self::IsUtc),
+            "issuer-certificate-files" => Some(Self::IssuerCertificateFiles),
+            "key-sets" => Some(Self::KeySets),
+            "landing-call-control" => Some(Self::LandingCallControl),
+            "landing-calls" => Some(Self::LandingCalls),
+            "landing-door-status" => Some(Self::LandingDoorStatus),
+            "last-access-event" => Some(Self::LastAccessEvent),
+            "last-access-point" => Some(Self::LastAccessPoint),
+            "last-command-time" => Some(Self::LastCommandTime),
+            "last-credential-added" => Some(Self::LastCredentialAdded),
+            "last-credential-added-time" => Some(Self::LastCredentialAddedTime),
+            "last-credential-removed" => Some(Self::LastCredentialRemoved),
+            "last-credential-removed-time" => Some(Self::LastCredentialRemovedTime),
+            "last-key-server" => Some(Self::LastKeyServer),
+            "last-notify-record" => Some(Self::LastNotifyRecord),
+            "last-priority" => Some(Self::LastPriority),
+            "last-restart-reason" => Some(Self::LastRestartReason),
+            "last-restore-time" => Some(Self::LastRestoreTime),
+            "last-state-change" => Some(Self::LastStateChange),
+            "last-use-time" => Some(Self::LastUseTime),
+            "life-safety-alarm-values" => Some(Self::LifeSafetyAlarmValues),
+            "lighting-command" => Some(Self::LightingCommand),
+            "lighting-command-default-priority" => Some(Self::LightingCommandDefaultPriority),
             "limit-enable" => Some(Self::LimitEnable),
+            "limit-monitoring-interval" => Some(Self::LimitMonitoringInterval),
+            "link-speed" => Some(Self::LinkSpeed),
+            "link-speed-autonegotiate" => Some(Self::LinkSpeedAutonegotiate),
+            "link-speeds" => Some(Self::LinkSpeeds),
+            "list-of-group-members" => Some(Self::ListOfGroupMembers),
             "list-of-object-property-references" => Some(Self::ListOfObjectPropertyReferences),
+            "local-date" => Some(Self::LocalDate),
+            "local-forwarding-only" => Some(Self::LocalForwardingOnly),
+            "local-time" => Some(Self::LocalTime),
+            "location" => Some(Self::Location),
+            "lock-status" => Some(Self::LockStatus),
+            "lockout" => Some(Self::Lockout),
+            "lockout-relinquish-time" => Some(Self::LockoutRelinquishTime),
             "log-buffer" => Some(Self::LogBuffer),
             "log-device-object-property" => Some(Self::LogDeviceObjectProperty),
             "log-interval" => Some(Self::LogInterval),
+            "logging-object" => Some(Self::LoggingObject),
+            "logging-record" => Some(Self::LoggingRecord),
+            "logging-type" => Some(Self::LoggingType),
+            "low-diff-limit" => Some(Self::LowDiffLimit),
+            "low-end-trim" => Some(Self::LowEndTrim),
             "low-limit" => Some(Self::LowLimit),
+            "lower-deck" => Some(Self::LowerDeck),
+            "mac-address" => Some(Self::MacAddress),
+            "machine-room-id" => Some(Self::MachineRoomId),
+            "maintenance-required" => Some(Self::MaintenanceRequired),
+            "making-car-call" => Some(Self::MakingCarCall),
+            "manipulated-variable-reference" => Some(Self::ManipulatedVariableReference),
+            "manual-slave-address-binding" => Some(Self::ManualSlaveAddressBinding),
+            "masked-alarm-values" => Some(Self::MaskedAlarmValues),
+            "max-actual-value" => Some(Self::MaxActualValue),
             "max-apdu-length-accepted" => Some(Self::MaxApduLengthAccepted),
+            "max-bvlc-length-accepted" => Some(Self::MaxBvlcLengthAccepted),
+            "max-failed-attempts" => Some(Self::MaxFailedAttempts),
+            "max-info-frames" => Some(Self::MaxInfoFrames),
+            "max-master" => Some(Self::MaxMaster),
+            "max-npdu-length-accepted" => Some(Self::MaxNpduLengthAccepted),
             "max-pres-value" => Some(Self::MaxPresValue),
+            "max-segments-accepted" => Some(Self::MaxSegmentsAccepted),
+            "maximum-output" => Some(Self::MaximumOutput),
+            "maximum-send-delay" => Some(Self::MaximumSendDelay),
+            "maximum-value" => Some(Self::MaximumValue),
+            "maximum-value-timestamp" => Some(Self::MaximumValueTimestamp),
+            "member-of" => Some(Self::MemberOf),
+            "member-status-flags" => Some(Self::MemberStatusFlags),
+            "members" => Some(Self::Members),
+            "min-actual-value" => Some(Self::MinActualValue),
             "min-pres-value" => Some(Self::MinPresValue),
+            "minimum-off-time" => Some(Self::MinimumOffTime),
+            "minimum-on-time" => Some(Self::MinimumOnTime),
+            "minimum-output" => Some(Self::MinimumOutput),
+            "minimum-value" => Some(Self::MinimumValue),
+            "minimum-value-timestamp" => Some(Self::MinimumValueTimestamp),
+            "mode" => Some(Self::Mode),
             "model-name" => Some(Self::ModelName),
+            "modification-date" => Some(Self::ModificationDate),
+            "monitored-objects" => Some(Self::MonitoredObjects),
+            "muster-point" => Some(Self::MusterPoint),
+            "negative-access-rules" => Some(Self::NegativeAccessRules),
+            "network-access-security-policies" => Some(Self::NetworkAccessSecurityPolicies),
+            "network-interface-name" => Some(Self::NetworkInterfaceName),
+            "network-number" => Some(Self::NetworkNumber),
+            "network-number-quality" => Some(Self::NetworkNumberQuality),
+            "network-type" => Some(Self::NetworkType),
+            "next-stopping-floor" => Some(Self::NextStoppingFloor),
+            "node-subtype" => Some(Self::NodeSubtype),
+            "node-type" => Some(Self::NodeType),
             "notification-class" => Some(Self::NotificationClass),
+            "notification-threshold" => Some(Self::NotificationThreshold),
             "notify-type" => Some(Self::NotifyType),
             "number-of-apdu-retries" => Some(Self::NumberOfApduRetries),
+            "number-of-authentication-policies" => Some(Self::NumberOfAuthenticationPolicies),
+            "number-of-states" => Some(Self::NumberOfStates),
             "object-identifier" => Some(Self::ObjectIdentifier),
             "object-list" => Some(Self::ObjectList),
             "object-name" => Some(Self::ObjectName),
+            "object-property-reference" => Some(Self::ObjectPropertyReference),
             "object-type" => Some(Self::ObjectType),
+            "occupancy-count" => Some(Self::OccupancyCount),
+            "occupancy-count-adjust" => Some(Self::OccupancyCountAdjust),
+            "occupancy-count-enable" => Some(Self::OccupancyCountEnable),
+            "occupancy-lower-limit" => Some(Self::OccupancyLowerLimit),
+            "occupancy-lower-limit-enforced" => Some(Self::OccupancyLowerLimitEnforced),
+            "occupancy-state" => Some(Self::OccupancyState),
+            "occupancy-upper-limit" => Some(Self::OccupancyUpperLimit),
+            "occupancy-upper-limit-enforced" => Some(Self::OccupancyUpperLimitEnforced),
+            "operation-direction" => Some(Self::OperationDirection),
+            "operation-expected" => Some(Self::OperationExpected),
+            "operational-certificate-file" => Some(Self::OperationalCertificateFile),
+            "optional" => Some(Self::Optional),
             "out-of-service" => Some(Self::OutOfService),
+            "output-units" => Some(Self::OutputUnits),
+            "override-color-reference" => Some(Self::OverrideColorReference),
+            "packet-reorder-time" => Some(Self::PacketReorderTime),
+            "passback-mode" => Some(Self::PassbackMode),
+            "passback-timeout" => Some(Self::PassbackTimeout),
+            "passenger-alarm" => Some(Self::PassengerAlarm),
+            "polarity" => Some(Self::Polarity),
+            "port-filter" => Some(Self::PortFilter),
+            "positive-access-rules" => Some(Self::PositiveAccessRules),
+            "power" => Some(Self::Power),
+            "power-mode" => Some(Self::PowerMode),
+            "prescale" => Some(Self::Prescale),
+            "present-stage" => Some(Self::PresentStage),
             "present-value" => Some(Self::PresentValue),
+            "priority" => Some(Self::Priority),
             "priority-array" => Some(Self::PriorityArray),
+            "priority-for-writing" => Some(Self::PriorityForWriting),
+            "process-identifier" => Some(Self::ProcessIdentifier),
+            "process-identifier-filter" => Some(Self::ProcessIdentifierFilter),
+            "profile-location" => Some(Self::ProfileLocation),
+            "profile-name" => Some(Self::ProfileName),
+            "program-change" => Some(Self::ProgramChange),
+            "program-location" => Some(Self::ProgramLocation),
+            "program-state" => Some(Self::ProgramState),
+            "property-list" => Some(Self::PropertyList),
+            "proportional-constant" => Some(Self::ProportionalConstant),
+            "proportional-constant-units" => Some(Self::ProportionalConstantUnits),
+            "protocol-level" => Some(Self::ProtocolLevel),
+            "protocol-object-types-supported" => Some(Self::ProtocolObjectTypesSupported),
             "protocol-revision" => Some(Self::ProtocolRevision),
+            "protocol-services-supported" => Some(Self::ProtocolServicesSupported),
             "protocol-version" => Some(Self::ProtocolVersion),
+            "pulse-rate" => Some(Self::PulseRate),
+            "read-only" => Some(Self::ReadOnly),
+            "reason-for-disable" => Some(Self::ReasonForDisable),
+            "reason-for-halt" => Some(Self::ReasonForHalt),
             "recipient-list" => Some(Self::RecipientList),
             "record-count" => Some(Self::RecordCount),
+            "records-since-notification" => Some(Self::RecordsSinceNotification),
+            "reference-port" => Some(Self::ReferencePort),
+            "registered-car-call" => Some(Self::RegisteredCarCall),
             "reliability" => Some(Self::Reliability),
+            "reliability-evaluation-inhibit" => Some(Self::ReliabilityEvaluationInhibit),
             "relinquish-default" => Some(Self::RelinquishDefault),
+            "represents" => Some(Self::Represents),
+            "requested-shed-level" => Some(Self::RequestedShedLevel),
+            "requested-update-interval" => Some(Self::RequestedUpdateInterval),
+            "required" => Some(Self::Required),
             "resolution" => Some(Self::Resolution),
+            "restart-notification-recipients" => Some(Self::RestartNotificationRecipients),
+            "restore-completion-time" => Some(Self::RestoreCompletionTime),
+            "restore-preparation-time" => Some(Self::RestorePreparationTime),
+            "routing-table" => Some(Self::RoutingTable),
+            "sc-connect-wait-timeout" => Some(Self::ScConnectWaitTimeout),
+            "sc-direct-connect-accept-enable" => Some(Self::ScDirectConnectAcceptEnable),
+            "sc-direct-connect-accept-uris" => Some(Self::ScDirectConnectAcceptUris),
+            "sc-direct-connect-binding" => Some(Self::ScDirectConnectBinding),
+            "sc-direct-connect-connection-status" => Some(Self::ScDirectConnectConnectionStatus),
+            "sc-direct-connect-initiate-enable" => Some(Self::ScDirectConnectInitiateEnable),
+            "sc-disconnect-wait-timeout" => Some(Self::ScDisconnectWaitTimeout),
+            "sc-failed-connection-requests" => Some(Self::ScFailedConnectionRequests),
+            "sc-failover-hub-connection-status" => Some(Self::ScFailoverHubConnectionStatus),
+            "sc-failover-hub-uri" => Some(Self::ScFailoverHubUri),
+            "sc-heartbeat-timeout" => Some(Self::ScHeartbeatTimeout),
+            "sc-hub-connector-state" => Some(Self::ScHubConnectorState),
+            "sc-hub-function-accept-uris" => Some(Self::ScHubFunctionAcceptUris),
+            "sc-hub-function-binding" => Some(Self::ScHubFunctionBinding),
+            "sc-hub-function-connection-status" => Some(Self::ScHubFunctionConnectionStatus),
+            "sc-hub-function-enable" => Some(Self::ScHubFunctionEnable),
+            "sc-maximum-reconnect-time" => Some(Self::ScMaximumReconnectTime),
+            "sc-minimum-reconnect-time" => Some(Self::ScMinimumReconnectTime),
+            "sc-primary-hub-connection-status" => Some(Self::ScPrimaryHubConnectionStatus),
+            "sc-primary-hub-uri" => Some(Self::ScPrimaryHubUri),
+            "scale" => Some(Self::Scale),
+            "scale-factor" => Some(Self::ScaleFactor),
             "schedule-default" => Some(Self::ScheduleDefault),
+            "secured-status" => Some(Self::SecuredStatus),
+            "security-pdu-timeout" => Some(Self::SecurityPduTimeout),
+            "security-time-window" => Some(Self::SecurityTimeWindow),
             "segmentation-supported" => Some(Self::SegmentationSupported),
+            "send-now" => Some(Self::SendNow),
+            "serial-number" => Some(Self::SerialNumber),
+            "setpoint" => Some(Self::Setpoint),
+            "setpoint-reference" => Some(Self::SetpointReference),
+            "setting" => Some(Self::Setting),
+            "shed-duration" => Some(Self::ShedDuration),
+            "shed-level-descriptions" => Some(Self::ShedLevelDescriptions),
+            "shed-levels" => Some(Self::ShedLevels),
+            "silenced" => Some(Self::Silenced),
+            "slave-address-binding" => Some(Self::SlaveAddressBinding),
+            "slave-proxy-enable" => Some(Self::SlaveProxyEnable),
+            "stage-names" => Some(Self::StageNames),
+            "stages" => Some(Self::Stages),
             "start-time" => Some(Self::StartTime),
+            "state-change-values" => Some(Self::StateChangeValues),
+            "state-description" => Some(Self::StateDescription),
+            "state-text" => Some(Self::StateText),
             "status-flags" => Some(Self::StatusFlags),
             "stop-time" => Some(Self::StopTime),
+            "stop-when-full" => Some(Self::StopWhenFull),
+            "strike-count" => Some(Self::StrikeCount),
+            "structured-object-list" => Some(Self::StructuredObjectList),
+            "subordinate-annotations" => Some(Self::SubordinateAnnotations),
+            "subordinate-list" => Some(Self::SubordinateList),
+            "subordinate-node-types" => Some(Self::SubordinateNodeTypes),
+            "subordinate-relationships" => Some(Self::SubordinateRelationships),
+            "subordinate-tags" => Some(Self::SubordinateTags),
+            "subscribed-recipients" => Some(Self::SubscribedRecipients),
+            "supported-format-classes" => Some(Self::SupportedFormatClasses),
+            "supported-formats" => Some(Self::SupportedFormats),
+            "supported-security-algorithms" => Some(Self::SupportedSecurityAlgorithms),
             "system-status" => Some(Self::SystemStatus),
+            "tags" => Some(Self::Tags),
+            "target-references" => Some(Self::TargetReferences),
+            "threat-authority" => Some(Self::ThreatAuthority),
+            "threat-level" => Some(Self::ThreatLevel),
             "time-delay" => Some(Self::TimeDelay),
+            "time-delay-normal" => Some(Self::TimeDelayNormal),
+            "time-of-active-time-reset" => Some(Self::TimeOfActiveTimeReset),
+            "time-of-device-restart" => Some(Self::TimeOfDeviceRestart),
+            "time-of-state-count-reset" => Some(Self::TimeOfStateCountReset),
+            "time-of-strike-count-reset" => Some(Self::TimeOfStrikeCountReset),
+            "time-synchronization-interval" => Some(Self::TimeSynchronizationInterval),
+            "time-synchronization-recipients" => Some(Self::TimeSynchronizationRecipients),
+            "timer-running" => Some(Self::TimerRunning),
+            "timer-state" => Some(Self::TimerState),
             "total-record-count" => Some(Self::TotalRecordCount),
+            "trace-flag" => Some(Self::TraceFlag),
+            "tracking-value" => Some(Self::TrackingValue),
+            "transaction-notification-class" => Some(Self::TransactionNotificationClass),
+            "transition" => Some(Self::Transition),
+            "trigger" => Some(Self::Trigger),
+            "trim-fade-time" => Some(Self::TrimFadeTime),
             "units" => Some(Self::Units),
             "update-interval" => Some(Self::UpdateInterval),
+            "update-key-set-timeout" => Some(Self::UpdateKeySetTimeout),
+            "update-time" => Some(Self::UpdateTime),
+            "user-external-identifier" => Some(Self::UserExternalIdentifier),
+            "user-information-reference" => Some(Self::UserInformationReference),
+            "user-name" => Some(Self::UserName),
+            "user-type" => Some(Self::UserType),
+            "uses-remaining" => Some(Self::UsesRemaining),
+            "utc-offset" => Some(Self::UtcOffset),
+            "utc-time-synchronization-recipients" => Some(Self::UtcTimeSynchronizationRecipients),
+            "valid-samples" => Some(Self::ValidSamples),
+            "value-before-change" => Some(Self::ValueBeforeChange),
+            "value-change-time" => Some(Self::ValueChangeTime),
+            "value-set" => Some(Self::ValueSet),
+            "value-source" => Some(Self::ValueSource),
+            "value-source-array" => Some(Self::ValueSourceArray),
+            "variance-value" => Some(Self::VarianceValue),
+            "vendor-identifier" => Some(Self::VendorIdentifier),
             "vendor-name" => Some(Self::VendorName),
+            "verification-time" => Some(Self::VerificationTime),
+            "virtual-mac-address-table" => Some(Self::VirtualMacAddressTable),
+            "vt-classes-supported" => Some(Self::VtClassesSupported),
             "weekly-schedule" => Some(Self::WeeklySchedule),
+            "window-interval" => Some(Self::WindowInterval),
+            "window-samples" => Some(Self::WindowSamples),
+            "write-status" => Some(Self::WriteStatus),
+            "zone-from" => Some(Self::ZoneFrom),
+            "zone-members" => Some(Self::ZoneMembers),
+            "zone-to" => Some(Self::ZoneTo),
             _ => None,
         }
     }
+
+    /// Returns `true` for ALL, REQUIRED and OPTIONAL, which ReadPropertyMultiple expands
+    /// into a list of the object's properties rather than reading a property of that name.
+    pub const fn is_special(self) -> bool {
+        matches!(self, Self::All | Self::Required | Self::Optional)
+    }
 }
 
 impl core::fmt::Display for PropertyId {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
+            Self::AbsenteeLimit => f.write_str("absentee-limit"),
+            Self::AcceptedModes => f.write_str("accepted-modes"),
+            Self::AccessAlarmEvents => f.write_str("access-alarm-events"),
+            Self::AccessDoors => f.write_str("access-doors"),
+            Self::AccessEvent => f.write_str("access-event"),
+            Self::AccessEventAuthenticationFactor => {
+                f.write_str("access-event-authentication-factor")
+            }
+            Self::AccessEventCredential => f.write_str("access-event-credential"),
+            Self::AccessEventTag => f.write_str("access-event-tag"),
+            Self::AccessEventTime => f.write_str("access-event-time"),
+            Self::AccessTransactionEvents => f.write_str("access-transaction-events"),
+            Self::Accompaniment => f.write_str("accompaniment"),
+            Self::AccompanimentTime => f.write_str("accompaniment-time"),
+            Self::AckRequired => f.write_str("ack-required"),
             Self::AckedTransitions => f.write_str("acked-transitions"),
+            Self::Action => f.write_str("action"),
+            Self::ActionText => f.write_str("action-text"),
+            Self::ActivationTime => f.write_str("activation-time"),
+            Self::ActiveAuthenticationPolicy => f.write_str("active-authentication-policy"),
+            Self::ActiveCovMultipleSubscriptions => {
+                f.write_str("active-cov-multiple-subscriptions")
+            }
+            Self::ActiveCovSubscriptions => f.write_str("active-cov-subscriptions"),
             Self::ActiveText => f.write_str("active-text"),
+            Self::ActiveVtSessions => f.write_str("active-vt-sessions"),
+            Self::ActualShedLevel => f.write_str("actual-shed-level"),
+            Self::AdditionalReferencePorts => f.write_str("additional-reference-ports"),
+            Self::AdjustValue => f.write_str("adjust-value"),
+            Self::AlarmValue => f.write_str("alarm-value"),
+            Self::AlarmValues => f.write_str("alarm-values"),
+            Self::AlignIntervals => f.write_str("align-intervals"),
+            Self::All => f.write_str("all"),
+            Self::AllWritesSuccessful => f.write_str("all-writes-successful"),
+            Self::AllowGroupDelayInhibit => f.write_str("allow-group-delay-inhibit"),
+            Self::ApduLength => f.write_str("apdu-length"),
+            Self::ApduSegmentTimeout => f.write_str("apdu-segment-timeout"),
             Self::ApduTimeout => f.write_str("apdu-timeout"),
             Self::ApplicationSoftwareVersion => f.write_str("application-software-version"),
+            Self::Archive => f.write_str("archive"),
+            Self::AssignedAccessRights => f.write_str("assigned-access-rights"),
+            Self::AssignedLandingCalls => f.write_str("assigned-landing-calls"),
+            Self::AttemptedSamples => f.write_str("attempted-samples"),
+            Self::AuditLevel => f.write_str("audit-level"),
+            Self::AuditNotificationRecipient => f.write_str("audit-notification-recipient"),
+            Self::AuditPriorityFilter => f.write_str("audit-priority-filter"),
+            Self::AuditSourceLevel => f.write_str("audit-source-level"),
+            Self::AuditableOperations => f.write_str("auditable-operations"),
+            Self::AuthenticationFactors => f.write_str("authentication-factors"),
+            Self::AuthenticationPolicyList => f.write_str("authentication-policy-list"),
+            Self::AuthenticationPolicyNames => f.write_str("authentication-policy-names"),
+            Self::AuthenticationStatus => f.write_str("authentication-status"),
+            Self::AuthorizationExemptions => f.write_str("authorization-exemptions"),
+            Self::AuthorizationMode => f.write_str("authorization-mode"),
+            Self::AutoSlaveDiscovery => f.write_str("auto-slave-discovery"),
+            Self::AverageValue => f.write_str("average-value"),
+            Self::BackupAndRestoreState => f.write_str("backup-and-restore-state"),
+            Self::BackupFailureTimeout => f.write_str("backup-failure-timeout"),
+            Self::BackupPreparationTime => f.write_str("backup-preparation-time"),
+            Self::BacnetIpGlobalAddress => f.write_str("bacnet-ip-global-address"),
+            Self::BacnetIpMode => f.write_str("bacnet-ip-mode"),
+            Self::BacnetIpMulticastAddress => f.write_str("bacnet-ip-multicast-address"),
+            Self::BacnetIpNatTraversal => f.write_str("bacnet-ip-nat-traversal"),
+            Self::BacnetIpUdpPort => f.write_str("bacnet-ip-udp-port"),
+            Self::BacnetIpv6Mode => f.write_str("bacnet-ipv6-mode"),
+            Self::BacnetIpv6MulticastAddress => f.write_str("bacnet-ipv6-multicast-address"),
+            Self::BacnetIpv6UdpPort => f.write_str("bacnet-ipv6-udp-port"),
+            Self::BaseDeviceSecurityPolicy => f.write_str("base-device-security-policy"),
+            Self::BbmdAcceptFdRegistrations => f.write_str("bbmd-accept-fd-registrations"),
+            Self::BbmdBroadcastDistributionTable => {
+                f.write_str("bbmd-broadcast-distribution-table")
+            }
+            Self::BbmdForeignDeviceTable => f.write_str("bbmd-foreign-device-table"),
+            Self::BelongsTo => f.write_str("belongs-to"),
+            Self::Bias => f.write_str("bias"),
+            Self::BitMask => f.write_str("bit-mask"),
+            Self::BitText => f.write_str("bit-text"),
+            Self::BlinkWarnEnable => f.write_str("blink-warn-enable"),
             Self::BufferSize => f.write_str("buffer-size"),
+            Self::CarAssignedDirection => f.write_str("car-assigned-direction"),
+            Self::CarDoorCommand => f.write_str("car-door-command"),
+            Self::CarDoorStatus => f.write_str("car-door-status"),
+            Self::CarDoorText => f.write_str("car-door-text"),
+            Self::CarDoorZone => f.write_str("car-door-zone"),
+            Self::CarDriveStatus => f.write_str("car-drive-status"),
+            Self::CarLoad => f.write_str("car-load"),
+            Self::CarLoadUnits => f.write_str("car-load-units"),
+            Self::CarMode => f.write_str("car-mode"),
+            Self::CarMovingDirection => f.write_str("car-moving-direction"),
+            Self::CarPosition => f.write_str("car-position"),
+            Self::CertificateSigningRequestFile => f.write_str("certificate-signing-request-file"),
+            Self::ChangeOfStateCount => f.write_str("change-of-state-count"),
+            Self::ChangeOfStateTime => f.write_str("change-of-state-time"),
+            Self::ChangesPending => f.write_str("changes-pending"),
+            Self::ChannelNumber => f.write_str("channel-number"),
+            Self::ClientCovIncrement => f.write_str("client-cov-increment"),
+            Self::ColorCommand => f.write_str("color-command"),
+            Self::ColorOverride => f.write_str("color-override"),
+            Self::ColorReference => f.write_str("color-reference"),
+            Self::Command => f.write_str("command"),
+            Self::CommandTimeArray => f.write_str("command-time-array"),
+            Self::CommandValidationResult => f.write_str("command-validation-result"),
+            Self::ConfigurationFiles => f.write_str("configuration-files"),
+            Self::ControlGroups => f.write_str("control-groups"),
+            Self::ControlledVariableReference => f.write_str("controlled-variable-reference"),
+            Self::ControlledVariableUnits => f.write_str("controlled-variable-units"),
+            Self::ControlledVariableValue => f.write_str("controlled-variable-value"),
+            Self::Count => f.write_str("count"),
+            Self::CountBeforeChange => f.write_str("count-before-change"),
+            Self::CountChangeTime => f.write_str("count-change-time"),
             Self::CovIncrement => f.write_str("cov-increment"),
+            Self::CovPeriod => f.write_str("cov-period"),
+            Self::CovResubscriptionInterval => f.write_str("cov-resubscription-interval"),
+            Self::CovuPeriod => f.write_str("covu-period"),
+            Self::CovuRecipients => f.write_str("covu-recipients"),
+            Self::CredentialDisable => f.write_str("credential-disable"),
+            Self::CredentialStatus => f.write_str("credential-status"),
+            Self::Credentials => f.write_str("credentials"),
+            Self::CredentialsInZone => f.write_str("credentials-in-zone"),
+            Self::CurrentCommandPriority => f.write_str("current-command-priority"),
+            Self::CurrentHealth => f.write_str("current-health"),
             Self::DatabaseRevision => f.write_str("database-revision"),
             Self::DateList => f.write_str("date-list"),
+            Self::DaylightSavingsStatus => f.write_str("daylight-savings-status"),
+            Self::DaysRemaining => f.write_str("days-remaining"),
             Self::Deadband => f.write_str("deadband"),
+            Self::DefaultColor => f.write_str("default-color"),
+            Self::DefaultColorTemperature => f.write_str("default-color-temperature"),
+            Self::DefaultFadeTime => f.write_str("default-fade-time"),
+            Self::DefaultPresentValue => f.write_str("default-present-value"),
+            Self::DefaultRampRate => f.write_str("default-ramp-rate"),
+            Self::DefaultStepIncrement => f.write_str("default-step-increment"),
+            Self::DefaultSubordinateRelationship => f.write_str("default-subordinate-relationship"),
+            Self::DefaultTimeout => f.write_str("default-timeout"),
+            Self::DeleteOnForward => f.write_str("delete-on-forward"),
+            Self::DeployedProfileLocation => f.write_str("deployed-profile-location"),
+            Self::DerivativeConstant => f.write_str("derivative-constant"),
+            Self::DerivativeConstantUnits => f.write_str("derivative-constant-units"),
             Self::Description => f.write_str("description"),
+            Self::DescriptionOfHalt => f.write_str("description-of-halt"),
+            Self::DeviceAddressBinding => f.write_str("device-address-binding"),
+            Self::DeviceType => f.write_str("device-type"),
+            Self::DeviceUuid => f.write_str("device-uuid"),
+            Self::DirectReading => f.write_str("direct-reading"),
+            Self::DistributionKeyRevision => f.write_str("distribution-key-revision"),
+            Self::DoNotHide => f.write_str("do-not-hide"),
+            Self::DoorAlarmState => f.write_str("door-alarm-state"),
+            Self::DoorExtendedPulseTime => f.write_str("door-extended-pulse-time"),
+            Self::DoorMembers => f.write_str("door-members"),
+            Self::DoorOpenTooLongTime => f.write_str("door-open-too-long-time"),
+            Self::DoorPulseTime => f.write_str("door-pulse-time"),
+            Self::DoorStatus => f.write_str("door-status"),
+            Self::DoorUnlockDelayTime => f.write_str("door-unlock-delay-time"),
+            Self::DutyWindow => f.write_str("duty-window"),
             Self::EffectivePeriod => f.write_str("effective-period"),
+            Self::EgressActive => f.write_str("egress-active"),
+            Self::EgressTime => f.write_str("egress-time"),
+            Self::ElapsedActiveTime => f.write_str("elapsed-active-time"),
+            Self::ElevatorGroup => f.write_str("elevator-group"),
             Self::Enable => f.write_str("enable"),
+            Self::EnergyMeter => f.write_str("energy-meter"),
+            Self::EnergyMeterRef => f.write_str("energy-meter-ref"),
+            Self::EntryPoints => f.write_str("entry-points"),
+            Self::ErrorLimit => f.write_str("error-limit"),
+            Self::EscalatorMode => f.write_str("escalator-mode"),
+            Self::EventAlgorithmInhibit => f.write_str("event-algorithm-inhibit"),
+            Self::EventAlgorithmInhibitRef => f.write_str("event-algorithm-inhibit-ref"),
+            Self::EventDetectionEnable => f.write_str("event-detection-enable"),
             Self::EventEnable => f.write_str("event-enable"),
+            Self::EventMessageTexts => f.write_str("event-message-texts"),
+            Self::EventMessageTextsConfig => f.write_str("event-message-texts-config"),
+            Self::EventParameters => f.write_str("event-parameters"),
             Self::EventState => f.write_str("event-state"),
             Self::EventTimeStamps => f.write_str("event-time-stamps"),
+            Self::EventType => f.write_str("event-type"),
             Self::ExceptionSchedule => f.write_str("exception-schedule"),
+            Self::ExecutionDelay => f.write_str("execution-delay"),
+            Self::ExitPoints => f.write_str("exit-points"),
+            Self::ExpectedShedLevel => f.write_str("expected-shed-level"),
+            Self::ExpirationTime => f.write_str("expiration-time"),
+            Self::ExtendedTimeEnable => f.write_str("extended-time-enable"),
+            Self::FailedAttemptEvents => f.write_str("failed-attempt-events"),
+            Self::FailedAttempts => f.write_str("failed-attempts"),
+            Self::FailedAttemptsTime => f.write_str("failed-attempts-time"),
+            Self::FaultHighLimit => f.write_str("fault-high-limit"),
+            Self::FaultLowLimit => f.write_str("fault-low-limit"),
+            Self::FaultParameters => f.write_str("fault-parameters"),
+            Self::FaultSignals => f.write_str("fault-signals"),
+            Self::FaultType => f.write_str("fault-type"),
+            Self::FaultValues => f.write_str("fault-values"),
+            Self::FdBbmdAddress => f.write_str("fd-bbmd-address"),
+            Self::FdSubscriptionLifetime => f.write_str("fd-subscription-lifetime"),
+            Self::FeedbackValue => f.write_str("feedback-value"),
+            Self::FileAccessMethod => f.write_str("file-access-method"),
+            Self::FileSize => f.write_str("file-size"),
+            Self::FileType => f.write_str("file-type"),
             Self::FirmwareRevision => f.write_str("firmware-revision"),
+            Self::FloorNumber => f.write_str("floor-number"),
+            Self::FloorText => f.write_str("floor-text"),
+            Self::FullDutyBaseline => f.write_str("full-duty-baseline"),
+            Self::GlobalIdentifier => f.write_str("global-identifier"),
+            Self::GroupId => f.write_str("group-id"),
+            Self::GroupMemberNames => f.write_str("group-member-names"),
+            Self::GroupMembers => f.write_str("group-members"),
+            Self::GroupMode => f.write_str("group-mode"),
+            Self::HighEndTrim => f.write_str("high-end-trim"),
             Self::HighLimit => f.write_str("high-limit"),
+            Self::HigherDeck => f.write_str("higher-deck"),
+            Self::InProcess => f.write_str("in-process"),
+            Self::InProgress => f.write_str("in-progress"),
             Self::InactiveText => f.write_str("inactive-text"),
+            Self::InitialTimeout => f.write_str("initial-timeout"),
+            Self::InputReference => f.write_str("input-reference"),
+            Self::InstallationId => f.write_str("installation-id"),
+            Self::InstanceOf => f.write_str("instance-of"),
+            Self::InstantaneousPower => f.write_str("instantaneous-power"),
+            Self::IntegralConstant => f.write_str("integral-constant"),
+            Self::IntegralConstantUnits => f.write_str("integral-constant-units"),
+            Self::InterfaceValue => f.write_str("interface-value"),
+            Self::IntervalOffset => f.write_str("interval-offset"),
+            Self::IpAddress => f.write_str("ip-address"),
+            Self::IpDefaultGateway => f.write_str("ip-default-gateway"),
+            Self::IpDhcpEnable => f.write_str("ip-dhcp-enable"),
+            Self::IpDhcpLeaseTime => f.write_str("ip-dhcp-lease-time"),
+            Self::IpDhcpLeaseTimeRemaining => f.write_str("ip-dhcp-lease-time-remaining"),
+            Self::IpDhcpServer => f.write_str("ip-dhcp-server"),
+            Self::IpDnsServer => f.write_str("ip-dns-server"),
+            Self::IpSubnetMask => f.write_str("ip-subnet-mask"),
+            Self::Ipv6Address => f.write_str("ipv6-address"),
+            Self::Ipv6AutoAddressingEnable => f.write_str("ipv6-auto-addressing-enable"),
+            Self::Ipv6DefaultGateway => f.write_str("ipv6-default-gateway"),
+            Self::Ipv6DhcpLeaseTime => f.write_str("ipv6-dhcp-lease-time"),
+            Self::Ipv6DhcpLeaseTimeRemaining => f.write_str("ipv6-dhcp-lease-time-remaining"),
+            Self::Ipv6DhcpServer => f.write_str("ipv6-dhcp-server"),
+            Self::Ipv6DnsServer => f.write_str("ipv6-dns-server"),
+            Self::Ipv6PrefixLength => f.write_str("ipv6-prefix-length"),
+            Self::Ipv6ZoneIndex => f.write_str("ipv6-zone-index"),
+            Self::IsUtc => f.write_str("is-utc"),
+            Self::IssuerCertificateFiles => f.write_str("issuer-certificate-files"),
+            Self::KeySets => f.write_str("key-sets"),
+            Self::LandingCallControl => f.write_str("landing-call-control"),
+            Self::LandingCalls => f.write_str("landing-calls"),
+            Self::LandingDoorStatus => f.write_str("landing-door-status"),
+            Self::LastAccessEvent => f.write_str("last-access-event"),
+            Self::LastAccessPoint => f.write_str("last-access-point"),
+            Self::LastCommandTime => f.write_str("last-command-time"),
+            Self::LastCredentialAdded => f.write_str("last-credential-added"),
+            Self::LastCredentialAddedTime => f.write_str("last-credential-added-time"),
+            Self::LastCredentialRemoved => f.write_str("last-credential-removed"),
+            Self::LastCredentialRemovedTime => f.write_str("last-credential-removed-time"),
+            Self::LastKeyServer => f.write_str("last-key-server"),
+            Self::LastNotifyRecord => f.write_str("last-notify-record"),
+            Self::LastPriority => f.write_str("last-priority"),
+            Self::LastRestartReason => f.write_str("last-restart-reason"),
+            Self::LastRestoreTime => f.write_str("last-restore-time"),
+            Self::LastStateChange => f.write_str("last-state-change"),
+            Self::LastUseTime => f.write_str("last-use-time"),
+            Self::LifeSafetyAlarmValues => f.write_str("life-safety-alarm-values"),
+            Self::LightingCommand => f.write_str("lighting-command"),
+            Self::LightingCommandDefaultPriority => {
+                f.write_str("lighting-command-default-priority")
+            }
             Self::LimitEnable => f.write_str("limit-enable"),
+            Self::LimitMonitoringInterval => f.write_str("limit-monitoring-interval"),
+            Self::LinkSpeed => f.write_str("link-speed"),
+            Self::LinkSpeedAutonegotiate => f.write_str("link-speed-autonegotiate"),
+            Self::LinkSpeeds => f.write_str("link-speeds"),
+            Self::ListOfGroupMembers => f.write_str("list-of-group-members"),
             Self::ListOfObjectPropertyReferences => {
                 f.write_str("list-of-object-property-references")
             }
+            Self::LocalDate => f.write_str("local-date"),
+            Self::LocalForwardingOnly => f.write_str("local-forwarding-only"),
+            Self::LocalTime => f.write_str("local-time"),
+            Self::Location => f.write_str("location"),
+            Self::LockStatus => f.write_str("lock-status"),
+            Self::Lockout => f.write_str("lockout"),
+            Self::LockoutRelinquishTime => f.write_str("lockout-relinquish-time"),
             Self::LogBuffer => f.write_str("log-buffer"),
             Self::LogDeviceObjectProperty => f.write_str("log-device-object-property"),
             Self::LogInterval => f.write_str("log-interval"),
+            Self::LoggingObject => f.write_str("logging-object"),
+            Self::LoggingRecord => f.write_str("logging-record"),
+            Self::LoggingType => f.write_str("logging-type"),
+            Self::LowDiffLimit => f.write_str("low-diff-limit"),
+            Self::LowEndTrim => f.write_str("low-end-trim"),
             Self::LowLimit => f.write_str("low-limit"),
+            Self::LowerDeck => f.write_str("lower-deck"),
+            Self::MacAddress => f.write_str("mac-address"),
+            Self::MachineRoomId => f.write_str("machine-room-id"),
+            Self::MaintenanceRequired => f.write_str("maintenance-required"),
+            Self::MakingCarCall => f.write_str("making-car-call"),
+            Self::ManipulatedVariableReference => f.write_str("manipulated-variable-reference"),
+            Self::ManualSlaveAddressBinding => f.write_str("manual-slave-address-binding"),
+            Self::MaskedAlarmValues => f.write_str("masked-alarm-values"),
+            Self::MaxActualValue => f.write_str("max-actual-value"),
             Self::MaxApduLengthAccepted => f.write_str("max-apdu-length-accepted"),
+            Self::MaxBvlcLengthAccepted => f.write_str("max-bvlc-length-accepted"),
+            Self::MaxFailedAttempts => f.write_str("max-failed-attempts"),
+            Self::MaxInfoFrames => f.write_str("max-info-frames"),
+            Self::MaxMaster => f.write_str("max-master"),
+            Self::MaxNpduLengthAccepted => f.write_str("max-npdu-length-accepted"),
             Self::MaxPresValue => f.write_str("max-pres-value"),
+            Self::MaxSegmentsAccepted => f.write_str("max-segments-accepted"),
+            Self::MaximumOutput => f.write_str("maximum-output"),
+            Self::MaximumSendDelay => f.write_str("maximum-send-delay"),
+            Self::MaximumValue => f.write_str("maximum-value"),
+            Self::MaximumValueTimestamp => f.write_str("maximum-value-timestamp"),
+            Self::MemberOf => f.write_str("member-of"),
+            Self::MemberStatusFlags => f.write_str("member-status-flags"),
+            Self::Members => f.write_str("members"),
+            Self::MinActualValue => f.write_str("min-actual-value"),
             Self::MinPresValue => f.write_str("min-pres-value"),
+            Self::MinimumOffTime => f.write_str("minimum-off-time"),
+            Self::MinimumOnTime => f.write_str("minimum-on-time"),
+            Self::MinimumOutput => f.write_str("minimum-output"),
+            Self::MinimumValue => f.write_str("minimum-value"),
+            Self::MinimumValueTimestamp => f.write_str("minimum-value-timestamp"),
+            Self::Mode => f.write_str("mode"),
             Self::ModelName => f.write_str("model-name"),
+            Self::ModificationDate => f.write_str("modification-date"),
+            Self::MonitoredObjects => f.write_str("monitored-objects"),
+            Self::MusterPoint => f.write_str("muster-point"),
+            Self::NegativeAccessRules => f.write_str("negative-access-rules"),
+            Self::NetworkAccessSecurityPolicies => f.write_str("network-access-security-policies"),
+            Self::NetworkInterfaceName => f.write_str("network-interface-name"),
+            Self::NetworkNumber => f.write_str("network-number"),
+            Self::NetworkNumberQuality => f.write_str("network-number-quality"),
+            Self::NetworkType => f.write_str("network-type"),
+            Self::NextStoppingFloor => f.write_str("next-stopping-floor"),
+            Self::NodeSubtype => f.write_str("node-subtype"),
+            Self::NodeType => f.write_str("node-type"),
             Self::NotificationClass => f.write_str("notification-class"),
+            Self::NotificationThreshold => f.write_str("notification-threshold"),
             Self::NotifyType => f.write_str("notify-type"),
             Self::NumberOfApduRetries => f.write_str("number-of-apdu-retries"),
+            Self::NumberOfAuthenticationPolicies => {
+                f.write_str("number-of-authentication-policies")
+            }
+            Self::NumberOfStates => f.write_str("number-of-states"),
             Self::ObjectIdentifier => f.write_str("object-identifier"),
             Self::ObjectList => f.write_str("object-list"),
             Self::ObjectName => f.write_str("object-name"),
+            Self::ObjectPropertyReference => f.write_str("object-property-reference"),
             Self::ObjectType => f.write_str("object-type"),
+            Self::OccupancyCount => f.write_str("occupancy-count"),
+            Self::OccupancyCountAdjust => f.write_str("occupancy-count-adjust"),
+            Self::OccupancyCountEnable => f.write_str("occupancy-count-enable"),
+            Self::OccupancyLowerLimit => f.write_str("occupancy-lower-limit"),
+            Self::OccupancyLowerLimitEnforced => f.write_str("occupancy-lower-limit-enforced"),
+            Self::OccupancyState => f.write_str("occupancy-state"),
+            Self::OccupancyUpperLimit => f.write_str("occupancy-upper-limit"),
+            Self::OccupancyUpperLimitEnforced => f.write_str("occupancy-upper-limit-enforced"),
+            Self::OperationDirection => f.write_str("operation-direction"),
+            Self::OperationExpected => f.write_str("operation-expected"),
+            Self::OperationalCertificateFile => f.write_str("operational-certificate-file"),
+            Self::Optional => f.write_str("optional"),
             Self::OutOfService => f.write_str("out-of-service"),
+            Self::OutputUnits => f.write_str("output-units"),
+            Self::OverrideColorReference => f.write_str("override-color-reference"),
+            Self::PacketReorderTime => f.write_str("packet-reorder-time"),
+            Self::PassbackMode => f.write_str("passback-mode"),
+            Self::PassbackTimeout => f.write_str("passback-timeout"),
+            Self::PassengerAlarm => f.write_str("passenger-alarm"),
+            Self::Polarity => f.write_str("polarity"),
+            Self::PortFilter => f.write_str("port-filter"),
+            Self::PositiveAccessRules => f.write_str("positive-access-rules"),
+            Self::Power => f.write_str("power"),
+            Self::PowerMode => f.write_str("power-mode"),
+            Self::Prescale => f.write_str("prescale"),
+            Self::PresentStage => f.write_str("present-stage"),
             Self::PresentValue => f.write_str("present-value"),
+            Self::Priority => f.write_str("priority"),
             Self::PriorityArray => f.write_str("priority-array"),
+            Self::PriorityForWriting => f.write_str("priority-for-writing"),
+            Self::ProcessIdentifier => f.write_str("process-identifier"),
+            Self::ProcessIdentifierFilter => f.write_str("process-identifier-filter"),
+            Self::ProfileLocation => f.write_str("profile-location"),
+            Self::ProfileName => f.write_str("profile-name"),
+            Self::ProgramChange => f.write_str("program-change"),
+            Self::ProgramLocation => f.write_str("program-location"),
+            Self::ProgramState => f.write_str("program-state"),
+            Self::PropertyList => f.write_str("property-list"),
+            Self::ProportionalConstant => f.write_str("proportional-constant"),
+            Self::ProportionalConstantUnits => f.write_str("proportional-constant-units"),
+            Self::ProtocolLevel => f.write_str("protocol-level"),
+            Self::ProtocolObjectTypesSupported => f.write_str("protocol-object-types-supported"),
             Self::ProtocolRevision => f.write_str("protocol-revision"),
+            Self::ProtocolServicesSupported => f.write_str("protocol-services-supported"),
             Self::ProtocolVersion => f.write_str("protocol-version"),
+            Self::PulseRate => f.write_str("pulse-rate"),
+            Self::ReadOnly => f.write_str("read-only"),
+            Self::ReasonForDisable => f.write_str("reason-for-disable"),
+            Self::ReasonForHalt => f.write_str("reason-for-halt"),
             Self::RecipientList => f.write_str("recipient-list"),
             Self::RecordCount => f.write_str("record-count"),
+            Self::RecordsSinceNotification => f.write_str("records-since-notification"),
+            Self::ReferencePort => f.write_str("reference-port"),
+            Self::RegisteredCarCall => f.write_str("registered-car-call"),
             Self::Reliability => f.write_str("reliability"),
+            Self::ReliabilityEvaluationInhibit => f.write_str("reliability-evaluation-inhibit"),
             Self::RelinquishDefault => f.write_str("relinquish-default"),
+            Self::Represents => f.write_str("represents"),
+            Self::RequestedShedLevel => f.write_str("requested-shed-level"),
+            Self::RequestedUpdateInterval => f.write_str("requested-update-interval"),
+            Self::Required => f.write_str("required"),
             Self::Resolution => f.write_str("resolution"),
+            Self::RestartNotificationRecipients => f.write_str("restart-notification-recipients"),
+            Self::RestoreCompletionTime => f.write_str("restore-completion-time"),
+            Self::RestorePreparationTime => f.write_str("restore-preparation-time"),
+            Self::RoutingTable => f.write_str("routing-table"),
+            Self::ScConnectWaitTimeout => f.write_str("sc-connect-wait-timeout"),
+            Self::ScDirectConnectAcceptEnable => f.write_str("sc-direct-connect-accept-enable"),
+            Self::ScDirectConnectAcceptUris => f.write_str("sc-direct-connect-accept-uris"),
+            Self::ScDirectConnectBinding => f.write_str("sc-direct-connect-binding"),
+            Self::ScDirectConnectConnectionStatus => {
+                f.write_str("sc-direct-connect-connection-status")
+            }
+            Self::ScDirectConnectInitiateEnable => f.write_str("sc-direct-connect-initiate-enable"),
+            Self::ScDisconnectWaitTimeout => f.write_str("sc-disconnect-wait-timeout"),
+            Self::ScFailedConnectionRequests => f.write_str("sc-failed-connection-requests"),
+            Self::ScFailoverHubConnectionStatus => f.write_str("sc-failover-hub-connection-status"),
+            Self::ScFailoverHubUri => f.write_str("sc-failover-hub-uri"),
+            Self::ScHeartbeatTimeout => f.write_str("sc-heartbeat-timeout"),
+            Self::ScHubConnectorState => f.write_str("sc-hub-connector-state"),
+            Self::ScHubFunctionAcceptUris => f.write_str("sc-hub-function-accept-uris"),
+            Self::ScHubFunctionBinding => f.write_str("sc-hub-function-binding"),
+            Self::ScHubFunctionConnectionStatus => f.write_str("sc-hub-function-connection-status"),
+            Self::ScHubFunctionEnable => f.write_str("sc-hub-function-enable"),
+            Self::ScMaximumReconnectTime => f.write_str("sc-maximum-reconnect-time"),
+            Self::ScMinimumReconnectTime => f.write_str("sc-minimum-reconnect-time"),
+            Self::ScPrimaryHubConnectionStatus => f.write_str("sc-primary-hub-connection-status"),
+            Self::ScPrimaryHubUri => f.write_str("sc-primary-hub-uri"),
+            Self::Scale => f.write_str("scale"),
+            Self::ScaleFactor => f.write_str("scale-factor"),
             Self::ScheduleDefault => f.write_str("schedule-default"),
+            Self::SecuredStatus => f.write_str("secured-status"),
+            Self::SecurityPduTimeout => f.write_str("security-pdu-timeout"),
+            Self::SecurityTimeWindow => f.write_str("security-time-window"),
             Self::SegmentationSupported => f.write_str("segmentation-supported"),
+            Self::SendNow => f.write_str("send-now"),
+            Self::SerialNumber => f.write_str("serial-number"),
+            Self::Setpoint => f.write_str("setpoint"),
+            Self::SetpointReference => f.write_str("setpoint-reference"),
+            Self::Setting => f.write_str("setting"),
+            Self::ShedDuration => f.write_str("shed-duration"),
+            Self::ShedLevelDescriptions => f.write_str("shed-level-descriptions"),
+            Self::ShedLevels => f.write_str("shed-levels"),
+            Self::Silenced => f.write_str("silenced"),
+            Self::SlaveAddressBinding => f.write_str("slave-address-binding"),
+            Self::SlaveProxyEnable => f.write_str("slave-proxy-enable"),
+            Self::StageNames => f.write_str("stage-names"),
+            Self::Stages => f.write_str("stages"),
             Self::StartTime => f.write_str("start-time"),
+            Self::StateChangeValues => f.write_str("state-change-values"),
+            Self::StateDescription => f.write_str("state-description"),
+            Self::StateText => f.write_str("state-text"),
             Self::StatusFlags => f.write_str("status-flags"),
             Self::StopTime => f.write_str("stop-time"),
+            Self::StopWhenFull => f.write_str("stop-when-full"),
+            Self::StrikeCount => f.write_str("strike-count"),
+            Self::StructuredObjectList => f.write_str("structured-object-list"),
+            Self::SubordinateAnnotations => f.write_str("subordinate-annotations"),
+            Self::SubordinateList => f.write_str("subordinate-list"),
+            Self::SubordinateNodeTypes => f.write_str("subordinate-node-types"),
+            Self::SubordinateRelationships => f.write_str("subordinate-relationships"),
+            Self::SubordinateTags => f.write_str("subordinate-tags"),
+            Self::SubscribedRecipients => f.write_str("subscribed-recipients"),
+            Self::SupportedFormatClasses => f.write_str("supported-format-classes"),
+            Self::SupportedFormats => f.write_str("supported-formats"),
+            Self::SupportedSecurityAlgorithms => f.write_str("supported-security-algorithms"),
             Self::SystemStatus => f.write_str("system-status"),
+            Self::Tags => f.write_str("tags"),
+            Self::TargetReferences => f.write_str("target-references"),
+            Self::ThreatAuthority => f.write_str("threat-authority"),
+            Self::ThreatLevel => f.write_str("threat-level"),
             Self::TimeDelay => f.write_str("time-delay"),
+            Self::TimeDelayNormal => f.write_str("time-delay-normal"),
+            Self::TimeOfActiveTimeReset => f.write_str("time-of-active-time-reset"),
+            Self::TimeOfDeviceRestart => f.write_str("time-of-device-restart"),
+            Self::TimeOfStateCountReset => f.write_str("time-of-state-count-reset"),
+            Self::TimeOfStrikeCountReset => f.write_str("time-of-strike-count-reset"),
+            Self::TimeSynchronizationInterval => f.write_str("time-synchronization-interval"),
+            Self::TimeSynchronizationRecipients => f.write_str("time-synchronization-recipients"),
+            Self::TimerRunning => f.write_str("timer-running"),
+            Self::TimerState => f.write_str("timer-state"),
             Self::TotalRecordCount => f.write_str("total-record-count"),
+            Self::TraceFlag => f.write_str("trace-flag"),
+            Self::TrackingValue => f.write_str("tracking-value"),
+            Self::TransactionNotificationClass => f.write_str("transaction-notification-class"),
+            Self::Transition => f.write_str("transition"),
+            Self::Trigger => f.write_str("trigger"),
+            Self::TrimFadeTime => f.write_str("trim-fade-time"),
             Self::Units => f.write_str("units"),
             Self::UpdateInterval => f.write_str("update-interval"),
+            Self::UpdateKeySetTimeout => f.write_str("update-key-set-timeout"),
+            Self::UpdateTime => f.write_str("update-time"),
+            Self::UserExternalIdentifier => f.write_str("user-external-identifier"),
+            Self::UserInformationReference => f.write_str("user-information-reference"),
+            Self::UserName => f.write_str("user-name"),
+            Self::UserType => f.write_str("user-type"),
+            Self::UsesRemaining => f.write_str("uses-remaining"),
+            Self::UtcOffset => f.write_str("utc-offset"),
+            Self::UtcTimeSynchronizationRecipients => {
+                f.write_str("utc-time-synchronization-recipients")
+            }
+            Self::ValidSamples => f.write_str("valid-samples"),
+            Self::ValueBeforeChange => f.write_str("value-before-change"),
+            Self::ValueChangeTime => f.write_str("value-change-time"),
+            Self::ValueSet => f.write_str("value-set"),
+            Self::ValueSource => f.write_str("value-source"),
+            Self::ValueSourceArray => f.write_str("value-source-array"),
+            Self::VarianceValue => f.write_str("variance-value"),
+            Self::VendorIdentifier => f.write_str("vendor-identifier"),
             Self::VendorName => f.write_str("vendor-name"),
+            Self::VerificationTime => f.write_str("verification-time"),
+            Self::VirtualMacAddressTable => f.write_str("virtual-mac-address-table"),
+            Self::VtClassesSupported => f.write_str("vt-classes-supported"),
             Self::WeeklySchedule => f.write_str("weekly-schedule"),
+            Self::WindowInterval => f.write_str("window-interval"),
+            Self::WindowSamples => f.write_str("window-samples"),
+            Self::WriteStatus => f.write_str("write-status"),
+            Self::ZoneFrom => f.write_str("zone-from"),
+            Self::ZoneMembers => f.write_str("zone-members"),
+            Self::ZoneTo => f.write_str("zone-to"),
             Self::Proprietary(v) => write!(f, "proprietary-{v}"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PropertyId;
+
+    #[test]
+    fn standard_identifiers_round_trip_through_numbers_and_names() {
+        let mut named = 0;
+        for value in (0..=600).chain(4_194_300..=4_194_340) {
+            let id = PropertyId::from_u32(value);
+            assert_eq!(id.to_u32(), value);
+            if let PropertyId::Proprietary(_) = id {
+                continue;
+            }
+            named += 1;
+            #[cfg(feature = "alloc")]
+            {
+                use alloc::string::ToString;
+                assert_eq!(PropertyId::from_name(&id.to_string()), Some(id));
+            }
+        }
+        assert_eq!(named, 509);
+        assert_eq!(PropertyId::from_u32(512), PropertyId::Proprietary(512));
+        assert_eq!(
+            PropertyId::from_u32(4_194_304),
+            PropertyId::MaxBvlcLengthAccepted
+        );
+        assert_eq!(
+            PropertyId::from_name("network-number-quality"),
+            Some(PropertyId::NetworkNumberQuality)
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use crate::encoding::{
+    primitives::{decode_unsigned, encode_ctx_object_id, encode_ctx_unsigned},
+    reader::Reader,
+    tag::Tag,
+    writer::Writer,
+};
+use crate::services::take_optional_ctx_tag;
+use crate::types::{ObjectId, PropertyId};
+use crate::{DecodeError, EncodeError};
+
+/// BACnetPropertyReference: a property, and optionally one array element, of an object
+/// given elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropertyReference {
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+}
+
+impl PropertyReference {
+    pub const fn new(property_id: PropertyId) -> Self {
+        Self {
+            property_id,
+            array_index: None,
+        }
+    }
+
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_ctx_unsigned(w, 0, self.property_id.to_u32())?;
+        if let Some(array_index) = self.array_index {
+            encode_ctx_unsigned(w, 1, array_index)?;
+        }
+        Ok(())
+    }
+
+    /// Decodes one reference, stopping before the tag that follows it.
+    pub fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            property_id: PropertyId::from_u32(decode_ctx(r, 0)?),
+            array_index: decode_optional_ctx(r, 1)?,
+        })
+    }
+}
+
+/// BACnetObjectPropertyReference: a property of an object in the same device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectPropertyReference {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+}
+
+impl ObjectPropertyReference {
+    pub const fn new(object_id: ObjectId, property_id: PropertyId) -> Self {
+        Self {
+            object_id,
+            property_id,
+            array_index: None,
+        }
+    }
+
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_ctx_object_id(w, 0, self.object_id.raw())?;
+        encode_ctx_unsigned(w, 1, self.property_id.to_u32())?;
+        if let Some(array_index) = self.array_index {
+            encode_ctx_unsigned(w, 2, array_index)?;
+        }
+        Ok(())
+    }
+
+    /// Decodes one reference, stopping before the tag that follows it.
+    pub fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            object_id: ObjectId::from_raw(decode_ctx(r, 0)?),
+            property_id: PropertyId::from_u32(decode_ctx(r, 1)?),
+            array_index: decode_optional_ctx(r, 2)?,
+        })
+    }
+}
+
+/// BACnetDeviceObjectPropertyReference: a property of an object, optionally in another
+/// device. Used by Channel and Schedule List_Of_Object_Property_References, Global Group
+/// Group_Members and Event Enrollment Object_Property_Reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceObjectPropertyReference {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    /// The device holding the object; `None` for an object in the referencing device.
+    pub device_id: Option<ObjectId>,
+}
+
+impl DeviceObjectPropertyReference {
+    /// A reference to `property_id` of `object_id` in the referencing device.
+    pub const fn local(object_id: ObjectId, property_id: PropertyId) -> Self {
+        Self {
+            object_id,
+            property_id,
+            array_index: None,
+            device_id: None,
+        }
+    }
+
+    /// A reference to `property_id` of `object_id` in the device `device_id`.
+    pub const fn remote(device_id: ObjectId, object_id: ObjectId, property_id: PropertyId) -> Self {
+        Self {
+            object_id,
+            property_id,
+            array_index: None,
+            device_id: Some(device_id),
+        }
+    }
+
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        encode_ctx_object_id(w, 0, self.object_id.raw())?;
+        encode_ctx_unsigned(w, 1, self.property_id.to_u32())?;
+        if let Some(array_index) = self.array_index {
+            encode_ctx_unsigned(w, 2, array_index)?;
+        }
+        if let Some(device_id) = self.device_id {
+            encode_ctx_object_id(w, 3, device_id.raw())?;
+        }
+        Ok(())
+    }
+
+    /// Decodes one reference, stopping before the tag that follows it.
+    pub fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            object_id: ObjectId::from_raw(decode_ctx(r, 0)?),
+            property_id: PropertyId::from_u32(decode_ctx(r, 1)?),
+            array_index: decode_optional_ctx(r, 2)?,
+            device_id: decode_optional_ctx(r, 3)?.map(ObjectId::from_raw),
+        })
+    }
+}
+
+impl From<ObjectPropertyReference> for DeviceObjectPropertyReference {
+    fn from(reference: ObjectPropertyReference) -> Self {
+        Self {
+            object_id: reference.object_id,
+            property_id: reference.property_id,
+            array_index: reference.array_index,
+            device_id: None,
+        }
+    }
+}
+
+/// BACnetDeviceObjectReference: an object, optionally in another device. Used for the
+/// credential in access-event notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceObjectReference {
+    /// The device holding the object; `None` for an object in the referencing device.
+    pub device_id: Option<ObjectId>,
+    pub object_id: ObjectId,
+}
+
+impl DeviceObjectReference {
+    /// A reference to `object_id` in the referencing device.
+    pub const fn local(object_id: ObjectId) -> Self {
+        Self {
+            device_id: None,
+            object_id,
+        }
+    }
+
+    pub fn encode(&self, w: &mut Writer<'_>) -> Result<(), EncodeError> {
+        if let Some(device_id) = self.device_id {
+            encode_ctx_object_id(w, 0, device_id.raw())?;
+        }
+        encode_ctx_object_id(w, 1, self.object_id.raw())
+    }
+
+    /// Decodes one reference, stopping before the tag that follows it.
+    pub fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            device_id: decode_optional_ctx(r, 0)?.map(ObjectId::from_raw),
+            object_id: ObjectId::from_raw(decode_ctx(r, 1)?),
+        })
+    }
+}
+
+fn decode_ctx(r: &mut Reader<'_>, expected: u8) -> Result<u32, DecodeError> {
+    match Tag::decode(r)? {
+        Tag::Context { tag_num, len } if tag_num == expected => decode_unsigned(r, len as usize),
+        _ => Err(DecodeError::InvalidTag),
+    }
+}
+
+fn decode_optional_ctx(r: &mut Reader<'_>, expected: u8) -> Result<Option<u32>, DecodeError> {
+    take_optional_ctx_tag(r, expected)?
+        .map(|len| decode_unsigned(r, len as usize))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DeviceObjectPropertyReference, DeviceObjectReference, ObjectPropertyReference,
+        PropertyReference,
+    };
+    use crate::encoding::{reader::Reader, writer::Writer};
+    use crate::types::{ObjectId, ObjectType, PropertyId};
+
+    #[test]
+    fn references_encode_their_context_tags() {
+        let object_id = ObjectId::new(ObjectType::AnalogInput, 3);
+        let mut buf = [0u8; 32];
+
+        let mut w = Writer::new(&mut buf);
+        PropertyReference {
+            property_id: PropertyId::PriorityArray,
+            array_index: Some(8),
+        }
+        .encode(&mut w)
+        .unwrap();
+        assert_eq!(w.as_written(), &[0x09, 0x57, 0x19, 0x08]);
+
+        let mut w = Writer::new(&mut buf);
+        ObjectPropertyReference::new(object_id, PropertyId::PresentValue)
+            .encode(&mut w)
+            .unwrap();
+        assert_eq!(w.as_written(), &[0x0C, 0x00, 0x00, 0x00, 0x03, 0x19, 0x55]);
+
+        let mut w = Writer::new(&mut buf);
+        DeviceObjectPropertyReference::remote(
+            ObjectId::new(ObjectType::Device, 5),
+            object_id,
+            PropertyId::PresentValue,
+        )
+        .encode(&mut w)
+        .unwrap();
+        assert_eq!(
+            w.as_written(),
+            &[0x0C, 0x00, 0x00, 0x00, 0x03, 0x19, 0x55, 0x3C, 0x02, 0x00, 0x00, 0x05]
+        );
+    }
+
+    #[test]
+    fn references_round_trip_and_stop_at_the_next_tag() {
+        let property = PropertyReference::new(PropertyId::ObjectName);
+        let object = ObjectPropertyReference {
+            object_id: ObjectId::new(ObjectType::BinaryValue, 9),
+            property_id: PropertyId::PresentValue,
+            array_index: Some(1),
+        };
+        let device = DeviceObjectPropertyReference {
+            device_id: Some(ObjectId::new(ObjectType::Device, 1)),
+            ..object.into()
+        };
+        let credential = DeviceObjectReference {
+            device_id: Some(ObjectId::new(ObjectType::Device, 1)),
+            object_id: ObjectId::new(ObjectType::AccessCredential, 4),
+        };
+
+        let mut buf = [0u8; 64];
+        let mut w = Writer::new(&mut buf);
+        property.encode(&mut w).unwrap();
+        object.encode(&mut w).unwrap();
+        device.encode(&mut w).unwrap();
+        credential.encode(&mut w).unwrap();
+        DeviceObjectReference::local(credential.object_id)
+            .encode(&mut w)
+            .unwrap();
+        // A closing tag ends the sequence.
+        w.write_u8(0x3F).unwrap();
+
+        let mut r = Reader::new(w.as_written());
+        assert_eq!(PropertyReference::decode(&mut r).unwrap(), property);
+        assert_eq!(ObjectPropertyReference::decode(&mut r).unwrap(), object);
+        assert_eq!(
+            DeviceObjectPropertyReference::decode(&mut r).unwrap(),
+            device
+        );
+        assert_eq!(DeviceObjectReference::decode(&mut r).unwrap(), credential);
+        assert_eq!(
+            DeviceObjectReference::decode(&mut r).unwrap(),
+            DeviceObjectReference::local(credential.object_id)
+        );
+        assert_eq!(r.read_u8().unwrap(), 0x3F);
+    }
+}
@@ -1,5 +1,6 @@
 /// Segmentation capability advertised during device discovery.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum Segmentation {
     SegmentedBoth = 0,
@@ -45,6 +46,7 @@ pub enum ErrorCode {
     UnknownProperty = 32,
     WriteAccessDenied = 40,
     ValueOutOfRange = 37,
+    InvalidTag = 57,
 }
 
 impl Segmentation {
@@ -115,6 +117,7 @@ impl ErrorCode {
             32 => Some(Self::UnknownProperty),
             37 => Some(Self::ValueOutOfRange),
             40 => Some(Self::WriteAccessDenied),
+            57 => Some(Self::InvalidTag),
             _ => None,
         }
     }
@@ -0,0 +1,125 @@
+//! Every decoder must return `Err` (never panic) on arbitrary input.
+#![cfg(feature = "alloc")]
+
+use proptest::prelude::*;
+use rustbac_core::apdu::{
+    AbortPdu, BacnetError, ComplexAckHeader, ConfirmedRequestHeader, RejectPdu, SegmentAck,
+    SimpleAck, UnconfirmedRequestHeader,
+};
+use rustbac_core::encoding::{reader::Reader, tag::Tag};
+use rustbac_core::npdu::Npdu;
+use rustbac_core::services::{
+    alarm_summary::GetAlarmSummaryAck,
+    atomic_read_file::AtomicReadFileAck,
+    atomic_write_file::AtomicWriteFileAck,
+    cov_notification::CovNotificationRequest,
+    enrollment_summary::GetEnrollmentSummaryAck,
+    event_information::GetEventInformationAck,
+    event_notification::EventNotificationRequest,
+    i_am::IAmRequest,
+    object_management::CreateObjectAck,
+    private_transfer::ConfirmedPrivateTransferAck,
+    read_property::ReadPropertyAck,
+    read_property_multiple::ReadPropertyMultipleAck,
+    read_range::ReadRangeAck,
+    time_synchronization::TimeSynchronizationRequest,
+    value_codec::{decode_application_data_value, ValueEvents},
+    who_has::IHaveRequest,
+};
+use std::fs;
+use std::path::Path;
+
+fn decode_all(data: &[u8]) {
+    let _ = Npdu::decode(&mut Reader::new(data));
+    let _ = ConfirmedRequestHeader::decode(&mut Reader::new(data));
+    let _ = UnconfirmedRequestHeader::decode(&mut Reader::new(data));
+    let _ = ComplexAckHeader::decode(&mut Reader::new(data));
+    let _ = SimpleAck::decode(&mut Reader::new(data));
+    let _ = BacnetError::decode(&mut Reader::new(data));
+    let _ = RejectPdu::decode(&mut Reader::new(data));
+    let _ = AbortPdu::decode(&mut Reader::new(data));
+    let _ = SegmentAck::decode(&mut Reader::new(data));
+
+    let mut r = Reader::new(data);
+    while Tag::decode(&mut r).is_ok() {}
+    let _ = decode_application_data_value(&mut Reader::new(data));
+    ValueEvents::new(&mut Reader::new(data)).for_each(drop);
+
+    let _ = ReadPropertyAck::decode_after_header(&mut Reader::new(data));
+    let _ = ReadPropertyMultipleAck::decode_after_header(&mut Reader::new(data));
+    let _ = ReadRangeAck::decode_after_header(&mut Reader::new(data));
+    let _ = CovNotificationRequest::decode_after_header(&mut Reader::new(data));
+    let _ = EventNotificationRequest::decode_after_header(&mut Reader::new(data));
+    let _ = IAmRequest::decode_after_header(&mut Reader::new(data));
+    let _ = IHaveRequest::decode_after_header(&mut Reader::new(data));
+    let _ = GetAlarmSummaryAck::decode_after_header(&mut Reader::new(data));
+    let _ = GetEnrollmentSummaryAck::decode_after_header(&mut Reader::new(data));
+    let _ = GetEventInformationAck::decode_after_header(&mut Reader::new(data));
+    let _ = AtomicReadFileAck::decode_after_header(&mut Reader::new(data));
+    let _ = AtomicWriteFileAck::decode_after_header(&mut Reader::new(data));
+    let _ = CreateObjectAck::decode_after_header(&mut Reader::new(data));
+    let _ = TimeSynchronizationRequest::decode_after_header(&mut Reader::new(data));
+    let _ = ConfirmedPrivateTransferAck::decode(&mut Reader::new(data));
+}
+
+fn golden_fixtures() -> Vec<Vec<u8>> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../fixtures/golden");
+    let mut out = Vec::new();
+    for entry in fs::read_dir(dir).expect("fixtures directory should exist") {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "hex") {
+            let content = fs::read_to_string(&path).unwrap();
+            let bytes = content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .flat_map(|line| line.split_whitespace())
+                .map(|token| u8::from_str_radix(token, 16).unwrap())
+                .collect();
+            out.push(bytes);
+        }
+    }
+    out
+}
+
+#[test]
+fn truncated_golden_frames_do_not_panic() {
+    for frame in golden_fixtures() {
+        for end in 0..=frame.len() {
+            for start in 0..end {
+                decode_all(&frame[start..end]);
+            }
+        }
+    }
+}
+
+#[test]
+fn deeply_nested_constructed_value_is_rejected() {
+    // 10k opening tags would recurse once per level without a depth limit.
+    let data = vec![0x0Eu8; 10_000];
+    assert!(decode_application_data_value(&mut Reader::new(&data)).is_err());
+    decode_all(&data);
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn arbitrary_bytes_do_not_panic(data in proptest::collection::vec(any::<u8>(), 0..256)) {
+        decode_all(&data);
+    }
+
+    #[test]
+    fn tag_heavy_bytes_do_not_panic(
+        data in proptest::collection::vec(
+            prop_oneof![
+                Just(0x0Eu8), Just(0x0F), Just(0x1E), Just(0x1F), Just(0x3E), Just(0x3F),
+                Just(0x09), Just(0x19), Just(0x29), Just(0xC4), Just(0x75), Just(0x44),
+                any::<u8>(),
+            ],
+            0..256,
+        )
+    ) {
+        decode_all(&data);
+    }
+}
@@ -1,5 +1,6 @@
 use rustbac_core::encoding::{reader::Reader, writer::Writer};
 use rustbac_core::{DecodeError, EncodeError};
+use std::fmt;
 
 pub const BVLC_TYPE_BIP: u8 = 0x81;
 
@@ -58,6 +59,102 @@ impl BvlcFunction {
     }
 }
 
+/// Result code carried by a BVLC-Result message (Annex J.2.1.1).
+///
+/// A BBMD answers each table or registration command with one of these; every code other
+/// than [`Success`](Self::Success) is a NAK naming the rejected command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BvlcResultCode {
+    Success,
+    WriteBroadcastDistributionTableNak,
+    ReadBroadcastDistributionTableNak,
+    RegisterForeignDeviceNak,
+    ReadForeignDeviceTableNak,
+    DeleteForeignDeviceTableEntryNak,
+    DistributeBroadcastToNetworkNak,
+    Unknown(u16),
+}
+
+impl BvlcResultCode {
+    pub const fn from_u16(value: u16) -> Self {
+        match value {
+            0x0000 => Self::Success,
+            0x0010 => Self::WriteBroadcastDistributionTableNak,
+            0x0020 => Self::ReadBroadcastDistributionTableNak,
+            0x0030 => Self::RegisterForeignDeviceNak,
+            0x0040 => Self::ReadForeignDeviceTableNak,
+            0x0050 => Self::DeleteForeignDeviceTableEntryNak,
+            0x0060 => Self::DistributeBroadcastToNetworkNak,
+            v => Self::Unknown(v),
+        }
+    }
+
+    pub const fn to_u16(self) -> u16 {
+        match self {
+            Self::Success => 0x0000,
+            Self::WriteBroadcastDistributionTableNak => 0x0010,
+            Self::ReadBroadcastDistributionTableNak => 0x0020,
+            Self::RegisterForeignDeviceNak => 0x0030,
+            Self::ReadForeignDeviceTableNak => 0x0040,
+            Self::DeleteForeignDeviceTableEntryNak => 0x0050,
+            Self::DistributeBroadcastToNetworkNak => 0x0060,
+            Self::Unknown(v) => v,
+        }
+    }
+
+    /// Standard name of the code, e.g. `"Register-Foreign-Device-NAK"`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Success => "Successful-Completion",
+            Self::WriteBroadcastDistributionTableNak => "Write-Broadcast-Distribution-Table-NAK",
+            Self::ReadBroadcastDistributionTableNak => "Read-Broadcast-Distribution-Table-NAK",
+            Self::RegisterForeignDeviceNak => "Register-Foreign-Device-NAK",
+            Self::ReadForeignDeviceTableNak => "Read-Foreign-Device-Table-NAK",
+            Self::DeleteForeignDeviceTableEntryNak => "Delete-Foreign-Device-Table-Entry-NAK",
+            Self::DistributeBroadcastToNetworkNak => "Distribute-Broadcast-To-Network-NAK",
+            Self::Unknown(_) => "unknown BVLC result",
+        }
+    }
+
+    /// Short explanation of why a BBMD typically returns this code.
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Success => "the command completed",
+            Self::WriteBroadcastDistributionTableNak => {
+                "the BBMD refused to replace its broadcast distribution table"
+            }
+            Self::ReadBroadcastDistributionTableNak => {
+                "the device is not a BBMD or has no broadcast distribution table"
+            }
+            Self::RegisterForeignDeviceNak => {
+                "the BBMD does not accept foreign devices or its foreign device table is full"
+            }
+            Self::ReadForeignDeviceTableNak => {
+                "the device is not a BBMD or does not support foreign device registration"
+            }
+            Self::DeleteForeignDeviceTableEntryNak => {
+                "no matching entry exists in the foreign device table"
+            }
+            Self::DistributeBroadcastToNetworkNak => {
+                "the BBMD does not hold a registration for this foreign device"
+            }
+            Self::Unknown(_) => "the code is not defined by Annex J",
+        }
+    }
+}
+
+impl fmt::Display for BvlcResultCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (0x{:04x}): {}",
+            self.name(),
+            self.to_u16(),
+            self.description()
+        )
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct BvlcHeader {
     pub function: BvlcFunction,
@@ -86,7 +183,7 @@ impl BvlcHeader {
 
 #[cfg(test)]
 mod tests {
-    use super::{BvlcFunction, BvlcHeader, BVLC_TYPE_BIP};
+    use super::{BvlcFunction, BvlcHeader, BvlcResultCode, BVLC_TYPE_BIP};
     use rustbac_core::encoding::{reader::Reader, writer::Writer};
 
     #[test]
@@ -137,4 +234,23 @@ mod tests {
         let decoded = BvlcHeader::decode(&mut r).unwrap();
         assert_eq!(decoded.function, BvlcFunction::Unknown(0x99));
     }
+
+    #[test]
+    fn result_codes_roundtrip_and_describe() {
+        for raw in [
+            0x0000, 0x0010, 0x0020, 0x0030, 0x0040, 0x0050, 0x0060, 0x0099,
+        ] {
+            assert_eq!(BvlcResultCode::from_u16(raw).to_u16(), raw);
+        }
+        assert_eq!(
+            BvlcResultCode::from_u16(0x0030),
+            BvlcResultCode::RegisterForeignDeviceNak
+        );
+        let text = BvlcResultCode::RegisterForeignDeviceNak.to_string();
+        assert!(text.starts_with("Register-Foreign-Device-NAK (0x0030): "));
+        assert_eq!(
+            BvlcResultCode::from_u16(0x0099).to_string(),
+            "unknown BVLC result (0x0099): the code is not defined by Annex J"
+        );
+    }
 }
@@ -0,0 +1,92 @@
+use rustbac_core::npdu::NetworkPriority;
+
+/// Maps the network priority of an outgoing NPDU to the IP DSCP it is sent with.
+///
+/// The default follows RFC 4594 classes: Life Safety → EF (46), Critical Equipment →
+/// AF41 (34), Urgent → AF31 (26) and Normal → best effort (0). Code points are 6 bits;
+/// higher bits are ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DscpMap {
+    pub normal: u8,
+    pub urgent: u8,
+    pub critical_equipment: u8,
+    pub life_safety: u8,
+}
+
+impl DscpMap {
+    /// Expedited Forwarding.
+    pub const EF: u8 = 46;
+    /// Assured Forwarding class 4, low drop.
+    pub const AF41: u8 = 34;
+    /// Assured Forwarding class 3, low drop.
+    pub const AF31: u8 = 26;
+    /// Best effort.
+    pub const DEFAULT: u8 = 0;
+
+    /// Sends every priority with the same code point.
+    pub const fn uniform(dscp: u8) -> Self {
+        Self {
+            normal: dscp,
+            urgent: dscp,
+            critical_equipment: dscp,
+            life_safety: dscp,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: NetworkPriority, dscp: u8) -> Self {
+        match priority {
+            NetworkPriority::Normal => self.normal = dscp,
+            NetworkPriority::Urgent => self.urgent = dscp,
+            NetworkPriority::CriticalEquipment => self.critical_equipment = dscp,
+            NetworkPriority::LifeSafety => self.life_safety = dscp,
+        }
+        self
+    }
+
+    /// The code point for `priority`.
+    pub fn dscp(&self, priority: NetworkPriority) -> u8 {
+        let dscp = match priority {
+            NetworkPriority::Normal => self.normal,
+            NetworkPriority::Urgent => self.urgent,
+            NetworkPriority::CriticalEquipment => self.critical_equipment,
+            NetworkPriority::LifeSafety => self.life_safety,
+        };
+        dscp & 0x3F
+    }
+
+    /// The code point for an encoded NPDU, read from its control octet; frames too short
+    /// to carry one are sent as Normal.
+    pub fn dscp_for_npdu(&self, npdu: &[u8]) -> u8 {
+        let priority = npdu.get(1).map_or(NetworkPriority::Normal, |&control| {
+            NetworkPriority::from_control(control)
+        });
+        self.dscp(priority)
+    }
+}
+
+impl Default for DscpMap {
+    fn default() -> Self {
+        Self {
+            normal: Self::DEFAULT,
+            urgent: Self::AF31,
+            critical_equipment: Self::AF41,
+            life_safety: Self::EF,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DscpMap;
+    use rustbac_core::npdu::NetworkPriority;
+
+    #[test]
+    fn npdu_priority_selects_code_point() {
+        let map = DscpMap::default().with_priority(NetworkPriority::Urgent, 0xFF);
+        assert_eq!(map.dscp_for_npdu(&[0x01, 0x07]), DscpMap::EF);
+        assert_eq!(map.dscp_for_npdu(&[0x01, 0x06]), DscpMap::AF41);
+        assert_eq!(map.dscp_for_npdu(&[0x01, 0x01]), 0x3F);
+        assert_eq!(map.dscp_for_npdu(&[0x01, 0x04]), DscpMap::DEFAULT);
+        assert_eq!(map.dscp_for_npdu(&[]), DscpMap::DEFAULT);
+    }
+}
@@ -1,2 +1,4 @@
 pub mod bvlc;
+pub mod dscp;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod transport;
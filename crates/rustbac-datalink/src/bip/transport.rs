@@ -1,8 +1,10 @@
-use crate::bip::bvlc::{BvlcFunction, BvlcHeader};
+use crate::bip::bvlc::{BvlcFunction, BvlcHeader, BvlcResultCode};
+use crate::bip::dscp::DscpMap;
 use crate::{DataLink, DataLinkAddress, DataLinkError};
 use rustbac_core::encoding::{reader::Reader, writer::Writer};
 use std::io;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::net::UdpSocket;
 use tokio::sync::Mutex;
@@ -23,11 +25,22 @@ pub struct ForeignDeviceTableEntry {
     pub remaining_seconds: u16,
 }
 
+/// BACnet/IP (Annex J) datalink over UDP.
+///
+/// In foreign-device mode the transport knows one or more BBMDs in priority order. One of
+/// them is active at a time: it receives registrations, BBMD table commands and
+/// Distribute-Broadcast-To-Network. [`register_foreign_device`](Self::register_foreign_device)
+/// fails over to the next BBMD when the active one does not acknowledge.
 #[derive(Debug, Clone)]
 pub struct BacnetIpTransport {
     socket: Arc<UdpSocket>,
-    bbmd: Option<SocketAddr>,
+    bbmds: Arc<[SocketAddr]>,
+    active_bbmd: Arc<AtomicUsize>,
     bbmd_command_lock: Arc<Mutex<()>>,
+    dscp: Option<DscpMap>,
+    /// DSCP the socket is currently set to; held across a send so concurrent sends with
+    /// different priorities do not interleave.
+    socket_dscp: Arc<Mutex<Option<u8>>>,
 }
 
 impl BacnetIpTransport {
@@ -36,8 +49,11 @@ impl BacnetIpTransport {
         socket.set_broadcast(true)?;
         Ok(Self {
             socket: Arc::new(socket),
-            bbmd: None,
+            bbmds: Arc::from([]),
+            active_bbmd: Arc::new(AtomicUsize::new(0)),
             bbmd_command_lock: Arc::new(Mutex::new(())),
+            dscp: None,
+            socket_dscp: Arc::new(Mutex::new(None)),
         })
     }
 
@@ -45,25 +61,74 @@ impl BacnetIpTransport {
         bind_addr: SocketAddr,
         bbmd_addr: SocketAddr,
     ) -> Result<Self, DataLinkError> {
+        Self::bind_foreign_redundant(bind_addr, &[bbmd_addr]).await
+    }
+
+    /// Binds a foreign-device transport with several BBMDs in priority order.
+    ///
+    /// The first BBMD starts out active; registration fails over to the others. Returns
+    /// [`DataLinkError::BbmdNotConfigured`] if `bbmd_addrs` is empty.
+    pub async fn bind_foreign_redundant(
+        bind_addr: SocketAddr,
+        bbmd_addrs: &[SocketAddr],
+    ) -> Result<Self, DataLinkError> {
+        if bbmd_addrs.is_empty() {
+            return Err(DataLinkError::BbmdNotConfigured);
+        }
         let socket = UdpSocket::bind(bind_addr).await?;
         socket.set_broadcast(true)?;
         Ok(Self {
             socket: Arc::new(socket),
-            bbmd: Some(bbmd_addr),
+            bbmds: Arc::from(bbmd_addrs),
+            active_bbmd: Arc::new(AtomicUsize::new(0)),
             bbmd_command_lock: Arc::new(Mutex::new(())),
+            dscp: None,
+            socket_dscp: Arc::new(Mutex::new(None)),
         })
     }
 
+    /// Sends each NPDU with the DSCP `map` gives its network priority, so converged IP
+    /// networks can queue life-safety traffic ahead of routine polling.
+    ///
+    /// Applies to NPDUs sent through [`DataLink::send`] by this handle; BVLC commands to a
+    /// BBMD keep whatever DSCP the socket was last set to. Sends fail with an I/O error on
+    /// platforms where the socket option cannot be set.
+    pub fn with_dscp_map(mut self, map: DscpMap) -> Self {
+        self.dscp = Some(map);
+        self
+    }
+
+    /// The DSCP mapping set with [`with_dscp_map`](Self::with_dscp_map).
+    pub fn dscp_map(&self) -> Option<DscpMap> {
+        self.dscp
+    }
+
+    fn set_socket_dscp(&self, dscp: u8) -> io::Result<()> {
+        let traffic_class = u32::from(dscp) << 2;
+        match self.socket.local_addr()? {
+            SocketAddr::V4(_) => set_tos_v4(&self.socket, traffic_class),
+            SocketAddr::V6(_) => set_tclass_v6(&self.socket, traffic_class),
+        }
+    }
+
     pub fn local_addr(&self) -> Result<SocketAddr, DataLinkError> {
         self.socket.local_addr().map_err(DataLinkError::Io)
     }
 
+    /// The active BBMD, if the transport is in foreign-device mode.
     pub fn bbmd_addr(&self) -> Option<SocketAddr> {
-        self.bbmd
+        self.bbmds
+            .get(self.active_bbmd.load(Ordering::Relaxed))
+            .copied()
+    }
+
+    /// All configured BBMDs in priority order.
+    pub fn bbmd_addrs(&self) -> &[SocketAddr] {
+        &self.bbmds
     }
 
     fn require_bbmd(&self) -> Result<SocketAddr, DataLinkError> {
-        self.bbmd.ok_or(DataLinkError::BbmdNotConfigured)
+        self.bbmd_addr().ok_or(DataLinkError::BbmdNotConfigured)
     }
 
     fn parse_bvlc_result(payload: &[u8]) -> Result<(), DataLinkError> {
@@ -74,7 +139,7 @@ impl BacnetIpTransport {
         if code == 0 {
             Ok(())
         } else {
-            Err(DataLinkError::BvlcResult(code))
+            Err(DataLinkError::BvlcResult(BvlcResultCode::from_u16(code)))
         }
     }
 
@@ -83,7 +148,16 @@ impl BacnetIpTransport {
         function: BvlcFunction,
         payload: &[u8],
     ) -> Result<(), DataLinkError> {
-        let bbmd = self.bbmd.ok_or(DataLinkError::BbmdNotConfigured)?;
+        let bbmd = self.require_bbmd()?;
+        self.send_bvlc_to(bbmd, function, payload).await
+    }
+
+    async fn send_bvlc_to(
+        &self,
+        bbmd: SocketAddr,
+        function: BvlcFunction,
+        payload: &[u8],
+    ) -> Result<(), DataLinkError> {
         let total_len = 4usize
             .checked_add(payload.len())
             .ok_or(DataLinkError::FrameTooLarge)?;
@@ -112,6 +186,16 @@ impl BacnetIpTransport {
         timeout_duration: Duration,
     ) -> Result<Vec<u8>, DataLinkError> {
         let bbmd = self.require_bbmd()?;
+        self.recv_bvlc_reply_from(bbmd, expected, timeout_duration)
+            .await
+    }
+
+    async fn recv_bvlc_reply_from(
+        &self,
+        bbmd: SocketAddr,
+        expected: BvlcFunction,
+        timeout_duration: Duration,
+    ) -> Result<Vec<u8>, DataLinkError> {
         let deadline = Instant::now() + timeout_duration;
         let mut rx = [0u8; 1600];
         loop {
@@ -161,13 +245,46 @@ impl BacnetIpTransport {
             .await
     }
 
+    /// Registers as a foreign device and waits for the BVLC-Result.
+    ///
+    /// The active BBMD is tried first, then the remaining BBMDs in priority order; the
+    /// first one to acknowledge becomes active, so broadcasts follow the registration. If
+    /// every BBMD fails, the active BBMD's error is returned.
     pub async fn register_foreign_device(&self, ttl_seconds: u16) -> Result<(), DataLinkError> {
         let _guard = self.bbmd_command_lock.lock().await;
+        let active = self.active_bbmd.load(Ordering::Relaxed);
+        let count = self.bbmds.len();
+        if count == 0 {
+            return Err(DataLinkError::BbmdNotConfigured);
+        }
+        let mut first_err = None;
+        for index in (0..count).map(|offset| (active + offset) % count) {
+            match self.register_with(self.bbmds[index], ttl_seconds).await {
+                Ok(()) => {
+                    if index != active {
+                        log::warn!(
+                            "foreign device registration failed over from {} to {}",
+                            self.bbmds[active],
+                            self.bbmds[index]
+                        );
+                        self.active_bbmd.store(index, Ordering::Relaxed);
+                    }
+                    return Ok(());
+                }
+                Err(err) => {
+                    first_err.get_or_insert(err);
+                }
+            }
+        }
+        Err(first_err.unwrap_or(DataLinkError::BbmdNotConfigured))
+    }
+
+    async fn register_with(&self, bbmd: SocketAddr, ttl_seconds: u16) -> Result<(), DataLinkError> {
         let payload = ttl_seconds.to_be_bytes();
-        self.send_bvlc_to_bbmd(BvlcFunction::RegisterForeignDevice, &payload)
+        self.send_bvlc_to(bbmd, BvlcFunction::RegisterForeignDevice, &payload)
             .await?;
         let payload = self
-            .recv_bvlc_reply(BvlcFunction::Result, Duration::from_secs(2))
+            .recv_bvlc_reply_from(bbmd, BvlcFunction::Result, Duration::from_secs(2))
             .await?;
         Self::parse_bvlc_result(&payload)
     }
@@ -268,13 +385,69 @@ impl BacnetIpTransport {
     }
 }
 
+#[cfg(not(any(
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "solaris",
+    target_os = "illumos",
+    target_os = "haiku"
+)))]
+fn set_tos_v4(socket: &UdpSocket, tos: u32) -> io::Result<()> {
+    socket.set_tos_v4(tos)
+}
+
+#[cfg(any(
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "solaris",
+    target_os = "illumos",
+    target_os = "haiku"
+))]
+fn set_tos_v4(_socket: &UdpSocket, _tos: u32) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "setting the DSCP is not supported on this platform",
+    ))
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "fuchsia",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+fn set_tclass_v6(socket: &UdpSocket, tclass: u32) -> io::Result<()> {
+    socket.set_tclass_v6(tclass)
+}
+
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "fuchsia",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+fn set_tclass_v6(_socket: &UdpSocket, _tclass: u32) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "setting the DSCP is not supported on this platform",
+    ))
+}
+
 impl DataLink for BacnetIpTransport {
     async fn send(&self, address: DataLinkAddress, payload: &[u8]) -> Result<(), DataLinkError> {
         let addr = address.as_socket_addr();
         let is_broadcast = matches!(addr.ip(), IpAddr::V4(v4) if v4.is_broadcast());
 
         let (function, target_addr) = if is_broadcast {
-            if let Some(bbmd) = self.bbmd {
+            if let Some(bbmd) = self.bbmd_addr() {
                 (BvlcFunction::DistributeBroadcastToNetwork, bbmd)
             } else {
                 (BvlcFunction::OriginalBroadcastNpdu, addr)
@@ -301,6 +474,16 @@ impl DataLink for BacnetIpTransport {
         w.write_all(payload)
             .map_err(|_| DataLinkError::FrameTooLarge)?;
 
+        let Some(map) = self.dscp else {
+            self.socket.send_to(w.as_written(), target_addr).await?;
+            return Ok(());
+        };
+        let dscp = map.dscp_for_npdu(payload);
+        let mut socket_dscp = self.socket_dscp.lock().await;
+        if *socket_dscp != Some(dscp) {
+            self.set_socket_dscp(dscp)?;
+            *socket_dscp = Some(dscp);
+        }
         self.socket.send_to(w.as_written(), target_addr).await?;
         Ok(())
     }
@@ -428,6 +611,53 @@ mod tests {
         responder.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn register_foreign_device_fails_over_to_secondary_bbmd() {
+        let primary = UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0))
+            .await
+            .unwrap();
+        let secondary = UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0))
+            .await
+            .unwrap();
+        let primary_addr = primary.local_addr().unwrap();
+        let secondary_addr = secondary.local_addr().unwrap();
+        let transport = BacnetIpTransport::bind_foreign_redundant(
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+            &[primary_addr, secondary_addr],
+        )
+        .await
+        .unwrap();
+        assert_eq!(transport.bbmd_addr(), Some(primary_addr));
+
+        // The primary stays silent; the secondary acknowledges.
+        let responder = tokio::spawn(async move {
+            let mut recv = [0u8; 64];
+            let (_, src) = secondary.recv_from(&mut recv).await.unwrap();
+            assert_eq!(recv[1], BvlcFunction::RegisterForeignDevice.to_u8());
+            let reply = [BVLC_TYPE_BIP, 0x00, 0x00, 0x06, 0x00, 0x00];
+            secondary.send_to(&reply, src).await.unwrap();
+            secondary
+        });
+        transport.register_foreign_device(60).await.unwrap();
+        let secondary = responder.await.unwrap();
+        assert_eq!(transport.bbmd_addr(), Some(secondary_addr));
+
+        // Broadcasts are now distributed through the secondary.
+        transport
+            .send(
+                DataLinkAddress::Ip(SocketAddr::new(IpAddr::V4(Ipv4Addr::BROADCAST), 47808)),
+                &[1, 2, 3],
+            )
+            .await
+            .unwrap();
+        let mut recv = [0u8; 64];
+        let (n, _) = timeout(Duration::from_secs(1), secondary.recv_from(&mut recv))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&recv[..n], &[BVLC_TYPE_BIP, 0x09, 0x00, 0x07, 1, 2, 3][..]);
+    }
+
     #[tokio::test]
     async fn register_foreign_device_no_wait_sends_ttl() {
         let bbmd = UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0))
@@ -727,4 +957,35 @@ mod tests {
         let err = transport.recv(&mut out).await.unwrap_err();
         assert!(matches!(err, DataLinkError::UnsupportedBvlcFunction(0x99)));
     }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn send_sets_dscp_from_npdu_priority() {
+        use crate::DscpMap;
+
+        let transport =
+            BacnetIpTransport::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0))
+                .await
+                .unwrap()
+                .with_dscp_map(DscpMap::default());
+        let receiver = UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0))
+            .await
+            .unwrap();
+        let target = DataLinkAddress::Ip(receiver.local_addr().unwrap());
+
+        transport.send(target, &[0x01, 0x07, 0xAA]).await.unwrap();
+        assert_eq!(
+            transport.socket.tos_v4().unwrap(),
+            u32::from(DscpMap::EF) << 2
+        );
+        transport.send(target, &[0x01, 0x04, 0xBB]).await.unwrap();
+        assert_eq!(transport.socket.tos_v4().unwrap(), 0);
+
+        let mut buf = [0u8; 16];
+        let (n, _) = receiver.recv_from(&mut buf).await.unwrap();
+        assert_eq!(
+            &buf[..n],
+            &[BVLC_TYPE_BIP, 0x0A, 0x00, 0x07, 0x01, 0x07, 0xAA]
+        );
+    }
 }
@@ -3,6 +3,9 @@
 //! Provides the [`DataLink`] trait for sending and receiving BACnet frames,
 //! along with a ready-to-use [`BacnetIpTransport`] (BACnet/IP over UDP)
 //! including BBMD foreign-device registration support.
+//!
+//! On `wasm32` the UDP transport is unavailable; the trait, addresses and wrappers still
+//! build so socket-free transports (such as BACnet/SC in the browser) can use them.
 
 #![allow(async_fn_in_trait)]
 
@@ -12,10 +15,16 @@ pub mod address;
 pub mod bip;
 /// PCAP packet capture via a [`DataLink`] wrapper.
 pub mod capture;
+/// Replaceable [`DataLink`] wrapper for hot-swapping transports.
+pub mod swap;
 /// The [`DataLink`] trait and associated error type.
 pub mod traits;
 
 pub use address::DataLinkAddress;
+pub use bip::bvlc::BvlcResultCode;
+pub use bip::dscp::DscpMap;
+#[cfg(not(target_arch = "wasm32"))]
 pub use bip::transport::{BacnetIpTransport, BroadcastDistributionEntry, ForeignDeviceTableEntry};
 pub use capture::CapturingDataLink;
+pub use swap::SwappableDataLink;
 pub use traits::{DataLink, DataLinkError};
@@ -0,0 +1,142 @@
+//! Replaceable [`DataLink`](crate::DataLink) wrapper.
+//!
+//! [`SwappableDataLink`] lets a long-lived client keep running across link changes (an
+//! interface going down, a re-bound socket, a fresh BACnet/SC session) by swapping the
+//! transport underneath it.
+
+use crate::{DataLink, DataLinkAddress, DataLinkError};
+use std::sync::{Arc, RwLock};
+use tokio::sync::watch;
+
+/// A [`DataLink`] wrapper whose inner transport can be replaced while in use.
+///
+/// [`replace`](Self::replace) makes every `recv` pending on the old link return
+/// [`DataLinkError::Disconnected`] immediately, so in-flight transactions fail fast
+/// instead of waiting out their timeout. Sends and receives started afterwards use the
+/// new link.
+///
+/// ```no_run
+/// # use rustbac_datalink::bip::transport::BacnetIpTransport;
+/// # use rustbac_datalink::SwappableDataLink;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let link = SwappableDataLink::new(BacnetIpTransport::bind("0.0.0.0:47808".parse()?).await?);
+/// // ... after the interface comes back:
+/// link.replace(BacnetIpTransport::bind("0.0.0.0:47808".parse()?).await?);
+/// # Ok(())
+/// # }
+/// ```
+pub struct SwappableDataLink<D: DataLink> {
+    inner: RwLock<Arc<D>>,
+    generation: watch::Sender<u64>,
+}
+
+impl<D: DataLink> SwappableDataLink<D> {
+    pub fn new(inner: D) -> Self {
+        Self {
+            inner: RwLock::new(Arc::new(inner)),
+            generation: watch::channel(0).0,
+        }
+    }
+
+    /// The link currently in use.
+    pub fn current(&self) -> Arc<D> {
+        self.inner
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Installs `inner` as the new link and fails receives pending on the old one.
+    ///
+    /// The old link is dropped once its last in-flight operation finishes.
+    pub fn replace(&self, inner: D) {
+        *self
+            .inner
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Arc::new(inner);
+        self.generation.send_modify(|generation| *generation += 1);
+    }
+
+    /// Number of times the link has been replaced.
+    pub fn generation(&self) -> u64 {
+        *self.generation.borrow()
+    }
+}
+
+impl<D: DataLink + std::fmt::Debug> std::fmt::Debug for SwappableDataLink<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwappableDataLink")
+            .field("inner", &self.current())
+            .field("generation", &self.generation())
+            .finish()
+    }
+}
+
+impl<D: DataLink> DataLink for SwappableDataLink<D> {
+    async fn send(&self, address: DataLinkAddress, payload: &[u8]) -> Result<(), DataLinkError> {
+        self.current().send(address, payload).await
+    }
+
+    async fn recv(&self, buf: &mut [u8]) -> Result<(usize, DataLinkAddress), DataLinkError> {
+        // Subscribe before reading the link so a replacement in between is not missed.
+        let mut replaced = self.generation.subscribe();
+        let link = self.current();
+        tokio::select! {
+            result = link.recv(buf) => result,
+            _ = replaced.changed() => Err(DataLinkError::Disconnected),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SwappableDataLink;
+    use crate::bip::transport::BacnetIpTransport;
+    use crate::{DataLink, DataLinkAddress, DataLinkError};
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::sync::Arc;
+    use tokio::net::UdpSocket;
+    use tokio::time::{timeout, Duration};
+
+    fn loopback() -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)
+    }
+
+    #[tokio::test]
+    async fn replace_fails_pending_recv_and_uses_new_link() {
+        let link = Arc::new(SwappableDataLink::new(
+            BacnetIpTransport::bind(loopback()).await.unwrap(),
+        ));
+        let pending = tokio::spawn({
+            let link = link.clone();
+            async move {
+                let mut buf = [0u8; 64];
+                link.recv(&mut buf).await.map(|(n, _)| n)
+            }
+        });
+        tokio::task::yield_now().await;
+
+        let fresh = BacnetIpTransport::bind(loopback()).await.unwrap();
+        let fresh_addr = fresh.local_addr().unwrap();
+        link.replace(fresh);
+        let result = timeout(Duration::from_secs(1), pending)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(result, Err(DataLinkError::Disconnected)));
+        assert_eq!(link.generation(), 1);
+
+        // Frames sent to the new socket are received through the wrapper.
+        let peer = UdpSocket::bind(loopback()).await.unwrap();
+        peer.send_to(&[0x81, 0x0A, 0x00, 0x06, 0x01, 0x02], fresh_addr)
+            .await
+            .unwrap();
+        let mut buf = [0u8; 64];
+        let (n, src) = timeout(Duration::from_secs(1), link.recv(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&buf[..n], &[0x01, 0x02]);
+        assert_eq!(src, DataLinkAddress::Ip(peer.local_addr().unwrap()));
+    }
+}
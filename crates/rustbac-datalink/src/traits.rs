@@ -1,3 +1,4 @@
+use crate::bip::bvlc::BvlcResultCode;
 use crate::DataLinkAddress;
 use thiserror::Error;
 
@@ -12,10 +13,66 @@ pub enum DataLinkError {
     InvalidFrame,
     #[error("unsupported BVLC function 0x{0:02x}")]
     UnsupportedBvlcFunction(u8),
-    #[error("BVLC result code 0x{0:04x}")]
-    BvlcResult(u16),
+    /// The BBMD answered a BVLC command with a NAK result code.
+    #[error("BBMD rejected the request: {0}")]
+    BvlcResult(BvlcResultCode),
     #[error("bbmd not configured")]
     BbmdNotConfigured,
+    /// A connection-oriented link (e.g. BACnet/SC) has lost its connection to the peer.
+    #[error("link disconnected")]
+    Disconnected,
+    /// The link was configured with an invalid endpoint, certificate, or similar setting.
+    #[error("invalid configuration: {0}")]
+    Config(String),
+    /// Establishing the link to `target` failed; `source` is the underlying cause.
+    #[error("failed to connect to {target}: {source}")]
+    Connect {
+        target: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+}
+
+impl DataLinkError {
+    /// Stable, machine-readable identifier for the error kind (e.g. `"datalink.io"`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Io(_) => "datalink.io",
+            Self::FrameTooLarge => "datalink.frame_too_large",
+            Self::InvalidFrame => "datalink.invalid_frame",
+            Self::UnsupportedBvlcFunction(_) => "datalink.unsupported_bvlc_function",
+            Self::BvlcResult(_) => "datalink.bvlc_result",
+            Self::BbmdNotConfigured => "datalink.bbmd_not_configured",
+            Self::Disconnected => "datalink.disconnected",
+            Self::Config(_) => "datalink.config",
+            Self::Connect { .. } => "datalink.connect",
+        }
+    }
+
+    /// Returns `true` when retrying the operation later may succeed (timeouts, resets,
+    /// lost or refused connections). Configuration and framing errors are not transient.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Io(err) => matches!(
+                err.kind(),
+                std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::WouldBlock
+                    | std::io::ErrorKind::Interrupted
+                    | std::io::ErrorKind::ConnectionRefused
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::UnexpectedEof
+            ),
+            Self::Disconnected | Self::Connect { .. } => true,
+            Self::FrameTooLarge
+            | Self::InvalidFrame
+            | Self::UnsupportedBvlcFunction(_)
+            | Self::BvlcResult(_)
+            | Self::BbmdNotConfigured
+            | Self::Config(_) => false,
+        }
+    }
 }
 
 /// Async trait for sending and receiving raw BACnet frames.
@@ -29,3 +86,14 @@ pub trait DataLink: Send + Sync {
     /// Receives a frame into `buf`, returning `(bytes_read, source_address)`.
     async fn recv(&self, buf: &mut [u8]) -> Result<(usize, DataLinkAddress), DataLinkError>;
 }
+
+/// Lets several owners (for example the devices of a simulated network) share one link.
+impl<T: DataLink> DataLink for std::sync::Arc<T> {
+    async fn send(&self, address: DataLinkAddress, payload: &[u8]) -> Result<(), DataLinkError> {
+        T::send(self, address, payload).await
+    }
+
+    async fn recv(&self, buf: &mut [u8]) -> Result<(usize, DataLinkAddress), DataLinkError> {
+        T::recv(self, buf).await
+    }
+}
@@ -0,0 +1,19 @@
+use std::path::PathBuf;
+
+fn main() {
+    let crate_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("cbindgen.toml is valid");
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    // The whole ABI lives in lib.rs; parsing it directly avoids a `cargo metadata` run
+    // from inside the build. The committed `include/rustbac.h` is checked against this
+    // copy by a test and refreshed with `scripts/update_ffi_header.sh`.
+    cbindgen::Builder::new()
+        .with_src(crate_dir.join("src/lib.rs"))
+        .with_config(config)
+        .generate()
+        .expect("generate C bindings")
+        .write_to_file(out_dir.join("rustbac.h"));
+}
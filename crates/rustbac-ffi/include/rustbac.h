@@ -0,0 +1,238 @@
+#ifndef RUSTBAC_H
+#define RUSTBAC_H
+
+/* Generated by cbindgen from crates/rustbac-ffi/src/lib.rs; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Size of the NUL-terminated address buffers in [`RustbacDevice`] and
+// [`RustbacCovUpdate`].
+#define RUSTBAC_ADDRESS_LEN 64
+
+// Result of a fallible call. Anything other than `Ok` leaves a message for
+// [`rustbac_last_error`].
+typedef enum RustbacStatus {
+  RUSTBAC_STATUS_OK = 0,
+  // A NULL pointer, malformed address or unsupported value was passed in.
+  RUSTBAC_STATUS_INVALID_ARGUMENT = -1,
+  // The device did not answer within the response timeout.
+  RUSTBAC_STATUS_TIMEOUT = -2,
+  // The device answered with a BACnet Error (unknown object, write access denied, ...).
+  RUSTBAC_STATUS_REMOTE_ERROR = -3,
+  // The device rejected or aborted the request.
+  RUSTBAC_STATUS_REJECTED = -4,
+  // The network transport failed (socket error, BBMD NAK).
+  RUSTBAC_STATUS_TRANSPORT = -5,
+  // The response could not be decoded.
+  RUSTBAC_STATUS_DECODE = -6,
+  RUSTBAC_STATUS_OTHER = -99,
+} RustbacStatus;
+
+// Which field of a [`RustbacValue`] holds the value.
+typedef enum RustbacValueKind {
+  RUSTBAC_VALUE_KIND_NULL = 0,
+  // `boolean_value`.
+  RUSTBAC_VALUE_KIND_BOOLEAN = 1,
+  // `unsigned_value`.
+  RUSTBAC_VALUE_KIND_UNSIGNED = 2,
+  // `signed_value`.
+  RUSTBAC_VALUE_KIND_SIGNED = 3,
+  // `real_value`.
+  RUSTBAC_VALUE_KIND_REAL = 4,
+  // `real_value`.
+  RUSTBAC_VALUE_KIND_DOUBLE = 5,
+  // `unsigned_value`.
+  RUSTBAC_VALUE_KIND_ENUMERATED = 6,
+  // `text`.
+  RUSTBAC_VALUE_KIND_CHARACTER_STRING = 7,
+  // `object_type` and `instance`.
+  RUSTBAC_VALUE_KIND_OBJECT_ID = 8,
+  // Any other BACnet type (dates, bit strings, constructed values, ...); `text` holds a
+  // readable rendering. Cannot be written.
+  RUSTBAC_VALUE_KIND_OTHER = 9,
+} RustbacValueKind;
+
+// Opaque client handle.
+typedef struct RustbacClient RustbacClient;
+
+// Opaque COV subscription handle.
+typedef struct RustbacSubscription RustbacSubscription;
+
+// A device that answered Who-Is.
+typedef struct RustbacDevice {
+  uint32_t device_instance;
+  char address[RUSTBAC_ADDRESS_LEN];
+} RustbacDevice;
+
+// A BACnet application value.
+typedef struct RustbacValue {
+  enum RustbacValueKind kind;
+  bool boolean_value;
+  uint32_t unsigned_value;
+  int32_t signed_value;
+  double real_value;
+  uint16_t object_type;
+  uint32_t instance;
+  // NUL-terminated UTF-8 text, or NULL. Owned by the value when returned by this
+  // library; release with [`rustbac_value_clear`].
+  char *text;
+} RustbacValue;
+
+// One property value from a COV notification (or a polled fallback read).
+//
+// Only valid for the duration of the callback; `value.text` is released afterwards.
+typedef struct RustbacCovUpdate {
+  char address[RUSTBAC_ADDRESS_LEN];
+  uint16_t object_type;
+  uint32_t instance;
+  uint32_t property_id;
+  struct RustbacValue value;
+  // `true` if the value was polled because the subscription failed or went silent.
+  bool polled;
+} RustbacCovUpdate;
+
+// Called on a library-owned thread for every property in every COV update.
+typedef void (*RustbacCovCallback)(void *user_data, const struct RustbacCovUpdate *update);
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Message describing the last failed call on this thread, or NULL. Valid until the next
+// call into this library on the same thread.
+const char *rustbac_last_error(void);
+
+// Creates a BACnet/IP client bound to `bind_addr` (`"ip:port"`; NULL binds
+// `0.0.0.0:0`). Returns NULL on failure.
+//
+// # Safety
+//
+// `bind_addr` must be NULL or point to a NUL-terminated string that stays valid for the
+// call. The returned client is owned by the caller and released with
+// [`rustbac_client_free`].
+struct RustbacClient *rustbac_client_new(const char *bind_addr);
+
+// Creates a client registered as a foreign device with the BBMD at `bbmd_addr`
+// (`"ip:port"`). Returns NULL on failure.
+//
+// # Safety
+//
+// `bbmd_addr` must be NULL or point to a NUL-terminated string that stays valid for the
+// call. The returned client is owned by the caller and released with
+// [`rustbac_client_free`].
+struct RustbacClient *rustbac_client_new_foreign(const char *bbmd_addr, uint16_t ttl_seconds);
+
+// Sets the per-request response timeout (default 3000 ms). Calls already waiting keep
+// the timeout they started with.
+//
+// # Safety
+//
+// `client` must be NULL or a client from `rustbac_client_new*` that has not been freed.
+// The call may run concurrently with other calls on the client.
+enum RustbacStatus rustbac_client_set_timeout(const struct RustbacClient *client,
+                                              uint32_t timeout_ms);
+
+// Releases a client. Subscriptions created from it must be freed first.
+//
+// # Safety
+//
+// `client` must be NULL or a client from `rustbac_client_new*` that has not been freed.
+// No other call may be using the client, and it must not be used afterwards.
+void rustbac_client_free(struct RustbacClient *client);
+
+// Broadcasts Who-Is and collects I-Am replies for `wait_ms`.
+//
+// Up to `capacity` devices are written to `out`; `count` receives the number of devices
+// found, which may exceed `capacity`.
+//
+// # Safety
+//
+// `client` must be NULL or a live client, `count` must be NULL or valid for a write,
+// and `out` must be NULL or valid for writes of `capacity` devices. The client may be
+// used from other threads at the same time.
+enum RustbacStatus rustbac_who_is(const struct RustbacClient *client,
+                                  uint32_t wait_ms,
+                                  struct RustbacDevice *out,
+                                  size_t capacity,
+                                  size_t *count);
+
+// Reads one property. On success `out` holds the value; release it with
+// [`rustbac_value_clear`].
+//
+// # Safety
+//
+// `client` must be NULL or a live client, `address` NULL or a NUL-terminated string,
+// and `out` NULL or valid for a write. Any text `out` held before is overwritten, not
+// released. The client may be used from other threads at the same time.
+enum RustbacStatus rustbac_read_property(const struct RustbacClient *client,
+                                         const char *address,
+                                         uint16_t object_type,
+                                         uint32_t instance,
+                                         uint32_t property_id,
+                                         struct RustbacValue *out);
+
+// Writes one property. `priority` is the command priority 1-16, or 0 for none.
+//
+// # Safety
+//
+// `client` must be NULL or a live client, `address` NULL or a NUL-terminated string,
+// and `value` NULL or a valid value whose `text`, when the kind is CharacterString, is
+// NULL or NUL-terminated. The value stays owned by the caller. The client may be used
+// from other threads at the same time.
+enum RustbacStatus rustbac_write_property(const struct RustbacClient *client,
+                                          const char *address,
+                                          uint16_t object_type,
+                                          uint32_t instance,
+                                          uint32_t property_id,
+                                          const struct RustbacValue *value,
+                                          uint8_t priority);
+
+// Releases the text owned by a value returned from this library and resets it to Null.
+//
+// # Safety
+//
+// `value` must be NULL or valid for reads and writes, and its `text` NULL or a string
+// returned by this library and not yet released. The caller must not release that text
+// any other way.
+void rustbac_value_clear(struct RustbacValue *value);
+
+// Subscribes to COV notifications for an object, renewing the subscription every
+// `lifetime_seconds` and polling if the device stops notifying.
+//
+// `callback` runs on a library-owned thread until the subscription is freed; `user_data`
+// is passed through unchanged. On success `*out` receives the subscription handle.
+//
+// # Safety
+//
+// `client` must be NULL or a live client, `address` NULL or a NUL-terminated string,
+// and `out` NULL or valid for a write. `callback` and `user_data` must stay valid until
+// [`rustbac_subscription_free`] returns, and `user_data` must be safe to use from
+// another thread. The subscription is owned by the caller and must be freed before the
+// client.
+enum RustbacStatus rustbac_subscribe_cov(const struct RustbacClient *client,
+                                         const char *address,
+                                         uint16_t object_type,
+                                         uint32_t instance,
+                                         uint32_t lifetime_seconds,
+                                         RustbacCovCallback callback,
+                                         void *user_data,
+                                         struct RustbacSubscription **out);
+
+// Cancels a COV subscription. After this returns the callback is no longer invoked.
+// Must not be called from inside the callback.
+//
+// # Safety
+//
+// `subscription` must be NULL or a subscription from [`rustbac_subscribe_cov`] that has
+// not been freed, and must not be used afterwards.
+void rustbac_subscription_free(struct RustbacSubscription *subscription);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* RUSTBAC_H */
@@ -0,0 +1,829 @@
+//! C ABI for the rustbac BACnet/IP client, for embedding the stack in C and C++ building
+//! management software.
+//!
+//! The generated header is `include/rustbac.h`; after changing the ABI, regenerate it
+//! with `scripts/update_ffi_header.sh`. Conventions:
+//!
+//! - Clients and subscriptions are opaque handles created by `rustbac_*_new` /
+//!   `rustbac_subscribe_cov` and released with the matching `*_free` function.
+//! - Fallible functions return a [`RustbacStatus`]; on failure
+//!   [`rustbac_last_error`] describes the error on the calling thread.
+//! - Device addresses are `"ip:port"` strings.
+//! - Strings returned inside a [`RustbacValue`] are owned by the value and released with
+//!   [`rustbac_value_clear`].
+//!
+//! Calls block the calling thread; a client may be used from several threads at once.
+
+#[cfg(feature = "python")]
+mod python;
+
+use rustbac_client::blocking::BacnetClient;
+use rustbac_client::{
+    ClientDataValue, ClientError, CovManager, CovManagerBuilder, CovMode, CovSubscriptionSpec,
+    SubscriberProcessIdAllocator, UpdateSource,
+};
+use rustbac_core::services::write_property::WritePropertyRequest;
+use rustbac_core::types::{DataValue, ObjectId, ObjectType, PropertyId};
+use rustbac_datalink::bip::transport::BacnetIpTransport;
+use rustbac_datalink::DataLinkAddress;
+use std::cell::RefCell;
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::net::SocketAddr;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Size of the NUL-terminated address buffers in [`RustbacDevice`] and
+/// [`RustbacCovUpdate`].
+pub const RUSTBAC_ADDRESS_LEN: usize = 64;
+
+const DEFAULT_TIMEOUT_MS: u32 = 3000;
+
+/// Result of a fallible call. Anything other than `Ok` leaves a message for
+/// [`rustbac_last_error`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RustbacStatus {
+    Ok = 0,
+    /// A NULL pointer, malformed address or unsupported value was passed in.
+    InvalidArgument = -1,
+    /// The device did not answer within the response timeout.
+    Timeout = -2,
+    /// The device answered with a BACnet Error (unknown object, write access denied, ...).
+    RemoteError = -3,
+    /// The device rejected or aborted the request.
+    Rejected = -4,
+    /// The network transport failed (socket error, BBMD NAK).
+    Transport = -5,
+    /// The response could not be decoded.
+    Decode = -6,
+    Other = -99,
+}
+
+/// Which field of a [`RustbacValue`] holds the value.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RustbacValueKind {
+    Null = 0,
+    /// `boolean_value`.
+    Boolean = 1,
+    /// `unsigned_value`.
+    Unsigned = 2,
+    /// `signed_value`.
+    Signed = 3,
+    /// `real_value`.
+    Real = 4,
+    /// `real_value`.
+    Double = 5,
+    /// `unsigned_value`.
+    Enumerated = 6,
+    /// `text`.
+    CharacterString = 7,
+    /// `object_type` and `instance`.
+    ObjectId = 8,
+    /// Any other BACnet type (dates, bit strings, constructed values, ...); `text` holds a
+    /// readable rendering. Cannot be written.
+    Other = 9,
+}
+
+/// A BACnet application value.
+#[repr(C)]
+#[derive(Debug)]
+pub struct RustbacValue {
+    pub kind: RustbacValueKind,
+    pub boolean_value: bool,
+    pub unsigned_value: u32,
+    pub signed_value: i32,
+    pub real_value: f64,
+    pub object_type: u16,
+    pub instance: u32,
+    /// NUL-terminated UTF-8 text, or NULL. Owned by the value when returned by this
+    /// library; release with [`rustbac_value_clear`].
+    pub text: *mut c_char,
+}
+
+/// A device that answered Who-Is.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RustbacDevice {
+    pub device_instance: u32,
+    pub address: [c_char; RUSTBAC_ADDRESS_LEN],
+}
+
+/// One property value from a COV notification (or a polled fallback read).
+///
+/// Only valid for the duration of the callback; `value.text` is released afterwards.
+#[repr(C)]
+#[derive(Debug)]
+pub struct RustbacCovUpdate {
+    pub address: [c_char; RUSTBAC_ADDRESS_LEN],
+    pub object_type: u16,
+    pub instance: u32,
+    pub property_id: u32,
+    pub value: RustbacValue,
+    /// `true` if the value was polled because the subscription failed or went silent.
+    pub polled: bool,
+}
+
+/// Called on a library-owned thread for every property in every COV update.
+pub type RustbacCovCallback =
+    Option<unsafe extern "C" fn(user_data: *mut c_void, update: *const RustbacCovUpdate)>;
+
+/// Opaque client handle.
+pub struct RustbacClient {
+    inner: BacnetClient,
+    /// Response timeout applied to every call, settable while other threads use the
+    /// client.
+    timeout_ms: AtomicU32,
+    /// Subscriber process ids of the client's COV subscriptions.
+    process_ids: Arc<SubscriberProcessIdAllocator>,
+}
+
+impl RustbacClient {
+    fn new(inner: BacnetClient) -> Self {
+        Self {
+            inner,
+            timeout_ms: AtomicU32::new(DEFAULT_TIMEOUT_MS),
+            process_ids: Arc::new(SubscriberProcessIdAllocator::new()),
+        }
+    }
+}
+
+/// Opaque COV subscription handle.
+pub struct RustbacSubscription {
+    stop: tokio::sync::watch::Sender<bool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+    process_id: u32,
+    process_ids: Arc<SubscriberProcessIdAllocator>,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: impl Into<String>) {
+    let message = CString::new(message.into().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+}
+
+fn invalid(message: &str) -> RustbacStatus {
+    set_last_error(message);
+    RustbacStatus::InvalidArgument
+}
+
+fn client_error(err: ClientError) -> RustbacStatus {
+    let status = match err.root() {
+        ClientError::Timeout => RustbacStatus::Timeout,
+        ClientError::RemoteServiceError { .. } => RustbacStatus::RemoteError,
+        ClientError::RemoteReject { .. } | ClientError::RemoteAbort { .. } => {
+            RustbacStatus::Rejected
+        }
+        ClientError::DataLink(_) | ClientError::BbmdNak(_) => RustbacStatus::Transport,
+        ClientError::Decode(_) | ClientError::UnsupportedResponse => RustbacStatus::Decode,
+        _ => RustbacStatus::Other,
+    };
+    set_last_error(err.to_string());
+    status
+}
+
+/// Runs `body`, converting panics into [`RustbacStatus::Other`] so they never unwind
+/// into C.
+fn guard(body: impl FnOnce() -> Result<(), RustbacStatus>) -> RustbacStatus {
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => RustbacStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => {
+            set_last_error("internal panic");
+            RustbacStatus::Other
+        }
+    }
+}
+
+unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, RustbacStatus> {
+    if ptr.is_null() {
+        return Err(invalid(&format!("{name} is NULL")));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| invalid(&format!("{name} is not valid UTF-8")))
+}
+
+unsafe fn socket_addr_arg(ptr: *const c_char, name: &str) -> Result<SocketAddr, RustbacStatus> {
+    let text = str_arg(ptr, name)?;
+    text.parse()
+        .map_err(|_| invalid(&format!("{name} '{text}' is not an ip:port address")))
+}
+
+/// The client behind `client`, with the timeout currently set on it.
+unsafe fn client_arg(client: *const RustbacClient) -> Result<BacnetClient, RustbacStatus> {
+    let client = client.as_ref().ok_or_else(|| invalid("client is NULL"))?;
+    let timeout_ms = client.timeout_ms.load(Ordering::Relaxed);
+    Ok(client
+        .inner
+        .clone()
+        .with_response_timeout(Duration::from_millis(u64::from(timeout_ms))))
+}
+
+fn object_id(object_type: u16, instance: u32) -> ObjectId {
+    ObjectId::new(ObjectType::from_u16(object_type), instance)
+}
+
+fn address_buffer(address: DataLinkAddress) -> [c_char; RUSTBAC_ADDRESS_LEN] {
+    let mut out = [0 as c_char; RUSTBAC_ADDRESS_LEN];
+    for (slot, byte) in out
+        .iter_mut()
+        .zip(address.to_string().bytes().take(RUSTBAC_ADDRESS_LEN - 1))
+    {
+        *slot = byte as c_char;
+    }
+    out
+}
+
+fn owned_text(text: String) -> *mut c_char {
+    CString::new(text.replace('\0', " "))
+        .map(CString::into_raw)
+        .unwrap_or(ptr::null_mut())
+}
+
+fn to_ffi_value(value: ClientDataValue) -> RustbacValue {
+    let mut out = RustbacValue {
+        kind: RustbacValueKind::Null,
+        boolean_value: false,
+        unsigned_value: 0,
+        signed_value: 0,
+        real_value: 0.0,
+        object_type: 0,
+        instance: 0,
+        text: ptr::null_mut(),
+    };
+    match value {
+        ClientDataValue::Null => {}
+        ClientDataValue::Boolean(v) => {
+            out.kind = RustbacValueKind::Boolean;
+            out.boolean_value = v;
+        }
+        ClientDataValue::Unsigned(v) => {
+            out.kind = RustbacValueKind::Unsigned;
+            out.unsigned_value = v;
+        }
+        ClientDataValue::Signed(v) => {
+            out.kind = RustbacValueKind::Signed;
+            out.signed_value = v;
+        }
+        ClientDataValue::Real(v) => {
+            out.kind = RustbacValueKind::Real;
+            out.real_value = f64::from(v);
+        }
+        ClientDataValue::Double(v) => {
+            out.kind = RustbacValueKind::Double;
+            out.real_value = v;
+        }
+        ClientDataValue::Enumerated(v) => {
+            out.kind = RustbacValueKind::Enumerated;
+            out.unsigned_value = v;
+        }
+        ClientDataValue::CharacterString(text) => {
+            out.kind = RustbacValueKind::CharacterString;
+            out.text = owned_text(text.to_string());
+        }
+        ClientDataValue::ObjectId(id) => {
+            out.kind = RustbacValueKind::ObjectId;
+            out.object_type = id.object_type().to_u16();
+            out.instance = id.instance();
+        }
+        other => {
+            out.kind = RustbacValueKind::Other;
+            out.text = owned_text(format!("{other:?}"));
+        }
+    }
+    out
+}
+
+unsafe fn from_ffi_value(value: &RustbacValue) -> Result<DataValue<'_>, RustbacStatus> {
+    Ok(match value.kind {
+        RustbacValueKind::Null => DataValue::Null,
+        RustbacValueKind::Boolean => DataValue::Boolean(value.boolean_value),
+        RustbacValueKind::Unsigned => DataValue::Unsigned(value.unsigned_value),
+        RustbacValueKind::Signed => DataValue::Signed(value.signed_value),
+        RustbacValueKind::Real => DataValue::Real(value.real_value as f32),
+        RustbacValueKind::Double => DataValue::Double(value.real_value),
+        RustbacValueKind::Enumerated => DataValue::Enumerated(value.unsigned_value),
+        RustbacValueKind::CharacterString => {
+            DataValue::CharacterString(str_arg(value.text, "value.text")?)
+        }
+        RustbacValueKind::ObjectId => {
+            DataValue::ObjectId(object_id(value.object_type, value.instance))
+        }
+        RustbacValueKind::Other => return Err(invalid("values of kind Other cannot be written")),
+    })
+}
+
+/// Message describing the last failed call on this thread, or NULL. Valid until the next
+/// call into this library on the same thread.
+#[no_mangle]
+pub extern "C" fn rustbac_last_error() -> *const c_char {
+    LAST_ERROR.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Creates a BACnet/IP client bound to `bind_addr` (`"ip:port"`; NULL binds
+/// `0.0.0.0:0`). Returns NULL on failure.
+///
+/// # Safety
+///
+/// `bind_addr` must be NULL or point to a NUL-terminated string that stays valid for the
+/// call. The returned client is owned by the caller and released with
+/// [`rustbac_client_free`].
+#[no_mangle]
+pub unsafe extern "C" fn rustbac_client_new(bind_addr: *const c_char) -> *mut RustbacClient {
+    let mut client = ptr::null_mut();
+    guard(|| {
+        let bind_addr = if bind_addr.is_null() {
+            SocketAddr::from(([0, 0, 0, 0], 0))
+        } else {
+            socket_addr_arg(bind_addr, "bind_addr")?
+        };
+        let inner = BacnetClient::connect(async move {
+            Ok(rustbac_client::BacnetClient::with_datalink(
+                BacnetIpTransport::bind(bind_addr).await?,
+            ))
+        })
+        .map_err(client_error)?;
+        client = Box::into_raw(Box::new(RustbacClient::new(inner)));
+        Ok(())
+    });
+    client
+}
+
+/// Creates a client registered as a foreign device with the BBMD at `bbmd_addr`
+/// (`"ip:port"`). Returns NULL on failure.
+///
+/// # Safety
+///
+/// `bbmd_addr` must be NULL or point to a NUL-terminated string that stays valid for the
+/// call. The returned client is owned by the caller and released with
+/// [`rustbac_client_free`].
+#[no_mangle]
+pub unsafe extern "C" fn rustbac_client_new_foreign(
+    bbmd_addr: *const c_char,
+    ttl_seconds: u16,
+) -> *mut RustbacClient {
+    let mut client = ptr::null_mut();
+    guard(|| {
+        let bbmd_addr = socket_addr_arg(bbmd_addr, "bbmd_addr")?;
+        let inner = BacnetClient::new_foreign(bbmd_addr, ttl_seconds).map_err(client_error)?;
+        client = Box::into_raw(Box::new(RustbacClient::new(inner)));
+        Ok(())
+    });
+    client
+}
+
+/// Sets the per-request response timeout (default 3000 ms). Calls already waiting keep
+/// the timeout they started with.
+///
+/// # Safety
+///
+/// `client` must be NULL or a client from `rustbac_client_new*` that has not been freed.
+/// The call may run concurrently with other calls on the client.
+#[no_mangle]
+pub unsafe extern "C" fn rustbac_client_set_timeout(
+    client: *const RustbacClient,
+    timeout_ms: u32,
+) -> RustbacStatus {
+    guard(|| {
+        let client = client.as_ref().ok_or_else(|| invalid("client is NULL"))?;
+        client.timeout_ms.store(timeout_ms, Ordering::Relaxed);
+        Ok(())
+    })
+}
+
+/// Releases a client. Subscriptions created from it must be freed first.
+///
+/// # Safety
+///
+/// `client` must be NULL or a client from `rustbac_client_new*` that has not been freed.
+/// No other call may be using the client, and it must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn rustbac_client_free(client: *mut RustbacClient) {
+    if !client.is_null() {
+        drop(Box::from_raw(client));
+    }
+}
+
+/// Broadcasts Who-Is and collects I-Am replies for `wait_ms`.
+///
+/// Up to `capacity` devices are written to `out`; `count` receives the number of devices
+/// found, which may exceed `capacity`.
+///
+/// # Safety
+///
+/// `client` must be NULL or a live client, `count` must be NULL or valid for a write,
+/// and `out` must be NULL or valid for writes of `capacity` devices. The client may be
+/// used from other threads at the same time.
+#[no_mangle]
+pub unsafe extern "C" fn rustbac_who_is(
+    client: *const RustbacClient,
+    wait_ms: u32,
+    out: *mut RustbacDevice,
+    capacity: usize,
+    count: *mut usize,
+) -> RustbacStatus {
+    guard(|| {
+        let client = client_arg(client)?;
+        if count.is_null() || (out.is_null() && capacity > 0) {
+            return Err(invalid("out or count is NULL"));
+        }
+        let devices = client
+            .who_is(None, Duration::from_millis(u64::from(wait_ms)))
+            .map_err(client_error)?;
+        *count = devices.len();
+        for (index, device) in devices.iter().take(capacity).enumerate() {
+            *out.add(index) = RustbacDevice {
+                device_instance: device.device_id.map_or(u32::MAX, ObjectId::instance),
+                address: address_buffer(device.address),
+            };
+        }
+        Ok(())
+    })
+}
+
+/// Reads one property. On success `out` holds the value; release it with
+/// [`rustbac_value_clear`].
+///
+/// # Safety
+///
+/// `client` must be NULL or a live client, `address` NULL or a NUL-terminated string,
+/// and `out` NULL or valid for a write. Any text `out` held before is overwritten, not
+/// released. The client may be used from other threads at the same time.
+#[no_mangle]
+pub unsafe extern "C" fn rustbac_read_property(
+    client: *const RustbacClient,
+    address: *const c_char,
+    object_type: u16,
+    instance: u32,
+    property_id: u32,
+    out: *mut RustbacValue,
+) -> RustbacStatus {
+    guard(|| {
+        let client = client_arg(client)?;
+        let address = DataLinkAddress::Ip(socket_addr_arg(address, "address")?);
+        let out = out.as_mut().ok_or_else(|| invalid("out is NULL"))?;
+        let value = client
+            .read_property(
+                address,
+                object_id(object_type, instance),
+                PropertyId::from_u32(property_id),
+            )
+            .map_err(client_error)?;
+        ptr::write(out, to_ffi_value(value));
+        Ok(())
+    })
+}
+
+/// Writes one property. `priority` is the command priority 1-16, or 0 for none.
+///
+/// # Safety
+///
+/// `client` must be NULL or a live client, `address` NULL or a NUL-terminated string,
+/// and `value` NULL or a valid value whose `text`, when the kind is CharacterString, is
+/// NULL or NUL-terminated. The value stays owned by the caller. The client may be used
+/// from other threads at the same time.
+#[no_mangle]
+pub unsafe extern "C" fn rustbac_write_property(
+    client: *const RustbacClient,
+    address: *const c_char,
+    object_type: u16,
+    instance: u32,
+    property_id: u32,
+    value: *const RustbacValue,
+    priority: u8,
+) -> RustbacStatus {
+    guard(|| {
+        let client = client_arg(client)?;
+        let address = DataLinkAddress::Ip(socket_addr_arg(address, "address")?);
+        let value = value.as_ref().ok_or_else(|| invalid("value is NULL"))?;
+        if priority > 16 {
+            return Err(invalid("priority must be 0-16"));
+        }
+        let request = WritePropertyRequest {
+            object_id: object_id(object_type, instance),
+            property_id: PropertyId::from_u32(property_id),
+            value: from_ffi_value(value)?,
+            priority: (priority != 0).then_some(priority),
+            ..Default::default()
+        };
+        client
+            .write_property(address, request)
+            .map_err(client_error)
+    })
+}
+
+/// Releases the text owned by a value returned from this library and resets it to Null.
+///
+/// # Safety
+///
+/// `value` must be NULL or valid for reads and writes, and its `text` NULL or a string
+/// returned by this library and not yet released. The caller must not release that text
+/// any other way.
+#[no_mangle]
+pub unsafe extern "C" fn rustbac_value_clear(value: *mut RustbacValue) {
+    if let Some(value) = value.as_mut() {
+        if !value.text.is_null() {
+            drop(CString::from_raw(value.text));
+        }
+        value.text = ptr::null_mut();
+        value.kind = RustbacValueKind::Null;
+    }
+}
+
+struct UserData(*mut c_void);
+
+// SAFETY: the caller of `rustbac_subscribe_cov` guarantees `user_data` may be used from
+// the callback thread.
+unsafe impl Send for UserData {}
+
+impl UserData {
+    // A method call makes closures capture the whole `Send` wrapper rather than the raw
+    // pointer field.
+    fn get(&self) -> *mut c_void {
+        self.0
+    }
+}
+
+/// Subscribes to COV notifications for an object, renewing the subscription every
+/// `lifetime_seconds` and polling if the device stops notifying.
+///
+/// `callback` runs on a library-owned thread until the subscription is freed; `user_data`
+/// is passed through unchanged. On success `*out` receives the subscription handle.
+///
+/// # Safety
+///
+/// `client` must be NULL or a live client, `address` NULL or a NUL-terminated string,
+/// and `out` NULL or valid for a write. `callback` and `user_data` must stay valid until
+/// [`rustbac_subscription_free`] returns, and `user_data` must be safe to use from
+/// another thread. The subscription is owned by the caller and must be freed before the
+/// client.
+#[no_mangle]
+pub unsafe extern "C" fn rustbac_subscribe_cov(
+    client: *const RustbacClient,
+    address: *const c_char,
+    object_type: u16,
+    instance: u32,
+    lifetime_seconds: u32,
+    callback: RustbacCovCallback,
+    user_data: *mut c_void,
+    out: *mut *mut RustbacSubscription,
+) -> RustbacStatus {
+    guard(|| {
+        let process_ids = client
+            .as_ref()
+            .map(|client| client.process_ids.clone())
+            .ok_or_else(|| invalid("client is NULL"))?;
+        let client = client_arg(client)?;
+        let address = DataLinkAddress::Ip(socket_addr_arg(address, "address")?);
+        let callback = callback.ok_or_else(|| invalid("callback is NULL"))?;
+        if out.is_null() {
+            return Err(invalid("out is NULL"));
+        }
+        let process_id = process_ids
+            .allocate()
+            .ok_or_else(|| invalid("no subscriber process id is free"))?;
+        let spec = CovSubscriptionSpec {
+            address,
+            object_id: object_id(object_type, instance),
+            property_id: None,
+            lifetime_seconds,
+            cov_increment: None,
+            confirmed: false,
+            subscriber_process_id: process_id,
+            mode: CovMode::Subscribe,
+        };
+        let async_client = Arc::new(client.async_client().clone());
+        let manager = client
+            .block_on(async { CovManagerBuilder::new(async_client).subscribe(spec).build() })
+            .map_err(|err| {
+                process_ids.release(process_id);
+                client_error(err)
+            })?;
+
+        let (stop, stop_rx) = tokio::sync::watch::channel(false);
+        let user_data = UserData(user_data);
+        let thread = std::thread::spawn(move || {
+            run_cov_callbacks(&client, manager, stop_rx, |update| {
+                // SAFETY: `callback` and `user_data` come from the caller, who keeps them
+                // valid until `rustbac_subscription_free` returns.
+                unsafe { callback(user_data.get(), update) }
+            });
+        });
+        *out = Box::into_raw(Box::new(RustbacSubscription {
+            stop,
+            thread: Some(thread),
+            process_id,
+            process_ids,
+        }));
+        Ok(())
+    })
+}
+
+fn run_cov_callbacks(
+    client: &BacnetClient,
+    mut manager: CovManager,
+    mut stop: tokio::sync::watch::Receiver<bool>,
+    mut deliver: impl FnMut(*const RustbacCovUpdate),
+) {
+    loop {
+        let next = client.block_on(async {
+            tokio::select! {
+                update = manager.recv() => update,
+                _ = stop.changed() => None,
+            }
+        });
+        let Some(update) = next else { break };
+        for value in update.values {
+            let mut ffi = RustbacCovUpdate {
+                address: address_buffer(update.address),
+                object_type: update.object_id.object_type().to_u16(),
+                instance: update.object_id.instance(),
+                property_id: value.property_id.to_u32(),
+                value: to_ffi_value(value.value),
+                polled: update.source == UpdateSource::Poll,
+            };
+            // A panicking callback must not unwind through the library thread's C frames.
+            let _ = catch_unwind(AssertUnwindSafe(|| deliver(&ffi)));
+            // SAFETY: `ffi.value` was produced by `to_ffi_value` above.
+            unsafe { rustbac_value_clear(&mut ffi.value) };
+        }
+    }
+    manager.stop();
+}
+
+/// Cancels a COV subscription. After this returns the callback is no longer invoked.
+/// Must not be called from inside the callback.
+///
+/// # Safety
+///
+/// `subscription` must be NULL or a subscription from [`rustbac_subscribe_cov`] that has
+/// not been freed, and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn rustbac_subscription_free(subscription: *mut RustbacSubscription) {
+    if subscription.is_null() {
+        return;
+    }
+    let mut subscription = Box::from_raw(subscription);
+    let _ = subscription.stop.send(true);
+    if let Some(thread) = subscription.thread.take() {
+        let _ = thread.join();
+    }
+    subscription.process_ids.release(subscription.process_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustbac_client::SimulatedDevice;
+
+    #[test]
+    fn committed_header_matches_the_generated_one() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/rustbac.h"));
+        let committed_path = concat!(env!("CARGO_MANIFEST_DIR"), "/include/rustbac.h");
+        if std::env::var_os("RUSTBAC_UPDATE_HEADER").is_some() {
+            std::fs::write(committed_path, generated).unwrap();
+            return;
+        }
+        let committed = std::fs::read_to_string(committed_path).unwrap();
+        assert!(
+            committed == generated,
+            "include/rustbac.h is out of date; run scripts/update_ffi_header.sh"
+        );
+    }
+
+    /// Starts a simulated device 7 with Analog Value 1 on `runtime` and returns its
+    /// address.
+    fn simulated_device(runtime: &tokio::runtime::Runtime) -> CString {
+        runtime.block_on(async {
+            let transport = BacnetIpTransport::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+                .await
+                .unwrap();
+            let addr = transport.local_addr().unwrap();
+            let device = SimulatedDevice::new(7, transport);
+            device
+                .add_object(
+                    ObjectId::new(ObjectType::AnalogValue, 1),
+                    [(PropertyId::PresentValue, ClientDataValue::Real(20.0))].into(),
+                )
+                .await;
+            tokio::spawn(async move { device.run().await });
+            CString::new(addr.to_string()).unwrap()
+        })
+    }
+
+    #[test]
+    fn subscriptions_take_process_ids_from_the_client() {
+        unsafe extern "C" fn ignore(_: *mut c_void, _: *const RustbacCovUpdate) {}
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let device_addr = simulated_device(&runtime);
+        unsafe {
+            let bind = CString::new("127.0.0.1:0").unwrap();
+            let client = rustbac_client_new(bind.as_ptr());
+            assert!(!client.is_null());
+
+            let av = ObjectType::AnalogValue.to_u16();
+            let mut subscriptions = [ptr::null_mut(); 2];
+            for out in &mut subscriptions {
+                let status = rustbac_subscribe_cov(
+                    client,
+                    device_addr.as_ptr(),
+                    av,
+                    1,
+                    60,
+                    Some(ignore),
+                    ptr::null_mut(),
+                    out,
+                );
+                assert_eq!(status, RustbacStatus::Ok);
+            }
+            let ids = subscriptions.map(|subscription| (*subscription).process_id);
+            assert_ne!(ids[0], ids[1]);
+            assert!(ids.iter().all(|&id| (*client).process_ids.is_in_use(id)));
+
+            for subscription in subscriptions {
+                rustbac_subscription_free(subscription);
+            }
+            assert!(!ids.iter().any(|&id| (*client).process_ids.is_in_use(id)));
+            rustbac_client_free(client);
+        }
+    }
+
+    #[test]
+    fn reads_and_writes_through_the_c_abi() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let device_addr = simulated_device(&runtime);
+
+        unsafe {
+            let bind = CString::new("127.0.0.1:0").unwrap();
+            let client = rustbac_client_new(bind.as_ptr());
+            assert!(!client.is_null());
+            assert_eq!(rustbac_client_set_timeout(client, 1500), RustbacStatus::Ok);
+            assert_eq!(
+                rustbac_client_set_timeout(ptr::null(), 1500),
+                RustbacStatus::InvalidArgument
+            );
+
+            let av = ObjectType::AnalogValue.to_u16();
+            let present_value = PropertyId::PresentValue.to_u32();
+            let mut value = to_ffi_value(ClientDataValue::Real(42.5));
+            let status = rustbac_write_property(
+                client,
+                device_addr.as_ptr(),
+                av,
+                1,
+                present_value,
+                &value,
+                0,
+            );
+            assert_eq!(status, RustbacStatus::Ok);
+
+            let status = rustbac_read_property(
+                client,
+                device_addr.as_ptr(),
+                av,
+                1,
+                present_value,
+                &mut value,
+            );
+            assert_eq!(status, RustbacStatus::Ok);
+            assert_eq!(value.kind, RustbacValueKind::Real);
+            assert_eq!(value.real_value, 42.5);
+
+            let status = rustbac_read_property(
+                client,
+                device_addr.as_ptr(),
+                ObjectType::Device.to_u16(),
+                7,
+                PropertyId::ObjectName.to_u32(),
+                &mut value,
+            );
+            assert_eq!(status, RustbacStatus::Ok);
+            assert_eq!(value.kind, RustbacValueKind::CharacterString);
+            assert_eq!(CStr::from_ptr(value.text).to_str().unwrap(), "SimDevice-7");
+            rustbac_value_clear(&mut value);
+            assert!(value.text.is_null());
+
+            let bad = CString::new("not-an-address").unwrap();
+            let status = rustbac_read_property(client, bad.as_ptr(), av, 1, 85, &mut value);
+            assert_eq!(status, RustbacStatus::InvalidArgument);
+            assert!(CStr::from_ptr(rustbac_last_error())
+                .to_str()
+                .unwrap()
+                .contains("not-an-address"));
+
+            rustbac_client_free(client);
+        }
+    }
+}
@@ -0,0 +1,433 @@
+//! Python bindings (`python` feature), built with maturin as the `rustbac` module.
+//!
+//! Every network call returns an awaitable driven by a shared Tokio runtime, so the
+//! client plugs straight into `asyncio`:
+//!
+//! ```python
+//! import asyncio, rustbac
+//!
+//! async def main():
+//!     client = await rustbac.BacnetClient.connect()
+//!     for device in await client.who_is(wait=2.0):
+//!         print(device["device_instance"], device["address"])
+//!     async for update in client.subscribe_cov("192.168.1.20:47808", 0, 1):
+//!         print(update["values"])
+//!
+//! asyncio.run(main())
+//! ```
+//!
+//! Objects and properties are numeric (`object_type`, `instance`, `property_id`). Values
+//! map to `None`, `bool`, `int`, `float`, `str`, `bytes`, `(object_type, instance)` tuples
+//! and lists; dates, times and bit strings are returned as their debug rendering.
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyStopAsyncIteration, PyTimeoutError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyBytes, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use pyo3_async_runtimes::tokio::future_into_py;
+use rustbac_client::{
+    BacnetClient, ClientDataValue, ClientError, CovManager, CovManagerBuilder, CovMode,
+    CovSubscriptionSpec, CovUpdate, UpdateSource,
+};
+use rustbac_core::services::write_property::WritePropertyRequest;
+use rustbac_core::types::{DataValue, ObjectId, ObjectType, PropertyId};
+use rustbac_datalink::bip::transport::BacnetIpTransport;
+use rustbac_datalink::DataLinkAddress;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, Mutex};
+
+create_exception!(
+    rustbac,
+    BacnetError,
+    PyException,
+    "A BACnet request failed."
+);
+
+static NEXT_PROCESS_ID: AtomicU32 = AtomicU32::new(1);
+
+fn py_err(err: ClientError) -> PyErr {
+    match err.root() {
+        ClientError::Timeout => PyTimeoutError::new_err(err.to_string()),
+        _ => BacnetError::new_err(err.to_string()),
+    }
+}
+
+fn parse_addr(address: &str) -> PyResult<SocketAddr> {
+    address
+        .parse()
+        .map_err(|_| PyValueError::new_err(format!("'{address}' is not an ip:port address")))
+}
+
+fn object_id(object_type: u16, instance: u32) -> ObjectId {
+    ObjectId::new(ObjectType::from_u16(object_type), instance)
+}
+
+fn value_to_py(py: Python<'_>, value: ClientDataValue) -> PyResult<PyObject> {
+    Ok(match value {
+        ClientDataValue::Null => py.None(),
+        ClientDataValue::Boolean(v) => v.into_pyobject(py)?.to_owned().into_any().unbind(),
+        ClientDataValue::Unsigned(v) | ClientDataValue::Enumerated(v) => {
+            v.into_pyobject(py)?.into_any().unbind()
+        }
+        ClientDataValue::Signed(v) => v.into_pyobject(py)?.into_any().unbind(),
+        ClientDataValue::Real(v) => f64::from(v).into_pyobject(py)?.into_any().unbind(),
+        ClientDataValue::Double(v) => v.into_pyobject(py)?.into_any().unbind(),
+        ClientDataValue::OctetString(bytes) => PyBytes::new(py, &bytes).into_any().unbind(),
+        ClientDataValue::CharacterString(text) => text.into_pyobject(py)?.into_any().unbind(),
+        ClientDataValue::ObjectId(id) => {
+            PyTuple::new(py, [u32::from(id.object_type().to_u16()), id.instance()])?
+                .into_any()
+                .unbind()
+        }
+        ClientDataValue::Constructed { values, .. } => {
+            let items = values
+                .into_iter()
+                .map(|value| value_to_py(py, value))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, items)?.into_any().unbind()
+        }
+        other => format!("{other:?}").into_pyobject(py)?.into_any().unbind(),
+    })
+}
+
+/// A value to write, converted from Python before the request future starts.
+enum WriteValue {
+    Null,
+    Boolean(bool),
+    Unsigned(u32),
+    Signed(i32),
+    Real(f32),
+    Double(f64),
+    Enumerated(u32),
+    Text(String),
+}
+
+impl WriteValue {
+    /// Converts a Python value. `datatype` overrides the inferred BACnet type: `int` is
+    /// Unsigned (Signed when negative) and `float` is Real unless told otherwise.
+    fn from_py(value: &Bound<'_, PyAny>, datatype: Option<&str>) -> PyResult<Self> {
+        Ok(match datatype {
+            Some("null") => Self::Null,
+            Some("boolean") => Self::Boolean(value.extract()?),
+            Some("unsigned") => Self::Unsigned(value.extract()?),
+            Some("signed") => Self::Signed(value.extract()?),
+            Some("real") => Self::Real(value.extract()?),
+            Some("double") => Self::Double(value.extract()?),
+            Some("enumerated") => Self::Enumerated(value.extract()?),
+            Some("string") => Self::Text(value.extract()?),
+            Some(other) => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown datatype '{other}' (expected null, boolean, unsigned, signed, \
+                     real, double, enumerated or string)"
+                )))
+            }
+            None if value.is_none() => Self::Null,
+            None if value.is_instance_of::<PyBool>() => Self::Boolean(value.extract()?),
+            None if value.is_instance_of::<PyInt>() => {
+                let number: i64 = value.extract()?;
+                match u32::try_from(number) {
+                    Ok(unsigned) => Self::Unsigned(unsigned),
+                    Err(_) => Self::Signed(i32::try_from(number).map_err(|_| {
+                        PyValueError::new_err(format!("{number} does not fit a BACnet integer"))
+                    })?),
+                }
+            }
+            None if value.is_instance_of::<PyFloat>() => Self::Real(value.extract()?),
+            None if value.is_instance_of::<PyString>() => Self::Text(value.extract()?),
+            None => {
+                return Err(PyValueError::new_err(
+                    "value must be None, bool, int, float or str",
+                ))
+            }
+        })
+    }
+
+    fn as_data_value(&self) -> DataValue<'_> {
+        match self {
+            Self::Null => DataValue::Null,
+            Self::Boolean(v) => DataValue::Boolean(*v),
+            Self::Unsigned(v) => DataValue::Unsigned(*v),
+            Self::Signed(v) => DataValue::Signed(*v),
+            Self::Real(v) => DataValue::Real(*v),
+            Self::Double(v) => DataValue::Double(*v),
+            Self::Enumerated(v) => DataValue::Enumerated(*v),
+            Self::Text(text) => DataValue::CharacterString(text),
+        }
+    }
+}
+
+fn update_to_py(py: Python<'_>, update: CovUpdate) -> PyResult<PyObject> {
+    let values = PyDict::new(py);
+    for value in update.values {
+        values.set_item(value.property_id.to_u32(), value_to_py(py, value.value)?)?;
+    }
+    let dict = PyDict::new(py);
+    dict.set_item("address", update.address.to_string())?;
+    dict.set_item("object_type", update.object_id.object_type().to_u16())?;
+    dict.set_item("instance", update.object_id.instance())?;
+    dict.set_item("values", values)?;
+    dict.set_item("polled", update.source == UpdateSource::Poll)?;
+    Ok(dict.into_any().unbind())
+}
+
+/// Asyncio BACnet/IP client.
+#[pyclass(name = "BacnetClient", module = "rustbac", frozen)]
+struct PyBacnetClient {
+    inner: BacnetClient<BacnetIpTransport>,
+}
+
+#[pymethods]
+impl PyBacnetClient {
+    /// Binds a client to `bind` (`"ip:port"`).
+    #[staticmethod]
+    #[pyo3(signature = (bind = "0.0.0.0:0", timeout = 3.0))]
+    fn connect<'py>(py: Python<'py>, bind: &str, timeout: f64) -> PyResult<Bound<'py, PyAny>> {
+        let bind = parse_addr(bind)?;
+        let timeout = Duration::from_secs_f64(timeout);
+        future_into_py(py, async move {
+            let transport = BacnetIpTransport::bind(bind)
+                .await
+                .map_err(|err| py_err(err.into()))?;
+            Ok(PyBacnetClient {
+                inner: BacnetClient::with_datalink(transport).with_response_timeout(timeout),
+            })
+        })
+    }
+
+    /// Registers as a foreign device with the BBMD at `bbmd` (`"ip:port"`).
+    #[staticmethod]
+    #[pyo3(signature = (bbmd, ttl = 60, timeout = 3.0))]
+    fn connect_foreign<'py>(
+        py: Python<'py>,
+        bbmd: &str,
+        ttl: u16,
+        timeout: f64,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let bbmd = parse_addr(bbmd)?;
+        let timeout = Duration::from_secs_f64(timeout);
+        future_into_py(py, async move {
+            let client = BacnetClient::new_foreign(bbmd, ttl).await.map_err(py_err)?;
+            Ok(PyBacnetClient {
+                inner: client.with_response_timeout(timeout),
+            })
+        })
+    }
+
+    /// Broadcasts Who-Is and returns `[{"device_instance", "address"}]` after `wait`
+    /// seconds.
+    #[pyo3(signature = (low = None, high = None, wait = 3.0))]
+    fn who_is<'py>(
+        &self,
+        py: Python<'py>,
+        low: Option<u32>,
+        high: Option<u32>,
+        wait: f64,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let range = match (low, high) {
+            (Some(low), Some(high)) => Some((low, high)),
+            (None, None) => None,
+            _ => return Err(PyValueError::new_err("low and high must be given together")),
+        };
+        let client = self.inner.clone();
+        future_into_py(py, async move {
+            let devices = client
+                .who_is(range, Duration::from_secs_f64(wait))
+                .await
+                .map_err(py_err)?;
+            Python::with_gil(|py| {
+                devices
+                    .into_iter()
+                    .map(|device| {
+                        let dict = PyDict::new(py);
+                        dict.set_item("device_instance", device.device_id.map(|id| id.instance()))?;
+                        dict.set_item("address", device.address.to_string())?;
+                        Ok(dict.into_any().unbind())
+                    })
+                    .collect::<PyResult<Vec<PyObject>>>()
+            })
+        })
+    }
+
+    /// Reads one property.
+    fn read_property<'py>(
+        &self,
+        py: Python<'py>,
+        address: &str,
+        object_type: u16,
+        instance: u32,
+        property_id: u32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let address = DataLinkAddress::Ip(parse_addr(address)?);
+        let client = self.inner.clone();
+        future_into_py(py, async move {
+            let value = client
+                .read_property(
+                    address,
+                    object_id(object_type, instance),
+                    PropertyId::from_u32(property_id),
+                )
+                .await
+                .map_err(py_err)?;
+            Python::with_gil(|py| value_to_py(py, value))
+        })
+    }
+
+    /// Reads several properties of one object with ReadPropertyMultiple, falling back to
+    /// single reads. Returns `{property_id: value}`.
+    fn read_properties<'py>(
+        &self,
+        py: Python<'py>,
+        address: &str,
+        object_type: u16,
+        instance: u32,
+        property_ids: Vec<u32>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let address = DataLinkAddress::Ip(parse_addr(address)?);
+        let property_ids: Vec<PropertyId> =
+            property_ids.into_iter().map(PropertyId::from_u32).collect();
+        let client = self.inner.clone();
+        future_into_py(py, async move {
+            let values = client
+                .read_properties_with_fallback(
+                    address,
+                    object_id(object_type, instance),
+                    &property_ids,
+                )
+                .await
+                .map_err(py_err)?;
+            Python::with_gil(|py| {
+                let dict = PyDict::new(py);
+                for (property_id, value) in values {
+                    dict.set_item(property_id.to_u32(), value_to_py(py, value)?)?;
+                }
+                Ok(dict.into_any().unbind())
+            })
+        })
+    }
+
+    /// Writes one property. `datatype` forces the BACnet type (see module docs);
+    /// `priority` is 1-16.
+    #[pyo3(signature = (address, object_type, instance, property_id, value, datatype = None, priority = None))]
+    #[allow(clippy::too_many_arguments)]
+    fn write_property<'py>(
+        &self,
+        py: Python<'py>,
+        address: &str,
+        object_type: u16,
+        instance: u32,
+        property_id: u32,
+        value: &Bound<'py, PyAny>,
+        datatype: Option<&str>,
+        priority: Option<u8>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let address = DataLinkAddress::Ip(parse_addr(address)?);
+        if priority.is_some_and(|p| !(1..=16).contains(&p)) {
+            return Err(PyValueError::new_err("priority must be 1-16"));
+        }
+        let value = WriteValue::from_py(value, datatype)?;
+        let client = self.inner.clone();
+        future_into_py(py, async move {
+            let request = WritePropertyRequest {
+                object_id: object_id(object_type, instance),
+                property_id: PropertyId::from_u32(property_id),
+                value: value.as_data_value(),
+                priority,
+                ..Default::default()
+            };
+            client
+                .write_property(address, request)
+                .await
+                .map_err(py_err)
+        })
+    }
+
+    /// Subscribes to COV notifications for an object and returns an async iterator of
+    /// `{"address", "object_type", "instance", "values", "polled"}` updates. The
+    /// subscription is renewed automatically and falls back to polling when the device
+    /// stops notifying.
+    #[pyo3(signature = (address, object_type, instance, lifetime = 300, confirmed = false))]
+    fn subscribe_cov(
+        &self,
+        address: &str,
+        object_type: u16,
+        instance: u32,
+        lifetime: u32,
+        confirmed: bool,
+    ) -> PyResult<PyCovSubscription> {
+        let spec = CovSubscriptionSpec {
+            address: DataLinkAddress::Ip(parse_addr(address)?),
+            object_id: object_id(object_type, instance),
+            property_id: None,
+            lifetime_seconds: lifetime,
+            cov_increment: None,
+            confirmed,
+            subscriber_process_id: NEXT_PROCESS_ID.fetch_add(1, Ordering::Relaxed),
+            mode: CovMode::Subscribe,
+        };
+        let _runtime = pyo3_async_runtimes::tokio::get_runtime().enter();
+        let manager = CovManagerBuilder::new(Arc::new(self.inner.clone()))
+            .subscribe(spec)
+            .build()
+            .map_err(py_err)?;
+        Ok(PyCovSubscription {
+            manager: Arc::new(Mutex::new(Some(manager))),
+            closed: watch::channel(false).0,
+        })
+    }
+}
+
+/// Async iterator over COV updates; see `BacnetClient.subscribe_cov`.
+#[pyclass(name = "CovSubscription", module = "rustbac", frozen)]
+struct PyCovSubscription {
+    manager: Arc<Mutex<Option<CovManager>>>,
+    closed: watch::Sender<bool>,
+}
+
+#[pymethods]
+impl PyCovSubscription {
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let manager = self.manager.clone();
+        let mut closed = self.closed.subscribe();
+        future_into_py(py, async move {
+            let mut guard = manager.lock().await;
+            let update = match guard.as_mut() {
+                Some(manager) => tokio::select! {
+                    update = manager.recv() => update,
+                    _ = closed.wait_for(|closed| *closed) => None,
+                },
+                None => None,
+            };
+            match update {
+                Some(update) => Python::with_gil(|py| update_to_py(py, update)),
+                None => {
+                    // Dropping the manager stops its renewal and polling tasks.
+                    guard.take();
+                    Err(PyStopAsyncIteration::new_err(()))
+                }
+            }
+        })
+    }
+
+    /// Stops the subscription; pending and later iterations end.
+    fn close(&self) {
+        self.closed.send_replace(true);
+        if let Ok(mut guard) = self.manager.try_lock() {
+            guard.take();
+        }
+    }
+}
+
+#[pymodule]
+fn rustbac(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyBacnetClient>()?;
+    m.add_class::<PyCovSubscription>()?;
+    m.add("BacnetError", m.py().get_type::<BacnetError>())?;
+    Ok(())
+}
@@ -164,12 +164,11 @@ impl MstpTransport {
             .parity(tokio_serial::Parity::None)
             .stop_bits(tokio_serial::StopBits::One);
 
-        let port = tokio_serial::SerialStream::open(&builder).map_err(|e| {
-            DataLinkError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Failed to open serial port {}: {e}", config.port),
-            ))
-        })?;
+        let port =
+            tokio_serial::SerialStream::open(&builder).map_err(|e| DataLinkError::Connect {
+                target: config.port.clone(),
+                source: Box::new(e),
+            })?;
 
         let state = TokenState::new(config.mac_address, config.max_master);
 
@@ -204,18 +203,8 @@ impl MstpTransport {
         frame: &MstpFrame,
     ) -> Result<(), DataLinkError> {
         let encoded = frame.encode();
-        port.write_all(&encoded).await.map_err(|e| {
-            DataLinkError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Serial write failed: {e}"),
-            ))
-        })?;
-        port.flush().await.map_err(|e| {
-            DataLinkError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Serial flush failed: {e}"),
-            ))
-        })?;
+        port.write_all(&encoded).await?;
+        port.flush().await?;
         Ok(())
     }
 
@@ -323,20 +312,12 @@ impl MstpTransport {
             let mut tmp = [0u8; 512];
             match tokio::time::timeout(remaining, port.read(&mut tmp)).await {
                 Ok(Ok(0)) => {
-                    return Err(DataLinkError::Io(std::io::Error::new(
-                        std::io::ErrorKind::UnexpectedEof,
-                        "Serial port closed",
-                    )));
+                    return Err(DataLinkError::Disconnected);
                 }
                 Ok(Ok(n)) => {
                     rx_buf.extend_from_slice(&tmp[..n]);
                 }
-                Ok(Err(e)) => {
-                    return Err(DataLinkError::Io(std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        format!("Serial read failed: {e}"),
-                    )));
-                }
+                Ok(Err(e)) => return Err(DataLinkError::Io(e)),
                 Err(_) => {
                     // Timeout expired
                     return Ok(None);
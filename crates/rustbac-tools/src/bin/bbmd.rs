@@ -0,0 +1,233 @@
+use clap::{Parser, Subcommand};
+use rustbac_client::{BacnetClient, BroadcastDistributionEntry, ForeignDeviceTableEntry};
+use rustbac_tools::parse_bdt_entry;
+use std::io::{BufRead, Write};
+use std::net::{SocketAddr, SocketAddrV4};
+use std::path::{Path, PathBuf};
+
+/// Inspect and manage a BBMD's Broadcast Distribution Table and Foreign Device Table.
+///
+/// BDT files list one `ip:port/mask` entry per line; FDT files list one `ip:port` per
+/// line. Blank lines and text after `#` are ignored. `read-bdt` output is itself a valid
+/// BDT file. Diffs mark entries only in the file with `-` and entries only on the BBMD
+/// with `+`.
+#[derive(Parser, Debug)]
+#[command(name = "bacnet-bbmd")]
+struct Args {
+    #[arg(long)]
+    bbmd: SocketAddr,
+    #[arg(long, default_value_t = 60)]
+    foreign_ttl: u16,
+    #[arg(long)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the Broadcast Distribution Table.
+    ReadBdt,
+    /// Print the Foreign Device Table.
+    ReadFdt,
+    /// Replace the Broadcast Distribution Table with the entries in a file.
+    WriteBdt {
+        file: PathBuf,
+        /// Write without asking for confirmation.
+        #[arg(long, short)]
+        yes: bool,
+    },
+    /// Compare the Broadcast Distribution Table with a file; exits 1 on differences.
+    DiffBdt { file: PathBuf },
+    /// Compare the registered foreign devices with a file; exits 1 on differences.
+    DiffFdt { file: PathBuf },
+    /// Remove a foreign device from the Foreign Device Table.
+    DeleteFdt {
+        address: SocketAddrV4,
+        /// Delete without asking for confirmation.
+        #[arg(long, short)]
+        yes: bool,
+    },
+}
+
+fn format_bdt_entry(entry: &BroadcastDistributionEntry) -> String {
+    format!("{}/{}", entry.address, entry.mask)
+}
+
+/// Reads the non-empty, non-comment lines of `path`, parsing each with `parse`.
+fn load<T>(
+    path: &Path,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<Vec<T>, Box<dyn std::error::Error>> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    let mut entries = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let entry = parse(line).map_err(|e| format!("{}:{}: {e}", path.display(), index + 1))?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Entries of `before` missing from `after`, and entries of `after` missing from `before`.
+fn diff<T: PartialEq + Clone>(before: &[T], after: &[T]) -> (Vec<T>, Vec<T>) {
+    let removed = before
+        .iter()
+        .filter(|e| !after.contains(e))
+        .cloned()
+        .collect();
+    let added = after
+        .iter()
+        .filter(|e| !before.contains(e))
+        .cloned()
+        .collect();
+    (removed, added)
+}
+
+fn print_diff(removed: &[String], added: &[String], json: bool) {
+    if json {
+        let report = serde_json::json!({ "removed": removed, "added": added });
+        println!("{report:#}");
+    } else if removed.is_empty() && added.is_empty() {
+        println!("no differences");
+    } else {
+        for entry in removed {
+            println!("- {entry}");
+        }
+        for entry in added {
+            println!("+ {entry}");
+        }
+    }
+}
+
+fn confirm(prompt: &str) -> std::io::Result<bool> {
+    print!("{prompt} [y/N] ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+fn fdt_json(entries: &[ForeignDeviceTableEntry]) -> serde_json::Value {
+    entries
+        .iter()
+        .map(|e| {
+            serde_json::json!({
+                "address": e.address.to_string(),
+                "ttl_seconds": e.ttl_seconds,
+                "remaining_seconds": e.remaining_seconds,
+            })
+        })
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+    let args = Args::parse();
+    let client = BacnetClient::new_foreign(args.bbmd, args.foreign_ttl).await?;
+
+    match args.command {
+        Command::ReadBdt => {
+            let entries = client.read_broadcast_distribution_table().await?;
+            let lines: Vec<String> = entries.iter().map(format_bdt_entry).collect();
+            if args.json {
+                println!("{:#}", serde_json::json!(lines));
+            } else if lines.is_empty() {
+                println!("# bdt is empty");
+            } else {
+                for line in lines {
+                    println!("{line}");
+                }
+            }
+        }
+        Command::ReadFdt => {
+            let entries = client.read_foreign_device_table().await?;
+            if args.json {
+                println!("{:#}", fdt_json(&entries));
+            } else if entries.is_empty() {
+                println!("fdt is empty");
+            } else {
+                for e in entries {
+                    println!(
+                        "{} ttl={}s remaining={}s",
+                        e.address, e.ttl_seconds, e.remaining_seconds
+                    );
+                }
+            }
+        }
+        Command::WriteBdt { file, yes } => {
+            let wanted = load(&file, parse_bdt_entry)?;
+            let current = client.read_broadcast_distribution_table().await?;
+            let (removed, added) = diff(&current, &wanted);
+            if removed.is_empty() && added.is_empty() && current.len() == wanted.len() {
+                println!("bdt already matches {}", file.display());
+                return Ok(());
+            }
+            let removed: Vec<String> = removed.iter().map(format_bdt_entry).collect();
+            let added: Vec<String> = added.iter().map(format_bdt_entry).collect();
+            print_diff(&removed, &added, false);
+            if !yes && !confirm(&format!("write {} bdt entries?", wanted.len()))? {
+                println!("aborted");
+                std::process::exit(1);
+            }
+            client.write_broadcast_distribution_table(&wanted).await?;
+            // Read back: some BBMDs silently truncate or reorder tables.
+            let written = client.read_broadcast_distribution_table().await?;
+            let (missing, unexpected) = diff(&wanted, &written);
+            if !missing.is_empty() || !unexpected.is_empty() {
+                return Err(format!(
+                    "bdt read back differs from written table ({} missing, {} unexpected)",
+                    missing.len(),
+                    unexpected.len()
+                )
+                .into());
+            }
+            println!("wrote {} bdt entries", wanted.len());
+        }
+        Command::DiffBdt { file } => {
+            let wanted = load(&file, parse_bdt_entry)?;
+            let current = client.read_broadcast_distribution_table().await?;
+            let (removed, added) = diff(&wanted, &current);
+            let removed: Vec<String> = removed.iter().map(format_bdt_entry).collect();
+            let added: Vec<String> = added.iter().map(format_bdt_entry).collect();
+            print_diff(&removed, &added, args.json);
+            if !removed.is_empty() || !added.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Command::DiffFdt { file } => {
+            let wanted = load(&file, |line| {
+                line.parse::<SocketAddrV4>()
+                    .map_err(|e| format!("invalid address '{line}': {e}"))
+            })?;
+            // Compare addresses only; TTLs and remaining time change continuously.
+            let current: Vec<SocketAddrV4> = client
+                .read_foreign_device_table()
+                .await?
+                .iter()
+                .map(|e| e.address)
+                .collect();
+            let (removed, added) = diff(&wanted, &current);
+            let removed: Vec<String> = removed.iter().map(ToString::to_string).collect();
+            let added: Vec<String> = added.iter().map(ToString::to_string).collect();
+            print_diff(&removed, &added, args.json);
+            if !removed.is_empty() || !added.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Command::DeleteFdt { address, yes } => {
+            if !yes && !confirm(&format!("delete fdt entry {address}?"))? {
+                println!("aborted");
+                std::process::exit(1);
+            }
+            client.delete_foreign_device_table_entry(address).await?;
+            println!("deleted fdt entry {address}");
+        }
+    }
+    Ok(())
+}
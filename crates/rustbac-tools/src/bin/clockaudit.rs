@@ -0,0 +1,173 @@
+use clap::Parser;
+use rustbac_client::{BacnetClient, ClockAuditEntry, ClockAuditReport};
+use rustbac_core::types::{Date, Time};
+use rustbac_datalink::DataLinkAddress;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+#[derive(Parser, Debug)]
+#[command(name = "bacnet-clockaudit")]
+struct Args {
+    /// Device to check, as `ip:port`; repeat for several devices.
+    #[arg(long = "target", required = true)]
+    targets: Vec<SocketAddr>,
+    /// Drift from the host clock, in seconds, reported as a problem.
+    #[arg(long, default_value_t = 5.0)]
+    threshold_seconds: f64,
+    /// UTC offset every device should have, in minutes local time is behind UTC with
+    /// daylight saving included (e.g. 300 for US Eastern standard time).
+    #[arg(long, allow_hyphen_values = true)]
+    expect_utc_offset: Option<i32>,
+    #[arg(long, default_value_t = 3000)]
+    timeout_ms: u64,
+    #[arg(long)]
+    bbmd: Option<SocketAddr>,
+    #[arg(long, default_value_t = 60)]
+    foreign_ttl: u16,
+    #[arg(long)]
+    json: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+    let args = Args::parse();
+    let client = match args.bbmd {
+        Some(bbmd) => BacnetClient::new_foreign(bbmd, args.foreign_ttl).await?,
+        None => BacnetClient::new().await?,
+    }
+    .with_response_timeout(Duration::from_millis(args.timeout_ms));
+    let targets: Vec<DataLinkAddress> = args
+        .targets
+        .iter()
+        .copied()
+        .map(DataLinkAddress::Ip)
+        .collect();
+    let threshold = Duration::from_secs_f64(args.threshold_seconds.max(0.0));
+    let report = client.audit_device_clocks(&targets, threshold).await;
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report_json(&report, args.expect_utc_offset))?
+        );
+    } else {
+        print_report(&report, args.expect_utc_offset);
+    }
+    let offsets_ok = args.expect_utc_offset.map_or(true, |expected| {
+        report.offset_mismatches(expected).next().is_none()
+    });
+    if !report.is_clean() || !offsets_ok {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn format_date_time(date: Date, time: Time) -> String {
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:02}",
+        1900 + u16::from(date.year_since_1900),
+        date.month,
+        date.day,
+        time.hour,
+        time.minute,
+        time.second,
+        time.hundredths
+    )
+}
+
+fn problems(
+    entry: &ClockAuditEntry,
+    report: &ClockAuditReport,
+    expected: Option<i32>,
+) -> Vec<&'static str> {
+    let mut problems = Vec::new();
+    if report
+        .drifted()
+        .any(|drifted| drifted.address == entry.address)
+    {
+        problems.push("drift");
+    }
+    if entry.drift_millis.is_none() {
+        problems.push("unchecked");
+    }
+    if expected.is_some_and(|expected| {
+        report
+            .offset_mismatches(expected)
+            .any(|m| m.address == entry.address)
+    }) {
+        problems.push("utc-offset");
+    }
+    problems
+}
+
+fn print_report(report: &ClockAuditReport, expected: Option<i32>) {
+    for entry in &report.clocks {
+        let offset = entry
+            .clock
+            .utc_offset_minutes
+            .map_or_else(|| "-".to_string(), |offset| offset.to_string());
+        let dst = match entry.clock.daylight_savings {
+            Some(true) => "on",
+            Some(false) => "off",
+            None => "-",
+        };
+        let drift = entry.drift_millis.map_or_else(
+            || "unknown".to_string(),
+            |drift| format!("{:+.2} s", drift as f64 / 1000.0),
+        );
+        let problems = problems(entry, report, expected);
+        println!(
+            "{}: local {} utc-offset {offset} dst {dst} drift {drift}{}",
+            entry.address,
+            format_date_time(entry.clock.date, entry.clock.time),
+            if problems.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", problems.join(", "))
+            }
+        );
+    }
+    for failure in &report.failed {
+        println!("{}: unreadable: {}", failure.address, failure.error);
+    }
+    println!(
+        "{} checked, {} drifted beyond {:.1} s, {} unreadable",
+        report.clocks.len(),
+        report.drifted().count(),
+        report.threshold.as_secs_f64(),
+        report.failed.len()
+    );
+}
+
+fn report_json(report: &ClockAuditReport, expected: Option<i32>) -> serde_json::Value {
+    let clocks: Vec<serde_json::Value> = report
+        .clocks
+        .iter()
+        .map(|entry| {
+            serde_json::json!({
+                "address": entry.address.to_string(),
+                "local": format_date_time(entry.clock.date, entry.clock.time),
+                "utc_offset_minutes": entry.clock.utc_offset_minutes,
+                "daylight_savings": entry.clock.daylight_savings,
+                "drift_ms": entry.drift_millis,
+                "problems": problems(entry, report, expected),
+            })
+        })
+        .collect();
+    let failed: Vec<serde_json::Value> = report
+        .failed
+        .iter()
+        .map(|failure| {
+            serde_json::json!({
+                "address": failure.address.to_string(),
+                "error": failure.error.to_string(),
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "threshold_ms": report.threshold.as_millis() as u64,
+        "clocks": clocks,
+        "failed": failed,
+    })
+}
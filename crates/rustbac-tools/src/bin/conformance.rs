@@ -0,0 +1,467 @@
+use clap::Parser;
+use rustbac_client::{BacnetClient, ClientDataValue, ClientError};
+use rustbac_core::services::subscribe_cov::SubscribeCovRequest;
+use rustbac_core::types::{ErrorClass, ErrorCode, ObjectId, ObjectType, PropertyId, Segmentation};
+use rustbac_datalink::{BacnetIpTransport, DataLinkAddress};
+use rustbac_tools::ObjectTypeArg;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+/// Abort reason sent by devices that cannot segment a response.
+const ABORT_SEGMENTATION_NOT_SUPPORTED: u8 = 4;
+/// Largest valid property identifier; no device defines it.
+const UNDEFINED_PROPERTY: u32 = 4_194_302;
+/// Largest instance that is not the wildcard; no device defines it.
+const UNDEFINED_INSTANCE: u32 = 4_194_302;
+/// Max_APDU_Length_Accepted values permitted by the standard.
+const VALID_MAX_APDU: [u32; 6] = [50, 128, 206, 480, 1024, 1476];
+
+/// Device properties required by the standard that decode as a single value.
+const REQUIRED_DEVICE_PROPERTIES: [(&str, u32); 17] = [
+    ("object-identifier", 75),
+    ("object-name", 77),
+    ("object-type", 79),
+    ("system-status", 112),
+    ("vendor-name", 121),
+    ("vendor-identifier", 120),
+    ("model-name", 70),
+    ("firmware-revision", 44),
+    ("application-software-version", 12),
+    ("protocol-version", 98),
+    ("protocol-revision", 139),
+    ("protocol-services-supported", 97),
+    ("protocol-object-types-supported", 96),
+    ("max-apdu-length-accepted", 62),
+    ("segmentation-supported", 107),
+    ("apdu-timeout", 11),
+    ("number-of-apdu-retries", 73),
+];
+
+#[derive(Parser, Debug)]
+#[command(name = "bacnet-conformance")]
+struct Args {
+    #[arg(long)]
+    ip: IpAddr,
+    #[arg(long, default_value_t = 47808)]
+    port: u16,
+    #[arg(long)]
+    instance: u32,
+    /// Object used for the COV checks; skipped when the device rejects SubscribeCOV.
+    #[arg(long, value_enum, default_value = "analog-input")]
+    cov_object_type: ObjectTypeArg,
+    #[arg(long, default_value_t = 0)]
+    cov_instance: u32,
+    #[arg(long, default_value_t = 60)]
+    cov_lifetime_seconds: u32,
+    #[arg(long, default_value_t = 5)]
+    timeout_secs: u64,
+    #[arg(long)]
+    bbmd: Option<SocketAddr>,
+    #[arg(long, default_value_t = 60)]
+    foreign_ttl: u16,
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Pass,
+    Fail,
+    Skip,
+}
+
+impl Outcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Fail => "fail",
+            Self::Skip => "skip",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Report {
+    checks: Vec<(String, Outcome, String)>,
+}
+
+impl Report {
+    fn record(&mut self, name: impl Into<String>, outcome: Outcome, detail: impl Into<String>) {
+        self.checks.push((name.into(), outcome, detail.into()));
+    }
+
+    fn count(&self, outcome: Outcome) -> usize {
+        self.checks.iter().filter(|(_, o, _)| *o == outcome).count()
+    }
+}
+
+/// Formats an error with the remote error class/code, which `Display` omits.
+fn describe(e: &ClientError) -> String {
+    match e.root() {
+        ClientError::RemoteServiceError {
+            error_class_raw,
+            error_code_raw,
+            ..
+        } => format!("Error PDU class={error_class_raw:?} code={error_code_raw:?}"),
+        other => other.to_string(),
+    }
+}
+
+struct Target<'a> {
+    client: &'a BacnetClient<BacnetIpTransport>,
+    addr: DataLinkAddress,
+    device_id: ObjectId,
+}
+
+async fn check_required_properties(t: &Target<'_>, report: &mut Report) {
+    for (name, raw) in REQUIRED_DEVICE_PROPERTIES {
+        let check = format!("device/{name}");
+        match t
+            .client
+            .read_property(t.addr, t.device_id, PropertyId::from_u32(raw))
+            .await
+        {
+            Ok(value) => {
+                let problem = match (raw, &value) {
+                    (75, ClientDataValue::ObjectId(id)) if *id != t.device_id => {
+                        Some(format!("expected {:?}, got {id:?}", t.device_id))
+                    }
+                    (79, ClientDataValue::Enumerated(v))
+                        if *v != ObjectType::Device.to_u16() as u32 =>
+                    {
+                        Some(format!("expected device (8), got {v}"))
+                    }
+                    (77, ClientDataValue::CharacterString(s)) if s.is_empty() => {
+                        Some("object-name is empty".to_string())
+                    }
+                    _ => None,
+                };
+                match problem {
+                    Some(problem) => report.record(check, Outcome::Fail, problem),
+                    None => report.record(check, Outcome::Pass, format!("{value:?}")),
+                }
+            }
+            Err(e) => report.record(check, Outcome::Fail, describe(&e)),
+        }
+    }
+}
+
+fn expect_error(
+    report: &mut Report,
+    check: &str,
+    result: Result<ClientDataValue, ClientError>,
+    class: ErrorClass,
+    code: ErrorCode,
+) {
+    match result.map_err(ClientError::into_root) {
+        Err(ClientError::RemoteServiceError {
+            error_class,
+            error_code,
+            ..
+        }) if error_class == Some(class) && error_code == Some(code) => {
+            report.record(check, Outcome::Pass, format!("{class:?}/{code:?}"));
+        }
+        Err(ClientError::RemoteServiceError {
+            error_class_raw,
+            error_code_raw,
+            ..
+        }) => report.record(
+            check,
+            Outcome::Fail,
+            format!(
+                "expected {class:?}/{code:?}, got class={error_class_raw:?} code={error_code_raw:?}"
+            ),
+        ),
+        Err(e) => report.record(
+            check,
+            Outcome::Fail,
+            format!("expected Error PDU, got {}", describe(&e)),
+        ),
+        Ok(value) => report.record(
+            check,
+            Outcome::Fail,
+            format!("expected Error PDU, got {value:?}"),
+        ),
+    }
+}
+
+async fn check_error_behavior(t: &Target<'_>, report: &mut Report) {
+    let result = t
+        .client
+        .read_property(
+            t.addr,
+            t.device_id,
+            PropertyId::from_u32(UNDEFINED_PROPERTY),
+        )
+        .await;
+    expect_error(
+        report,
+        "error/unknown-property",
+        result,
+        ErrorClass::Property,
+        ErrorCode::UnknownProperty,
+    );
+
+    let result = t
+        .client
+        .read_property(
+            t.addr,
+            ObjectId::new(ObjectType::AnalogInput, UNDEFINED_INSTANCE),
+            PropertyId::ObjectName,
+        )
+        .await;
+    expect_error(
+        report,
+        "error/unknown-object",
+        result,
+        ErrorClass::Object,
+        ErrorCode::UnknownObject,
+    );
+}
+
+async fn check_segmentation(t: &Target<'_>, report: &mut Report) {
+    match t
+        .client
+        .read_property(t.addr, t.device_id, PropertyId::MaxApduLengthAccepted)
+        .await
+    {
+        Ok(ClientDataValue::Unsigned(n)) if VALID_MAX_APDU.contains(&n) => {
+            report.record("segmentation/max-apdu", Outcome::Pass, n.to_string())
+        }
+        Ok(other) => report.record(
+            "segmentation/max-apdu",
+            Outcome::Fail,
+            format!("not a standard APDU size: {other:?}"),
+        ),
+        Err(e) => report.record("segmentation/max-apdu", Outcome::Fail, describe(&e)),
+    }
+
+    let segmentation = match t
+        .client
+        .read_property(t.addr, t.device_id, PropertyId::SegmentationSupported)
+        .await
+    {
+        Ok(ClientDataValue::Enumerated(v)) => match Segmentation::from_u32(v) {
+            Some(segmentation) => {
+                report.record(
+                    "segmentation/supported",
+                    Outcome::Pass,
+                    format!("{segmentation:?}"),
+                );
+                segmentation
+            }
+            None => {
+                report.record(
+                    "segmentation/supported",
+                    Outcome::Fail,
+                    format!("unknown value {v}"),
+                );
+                return;
+            }
+        },
+        Ok(other) => {
+            report.record(
+                "segmentation/supported",
+                Outcome::Fail,
+                format!("{other:?}"),
+            );
+            return;
+        }
+        Err(e) => {
+            report.record("segmentation/supported", Outcome::Fail, describe(&e));
+            return;
+        }
+    };
+
+    // Far larger than any unsegmented APDU: either the device segments the response or it
+    // must abort with segmentation-not-supported.
+    let properties: Vec<PropertyId> = [
+        PropertyId::ObjectName,
+        PropertyId::VendorName,
+        PropertyId::ModelName,
+        PropertyId::FirmwareRevision,
+        PropertyId::ApplicationSoftwareVersion,
+        PropertyId::ProtocolVersion,
+        PropertyId::ProtocolRevision,
+    ]
+    .into_iter()
+    .cycle()
+    .take(210)
+    .collect();
+    let transmits = matches!(
+        segmentation,
+        Segmentation::SegmentedBoth | Segmentation::SegmentedTransmit
+    );
+    let check = "segmentation/large-response";
+    match t
+        .client
+        .read_property_multiple(t.addr, t.device_id, &properties)
+        .await
+        .map_err(ClientError::into_root)
+    {
+        Ok(values) if values.len() == properties.len() => report.record(
+            check,
+            Outcome::Pass,
+            format!("{} values reassembled", values.len()),
+        ),
+        Ok(values) => report.record(
+            check,
+            Outcome::Fail,
+            format!(
+                "requested {} values, got {}",
+                properties.len(),
+                values.len()
+            ),
+        ),
+        Err(ClientError::RemoteAbort {
+            reason: ABORT_SEGMENTATION_NOT_SUPPORTED,
+            ..
+        }) if !transmits => report.record(
+            check,
+            Outcome::Pass,
+            "aborted with segmentation-not-supported",
+        ),
+        Err(e) => report.record(check, Outcome::Fail, describe(&e)),
+    }
+}
+
+async fn check_cov(t: &Target<'_>, args: &Args, report: &mut Report) {
+    let object_id = ObjectId::new(
+        args.cov_object_type.clone().into_object_type(),
+        args.cov_instance,
+    );
+    let process_id = 0x00C0_FFEE;
+    let subscribe = t
+        .client
+        .subscribe_cov(
+            t.addr,
+            SubscribeCovRequest {
+                subscriber_process_id: process_id,
+                monitored_object_id: object_id,
+                issue_confirmed_notifications: Some(false),
+                lifetime_seconds: Some(args.cov_lifetime_seconds),
+                invoke_id: 0,
+            },
+        )
+        .await;
+    match subscribe.map_err(ClientError::into_root) {
+        Ok(()) => report.record("cov/subscribe", Outcome::Pass, format!("{object_id:?}")),
+        Err(e @ (ClientError::RemoteServiceError { .. } | ClientError::RemoteReject { .. })) => {
+            report.record(
+                "cov/subscribe",
+                Outcome::Skip,
+                format!("not supported: {}", describe(&e)),
+            );
+            return;
+        }
+        Err(e) => {
+            report.record("cov/subscribe", Outcome::Fail, describe(&e));
+            return;
+        }
+    }
+
+    let wait = Duration::from_secs(args.timeout_secs);
+    match t.client.recv_cov_notification(wait).await {
+        Ok(Some(n))
+            if n.subscriber_process_id != process_id || n.monitored_object_id != object_id =>
+        {
+            report.record(
+                "cov/initial-notification",
+                Outcome::Fail,
+                format!(
+                    "unexpected notification for process {} object {:?}",
+                    n.subscriber_process_id, n.monitored_object_id
+                ),
+            )
+        }
+        Ok(Some(n)) => {
+            report.record("cov/initial-notification", Outcome::Pass, "received");
+            let remaining = n.time_remaining_seconds;
+            let outcome = if remaining > 0 && remaining <= args.cov_lifetime_seconds {
+                Outcome::Pass
+            } else {
+                Outcome::Fail
+            };
+            report.record(
+                "cov/time-remaining",
+                outcome,
+                format!("{remaining}s of {}s", args.cov_lifetime_seconds),
+            );
+        }
+        Ok(None) => report.record(
+            "cov/initial-notification",
+            Outcome::Fail,
+            format!("none within {wait:?}"),
+        ),
+        Err(e) => report.record("cov/initial-notification", Outcome::Fail, describe(&e)),
+    }
+
+    match t
+        .client
+        .cancel_cov_subscription(t.addr, process_id, object_id)
+        .await
+    {
+        Ok(()) => report.record("cov/cancel", Outcome::Pass, ""),
+        Err(e) => report.record("cov/cancel", Outcome::Fail, describe(&e)),
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+    let args = Args::parse();
+    let client = match args.bbmd {
+        Some(bbmd) => BacnetClient::new_foreign(bbmd, args.foreign_ttl).await?,
+        None => BacnetClient::new().await?,
+    }
+    .with_response_timeout(Duration::from_secs(args.timeout_secs));
+
+    let target = Target {
+        client: &client,
+        addr: DataLinkAddress::Ip((args.ip, args.port).into()),
+        device_id: ObjectId::new(ObjectType::Device, args.instance),
+    };
+
+    let mut report = Report::default();
+    check_required_properties(&target, &mut report).await;
+    check_error_behavior(&target, &mut report).await;
+    check_segmentation(&target, &mut report).await;
+    check_cov(&target, &args, &mut report).await;
+
+    let failed = report.count(Outcome::Fail);
+    if args.json {
+        let checks: Vec<_> = report
+            .checks
+            .iter()
+            .map(|(name, outcome, detail)| {
+                serde_json::json!({ "check": name, "result": outcome.as_str(), "detail": detail })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "device": target.device_id.instance(),
+                "address": target.addr.to_string(),
+                "passed": report.count(Outcome::Pass),
+                "failed": failed,
+                "skipped": report.count(Outcome::Skip),
+                "checks": checks,
+            }))?
+        );
+    } else {
+        for (name, outcome, detail) in &report.checks {
+            println!("{:<4}  {name:<44} {detail}", outcome.as_str());
+        }
+        println!(
+            "\n{} passed, {} failed, {} skipped",
+            report.count(Outcome::Pass),
+            failed,
+            report.count(Outcome::Skip)
+        );
+    }
+
+    if failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
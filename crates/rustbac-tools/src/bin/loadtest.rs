@@ -0,0 +1,389 @@
+use clap::{Parser, ValueEnum};
+use rustbac_client::{BacnetClient, ClientError};
+use rustbac_core::services::subscribe_cov::SubscribeCovRequest;
+use rustbac_core::types::{ObjectId, PropertyId};
+use rustbac_datalink::DataLinkAddress;
+use rustbac_tools::ObjectTypeArg;
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Parser, Debug)]
+#[command(name = "bacnet-loadtest")]
+struct Args {
+    /// Device to load, as `ip:port`; repeat for several targets.
+    #[arg(long = "target", required = true)]
+    targets: Vec<SocketAddr>,
+    /// Services to send, in rotation; repeat to mix.
+    #[arg(long = "service", value_enum, default_values_t = [LoadService::ReadProperty])]
+    services: Vec<LoadService>,
+    #[arg(long, value_enum, default_value = "analog-input")]
+    object_type: ObjectTypeArg,
+    #[arg(long, default_value_t = 0)]
+    instance: u32,
+    /// Requests in flight at once, one per worker. The workers share one client, whose
+    /// requests wait for each other only while being sent.
+    #[arg(long, default_value_t = 8)]
+    concurrency: usize,
+    #[arg(long, default_value_t = 10)]
+    duration_seconds: u64,
+    /// Overall request rate cap in requests per second.
+    #[arg(long)]
+    rate: Option<u32>,
+    #[arg(long, default_value_t = 3000)]
+    timeout_ms: u64,
+    #[arg(long)]
+    bbmd: Option<SocketAddr>,
+    #[arg(long, default_value_t = 60)]
+    foreign_ttl: u16,
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum LoadService {
+    /// ReadProperty of Present_Value.
+    ReadProperty,
+    /// ReadPropertyMultiple of Present_Value, Status_Flags and Object_Name.
+    Rpm,
+    /// SubscribeCOV (unconfirmed notifications, 60 s lifetime).
+    Cov,
+}
+
+impl LoadService {
+    fn name(self) -> &'static str {
+        match self {
+            Self::ReadProperty => "read-property",
+            Self::Rpm => "rpm",
+            Self::Cov => "cov",
+        }
+    }
+}
+
+/// Lifetime of the COV subscriptions the load creates.
+const COV_LIFETIME_SECONDS: u32 = 60;
+
+/// Number of power-of-two microsecond buckets; the last also holds everything slower.
+const BUCKETS: usize = 25;
+
+/// Latencies in power-of-two microsecond buckets.
+#[derive(Debug, Clone)]
+struct LatencyHistogram {
+    buckets: [u64; BUCKETS],
+    count: u64,
+    total: Duration,
+    min: Duration,
+    max: Duration,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            buckets: [0; BUCKETS],
+            count: 0,
+            total: Duration::ZERO,
+            min: Duration::MAX,
+            max: Duration::ZERO,
+        }
+    }
+}
+
+impl LatencyHistogram {
+    fn record(&mut self, latency: Duration) {
+        let micros = latency.as_micros().max(1);
+        let bucket = (u128::BITS - 1 - micros.leading_zeros()) as usize;
+        self.buckets[bucket.min(BUCKETS - 1)] += 1;
+        self.count += 1;
+        self.total += latency;
+        self.min = self.min.min(latency);
+        self.max = self.max.max(latency);
+    }
+
+    /// Exclusive upper bound of a bucket.
+    fn bucket_bound(bucket: usize) -> Duration {
+        Duration::from_micros(1 << (bucket + 1))
+    }
+
+    fn mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        self.total.div_f64(self.count as f64)
+    }
+
+    /// Latency at or below which `quantile` of the samples fall, to bucket precision.
+    fn percentile(&self, quantile: f64) -> Duration {
+        let rank = ((self.count as f64) * quantile).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (bucket, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Self::bucket_bound(bucket).min(self.max);
+            }
+        }
+        self.max
+    }
+}
+
+/// Results for one service.
+#[derive(Debug, Default)]
+struct ServiceStats {
+    latency: LatencyHistogram,
+    /// Failures by error code (e.g. `client.timeout`).
+    errors: BTreeMap<&'static str, u64>,
+}
+
+#[derive(Debug, Default)]
+struct TargetStats {
+    ok: u64,
+    failed: u64,
+}
+
+#[derive(Debug, Default)]
+struct Results {
+    services: BTreeMap<LoadService, ServiceStats>,
+    targets: BTreeMap<SocketAddr, TargetStats>,
+}
+
+impl Results {
+    fn record(
+        &mut self,
+        service: LoadService,
+        target: SocketAddr,
+        latency: Duration,
+        outcome: Result<(), ClientError>,
+    ) {
+        let stats = self.services.entry(service).or_default();
+        let target = self.targets.entry(target).or_default();
+        match outcome {
+            Ok(()) => {
+                stats.latency.record(latency);
+                target.ok += 1;
+            }
+            Err(e) => {
+                *stats.errors.entry(e.code()).or_default() += 1;
+                target.failed += 1;
+            }
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+    let args = Args::parse();
+    let client = match args.bbmd {
+        Some(bbmd) => BacnetClient::new_foreign(bbmd, args.foreign_ttl).await?,
+        None => BacnetClient::new().await?,
+    }
+    .with_response_timeout(Duration::from_millis(args.timeout_ms));
+
+    let object_id = ObjectId::new(args.object_type.into_object_type(), args.instance);
+    let concurrency = args.concurrency.max(1);
+    let duration = Duration::from_secs(args.duration_seconds);
+    // Each worker sends at its share of the overall rate.
+    let pace = args
+        .rate
+        .filter(|&rate| rate > 0)
+        .map(|rate| Duration::from_secs_f64(concurrency as f64 / f64::from(rate)));
+    let results = Arc::new(Mutex::new(Results::default()));
+
+    let started = Instant::now();
+    let deadline = started + duration;
+    let mut workers = Vec::with_capacity(concurrency);
+    for worker in 0..concurrency {
+        let client = client.clone();
+        let targets = args.targets.clone();
+        let services = args.services.clone();
+        let results = results.clone();
+        workers.push(tokio::spawn(async move {
+            let process_id = worker as u32 + 1;
+            let mut ticker = pace.map(tokio::time::interval);
+            let mut n = worker;
+            let mut subscribed = Vec::new();
+            while Instant::now() < deadline {
+                if let Some(ticker) = ticker.as_mut() {
+                    ticker.tick().await;
+                }
+                let target = targets[n % targets.len()];
+                // Every target sees every service, even when the counts share a factor.
+                let service = services[(n / targets.len()) % services.len()];
+                n += 1;
+                let address = DataLinkAddress::Ip(target);
+                // Includes only a brief wait while other workers' requests go out; the
+                // answers are awaited concurrently.
+                let sent = Instant::now();
+                let outcome = match service {
+                    LoadService::ReadProperty => client
+                        .read_property(address, object_id, PropertyId::PresentValue)
+                        .await
+                        .map(drop),
+                    LoadService::Rpm => client
+                        .read_property_multiple(
+                            address,
+                            object_id,
+                            &[
+                                PropertyId::PresentValue,
+                                PropertyId::StatusFlags,
+                                PropertyId::ObjectName,
+                            ],
+                        )
+                        .await
+                        .map(drop),
+                    LoadService::Cov => {
+                        let outcome = client
+                            .subscribe_cov(
+                                address,
+                                SubscribeCovRequest {
+                                    subscriber_process_id: process_id,
+                                    monitored_object_id: object_id,
+                                    issue_confirmed_notifications: Some(false),
+                                    lifetime_seconds: Some(COV_LIFETIME_SECONDS),
+                                    invoke_id: 0,
+                                },
+                            )
+                            .await;
+                        if outcome.is_ok() && !subscribed.contains(&address) {
+                            subscribed.push(address);
+                        }
+                        outcome
+                    }
+                };
+                let latency = sent.elapsed();
+                results
+                    .lock()
+                    .expect("results lock poisoned")
+                    .record(service, target, latency, outcome);
+            }
+            // Leave no subscriptions behind on the targets.
+            for address in subscribed {
+                let _ = client
+                    .cancel_cov_subscription(address, process_id, object_id)
+                    .await;
+            }
+        }));
+    }
+    for worker in workers {
+        worker.await?;
+    }
+    let elapsed = started
+        .elapsed()
+        .min(duration)
+        .max(Duration::from_millis(1));
+
+    let results = results.lock().expect("results lock poisoned");
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report_json(&results, elapsed))?
+        );
+    } else {
+        print_report(&results, elapsed);
+    }
+    Ok(())
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn report_json(results: &Results, elapsed: Duration) -> serde_json::Value {
+    let services: serde_json::Map<String, serde_json::Value> = results
+        .services
+        .iter()
+        .map(|(service, stats)| {
+            let latency = &stats.latency;
+            let failed: u64 = stats.errors.values().sum();
+            let histogram: Vec<serde_json::Value> = latency
+                .buckets
+                .iter()
+                .enumerate()
+                .filter(|(_, count)| **count > 0)
+                .map(|(bucket, count)| {
+                    serde_json::json!({
+                        "le_ms": millis(LatencyHistogram::bucket_bound(bucket)),
+                        "count": count,
+                    })
+                })
+                .collect();
+            let value = serde_json::json!({
+                "ok": latency.count,
+                "failed": failed,
+                "per_second": (latency.count + failed) as f64 / elapsed.as_secs_f64(),
+                "latency_ms": {
+                    "min": if latency.count > 0 { millis(latency.min) } else { 0.0 },
+                    "mean": millis(latency.mean()),
+                    "p50": millis(latency.percentile(0.5)),
+                    "p90": millis(latency.percentile(0.9)),
+                    "p99": millis(latency.percentile(0.99)),
+                    "max": millis(latency.max),
+                },
+                "histogram": histogram,
+                "errors": stats.errors,
+            });
+            (service.name().to_string(), value)
+        })
+        .collect();
+    let targets: serde_json::Map<String, serde_json::Value> = results
+        .targets
+        .iter()
+        .map(|(target, stats)| {
+            (
+                target.to_string(),
+                serde_json::json!({ "ok": stats.ok, "failed": stats.failed }),
+            )
+        })
+        .collect();
+    serde_json::json!({
+        "elapsed_seconds": elapsed.as_secs_f64(),
+        "services": services,
+        "targets": targets,
+    })
+}
+
+fn print_report(results: &Results, elapsed: Duration) {
+    println!("Load test: {:.1} s", elapsed.as_secs_f64());
+    for (service, stats) in &results.services {
+        let latency = &stats.latency;
+        let failed: u64 = stats.errors.values().sum();
+        println!();
+        println!(
+            "{}: {} ok, {} failed, {:.1} req/s",
+            service.name(),
+            latency.count,
+            failed,
+            (latency.count + failed) as f64 / elapsed.as_secs_f64()
+        );
+        if latency.count > 0 {
+            println!(
+                "  latency ms: min {:.2}  mean {:.2}  p50 {:.2}  p90 {:.2}  p99 {:.2}  max {:.2}",
+                millis(latency.min),
+                millis(latency.mean()),
+                millis(latency.percentile(0.5)),
+                millis(latency.percentile(0.9)),
+                millis(latency.percentile(0.99)),
+                millis(latency.max)
+            );
+            let peak = latency.buckets.iter().copied().max().unwrap_or(1).max(1);
+            for (bucket, &count) in latency.buckets.iter().enumerate() {
+                if count == 0 {
+                    continue;
+                }
+                let bar = "#".repeat(((count * 40).div_ceil(peak)) as usize);
+                println!(
+                    "  < {:>9.3} ms {:>8} {bar}",
+                    millis(LatencyHistogram::bucket_bound(bucket)),
+                    count
+                );
+            }
+        }
+        for (code, count) in &stats.errors {
+            println!("  error {code}: {count}");
+        }
+    }
+    println!();
+    for (target, stats) in &results.targets {
+        println!("{target}: {} ok, {} failed", stats.ok, stats.failed);
+    }
+}
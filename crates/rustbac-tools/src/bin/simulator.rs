@@ -1,8 +1,9 @@
 use clap::Parser;
-use rustbac_client::{ClientDataValue, SimulatedDevice};
+use rustbac_client::{ClientDataValue, SimulatedDevice, SimulatorFaults};
 use rustbac_core::types::{ObjectId, ObjectType, PropertyId};
 use rustbac_datalink::bip::transport::BacnetIpTransport;
 use std::collections::HashMap;
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(name = "bacnet-simulator")]
@@ -16,6 +17,18 @@ struct Args {
     /// Number of binary-input objects to create.
     #[arg(long, default_value_t = 2)]
     binary_inputs: u32,
+    /// Delay every reply by this many milliseconds.
+    #[arg(long, default_value_t = 0)]
+    latency_ms: u64,
+    /// Add a random delay of up to this many milliseconds to every reply.
+    #[arg(long, default_value_t = 0)]
+    jitter_ms: u64,
+    /// Probability (0.0-1.0) of silently dropping a reply.
+    #[arg(long, default_value_t = 0.0)]
+    drop_probability: f64,
+    /// Seed for the jitter and drop decisions.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
 }
 
 #[tokio::main]
@@ -24,14 +37,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
     let bind_addr: std::net::SocketAddr = "0.0.0.0:47808".parse()?;
     let transport = BacnetIpTransport::bind(bind_addr).await?;
-    let sim = SimulatedDevice::new(args.instance, transport);
+    let sim = SimulatedDevice::new(args.instance, transport).with_faults(
+        SimulatorFaults::default()
+            .with_latency(Duration::from_millis(args.latency_ms))
+            .with_jitter(Duration::from_millis(args.jitter_ms))
+            .with_drop_probability(args.drop_probability)
+            .with_seed(args.seed),
+    );
 
     for i in 0..args.analog_inputs {
         let oid = ObjectId::new(ObjectType::AnalogInput, i);
         let mut props = HashMap::new();
         props.insert(
             PropertyId::ObjectName,
-            ClientDataValue::CharacterString(format!("AI-{i}")),
+            ClientDataValue::CharacterString(format!("AI-{i}").into()),
         );
         props.insert(PropertyId::PresentValue, ClientDataValue::Real(0.0));
         props.insert(
@@ -46,7 +65,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let mut props = HashMap::new();
         props.insert(
             PropertyId::ObjectName,
-            ClientDataValue::CharacterString(format!("BI-{i}")),
+            ClientDataValue::CharacterString(format!("BI-{i}").into()),
         );
         props.insert(PropertyId::PresentValue, ClientDataValue::Enumerated(0));
         props.insert(
@@ -1,5 +1,7 @@
 use clap::Parser;
-use rustbac_client::{walk::walk_device, BacnetClient};
+use futures_util::StreamExt;
+use rustbac_client::walk::{read_device_info, walk_device_stream, WalkOptions};
+use rustbac_client::{BacnetClient, DeviceWalkResult};
 use rustbac_core::types::{ObjectId, ObjectType};
 use rustbac_datalink::DataLinkAddress;
 use std::net::{IpAddr, SocketAddr};
@@ -13,6 +15,12 @@ struct Args {
     port: u16,
     #[arg(long)]
     instance: u32,
+    /// Objects read at once.
+    #[arg(long, default_value_t = 4)]
+    concurrency: usize,
+    /// Read each object with one ReadPropertyMultiple of ALL.
+    #[arg(long)]
+    read_all: bool,
     #[arg(long)]
     bbmd: Option<SocketAddr>,
     #[arg(long, default_value_t = 60)]
@@ -32,32 +40,47 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let addr = DataLinkAddress::Ip((args.ip, args.port).into());
     let device_id = ObjectId::new(ObjectType::Device, args.instance);
 
-    let result = walk_device(&client, addr, device_id).await;
-    match result {
-        Ok(walk) => {
-            if args.json {
-                println!("{}", serde_json::to_string_pretty(&walk)?);
-            } else {
-                println!(
-                    "Device {:?} — {} objects:",
-                    walk.device_id,
-                    walk.objects.len()
-                );
-                for obj in &walk.objects {
-                    let name = obj.object_name.as_deref().unwrap_or("?");
-                    let pv = obj
-                        .present_value
-                        .as_ref()
-                        .map(|v| format!("{v:?}"))
-                        .unwrap_or_default();
-                    println!("  {:?} \"{name}\" = {pv}", obj.object_id);
-                }
+    // Text output prints each object as it is read; JSON needs the whole walk.
+    let options = WalkOptions::default()
+        .with_concurrency(args.concurrency)
+        .with_read_all(args.read_all);
+    let mut objects = Vec::new();
+    let mut count = 0usize;
+    let mut stream = std::pin::pin!(walk_device_stream(&client, addr, device_id, options));
+    if !args.json {
+        println!("Device {device_id:?}:");
+    }
+    while let Some(item) = stream.next().await {
+        let obj = match item {
+            Ok(obj) => obj,
+            Err(e) => {
+                eprintln!("walk failed: {e}");
+                std::process::exit(1);
             }
+        };
+        count += 1;
+        if args.json {
+            objects.push(obj);
+        } else {
+            let name = obj.object_name.as_deref().unwrap_or("?");
+            let pv = obj
+                .present_value
+                .as_ref()
+                .map(|v| format!("{v:?}"))
+                .unwrap_or_default();
+            println!("  {:?} \"{name}\" = {pv}", obj.object_id);
         }
-        Err(e) => {
-            eprintln!("walk failed: {e}");
-            std::process::exit(1);
-        }
+    }
+
+    if args.json {
+        let walk = DeviceWalkResult {
+            device_id,
+            device_info: read_device_info(&client, addr, device_id).await,
+            objects,
+        };
+        println!("{}", serde_json::to_string_pretty(&walk)?);
+    } else {
+        println!("{count} objects");
     }
     Ok(())
 }
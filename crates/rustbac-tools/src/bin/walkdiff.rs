@@ -0,0 +1,72 @@
+use clap::Parser;
+use rustbac_client::{diff_walks_with_options, DeviceWalkResult, WalkDiffOptions};
+use std::path::PathBuf;
+
+/// Compare two `bacnet-walkdevice --json` snapshots of the same device.
+#[derive(Parser, Debug)]
+#[command(name = "bacnet-walkdiff")]
+struct Args {
+    /// Earlier snapshot.
+    before: PathBuf,
+    /// Later snapshot.
+    after: PathBuf,
+    /// Ignore Present_Value and Status_Flags, reporting configuration changes only.
+    #[arg(long)]
+    config_only: bool,
+    /// Tolerance for comparing floating-point values.
+    #[arg(long, default_value_t = 0.0)]
+    tolerance: f64,
+    #[arg(long)]
+    json: bool,
+}
+
+fn load(path: &PathBuf) -> Result<DeviceWalkResult, Box<dyn std::error::Error>> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    Ok(serde_json::from_str(&text)
+        .map_err(|e| format!("invalid snapshot {}: {e}", path.display()))?)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let before = load(&args.before)?;
+    let after = load(&args.after)?;
+    let options = WalkDiffOptions::default()
+        .with_ignore_live_values(args.config_only)
+        .with_float_tolerance(args.tolerance);
+    let diff = diff_walks_with_options(&before, &after, &options);
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else if diff.is_empty() {
+        println!("no differences");
+    } else {
+        for change in &diff.device_info {
+            println!(
+                "~ device {}: {:?} -> {:?}",
+                change.property_id, change.before, change.after
+            );
+        }
+        for obj in &diff.removed {
+            let name = obj.object_name.as_deref().unwrap_or("?");
+            println!("- {:?} \"{name}\"", obj.object_id);
+        }
+        for obj in &diff.added {
+            let name = obj.object_name.as_deref().unwrap_or("?");
+            println!("+ {:?} \"{name}\"", obj.object_id);
+        }
+        for obj in &diff.changed {
+            for change in &obj.changes {
+                println!(
+                    "~ {:?} {}: {:?} -> {:?}",
+                    obj.object_id, change.property_id, change.before, change.after
+                );
+            }
+        }
+    }
+    // Exit non-zero on drift so the tool can gate scripts, like `diff`.
+    if !diff.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
@@ -1,19 +1,7 @@
 use clap::Parser;
 use rustbac_client::{BacnetClient, BroadcastDistributionEntry};
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
-
-fn parse_bdt_entry(value: &str) -> Result<BroadcastDistributionEntry, String> {
-    let (addr_part, mask_part) = value
-        .split_once('/')
-        .ok_or_else(|| "entry must be in ip:port/mask format".to_string())?;
-    let address: SocketAddrV4 = addr_part
-        .parse()
-        .map_err(|e| format!("invalid entry address '{addr_part}': {e}"))?;
-    let mask: Ipv4Addr = mask_part
-        .parse()
-        .map_err(|e| format!("invalid subnet mask '{mask_part}': {e}"))?;
-    Ok(BroadcastDistributionEntry { address, mask })
-}
+use rustbac_tools::parse_bdt_entry;
+use std::net::SocketAddr;
 
 #[derive(Parser, Debug)]
 #[command(name = "bacnet-write-bdt")]
@@ -1,5 +1,5 @@
 use clap::Parser;
-use rustbac_client::BacnetClient;
+use rustbac_client::{BacnetClient, PointReference};
 use rustbac_core::services::write_property::WritePropertyRequest;
 use rustbac_core::types::{DataValue, ObjectId, ObjectType, PropertyId};
 use rustbac_datalink::DataLinkAddress;
@@ -8,12 +8,17 @@ use std::net::{IpAddr, SocketAddr};
 #[derive(Parser, Debug)]
 #[command(name = "bacnet-writeprop")]
 struct Args {
-    #[arg(long)]
-    ip: IpAddr,
+    #[arg(long, required_unless_present = "point")]
+    ip: Option<IpAddr>,
     #[arg(long, default_value_t = 47808)]
     port: u16,
-    #[arg(long)]
-    instance: u32,
+    /// Analog Output instance whose Present_Value is written at priority 8.
+    #[arg(long, required_unless_present = "point")]
+    instance: Option<u32>,
+    /// Property to write instead, as `address/object-type,instance/property[index]@priority`
+    /// (e.g. `192.168.1.20/analog-value,3/present-value@10`).
+    #[arg(long, conflicts_with_all = ["ip", "instance"])]
+    point: Option<PointReference>,
     #[arg(long)]
     value: f32,
     #[arg(long)]
@@ -30,17 +35,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(bbmd) => BacnetClient::new_foreign(bbmd, args.foreign_ttl).await?,
         None => BacnetClient::new().await?,
     };
-    let addr = DataLinkAddress::Ip((args.ip, args.port).into());
+    let point = match args.point {
+        Some(point) => point,
+        None => PointReference {
+            address: DataLinkAddress::Ip((args.ip.expect("required by clap"), args.port).into()),
+            object_id: ObjectId::new(
+                ObjectType::AnalogOutput,
+                args.instance.expect("required by clap"),
+            ),
+            property_id: PropertyId::PresentValue,
+            array_index: None,
+            priority: Some(8),
+        },
+    };
 
     let req = WritePropertyRequest {
-        object_id: ObjectId::new(ObjectType::AnalogOutput, args.instance),
-        property_id: PropertyId::PresentValue,
+        object_id: point.object_id,
+        property_id: point.property_id,
+        array_index: point.array_index,
         value: DataValue::Real(args.value),
-        priority: Some(8),
+        priority: point.priority,
         ..Default::default()
     };
 
-    client.write_property(addr, req).await?;
+    client.write_property(point.address, req).await?;
     println!("write request sent");
     Ok(())
 }
@@ -1,4 +1,5 @@
 use clap::Parser;
+use rustbac_client::point_ref::{parse_object_type, parse_property_id};
 use rustbac_client::BacnetClient;
 use rustbac_core::services::write_property_multiple::PropertyWriteSpec;
 use rustbac_core::types::{DataValue, ObjectId, ObjectType, PropertyId};
@@ -6,120 +7,6 @@ use rustbac_datalink::DataLinkAddress;
 use std::collections::HashMap;
 use std::net::{IpAddr, SocketAddr};
 
-/// Parse an ObjectType from a name like "analog-input" or a numeric string like "1".
-fn parse_object_type(s: &str) -> Result<ObjectType, String> {
-    // Try numeric first
-    if let Ok(n) = s.parse::<u16>() {
-        return Ok(ObjectType::from_u16(n));
-    }
-    // Try name (case-insensitive, accept kebab-case or PascalCase)
-    let normalized = s.to_ascii_lowercase().replace(['-', '_'], "");
-    let ot = match normalized.as_str() {
-        "analoginput" => ObjectType::AnalogInput,
-        "analogoutput" => ObjectType::AnalogOutput,
-        "analogvalue" => ObjectType::AnalogValue,
-        "binaryinput" => ObjectType::BinaryInput,
-        "binaryoutput" => ObjectType::BinaryOutput,
-        "binaryvalue" => ObjectType::BinaryValue,
-        "calendar" => ObjectType::Calendar,
-        "command" => ObjectType::Command,
-        "device" => ObjectType::Device,
-        "eventenrollment" => ObjectType::EventEnrollment,
-        "file" => ObjectType::File,
-        "group" => ObjectType::Group,
-        "loop" => ObjectType::Loop,
-        "multistateinput" => ObjectType::MultiStateInput,
-        "multistateoutput" => ObjectType::MultiStateOutput,
-        "notificationclass" => ObjectType::NotificationClass,
-        "program" => ObjectType::Program,
-        "schedule" => ObjectType::Schedule,
-        "averaging" => ObjectType::Averaging,
-        "multistatevalue" => ObjectType::MultiStateValue,
-        "trendlog" => ObjectType::TrendLog,
-        "lifesafetypoint" => ObjectType::LifeSafetyPoint,
-        "lifesafetyzone" => ObjectType::LifeSafetyZone,
-        "accumulator" => ObjectType::Accumulator,
-        "pulseconverter" => ObjectType::PulseConverter,
-        "eventlog" => ObjectType::EventLog,
-        "globalgroup" => ObjectType::GlobalGroup,
-        "trendlogmultiple" => ObjectType::TrendLogMultiple,
-        "structuredview" => ObjectType::StructuredView,
-        "accessdoor" => ObjectType::AccessDoor,
-        _ => return Err(format!("unknown object type: {s:?}")),
-    };
-    Ok(ot)
-}
-
-/// Parse a PropertyId from a name like "present-value" or a numeric string like "85".
-fn parse_property_id(s: &str) -> Result<PropertyId, String> {
-    if let Ok(n) = s.parse::<u32>() {
-        return Ok(PropertyId::from_u32(n));
-    }
-    let normalized = s.to_ascii_lowercase().replace(['-', '_'], "");
-    let pid = match normalized.as_str() {
-        "ackedtransitions" => PropertyId::AckedTransitions,
-        "activetext" => PropertyId::ActiveText,
-        "apdutimeout" => PropertyId::ApduTimeout,
-        "applicationsoftwareversion" => PropertyId::ApplicationSoftwareVersion,
-        "buffersize" => PropertyId::BufferSize,
-        "covincrement" => PropertyId::CovIncrement,
-        "databaserevision" => PropertyId::DatabaseRevision,
-        "datelist" => PropertyId::DateList,
-        "deadband" => PropertyId::Deadband,
-        "description" => PropertyId::Description,
-        "effectiveperiod" => PropertyId::EffectivePeriod,
-        "enable" => PropertyId::Enable,
-        "eventenable" => PropertyId::EventEnable,
-        "eventstate" => PropertyId::EventState,
-        "eventtimestamps" => PropertyId::EventTimeStamps,
-        "exceptionschedule" => PropertyId::ExceptionSchedule,
-        "firmwarerevision" => PropertyId::FirmwareRevision,
-        "highlimit" => PropertyId::HighLimit,
-        "inactivetext" => PropertyId::InactiveText,
-        "limitenable" => PropertyId::LimitEnable,
-        "listofobjectpropertyreferences" => PropertyId::ListOfObjectPropertyReferences,
-        "logbuffer" => PropertyId::LogBuffer,
-        "logdeviceobjectproperty" => PropertyId::LogDeviceObjectProperty,
-        "loginterval" => PropertyId::LogInterval,
-        "lowlimit" => PropertyId::LowLimit,
-        "maxapdulengthaccepted" => PropertyId::MaxApduLengthAccepted,
-        "maxpresvalue" => PropertyId::MaxPresValue,
-        "minpresvalue" => PropertyId::MinPresValue,
-        "modelname" => PropertyId::ModelName,
-        "notificationclass" => PropertyId::NotificationClass,
-        "notifytype" => PropertyId::NotifyType,
-        "numberofapduretries" => PropertyId::NumberOfApduRetries,
-        "objectidentifier" => PropertyId::ObjectIdentifier,
-        "objectlist" => PropertyId::ObjectList,
-        "objectname" => PropertyId::ObjectName,
-        "objecttype" => PropertyId::ObjectType,
-        "outofservice" => PropertyId::OutOfService,
-        "presentvalue" => PropertyId::PresentValue,
-        "priorityarray" => PropertyId::PriorityArray,
-        "protocolrevision" => PropertyId::ProtocolRevision,
-        "protocolversion" => PropertyId::ProtocolVersion,
-        "recipientlist" => PropertyId::RecipientList,
-        "recordcount" => PropertyId::RecordCount,
-        "reliability" => PropertyId::Reliability,
-        "relinquishdefault" => PropertyId::RelinquishDefault,
-        "resolution" => PropertyId::Resolution,
-        "scheduledefault" => PropertyId::ScheduleDefault,
-        "segmentationsupported" => PropertyId::SegmentationSupported,
-        "starttime" => PropertyId::StartTime,
-        "statusflags" => PropertyId::StatusFlags,
-        "stoptime" => PropertyId::StopTime,
-        "systemstatus" => PropertyId::SystemStatus,
-        "timedelay" => PropertyId::TimeDelay,
-        "totalrecordcount" => PropertyId::TotalRecordCount,
-        "units" => PropertyId::Units,
-        "updateinterval" => PropertyId::UpdateInterval,
-        "vendorname" => PropertyId::VendorName,
-        "weeklyschedule" => PropertyId::WeeklySchedule,
-        _ => return Err(format!("unknown property id: {s:?}")),
-    };
-    Ok(pid)
-}
-
 /// Auto-detect or explicitly type a DataValue from a string.
 ///
 /// With `--type float|unsigned|boolean|string` the value is coerced to that
@@ -198,11 +85,11 @@ fn parse_write_arg(parts: &[String]) -> Result<WriteSpec, String> {
         .rsplit_once(':')
         .ok_or_else(|| format!("expected OBJECT_TYPE:INSTANCE, got {obj_str:?}"))?;
 
-    let object_type = parse_object_type(type_part)?;
+    let object_type = parse_object_type(type_part).map_err(|e| e.to_string())?;
     let instance: u32 = inst_part
         .parse()
         .map_err(|_| format!("expected numeric instance, got {inst_part:?}"))?;
-    let property = parse_property_id(prop_str)?;
+    let property = parse_property_id(prop_str).map_err(|e| e.to_string())?;
 
     Ok(WriteSpec {
         object_type,
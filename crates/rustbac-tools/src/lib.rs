@@ -1,5 +1,7 @@
 use clap::ValueEnum;
+use rustbac_client::BroadcastDistributionEntry;
 use rustbac_core::types::ObjectType;
+use std::net::{Ipv4Addr, SocketAddrV4};
 
 /// CLI-friendly enum for selecting BACnet object types.
 ///
@@ -34,8 +36,43 @@ pub enum ObjectTypeArg {
     EventLog,
     GlobalGroup,
     TrendLogMultiple,
+    LoadControl,
     StructuredView,
     AccessDoor,
+    Timer,
+    AccessCredential,
+    AccessPoint,
+    AccessRights,
+    AccessUser,
+    AccessZone,
+    CredentialDataInput,
+    NetworkSecurity,
+    BitstringValue,
+    CharacterstringValue,
+    DatePatternValue,
+    DateValue,
+    DatetimePatternValue,
+    DatetimeValue,
+    IntegerValue,
+    LargeAnalogValue,
+    OctetstringValue,
+    PositiveIntegerValue,
+    TimePatternValue,
+    TimeValue,
+    NotificationForwarder,
+    AlertEnrollment,
+    Channel,
+    LightingOutput,
+    BinaryLightingOutput,
+    NetworkPort,
+    ElevatorGroup,
+    Escalator,
+    Lift,
+    Staging,
+    AuditLog,
+    AuditReporter,
+    Color,
+    ColorTemperature,
 }
 
 impl ObjectTypeArg {
@@ -70,8 +107,58 @@ impl ObjectTypeArg {
             Self::EventLog => ObjectType::EventLog,
             Self::GlobalGroup => ObjectType::GlobalGroup,
             Self::TrendLogMultiple => ObjectType::TrendLogMultiple,
+            Self::LoadControl => ObjectType::LoadControl,
             Self::StructuredView => ObjectType::StructuredView,
             Self::AccessDoor => ObjectType::AccessDoor,
+            Self::Timer => ObjectType::Timer,
+            Self::AccessCredential => ObjectType::AccessCredential,
+            Self::AccessPoint => ObjectType::AccessPoint,
+            Self::AccessRights => ObjectType::AccessRights,
+            Self::AccessUser => ObjectType::AccessUser,
+            Self::AccessZone => ObjectType::AccessZone,
+            Self::CredentialDataInput => ObjectType::CredentialDataInput,
+            Self::NetworkSecurity => ObjectType::NetworkSecurity,
+            Self::BitstringValue => ObjectType::BitstringValue,
+            Self::CharacterstringValue => ObjectType::CharacterstringValue,
+            Self::DatePatternValue => ObjectType::DatePatternValue,
+            Self::DateValue => ObjectType::DateValue,
+            Self::DatetimePatternValue => ObjectType::DatetimePatternValue,
+            Self::DatetimeValue => ObjectType::DatetimeValue,
+            Self::IntegerValue => ObjectType::IntegerValue,
+            Self::LargeAnalogValue => ObjectType::LargeAnalogValue,
+            Self::OctetstringValue => ObjectType::OctetstringValue,
+            Self::PositiveIntegerValue => ObjectType::PositiveIntegerValue,
+            Self::TimePatternValue => ObjectType::TimePatternValue,
+            Self::TimeValue => ObjectType::TimeValue,
+            Self::NotificationForwarder => ObjectType::NotificationForwarder,
+            Self::AlertEnrollment => ObjectType::AlertEnrollment,
+            Self::Channel => ObjectType::Channel,
+            Self::LightingOutput => ObjectType::LightingOutput,
+            Self::BinaryLightingOutput => ObjectType::BinaryLightingOutput,
+            Self::NetworkPort => ObjectType::NetworkPort,
+            Self::ElevatorGroup => ObjectType::ElevatorGroup,
+            Self::Escalator => ObjectType::Escalator,
+            Self::Lift => ObjectType::Lift,
+            Self::Staging => ObjectType::Staging,
+            Self::AuditLog => ObjectType::AuditLog,
+            Self::AuditReporter => ObjectType::AuditReporter,
+            Self::Color => ObjectType::Color,
+            Self::ColorTemperature => ObjectType::ColorTemperature,
         }
     }
 }
+
+/// Parses a Broadcast Distribution Table entry given as `ip:port/mask`
+/// (e.g. `192.168.1.10:47808/255.255.255.255`).
+pub fn parse_bdt_entry(value: &str) -> Result<BroadcastDistributionEntry, String> {
+    let (addr_part, mask_part) = value
+        .split_once('/')
+        .ok_or_else(|| "entry must be in ip:port/mask format".to_string())?;
+    let address: SocketAddrV4 = addr_part
+        .parse()
+        .map_err(|e| format!("invalid entry address '{addr_part}': {e}"))?;
+    let mask: Ipv4Addr = mask_part
+        .parse()
+        .map_err(|e| format!("invalid subnet mask '{mask_part}': {e}"))?;
+    Ok(BroadcastDistributionEntry { address, mask })
+}
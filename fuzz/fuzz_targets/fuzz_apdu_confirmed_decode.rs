@@ -1,6 +1,10 @@
 #![no_main]
 use libfuzzer_sys::fuzz_target;
-use rustbac_core::apdu::confirmed::{AbortPdu, ComplexAckHeader, ConfirmedRequestHeader};
+use rustbac_core::apdu::confirmed::{
+    AbortPdu, BacnetError, ComplexAckHeader, ConfirmedRequestHeader, RejectPdu, SegmentAck,
+    SimpleAck,
+};
+use rustbac_core::apdu::UnconfirmedRequestHeader;
 use rustbac_core::encoding::reader::Reader;
 
 fuzz_target!(|data: &[u8]| {
@@ -9,14 +13,39 @@ fuzz_target!(|data: &[u8]| {
         let mut r = Reader::new(data);
         let _ = ConfirmedRequestHeader::decode(&mut r);
     }
+    // Fuzz UnconfirmedRequestHeader::decode
+    {
+        let mut r = Reader::new(data);
+        let _ = UnconfirmedRequestHeader::decode(&mut r);
+    }
     // Fuzz ComplexAckHeader::decode
     {
         let mut r = Reader::new(data);
         let _ = ComplexAckHeader::decode(&mut r);
     }
+    // Fuzz SimpleAck::decode
+    {
+        let mut r = Reader::new(data);
+        let _ = SimpleAck::decode(&mut r);
+    }
+    // Fuzz BacnetError::decode
+    {
+        let mut r = Reader::new(data);
+        let _ = BacnetError::decode(&mut r);
+    }
+    // Fuzz RejectPdu::decode
+    {
+        let mut r = Reader::new(data);
+        let _ = RejectPdu::decode(&mut r);
+    }
     // Fuzz AbortPdu::decode
     {
         let mut r = Reader::new(data);
         let _ = AbortPdu::decode(&mut r);
     }
+    // Fuzz SegmentAck::decode
+    {
+        let mut r = Reader::new(data);
+        let _ = SegmentAck::decode(&mut r);
+    }
 });
@@ -2,9 +2,14 @@
 use libfuzzer_sys::fuzz_target;
 use rustbac_core::encoding::reader::Reader;
 use rustbac_core::services::{
-    alarm_summary::GetAlarmSummaryAck, cov_notification::CovNotificationRequest,
-    event_notification::EventNotificationRequest, i_am::IAmRequest, read_property::ReadPropertyAck,
-    read_property_multiple::ReadPropertyMultipleAck,
+    alarm_summary::GetAlarmSummaryAck, atomic_read_file::AtomicReadFileAck,
+    atomic_write_file::AtomicWriteFileAck, cov_notification::CovNotificationRequest,
+    enrollment_summary::GetEnrollmentSummaryAck, event_information::GetEventInformationAck,
+    event_notification::EventNotificationRequest, i_am::IAmRequest,
+    object_management::CreateObjectAck, private_transfer::ConfirmedPrivateTransferAck,
+    read_property::ReadPropertyAck, read_property_multiple::ReadPropertyMultipleAck,
+    read_range::ReadRangeAck, time_synchronization::TimeSynchronizationRequest,
+    who_has::IHaveRequest,
 };
 
 fuzz_target!(|data: &[u8]| {
@@ -13,39 +18,54 @@ fuzz_target!(|data: &[u8]| {
     }
 
     // Use the first byte to pick which service decoder to exercise.
-    let selector = data[0] % 6;
-    let payload = &data[1..];
+    let selector = data[0] % 15;
+    let mut r = Reader::new(&data[1..]);
 
     match selector {
         0 => {
-            // ReadPropertyAck::decode_after_header
-            let mut r = Reader::new(payload);
             let _ = ReadPropertyAck::decode_after_header(&mut r);
         }
         1 => {
-            // ReadPropertyMultipleAck::decode_after_header
-            let mut r = Reader::new(payload);
             let _ = ReadPropertyMultipleAck::decode_after_header(&mut r);
         }
         2 => {
-            // CovNotificationRequest::decode_after_header
-            let mut r = Reader::new(payload);
             let _ = CovNotificationRequest::decode_after_header(&mut r);
         }
         3 => {
-            // EventNotificationRequest::decode_after_header
-            let mut r = Reader::new(payload);
             let _ = EventNotificationRequest::decode_after_header(&mut r);
         }
         4 => {
-            // IAmRequest::decode_after_header
-            let mut r = Reader::new(payload);
             let _ = IAmRequest::decode_after_header(&mut r);
         }
-        _ => {
-            // GetAlarmSummaryAck::decode_after_header
-            let mut r = Reader::new(payload);
+        5 => {
             let _ = GetAlarmSummaryAck::decode_after_header(&mut r);
         }
+        6 => {
+            let _ = ReadRangeAck::decode_after_header(&mut r);
+        }
+        7 => {
+            let _ = IHaveRequest::decode_after_header(&mut r);
+        }
+        8 => {
+            let _ = GetEnrollmentSummaryAck::decode_after_header(&mut r);
+        }
+        9 => {
+            let _ = GetEventInformationAck::decode_after_header(&mut r);
+        }
+        10 => {
+            let _ = AtomicReadFileAck::decode_after_header(&mut r);
+        }
+        11 => {
+            let _ = AtomicWriteFileAck::decode_after_header(&mut r);
+        }
+        12 => {
+            let _ = CreateObjectAck::decode_after_header(&mut r);
+        }
+        13 => {
+            let _ = TimeSynchronizationRequest::decode_after_header(&mut r);
+        }
+        _ => {
+            let _ = ConfirmedPrivateTransferAck::decode(&mut r);
+        }
     }
 });
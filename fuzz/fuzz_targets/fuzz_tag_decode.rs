@@ -0,0 +1,17 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use rustbac_core::encoding::{reader::Reader, tag::Tag};
+use rustbac_core::services::value_codec::decode_application_data_value;
+
+fuzz_target!(|data: &[u8]| {
+    // Fuzz Tag::decode over the whole buffer
+    {
+        let mut r = Reader::new(data);
+        while Tag::decode(&mut r).is_ok() {}
+    }
+    // Fuzz decode_application_data_value (including nested constructed values)
+    {
+        let mut r = Reader::new(data);
+        while r.remaining() > 0 && decode_application_data_value(&mut r).is_ok() {}
+    }
+});
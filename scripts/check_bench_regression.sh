@@ -0,0 +1,36 @@
+#!/usr/bin/env bash
+# Fails when any criterion benchmark got slower than THRESHOLD percent (default 15)
+# in the last `cargo bench` run compared with its saved baseline.
+#
+#   cargo bench -p rustbac-core --bench codec -- --save-baseline base   # on the base commit
+#   cargo bench -p rustbac-core --bench codec -- --baseline-lenient base
+#   scripts/check_bench_regression.sh 15
+set -euo pipefail
+
+ROOT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")"/.. && pwd)"
+CRITERION_DIR="${CRITERION_DIR:-$ROOT_DIR/target/criterion}"
+THRESHOLD="${1:-15}"
+
+failed=0
+checked=0
+while IFS= read -r estimates; do
+  bench="${estimates#"$CRITERION_DIR"/}"
+  bench="${bench%/change/estimates.json}"
+  change="$(jq -r '.mean.point_estimate * 100' "$estimates")"
+  checked=$((checked + 1))
+  if awk -v c="$change" -v t="$THRESHOLD" 'BEGIN { exit !(c > t) }'; then
+    printf 'REGRESSION %-32s %+7.2f%%\n' "$bench" "$change"
+    failed=1
+  else
+    printf 'ok         %-32s %+7.2f%%\n' "$bench" "$change"
+  fi
+done < <(find "$CRITERION_DIR" -path '*/change/estimates.json' | sort)
+
+if [ "$checked" -eq 0 ]; then
+  echo "no benchmark comparisons found under $CRITERION_DIR" >&2
+  exit 1
+fi
+if [ "$failed" -ne 0 ]; then
+  echo "benchmarks regressed by more than ${THRESHOLD}%" >&2
+  exit 1
+fi
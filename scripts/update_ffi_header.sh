@@ -0,0 +1,8 @@
+#!/usr/bin/env bash
+# Regenerates crates/rustbac-ffi/include/rustbac.h from the C ABI in
+# crates/rustbac-ffi/src/lib.rs. `cargo test` fails while the committed header is stale.
+set -euo pipefail
+
+ROOT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")"/.. && pwd)"
+cd "$ROOT_DIR"
+RUSTBAC_UPDATE_HEADER=1 cargo test -p rustbac-ffi --lib committed_header_matches_the_generated_one